@@ -0,0 +1,648 @@
+//! Strategy backtesting against recorded sessions
+//!
+//! `Backtest` replays a recording (see `recording`) through the decision
+//! path `snipe start` takes for new tokens - `TokenFilter`, `AdaptiveFilter`
+//! and `StrategyEngine` - and the position monitor's exit rules (the start
+//! profile, exit styles and early weakness included), with orders filled by a
+//! `SimulatedTrader`. Positions live in a `PositionManager` without a
+//! persistence path, and the filter cache is not snapshotted, so nothing
+//! touches positions.json, trades.json or the live caches.
+//!
+//! Not replayed: signal providers and enrichment that need the network
+//! (only metadata scoring runs), the honeypot round trip, kill-switches,
+//! cooldowns, scale-in, the momentum gate and trade-signal entries.
+//! Time-based engine state (loss-streak cooldowns, chain health) follows the
+//! wall clock, not the recording.
+
+pub mod recording;
+pub mod report;
+
+pub use recording::{read_events, RecordedEvent, SessionEvent, SessionRecorder};
+pub use report::{BacktestReport, Breakdown};
+
+use chrono::{DateTime, Utc};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, warn};
+
+use crate::config::Config;
+use crate::error::Result;
+use crate::filter::honeypot::curve_from_event;
+use crate::filter::token_filter::{FilterResult, TokenFilter};
+use crate::filter::{AdaptiveFilter, MetadataSignalProvider, Recommendation, SignalContext};
+use crate::position::auto_sell::{
+    check_exit, check_styled_exit, ExitAdvisor, ExitLayer, ExitSignal, StrategyExits,
+};
+use crate::position::early_weakness::{check_early_weakness, FlowWatch};
+use crate::position::history::{ExitDetails, TradeExitReason};
+use crate::position::manager::{EntryType, Position, PositionManager};
+use crate::position::PositionClose;
+use crate::strategy::engine::{StrategyEngine, TokenAnalysisContext};
+use crate::strategy::{ExitAction, RegimeKind, TradingAction, TradingStrategy};
+use crate::stream::decoder::TokenCreatedEvent;
+use crate::stream::pumpportal::{NewTokenEvent, TradeEvent};
+use crate::trading::simulated::{SimFill, SimSide};
+use crate::trading::{SimulatedTrader, Trader};
+
+/// A buy waiting for its simulated fill
+struct PendingBuy {
+    token: NewTokenEvent,
+    size_sol: f64,
+    entry_type: EntryType,
+    regime: Option<(RegimeKind, TradingStrategy)>,
+}
+
+/// Replays recorded sessions through the entry and exit logic
+pub struct Backtest {
+    config: Config,
+    token_filter: TokenFilter,
+    adaptive_filter: Option<AdaptiveFilter>,
+    engine: Option<Arc<RwLock<StrategyEngine>>>,
+    exits: Option<StrategyExits>,
+    flow: Option<FlowWatch>,
+    positions: PositionManager,
+    closes: broadcast::Receiver<PositionClose>,
+    trader: SimulatedTrader,
+    /// signature -> buy
+    buys: HashMap<String, PendingBuy>,
+    /// signature -> exit being executed
+    sells: HashMap<String, ExitSignal>,
+    /// Mints with a sell in flight
+    selling: HashSet<String>,
+    clock: DateTime<Utc>,
+    /// First recorded time and the instant it maps to, for rolling windows
+    epoch: Option<(DateTime<Utc>, Instant)>,
+    report: BacktestReport,
+}
+
+impl Backtest {
+    pub async fn new(config: &Config) -> Result<Self> {
+        let mut config = config.clone();
+        // Never write the live filter cache snapshot
+        config.adaptive_filter.cache.persist_path = None;
+
+        let token_filter = TokenFilter::new(config.filters.clone())?;
+        let adaptive_filter = if config.adaptive_filter.enabled {
+            let mut filter = AdaptiveFilter::new(config.adaptive_filter.clone()).await?;
+            filter.register_provider(Arc::new(MetadataSignalProvider::new()));
+            Some(filter)
+        } else {
+            None
+        };
+        let engine = if config.strategy.enabled {
+            let mut engine = StrategyEngine::new(config.strategy.clone());
+            if let Some(ref filter) = adaptive_filter {
+                engine.set_filter_cache(filter.cache().clone());
+            }
+            Some(Arc::new(RwLock::new(engine)))
+        } else {
+            None
+        };
+        let exits = engine.clone().map(StrategyExits::new);
+        let flow = config
+            .auto_sell
+            .early_weakness
+            .enabled
+            .then(|| FlowWatch::new(config.auto_sell.early_weakness.clone()));
+
+        let positions = PositionManager::new(config.safety.clone(), None)
+            .with_exit_thresholds(config.auto_sell.entry_types.clone());
+        let closes = positions.subscribe_closes();
+        let trader = SimulatedTrader::new(
+            config.backtest.latency_ms,
+            config.backtest.slippage_pct,
+            config
+                .pumpportal
+                .platform_fee_pct(config.backtest.local_api),
+        );
+
+        Ok(Self {
+            config,
+            token_filter,
+            adaptive_filter,
+            engine,
+            exits,
+            flow,
+            positions,
+            closes,
+            trader,
+            buys: HashMap::new(),
+            sells: HashMap::new(),
+            selling: HashSet::new(),
+            clock: Utc::now(),
+            epoch: None,
+            report: BacktestReport::default(),
+        })
+    }
+
+    /// Replay `events` (in time order) and report what was traded
+    pub async fn run(mut self, events: Vec<RecordedEvent>) -> BacktestReport {
+        for recorded in events {
+            self.report.events += 1;
+            self.clock = recorded.time;
+            self.epoch.get_or_insert((recorded.time, Instant::now()));
+            self.trader.advance(self.clock);
+            self.process_fills().await;
+            match recorded.event {
+                SessionEvent::NewToken(token) => self.on_new_token(token).await,
+                SessionEvent::Trade(trade) => self.on_trade(trade).await,
+            }
+        }
+
+        // Let orders still in flight land at the last recorded curves
+        if self.trader.pending_orders() > 0 {
+            self.clock += chrono::Duration::milliseconds(self.config.backtest.latency_ms as i64);
+            self.trader.advance(self.clock);
+            self.process_fills().await;
+        }
+
+        let open = self.positions.get_all_positions().await;
+        self.report.open_positions = open.len();
+        self.report.unrealized_pnl_sol = open.iter().map(|p| p.net_unrealized_pnl()).sum();
+        self.report
+    }
+
+    async fn on_new_token(&mut self, token: NewTokenEvent) {
+        self.report.tokens += 1;
+        let curve = curve_from_event(
+            token.v_sol_in_bonding_curve as f64,
+            token.v_tokens_in_bonding_curve as f64,
+        );
+        self.trader.set_curve(&token.mint, curve.clone());
+
+        if let Some(reason) = self.filter_reason(&token, curve.virtual_token_reserves) {
+            debug!("{} filtered: {}", token.symbol, reason);
+            self.report.skip("filtered");
+            return;
+        }
+        if self.positions.is_daily_loss_limit_reached().await {
+            self.report.skip("daily_loss_limit");
+            return;
+        }
+        if let Some(ref engine) = self.engine {
+            let engine = engine.read().await;
+            if engine.should_pause_trading().await {
+                self.report.skip("paused");
+                return;
+            }
+            if !engine.get_portfolio_state().await.can_open_new {
+                self.report.skip("portfolio");
+                return;
+            }
+        }
+
+        let (multiplier, recommendation) = match self.adaptive_filter {
+            Some(ref filter) => {
+                let context = SignalContext::from_new_token(
+                    token.mint.clone(),
+                    token.name.clone(),
+                    token.symbol.clone(),
+                    token.uri.clone(),
+                    token.trader_public_key.clone(),
+                    token.bonding_curve_key.clone(),
+                    token.initial_buy,
+                    token.v_tokens_in_bonding_curve,
+                    token.v_sol_in_bonding_curve,
+                    token.market_cap_sol,
+                );
+                let result = filter.score_fast_cached(&context).await;
+                match result.recommendation {
+                    Recommendation::Avoid => {
+                        self.report.skip("avoid");
+                        return;
+                    }
+                    Recommendation::Observe => {
+                        self.report.skip("observe");
+                        return;
+                    }
+                    _ => (result.position_size_multiplier, result.recommendation),
+                }
+            }
+            None => (1.0, Recommendation::Opportunity),
+        };
+
+        let default_size = self.config.trading.buy_amount_sol * multiplier;
+        let (size_sol, regime) = match self.engine {
+            Some(ref engine) => {
+                let ctx = analysis_context(&token, multiplier);
+                let eval = engine.write().await.evaluate_entry(&ctx).await;
+                let regime = Some((
+                    eval.regime.regime.kind(),
+                    eval.explanation.selected_strategy,
+                ));
+                match eval.decision.action {
+                    TradingAction::Enter { size_sol, .. } => (size_sol, regime),
+                    TradingAction::FatalReject { .. } | TradingAction::Skip { .. } => {
+                        self.report.skip("strategy");
+                        return;
+                    }
+                    _ => (default_size, regime),
+                }
+            }
+            None => (default_size, None),
+        };
+
+        if !self.reserve_slot(size_sol).await {
+            self.report.skip("no_slot");
+            return;
+        }
+
+        let base_bps = self.config.trading.slippage_bps;
+        let slippage_pct = match self.engine {
+            Some(ref engine) => engine.read().await.slippage_bps(base_bps).await / 100,
+            None => base_bps / 100,
+        };
+        let priority_fee = self.config.trading.priority_fee_lamports as f64 / 1e9;
+        let sent = self
+            .trader
+            .buy(&token.mint, size_sol, slippage_pct, priority_fee)
+            .await;
+        match sent {
+            Ok(signature) => {
+                self.report.buys += 1;
+                self.buys.insert(
+                    signature,
+                    PendingBuy {
+                        size_sol,
+                        entry_type: EntryType::from_recommendation(recommendation),
+                        token,
+                        regime,
+                    },
+                );
+            }
+            Err(e) => {
+                debug!("Buy of {} not sent: {}", token.symbol, e);
+                self.report.failed_buys += 1;
+            }
+        }
+    }
+
+    async fn on_trade(&mut self, trade: TradeEvent) {
+        self.trader.set_curve(
+            &trade.mint,
+            curve_from_event(
+                trade.v_sol_in_bonding_curve,
+                trade.v_tokens_in_bonding_curve,
+            ),
+        );
+        // pump.fun supply is 1B tokens
+        let price = trade.market_cap_sol / 1_000_000_000.0;
+        if price <= 0.0 {
+            return;
+        }
+        self.positions.update_price(&trade.mint, price).await;
+        if let Some(ref flow) = self.flow {
+            flow.record_trade_at(&trade, self.instant());
+        }
+        if !self.selling.contains(&trade.mint) {
+            self.check_exit(&trade.mint, price).await;
+        }
+    }
+
+    /// The replay clock as an `Instant`
+    fn instant(&self) -> Instant {
+        let (start, instant) = self.epoch.unwrap_or((self.clock, Instant::now()));
+        instant + (self.clock - start).to_std().unwrap_or_default()
+    }
+
+    /// Start or stop following order flow as positions open and close
+    async fn sync_flow(&self) {
+        if let Some(ref flow) = self.flow {
+            flow.sync_positions(&self.positions.get_all_positions().await);
+        }
+    }
+
+    /// Same name / liquidity / market cap / curve checks as `snipe start`
+    fn filter_reason(&self, token: &NewTokenEvent, virtual_token_reserves: u64) -> Option<String> {
+        if !self.config.filters.enabled {
+            return None;
+        }
+        let pubkey = |s: &str| Pubkey::from_str(s).unwrap_or_default();
+        let event = TokenCreatedEvent {
+            signature: token.signature.clone(),
+            slot: 0,
+            mint: pubkey(&token.mint),
+            name: token.name.clone(),
+            symbol: token.symbol.clone(),
+            uri: token.uri.clone(),
+            bonding_curve: pubkey(&token.bonding_curve_key),
+            associated_bonding_curve: Default::default(),
+            creator: pubkey(&token.trader_public_key),
+            timestamp: self.clock,
+        };
+        let curve_pct = crate::pump::price::curve_progress_pct(virtual_token_reserves);
+        [
+            self.token_filter.filter(&event),
+            self.token_filter.check_liquidity(token.market_cap_sol),
+            self.token_filter.check_market_cap(token.market_cap_sol),
+            self.token_filter.check_bonding_curve(curve_pct),
+        ]
+        .into_iter()
+        .find_map(|result| match result {
+            FilterResult::Filtered(reason) => Some(reason.to_string()),
+            FilterResult::Pass => None,
+        })
+    }
+
+    /// Position limits, counting buys still in flight
+    async fn reserve_slot(&self, size_sol: f64) -> bool {
+        let held = self.positions.get_all_positions().await;
+        if held.len() + self.buys.len() >= self.config.safety.max_concurrent_positions {
+            return false;
+        }
+        let in_flight_sol: f64 = self.buys.values().map(|buy| buy.size_sol).sum();
+        match self
+            .positions
+            .try_reserve_slot(size_sol + in_flight_sol)
+            .await
+        {
+            Ok(slot) => {
+                slot.release();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    async fn check_exit(&mut self, mint: &str, price: f64) {
+        let Some(position) = self.positions.get_position(mint).await else {
+            return;
+        };
+        let profile = self.config.auto_sell.monitor.start;
+        let hold_time_secs = (self.clock - position.entry_time).num_seconds().max(0) as u64;
+        if hold_time_secs < profile.confirm_wait_secs || position.entry_price <= 0.0 {
+            return;
+        }
+
+        let thresholds = &self.positions.exit_thresholds();
+        let weakness = self.check_early_weakness(&position, price, hold_time_secs);
+        let signal = match (weakness, &position.exit_style, &self.exits) {
+            (Some(signal), _, _) => Some(signal),
+            (None, Some(style), Some(exits)) => {
+                let action = exits.advise(&position, style, price, hold_time_secs).await;
+                if let ExitAction::TightenTrail { style, .. } = &action {
+                    let _ = self.positions.set_exit_style(mint, style.clone()).await;
+                }
+                check_styled_exit(thresholds, &position, price, hold_time_secs, &action)
+            }
+            _ => check_exit(
+                &self.config.auto_sell,
+                &profile,
+                thresholds,
+                &position,
+                price,
+                hold_time_secs,
+            ),
+        };
+        let Some(signal) = signal else {
+            return;
+        };
+
+        let slippage_pct = self.config.trading.slippage_bps / 100;
+        let priority_fee = self.config.trading.priority_fee_lamports as f64 / 1e9;
+        let amount = signal.layer.sell_pct();
+        let sent = self
+            .trader
+            .sell(mint, &amount, slippage_pct, priority_fee)
+            .await;
+        match sent {
+            Ok(signature) => {
+                self.selling.insert(mint.to_string());
+                self.sells.insert(signature, signal);
+            }
+            Err(e) => {
+                debug!("Sell of {} not sent: {}", position.symbol, e);
+                self.report.failed_sells += 1;
+            }
+        }
+    }
+
+    fn check_early_weakness(
+        &self,
+        position: &Position,
+        price: f64,
+        hold_time_secs: u64,
+    ) -> Option<ExitSignal> {
+        let flow = self.flow.as_ref()?;
+        let snapshot = flow.snapshot_at(&position.mint, self.instant())?;
+        let pnl_pct = (price - position.entry_price) / position.entry_price * 100.0;
+        let sl_pct = position.effective_stop_loss_pct(&self.positions.exit_thresholds());
+        check_early_weakness(
+            flow.config(),
+            &snapshot,
+            pnl_pct,
+            sl_pct,
+            hold_time_secs,
+            self.config.auto_sell.monitor.start.confirm_wait_secs,
+        )
+        .map(|reason| ExitSignal {
+            layer: ExitLayer::Full,
+            reason,
+        })
+    }
+
+    async fn process_fills(&mut self) {
+        for fill in self.trader.take_fills() {
+            match fill.side {
+                SimSide::Buy => self.on_buy_fill(fill).await,
+                SimSide::Sell => self.on_sell_fill(fill).await,
+            }
+        }
+    }
+
+    async fn on_buy_fill(&mut self, fill: SimFill) {
+        let Some(buy) = self.buys.remove(&fill.signature) else {
+            return;
+        };
+        if let Some(error) = fill.error {
+            debug!("Buy of {} failed: {}", buy.token.symbol, error);
+            self.report.failed_buys += 1;
+            return;
+        }
+
+        let regime = buy.regime.map(|(regime, _)| regime);
+        let exit_style = match self.engine {
+            Some(ref engine) => {
+                engine
+                    .read()
+                    .await
+                    .select_exit_style(buy.entry_type.as_str(), regime)
+                    .await
+            }
+            None => None,
+        };
+        let token = buy.token;
+        let position = Position {
+            mint: token.mint.clone(),
+            name: token.name.clone(),
+            symbol: token.symbol.clone(),
+            bonding_curve: token.bonding_curve_key.clone(),
+            token_amount: fill.tokens,
+            entry_price: fill.price,
+            total_cost_sol: fill.sol,
+            entry_time: self.clock,
+            entry_signature: fill.signature.clone(),
+            entry_type: buy.entry_type,
+            quick_profit_taken: false,
+            second_profit_taken: false,
+            peak_price: fill.price,
+            trough_price: fill.price,
+            current_price: fill.price,
+            kill_switch_triggered: false,
+            kill_switch_reason: None,
+            wallet_pubkey: String::new(),
+            fills: vec![],
+            scale_in: None,
+            exit_override: Default::default(),
+            partial_fill: false,
+            regime,
+            exit_style,
+            exit_levels_hit: vec![],
+            paper: false,
+            privileges: None,
+            exit_liquidity: None,
+            slippage: None,
+            strategy_profile: None,
+            holder_distribution: None,
+            fees: fill.fees,
+        };
+        if let Err(e) = self.positions.open_position(position).await {
+            warn!("Simulated buy of {} not recorded: {}", token.symbol, e);
+            self.report.skip("no_slot");
+            return;
+        }
+
+        if let Some(ref engine) = self.engine {
+            let held = crate::strategy::types::Position {
+                mint: token.mint.clone(),
+                entry_price: fill.price,
+                entry_time: self.clock,
+                size_sol: fill.sol,
+                tokens_held: fill.tokens,
+                strategy: buy
+                    .regime
+                    .map_or(self.config.strategy.default_strategy, |(_, s)| s),
+                exit_style: crate::strategy::types::ExitStyle::default(),
+                highest_price: fill.price,
+                lowest_price: fill.price,
+                exit_levels_hit: vec![],
+                creator: Some(token.trader_public_key.clone()),
+                funding_cluster: None,
+            };
+            engine.write().await.record_entry(held).await;
+        }
+        self.sync_flow().await;
+    }
+
+    async fn on_sell_fill(&mut self, fill: SimFill) {
+        let Some(signal) = self.sells.remove(&fill.signature) else {
+            return;
+        };
+        self.selling.remove(&fill.mint);
+        if let Some(error) = fill.error {
+            // Retried on the next price update
+            debug!("Sell of {} failed: {}", fill.mint, error);
+            self.report.failed_sells += 1;
+            return;
+        }
+        let Some(position) = self.positions.get_position(&fill.mint).await else {
+            return;
+        };
+
+        let marked = match signal.layer {
+            ExitLayer::QuickProfit => self.positions.mark_quick_profit_taken(&fill.mint).await,
+            ExitLayer::SecondProfit => self.positions.mark_second_profit_taken(&fill.mint).await,
+            ExitLayer::Tier { level, .. } => {
+                self.positions.mark_exit_level_hit(&fill.mint, level).await
+            }
+            ExitLayer::Full | ExitLayer::Mirror(_) | ExitLayer::Slice(_) | ExitLayer::Reduce(_) => {
+                Ok(())
+            }
+        };
+        if let Err(e) = marked {
+            warn!("Could not mark exit layer for {}: {}", position.symbol, e);
+        }
+
+        let exit = ExitDetails::new(TradeExitReason::from_description(&signal.reason))
+            .with_signature(&fill.signature)
+            .with_fees(fill.fees);
+        let tokens = fill.tokens.min(position.token_amount);
+        if let Err(e) = self
+            .positions
+            .close_position_with_exit(&fill.mint, tokens, fill.sol, exit)
+            .await
+        {
+            warn!("Simulated sell of {} not recorded: {}", position.symbol, e);
+            return;
+        }
+
+        while let Ok(close) = self.closes.try_recv() {
+            let mut trade = close.trade;
+            trade.exit_time = self.clock;
+            trade.exit_price = fill.price;
+            if let Some(ref engine) = self.engine {
+                let mut engine = engine.write().await;
+                if close.fully_closed {
+                    engine.record_exit(&trade.mint, trade.pnl_sol).await;
+                } else {
+                    engine.record_partial_exit(trade.pnl_sol).await;
+                }
+            }
+            self.report.trades.push(trade);
+        }
+        self.sync_flow().await;
+    }
+}
+
+/// Entry context from the creation event alone (no trades, holders or
+/// creator history yet), as `snipe start` builds it before they arrive
+fn analysis_context(token: &NewTokenEvent, confidence: f64) -> TokenAnalysisContext {
+    use crate::strategy::regime::{CreatorBehavior, OrderFlowAnalysis, TokenDistribution};
+
+    // PumpPortal sends SOL; older recordings may carry lamports
+    let sol_reserves = if token.v_sol_in_bonding_curve < 1000 {
+        token.v_sol_in_bonding_curve as f64
+    } else {
+        token.v_sol_in_bonding_curve as f64 / 1e9
+    };
+    TokenAnalysisContext {
+        mint: token.mint.clone(),
+        creator: token.trader_public_key.clone(),
+        funding_cluster: None,
+        order_flow: OrderFlowAnalysis {
+            organic_score: confidence.max(0.5),
+            wash_trading_score: 0.0,
+            buy_sell_ratio: 1.0,
+            early_sell_pressure: 0.0,
+            burst_detected: false,
+            burst_intensity: 0.0,
+            organic_demand: None,
+        },
+        distribution: TokenDistribution {
+            holder_count: 1,
+            top_holder_pct: 100.0,
+            top_10_holders_pct: 100.0,
+            deployer_holdings_pct: 0.0,
+            sniper_holdings_pct: 0.0,
+            gini_coefficient: 1.0,
+        },
+        creator_behavior: CreatorBehavior {
+            selling_consistently: false,
+            total_sold_pct: 0.0,
+            avg_sell_interval_secs: 0,
+            sell_count: 0,
+        },
+        price_action: crate::strategy::price_action::PriceAction::default(),
+        sol_reserves,
+        token_reserves: token.v_tokens_in_bonding_curve as f64,
+        confidence_score: confidence,
+        round_trip_excess_loss_pct: None,
+        privileges: None,
+        proposals: vec![],
+        equity: None,
+    }
+}
@@ -0,0 +1,141 @@
+//! Recorded stream sessions
+//!
+//! `snipe start --record <file>` appends every new-token and trade event
+//! from the PumpPortal feed to a JSON-lines file, stamped with the time it
+//! arrived; `snipe backtest --events <file>` replays it. Lines are written
+//! by a background task and dropped (with a debug log) if it falls behind.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::error::Result;
+use crate::stream::pumpportal::{NewTokenEvent, TradeEvent};
+
+/// Events buffered before new ones are dropped
+const CHANNEL_CAPACITY: usize = 8192;
+
+/// A stream event as replayed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEvent {
+    NewToken(NewTokenEvent),
+    Trade(TradeEvent),
+}
+
+/// One line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub time: DateTime<Utc>,
+    pub event: SessionEvent,
+}
+
+/// Appends stream events to a recording
+#[derive(Clone)]
+pub struct SessionRecorder {
+    sender: mpsc::Sender<RecordedEvent>,
+}
+
+impl SessionRecorder {
+    /// Open (append to) `path` and start the writer task
+    pub fn spawn(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (sender, mut receiver) = mpsc::channel::<RecordedEvent>(CHANNEL_CAPACITY);
+        let path = path.display().to_string();
+        tokio::spawn(async move {
+            let mut writer = BufWriter::new(file);
+            while let Some(event) = receiver.recv().await {
+                if let Err(e) = write_line(&mut writer, &event) {
+                    warn!(path = %path, error = %e, "Failed to write session recording");
+                }
+                // Flush whenever the backlog is drained
+                if receiver.is_empty() {
+                    let _ = writer.flush();
+                }
+            }
+        });
+        Ok(Self { sender })
+    }
+
+    pub fn record(&self, event: SessionEvent) {
+        let event = RecordedEvent {
+            time: Utc::now(),
+            event,
+        };
+        if self.sender.try_send(event).is_err() {
+            debug!("Session recorder backlogged - event dropped");
+        }
+    }
+}
+
+fn write_line(writer: &mut BufWriter<File>, event: &RecordedEvent) -> Result<()> {
+    serde_json::to_writer(&mut *writer, event)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Read a recording in time order, skipping lines that don't parse
+pub fn read_events(path: &Path) -> Result<Vec<RecordedEvent>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = Vec::new();
+    let mut skipped = 0;
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<RecordedEvent>(&line) {
+            Ok(event) => events.push(event),
+            Err(_) => skipped += 1,
+        }
+    }
+    if skipped > 0 {
+        warn!("Skipped {} unreadable lines in {}", skipped, path.display());
+    }
+    // Stable, so same-time events keep their recorded order
+    events.sort_by_key(|e| e.time);
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let recorder = SessionRecorder::spawn(&path).unwrap();
+        recorder.record(SessionEvent::Trade(TradeEvent {
+            signature: "sig".to_string(),
+            mint: "mint".to_string(),
+            trader_public_key: "trader".to_string(),
+            tx_type: "buy".to_string(),
+            token_amount: 1000.0,
+            sol_amount: 0.5,
+            bonding_curve_key: "curve".to_string(),
+            v_tokens_in_bonding_curve: 1_000_000_000.0,
+            v_sol_in_bonding_curve: 31.0,
+            market_cap_sol: 31.0,
+        }));
+        drop(recorder);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 1);
+        match &events[0].event {
+            SessionEvent::Trade(trade) => {
+                assert_eq!(trade.mint, "mint");
+                assert_eq!(trade.sol_amount, 0.5);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+}
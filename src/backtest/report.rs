@@ -0,0 +1,106 @@
+//! Backtest results
+
+use std::collections::BTreeMap;
+
+use crate::position::history::ClosedTrade;
+
+/// Trades, wins and P&L for one group of closes
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Breakdown {
+    pub trades: usize,
+    pub wins: usize,
+    pub pnl_sol: f64,
+}
+
+impl Breakdown {
+    fn add(&mut self, pnl_sol: f64) {
+        self.trades += 1;
+        if pnl_sol > 0.0 {
+            self.wins += 1;
+        }
+        self.pnl_sol += pnl_sol;
+    }
+
+    /// Wins as % of trades
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64 * 100.0
+        }
+    }
+}
+
+/// What a replay did
+///
+/// Every recorded close (partial exits included) counts as a trade, as in
+/// `snipe stats`.
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    /// Events replayed
+    pub events: usize,
+    /// New tokens seen
+    pub tokens: usize,
+    /// Why tokens weren't bought (filtered, avoid, observe, strategy, no_slot, ...)
+    pub skipped: BTreeMap<String, usize>,
+    /// Buys sent
+    pub buys: usize,
+    /// Buys that failed (slippage, no curve)
+    pub failed_buys: usize,
+    /// Sells that failed and were retried
+    pub failed_sells: usize,
+    /// Closes in the order they happened
+    pub trades: Vec<ClosedTrade>,
+    /// Positions still open when the recording ended
+    pub open_positions: usize,
+    /// Their P&L at the last recorded price
+    pub unrealized_pnl_sol: f64,
+}
+
+impl BacktestReport {
+    pub(crate) fn skip(&mut self, reason: &str) {
+        *self.skipped.entry(reason.to_string()).or_insert(0) += 1;
+    }
+
+    /// All closes
+    pub fn total(&self) -> Breakdown {
+        let mut total = Breakdown::default();
+        for trade in &self.trades {
+            total.add(trade.pnl_sol);
+        }
+        total
+    }
+
+    /// Largest fall of cumulative realized P&L from its running peak (SOL)
+    pub fn max_drawdown_sol(&self) -> f64 {
+        let mut equity = 0.0_f64;
+        let mut peak = 0.0_f64;
+        let mut drawdown = 0.0_f64;
+        for trade in &self.trades {
+            equity += trade.pnl_sol;
+            peak = peak.max(equity);
+            drawdown = drawdown.max(peak - equity);
+        }
+        drawdown
+    }
+
+    pub fn by_entry_type(&self) -> BTreeMap<String, Breakdown> {
+        self.group(|t| t.entry_type.as_str().to_string())
+    }
+
+    pub fn by_regime(&self) -> BTreeMap<String, Breakdown> {
+        self.group(|t| t.regime.map_or("none".to_string(), |r| r.to_string()))
+    }
+
+    pub fn by_exit_reason(&self) -> BTreeMap<String, Breakdown> {
+        self.group(|t| t.exit_reason.to_string())
+    }
+
+    fn group(&self, key: impl Fn(&ClosedTrade) -> String) -> BTreeMap<String, Breakdown> {
+        let mut groups: BTreeMap<String, Breakdown> = BTreeMap::new();
+        for trade in &self.trades {
+            groups.entry(key(trade)).or_default().add(trade.pnl_sol);
+        }
+        groups
+    }
+}
@@ -6,89 +6,39 @@ use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
 use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use tracing::{error, info, warn};
 
-use crate::config::Config;
+use crate::config::{Config, ConfigSource};
+use crate::config_check::ConfigReport;
+use crate::config_reload::{BuyAmount, ConfigReloader, ReloadTargets};
 use crate::filter::{
-    AdaptiveFilter, HeliusClient, KillSwitchDecision, KillSwitchEvaluator, MetadataSignalProvider,
-    Recommendation, SignalContext, SmartMoneySignalProvider, WalletBehaviorSignalProvider,
-    WalletProfiler, WalletProfilerConfig,
+    AdaptiveFilter, BundledDetector, DecisionAction, DiscoverySource, EarlyBuy, EnrichmentPriority,
+    HeliusClient, KillSwitchDecision, KillSwitchEvaluator, Recommendation, SignalContext,
+    WalletDiscovery, WalletProfiler, WalletProfilerConfig,
 };
-use crate::filter::signals::EarlyMomentumSignalProvider;
+use crate::filter::signals::{
+    DistributionSignalProvider, FundingSourceProvider, OrderFlowSignalProvider, ProviderDeps,
+    ProviderRegistry,
+};
+use crate::notify::{DisconnectWatch, Notification, Notifier};
+use crate::position::reconcile::{query_token_balance, spawn_confirmation, ConfirmedTrade};
+use crate::run_state::{RunLock, RunMode, RunState, RUN_LOCK_FILE};
+use crate::scan_history::{ScanDelta, ScanHistory, SCAN_HISTORY_FILE};
 use crate::strategy::engine::StrategyEngine;
-use crate::strategy::types::TradingAction;
+use crate::strategy::tactics::{FrontRunDetector, SniperPiggyback};
+use crate::strategy::types::{DecisionSource, Proposal, ProposedAction, TradingAction};
+use crate::strategy::{
+    CreatorPrivilegeChecker, EquitySnapshot, ExitLiquidity, PositionSizingConfig, SizingMode,
+    SlippageEstimate, SlippageEstimator,
+};
 use crate::stream::pumpportal::{PumpPortalClient, PumpPortalEvent};
 #[cfg(feature = "shredstream")]
 use crate::stream::shredstream::ShredStreamClient;
+use crate::stream::{Delivery, NewTokenDedup};
+use crate::trading::paper::PaperTrader;
 use crate::trading::pumpportal_api::PumpPortalTrader;
-
-/// Query actual token balance for a wallet and mint
-/// Returns the token balance or 0 if not found
-fn query_token_balance(
-    rpc_client: &solana_client::rpc_client::RpcClient,
-    wallet: &Pubkey,
-    mint: &str,
-) -> u64 {
-    use solana_client::rpc_request::TokenAccountsFilter;
-
-    let mint_pubkey = match Pubkey::from_str(mint) {
-        Ok(pk) => pk,
-        Err(_) => return 0,
-    };
-
-    // Try SPL Token program with Mint filter (works for both SPL and Token2022)
-    if let Ok(accounts) =
-        rpc_client.get_token_accounts_by_owner(wallet, TokenAccountsFilter::Mint(mint_pubkey))
-    {
-        for account in &accounts {
-            if let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data {
-                if let Some(info) = parsed.parsed.get("info") {
-                    if let Some(token_amount) = info.get("tokenAmount") {
-                        if let Some(amount_str) = token_amount.get("amount") {
-                            if let Some(amount) = amount_str.as_str() {
-                                let bal = amount.parse::<u64>().unwrap_or(0);
-                                if bal > 0 {
-                                    return bal;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Fallback: Try Token2022 program explicitly (pump.fun tokens use this)
-    let token2022_program =
-        Pubkey::from_str("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb").unwrap();
-    if let Ok(accounts) = rpc_client
-        .get_token_accounts_by_owner(wallet, TokenAccountsFilter::ProgramId(token2022_program))
-    {
-        for account in &accounts {
-            if let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data {
-                if let Some(info) = parsed.parsed.get("info") {
-                    if let Some(account_mint) = info.get("mint") {
-                        if account_mint.as_str() == Some(mint) {
-                            if let Some(token_amount) = info.get("tokenAmount") {
-                                if let Some(amount_str) = token_amount.get("amount") {
-                                    if let Some(amount) = amount_str.as_str() {
-                                        let bal = amount.parse::<u64>().unwrap_or(0);
-                                        if bal > 0 {
-                                            return bal;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    0
-}
+use crate::wallet::SharedSigner;
 
 fn persist_bought_mints(path: &str, map: &std::collections::HashMap<String, i64>) {
     match serde_json::to_string_pretty(map) {
@@ -114,1550 +64,6851 @@ async fn remove_bought_mint(
     removed
 }
 
-/// Start the sniper bot
-pub async fn start(config: &Config, dry_run: bool) -> Result<()> {
-    if dry_run {
-        warn!("Running in DRY-RUN mode - no real trades will be executed");
-    }
+/// Replace an estimated token amount with the actual on-chain fill
+///
+/// Used by entry paths that record the position before the buy confirms.
+/// The fill is the buy event parsed from the confirmed transaction, so
+/// tokens already held don't count. Amounts are in whole tokens (pump.fun
+/// tokens have 6 decimals).
+#[allow(clippy::too_many_arguments)]
+fn spawn_fill_check(
+    confirmation: tokio::task::JoinHandle<Option<ConfirmedTrade>>,
+    position_manager: Arc<crate::position::manager::PositionManager>,
+    strategy_engine: Option<Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    mint: String,
+    signature: String,
+    size_sol: f64,
+    intended_tokens: u64,
+    submitted_at: std::time::Instant,
+    min_fill_ratio: f64,
+) {
+    tokio::spawn(async move {
+        let Some(raw_tokens) = confirmation
+            .await
+            .ok()
+            .flatten()
+            .and_then(|trade| trade.tokens_bought(&mint))
+        else {
+            // Not confirmed, failed or not a pump.fun buy - the estimate stands
+            tracing::debug!("No confirmed fill for {} ({})", mint, signature);
+            return;
+        };
+        let actual_tokens = raw_tokens / 1_000_000;
+        if let Err(e) = position_manager
+            .record_actual_fill(&mint, actual_tokens, intended_tokens, min_fill_ratio)
+            .await
+        {
+            warn!("Failed to record actual fill for {}: {}", mint, e);
+        }
+        let slippage = match position_manager
+            .record_fill_slippage(&mint, raw_tokens)
+            .await
+        {
+            Ok(slippage) => slippage,
+            Err(e) => {
+                warn!("Failed to record fill slippage for {}: {}", mint, e);
+                None
+            }
+        };
+        record_buy_fill(
+            strategy_engine.as_ref(),
+            &mint,
+            &signature,
+            size_sol,
+            intended_tokens,
+            actual_tokens,
+            submitted_at,
+            slippage,
+        )
+        .await;
+    });
+}
 
-    info!("Starting pump.fun sniper bot...");
-    info!(
-        "Buy amount: {} SOL, Slippage: {}bps",
-        config.trading.buy_amount_sol, config.trading.slippage_bps
-    );
+/// Feed a landed buy to execution feedback (token amounts in any common unit)
+///
+/// Prices are SOL per token, so the slippage is the shortfall of the actual
+/// amount against the intended one. The buy's slippage estimate, realized
+/// slippage included, is attached to the fill.
+#[allow(clippy::too_many_arguments)]
+async fn record_buy_fill(
+    strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    mint: &str,
+    signature: &str,
+    size_sol: f64,
+    intended_tokens: u64,
+    actual_tokens: u64,
+    submitted_at: std::time::Instant,
+    slippage: Option<SlippageEstimate>,
+) {
+    let Some(engine) = strategy_engine else {
+        return;
+    };
+    if intended_tokens == 0 || actual_tokens == 0 {
+        return;
+    }
+    let engine = engine.read().await;
+    engine
+        .record_execution(
+            mint,
+            true,
+            size_sol,
+            size_sol / intended_tokens as f64,
+            size_sol / actual_tokens as f64,
+            submitted_at.elapsed().as_millis() as u64,
+            signature,
+        )
+        .await;
+    if let Some(slippage) = slippage.filter(|s| s.estimated) {
+        engine.record_slippage_estimate(signature, slippage).await;
+    }
+}
 
-    // Initialize components
-    info!("Initializing RPC client...");
-    let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_timeout(
-        config.rpc.endpoint.clone(),
-        std::time::Duration::from_millis(config.rpc.timeout_ms),
-    ));
+/// Feed a failed buy to execution feedback
+async fn record_buy_failure(
+    strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    mint: &str,
+    size_sol: f64,
+    submitted_at: std::time::Instant,
+    reason: &str,
+) {
+    if let Some(engine) = strategy_engine {
+        engine
+            .read()
+            .await
+            .record_tx_failure(
+                mint,
+                true,
+                size_sol,
+                submitted_at.elapsed().as_millis() as u64,
+                reason,
+            )
+            .await;
+    }
+}
 
-    // Load keypair for local signing
-    let keypair_path = std::env::var("KEYPAIR_PATH")
-        .unwrap_or_else(|_| "credentials/hot-trading/keypair.json".to_string());
-    let keypair_data = std::fs::read_to_string(&keypair_path)?;
-    let secret_key: Vec<u8> = serde_json::from_str(&keypair_data)?;
-    let keypair = Arc::new(Keypair::from_bytes(&secret_key)?);
-    info!("Loaded keypair: {}", keypair.pubkey());
+/// Buy slippage for `sol_amount` of `mint`: the estimate from the trade feed
+/// (else the configured slippage), widened by execution feedback when enabled
+async fn buy_slippage(
+    config: &Config,
+    estimator: &SlippageEstimator,
+    strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    mint: &str,
+    sol_amount: f64,
+) -> SlippageEstimate {
+    let mut slippage = estimator.estimate(
+        mint,
+        crate::pump::price::sol_to_lamports(sol_amount),
+        config.trading.slippage_bps,
+    );
+    if let Some(engine) = strategy_engine {
+        slippage.chosen_bps = engine.read().await.slippage_bps(slippage.chosen_bps).await;
+    }
+    slippage
+}
 
-    // Initialize trader based on configuration
-    // Force Local API if configured (0.5% fee vs 1% for Lightning)
-    let use_local_api = config.pumpportal.api_key.is_empty() || config.pumpportal.force_local_api;
-    let pumpportal_trader = if config.pumpportal.use_for_trading {
-        info!("Using PumpPortal API for trading");
-        if use_local_api {
-            if config.pumpportal.force_local_api {
-                info!("Force Local API enabled - using Local API (0.5% fee, saving 0.5% per trade)");
-            } else {
-                info!("No API key configured - using Local API (sign + send locally)");
+/// Hot-wallet equity for the strategy engine's bankroll sizing (None =
+/// fixed sizing, or the balance is unavailable)
+///
+/// The balance watcher's last poll is used while fresh, else the balance is
+/// read from the chain.
+async fn entry_equity(
+    sizing: &PositionSizingConfig,
+    position_manager: &crate::position::manager::PositionManager,
+    balance_watch: Option<&crate::wallet::manager::BalanceWatcher>,
+    rpc_client: &solana_client::rpc_client::RpcClient,
+    wallet: &Pubkey,
+) -> Option<EquitySnapshot> {
+    if sizing.mode != SizingMode::Bankroll {
+        return None;
+    }
+    let polled = balance_watch.map(|watch| watch.report()).filter(|report| {
+        report
+            .updated_at
+            .is_some_and(|at| chrono::Utc::now() - at < chrono::Duration::seconds(60))
+    });
+    let balance_sol = match polled {
+        Some(report) => report.balance_sol,
+        None => match rpc_client.get_balance(wallet) {
+            Ok(lamports) => lamports as f64 / 1e9,
+            Err(e) => {
+                warn!(
+                    "Bankroll sizing: balance unavailable ({}), using base_size_sol",
+                    e
+                );
+                return None;
             }
-            Some(PumpPortalTrader::local())
-        } else {
-            info!("Using Lightning API (1% fee) - consider force_local_api=true to save 0.5%");
-            Some(PumpPortalTrader::lightning(
-                config.pumpportal.api_key.clone(),
-            ))
-        }
-    } else {
-        info!("Using Jito bundles for trading");
-        None
+        },
     };
+    Some(position_manager.equity_snapshot(balance_sol).await)
+}
 
-    // Initialize Jito client (for bundle submission if not using PumpPortal)
-    if !config.pumpportal.use_for_trading {
-        info!("Initializing Jito client...");
-        // TODO: Initialize Jito client
-    }
+/// Jitter a buy's size and priority fee (SOL) per `strategy.randomization`
+///
+/// Callers send and record the returned values, so P&L stays exact.
+async fn randomize_buy(
+    strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    size_sol: f64,
+    priority_fee: f64,
+) -> (f64, f64) {
+    let Some(engine) = strategy_engine else {
+        return (size_sol, priority_fee);
+    };
+    let buy = engine
+        .read()
+        .await
+        .randomize_buy(size_sol, (priority_fee * 1e9).round() as u64)
+        .await;
+    (buy.size_sol, buy.priority_fee_lamports as f64 / 1e9)
+}
 
-    // Set up event channel
-    let (event_tx, mut event_rx) =
-        mpsc::channel::<PumpPortalEvent>(config.backpressure.channel_capacity);
+/// Exit style for a new position (None = the monitor's legacy rules)
+async fn select_exit_style(
+    strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    entry_type: crate::position::manager::EntryType,
+    regime: Option<crate::strategy::types::RegimeKind>,
+) -> Option<crate::strategy::types::ExitStyle> {
+    let engine = strategy_engine?;
+    engine
+        .read()
+        .await
+        .select_exit_style(entry_type.as_str(), regime)
+        .await
+}
 
-    // Connect to token detection source
-    if config.pumpportal.enabled {
-        info!("Connecting to PumpPortal WebSocket for token detection...");
-        let pumpportal_config = crate::stream::pumpportal::PumpPortalConfig {
-            ws_url: config.pumpportal.ws_url.clone(),
-            reconnect_delay_ms: config.pumpportal.reconnect_delay_ms,
-            max_reconnect_attempts: config.pumpportal.max_reconnect_attempts,
-            ping_interval_secs: config.pumpportal.ping_interval_secs,
-        };
-        let pumpportal_client = PumpPortalClient::new(pumpportal_config, event_tx.clone());
+/// Sleep for the randomized entry delay before a buy
+async fn apply_entry_delay(strategy_engine: Option<&Arc<tokio::sync::RwLock<StrategyEngine>>>) {
+    if let Some(engine) = strategy_engine {
+        let delay = engine.read().await.get_entry_delay().await;
+        if delay.as_millis() > 0 {
+            tracing::debug!("Applying entry delay: {}ms", delay.as_millis());
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
 
-        // Get tracked wallets from config
-        let track_wallets = config.wallet_tracking.wallets.clone();
+/// Configured buy priority fee raised to the chain-health fee floor (SOL)
+fn buy_priority_fee(config: &Config, fee_floor: &std::sync::atomic::AtomicU64) -> f64 {
+    let floor = fee_floor.load(std::sync::atomic::Ordering::Relaxed);
+    config.trading.priority_fee_lamports.max(floor) as f64 / 1e9
+}
 
-        // Start PumpPortal connection with trade monitoring
-        // subscribe_new_tokens: true, subscribe_all_trades: true
-        if let Err(e) = pumpportal_client.start(true, true, track_wallets).await {
-            error!("PumpPortal connection error: {}", e);
-        }
+/// Send a buy: simulated in paper mode, else through the Local or Lightning API
+#[allow(clippy::too_many_arguments)]
+async fn send_buy(
+    trader: &PumpPortalTrader,
+    paper_trader: Option<&PaperTrader>,
+    use_local_api: bool,
+    mint: &str,
+    sol_amount: f64,
+    slippage_pct: u32,
+    priority_fee: f64,
+    signer: &dyn crate::wallet::TxSigner,
+    rpc_client: &solana_client::rpc_client::RpcClient,
+) -> crate::error::Result<String> {
+    if let Some(paper_trader) = paper_trader {
+        paper_trader
+            .buy_fill(mint, sol_amount, slippage_pct, priority_fee)
+            .await
+            .map(|fill| fill.signature)
+    } else if use_local_api {
+        trader
+            .buy_local(
+                mint,
+                sol_amount,
+                slippage_pct,
+                priority_fee,
+                signer,
+                rpc_client,
+            )
+            .await
     } else {
-        info!("Connecting to ShredStream for token detection...");
-        // TODO: Connect to ShredStream when available
-        warn!("ShredStream not yet implemented - enable PumpPortal in config");
+        trader
+            .buy(mint, sol_amount, slippage_pct, priority_fee)
+            .await
     }
+}
 
-    // Initialize position manager
-    info!("Loading positions...");
-    let position_manager = std::sync::Arc::new(crate::position::manager::PositionManager::new(
-        config.safety.clone(),
-        Some(format!("{}/positions.json", config.wallet.credentials_dir)),
-    ));
-    if let Err(e) = position_manager.load().await {
-        warn!("Could not load positions: {} (starting fresh)", e);
+/// Sell all of a position for the kill-switch under its urgency's policy
+///
+/// Goes through the monitor's seller, so the route, pricing and Jito bundle
+/// are the ones its own kill-switch exits get. Failures that are retryable
+/// or have another route left are retried straight away.
+async fn send_kill_switch_sell(
+    seller: &dyn crate::position::auto_sell::PositionSeller,
+    position: &crate::position::manager::Position,
+    policy: &crate::config::KillSwitchExecutionPolicy,
+) -> crate::error::Result<String> {
+    let mut attempt = 1;
+    loop {
+        match seller
+            .sell_with_policy(position, "100%", attempt, policy)
+            .await
+        {
+            Ok(sig) => return Ok(sig),
+            Err(e) => {
+                let retry = e.is_retryable()
+                    || seller.has_fallback(position, attempt, policy.lightning_attempts);
+                if !retry || attempt >= crate::position::auto_sell::MAX_SELL_ATTEMPTS {
+                    return Err(e);
+                }
+                warn!(
+                    "Kill-switch sell of {} failed (attempt {}): {}",
+                    position.symbol, attempt, e
+                );
+                attempt += 1;
+            }
+        }
     }
+}
 
-    // Initialize kill-switch evaluator
-    let kill_switch_evaluator = if config.smart_money.kill_switches.enabled {
-        info!("Initializing kill-switch evaluator...");
-        let evaluator = Arc::new(KillSwitchEvaluator::new(
-            config.smart_money.kill_switches.clone(),
-            config.smart_money.holder_watcher.clone(),
-        ));
-        info!(
-            "Kill-switches enabled: deployer_sell={}, top_holder_sell={}",
-            config.smart_money.kill_switches.deployer_sell_any,
-            config.smart_money.kill_switches.top_holder_sell
-        );
-        Some(evaluator)
-    } else {
-        info!("Kill-switches disabled");
-        None
-    };
+/// Jito client for kill-switch policies that send bundles (None when no
+/// policy does or the client can't be built)
+fn kill_switch_jito(config: &Config) -> Option<Arc<crate::trading::jito::JitoClient>> {
+    if !config.auto_sell.kill_switch_execution.uses_jito() {
+        return None;
+    }
+    match crate::trading::jito::JitoClient::new(config.jito.clone()) {
+        Ok(jito) => Some(Arc::new(jito)),
+        Err(e) => {
+            warn!("Kill-switch Jito bundles disabled: {}", e);
+            None
+        }
+    }
+}
 
-    // Initialize token filter
-    let token_filter = crate::filter::token_filter::TokenFilter::new(config.filters.clone())
-        .map_err(|e| anyhow::anyhow!("Failed to create token filter: {}", e))?;
+/// Filter verdict for the position-opened alert, e.g. `StrongBuy +0.72`
+fn score_summary(result: &crate::filter::ScoringResult) -> String {
+    format!("{:?} {:+.2}", result.recommendation, result.score)
+}
 
-    // Initialize Helius client and WalletProfiler for smart money signals
-    let (helius_client, wallet_profiler) = if config.smart_money.enabled {
-        if let Some(helius) = HeliusClient::from_rpc_url(&config.rpc.endpoint) {
-            info!("Smart money signals ENABLED - Helius client initialized");
-            let helius_arc = Arc::new(helius);
-            let profiler = Arc::new(WalletProfiler::new(
-                helius_arc.clone(),
-                WalletProfilerConfig::default(),
-            ));
-            (Some(helius_arc), Some(profiler))
+/// What the filter, an entry tactic and the kill-switch propose for an entry
+///
+/// The strategy engine arbitrates these alongside its own inputs.
+fn entry_proposals(
+    scoring: Option<&crate::filter::ScoringResult>,
+    tactic: Option<(&str, f64)>,
+    kill_switch: Option<&KillSwitchEvaluator>,
+    mint: &str,
+) -> Vec<Proposal> {
+    let mut proposals = vec![];
+    if let Some(result) = scoring {
+        let action = if result.allows_any_trading() {
+            ProposedAction::Enter
         } else {
-            warn!("Smart money enabled but Helius API key not found in RPC URL");
-            (None, None)
-        }
-    } else {
-        (None, None)
-    };
-
-    // Initialize adaptive filter if enabled
-    let adaptive_filter = if config.adaptive_filter.enabled {
-        info!("Initializing adaptive filter...");
-        let mut filter = AdaptiveFilter::new(config.adaptive_filter.clone())
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to create adaptive filter: {}", e))?;
+            ProposedAction::Hold
+        };
+        proposals.push(Proposal::new(
+            DecisionSource::Filter,
+            action,
+            result.confidence,
+            format!("{:?} (score {:+.2})", result.recommendation, result.score),
+        ));
+    }
+    if let Some((name, confidence)) = tactic {
+        proposals.push(Proposal::new(
+            DecisionSource::Tactics,
+            ProposedAction::Enter,
+            confidence,
+            name,
+        ));
+    }
+    if let Some(KillSwitchDecision::Exit(alert)) = kill_switch.map(|k| k.should_exit(mint)) {
+        proposals.push(Proposal::new(
+            DecisionSource::KillSwitch,
+            ProposedAction::Skip,
+            1.0,
+            alert.reason,
+        ));
+    }
+    proposals
+}
 
-        // Register signal providers
-        let metadata_provider = Arc::new(MetadataSignalProvider::new());
-        filter.register_provider(metadata_provider);
+/// `name` in the credentials dir, `paper_`-prefixed for paper runs
+fn state_path(config: &Config, name: &str, paper: bool) -> String {
+    let prefix = if paper { "paper_" } else { "" };
+    format!("{}/{}{}", config.wallet.credentials_dir, prefix, name)
+}
 
-        let wallet_provider = Arc::new(WalletBehaviorSignalProvider::new(filter.cache().clone()));
-        filter.register_provider(wallet_provider);
+/// The bot holding the live (else the paper) run lock, if one is running
+fn running_instance(config: &Config) -> Option<RunState> {
+    [false, true].into_iter().find_map(|paper| {
+        RunState::running(std::path::Path::new(&state_path(
+            config,
+            RUN_LOCK_FILE,
+            paper,
+        )))
+    })
+}
 
-        // Register early momentum signal provider
-        let early_momentum = Arc::new(EarlyMomentumSignalProvider::new(
-            config.early_detection.clone(),
-        ));
-        filter.register_provider(early_momentum);
+/// Control channel of the running bot, from its run lock or else the config
+fn control_endpoint(config: &Config) -> crate::control::ControlEndpoint {
+    let running = running_instance(config);
+    let paper = running.as_ref().is_some_and(|state| state.mode.is_paper());
+    crate::control::ControlEndpoint {
+        addr: running
+            .and_then(|state| state.control_addr)
+            .unwrap_or_else(|| config.control.listen_addr.clone()),
+        token_path: state_path(config, crate::control::TOKEN_FILE, paper).into(),
+    }
+}
 
-        // Register smart money signal provider if profiler available
-        if let Some(ref profiler) = wallet_profiler {
-            let smart_money = Arc::new(SmartMoneySignalProvider::new(profiler.clone()));
-            filter.register_provider(smart_money);
-            info!("Smart money signal provider registered");
-        }
+/// Refuse the file-edit fallbacks while any bot holds a run lock
+///
+/// A running bot has the state files loaded and would overwrite the edit
+/// (or act on it twice), so they are only edited when none is running.
+fn ensure_bot_stopped(config: &Config) -> Result<()> {
+    if let Some(state) = running_instance(config) {
+        anyhow::bail!(
+            "snipe start is running ({}) but did not answer on its control channel; stop it before changing its state files",
+            state
+        );
+    }
+    Ok(())
+}
 
-        let provider_count = if wallet_profiler.is_some() { 4 } else { 3 };
-        if filter.is_degraded().await {
-            warn!("Adaptive filter running in degraded mode - some signals may be unavailable");
-        } else {
-            info!("Adaptive filter initialized with {} providers", provider_count);
-        }
+/// Entry size multiplier of the trading window in force (logged when it isn't 1)
+fn window_size_multiplier(throttle: &crate::strategy::LaunchThrottle, symbol: &str) -> f64 {
+    let multiplier = throttle.size_multiplier(chrono::Utc::now());
+    if multiplier != 1.0 {
+        info!("Trading window sizes {} x{:.2}", symbol, multiplier);
+    }
+    multiplier
+}
 
-        Some(filter)
-    } else {
-        info!("Adaptive filter disabled - using basic filtering only");
-        None
+/// Buy-size multiplier from approved AI sizing proposals
+async fn advisor_size_multiplier(
+    advisor: Option<&Arc<crate::wallet::advisor::AiAdvisor>>,
+    symbol: &str,
+) -> f64 {
+    let Some(advisor) = advisor else {
+        return 1.0;
     };
+    let multiplier = advisor.buy_size_multiplier().await;
+    if multiplier != 1.0 {
+        info!("AI advisor sizes {} x{:.2}", symbol, multiplier);
+    }
+    multiplier
+}
 
-    // Initialize strategy engine if enabled
-    let strategy_engine = if config.strategy.enabled {
-        info!("Initializing aggressive strategy engine...");
-        let mut engine = StrategyEngine::new(config.strategy.clone());
+/// Hot keypair file used when neither `KEYPAIR_PATH` nor
+/// `wallet.use_keystore` is set
+const DEFAULT_KEYPAIR_PATH: &str = "credentials/hot-trading/keypair.json";
 
-        // Share filter cache with strategy engine if available
-        if let Some(ref filter) = adaptive_filter {
-            engine.set_filter_cache(filter.cache().clone());
-        }
+/// Hot keypair file and where it came from: `KEYPAIR_PATH` if set, else the
+/// `wallet.hot_wallet` credentials store entry with `wallet.use_keystore`,
+/// else `DEFAULT_KEYPAIR_PATH`
+fn hot_keypair_source(config: &Config) -> Result<(std::path::PathBuf, &'static str)> {
+    use crate::wallet::CredentialManager;
 
-        info!(
-            "Strategy engine initialized: default_strategy={}, max_positions={}, max_exposure={} SOL",
-            config.strategy.default_strategy,
-            config.strategy.portfolio_risk.max_concurrent_positions,
-            config.strategy.portfolio_risk.max_exposure_sol
-        );
+    if let Ok(path) = std::env::var("KEYPAIR_PATH") {
+        return Ok((path.into(), "KEYPAIR_PATH"));
+    }
+    if config.wallet.use_keystore {
+        let path = CredentialManager::load(std::path::Path::new(&config.wallet.credentials_dir))?
+            .keypair_path(&config.wallet.hot_wallet)?;
+        return Ok((path, "credentials store (wallet.use_keystore)"));
+    }
+    Ok((DEFAULT_KEYPAIR_PATH.into(), "default path"))
+}
 
-        Some(Arc::new(tokio::sync::RwLock::new(engine)))
-    } else {
-        info!("Strategy engine disabled - using basic mode");
-        None
-    };
+/// Signing keypair (plain or encrypted) from `hot_keypair_source`
+fn load_hot_keypair(config: &Config) -> Result<Keypair> {
+    let (path, source) = hot_keypair_source(config)?;
+    info!("Loading hot keypair from {} ({})", path.display(), source);
+    Ok(crate::wallet::CredentialManager::read_keypair(
+        &path,
+        &config.wallet.hot_wallet,
+    )?)
+}
 
-    // Track wallets for copy trading
-    let tracked_wallets: std::collections::HashSet<String> =
-        config.wallet_tracking.wallets.iter().cloned().collect();
+/// Remote/subprocess hot wallet signer from `[wallet.signer]` (None = the
+/// local keypair signs)
+fn external_hot_signer(config: &Config) -> Result<Option<SharedSigner>> {
+    if config.wallet.signer.backend == crate::wallet::signer::LOCAL {
+        return Ok(None);
+    }
+    Ok(Some(crate::wallet::signer::from_config(
+        &config.wallet.signer,
+    )?))
+}
 
-    // Track tokens we've already evaluated from trade events (to avoid re-evaluating)
-    let seen_trade_tokens: std::sync::Arc<tokio::sync::Mutex<std::collections::HashSet<String>>> =
-        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashSet::new()));
-
-    info!("Starting price feed...");
-    // Wrap trader in Arc for sharing across tasks
-    let trader_arc: Option<std::sync::Arc<PumpPortalTrader>> =
-        pumpportal_trader.map(std::sync::Arc::new);
+/// Hot wallet signer: the `[wallet.signer]` backend, else `load_hot_keypair`
+fn load_hot_signer(config: &Config) -> Result<SharedSigner> {
+    let signer = match external_hot_signer(config)? {
+        Some(signer) => signer,
+        None => Arc::new(load_hot_keypair(config)?),
+    };
+    info!("Loaded signer: {} ({})", signer.pubkey(), signer.backend());
+    Ok(signer)
+}
 
-    // === IMPROVED POSITION MONITOR WITH LOCAL FALLBACK ===
-    // Features: Trailing stop, no-movement exit, quick profit, retry with local fallback
-    if config.auto_sell.enabled && !dry_run {
-        let monitor_config = config.clone();
-        let monitor_positions = position_manager.clone();
-        let monitor_trader = trader_arc.clone();
-        let monitor_keypair = keypair.clone();
-        let monitor_rpc = rpc_client.clone();
+/// Address of the hot wallet signer, without unlocking an encrypted keypair
+fn hot_wallet_address(config: &Config) -> Result<Pubkey> {
+    use crate::wallet::CredentialManager;
 
-        tokio::spawn(async move {
-            info!("=== POSITION MONITOR STARTED ===");
-            info!("Features: Trailing Stop (5%), Quick Profit, LOCAL FALLBACK (No-Movement Exit DISABLED)");
+    if config.wallet.signer.backend != crate::wallet::signer::LOCAL {
+        let pubkey = config.wallet.signer.pubkey.as_deref().unwrap_or_default();
+        return Ok(Pubkey::from_str(pubkey)?);
+    }
+    let (path, _) = hot_keypair_source(config)?;
+    Ok(CredentialManager::read_pubkey(&path)?)
+}
 
-            // Track sell attempts for retry logic
-            let mut sell_attempts: std::collections::HashMap<String, u32> =
-                std::collections::HashMap::new();
+/// Wallet manager settings from `[wallet]`
+fn wallet_manager_config(config: &Config) -> crate::wallet::manager::WalletManagerConfig {
+    let safety = &config.wallet.safety;
+    crate::wallet::manager::WalletManagerConfig {
+        hot_wallet_name: config.wallet.hot_wallet.clone(),
+        vault_wallet_name: config.wallet.vault_wallet.clone(),
+        credentials_dir: config.wallet.credentials_dir.clone(),
+        safety: crate::wallet::safety::WalletSafetyConfig {
+            min_hot_balance_sol: safety.min_hot_balance_sol,
+            max_single_transfer_sol: safety.max_single_transfer_sol,
+            max_daily_extraction_sol: safety.max_daily_extraction_sol,
+            confirm_above_sol: safety.confirm_above_sol,
+            emergency_threshold_sol: safety.emergency_threshold_sol,
+            vault_address_locked: safety.vault_address_locked,
+            allow_external_transfers: safety.allow_external_transfers,
+            ai_max_auto_transfer_sol: safety.ai_max_auto_transfer_sol,
+            dual_confirm_above_sol: safety.dual_confirm_above_sol,
+            dual_confirm_window_secs: safety.dual_confirm_window_mins * 60,
+            day_reset_utc_offset_hours: config.strategy.portfolio_risk.daily_reset_utc_offset_hours,
+        },
+    }
+}
 
-            loop {
-                tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+/// Balance watcher thresholds from `[wallet.balance_watch]`
+fn balance_watch_config(config: &Config) -> crate::wallet::manager::BalanceWatchConfig {
+    let watch = &config.wallet.balance_watch;
+    crate::wallet::manager::BalanceWatchConfig {
+        poll_interval_secs: watch.poll_interval_secs,
+        entry_floor_sol: watch.entry_floor_sol,
+        warn_below_sol: watch.warn_below_sol,
+        max_drain_sol_per_hour: watch.max_drain_sol_per_hour,
+    }
+}
 
-                let positions = monitor_positions.get_all_positions().await;
-                if positions.is_empty() {
-                    continue;
-                }
+/// Auto-extraction rules from `[wallet.extraction]`
+fn extraction_config(config: &Config) -> crate::wallet::extractor::ExtractionConfig {
+    let settings = &config.wallet.extraction;
+    crate::wallet::extractor::ExtractionConfig {
+        auto_extract: settings.auto_extract,
+        profit_threshold_sol: settings.profit_threshold_sol,
+        profit_percentage: settings.profit_percentage,
+        balance_ceiling_sol: settings.balance_ceiling_sol,
+        min_extraction_interval_secs: settings.min_extraction_interval_secs,
+        check_interval_secs: settings.check_interval_secs,
+        jitter_secs: settings.jitter_secs,
+        hot_buffer_sol: settings.hot_buffer_sol,
+        excess_trigger_sol: settings.excess_trigger_sol,
+        daily_hour_utc: settings.daily_hour_utc,
+        max_open_exposure_sol: settings.max_open_exposure_sol,
+    }
+}
 
-                for position in positions {
-                    let current_price = position.current_price;
-                    if current_price <= 0.0 {
-                        continue;
-                    }
+/// Wallet manager with transfer_history.json loaded (and saved back to it);
+/// `hot_signer` signs vault transfers in place of the registry keypair
+async fn load_wallet_manager(
+    config: &Config,
+    hot_signer: Option<SharedSigner>,
+) -> crate::error::Result<crate::wallet::manager::WalletManager> {
+    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+    let mut wallet_manager =
+        crate::wallet::manager::WalletManager::new(wallet_manager_config(config), rpc_client)
+            .await?;
+    let history_path = format!("{}/transfer_history.json", config.wallet.credentials_dir);
+    if let Err(e) = wallet_manager.load_history(&history_path).await {
+        warn!("Could not load transfer history: {}", e);
+    }
+    wallet_manager.set_history_path(history_path);
+    if let Some(signer) = hot_signer {
+        wallet_manager.set_hot_signer(signer);
+    }
+    Ok(wallet_manager)
+}
 
-                    // CONFIRMATION WAIT: Skip positions less than 10 seconds old
-                    // This gives time for buy tx to confirm and ATA to be created
-                    let position_age_secs = (chrono::Utc::now() - position.entry_time)
-                        .num_seconds()
-                        .max(0) as u64;
-                    if position_age_secs < 10 {
-                        continue; // Too new, wait for confirmation
-                    }
+/// Start the rule-based extraction loop; it stops when `shutdown` fires
+fn start_extractor(
+    config: &Config,
+    wallet_manager: Arc<crate::wallet::manager::WalletManager>,
+    position_manager: Arc<crate::position::manager::PositionManager>,
+    shutdown: tokio::sync::broadcast::Receiver<()>,
+) {
+    let mut extractor = crate::wallet::extractor::ProfitExtractor::with_position_manager(
+        extraction_config(config),
+        wallet_manager,
+        position_manager,
+    );
+    tokio::spawn(async move { extractor.start(shutdown).await });
+}
 
-                    // Calculate P&L from entry
-                    let pnl_pct = if position.entry_price > 0.0 {
-                        ((current_price - position.entry_price) / position.entry_price) * 100.0
-                    } else {
-                        0.0
-                    };
+/// Start the AI advisor loop (None if proposals.json can't be loaded)
+async fn start_advisor(
+    config: &Config,
+    wallet_manager: Arc<crate::wallet::manager::WalletManager>,
+    position_manager: Arc<crate::position::manager::PositionManager>,
+) -> Option<Arc<crate::wallet::advisor::AiAdvisor>> {
+    use crate::wallet::advisor::{AdvisorConfig, AiAdvisor};
+
+    let dir = &config.wallet.credentials_dir;
+    if let Err(e) = wallet_manager
+        .load_proposals(&format!("{}/proposals.json", dir))
+        .await
+    {
+        warn!("AI advisor disabled - {}", e);
+        return None;
+    }
 
-                    // Calculate drop from peak (for trailing stop)
-                    let peak_price = if position.peak_price > 0.0 {
-                        position.peak_price
-                    } else {
-                        position.entry_price
-                    };
-                    let drop_from_peak_pct = if peak_price > 0.0 {
-                        ((peak_price - current_price) / peak_price) * 100.0
-                    } else {
-                        0.0
-                    };
+    let settings = &config.wallet.advisor;
+    let advisor = AiAdvisor::new(
+        AdvisorConfig {
+            enabled: true,
+            profit_extraction_trigger: settings.profit_extraction_trigger_sol,
+            balance_extraction_trigger: settings.balance_extraction_trigger_sol,
+            losing_streak_threshold: settings.losing_trades_threshold,
+            min_confidence_auto: settings.min_confidence_auto,
+            interval_secs: settings.interval_secs,
+            proposal_ttl_secs: settings.proposal_ttl_secs,
+            low_balance_sol: settings.low_balance_sol,
+            min_buy_size_multiplier: settings.min_buy_size_multiplier,
+        },
+        wallet_manager,
+    )
+    .with_position_manager(position_manager);
+    let advisor = Arc::new(advisor);
+    tokio::spawn(advisor.clone().run());
+    Some(advisor)
+}
 
-                    let hold_time_secs = (chrono::Utc::now() - position.entry_time)
-                        .num_seconds()
-                        .max(0) as u64;
-
-                    // Get entry-type-specific thresholds
-                    let tp_pct = position.entry_type.take_profit_pct();
-                    let sl_pct = position.entry_type.stop_loss_pct();
-                    let quick_profit_pct = position.entry_type.quick_profit_pct();
-                    let max_hold = position.entry_type.max_hold_secs();
-
-                    // Trailing stop: 5% drop from peak (only if we're in profit)
-                    let trailing_stop_pct = 5.0;
-                    // No-movement exit: DISABLED (was causing exits before pumps)
-                    let no_movement_secs = 999999u64;
-                    let no_movement_threshold = 0.0;
-
-                    let mut should_sell = false;
-                    let mut sell_pct = "100%";
-                    let mut reason = String::new();
-
-                    // 1. Check stop loss FIRST (cut losses quickly)
-                    if pnl_pct <= -sl_pct {
-                        should_sell = true;
-                        reason = format!("STOP LOSS at {:.1}% (limit: -{:.0}%)", pnl_pct, sl_pct);
-                    }
+/// Load the shared cooldown tracker (cooldowns.json next to bought_mints.json)
+async fn load_cooldowns(config: &Config, paper: bool) -> Arc<crate::position::CooldownTracker> {
+    let tracker = crate::position::CooldownTracker::new(
+        config.cooldowns.clone(),
+        Some(state_path(config, "cooldowns.json", paper)),
+    );
+    if let Err(e) = tracker.load().await {
+        warn!("Could not load cooldowns: {} (starting fresh)", e);
+    }
+    Arc::new(tracker)
+}
 
-                    // 2. Check trailing stop (only if in profit and dropped from peak)
-                    if !should_sell && pnl_pct > 0.0 && drop_from_peak_pct >= trailing_stop_pct {
-                        should_sell = true;
-                        reason = format!(
-                            "TRAILING STOP: dropped {:.1}% from peak (P&L: +{:.1}%)",
-                            drop_from_peak_pct, pnl_pct
+/// Check the deployed pump.fun program now and every `check_interval_secs`,
+/// pausing new entries while a check finds drift
+///
+/// Entries wait for the first check; one that can't reach the RPC leaves
+/// entries open (and a later failed check leaves them as they were).
+fn spawn_program_check(
+    config: crate::config::PumpProgramConfig,
+    rpc_client: Arc<solana_client::rpc_client::RpcClient>,
+    position_manager: Arc<crate::position::manager::PositionManager>,
+    notifier: Notifier,
+) {
+    position_manager.set_entries_paused(Some("pump.fun program check pending".to_string()));
+    tokio::spawn(async move {
+        let mut first = true;
+        let mut alerted = false;
+        loop {
+            let check_config = config.clone();
+            let rpc = rpc_client.clone();
+            let result =
+                tokio::task::spawn_blocking(move || crate::pump::drift::check(&rpc, &check_config))
+                    .await
+                    .unwrap_or_else(|e| Err(crate::error::Error::Internal(e.to_string())));
+            match result {
+                Ok(report) if report.is_ok() => {
+                    info!("Pump.fun program check passed: {}", report);
+                    if first && config.known_hash.is_empty() {
+                        warn!(
+                            "pump_program.known_hash is not set; review the program and run \
+                             `snipe pump accept-current` to pin {}",
+                            report.program_hash
                         );
                     }
-
-                    // 3. Check take profit
-                    if !should_sell && pnl_pct >= tp_pct {
-                        should_sell = true;
-                        reason = format!("TAKE PROFIT at {:.1}% (target: {:.0}%)", pnl_pct, tp_pct);
+                    position_manager.set_entries_paused(None);
+                    alerted = false;
+                }
+                Ok(report) => {
+                    error!("PUMP.FUN PROGRAM DRIFT: {} - new entries paused", report);
+                    position_manager.set_entries_paused(Some(report.to_string()));
+                    if !alerted {
+                        notifier.notify(Notification::ProgramDrift {
+                            reason: report.to_string(),
+                        });
+                        alerted = true;
                     }
-
-                    // 4. Check quick profit (partial exit)
-                    if !should_sell
-                        && !position.quick_profit_taken
-                        && pnl_pct >= quick_profit_pct
-                        && pnl_pct < tp_pct
-                    {
-                        should_sell = true;
-                        sell_pct = "50%";
-                        reason = format!("QUICK PROFIT at {:.1}% - selling 50%", pnl_pct);
+                }
+                Err(e) => {
+                    warn!("Pump.fun program check failed: {}", e);
+                    if first {
+                        position_manager.set_entries_paused(None);
                     }
+                }
+            }
+            first = false;
+            if config.check_interval_secs == 0 {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(config.check_interval_secs)).await;
+        }
+    });
+}
 
-                    // 5. Check no-movement exit (60s with <2% move either way)
-                    if !should_sell
-                        && hold_time_secs >= no_movement_secs
-                        && pnl_pct.abs() < no_movement_threshold
-                    {
-                        should_sell = true;
-                        reason = format!(
-                            "NO MOVEMENT: {:.1}% after {}s - exiting stale position",
-                            pnl_pct, hold_time_secs
-                        );
-                    }
+/// Shared handles needed to place the remaining tranches of a scale-in entry
+#[derive(Clone)]
+struct ScaleInContext {
+    position_manager: Arc<crate::position::manager::PositionManager>,
+    trader: Arc<PumpPortalTrader>,
+    signer: SharedSigner,
+    rpc_client: Arc<solana_client::rpc_client::RpcClient>,
+    strategy_engine: Option<Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    kill_switch: Option<Arc<KillSwitchEvaluator>>,
+    use_local_api: bool,
+    check_wallet: Pubkey,
+    slippage_pct: u32,
+    priority_fee: f64,
+    platform_fee_pct: f64,
+}
 
-                    // 6. Check max hold time last (safety net)
-                    if !should_sell {
-                        if let Some(max_secs) = max_hold {
-                            if hold_time_secs >= max_secs {
-                                should_sell = true;
-                                reason = format!(
-                                    "MAX HOLD TIME ({} secs) P&L: {:.1}%",
-                                    max_secs, pnl_pct
-                                );
-                            }
-                        }
+/// Place the remaining tranches of a scale-in plan in the background
+///
+/// Tranches are cancelled as soon as the position is gone, a kill-switch
+/// fires, or the strategy engine flags a fatal risk for the token.
+fn spawn_scale_in_tranches(ctx: ScaleInContext, mint: String, creator: String) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+            let position = match ctx.position_manager.get_position(&mint).await {
+                Some(p) => p,
+                None => break, // Position closed - nothing left to scale into
+            };
+            let plan = match position.scale_in {
+                Some(ref plan) => plan.clone(),
+                None => break,
+            };
+            if plan.is_complete() {
+                break;
+            }
+
+            // Cancellation checks run before every tranche
+            let mut cancel_reason = position
+                .kill_switch_reason
+                .clone()
+                .filter(|_| position.kill_switch_triggered)
+                .map(|r| format!("kill-switch: {}", r));
+            if cancel_reason.is_none() {
+                if let Some(ref evaluator) = ctx.kill_switch {
+                    if let KillSwitchDecision::Exit(alert) = evaluator.should_exit(&mint) {
+                        cancel_reason = Some(format!("kill-switch: {}", alert.reason));
                     }
+                }
+            }
+            if cancel_reason.is_none() {
+                if let Some(ref engine) = ctx.strategy_engine {
+                    if let Some(risk) = engine.read().await.quick_fatal_check(&mint, &creator).await {
+                        cancel_reason = Some(format!("fatal risk: {}", risk));
+                    }
+                }
+            }
+            let now = chrono::Utc::now();
+            if cancel_reason.is_none() && plan.is_expired(now) {
+                cancel_reason = Some("price-drop tranche timed out".to_string());
+            }
+            if let Some(reason) = cancel_reason {
+                warn!("[{}] Scale-in cancelled: {}", position.symbol, reason);
+                let _ = ctx.position_manager.cancel_scale_in(&mint, &reason).await;
+                break;
+            }
 
-                    // Execute sell if triggered
-                    if should_sell {
-                        warn!(
-                            "AUTO-SELL TRIGGERED: {} ({}) - {}",
-                            position.symbol, position.mint, reason
-                        );
+            if !plan.is_next_due(now, position.current_price) {
+                continue;
+            }
+            let tranche = match plan.next_pending() {
+                Some(t) => t.clone(),
+                None => break,
+            };
 
-                        if let Some(ref trader) = monitor_trader {
-                            let slippage = monitor_config.trading.slippage_bps / 100;
-                            let priority_fee =
-                                monitor_config.trading.priority_fee_lamports as f64 / 1e9;
+            // Never buy while the position is being sold (or bought elsewhere)
+            let _mint_lock = match ctx.position_manager.lock_mint(&mint, "scale-in").await {
+                Ok(lock) => lock,
+                Err(e) => {
+                    warn!("[{}] Scale-in tranche deferred: {}", position.symbol, e);
+                    continue;
+                }
+            };
+            if ctx.position_manager.get_position(&mint).await.is_none() {
+                break;
+            }
 
-                            // Retry logic: try up to 3 times with Lightning, then try local fallback
-                            let attempts = sell_attempts.entry(position.mint.clone()).or_insert(0);
-                            *attempts += 1;
+            let (size_sol, priority_fee) = randomize_buy(
+                ctx.strategy_engine.as_ref(),
+                tranche.size_sol,
+                ctx.priority_fee,
+            )
+            .await;
+            // Held until the fill is recorded, so concurrent entries see the capital
+            let reservation = match ctx.position_manager.try_reserve_add(size_sol).await {
+                Ok(reservation) => reservation,
+                Err(e) => {
+                    warn!("[{}] Scale-in stopped by risk limits: {}", position.symbol, e);
+                    let _ = ctx
+                        .position_manager
+                        .cancel_scale_in(&mint, &format!("risk limit: {}", e))
+                        .await;
+                    break;
+                }
+            };
+            info!(
+                "[{}] Scale-in tranche {}/{}: buying {:.4} SOL",
+                position.symbol,
+                tranche.index + 1,
+                plan.tranches.len(),
+                size_sol
+            );
 
-                            if *attempts > 5 {
-                                error!("AUTO-SELL GAVE UP for {} after 5 attempts - removing from tracking", position.symbol);
-                                // Estimate received SOL as 0 since sell failed
-                                let _ = monitor_positions
-                                    .close_position(&position.mint, position.token_amount, 0.0)
-                                    .await;
-                                sell_attempts.remove(&position.mint);
-                                continue;
-                            }
+            let balance_before = query_token_balance(&ctx.rpc_client, &ctx.check_wallet, &mint);
+            let buy_result = if ctx.use_local_api {
+                ctx.trader
+                    .buy_local(
+                        &mint,
+                        size_sol,
+                        ctx.slippage_pct,
+                        priority_fee,
+                        &ctx.signer,
+                        &ctx.rpc_client,
+                    )
+                    .await
+            } else {
+                ctx.trader
+                    .buy(&mint, size_sol, ctx.slippage_pct, priority_fee)
+                    .await
+            };
 
-                            // Try Lightning API first (attempts 1-3)
-                            let sell_result: Result<String, crate::error::Error> = if *attempts <= 3
-                            {
-                                info!("Attempting Lightning API sell (attempt {})", attempts);
-                                trader
-                                    .sell(&position.mint, sell_pct, slippage, priority_fee)
-                                    .await
-                            } else {
-                                // Attempts 4-5: Try local signing fallback
-                                warn!("Lightning failed 3x, trying LOCAL SIGNING fallback (attempt {})", attempts);
-                                trader
-                                    .sell_local(
-                                        &position.mint,
-                                        sell_pct,
-                                        slippage,
-                                        priority_fee,
-                                        &monitor_keypair,
-                                        &monitor_rpc,
-                                    )
-                                    .await
-                            };
+            match buy_result {
+                Ok(signature) => {
+                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                    let balance_after =
+                        query_token_balance(&ctx.rpc_client, &ctx.check_wallet, &mint);
+                    let received = balance_after.saturating_sub(balance_before);
 
-                            match sell_result {
-                                Ok(sig) => {
-                                    info!("AUTO-SELL EXECUTED: {} - {}", position.symbol, sig);
-                                    sell_attempts.remove(&position.mint);
-
-                                    // Calculate trade metrics
-                                    let hold_secs =
-                                        (chrono::Utc::now() - position.entry_time).num_seconds();
-                                    let price_change_pct = ((current_price - position.entry_price)
-                                        / position.entry_price)
-                                        * 100.0;
-
-                                    if sell_pct == "50%" {
-                                        // Partial exit - mark quick profit taken
-                                        let half_amount = position.token_amount / 2;
-                                        // Estimate received SOL based on current price (minus ~2% slippage estimate)
-                                        let estimated_received =
-                                            (half_amount as f64 * current_price) * 0.98;
-                                        let pnl_sol =
-                                            estimated_received - (position.total_cost_sol / 2.0);
-                                        let _ = monitor_positions
-                                            .close_position(
-                                                &position.mint,
-                                                half_amount,
-                                                estimated_received,
-                                            )
-                                            .await;
-                                        let _ = monitor_positions
-                                            .mark_quick_profit_taken(&position.mint)
-                                            .await;
-                                        info!("=== TRADE CLOSED (Partial) ===");
-                                        info!(
-                                            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
-                                            position.symbol,
-                                            position.entry_price,
-                                            current_price,
-                                            price_change_pct
-                                        );
-                                        info!("  Tokens: {} | Received: {:.4} SOL | P&L: {:+.4} SOL | Hold: {}s",
-                                              half_amount, estimated_received, pnl_sol, hold_secs);
-                                    } else {
-                                        // Full exit
-                                        // Estimate received SOL based on current price (minus ~2% slippage estimate)
-                                        let estimated_received =
-                                            (position.token_amount as f64 * current_price) * 0.98;
-                                        let pnl_sol = estimated_received - position.total_cost_sol;
-                                        let pnl_pct = (pnl_sol / position.total_cost_sol) * 100.0;
-                                        let _ = monitor_positions
-                                            .close_position(
-                                                &position.mint,
-                                                position.token_amount,
-                                                estimated_received,
-                                            )
-                                            .await;
-                                        info!("=== TRADE CLOSED (Full) ===");
-                                        info!(
-                                            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
-                                            position.symbol,
-                                            position.entry_price,
-                                            current_price,
-                                            price_change_pct
-                                        );
-                                        info!("  Cost: {:.4} SOL | Received: {:.4} SOL | P&L: {:+.4} SOL ({:+.1}%) | Hold: {}s",
-                                              position.total_cost_sol, estimated_received, pnl_sol, pnl_pct, hold_secs);
-                                    }
-                                }
-                                Err(e) => {
-                                    error!(
-                                        "AUTO-SELL FAILED for {} (attempt {}): {}",
-                                        position.symbol, attempts, e
-                                    );
-                                }
-                            }
-                        }
+                    if received == 0 {
+                        error!(
+                            "[{}] Scale-in tranche {} landed no tokens (sig: {})",
+                            position.symbol,
+                            tranche.index + 1,
+                            signature
+                        );
+                        let _ = ctx
+                            .position_manager
+                            .update_scale_in(&mint, |plan| plan.mark_failed(tranche.index))
+                            .await;
+                        continue;
+                    }
+
+                    // Same unit as the position's prices (SOL per token unit held)
+                    let fill_price = size_sol / received as f64;
+                    let fill = crate::position::manager::Fill {
+                        token_amount: received,
+                        cost_sol: size_sol,
+                        price: fill_price,
+                        signature: signature.clone(),
+                        time: chrono::Utc::now(),
+                        fees: crate::position::TradeFees::estimate(
+                            priority_fee,
+                            0,
+                            size_sol,
+                            ctx.platform_fee_pct,
+                        ),
+                    };
+                    let added = ctx.position_manager.add_fill(&mint, fill).await;
+                    reservation.release();
+                    if let Err(e) = added {
+                        error!("[{}] Failed to record scale-in fill: {}", position.symbol, e);
+                    } else {
+                        spawn_confirmation(
+                            ctx.rpc_client.clone(),
+                            ctx.position_manager.clone(),
+                            signature.clone(),
+                        );
                     }
+                    let _ = ctx
+                        .position_manager
+                        .update_scale_in(&mint, |plan| {
+                            plan.mark_filled(tranche.index, &signature, fill_price)
+                        })
+                        .await;
+                    info!(
+                        "[{}] Scale-in tranche {} filled: {} tokens (sig: {})",
+                        position.symbol,
+                        tranche.index + 1,
+                        received,
+                        signature
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        "[{}] Scale-in tranche {} failed: {}",
+                        position.symbol,
+                        tranche.index + 1,
+                        e
+                    );
+                    let _ = ctx
+                        .position_manager
+                        .update_scale_in(&mint, |plan| plan.mark_failed(tranche.index))
+                        .await;
                 }
             }
-        });
+        }
+    });
+}
+
+/// Start the sniper bot
+pub async fn start(
+    config: &Config,
+    config_source: &ConfigSource,
+    mode: RunMode,
+    record: Option<&str>,
+    allow_multiple: bool,
+    flatten: bool,
+) -> Result<()> {
+    let dry_run = mode == RunMode::DryRun;
+    let paper = mode.is_paper();
+    if dry_run {
+        warn!("Running in DRY-RUN mode - no real trades will be executed");
+    }
+    if paper {
+        warn!(
+            "Running in PAPER mode - fills are simulated against live curves ({} ms latency, {:.1}% slippage)",
+            config.paper.latency_ms, config.paper.slippage_pct
+        );
+    }
+    if mode == RunMode::Simulate {
+        warn!("SIMULATE: every buy and sell is also built, signed and simulated against the cluster - never sent");
     }
 
-    info!("Bot started. Listening for new tokens...");
+    info!("Starting pump.fun sniper bot...");
+    match config.trading.buy_amount_usd {
+        Some(usd) => info!(
+            "Buy amount: ${} (fallback {} SOL), Slippage: {}bps",
+            usd, config.trading.buy_amount_sol, config.trading.slippage_bps
+        ),
+        None => info!(
+            "Buy amount: {} SOL, Slippage: {}bps",
+            config.trading.buy_amount_sol, config.trading.slippage_bps
+        ),
+    }
+    check_config_at_startup(&ConfigReport::check(config))?;
 
-    // Main event loop
-    loop {
-        tokio::select! {
-            Some(event) = event_rx.recv() => {
-                match event {
-                    PumpPortalEvent::NewToken(token) => {
-                        info!(
-                            "New token detected: {} ({}) - Mint: {} | v_sol={} market_cap={}",
-                            token.name, token.symbol, token.mint,
-                            token.v_sol_in_bonding_curve,
-                            token.market_cap_sol
-                        );
+    // One bot per state dir: two would sell the same positions
+    let lock_path = state_path(config, RUN_LOCK_FILE, paper);
+    let _run_lock = if allow_multiple {
+        warn!("--allow-multiple: not taking {}", lock_path);
+        None
+    } else {
+        let run_state = RunState::current(
+            mode,
+            config
+                .control
+                .enabled
+                .then(|| config.control.listen_addr.clone()),
+        );
+        let lock = RunLock::acquire(std::path::Path::new(&lock_path), &run_state).map_err(|e| {
+            anyhow::anyhow!(
+                "{} - stop it first, or pass --allow-multiple to run both anyway",
+                e
+            )
+        })?;
+        info!("Holding {} (pid {})", lock_path, run_state.pid);
+        Some(lock)
+    };
 
-                        // Apply filters
-                        if config.filters.enabled {
-                            use crate::filter::token_filter::FilterResult;
-                            use crate::stream::decoder::TokenCreatedEvent;
-                            use std::str::FromStr;
+    // Initialize components
+    info!("Initializing RPC client...");
+    let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    ));
 
-                            // Convert NewTokenEvent to TokenCreatedEvent for filtering
-                            let filter_event = TokenCreatedEvent {
-                                signature: token.signature.clone(),
-                                slot: 0, // Not available from PumpPortal
-                                mint: solana_sdk::pubkey::Pubkey::from_str(&token.mint).unwrap_or_default(),
-                                name: token.name.clone(),
-                                symbol: token.symbol.clone(),
-                                uri: token.uri.clone(),
-                                bonding_curve: solana_sdk::pubkey::Pubkey::from_str(&token.bonding_curve_key).unwrap_or_default(),
-                                associated_bonding_curve: solana_sdk::pubkey::Pubkey::default(),
-                                creator: solana_sdk::pubkey::Pubkey::from_str(&token.trader_public_key).unwrap_or_default(),
-                                timestamp: chrono::Utc::now(),
-                            };
+    // Hot wallet signer (local keypair or [wallet.signer] backend)
+    let signer = load_hot_signer(config)?;
 
-                            match token_filter.filter(&filter_event) {
-                                FilterResult::Pass => {
-                                    info!("Token {} passed name/symbol filters", token.symbol);
-                                }
-                                FilterResult::Filtered(reason) => {
-                                    info!("Token {} filtered out: {}", token.symbol, reason);
-                                    continue;
-                                }
-                            }
+    // Initialize trader based on configuration
+    // Force Local API if configured (0.5% fee vs 1% for Lightning)
+    let use_local_api = config.pumpportal.api_key.is_empty() || config.pumpportal.force_local_api;
+    let platform_fee_pct = config.pumpportal.platform_fee_pct(use_local_api);
+    let pumpportal_trader = if config.pumpportal.use_for_trading {
+        info!("Using PumpPortal API for trading");
+        if use_local_api {
+            if config.pumpportal.force_local_api {
+                info!("Force Local API enabled - using Local API (0.5% fee, saving 0.5% per trade)");
+            } else {
+                info!("No API key configured - using Local API (sign + send locally)");
+            }
+            Some(PumpPortalTrader::local())
+        } else {
+            info!("Using Lightning API (1% fee) - consider force_local_api=true to save 0.5%");
+            Some(PumpPortalTrader::lightning(
+                config.pumpportal.api_key.clone(),
+            ))
+        }
+    } else {
+        info!("Using Jito bundles for trading");
+        None
+    };
+    // Paper mode fills buys and sells against the curves from the trade feed
+    let paper_trader = paper.then(|| Arc::new(PaperTrader::new(&config.paper, platform_fee_pct)));
+
+    // Copy-traded wallets: config plus `snipe track add`, changed live via the control channel
+    let wallet_tracker = Arc::new(
+        crate::filter::WalletTracker::new(config.wallet_tracking.clone())?
+            .with_persistence(std::path::PathBuf::from(state_path(
+                config,
+                crate::filter::wallet_tracker::TRACKED_WALLETS_FILE,
+                false,
+            )))
+            .with_stats(std::path::PathBuf::from(state_path(
+                config,
+                crate::filter::wallet_tracker::COPY_STATS_FILE,
+                paper,
+            ))),
+    );
 
-                            // Check liquidity (from market cap estimate)
-                            let liquidity_sol = token.market_cap_sol;
-                            if liquidity_sol < config.filters.min_liquidity_sol {
-                                info!(
-                                    "Token {} filtered: liquidity {:.4} SOL < min {:.4} SOL",
-                                    token.symbol, liquidity_sol, config.filters.min_liquidity_sol
-                                );
-                                continue;
-                            }
+    // Initialize Jito client (for bundle submission if not using PumpPortal)
+    if !config.pumpportal.use_for_trading {
+        info!("Initializing Jito client...");
+        // TODO: Initialize Jito client
+    }
 
-                            // Check market cap minimum (for established tokens)
-                            if config.filters.min_market_cap_sol > 0.0 && token.market_cap_sol < config.filters.min_market_cap_sol {
-                                info!(
-                                    "Token {} filtered: market cap {:.2} SOL < min {:.2} SOL (too new)",
+    // Set up event channel
+    let (event_tx, mut event_rx) =
+        mpsc::channel::<PumpPortalEvent>(config.backpressure.channel_capacity);
+
+    // Connect to token detection source
+    if config.pumpportal.enabled {
+        info!("Connecting to PumpPortal WebSocket for token detection...");
+        let pumpportal_config = crate::stream::pumpportal::PumpPortalConfig {
+            ws_url: config.pumpportal.ws_url.clone(),
+            reconnect_delay_ms: config.pumpportal.reconnect_delay_ms,
+            max_reconnect_attempts: config.pumpportal.max_reconnect_attempts,
+            ping_interval_secs: config.pumpportal.ping_interval_secs,
+        };
+        let pumpportal_client = PumpPortalClient::new(pumpportal_config, event_tx.clone());
+
+        // Wallets tracked at startup; later additions arrive with the all-trades feed
+        let track_wallets = wallet_tracker.get_wallets();
+
+        // Start PumpPortal connection with trade monitoring
+        // subscribe_new_tokens: true, subscribe_all_trades: true
+        if let Err(e) = pumpportal_client.start(true, true, track_wallets).await {
+            error!("PumpPortal connection error: {}", e);
+        }
+    } else {
+        info!("Connecting to ShredStream for token detection...");
+        // TODO: Connect to ShredStream when available
+        warn!("ShredStream not yet implemented - enable PumpPortal in config");
+    }
+
+    // Wallet the buys spend from (the Lightning API trades from its own wallet)
+    let trading_wallet = if use_local_api {
+        signer.pubkey()
+    } else {
+        Pubkey::from_str(&config.pumpportal.lightning_wallet).unwrap_or(signer.pubkey())
+    };
+
+    // Telegram / Discord alerts (simulated runs stay quiet)
+    let notifier = if paper {
+        Notifier::disabled()
+    } else {
+        Notifier::spawn(&config.notifications)
+    };
+    let stream_watch = DisconnectWatch::new(
+        notifier.clone(),
+        "PumpPortal",
+        config.notifications.stream_down_secs,
+    );
+    // Each mint's creation is evaluated once (sources re-deliver after reconnects)
+    let new_token_dedup = NewTokenDedup::default();
+
+    // SOL/USD price for the USD-denominated buy size and safety limits
+    let sol_price = Arc::new(crate::sol_price::SolUsdPrice::new(
+        config.sol_price.clone(),
+        rpc_client.clone(),
+    ));
+    if config.uses_usd_amounts() {
+        match sol_price.refresh().await {
+            Ok(quote) => info!(
+                "SOL/USD ${:.2} from {}; USD amounts are converted at each buy",
+                quote.usd_per_sol, config.sol_price.oracle
+            ),
+            Err(e) => warn!(
+                "SOL/USD price unavailable ({}); using SOL amounts until it is",
+                e
+            ),
+        }
+        sol_price.spawn_refresh();
+    }
+
+    // Initialize position manager
+    info!("Loading positions...");
+    let mut position_manager = crate::position::manager::PositionManager::new(
+        config.safety.clone(),
+        Some(state_path(config, "positions.json", paper)),
+    )
+    .with_exit_thresholds(config.auto_sell.entry_types.clone())
+    .with_notifier(notifier.clone())
+    .with_sol_price(sol_price.clone());
+    // Per-wallet daily spend caps (wallets.json); real buys only
+    if !dry_run && !paper {
+        let safety =
+            crate::wallet::manager::spend_enforcer(&wallet_manager_config(config), signer.pubkey())?;
+        position_manager = position_manager.with_spend_guard(Arc::new(safety), trading_wallet);
+    }
+    let position_manager = std::sync::Arc::new(position_manager);
+    if let Err(e) = position_manager.load().await {
+        warn!("Could not load positions: {} (starting fresh)", e);
+    }
+    if let Some(ref paper_trader) = paper_trader {
+        for position in position_manager.get_all_positions().await {
+            paper_trader.restore_holding(&position.mint, position.token_amount);
+        }
+    }
+
+    // New entries stay paused while the pump.fun program looks changed
+    if config.pump_program.enabled {
+        spawn_program_check(
+            config.pump_program.clone(),
+            rpc_client.clone(),
+            position_manager.clone(),
+            notifier.clone(),
+        );
+    }
+
+    // Reconcile tracked positions with what the trading wallet actually holds
+    if config.positions.reconcile_on_start && !paper {
+        reconcile_positions(config, &position_manager, &rpc_client, &trading_wallet).await;
+    }
+
+    // Re-entry cooldowns and failed / rugged blacklists shared with hot_scan
+    let cooldowns = load_cooldowns(config, paper).await;
+
+    // Observe-rated tokens are tracked as hypothetical Probe entries
+    let shadow_tracker = if config.shadow.enabled {
+        let tracker = Arc::new(crate::position::ShadowTracker::new(
+            config.shadow.clone(),
+            config.auto_sell.entry_types.probe,
+            Some(std::path::Path::new(&config.wallet.credentials_dir).join("shadow_trades.json")),
+        ));
+        let expiring = tracker.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                expiring.expire().await;
+            }
+        });
+        info!(
+            "Shadow tracking enabled: Observe tokens priced for {}s",
+            config.shadow.window_secs
+        );
+        Some(tracker)
+    } else {
+        None
+    };
+
+    // Initialize token filter (plus the operator's deny-symbol patterns)
+    let deny_symbols = Arc::new(crate::filter::DenySymbols::load(std::path::Path::new(
+        &config.adaptive_filter.known_actors.deny_symbols_file,
+    )));
+    let token_filter = crate::filter::token_filter::TokenFilter::new(config.filters.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to create token filter: {}", e))?
+        .with_deny_symbols(deny_symbols.clone());
+
+    // Initialize Helius client and WalletProfiler for smart money signals
+    let (helius_client, wallet_profiler) = if config.smart_money.enabled {
+        if let Some(helius) = HeliusClient::from_rpc_url(&config.rpc.endpoint) {
+            info!("Smart money signals ENABLED - Helius client initialized");
+            let helius_arc = Arc::new(helius.with_budget(config.smart_money.helius.clone()));
+            let profiler = Arc::new(WalletProfiler::new(
+                helius_arc.clone(),
+                WalletProfilerConfig::default(),
+            ));
+            (Some(helius_arc), Some(profiler))
+        } else {
+            warn!("Smart money enabled but Helius API key not found in RPC URL");
+            (None, None)
+        }
+    } else {
+        (None, None)
+    };
+
+    // Trusted wallet discovery: profiles early launch buyers in the background
+    let wallet_discovery = match wallet_profiler {
+        Some(ref profiler) if config.smart_money.discovery.enabled => {
+            let discovery = Arc::new(WalletDiscovery::new(
+                config.smart_money.discovery.clone(),
+                profiler.clone(),
+            ));
+            discovery.clone().spawn();
+            info!(
+                "Wallet discovery enabled (first {}s of launches, {} profiles/hour)",
+                config.smart_money.discovery.early_window_secs,
+                config.smart_money.discovery.max_profiles_per_hour
+            );
+            Some(discovery)
+        }
+        _ => None,
+    };
+    // Discovered trusted wallets are copy-traded alongside [wallet_tracking]
+    let copy_discovered = wallet_discovery
+        .clone()
+        .filter(|_| config.smart_money.discovery.copy_trade);
+
+    // Piggyback entries: sniper stats from the trade feed, entries behind proven snipers
+    let piggyback = if config.strategy.tactics.piggyback.enabled {
+        let tracker = Arc::new(tokio::sync::Mutex::new(SniperPiggyback::new(
+            config.strategy.tactics.piggyback.clone(),
+        )));
+        let (maintained, positions, discovery) = (
+            tracker.clone(),
+            position_manager.clone(),
+            wallet_discovery.clone(),
+        );
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let held: std::collections::HashSet<String> = positions
+                    .get_all_positions()
+                    .await
+                    .into_iter()
+                    .map(|p| p.mint)
+                    .collect();
+                let mut tracker = maintained.lock().await;
+                tracker.retain_followed(|mint| held.contains(mint));
+                tracker.cleanup_inactive(24);
+                if let Some(ref discovery) = discovery {
+                    tracker.set_trusted(discovery.trusted());
+                }
+            }
+        });
+        info!(
+            "Piggyback entries enabled: snipers with {}+ trades at {:.0}%+ win rate, max {} positions",
+            config.strategy.tactics.piggyback.min_trades,
+            config.strategy.tactics.piggyback.min_win_rate * 100.0,
+            config.strategy.tactics.piggyback.max_concurrent_positions
+        );
+        Some(tracker)
+    } else {
+        None
+    };
+
+    // Accumulation entries: the front-run detector watches every trade in the feed
+    let frontrun = if config.strategy.tactics.frontrun.enabled {
+        let detector = Arc::new(tokio::sync::Mutex::new(FrontRunDetector::new(
+            config.strategy.tactics.frontrun.clone(),
+        )));
+        let pruned = detector.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                pruned.lock().await.prune();
+            }
+        });
+        if shadow_tracker.is_none() {
+            warn!("Accumulation entries enabled with [shadow] off: skipped candidates are not recorded");
+        }
+        info!(
+            "Accumulation entries enabled: candidates at {:.2}+ detector confidence",
+            config.strategy.tactics.frontrun.min_entry_confidence
+        );
+        Some(detector)
+    } else {
+        None
+    };
+
+    // Bundled launch detection: early buys are collected per new mint
+    let bundled_detector = if config.smart_money.bundled_detection.enabled {
+        info!(
+            "Bundled launch detection enabled ({}s early buy window)",
+            config.smart_money.bundled_detection.early_window_secs
+        );
+        Some(Arc::new(BundledDetector::new(
+            config.smart_money.bundled_detection.clone(),
+            helius_client.clone(),
+        )))
+    } else {
+        None
+    };
+
+    // Initialize kill-switch evaluator
+    let kill_switch_evaluator = if config.smart_money.kill_switches.enabled {
+        info!("Initializing kill-switch evaluator...");
+        let mut evaluator = KillSwitchEvaluator::new(
+            config.smart_money.kill_switches.clone(),
+            config.smart_money.holder_watcher.clone(),
+        );
+        if let Some(ref detector) = bundled_detector {
+            evaluator = evaluator.with_bundled_detector(detector.clone());
+        }
+        info!(
+            "Kill-switches enabled: deployer_sell={}, top_holder_sell={}, bundled_sell={}",
+            config.smart_money.kill_switches.deployer_sell_any,
+            config.smart_money.kill_switches.top_holder_sell,
+            bundled_detector.is_some()
+        );
+        Some(Arc::new(evaluator))
+    } else {
+        info!("Kill-switches disabled");
+        None
+    };
+
+    // Rug prediction on held positions, fed by the trade feed below
+    let rug_watch = if config.strategy.tactics.rug_predictor.enabled {
+        let watch = Arc::new(crate::position::RugWatch::new(
+            config.strategy.tactics.rug_predictor.clone(),
+            position_manager.clone(),
+            kill_switch_evaluator.clone(),
+            Some(std::path::Path::new(&config.wallet.credentials_dir).join("rug_predictions.json")),
+        ));
+        watch.clone().spawn();
+        Some(watch)
+    } else {
+        None
+    };
+
+    // Early-weakness exit on held positions, fed by the trade feed below
+    let flow_watch = if config.auto_sell.early_weakness.enabled {
+        info!("Early-weakness exit enabled");
+        Some(Arc::new(crate::position::FlowWatch::new(
+            config.auto_sell.early_weakness.clone(),
+        )))
+    } else {
+        None
+    };
+
+    // Raw stream events for `snipe backtest`
+    let recorder = match record {
+        Some(path) => {
+            info!("Recording stream events to {}", path);
+            Some(crate::backtest::SessionRecorder::spawn(
+                std::path::Path::new(path),
+            )?)
+        }
+        None => None,
+    };
+
+    // Order flow provider is fed by the trade feed below
+    let mut order_flow_provider: Option<Arc<OrderFlowSignalProvider>> = None;
+    // Distribution provider also supplies the strategy engine's TokenDistribution
+    let mut distribution_provider: Option<Arc<DistributionSignalProvider>> = None;
+    // Funding provider also supplies the portfolio governor's funding clusters
+    let mut funding_provider: Option<Arc<FundingSourceProvider>> = None;
+
+    // Launches per creator, fed by every new-token event (creator_launches.json)
+    let creator_launches = Arc::new(
+        crate::filter::CreatorLaunches::new(config.adaptive_filter.creator_launches.clone())
+            .with_persistence(std::path::PathBuf::from(state_path(
+                config,
+                crate::filter::creator_launches::CREATOR_LAUNCHES_FILE,
+                paper,
+            ))),
+    );
+
+    // Initialize adaptive filter if enabled
+    let adaptive_filter = if config.adaptive_filter.enabled {
+        info!("Initializing adaptive filter...");
+        let mut filter = AdaptiveFilter::new(config.adaptive_filter.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create adaptive filter: {}", e))?;
+
+        // Register the signal providers named in adaptive_filter.providers
+        let order_flow = Arc::new(OrderFlowSignalProvider::new(filter.cache().clone()));
+        let distribution = Arc::new(DistributionSignalProvider::new(filter.cache().clone()));
+        let funding = helius_client
+            .as_ref()
+            .filter(|_| config.adaptive_filter.funding.enabled)
+            .map(|helius| {
+                Arc::new(FundingSourceProvider::new(
+                    helius.clone(),
+                    filter.cache().clone(),
+                    config.adaptive_filter.funding.clone(),
+                ))
+            });
+        let deps = ProviderDeps {
+            cache: filter.cache().clone(),
+            early_detection: config.early_detection.clone(),
+            creator_launches: Some(creator_launches.clone()),
+            wallet_profiler: wallet_profiler.clone(),
+            bundled_detector: bundled_detector.clone(),
+            order_flow: order_flow.clone(),
+            distribution: distribution.clone(),
+            funding: funding.clone(),
+        };
+        let providers = ProviderRegistry::with_builtins()
+            .build(&config.adaptive_filter, &deps)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let registered: Vec<String> = providers.iter().map(|(name, _)| name.clone()).collect();
+        for (_, provider) in providers {
+            filter.register_provider(provider);
+        }
+        let uses = |name: &str| registered.iter().any(|registered| registered == name);
+        order_flow_provider = Some(order_flow).filter(|_| uses("order_flow"));
+        distribution_provider = Some(distribution).filter(|_| uses("distribution"));
+        funding_provider = funding.filter(|_| uses("funding"));
+        if funding_provider.is_some() {
+            info!(
+                "Funding source provider registered (max {} hops)",
+                config.adaptive_filter.funding.max_hops
+            );
+        }
+
+        if filter.is_degraded().await {
+            warn!("Adaptive filter running in degraded mode - some signals may be unavailable");
+        } else {
+            info!(
+                "Adaptive filter initialized with {} providers: {}",
+                registered.len(),
+                registered.join(", ")
+            );
+        }
+
+        Some(Arc::new(filter))
+    } else {
+        info!("Adaptive filter disabled - using basic filtering only");
+        None
+    };
+
+    // Reload local and remote known actor lists and the operator deny / allow
+    // lists while running
+    crate::filter::KnownActorsRefresher::new(
+        adaptive_filter.as_ref().map(|f| f.cache().clone()),
+        config.adaptive_filter.known_actors.clone(),
+    )
+    .with_deny_symbols(deny_symbols)
+    .spawn();
+
+    // Deployers of tokens that rug a held position are blacklisted
+    let rug_recorder = Arc::new(crate::filter::RugRecorder::new(
+        adaptive_filter.as_ref().map(|f| f.cache().clone()),
+        &config.adaptive_filter.known_actors,
+    ));
+
+    // Trading windows and launch-rate governor; `snipe status` reads throttle.json
+    let throttle = Arc::new(
+        crate::strategy::LaunchThrottle::new(config.strategy.throttle.clone()).with_persistence(
+            std::path::PathBuf::from(state_path(config, "throttle.json", paper)),
+        ),
+    );
+
+    // Per-buy slippage from the curve reserves and recent volatility on the trade feed
+    let slippage_estimator = SlippageEstimator::new(config.strategy.slippage.clone());
+
+    // Vault extraction (auto-extraction rules and AI advisor proposals share one
+    // wallet manager, so one daily limit); it moves real SOL, so not in dry-run/paper
+    let wants_wallet_manager =
+        config.wallet.advisor.enabled || config.wallet.extraction.auto_extract;
+    let wallet_manager = if wants_wallet_manager && !dry_run && !paper {
+        let hot_signer = (config.wallet.signer.backend != crate::wallet::signer::LOCAL)
+            .then(|| signer.clone());
+        match load_wallet_manager(config, hot_signer).await {
+            Ok(manager) => Some(Arc::new(manager)),
+            Err(e) => {
+                warn!(
+                    "Vault extraction disabled - wallet manager unavailable: {}",
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        if wants_wallet_manager {
+            info!("Auto-extraction and AI advisor not started in dry-run/paper mode");
+        }
+        None
+    };
+    let (extractor_shutdown, extractor_shutdown_rx) = tokio::sync::broadcast::channel(1);
+    if let Some(ref wallet_manager) = wallet_manager {
+        if config.wallet.extraction.auto_extract {
+            start_extractor(
+                config,
+                wallet_manager.clone(),
+                position_manager.clone(),
+                extractor_shutdown_rx,
+            );
+        }
+    }
+    // Hot wallet balance floor and drain alerts; `snipe status` reads balance_watch.json
+    let balance_watch = if config.wallet.balance_watch.enabled && !dry_run && !paper {
+        let mut watcher = crate::wallet::manager::BalanceWatcher::new(
+            balance_watch_config(config),
+            signer.pubkey(),
+            Arc::new(solana_client::nonblocking::rpc_client::RpcClient::new(
+                config.rpc.endpoint.clone(),
+            )),
+        )
+        .with_position_manager(position_manager.clone())
+        .with_persistence(std::path::PathBuf::from(state_path(
+            config,
+            "balance_watch.json",
+            paper,
+        )));
+        if let Some(ref wallet_manager) = wallet_manager {
+            watcher = watcher.with_wallet_manager(wallet_manager.clone());
+        }
+        let watcher = Arc::new(watcher);
+        notifier.watch_balance(watcher.subscribe());
+        watcher.clone().spawn(extractor_shutdown.subscribe());
+        Some(watcher)
+    } else {
+        None
+    };
+    // AI advisor proposals (proposals.json)
+    let advisor = match wallet_manager {
+        Some(wallet_manager) if config.wallet.advisor.enabled => {
+            start_advisor(config, wallet_manager, position_manager.clone()).await
+        }
+        _ => None,
+    };
+
+    // Priority-fee floor from chain health sampling (lamports, 0 = no data yet)
+    let chain_fee_floor = Arc::new(std::sync::atomic::AtomicU64::new(0));
+
+    // Mint and metadata authorities, checked before every strategy-engine entry
+    let privilege_checker =
+        CreatorPrivilegeChecker::new(config.strategy.creator_privileges.clone());
+    let privilege_rpc = Arc::new(
+        solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout(
+            config.rpc.endpoint.clone(),
+            std::time::Duration::from_millis(config.rpc.timeout_ms),
+        ),
+    );
+
+    // Initialize strategy engine if enabled
+    let strategy_engine = if config.strategy.enabled {
+        info!("Initializing aggressive strategy engine...");
+        let mut engine = StrategyEngine::new(config.strategy.clone());
+
+        // Share filter cache with strategy engine if available
+        if let Some(ref filter) = adaptive_filter {
+            engine.set_filter_cache(filter.cache().clone());
+        }
+        if let Some(ref watcher) = balance_watch {
+            engine.set_balance_watcher(watcher.clone());
+        }
+
+        // Loss-streak cooldowns and daily P&L survive a restart
+        let risk_state_path =
+            std::path::PathBuf::from(state_path(config, "portfolio_risk.json", paper));
+        if let Err(e) = engine.set_portfolio_persistence(risk_state_path).await {
+            warn!(
+                "Could not load portfolio risk state: {} (starting fresh)",
+                e
+            );
+        }
+
+        // Slippage/size adjustments survive a restart; `snipe status --execution` reads the report
+        let execution_path =
+            std::path::PathBuf::from(state_path(config, "execution_feedback.json", paper));
+        if let Err(e) = engine.set_execution_persistence(execution_path).await {
+            warn!(
+                "Could not load execution feedback state: {} (starting fresh)",
+                e
+            );
+        }
+
+        info!(
+            "Strategy engine initialized: profile={}, default_strategy={}, max_positions={}, max_exposure={} SOL",
+            engine.active_profile(),
+            engine.default_strategy(),
+            config.strategy.portfolio_risk.max_concurrent_positions,
+            config.strategy.portfolio_risk.max_exposure_sol
+        );
+
+        // Bankroll sizing: show what the current equity buys per entry
+        let sizing = engine.position_sizing();
+        if let Some(equity) = entry_equity(
+            sizing,
+            &position_manager,
+            balance_watch.as_deref(),
+            &rpc_client,
+            &trading_wallet,
+        )
+        .await
+        {
+            let per_entry = (equity.total_sol() * sizing.bankroll_pct / 100.0)
+                .clamp(sizing.min_size_sol, sizing.max_size_sol);
+            info!(
+                "Capital plan: equity {:.4} SOL ({:.4} balance + {:.4} in positions), {}% per entry = {:.4} SOL (clamped to {}-{} SOL), re-sized at every entry",
+                equity.total_sol(),
+                equity.balance_sol,
+                equity.positions_value_sol,
+                sizing.bankroll_pct,
+                per_entry,
+                sizing.min_size_sol,
+                sizing.max_size_sol
+            );
+        }
+
+        let engine = Arc::new(tokio::sync::RwLock::new(engine));
+
+        // Every close feeds the portfolio governor (loss streaks, creator limits)
+        // and measured sell fills feed execution feedback
+        let mut closes = position_manager.subscribe_closes();
+        let close_engine = engine.clone();
+        tokio::spawn(async move {
+            loop {
+                match closes.recv().await {
+                    Ok(close) => {
+                        let mut engine = close_engine.write().await;
+                        if let Some(fill) = close.fill.filter(|f| f.expected_sol > 0.0) {
+                            let expected_price = close.trade.exit_price;
+                            engine
+                                .record_execution(
+                                    &close.trade.mint,
+                                    false,
+                                    fill.received_sol,
+                                    expected_price,
+                                    expected_price * fill.received_sol / fill.expected_sol,
+                                    fill.latency_ms,
+                                    close.trade.exit_signature.as_deref().unwrap_or(""),
+                                )
+                                .await;
+                        }
+                        if close.fully_closed {
+                            engine
+                                .record_exit(&close.trade.mint, close.trade.pnl_sol)
+                                .await;
+                        } else {
+                            engine.record_partial_exit(close.trade.pnl_sol).await;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Portfolio governor missed {} closes", missed);
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        // Chain health sampler: congestion level and priority-fee floor
+        if config.strategy.chain_health.enabled {
+            let chain_config = config.strategy.chain_health.clone();
+            let rpc = solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout(
+                config.rpc.endpoint.clone(),
+                std::time::Duration::from_millis(config.rpc.timeout_ms),
+            );
+            let sample_engine = engine.clone();
+            let fee_floor = chain_fee_floor.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                    chain_config.sample_interval_secs.max(1),
+                ));
+                let mut last_level = None;
+                loop {
+                    interval.tick().await;
+                    // Sampled without holding the engine lock
+                    let sample = crate::strategy::chain_health::fetch_sample(
+                        &rpc,
+                        chain_config.fee_percentile,
+                    )
+                    .await;
+                    let state = sample_engine
+                        .read()
+                        .await
+                        .record_chain_sample(&sample)
+                        .await;
+                    fee_floor.store(
+                        state.priority_fee_floor_lamports,
+                        std::sync::atomic::Ordering::Relaxed,
+                    );
+                    if last_level != Some(state.congestion_level) {
+                        info!(
+                            "Chain health: {:?} congestion (TPS {:.0}, slot lag {}, fee floor {} lamports)",
+                            state.congestion_level,
+                            state.tps,
+                            state.slot_lag,
+                            state.priority_fee_floor_lamports
+                        );
+                        last_level = Some(state.congestion_level);
+                    }
+                }
+            });
+            info!(
+                "Chain health sampling every {}s",
+                config.strategy.chain_health.sample_interval_secs
+            );
+        }
+
+        Some(engine)
+    } else {
+        info!("Strategy engine disabled - using basic mode");
+        None
+    };
+
+    // Live config reloads (file changes and `snipe reload`): thresholds,
+    // weights, exits, buy size, cooldowns, notifications and the throttle
+    let (auto_sell_updates, auto_sell_config) = watch::channel(config.auto_sell.clone());
+    let buy_amount = Arc::new(
+        BuyAmount::new(config.trading.buy_amount_sol)
+            .with_usd(config.trading.buy_amount_usd, sol_price.clone()),
+    );
+    let reloader = Arc::new(tokio::sync::Mutex::new(ConfigReloader::new(
+        config_source,
+        ReloadTargets {
+            adaptive_filter: adaptive_filter.clone(),
+            auto_sell: auto_sell_updates,
+            position_manager: position_manager.clone(),
+            cooldowns: cooldowns.clone(),
+            notifier: notifier.clone(),
+            throttle: throttle.clone(),
+            buy_amount: buy_amount.clone(),
+        },
+    )?));
+    ConfigReloader::spawn_watch(reloader.clone());
+
+    // Sells requested outside the position monitor, executed on its next pass
+    let (exit_request_tx, exit_request_rx) = tokio::sync::mpsc::channel(64);
+    let mut exit_request_rx = Some(exit_request_rx);
+
+    // Start local control channel for per-position overrides and closes,
+    // status, strategy profile switches and config reloads
+    if config.control.enabled {
+        let mut server =
+            crate::control::ControlServer::new(config.control.clone(), position_manager.clone())
+                .with_reloader(reloader.clone())
+                .with_exit_requests(exit_request_tx.clone())
+                .with_cooldowns(cooldowns.clone())
+                .with_wallet_tracker(wallet_tracker.clone());
+        if let Some(ref helius) = helius_client {
+            server = server.with_helius(helius.clone());
+        }
+        if let Some(ref engine) = strategy_engine {
+            server = server.with_strategy_engine(engine.clone());
+        }
+        if let Some(ref advisor) = advisor {
+            server = server.with_advisor(advisor.clone());
+        }
+        let token_path = state_path(config, crate::control::TOKEN_FILE, paper);
+        if let Err(e) = server.start(std::path::Path::new(&token_path)).await {
+            warn!("Control channel unavailable: {}", e);
+        }
+    }
+
+    // Attribute copy closes to the wallet copied (per-wallet P&L, pruning suggestions)
+    let mut copy_closes = position_manager.subscribe_closes();
+    let close_tracker = wallet_tracker.clone();
+    tokio::spawn(async move {
+        loop {
+            match copy_closes.recv().await {
+                Ok(close) => {
+                    if close.trade.entry_type != crate::position::manager::EntryType::CopyTrade {
+                        continue;
+                    }
+                    let trade = &close.trade;
+                    if let Some(wallet) =
+                        close_tracker.record_close(&trade.mint, trade.pnl_sol, close.fully_closed)
+                    {
+                        let stats = close_tracker.stats(&wallet);
+                        warn!(
+                            "Copies of {} lost {:.4} SOL over the last {} closes - consider `snipe track remove {}`",
+                            wallet,
+                            -stats.recent_pnl_sol.iter().sum::<f64>(),
+                            stats.recent_pnl_sol.len(),
+                            wallet
+                        );
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                    warn!("Copy stats missed {} closes", missed);
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+    // Their holdings, so a sell can be mirrored as the same share of our copy
+    let tracked_holdings = crate::filter::TrackedHoldings::new();
+
+    // Track tokens we've already evaluated from trade events (to avoid re-evaluating)
+    let seen_trade_tokens: std::sync::Arc<tokio::sync::Mutex<std::collections::HashSet<String>>> =
+        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashSet::new()));
+
+    info!("Starting price feed...");
+    // Wrap trader in Arc for sharing across tasks
+    let trader_arc: Option<std::sync::Arc<PumpPortalTrader>> =
+        pumpportal_trader.map(std::sync::Arc::new);
+
+    let honeypot_checker = crate::filter::HoneypotChecker::new(
+        config,
+        trader_arc.clone(),
+        rpc_client.clone(),
+    );
+    if honeypot_checker.is_enabled() {
+        info!(
+            "Honeypot round-trip check enabled (probe {} SOL, max loss {:.1}% beyond fees)",
+            config.filters.honeypot.probe_sol, config.filters.honeypot.max_round_trip_loss_pct
+        );
+    }
+
+    // Upgrade add-ons and momentum top-ups go through the scale-in executor as a single tranche
+    let add_on_handler: Option<crate::filter::rescoring::AddOnHandler> = match (&trader_arc, dry_run || paper) {
+        (Some(trader), false) => {
+            let ctx = ScaleInContext {
+                position_manager: position_manager.clone(),
+                trader: trader.clone(),
+                signer: signer.clone(),
+                rpc_client: rpc_client.clone(),
+                strategy_engine: strategy_engine.clone(),
+                kill_switch: kill_switch_evaluator.clone(),
+                use_local_api,
+                check_wallet: if use_local_api {
+                    signer.pubkey()
+                } else {
+                    Pubkey::from_str(&config.pumpportal.lightning_wallet)
+                        .unwrap_or(signer.pubkey())
+                },
+                slippage_pct: config.trading.slippage_bps / 100,
+                priority_fee: config.trading.priority_fee_lamports as f64 / 1e9,
+                platform_fee_pct,
+            };
+            Some(Arc::new(move |context: &SignalContext, size_sol: f64| {
+                let ctx = ctx.clone();
+                let mint = context.mint.clone();
+                let creator = context.creator.clone();
+                tokio::spawn(async move {
+                    let plan = crate::strategy::scale_in::ScaleInPlan::add_on(size_sol);
+                    match ctx.position_manager.start_scale_in(&mint, plan).await {
+                        Ok(true) => spawn_scale_in_tranches(ctx, mint, creator),
+                        Ok(false) => info!("Add-on for {} skipped: scale-in in progress", mint),
+                        Err(e) => warn!("Add-on for {} not started: {}", mint, e),
+                    }
+                });
+            }))
+        }
+        _ => None,
+    };
+
+    // Re-score Probe / Opportunity entries once enrichment has landed
+    let rescorer = adaptive_filter.as_ref().map(|filter| {
+        let mut rescorer = crate::filter::Rescorer::new(
+            filter.clone(),
+            position_manager.clone(),
+            config.adaptive_filter.rescoring.clone(),
+        );
+        if let Some(ref handler) = add_on_handler {
+            rescorer = rescorer.with_add_on(handler.clone());
+        }
+        Arc::new(rescorer)
+    });
+
+    // Enter StrongBuy / Opportunity with a starter and top up once momentum confirms
+    // (not in paper mode: top-ups go through the live scale-in executor)
+    let momentum_gate = adaptive_filter
+        .as_ref()
+        .filter(|_| config.adaptive_filter.momentum.enabled && !paper)
+        .map(|_| {
+            info!(
+                "Momentum gate enabled: {:.0}% starter, {}s window",
+                config.adaptive_filter.momentum.starter_fraction * 100.0,
+                config.adaptive_filter.momentum.window_secs
+            );
+            let mut gate = crate::filter::MomentumGate::new(
+                position_manager.clone(),
+                config.adaptive_filter.momentum.clone(),
+            );
+            if let Some(ref handler) = add_on_handler {
+                gate = gate.with_top_up(handler.clone());
+            }
+            Arc::new(gate)
+        });
+
+    // Machine-readable log of every decision-stage scoring result
+    let decision_log = if adaptive_filter.is_some() {
+        crate::filter::DecisionLog::spawn(&config.adaptive_filter.decision_log)
+    } else {
+        crate::filter::DecisionLog::disabled()
+    };
+
+    // --dry-run=simulate: paper orders are also simulated on-chain
+    if let (RunMode::Simulate, Some(paper_trader)) = (mode, &paper_trader) {
+        paper_trader.enable_preflight(crate::trading::paper::Preflight::new(
+            signer.clone(),
+            rpc_client.clone(),
+            decision_log.clone(),
+        ));
+    }
+
+    // Helius rate limiting or an open circuit breaker degrades the adaptive filter
+    if let (Some(filter), Some(helius)) = (&adaptive_filter, &helius_client) {
+        let filter = filter.clone();
+        let helius = helius.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let throttled = helius.budget().is_throttled();
+                if !filter.set_enrichment_throttled(throttled).await {
+                    continue;
+                }
+                let stats = helius.budget().stats();
+                if throttled {
+                    warn!(
+                        "Helius throttled (breaker {}, error rate {}) - adaptive filter confidence reduced",
+                        stats.breaker,
+                        stats
+                            .error_rate
+                            .map_or("n/a".to_string(), |r| format!("{:.0}%", r * 100.0))
+                    );
+                } else {
+                    info!("Helius recovered - enrichment no longer throttled");
+                }
+            }
+        });
+    }
+
+    // Background Helius enrichment: held positions first, then pending decisions
+    let enrichment_handle = match (&adaptive_filter, &helius_client) {
+        (Some(filter), Some(helius)) => {
+            let enrichment_config = config.adaptive_filter.enrichment.clone();
+            let (_, handle, mut worker) = crate::filter::create_enrichment_system(
+                helius.clone(),
+                filter.cache().clone(),
+                enrichment_config.clone(),
+            );
+            let holder_watcher = config.smart_money.holder_watcher.clone();
+            let concentration_watch = Arc::new(
+                crate::position::ConcentrationWatch::new(
+                    holder_watcher.clone(),
+                    distribution_provider.clone().unwrap_or_else(|| {
+                        Arc::new(DistributionSignalProvider::new(filter.cache().clone()))
+                    }),
+                    position_manager.clone(),
+                )
+                .with_exit_requests(exit_request_tx.clone()),
+            );
+            // Fresh holder lists of held positions go to the holder watcher,
+            // then the concentration watch compares them with entry
+            let holder_evaluator = kill_switch_evaluator.clone();
+            let refreshed_watch = concentration_watch.clone();
+            worker = worker.with_holder_refresh(Arc::new(
+                move |mint: &str, holders: &[crate::filter::TokenHolderInfo]| {
+                    if let Some(ref evaluator) = holder_evaluator {
+                        evaluator.refresh_holders(mint, holders);
+                    }
+                    let watch = refreshed_watch.clone();
+                    let mint = mint.to_string();
+                    tokio::spawn(async move {
+                        watch.evaluate(&mint).await;
+                    });
+                },
+            ));
+            if let Some(ref evaluator) = kill_switch_evaluator {
+                // Wallets the creator handed its allocation to count as the deployer
+                let transfer_evaluator = evaluator.clone();
+                worker = worker.with_creator_transfers(Arc::new(
+                    move |mint: &str, _creator: &str, transfers: &[crate::filter::SplTransfer]| {
+                        transfer_evaluator.watch_creator_transfers(mint, transfers);
+                    },
+                ));
+            }
+            tokio::spawn(worker.run());
+
+            // Queue a Critical refresh for every held position
+            let refresh_handle = handle.clone();
+            let position_manager = position_manager.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                    holder_watcher.refresh_secs.max(1),
+                ));
+                loop {
+                    interval.tick().await;
+                    let positions = position_manager.get_all_positions().await;
+                    concentration_watch.sync_positions(&positions);
+                    for position in positions {
+                        refresh_handle.request_enrichment(
+                            position.mint,
+                            String::new(),
+                            EnrichmentPriority::Critical,
+                        );
+                    }
+                }
+            });
+
+            // Queue depth and per-lane throughput
+            let stats_handle = handle.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    info!("Enrichment: {}", stats_handle.stats());
+                }
+            });
+
+            info!(
+                "Enrichment lanes enabled: critical {}/min (every {}s per position), high {}/min, low {}/min",
+                enrichment_config.critical_per_min,
+                config.smart_money.holder_watcher.refresh_secs,
+                enrichment_config.high_per_min,
+                enrichment_config.low_per_min
+            );
+            Some(handle)
+        }
+        _ => None,
+    };
+
+    // === POSITION MONITOR ===
+    // Prices come from the trade feed; behavior is set by [auto_sell.monitor.start]
+    // Mirrored copy-trade sells and manual closes are sent to it (exit_request_tx)
+    // so they get its retry handling
+    // The event loop's kill-switch sells go through the same seller
+    // Paper positions sell against the simulated holdings, not the wallet
+    let position_seller: Option<Arc<dyn crate::position::auto_sell::PositionSeller>> =
+        match (&paper_trader, &trader_arc) {
+            (Some(paper_trader), _) => {
+                Some(Arc::new(crate::position::auto_sell::PaperSeller::new(
+                    paper_trader.clone(),
+                    config,
+                    platform_fee_pct,
+                )))
+            }
+            (None, Some(trader)) => Some(Arc::new(
+                crate::position::auto_sell::PumpPortalSeller::new(
+                    trader.clone(),
+                    rpc_client.clone(),
+                    signer.clone(),
+                    config,
+                )
+                .with_jupiter(Arc::new(crate::trading::JupiterClient::new()))
+                .with_pumpswap(Arc::new(crate::trading::PumpSwapTrader::new(
+                    config.trading.clone(),
+                )))
+                .with_jito(kill_switch_jito(config))
+                .with_local_api_lightning_attempts(
+                    config.auto_sell.monitor.start.local_api_lightning_attempts,
+                ),
+            )),
+            (None, None) => None,
+        };
+    if config.auto_sell.enabled && !dry_run {
+        if let Some(seller) = position_seller.clone() {
+            let mut profile = config.auto_sell.monitor.start;
+            if paper {
+                profile.verify_balance = false;
+                profile.measure_proceeds = false;
+            }
+            crate::position::AutoSeller::new(
+                config.auto_sell.clone(),
+                profile,
+                position_manager.clone(),
+                Arc::new(crate::position::auto_sell::TradeFeedPrices),
+                seller,
+                rpc_client.clone(),
+                cooldowns.clone(),
+            )
+            .with_kill_switch(kill_switch_evaluator.clone())
+            .with_rug_recorder(rug_recorder.clone())
+            .with_exit_advisor(strategy_engine.clone().map(|engine| {
+                Arc::new(crate::position::auto_sell::StrategyExits::new(engine))
+                    as Arc<dyn crate::position::auto_sell::ExitAdvisor>
+            }))
+            .with_flow_watch(flow_watch.clone())
+            .with_exit_requests(exit_request_rx.take().expect("exit requests taken once"))
+            .with_config_updates(auto_sell_config.clone())
+            // Migrated tokens leave the trade feed; price them from the price APIs
+            .with_migration_watch(Arc::new(crate::position::auto_sell::ApiPrices::new(
+                Arc::new(crate::price_source::CompositePriceSource::new(
+                    &config.price_sources,
+                    Arc::new(crate::dexscreener::DexScreenerClient::new()),
+                )),
+            )))
+            .spawn();
+        }
+    }
+    // No monitor: requests fail right away instead of queueing forever
+    drop(exit_request_rx);
+
+    // Re-check open positions' authorities; one regained since entry trips the kill-switch
+    let recheck_secs = config.strategy.creator_privileges.recheck_interval_secs;
+    if config.strategy.creator_privileges.enabled && recheck_secs > 0 {
+        let checker = privilege_checker.clone();
+        let rpc = privilege_rpc.clone();
+        let position_manager = position_manager.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(recheck_secs));
+            loop {
+                interval.tick().await;
+                for position in position_manager.get_all_positions().await {
+                    let Some(ref at_entry) = position.privileges else {
+                        continue;
+                    };
+                    if position.kill_switch_triggered {
+                        continue;
+                    }
+                    let Some(current) = checker.lookup(&rpc, &position.mint).await else {
+                        continue;
+                    };
+                    let regained = at_entry.regained(&current);
+                    if regained.is_empty() {
+                        continue;
+                    }
+                    let names: Vec<String> = regained.iter().map(|p| p.to_string()).collect();
+                    let reason = format!("creator regained {} authority", names.join(", "));
+                    warn!(
+                        "KILL-SWITCH: {} ({}) - {}",
+                        position.symbol, position.mint, reason
+                    );
+                    if let Err(e) = position_manager
+                        .trigger_kill_switch(&position.mint, &reason)
+                        .await
+                    {
+                        warn!("Could not flag {} for exit: {}", position.mint, e);
+                    }
+                }
+            }
+        });
+    }
+
+    // Hourly: report how many launches were flagged, forget old bundles we don't hold
+    if let Some(ref detector) = bundled_detector {
+        let detector = detector.clone();
+        let position_manager = position_manager.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let (analyzed, flagged) = detector.take_flag_counts();
+                if dry_run {
+                    info!(
+                        "DRY-RUN: bundle detection flagged {}/{} launches in the last hour",
+                        flagged, analyzed
+                    );
+                }
+                let held: std::collections::HashSet<String> = position_manager
+                    .get_all_positions()
+                    .await
+                    .into_iter()
+                    .map(|p| p.mint)
+                    .collect();
+                detector.prune(chrono::Duration::hours(1), |mint| held.contains(mint));
+            }
+        });
+    }
+
+    info!("Bot started. Listening for new tokens...");
+
+    // Main event loop
+    loop {
+        tokio::select! {
+            Some(event) = event_rx.recv() => {
+                match event {
+                    PumpPortalEvent::NewToken(token) => {
+                        match new_token_dedup.check(&token.mint, std::time::Instant::now()) {
+                            Delivery::First => {}
+                            Delivery::Replay => {
+                                tracing::debug!("Replayed new token {} ({}) ignored", token.symbol, token.mint);
+                                continue;
+                            }
+                            Delivery::Duplicate => {
+                                info!(
+                                    "Duplicate new token {} ({}) ignored ({} suppressed so far)",
+                                    token.symbol, token.mint, new_token_dedup.suppressed()
+                                );
+                                continue;
+                            }
+                        }
+                        // Another path (copy-trade, trade signal) is already trading it
+                        if let Some(holder) = position_manager.mint_lock_holder(&token.mint) {
+                            info!("Skipping new token {}: {} is trading it", token.symbol, holder);
+                            continue;
+                        }
+                        if let Some(ref recorder) = recorder {
+                            recorder.record(crate::backtest::SessionEvent::NewToken(token.clone()));
+                        }
+                        let curve = crate::filter::honeypot::curve_from_event(token.v_sol_in_bonding_curve as f64, token.v_tokens_in_bonding_curve as f64);
+                        if let Some(ref paper_trader) = paper_trader {
+                            paper_trader.set_curve(&token.mint, curve.clone());
+                        }
+                        slippage_estimator.record_curve(&token.mint, curve);
+                        info!(
+                            "New token detected: {} ({}) - Mint: {} | v_sol={} market_cap={}",
+                            token.name, token.symbol, token.mint,
+                            token.v_sol_in_bonding_curve,
+                            token.market_cap_sol
+                        );
+
+                        throttle.record_launch(chrono::Utc::now());
+                        let launches = creator_launches.record(&token.trader_public_key, &token.mint, &token.symbol, chrono::Utc::now());
+                        if launches.last_1h > 1 {
+                            info!(
+                                "Creator {} launched {} tokens in the last hour ({} in 24h)",
+                                &token.trader_public_key[..8.min(token.trader_public_key.len())],
+                                launches.last_1h,
+                                launches.last_24h
+                            );
+                        }
+
+                        // Early buyers become trusted wallet candidates
+                        if let Some(ref discovery) = wallet_discovery {
+                            discovery.observe_launch(&token.mint);
+                        }
+
+                        // Collect early buys; a flagged bundle drops the cached verdict
+                        // so trade entries and re-scores pick up the bundle signal
+                        if let Some(ref detector) = bundled_detector {
+                            let cache = adaptive_filter.as_ref().map(|f| f.cache().clone());
+                            let symbol = token.symbol.clone();
+                            detector.spawn_window(token.mint.clone(), move |bundle| {
+                                warn!(
+                                    "Token {} flagged as bundled launch: {} wallets, {:.2} SOL ({:?})",
+                                    symbol,
+                                    bundle.wallets.len(),
+                                    bundle.total_buy_sol,
+                                    bundle.detection_reason
+                                );
+                                if let Some(cache) = cache {
+                                    cache.invalidate_score(&bundle.mint);
+                                }
+                            });
+                        }
+
+                        // Apply filters
+                        if config.filters.enabled {
+                            use crate::filter::token_filter::FilterResult;
+                            use crate::stream::decoder::TokenCreatedEvent;
+                            use std::str::FromStr;
+
+                            // Convert NewTokenEvent to TokenCreatedEvent for filtering
+                            let filter_event = TokenCreatedEvent {
+                                signature: token.signature.clone(),
+                                slot: 0, // Not available from PumpPortal
+                                mint: solana_sdk::pubkey::Pubkey::from_str(&token.mint).unwrap_or_default(),
+                                name: token.name.clone(),
+                                symbol: token.symbol.clone(),
+                                uri: token.uri.clone(),
+                                bonding_curve: solana_sdk::pubkey::Pubkey::from_str(&token.bonding_curve_key).unwrap_or_default(),
+                                associated_bonding_curve: solana_sdk::pubkey::Pubkey::default(),
+                                creator: solana_sdk::pubkey::Pubkey::from_str(&token.trader_public_key).unwrap_or_default(),
+                                timestamp: chrono::Utc::now(),
+                            };
+
+                            match token_filter.filter(&filter_event) {
+                                FilterResult::Pass => {
+                                    info!("Token {} passed name/symbol filters", token.symbol);
+                                }
+                                FilterResult::Filtered(reason) => {
+                                    info!("Token {} filtered out: {}", token.symbol, reason);
+                                    continue;
+                                }
+                            }
+
+                            // Check liquidity (from market cap estimate)
+                            let liquidity_sol = token.market_cap_sol;
+                            if liquidity_sol < config.filters.min_liquidity_sol {
+                                info!(
+                                    "Token {} filtered: liquidity {:.4} SOL < min {:.4} SOL",
+                                    token.symbol, liquidity_sol, config.filters.min_liquidity_sol
+                                );
+                                continue;
+                            }
+
+                            // Check market cap minimum (for established tokens)
+                            if config.filters.min_market_cap_sol > 0.0 && token.market_cap_sol < config.filters.min_market_cap_sol {
+                                info!(
+                                    "Token {} filtered: market cap {:.2} SOL < min {:.2} SOL (too new)",
                                     token.symbol, token.market_cap_sol, config.filters.min_market_cap_sol
                                 );
                                 continue;
                             }
 
-                            // Check bonding curve progress (for established tokens)
-                            // Calculate bonding curve % from virtual reserves
-                            // v_sol_in_bonding_curve is in lamports (u64), convert to SOL
-                            // Initial: ~30 SOL virtual, At graduation: ~85 SOL in curve
-                            let v_sol = token.v_sol_in_bonding_curve as f64 / 1_000_000_000.0;
-                            let bonding_curve_pct = if v_sol > 0.0 {
-                                // Approximate: more SOL = more progress
-                                // Full curve is ~85 SOL (starting from ~30 virtual)
-                                ((v_sol - 30.0) / 55.0 * 100.0).clamp(0.0, 100.0)
-                            } else {
-                                0.0
-                            };
+                            // Check bonding curve progress (for established tokens)
+                            let bonding_curve_pct = crate::pump::price::curve_progress_pct(
+                                crate::filter::honeypot::curve_from_event(
+                                    token.v_sol_in_bonding_curve as f64,
+                                    token.v_tokens_in_bonding_curve as f64,
+                                )
+                                .virtual_token_reserves,
+                            );
+
+                            if config.filters.min_bonding_curve_pct > 0.0 && bonding_curve_pct < config.filters.min_bonding_curve_pct {
+                                info!(
+                                    "Token {} filtered: bonding curve {:.1}% < min {:.1}% (too new)",
+                                    token.symbol, bonding_curve_pct, config.filters.min_bonding_curve_pct
+                                );
+                                continue;
+                            }
+
+                            if config.filters.max_bonding_curve_pct > 0.0 && bonding_curve_pct > config.filters.max_bonding_curve_pct {
+                                info!(
+                                    "Token {} filtered: bonding curve {:.1}% > max {:.1}% (near graduation)",
+                                    token.symbol, bonding_curve_pct, config.filters.max_bonding_curve_pct
+                                );
+                                continue;
+                            }
+
+                            info!(
+                                "Token {} passed all filters: mcap={:.2} SOL, bonding={:.1}%",
+                                token.symbol, token.market_cap_sol, bonding_curve_pct
+                            );
+                        }
+
+                        // Check daily loss limit
+                        if position_manager.is_daily_loss_limit_reached().await {
+                            warn!("Daily loss limit reached - skipping buy");
+                            continue;
+                        }
+
+                        // Trading windows and the launch-rate entry cap
+                        if let Some(reason) = throttle.check_entry(chrono::Utc::now()) {
+                            info!("Skipping {}: {}", token.symbol, reason);
+                            decision_log.record(&token.mint, &token.symbol, None, DecisionAction::Throttled, Some(reason));
+                            continue;
+                        }
+
+                        // Hot wallet balance below the entry floor
+                        if let Some(reason) = balance_watch.as_ref().and_then(|w| w.entry_block_reason()) {
+                            warn!("Skipping {}: {}", token.symbol, reason);
+                            decision_log.record(&token.mint, &token.symbol, None, DecisionAction::Throttled, Some(reason));
+                            continue;
+                        }
+
+                        // Check strategy engine constraints (if enabled)
+                        if let Some(ref engine) = strategy_engine {
+                            let engine_guard = engine.read().await;
+
+                            // Check if trading should be paused
+                            if engine_guard.should_pause_trading().await {
+                                let chain_state = engine_guard.get_chain_state().await;
+                                warn!(
+                                    "Strategy engine paused trading: congestion={:?}",
+                                    chain_state.congestion_level
+                                );
+                                continue;
+                            }
+
+                            // Check portfolio limits
+                            let portfolio_state = engine_guard.get_portfolio_state().await;
+                            if !portfolio_state.can_open_new {
+                                warn!(
+                                    "Portfolio limit reached: {} positions, {} SOL exposure - {:?}",
+                                    portfolio_state.open_position_count,
+                                    portfolio_state.total_exposure_sol,
+                                    portfolio_state.reason_if_blocked
+                                );
+                                continue;
+                            }
+                        }
+
+                        // Honeypot round trip: tiny buy + immediate sell against the curve
+                        let round_trip = if honeypot_checker.is_enabled() {
+                            let curve = crate::filter::honeypot::curve_from_event(
+                                token.v_sol_in_bonding_curve as f64,
+                                token.v_tokens_in_bonding_curve as f64,
+                            );
+                            let trip = honeypot_checker
+                                .check(
+                                    &token.mint,
+                                    &token.trader_public_key,
+                                    token.initial_buy as f64,
+                                    &curve,
+                                )
+                                .await;
+                            info!("Honeypot check {}: {}", token.symbol, trip.describe());
+                            // Without the adaptive filter there is nothing else to veto it
+                            if trip.failure.is_some() && adaptive_filter.is_none() {
+                                warn!("Token {} rejected as honeypot", token.symbol);
+                                continue;
+                            }
+                            Some(trip)
+                        } else {
+                            None
+                        };
+
+                        // Background enrichment for anything that passed the basic filters
+                        if let Some(ref enrichment) = enrichment_handle {
+                            enrichment.request_enrichment(
+                                token.mint.clone(),
+                                token.trader_public_key.clone(),
+                                EnrichmentPriority::Low,
+                            );
+                        }
+
+                        // Apply adaptive filter scoring if enabled
+                        // Track both position multiplier AND recommendation for context-aware exits
+                        // (the scored context is kept for deferred re-scoring)
+                        let mut entry_scoring = None;
+                        let (position_multiplier, entry_recommendation) = if let Some(ref filter) = adaptive_filter {
+                            // Create signal context from token event
+                            let mut signal_context = SignalContext::from_new_token(
+                                token.mint.clone(),
+                                token.name.clone(),
+                                token.symbol.clone(),
+                                token.uri.clone(),
+                                token.trader_public_key.clone(),
+                                token.bonding_curve_key.clone(),
+                                token.initial_buy,
+                                token.v_tokens_in_bonding_curve,
+                                token.v_sol_in_bonding_curve,
+                                token.market_cap_sol,
+                            );
+                            signal_context.round_trip = round_trip.clone();
+
+                            // Score the token (cached so later trade events reuse the verdict)
+                            let mut result = filter.score_fast_cached(&signal_context).await;
+                            if throttle.regrade(&mut result, &filter.thresholds()) {
+                                info!("Launch surge: {} regraded to {:?}", token.symbol, result.recommendation);
+                            }
+
+                            info!(
+                                "Adaptive filter: {} score={:.2} risk={:.2} confidence={:.2} recommendation={:?}{}",
+                                token.symbol, result.score, result.risk_score, result.confidence, result.recommendation,
+                                if result.cached { " (cached)" } else { "" }
+                            );
+
+                            // Log individual signals for debugging
+                            for signal in &result.signals {
+                                tracing::debug!(
+                                    signal_type = %signal.signal_type,
+                                    value = %signal.value,
+                                    confidence = %signal.confidence,
+                                    reason = %signal.reason,
+                                    "Signal contribution"
+                                );
+                            }
+
+                            // Check recommendation using new confidence regime model
+                            // When information is weak, the system watches — not trades
+                            match result.recommendation {
+                                Recommendation::Avoid => {
+                                    warn!(
+                                        "Token {} marked AVOID by adaptive filter: {}",
+                                        token.symbol, result.summary
+                                    );
+                                    decision_log.record(&token.mint, &token.symbol, Some(&result), DecisionAction::Avoid, None);
+                                    continue;
+                                }
+                                Recommendation::Observe => {
+                                    // OBSERVE = watch only, don't trade
+                                    // This is the key change: uncertainty means NO trading
+                                    info!(
+                                        "Token {} marked OBSERVE (insufficient data/confidence): {}",
+                                        token.symbol, result.summary
+                                    );
+                                    decision_log.record(&token.mint, &token.symbol, Some(&result), DecisionAction::Observe, None);
+                                    // Later trades re-score it once enrichment lands
+                                    if let Some(ref enrichment) = enrichment_handle {
+                                        enrichment.request_enrichment(
+                                            token.mint.clone(),
+                                            token.trader_public_key.clone(),
+                                            EnrichmentPriority::High,
+                                        );
+                                    }
+                                    if let Some(ref shadows) = shadow_tracker {
+                                        shadows
+                                            .open(
+                                                &token.mint,
+                                                &token.symbol,
+                                                Recommendation::Observe,
+                                                token.market_cap_sol / 1_000_000_000.0,
+                                            )
+                                            .await;
+                                    }
+                                    continue;
+                                }
+                                Recommendation::Probe => {
+                                    // PROBE = micro-position for learning only
+                                    // 5% position size, quick scalp exit
+                                    info!(
+                                        "Token {} in PROBE mode (learning position): {}",
+                                        token.symbol, result.summary
+                                    );
+                                    // Continue to trading with reduced size
+                                }
+                                Recommendation::Opportunity => {
+                                    // Standard buy opportunity
+                                    info!(
+                                        "Token {} marked OPPORTUNITY by adaptive filter: {}",
+                                        token.symbol, result.summary
+                                    );
+                                }
+                                Recommendation::StrongBuy => {
+                                    info!(
+                                        "Token {} marked STRONG BUY by adaptive filter: {}",
+                                        token.symbol, result.summary
+                                    );
+                                }
+                            }
+
+                            let multiplier = result.position_size_multiplier;
+                            let recommendation = result.recommendation;
+                            entry_scoring = Some((signal_context, result));
+                            (multiplier, recommendation)
+                        } else {
+                            (1.0, Recommendation::Opportunity) // Default if adaptive filter disabled
+                        };
+
+                        // Strategy engine evaluation (if enabled); the regime playbook picks the strategy
+                        let mut entry_privileges = None;
+                        let mut entry_arbitration = None;
+                        let mut entry_liquidity = None;
+                        let mut entry_profile = None;
+                        let mut max_sell_impact_pct = config.strategy.position_sizing.max_sell_impact_pct();
+                        let (strategy_entry, strategy_size, entry_regime) = if let Some(ref engine) = strategy_engine {
+                            let mut engine_guard = engine.write().await;
+
+                            // Build token analysis context for strategy engine
+                            // Note: PumpPortal sends v_sol_in_bonding_curve as SOL, not lamports
+                            // The value is typically ~30 SOL (virtual liquidity)
+                            // For actual tradeable liquidity, we use initial_buy or market_cap
+                            let liquidity_sol = if token.v_sol_in_bonding_curve < 1000 {
+                                // Small value = already in SOL
+                                token.v_sol_in_bonding_curve as f64
+                            } else {
+                                // Large value = lamports, convert to SOL
+                                token.v_sol_in_bonding_curve as f64 / 1e9
+                            };
+                            let token_reserves = token.v_tokens_in_bonding_curve as f64;
+
+                            // Order flow from the trade buffer; neutral until trades arrive
+                            let order_flow = order_flow_provider
+                                .as_ref()
+                                .and_then(|provider| provider.analyze(&token.mint))
+                                .map(|flow| crate::strategy::regime::OrderFlowAnalysis::from(&flow))
+                                .unwrap_or(crate::strategy::regime::OrderFlowAnalysis {
+                                    organic_score: position_multiplier.max(0.5),
+                                    wash_trading_score: 0.0,
+                                    buy_sell_ratio: 1.0,
+                                    early_sell_pressure: 0.0,
+                                    burst_detected: false,
+                                    burst_intensity: 0.0,
+                                    organic_demand: None,
+                                });
+
+                            // Holder distribution from enrichment; placeholder until holders are cached
+                            let live_distribution = match distribution_provider {
+                                Some(ref provider) => {
+                                    provider
+                                        .distribution(
+                                            &token.mint,
+                                            &token.trader_public_key,
+                                            &token.bonding_curve_key,
+                                        )
+                                        .await
+                                }
+                                None => None,
+                            };
+                            let distribution = match live_distribution {
+                                Some(ref dist) => crate::strategy::regime::TokenDistribution::from(dist),
+                                None => {
+                                    tracing::debug!(
+                                        "Holder distribution unavailable for {} - using placeholder",
+                                        token.symbol
+                                    );
+                                    crate::strategy::regime::TokenDistribution {
+                                        holder_count: 1,
+                                        top_holder_pct: 100.0,
+                                        top_10_holders_pct: 100.0,
+                                        deployer_holdings_pct: 0.0,
+                                        sniper_holdings_pct: 0.0,
+                                        gini_coefficient: 1.0,
+                                    }
+                                }
+                            };
+
+                            // Create creator behavior
+                            let creator_behavior = crate::strategy::regime::CreatorBehavior {
+                                selling_consistently: false,
+                                total_sold_pct: 0.0,
+                                avg_sell_interval_secs: 0,
+                                sell_count: 0,
+                            };
+
+                            // Create minimal price action
+                            let price_action = crate::strategy::price_action::PriceAction::default();
+
+                            // Evaluate entry using strategy engine
+                            let analysis_ctx = crate::strategy::engine::TokenAnalysisContext {
+                                mint: token.mint.clone(),
+                                creator: token.trader_public_key.clone(),
+                                funding_cluster: funding_provider
+                                    .as_ref()
+                                    .and_then(|provider| provider.funding_cluster(&token.trader_public_key)),
+                                order_flow,
+                                distribution,
+                                creator_behavior,
+                                price_action,
+                                sol_reserves: liquidity_sol,
+                                token_reserves,
+                                confidence_score: position_multiplier,
+                                round_trip_excess_loss_pct: round_trip
+                                    .as_ref()
+                                    .map(|trip| trip.excess_loss_pct),
+                                privileges: privilege_checker.lookup(&privilege_rpc, &token.mint).await,
+                                proposals: entry_proposals(
+                                    entry_scoring.as_ref().map(|(_, result)| result),
+                                    None,
+                                    kill_switch_evaluator.as_deref(),
+                                    &token.mint,
+                                ),
+                                equity: entry_equity(engine_guard.position_sizing(), &position_manager, balance_watch.as_deref(), &rpc_client, &trading_wallet).await,
+                            };
+
+                            let eval = engine_guard.evaluate_entry(&analysis_ctx).await;
+                            entry_privileges = eval.privileges.clone();
+                            entry_arbitration = eval.decision.arbitration.clone();
+                            entry_liquidity = Some(eval.liquidity.clone());
+                            entry_profile = Some(engine_guard.active_profile().to_string());
+                            max_sell_impact_pct = engine_guard.position_sizing().max_sell_impact_pct();
+                            let regime = Some((eval.regime.regime.kind(), eval.explanation.selected_strategy));
+
+                            // Check the decision
+                            match &eval.decision.action {
+                                TradingAction::Enter { mint: _, size_sol, strategy } => {
+                                    info!(
+                                        "Strategy engine: ENTER {} using {} strategy ({} regime), size: {:.4} SOL",
+                                        token.symbol, strategy, eval.regime.regime.kind(), size_sol
+                                    );
+                                    (true, *size_sol, regime)
+                                }
+                                TradingAction::FatalReject { reason } => {
+                                    warn!(
+                                        "Strategy engine: FATAL REJECT for {}: {}",
+                                        token.symbol, reason
+                                    );
+                                    decision_log.record_arbitrated(
+                                        &token.mint,
+                                        &token.symbol,
+                                        entry_scoring.as_ref().map(|(_, result)| result),
+                                        DecisionAction::StrategySkip,
+                                        Some(format!("fatal reject: {}", reason)),
+                                        entry_arbitration.as_ref(),
+                                    );
+                                    (false, 0.0, regime)
+                                }
+                                TradingAction::Skip { reason } => {
+                                    info!(
+                                        "Strategy engine: SKIP {}: {}",
+                                        token.symbol, reason
+                                    );
+                                    decision_log.record_arbitrated(
+                                        &token.mint,
+                                        &token.symbol,
+                                        entry_scoring.as_ref().map(|(_, result)| result),
+                                        DecisionAction::StrategySkip,
+                                        Some(reason.clone()),
+                                        entry_arbitration.as_ref(),
+                                    );
+                                    (false, 0.0, regime)
+                                }
+                                _ => {
+                                    // Hold or other action - fall through to adaptive filter decision
+                                    (true, buy_amount.get() * position_multiplier, regime)
+                                }
+                            }
+                        } else {
+                            // No strategy engine - use adaptive filter multiplier
+                            (true, buy_amount.get() * position_multiplier, None)
+                        };
+
+                        // Skip if strategy engine rejected
+                        if !strategy_entry {
+                            continue;
+                        }
+
+                        let final_amount_sol = strategy_size
+                            * window_size_multiplier(&throttle, &token.symbol)
+                            * advisor_size_multiplier(advisor.as_ref(), &token.symbol).await;
+
+                        // Scale-in: StrongBuy entries may be split into tranches
+                        let scale_in_plan = match (&strategy_engine, entry_recommendation) {
+                            (Some(engine), Recommendation::StrongBuy) if !paper => {
+                                engine.read().await.plan_scale_in(final_amount_sol)
+                            }
+                            _ => None,
+                        };
+                        // Momentum gate: buy a starter now, the rest once momentum confirms
+                        let momentum_starter = match (&momentum_gate, entry_recommendation) {
+                            (Some(gate), Recommendation::StrongBuy | Recommendation::Opportunity)
+                                if scale_in_plan.is_none() =>
+                            {
+                                gate.starter_size(final_amount_sol)
+                            }
+                            _ => None,
+                        };
+                        let first_buy_sol = match (&scale_in_plan, momentum_starter) {
+                            (Some(plan), _) => plan.first_size(),
+                            (None, Some(starter)) => {
+                                info!(
+                                    "Momentum gate for {}: starter {:.4} of {:.4} SOL",
+                                    token.symbol, starter, final_amount_sol
+                                );
+                                starter
+                            }
+                            (None, None) => final_amount_sol,
+                        };
+                        if let Some(ref plan) = scale_in_plan {
+                            info!(
+                                "Scale-in for {}: {} tranches of {:.4} SOL",
+                                token.symbol,
+                                plan.tranches.len(),
+                                first_buy_sol
+                            );
+                        }
+
+                        // Execute buy
+                        if !dry_run {
+                            if let Some(ref trader) = trader_arc {
+                                let mint = &token.mint;
+                                let priority_fee = buy_priority_fee(config, &chain_fee_floor);
+                                let (first_buy_sol, priority_fee) =
+                                    randomize_buy(strategy_engine.as_ref(), first_buy_sol, priority_fee).await;
+                                let slippage = buy_slippage(config, &slippage_estimator, strategy_engine.as_ref(), mint, first_buy_sol).await;
+                                let slippage_pct = slippage.pct();
+
+                                let entry_result = entry_scoring.as_ref().map(|(_, result)| result);
+                                // One trade per mint at a time; re-check for a position under the lock
+                                let Some(_mint_lock) = position_manager.try_lock_mint(mint, "new-token") else {
+                                    info!("Skipping {} - another trade of it is in progress", token.symbol);
+                                    continue;
+                                };
+                                if position_manager.get_position(mint).await.is_some() {
+                                    info!("Skipping {} - already have position", token.symbol);
+                                    continue;
+                                }
+                                if let Some(hit) = cooldowns.check(mint).await {
+                                    info!("Skipping {} - {}", token.symbol, hit);
+                                    decision_log.record_arbitrated(mint, &token.symbol, entry_result, DecisionAction::Cooldown, Some(hit.to_string()), entry_arbitration.as_ref());
+                                    continue;
+                                }
+
+                                // Hold a position slot while the buy is in flight
+                                let slot = match position_manager.try_reserve_slot(first_buy_sol).await {
+                                    Ok(slot) => slot,
+                                    Err(e) => {
+                                        warn!("Skipping {}: {}", token.symbol, e);
+                                        decision_log.record_arbitrated(mint, &token.symbol, entry_result, DecisionAction::NoSlot, Some(e.to_string()), entry_arbitration.as_ref());
+                                        continue;
+                                    }
+                                };
+                                throttle.record_entry(chrono::Utc::now());
+
+                                // Apply entry delay for adversarial resistance
+                                apply_entry_delay(strategy_engine.as_ref()).await;
+
+                                info!("Buying {} SOL of {} ({})...", first_buy_sol, token.symbol, mint);
+
+                                let submitted_at = std::time::Instant::now();
+                                let buy_result = send_buy(trader, paper_trader.as_deref(), use_local_api, mint, first_buy_sol, slippage_pct, priority_fee, &signer, &rpc_client).await;
+
+                                match buy_result {
+                                    Ok(signature) => {
+                                        info!("Buy successful! Signature: {}", signature);
+                                        info!("View on Solscan: https://solscan.io/tx/{}", signature);
+
+                                        // CRITICAL: Verify tokens were actually received before recording position
+                                        // Wait for transaction to confirm (paper fills are already settled)
+                                        if paper_trader.is_none() {
+                                            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                                        }
+
+                                        // Determine which wallet to check based on API mode
+                                        let check_wallet = if use_local_api {
+                                            signer.pubkey()
+                                        } else {
+                                            // For Lightning API, use the lightning wallet
+                                            Pubkey::from_str(&config.pumpportal.lightning_wallet)
+                                                .unwrap_or(signer.pubkey())
+                                        };
+
+                                        let actual_tokens = match paper_trader {
+                                            Some(ref paper_trader) => paper_trader.holdings(mint),
+                                            None => query_token_balance(&rpc_client, &check_wallet, mint),
+                                        };
+
+                                        if actual_tokens == 0 {
+                                            // Transaction may have failed - DON'T record position
+                                            error!(
+                                                "BUY VERIFICATION FAILED: No tokens received for {} ({}) after 2s wait. TX may have failed silently. NOT recording position.",
+                                                token.symbol, mint
+                                            );
+                                            error!("Check transaction on Solscan: https://solscan.io/tx/{}", signature);
+                                            // Skip position recording and kill-switch setup
+                                            slot.release();
+                                            record_buy_failure(strategy_engine.as_ref(), mint, first_buy_sol, submitted_at, "no tokens after buy").await;
+                                            decision_log.record_arbitrated(
+                                                mint,
+                                                &token.symbol,
+                                                entry_result,
+                                                DecisionAction::BuyFailed,
+                                                Some("no tokens after buy".to_string()),
+                                                entry_arbitration.as_ref(),
+                                            );
+                                            let _ = cooldowns
+                                                .mark(
+                                                    mint,
+                                                    crate::position::CooldownKind::Failed,
+                                                    Some("no tokens after buy".to_string()),
+                                                )
+                                                .await;
+                                            continue;
+                                        }
+
+                                        info!("BUY VERIFIED: Received {} tokens for {}", actual_tokens, token.symbol);
+                                        decision_log.record_arbitrated(
+                                            mint,
+                                            &token.symbol,
+                                            entry_result,
+                                            DecisionAction::Bought,
+                                            Some(format!("{:.4} SOL", first_buy_sol)),
+                                            entry_arbitration.as_ref(),
+                                        );
+
+                                        // Record position with ACTUAL token amount (not estimate)
+                                        let estimated_price = if token.v_tokens_in_bonding_curve > 0 {
+                                            token.v_sol_in_bonding_curve as f64 / token.v_tokens_in_bonding_curve as f64
+                                        } else {
+                                            0.000001 // fallback
+                                        };
+
+                                        // Convert recommendation to EntryType for context-aware exits
+                                        let entry_type = crate::position::manager::EntryType::from_recommendation(entry_recommendation);
+
+                                        let position = crate::position::manager::Position {
+                                            mint: token.mint.clone(),
+                                            name: token.name.clone(),
+                                            symbol: token.symbol.clone(),
+                                            bonding_curve: token.bonding_curve_key.clone(),
+                                            token_amount: actual_tokens, // Use ACTUAL tokens, not estimate
+                                            entry_price: estimated_price,
+                                            total_cost_sol: first_buy_sol,
+                                            entry_time: chrono::Utc::now(),
+                                            entry_signature: signature.clone(),
+                                            entry_type,
+                                            quick_profit_taken: false,
+                                            second_profit_taken: false,
+                                            peak_price: estimated_price,
+                                            trough_price: estimated_price,
+                                            current_price: estimated_price,
+                                            kill_switch_triggered: false,
+                                            kill_switch_reason: None,
+                                            wallet_pubkey: signer.pubkey().to_string(),
+                                            fills: vec![],
+                                            scale_in: scale_in_plan.clone().map(|mut plan| {
+                                                plan.mark_filled(0, &signature, estimated_price);
+                                                plan
+                                            }),
+                                            exit_override: Default::default(),
+                                            partial_fill: false,
+                                            regime: entry_regime.map(|(regime, _)| regime),
+                                            exit_style: select_exit_style(strategy_engine.as_ref(), entry_type, entry_regime.map(|(regime, _)| regime)).await,
+                                            exit_levels_hit: vec![],
+                                            paper: paper_trader.is_some(),
+                                            privileges: entry_privileges.clone(),
+                                            exit_liquidity: entry_liquidity.as_ref().and_then(|l| ExitLiquidity::at_entry(l, first_buy_sol, max_sell_impact_pct)),
+                                            slippage: Some(slippage),
+                                            strategy_profile: entry_profile.clone(),
+                                            holder_distribution: live_distribution.clone(),
+                                            fees: crate::position::TradeFees::estimate(
+                                                priority_fee,
+                                                0,
+                                                first_buy_sol,
+                                                platform_fee_pct,
+                                            ),
+                                        };
+
+                                        // Intended raw amount at the creation price (6 decimals)
+                                        let mut position = position;
+                                        if let Some(ref mut slippage) = position.slippage {
+                                            slippage.record_fill(actual_tokens);
+                                        }
+                                        if token.market_cap_sol > 0.0 {
+                                            let intended_tokens =
+                                                (first_buy_sol / (token.market_cap_sol / 1e9) * 1e6) as u64;
+                                            position.check_partial_fill(
+                                                intended_tokens,
+                                                config.positions.min_fill_ratio,
+                                            );
+                                            record_buy_fill(
+                                                strategy_engine.as_ref(),
+                                                mint,
+                                                &signature,
+                                                first_buy_sol,
+                                                intended_tokens,
+                                                actual_tokens,
+                                                submitted_at,
+                                                position.slippage,
+                                            )
+                                            .await;
+                                        }
+
+                                        let score = entry_scoring.as_ref().map(|(_, result)| score_summary(result));
+                                        if let Err(e) = slot.commit_scored(position, score).await {
+                                            error!("Failed to record position: {}", e);
+                                        } else {
+                                            if paper_trader.is_none() {
+                                                spawn_confirmation(
+                                                    rpc_client.clone(),
+                                                    position_manager.clone(),
+                                                    signature.clone(),
+                                                );
+                                            }
+                                            if let (Some(gate), Some(starter), Some((context, _))) =
+                                                (&momentum_gate, momentum_starter, entry_scoring.as_ref())
+                                            {
+                                                gate.watch(context.clone(), final_amount_sol - starter).await;
+                                            }
+                                            if let (Some(rescorer), Some((context, result))) =
+                                                (&rescorer, entry_scoring.take())
+                                            {
+                                                rescorer.schedule(context, result);
+                                            }
+                                            if scale_in_plan.is_some() {
+                                                spawn_scale_in_tranches(
+                                                    ScaleInContext {
+                                                        position_manager: position_manager.clone(),
+                                                        trader: trader.clone(),
+                                                        signer: signer.clone(),
+                                                        rpc_client: rpc_client.clone(),
+                                                        strategy_engine: strategy_engine.clone(),
+                                                        kill_switch: kill_switch_evaluator.clone(),
+                                                        use_local_api,
+                                                        check_wallet,
+                                                        slippage_pct,
+                                                        priority_fee,
+                                                        platform_fee_pct,
+                                                    },
+                                                    token.mint.clone(),
+                                                    token.trader_public_key.clone(),
+                                                );
+                                            }
+                                        }
+
+                                        // Start kill-switch monitoring for this position
+                                        if let Some(ref evaluator) = kill_switch_evaluator {
+                                            // Creator is the trader_public_key for new tokens
+                                            let creator = token.trader_public_key.clone();
+                                            // Holders arrive with the Critical enrichment refresh,
+                                            // which also resolves wallets holding the creator allocation
+                                            evaluator.watch_position(&token.mint, &creator, vec![]);
+                                            if let Some(ref enrichment) = enrichment_handle {
+                                                enrichment.request_enrichment(
+                                                    token.mint.clone(),
+                                                    creator.clone(),
+                                                    EnrichmentPriority::Critical,
+                                                );
+                                            }
+                                            info!(
+                                                "Kill-switch monitoring active for {} (creator: {})",
+                                                &token.mint[..12], &creator[..8]
+                                            );
+                                        }
+
+                                        // Record entry in strategy engine
+                                        if let Some(ref engine) = strategy_engine {
+                                            let strategy_position = crate::strategy::types::Position {
+                                                mint: token.mint.clone(),
+                                                entry_price: estimated_price,
+                                                entry_time: chrono::Utc::now(),
+                                                size_sol: first_buy_sol,
+                                                tokens_held: actual_tokens,
+                                                strategy: entry_regime
+                                                    .map_or(config.strategy.default_strategy, |(_, strategy)| strategy),
+                                                exit_style: crate::strategy::types::ExitStyle::default(),
+                                                highest_price: estimated_price,
+                                                lowest_price: estimated_price,
+                                                exit_levels_hit: vec![],
+                                                creator: Some(token.trader_public_key.clone()),
+                                                funding_cluster: funding_provider
+                                                    .as_ref()
+                                                    .and_then(|provider| provider.funding_cluster(&token.trader_public_key)),
+                                            };
+                                            engine.write().await.record_entry(strategy_position).await;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        error!("Buy failed for {}: {}", token.symbol, e);
+                                        record_buy_failure(strategy_engine.as_ref(), mint, first_buy_sol, submitted_at, &e.to_string()).await;
+                                        decision_log.record_arbitrated(mint, &token.symbol, entry_result, DecisionAction::BuyFailed, Some(e.to_string()), entry_arbitration.as_ref());
+                                    }
+                                }
+                            }
+                        } else {
+                            info!(
+                                "DRY-RUN: Would buy {} SOL of {} (strategy size)",
+                                final_amount_sol, token.mint
+                            );
+                            decision_log.record_arbitrated(
+                                &token.mint,
+                                &token.symbol,
+                                entry_scoring.as_ref().map(|(_, result)| result),
+                                DecisionAction::DryRun,
+                                Some(format!("{:.4} SOL", first_buy_sol)),
+                                entry_arbitration.as_ref(),
+                            );
+                        }
+                    }
+                    PumpPortalEvent::Trade(trade) => {
+                        if let Some(ref recorder) = recorder {
+                            recorder.record(crate::backtest::SessionEvent::Trade(trade.clone()));
+                        }
+                        let curve = crate::filter::honeypot::curve_from_event(trade.v_sol_in_bonding_curve as f64, trade.v_tokens_in_bonding_curve as f64);
+                        if let Some(ref paper_trader) = paper_trader {
+                            paper_trader.set_curve(&trade.mint, curve.clone());
+                        }
+                        slippage_estimator.record_curve(&trade.mint, curve);
+                        // Calculate SOL amount for logging
+                        let sol_amount = trade.sol_amount as f64 / 1e9;
+
+                        // Log all trades for visibility
+                        info!(
+                            "Trade: {} {} {:.6} SOL on {} (mcap: {:.2})",
+                            &trade.trader_public_key[..8],
+                            trade.tx_type,
+                            sol_amount,
+                            &trade.mint[..12],
+                            trade.market_cap_sol
+                        );
+
+                        // Early buys feed the launch's bundle detection window
+                        if let Some(detector) = bundled_detector.as_ref().filter(|_| trade.tx_type == "buy") {
+                            detector.record_early_buy(
+                                &trade.mint,
+                                EarlyBuy {
+                                    wallet: trade.trader_public_key.clone(),
+                                    amount_sol: trade.sol_amount,
+                                    slot: None, // Not provided by PumpPortal
+                                    timestamp: chrono::Utc::now(),
+                                    signature: trade.signature.clone(),
+                                },
+                            );
+                        }
+
+                        if let Some(discovery) = wallet_discovery.as_ref().filter(|_| trade.tx_type == "buy") {
+                            discovery.record_buy(&trade.mint, &trade.trader_public_key);
+                        }
+
+                        // Every trade updates sniper stats; a proven sniper's buy is an entry candidate
+                        let (piggyback_signal, piggyback_exit) = match piggyback {
+                            Some(ref tracker) => {
+                                let mut tracker = tracker.lock().await;
+                                let price = trade.market_cap_sol / 1_000_000_000.0;
+                                if trade.tx_type == "buy" {
+                                    tracker.record_sniper_buy(&trade.trader_public_key, &trade.mint, trade.sol_amount, price);
+                                    (tracker.on_sniper_buy(&trade.trader_public_key, &trade.mint, trade.sol_amount), false)
+                                } else {
+                                    tracker.record_sniper_sell(&trade.trader_public_key, &trade.mint, trade.sol_amount, price);
+                                    (None, tracker.on_sniper_sell(&trade.trader_public_key, &trade.mint))
+                                }
+                            }
+                            None => (None, false),
+                        };
+
+                        // The sniper we followed in is leaving: bias our position toward the exit
+                        if piggyback_exit {
+                            let trailing_pct = config.strategy.tactics.piggyback.exit_trailing_pct;
+                            info!(
+                                "Piggybacked sniper {} sold {} - tightening trailing stop to {:.1}%",
+                                &trade.trader_public_key[..8],
+                                trade.mint,
+                                trailing_pct
+                            );
+                            if let Err(e) = position_manager
+                                .set_exit_override(&trade.mint, None, None, Some(trailing_pct))
+                                .await
+                            {
+                                warn!("Piggyback exit bias failed for {}: {}", trade.mint, e);
+                            }
+                        }
+
+                        // Whale or clustered buying is an entry candidate (once per mint)
+                        let accumulation_signal = match frontrun {
+                            Some(ref detector) => detector.lock().await.on_trade(
+                                &trade.mint,
+                                crate::strategy::tactics::frontrun::TradeRecord {
+                                    timestamp: chrono::Utc::now(),
+                                    trader: trade.trader_public_key.clone(),
+                                    is_buy: trade.tx_type == "buy",
+                                    sol_amount: trade.sol_amount,
+                                    token_amount: trade.token_amount,
+                                },
+                            ),
+                            None => None,
+                        };
+
+                        // Starter positions waiting on momentum watch the same feed
+                        if let Some(ref gate) = momentum_gate {
+                            gate.record_trade(
+                                &trade.mint,
+                                trade.tx_type == "buy",
+                                trade.sol_amount,
+                                trade.token_amount,
+                                &trade.trader_public_key,
+                            )
+                            .await;
+                        }
+
+                        // Buffer the trade for order flow analysis
+                        if let Some(ref order_flow) = order_flow_provider {
+                            order_flow.record_trade(
+                                &trade.mint,
+                                &trade.trader_public_key,
+                                trade.tx_type == "buy",
+                                crate::pump::price::sol_to_lamports(trade.sol_amount),
+                                trade.token_amount as u64,
+                                &trade.signature,
+                            );
+                        }
+
+                        if let Some(ref watch) = rug_watch {
+                            watch.record_trade(&trade);
+                        }
+                        if let Some(ref watch) = flow_watch {
+                            watch.record_trade(&trade);
+                        }
+
+                        // Keep held (and shadow) positions priced from the trade feed (pump.fun supply is 1B tokens)
+                        if trade.market_cap_sol > 0.0 {
+                            position_manager
+                                .update_price(&trade.mint, trade.market_cap_sol / 1_000_000_000.0)
+                                .await;
+                            if let Some(ref shadows) = shadow_tracker {
+                                shadows
+                                    .update_price(&trade.mint, trade.market_cap_sol / 1_000_000_000.0)
+                                    .await;
+                            }
+                        }
+
+                        // KILL-SWITCH: a curve drain shows in the reserves before wallet heuristics fire
+                        let liquidity_decision = kill_switch_evaluator
+                            .as_ref()
+                            .map(|evaluator| evaluator.record_liquidity(&trade.mint, trade.v_sol_in_bonding_curve))
+                            .unwrap_or(KillSwitchDecision::Continue);
+
+                        // KILL-SWITCH: Check sells on tokens we hold
+                        if trade.tx_type == "sell" {
+                            // Check if we have a position in this token
+                            let positions = position_manager.get_all_positions().await;
+                            let our_position = positions.iter().find(|p| p.mint == trade.mint);
+
+                            if let Some(position) = our_position {
+                                let position_token_amount = position.token_amount;
+
+                                if let Some(ref evaluator) = kill_switch_evaluator {
+                                    let decision = match liquidity_decision {
+                                        KillSwitchDecision::Exit(alert) => KillSwitchDecision::Exit(alert),
+                                        KillSwitchDecision::Continue => evaluator.evaluate_sell(
+                                            &trade.mint,
+                                            &trade.trader_public_key,
+                                            trade.token_amount as u64,
+                                            sol_amount,
+                                            &trade.signature,
+                                        ),
+                                    };
+
+                                    if let KillSwitchDecision::Exit(alert) = decision {
+                                        warn!(
+                                            "KILL-SWITCH TRIGGERED for {}: {} - AUTO-SELLING",
+                                            &trade.mint[..12], alert.reason
+                                        );
+                                        notifier.notify(Notification::KillSwitch {
+                                            symbol: position.symbol.clone(),
+                                            mint: trade.mint.clone(),
+                                            reason: alert.reason.clone(),
+                                        });
+
+                                        // Never add to a position that is being exited
+                                        let _ = position_manager
+                                            .cancel_scale_in(&trade.mint, &format!("kill-switch: {}", alert.reason))
+                                            .await;
+
+                                        // Execute emergency sell if not dry run
+                                        if !dry_run {
+                                            // The monitor (or a buy) may be trading this mint right now
+                                            let sell_lock = position_manager.try_lock_sell(&trade.mint);
+                                            if sell_lock.is_none() {
+                                                warn!(
+                                                    "Kill-switch sell of {} left to the monitor: another trade of it is in progress",
+                                                    &trade.mint[..12]
+                                                );
+                                            } else if let Some(ref seller) = position_seller {
+                                                // Route and pricing come from the urgency's policy
+                                                let (policy_name, policy) =
+                                                    config.auto_sell.kill_switch_execution.policy(alert.urgency);
+
+                                                // Sell 100% immediately
+                                                info!(
+                                                    "Executing kill-switch sell for {} (urgency: {:?}, policy '{}': {:?})",
+                                                    &trade.mint[..12], alert.urgency, policy_name, policy
+                                                );
+
+                                                let triggered_at = std::time::Instant::now();
+                                                let sell_result =
+                                                    send_kill_switch_sell(seller.as_ref(), position, policy).await;
+
+                                                match sell_result {
+                                                    Ok(sig) => {
+                                                        warn!(
+                                                            "KILL-SWITCH SELL EXECUTED: {} - policy '{}', sent in {} ms - sig: {}",
+                                                            alert.reason,
+                                                            policy_name,
+                                                            triggered_at.elapsed().as_millis(),
+                                                            sig
+                                                        );
+                                                        crate::position::auto_sell::spawn_landing_check(
+                                                            rpc_client.clone(),
+                                                            format!("{} policy '{}'", &trade.mint[..12], policy_name),
+                                                            sig.clone(),
+                                                            triggered_at,
+                                                        );
+
+                                                        // Close position in manager (use position's token amount since we sold 100%)
+                                                        // Note: We don't know exact proceeds yet, estimate from current price
+                                                        let estimated_proceeds = position_token_amount as f64 * trade.market_cap_sol / 1_000_000_000.0;
+                                                        let exit = crate::position::ExitDetails::new(crate::position::TradeExitReason::KillSwitch)
+                                                            .with_signature(&sig)
+                                                            .with_fees(seller.estimate_fees(&sig, estimated_proceeds));
+                                                        match position_manager.close_position_with_exit(&trade.mint, position_token_amount, estimated_proceeds, exit).await {
+                                                            Ok(net_pnl) => {
+                                                                info!("Kill-switch exit P&L: {:+.4} SOL net of fees", net_pnl);
+                                                                if paper_trader.is_none() {
+                                                                    spawn_confirmation(rpc_client.clone(), position_manager.clone(), sig.clone());
+                                                                }
+                                                            }
+                                                            Err(e) => error!("Failed to close position after kill-switch: {}", e),
+                                                        }
+
+                                                        // Never re-enter a token that triggered a kill-switch
+                                                        let _ = cooldowns
+                                                            .mark(
+                                                                &trade.mint,
+                                                                crate::position::CooldownKind::Rugged,
+                                                                Some(alert.reason.clone()),
+                                                            )
+                                                            .await;
+
+                                                        // ...or any other token from its deployer
+                                                        if let Some(creator) = evaluator.deployer_tracker().get_deployer(&trade.mint) {
+                                                            rug_recorder.record_rug(&creator, &trade.mint, &alert.reason).await;
+                                                        }
+
+                                                        // Stop monitoring this position
+                                                        evaluator.unwatch_position(&trade.mint);
+                                                    }
+                                                    Err(e) => {
+                                                        error!(
+                                                            "KILL-SWITCH SELL FAILED for {}: {} - MANUAL EXIT NEEDED!",
+                                                            &trade.mint[..12], e
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                        } else {
+                                            warn!(
+                                                "DRY-RUN: Kill-switch would sell 100% of {} (reason: {})",
+                                                &trade.mint[..12], alert.reason
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Check for tracked wallet trades (copy trading)
+                        let tracked = wallet_tracker.is_tracked_address(&trade.trader_public_key)
+                            || copy_discovered
+                                .as_ref()
+                                .is_some_and(|d| d.is_trusted(&trade.trader_public_key));
+                        if config.wallet_tracking.enabled && tracked {
+                            info!(
+                                "Tracked wallet {} {} {:.4} SOL of {}",
+                                trade.trader_public_key,
+                                if trade.tx_type == "buy" { "bought" } else { "sold" },
+                                sol_amount,
+                                trade.mint
+                            );
+
+                            let copy = wallet_tracker.copy_settings(&trade.trader_public_key);
+                            let sold_fraction = if trade.tx_type == "buy" {
+                                tracked_holdings.record_buy(&trade.trader_public_key, &trade.mint, trade.token_amount as u64);
+                                None
+                            } else {
+                                tracked_holdings.record_sell(&trade.trader_public_key, &trade.mint, trade.token_amount as u64)
+                            };
+
+                            // Mirror a sell as the same share of our copy position
+                            if let Some(fraction) = sold_fraction.filter(|_| copy.enabled && copy.mirror_sells) {
+                                let copied = position_manager
+                                    .get_position(&trade.mint)
+                                    .await
+                                    .filter(|p| p.entry_type == crate::position::manager::EntryType::CopyTrade);
+                                if copied.is_some() {
+                                    let signal = crate::position::auto_sell::ExitSignal::mirror(fraction, &trade.trader_public_key);
+                                    if dry_run {
+                                        warn!("DRY-RUN: Would sell {} of {} ({})", signal.layer.sell_pct(), trade.mint, signal.reason);
+                                    } else {
+                                        info!("Mirroring sell: {} of {}", signal.layer.sell_pct(), trade.mint);
+                                        let request = crate::position::auto_sell::ExitRequest {
+                                            mint: trade.mint.clone(),
+                                            signal,
+                                        };
+                                        if exit_request_tx.send(request).await.is_err() {
+                                            warn!("Mirrored sell for {} dropped: position monitor not running", trade.mint);
+                                        }
+                                    }
+                                }
+                            }
+
+                            // Copy the trade if it's a buy
+                            if trade.tx_type == "buy" && copy.enabled && !dry_run {
+                                // One trade per mint at a time; re-check for a position under the lock
+                                let mint_lock = position_manager.try_lock_mint(&trade.mint, "copy-trade");
+                                let held = mint_lock.is_some() && position_manager.get_position(&trade.mint).await.is_some();
+                                if mint_lock.is_none() {
+                                    info!("Copy trade skipped for {}: another trade of it is in progress", trade.mint);
+                                } else if held {
+                                    info!("Copy trade skipped for {}: already have a position", trade.mint);
+                                } else if let Some(hit) = cooldowns.check(&trade.mint).await {
+                                    info!("Copy trade skipped for {}: {}", trade.mint, hit);
+                                } else if let Some(ref trader) = trader_arc {
+                                    let priority_fee = buy_priority_fee(config, &chain_fee_floor);
+
+                                    // Proportional to their buy, capped by max_copy_sol
+                                    let copy_amount_sol = copy.copy_amount_sol(sol_amount, buy_amount.get());
+                                    let (copy_amount_sol, priority_fee) =
+                                        randomize_buy(strategy_engine.as_ref(), copy_amount_sol, priority_fee).await;
+                                    let slippage = buy_slippage(config, &slippage_estimator, strategy_engine.as_ref(), &trade.mint, copy_amount_sol).await;
+                                    let slippage_pct = slippage.pct();
+                                    match position_manager.try_reserve_slot(copy_amount_sol).await {
+                                        Err(e) => warn!("Copy trade skipped for {}: {}", trade.mint, e),
+                                        Ok(slot) => {
+                                            apply_entry_delay(strategy_engine.as_ref()).await;
+                                            info!("Copy trading: buying {} SOL of {}", copy_amount_sol, trade.mint);
+                                            let submitted_at = std::time::Instant::now();
+                                            let copy_result = send_buy(trader, paper_trader.as_deref(), use_local_api, &trade.mint, copy_amount_sol, slippage_pct, priority_fee, &signer, &rpc_client).await;
+                                            match copy_result {
+                                                Ok(sig) => {
+                                                    info!("Copy trade executed: {}", sig);
+                                                    wallet_tracker.record_copy(&trade.trader_public_key, &trade.mint);
+                                                    // Track the copy so it counts against limits and gets exits
+                                                    let estimated_price = if trade.market_cap_sol > 0.0 {
+                                                        trade.market_cap_sol / 1_000_000_000.0
+                                                    } else {
+                                                        0.000001
+                                                    };
+                                                    let position = crate::position::manager::Position {
+                                                        mint: trade.mint.clone(),
+                                                        name: format!("Copy-{}", &trade.mint[..8]),
+                                                        symbol: "???".to_string(),
+                                                        bonding_curve: trade.bonding_curve_key.clone(),
+                                                        token_amount: match paper_trader {
+                                                            Some(ref paper_trader) => paper_trader.holdings(&trade.mint),
+                                                            None => (copy_amount_sol / estimated_price) as u64,
+                                                        },
+                                                        entry_price: estimated_price,
+                                                        total_cost_sol: copy_amount_sol,
+                                                        entry_time: chrono::Utc::now(),
+                                                        entry_signature: sig.clone(),
+                                                        entry_type: crate::position::manager::EntryType::CopyTrade,
+                                                        quick_profit_taken: false,
+                                                        second_profit_taken: false,
+                                                        peak_price: estimated_price,
+                                                        trough_price: estimated_price,
+                                                        current_price: estimated_price,
+                                                        kill_switch_triggered: false,
+                                                        kill_switch_reason: None,
+                                                        wallet_pubkey: signer.pubkey().to_string(),
+                                                        fills: vec![],
+                                                        scale_in: None,
+                                                        exit_override: Default::default(),
+                                                        partial_fill: false,
+                                                        regime: None,
+                                                        exit_style: select_exit_style(
+                                                            strategy_engine.as_ref(),
+                                                            crate::position::manager::EntryType::CopyTrade,
+                                                            None,
+                                                        )
+                                                        .await,
+                                                        exit_levels_hit: vec![],
+                                                        paper: paper_trader.is_some(),
+                                                        privileges: None,
+                                                        exit_liquidity: None,
+                                                        slippage: Some(slippage),
+                                                        strategy_profile: None,
+                                                        holder_distribution: None,
+                                                        fees: crate::position::TradeFees::estimate(
+                                                            priority_fee,
+                                                            0,
+                                                            copy_amount_sol,
+                                                            platform_fee_pct,
+                                                        ),
+                                                    };
+                                                    let intended_tokens = position.token_amount;
+                                                    if let Err(e) = slot.commit(position).await {
+                                                        error!("Failed to record copy trade position: {}", e);
+                                                    } else if paper_trader.is_none() {
+                                                        let confirmation = spawn_confirmation(rpc_client.clone(), position_manager.clone(), sig.clone());
+                                                        spawn_fill_check(
+                                                            confirmation,
+                                                            position_manager.clone(),
+                                                            strategy_engine.clone(),
+                                                            trade.mint.clone(),
+                                                            sig,
+                                                            copy_amount_sol,
+                                                            intended_tokens,
+                                                            submitted_at,
+                                                            config.positions.min_fill_ratio,
+                                                        );
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    error!("Copy trade failed: {}", e);
+                                                    record_buy_failure(strategy_engine.as_ref(), &trade.mint, copy_amount_sol, submitted_at, &e.to_string()).await;
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Evaluate tokens with significant buy volume that we haven't seen before
+                        let entry_signal = piggyback_signal.is_some() || accumulation_signal.is_some();
+                        if trade.tx_type == "buy" && (sol_amount >= 0.05 || entry_signal) {
+                            let mut seen = seen_trade_tokens.lock().await;
+
+                            // Skip if we've already evaluated this token (piggyback and accumulation signals re-evaluate it)
+                            if seen.contains(&trade.mint) && !entry_signal {
+                                continue;
+                            }
+
+                            // Check if we already have a position in this token
+                            let positions = position_manager.get_all_positions().await;
+                            if positions.iter().any(|p| p.mint == trade.mint) {
+                                seen.insert(trade.mint.clone());
+                                continue;
+                            }
+
+                            // Mark as seen
+                            seen.insert(trade.mint.clone());
+                            drop(seen); // Release lock before async operations
+
+                            info!(
+                                "Trade detected: {} bought {:.4} SOL of {} (mcap: {:.2} SOL) - evaluating...",
+                                &trade.trader_public_key[..8],
+                                sol_amount,
+                                trade.mint,
+                                trade.market_cap_sol
+                            );
+                            if let Some(ref signal) = piggyback_signal {
+                                info!(
+                                    "Piggyback candidate: sniper win rate {:.0}%, R-multiple {}, size x{:.2}",
+                                    signal.sniper_win_rate * 100.0,
+                                    signal.r_multiple.map_or("n/a".to_string(), |r| format!("{:+.2}", r)),
+                                    signal.size_multiplier
+                                );
+                            }
+                            if let Some(ref signal) = accumulation_signal {
+                                info!(
+                                    "Accumulation candidate: {:.2} SOL from {} buyers ({:.2}% of supply), confidence {:.2}",
+                                    signal.total_accumulated_sol,
+                                    signal.unique_buyers,
+                                    signal.float_pct(),
+                                    signal.confidence
+                                );
+                                // Shadowed from detection so every skip path is covered; dropped if we buy
+                                if let Some(ref shadows) = shadow_tracker {
+                                    shadows
+                                        .open_from(
+                                            DiscoverySource::Accumulation,
+                                            &trade.mint,
+                                            &trade.mint[..8],
+                                            Recommendation::Observe,
+                                            trade.market_cap_sol / 1_000_000_000.0,
+                                        )
+                                        .await;
+                                }
+                            }
+                            let (origin, discovery_source) = match (&piggyback_signal, &accumulation_signal) {
+                                (Some(_), _) => ("piggyback", DiscoverySource::Piggyback),
+                                (None, Some(_)) => ("accumulation", DiscoverySource::Accumulation),
+                                (None, None) => ("trade signal", DiscoverySource::TradeEvent),
+                            };
+
+                            // Calculate liquidity from bonding curve
+                            // Note: PumpPortal sends values in SOL, not lamports
+                            let liquidity_sol = if trade.v_sol_in_bonding_curve < 1000.0 {
+                                trade.v_sol_in_bonding_curve as f64
+                            } else {
+                                trade.v_sol_in_bonding_curve as f64 / 1e9
+                            };
+
+                            // Quick liquidity check
+                            if liquidity_sol < 0.05 {
+                                info!("Token {} rejected: liquidity {:.4} SOL too low", trade.mint, liquidity_sol);
+                                continue;
+                            }
+
+                            // Fast mode keeps the old volume-only entry
+                            let fast_entry = config.trading.fast_trade_entries;
+
+                            // The trade event has no name/symbol/creator: fetch them for the filters
+                            let (metadata, creator) = match helius_client {
+                                Some(ref helius) if !fast_entry => {
+                                    let (metadata, creator) = tokio::join!(
+                                        helius.get_asset_metadata(&trade.mint),
+                                        helius.get_token_creator(&trade.mint)
+                                    );
+                                    if let Err(ref e) = metadata {
+                                        tracing::debug!("Metadata unavailable for {}: {}", trade.mint, e);
+                                    }
+                                    (metadata.unwrap_or_default(), creator.unwrap_or_default())
+                                }
+                                _ => (crate::filter::AssetMetadata::default(), String::new()),
+                            };
+                            let symbol = metadata.symbol.clone();
+
+                            let curve = crate::filter::honeypot::curve_from_event(
+                                trade.v_sol_in_bonding_curve,
+                                trade.v_tokens_in_bonding_curve,
+                            );
+                            let mut signal_context = SignalContext::from_new_token(
+                                trade.mint.clone(),
+                                metadata.name.clone(),
+                                metadata.symbol.clone(),
+                                metadata.uri.clone(),
+                                creator.clone(),
+                                trade.bonding_curve_key.clone(),
+                                0,
+                                curve.virtual_token_reserves,
+                                curve.virtual_sol_reserves,
+                                trade.market_cap_sol,
+                            )
+                            .with_discovery(discovery_source, None);
+                            signal_context.accumulation = accumulation_signal.as_ref().map(|s| s.metrics());
+
+                            // Same name/symbol and curve filters as new tokens
+                            if !fast_entry && config.filters.enabled {
+                                use crate::filter::token_filter::FilterResult;
+
+                                if !symbol.is_empty() {
+                                    let filter_event = crate::stream::decoder::TokenCreatedEvent {
+                                        signature: trade.signature.clone(),
+                                        slot: 0,
+                                        mint: Pubkey::from_str(&trade.mint).unwrap_or_default(),
+                                        name: metadata.name.clone(),
+                                        symbol: symbol.clone(),
+                                        uri: metadata.uri.clone(),
+                                        bonding_curve: Pubkey::from_str(&trade.bonding_curve_key).unwrap_or_default(),
+                                        associated_bonding_curve: Pubkey::default(),
+                                        creator: Pubkey::from_str(&creator).unwrap_or_default(),
+                                        timestamp: chrono::Utc::now(),
+                                    };
+                                    if let FilterResult::Filtered(reason) = token_filter.filter(&filter_event) {
+                                        info!("Trade token {} filtered out: {}", symbol, reason);
+                                        continue;
+                                    }
+                                }
+                                if let FilterResult::Filtered(reason) =
+                                    token_filter.check_bonding_curve(signal_context.curve_progress_pct)
+                                {
+                                    info!("Trade token {} filtered out: {}", trade.mint, reason);
+                                    continue;
+                                }
+                            }
+
+                            // Trading windows and the launch-rate entry cap
+                            if let Some(reason) = throttle.check_entry(chrono::Utc::now()) {
+                                info!("Trade signal skipped for {}: {}", trade.mint, reason);
+                                decision_log.record(&trade.mint, &symbol, None, DecisionAction::Throttled, Some(reason));
+                                continue;
+                            }
+
+                            // Hot wallet balance below the entry floor
+                            if let Some(reason) = balance_watch.as_ref().and_then(|w| w.entry_block_reason()) {
+                                warn!("Trade signal skipped for {}: {}", trade.mint, reason);
+                                decision_log.record(&trade.mint, &symbol, None, DecisionAction::Throttled, Some(reason));
+                                continue;
+                            }
+
+                            // Adaptive filter (creation-time verdict is reused when cached;
+                            // accumulation candidates score fresh so their signal counts)
+                            let trade_scoring = if let Some(ref filter) = adaptive_filter {
+                                let mut result = if signal_context.accumulation.is_some() {
+                                    filter.score_fast(&signal_context).await
+                                } else {
+                                    filter.score_fast_cached(&signal_context).await
+                                };
+                                if throttle.regrade(&mut result, &filter.thresholds()) {
+                                    info!("Launch surge: {} regraded to {:?}", trade.mint, result.recommendation);
+                                }
+                                info!(
+                                    "Adaptive filter: {} score={:.2} recommendation={:?} curve={:.0}% ({})",
+                                    trade.mint,
+                                    result.score,
+                                    result.recommendation,
+                                    signal_context.curve_progress_pct,
+                                    if result.cached { "cached" } else { "fresh" }
+                                );
+                                if result.recommendation == Recommendation::Avoid {
+                                    warn!("Token {} marked AVOID by adaptive filter: {}", trade.mint, result.summary);
+                                    decision_log.record(&trade.mint, &symbol, Some(&result), DecisionAction::Avoid, None);
+                                    continue;
+                                }
+                                // Anything below Probe is watch-only
+                                if !fast_entry && !result.allows_any_trading() {
+                                    info!("Token {} marked OBSERVE by adaptive filter: {}", trade.mint, result.summary);
+                                    decision_log.record(&trade.mint, &symbol, Some(&result), DecisionAction::Observe, None);
+                                    continue;
+                                }
+                                Some(result)
+                            } else {
+                                None
+                            };
+                            let position_multiplier = trade_scoring
+                                .as_ref()
+                                .map(|r| r.position_size_multiplier)
+                                .unwrap_or(1.0);
+
+                            // Strategy engine: pause, portfolio limits and entry decision
+                            let mut entry_privileges = None;
+                            let mut entry_arbitration = None;
+                            let mut entry_liquidity = None;
+                            let mut entry_profile = None;
+                            let mut max_sell_impact_pct = config.strategy.position_sizing.max_sell_impact_pct();
+                            let tactic = match (&piggyback_signal, &accumulation_signal) {
+                                (Some(signal), _) => Some(("piggyback", signal.confidence)),
+                                (None, Some(signal)) => Some(("accumulation", signal.confidence)),
+                                (None, None) => None,
+                            };
+                            let outside_proposals = entry_proposals(trade_scoring.as_ref(), tactic, kill_switch_evaluator.as_deref(), &trade.mint);
+                            let (strategy_size, entry_regime) = match strategy_engine {
+                                Some(ref engine) if !fast_entry => {
+                                    let mut engine_guard = engine.write().await;
+
+                                    if engine_guard.should_pause_trading().await {
+                                        warn!("Strategy engine paused trading - skipping trade signal for {}", trade.mint);
+                                        continue;
+                                    }
+                                    let portfolio_state = engine_guard.get_portfolio_state().await;
+                                    if !portfolio_state.can_open_new {
+                                        warn!(
+                                            "Portfolio limit reached - skipping trade signal for {}: {:?}",
+                                            trade.mint, portfolio_state.reason_if_blocked
+                                        );
+                                        // Blocked before the engine ran: the governor overrides the tactic / filter
+                                        let mut proposals = outside_proposals.clone();
+                                        proposals.push(Proposal::new(
+                                            DecisionSource::PortfolioRisk,
+                                            ProposedAction::Skip,
+                                            1.0,
+                                            portfolio_state.reason_if_blocked.clone().unwrap_or_default(),
+                                        ));
+                                        let arbitration = crate::strategy::types::ArbitrationRecord {
+                                            proposals,
+                                            winner: DecisionSource::PortfolioRisk,
+                                            action: ProposedAction::Skip,
+                                            rule: "portfolio_block".to_string(),
+                                        };
+                                        decision_log.record_arbitrated(
+                                            &trade.mint,
+                                            &symbol,
+                                            trade_scoring.as_ref(),
+                                            DecisionAction::NoSlot,
+                                            portfolio_state.reason_if_blocked.clone(),
+                                            Some(&arbitration),
+                                        );
+                                        continue;
+                                    }
+
+                                    let order_flow = order_flow_provider
+                                        .as_ref()
+                                        .and_then(|provider| provider.analyze(&trade.mint))
+                                        .map(|flow| crate::strategy::regime::OrderFlowAnalysis::from(&flow))
+                                        .unwrap_or(crate::strategy::regime::OrderFlowAnalysis {
+                                            organic_score: position_multiplier.max(0.5),
+                                            wash_trading_score: 0.0,
+                                            buy_sell_ratio: 1.0,
+                                            early_sell_pressure: 0.0,
+                                            burst_detected: false,
+                                            burst_intensity: 0.0,
+                                            organic_demand: None,
+                                        });
+                                    let live_distribution = match distribution_provider {
+                                        Some(ref provider) if !creator.is_empty() => {
+                                            provider
+                                                .distribution(&trade.mint, &creator, &trade.bonding_curve_key)
+                                                .await
+                                        }
+                                        _ => None,
+                                    };
+                                    let distribution = match live_distribution {
+                                        Some(ref dist) => crate::strategy::regime::TokenDistribution::from(dist),
+                                        None => crate::strategy::regime::TokenDistribution {
+                                            holder_count: 1,
+                                            top_holder_pct: 100.0,
+                                            top_10_holders_pct: 100.0,
+                                            deployer_holdings_pct: 0.0,
+                                            sniper_holdings_pct: 0.0,
+                                            gini_coefficient: 1.0,
+                                        },
+                                    };
+
+                                    let analysis_ctx = crate::strategy::engine::TokenAnalysisContext {
+                                        mint: trade.mint.clone(),
+                                        creator: creator.clone(),
+                                        funding_cluster: funding_provider
+                                            .as_ref()
+                                            .and_then(|provider| provider.funding_cluster(&creator)),
+                                        order_flow,
+                                        distribution,
+                                        creator_behavior: crate::strategy::regime::CreatorBehavior {
+                                            selling_consistently: false,
+                                            total_sold_pct: 0.0,
+                                            avg_sell_interval_secs: 0,
+                                            sell_count: 0,
+                                        },
+                                        price_action: crate::strategy::price_action::PriceAction::default(),
+                                        sol_reserves: liquidity_sol,
+                                        token_reserves: curve.virtual_token_reserves as f64,
+                                        confidence_score: position_multiplier,
+                                        round_trip_excess_loss_pct: None,
+                                        privileges: privilege_checker.lookup(&privilege_rpc, &trade.mint).await,
+                                        proposals: outside_proposals.clone(),
+                                        equity: entry_equity(engine_guard.position_sizing(), &position_manager, balance_watch.as_deref(), &rpc_client, &trading_wallet).await,
+                                    };
+
+                                    let eval = engine_guard.evaluate_entry(&analysis_ctx).await;
+                                    entry_privileges = eval.privileges.clone();
+                                    entry_arbitration = eval.decision.arbitration.clone();
+                                    entry_liquidity = Some(eval.liquidity.clone());
+                                    entry_profile = Some(engine_guard.active_profile().to_string());
+                                    max_sell_impact_pct = engine_guard.position_sizing().max_sell_impact_pct();
+                                    let regime = Some((eval.regime.regime.kind(), eval.explanation.selected_strategy));
+                                    match &eval.decision.action {
+                                        TradingAction::Enter { size_sol, strategy, .. } => {
+                                            info!(
+                                                "Strategy engine: ENTER {} (trade signal) using {} strategy ({} regime), size: {:.4} SOL",
+                                                trade.mint, strategy, eval.regime.regime.kind(), size_sol
+                                            );
+                                            (*size_sol, regime)
+                                        }
+                                        TradingAction::FatalReject { reason } | TradingAction::Skip { reason } => {
+                                            info!("Strategy engine: SKIP trade signal for {}: {}", trade.mint, reason);
+                                            decision_log.record_arbitrated(
+                                                &trade.mint,
+                                                &symbol,
+                                                trade_scoring.as_ref(),
+                                                DecisionAction::StrategySkip,
+                                                Some(reason.clone()),
+                                                entry_arbitration.as_ref(),
+                                            );
+                                            continue;
+                                        }
+                                        _ => (buy_amount.get() * position_multiplier, regime),
+                                    }
+                                }
+                                _ if fast_entry => (buy_amount.get(), None),
+                                _ => (buy_amount.get() * position_multiplier, None),
+                            };
+
+                            // Piggyback entries scale with the sniper's track record
+                            let final_amount_sol = strategy_size
+                                * piggyback_signal.as_ref().map_or(1.0, |s| s.size_multiplier)
+                                * window_size_multiplier(&throttle, &symbol)
+                                * advisor_size_multiplier(advisor.as_ref(), &symbol).await;
+                            let entry_type = trade_scoring
+                                .as_ref()
+                                .map(|r| crate::position::manager::EntryType::from_recommendation(r.recommendation))
+                                .unwrap_or_default();
+
+                            info!(
+                                "Trade signal: BUY {:.4} SOL of {} (liquidity: {:.4} SOL, entry: {:?})",
+                                final_amount_sol, trade.mint, liquidity_sol, entry_type
+                            );
+
+                            if !dry_run {
+                                if let Some(ref trader) = trader_arc {
+                                    let priority_fee = buy_priority_fee(config, &chain_fee_floor);
+                                    let (final_amount_sol, priority_fee) =
+                                        randomize_buy(strategy_engine.as_ref(), final_amount_sol, priority_fee).await;
+                                    let slippage = buy_slippage(config, &slippage_estimator, strategy_engine.as_ref(), &trade.mint, final_amount_sol).await;
+                                    let slippage_pct = slippage.pct();
+
+                                    // One trade per mint at a time; re-check for a position under the lock
+                                    let Some(_mint_lock) = position_manager.try_lock_mint(&trade.mint, "trade-signal") else {
+                                        info!("Trade signal skipped for {}: another trade of it is in progress", trade.mint);
+                                        continue;
+                                    };
+                                    if position_manager.get_position(&trade.mint).await.is_some() {
+                                        info!("Trade signal skipped for {}: already have a position", trade.mint);
+                                        continue;
+                                    }
+
+                                    if let Some(hit) = cooldowns.check(&trade.mint).await {
+                                        info!("Trade signal skipped for {}: {}", trade.mint, hit);
+                                        decision_log.record_arbitrated(&trade.mint, &symbol, trade_scoring.as_ref(), DecisionAction::Cooldown, Some(hit.to_string()), entry_arbitration.as_ref());
+                                        continue;
+                                    }
+
+                                    let slot = match position_manager.try_reserve_slot(final_amount_sol).await {
+                                        Ok(slot) => slot,
+                                        Err(e) => {
+                                            warn!("Trade signal skipped for {}: {}", trade.mint, e);
+                                            decision_log.record_arbitrated(&trade.mint, &symbol, trade_scoring.as_ref(), DecisionAction::NoSlot, Some(e.to_string()), entry_arbitration.as_ref());
+                                            continue;
+                                        }
+                                    };
+                                    throttle.record_entry(chrono::Utc::now());
+
+                                    apply_entry_delay(strategy_engine.as_ref()).await;
+                                    let submitted_at = std::time::Instant::now();
+                                    let buy_result = send_buy(trader, paper_trader.as_deref(), use_local_api, &trade.mint, final_amount_sol, slippage_pct, priority_fee, &signer, &rpc_client).await;
+                                    match buy_result {
+                                        Ok(sig) => {
+                                            info!("Trade buy executed: {}", sig);
+                                            if let (Some(shadows), Some(_)) = (&shadow_tracker, &accumulation_signal) {
+                                                shadows.discard(&trade.mint).await;
+                                            }
+                                            decision_log.record_arbitrated(
+                                                &trade.mint,
+                                                &symbol,
+                                                trade_scoring.as_ref(),
+                                                DecisionAction::Bought,
+                                                Some(format!("{:.4} SOL ({})", final_amount_sol, origin)),
+                                                entry_arbitration.as_ref(),
+                                            );
+                                            // Estimate tokens from market cap
+                                            let estimated_price = if trade.market_cap_sol > 0.0 {
+                                                trade.market_cap_sol / 1_000_000_000.0
+                                            } else {
+                                                0.000001
+                                            };
+                                            // Exact curve quote (fee and price impact included), in whole tokens
+                                            let quoted = crate::pump::price::quote_buy(
+                                                &crate::filter::honeypot::curve_from_event(trade.v_sol_in_bonding_curve, trade.v_tokens_in_bonding_curve),
+                                                crate::pump::price::sol_to_lamports(final_amount_sol),
+                                            );
+                                            let estimated_tokens = match (&paper_trader, quoted) {
+                                                (Some(paper_trader), _) => paper_trader.holdings(&trade.mint),
+                                                (None, Ok(quote)) => quote.tokens_out / 1_000_000,
+                                                (None, Err(_)) => (final_amount_sol / estimated_price) as u64,
+                                            };
+
+                                            // Record position with the tier the filter actually gave it
+                                            let position = crate::position::manager::Position {
+                                                mint: trade.mint.clone(),
+                                                name: if metadata.name.is_empty() {
+                                                    format!("Trade-{}", &trade.mint[..8])
+                                                } else {
+                                                    metadata.name.clone()
+                                                },
+                                                symbol: if symbol.is_empty() { "???".to_string() } else { symbol.clone() },
+                                                bonding_curve: trade.bonding_curve_key.clone(),
+                                                token_amount: estimated_tokens,
+                                                entry_price: estimated_price,
+                                                total_cost_sol: final_amount_sol,
+                                                entry_time: chrono::Utc::now(),
+                                                entry_signature: sig.clone(),
+                                                entry_type,
+                                                quick_profit_taken: false,
+                                                second_profit_taken: false,
+                                                peak_price: estimated_price,
+                                                trough_price: estimated_price,
+                                                current_price: estimated_price,
+                                                kill_switch_triggered: false,
+                                                kill_switch_reason: None,
+                                                wallet_pubkey: signer.pubkey().to_string(),
+                                                fills: vec![],
+                                                scale_in: None,
+                                                exit_override: Default::default(),
+                                                partial_fill: false,
+                                                regime: entry_regime.map(|(regime, _)| regime),
+                                                exit_style: select_exit_style(strategy_engine.as_ref(), entry_type, entry_regime.map(|(regime, _)| regime)).await,
+                                                exit_levels_hit: vec![],
+                                                paper: paper_trader.is_some(),
+                                                privileges: entry_privileges.clone(),
+                                                exit_liquidity: entry_liquidity.as_ref().and_then(|l| ExitLiquidity::at_entry(l, final_amount_sol, max_sell_impact_pct)),
+                                                slippage: Some(slippage),
+                                                strategy_profile: entry_profile.clone(),
+                                                holder_distribution: live_distribution.clone(),
+                                                fees: crate::position::TradeFees::estimate(
+                                                    priority_fee,
+                                                    0,
+                                                    final_amount_sol,
+                                                    platform_fee_pct,
+                                                ),
+                                            };
+                                            let score = trade_scoring.as_ref().map(score_summary);
+                                            if let Err(e) = slot.commit_scored(position, score).await {
+                                                error!("Failed to record position: {}", e);
+                                            } else {
+                                                if paper_trader.is_none() {
+                                                    let confirmation = spawn_confirmation(rpc_client.clone(), position_manager.clone(), sig.clone());
+                                                    spawn_fill_check(
+                                                        confirmation,
+                                                        position_manager.clone(),
+                                                        strategy_engine.clone(),
+                                                        trade.mint.clone(),
+                                                        sig,
+                                                        final_amount_sol,
+                                                        estimated_tokens,
+                                                        submitted_at,
+                                                        config.positions.min_fill_ratio,
+                                                    );
+                                                }
+
+                                                if let (Some(tracker), Some(signal)) = (&piggyback, &piggyback_signal) {
+                                                    tracker.lock().await.follow(&trade.mint, &signal.sniper_address);
+                                                }
+
+                                                // Kill-switch deployer monitoring needs the creator
+                                                if let Some(ref evaluator) = kill_switch_evaluator {
+                                                    if !creator.is_empty() {
+                                                        evaluator.watch_position(&trade.mint, &creator, vec![]);
+                                                        if let Some(ref enrichment) = enrichment_handle {
+                                                            enrichment.request_enrichment(
+                                                                trade.mint.clone(),
+                                                                creator.clone(),
+                                                                EnrichmentPriority::Critical,
+                                                            );
+                                                        }
+                                                    }
+                                                }
+
+                                                if let Some(ref engine) = strategy_engine {
+                                                    let strategy_position = crate::strategy::types::Position {
+                                                        mint: trade.mint.clone(),
+                                                        entry_price: estimated_price,
+                                                        entry_time: chrono::Utc::now(),
+                                                        size_sol: final_amount_sol,
+                                                        tokens_held: estimated_tokens,
+                                                        strategy: entry_regime
+                                                            .map_or(config.strategy.default_strategy, |(_, strategy)| strategy),
+                                                        exit_style: crate::strategy::types::ExitStyle::default(),
+                                                        highest_price: estimated_price,
+                                                        lowest_price: estimated_price,
+                                                        exit_levels_hit: vec![],
+                                                        creator: Some(creator.clone()).filter(|c| !c.is_empty()),
+                                                        funding_cluster: funding_provider
+                                                            .as_ref()
+                                                            .and_then(|provider| provider.funding_cluster(&creator)),
+                                                    };
+                                                    engine.write().await.record_entry(strategy_position).await;
+                                                }
+                                            }
+                                        }
+                                        Err(e) => {
+                                            error!("Trade buy failed: {}", e);
+                                            record_buy_failure(strategy_engine.as_ref(), &trade.mint, final_amount_sol, submitted_at, &e.to_string()).await;
+                                            decision_log.record_arbitrated(&trade.mint, &symbol, trade_scoring.as_ref(), DecisionAction::BuyFailed, Some(e.to_string()), entry_arbitration.as_ref());
+                                        }
+                                    }
+                                }
+                            } else {
+                                info!(
+                                    "DRY-RUN: Would buy {:.4} SOL of {} based on trade activity",
+                                    final_amount_sol, trade.mint
+                                );
+                                decision_log.record_arbitrated(
+                                    &trade.mint,
+                                    &symbol,
+                                    trade_scoring.as_ref(),
+                                    DecisionAction::DryRun,
+                                    Some(format!("{:.4} SOL ({})", final_amount_sol, origin)),
+                                    entry_arbitration.as_ref(),
+                                );
+                            }
+                        }
+                    }
+                    PumpPortalEvent::Connected => {
+                        info!(
+                            "Connected to token detection source ({} duplicate new tokens suppressed so far)",
+                            new_token_dedup.suppressed()
+                        );
+                        stream_watch.connected();
+                        new_token_dedup.reconnected(std::time::Instant::now());
+                    }
+                    PumpPortalEvent::Disconnected => {
+                        warn!("Disconnected from token detection source");
+                        stream_watch.disconnected();
+                    }
+                    PumpPortalEvent::Error(e) => {
+                        error!("Token detection error: {}", e);
+                    }
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                info!("Received shutdown signal");
+                let _ = extractor_shutdown.send(());
+                graceful_shutdown(
+                    config,
+                    &position_manager,
+                    &exit_request_tx,
+                    (!paper).then_some((rpc_client.as_ref(), &trading_wallet)),
+                    flatten || config.shutdown.flatten_positions,
+                )
+                .await;
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare tracked positions with the trading wallet and log the differences
+async fn reconcile_positions(
+    config: &Config,
+    position_manager: &crate::position::manager::PositionManager,
+    rpc_client: &solana_client::rpc_client::RpcClient,
+    trading_wallet: &Pubkey,
+) {
+    match position_manager
+        .reconcile_with_chain(rpc_client, trading_wallet, config.positions.adopt_orphans)
+        .await
+    {
+        Ok(report) => {
+            info!(
+                "Reconciliation: {} matched, {} orphans, {} ghosts",
+                report.matched.len(),
+                report.orphans.len(),
+                report.ghosts.len()
+            );
+            for orphan in report.orphans.iter().filter(|o| !o.adopted) {
+                warn!(
+                    "ORPHAN: {} holds {:.2} tokens with no tracked position (set positions.adopt_orphans or sell manually)",
+                    orphan.holding.mint,
+                    orphan.holding.ui_amount()
+                );
+            }
+            for ghost in &report.ghosts {
+                warn!("GHOST: position {} has zero on-chain balance", ghost);
+            }
+        }
+        Err(e) => warn!("Position reconciliation failed: {}", e),
+    }
+}
+
+/// Ctrl-C sequence of `snipe start`
+///
+/// Stops new entries, waits for buys and sells in flight, optionally sells
+/// every position, reconciles with the wallet and saves. A second Ctrl-C
+/// exits at once.
+async fn graceful_shutdown(
+    config: &Config,
+    position_manager: &crate::position::manager::PositionManager,
+    exit_requests: &mpsc::Sender<crate::position::auto_sell::ExitRequest>,
+    reconcile_with: Option<(&solana_client::rpc_client::RpcClient, &Pubkey)>,
+    flatten: bool,
+) {
+    use crate::position::auto_sell::{ExitRequest, ExitSignal};
+    use tokio::time::{sleep, Duration, Instant};
+
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            warn!("Second Ctrl-C: exiting without finishing shutdown");
+            std::process::exit(130);
+        }
+    });
+
+    info!("Shutdown 1/5: new entries stopped");
+    position_manager.close_entries();
+
+    let grace = config.shutdown.grace_period_secs;
+    info!(
+        "Shutdown 2/5: waiting up to {}s for buys and sells in flight",
+        grace
+    );
+    let deadline = Instant::now() + Duration::from_secs(grace);
+    let mut last = None;
+    loop {
+        let (buys, sells) = position_manager.in_flight();
+        if buys + sells == 0 {
+            info!("Nothing in flight");
+            break;
+        }
+        if Instant::now() >= deadline {
+            warn!(
+                "Grace period over with {} buys and {} sells still in flight",
+                buys, sells
+            );
+            break;
+        }
+        if last != Some((buys, sells)) {
+            info!("{} buys and {} sells in flight", buys, sells);
+            last = Some((buys, sells));
+        }
+        sleep(Duration::from_millis(250)).await;
+    }
+
+    let positions = position_manager.get_all_positions().await;
+    if flatten && !positions.is_empty() {
+        info!(
+            "Shutdown 3/5: selling {} open positions (up to {}s)",
+            positions.len(),
+            config.shutdown.flatten_timeout_secs
+        );
+        let mut queued = true;
+        for position in &positions {
+            // A hold would keep the monitor from acting on the close
+            let _ = position_manager.hold_position(&position.mint, 0).await;
+            let request = ExitRequest {
+                mint: position.mint.clone(),
+                signal: ExitSignal::manual(),
+            };
+            if exit_requests.send(request).await.is_err() {
+                warn!("Position monitor not running: sell with `snipe sell-all` after exit");
+                queued = false;
+                break;
+            }
+        }
+        let deadline = Instant::now() + Duration::from_secs(config.shutdown.flatten_timeout_secs);
+        while queued {
+            let open = position_manager.get_all_positions().await.len();
+            if open == 0 {
+                info!("All positions sold");
+                break;
+            }
+            if Instant::now() >= deadline {
+                warn!("Flatten timed out with {} positions still open", open);
+                break;
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+    } else {
+        info!(
+            "Shutdown 3/5: leaving {} positions open (flatten off)",
+            positions.len()
+        );
+    }
+
+    match reconcile_with {
+        Some((rpc_client, wallet)) => {
+            info!("Shutdown 4/5: reconciling positions with wallet {}", wallet);
+            reconcile_positions(config, position_manager, rpc_client, wallet).await;
+        }
+        None => info!("Shutdown 4/5: no wallet to reconcile (paper)"),
+    }
+
+    info!("Shutdown 5/5: saving positions");
+    if let Err(e) = position_manager.save().await {
+        error!("Failed to save positions: {}", e);
+    }
+    info!("Shutdown complete");
+}
+
+/// Manually sell a token position
+pub async fn sell(
+    config: &Config,
+    token: &str,
+    amount: &str,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    info!("Sell command: token={}, amount={}", token, amount);
+
+    // Parse token address
+    let _token_pubkey = solana_sdk::pubkey::Pubkey::try_from(token)
+        .map_err(|e| anyhow::anyhow!("Invalid token address: {}", e))?;
+
+    // Parse amount (can be percentage like "50%" or absolute)
+    let is_percentage = amount.ends_with('%');
+    let amount_value: f64 = if is_percentage {
+        amount
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid amount: {}", e))?
+    } else {
+        amount
+            .parse()
+            .map_err(|e| anyhow::anyhow!("Invalid amount: {}", e))?
+    };
+
+    if is_percentage && (amount_value <= 0.0 || amount_value > 100.0) {
+        anyhow::bail!("Percentage must be between 0 and 100");
+    }
+
+    // Initialize RPC client for balance queries
+    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+
+    // Determine which wallet to query for balance (Lightning or local)
+    let balance_wallet = if !config.pumpportal.lightning_wallet.is_empty() {
+        Pubkey::from_str(&config.pumpportal.lightning_wallet)?
+    } else {
+        // Fall back to local keypair
+        hot_wallet_address(config)?
+    };
+
+    // Initialize position manager
+    let position_manager = std::sync::Arc::new(crate::position::manager::PositionManager::new(
+        config.safety.clone(),
+        Some(format!("{}/positions.json", config.wallet.credentials_dir)),
+    )
+    .with_exit_thresholds(config.auto_sell.entry_types.clone()));
+    if let Err(e) = position_manager.load().await {
+        warn!("Could not load positions: {} (continuing anyway)", e);
+    }
+
+    // Load bought_mints cache
+    let bought_mints_path = format!("{}/bought_mints.json", config.wallet.credentials_dir);
+    let bought_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> = {
+        if std::path::Path::new(&bought_mints_path).exists() {
+            match std::fs::read_to_string(&bought_mints_path) {
+                Ok(data) => {
+                    if let Ok(mints) = serde_json::from_str::<std::collections::HashMap<String, i64>>(&data) {
+                        std::sync::Arc::new(tokio::sync::Mutex::new(mints))
+                    } else {
+                        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()))
+                    }
+                }
+                Err(_) => std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
+            }
+        } else {
+            std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()))
+        }
+    };
+    let bought_mints_path = std::sync::Arc::new(bought_mints_path);
+
+    // Get position info if we have it
+    let position = position_manager.get_position(token).await;
+    if let Some(ref pos) = position {
+        println!("\nPosition found:");
+        println!("  Symbol: {}", pos.symbol);
+        println!("  Tokens: {}", pos.token_amount);
+        println!("  Entry price: {:.10} SOL", pos.entry_price);
+        println!("  Cost: {:.4} SOL", pos.total_cost_sol);
+    }
+
+    // Confirmation prompt (unless --force)
+    if config.safety.require_sell_confirmation && !force {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Sell {} of token {}? This cannot be undone.",
+                amount, token
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            info!("Sell cancelled by user");
+            return Ok(());
+        }
+    }
+
+    if dry_run {
+        info!("DRY-RUN: Would sell {} of {}", amount, token);
+        return Ok(());
+    }
+
+    // Execute sell based on configuration
+    if config.pumpportal.use_for_trading {
+        // Use PumpPortal API
+        if config.pumpportal.api_key.is_empty() {
+            anyhow::bail!("PumpPortal API key required for selling via Lightning API");
+        }
+
+        let trader = PumpPortalTrader::lightning(config.pumpportal.api_key.clone());
+        let slippage_pct = config.trading.slippage_bps / 100;
+        let priority_fee = config.trading.priority_fee_lamports as f64 / 1_000_000_000.0;
+
+        // Query SOL balance BEFORE sell for real P&L
+        let sol_before = rpc_client.get_balance(&balance_wallet).unwrap_or(0) as f64 / 1_000_000_000.0;
+        info!("Balance before sell: {:.4} SOL", sol_before);
+
+        info!("Submitting sell via PumpPortal API...");
+        match trader.sell(token, amount, slippage_pct, priority_fee).await {
+            Ok(signature) => {
+                info!("Sell successful! Signature: {}", signature);
+                println!("\nSell transaction confirmed!");
+                println!("Signature: {}", signature);
+                println!("View on Solscan: https://solscan.io/tx/{}", signature);
+
+                // Wait for tx confirmation then query actual SOL received
+                tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
+                let sol_after = rpc_client.get_balance(&balance_wallet).unwrap_or(0) as f64 / 1_000_000_000.0;
+                let raw_received = (sol_after - sol_before).max(0.0);
+
+                println!("Balance after sell: {:.4} SOL", sol_after);
+                println!("SOL received (raw): {:.4} SOL", raw_received);
+
+                // Update position manager and stats
+                if let Some(ref pos) = position {
+                    let is_full_sell = amount == "100%" || amount_value >= 100.0;
+                    let tokens_sold = if is_full_sell {
+                        pos.token_amount
+                    } else if is_percentage {
+                        (pos.token_amount as f64 * amount_value / 100.0) as u64
+                    } else {
+                        amount_value as u64
+                    };
+
+                    // Sanity check: received SOL shouldn't be more than 10x position cost
+                    // If it is, the balance query likely failed (sol_before was 0)
+                    let max_reasonable = pos.total_cost_sol * 10.0;
+                    let actual_received = if raw_received > max_reasonable {
+                        warn!(
+                            "Balance query anomaly: before={:.4}, after={:.4}, diff={:.4} (max reasonable: {:.4}) - using estimate",
+                            sol_before, sol_after, raw_received, max_reasonable
+                        );
+                        0.0 // Force fallback to estimate
+                    } else {
+                        raw_received
+                    };
+
+                    // Use actual received SOL, fallback to estimate if balance query failed
+                    let received = if actual_received > 0.0 {
+                        actual_received
+                    } else {
+                        // Estimate based on position price (use current_price if available, else entry_price)
+                        let price = if pos.current_price > 0.0 { pos.current_price } else { pos.entry_price };
+                        let estimated = (tokens_sold as f64 * price) * 0.98;
+                        warn!("Balance query returned 0 or anomaly detected, using estimated received: {:.4} SOL", estimated);
+                        estimated
+                    };
+
+                    // Lightning sell: network + 1% platform fee; refine network fee from tx meta
+                    let mut exit_fees = crate::position::TradeFees::estimate(
+                        priority_fee,
+                        0,
+                        received,
+                        config.pumpportal.platform_fee_pct(false),
+                    );
+                    if let Some(trade) =
+                        crate::position::reconcile::fetch_confirmed_trade(&rpc_client, &signature)
+                    {
+                        exit_fees.refine(trade.network_fee_lamports);
+                    }
+                    // A measured balance delta is already net of sell fees; record gross proceeds
+                    let proceeds = if actual_received > 0.0 {
+                        received + exit_fees.total_sol()
+                    } else {
+                        received
+                    };
+                    let exit = crate::position::ExitDetails::new(crate::position::TradeExitReason::Manual)
+                        .with_signature(&signature)
+                        .with_fees(exit_fees);
+                    let net_pnl_sol = position_manager
+                        .close_position_with_exit(token, tokens_sold, proceeds, exit)
+                        .await
+                        .ok();
+
+                    // Persist position state immediately
+                    if let Err(e) = position_manager.save().await {
+                        warn!("Failed to persist position state: {}", e);
+                    }
+
+                    let cost_portion = if is_full_sell {
+                        pos.total_cost_sol
+                    } else {
+                        pos.total_cost_sol * amount_value / 100.0
+                    };
+                    let pnl_sol = proceeds - cost_portion;
+                    let pnl_pct = (pnl_sol / cost_portion) * 100.0;
+                    let net_pnl_sol = net_pnl_sol.unwrap_or(pnl_sol);
+                    let net_pnl_pct = (net_pnl_sol / cost_portion) * 100.0;
+
+                    println!("\n=== TRADE CLOSED ===");
+                    println!("  Cost: {:.4} SOL | Received: {:.4} SOL", cost_portion, received);
+                    println!("  P&L: {:+.4} SOL ({:+.1}%) net of fees | {:+.4} SOL ({:+.1}%) gross",
+                            net_pnl_sol, net_pnl_pct, pnl_sol, pnl_pct);
+
+                    // Clean up bought_mints if position is fully closed
+                    // Check if position still exists after close_position
+                    let position_closed = position_manager.get_position(token).await.is_none();
+                    if position_closed {
+                        let _ = remove_bought_mint(&bought_mints, &bought_mints_path, token).await;
+                        info!("Removed {} from bought_mints cache", token);
+                    }
+                } else {
+                    // No position tracked - still clean up bought_mints
+                    let removed = remove_bought_mint(&bought_mints, &bought_mints_path, token).await;
+                    if removed {
+                        info!("Removed {} from bought_mints cache", token);
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Sell failed: {}", e);
+                anyhow::bail!("Sell transaction failed: {}", e);
+            }
+        }
+    } else {
+        // Use Jito bundles
+        warn!("Jito sell not yet implemented - use PumpPortal Lightning API");
+        anyhow::bail!("Jito sell not implemented. Set pumpportal.use_for_trading = true in config.toml");
+    }
+
+    Ok(())
+}
+
+/// Emergency liquidation: sell 100% of every open position right now
+///
+/// Uses the `[trading.panic]` slippage and priority fee and signs locally on
+/// the first attempt (Lightning is only the fallback). Works while the
+/// emergency lock is active, which refuses new buys.
+pub async fn sell_all(config: &Config, force: bool, dry_run: bool) -> Result<()> {
+    use crate::position::auto_sell::{PositionSeller, PumpPortalSeller};
+
+    let position_manager = Arc::new(
+        crate::position::manager::PositionManager::new(
+            config.safety.clone(),
+            Some(format!("{}/positions.json", config.wallet.credentials_dir)),
+        )
+        .with_exit_thresholds(config.auto_sell.entry_types.clone()),
+    );
+    position_manager.load().await?;
+
+    let positions = position_manager.get_all_positions().await;
+    if positions.is_empty() {
+        println!("No open positions.");
+        return Ok(());
+    }
+
+    if let Some(lock) = position_manager.emergency_lock_path() {
+        if lock.exists() {
+            println!("Emergency lock active - new buys are refused, liquidation is allowed.");
+        }
+    }
+
+    let panic = &config.trading.panic;
+    println!("\n=== SELL ALL: {} open positions ===", positions.len());
+    for position in &positions {
+        println!(
+            "  {:<10} {} | Tokens: {} | Cost: {:.4} SOL",
+            position.symbol, position.mint, position.token_amount, position.total_cost_sol
+        );
+    }
+    println!(
+        "Slippage: {}% | Priority fee: {:.6} SOL",
+        panic.slippage_bps / 100,
+        panic.priority_fee_lamports as f64 / 1e9
+    );
+
+    if !force {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Sell 100% of all {} positions? This cannot be undone.",
+                positions.len()
+            ))
+            .default(false)
+            .interact()?;
+
+        if !confirmed {
+            info!("Sell-all cancelled by user");
+            return Ok(());
+        }
+    }
+
+    if dry_run {
+        info!("DRY-RUN: Would sell {} positions", positions.len());
+        return Ok(());
+    }
+
+    if !config.pumpportal.use_for_trading {
+        anyhow::bail!("Jito sell not implemented. Set pumpportal.use_for_trading = true in config.toml");
+    }
+
+    let signer = load_hot_signer(config)?;
+
+    let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    ));
+
+    let multi_wallet = if !config.wallet.trading_wallets.is_empty() {
+        match crate::wallet::MultiWalletManager::new(
+            config.wallet.trading_wallets.clone(),
+            &config.wallet.selection_strategy,
+        ) {
+            Ok(mw) => Some(Arc::new(mw)),
+            Err(e) => {
+                warn!("Failed to initialize multi-wallet: {} - using primary wallet", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let use_local_api = config.pumpportal.api_key.is_empty() || config.pumpportal.force_local_api;
+    let api_key = (!config.pumpportal.api_key.is_empty()).then(|| config.pumpportal.api_key.clone());
+    let trader = Arc::new(PumpPortalTrader::new(api_key, use_local_api));
+    let seller = PumpPortalSeller::new(trader, rpc_client, signer, config)
+        .with_multi_wallet(multi_wallet)
+        .for_liquidation(panic);
+
+    // Local signing first, then one Lightning (or local) retry
+    let mut results: Vec<(crate::position::manager::Position, std::result::Result<String, String>)> =
+        Vec::new();
+    for position in positions {
+        let mut outcome = Err(String::new());
+        for attempt in 1..=2 {
+            match seller.sell(&position, "100%", attempt).await {
+                Ok(sig) => {
+                    outcome = Ok(sig);
+                    break;
+                }
+                Err(e) => {
+                    warn!("[{}] Sell attempt {} failed: {}", position.symbol, attempt, e);
+                    outcome = Err(e.to_string());
+                    // Permanent errors only get the retry on another route
+                    if !e.is_retryable() && !seller.has_fallback(&position, attempt, None) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Ok(ref sig) = outcome {
+            // current_price isn't persisted; estimate from entry price minus ~2% slippage
+            let price = if position.current_price > 0.0 {
+                position.current_price
+            } else {
+                position.entry_price
+            };
+            let received = (position.token_amount as f64 * price) * 0.98;
+            let exit = crate::position::ExitDetails::new(crate::position::TradeExitReason::Manual)
+                .with_signature(sig)
+                .with_fees(seller.estimate_fees(sig, received));
+            if let Err(e) = position_manager
+                .close_position_with_exit(&position.mint, position.token_amount, received, exit)
+                .await
+            {
+                warn!("[{}] Sold but failed to record trade: {}", position.symbol, e);
+            }
+        }
+        results.push((position, outcome));
+    }
+
+    let sold = results.iter().filter(|(_, r)| r.is_ok()).count();
+    println!("\n=== SELL ALL RESULTS: {}/{} sold ===", sold, results.len());
+    for (position, result) in &results {
+        match result {
+            Ok(sig) => println!("  OK     {:<10} {} | https://solscan.io/tx/{}", position.symbol, position.mint, sig),
+            Err(e) => println!("  FAILED {:<10} {} | {}", position.symbol, position.mint, e),
+        }
+    }
+    if sold < results.len() {
+        println!("\nFailed positions are still tracked - rerun `snipe sell-all` or sell manually.");
+    }
+
+    Ok(())
+}
 
-                            if config.filters.min_bonding_curve_pct > 0.0 && bonding_curve_pct < config.filters.min_bonding_curve_pct {
-                                info!(
-                                    "Token {} filtered: bonding curve {:.1}% < min {:.1}% (too new)",
-                                    token.symbol, bonding_curve_pct, config.filters.min_bonding_curve_pct
-                                );
-                                continue;
-                            }
+/// Show current positions and P&L
+///
+/// Reads persisted state and public prices only, so it works whether or not
+/// a bot is running. `watch` redraws every `interval` seconds.
+pub async fn status(config: &Config, json: bool, watch: bool, interval: u64) -> Result<()> {
+    let sol_price = Arc::new(sol_usd_price(config));
+    loop {
+        if let Err(e) = sol_price.refresh_if_due().await {
+            warn!("SOL/USD price unavailable: {}", e);
+        }
+        let position_manager = crate::position::manager::PositionManager::new(
+            config.safety.clone(),
+            Some(format!("{}/positions.json", config.wallet.credentials_dir)),
+        )
+        .with_exit_thresholds(config.auto_sell.entry_types.clone())
+        .with_sol_price(sol_price.clone());
+        if let Err(e) = position_manager.load().await {
+            warn!("Could not load positions: {}", e);
+        }
+        let trades = position_manager
+            .trade_history_path()
+            .map(|path| crate::position::history::load_trades(&path))
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Could not load trade history: {}", e);
+                None
+            })
+            .unwrap_or_default();
+        let mut snapshot = status_snapshot(config, &position_manager, &trades).await;
+        snapshot.instance = running_instance(config);
+        snapshot.sol_usd = sol_price
+            .fresh_quote(chrono::Utc::now())
+            .map(|q| q.usd_per_sol);
+
+        if json && watch {
+            // One line per refresh
+            println!("{}", serde_json::to_string(&snapshot)?);
+        } else if json {
+            println!("{}", serde_json::to_string_pretty(&snapshot)?);
+        } else {
+            if watch {
+                // Clear the screen and home the cursor
+                print!("\x1b[2J\x1b[H");
+            }
+            print_status(config, &position_manager, &trades, &snapshot).await;
+        }
 
-                            if config.filters.max_bonding_curve_pct > 0.0 && bonding_curve_pct > config.filters.max_bonding_curve_pct {
-                                info!(
-                                    "Token {} filtered: bonding curve {:.1}% > max {:.1}% (near graduation)",
-                                    token.symbol, bonding_curve_pct, config.filters.max_bonding_curve_pct
-                                );
-                                continue;
-                            }
+        if !watch {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(interval.max(1))).await;
+    }
+    Ok(())
+}
 
-                            info!(
-                                "Token {} passed all filters: mcap={:.2} SOL, bonding={:.1}%",
-                                token.symbol, token.market_cap_sol, bonding_curve_pct
-                            );
-                        }
+/// Open positions marked to live prices, plus today's realized P&L
+async fn status_snapshot(
+    config: &Config,
+    position_manager: &crate::position::manager::PositionManager,
+    trades: &[crate::position::ClosedTrade],
+) -> crate::position::StatusSnapshot {
+    use crate::position::status::{daily_stats, StatusSnapshot};
+    use crate::strategy::portfolio_risk::{trading_date, trading_day_start};
+
+    let positions = position_manager.get_all_positions().await;
+    let prices = fetch_position_prices(config, &positions).await;
+
+    let now = chrono::Utc::now();
+    let offset_hours = config.strategy.portfolio_risk.daily_reset_utc_offset_hours;
+    let day_start = trading_day_start(now, offset_hours);
+    let mut today = daily_stats(trades, day_start, trading_date(now, offset_hours));
+    // Entry and trade counts come from the bot's own stats
+    let counted = position_manager.get_daily_stats().await;
+    today.entries = counted.entries;
+    today.transactions = counted.transactions;
+    StatusSnapshot::build(
+        &positions,
+        &prices,
+        today,
+        position_manager.daily_loss_limit_sol(),
+        now,
+    )
+}
 
-                        // Check daily loss limit
-                        if position_manager.is_daily_loss_limit_reached().await {
-                            warn!("Daily loss limit reached - skipping buy");
-                            continue;
-                        }
+/// Current price of each position by mint: bonding curve, the price APIs once graduated
+async fn fetch_position_prices(
+    config: &Config,
+    positions: &[crate::position::manager::Position],
+) -> std::collections::HashMap<String, (f64, crate::position::PriceSource)> {
+    let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    ));
+    let price_sources = crate::price_source::CompositePriceSource::new(
+        &config.price_sources,
+        Arc::new(crate::dexscreener::DexScreenerClient::new()),
+    );
+    let feed = crate::position::PriceFeed::new(rpc_client, config.auto_sell.clone())
+        .with_price_sources(Arc::new(price_sources));
 
-                        // Check strategy engine constraints (if enabled)
-                        if let Some(ref engine) = strategy_engine {
-                            let engine_guard = engine.read().await;
+    let mut prices = std::collections::HashMap::new();
+    for position in positions {
+        let Ok(mint) = Pubkey::from_str(&position.mint) else {
+            continue;
+        };
+        let bonding_curve = Pubkey::from_str(&position.bonding_curve).ok().or_else(|| {
+            crate::trading::transaction::derive_bonding_curve(&mint)
+                .ok()
+                .map(|(pda, _)| pda)
+        });
+        let Some(bonding_curve) = bonding_curve else {
+            continue;
+        };
+        match feed.fetch_price(&mint, &bonding_curve).await {
+            Ok(price) => {
+                prices.insert(position.mint.clone(), price);
+            }
+            Err(e) => warn!("[{}] No current price: {}", position.symbol, e),
+        }
+    }
+    prices
+}
 
-                            // Check if trading should be paused
-                            if engine_guard.should_pause_trading().await {
-                                let chain_state = engine_guard.get_chain_state().await;
-                                warn!(
-                                    "Strategy engine paused trading: congestion={:?}",
-                                    chain_state.congestion_level
-                                );
-                                continue;
-                            }
+/// Text status: reconciliation, totals, today, throttle, hot wallet, positions
+async fn print_status(
+    config: &Config,
+    position_manager: &crate::position::manager::PositionManager,
+    trades: &[crate::position::ClosedTrade],
+    snapshot: &crate::position::StatusSnapshot,
+) {
+    use crate::position::status::format_hold;
 
-                            // Check portfolio limits
-                            let portfolio_state = engine_guard.get_portfolio_state().await;
-                            if !portfolio_state.can_open_new {
-                                warn!(
-                                    "Portfolio limit reached: {} positions, {} SOL exposure - {:?}",
-                                    portfolio_state.open_position_count,
-                                    portfolio_state.total_exposure_sol,
-                                    portfolio_state.reason_if_blocked
-                                );
-                                continue;
-                            }
+    let positions = position_manager.get_all_positions().await;
+
+    println!("\n=== SNIPER BOT STATUS ===\n");
+    match snapshot.instance {
+        Some(ref state) => println!("Bot: running ({})\n", state),
+        None => println!("Bot: not running\n"),
+    }
+
+    // Discrepancies from the last startup reconciliation come first
+    if let Some(path) = position_manager.reconciliation_report_path() {
+        match crate::position::ReconciliationReport::load(&path) {
+            Ok(Some(report)) if report.has_discrepancies() => {
+                println!("!!! POSITION DISCREPANCIES !!!");
+                if let Some(checked_at) = report.checked_at {
+                    println!("(wallet {}, checked {})", report.wallet, checked_at.to_rfc3339());
+                }
+                for orphan in report.orphans.iter().filter(|o| !o.adopted) {
+                    println!(
+                        "  ORPHAN {} | {:.2} tokens on-chain, not tracked | Entry: {}",
+                        orphan.holding.mint,
+                        orphan.holding.ui_amount(),
+                        orphan
+                            .entry
+                            .as_ref()
+                            .map(|e| format!("{:.10} SOL ({:.4} SOL cost)", e.price, e.cost_sol))
+                            .unwrap_or_else(|| "unknown".to_string())
+                    );
+                }
+                for ghost in &report.ghosts {
+                    println!("  GHOST  {} | tracked, zero balance on-chain", ghost);
+                }
+                println!();
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not load reconciliation report: {}", e),
+        }
+    }
+
+    // Realized P&L from closed trades, net of fees with gross alongside
+    let realized_net: f64 = trades.iter().map(|t| t.pnl_sol).sum();
+    let realized_gross: f64 = trades.iter().map(|t| t.gross_pnl_sol).sum();
+    let realized_fees: f64 = trades.iter().map(|t| t.fees_sol).sum();
+    let open_entry_fees: f64 = positions.iter().map(|p| p.fees.total_sol()).sum();
+
+    println!("Positions: {}", positions.len());
+    println!("Total Cost: {:.4} SOL", snapshot.total_cost_sol);
+    println!(
+        "Unrealized P&L: {:+.4} SOL | Value {:.4} SOL{}",
+        snapshot.unrealized_pnl_sol,
+        snapshot.total_value_sol,
+        if snapshot.unpriced > 0 {
+            format!(" ({} without a price)", snapshot.unpriced)
+        } else {
+            String::new()
+        }
+    );
+    println!(
+        "Realized P&L: {:+.4} SOL net | {:+.4} SOL gross ({} trades)",
+        realized_net,
+        realized_gross,
+        trades.len()
+    );
+    println!(
+        "Fees Paid: {:.4} SOL closed | {:.4} SOL on open positions",
+        realized_fees, open_entry_fees
+    );
+    let today = &snapshot.today;
+    println!("\nToday ({}):", today.date);
+    println!(
+        "  Trades: {} ({} wins, {} losses, {:.0}% win rate)",
+        today.total_trades,
+        today.winning_trades,
+        today.losing_trades,
+        today.win_rate()
+    );
+    let cap = |limit: Option<u32>| limit.map_or_else(|| "no cap".to_string(), |l| l.to_string());
+    println!(
+        "  Entries: {} / {}   Transactions: {} / {}",
+        today.entries,
+        cap(config.safety.max_daily_entries),
+        today.transactions,
+        cap(config.safety.max_daily_trades)
+    );
+    println!("  Realized P&L: {:+.4} SOL", today.net_pnl_sol);
+    println!(
+        "  Daily Loss Used: {:.4} / {} SOL {}",
+        today.total_loss_sol,
+        snapshot.daily_loss_limit_sol,
+        snapshot.loss_limit_bar(20)
+    );
+    print_usd_amounts(config, snapshot.sol_usd);
+    print_throttle_status(config);
+    print_balance_status(config).await;
+
+    println!("\n=== OPEN POSITIONS ===\n");
+    if snapshot.positions.is_empty() {
+        println!("No open positions.");
+    } else {
+        println!(
+            "{:<10} {:<8} {:>14} {:>14} {:<13} {:>8} {:>9} {:>7} {:<12} {:>14}",
+            "SYMBOL",
+            "MINT",
+            "ENTRY",
+            "CURRENT",
+            "SOURCE",
+            "P&L %",
+            "P&L SOL",
+            "HELD",
+            "TYPE",
+            "PEAK"
+        );
+        println!("{}", "-".repeat(120));
+    }
+    for status in &snapshot.positions {
+        println!(
+            "{:<10} {:<8} {:>14.10} {:>14} {:<13} {:>8} {:>9} {:>7} {:<12} {:>14.10}",
+            status.symbol,
+            status.mint_short(),
+            status.entry_price,
+            status
+                .current_price
+                .map(|p| format!("{:.10}", p))
+                .unwrap_or_else(|| "-".into()),
+            status
+                .price_source
+                .map_or_else(|| "-".to_string(), |s| s.to_string()),
+            status
+                .pnl_pct
+                .map(|p| format!("{:+.1}%", p))
+                .unwrap_or_else(|| "-".into()),
+            status
+                .pnl_sol
+                .map(|p| format!("{:+.4}", p))
+                .unwrap_or_else(|| "-".into()),
+            format_hold(status.hold_secs),
+            format!("{:?}", status.entry_type),
+            status.peak_price
+        );
+    }
+    for pos in &positions {
+        let details = pos.fill_count() > 1
+            || pos.partial_fill
+            || !pos.exit_override.is_empty()
+            || pos.scale_in.is_some()
+            || pos.slippage.is_some_and(|s| s.estimated);
+        if !details {
+            continue;
+        }
+        println!(
+            "\n{} ({}) | Tokens: {} | Cost: {:.4} SOL | Fills: {}",
+            pos.symbol,
+            &pos.mint[..8.min(pos.mint.len())],
+            pos.token_amount,
+            pos.total_cost_sol,
+            pos.fill_count()
+        );
+        println!("  Entry fees: {}", pos.fees.summary());
+        if pos.partial_fill {
+            println!("  Partial fill: exits use Probe targets");
+        }
+        if let Some(slippage) = pos.slippage {
+            println!("  Slippage: {}", slippage.summary());
+        }
+        if !pos.exit_override.is_empty() {
+            let o = &pos.exit_override;
+            println!(
+                "  Override: SL {} | TP {} | Trail {} | Hold {}",
+                o.stop_loss_pct.map(|v| format!("-{:.1}%", v)).unwrap_or_else(|| "-".into()),
+                o.take_profit_pct.map(|v| format!("+{:.1}%", v)).unwrap_or_else(|| "-".into()),
+                o.trailing_pct.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "-".into()),
+                o.hold_until.map(|t| t.to_rfc3339()).unwrap_or_else(|| "-".into()),
+            );
+        }
+        if let Some(ref plan) = pos.scale_in {
+            println!("  Scale-in: {}", plan.summary());
+            for tranche in &plan.tranches {
+                println!(
+                    "    #{} {:.4} SOL - {}{}",
+                    tranche.index + 1,
+                    tranche.size_sol,
+                    tranche.status,
+                    tranche
+                        .signature
+                        .as_ref()
+                        .map(|sig| format!(" ({})", sig))
+                        .unwrap_or_default()
+                );
+            }
+        }
+    }
+
+    // `snipe start --paper` keeps its positions and trades apart from the live ones
+    let paper_path = state_path(config, "positions.json", true);
+    if std::path::Path::new(&paper_path).exists() {
+        let paper_manager =
+            crate::position::manager::PositionManager::new(config.safety.clone(), Some(paper_path));
+        if let Err(e) = paper_manager.load().await {
+            warn!("Could not load paper positions: {}", e);
+        }
+        let paper_positions = paper_manager.get_all_positions().await;
+        let paper_trades = paper_manager
+            .trade_history_path()
+            .map(|path| crate::position::history::load_trades(&path))
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Could not load paper trade history: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        println!("\n=== PAPER POSITIONS (simulated) ===\n");
+        println!(
+            "Realized P&L: {:+.4} SOL net ({} trades)",
+            paper_trades.iter().map(|t| t.pnl_sol).sum::<f64>(),
+            paper_trades.len()
+        );
+        if paper_positions.is_empty() {
+            println!("No open paper positions.");
+        }
+        for pos in &paper_positions {
+            println!(
+                "[PAPER] {} ({}) | Tokens: {} | Avg Entry: {:.10} | Cost: {:.4} SOL | {:?}",
+                pos.symbol,
+                &pos.mint[..8.min(pos.mint.len())],
+                pos.token_amount,
+                pos.entry_price,
+                pos.total_cost_sol,
+                pos.entry_type
+            );
+        }
+    }
+}
+
+/// SOL/USD oracle for one-off CLI reads
+fn sol_usd_price(config: &Config) -> crate::sol_price::SolUsdPrice {
+    let rpc_client = Arc::new(solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    ));
+    crate::sol_price::SolUsdPrice::new(config.sol_price.clone(), rpc_client)
+}
+
+/// Buy size and safety limits in SOL and USD (`sol_usd` None = no fresh price)
+fn print_usd_amounts(config: &Config, sol_usd: Option<f64>) {
+    match sol_usd {
+        Some(price) => println!("\nSOL/USD: ${:.2} ({})", price, config.sol_price.oracle),
+        None => println!("\nSOL/USD: no fresh price from {}", config.sol_price.oracle),
+    }
+    let amounts = [
+        (
+            "Buy amount",
+            config.trading.buy_amount_sol,
+            config.trading.buy_amount_usd,
+        ),
+        (
+            "Max position",
+            config.safety.max_position_sol,
+            config.safety.max_position_usd,
+        ),
+        (
+            "Daily loss limit",
+            config.safety.daily_loss_limit_sol,
+            config.safety.daily_loss_limit_usd,
+        ),
+    ];
+    for (label, sol, usd) in amounts {
+        let value = match (usd, sol_usd) {
+            (Some(usd), Some(price)) => format!(
+                "${:.2} = {:.4} SOL (fallback {} SOL)",
+                usd,
+                usd / price,
+                sol
+            ),
+            (Some(usd), None) => format!("${:.2} -> {} SOL fallback in use", usd, sol),
+            (None, Some(price)) => format!("{} SOL = ${:.2}", sol, sol * price),
+            (None, None) => format!("{} SOL", sol),
+        };
+        println!("  {:<17} {}", format!("{}:", label), value);
+    }
+}
+
+/// Show execution quality and the slippage / size adjustments made from it
+/// Trading windows from the config, launch rate from the running bot's throttle.json
+fn print_throttle_status(config: &Config) {
+    use crate::strategy::throttle::load_throttle_report;
+
+    let throttle = &config.strategy.throttle;
+    let now = chrono::Utc::now();
+    println!("\n=== THROTTLE ===\n");
+    if throttle.windows.is_empty() {
+        println!("Trading windows: none (trading around the clock)");
+    } else {
+        for window in &throttle.windows {
+            println!("  Window {}", window);
+        }
+        println!(
+            "  Outside windows: x{:.2}",
+            throttle.outside_size_multiplier
+        );
+    }
+    println!(
+        "Size now ({} UTC): x{:.2}",
+        now.format("%H:%M"),
+        throttle.size_multiplier_at(now)
+    );
+
+    let path = state_path(config, "throttle.json", false);
+    match load_throttle_report(std::path::Path::new(&path)) {
+        Ok(Some(report)) => {
+            let updated = report
+                .updated_at
+                .map(|t| format!("{}s ago", (now - t).num_seconds()))
+                .unwrap_or_else(|| "unknown".to_string());
+            println!(
+                "Launch rate: {}/min (surge at {}/min){} | updated {}",
+                report.launches_per_min,
+                throttle.launch_rate.surge_launches_per_min,
+                if report.surging { " - SURGE" } else { "" },
+                updated
+            );
+            if report.threshold_boost > 0.0 {
+                println!(
+                    "  Opportunity/strong-buy thresholds +{:.2}",
+                    report.threshold_boost
+                );
+            }
+            println!(
+                "  Entries last minute: {} / {}",
+                report.entries_last_min,
+                report
+                    .max_entries_per_min
+                    .map(|cap| cap.to_string())
+                    .unwrap_or_else(|| "unlimited".to_string())
+            );
+        }
+        Ok(None) => println!("Launch rate: no data yet (written by a running bot)"),
+        Err(e) => warn!("Could not load throttle report: {}", e),
+    }
+}
+
+/// Hot wallet balance from the running bot's balance_watch.json, else a live read
+async fn print_balance_status(config: &Config) {
+    use crate::wallet::manager::{load_balance_report, BalanceLevel};
+
+    let watch = &config.wallet.balance_watch;
+    let now = chrono::Utc::now();
+    println!("\n=== HOT WALLET ===\n");
+
+    let path = state_path(config, "balance_watch.json", false);
+    let report = load_balance_report(std::path::Path::new(&path)).unwrap_or_else(|e| {
+        warn!("Could not load balance report: {}", e);
+        None
+    });
+    // Older than a few polls: the bot is not running
+    let stale_after = chrono::Duration::seconds(3 * watch.poll_interval_secs as i64);
+    match report.filter(|r| r.updated_at.is_some_and(|t| now - t < stale_after)) {
+        Some(report) => {
+            println!(
+                "Balance: {:.4} SOL | updated {}s ago",
+                report.balance_sol,
+                report
+                    .updated_at
+                    .map(|t| (now - t).num_seconds())
+                    .unwrap_or_default()
+            );
+            println!(
+                "Net change today: {:+.4} SOL",
+                report.net_change_today_sol()
+            );
+            if report.lost_last_hour_sol > 0.0 {
+                println!(
+                    "Lost last hour: {:.4} SOL (alert above {} SOL)",
+                    report.lost_last_hour_sol, watch.max_drain_sol_per_hour
+                );
+            }
+            let entries = match report.level {
+                BalanceLevel::Blocked => format!("BLOCKED (below {} SOL)", watch.entry_floor_sol),
+                BalanceLevel::Low => format!("allowed - LOW (below {} SOL)", watch.warn_below_sol),
+                BalanceLevel::Ok => "allowed".to_string(),
+            };
+            println!("Entries: {}", entries);
+        }
+        None => {
+            let rpc =
+                solana_client::nonblocking::rpc_client::RpcClient::new(config.rpc.endpoint.clone());
+            let balance: Result<u64> = async {
+                let address = hot_wallet_address(config)?;
+                Ok(rpc.get_balance(&address).await?)
+            }
+            .await;
+            match balance {
+                Ok(lamports) => {
+                    let balance_sol = lamports as f64 / 1e9;
+                    let level = balance_watch_config(config).level_for(balance_sol);
+                    println!("Balance: {:.4} SOL (live, no running bot)", balance_sol);
+                    println!("Net change today: unknown (tracked by a running bot)");
+                    println!(
+                        "Entries: {}",
+                        if level == BalanceLevel::Blocked {
+                            "would be BLOCKED"
+                        } else {
+                            "allowed"
                         }
+                    );
+                }
+                Err(e) => println!("Balance: unavailable ({})", e),
+            }
+        }
+    }
+}
+
+pub fn execution_status(config: &Config) -> Result<()> {
+    use crate::strategy::execution_feedback::{load_execution_report, AdjustmentAction};
+
+    let path = std::path::Path::new(&config.wallet.credentials_dir).join("execution_feedback.json");
+    println!("\n=== EXECUTION ===\n");
+    let Some(report) = load_execution_report(&path)? else {
+        println!("No execution data yet (written by a running bot).");
+        return Ok(());
+    };
+
+    if let Some(updated_at) = report.updated_at {
+        println!("Updated: {}", updated_at.to_rfc3339());
+    }
+    let quality = &report.quality;
+    println!(
+        "Avg slippage: {:.2}% | Avg time to land: {} ms | Fill rate: {:.1}%",
+        quality.recent_avg_slippage,
+        quality.recent_avg_latency_ms,
+        quality.recent_fill_rate * 100.0
+    );
+    if quality.should_pause_trading {
+        println!("Severe slippage: trading paused");
+    }
+    if let Some(miss_rate) = quality.estimator_miss_rate {
+        println!(
+            "Slippage estimator: {:.0}% of checked buys slipped past the estimate",
+            miss_rate * 100.0
+        );
+    }
+    println!(
+        "Buy slippage: {} bps configured + {} bps | Size: {:.0}%",
+        config.trading.slippage_bps,
+        report.adjustment.extra_slippage_bps,
+        report.adjustment.size_factor * 100.0
+    );
+
+    println!("\n=== ADJUSTMENTS ===\n");
+    if report.adjustments.is_empty() {
+        println!("No adjustments.");
+    }
+    for adjustment in report.adjustments.iter().rev() {
+        let action = match adjustment.action {
+            AdjustmentAction::WidenSlippage => "widen slippage",
+            AdjustmentAction::ReduceSize => "reduce size",
+            AdjustmentAction::RestoreSize => "restore size",
+            AdjustmentAction::TightenSlippage => "tighten slippage",
+        };
+        println!(
+            "{} | {} -> +{} bps, size {:.0}% | {}",
+            adjustment.at.format("%Y-%m-%d %H:%M:%S"),
+            action,
+            adjustment.extra_slippage_bps,
+            adjustment.size_factor * 100.0,
+            adjustment.trigger
+        );
+    }
+
+    println!("\n=== RECENT FILLS ===\n");
+    if report.recent.is_empty() {
+        println!("No fills recorded.");
+    }
+    for record in report.recent.iter().rev() {
+        let side = match record.side {
+            crate::strategy::types::Side::Buy => "BUY ",
+            crate::strategy::types::Side::Sell => "SELL",
+        };
+        let mint = &record.mint[..8.min(record.mint.len())];
+        if record.success {
+            println!(
+                "{} {} {} | {:.4} SOL | slippage {:+.2}% | {} ms",
+                record.timestamp.format("%H:%M:%S"),
+                side,
+                mint,
+                record.filled_size_sol,
+                record.slippage_pct,
+                record.latency_ms
+            );
+            if let Some(slippage) = record.slippage_estimate {
+                println!("    {}", slippage.summary());
+            }
+        } else {
+            println!(
+                "{} {} {} | {:.4} SOL | FAILED: {}",
+                record.timestamp.format("%H:%M:%S"),
+                side,
+                mint,
+                record.requested_size_sol,
+                record.failure_reason.as_deref().unwrap_or("unknown")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Export closed trades to CSV or JSON
+pub async fn export(
+    config: &Config,
+    format: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    out: Option<&str>,
+    mint: Option<String>,
+    reason: Option<&str>,
+) -> Result<()> {
+    use crate::position::history::{self, ExportFormat, TradeFilter};
+
+    let format: ExportFormat = format.parse()?;
+    let filter = TradeFilter {
+        from: from.map(|d| history::parse_date(d, false)).transpose()?,
+        to: to.map(|d| history::parse_date(d, true)).transpose()?,
+        mint,
+        reason: reason.map(|r| r.parse()).transpose()?,
+    };
+
+    let path = std::path::Path::new(&config.wallet.credentials_dir).join("trades.json");
+    let trades: Vec<_> = history::load_trades(&path)?
+        .into_iter()
+        .filter(|t| filter.matches(t))
+        .collect();
+    let rendered = history::render(&trades, format)?;
+
+    match out {
+        Some(file) => {
+            std::fs::write(file, rendered)?;
+            println!("Exported {} trades to {}", trades.len(), file);
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+/// List active re-entry cooldowns and blacklisted mints
+pub async fn cooldowns_list(config: &Config) -> Result<()> {
+    let tracker = load_cooldowns(config, false).await;
+    let entries = tracker.list().await;
+
+    println!("\n=== COOLDOWNS ===\n");
+    if entries.is_empty() {
+        println!("No active cooldowns.");
+        return Ok(());
+    }
+    for (mint, entry, hit) in &entries {
+        let since = chrono::DateTime::from_timestamp(entry.since, 0)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+        println!(
+            "{} | {:<6} | since {} | {} | {}",
+            mint,
+            entry.kind.to_string(),
+            since,
+            hit.remaining_secs
+                .map(|r| format!("{}s left", r))
+                .unwrap_or_else(|| "permanent".to_string()),
+            entry.reason.as_deref().unwrap_or("-")
+        );
+    }
+    println!("\n{} active", entries.len());
+
+    Ok(())
+}
 
-                        // Apply adaptive filter scoring if enabled
-                        // Track both position multiplier AND recommendation for context-aware exits
-                        let (position_multiplier, entry_recommendation) = if let Some(ref filter) = adaptive_filter {
-                            // Create signal context from token event
-                            let signal_context = SignalContext::from_new_token(
-                                token.mint.clone(),
-                                token.name.clone(),
-                                token.symbol.clone(),
-                                token.uri.clone(),
-                                token.trader_public_key.clone(),
-                                token.bonding_curve_key.clone(),
-                                token.initial_buy,
-                                token.v_tokens_in_bonding_curve,
-                                token.v_sol_in_bonding_curve,
-                                token.market_cap_sol,
-                            );
+/// Remove cooldowns by mint and/or kind
+pub async fn cooldowns_clear(
+    config: &Config,
+    mint: Option<&str>,
+    kind: Option<&str>,
+    all: bool,
+) -> Result<()> {
+    if mint.is_none() && kind.is_none() && !all {
+        anyhow::bail!("Specify a mint, --kind, or --all");
+    }
+    let kind: Option<crate::position::CooldownKind> = kind.map(|k| k.parse()).transpose()?;
 
-                            // Score the token
-                            let result = filter.score_fast(&signal_context).await;
+    let tracker = load_cooldowns(config, false).await;
+    let removed = tracker.clear(mint, kind).await?;
+    println!("Cleared {} cooldown(s)", removed);
+    println!("Note: a running bot keeps its own copy; restart it to pick up the change.");
 
-                            info!(
-                                "Adaptive filter: {} score={:.2} risk={:.2} confidence={:.2} recommendation={:?}",
-                                token.symbol, result.score, result.risk_score, result.confidence, result.recommendation
-                            );
+    Ok(())
+}
 
-                            // Log individual signals for debugging
-                            for signal in &result.signals {
-                                tracing::debug!(
-                                    signal_type = %signal.signal_type,
-                                    value = %signal.value,
-                                    confidence = %signal.confidence,
-                                    reason = %signal.reason,
-                                    "Signal contribution"
-                                );
-                            }
+/// Add an address to a local known actors list
+pub fn actors_add(config: &Config, address: &str, kind: &str, note: Option<&str>) -> Result<()> {
+    use crate::filter::known_actors;
 
-                            // Check recommendation using new confidence regime model
-                            // When information is weak, the system watches — not trades
-                            match result.recommendation {
-                                Recommendation::Avoid => {
-                                    warn!(
-                                        "Token {} marked AVOID by adaptive filter: {}",
-                                        token.symbol, result.summary
-                                    );
-                                    continue;
-                                }
-                                Recommendation::Observe => {
-                                    // OBSERVE = watch only, don't trade
-                                    // This is the key change: uncertainty means NO trading
-                                    info!(
-                                        "Token {} marked OBSERVE (insufficient data/confidence): {}",
-                                        token.symbol, result.summary
-                                    );
-                                    continue;
-                                }
-                                Recommendation::Probe => {
-                                    // PROBE = micro-position for learning only
-                                    // 5% position size, quick scalp exit
-                                    info!(
-                                        "Token {} in PROBE mode (learning position): {}",
-                                        token.symbol, result.summary
-                                    );
-                                    // Continue to trading with reduced size
-                                }
-                                Recommendation::Opportunity => {
-                                    // Standard buy opportunity
-                                    info!(
-                                        "Token {} marked OPPORTUNITY by adaptive filter: {}",
-                                        token.symbol, result.summary
-                                    );
-                                }
-                                Recommendation::StrongBuy => {
-                                    info!(
-                                        "Token {} marked STRONG BUY by adaptive filter: {}",
-                                        token.symbol, result.summary
-                                    );
-                                }
-                            }
+    Pubkey::from_str(address).map_err(|e| anyhow::anyhow!("Invalid address {}: {}", address, e))?;
+    let kind: crate::filter::ActorKind = kind.parse()?;
+    let path = kind.local_file(&config.adaptive_filter.known_actors);
 
-                            (result.position_size_multiplier, result.recommendation)
-                        } else {
-                            (1.0, Recommendation::Opportunity) // Default if adaptive filter disabled
-                        };
+    if known_actors::append_address(std::path::Path::new(path), address, note)? {
+        println!("Added {} to {} list ({})", address, kind, path);
+    } else {
+        println!("{} is already in the {} list", address, kind);
+    }
+    print_actors_refresh_note(config);
 
-                        // Strategy engine evaluation (if enabled)
-                        let (strategy_entry, strategy_size) = if let Some(ref engine) = strategy_engine {
-                            let mut engine_guard = engine.write().await;
+    Ok(())
+}
 
-                            // Build token analysis context for strategy engine
-                            // Note: PumpPortal sends v_sol_in_bonding_curve as SOL, not lamports
-                            // The value is typically ~30 SOL (virtual liquidity)
-                            // For actual tradeable liquidity, we use initial_buy or market_cap
-                            let liquidity_sol = if token.v_sol_in_bonding_curve < 1000 {
-                                // Small value = already in SOL
-                                token.v_sol_in_bonding_curve as f64
-                            } else {
-                                // Large value = lamports, convert to SOL
-                                token.v_sol_in_bonding_curve as f64 / 1e9
-                            };
-                            let token_reserves = token.v_tokens_in_bonding_curve as f64;
+/// Remove an address from a local known actors list
+pub fn actors_remove(config: &Config, address: &str, kind: &str) -> Result<()> {
+    use crate::filter::known_actors;
 
-                            // Create order flow analysis from available data
-                            let order_flow = crate::strategy::regime::OrderFlowAnalysis {
-                                organic_score: position_multiplier.max(0.5),
-                                wash_trading_score: 0.0,
-                                buy_sell_ratio: 1.0,
-                                early_sell_pressure: 0.0,
-                                burst_detected: false,
-                                burst_intensity: 0.0,
-                            };
+    let kind: crate::filter::ActorKind = kind.parse()?;
+    let path = kind.local_file(&config.adaptive_filter.known_actors);
 
-                            // Create token distribution from available data
-                            let distribution = crate::strategy::regime::TokenDistribution {
-                                holder_count: 1,
-                                top_holder_pct: 100.0,
-                                top_10_holders_pct: 100.0,
-                                deployer_holdings_pct: 0.0,
-                                sniper_holdings_pct: 0.0,
-                                gini_coefficient: 1.0,
-                            };
+    if known_actors::remove_address(std::path::Path::new(path), address)? {
+        println!("Removed {} from {} list ({})", address, kind, path);
+    } else {
+        println!("{} is not in the local {} list", address, kind);
+    }
+    print_actors_refresh_note(config);
 
-                            // Create creator behavior
-                            let creator_behavior = crate::strategy::regime::CreatorBehavior {
-                                selling_consistently: false,
-                                total_sold_pct: 0.0,
-                                avg_sell_interval_secs: 0,
-                                sell_count: 0,
-                            };
+    Ok(())
+}
 
-                            // Create minimal price action
-                            let price_action = crate::strategy::price_action::PriceAction::default();
+/// List the addresses in the local known actors lists
+pub fn actors_list(config: &Config, kind: Option<&str>) -> Result<()> {
+    use crate::filter::{known_actors, ActorKind};
 
-                            // Evaluate entry using strategy engine
-                            let analysis_ctx = crate::strategy::engine::TokenAnalysisContext {
-                                mint: token.mint.clone(),
-                                order_flow,
-                                distribution,
-                                creator_behavior,
-                                price_action,
-                                sol_reserves: liquidity_sol,
-                                token_reserves,
-                                confidence_score: position_multiplier,
-                            };
+    let kinds = match kind {
+        Some(kind) => vec![kind.parse::<ActorKind>()?],
+        None => vec![ActorKind::Deployer, ActorKind::Sniper, ActorKind::Trusted],
+    };
+    let actors_config = &config.adaptive_filter.known_actors;
+
+    println!("\n=== KNOWN ACTORS ===");
+    for kind in kinds {
+        let path = kind.local_file(actors_config);
+        let mut addresses: Vec<_> = known_actors::read_list(std::path::Path::new(path))
+            .into_iter()
+            .collect();
+        addresses.sort();
+
+        println!("\n{} ({}): {}", kind, path, addresses.len());
+        for address in &addresses {
+            println!("  {}", address);
+        }
+    }
+    let remote = &actors_config.remote_urls;
+    let remote_count = remote.deployers.len() + remote.snipers.len() + remote.trusted.len();
+    if remote_count > 0 {
+        println!("\n{} remote list(s) are merged in by the running bot", remote_count);
+    }
 
-                            let eval = engine_guard.evaluate_entry(&analysis_ctx).await;
+    Ok(())
+}
 
-                            // Check the decision
-                            match &eval.decision.action {
-                                TradingAction::Enter { mint: _, size_sol, strategy } => {
-                                    info!(
-                                        "Strategy engine: ENTER {} using {} strategy, size: {:.4} SOL",
-                                        token.symbol, strategy, size_sol
-                                    );
-                                    (true, *size_sol)
-                                }
-                                TradingAction::FatalReject { reason } => {
-                                    warn!(
-                                        "Strategy engine: FATAL REJECT for {}: {}",
-                                        token.symbol, reason
-                                    );
-                                    (false, 0.0)
-                                }
-                                TradingAction::Skip { reason } => {
-                                    info!(
-                                        "Strategy engine: SKIP {}: {}",
-                                        token.symbol, reason
-                                    );
-                                    (false, 0.0)
-                                }
-                                _ => {
-                                    // Hold or other action - fall through to adaptive filter decision
-                                    (true, config.trading.buy_amount_sol * position_multiplier)
-                                }
-                            }
-                        } else {
-                            // No strategy engine - use adaptive filter multiplier
-                            (true, config.trading.buy_amount_sol * position_multiplier)
-                        };
+/// Print a creator's observed launch history (creator_launches.json)
+pub fn actors_stats(config: &Config, creator: &str) -> Result<()> {
+    use crate::filter::creator_launches::{load_launches, CREATOR_LAUNCHES_FILE};
+
+    // The live bot's history, else a paper run's
+    let path = [false, true]
+        .into_iter()
+        .map(|paper| state_path(config, CREATOR_LAUNCHES_FILE, paper))
+        .find(|path| std::path::Path::new(path).exists())
+        .unwrap_or_else(|| state_path(config, CREATOR_LAUNCHES_FILE, false));
+    let launches = load_launches(std::path::Path::new(&path), creator)?;
+
+    println!("\n=== CREATOR {} ===", creator);
+    if launches.is_empty() {
+        println!("No launches observed ({})", path);
+        return Ok(());
+    }
+    let now = chrono::Utc::now();
+    let within = |hours| {
+        launches
+            .iter()
+            .filter(|l| now - l.at < chrono::Duration::hours(hours))
+            .count()
+    };
+    println!(
+        "Launches: {} in 1h, {} in 24h, {} in the last {}h",
+        within(1),
+        within(24),
+        launches.len(),
+        config.adaptive_filter.creator_launches.retention_hours
+    );
+    let actors = crate::filter::known_actors::load_local(&config.adaptive_filter.known_actors);
+    if actors.is_known_deployer(creator) {
+        println!("Listed as a known deployer");
+    }
+    println!();
+    for launch in launches.iter().rev() {
+        println!(
+            "  {}  {:<10} {}",
+            launch.at.format("%Y-%m-%d %H:%M:%S"),
+            launch.symbol,
+            launch.mint
+        );
+    }
 
-                        // Skip if strategy engine rejected
-                        if !strategy_entry {
-                            continue;
-                        }
+    Ok(())
+}
 
-                        let final_amount_sol = strategy_size;
+/// Wallet tracker reading tracked_wallets.json and the copy stats of the
+/// live bot (else a paper run's)
+fn load_wallet_tracker(config: &Config) -> Result<crate::filter::WalletTracker> {
+    use crate::filter::wallet_tracker::{COPY_STATS_FILE, TRACKED_WALLETS_FILE};
+
+    let stats_path = [false, true]
+        .into_iter()
+        .map(|paper| state_path(config, COPY_STATS_FILE, paper))
+        .find(|path| std::path::Path::new(path).exists())
+        .unwrap_or_else(|| state_path(config, COPY_STATS_FILE, false));
+    Ok(
+        crate::filter::WalletTracker::new(config.wallet_tracking.clone())?
+            .with_persistence(std::path::PathBuf::from(state_path(
+                config,
+                TRACKED_WALLETS_FILE,
+                false,
+            )))
+            .with_stats(std::path::PathBuf::from(stats_path)),
+    )
+}
 
-                        // Execute buy
-                        if !dry_run {
-                            if let Some(ref trader) = trader_arc {
-                                let mint = &token.mint;
-                                let slippage_pct = config.trading.slippage_bps / 100;
-                                let priority_fee = config.trading.priority_fee_lamports as f64 / 1e9;
+/// Send a `snipe track` change to the running bot, or apply it to
+/// tracked_wallets.json if no bot is running
+async fn send_track_command(
+    config: &Config,
+    command: crate::control::ControlCommand,
+) -> Result<()> {
+    use crate::control::{send_command, ControlCommand};
 
-                                // Apply entry delay for adversarial resistance
-                                if let Some(ref engine) = strategy_engine {
-                                    let delay = engine.read().await.get_entry_delay().await;
-                                    if delay.as_millis() > 0 {
-                                        tracing::debug!("Applying entry delay: {}ms", delay.as_millis());
-                                        tokio::time::sleep(delay).await;
-                                    }
-                                }
+    match send_command(&control_endpoint(config), &command).await {
+        Ok(response) if response.ok => {
+            println!("{}", response.message);
+            return Ok(());
+        }
+        Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+        Err(e) => warn!("{} - updating tracked wallets file directly", e),
+    }
+    ensure_bot_stopped(config)?;
+
+    let tracker = load_wallet_tracker(config)?;
+    match command {
+        ControlCommand::TrackWallet {
+            address,
+            size_mult,
+            mirror_sells,
+        } => {
+            let verb = if tracker.add_wallet(&address, size_mult, mirror_sells)? {
+                "Tracking"
+            } else {
+                "Updated"
+            };
+            println!("{} {} (applies on next start)", verb, address);
+        }
+        ControlCommand::UntrackWallet { address } => {
+            if !tracker.remove_wallet(&address)? {
+                anyhow::bail!("{} is not tracked", address);
+            }
+            println!("Stopped tracking {} (applies on next start)", address);
+        }
+        other => anyhow::bail!("{:?} is not a tracking command", other),
+    }
+    Ok(())
+}
+
+/// Copy-trade a wallet, or update the settings of a tracked one
+pub async fn track_add(
+    config: &Config,
+    address: &str,
+    size_mult: f64,
+    mirror_sells: Option<bool>,
+) -> Result<()> {
+    Pubkey::from_str(address).map_err(|e| anyhow::anyhow!("Invalid address {}: {}", address, e))?;
+    if !size_mult.is_finite() || size_mult <= 0.0 {
+        anyhow::bail!("--size-mult must be positive");
+    }
+    if !config.wallet_tracking.enabled || !config.wallet_tracking.auto_copy_trade {
+        warn!("[wallet_tracking] enabled and auto_copy_trade must be on for copies to be sent");
+    }
 
-                                info!("Buying {} SOL of {} ({})...", final_amount_sol, token.symbol, mint);
+    send_track_command(
+        config,
+        crate::control::ControlCommand::TrackWallet {
+            address: address.to_string(),
+            size_mult,
+            mirror_sells,
+        },
+    )
+    .await
+}
 
-                                // Use buy_local for Local API, buy for Lightning API
-                                let buy_result = if use_local_api {
-                                    trader.buy_local(mint, final_amount_sol, slippage_pct, priority_fee, &keypair, &rpc_client).await
-                                } else {
-                                    trader.buy(mint, final_amount_sol, slippage_pct, priority_fee).await
-                                };
+/// Stop copy-trading a wallet added with `snipe track add`
+pub async fn track_remove(config: &Config, address: &str) -> Result<()> {
+    send_track_command(
+        config,
+        crate::control::ControlCommand::UntrackWallet {
+            address: address.to_string(),
+        },
+    )
+    .await
+}
 
-                                match buy_result {
-                                    Ok(signature) => {
-                                        info!("Buy successful! Signature: {}", signature);
-                                        info!("View on Solscan: https://solscan.io/tx/{}", signature);
+/// Print tracked wallets with the P&L of their copies
+pub fn track_list(config: &Config) -> Result<()> {
+    let tracker = load_wallet_tracker(config)?;
+    let wallets = tracker.list();
 
-                                        // CRITICAL: Verify tokens were actually received before recording position
-                                        // Wait for transaction to confirm
-                                        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    println!("\n=== TRACKED WALLETS ===");
+    if wallets.is_empty() {
+        println!("No wallets tracked. Add one with `snipe track add <address>`");
+        return Ok(());
+    }
+    println!(
+        "{:<44} {:>6} {:>7} {:>7} {:>6} {:>10}  Source",
+        "Wallet", "Size", "Mirror", "Copied", "Wins", "P&L SOL"
+    );
+    for wallet in &wallets {
+        let settings = wallet.settings.as_ref();
+        let mirror = tracker.copy_settings(&wallet.address).mirror_sells;
+        let stats = &wallet.stats;
+        let source = if wallet.from_config {
+            "config"
+        } else {
+            "runtime"
+        };
+        let flag = if wallet.prune_suggested {
+            "  PRUNE?"
+        } else {
+            ""
+        };
+        println!(
+            "{:<44} {:>5.2}x {:>7} {:>7} {:>3}/{:<2} {:>+10.4}  {}{}",
+            wallet.address,
+            settings.map_or(1.0, |s| s.size_mult),
+            if mirror { "yes" } else { "no" },
+            stats.copied_trades,
+            stats.wins,
+            stats.closed_trades,
+            stats.pnl_sol,
+            source,
+            flag
+        );
+    }
 
-                                        // Determine which wallet to check based on API mode
-                                        let check_wallet = if use_local_api {
-                                            keypair.pubkey()
-                                        } else {
-                                            // For Lightning API, use the lightning wallet
-                                            Pubkey::from_str(&config.pumpportal.lightning_wallet)
-                                                .unwrap_or(keypair.pubkey())
-                                        };
+    let suggested: Vec<&str> = wallets
+        .iter()
+        .filter(|w| w.prune_suggested)
+        .map(|w| w.address.as_str())
+        .collect();
+    if !suggested.is_empty() {
+        println!(
+            "\n{} wallet(s) lost money over their last {} closed copies; consider `snipe track remove`:",
+            suggested.len(),
+            config.wallet_tracking.prune_sample
+        );
+        for address in suggested {
+            println!("  {}", address);
+        }
+    }
+    println!();
 
-                                        let actual_tokens = query_token_balance(&rpc_client, &check_wallet, mint);
+    Ok(())
+}
 
-                                        if actual_tokens == 0 {
-                                            // Transaction may have failed - DON'T record position
-                                            error!(
-                                                "BUY VERIFICATION FAILED: No tokens received for {} ({}) after 2s wait. TX may have failed silently. NOT recording position.",
-                                                token.symbol, mint
-                                            );
-                                            error!("Check transaction on Solscan: https://solscan.io/tx/{}", signature);
-                                            // Skip position recording and kill-switch setup
-                                            continue;
-                                        }
+/// List wallets found by trusted wallet discovery
+pub fn smart_money_list(config: &Config, category: Option<&str>) -> Result<()> {
+    use crate::filter::smart_money::discovery::read_discovered;
+    use crate::filter::DiscoveryStatus;
+
+    const CATEGORIES: [&str; 10] = [
+        "elite",
+        "demoted",
+        "candidate",
+        "true_signal",
+        "profitable",
+        "neutral",
+        "unprofitable",
+        "bundled_team",
+        "mev_bot",
+        "unknown",
+    ];
+    let category = category.map(|c| c.to_lowercase().replace('-', "_"));
+    if let Some(ref category) = category {
+        if !CATEGORIES.contains(&category.as_str()) {
+            anyhow::bail!(
+                "unknown category '{}' (expected one of: {})",
+                category,
+                CATEGORIES.join(", ")
+            );
+        }
+    }
+
+    let path = &config.smart_money.discovery.persist_path;
+    let mut wallets = read_discovered(std::path::Path::new(path))?;
+    wallets.retain(|w| match category.as_deref() {
+        None => true,
+        Some("elite") => w.status == DiscoveryStatus::Promoted,
+        Some("demoted") => w.status == DiscoveryStatus::Demoted,
+        Some("candidate") => w.status == DiscoveryStatus::Candidate,
+        // WalletCategory names in snake_case
+        Some(other) => format!("{:?}", w.category).to_lowercase() == other.replace('_', ""),
+    });
+    wallets.sort_by(|a, b| b.alpha_score.total_cmp(&a.alpha_score));
+
+    println!("\n=== DISCOVERED WALLETS ({}) ===", path);
+    if wallets.is_empty() {
+        match category {
+            Some(category) => println!("No wallets in category {}", category),
+            None => println!("No wallets discovered yet"),
+        }
+        return Ok(());
+    }
+    println!(
+        "{:<44} {:<9} {:>6} {:>6} {:>6} {:>7}  {:<13} Reason",
+        "Wallet", "Status", "Alpha", "Trades", "Win%", "Recent%", "Category"
+    );
+    for w in &wallets {
+        println!(
+            "{:<44} {:<9} {:>6.2} {:>6} {:>5.0}% {:>6.0}%  {:<13} {} (since {}, found on {})",
+            w.address,
+            w.status.to_string(),
+            w.alpha_score,
+            w.total_trades,
+            w.win_rate * 100.0,
+            w.recent_win_rate * 100.0,
+            format!("{:?}", w.category),
+            w.reason,
+            w.status_changed_at.format("%Y-%m-%d %H:%M"),
+            &w.found_on_mint[..w.found_on_mint.len().min(8)]
+        );
+    }
+    let promoted = wallets
+        .iter()
+        .filter(|w| w.status == DiscoveryStatus::Promoted)
+        .count();
+    println!(
+        "\n{} wallets, {} in the discovered trusted set",
+        wallets.len(),
+        promoted
+    );
+
+    Ok(())
+}
 
-                                        info!("BUY VERIFIED: Received {} tokens for {}", actual_tokens, token.symbol);
+/// Add an entry to an operator deny / allow list
+pub fn list_add(config: &Config, kind: &str, entry: &str, note: Option<&str>) -> Result<()> {
+    use crate::filter::{known_actors, ListKind};
 
-                                        // Record position with ACTUAL token amount (not estimate)
-                                        let estimated_price = if token.v_tokens_in_bonding_curve > 0 {
-                                            token.v_sol_in_bonding_curve as f64 / token.v_tokens_in_bonding_curve as f64
-                                        } else {
-                                            0.000001 // fallback
-                                        };
+    let kind: ListKind = kind.parse()?;
+    kind.validate(entry)?;
+    let path = kind.local_file(&config.adaptive_filter.known_actors);
 
-                                        // Convert recommendation to EntryType for context-aware exits
-                                        let entry_type = match entry_recommendation {
-                                            Recommendation::StrongBuy => crate::position::manager::EntryType::StrongBuy,
-                                            Recommendation::Opportunity => crate::position::manager::EntryType::Opportunity,
-                                            Recommendation::Probe => crate::position::manager::EntryType::Probe,
-                                            _ => crate::position::manager::EntryType::Legacy,
-                                        };
+    if known_actors::append_address(std::path::Path::new(path), entry, note)? {
+        println!("Added {} to {} list ({})", entry, kind, path);
+    } else {
+        println!("{} is already in the {} list", entry, kind);
+    }
+    print_actors_refresh_note(config);
 
-                                        let position = crate::position::manager::Position {
-                                            mint: token.mint.clone(),
-                                            name: token.name.clone(),
-                                            symbol: token.symbol.clone(),
-                                            bonding_curve: token.bonding_curve_key.clone(),
-                                            token_amount: actual_tokens, // Use ACTUAL tokens, not estimate
-                                            entry_price: estimated_price,
-                                            total_cost_sol: final_amount_sol,
-                                            entry_time: chrono::Utc::now(),
-                                            entry_signature: signature.clone(),
-                                            entry_type,
-                                            quick_profit_taken: false,
-                                            second_profit_taken: false,
-                                            peak_price: estimated_price,
-                                            current_price: estimated_price,
-                                            kill_switch_triggered: false,
-                                            kill_switch_reason: None,
-                                            wallet_pubkey: keypair.pubkey().to_string(),
-                                        };
+    Ok(())
+}
 
-                                        if let Err(e) = position_manager.open_position(position).await {
-                                            error!("Failed to record position: {}", e);
-                                        }
+/// Remove an entry from an operator deny / allow list
+pub fn list_remove(config: &Config, kind: &str, entry: &str) -> Result<()> {
+    use crate::filter::{known_actors, ListKind};
 
-                                        // Start kill-switch monitoring for this position
-                                        if let Some(ref evaluator) = kill_switch_evaluator {
-                                            // Creator is the trader_public_key for new tokens
-                                            let creator = token.trader_public_key.clone();
-                                            // TODO: Fetch top holders from Helius for holder_watcher
-                                            // For now, we just track the deployer
-                                            evaluator.watch_position(&token.mint, &creator, vec![]);
-                                            info!(
-                                                "Kill-switch monitoring active for {} (creator: {})",
-                                                &token.mint[..12], &creator[..8]
-                                            );
-                                        }
+    let kind: ListKind = kind.parse()?;
+    let path = kind.local_file(&config.adaptive_filter.known_actors);
 
-                                        // Record entry in strategy engine
-                                        if let Some(ref engine) = strategy_engine {
-                                            let strategy_position = crate::strategy::types::Position {
-                                                mint: token.mint.clone(),
-                                                entry_price: estimated_price,
-                                                entry_time: chrono::Utc::now(),
-                                                size_sol: final_amount_sol,
-                                                tokens_held: actual_tokens,
-                                                strategy: config.strategy.default_strategy.clone(),
-                                                exit_style: crate::strategy::types::ExitStyle::default(),
-                                                highest_price: estimated_price,
-                                                lowest_price: estimated_price,
-                                                exit_levels_hit: vec![],
-                                            };
-                                            engine.write().await.record_entry(strategy_position).await;
-                                        }
-                                    }
-                                    Err(e) => {
-                                        error!("Buy failed for {}: {}", token.symbol, e);
-                                    }
-                                }
-                            }
-                        } else {
-                            info!(
-                                "DRY-RUN: Would buy {} SOL of {} (strategy size)",
-                                final_amount_sol, token.mint
-                            );
-                        }
-                    }
-                    PumpPortalEvent::Trade(trade) => {
-                        // Calculate SOL amount for logging
-                        let sol_amount = trade.sol_amount as f64 / 1e9;
+    if known_actors::remove_address(std::path::Path::new(path), entry)? {
+        println!("Removed {} from {} list ({})", entry, kind, path);
+    } else {
+        println!("{} is not in the {} list", entry, kind);
+    }
+    print_actors_refresh_note(config);
 
-                        // Log all trades for visibility
-                        info!(
-                            "Trade: {} {} {:.6} SOL on {} (mcap: {:.2})",
-                            &trade.trader_public_key[..8],
-                            trade.tx_type,
-                            sol_amount,
-                            &trade.mint[..12],
-                            trade.market_cap_sol
-                        );
+    Ok(())
+}
 
-                        // KILL-SWITCH: Check sells on tokens we hold
-                        if trade.tx_type == "sell" {
-                            // Check if we have a position in this token
-                            let positions = position_manager.get_all_positions().await;
-                            let our_position = positions.iter().find(|p| p.mint == trade.mint);
+/// Show the operator deny / allow lists
+pub fn list_show(config: &Config, kind: Option<&str>) -> Result<()> {
+    use crate::filter::{known_actors, ListKind};
 
-                            if let Some(position) = our_position {
-                                let position_token_amount = position.token_amount;
+    let kinds = match kind {
+        Some(kind) => vec![kind.parse::<ListKind>()?],
+        None => ListKind::ALL.to_vec(),
+    };
+    let actors_config = &config.adaptive_filter.known_actors;
+
+    println!("\n=== DENY / ALLOW LISTS ===");
+    for kind in kinds {
+        let path = kind.local_file(actors_config);
+        let mut entries: Vec<_> = known_actors::read_list(std::path::Path::new(path))
+            .into_iter()
+            .collect();
+        entries.sort();
+
+        println!("\n{} ({}): {}", kind, path, entries.len());
+        for entry in &entries {
+            println!("  {}", entry);
+        }
+    }
+    println!(
+        "\nAllow-listed creators score {:+.2}; deny-symbol patterns apply with [filters] enabled",
+        actors_config.allow_creator_bonus
+    );
 
-                                if let Some(ref evaluator) = kill_switch_evaluator {
-                                    let decision = evaluator.evaluate_sell(
-                                        &trade.mint,
-                                        &trade.trader_public_key,
-                                        trade.token_amount as u64,
-                                        sol_amount,
-                                        &trade.signature,
-                                    );
+    Ok(())
+}
 
-                                    if let KillSwitchDecision::Exit(alert) = decision {
-                                        warn!(
-                                            "KILL-SWITCH TRIGGERED for {}: {} - AUTO-SELLING",
-                                            &trade.mint[..12], alert.reason
-                                        );
+fn print_actors_refresh_note(config: &Config) {
+    println!(
+        "Note: a running bot picks up the change within {}s.",
+        config.adaptive_filter.known_actors.refresh_interval_secs
+    );
+}
 
-                                        // Execute emergency sell if not dry run
-                                        if !dry_run {
-                                            if let Some(ref trader) = trader_arc {
-                                                let slippage_pct = config.trading.slippage_bps / 100;
-                                                let priority_fee = config.trading.priority_fee_lamports as f64 / 1e9;
+/// Print the most recent decision log entries, optionally following new ones
+pub async fn decisions_tail(config: &Config, limit: usize, follow: bool) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
 
-                                                // Sell 100% immediately
-                                                info!(
-                                                    "Executing kill-switch sell for {} (urgency: {:?})",
-                                                    &trade.mint[..12], alert.urgency
-                                                );
+    let log_config = &config.adaptive_filter.decision_log;
+    let records = crate::filter::decision_log::read_records(log_config)?;
+    if records.is_empty() && !follow {
+        println!("No decisions logged yet ({})", log_config.path);
+        return Ok(());
+    }
+    for record in &records[records.len().saturating_sub(limit)..] {
+        print_decision(record, false);
+    }
+    if !follow {
+        return Ok(());
+    }
 
-                                                let sell_result = if use_local_api {
-                                                    trader.sell_local(
-                                                        &trade.mint,
-                                                        "100%", // 100% sell
-                                                        slippage_pct,
-                                                        priority_fee,
-                                                        &keypair,
-                                                        &rpc_client,
-                                                    ).await
-                                                } else {
-                                                    trader.sell(&trade.mint, "100%", slippage_pct, priority_fee).await
-                                                };
+    // Poll the active file for appended lines (a shrink means it rotated)
+    let path = std::path::Path::new(&log_config.path);
+    let file_len = |path: &std::path::Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let mut offset = file_len(path);
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let len = file_len(path);
+        if len < offset {
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        let mut file = std::fs::File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut appended = String::new();
+        file.read_to_string(&mut appended)?;
+
+        // Leave a partially written last line for the next pass
+        let complete = appended.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        for line in appended[..complete].lines() {
+            if let Ok(record) = serde_json::from_str::<crate::filter::DecisionRecord>(line) {
+                print_decision(&record, false);
+            }
+        }
+        offset += complete as u64;
+    }
+}
 
-                                                match sell_result {
-                                                    Ok(sig) => {
-                                                        warn!(
-                                                            "KILL-SWITCH SELL EXECUTED: {} - sig: {}",
-                                                            alert.reason, sig
-                                                        );
+/// Print every logged decision for one mint with its signal breakdown
+pub fn decisions_show(config: &Config, mint: &str) -> Result<()> {
+    use crate::filter::decision_log;
 
-                                                        // Close position in manager (use position's token amount since we sold 100%)
-                                                        // Note: We don't know exact proceeds yet, estimate from current price
-                                                        let estimated_proceeds = position_token_amount as f64 * trade.market_cap_sol / 1_000_000_000.0;
-                                                        if let Err(e) = position_manager.close_position(&trade.mint, position_token_amount, estimated_proceeds).await {
-                                                            error!("Failed to close position after kill-switch: {}", e);
-                                                        }
+    let records: Vec<_> = decision_log::read_records(&config.adaptive_filter.decision_log)?
+        .into_iter()
+        .filter(|r| r.mint == mint)
+        .collect();
+    if records.is_empty() {
+        println!("No logged decisions for {}", mint);
+        return Ok(());
+    }
+    for record in &records {
+        print_decision(record, true);
+        println!();
+    }
+    Ok(())
+}
 
-                                                        // Stop monitoring this position
-                                                        evaluator.unwatch_position(&trade.mint);
-                                                    }
-                                                    Err(e) => {
-                                                        error!(
-                                                            "KILL-SWITCH SELL FAILED for {}: {} - MANUAL EXIT NEEDED!",
-                                                            &trade.mint[..12], e
-                                                        );
-                                                    }
-                                                }
-                                            }
-                                        } else {
-                                            warn!(
-                                                "DRY-RUN: Kill-switch would sell 100% of {} (reason: {})",
-                                                &trade.mint[..12], alert.reason
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
+/// Count arbitration conflicts in the decision log by component and rule
+pub fn decisions_conflicts(config: &Config) -> Result<()> {
+    use crate::filter::decision_log::{self, ConflictSummary};
 
-                        // Check for tracked wallet trades (copy trading)
-                        if config.wallet_tracking.enabled && tracked_wallets.contains(&trade.trader_public_key) {
-                            info!(
-                                "Tracked wallet {} {} {:.4} SOL of {}",
-                                trade.trader_public_key,
-                                if trade.tx_type == "buy" { "bought" } else { "sold" },
-                                sol_amount,
-                                trade.mint
-                            );
+    let records = decision_log::read_records(&config.adaptive_filter.decision_log)?;
+    let summary = ConflictSummary::build(&records);
 
-                            // Copy the trade if it's a buy
-                            if trade.tx_type == "buy" && !dry_run {
-                                if let Some(ref trader) = trader_arc {
-                                    let slippage_pct = config.trading.slippage_bps / 100;
-                                    let priority_fee = config.trading.priority_fee_lamports as f64 / 1e9;
+    println!("\n=== ARBITRATION CONFLICTS ===\n");
+    println!(
+        "{} conflicts in {} logged decisions",
+        summary.conflicts, summary.decisions
+    );
+    if summary.conflicts == 0 {
+        return Ok(());
+    }
 
-                                    info!("Copy trading: buying {} SOL of {}", config.trading.buy_amount_sol, trade.mint);
-                                    let copy_result = if use_local_api {
-                                        trader.buy_local(&trade.mint, config.trading.buy_amount_sol, slippage_pct, priority_fee, &keypair, &rpc_client).await
-                                    } else {
-                                        trader.buy(&trade.mint, config.trading.buy_amount_sol, slippage_pct, priority_fee).await
-                                    };
-                                    match copy_result {
-                                        Ok(sig) => info!("Copy trade executed: {}", sig),
-                                        Err(e) => error!("Copy trade failed: {}", e),
-                                    }
-                                }
-                            }
-                        }
+    println!(
+        "\n{:<16} {:>8} {:>6} {:>10} {:>10}",
+        "component", "proposed", "won", "overridden", "override%"
+    );
+    for (component, tally) in &summary.components {
+        println!(
+            "{:<16} {:>8} {:>6} {:>10} {:>9.0}%",
+            component,
+            tally.proposed,
+            tally.won,
+            tally.overridden,
+            tally.overridden as f64 / tally.proposed.max(1) as f64 * 100.0
+        );
+    }
 
-                        // Evaluate tokens with significant buy volume that we haven't seen before
-                        if trade.tx_type == "buy" && sol_amount >= 0.05 {
-                            let mut seen = seen_trade_tokens.lock().await;
+    println!("\nOverrides (overridden <- winner, rule):");
+    let mut overrides: Vec<_> = summary.overrides.iter().collect();
+    overrides.sort_by(|a, b| b.1.cmp(a.1));
+    for ((overridden, winner, rule), count) in overrides {
+        println!("  {:>5}  {} <- {} ({})", count, overridden, winner, rule);
+    }
+    Ok(())
+}
 
-                            // Skip if we've already evaluated this token
-                            if seen.contains(&trade.mint) {
-                                continue;
-                            }
+/// One-line decision summary, plus each signal's contribution when `with_signals`
+fn print_decision(record: &crate::filter::DecisionRecord, with_signals: bool) {
+    let result = &record.result;
+    let recommendation = format!("{:?}", result.recommendation);
+    let symbol = if record.symbol.is_empty() {
+        "-"
+    } else {
+        &record.symbol
+    };
+    let detail = match (&record.detail, &record.simulation) {
+        (Some(detail), Some(simulation)) => {
+            Some(format!(" ({}: {})", detail, simulation.summary()))
+        }
+        (Some(detail), None) => Some(format!(" ({})", detail)),
+        (None, _) => None,
+    };
+    println!(
+        "{} {:<13} {:<11} score {:+.2} conf {:.2} {} {}{}",
+        record.timestamp.format("%Y-%m-%d %H:%M:%S"),
+        record.action.to_string(),
+        recommendation,
+        result.score,
+        result.confidence,
+        symbol,
+        record.mint,
+        detail.unwrap_or_default()
+    );
+    if !with_signals {
+        return;
+    }
 
-                            // Check if we already have a position in this token
-                            let positions = position_manager.get_all_positions().await;
-                            if positions.iter().any(|p| p.mint == trade.mint) {
-                                seen.insert(trade.mint.clone());
-                                continue;
-                            }
+    println!("  {}", result.summary);
+    if let Some(ref simulation) = record.simulation {
+        for log in &simulation.logs {
+            println!("  {}", log);
+        }
+    }
+    if let Some(ref arbitration) = record.arbitration {
+        println!(
+            "  arbitration: {} won ({}) -> {}",
+            arbitration.winner, arbitration.rule, arbitration.action
+        );
+        for proposal in &arbitration.proposals {
+            println!(
+                "    {:<16} {:<6} conf {:.2}  {}",
+                proposal.source.to_string(),
+                proposal.action.to_string(),
+                proposal.confidence,
+                proposal.reason
+            );
+        }
+    }
+    let mut signals: Vec<_> = result.signals.iter().collect();
+    signals.sort_by(|a, b| {
+        let impact = |s: &crate::filter::Signal| (s.value * s.confidence * s.weight).abs();
+        impact(b).total_cmp(&impact(a))
+    });
+    for signal in signals {
+        println!(
+            "  {:<24} value {:+.2} conf {:.2} weight {:.1}  {}",
+            signal.signal_type.to_string(),
+            signal.value,
+            signal.confidence,
+            signal.weight,
+            signal.reason
+        );
+    }
+}
 
-                            // Mark as seen
-                            seen.insert(trade.mint.clone());
-                            drop(seen); // Release lock before async operations
+/// Propose signal weights from logged entries and their closed-trade outcomes
+///
+/// Only prints a suggested `[adaptive_filter.weights]` block; config.toml and
+/// the running filter are left untouched.
+pub fn tune(config: &Config, min_samples: usize) -> Result<()> {
+    use crate::filter::decision_log;
+    use crate::filter::tuning::{self, TuningReport};
+    use crate::position::history;
+
+    let records = decision_log::read_records(&config.adaptive_filter.decision_log)?;
+    let trades_path = std::path::Path::new(&config.wallet.credentials_dir).join("trades.json");
+    let trades = history::load_trades(&trades_path)?;
+    let outcomes = tuning::join_outcomes(&records, &trades);
+
+    println!("\n=== SIGNAL TUNING ===\n");
+    println!(
+        "{} closed trades, {} matched to logged entries",
+        trades.len(),
+        outcomes.len()
+    );
+    if outcomes.is_empty() {
+        println!("Nothing to tune yet - trades need a `bought` decision in the decision log.");
+        return Ok(());
+    }
 
-                            info!(
-                                "Trade detected: {} bought {:.4} SOL of {} (mcap: {:.2} SOL) - evaluating...",
-                                &trade.trader_public_key[..8],
-                                sol_amount,
-                                trade.mint,
-                                trade.market_cap_sol
-                            );
+    let report = TuningReport::build(
+        &outcomes,
+        &config.adaptive_filter.signal_weights(),
+        min_samples,
+    );
+    println!(
+        "Baseline win rate: {:.0}% ({}/{})\n",
+        report.wins as f64 / report.trades as f64 * 100.0,
+        report.wins,
+        report.trades
+    );
 
-                            // Calculate liquidity from bonding curve
-                            // Note: PumpPortal sends values in SOL, not lamports
-                            let liquidity_sol = if trade.v_sol_in_bonding_curve < 1000.0 {
-                                trade.v_sol_in_bonding_curve as f64
-                            } else {
-                                trade.v_sol_in_bonding_curve as f64 / 1e9
-                            };
+    println!(
+        "{:<24} {:>7} {:>13} {:>13} {:>7} {:>6} {:>8}",
+        "signal", "samples", "win% bullish", "win% bearish", "r", "weight", "proposed"
+    );
+    let rate = |bucket: &tuning::Bucket| match bucket.win_rate() {
+        Some(rate) => format!("{:.0}% of {}", rate * 100.0, bucket.trades),
+        None => "-".to_string(),
+    };
+    for signal in &report.signals {
+        let correlation = signal
+            .correlation
+            .map_or("-".to_string(), |r| format!("{:+.2}", r));
+        let proposed = signal
+            .suggested_weight
+            .map_or("-".to_string(), |w| format!("{:.2}", w));
+        println!(
+            "{:<24} {:>7} {:>13} {:>13} {:>7} {:>6.2} {:>8}",
+            tuning::config_key(signal.signal_type),
+            signal.samples,
+            rate(&signal.positive),
+            rate(&signal.negative),
+            correlation,
+            signal.current_weight,
+            proposed
+        );
+    }
+
+    let low: Vec<_> = report.low_sample_signals().collect();
+    if !low.is_empty() {
+        println!();
+        for signal in low {
+            println!(
+                "WARNING: {} has only {} samples (need {}) - weight left unchanged",
+                tuning::config_key(signal.signal_type),
+                signal.samples,
+                min_samples
+            );
+        }
+    }
 
-                            // Quick liquidity check
-                            if liquidity_sol < 0.05 {
-                                info!("Token {} rejected: liquidity {:.4} SOL too low", trade.mint, liquidity_sol);
-                                continue;
-                            }
+    println!("\nSuggested weights (not applied - review and copy into config.toml):\n");
+    print!("{}", report.to_toml());
+    Ok(())
+}
 
-                            // Use configured buy amount for trade-based entries
-                            let final_amount_sol = config.trading.buy_amount_sol;
+/// Print peak/drawdown analytics over closed trades
+pub async fn stats(config: &Config, from: Option<&str>, to: Option<&str>) -> Result<()> {
+    use crate::position::history::{self, TradeFilter};
+    use crate::position::{shadow, ExcursionStats};
 
-                            info!(
-                                "Trade signal: BUY {:.4} SOL of {} (liquidity: {:.4} SOL)",
-                                final_amount_sol, trade.mint, liquidity_sol
-                            );
+    let filter = TradeFilter {
+        from: from.map(|d| history::parse_date(d, false)).transpose()?,
+        to: to.map(|d| history::parse_date(d, true)).transpose()?,
+        ..Default::default()
+    };
 
-                            if !dry_run {
-                                if let Some(ref trader) = trader_arc {
-                                    let slippage_pct = config.trading.slippage_bps / 100;
-                                    let priority_fee = config.trading.priority_fee_lamports as f64 / 1e9;
+    let dir = std::path::Path::new(&config.wallet.credentials_dir);
+    let trades: Vec<_> = history::load_trades(&dir.join("trades.json"))?
+        .into_iter()
+        .filter(|t| filter.matches(t))
+        .collect();
+    let shadows: Vec<_> = shadow::load_shadow_outcomes(&dir.join("shadow_trades.json"))?
+        .into_iter()
+        .filter(|s| {
+            filter.from.map_or(true, |from| s.exit_time >= from)
+                && filter.to.map_or(true, |to| s.exit_time < to)
+        })
+        .collect();
+
+    println!("\n=== TRADE STATS ===\n");
+    if !shadows.is_empty() {
+        print_win_rate_comparison(&trades, &shadows);
+    }
+    if trades.is_empty() {
+        println!("No closed trades.");
+        return Ok(());
+    }
 
-                                    let buy_result = if use_local_api {
-                                        trader.buy_local(&trade.mint, final_amount_sol, slippage_pct, priority_fee, &keypair, &rpc_client).await
-                                    } else {
-                                        trader.buy(&trade.mint, final_amount_sol, slippage_pct, priority_fee).await
-                                    };
-                                    match buy_result {
-                                        Ok(sig) => {
-                                            info!("Trade buy executed: {}", sig);
-                                            // Estimate tokens from market cap
-                                            let estimated_price = if trade.market_cap_sol > 0.0 {
-                                                trade.market_cap_sol / 1_000_000_000.0
-                                            } else {
-                                                0.000001
-                                            };
-                                            let estimated_tokens = (final_amount_sol / estimated_price) as u64;
+    let stats = ExcursionStats::from_trades(&trades);
+    println!(
+        "Closes: {} ({} with peak/drawdown data)",
+        stats.trades, stats.tracked
+    );
 
-                                            // Record position - trade event entries are treated as Probe
-                                            // since we have less information than new token events
-                                            let position = crate::position::manager::Position {
-                                                mint: trade.mint.clone(),
-                                                name: format!("Trade-{}", &trade.mint[..8]),
-                                                symbol: "???".to_string(),
-                                                bonding_curve: trade.bonding_curve_key.clone(),
-                                                token_amount: estimated_tokens,
-                                                entry_price: estimated_price,
-                                                total_cost_sol: final_amount_sol,
-                                                entry_time: chrono::Utc::now(),
-                                                entry_signature: sig.clone(),
-                                                entry_type: crate::position::manager::EntryType::Probe, // Conservative for trade-based entries
-                                                quick_profit_taken: false,
-                                                second_profit_taken: false,
-                                                peak_price: estimated_price,
-                                                current_price: estimated_price,
-                                                kill_switch_triggered: false,
-                                                kill_switch_reason: None,
-                                                wallet_pubkey: keypair.pubkey().to_string(),
-                                            };
-                                            if let Err(e) = position_manager.open_position(position).await {
-                                                error!("Failed to record position: {}", e);
-                                            }
-                                        }
-                                        Err(e) => error!("Trade buy failed: {}", e),
-                                    }
-                                }
-                            } else {
-                                info!(
-                                    "DRY-RUN: Would buy {:.4} SOL of {} based on trade activity",
-                                    final_amount_sol, trade.mint
-                                );
-                            }
-                        }
-                    }
-                    PumpPortalEvent::Connected => {
-                        info!("Connected to token detection source");
-                    }
-                    PumpPortalEvent::Disconnected => {
-                        warn!("Disconnected from token detection source");
-                    }
-                    PumpPortalEvent::Error(e) => {
-                        error!("Token detection error: {}", e);
-                    }
-                }
-            }
-            _ = tokio::signal::ctrl_c() => {
-                info!("Received shutdown signal");
-                // Save positions before exit
-                if let Err(e) = position_manager.save().await {
-                    error!("Failed to save positions: {}", e);
-                }
-                break;
-            }
+    if stats.tracked > 0 {
+        println!("\nPeak capture:");
+        println!("  Avg peak (MFE):      {:+.1}%", stats.avg_mfe_pct);
+        println!("  Avg exit:            {:+.1}%", stats.avg_exit_pct);
+        println!("  Avg given back:      {:.1} pts", stats.avg_given_back_pct);
+        println!("  Avg drawdown (MAE):  {:+.1}%", stats.avg_mae_pct);
+        match stats.capture_ratio() {
+            Some(ratio) => println!("  Captured:            {:.0}% of the average peak", ratio * 100.0),
+            None => println!("  Captured:            n/a (no peak gain)"),
+        }
+        println!(
+            "  Given back:          {:.4} SOL of {:.4} SOL peak gain",
+            stats.total_given_back_sol, stats.total_mfe_sol
+        );
+
+        println!("\nBy exit reason (most given back first):");
+        for r in &stats.by_reason {
+            println!(
+                "  {:<14} {:>4} trades | peak {:+.1}% | exit {:+.1}% | gave back {:.1} pts / {:.4} SOL",
+                r.reason.to_string(),
+                r.trades,
+                r.avg_mfe_pct,
+                r.avg_exit_pct,
+                r.avg_given_back_pct,
+                r.given_back_sol
+            );
         }
     }
 
+    // Regimes losing money here are candidates for a zero regime_strategies multiplier
+    if stats.by_regime.iter().any(|r| r.regime.is_some()) {
+        println!("\nBy entry regime (worst P&L first):");
+        for r in &stats.by_regime {
+            println!(
+                "  {:<14} {:>4} trades | win {:>5.1}% | avg {:+.1}% | {:+.4} SOL",
+                r.label(),
+                r.trades,
+                r.win_rate_pct(),
+                r.avg_pnl_pct,
+                r.pnl_sol
+            );
+        }
+    }
+
+    println!("\nHold times (median {}s):", stats.median_hold_secs);
+    for (label, count) in &stats.hold_distribution {
+        let pct = *count as f64 / stats.trades as f64 * 100.0;
+        println!("  {:<10} {:>4} ({:>5.1}%) {}", label, count, pct, "#".repeat((pct / 2.0).round() as usize));
+    }
+
     Ok(())
 }
 
-/// Manually sell a token position
-pub async fn sell(
-    config: &Config,
-    token: &str,
-    amount: &str,
-    force: bool,
-    dry_run: bool,
-) -> Result<()> {
-    info!("Sell command: token={}, amount={}", token, amount);
+/// Precision and recall of logged rug predictions
+pub fn stats_rug_predictor(config: &Config, from: Option<&str>, to: Option<&str>) -> Result<()> {
+    use crate::position::history;
+    use crate::position::rug_watch::{load_rug_predictions, PredictionScore};
+
+    let from = from.map(|d| history::parse_date(d, false)).transpose()?;
+    let to = to.map(|d| history::parse_date(d, true)).transpose()?;
+    let path = std::path::Path::new(&config.wallet.credentials_dir).join("rug_predictions.json");
+    let records: Vec<_> = load_rug_predictions(&path)?
+        .into_iter()
+        .filter(|r| from.map_or(true, |from| r.time >= from) && to.map_or(true, |to| r.time < to))
+        .collect();
+
+    let rug = &config.strategy.tactics.rug_predictor;
+    println!("\n=== RUG PREDICTOR ===\n");
+    if records.is_empty() {
+        println!("No resolved predictions.");
+        return Ok(());
+    }
 
-    // Parse token address
-    let _token_pubkey = solana_sdk::pubkey::Pubkey::try_from(token)
-        .map_err(|e| anyhow::anyhow!("Invalid token address: {}", e))?;
+    let drops = records.iter().filter(|r| r.dropped).count();
+    println!(
+        "Predictions: {} | followed by a drop: {} ({:.1}%)",
+        records.len(),
+        drops,
+        drops as f64 / records.len() as f64 * 100.0
+    );
+    println!(
+        "(drop = price fell more than {:.0}% within {} min, as configured when logged)\n",
+        rug.outcome_drop_pct, rug.outcome_window_mins
+    );
 
-    // Parse amount (can be percentage like "50%" or absolute)
-    let is_percentage = amount.ends_with('%');
-    let amount_value: f64 = if is_percentage {
-        amount
-            .trim_end_matches('%')
-            .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid amount: {}", e))?
-    } else {
-        amount
-            .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid amount: {}", e))?
-    };
+    let pct = |v: Option<f64>| v.map_or("n/a".to_string(), |v| format!("{:.1}%", v * 100.0));
+    for (label, threshold) in [
+        ("warning", rug.warning_threshold),
+        ("critical", rug.critical_threshold),
+    ] {
+        let score = PredictionScore::at_threshold(&records, threshold);
+        println!(
+            "  {:<8} >= {:>3.0}%  flagged {:>5} | precision {:>6} | recall {:>6}",
+            label,
+            threshold * 100.0,
+            score.true_positives + score.false_positives,
+            pct(score.precision()),
+            pct(score.recall())
+        );
+    }
+    Ok(())
+}
 
-    if is_percentage && (amount_value <= 0.0 || amount_value > 100.0) {
-        anyhow::bail!("Percentage must be between 0 and 100");
+/// Replay a recorded session and report what the current config would have traded
+pub async fn backtest(config: &Config, events_path: &str) -> Result<()> {
+    use crate::backtest::{read_events, Backtest, Breakdown};
+
+    let events = read_events(std::path::Path::new(events_path))?;
+    if events.is_empty() {
+        anyhow::bail!("No events in {}", events_path);
     }
+    let span = events[events.len() - 1].time - events[0].time;
+    let report = Backtest::new(config).await?.run(events).await;
 
-    // Initialize RPC client for balance queries
-    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
-        config.rpc.endpoint.clone(),
-        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    println!("\n=== BACKTEST ===\n");
+    println!(
+        "Replayed {} events over {} min: {} new tokens, {} buys sent ({} failed)",
+        report.events,
+        span.num_minutes(),
+        report.tokens,
+        report.buys,
+        report.failed_buys
     );
+    println!(
+        "Fills: {} ms latency, {:.1}% slippage | failed sells (retried): {}",
+        config.backtest.latency_ms, config.backtest.slippage_pct, report.failed_sells
+    );
+    if !report.skipped.is_empty() {
+        let skipped: Vec<String> = report
+            .skipped
+            .iter()
+            .map(|(reason, count)| format!("{} {}", reason, count))
+            .collect();
+        println!("Skipped: {}", skipped.join(", "));
+    }
 
-    // Determine which wallet to query for balance (Lightning or local)
-    let balance_wallet = if !config.pumpportal.lightning_wallet.is_empty() {
-        Pubkey::from_str(&config.pumpportal.lightning_wallet)?
-    } else {
-        // Fall back to local keypair
-        let keypair_path = std::env::var("KEYPAIR_PATH")
-            .unwrap_or_else(|_| "credentials/hot-trading/keypair.json".to_string());
-        let keypair_data = std::fs::read_to_string(&keypair_path)?;
-        let secret_key: Vec<u8> = serde_json::from_str(&keypair_data)?;
-        let keypair = Keypair::from_bytes(&secret_key)?;
-        keypair.pubkey()
-    };
-
-    // Initialize position manager
-    let position_manager = std::sync::Arc::new(crate::position::manager::PositionManager::new(
-        config.safety.clone(),
-        Some(format!("{}/positions.json", config.wallet.credentials_dir)),
-    ));
-    if let Err(e) = position_manager.load().await {
-        warn!("Could not load positions: {} (continuing anyway)", e);
+    let total = report.total();
+    println!(
+        "\nCloses: {} | win {:.1}% | P&L {:+.4} SOL | max drawdown {:.4} SOL",
+        total.trades,
+        total.win_rate(),
+        total.pnl_sol,
+        report.max_drawdown_sol()
+    );
+    if report.open_positions > 0 {
+        println!(
+            "Still open: {} ({:+.4} SOL unrealized at the last recorded price)",
+            report.open_positions, report.unrealized_pnl_sol
+        );
     }
 
-    // Load bought_mints cache
-    let bought_mints_path = format!("{}/bought_mints.json", config.wallet.credentials_dir);
-    let bought_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> = {
-        if std::path::Path::new(&bought_mints_path).exists() {
-            match std::fs::read_to_string(&bought_mints_path) {
-                Ok(data) => {
-                    if let Ok(mints) = serde_json::from_str::<std::collections::HashMap<String, i64>>(&data) {
-                        std::sync::Arc::new(tokio::sync::Mutex::new(mints))
-                    } else {
-                        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()))
-                    }
-                }
-                Err(_) => std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
-            }
-        } else {
-            std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()))
+    let print_breakdown = |title: &str, groups: std::collections::BTreeMap<String, Breakdown>| {
+        if groups.is_empty() {
+            return;
+        }
+        println!("\nBy {}:", title);
+        for (label, b) in groups {
+            println!(
+                "  {:<16} {:>4} trades | win {:>5.1}% | {:+.4} SOL",
+                label,
+                b.trades,
+                b.win_rate(),
+                b.pnl_sol
+            );
         }
     };
-    let bought_mints_path = std::sync::Arc::new(bought_mints_path);
+    print_breakdown("entry type", report.by_entry_type());
+    print_breakdown("regime", report.by_regime());
+    print_breakdown("exit reason", report.by_exit_reason());
+    Ok(())
+}
 
-    // Get position info if we have it
-    let position = position_manager.get_position(token).await;
-    if let Some(ref pos) = position {
-        println!("\nPosition found:");
-        println!("  Symbol: {}", pos.symbol);
-        println!("  Tokens: {}", pos.token_amount);
-        println!("  Entry price: {:.10} SOL", pos.entry_price);
-        println!("  Cost: {:.4} SOL", pos.total_cost_sol);
+/// Real win rate by entry type vs the hypothetical Probe outcome of Observe tokens
+fn print_win_rate_comparison(
+    trades: &[crate::position::ClosedTrade],
+    shadows: &[crate::position::ShadowOutcome],
+) {
+    println!("Win rate by recommendation (real net vs shadow gross):");
+    for row in crate::position::shadow::compare_win_rates(trades, shadows) {
+        println!(
+            "  {:<12} {:<6} {:>4} trades | win {:>5.1}% | avg {:+.1}%",
+            row.label,
+            if row.shadow { "shadow" } else { "real" },
+            row.trades,
+            row.win_rate_pct(),
+            row.avg_pnl_pct
+        );
     }
 
-    // Confirmation prompt (unless --force)
-    if config.safety.require_sell_confirmation && !force {
-        let confirmed = Confirm::new()
-            .with_prompt(format!(
-                "Sell {} of token {}? This cannot be undone.",
-                amount, token
-            ))
-            .default(false)
-            .interact()?;
-
-        if !confirmed {
-            info!("Sell cancelled by user");
-            return Ok(());
+    let mut by_rule: Vec<(String, usize)> = Vec::new();
+    for outcome in shadows {
+        let label = outcome.exit_label();
+        match by_rule.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, count)) => *count += 1,
+            None => by_rule.push((label, 1)),
         }
     }
+    by_rule.sort_by(|a, b| b.1.cmp(&a.1));
+    let rules: Vec<String> = by_rule
+        .iter()
+        .map(|(label, count)| format!("{} {}", label, count))
+        .collect();
+    println!("  Shadow exits: {}\n", rules.join(", "));
+}
 
-    if dry_run {
-        info!("DRY-RUN: Would sell {} of {}", amount, token);
-        return Ok(());
+/// Send a position command to the running bot, or apply it to positions.json if no bot is running
+async fn send_position_command(
+    config: &Config,
+    command: crate::control::ControlCommand,
+) -> Result<()> {
+    use crate::control::{send_command, ControlCommand};
+
+    match send_command(&control_endpoint(config), &command).await {
+        Ok(response) => {
+            if response.ok {
+                println!("{}", response.message);
+                return Ok(());
+            }
+            anyhow::bail!("Bot rejected command: {}", response.message);
+        }
+        Err(e) => {
+            warn!("{} - updating positions file directly", e);
+        }
     }
 
-    // Execute sell based on configuration
-    if config.pumpportal.use_for_trading {
-        // Use PumpPortal API
-        if config.pumpportal.api_key.is_empty() {
-            anyhow::bail!("PumpPortal API key required for selling via Lightning API");
+    // No running bot: safe to edit persisted positions
+    ensure_bot_stopped(config)?;
+    let position_manager = crate::position::manager::PositionManager::new(
+        config.safety.clone(),
+        Some(format!("{}/positions.json", config.wallet.credentials_dir)),
+    )
+    .with_exit_thresholds(config.auto_sell.entry_types.clone());
+    position_manager.load().await?;
+    let updated = match command {
+        ControlCommand::SetExitOverride {
+            mint,
+            stop_loss_pct,
+            take_profit_pct,
+            trailing_pct,
+        } => {
+            position_manager
+                .set_exit_override(&mint, stop_loss_pct, take_profit_pct, trailing_pct)
+                .await?
+        }
+        ControlCommand::Hold { mint, until_secs } => {
+            position_manager.hold_position(&mint, until_secs).await?
         }
+        other => anyhow::bail!("{:?} needs a running bot", other),
+    };
+    println!("Override saved (applies on next start): {:?}", updated);
+    Ok(())
+}
 
-        let trader = PumpPortalTrader::lightning(config.pumpportal.api_key.clone());
-        let slippage_pct = config.trading.slippage_bps / 100;
-        let priority_fee = config.trading.priority_fee_lamports as f64 / 1_000_000_000.0;
+/// Override exit thresholds for one position
+pub async fn position_set_stop(
+    config: &Config,
+    mint: &str,
+    stop_loss_pct: Option<f64>,
+    take_profit_pct: Option<f64>,
+    trailing_pct: Option<f64>,
+) -> Result<()> {
+    if stop_loss_pct.is_none() && take_profit_pct.is_none() && trailing_pct.is_none() {
+        anyhow::bail!("Specify at least one of --stop-loss-pct, --take-profit-pct, --trailing-pct");
+    }
+    for pct in [stop_loss_pct, take_profit_pct, trailing_pct].into_iter().flatten() {
+        if pct <= 0.0 {
+            anyhow::bail!("Percentages must be positive");
+        }
+    }
+    if matches!(stop_loss_pct, Some(sl) if sl >= 100.0) {
+        anyhow::bail!("stop_loss_pct must be below 100");
+    }
 
-        // Query SOL balance BEFORE sell for real P&L
-        let sol_before = rpc_client.get_balance(&balance_wallet).unwrap_or(0) as f64 / 1_000_000_000.0;
-        info!("Balance before sell: {:.4} SOL", sol_before);
+    send_position_command(
+        config,
+        crate::control::ControlCommand::SetExitOverride {
+            mint: mint.to_string(),
+            stop_loss_pct,
+            take_profit_pct,
+            trailing_pct,
+        },
+    )
+    .await
+}
 
-        info!("Submitting sell via PumpPortal API...");
-        match trader.sell(token, amount, slippage_pct, priority_fee).await {
-            Ok(signature) => {
-                info!("Sell successful! Signature: {}", signature);
-                println!("\nSell transaction confirmed!");
-                println!("Signature: {}", signature);
-                println!("View on Solscan: https://solscan.io/tx/{}", signature);
+/// Suspend automatic exits for one position
+pub async fn position_hold(config: &Config, mint: &str, until_secs: u64) -> Result<()> {
+    send_position_command(
+        config,
+        crate::control::ControlCommand::Hold {
+            mint: mint.to_string(),
+            until_secs,
+        },
+    )
+    .await
+}
 
-                // Wait for tx confirmation then query actual SOL received
-                tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
-                let sol_after = rpc_client.get_balance(&balance_wallet).unwrap_or(0) as f64 / 1_000_000_000.0;
-                let raw_received = (sol_after - sol_before).max(0.0);
+/// Positions file of a bot that is not running
+async fn load_position_manager(
+    config: &Config,
+) -> Result<crate::position::manager::PositionManager> {
+    let position_manager = crate::position::manager::PositionManager::new(
+        config.safety.clone(),
+        Some(format!("{}/positions.json", config.wallet.credentials_dir)),
+    )
+    .with_exit_thresholds(config.auto_sell.entry_types.clone());
+    position_manager.load().await?;
+    Ok(position_manager)
+}
 
-                println!("Balance after sell: {:.4} SOL", sol_after);
-                println!("SOL received (raw): {:.4} SOL", raw_received);
+/// Drop a mint from bought_mints.json so hot-scan may buy it again
+fn forget_bought_mint(config: &Config, mint: &str) {
+    let path = format!("{}/bought_mints.json", config.wallet.credentials_dir);
+    let mut mints: std::collections::HashMap<String, i64> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    if mints.remove(mint).is_some() {
+        persist_bought_mints(&path, &mints);
+    }
+}
 
-                // Update position manager and stats
-                if let Some(ref pos) = position {
-                    let is_full_sell = amount == "100%" || amount_value >= 100.0;
-                    let tokens_sold = if is_full_sell {
-                        pos.token_amount
-                    } else if is_percentage {
-                        (pos.token_amount as f64 * amount_value / 100.0) as u64
-                    } else {
-                        amount_value as u64
-                    };
+/// Sell all of one position and record the proceeds
+///
+/// A running bot sells through its position monitor; otherwise the sell is
+/// sent from here like `snipe sell <mint> 100%`.
+pub async fn position_close(config: &Config, mint: &str, force: bool) -> Result<()> {
+    use crate::control::{send_command, ControlCommand};
 
-                    // Sanity check: received SOL shouldn't be more than 10x position cost
-                    // If it is, the balance query likely failed (sol_before was 0)
-                    let max_reasonable = pos.total_cost_sol * 10.0;
-                    let actual_received = if raw_received > max_reasonable {
-                        warn!(
-                            "Balance query anomaly: before={:.4}, after={:.4}, diff={:.4} (max reasonable: {:.4}) - using estimate",
-                            sol_before, sol_after, raw_received, max_reasonable
-                        );
-                        0.0 // Force fallback to estimate
-                    } else {
-                        raw_received
-                    };
+    let command = ControlCommand::ClosePosition {
+        mint: mint.to_string(),
+    };
+    match send_command(&control_endpoint(config), &command).await {
+        Ok(response) if response.ok => {
+            println!("{}", response.message);
+            forget_bought_mint(config, mint);
+            return Ok(());
+        }
+        Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+        Err(e) => warn!("{} - selling from this process", e),
+    }
+    ensure_bot_stopped(config)?;
 
-                    // Use actual received SOL, fallback to estimate if balance query failed
-                    let received = if actual_received > 0.0 {
-                        actual_received
-                    } else {
-                        // Estimate based on position price (use current_price if available, else entry_price)
-                        let price = if pos.current_price > 0.0 { pos.current_price } else { pos.entry_price };
-                        let estimated = (tokens_sold as f64 * price) * 0.98;
-                        warn!("Balance query returned 0 or anomaly detected, using estimated received: {:.4} SOL", estimated);
-                        estimated
-                    };
+    if load_position_manager(config)
+        .await?
+        .get_position(mint)
+        .await
+        .is_none()
+    {
+        anyhow::bail!("No open position for {}", mint);
+    }
+    sell(config, mint, "100%", force, false).await?;
 
-                    let _ = position_manager
-                        .close_position(token, tokens_sold, received)
-                        .await;
+    // sell() records the close and cleans bought_mints; a cancelled prompt
+    // leaves the position open
+    if load_position_manager(config)
+        .await?
+        .get_position(mint)
+        .await
+        .is_none()
+    {
+        load_cooldowns(config, false)
+            .mark(mint, crate::position::CooldownKind::Sold, None)
+            .await?;
+    }
+    Ok(())
+}
 
-                    // Persist position state immediately
-                    if let Err(e) = position_manager.save().await {
-                        warn!("Failed to persist position state: {}", e);
-                    }
+/// Record a position as a total loss without selling (rugged, unsellable)
+pub async fn position_abandon(config: &Config, mint: &str, force: bool) -> Result<()> {
+    use crate::control::{send_command, ControlCommand};
 
-                    let cost_portion = if is_full_sell {
-                        pos.total_cost_sol
-                    } else {
-                        pos.total_cost_sol * amount_value / 100.0
-                    };
-                    let pnl_sol = received - cost_portion;
-                    let pnl_pct = (pnl_sol / cost_portion) * 100.0;
+    if !force {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Close {} with zero proceeds? The tokens stay in the wallet.",
+                mint
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            info!("Abandon cancelled by user");
+            return Ok(());
+        }
+    }
 
-                    println!("\n=== TRADE CLOSED ===");
-                    println!("  Cost: {:.4} SOL | Received: {:.4} SOL | P&L: {:+.4} SOL ({:+.1}%)",
-                            cost_portion, received, pnl_sol, pnl_pct);
+    let command = ControlCommand::AbandonPosition {
+        mint: mint.to_string(),
+    };
+    match send_command(&control_endpoint(config), &command).await {
+        Ok(response) if response.ok => {
+            println!("{}", response.message);
+            forget_bought_mint(config, mint);
+            return Ok(());
+        }
+        Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+        Err(e) => warn!("{} - updating positions file directly", e),
+    }
+    ensure_bot_stopped(config)?;
+
+    let pnl = load_position_manager(config)
+        .await?
+        .abandon_with_loss(mint)
+        .await?;
+    forget_bought_mint(config, mint);
+    load_cooldowns(config, false)
+        .mark(
+            mint,
+            crate::position::CooldownKind::Failed,
+            Some("abandoned by operator".to_string()),
+        )
+        .await?;
+    println!("Abandoned {} ({:+.4} SOL recorded)", mint, pnl);
+    Ok(())
+}
 
-                    // Clean up bought_mints if position is fully closed
-                    // Check if position still exists after close_position
-                    let position_closed = position_manager.get_position(token).await.is_none();
-                    if position_closed {
-                        let _ = remove_bought_mint(&bought_mints, &bought_mints_path, token).await;
-                        info!("Removed {} from bought_mints cache", token);
-                    }
-                } else {
-                    // No position tracked - still clean up bought_mints
-                    let removed = remove_bought_mint(&bought_mints, &bought_mints_path, token).await;
-                    if removed {
-                        info!("Removed {} from bought_mints cache", token);
-                    }
-                }
-            }
+/// Print the full record of one open position
+pub async fn position_show(config: &Config, mint: &str) -> Result<()> {
+    use crate::control::{send_command, ControlCommand};
+
+    let command = ControlCommand::ShowPosition {
+        mint: mint.to_string(),
+    };
+    let position: crate::position::manager::Position =
+        match send_command(&control_endpoint(config), &command).await {
+            Ok(response) if response.ok => serde_json::from_str(&response.message)?,
+            Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
             Err(e) => {
-                error!("Sell failed: {}", e);
-                anyhow::bail!("Sell transaction failed: {}", e);
+                warn!("{} - reading positions file", e);
+                load_position_manager(config)
+                    .await?
+                    .get_position(mint)
+                    .await
+                    .ok_or_else(|| anyhow::anyhow!("No open position for {}", mint))?
             }
+        };
+    println!("{}", serde_json::to_string_pretty(&position)?);
+    Ok(())
+}
+
+/// Switch the running bot to another strategy profile
+pub async fn strategy_use(config: &Config, name: &str) -> Result<()> {
+    // Catch typos before reaching for the bot
+    if let Err(e) = config.strategy.resolve(Some(name)) {
+        anyhow::bail!("{}", e);
+    }
+
+    let command = crate::control::ControlCommand::UseStrategy {
+        profile: name.to_string(),
+    };
+    match crate::control::send_command(&control_endpoint(config), &command).await {
+        Ok(response) if response.ok => {
+            println!("{}", response.message);
+            Ok(())
         }
-    } else {
-        // Use Jito bundles
-        warn!("Jito sell not yet implemented - use PumpPortal Lightning API");
-        anyhow::bail!("Jito sell not implemented. Set pumpportal.use_for_trading = true in config.toml");
+        Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+        Err(e) => anyhow::bail!(
+            "{} - set strategy.active_profile = \"{}\" to use it on the next start",
+            e,
+            name
+        ),
     }
+}
 
-    Ok(())
+/// Make the running bot re-read the config file and apply the live subset
+pub async fn reload(config: &Config) -> Result<()> {
+    let command = crate::control::ControlCommand::Reload;
+    match crate::control::send_command(&control_endpoint(config), &command).await {
+        Ok(response) if response.ok => {
+            println!("{}", response.message);
+            Ok(())
+        }
+        Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+        Err(e) => anyhow::bail!("{}", e),
+    }
 }
 
-/// Show current positions and P&L
-pub async fn status(config: &Config) -> Result<()> {
-    info!("Loading positions...");
+/// Show current configuration (secrets masked)
+pub async fn show_config(config: &Config, source: &ConfigSource) -> Result<()> {
+    println!("{}", config.masked_display());
+    print_strategy_profile(config);
 
-    // TODO: Load positions from persistence
-    // TODO: Fetch current prices
-    // TODO: Calculate P&L
+    let sol_price = sol_usd_price(config);
+    let sol_usd = match sol_price.refresh().await {
+        Ok(_) => sol_price
+            .fresh_quote(chrono::Utc::now())
+            .map(|q| q.usd_per_sol),
+        Err(e) => {
+            warn!("SOL/USD price unavailable: {}", e);
+            None
+        }
+    };
+    print_usd_amounts(config, sol_usd);
 
-    println!("\n=== SNIPER BOT STATUS ===\n");
+    println!("\nSources:");
+    println!("  file:    {}", source.path.display());
+    match source.profile_path() {
+        Some(path) => println!("  profile: {}", path.display()),
+        None => println!("  profile: (none)"),
+    }
+    let overrides = source.overrides()?;
+    if overrides.is_empty() {
+        println!("\nOverrides: none");
+    } else {
+        println!("\nOverrides:");
+        for o in &overrides {
+            println!("  {} = {}  [{}]", o.key, o.masked_value(), o.source);
+        }
+    }
+    Ok(())
+}
 
-    // Placeholder output
-    println!("Positions: 0");
-    println!("Total Value: 0.00 SOL");
-    println!("Total P&L: 0.00 SOL (0.00%)");
-    println!("\nDaily Stats:");
-    println!("  Trades: 0");
-    println!("  Wins: 0");
-    println!("  Losses: 0");
+/// Check the config file for bad values and risky combinations
+pub fn config_validate(source: &ConfigSource) -> Result<()> {
+    let config_path = source.path.display();
+    // Values Config::load rejects outright come back as a load error
+    let config = Config::load_from(source)
+        .map_err(|e| anyhow::anyhow!("{} does not load: {:#}", config_path, e))?;
+    let report = ConfigReport::check(&config);
+
+    if report.findings.is_empty() {
+        println!("{}: OK", config_path);
+        return Ok(());
+    }
+    for finding in report.sorted() {
+        println!("{:<5}  {}", finding.severity, finding);
+    }
     println!(
-        "  Daily Loss Used: 0.00 / {} SOL",
-        config.safety.daily_loss_limit_sol
+        "\n{} error(s), {} warning(s)",
+        report.errors(),
+        report.warnings()
     );
+    if report.errors() > 0 {
+        anyhow::bail!("{} is not valid", config_path);
+    }
+    Ok(())
+}
 
-    println!("\n=== OPEN POSITIONS ===\n");
-    println!("No open positions.");
+/// `snipe pump accept-current`: pin the deployed pump.fun program as known good
+pub fn pump_accept_current(config: &Config, source: &ConfigSource) -> Result<()> {
+    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+    let mut check_config = config.pump_program.clone();
+    check_config.known_hash.clear();
+    let report = crate::pump::drift::check(&rpc_client, &check_config)?;
+
+    println!("Deployed program hash: {}", report.program_hash);
+    match config.pump_program.known_hash.as_str() {
+        "" => println!("Known good hash:       (not set)"),
+        known => println!("Known good hash:       {}", known),
+    }
+    match report.create {
+        Some(ref create) if create.decoded => {
+            println!(
+                "Recent create {} decodes with our discriminators",
+                create.signature
+            )
+        }
+        Some(ref create) => println!(
+            "WARNING: recent create {} does NOT decode with our discriminators",
+            create.signature
+        ),
+        None => println!("No recent create found to decode"),
+    }
+    if config.pump_program.known_hash == report.program_hash {
+        println!("Already accepted");
+        return Ok(());
+    }
 
+    let path = &source.path;
+    let text = std::fs::read_to_string(path)?;
+    std::fs::write(
+        path,
+        crate::pump::drift::set_known_hash(&text, &report.program_hash),
+    )?;
+    println!("Wrote pump_program.known_hash to {}", path.display());
+    if running_instance(config).is_some() {
+        println!("Restart the running bot to pick it up");
+    }
     Ok(())
 }
 
-/// Show current configuration (secrets masked)
-pub fn show_config(config: &Config) -> Result<()> {
-    println!("{}", config.masked_display());
+/// Log the findings; refuse to run on errors
+fn check_config_at_startup(report: &ConfigReport) -> Result<()> {
+    report.log();
+    if report.errors() > 0 {
+        anyhow::bail!(
+            "{} config error(s) - run `snipe config validate` for details",
+            report.errors()
+        );
+    }
     Ok(())
 }
 
+/// The strategy settings in effect once the active profile is applied
+fn print_strategy_profile(config: &Config) {
+    let name = config
+        .strategy
+        .active_profile
+        .as_deref()
+        .unwrap_or(crate::strategy::BASE_PROFILE);
+    let effective = match config.strategy.resolve(Some(name)) {
+        Ok(effective) => effective,
+        Err(e) => {
+            println!("\n=== STRATEGY PROFILE ===\n  {}", e);
+            return;
+        }
+    };
+
+    println!("\n=== STRATEGY PROFILE: {} ===", name);
+    if let Some(description) = config
+        .strategy
+        .profiles
+        .get(name)
+        .and_then(|p| p.description.as_ref())
+    {
+        println!("  {}", description);
+    }
+    println!(
+        "  Available:        {}",
+        config.strategy.profile_names().join(", ")
+    );
+    println!("  Default strategy: {}", effective.default_strategy);
+    let mut regimes: Vec<_> = effective.regime_strategies.iter().collect();
+    regimes.sort_by_key(|(regime, _)| regime.as_str());
+    for (regime, playbook) in regimes {
+        println!(
+            "    {:<15} {} (size x{:.2})",
+            regime.as_str(),
+            playbook.strategy,
+            playbook.size_multiplier
+        );
+    }
+
+    let sizing = &effective.position_sizing;
+    println!(
+        "  Sizing:           base {} SOL, {}-{} SOL, confidence scaling {}, round-trip impact <= {}%",
+        sizing.base_size_sol,
+        sizing.min_size_sol,
+        sizing.max_size_sol,
+        sizing.confidence_scaling,
+        sizing.max_round_trip_impact_pct
+    );
+    if sizing.mode == SizingMode::Bankroll {
+        println!(
+            "  Bankroll sizing:  {}% of equity (balance + open positions), down to {:.2}x at the daily loss limit",
+            sizing.bankroll_pct, sizing.drawdown_floor_multiplier
+        );
+    }
+
+    let exits = &effective.exits;
+    println!("  Exit style:       {}", exits.default_style);
+    println!(
+        "  Exits:            stop {}%, scalp {}%, trail {}% from +{}%, max hold {}s",
+        exits.stop_loss_pct,
+        exits.quick_scalp_target_pct,
+        exits.trailing_stop_pct,
+        exits.trailing_activation_pct,
+        exits.max_hold_secs
+    );
+    let levels: Vec<String> = exits
+        .tiered_levels
+        .iter()
+        .map(|(gain, sell)| format!("+{}%: {}%", gain, sell))
+        .collect();
+    println!("  Ladder:           {}", levels.join(", "));
+    let mut styles: Vec<String> = exits
+        .entry_type_styles
+        .iter()
+        .map(|(entry_type, style)| format!("{} {:?}", entry_type, style))
+        .chain(
+            exits
+                .regime_styles
+                .iter()
+                .map(|(regime, style)| format!("{} {:?}", regime.as_str(), style)),
+        )
+        .collect();
+    styles.sort();
+    if !styles.is_empty() {
+        println!("  Styles:           {}", styles.join(", "));
+    }
+}
+
 /// Check system health
-pub async fn health(config: &Config) -> Result<()> {
-    println!("\n=== SYSTEM HEALTH CHECK ===\n");
+///
+/// Fails (nonzero exit) when a check the bot cannot trade without fails.
+pub async fn health(config: &Config, json: bool) -> Result<()> {
+    use crate::health::{HealthCheck, HealthReport};
 
-    let mut all_healthy = true;
+    let mut report = HealthReport::default();
 
-    // Check RPC
-    print!("RPC Endpoint... ");
-    match check_rpc(config).await {
-        Ok(latency) => println!("OK ({}ms)", latency),
-        Err(e) => {
-            println!("FAILED: {}", e);
-            all_healthy = false;
+    report.push(
+        match check_rpc(config).await {
+            Ok(latency) => HealthCheck::ok("RPC endpoint", "").with_latency(latency),
+            Err(e) => HealthCheck::failed("RPC endpoint", e),
         }
-    }
+        .critical(),
+    );
+
+    report.push(check_chain(config).await);
 
-    // Check PumpPortal (if enabled)
     if config.pumpportal.enabled {
-        print!("PumpPortal WebSocket... ");
-        match check_pumpportal(config).await {
-            Ok(_) => println!("OK"),
-            Err(e) => {
-                println!("FAILED: {}", e);
-                all_healthy = false;
+        report.push(
+            match check_pumpportal(config).await {
+                Ok(latency) => HealthCheck::ok("PumpPortal WebSocket", "").with_latency(latency),
+                Err(e) => HealthCheck::failed("PumpPortal WebSocket", e),
             }
-        }
+            .critical(),
+        );
     } else {
-        println!("PumpPortal... DISABLED");
+        report.push(HealthCheck::skipped("PumpPortal WebSocket", "disabled"));
     }
 
-    // Check ShredStream (if PumpPortal disabled and shredstream feature enabled)
+    // ShredStream is the token feed when PumpPortal is disabled
     #[cfg(feature = "shredstream")]
     if !config.pumpportal.enabled {
-        print!("ShredStream... ");
-        match check_shredstream(config).await {
-            Ok(_) => println!("OK"),
-            Err(e) => {
-                println!("FAILED: {}", e);
-                all_healthy = false;
+        report.push(
+            match check_shredstream(config).await {
+                Ok(_) => HealthCheck::ok("ShredStream", ""),
+                Err(e) => HealthCheck::failed("ShredStream", e),
             }
-        }
+            .critical(),
+        );
     }
 
     #[cfg(not(feature = "shredstream"))]
     if !config.pumpportal.enabled {
-        println!("ShredStream... DISABLED (feature not compiled)");
+        report.push(HealthCheck::skipped("ShredStream", "feature not compiled"));
     }
 
-    // Check Jito (if not using PumpPortal for trading)
-    if !config.pumpportal.use_for_trading {
-        print!("Jito Block Engine... ");
-        match check_jito(config).await {
-            Ok(latency) => println!("OK ({}ms)", latency),
-            Err(e) => {
-                println!("FAILED: {}", e);
-                all_healthy = false;
-            }
-        }
-    } else {
-        println!("Jito... SKIPPED (using PumpPortal for trading)");
-    }
+    report.push(check_jito(config).await);
 
-    // Check PumpPortal API (if using for trading)
     if config.pumpportal.use_for_trading {
-        print!("PumpPortal Trading API... ");
-        if config.pumpportal.api_key.is_empty() {
-            println!("LOCAL MODE (no API key)");
+        report.push(if config.pumpportal.api_key.is_empty() {
+            HealthCheck::ok("PumpPortal trading API", "local mode (no API key)")
         } else {
-            println!("LIGHTNING MODE (API key configured)");
-        }
+            HealthCheck::ok(
+                "PumpPortal trading API",
+                "lightning mode (API key configured)",
+            )
+        });
     }
 
-    // Check keypair
-    print!("Keypair... ");
-    match check_keypair().await {
-        Ok(balance) => println!("OK (balance: {} SOL)", balance),
-        Err(e) => {
-            println!("FAILED: {}", e);
-            all_healthy = false;
+    report.push(check_helius(config).await);
+    report.push(check_dexscreener().await);
+    report.push(check_keypair(config).await);
+
+    let failures = report.critical_failures();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("\n=== SYSTEM HEALTH CHECK ===\n");
+        for check in &report.checks {
+            println!("{}", check);
+        }
+        println!();
+        if report.healthy() {
+            println!("All systems healthy!");
+        } else if failures.is_empty() {
+            println!("Degraded: check the warnings and failures above.");
         }
     }
 
-    println!();
-    if all_healthy {
-        println!("All systems healthy!");
+    if !failures.is_empty() {
+        let names: Vec<&str> = failures.iter().map(|c| c.name.as_str()).collect();
+        anyhow::bail!("Critical checks failed: {}", names.join(", "));
+    }
+    Ok(())
+}
+
+/// Helius `getAsset` probe plus request stats (live from a running bot if any)
+///
+/// Failed if the probe fails, the breaker is open or the error rate is over
+/// the throttling threshold.
+async fn check_helius(config: &Config) -> crate::health::HealthCheck {
+    use crate::health::HealthCheck;
+    const NAME: &str = "Helius";
+    const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+    let helius = match HeliusClient::from_rpc_url(&config.rpc.endpoint) {
+        Some(helius) => helius.with_budget(config.smart_money.helius.clone()),
+        None => return HealthCheck::skipped(NAME, "no api-key in rpc.endpoint"),
+    };
+
+    let live = if config.control.enabled {
+        match crate::control::send_command(
+            &control_endpoint(config),
+            &crate::control::ControlCommand::HeliusStatus,
+        )
+        .await
+        {
+            Ok(response) if response.ok => {
+                serde_json::from_str::<crate::filter::HeliusBudgetStats>(&response.message).ok()
+            }
+            _ => None,
+        }
     } else {
-        println!("Some systems are unhealthy. Check the errors above.");
+        None
+    };
+
+    let start = std::time::Instant::now();
+    let probe = helius.get_asset_metadata(WSOL_MINT).await;
+    let latency = start.elapsed().as_millis() as u64;
+
+    let (source, stats) = match live {
+        Some(stats) => ("stats live from running bot", stats),
+        None => ("no running bot", helius.budget().stats()),
+    };
+
+    let totals = stats.totals();
+    let mut details = vec![format!(
+        "requests {}, errors {} ({} rate limited), error rate {} over {}s",
+        totals.requests,
+        totals.errors,
+        totals.rate_limited,
+        stats
+            .error_rate
+            .map_or("n/a".to_string(), |r| format!("{:.1}%", r * 100.0)),
+        stats.window_secs
+    )];
+    details.push(format!(
+        "circuit breaker: {} (opened {}x, {} requests refused{})",
+        stats.breaker,
+        stats.total_trips,
+        stats.short_circuited,
+        stats
+            .retry_in_secs
+            .map_or_else(String::new, |secs| format!(", retry in {}s", secs))
+    ));
+    for (endpoint, counts) in &stats.endpoints {
+        details.push(format!(
+            "{:<18} {} requests, {} errors, {} rate limited",
+            endpoint, counts.requests, counts.errors, counts.rate_limited
+        ));
     }
 
-    Ok(())
+    let headroom = helius
+        .rate_limit_remaining()
+        .map_or("rate limit headroom not reported".to_string(), |left| {
+            format!("{} requests left in rate limit window", left)
+        });
+    let throttled = stats
+        .error_rate
+        .is_some_and(|r| r >= config.smart_money.helius.throttled_error_rate);
+    let check = match probe {
+        Err(e) => HealthCheck::failed(NAME, format!("getAsset failed: {}", e)),
+        Ok(_) if stats.breaker != crate::filter::BreakerState::Closed => {
+            HealthCheck::failed(NAME, format!("circuit breaker {}", stats.breaker))
+        }
+        Ok(_) if throttled => HealthCheck::failed(NAME, "error rate over throttled_error_rate"),
+        Ok(_) => HealthCheck::ok(NAME, format!("{}, {}", headroom, source)),
+    };
+    check.with_latency(latency).with_details(details)
 }
 
 async fn check_rpc(config: &Config) -> Result<u64> {
@@ -1675,6 +6926,43 @@ async fn check_rpc(config: &Config) -> Result<u64> {
     Ok(latency)
 }
 
+/// TPS, slot lag and congestion from one chain health sample
+///
+/// Warns when congestion would block new entries.
+async fn check_chain(config: &Config) -> crate::health::HealthCheck {
+    use crate::health::HealthCheck;
+    use crate::strategy::chain_health::{fetch_sample, ChainHealth};
+    const NAME: &str = "Chain";
+
+    let rpc = solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+    let sample = fetch_sample(&rpc, config.strategy.chain_health.fee_percentile).await;
+    if sample.tps.is_none() && sample.slot_lag.is_none() {
+        return HealthCheck::skipped(NAME, "no performance samples or slot data");
+    }
+
+    let mut health = ChainHealth::new(config.strategy.chain_health.clone());
+    health.record_sample(&sample);
+    let state = health.get_state();
+    let message = format!(
+        "{:?} congestion (TPS {:.0}, slot time {}ms, slot lag {}, fee floor {} lamports)",
+        state.congestion_level,
+        state.tps,
+        state.avg_slot_time_ms,
+        sample
+            .slot_lag
+            .map_or_else(|| "?".to_string(), |lag| lag.to_string()),
+        state.priority_fee_floor_lamports
+    );
+    if health.should_block_entries() {
+        HealthCheck::warn(NAME, format!("{}, new entries blocked", message))
+    } else {
+        HealthCheck::ok(NAME, message)
+    }
+}
+
 #[cfg(feature = "shredstream")]
 async fn check_shredstream(_config: &Config) -> Result<()> {
     // TODO: Implement ShredStream health check
@@ -1682,7 +6970,8 @@ async fn check_shredstream(_config: &Config) -> Result<()> {
     Ok(())
 }
 
-async fn check_pumpportal(config: &Config) -> Result<()> {
+/// Connect to the PumpPortal WebSocket, returning the connect time in ms
+async fn check_pumpportal(config: &Config) -> Result<u64> {
     use std::time::Duration;
     use tokio_tungstenite::connect_async;
 
@@ -1690,6 +6979,7 @@ async fn check_pumpportal(config: &Config) -> Result<()> {
         .map_err(|e| anyhow::anyhow!("Invalid WebSocket URL: {}", e))?;
 
     // Try to connect with timeout
+    let start = std::time::Instant::now();
     let connect_future = connect_async(url);
     let timeout = Duration::from_secs(5);
 
@@ -1697,7 +6987,7 @@ async fn check_pumpportal(config: &Config) -> Result<()> {
         Ok(Ok((ws, _))) => {
             // Successfully connected, close by dropping
             drop(ws);
-            Ok(())
+            Ok(start.elapsed().as_millis() as u64)
         }
         Ok(Err(e)) => Err(anyhow::anyhow!("WebSocket connection failed: {}", e)),
         Err(_) => Err(anyhow::anyhow!(
@@ -1707,16 +6997,123 @@ async fn check_pumpportal(config: &Config) -> Result<()> {
     }
 }
 
-async fn check_jito(_config: &Config) -> Result<u64> {
-    // TODO: Implement Jito health check
-    // For now, just return placeholder latency
-    Ok(50)
+/// Block engine round trip and the current landed-tip floor
+///
+/// Critical only when trades go out as Jito bundles.
+async fn check_jito(config: &Config) -> crate::health::HealthCheck {
+    use crate::health::HealthCheck;
+    const NAME: &str = "Jito block engine";
+
+    let critical = !config.pumpportal.use_for_trading;
+    let mark = |check: HealthCheck| if critical { check.critical() } else { check };
+    let jito = match crate::trading::jito::JitoClient::new(config.jito.clone()) {
+        Ok(jito) => jito,
+        Err(e) => return mark(HealthCheck::failed(NAME, e)),
+    };
+    let latency = match jito.ping().await {
+        Ok(latency) => latency,
+        Err(e) => return mark(HealthCheck::failed(NAME, e)),
+    };
+
+    let unused = if critical {
+        ""
+    } else {
+        ", unused (trading through PumpPortal)"
+    };
+    let check = match jito.tip_floor().await {
+        Ok(tip) => HealthCheck::ok(
+            NAME,
+            format!(
+                "tip floor p{} {} lamports{}",
+                config.jito.tip_percentile, tip, unused
+            ),
+        ),
+        Err(e) => HealthCheck::warn(
+            NAME,
+            format!(
+                "tip floor unavailable, tips fall back to {} lamports: {}{}",
+                config.jito.min_tip_lamports, e, unused
+            ),
+        ),
+    };
+    mark(check.with_latency(latency))
+}
+
+/// Fetch a well-known pair (SOL/USDC on Raydium) from DexScreener
+async fn check_dexscreener() -> crate::health::HealthCheck {
+    use crate::health::HealthCheck;
+    const NAME: &str = "DexScreener";
+    const SOL_USDC_PAIR: &str = "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2";
+
+    let client = crate::dexscreener::DexScreenerClient::new();
+    let start = std::time::Instant::now();
+    match client.get_pair("solana", SOL_USDC_PAIR).await {
+        Ok(Some(pair)) => HealthCheck::ok(
+            NAME,
+            format!("SOL ${}", pair.price_usd.as_deref().unwrap_or("?")),
+        )
+        .with_latency(start.elapsed().as_millis() as u64),
+        Ok(None) => HealthCheck::failed(NAME, format!("pair {} not returned", SOL_USDC_PAIR)),
+        Err(e) => HealthCheck::failed(NAME, e),
+    }
 }
 
-async fn check_keypair() -> Result<f64> {
-    // TODO: Implement keypair check
-    // Load keypair, check balance
-    Ok(0.0)
+/// Hot wallet address (via the credential store) and its SOL balance
+///
+/// Fails below `wallet.safety.emergency_threshold_sol` (trading pauses), warns
+/// when a buy would dip under `min_hot_balance_sol` or the balance is over
+/// `safety.keypair_balance_warning_sol`.
+async fn check_keypair(config: &Config) -> crate::health::HealthCheck {
+    use crate::health::HealthCheck;
+    const NAME: &str = "Keypair";
+
+    let address = match hot_wallet_address(config) {
+        Ok(address) => address,
+        Err(e) => return HealthCheck::failed(NAME, e).critical(),
+    };
+    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+    let balance = match rpc_client.get_balance(&address) {
+        Ok(lamports) => lamports as f64 / 1_000_000_000.0,
+        Err(e) => {
+            return HealthCheck::failed(NAME, format!("{}: balance fetch failed: {}", address, e))
+                .critical()
+        }
+    };
+
+    let safety = &config.wallet.safety;
+    let low = safety.min_hot_balance_sol + config.trading.buy_amount_sol;
+    let message = format!("{} balance {:.4} SOL", address, balance);
+    let check = if balance < safety.emergency_threshold_sol {
+        HealthCheck::failed(
+            NAME,
+            format!(
+                "{}, below emergency threshold {} SOL",
+                message, safety.emergency_threshold_sol
+            ),
+        )
+    } else if balance < low {
+        HealthCheck::warn(
+            NAME,
+            format!(
+                "{}, low: one buy leaves less than min_hot_balance_sol {} SOL",
+                message, safety.min_hot_balance_sol
+            ),
+        )
+    } else if balance > config.safety.keypair_balance_warning_sol {
+        HealthCheck::warn(
+            NAME,
+            format!(
+                "{}, above {} SOL: consider moving funds out",
+                message, config.safety.keypair_balance_warning_sol
+            ),
+        )
+    } else {
+        HealthCheck::ok(NAME, message)
+    };
+    check.critical()
 }
 
 // =============================================================================
@@ -1838,6 +7235,45 @@ pub async fn wallet_list(config: &Config) -> Result<()> {
     Ok(())
 }
 
+/// Check wallets.json against the keypair files
+pub async fn wallet_verify(config: &Config) -> Result<()> {
+    use crate::wallet::credentials::{CredentialManager, IntegrityStatus};
+
+    let mut creds =
+        CredentialManager::load_unverified(std::path::Path::new(&config.wallet.credentials_dir))?;
+    let checks = creds.verify();
+
+    println!("\n=== WALLET INTEGRITY ===\n");
+    println!("{:<20} {:<8} {:<6} {}", "NAME", "TYPE", "STATUS", "REASONS");
+    println!("{}", "-".repeat(80));
+    for check in &checks {
+        println!(
+            "{:<20} {:<8} {:<6} {}",
+            check.name,
+            format!("{:?}", check.wallet_type),
+            check.status,
+            check.reasons.join("; ")
+        );
+    }
+    if checks.is_empty() {
+        println!("No hot or vault wallets configured.");
+    }
+    println!();
+
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == IntegrityStatus::Fail)
+        .count();
+    if failed > 0 {
+        anyhow::bail!(
+            "{} wallet(s) failed the integrity check. If a keypair was replaced on purpose, \
+             update its address and remove its keypair_sha256 from wallets.json.",
+            failed
+        );
+    }
+    Ok(())
+}
+
 /// Add a new wallet
 pub async fn wallet_add(
     config: &Config,
@@ -1930,6 +7366,8 @@ pub async fn wallet_add(
         address: final_address.clone(),
         created_at: Utc::now(),
         notes: String::new(),
+        daily_spend_limit_sol: None,
+        keypair_sha256: None,
     };
 
     creds
@@ -1951,8 +7389,6 @@ pub async fn wallet_extract(
     force: bool,
     dry_run: bool,
 ) -> Result<()> {
-    use crate::wallet::manager::{WalletManager, WalletManagerConfig};
-    use crate::wallet::safety::WalletSafetyConfig;
     use crate::wallet::types::{InitiatedBy, TransferReason};
     use dialoguer::Confirm;
 
@@ -1965,31 +7401,23 @@ pub async fn wallet_extract(
         return Ok(());
     }
 
-    // Build wallet manager config
-    let wallet_config = WalletManagerConfig {
-        hot_wallet_name: config.wallet.hot_wallet.clone(),
-        vault_wallet_name: config.wallet.vault_wallet.clone(),
-        credentials_dir: config.wallet.credentials_dir.clone(),
-        safety: WalletSafetyConfig {
-            min_hot_balance_sol: config.wallet.safety.min_hot_balance_sol,
-            max_single_transfer_sol: config.wallet.safety.max_single_transfer_sol,
-            max_daily_extraction_sol: config.wallet.safety.max_daily_extraction_sol,
-            confirm_above_sol: config.wallet.safety.confirm_above_sol,
-            emergency_threshold_sol: config.wallet.safety.emergency_threshold_sol,
-            vault_address_locked: config.wallet.safety.vault_address_locked,
-            ai_max_auto_transfer_sol: config.wallet.safety.ai_max_auto_transfer_sol,
-        },
-    };
-
-    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
-        config.rpc.endpoint.clone(),
-        std::time::Duration::from_millis(config.rpc.timeout_ms),
-    );
-
-    let wallet_manager = WalletManager::new(wallet_config, rpc_client)
+    // History loaded so today's earlier extractions count toward the daily limit
+    let wallet_manager = load_wallet_manager(config, external_hot_signer(config)?)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to create wallet manager: {}", e))?;
 
+    if wallet_manager.safety().requires_dual_confirmation(amount) {
+        return stage_and_await_confirmation(
+            config,
+            &wallet_manager,
+            &config.wallet.hot_wallet,
+            &config.wallet.vault_wallet,
+            amount,
+            false,
+        )
+        .await;
+    }
+
     // Confirmation prompt
     if !force && amount > config.wallet.safety.confirm_above_sol {
         let confirmed = Confirm::new()
@@ -2035,6 +7463,169 @@ pub async fn wallet_extract(
     Ok(())
 }
 
+/// Encrypt a wallet's keypair file in place
+pub async fn wallet_encrypt(config: &Config, name: &str, keyring: bool) -> Result<()> {
+    use crate::wallet::{keystore, CredentialManager};
+
+    let mut credentials =
+        CredentialManager::load(std::path::Path::new(&config.wallet.credentials_dir))?;
+    if credentials.is_encrypted(name) {
+        println!("Keypair for {} is already encrypted.", name);
+        return Ok(());
+    }
+
+    let passphrase = keystore::new_passphrase()?;
+    let path = credentials.encrypt_keypair(name, &passphrase)?;
+    if keyring {
+        keystore::store_in_keyring(&passphrase)?;
+    }
+
+    println!("\n=== KEYPAIR ENCRYPTED ===");
+    println!("Wallet: {}", name);
+    println!("File:   {}", path.display());
+    if keyring {
+        println!(
+            "Passphrase saved in the OS keyring (service '{}').",
+            keystore::KEYRING_SERVICE
+        );
+    } else {
+        println!(
+            "At startup the passphrase is read from {} or prompted for.",
+            keystore::PASSPHRASE_ENV
+        );
+    }
+    println!("Use one passphrase for all wallets; the keypair can't be recovered without it.");
+    println!("Delete any copies of the old plaintext keypair.");
+    Ok(())
+}
+
+/// Set or clear (`daily_spend` None) a wallet's daily spend cap
+pub async fn wallet_set_limit(config: &Config, name: &str, daily_spend: Option<f64>) -> Result<()> {
+    use crate::wallet::CredentialManager;
+
+    if daily_spend.is_some_and(|limit| !limit.is_finite() || limit <= 0.0) {
+        anyhow::bail!("--daily-spend must be positive (use --clear to remove the cap)");
+    }
+    let mut credentials =
+        CredentialManager::load(std::path::Path::new(&config.wallet.credentials_dir))?;
+    credentials.set_daily_spend_limit(name, daily_spend)?;
+
+    let address = if name == config.wallet.hot_wallet {
+        hot_wallet_address(config)?
+    } else {
+        credentials.get_address(name)?
+    };
+    let spent = crate::wallet::manager::spend_enforcer(&wallet_manager_config(config), address)?
+        .spent_today(&address);
+    match daily_spend {
+        Some(limit) => println!(
+            "Daily spend cap of {} set to {} SOL ({:.4} SOL spent today).",
+            name, limit, spent
+        ),
+        None => println!(
+            "Daily spend cap of {} removed ({:.4} SOL spent today).",
+            name, spent
+        ),
+    }
+    println!("A running bot applies the change after a restart.");
+    Ok(())
+}
+
+/// Show the auto-extraction rules and today's extracted total
+pub async fn wallet_extract_status(config: &Config) -> Result<()> {
+    let rules = extraction_config(config);
+    let safety = &config.wallet.safety;
+    let history_path = format!("{}/transfer_history.json", config.wallet.credentials_dir);
+    let history: crate::wallet::types::TransferHistory = std::fs::read_to_string(&history_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let now = chrono::Utc::now();
+    let today = history.daily_stats(now);
+
+    println!("\n=== AUTO-EXTRACTION ===\n");
+    println!(
+        "Auto-extract: {} (runs in snipe start, live mode only)",
+        if rules.auto_extract { "ON" } else { "OFF" }
+    );
+    println!(
+        "Checks:       every {}s (+0-{}s jitter), {}s between extractions",
+        rules.check_interval_secs, rules.jitter_secs, rules.min_extraction_interval_secs
+    );
+
+    let floor = rules.hot_floor(safety.min_hot_balance_sol);
+    let last_daily = history.last_auto(Some("daily")).map(|t| t.timestamp);
+    println!("\nRules (first match wins):");
+    if rules.excess_trigger_sol > 0.0 {
+        println!(
+            "  hot_excess        above {:.4} SOL, down to {:.4} SOL (min {:.4} + buffer {:.4})",
+            floor + rules.excess_trigger_sol,
+            floor,
+            safety.min_hot_balance_sol,
+            rules.hot_buffer_sol
+        );
+    } else {
+        println!("  hot_excess        off");
+    }
+    match rules.daily_hour_utc {
+        Some(hour) => println!(
+            "  daily             {:02}:00 UTC, down to {:.4} SOL - {}",
+            hour,
+            floor,
+            if rules.daily_due(now, last_daily.map(|at| at.date_naive())) {
+                "due"
+            } else {
+                "not due"
+            }
+        ),
+        None => println!("  daily             off"),
+    }
+    println!(
+        "  balance_ceiling   above {:.4} SOL",
+        rules.balance_ceiling_sol
+    );
+    println!(
+        "  realized_profits  {:.0}% once {:.4} SOL of profit is pending",
+        rules.profit_percentage, rules.profit_threshold_sol
+    );
+    if rules.max_open_exposure_sol > 0.0 {
+        println!(
+            "Skipped while more than {:.4} SOL is in open positions",
+            rules.max_open_exposure_sol
+        );
+    }
+
+    println!("\nToday ({}):", today.date);
+    println!(
+        "  Extracted:  {:.4} / {:.4} SOL in {} transfer(s) ({:.4} SOL left)",
+        today.total_extracted_sol,
+        safety.max_daily_extraction_sol,
+        today.extraction_count,
+        (safety.max_daily_extraction_sol - today.total_extracted_sol).max(0.0)
+    );
+    match history.last_auto(None) {
+        Some(last) => {
+            println!(
+                "  Last auto:  {:.4} SOL at {} ({})",
+                last.amount_sol,
+                last.timestamp.format("%Y-%m-%d %H:%M UTC"),
+                last.initiated_by
+            );
+            let cooldown = rules.min_extraction_interval_secs as i64
+                - now.signed_duration_since(last.timestamp).num_seconds();
+            if cooldown > 0 {
+                println!("  Cooldown:   {}s left", cooldown);
+            }
+        }
+        None => println!("  Last auto:  never"),
+    }
+    if let Some(at) = last_daily {
+        println!("  Last daily: {}", at.format("%Y-%m-%d %H:%M UTC"));
+    }
+    println!();
+    Ok(())
+}
+
 /// View transfer history
 pub async fn wallet_history(config: &Config, limit: usize) -> Result<()> {
     println!("\n=== TRANSFER HISTORY ===\n");
@@ -2072,6 +7663,21 @@ pub async fn wallet_history(config: &Config, limit: usize) -> Result<()> {
                     },
                     format!("{}", record.reason)
                 );
+                if record.status != crate::wallet::types::TransferStatus::Completed {
+                    println!("             ({})", record.status);
+                }
+                if let Some(sweep) = &record.sweep {
+                    println!(
+                        "             {} {} {} ({})",
+                        sweep.action,
+                        sweep.token_amount,
+                        sweep.mint,
+                        sweep
+                            .value_usd
+                            .map(|v| format!("${:.2}", v))
+                            .unwrap_or_else(|| "no price".to_string())
+                    );
+                }
             }
         }
     } else {
@@ -2083,27 +7689,117 @@ pub async fn wallet_history(config: &Config, limit: usize) -> Result<()> {
 }
 
 /// View/manage AI proposals
+///
+/// Decisions go to the running bot, which executes approved proposals at
+/// once. With no bot running they are written to proposals.json and approved
+/// proposals execute on the next `snipe start` (unless they expire first).
 pub async fn wallet_proposals(
-    _config: &Config,
+    config: &Config,
     approve: Option<String>,
     reject: Option<String>,
 ) -> Result<()> {
-    println!("\n=== AI PROPOSALS ===\n");
+    use crate::control::{send_command, ControlCommand};
+    use crate::wallet::{ProposalStatus, ProposalStore};
 
-    // TODO: Implement proposal management
-    // This requires integration with the running bot instance
+    let path = format!("{}/proposals.json", config.wallet.credentials_dir);
+    let decision = approve
+        .map(|id| (id, true))
+        .or_else(|| reject.map(|id| (id, false)));
 
-    if let Some(id) = approve {
-        println!("Approving proposal: {}", id);
-        println!("(Not yet implemented - requires running bot instance)");
-    } else if let Some(id) = reject {
-        println!("Rejecting proposal: {}", id);
-        println!("(Not yet implemented - requires running bot instance)");
-    } else {
+    if let Some((id, approved)) = decision {
+        let command = if approved {
+            ControlCommand::ApproveProposal { id: id.clone() }
+        } else {
+            ControlCommand::RejectProposal { id: id.clone() }
+        };
+        match send_command(&control_endpoint(config), &command).await {
+            Ok(response) if response.ok => {
+                println!("{}", response.message);
+                return Ok(());
+            }
+            Ok(response) => anyhow::bail!("Bot rejected command: {}", response.message),
+            Err(e) => warn!("{} - updating proposals file directly", e),
+        }
+
+        // No running bot: safe to edit the store
+        ensure_bot_stopped(config)?;
+        let mut store = ProposalStore::load(&path)?;
+        let proposal = if approved {
+            store.approve(&id, Utc::now())?
+        } else {
+            store.reject(&id, Utc::now())?
+        };
+        store.save()?;
+        println!("{} {} ({})", proposal.status, proposal.id, proposal.action);
+        if approved {
+            println!("Executes when the bot next starts (unless it expires first).");
+        }
+        return Ok(());
+    }
+
+    let mut store = ProposalStore::load(&path)?;
+    store.expire(Utc::now());
+    let ai_max = config.wallet.safety.ai_max_auto_transfer_sol;
+
+    println!("\n=== AI PROPOSALS ===\n");
+    let pending = store.with_status(ProposalStatus::Pending);
+    if pending.is_empty() {
         println!("No pending proposals.");
-        println!("\nTo approve a proposal: snipe wallet proposals --approve <ID>");
-        println!("To reject a proposal: snipe wallet proposals --reject <ID>");
     }
+    for proposal in &pending {
+        let amount = proposal
+            .amount_sol
+            .map(|a| format!(" {:.4} SOL", a))
+            .unwrap_or_default();
+        let expires = proposal
+            .expires_at
+            .map(|at| format!(", expires in {}m", (at - Utc::now()).num_minutes()))
+            .unwrap_or_default();
+        println!(
+            "{}  {}{} (confidence {:.0}%{})",
+            proposal.id,
+            proposal.action,
+            amount,
+            proposal.confidence * 100.0,
+            expires
+        );
+        println!("    {}", proposal.reasoning);
+        if proposal.amount_sol.is_some_and(|a| a > ai_max) {
+            println!(
+                "    Above ai_max_auto_transfer_sol ({} SOL) - needs approval",
+                ai_max
+            );
+        }
+    }
+
+    let recent: Vec<_> = store
+        .all()
+        .iter()
+        .rev()
+        .filter(|p| p.status != ProposalStatus::Pending)
+        .take(10)
+        .collect();
+    if !recent.is_empty() {
+        println!("\nRecent:");
+        for proposal in recent {
+            println!(
+                "  {} {:<14} {:<20} {}",
+                proposal.created_at.format("%Y-%m-%d %H:%M"),
+                proposal.status.to_string(),
+                proposal.action.to_string(),
+                proposal.outcome.as_deref().unwrap_or("")
+            );
+        }
+    }
+    if store.buy_size_multiplier() != 1.0 {
+        println!(
+            "\nAI buy size multiplier: x{:.2}",
+            store.buy_size_multiplier()
+        );
+    }
+
+    println!("\nTo approve a proposal: snipe wallet proposals --approve <ID>");
+    println!("To reject a proposal: snipe wallet proposals --reject <ID>");
 
     Ok(())
 }
@@ -2121,7 +7817,18 @@ pub async fn wallet_emergency(config: &Config, shutdown: bool, resume: bool) ->
 
         println!("\nEmergency lock activated!");
         println!("Lock file created: {}", lock_file);
+        println!("New buys are refused. To liquidate open positions: snipe sell-all");
         println!("\nTo resume operations: snipe wallet emergency --resume");
+
+        if config.notifications.enabled {
+            let results =
+                crate::notify::send_now(&config.notifications, &Notification::EmergencyLock).await;
+            for (sink, result) in results {
+                if let Err(e) = result {
+                    warn!("Emergency alert via {} failed: {}", sink, e);
+                }
+            }
+        }
     } else if resume {
         info!("=== RESUMING OPERATIONS ===");
 
@@ -2149,113 +7856,516 @@ pub async fn wallet_emergency(config: &Config, shutdown: bool, resume: bool) ->
     Ok(())
 }
 
+/// Send a test message to every configured notification sink
+pub async fn notify_test(config: &Config) -> Result<()> {
+    if config.notifications.sinks().is_empty() {
+        anyhow::bail!("No notification sinks configured - add [notifications.telegram] or [notifications.discord] to config.toml");
+    }
+    if !config.notifications.enabled {
+        println!("Note: notifications.enabled = false, the bot will not send alerts");
+    }
+
+    let results = crate::notify::send_now(&config.notifications, &Notification::Test).await;
+    let mut failed = 0;
+    for (sink, result) in &results {
+        match result {
+            Ok(()) => println!("{:<10} OK", sink),
+            Err(e) => {
+                failed += 1;
+                println!("{:<10} FAILED: {}", sink, e);
+            }
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{} of {} sinks failed", failed, results.len());
+    }
+    Ok(())
+}
+
 /// Transfer SOL between wallets
+///
+/// Goes through the wallet manager, so the safety limits apply and the
+/// transfer lands in the history. Transfers out of the vault need
+/// `--unlock-vault` and an interactive confirmation, even with `--force`.
 pub async fn wallet_transfer(
     config: &Config,
     from: &str,
     to: &str,
     amount: f64,
     force: bool,
+    unlock_vault: bool,
 ) -> Result<()> {
-    use solana_sdk::signature::Signer;
-    use std::str::FromStr;
+    use crate::wallet::types::{InitiatedBy, WalletType};
 
     info!(
         "Initiating transfer of {} SOL from {} to {}",
         amount, from, to
     );
 
-    // Load source wallet
-    let from_path = format!("{}/{}/keypair.json", config.wallet.credentials_dir, from);
-    if !std::path::Path::new(&from_path).exists() {
-        anyhow::bail!("Source wallet '{}' not found", from);
+    // A remotely signed hot wallet signs through [wallet.signer]
+    let external = if from == config.wallet.hot_wallet {
+        external_hot_signer(config)?
+    } else {
+        None
+    };
+    let wallet_manager = load_wallet_manager(config, external)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create wallet manager: {}", e))?;
+
+    let source = wallet_manager
+        .wallet(from)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Source wallet '{}' not registered", from))?;
+    let destination = wallet_manager.resolve_destination(to).await?;
+    let from_vault = source.wallet_type == WalletType::Vault;
+    if from_vault && !unlock_vault {
+        anyhow::bail!(
+            "Transfer refused: {} (use --unlock-vault to override)",
+            crate::wallet::SafetyViolation::VaultWithdrawalBlocked
+        );
+    }
+
+    if wallet_manager.safety().requires_dual_confirmation(amount) {
+        return stage_and_await_confirmation(
+            config,
+            &wallet_manager,
+            from,
+            to,
+            amount,
+            unlock_vault,
+        )
+        .await;
+    }
+
+    // Confirmation
+    let confirm = if from_vault {
+        Some(format!(
+            "Withdraw {} SOL from VAULT {} to {} ({})?",
+            amount, from, destination.name, destination.address
+        ))
+    } else if !force && wallet_manager.safety().requires_confirmation(amount) {
+        Some(format!(
+            "Transfer {} SOL from {} to {} ({})?",
+            amount, from, destination.name, destination.address
+        ))
+    } else {
+        None
+    };
+    if let Some(prompt) = confirm {
+        let confirmed = Confirm::new()
+            .with_prompt(prompt)
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("Transfer cancelled.");
+            return Ok(());
+        }
+    }
+
+    let record = match wallet_manager
+        .transfer(from, to, amount, InitiatedBy::User, true, unlock_vault)
+        .await
+    {
+        Ok(record) => record,
+        Err(crate::error::Error::SafetyLimitExceeded(reason)) => {
+            anyhow::bail!("Transfer refused: {}", reason)
+        }
+        Err(e) => anyhow::bail!("Transfer failed: {}", e),
+    };
+
+    println!(
+        "Transferred {} SOL from {} to {} ({})",
+        record.amount_sol, record.from_wallet, record.to_wallet, destination.address
+    );
+    println!("Reason: {}", record.reason);
+    println!("Signature: {}", record.signature);
+
+    Ok(())
+}
+
+/// Stage a transfer above `dual_confirm_above_sol` and wait for an approver
+///
+/// The one-time code goes privately to each Telegram approver, never to the
+/// terminal. An approver's `/confirm <code>` reply sends the transfer; left
+/// unconfirmed it is cancelled when the window ends.
+async fn stage_and_await_confirmation(
+    config: &Config,
+    wallet_manager: &crate::wallet::WalletManager,
+    from: &str,
+    to: &str,
+    amount: f64,
+    unlock_vault: bool,
+) -> Result<()> {
+    use crate::notify::telegram::{confirm_code, user_identity, TelegramSink};
+
+    let telegram = config
+        .notifications
+        .telegram
+        .as_ref()
+        .filter(|t| t.is_configured())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Transfer of {} SOL needs dual confirmation: configure [notifications.telegram]",
+                amount
+            )
+        })?;
+    let operator = telegram.operator_user_id.ok_or_else(|| {
+        anyhow::anyhow!("Dual confirmation needs notifications.telegram.operator_user_id")
+    })?;
+    let approvers = telegram.approvers();
+    if approvers.is_empty() {
+        anyhow::bail!(
+            "Dual confirmation needs notifications.telegram.approver_user_ids \
+             (other than the operator)"
+        );
+    }
+    let sink = TelegramSink::new(telegram.clone());
+    // Only replies sent after the code count
+    let mut offset = sink
+        .updates(0, 0)
+        .await?
+        .iter()
+        .map(|m| m.update_id + 1)
+        .max()
+        .unwrap_or(0);
+
+    let (record, code) = wallet_manager
+        .stage_transfer(
+            from,
+            to,
+            amount,
+            InitiatedBy::User,
+            unlock_vault,
+            &user_identity(operator),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Transfer refused: {}", e))?;
+    let expires_at = record
+        .dual_confirmation
+        .as_ref()
+        .map(|c| c.expires_at)
+        .unwrap_or_else(chrono::Utc::now);
+    let request = format!(
+        "Transfer {} SOL from {} to {} staged by {}. Reply /confirm {} before {} to send it.",
+        record.amount_sol,
+        record.from_wallet,
+        record.to_wallet,
+        user_identity(operator),
+        code,
+        expires_at.format("%H:%M:%S UTC")
+    );
+    let mut delivered = 0;
+    for approver in &approvers {
+        match sink.send_to(&approver.to_string(), &request).await {
+            Ok(()) => delivered += 1,
+            Err(e) => warn!("Confirmation code not sent to {}: {}", approver, e),
+        }
+    }
+    if delivered == 0 {
+        anyhow::bail!(
+            "Transfer {} staged but no approver could be reached; it expires at {}",
+            record.id,
+            expires_at
+        );
+    }
+
+    println!("\n=== TRANSFER STAGED (dual confirmation) ===");
+    println!("Amount:  {} SOL", record.amount_sol);
+    println!("From:    {}", record.from_wallet);
+    println!("To:      {}", record.to_wallet);
+    println!("Expires: {}", expires_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!(
+        "\nConfirmation code sent to {} approver(s) on Telegram. Waiting...",
+        delivered
+    );
+
+    while chrono::Utc::now() < expires_at {
+        let wait = (expires_at - chrono::Utc::now()).num_seconds().max(1) as u64;
+        let messages = match sink.updates(offset, wait).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                warn!("Telegram poll failed: {}", e);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        for message in messages {
+            offset = offset.max(message.update_id + 1);
+            let Some(reply) = confirm_code(&message.text) else {
+                continue;
+            };
+            if !approvers.contains(&message.from_id) {
+                warn!("Ignoring /confirm from non-approver {}", message.from_id);
+                continue;
+            }
+            let chat = message.from_id.to_string();
+            match wallet_manager
+                .confirm_staged(reply, &user_identity(message.from_id))
+                .await
+            {
+                Ok(sent) => {
+                    let _ = sink
+                        .send_to(&chat, &format!("Sent: {}", sent.signature))
+                        .await;
+                    println!(
+                        "Confirmed by {}: transferred {} SOL from {} to {}",
+                        user_identity(message.from_id),
+                        sent.amount_sol,
+                        sent.from_wallet,
+                        sent.to_wallet
+                    );
+                    println!("Signature: {}", sent.signature);
+                    return Ok(());
+                }
+                Err(e) => {
+                    let _ = sink.send_to(&chat, &format!("Not sent: {}", e)).await;
+                    warn!("Confirmation from {} refused: {}", message.from_id, e);
+                }
+            }
+        }
+    }
+    // Listing expires it, so the record reads cancelled right away
+    wallet_manager.staged_transfers().await;
+    anyhow::bail!(
+        "Transfer {} was not confirmed in time and is cancelled",
+        record.id
+    )
+}
+
+/// Sweep the hot wallet's stray tokens to the vault
+///
+/// Balances worth at least `min_value_usd` go to the vault, smaller ones are
+/// burned with `burn_dust`, and emptied accounts are closed for their rent.
+/// Mints with an open position are never touched.
+pub async fn wallet_sweep(
+    config: &Config,
+    to: &str,
+    min_value_usd: f64,
+    burn_dust: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    use crate::dexscreener::DexScreenerClient;
+    use crate::wallet::sweep::{plan_item, TokenSweeper};
+
+    if to != "vault" && to != config.wallet.vault_wallet {
+        anyhow::bail!("Sweep only goes to the vault (--to vault), not {}", to);
+    }
+
+    let signer = load_hot_signer(config)?;
+    let external =
+        (config.wallet.signer.backend != crate::wallet::signer::LOCAL).then(|| signer.clone());
+    let wallet_manager = load_wallet_manager(config, external)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create wallet manager: {}", e))?;
+    if wallet_manager.is_emergency_locked().await {
+        anyhow::bail!("Emergency lock active - run `snipe wallet emergency --resume` first");
+    }
+    let vault = wallet_manager.vault_address().await?;
+
+    let position_manager = crate::position::manager::PositionManager::new(
+        config.safety.clone(),
+        Some(state_path(config, "positions.json", false)),
+    );
+    if let Err(e) = position_manager.load().await {
+        warn!("Could not load positions: {}", e);
+    }
+    let open_mints: std::collections::HashSet<String> = position_manager
+        .get_all_positions()
+        .await
+        .into_iter()
+        .map(|p| p.mint)
+        .collect();
+
+    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
+        config.rpc.endpoint.clone(),
+        std::time::Duration::from_millis(config.rpc.timeout_ms),
+    );
+    let sweeper = TokenSweeper::new(rpc_client, signer.clone(), vault);
+    let accounts = sweeper.accounts()?;
+    if accounts.is_empty() {
+        println!("No token accounts to sweep.");
+        return Ok(());
+    }
+
+    let dex_client = DexScreenerClient::new();
+    let mut items = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let price = if account.raw_amount > 0 && !open_mints.contains(&account.mint.to_string()) {
+            match dex_client.get_token_pairs(&account.mint.to_string()).await {
+                Ok(pair) => pair
+                    .and_then(|p| p.price_usd)
+                    .and_then(|p| p.parse::<f64>().ok()),
+                Err(e) => {
+                    warn!("No price for {}: {}", account.mint, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        items.push(plan_item(
+            account,
+            price,
+            &open_mints,
+            min_value_usd,
+            burn_dust,
+        ));
+    }
+
+    println!("\n=== WALLET SWEEP ===");
+    println!("From:  {}", signer.pubkey());
+    println!("Vault: {}\n", vault);
+    println!(
+        "{:<46} {:>16} {:>10} {:<9} {}",
+        "MINT", "AMOUNT", "VALUE", "ACTION", "NOTE"
+    );
+    println!("{}", "-".repeat(100));
+    for item in &items {
+        println!(
+            "{:<46} {:>16.4} {:>10} {:<9} {}",
+            item.account.mint.to_string(),
+            item.token_amount(),
+            item.value_usd()
+                .map(|v| format!("${:.2}", v))
+                .unwrap_or_else(|| "-".to_string()),
+            item.action
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| "skip".to_string()),
+            item.note
+        );
     }
 
-    let from_data = std::fs::read_to_string(&from_path)?;
-    let from_secret: Vec<u8> = serde_json::from_str(&from_data)?;
-    let from_keypair = solana_sdk::signature::Keypair::from_bytes(&from_secret)?;
+    let planned: Vec<_> = items.iter().filter(|i| i.action.is_some()).collect();
+    let rent: f64 = planned.iter().map(|i| i.rent_reclaimed_sol()).sum();
+    println!(
+        "\n{} action(s), {:.6} SOL rent to reclaim",
+        planned.len(),
+        rent
+    );
 
-    // Determine destination address
-    let to_pubkey = if to.len() >= 32 && to.len() <= 44 {
-        // Looks like a base58 address
-        solana_sdk::pubkey::Pubkey::from_str(to)?
-    } else {
-        // It's a wallet name - load the pubkey
-        let to_path = format!("{}/{}/keypair.json", config.wallet.credentials_dir, to);
-        if !std::path::Path::new(&to_path).exists() {
-            anyhow::bail!("Destination wallet '{}' not found", to);
+    if planned.is_empty() || dry_run {
+        if dry_run {
+            println!("\n=== DRY RUN - nothing sent ===");
         }
-        let to_data = std::fs::read_to_string(&to_path)?;
-        let to_secret: Vec<u8> = serde_json::from_str(&to_data)?;
-        let to_keypair = solana_sdk::signature::Keypair::from_bytes(&to_secret)?;
-        to_keypair.pubkey()
-    };
-
-    // Check safety limits
-    if amount > config.wallet.safety.max_single_transfer_sol {
-        anyhow::bail!(
-            "Transfer amount {} SOL exceeds max_single_transfer_sol limit of {} SOL",
-            amount,
-            config.wallet.safety.max_single_transfer_sol
-        );
+        return Ok(());
     }
 
-    // Confirmation
-    if !force && amount > config.wallet.safety.confirm_above_sol {
-        use dialoguer::Confirm;
+    if !force {
         let confirmed = Confirm::new()
             .with_prompt(format!(
-                "Transfer {} SOL from {} to {}?",
-                amount, from, to_pubkey
+                "Execute {} sweep action(s)? This cannot be undone.",
+                planned.len()
             ))
+            .default(false)
             .interact()?;
-
         if !confirmed {
-            println!("Transfer cancelled.");
+            info!("Sweep cancelled by user");
             return Ok(());
         }
     }
 
-    // Execute transfer
-    let rpc_client = solana_client::rpc_client::RpcClient::new_with_timeout(
-        config.rpc.endpoint.clone(),
-        std::time::Duration::from_millis(config.rpc.timeout_ms),
-    );
-
-    let lamports = (amount * 1e9) as u64;
-    let balance = rpc_client.get_balance(&from_keypair.pubkey())?;
-
-    if balance < lamports + 5000 {
-        anyhow::bail!(
-            "Insufficient balance: have {} SOL, need {} SOL + fees",
-            balance as f64 / 1e9,
-            amount
-        );
+    let hot_wallet = signer.pubkey().to_string();
+    let (mut done, mut failed, mut reclaimed) = (0, 0, 0.0);
+    for item in planned {
+        match sweeper.execute(item).await {
+            Ok(signature) => {
+                done += 1;
+                reclaimed += item.rent_reclaimed_sol();
+                println!(
+                    "{} {}: {}",
+                    item.action.map(|a| a.to_string()).unwrap_or_default(),
+                    item.account.mint,
+                    signature
+                );
+                if let Some(record) = item.record(&hot_wallet, &vault.to_string(), &signature) {
+                    if let Err(e) = wallet_manager.record_transfer(record).await {
+                        warn!("Could not record sweep of {}: {}", item.account.mint, e);
+                    }
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("FAILED {}: {}", item.account.mint, e);
+            }
+        }
     }
 
-    let recent_blockhash = rpc_client.get_latest_blockhash()?;
-    let tx = solana_sdk::transaction::Transaction::new_signed_with_payer(
-        &[solana_sdk::system_instruction::transfer(
-            &from_keypair.pubkey(),
-            &to_pubkey,
-            lamports,
-        )],
-        Some(&from_keypair.pubkey()),
-        &[&from_keypair],
-        recent_blockhash,
+    println!(
+        "\nSwept {} account(s), {} failed, {:.6} SOL rent reclaimed",
+        done, failed, reclaimed
     );
+    if failed > 0 {
+        anyhow::bail!("{} sweep action(s) failed", failed);
+    }
+    Ok(())
+}
+
+/// `scan_history.json` for `snipe scan` / `snipe hot-scan` (None when disabled)
+fn load_scan_history(config: &Config) -> Option<ScanHistory> {
+    if !config.scan_history.enabled {
+        return None;
+    }
+    let path = std::path::Path::new(&config.wallet.credentials_dir).join(SCAN_HISTORY_FILE);
+    match ScanHistory::load(&path, config.scan_history.clone()) {
+        Ok(history) => Some(history),
+        Err(e) => {
+            warn!(
+                "Scan history disabled - failed to read {}: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
 
-    let sig = rpc_client.send_and_confirm_transaction(&tx)?;
-    info!("Transfer successful: {}", sig);
-    println!("Transferred {} SOL from {} to {}", amount, from, to_pubkey);
-    println!("Signature: {}", sig);
+/// Record one scan cycle and alert on its second-wind tokens (in scan order)
+fn record_scan_cycle(
+    history: &mut ScanHistory,
+    tokens: &[crate::dexscreener::HotToken],
+    notifier: &Notifier,
+) -> Vec<ScanDelta> {
+    let second_wind: Vec<ScanDelta> = history
+        .record(tokens, chrono::Utc::now())
+        .into_iter()
+        .filter(|d| d.is_second_wind(history.config()))
+        .collect();
+    for delta in &second_wind {
+        warn!("SECOND WIND: {}", delta);
+        notifier.notify(Notification::SecondWind {
+            symbol: delta.current.symbol.clone(),
+            mint: delta.mint.clone(),
+            score_delta: delta.score_delta(),
+            ratio_delta: delta.ratio_delta(),
+        });
+    }
+    if let Err(e) = history.save() {
+        warn!("Failed to save scan history: {}", e);
+    }
+    second_wind
+}
 
+/// Print the scan snapshots recorded for one token
+pub fn scan_history(config: &Config, mint: &str) -> Result<()> {
+    let path = std::path::Path::new(&config.wallet.credentials_dir).join(SCAN_HISTORY_FILE);
+    let history = ScanHistory::load(&path, config.scan_history.clone())?;
+    let trajectory = history.trajectory(mint);
+    let Some(first) = trajectory.first() else {
+        println!("No scan history for {}", mint);
+        return Ok(());
+    };
+    println!("{} ({}) - {} scans", first.symbol, mint, trajectory.len());
+    println!("{:-<80}", "");
+    for snapshot in trajectory {
+        println!("{}", snapshot);
+    }
     Ok(())
 }
 
 /// Scan existing tokens for opportunities
 pub async fn scan(
-    _config: &Config,
+    config: &Config,
     min_liquidity: f64,
     max_liquidity: f64,
     min_volume: f64,
@@ -2279,9 +8389,14 @@ pub async fn scan(
         max_market_cap: max_liquidity * 150.0 * 100.0, // Max liquidity implies max mcap
         ..Default::default()
     };
+    let mut scan_history = load_scan_history(config);
+    let notifier = Notifier::spawn(&config.notifications);
 
     loop {
         let tokens = client.scan_hot_tokens(&scan_config).await?;
+        if let Some(ref mut history) = scan_history {
+            record_scan_cycle(history, &tokens, &notifier);
+        }
         let tokens: Vec<_> = tokens.into_iter().take(limit).collect();
 
         if format == "json" {
@@ -2367,6 +8482,7 @@ pub async fn hot_scan(
         if dry_run {
             warn!("DRY-RUN mode - no real trades will be executed");
         }
+        check_config_at_startup(&ConfigReport::check(config).with_run_flags(dry_run, auto_buy))?;
     }
 
     // Jito bundle support note
@@ -2394,17 +8510,13 @@ pub async fn hot_scan(
         None
     };
 
-    // Fall back to single keypair if multi-wallet not configured
-    let keypair_path = std::env::var("KEYPAIR_PATH")
-        .unwrap_or_else(|_| format!("{}/hot-trading/keypair.json", config.wallet.credentials_dir));
-    let keypair_data = std::fs::read_to_string(&keypair_path)?;
-    let secret_key: Vec<u8> = serde_json::from_str(&keypair_data)?;
-    let keypair = std::sync::Arc::new(solana_sdk::signature::Keypair::from_bytes(&secret_key)?);
+    // Fall back to the hot wallet signer if multi-wallet not configured
+    let signer = load_hot_signer(config)?;
 
     if multi_wallet.is_some() {
-        info!("Primary wallet (fallback): {}", keypair.pubkey());
+        info!("Primary wallet (fallback): {}", signer.pubkey());
     } else {
-        info!("Signing wallet: {}", keypair.pubkey());
+        info!("Signing wallet: {}", signer.pubkey());
     }
 
     // Initialize RPC
@@ -2415,6 +8527,7 @@ pub async fn hot_scan(
 
     // Initialize trader - Force Local API if configured (0.5% fee vs 1% for Lightning)
     let use_local_api = config.pumpportal.api_key.is_empty() || config.pumpportal.force_local_api;
+    let platform_fee_pct = config.pumpportal.platform_fee_pct(use_local_api);
     let trader = if config.pumpportal.use_for_trading {
         if use_local_api {
             if config.pumpportal.force_local_api {
@@ -2422,7 +8535,7 @@ pub async fn hot_scan(
             } else {
                 info!("Using Local API (sign + send locally)");
             }
-            info!("Trading wallet: {}", keypair.pubkey());
+            info!("Trading wallet: {}", signer.pubkey());
             Some(std::sync::Arc::new(
                 crate::trading::pumpportal_api::PumpPortalTrader::local(),
             ))
@@ -2443,712 +8556,247 @@ pub async fn hot_scan(
     } else {
         None
     };
+    // Local API buys of migrated tokens go straight to their PumpSwap pool
+    let pumpswap = crate::trading::PumpSwapTrader::new(config.trading.clone());
+
+    // Trade and second-wind alerts
+    let notifier = Notifier::spawn(&config.notifications);
 
     // Initialize position manager for tracking
-    let position_manager = std::sync::Arc::new(crate::position::manager::PositionManager::new(
+    let mut position_manager = crate::position::manager::PositionManager::new(
         config.safety.clone(),
         Some(format!("{}/positions.json", config.wallet.credentials_dir)),
-    ));
+    )
+    .with_exit_thresholds(config.auto_sell.entry_types.clone());
+    // Per-wallet daily spend caps (wallets.json) and trade alerts
+    if auto_buy && !dry_run {
+        let trading_wallet = if use_local_api {
+            signer.pubkey()
+        } else {
+            Pubkey::from_str(&config.pumpportal.lightning_wallet).unwrap_or(signer.pubkey())
+        };
+        let safety =
+            crate::wallet::manager::spend_enforcer(&wallet_manager_config(config), signer.pubkey())?;
+        position_manager = position_manager
+            .with_spend_guard(Arc::new(safety), trading_wallet)
+            .with_notifier(notifier.clone());
+    }
+    let position_manager = std::sync::Arc::new(position_manager);
     position_manager.load().await?;
 
+    // Auto-buy candidates are checked on-chain first ([adaptive_filter.hot_scan])
+    let verify =
+        auto_buy && config.adaptive_filter.enabled && config.adaptive_filter.hot_scan.enabled;
+
     // Initialize smart money wallet profiler and Helius client (if enabled)
-    let (helius_client, wallet_profiler) = if config.smart_money.enabled {
+    let (helius_client, wallet_profiler) = if config.smart_money.enabled || verify {
         use crate::filter::helius::HeliusClient;
         use crate::filter::smart_money::wallet_profiler::{WalletProfiler, WalletProfilerConfig};
 
         if let Some(helius) = HeliusClient::from_rpc_url(&config.rpc.endpoint) {
-            info!("Smart money wallet profiler ENABLED - analyzing creators before buy");
-            let helius_arc = std::sync::Arc::new(helius);
-            let profiler = std::sync::Arc::new(WalletProfiler::new(
-                helius_arc.clone(),
-                WalletProfilerConfig::default(),
-            ));
-            (Some(helius_arc), Some(profiler))
+            let helius_arc =
+                std::sync::Arc::new(helius.with_budget(config.smart_money.helius.clone()));
+            let profiler = config.smart_money.enabled.then(|| {
+                info!("Smart money wallet profiler ENABLED - analyzing creators before buy");
+                std::sync::Arc::new(WalletProfiler::new(
+                    helius_arc.clone(),
+                    WalletProfilerConfig::default(),
+                ))
+            });
+            (Some(helius_arc), profiler)
         } else {
-            warn!("Smart money enabled but Helius API key not found in RPC URL - profiler disabled");
+            warn!("Helius API key not found in RPC URL - creator profiler and on-chain checks disabled");
             (None, None)
         }
-    } else {
-        info!("Smart money wallet profiler disabled");
-        (None, None)
-    };
-
-    // Track already-bought mints this session with persistence (mint -> timestamp)
-    // TTL: Remove entries older than 24 hours to allow re-buying of rebounding tokens
-    const BOUGHT_MINTS_TTL_HOURS: i64 = 24;
-    let bought_mints_path = format!("{}/bought_mints.json", config.wallet.credentials_dir);
-    let bought_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> = {
-        // Load from file if exists and prune stale entries
-        let now = chrono::Utc::now().timestamp();
-        let ttl_secs = BOUGHT_MINTS_TTL_HOURS * 3600;
-        let loaded: std::collections::HashMap<String, i64> =
-            if std::path::Path::new(&bought_mints_path).exists() {
-                match std::fs::read_to_string(&bought_mints_path) {
-                    Ok(data) => {
-                        // Try new format (HashMap with timestamps)
-                        if let Ok(map) =
-                            serde_json::from_str::<std::collections::HashMap<String, i64>>(&data)
-                        {
-                            let before = map.len();
-                            let pruned: std::collections::HashMap<String, i64> = map
-                                .into_iter()
-                                .filter(|(_, ts)| now - ts < ttl_secs)
-                                .collect();
-                            let removed = before - pruned.len();
-                            if removed > 0 {
-                                info!(
-                                    "Pruned {} stale entries from bought_mints (TTL: {}h)",
-                                    removed, BOUGHT_MINTS_TTL_HOURS
-                                );
-                            }
-                            info!("Loaded {} bought mints from session state", pruned.len());
-                            pruned
-                        } else if let Ok(mints) = serde_json::from_str::<Vec<String>>(&data) {
-                            // Migrate old format (Vec<String>) to new format with current timestamp
-                            info!("Migrating {} bought mints from legacy format", mints.len());
-                            mints.into_iter().map(|m| (m, now)).collect()
-                        } else {
-                            std::collections::HashMap::new()
-                        }
-                    }
-                    Err(_) => std::collections::HashMap::new(),
-                }
-            } else {
-                std::collections::HashMap::new()
-            };
-        std::sync::Arc::new(tokio::sync::Mutex::new(loaded))
-    };
-    let bought_mints_path = std::sync::Arc::new(bought_mints_path);
-
-    // Track recently sold mints with cooldown (5 minutes before re-entry allowed)
-    // This prevents buying back at the top immediately after selling
-    const SOLD_MINTS_COOLDOWN_SECS: i64 = 300; // 5 minutes
-    let sold_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> =
-        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()));
-
-    // Track failed mints (buys that didn't land tokens) with longer cooldown
-    // This prevents repeatedly trying to buy tokens that consistently fail
-    const FAILED_MINTS_COOLDOWN_SECS: i64 = 1800; // 30 minutes
-    let failed_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> =
-        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new()));
-
-    // Initialize kill-switch evaluator for smart money exits
-    let kill_switch_evaluator: Option<std::sync::Arc<KillSwitchEvaluator>> =
-        if config.smart_money.enabled && config.smart_money.kill_switches.enabled {
-            info!("Initializing kill-switch evaluator for hot_scan...");
-            let evaluator = std::sync::Arc::new(KillSwitchEvaluator::new(
-                config.smart_money.kill_switches.clone(),
-                config.smart_money.holder_watcher.clone(),
-            ));
-            info!(
-                "Kill-switches ENABLED: deployer_sell={}, top_holder_sell={}",
-                config.smart_money.kill_switches.deployer_sell_any,
-                config.smart_money.kill_switches.top_holder_sell
-            );
-            Some(evaluator)
-        } else {
-            info!("Kill-switches disabled in hot_scan mode");
-            None
-        };
-
-    let dex_client = DexScreenerClient::new();
-    let scan_config = HotScanConfig {
-        min_m5_change: min_m5,
-        min_buy_sell_ratio: min_ratio,
-        min_liquidity_usd: min_liquidity,
-        max_market_cap: max_mcap,
-        ..Default::default()
-    };
-
-    // === POSITION MONITOR BACKGROUND TASK ===
-    if config.auto_sell.enabled && !dry_run {
-        let monitor_config = config.clone();
-        let monitor_positions = position_manager.clone();
-        let monitor_trader = trader.clone();
-        let monitor_keypair = keypair.clone();
-        let monitor_rpc = rpc_client.clone();
-        let monitor_dex = DexScreenerClient::new();
-        let monitor_bought_mints = bought_mints.clone();
-        let monitor_bought_mints_path = bought_mints_path.clone();
-        let monitor_sold_mints = sold_mints.clone();
-        let monitor_failed_mints = failed_mints.clone();
-        let monitor_kill_switch = kill_switch_evaluator.clone();
-        let monitor_helius = helius_client.clone();
-        let monitor_use_local_api = use_local_api;
-        let monitor_multi_wallet = multi_wallet.clone();
-        // Determine which wallet to query for token balances
-        let monitor_wallet = if use_local_api {
-            keypair.pubkey()
-        } else if !config.pumpportal.lightning_wallet.is_empty() {
-            Pubkey::from_str(&config.pumpportal.lightning_wallet)
-                .unwrap_or_else(|_| keypair.pubkey())
-        } else {
-            keypair.pubkey()
-        };
-
-        tokio::spawn(async move {
-            info!("=== POSITION MONITOR STARTED ===");
-            let poll_interval_ms = monitor_config.auto_sell.price_poll_interval_ms;
-            info!("Features: Dynamic Trailing ({}%-{}%), Layered Exits ({}%/{}%/{}%), Kill-Switch, LOCAL FALLBACK",
-                monitor_config.auto_sell.trailing_stop_base_pct,
-                monitor_config.auto_sell.trailing_stop_tight_pct,
-                monitor_config.auto_sell.quick_profit_pct,
-                monitor_config.auto_sell.second_profit_pct,
-                monitor_config.auto_sell.take_profit_pct
-            );
-            info!("Poll interval: {}ms", poll_interval_ms);
-            if !monitor_use_local_api {
-                info!(
-                    "Using Lightning wallet for balance queries: {}",
-                    monitor_wallet
-                );
-            }
-
-            let mut sell_attempts: std::collections::HashMap<String, u32> =
-                std::collections::HashMap::new();
-            // Track confirmed positions (tx landed and ATA exists)
-            let mut confirmed_positions: std::collections::HashSet<String> =
-                std::collections::HashSet::new();
-
-            loop {
-                tokio::time::sleep(std::time::Duration::from_millis(poll_interval_ms)).await;
-
-                let positions = monitor_positions.get_all_positions().await;
-                if positions.is_empty() {
-                    continue;
-                }
-
-                // Fetch current prices from DexScreener with fallback handling
-                for position in positions {
-                    // Get current price from DexScreener with retry
-                    let price_result = monitor_dex.get_token_info(&position.mint).await;
-
-                    let current_price = match price_result {
-                        Ok(Some(token_info)) => {
-                            if token_info.price_native > 0.0 {
-                                token_info.price_native
-                            } else {
-                                // Zero price from API - use last known price if available
-                                if position.current_price > 0.0 {
-                                    warn!("[{}] DexScreener returned 0 price, using last known: {:.10}",
-                                          position.symbol, position.current_price);
-                                    position.current_price
-                                } else {
-                                    continue;
-                                }
-                            }
-                        }
-                        Ok(None) => {
-                            // Token not found on DexScreener - use last known price
-                            if position.current_price > 0.0 {
-                                warn!(
-                                    "[{}] Not found on DexScreener, using last known price: {:.10}",
-                                    position.symbol, position.current_price
-                                );
-                                position.current_price
-                            } else {
-                                warn!(
-                                    "[{}] Not found on DexScreener and no last price - skipping",
-                                    position.symbol
-                                );
-                                continue;
-                            }
-                        }
-                        Err(e) => {
-                            // API error - use last known price as fallback
-                            if position.current_price > 0.0 {
-                                warn!(
-                                    "[{}] DexScreener error: {} - using last known price: {:.10}",
-                                    position.symbol, e, position.current_price
-                                );
-                                position.current_price
-                            } else {
-                                error!(
-                                    "[{}] DexScreener error and no fallback price: {}",
-                                    position.symbol, e
-                                );
-                                continue;
-                            }
-                        }
-                    };
-
-                    // Update position price
-                    monitor_positions
-                        .update_price(&position.mint, current_price)
-                        .await;
-
-                    // Small delay between API calls to avoid rate limiting
-                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-
-                    // Get updated position with peak_price tracked
-                    let position = match monitor_positions.get_position(&position.mint).await {
-                        Some(p) => p,
-                        None => continue,
-                    };
-
-                    // TX CONFIRMATION CHECK: Verify buy tx confirmed before allowing sells
-                    if !confirmed_positions.contains(&position.mint) {
-                        let position_age_secs = (chrono::Utc::now() - position.entry_time)
-                            .num_seconds()
-                            .max(0) as u64;
-
-                        // First 5 seconds: just wait
-                        if position_age_secs < 5 {
-                            continue;
-                        }
-
-                        // After 5 seconds: check if we have tokens
-                        // Use position's wallet_pubkey if available (multi-wallet), fallback to monitor_wallet
-                        let check_wallet = if !position.wallet_pubkey.is_empty() {
-                            Pubkey::from_str(&position.wallet_pubkey).unwrap_or(monitor_wallet)
-                        } else {
-                            monitor_wallet
-                        };
-                        let token_balance =
-                            query_token_balance(&monitor_rpc, &check_wallet, &position.mint);
-
-                        if token_balance > 0 {
-                            info!(
-                                "[{}] TX CONFIRMED - token balance: {}",
-                                position.symbol, token_balance
-                            );
-                            confirmed_positions.insert(position.mint.clone());
-                        } else if position_age_secs > 30 {
-                            // After 30 seconds with no tokens, assume tx failed
-                            warn!(
-                                "[{}] TX LIKELY FAILED - no tokens after 30s, removing position (30min cooldown)",
-                                position.symbol
-                            );
-                            let _ = monitor_positions.abandon_position(&position.mint).await;
-                            let _ = remove_bought_mint(
-                                &monitor_bought_mints,
-                                &monitor_bought_mints_path,
-                                &position.mint,
-                            )
-                            .await;
-                            // Add to failed_mints with 30 minute cooldown to prevent repeated failures
-                            {
-                                let mut failed = monitor_failed_mints.lock().await;
-                                failed.insert(position.mint.clone(), chrono::Utc::now().timestamp());
-                                info!("[{}] Added to failed_mints blacklist (30min cooldown)", position.symbol);
-                            }
-                            continue;
-                        } else {
-                            // Still waiting for confirmation
-                            continue;
-                        }
-                    }
-
-                    // Calculate P&L from entry
-                    let pnl_pct = if position.entry_price > 0.0 {
-                        ((current_price - position.entry_price) / position.entry_price) * 100.0
-                    } else {
-                        0.0
-                    };
-
-                    // Calculate drop from peak (for trailing stop)
-                    let peak_price = if position.peak_price > 0.0 {
-                        position.peak_price
-                    } else {
-                        position.entry_price
-                    };
-                    let drop_from_peak_pct = if peak_price > 0.0 {
-                        ((peak_price - current_price) / peak_price) * 100.0
-                    } else {
-                        0.0
-                    };
-
-                    let hold_time_secs = (chrono::Utc::now() - position.entry_time)
-                        .num_seconds()
-                        .max(0) as u64;
-
-                    // Get entry-type-specific thresholds
-                    let tp_pct = position.entry_type.take_profit_pct();
-                    let sl_pct = position.entry_type.stop_loss_pct();
-                    let quick_profit_pct = position.entry_type.quick_profit_pct();
-                    let max_hold = position.entry_type.max_hold_secs();
-
-                    // Log position status periodically
-                    if hold_time_secs % 15 == 0 {
-                        info!(
-                            "[{}] Price: {:.10} | P&L: {:+.1}% | Peak: {:+.1}% | Hold: {}s",
-                            position.symbol,
-                            current_price,
-                            pnl_pct,
-                            if peak_price > position.entry_price {
-                                ((peak_price - position.entry_price) / position.entry_price) * 100.0
-                            } else {
-                                0.0
-                            },
-                            hold_time_secs
-                        );
-                    }
-
-                    // Get config values for layered exits
-                    let no_movement_secs = monitor_config.auto_sell.no_movement_secs;
-                    let no_movement_threshold = monitor_config.auto_sell.no_movement_threshold_pct;
-                    let second_profit_pct = monitor_config.auto_sell.second_profit_pct;
-
-                    // === DYNAMIC TRAILING STOP ===
-                    // Tighten trailing stop as profit grows to prevent round-tripping
-                    let trailing_stop_pct = if monitor_config.auto_sell.dynamic_trailing_enabled {
-                        if pnl_pct >= 25.0 {
-                            monitor_config.auto_sell.trailing_stop_tight_pct  // 3% at high gains
-                        } else if pnl_pct >= 15.0 {
-                            monitor_config.auto_sell.trailing_stop_medium_pct // 4% at medium gains
-                        } else {
-                            monitor_config.auto_sell.trailing_stop_base_pct   // 5% base
-                        }
-                    } else {
-                        5.0 // Fixed trailing stop if dynamic disabled
-                    };
-
-                    let mut should_sell = false;
-                    let mut sell_pct = "100%";
-                    let mut reason = String::new();
-
-                    // === KILL-SWITCH CHECK (HIGHEST PRIORITY) ===
-                    // First check position flag (set by other systems)
-                    if let Some(ks_reason) = monitor_positions.is_kill_switch_triggered(&position.mint).await {
-                        should_sell = true;
-                        reason = format!("KILL-SWITCH: {}", ks_reason);
-                        warn!("KILL-SWITCH EXIT: {} - {}", position.symbol, ks_reason);
-                    }
-                    // Then actively evaluate kill-switch conditions
-                    if !should_sell {
-                        if let Some(ref evaluator) = monitor_kill_switch {
-                            if let KillSwitchDecision::Exit(alert) = evaluator.should_exit(&position.mint) {
-                                should_sell = true;
-                                reason = format!("KILL-SWITCH: {} (urgency: {:?})", alert.reason, alert.urgency);
-                                warn!("KILL-SWITCH EXIT: {} - {} [{:?}]", position.symbol, alert.reason, alert.urgency);
-                            }
-                        }
-                    }
-
-                    // 1. Stop loss
-                    if !should_sell && pnl_pct <= -sl_pct {
-                        should_sell = true;
-                        reason = format!("STOP LOSS at {:.1}% (limit: -{:.0}%)", pnl_pct, sl_pct);
-                    }
-
-                    // 2. Trailing stop (only if in profit and dropped from peak)
-                    // Now uses dynamic trailing stop percentage
-                    if !should_sell && pnl_pct > 0.0 && drop_from_peak_pct >= trailing_stop_pct {
-                        should_sell = true;
-                        reason = format!(
-                            "TRAILING STOP: dropped {:.1}% from peak (P&L: +{:.1}%, trail: {:.0}%)",
-                            drop_from_peak_pct, pnl_pct, trailing_stop_pct
-                        );
-                    }
-
-                    // 3. Take profit (final exit)
-                    if !should_sell && pnl_pct >= tp_pct {
-                        should_sell = true;
-                        reason = format!("TAKE PROFIT at {:.1}% (target: {:.0}%)", pnl_pct, tp_pct);
-                    }
-
-                    // 4. Quick profit - FIRST LAYER (50% sell at quick_profit_pct)
-                    if !should_sell
-                        && !position.quick_profit_taken
-                        && pnl_pct >= quick_profit_pct
-                        && pnl_pct < second_profit_pct
-                    {
-                        should_sell = true;
-                        sell_pct = "50%";
-                        reason = format!("LAYER 1: Quick profit at {:.1}% - selling 50%", pnl_pct);
-                    }
-
-                    // 5. Second profit - SECOND LAYER (25% sell at second_profit_pct)
-                    if !should_sell
-                        && position.quick_profit_taken
-                        && !position.second_profit_taken
-                        && pnl_pct >= second_profit_pct
-                        && pnl_pct < tp_pct
-                    {
-                        should_sell = true;
-                        sell_pct = "25%";
-                        reason = format!("LAYER 2: Second profit at {:.1}% - selling 25%", pnl_pct);
-                    }
+    } else {
+        info!("Smart money wallet profiler disabled");
+        (None, None)
+    };
 
-                    // 6. No-movement exit
-                    if !should_sell
-                        && hold_time_secs >= no_movement_secs
-                        && pnl_pct.abs() < no_movement_threshold
-                    {
-                        should_sell = true;
-                        reason = format!("NO MOVEMENT: {:.1}% after {}s", pnl_pct, hold_time_secs);
-                    }
+    // Scores auto-buy candidates; skips and buys go to the decision log
+    let verifier = if verify {
+        let verifier = crate::filter::HotScanVerifier::new(
+            &config.adaptive_filter,
+            helius_client.clone(),
+            wallet_profiler.clone(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create hot-scan verifier: {}", e))?;
+        info!(
+            "On-chain verification ENABLED - buying {:?} or better",
+            verifier.min_recommendation()
+        );
+        Some(verifier)
+    } else {
+        None
+    };
+    let decision_log = if verifier.is_some() {
+        crate::filter::DecisionLog::spawn(&config.adaptive_filter.decision_log)
+    } else {
+        crate::filter::DecisionLog::disabled()
+    };
 
-                    // 7. Max hold time
-                    if !should_sell {
-                        if let Some(max_secs) = max_hold {
-                            if hold_time_secs >= max_secs {
-                                should_sell = true;
-                                reason = format!(
-                                    "MAX HOLD TIME ({} secs) P&L: {:.1}%",
-                                    max_secs, pnl_pct
+    // Track already-bought mints this session with persistence (mint -> timestamp)
+    // TTL: Remove entries older than 24 hours to allow re-buying of rebounding tokens
+    const BOUGHT_MINTS_TTL_HOURS: i64 = 24;
+    let bought_mints_path = format!("{}/bought_mints.json", config.wallet.credentials_dir);
+    let bought_mints: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, i64>>> = {
+        // Load from file if exists and prune stale entries
+        let now = chrono::Utc::now().timestamp();
+        let ttl_secs = BOUGHT_MINTS_TTL_HOURS * 3600;
+        let loaded: std::collections::HashMap<String, i64> =
+            if std::path::Path::new(&bought_mints_path).exists() {
+                match std::fs::read_to_string(&bought_mints_path) {
+                    Ok(data) => {
+                        // Try new format (HashMap with timestamps)
+                        if let Ok(map) =
+                            serde_json::from_str::<std::collections::HashMap<String, i64>>(&data)
+                        {
+                            let before = map.len();
+                            let pruned: std::collections::HashMap<String, i64> = map
+                                .into_iter()
+                                .filter(|(_, ts)| now - ts < ttl_secs)
+                                .collect();
+                            let removed = before - pruned.len();
+                            if removed > 0 {
+                                info!(
+                                    "Pruned {} stale entries from bought_mints (TTL: {}h)",
+                                    removed, BOUGHT_MINTS_TTL_HOURS
                                 );
                             }
+                            info!("Loaded {} bought mints from session state", pruned.len());
+                            pruned
+                        } else if let Ok(mints) = serde_json::from_str::<Vec<String>>(&data) {
+                            // Migrate old format (Vec<String>) to new format with current timestamp
+                            info!("Migrating {} bought mints from legacy format", mints.len());
+                            mints.into_iter().map(|m| (m, now)).collect()
+                        } else {
+                            std::collections::HashMap::new()
                         }
                     }
+                    Err(_) => std::collections::HashMap::new(),
+                }
+            } else {
+                std::collections::HashMap::new()
+            };
+        std::sync::Arc::new(tokio::sync::Mutex::new(loaded))
+    };
+    let bought_mints_path = std::sync::Arc::new(bought_mints_path);
 
-                    // Execute sell
-                    if should_sell {
-                        warn!(
-                            "AUTO-SELL TRIGGERED: {} ({}) - {}",
-                            position.symbol, position.mint, reason
-                        );
-
-                        if let Some(ref trader) = monitor_trader {
-                            let slippage = monitor_config.trading.slippage_bps / 100;
-                            let priority_fee =
-                                monitor_config.trading.priority_fee_lamports as f64 / 1e9;
-
-                            let attempts = sell_attempts.entry(position.mint.clone()).or_insert(0);
-                            *attempts += 1;
-
-                            if *attempts > 5 {
-                                error!("AUTO-SELL GAVE UP for {} after 5 attempts - removing from tracking", position.symbol);
-                                let _ = monitor_positions.abandon_position(&position.mint).await;
-                                let _ = remove_bought_mint(
-                                    &monitor_bought_mints,
-                                    &monitor_bought_mints_path,
-                                    &position.mint,
-                                )
-                                .await;
-                                sell_attempts.remove(&position.mint);
-                                continue;
-                            }
-
-                            // Query SOL balance BEFORE sell for real P&L tracking
-                            // Use position's wallet if available (multi-wallet), fallback to monitor_wallet
-                            let position_wallet = if !position.wallet_pubkey.is_empty() {
-                                Pubkey::from_str(&position.wallet_pubkey).unwrap_or(monitor_wallet)
-                            } else {
-                                monitor_wallet
-                            };
-                            let sol_before = monitor_rpc
-                                .get_balance(&position_wallet)
-                                .unwrap_or(0) as f64
-                                / 1_000_000_000.0;
-
-                            // Determine the correct keypair for this position
-                            // For multi-wallet, look up keypair by position's wallet_pubkey
-                            let sell_keypair: std::sync::Arc<solana_sdk::signature::Keypair> = if !position.wallet_pubkey.is_empty() {
-                                if let Some(ref mw) = monitor_multi_wallet {
-                                    // Find wallet matching position's pubkey
-                                    if let Some(wallet) = mw.find_by_address(&position.wallet_pubkey) {
-                                        std::sync::Arc::new(
-                                            solana_sdk::signature::Keypair::from_bytes(&wallet.keypair.to_bytes()).unwrap()
-                                        )
-                                    } else {
-                                        warn!("[{}] Position wallet {} not found in multi-wallet, using primary",
-                                              position.symbol, &position.wallet_pubkey[..8]);
-                                        monitor_keypair.clone()
-                                    }
-                                } else {
-                                    monitor_keypair.clone()
-                                }
-                            } else {
-                                monitor_keypair.clone()
-                            };
-
-                            // For Local API mode, use local signing directly
-                            // For Lightning mode, try Lightning first then fall back to local
-                            let sell_result: std::result::Result<String, crate::error::Error> =
-                                if monitor_use_local_api {
-                                    // Local API mode: use local signing with correct wallet
-                                    info!("Attempting Local API sell (attempt {}, wallet: {})",
-                                          attempts, &sell_keypair.pubkey().to_string()[..8]);
-                                    trader
-                                        .sell_local(
-                                            &position.mint,
-                                            sell_pct,
-                                            slippage,
-                                            priority_fee,
-                                            &sell_keypair,
-                                            &monitor_rpc,
-                                        )
-                                        .await
-                                } else if *attempts <= 3 {
-                                    info!("Attempting Lightning API sell (attempt {})", attempts);
-                                    trader
-                                        .sell(&position.mint, sell_pct, slippage, priority_fee)
-                                        .await
-                                } else {
-                                    warn!("Lightning failed 3x, trying LOCAL SIGNING fallback (attempt {})", attempts);
-                                    trader
-                                        .sell_local(
-                                            &position.mint,
-                                            sell_pct,
-                                            slippage,
-                                            priority_fee,
-                                            &sell_keypair,
-                                            &monitor_rpc,
-                                        )
-                                        .await
-                                };
-
-                            match sell_result {
-                                Ok(sig) => {
-                                    info!("AUTO-SELL EXECUTED: {} - {}", position.symbol, sig);
-                                    sell_attempts.remove(&position.mint);
-
-                                    // Wait for tx confirmation then query actual SOL received
-                                    tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
-                                    let sol_after = monitor_rpc
-                                        .get_balance(&position_wallet)
-                                        .unwrap_or(0) as f64
-                                        / 1_000_000_000.0;
-                                    let raw_received = (sol_after - sol_before).max(0.0);
-
-                                    // Sanity check: received SOL shouldn't be more than 10x position cost
-                                    // If it is, the balance query likely failed - use estimate instead
-                                    let max_reasonable = position.total_cost_sol * 10.0;
-                                    let actual_received = if raw_received > max_reasonable {
-                                        warn!(
-                                            "[{}] Balance query anomaly: before={:.4}, after={:.4}, diff={:.4} - using estimate",
-                                            position.symbol, sol_before, sol_after, raw_received
-                                        );
-                                        0.0 // Force fallback to estimate
-                                    } else {
-                                        raw_received
-                                    };
-
-                                    // Calculate trade metrics
-                                    let hold_secs =
-                                        (chrono::Utc::now() - position.entry_time).num_seconds();
-                                    let price_change_pct = ((current_price - position.entry_price)
-                                        / position.entry_price)
-                                        * 100.0;
-
-                                    if sell_pct == "50%" {
-                                        // LAYER 1: Quick profit - sell 50%
-                                        let sell_amount = position.token_amount / 2;
-                                        // Use actual received SOL (fallback to estimate if 0)
-                                        let received = if actual_received > 0.0 {
-                                            actual_received
-                                        } else {
-                                            (sell_amount as f64 * current_price) * 0.98
-                                        };
-                                        let pnl_sol = received - (position.total_cost_sol / 2.0);
-                                        let _ = monitor_positions
-                                            .close_position(
-                                                &position.mint,
-                                                sell_amount,
-                                                received,
-                                            )
-                                            .await;
-                                        let _ = monitor_positions
-                                            .mark_quick_profit_taken(&position.mint)
-                                            .await;
-                                        info!("=== LAYER 1 PROFIT TAKEN (50%) ===");
-                                        info!(
-                                            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
-                                            position.symbol,
-                                            position.entry_price,
-                                            current_price,
-                                            price_change_pct
-                                        );
-                                        info!("  Tokens: {} | Received: {:.4} SOL | P&L: {:+.4} SOL | Hold: {}s",
-                                              sell_amount, received, pnl_sol, hold_secs);
-                                    } else if sell_pct == "25%" {
-                                        // LAYER 2: Second profit - sell 25% of original (50% of remaining)
-                                        let sell_amount = position.token_amount / 2; // Half of what's left
-                                        let received = if actual_received > 0.0 {
-                                            actual_received
-                                        } else {
-                                            (sell_amount as f64 * current_price) * 0.98
-                                        };
-                                        // Cost basis is proportional to remaining position
-                                        let cost_ratio = sell_amount as f64 / position.token_amount as f64;
-                                        let cost_basis = position.total_cost_sol * cost_ratio;
-                                        let pnl_sol = received - cost_basis;
-                                        let _ = monitor_positions
-                                            .close_position(
-                                                &position.mint,
-                                                sell_amount,
-                                                received,
-                                            )
-                                            .await;
-                                        let _ = monitor_positions
-                                            .mark_second_profit_taken(&position.mint)
-                                            .await;
-                                        info!("=== LAYER 2 PROFIT TAKEN (25%) ===");
-                                        info!(
-                                            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
-                                            position.symbol,
-                                            position.entry_price,
-                                            current_price,
-                                            price_change_pct
-                                        );
-                                        info!("  Tokens: {} | Received: {:.4} SOL | P&L: {:+.4} SOL | Hold: {}s",
-                                              sell_amount, received, pnl_sol, hold_secs);
-                                    } else {
-                                        // Use actual received SOL (fallback to estimate if 0)
-                                        let received = if actual_received > 0.0 {
-                                            actual_received
-                                        } else {
-                                            (position.token_amount as f64 * current_price) * 0.98
-                                        };
-                                        let pnl_sol = received - position.total_cost_sol;
-                                        let pnl_pct = (pnl_sol / position.total_cost_sol) * 100.0;
-                                        let _ = monitor_positions
-                                            .close_position(
-                                                &position.mint,
-                                                position.token_amount,
-                                                received,
-                                            )
-                                            .await;
+    // Re-entry cooldowns (sold) and blacklists (failed / rugged), persisted across restarts
+    // This prevents buying back at the top immediately after selling
+    let cooldowns = load_cooldowns(config, false).await;
 
-                                        // Clean up bought_mints on successful full sell
-                                        let _ = remove_bought_mint(
-                                            &monitor_bought_mints,
-                                            &monitor_bought_mints_path,
-                                            &position.mint,
-                                        )
-                                        .await;
+    // Initialize kill-switch evaluator for smart money exits
+    let kill_switch_evaluator: Option<std::sync::Arc<KillSwitchEvaluator>> =
+        if config.smart_money.enabled && config.smart_money.kill_switches.enabled {
+            info!("Initializing kill-switch evaluator for hot_scan...");
+            let evaluator = std::sync::Arc::new(KillSwitchEvaluator::new(
+                config.smart_money.kill_switches.clone(),
+                config.smart_money.holder_watcher.clone(),
+            ));
+            info!(
+                "Kill-switches ENABLED: deployer_sell={}, top_holder_sell={}",
+                config.smart_money.kill_switches.deployer_sell_any,
+                config.smart_money.kill_switches.top_holder_sell
+            );
+            Some(evaluator)
+        } else {
+            info!("Kill-switches disabled in hot_scan mode");
+            None
+        };
 
-                                        // Add to sold_mints with 5-minute cooldown before re-entry
-                                        // This prevents immediate re-buy at the top
-                                        {
-                                            let mut sold = monitor_sold_mints.lock().await;
-                                            sold.insert(position.mint.clone(), chrono::Utc::now().timestamp());
-                                            info!("[{}] Added to sold_mints (5min cooldown before re-entry)", position.symbol);
-                                        }
+    // One client for scanning and the position monitor, so both share its
+    // rate limit and price cache; the monitor falls back to the other price APIs
+    let dex_client = std::sync::Arc::new(DexScreenerClient::new());
+    let price_sources = std::sync::Arc::new(crate::price_source::CompositePriceSource::new(
+        &config.price_sources,
+        dex_client.clone(),
+    ));
+    let scan_config = HotScanConfig {
+        min_m5_change: min_m5,
+        min_buy_sell_ratio: min_ratio,
+        min_liquidity_usd: min_liquidity,
+        max_market_cap: max_mcap,
+        ..Default::default()
+    };
 
-                                        info!("=== TRADE CLOSED (Full) ===");
-                                        info!(
-                                            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
-                                            position.symbol,
-                                            position.entry_price,
-                                            current_price,
-                                            price_change_pct
-                                        );
-                                        info!("  Cost: {:.4} SOL | Received: {:.4} SOL (actual) | P&L: {:+.4} SOL ({:+.1}%) | Hold: {}s",
-                                              position.total_cost_sol, received, pnl_sol, pnl_pct, hold_secs);
-                                    }
-                                }
-                                Err(e) => {
-                                    error!(
-                                        "AUTO-SELL FAILED for {} (attempt {}): {}",
-                                        position.symbol, attempts, e
-                                    );
-                                }
-                            }
-                        }
-                    }
+    // === POSITION MONITOR BACKGROUND TASK ===
+    // Prices come from DexScreener; behavior is set by [auto_sell.monitor.hot_scan]
+    if config.auto_sell.enabled && !dry_run {
+        if let Some(ref trader) = trader {
+            let (event_tx, mut event_rx) = tokio::sync::mpsc::channel(64);
+            let profile = config.auto_sell.monitor.hot_scan;
+            let seller = crate::position::auto_sell::PumpPortalSeller::new(
+                trader.clone(),
+                rpc_client.clone(),
+                signer.clone(),
+                config,
+            )
+            .with_multi_wallet(multi_wallet.clone())
+            .with_jupiter(std::sync::Arc::new(crate::trading::JupiterClient::new()))
+            .with_pumpswap(std::sync::Arc::new(crate::trading::PumpSwapTrader::new(
+                config.trading.clone(),
+            )))
+            .with_jito(kill_switch_jito(config))
+            .with_local_api_lightning_attempts(profile.local_api_lightning_attempts);
+            let prices = std::sync::Arc::new(crate::position::auto_sell::ApiPrices::new(
+                price_sources.clone(),
+            ));
+            crate::position::AutoSeller::new(
+                config.auto_sell.clone(),
+                profile,
+                position_manager.clone(),
+                prices.clone(),
+                std::sync::Arc::new(seller),
+                rpc_client.clone(),
+                cooldowns.clone(),
+            )
+            .with_kill_switch(kill_switch_evaluator.clone())
+            .with_rug_recorder(std::sync::Arc::new(crate::filter::RugRecorder::new(
+                None,
+                &config.adaptive_filter.known_actors,
+            )))
+            .with_events(event_tx)
+            // Already priced from the pools; the watch switches sells to Jupiter
+            .with_migration_watch(prices)
+            .spawn();
+
+            // Closed or abandoned mints may be bought again once their cooldown expires
+            let monitor_bought_mints = bought_mints.clone();
+            let monitor_bought_mints_path = bought_mints_path.clone();
+            tokio::spawn(async move {
+                while let Some(event) = event_rx.recv().await {
+                    let mint = match event {
+                        crate::position::auto_sell::MonitorEvent::Closed { mint }
+                        | crate::position::auto_sell::MonitorEvent::Abandoned { mint } => mint,
+                        crate::position::auto_sell::MonitorEvent::Migrated { .. } => continue,
+                    };
+                    let _ = remove_bought_mint(
+                        &monitor_bought_mints,
+                        &monitor_bought_mints_path,
+                        &mint,
+                    )
+                    .await;
                 }
-            }
-        });
+            });
+        }
     }
 
+    // Snapshots of earlier cycles, for second-wind alerts
+    let mut scan_history = load_scan_history(config);
+
     // Main scan loop
     loop {
         println!("\n{:=<80}", "");
         println!("Scanning DexScreener for hot tokens...");
 
         let hot_tokens = dex_client.scan_hot_tokens(&scan_config).await?;
+        info!("DexScreener: {}", dex_client.stats());
+        let second_wind = match scan_history {
+            Some(ref mut history) => record_scan_cycle(history, &hot_tokens, &notifier),
+            None => Vec::new(),
+        };
 
         if hot_tokens.is_empty() {
             println!("No tokens matching criteria found.");
@@ -3181,49 +8829,50 @@ pub async fn hot_scan(
                 } else {
                     let mut bought = bought_mints.lock().await;
 
+                    // Second-wind tokens first ([scan_history] auto_buy), then the top 3
+                    let mut candidates: Vec<&crate::dexscreener::HotToken> = Vec::new();
+                    if config.scan_history.auto_buy {
+                        candidates.extend(
+                            second_wind
+                                .iter()
+                                .filter_map(|d| hot_tokens.iter().find(|t| t.mint == d.mint)),
+                        );
+                    }
                     for token in hot_tokens.iter().take(3) {
+                        if !candidates.iter().any(|c| c.mint == token.mint) {
+                            candidates.push(token);
+                        }
+                    }
+
+                    for token in candidates {
                         if bought.contains_key(&token.mint) {
                             info!("Skipping {} - already bought this session", token.symbol);
                             continue;
                         }
 
-                        // Check sold_mints cooldown (5 minutes after selling)
-                        {
-                            let sold = sold_mints.lock().await;
-                            if let Some(&sold_at) = sold.get(&token.mint) {
-                                let now = chrono::Utc::now().timestamp();
-                                let elapsed = now - sold_at;
-                                if elapsed < SOLD_MINTS_COOLDOWN_SECS {
-                                    let remaining = SOLD_MINTS_COOLDOWN_SECS - elapsed;
-                                    info!("Skipping {} - sold {}s ago, cooldown {}s remaining",
-                                          token.symbol, elapsed, remaining);
-                                    continue;
-                                }
-                            }
+                        // Check re-entry cooldown / failed and rugged blacklists
+                        if let Some(hit) = cooldowns.check(&token.mint).await {
+                            info!("Skipping {} - {}", token.symbol, hit);
+                            continue;
                         }
 
-                        // Check failed_mints cooldown (30 minutes after failed buy)
-                        {
-                            let failed = failed_mints.lock().await;
-                            if let Some(&failed_at) = failed.get(&token.mint) {
-                                let now = chrono::Utc::now().timestamp();
-                                let elapsed = now - failed_at;
-                                if elapsed < FAILED_MINTS_COOLDOWN_SECS {
-                                    let remaining_mins = (FAILED_MINTS_COOLDOWN_SECS - elapsed) / 60;
-                                    info!("Skipping {} - failed buy {}m ago, cooldown {}m remaining",
-                                          token.symbol, elapsed / 60, remaining_mins);
+                        // One trade per mint at a time within this process (not against
+                        // a `snipe start` running alongside); check for a position under the lock
+                        let _mint_lock =
+                            match position_manager.lock_mint(&token.mint, "hot-scan").await {
+                                Ok(lock) => lock,
+                                Err(e) => {
+                                    warn!("Skipping {}: {}", token.symbol, e);
                                     continue;
                                 }
-                            }
-                        }
-
-                        // Check if we already have a position
+                            };
                         if position_manager.get_position(&token.mint).await.is_some() {
                             info!("Skipping {} - already have position", token.symbol);
                             continue;
                         }
 
-                        // PRE-TRADE VALIDATION: Check position limits BEFORE trading
+                        // PRE-TRADE VALIDATION: Cheap exposure check before profiling the creator
+                        // (the slot itself is reserved right before the buy)
                         if let Err(e) = position_manager.can_open_position(buy_amount).await {
                             warn!(
                                 "Cannot open position for {}: {} - stopping buy loop",
@@ -3250,61 +8899,120 @@ pub async fn hot_scan(
                             }
                         }
 
-                        // SMART MONEY CHECK: Analyze token creator's past performance
-                        let final_buy_amount = if let (Some(ref helius), Some(ref profiler)) = (&helius_client, &wallet_profiler) {
-                            match helius.get_token_creator(&token.mint).await {
-                                Ok(creator) => {
-                                    match profiler.get_or_compute(&creator).await {
-                                        Ok(profile) => {
-                                            // Check if creator should be avoided
-                                            if profile.should_avoid() {
-                                                warn!(
-                                                    "Skipping {} - creator {} is {:?} (should avoid)",
-                                                    token.symbol, &creator[..8], profile.alpha_score.category
-                                                );
-                                                continue;
-                                            }
+                        let creator = match helius_client {
+                            Some(ref helius) => match helius.get_token_creator(&token.mint).await {
+                                Ok(creator) => creator,
+                                Err(e) => {
+                                    warn!("Could not get creator for {}: {}", token.symbol, e);
+                                    String::new()
+                                }
+                            },
+                            None => String::new(),
+                        };
 
-                                            // Adjust position size based on alpha score
-                                            let alpha_multiplier = if profile.is_elite() {
-                                                info!(
-                                                    "[{}] ELITE creator {} | Win: {:.0}% | R: {:.1}x | Alpha: {:.2} -> 1.5x size",
-                                                    token.symbol,
-                                                    &creator[..8],
-                                                    profile.win_rate * 100.0,
-                                                    profile.avg_r_multiple,
-                                                    profile.alpha_score.value
-                                                );
-                                                1.5 // 50% more for elite wallets
-                                            } else if profile.win_rate >= 0.5 {
-                                                info!(
-                                                    "[{}] Good creator {} | Win: {:.0}% | Alpha: {:.2} -> 1.0x size",
-                                                    token.symbol, &creator[..8], profile.win_rate * 100.0, profile.alpha_score.value
-                                                );
-                                                1.0 // Normal for decent wallets
-                                            } else {
-                                                info!(
-                                                    "[{}] Weak creator {} | Win: {:.0}% | Alpha: {:.2} -> 0.7x size",
-                                                    token.symbol, &creator[..8], profile.win_rate * 100.0, profile.alpha_score.value
-                                                );
-                                                0.7 // 30% less for weak wallets
-                                            };
+                        // ON-CHAIN VERIFICATION: authorities, Token-2022 extensions, holders,
+                        // creator history and the DexScreener order flow, scored together
+                        let scoring = match verifier {
+                            Some(ref verifier) => {
+                                let result = verifier.score(token, &creator).await;
+                                if !verifier.passes(&result) {
+                                    warn!(
+                                        "Skipping {} - {:?} below {:?}: {}",
+                                        token.symbol,
+                                        result.recommendation,
+                                        verifier.min_recommendation(),
+                                        result.summary
+                                    );
+                                    let action = if result.recommendation == Recommendation::Avoid {
+                                        DecisionAction::Avoid
+                                    } else {
+                                        DecisionAction::Observe
+                                    };
+                                    let detail = format!(
+                                        "hot scan: below {:?}",
+                                        verifier.min_recommendation()
+                                    );
+                                    decision_log.record(
+                                        &token.mint,
+                                        &token.symbol,
+                                        Some(&result),
+                                        action,
+                                        Some(detail),
+                                    );
+                                    continue;
+                                }
+                                Some(result)
+                            }
+                            None => None,
+                        };
 
-                                            buy_amount * alpha_multiplier
-                                        }
-                                        Err(e) => {
-                                            warn!("Could not profile creator for {}: {} - using default size", token.symbol, e);
-                                            buy_amount
-                                        }
+                        // SMART MONEY CHECK: the creator's track record scales the scored size
+                        let creator_multiplier = match wallet_profiler {
+                            Some(ref profiler) if !creator.is_empty() => {
+                                match profiler.get_or_compute(&creator).await {
+                                    Ok(profile) if profile.should_avoid() => {
+                                        let category = profile.alpha_score.category;
+                                        warn!(
+                                            "Skipping {} - creator {} is {:?} (should avoid)",
+                                            token.symbol,
+                                            &creator[..8],
+                                            category
+                                        );
+                                        let detail = format!("creator is {:?}", category);
+                                        decision_log.record(
+                                            &token.mint,
+                                            &token.symbol,
+                                            scoring.as_ref(),
+                                            DecisionAction::Avoid,
+                                            Some(detail),
+                                        );
+                                        continue;
+                                    }
+                                    Ok(profile) => {
+                                        let multiplier = profile.creator_size_multiplier();
+                                        info!(
+                                            "[{}] Creator {} | Win: {:.0}% | R: {:.1}x | Alpha: {:.2} -> {:.1}x size",
+                                            token.symbol,
+                                            &creator[..8],
+                                            profile.win_rate * 100.0,
+                                            profile.avg_r_multiple,
+                                            profile.alpha_score.value,
+                                            multiplier
+                                        );
+                                        multiplier
+                                    }
+                                    Err(e) => {
+                                        warn!("Could not profile creator for {}: {} - using default size", token.symbol, e);
+                                        1.0
                                     }
-                                }
-                                Err(e) => {
-                                    warn!("Could not get creator for {}: {} - using default size", token.symbol, e);
-                                    buy_amount
                                 }
                             }
+                            _ => 1.0,
+                        };
+                        let score_multiplier =
+                            scoring.as_ref().map_or(1.0, |r| r.position_size_multiplier);
+                        // Tokens already trading in their PumpSwap pool are post-migration entries
+                        let post_migration = token.dex_id == "pumpswap";
+                        let migration_multiplier = if post_migration {
+                            config.strategy.position_sizing.post_migration_multiplier
+                        } else {
+                            1.0
+                        };
+                        let final_buy_amount = buy_amount
+                            * score_multiplier
+                            * creator_multiplier
+                            * migration_multiplier;
+                        let entry_type = if post_migration {
+                            crate::position::manager::EntryType::PostMigration
                         } else {
-                            buy_amount // No profiler, use default
+                            scoring.as_ref().map_or(
+                                crate::position::manager::EntryType::Opportunity,
+                                |r| {
+                                    crate::position::manager::EntryType::from_recommendation(
+                                        r.recommendation,
+                                    )
+                                },
+                            )
                         };
 
                         if dry_run {
@@ -3312,6 +9020,14 @@ pub async fn hot_scan(
                                 "DRY-RUN: Would buy {:.4} SOL of {}",
                                 final_buy_amount, token.symbol
                             );
+                            let detail = format!("{:.4} SOL (hot scan)", final_buy_amount);
+                            decision_log.record(
+                                &token.mint,
+                                &token.symbol,
+                                scoring.as_ref(),
+                                DecisionAction::DryRun,
+                                Some(detail),
+                            );
                             bought.insert(token.mint.clone(), chrono::Utc::now().timestamp());
                             // Persist bought_mints to disk (with timestamps)
                             persist_bought_mints(&*bought_mints_path, &*bought);
@@ -3323,15 +9039,29 @@ pub async fn hot_scan(
                             let priority_fee = config.trading.priority_fee_lamports as f64 / 1e9;
 
                             // Select wallet for this trade (multi-wallet or single)
-                            let (trading_keypair, wallet_name) = if let Some(ref mw) = multi_wallet {
+                            let (trading_signer, wallet_name) = if let Some(ref mw) = multi_wallet {
                                 let selected = mw.select_wallet(&rpc_client);
                                 let name = selected.name.clone();
-                                let kp = std::sync::Arc::new(
+                                let kp: SharedSigner = std::sync::Arc::new(
                                     solana_sdk::signature::Keypair::from_bytes(&selected.keypair.to_bytes()).unwrap()
                                 );
                                 (kp, name)
                             } else {
-                                (keypair.clone(), "default".to_string())
+                                (signer.clone(), "default".to_string())
+                            };
+
+                            // Hold a position slot while the buy is in flight; Local API buys
+                            // spend from the selected wallet
+                            let spend_wallet = use_local_api.then(|| trading_signer.pubkey());
+                            let slot = match position_manager.try_reserve_slot_for(final_buy_amount, spend_wallet.as_ref()).await {
+                                Ok(slot) => slot,
+                                Err(e) => {
+                                    warn!(
+                                        "Cannot open position for {}: {} - stopping buy loop",
+                                        token.symbol, e
+                                    );
+                                    break;
+                                }
                             };
 
                             info!(
@@ -3346,14 +9076,42 @@ pub async fn hot_scan(
                                 wallet_name
                             );
 
-                            let buy_result = if use_local_api {
+                            // Native PumpSwap buys pay no PumpPortal fee
+                            let mut entry_fee_pct = platform_fee_pct;
+                            let native = if use_local_api && post_migration {
+                                match pumpswap
+                                    .buy(
+                                        &token.mint,
+                                        final_buy_amount,
+                                        config.trading.slippage_bps,
+                                        trading_signer.as_ref(),
+                                        &rpc_client,
+                                    )
+                                    .await
+                                {
+                                    Ok(sig) => Some(sig),
+                                    Err(e) => {
+                                        warn!(
+                                            "PumpSwap buy of {} failed: {} - falling back to PumpPortal",
+                                            token.symbol, e
+                                        );
+                                        None
+                                    }
+                                }
+                            } else {
+                                None
+                            };
+                            let buy_result = if let Some(sig) = native {
+                                entry_fee_pct = 0.0;
+                                Ok(sig)
+                            } else if use_local_api {
                                 trader
                                     .buy_local(
                                         &token.mint,
                                         final_buy_amount,
                                         slippage,
                                         priority_fee,
-                                        &trading_keypair,
+                                        &trading_signer,
                                         &rpc_client,
                                     )
                                     .await
@@ -3366,6 +9124,14 @@ pub async fn hot_scan(
                             match buy_result {
                                 Ok(sig) => {
                                     info!("BUY EXECUTED: {} - {}", token.symbol, sig);
+                                    let detail = format!("{:.4} SOL (hot scan)", final_buy_amount);
+                                    decision_log.record(
+                                        &token.mint,
+                                        &token.symbol,
+                                        scoring.as_ref(),
+                                        DecisionAction::Bought,
+                                        Some(detail),
+                                    );
                                     bought
                                         .insert(token.mint.clone(), chrono::Utc::now().timestamp());
                                     // Persist bought_mints to disk (with timestamps)
@@ -3382,35 +9148,66 @@ pub async fn hot_scan(
                                         entry_price: token.price_native,
                                         total_cost_sol: final_buy_amount,
                                         entry_time: chrono::Utc::now(),
-                                        entry_signature: sig,
-                                        entry_type:
-                                            crate::position::manager::EntryType::Opportunity,
+                                        entry_signature: sig.clone(),
+                                        entry_type,
                                         quick_profit_taken: false,
                                         second_profit_taken: false,
                                         peak_price: token.price_native,
+                                        trough_price: token.price_native,
                                         current_price: token.price_native,
                                         kill_switch_triggered: false,
                                         kill_switch_reason: None,
-                                        wallet_pubkey: trading_keypair.pubkey().to_string(),
+                                        wallet_pubkey: trading_signer.pubkey().to_string(),
+                                        fills: vec![],
+                                        scale_in: None,
+                                        exit_override: Default::default(),
+                                        partial_fill: false,
+                                        regime: None,
+                                        exit_style: None,
+                                        exit_levels_hit: vec![],
+                                        paper: false,
+                                        privileges: None,
+                                        exit_liquidity: None,
+                                        slippage: None,
+                                        strategy_profile: None,
+                                        holder_distribution: None,
+                                        fees: crate::position::TradeFees::estimate(
+                                            priority_fee,
+                                            0,
+                                            final_buy_amount,
+                                            entry_fee_pct,
+                                        ),
                                     };
 
-                                    if let Err(e) = position_manager.open_position(position).await {
+                                    let hot_score = format!("hot score {:.0}", token.score());
+                                    let score = Some(match scoring {
+                                        Some(ref result) => {
+                                            format!("{}, {}", score_summary(result), hot_score)
+                                        }
+                                        None => hot_score,
+                                    });
+                                    if let Err(e) = slot.commit_scored(position, score).await {
                                         error!("Failed to record position: {}", e);
                                         bought.remove(&token.mint);
                                         persist_bought_mints(&*bought_mints_path, &*bought);
                                         continue;
                                     }
+                                    spawn_confirmation(
+                                        rpc_client.clone(),
+                                        position_manager.clone(),
+                                        sig.clone(),
+                                    );
 
                                     // CRITICAL: Wait for tx confirmation, then verify tokens received
                                     tokio::time::sleep(std::time::Duration::from_secs(3)).await;
 
                                     // Determine which wallet to check based on API mode
                                     let check_wallet = if use_local_api {
-                                        trading_keypair.pubkey() // Use the selected trading wallet
+                                        trading_signer.pubkey() // Use the selected trading wallet
                                     } else {
                                         // For Lightning API, use the lightning wallet
                                         Pubkey::from_str(&config.pumpportal.lightning_wallet)
-                                            .unwrap_or(trading_keypair.pubkey())
+                                            .unwrap_or(trading_signer.pubkey())
                                     };
 
                                     let actual_balance_raw = query_token_balance(
@@ -3431,6 +9228,13 @@ pub async fn hot_scan(
                                         if let Err(e) = position_manager.abandon_position(&token.mint).await {
                                             error!("Failed to abandon failed position: {}", e);
                                         }
+                                        let _ = cooldowns
+                                            .mark(
+                                                &token.mint,
+                                                crate::position::CooldownKind::Failed,
+                                                Some("no tokens after buy".to_string()),
+                                            )
+                                            .await;
                                         bought.remove(&token.mint);
                                         persist_bought_mints(&*bought_mints_path, &*bought);
                                         continue; // Skip kill-switch setup for failed buy
@@ -3445,7 +9249,12 @@ pub async fn hot_scan(
                                         );
                                         // Update position with NORMALIZED balance (not raw units)
                                         if let Err(e) = position_manager
-                                            .update_token_amount(&token.mint, actual_balance)
+                                            .record_actual_fill(
+                                                &token.mint,
+                                                actual_balance,
+                                                estimated_tokens,
+                                                config.positions.min_fill_ratio,
+                                            )
                                             .await
                                         {
                                             warn!("Failed to update token amount: {}", e);
@@ -3453,21 +9262,9 @@ pub async fn hot_scan(
                                     }
 
                                     // === SET UP KILL-SWITCH MONITORING ===
-                                    // Fetch creator and top holders for this token
+                                    // Fetch top holders (the creator was looked up before the buy)
                                     if let Some(ref evaluator) = kill_switch_evaluator {
                                         if let Some(ref helius) = helius_client {
-                                            // Get token creator
-                                            let creator = match helius.get_token_creator(&token.mint).await {
-                                                Ok(c) => {
-                                                    info!("[{}] Creator for kill-switch: {}", token.symbol, &c[..8]);
-                                                    c
-                                                }
-                                                Err(e) => {
-                                                    warn!("[{}] Could not get creator: {} - using empty", token.symbol, e);
-                                                    String::new()
-                                                }
-                                            };
-
                                             // Get top holders (address, amount, percentage)
                                             let holders = match helius.get_token_holders(&token.mint, 10).await {
                                                 Ok(h) => {
@@ -3494,6 +9291,13 @@ pub async fn hot_scan(
                                 }
                                 Err(e) => {
                                     error!("BUY FAILED for {}: {}", token.symbol, e);
+                                    decision_log.record(
+                                        &token.mint,
+                                        &token.symbol,
+                                        scoring.as_ref(),
+                                        DecisionAction::BuyFailed,
+                                        Some(e.to_string()),
+                                    );
                                     continue;
                                 }
                             }
@@ -3544,8 +9348,8 @@ pub async fn hot_scan(
                     pos.entry_price,
                     pnl_pct,
                     hold_time,
-                    pos.entry_type.take_profit_pct(),
-                    pos.entry_type.stop_loss_pct()
+                    pos.effective_take_profit_pct(&position_manager.exit_thresholds()),
+                    pos.effective_stop_loss_pct(&position_manager.exit_thresholds())
                 );
             }
             println!("  Total Unrealized P&L: {:+.4} SOL", total_unrealized);
@@ -2,15 +2,37 @@
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 // Re-export adaptive filter config
 pub use crate::filter::adaptive::config::AdaptiveFilterConfig;
 // Re-export holder watcher and kill switch configs
 pub use crate::filter::holder_watcher::HolderWatcherConfig;
 pub use crate::filter::kill_switch::KillSwitchConfig;
+// Re-export bundled launch detection config
+pub use crate::filter::bundled_detection::BundledDetectionConfig;
+// Re-export Helius circuit breaker config
+pub use crate::filter::helius_budget::HeliusBudgetConfig;
+// Re-export trusted wallet discovery config
+pub use crate::filter::smart_money::WalletDiscoveryConfig;
 // Re-export strategy config
 pub use crate::strategy::engine::StrategyEngineConfig;
+// Re-export control channel config
+pub use crate::control::ControlConfig;
+// Re-export notification config
+pub use crate::notify::NotificationsConfig;
+// Re-export price API config
+pub use crate::price_source::PriceSourcesConfig;
+// Re-export scan history config
+pub use crate::scan_history::ScanHistoryConfig;
+// Re-export SOL/USD oracle config
+pub use crate::sol_price::SolPriceConfig;
+// Re-export pump program check config
+pub use crate::pump::drift::PumpProgramConfig;
+
+use crate::filter::kill_switch::KillSwitchUrgency;
+use crate::position::manager::EntryType;
 
 /// Main configuration structure
 #[derive(Debug, Clone, Deserialize)]
@@ -35,6 +57,208 @@ pub struct Config {
     pub smart_money: SmartMoneyConfig,
     #[serde(default)]
     pub early_detection: EarlyDetectionConfig,
+    #[serde(default)]
+    pub control: ControlConfig,
+    #[serde(default)]
+    pub positions: PositionsConfig,
+    #[serde(default)]
+    pub cooldowns: CooldownConfig,
+    #[serde(default)]
+    pub shadow: ShadowConfig,
+    #[serde(default)]
+    pub backtest: BacktestConfig,
+    #[serde(default)]
+    pub paper: PaperConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub shutdown: ShutdownConfig,
+    #[serde(default)]
+    pub price_sources: PriceSourcesConfig,
+    #[serde(default)]
+    pub scan_history: ScanHistoryConfig,
+    #[serde(default)]
+    pub pump_program: PumpProgramConfig,
+    #[serde(default)]
+    pub sol_price: SolPriceConfig,
+}
+
+/// Position bookkeeping configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct PositionsConfig {
+    /// Compare positions.json with on-chain balances when `snipe start` boots
+    #[serde(default = "default_true")]
+    pub reconcile_on_start: bool,
+    /// Adopt untracked on-chain holdings as Legacy positions
+    /// (false = only report them for manual action)
+    #[serde(default)]
+    pub adopt_orphans: bool,
+    /// Buys landing below this fraction of the intended token amount are
+    /// treated as partial fills and exit with Probe targets
+    #[serde(default = "default_min_fill_ratio")]
+    pub min_fill_ratio: f64,
+}
+
+fn default_min_fill_ratio() -> f64 {
+    0.8
+}
+
+impl Default for PositionsConfig {
+    fn default() -> Self {
+        Self {
+            reconcile_on_start: true,
+            adopt_orphans: false,
+            min_fill_ratio: default_min_fill_ratio(),
+        }
+    }
+}
+
+/// What `snipe start` does on Ctrl-C
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShutdownConfig {
+    /// Wait this long for buys and sells in flight to land and be recorded
+    #[serde(default = "default_shutdown_grace_secs")]
+    pub grace_period_secs: u64,
+    /// Sell every open position before exiting (also `snipe start --flatten`)
+    #[serde(default)]
+    pub flatten_positions: bool,
+    /// Give up waiting for flattening sells after this long
+    #[serde(default = "default_flatten_timeout_secs")]
+    pub flatten_timeout_secs: u64,
+}
+
+fn default_shutdown_grace_secs() -> u64 {
+    30
+}
+
+fn default_flatten_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            grace_period_secs: default_shutdown_grace_secs(),
+            flatten_positions: false,
+            flatten_timeout_secs: default_flatten_timeout_secs(),
+        }
+    }
+}
+
+/// Re-entry cooldowns and mint blacklists (0 = never expires)
+#[derive(Debug, Clone, Deserialize)]
+pub struct CooldownConfig {
+    /// Re-entry cooldown after a full exit
+    #[serde(default = "default_sold_ttl_secs")]
+    pub sold_ttl_secs: u64,
+    /// Blacklist after a buy that landed no tokens
+    #[serde(default = "default_failed_ttl_secs")]
+    pub failed_ttl_secs: u64,
+    /// Blacklist after a kill-switch exit
+    #[serde(default)]
+    pub rugged_ttl_secs: u64,
+}
+
+fn default_sold_ttl_secs() -> u64 {
+    300 // 5 minutes
+}
+
+fn default_failed_ttl_secs() -> u64 {
+    1800 // 30 minutes
+}
+
+impl Default for CooldownConfig {
+    fn default() -> Self {
+        Self {
+            sold_ttl_secs: default_sold_ttl_secs(),
+            failed_ttl_secs: default_failed_ttl_secs(),
+            rugged_ttl_secs: 0,
+        }
+    }
+}
+
+/// Watch-only shadow positions for Observe-rated tokens
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShadowConfig {
+    /// Track Observe tokens as if they had been Probe entries
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How long a shadow is priced before it is closed at the last price
+    #[serde(default = "default_shadow_window_secs")]
+    pub window_secs: u64,
+    /// Cap on concurrently tracked shadows
+    #[serde(default = "default_shadow_max_open")]
+    pub max_open: usize,
+}
+
+fn default_shadow_window_secs() -> u64 {
+    600 // 10 minutes
+}
+
+fn default_shadow_max_open() -> usize {
+    200
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: default_shadow_window_secs(),
+            max_open: default_shadow_max_open(),
+        }
+    }
+}
+
+/// Simulated execution for `snipe backtest`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BacktestConfig {
+    /// Submit-to-fill delay; orders fill at the curve as it stood then
+    #[serde(default = "default_sim_latency_ms")]
+    pub latency_ms: u64,
+    /// Adverse slippage applied to every fill, in %
+    #[serde(default = "default_sim_slippage_pct")]
+    pub slippage_pct: f64,
+    /// Model Local API fees instead of Lightning (`pumpportal.*_fee_pct`)
+    #[serde(default)]
+    pub local_api: bool,
+}
+
+fn default_sim_latency_ms() -> u64 {
+    500
+}
+
+fn default_sim_slippage_pct() -> f64 {
+    1.0
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: default_sim_latency_ms(),
+            slippage_pct: default_sim_slippage_pct(),
+            local_api: false,
+        }
+    }
+}
+
+/// Simulated fills for `snipe start --paper`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperConfig {
+    /// Submit-to-fill delay; orders fill at the live curve as it stands then
+    #[serde(default = "default_sim_latency_ms")]
+    pub latency_ms: u64,
+    /// Adverse slippage applied to every fill, in %
+    #[serde(default = "default_sim_slippage_pct")]
+    pub slippage_pct: f64,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: default_sim_latency_ms(),
+            slippage_pct: default_sim_slippage_pct(),
+        }
+    }
 }
 
 /// Smart money detection and kill-switch configuration
@@ -51,6 +275,18 @@ pub struct SmartMoneyConfig {
     /// Holder watcher configuration
     #[serde(default)]
     pub holder_watcher: HolderWatcherConfig,
+
+    /// Bundled launch detection (early buy window, sell-together exit)
+    #[serde(default)]
+    pub bundled_detection: BundledDetectionConfig,
+
+    /// Helius request accounting and circuit breaker
+    #[serde(default)]
+    pub helius: HeliusBudgetConfig,
+
+    /// Trusted wallet discovery from early launch buyers
+    #[serde(default)]
+    pub discovery: WalletDiscoveryConfig,
 }
 
 impl Default for SmartMoneyConfig {
@@ -59,6 +295,9 @@ impl Default for SmartMoneyConfig {
             enabled: true,
             kill_switches: KillSwitchConfig::default(),
             holder_watcher: HolderWatcherConfig::default(),
+            bundled_detection: BundledDetectionConfig::default(),
+            helius: HeliusBudgetConfig::default(),
+            discovery: WalletDiscoveryConfig::default(),
         }
     }
 }
@@ -125,6 +364,31 @@ pub struct PumpPortalConfig {
     /// Force Local API even if api_key is present (0.5% fee vs 1%)
     #[serde(default)]
     pub force_local_api: bool,
+    /// PumpPortal fee % on Lightning API trades
+    #[serde(default = "default_lightning_fee_pct")]
+    pub lightning_fee_pct: f64,
+    /// PumpPortal fee % on Local API trades
+    #[serde(default = "default_local_fee_pct")]
+    pub local_fee_pct: f64,
+}
+
+impl PumpPortalConfig {
+    /// Platform fee % charged on a trade in the given API mode
+    pub fn platform_fee_pct(&self, use_local_api: bool) -> f64 {
+        if use_local_api {
+            self.local_fee_pct
+        } else {
+            self.lightning_fee_pct
+        }
+    }
+}
+
+fn default_lightning_fee_pct() -> f64 {
+    1.0
+}
+
+fn default_local_fee_pct() -> f64 {
+    0.5
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -147,12 +411,51 @@ pub enum DropPolicy {
 pub struct TradingConfig {
     #[serde(default = "default_buy_amount_sol")]
     pub buy_amount_sol: f64,
+    /// Buy size in USD, converted at the `[sol_price]` price when the buy is
+    /// sized (`buy_amount_sol` is the fallback while the price is stale)
+    #[serde(default)]
+    pub buy_amount_usd: Option<f64>,
     #[serde(default = "default_slippage_bps")]
     pub slippage_bps: u32,
     #[serde(default = "default_priority_fee")]
     pub priority_fee_lamports: u64,
     #[serde(default)]
     pub simulate_before_send: bool,
+    /// Buy trade-signal tokens on volume alone, skipping the token filter,
+    /// adaptive filter thresholds and strategy engine (old behavior)
+    #[serde(default)]
+    pub fast_trade_entries: bool,
+    /// Settings for `snipe sell-all`
+    #[serde(default)]
+    pub panic: PanicConfig,
+}
+
+/// Emergency liquidation (`snipe sell-all`): get out at almost any price
+#[derive(Debug, Clone, Deserialize)]
+pub struct PanicConfig {
+    /// Slippage tolerance in basis points
+    #[serde(default = "default_panic_slippage_bps")]
+    pub slippage_bps: u32,
+    /// Priority fee per sell
+    #[serde(default = "default_panic_priority_fee")]
+    pub priority_fee_lamports: u64,
+}
+
+impl Default for PanicConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bps: default_panic_slippage_bps(),
+            priority_fee_lamports: default_panic_priority_fee(),
+        }
+    }
+}
+
+fn default_panic_slippage_bps() -> u32 {
+    5000
+}
+
+fn default_panic_priority_fee() -> u64 {
+    1_000_000
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -176,6 +479,40 @@ pub struct FilterConfig {
     /// Maximum bonding curve progress % (tokens too close to graduation may be risky)
     #[serde(default = "default_filter_max_bonding_curve")]
     pub max_bonding_curve_pct: f64,
+    /// Simulated buy-then-sell check before committing capital
+    #[serde(default)]
+    pub honeypot: HoneypotConfig,
+}
+
+/// Honeypot round-trip check: simulate a tiny buy and an immediate sell
+#[derive(Debug, Clone, Deserialize)]
+pub struct HoneypotConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Size of the simulated probe buy
+    #[serde(default = "default_honeypot_probe_sol")]
+    pub probe_sol: f64,
+    /// Reject when the round trip loses more than this % beyond protocol fees
+    #[serde(default = "default_max_round_trip_loss_pct")]
+    pub max_round_trip_loss_pct: f64,
+}
+
+impl Default for HoneypotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            probe_sol: default_honeypot_probe_sol(),
+            max_round_trip_loss_pct: default_max_round_trip_loss_pct(),
+        }
+    }
+}
+
+fn default_honeypot_probe_sol() -> f64 {
+    0.001
+}
+
+fn default_max_round_trip_loss_pct() -> f64 {
+    5.0
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -192,10 +529,113 @@ pub struct WalletTrackingConfig {
     /// Auto-buy when tracked wallet buys
     #[serde(default)]
     pub auto_copy_trade: bool,
+    /// Copy buy size as % of the tracked wallet's SOL (0 = trading.buy_amount_sol)
+    #[serde(default = "default_copy_size_pct")]
+    pub copy_size_pct: f64,
+    /// Cap on a single copy buy
+    #[serde(default = "default_max_copy_sol")]
+    pub max_copy_sol: f64,
+    /// Sell the same % of a copy position when the tracked wallet sells
+    #[serde(default = "default_true")]
+    pub mirror_sells: bool,
+    /// Per-wallet overrides, keyed by wallet address
+    #[serde(default)]
+    pub per_wallet: HashMap<String, TrackedWalletConfig>,
+    /// Closed copies per wallet behind a pruning suggestion: a wallet whose
+    /// last this many copies lost money in total is flagged (0 = never)
+    #[serde(default = "default_prune_sample")]
+    pub prune_sample: usize,
 }
 
 fn default_min_trade_sol() -> f64 { 0.5 }
 
+fn default_prune_sample() -> usize {
+    10
+}
+
+fn default_copy_size_pct() -> f64 {
+    10.0
+}
+
+fn default_max_copy_sol() -> f64 {
+    0.1
+}
+
+/// Copy-trading settings for one tracked wallet (unset fields use the
+/// `[wallet_tracking]` values)
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrackedWalletConfig {
+    /// Copy this wallet's trades
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub copy_size_pct: Option<f64>,
+    #[serde(default)]
+    pub max_copy_sol: Option<f64>,
+    #[serde(default)]
+    pub mirror_sells: Option<bool>,
+}
+
+/// Resolved copy-trading settings for a tracked wallet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CopySettings {
+    pub enabled: bool,
+    pub copy_size_pct: f64,
+    pub max_copy_sol: f64,
+    pub mirror_sells: bool,
+    /// Runtime multiplier from `snipe track add --size-mult`
+    pub size_mult: f64,
+}
+
+impl CopySettings {
+    /// SOL to spend copying a buy of `their_sol` (`fixed_sol` when sizing is off)
+    pub fn copy_amount_sol(&self, their_sol: f64, fixed_sol: f64) -> f64 {
+        let amount = if self.copy_size_pct > 0.0 {
+            their_sol * self.copy_size_pct / 100.0
+        } else {
+            fixed_sol
+        };
+        (amount * self.size_mult).min(self.max_copy_sol)
+    }
+}
+
+impl Default for WalletTrackingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            wallets: vec![],
+            priority_boost: true,
+            min_trade_sol: default_min_trade_sol(),
+            auto_copy_trade: false,
+            copy_size_pct: default_copy_size_pct(),
+            max_copy_sol: default_max_copy_sol(),
+            mirror_sells: true,
+            per_wallet: HashMap::new(),
+            prune_sample: default_prune_sample(),
+        }
+    }
+}
+
+impl WalletTrackingConfig {
+    /// Copy-trading settings for a wallet, with its overrides applied
+    pub fn copy_settings(&self, wallet: &str) -> CopySettings {
+        let overrides = self.per_wallet.get(wallet);
+        CopySettings {
+            enabled: self.auto_copy_trade && overrides.map_or(true, |o| o.enabled),
+            copy_size_pct: overrides
+                .and_then(|o| o.copy_size_pct)
+                .unwrap_or(self.copy_size_pct),
+            max_copy_sol: overrides
+                .and_then(|o| o.max_copy_sol)
+                .unwrap_or(self.max_copy_sol),
+            mirror_sells: overrides
+                .and_then(|o| o.mirror_sells)
+                .unwrap_or(self.mirror_sells),
+            size_mult: 1.0,
+        }
+    }
+}
+
 /// Early detection configuration for pre-pump signals
 #[derive(Debug, Clone, Deserialize)]
 pub struct EarlyDetectionConfig {
@@ -323,6 +763,479 @@ pub struct AutoSellConfig {
     /// Tight trailing stop % (used when P&L > 25%)
     #[serde(default = "default_trailing_tight")]
     pub trailing_stop_tight_pct: f64,
+
+    // === EXIT SLICING ===
+    /// Most sells a full exit is split into when the curve at entry was too
+    /// thin for one (1 = never split)
+    #[serde(default = "default_max_exit_slices")]
+    pub max_exit_slices: u32,
+    /// Wait between exit slices (ms)
+    #[serde(default = "default_exit_slice_interval_ms")]
+    pub exit_slice_interval_ms: u64,
+
+    // === PER-ENTRY-TYPE THRESHOLDS ===
+    /// Exit thresholds by entry type (`[auto_sell.entry_types.*]`)
+    #[serde(default)]
+    pub entry_types: EntryTypeThresholds,
+
+    // === POSITION MONITOR ===
+    /// Monitor behaviour per command (`[auto_sell.monitor.*]`)
+    #[serde(default)]
+    pub monitor: MonitorProfiles,
+
+    // === EARLY WEAKNESS ===
+    /// Exit on negative order flow before the stop loss (`[auto_sell.early_weakness]`)
+    #[serde(default)]
+    pub early_weakness: EarlyWeaknessConfig,
+
+    // === KILL-SWITCH EXECUTION ===
+    /// How kill-switch exits are sent, by urgency (`[auto_sell.kill_switch_execution]`)
+    #[serde(default)]
+    pub kill_switch_execution: KillSwitchExecutionConfig,
+}
+
+/// Exit thresholds for one entry type (% relative to entry price)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ExitThresholds {
+    /// Exit remaining position at this profit
+    pub take_profit_pct: f64,
+    /// Exit if price drops this % below entry
+    pub stop_loss_pct: f64,
+    /// Sell 50% at this profit (first layer)
+    pub quick_profit_pct: f64,
+    /// Exit after this many seconds regardless of P&L (0 = disabled)
+    #[serde(default)]
+    pub max_hold_secs: u64,
+}
+
+impl ExitThresholds {
+    const fn new(take_profit_pct: f64, stop_loss_pct: f64, quick_profit_pct: f64) -> Self {
+        Self {
+            take_profit_pct,
+            stop_loss_pct,
+            quick_profit_pct,
+            max_hold_secs: 0,
+        }
+    }
+
+    /// Max hold time, if enabled
+    pub fn max_hold(&self) -> Option<u64> {
+        (self.max_hold_secs > 0).then_some(self.max_hold_secs)
+    }
+
+    /// Reject thresholds that can never fire in a sensible order
+    ///
+    /// The stop loss exit level (entry - SL%) must sit below the take profit
+    /// level (entry + TP%), and the quick profit layer must come before take
+    /// profit.
+    fn validate(&self, name: &str) -> Result<()> {
+        if self.stop_loss_pct <= 0.0 || self.stop_loss_pct >= 100.0 {
+            anyhow::bail!("auto_sell.entry_types.{}: stop_loss_pct must be between 0 and 100", name);
+        }
+        if self.take_profit_pct <= 0.0 {
+            anyhow::bail!("auto_sell.entry_types.{}: take_profit_pct must be positive", name);
+        }
+        if -self.stop_loss_pct >= self.take_profit_pct {
+            anyhow::bail!(
+                "auto_sell.entry_types.{}: stop loss (-{}%) must be below take profit (+{}%)",
+                name,
+                self.stop_loss_pct,
+                self.take_profit_pct
+            );
+        }
+        if self.quick_profit_pct <= 0.0 || self.quick_profit_pct >= self.take_profit_pct {
+            anyhow::bail!(
+                "auto_sell.entry_types.{}: quick_profit_pct ({}) must be between 0 and take_profit_pct ({})",
+                name,
+                self.quick_profit_pct,
+                self.take_profit_pct
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Exit thresholds for every entry type
+///
+/// Defaults are tuned for ~2 minute holds; time-based exits are disabled
+/// because they were exiting right before price spikes.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EntryTypeThresholds {
+    /// High conviction entries
+    #[serde(default = "default_strong_buy_thresholds")]
+    pub strong_buy: ExitThresholds,
+    /// Standard opportunities
+    #[serde(default = "default_opportunity_thresholds")]
+    pub opportunity: ExitThresholds,
+    /// Probe / learning positions and partial fills
+    #[serde(default = "default_probe_thresholds")]
+    pub probe: ExitThresholds,
+    /// Positions opened before entry type tracking (and adopted orphans)
+    #[serde(default = "default_legacy_thresholds")]
+    pub legacy: ExitThresholds,
+    /// Copies of tracked wallet buys
+    #[serde(default = "default_copy_trade_thresholds")]
+    pub copy_trade: ExitThresholds,
+    /// Entries in the PumpSwap pool after migration
+    #[serde(default = "default_post_migration_thresholds")]
+    pub post_migration: ExitThresholds,
+}
+
+fn default_strong_buy_thresholds() -> ExitThresholds {
+    ExitThresholds::new(15.0, 15.0, 8.0)
+}
+
+fn default_opportunity_thresholds() -> ExitThresholds {
+    ExitThresholds::new(10.0, 15.0, 5.0)
+}
+
+fn default_probe_thresholds() -> ExitThresholds {
+    ExitThresholds::new(8.0, 12.0, 4.0) // Quick scalp
+}
+
+fn default_legacy_thresholds() -> ExitThresholds {
+    ExitThresholds::new(10.0, 15.0, 5.0)
+}
+
+fn default_copy_trade_thresholds() -> ExitThresholds {
+    ExitThresholds::new(10.0, 12.0, 5.0)
+}
+
+fn default_post_migration_thresholds() -> ExitThresholds {
+    ExitThresholds::new(20.0, 10.0, 8.0) // Deeper pool, slower moves
+}
+
+impl Default for EntryTypeThresholds {
+    fn default() -> Self {
+        Self {
+            strong_buy: default_strong_buy_thresholds(),
+            opportunity: default_opportunity_thresholds(),
+            probe: default_probe_thresholds(),
+            legacy: default_legacy_thresholds(),
+            copy_trade: default_copy_trade_thresholds(),
+            post_migration: default_post_migration_thresholds(),
+        }
+    }
+}
+
+impl EntryTypeThresholds {
+    /// Thresholds for an entry type
+    pub fn get(&self, entry_type: EntryType) -> &ExitThresholds {
+        match entry_type {
+            EntryType::StrongBuy => &self.strong_buy,
+            EntryType::Opportunity => &self.opportunity,
+            EntryType::Probe => &self.probe,
+            EntryType::Legacy => &self.legacy,
+            EntryType::CopyTrade => &self.copy_trade,
+            EntryType::PostMigration => &self.post_migration,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.strong_buy.validate("strong_buy")?;
+        self.opportunity.validate("opportunity")?;
+        self.probe.validate("probe")?;
+        self.legacy.validate("legacy")?;
+        self.copy_trade.validate("copy_trade")?;
+        self.post_migration.validate("post_migration")
+    }
+}
+
+/// How the position monitor runs for one command
+///
+/// `snipe start` prices positions from the trade feed and `snipe hot-scan`
+/// polls DexScreener; everything else that differs between the two is set
+/// here. A profile table replaces the default profile as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct MonitorConfig {
+    /// Delay between monitor passes (0 = auto_sell.price_poll_interval_ms)
+    #[serde(default)]
+    pub poll_interval_ms: u64,
+    /// Don't evaluate exits for positions younger than this
+    pub confirm_wait_secs: u64,
+    /// Require an on-chain token balance before evaluating exits
+    pub verify_balance: bool,
+    /// With verify_balance: drop positions still holding no tokens after this long
+    #[serde(default = "default_unconfirmed_timeout_secs")]
+    pub unconfirmed_timeout_secs: u64,
+    /// Check kill-switches (position flag and evaluator) on every pass
+    pub kill_switch: bool,
+    /// Tighten the trailing stop as profit grows (also needs
+    /// auto_sell.dynamic_trailing_enabled); otherwise a fixed 5% trail
+    pub dynamic_trailing: bool,
+    /// Sell 25% at auto_sell.second_profit_pct after the quick profit layer
+    pub second_profit_layer: bool,
+    /// Apply the auto_sell.no_movement_* exit
+    pub no_movement_exit: bool,
+    /// Take sell proceeds from the wallet balance change instead of price x amount
+    pub measure_proceeds: bool,
+    /// Record a position whose sell kept failing as a full loss in trades.json
+    /// (otherwise it is dropped without P&L)
+    pub record_abandoned: bool,
+    /// Log price / P&L / peak for each position every 15s of hold time
+    pub status_log: bool,
+    /// Local API mode: Lightning attempts before a sell is signed locally
+    /// (Lightning mode always tries three)
+    #[serde(default)]
+    pub local_api_lightning_attempts: u32,
+}
+
+fn default_unconfirmed_timeout_secs() -> u64 {
+    30
+}
+
+impl MonitorConfig {
+    /// Effective delay between passes
+    pub fn poll_interval_ms(&self, auto_sell: &AutoSellConfig) -> u64 {
+        if self.poll_interval_ms > 0 {
+            self.poll_interval_ms
+        } else {
+            auto_sell.price_poll_interval_ms
+        }
+    }
+}
+
+/// Position monitor profile for each command that runs one
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MonitorProfiles {
+    /// `snipe start` (prices from the PumpPortal trade feed)
+    #[serde(default = "default_start_monitor")]
+    pub start: MonitorConfig,
+    /// `snipe hot-scan` (prices from DexScreener)
+    #[serde(default = "default_hot_scan_monitor")]
+    pub hot_scan: MonitorConfig,
+}
+
+fn default_start_monitor() -> MonitorConfig {
+    MonitorConfig {
+        poll_interval_ms: 2000,
+        confirm_wait_secs: 10,
+        verify_balance: false,
+        unconfirmed_timeout_secs: default_unconfirmed_timeout_secs(),
+        kill_switch: false, // start exits on kill-switch sells from the trade feed
+        dynamic_trailing: false,
+        second_profit_layer: false,
+        no_movement_exit: false,
+        measure_proceeds: false,
+        record_abandoned: true,
+        status_log: false,
+        local_api_lightning_attempts: 0,
+    }
+}
+
+fn default_hot_scan_monitor() -> MonitorConfig {
+    MonitorConfig {
+        poll_interval_ms: 0,
+        confirm_wait_secs: 5,
+        verify_balance: true,
+        unconfirmed_timeout_secs: default_unconfirmed_timeout_secs(),
+        kill_switch: true,
+        dynamic_trailing: true,
+        second_profit_layer: true,
+        no_movement_exit: true,
+        measure_proceeds: true,
+        record_abandoned: false,
+        status_log: true,
+        local_api_lightning_attempts: 0,
+    }
+}
+
+impl Default for MonitorProfiles {
+    fn default() -> Self {
+        Self {
+            start: default_start_monitor(),
+            hot_scan: default_hot_scan_monitor(),
+        }
+    }
+}
+
+/// Early-weakness exit: sell when order flow turns strongly negative while
+/// the position is between break-even and its stop loss
+///
+/// Fires only when every condition holds over the short window (and the net
+/// flow condition over the long one). Disabled by default until it has been
+/// back-tested against recorded sessions.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EarlyWeaknessConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Short flow window (seconds)
+    #[serde(default = "default_weakness_short_window")]
+    pub short_window_secs: u64,
+    /// Long flow window (seconds)
+    #[serde(default = "default_weakness_long_window")]
+    pub long_window_secs: u64,
+    /// Never fire before this hold time (the monitor's confirm_wait_secs
+    /// applies too, whichever is longer)
+    #[serde(default = "default_weakness_grace")]
+    pub grace_secs: u64,
+    /// Minimum trades in the short window
+    #[serde(default = "default_weakness_min_trades")]
+    pub min_trades: usize,
+    /// Minimum sell SOL in the short window
+    #[serde(default = "default_weakness_min_sell_sol")]
+    pub min_sell_sol: f64,
+    /// Short-window net flow at or below this ((buys - sells) / volume, -1..1)
+    #[serde(default = "default_weakness_short_net_flow")]
+    pub short_net_flow: f64,
+    /// Long-window net flow at or below this
+    #[serde(default = "default_weakness_long_net_flow")]
+    pub long_net_flow: f64,
+    /// Short-window price change at or below this %
+    #[serde(default = "default_weakness_max_price_change")]
+    pub max_price_change_pct: f64,
+}
+
+fn default_weakness_short_window() -> u64 {
+    15
+}
+fn default_weakness_long_window() -> u64 {
+    60
+}
+fn default_weakness_grace() -> u64 {
+    20
+}
+fn default_weakness_min_trades() -> usize {
+    5
+}
+fn default_weakness_min_sell_sol() -> f64 {
+    0.5
+}
+fn default_weakness_short_net_flow() -> f64 {
+    -0.6
+}
+fn default_weakness_long_net_flow() -> f64 {
+    -0.2
+}
+fn default_weakness_max_price_change() -> f64 {
+    -2.0
+}
+
+impl Default for EarlyWeaknessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            short_window_secs: default_weakness_short_window(),
+            long_window_secs: default_weakness_long_window(),
+            grace_secs: default_weakness_grace(),
+            min_trades: default_weakness_min_trades(),
+            min_sell_sol: default_weakness_min_sell_sol(),
+            short_net_flow: default_weakness_short_net_flow(),
+            long_net_flow: default_weakness_long_net_flow(),
+            max_price_change_pct: default_weakness_max_price_change(),
+        }
+    }
+}
+
+impl EarlyWeaknessConfig {
+    fn validate(&self) -> Result<()> {
+        if self.short_window_secs == 0 || self.short_window_secs >= self.long_window_secs {
+            anyhow::bail!(
+                "auto_sell.early_weakness: short_window_secs ({}) must be between 0 and long_window_secs ({})",
+                self.short_window_secs,
+                self.long_window_secs
+            );
+        }
+        for (name, flow) in [
+            ("short_net_flow", self.short_net_flow),
+            ("long_net_flow", self.long_net_flow),
+        ] {
+            if !(-1.0..0.0).contains(&flow) {
+                anyhow::bail!(
+                    "auto_sell.early_weakness: {} must be negative and at least -1",
+                    name
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How one kill-switch urgency tier sends its sell
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KillSwitchExecutionPolicy {
+    /// Lightning API attempts before signing locally (0 = sign locally first,
+    /// unset = the monitor's usual ladder; ignored in Local API mode)
+    #[serde(default)]
+    pub lightning_attempts: Option<u32>,
+    /// Use the `[trading.panic]` slippage and priority fee
+    #[serde(default)]
+    pub panic_pricing: bool,
+    /// Simulate locally signed sells before sending (the default skips
+    /// preflight like every other sell)
+    #[serde(default)]
+    pub preflight: bool,
+    /// Send locally signed sells as a Jito bundle tipped `jito.max_tip_lamports`
+    #[serde(default)]
+    pub jito_bundle: bool,
+}
+
+/// Kill-switch execution policy per urgency
+///
+/// Critical alerts (liquidity draining) sign locally at panic pricing right
+/// away, High/Immediate ones give Lightning a single attempt, the rest use
+/// the monitor's usual ladder.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct KillSwitchExecutionConfig {
+    #[serde(default = "default_critical_execution")]
+    pub critical: KillSwitchExecutionPolicy,
+    #[serde(default = "default_high_execution")]
+    pub high: KillSwitchExecutionPolicy,
+    #[serde(default = "default_normal_execution")]
+    pub normal: KillSwitchExecutionPolicy,
+}
+
+fn default_critical_execution() -> KillSwitchExecutionPolicy {
+    KillSwitchExecutionPolicy {
+        lightning_attempts: Some(0),
+        panic_pricing: true,
+        preflight: false,
+        jito_bundle: false,
+    }
+}
+fn default_high_execution() -> KillSwitchExecutionPolicy {
+    KillSwitchExecutionPolicy {
+        lightning_attempts: Some(1),
+        panic_pricing: false,
+        preflight: false,
+        jito_bundle: false,
+    }
+}
+fn default_normal_execution() -> KillSwitchExecutionPolicy {
+    KillSwitchExecutionPolicy {
+        lightning_attempts: None,
+        panic_pricing: false,
+        preflight: false,
+        jito_bundle: false,
+    }
+}
+
+impl Default for KillSwitchExecutionConfig {
+    fn default() -> Self {
+        Self {
+            critical: default_critical_execution(),
+            high: default_high_execution(),
+            normal: default_normal_execution(),
+        }
+    }
+}
+
+impl KillSwitchExecutionConfig {
+    /// Policy (and its table name) for an alert's urgency
+    pub fn policy(&self, urgency: KillSwitchUrgency) -> (&'static str, &KillSwitchExecutionPolicy) {
+        match urgency {
+            KillSwitchUrgency::Critical => ("critical", &self.critical),
+            KillSwitchUrgency::Immediate | KillSwitchUrgency::High => ("high", &self.high),
+            KillSwitchUrgency::Medium => ("normal", &self.normal),
+        }
+    }
+
+    /// Whether any tier sends through Jito
+    pub fn uses_jito(&self) -> bool {
+        [&self.critical, &self.high, &self.normal]
+            .iter()
+            .any(|policy| policy.jito_bundle)
+    }
 }
 
 fn default_quick_profit_pct() -> f64 { 4.0 }
@@ -332,6 +1245,8 @@ fn default_no_movement_secs() -> u64 { 120 }
 fn default_trailing_base() -> f64 { 5.0 }
 fn default_trailing_medium() -> f64 { 4.0 }
 fn default_trailing_tight() -> f64 { 3.0 }
+fn default_max_exit_slices() -> u32 { 4 }
+fn default_exit_slice_interval_ms() -> u64 { 2000 }
 
 fn default_trailing_activation() -> f64 {
     10.0
@@ -346,10 +1261,30 @@ pub struct SafetyConfig {
     pub require_sell_confirmation: bool,
     #[serde(default = "default_max_position_sol")]
     pub max_position_sol: f64,
+    /// `max_position_sol` in USD (the SOL value is the stale-price fallback)
+    #[serde(default)]
+    pub max_position_usd: Option<f64>,
     #[serde(default = "default_daily_loss_limit")]
     pub daily_loss_limit_sol: f64,
+    /// `daily_loss_limit_sol` in USD (the SOL value is the stale-price fallback)
+    #[serde(default)]
+    pub daily_loss_limit_usd: Option<f64>,
     #[serde(default = "default_keypair_balance_warning")]
     pub keypair_balance_warning_sol: f64,
+    /// Maximum simultaneous open positions across all entry paths
+    #[serde(default = "default_max_concurrent_positions")]
+    pub max_concurrent_positions: usize,
+    /// Positions opened per day (None = no cap)
+    #[serde(default)]
+    pub max_daily_entries: Option<u32>,
+    /// Buy and sell transactions per day; at the cap new buys stop, sells
+    /// continue (None = no cap)
+    #[serde(default)]
+    pub max_daily_trades: Option<u32>,
+    /// Where the day for the limits above starts; copied from
+    /// `strategy.portfolio_risk.daily_reset_utc_offset_hours` on load
+    #[serde(skip)]
+    pub daily_reset_utc_offset_hours: i32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -366,6 +1301,11 @@ pub struct WalletConfig {
     #[serde(default = "default_credentials_dir")]
     pub credentials_dir: String,
 
+    /// Sign with the `hot_wallet` keypair from the credentials store instead
+    /// of `credentials/hot-trading/keypair.json` (`KEYPAIR_PATH` overrides both)
+    #[serde(default)]
+    pub use_keystore: bool,
+
     /// Trading wallets - list of keypair paths for multi-wallet trading
     /// If empty, falls back to KEYPAIR_PATH env var or default hot-trading wallet
     #[serde(default)]
@@ -375,107 +1315,383 @@ pub struct WalletConfig {
     #[serde(default = "default_wallet_strategy")]
     pub selection_strategy: String,
 
-    /// Safety limits for wallet operations
+    /// Safety limits for wallet operations
+    #[serde(default)]
+    pub safety: WalletSafetyConfig,
+
+    /// Automatic extraction settings
+    #[serde(default)]
+    pub extraction: ExtractionConfig,
+
+    /// AI advisor proposals (snipe start)
+    #[serde(default)]
+    pub advisor: WalletAdvisorConfig,
+
+    /// Who signs for the hot wallet (local keypair, remote service, subprocess)
+    #[serde(default)]
+    pub signer: SignerConfig,
+
+    /// Hot wallet balance watcher (snipe start)
+    #[serde(default)]
+    pub balance_watch: BalanceWatchConfig,
+}
+
+impl Default for WalletConfig {
+    fn default() -> Self {
+        Self {
+            hot_wallet: default_hot_wallet(),
+            vault_wallet: default_vault_wallet(),
+            credentials_dir: default_credentials_dir(),
+            use_keystore: false,
+            trading_wallets: vec![],
+            selection_strategy: default_wallet_strategy(),
+            safety: WalletSafetyConfig::default(),
+            extraction: ExtractionConfig::default(),
+            advisor: WalletAdvisorConfig::default(),
+            signer: SignerConfig::default(),
+            balance_watch: BalanceWatchConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WalletSafetyConfig {
+    /// Minimum SOL to keep in hot wallet
+    #[serde(default = "default_min_hot_balance")]
+    pub min_hot_balance_sol: f64,
+
+    /// Maximum single transfer to vault
+    #[serde(default = "default_max_single_transfer")]
+    pub max_single_transfer_sol: f64,
+
+    /// Maximum total daily extraction
+    #[serde(default = "default_max_daily_extraction")]
+    pub max_daily_extraction_sol: f64,
+
+    /// Require confirmation above this amount
+    #[serde(default = "default_confirm_above")]
+    pub confirm_above_sol: f64,
+
+    /// Emergency threshold - pause trading below this
+    #[serde(default = "default_emergency_threshold")]
+    pub emergency_threshold_sol: f64,
+
+    /// Lock vault address: hot wallet transfers go only to the configured vault
+    #[serde(default = "default_true")]
+    pub vault_address_locked: bool,
+
+    /// With the vault address locked, still allow user transfers to external
+    /// addresses
+    #[serde(default)]
+    pub allow_external_transfers: bool,
+
+    /// Maximum AI can auto-execute
+    #[serde(default = "default_ai_max_auto_transfer")]
+    pub ai_max_auto_transfer_sol: f64,
+
+    /// Stage transfers above this for a second confirmation (0 = off)
+    #[serde(default)]
+    pub dual_confirm_above_sol: f64,
+
+    /// Minutes a staged transfer waits for its confirmation
+    #[serde(default = "default_dual_confirm_window")]
+    pub dual_confirm_window_mins: u64,
+}
+
+impl Default for WalletSafetyConfig {
+    fn default() -> Self {
+        Self {
+            min_hot_balance_sol: default_min_hot_balance(),
+            max_single_transfer_sol: default_max_single_transfer(),
+            max_daily_extraction_sol: default_max_daily_extraction(),
+            confirm_above_sol: default_confirm_above(),
+            emergency_threshold_sol: default_emergency_threshold(),
+            vault_address_locked: true,
+            allow_external_transfers: false,
+            ai_max_auto_transfer_sol: default_ai_max_auto_transfer(),
+            dual_confirm_above_sol: 0.0,
+            dual_confirm_window_mins: default_dual_confirm_window(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtractionConfig {
+    /// Enable automatic extraction
+    #[serde(default = "default_true")]
+    pub auto_extract: bool,
+
+    /// Extract when profit exceeds this threshold
+    #[serde(default = "default_profit_threshold")]
+    pub profit_threshold_sol: f64,
+
+    /// Percentage of profits to extract
+    #[serde(default = "default_profit_percentage")]
+    pub profit_percentage: f64,
+
+    /// Extract excess when balance exceeds ceiling
+    #[serde(default = "default_balance_ceiling")]
+    pub balance_ceiling_sol: f64,
+
+    /// Minimum time between extractions (seconds)
+    #[serde(default = "default_min_extraction_interval")]
+    pub min_extraction_interval_secs: u64,
+
+    /// Seconds between rule checks in `snipe start`
+    #[serde(default = "default_extraction_check_interval")]
+    pub check_interval_secs: u64,
+
+    /// Random extra delay per check (seconds)
+    #[serde(default = "default_extraction_jitter")]
+    pub jitter_secs: u64,
+
+    /// SOL kept above `wallet.safety.min_hot_balance_sol` by the excess and
+    /// daily rules
+    #[serde(default = "default_hot_buffer")]
+    pub hot_buffer_sol: f64,
+
+    /// Extract once the hot wallet is this far above min balance + buffer
+    /// (0 = off)
+    #[serde(default)]
+    pub excess_trigger_sol: f64,
+
+    /// UTC hour of a daily extraction down to min balance + buffer (unset = off)
+    #[serde(default)]
+    pub daily_hour_utc: Option<u32>,
+
+    /// Skip extraction while more SOL than this is in open positions
+    /// (0 = no limit)
+    #[serde(default = "default_max_open_exposure")]
+    pub max_open_exposure_sol: f64,
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            auto_extract: true,
+            profit_threshold_sol: default_profit_threshold(),
+            profit_percentage: default_profit_percentage(),
+            balance_ceiling_sol: default_balance_ceiling(),
+            min_extraction_interval_secs: default_min_extraction_interval(),
+            check_interval_secs: default_extraction_check_interval(),
+            jitter_secs: default_extraction_jitter(),
+            hot_buffer_sol: default_hot_buffer(),
+            excess_trigger_sol: 0.0,
+            daily_hour_utc: None,
+            max_open_exposure_sol: default_max_open_exposure(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WalletAdvisorConfig {
+    /// Run the AI advisor in `snipe start` (moves real SOL; off by default)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Seconds between analysis cycles
+    #[serde(default = "default_advisor_interval")]
+    pub interval_secs: u64,
+
+    /// Undecided proposals expire after this many seconds
+    #[serde(default = "default_proposal_ttl")]
+    pub proposal_ttl_secs: u64,
+
+    /// Propose extracting half the excess above this hot balance
+    #[serde(default = "default_balance_extraction_trigger")]
+    pub balance_extraction_trigger_sol: f64,
+
+    /// Propose extracting half the realized profit once it reaches this
+    #[serde(default = "default_profit_extraction_trigger")]
+    pub profit_extraction_trigger_sol: f64,
+
+    /// Propose pausing below this hot balance
+    #[serde(default = "default_advisor_low_balance")]
+    pub low_balance_sol: f64,
+
+    /// Losing trades in a day before proposing smaller buys
+    #[serde(default = "default_advisor_losing_trades")]
+    pub losing_trades_threshold: u32,
+
+    /// Floor for the buy-size multiplier sizing proposals move
+    #[serde(default = "default_min_buy_size_multiplier")]
+    pub min_buy_size_multiplier: f64,
+
+    /// Minimum confidence to auto-execute an extraction within
+    /// safety.ai_max_auto_transfer_sol
+    #[serde(default = "default_min_confidence_auto")]
+    pub min_confidence_auto: f64,
+}
+
+impl Default for WalletAdvisorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_advisor_interval(),
+            proposal_ttl_secs: default_proposal_ttl(),
+            balance_extraction_trigger_sol: default_balance_extraction_trigger(),
+            profit_extraction_trigger_sol: default_profit_extraction_trigger(),
+            low_balance_sol: default_advisor_low_balance(),
+            losing_trades_threshold: default_advisor_losing_trades(),
+            min_buy_size_multiplier: default_min_buy_size_multiplier(),
+            min_confidence_auto: default_min_confidence_auto(),
+        }
+    }
+}
+
+fn default_advisor_interval() -> u64 {
+    300
+}
+
+fn default_proposal_ttl() -> u64 {
+    3600
+}
+
+fn default_balance_extraction_trigger() -> f64 {
+    1.5
+}
+
+fn default_profit_extraction_trigger() -> f64 {
+    0.3
+}
+
+fn default_advisor_low_balance() -> f64 {
+    0.1
+}
+
+fn default_advisor_losing_trades() -> u32 {
+    5
+}
+
+fn default_min_buy_size_multiplier() -> f64 {
+    0.25
+}
+
+fn default_min_confidence_auto() -> f64 {
+    0.8
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignerConfig {
+    /// "local" (hot wallet keypair on this box), "remote" (HTTPS signing
+    /// service) or "subprocess" (sign-only child process)
+    #[serde(default = "default_signer_backend")]
+    pub backend: String,
+
+    /// Hot wallet address the remote/subprocess signer signs for; every
+    /// signature is verified against it
+    #[serde(default)]
+    pub pubkey: Option<String>,
+
+    /// Signing service base URL (remote); signatures come from POST {url}/sign
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Env var holding the shared HMAC-SHA256 secret (remote)
+    #[serde(default)]
+    pub hmac_secret_env: Option<String>,
+
+    /// Client certificate and PKCS#8 key (PEM) for mutual TLS (remote)
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+
+    /// Extra CA certificate (PEM) to trust for the signing service
     #[serde(default)]
-    pub safety: WalletSafetyConfig,
+    pub ca_cert_path: Option<String>,
 
-    /// Automatic extraction settings
+    /// Sign-only process and its arguments (subprocess)
     #[serde(default)]
-    pub extraction: ExtractionConfig,
+    pub command: Vec<String>,
+
+    /// Give up on a signature after this long
+    #[serde(default = "default_signer_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Log signatures slower than this at warn level (0 = never)
+    #[serde(default = "default_signer_latency_budget_ms")]
+    pub latency_budget_ms: u64,
 }
 
-impl Default for WalletConfig {
+impl Default for SignerConfig {
     fn default() -> Self {
         Self {
-            hot_wallet: default_hot_wallet(),
-            vault_wallet: default_vault_wallet(),
-            credentials_dir: default_credentials_dir(),
-            trading_wallets: vec![],
-            selection_strategy: default_wallet_strategy(),
-            safety: WalletSafetyConfig::default(),
-            extraction: ExtractionConfig::default(),
+            backend: default_signer_backend(),
+            pubkey: None,
+            url: None,
+            hmac_secret_env: None,
+            client_cert_path: None,
+            client_key_path: None,
+            ca_cert_path: None,
+            command: vec![],
+            timeout_ms: default_signer_timeout_ms(),
+            latency_budget_ms: default_signer_latency_budget_ms(),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct WalletSafetyConfig {
-    /// Minimum SOL to keep in hot wallet
-    #[serde(default = "default_min_hot_balance")]
-    pub min_hot_balance_sol: f64,
+fn default_signer_backend() -> String {
+    "local".to_string()
+}
 
-    /// Maximum single transfer to vault
-    #[serde(default = "default_max_single_transfer")]
-    pub max_single_transfer_sol: f64,
+fn default_signer_timeout_ms() -> u64 {
+    1000
+}
 
-    /// Maximum total daily extraction
-    #[serde(default = "default_max_daily_extraction")]
-    pub max_daily_extraction_sol: f64,
+fn default_signer_latency_budget_ms() -> u64 {
+    30
+}
 
-    /// Require confirmation above this amount
-    #[serde(default = "default_confirm_above")]
-    pub confirm_above_sol: f64,
+#[derive(Debug, Clone, Deserialize)]
+pub struct BalanceWatchConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
 
-    /// Emergency threshold - pause trading below this
-    #[serde(default = "default_emergency_threshold")]
-    pub emergency_threshold_sol: f64,
+    /// How often the hot wallet balance is polled
+    #[serde(default = "default_balance_poll_interval")]
+    pub poll_interval_secs: u64,
 
-    /// Lock vault address (prevent changes)
-    #[serde(default = "default_true")]
-    pub vault_address_locked: bool,
+    /// New entries are blocked below this balance (fees can't be covered)
+    #[serde(default = "default_entry_floor")]
+    pub entry_floor_sol: f64,
 
-    /// Maximum AI can auto-execute
-    #[serde(default = "default_ai_max_auto_transfer")]
-    pub ai_max_auto_transfer_sol: f64,
+    /// Warn below this balance
+    #[serde(default = "default_balance_warn_below")]
+    pub warn_below_sol: f64,
+
+    /// Alert when balance plus open position cost falls more than this
+    /// within an hour (possible key compromise; 0 = off)
+    #[serde(default = "default_max_drain_per_hour")]
+    pub max_drain_sol_per_hour: f64,
 }
 
-impl Default for WalletSafetyConfig {
+impl Default for BalanceWatchConfig {
     fn default() -> Self {
         Self {
-            min_hot_balance_sol: default_min_hot_balance(),
-            max_single_transfer_sol: default_max_single_transfer(),
-            max_daily_extraction_sol: default_max_daily_extraction(),
-            confirm_above_sol: default_confirm_above(),
-            emergency_threshold_sol: default_emergency_threshold(),
-            vault_address_locked: true,
-            ai_max_auto_transfer_sol: default_ai_max_auto_transfer(),
+            enabled: true,
+            poll_interval_secs: default_balance_poll_interval(),
+            entry_floor_sol: default_entry_floor(),
+            warn_below_sol: default_balance_warn_below(),
+            max_drain_sol_per_hour: default_max_drain_per_hour(),
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct ExtractionConfig {
-    /// Enable automatic extraction
-    #[serde(default = "default_true")]
-    pub auto_extract: bool,
-
-    /// Extract when profit exceeds this threshold
-    #[serde(default = "default_profit_threshold")]
-    pub profit_threshold_sol: f64,
-
-    /// Percentage of profits to extract
-    #[serde(default = "default_profit_percentage")]
-    pub profit_percentage: f64,
+fn default_balance_poll_interval() -> u64 {
+    30
+}
 
-    /// Extract excess when balance exceeds ceiling
-    #[serde(default = "default_balance_ceiling")]
-    pub balance_ceiling_sol: f64,
+fn default_entry_floor() -> f64 {
+    0.02
+}
 
-    /// Minimum time between extractions (seconds)
-    #[serde(default = "default_min_extraction_interval")]
-    pub min_extraction_interval_secs: u64,
+fn default_balance_warn_below() -> f64 {
+    0.1
 }
 
-impl Default for ExtractionConfig {
-    fn default() -> Self {
-        Self {
-            auto_extract: true,
-            profit_threshold_sol: default_profit_threshold(),
-            profit_percentage: default_profit_percentage(),
-            balance_ceiling_sol: default_balance_ceiling(),
-            min_extraction_interval_secs: default_min_extraction_interval(),
-        }
-    }
+fn default_max_drain_per_hour() -> f64 {
+    2.0
 }
 
 // Default value functions
@@ -588,6 +1804,10 @@ fn default_max_position_sol() -> f64 {
     0.5
 }
 
+fn default_max_concurrent_positions() -> usize {
+    5
+}
+
 fn default_daily_loss_limit() -> f64 {
     1.0
 }
@@ -641,6 +1861,10 @@ fn default_ai_max_auto_transfer() -> f64 {
     0.5
 }
 
+fn default_dual_confirm_window() -> u64 {
+    15
+}
+
 fn default_profit_threshold() -> f64 {
     0.2
 }
@@ -657,31 +1881,240 @@ fn default_min_extraction_interval() -> u64 {
     3600
 }
 
-impl Config {
-    /// Load configuration from file and environment variables
-    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
+fn default_extraction_check_interval() -> u64 {
+    60
+}
+
+fn default_extraction_jitter() -> u64 {
+    30
+}
+
+fn default_hot_buffer() -> f64 {
+    1.0
+}
+
+fn default_max_open_exposure() -> f64 {
+    1.0
+}
+
+/// Prefix of environment overrides: `SNIPE__TRADING__BUY_AMOUNT_SOL=0.05`
+/// sets `trading.buy_amount_sol`
+pub const ENV_PREFIX: &str = "SNIPE__";
+
+/// Older prefix, still accepted
+const LEGACY_ENV_PREFIX: &str = "SNIPER__";
+
+/// Selects the profile when `--profile` isn't given
+pub const PROFILE_ENV: &str = "SNIPE_PROFILE";
+
+/// Where an overridden value came from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueSource {
+    /// `config.<profile>.toml`
+    Profile(PathBuf),
+    /// Environment variable name
+    Env(String),
+}
+
+impl std::fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueSource::Profile(path) => write!(f, "profile {}", path.display()),
+            ValueSource::Env(name) => write!(f, "env {}", name),
+        }
+    }
+}
+
+/// A value the profile or the environment sets over the base file
+#[derive(Debug, Clone)]
+pub struct ConfigOverride {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub source: ValueSource,
+}
+
+/// Key names whose values are never printed
+const SECRET_KEYS: &[&str] = &[
+    "api_key",
+    "secret",
+    "token",
+    "password",
+    "passphrase",
+    "webhook",
+];
+
+impl ConfigOverride {
+    /// The value as `snipe config` prints it: secrets hidden, URL queries masked
+    pub fn masked_value(&self) -> String {
+        let name = self.key.rsplit('.').next().unwrap_or_default();
+        if SECRET_KEYS.iter().any(|s| name.contains(s)) {
+            return "***".to_string();
+        }
+        match self.value {
+            serde_json::Value::String(ref s) if s.contains("://") => mask_url(s),
+            serde_json::Value::String(ref s) => s.clone(),
+            ref other => other.to_string(),
+        }
+    }
+}
+
+/// An environment variable naming a config key
+#[derive(Debug, Clone, PartialEq)]
+struct EnvOverride {
+    name: String,
+    key: String,
+    value: String,
+}
+
+/// `SNIPE__*` variables as config keys, sorted by name
+fn env_overrides(vars: impl Iterator<Item = (String, String)>) -> Vec<EnvOverride> {
+    let mut overrides: Vec<EnvOverride> = vars
+        .filter_map(|(name, value)| {
+            let rest = name
+                .strip_prefix(ENV_PREFIX)
+                .or_else(|| name.strip_prefix(LEGACY_ENV_PREFIX))?;
+            let parts: Vec<&str> = rest.split("__").collect();
+            if parts.iter().any(|p| p.is_empty()) {
+                return None;
+            }
+            let key = parts.join(".").to_lowercase();
+            Some(EnvOverride { name, key, value })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.name.cmp(&b.name));
+    overrides
+}
+
+/// The layers a config is built from: base file, optional profile overlay,
+/// then `SNIPE__SECTION__KEY` environment overrides
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    /// Base file
+    pub path: PathBuf,
+    pub profile: Option<String>,
+}
+
+impl ConfigSource {
+    /// Without a profile, `SNIPE_PROFILE` picks one
+    pub fn new<P: AsRef<Path>>(path: P, profile: Option<String>) -> Self {
+        let profile = profile
+            .or_else(|| std::env::var(PROFILE_ENV).ok())
+            .filter(|p| !p.is_empty());
+        Self {
+            path: path.as_ref().to_path_buf(),
+            profile,
+        }
+    }
+
+    /// `config.<profile>.toml` next to the base file
+    pub fn profile_path(&self) -> Option<PathBuf> {
+        let profile = self.profile.as_ref()?;
+        let stem = self
+            .path
+            .file_stem()
+            .map_or("config".into(), |s| s.to_string_lossy());
+        let name = format!("{}.{}.toml", stem, profile);
+        Some(self.path.with_file_name(name))
+    }
+
+    /// The base file, plus the profile file when one is selected
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = vec![self.path.clone()];
+        if let Some(path) = self.profile_path() {
+            if !path.exists() {
+                anyhow::bail!(
+                    "profile {:?} selected but {} does not exist",
+                    self.profile.as_deref().unwrap_or_default(),
+                    path.display()
+                );
+            }
+            files.push(path);
+        }
+        Ok(files)
+    }
 
-        let settings = config::Config::builder()
+    fn settings(&self) -> Result<config::Config> {
+        let mut builder = config::Config::builder()
             // Start with defaults
             .set_default("rpc.endpoint", default_rpc_endpoint())?
             .set_default("rpc.ws_endpoint", default_ws_endpoint())?
             .set_default("rpc.timeout_ms", default_timeout_ms() as i64)?
-            .set_default("rpc.max_retries", default_max_retries() as i64)?
-            // Load from file if exists
-            .add_source(config::File::from(path).required(false))
-            // Override with environment variables (prefix SNIPER_)
-            .add_source(
-                config::Environment::with_prefix("SNIPER")
-                    .separator("__")
-                    .try_parsing(true),
-            )
-            .build()
-            .context("Failed to build configuration")?;
+            .set_default("rpc.max_retries", default_max_retries() as i64)?;
+        // Base file if it exists, then the profile overlay
+        for (i, path) in self.files()?.iter().enumerate() {
+            builder = builder.add_source(config::File::from(path.as_path()).required(i > 0));
+        }
+        // Environment last; strings are coerced to the field's type on load
+        for var in env_overrides(std::env::vars()) {
+            builder = builder.set_override(&var.key, var.value)?;
+        }
+        builder.build().context("Failed to build configuration")
+    }
+
+    /// Name the environment variable behind a type error
+    fn explain(&self, error: config::ConfigError) -> anyhow::Error {
+        let message = error.to_string();
+        match env_overrides(std::env::vars())
+            .into_iter()
+            .find(|var| message.contains(&format!("`{}`", var.key)))
+        {
+            Some(var) => anyhow::anyhow!("{}={:?}: {}", var.name, var.value, message),
+            None => anyhow::Error::new(error).context("Failed to deserialize configuration"),
+        }
+    }
+
+    /// Values the profile and environment set over the base file
+    pub fn overrides(&self) -> Result<Vec<ConfigOverride>> {
+        let mut overrides = Vec::new();
+        if let Some(profile_path) = self.profile_path() {
+            let read = |files: &[PathBuf]| -> Result<serde_json::Value> {
+                let mut builder = config::Config::builder();
+                for path in files {
+                    let file = config::File::from(path.as_path()).required(false);
+                    builder = builder.add_source(file);
+                }
+                Ok(builder.build()?.try_deserialize()?)
+            };
+            let files = self.files()?;
+            let base = read(&files[..1])?;
+            let layered = read(&files)?;
+            for change in crate::config_reload::diff(&base, &layered) {
+                if let Some(value) = change.after {
+                    overrides.push(ConfigOverride {
+                        key: change.key,
+                        value,
+                        source: ValueSource::Profile(profile_path.clone()),
+                    });
+                }
+            }
+        }
+        for var in env_overrides(std::env::vars()) {
+            overrides.push(ConfigOverride {
+                key: var.key,
+                value: serde_json::Value::String(var.value),
+                source: ValueSource::Env(var.name),
+            });
+        }
+        Ok(overrides)
+    }
+}
 
-        let config: Config = settings
+impl Config {
+    /// Load configuration from file and environment variables
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::load_from(&ConfigSource::new(path, None))
+    }
+
+    /// Load the layered configuration of `source`
+    pub fn load_from(source: &ConfigSource) -> Result<Self> {
+        let mut config: Config = source
+            .settings()?
             .try_deserialize()
-            .context("Failed to deserialize configuration")?;
+            .map_err(|e| source.explain(e))?;
+        config.apply_deprecated();
+        // One day boundary for every daily limit
+        config.safety.daily_reset_utc_offset_hours =
+            config.strategy.portfolio_risk.daily_reset_utc_offset_hours;
 
         // Validate configuration
         config.validate()?;
@@ -689,6 +2122,38 @@ impl Config {
         Ok(config)
     }
 
+    /// The values set in the file and environment, without defaults for
+    /// the keys left out (config reloads diff these)
+    pub fn load_values<P: AsRef<Path>>(path: P) -> Result<serde_json::Value> {
+        Self::load_values_from(&ConfigSource::new(path, None))
+    }
+
+    /// `load_values` for the layers of `source`
+    pub fn load_values_from(source: &ConfigSource) -> Result<serde_json::Value> {
+        source
+            .settings()?
+            .try_deserialize()
+            .context("Failed to read configuration values")
+    }
+
+    /// Move deprecated keys that are still set to their replacements
+    fn apply_deprecated(&mut self) {
+        if let Some(secs) = self.adaptive_filter.enrichment.critical_refresh_secs.take() {
+            tracing::warn!(
+                "adaptive_filter.enrichment.critical_refresh_secs is deprecated - \
+                 use smart_money.holder_watcher.refresh_secs"
+            );
+            self.smart_money.holder_watcher.refresh_secs = secs;
+        }
+    }
+
+    /// Any buy size or safety limit is set in USD
+    pub fn uses_usd_amounts(&self) -> bool {
+        self.trading.buy_amount_usd.is_some()
+            || self.safety.max_position_usd.is_some()
+            || self.safety.daily_loss_limit_usd.is_some()
+    }
+
     /// Validate configuration values
     fn validate(&self) -> Result<()> {
         // Validate Jito regions (max 2)
@@ -708,6 +2173,19 @@ impl Config {
             anyhow::bail!("slippage_bps cannot exceed 10000 (100%)");
         }
 
+        if self.trading.panic.slippage_bps > 10000 {
+            anyhow::bail!("trading.panic.slippage_bps cannot exceed 10000 (100%)");
+        }
+
+        if self.filters.honeypot.enabled {
+            if self.filters.honeypot.probe_sol <= 0.0 {
+                anyhow::bail!("filters.honeypot.probe_sol must be positive");
+            }
+            if self.filters.honeypot.max_round_trip_loss_pct < 0.0 {
+                anyhow::bail!("filters.honeypot.max_round_trip_loss_pct cannot be negative");
+            }
+        }
+
         // Validate safety limits
         if self.safety.max_position_sol <= 0.0 {
             anyhow::bail!("max_position_sol must be positive");
@@ -717,6 +2195,23 @@ impl Config {
             anyhow::bail!("daily_loss_limit_sol must be positive");
         }
 
+        if self.safety.max_daily_entries == Some(0) || self.safety.max_daily_trades == Some(0) {
+            anyhow::bail!("safety: max_daily_entries and max_daily_trades must be positive");
+        }
+
+        for (key, usd) in [
+            ("trading.buy_amount_usd", self.trading.buy_amount_usd),
+            ("safety.max_position_usd", self.safety.max_position_usd),
+            (
+                "safety.daily_loss_limit_usd",
+                self.safety.daily_loss_limit_usd,
+            ),
+        ] {
+            if usd.is_some_and(|usd| usd <= 0.0) {
+                anyhow::bail!("{} must be positive", key);
+            }
+        }
+
         // Validate auto-sell percentages
         if self.auto_sell.enabled {
             if self.auto_sell.take_profit_pct <= 0.0 {
@@ -725,6 +2220,143 @@ impl Config {
             if self.auto_sell.stop_loss_pct <= 0.0 || self.auto_sell.stop_loss_pct >= 100.0 {
                 anyhow::bail!("stop_loss_pct must be between 0 and 100");
             }
+            self.auto_sell.entry_types.validate()?;
+            if self.auto_sell.early_weakness.enabled {
+                self.auto_sell.early_weakness.validate()?;
+            }
+        }
+
+        let rug = &self.strategy.tactics.rug_predictor;
+        if rug.enabled
+            && !(0.0 < rug.warning_threshold && rug.warning_threshold <= rug.critical_threshold)
+        {
+            anyhow::bail!(
+                "strategy.tactics.rug_predictor: need 0 < warning_threshold <= critical_threshold"
+            );
+        }
+
+        if let Err(e) = self.strategy.throttle.validate() {
+            anyhow::bail!("strategy.throttle: {}", e);
+        }
+
+        if let Err(e) = self.strategy.slippage.validate() {
+            anyhow::bail!("strategy.slippage: {}", e);
+        }
+
+        if let Err(e) = self.strategy.position_sizing.validate() {
+            anyhow::bail!("strategy.position_sizing: {}", e);
+        }
+
+        if let Err(e) = self.smart_money.holder_watcher.validate() {
+            anyhow::bail!("smart_money.holder_watcher: {}", e);
+        }
+
+        if let Err(e) = self.adaptive_filter.enrichment.creator_history.validate() {
+            anyhow::bail!("adaptive_filter.enrichment.creator_history: {}", e);
+        }
+
+        if let Err(e) = self.strategy.validate_profiles() {
+            anyhow::bail!("strategy profiles: {}", e);
+        }
+
+        if !(-12..=14).contains(&self.strategy.portfolio_risk.daily_reset_utc_offset_hours) {
+            anyhow::bail!("strategy.portfolio_risk.daily_reset_utc_offset_hours must be -12..=14");
+        }
+
+        let watch = &self.wallet.balance_watch;
+        if watch.enabled && watch.poll_interval_secs == 0 {
+            anyhow::bail!("wallet.balance_watch.poll_interval_secs must be positive");
+        }
+        if watch.entry_floor_sol < 0.0 || watch.warn_below_sol < watch.entry_floor_sol {
+            anyhow::bail!("wallet.balance_watch: need 0 <= entry_floor_sol <= warn_below_sol");
+        }
+        if watch.max_drain_sol_per_hour < 0.0 {
+            anyhow::bail!("wallet.balance_watch.max_drain_sol_per_hour must not be negative");
+        }
+
+        let safety = &self.wallet.safety;
+        if safety.dual_confirm_above_sol < 0.0 {
+            anyhow::bail!("wallet.safety.dual_confirm_above_sol must not be negative");
+        }
+        if safety.dual_confirm_above_sol > 0.0 && safety.dual_confirm_window_mins == 0 {
+            anyhow::bail!("wallet.safety.dual_confirm_window_mins must be positive");
+        }
+
+        let extraction = &self.wallet.extraction;
+        if extraction.auto_extract && extraction.check_interval_secs == 0 {
+            anyhow::bail!("wallet.extraction.check_interval_secs must be positive");
+        }
+        if extraction.daily_hour_utc.is_some_and(|hour| hour > 23) {
+            anyhow::bail!("wallet.extraction.daily_hour_utc must be 0-23");
+        }
+        if extraction.hot_buffer_sol < 0.0 || extraction.excess_trigger_sol < 0.0 {
+            anyhow::bail!(
+                "wallet.extraction.hot_buffer_sol and excess_trigger_sol must not be negative"
+            );
+        }
+
+        let advisor = &self.wallet.advisor;
+        if advisor.enabled && advisor.interval_secs == 0 {
+            anyhow::bail!("wallet.advisor.interval_secs must be positive");
+        }
+        if advisor.min_buy_size_multiplier <= 0.0 || advisor.min_buy_size_multiplier > 1.0 {
+            anyhow::bail!("wallet.advisor.min_buy_size_multiplier must be in (0, 1]");
+        }
+
+        let signer = &self.wallet.signer;
+        if signer.backend != "local" {
+            if !matches!(signer.backend.as_str(), "remote" | "subprocess") {
+                anyhow::bail!(
+                    "wallet.signer.backend must be local, remote or subprocess (got '{}')",
+                    signer.backend
+                );
+            }
+            let pubkey = signer.pubkey.as_deref().unwrap_or_default();
+            if pubkey.parse::<solana_sdk::pubkey::Pubkey>().is_err() {
+                anyhow::bail!(
+                    "wallet.signer.pubkey must be the hot wallet address for the {} backend",
+                    signer.backend
+                );
+            }
+            if signer.timeout_ms == 0 {
+                anyhow::bail!("wallet.signer.timeout_ms must be positive");
+            }
+        }
+        if signer.backend == "remote" {
+            let url = signer.url.as_deref().unwrap_or_default();
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("wallet.signer.url must be an http(s) URL for the remote backend");
+            }
+            if signer.client_cert_path.is_some() != signer.client_key_path.is_some() {
+                anyhow::bail!("wallet.signer.client_cert_path and client_key_path go together");
+            }
+            if signer.hmac_secret_env.is_none() && signer.client_cert_path.is_none() {
+                anyhow::bail!(
+                    "wallet.signer: the remote backend needs hmac_secret_env and/or a client certificate"
+                );
+            }
+        }
+        if signer.backend == "subprocess" && signer.command.is_empty() {
+            anyhow::bail!("wallet.signer.command must be set for the subprocess backend");
+        }
+
+        if self.shadow.enabled && self.shadow.window_secs == 0 {
+            anyhow::bail!("shadow.window_secs must be positive when shadow tracking is enabled");
+        }
+
+        let price_sources = &self.price_sources;
+        let curve = crate::position::PriceSource::BondingCurve;
+        if price_sources.order.contains(&curve) {
+            anyhow::bail!("price_sources.order takes dexscreener, birdeye and geckoterminal");
+        }
+        if price_sources.timeout_ms == 0 {
+            anyhow::bail!("price_sources.timeout_ms must be positive");
+        }
+        if self.scan_history.enabled && self.scan_history.max_snapshots_per_mint < 2 {
+            anyhow::bail!("scan_history.max_snapshots_per_mint must be at least 2");
+        }
+        if self.pump_program.enabled && self.pump_program.recent_transactions == 0 {
+            anyhow::bail!("pump_program.recent_transactions must be positive");
         }
 
         // Validate filter patterns (compile regex to check)
@@ -744,6 +2376,17 @@ impl Config {
                 anyhow::bail!("Invalid wallet address: {}", wallet);
             }
         }
+        for wallet in self.wallet_tracking.per_wallet.keys() {
+            if !self.wallet_tracking.wallets.contains(wallet) {
+                anyhow::bail!(
+                    "wallet_tracking.per_wallet: {} is not a tracked wallet",
+                    wallet
+                );
+            }
+        }
+        if self.wallet_tracking.copy_size_pct < 0.0 || self.wallet_tracking.max_copy_sol <= 0.0 {
+            anyhow::bail!("wallet_tracking: copy_size_pct must be >= 0 and max_copy_sol > 0");
+        }
 
         // Warn about backpressure policy
         if self.backpressure.drop_policy == DropPolicy::Block {
@@ -855,6 +2498,8 @@ impl Default for Config {
                 use_for_trading: true,
                 lightning_wallet: String::new(),
                 force_local_api: false,
+                lightning_fee_pct: default_lightning_fee_pct(),
+                local_fee_pct: default_local_fee_pct(),
             },
             backpressure: BackpressureConfig {
                 channel_capacity: default_channel_capacity(),
@@ -862,9 +2507,12 @@ impl Default for Config {
             },
             trading: TradingConfig {
                 buy_amount_sol: default_buy_amount_sol(),
+                buy_amount_usd: None,
                 slippage_bps: default_slippage_bps(),
                 priority_fee_lamports: default_priority_fee(),
                 simulate_before_send: false,
+                fast_trade_entries: false,
+                panic: PanicConfig::default(),
             },
             filters: FilterConfig {
                 enabled: true,
@@ -875,14 +2523,9 @@ impl Default for Config {
                 min_market_cap_sol: 0.0,
                 min_bonding_curve_pct: 0.0,
                 max_bonding_curve_pct: default_filter_max_bonding_curve(),
+                honeypot: HoneypotConfig::default(),
             },
-            wallet_tracking: WalletTrackingConfig {
-                enabled: false,
-                wallets: vec![],
-                priority_boost: true,
-                min_trade_sol: default_min_trade_sol(),
-                auto_copy_trade: false,
-            },
+            wallet_tracking: WalletTrackingConfig::default(),
             auto_sell: AutoSellConfig {
                 enabled: true,
                 take_profit_pct: default_take_profit_pct(),
@@ -900,18 +2543,42 @@ impl Default for Config {
                 trailing_stop_base_pct: default_trailing_base(),
                 trailing_stop_medium_pct: default_trailing_medium(),
                 trailing_stop_tight_pct: default_trailing_tight(),
+                max_exit_slices: default_max_exit_slices(),
+                exit_slice_interval_ms: default_exit_slice_interval_ms(),
+                entry_types: EntryTypeThresholds::default(),
+                monitor: MonitorProfiles::default(),
+                early_weakness: EarlyWeaknessConfig::default(),
+                kill_switch_execution: KillSwitchExecutionConfig::default(),
             },
             safety: SafetyConfig {
                 require_sell_confirmation: true,
                 max_position_sol: default_max_position_sol(),
+                max_position_usd: None,
                 daily_loss_limit_sol: default_daily_loss_limit(),
+                daily_loss_limit_usd: None,
                 keypair_balance_warning_sol: default_keypair_balance_warning(),
+                max_concurrent_positions: default_max_concurrent_positions(),
+                max_daily_entries: None,
+                max_daily_trades: None,
+                daily_reset_utc_offset_hours: 0,
             },
             wallet: WalletConfig::default(),
             adaptive_filter: AdaptiveFilterConfig::default(),
             strategy: StrategyEngineConfig::default(),
             smart_money: SmartMoneyConfig::default(),
             early_detection: EarlyDetectionConfig::default(),
+            control: ControlConfig::default(),
+            positions: PositionsConfig::default(),
+            cooldowns: CooldownConfig::default(),
+            shadow: ShadowConfig::default(),
+            backtest: BacktestConfig::default(),
+            paper: PaperConfig::default(),
+            notifications: NotificationsConfig::default(),
+            shutdown: ShutdownConfig::default(),
+            price_sources: PriceSourcesConfig::default(),
+            scan_history: ScanHistoryConfig::default(),
+            pump_program: PumpProgramConfig::default(),
+            sol_price: SolPriceConfig::default(),
         }
     }
 }
@@ -935,6 +2602,175 @@ mod tests {
         assert_eq!(policy, DropPolicy::OldestNonPriority);
     }
 
+    #[test]
+    fn test_entry_type_thresholds() {
+        let defaults = EntryTypeThresholds::default();
+        assert!(defaults.validate().is_ok());
+        assert_eq!(defaults.get(EntryType::Probe).take_profit_pct, 8.0);
+        assert_eq!(defaults.get(EntryType::StrongBuy).max_hold(), None);
+
+        let parsed: EntryTypeThresholds = config::Config::builder()
+            .add_source(config::File::from_str(
+                r#"
+                [probe]
+                take_profit_pct = 6.0
+                stop_loss_pct = 10.0
+                quick_profit_pct = 3.0
+                max_hold_secs = 90
+                "#,
+                config::FileFormat::Toml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+        assert_eq!(parsed.probe.max_hold(), Some(90));
+        assert_eq!(parsed.strong_buy, defaults.strong_buy);
+
+        // Layers out of order
+        let mut bad = defaults.clone();
+        bad.opportunity.quick_profit_pct = 12.0;
+        assert!(bad.validate().is_err());
+
+        // Stop loss level above take profit level
+        let mut bad = defaults;
+        bad.legacy.stop_loss_pct = -20.0;
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_early_weakness_config() {
+        let defaults = EarlyWeaknessConfig::default();
+        assert!(!defaults.enabled);
+        assert!(defaults.validate().is_ok());
+
+        let mut bad = defaults.clone();
+        bad.short_window_secs = 60;
+        assert!(bad.validate().is_err());
+
+        let mut bad = defaults;
+        bad.short_net_flow = 0.2;
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_kill_switch_execution_policy() {
+        let parsed: KillSwitchExecutionConfig = config::Config::builder()
+            .add_source(config::File::from_str(
+                r#"
+                [critical]
+                lightning_attempts = 0
+                panic_pricing = true
+                jito_bundle = true
+                "#,
+                config::FileFormat::Toml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+        let (name, critical) = parsed.policy(KillSwitchUrgency::Critical);
+        assert_eq!(name, "critical");
+        assert!(critical.panic_pricing && critical.jito_bundle && !critical.preflight);
+        assert!(parsed.uses_jito());
+
+        let (name, high) = parsed.policy(KillSwitchUrgency::Immediate);
+        assert_eq!((name, high.lightning_attempts), ("high", Some(1)));
+        assert_eq!(parsed.policy(KillSwitchUrgency::High).0, "high");
+        let (name, normal) = parsed.policy(KillSwitchUrgency::Medium);
+        assert_eq!((name, normal.lightning_attempts), ("normal", None));
+        assert!(!KillSwitchExecutionConfig::default().uses_jito());
+    }
+
+    #[test]
+    fn test_copy_settings() {
+        let tracking: WalletTrackingConfig = config::Config::builder()
+            .add_source(config::File::from_str(
+                r#"
+                enabled = true
+                wallets = ["alpha", "beta", "gamma"]
+                auto_copy_trade = true
+                max_copy_sol = 0.2
+
+                [per_wallet.beta]
+                copy_size_pct = 25.0
+                mirror_sells = false
+
+                [per_wallet.gamma]
+                enabled = false
+                "#,
+                config::FileFormat::Toml,
+            ))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap();
+
+        let alpha = tracking.copy_settings("alpha");
+        assert!(alpha.enabled && alpha.mirror_sells);
+        // 10% of 1 SOL, then capped at 0.2
+        assert!((alpha.copy_amount_sol(1.0, 0.05) - 0.1).abs() < 1e-9);
+        assert!((alpha.copy_amount_sol(5.0, 0.05) - 0.2).abs() < 1e-9);
+
+        let beta = tracking.copy_settings("beta");
+        assert!(!beta.mirror_sells);
+        assert!((beta.copy_amount_sol(0.4, 0.05) - 0.1).abs() < 1e-9);
+        assert!(!tracking.copy_settings("gamma").enabled);
+
+        // Sizing off: fixed buy amount
+        let fixed = CopySettings {
+            copy_size_pct: 0.0,
+            ..alpha
+        };
+        assert!((fixed.copy_amount_sol(5.0, 0.05) - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_env_overrides_and_profile_path() {
+        let vars = [
+            ("SNIPE__TRADING__BUY_AMOUNT_SOL", "0.05"),
+            ("SNIPER__RPC__ENDPOINT", "https://rpc.example.com?key=abc"),
+            ("SNIPE__", "ignored"),
+            ("SNIPE__TRADING____X", "ignored"),
+            ("SNIPE_PROFILE", "paper"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        let overrides = env_overrides(vars.into_iter());
+        let keys: Vec<&str> = overrides.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, ["trading.buy_amount_sol", "rpc.endpoint"]);
+
+        let source = ConfigSource::new("conf/config.toml", Some("paper".to_string()));
+        assert_eq!(
+            source.profile_path(),
+            Some(PathBuf::from("conf/config.paper.toml"))
+        );
+        assert!(source.files().is_err());
+
+        let masked = ConfigOverride {
+            key: overrides[1].key.clone(),
+            value: serde_json::Value::String(overrides[1].value.clone()),
+            source: ValueSource::Env(overrides[1].name.clone()),
+        };
+        assert_eq!(masked.masked_value(), "https://rpc.example.com?***");
+        let secret = ConfigOverride {
+            key: "pumpportal.api_key".to_string(),
+            ..masked
+        };
+        assert_eq!(secret.masked_value(), "***");
+    }
+
+    #[test]
+    fn test_deprecated_critical_refresh_secs() {
+        let mut config = Config::default();
+        config.adaptive_filter.enrichment.critical_refresh_secs = Some(30);
+        config.apply_deprecated();
+        assert_eq!(config.smart_money.holder_watcher.refresh_secs, 30);
+        assert_eq!(
+            config.adaptive_filter.enrichment.critical_refresh_secs,
+            None
+        );
+    }
+
     #[test]
     fn test_mask_url() {
         assert_eq!(
@@ -0,0 +1,364 @@
+//! Config sanity checks for `snipe config validate` and startup
+//!
+//! `Config::load` rejects values that cannot work at all. These checks catch
+//! values that load fine but behave unexpectedly at runtime: ranges, ordering
+//! between related settings, missing files and risky combinations. Errors
+//! stop `snipe start`; warnings are only printed.
+
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::config::Config;
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Refuse to run
+    Error,
+    /// Run, but tell the operator
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Severity::Error => "ERROR",
+            Severity::Warning => "WARN",
+        })
+    }
+}
+
+/// One problem with the configuration
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Config key the finding is about, e.g. `trading.slippage_bps`
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// Findings for one config (and optionally one run's flags)
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReport {
+    pub findings: Vec<Finding>,
+}
+
+impl ConfigReport {
+    /// Check `config`
+    pub fn check(config: &Config) -> Self {
+        let mut report = Self::default();
+        report.check_slippage(config);
+        report.check_thresholds(config);
+        report.check_auto_sell(config);
+        report.check_files(config);
+        report.check_pumpportal(config);
+        report
+    }
+
+    /// Add the findings that depend on the command line of this run
+    pub fn with_run_flags(mut self, dry_run: bool, auto_buy: bool) -> Self {
+        if dry_run && auto_buy {
+            self.warn(
+                "--dry-run",
+                "auto-buy in dry run marks would-be buys as bought; later live runs skip those mints"
+                    .to_string(),
+            );
+        }
+        self
+    }
+
+    pub fn errors(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    /// Log every finding (errors first)
+    pub fn log(&self) {
+        for finding in self.sorted() {
+            match finding.severity {
+                Severity::Error => tracing::error!("Config: {}", finding),
+                Severity::Warning => tracing::warn!("Config: {}", finding),
+            }
+        }
+    }
+
+    /// Findings with errors first, in check order otherwise
+    pub fn sorted(&self) -> Vec<&Finding> {
+        let mut findings: Vec<&Finding> = self.findings.iter().collect();
+        findings.sort_by_key(|f| f.severity != Severity::Error);
+        findings
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    fn error(&mut self, key: &str, message: String) {
+        self.push(Severity::Error, key, message);
+    }
+
+    fn warn(&mut self, key: &str, message: String) {
+        self.push(Severity::Warning, key, message);
+    }
+
+    fn push(&mut self, severity: Severity, key: &str, message: String) {
+        self.findings.push(Finding {
+            severity,
+            key: key.to_string(),
+            message,
+        });
+    }
+
+    /// PumpPortal takes slippage in whole percent (`slippage_bps / 100`)
+    fn check_slippage(&mut self, config: &Config) {
+        for (key, bps) in [
+            ("trading.slippage_bps", config.trading.slippage_bps),
+            (
+                "trading.panic.slippage_bps",
+                config.trading.panic.slippage_bps,
+            ),
+        ] {
+            if !(1..=10_000).contains(&bps) {
+                self.error(key, format!("{} is outside 1-10000", bps));
+            } else if bps < 100 {
+                self.error(
+                    key,
+                    format!("{} bps is sent as whole percent and rounds to 0%", bps),
+                );
+            } else if bps % 100 != 0 {
+                self.warn(
+                    key,
+                    format!("{} bps is sent as whole percent ({}%)", bps, bps / 100),
+                );
+            }
+        }
+    }
+
+    fn check_thresholds(&mut self, config: &Config) {
+        if !config.adaptive_filter.enabled {
+            return;
+        }
+        let t = &config.adaptive_filter.thresholds;
+        if !(t.strong_buy > t.opportunity && t.opportunity > t.probe && t.probe > t.avoid) {
+            self.error(
+                "adaptive_filter.thresholds",
+                format!(
+                    "need strong_buy > opportunity > probe > avoid (got {} / {} / {} / {})",
+                    t.strong_buy, t.opportunity, t.probe, t.avoid
+                ),
+            );
+        }
+        for (key, value) in [
+            (
+                "adaptive_filter.thresholds.min_confidence",
+                t.min_confidence,
+            ),
+            (
+                "adaptive_filter.thresholds.min_data_completeness",
+                t.min_data_completeness,
+            ),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                self.error(key, format!("{} is outside 0-1", value));
+            }
+        }
+        if t.max_curve_progress_pct <= 0.0 || t.max_curve_progress_pct > 100.0 {
+            self.error(
+                "adaptive_filter.thresholds.max_curve_progress_pct",
+                format!("{} is outside (0, 100]", t.max_curve_progress_pct),
+            );
+        }
+        let hot_scan = &config.adaptive_filter.hot_scan;
+        if hot_scan.enabled && !hot_scan.min_recommendation.allows_trading() {
+            self.error(
+                "adaptive_filter.hot_scan.min_recommendation",
+                format!(
+                    "{:?} does not allow trading (use strong_buy, opportunity or probe)",
+                    hot_scan.min_recommendation
+                ),
+            );
+        }
+    }
+
+    fn check_auto_sell(&mut self, config: &Config) {
+        let auto_sell = &config.auto_sell;
+        if !auto_sell.enabled {
+            self.warn(
+                "auto_sell.enabled",
+                "auto-sell is off: no stop loss or take profit, only the kill-switch and manual sells exit"
+                    .to_string(),
+            );
+            return;
+        }
+        if !(auto_sell.quick_profit_pct < auto_sell.second_profit_pct
+            && auto_sell.second_profit_pct < auto_sell.take_profit_pct)
+        {
+            self.error(
+                "auto_sell",
+                format!(
+                    "profit layers must rise: quick_profit_pct < second_profit_pct < take_profit_pct (got {} / {} / {})",
+                    auto_sell.quick_profit_pct, auto_sell.second_profit_pct, auto_sell.take_profit_pct
+                ),
+            );
+        }
+        if auto_sell.trailing_stop_enabled && auto_sell.trailing_stop_activation_pct <= 0.0 {
+            self.error(
+                "auto_sell.trailing_stop_activation_pct",
+                "must be positive".to_string(),
+            );
+        }
+        if auto_sell.dynamic_trailing_enabled
+            && !(auto_sell.trailing_stop_base_pct >= auto_sell.trailing_stop_medium_pct
+                && auto_sell.trailing_stop_medium_pct >= auto_sell.trailing_stop_tight_pct
+                && auto_sell.trailing_stop_tight_pct > 0.0)
+        {
+            self.error(
+                "auto_sell",
+                format!(
+                    "trailing stop must tighten: trailing_stop_base_pct >= medium >= tight > 0 (got {} / {} / {})",
+                    auto_sell.trailing_stop_base_pct,
+                    auto_sell.trailing_stop_medium_pct,
+                    auto_sell.trailing_stop_tight_pct
+                ),
+            );
+        }
+    }
+
+    fn check_files(&mut self, config: &Config) {
+        if !Path::new(&config.wallet.credentials_dir).is_dir() {
+            self.error(
+                "wallet.credentials_dir",
+                format!("{} is not a directory", config.wallet.credentials_dir),
+            );
+        }
+
+        if config.adaptive_filter.enabled {
+            let actors = &config.adaptive_filter.known_actors;
+            for (key, path) in [
+                (
+                    "adaptive_filter.known_actors.deployers_file",
+                    &actors.deployers_file,
+                ),
+                (
+                    "adaptive_filter.known_actors.snipers_file",
+                    &actors.snipers_file,
+                ),
+                (
+                    "adaptive_filter.known_actors.trusted_file",
+                    &actors.trusted_file,
+                ),
+            ] {
+                if !Path::new(path).exists() {
+                    self.warn(key, format!("{} not found, the list starts empty", path));
+                }
+            }
+        }
+
+        let signer = &config.wallet.signer;
+        for (key, path) in [
+            ("wallet.signer.client_cert_path", &signer.client_cert_path),
+            ("wallet.signer.client_key_path", &signer.client_key_path),
+            ("wallet.signer.ca_cert_path", &signer.ca_cert_path),
+        ] {
+            if let Some(path) = path.as_ref().filter(|p| !Path::new(p).exists()) {
+                self.error(key, format!("{} not found", path));
+            }
+        }
+    }
+
+    fn check_pumpportal(&mut self, config: &Config) {
+        let pumpportal = &config.pumpportal;
+        let lightning_wallet = !pumpportal.lightning_wallet.is_empty();
+        if lightning_wallet && Pubkey::from_str(&pumpportal.lightning_wallet).is_err() {
+            self.error(
+                "pumpportal.lightning_wallet",
+                format!("{} is not a valid address", pumpportal.lightning_wallet),
+            );
+        }
+        if !pumpportal.use_for_trading || !lightning_wallet {
+            return;
+        }
+        if pumpportal.api_key.is_empty() {
+            self.error(
+                "pumpportal.lightning_wallet",
+                "set without an api_key: trades would go through the Local API from the keypair wallet, not the Lightning wallet"
+                    .to_string(),
+            );
+        } else if pumpportal.force_local_api {
+            self.warn(
+                "pumpportal.force_local_api",
+                "trades are signed by the keypair wallet; lightning_wallet is ignored".to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn has(report: &ConfigReport, key: &str, severity: Severity) -> bool {
+        report
+            .findings
+            .iter()
+            .any(|f| f.key == key && f.severity == severity)
+    }
+
+    #[test]
+    fn test_slippage_rounding() {
+        let mut config = Config::default();
+        config.trading.slippage_bps = 50;
+        config.trading.panic.slippage_bps = 2550;
+        let report = ConfigReport::check(&config);
+        assert!(has(&report, "trading.slippage_bps", Severity::Error));
+        assert!(has(
+            &report,
+            "trading.panic.slippage_bps",
+            Severity::Warning
+        ));
+    }
+
+    #[test]
+    fn test_ordering_checks() {
+        let mut config = Config::default();
+        config.adaptive_filter.enabled = true;
+        config.adaptive_filter.thresholds.probe = config.adaptive_filter.thresholds.opportunity;
+        config.auto_sell.enabled = true;
+        config.auto_sell.second_profit_pct = config.auto_sell.take_profit_pct + 1.0;
+        let report = ConfigReport::check(&config);
+        assert!(has(&report, "adaptive_filter.thresholds", Severity::Error));
+        assert!(has(&report, "auto_sell", Severity::Error));
+
+        config.auto_sell.enabled = false;
+        let report = ConfigReport::check(&config);
+        assert!(!has(&report, "auto_sell", Severity::Error));
+        assert!(has(&report, "auto_sell.enabled", Severity::Warning));
+    }
+
+    #[test]
+    fn test_lightning_wallet_needs_api_key() {
+        let mut config = Config::default();
+        config.pumpportal.use_for_trading = true;
+        config.pumpportal.api_key.clear();
+        config.pumpportal.lightning_wallet = Pubkey::new_unique().to_string();
+        let report = ConfigReport::check(&config).with_run_flags(true, true);
+        assert!(has(&report, "pumpportal.lightning_wallet", Severity::Error));
+        assert!(has(&report, "--dry-run", Severity::Warning));
+        assert!(report.errors() >= 1);
+        assert_eq!(report.sorted()[0].severity, Severity::Error);
+    }
+}
@@ -0,0 +1,406 @@
+//! Live config reloads for `snipe start`
+//!
+//! The running bot re-reads config.toml (and the selected profile file) when
+//! either changes, or on
+//! `snipe reload` through the control channel. Changes to the whitelisted
+//! keys below are applied to the running components; anything else (wallet,
+//! RPC, safety limits, ...) is only logged as needing a restart. Every change
+//! is logged with its old and new value.
+
+use serde_json::Value;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+use tracing::{info, warn};
+
+use crate::config::{AutoSellConfig, Config, ConfigSource};
+use crate::config_check::ConfigReport;
+use crate::error::{Error, Result};
+use crate::filter::AdaptiveFilter;
+use crate::notify::Notifier;
+use crate::position::manager::PositionManager;
+use crate::position::CooldownTracker;
+use crate::sol_price::{usd_or_sol, SolUsdPrice};
+use crate::strategy::LaunchThrottle;
+
+/// How often the config file's modification time is checked
+const RELOAD_POLL_SECS: u64 = 5;
+
+/// Keys applied without a restart (a key and everything under it)
+const LIVE_KEYS: &[&str] = &[
+    "adaptive_filter.thresholds",
+    "adaptive_filter.weights",
+    "auto_sell",
+    "trading.buy_amount_sol",
+    "trading.buy_amount_usd",
+    "cooldowns",
+    "notifications",
+    "strategy.throttle",
+];
+
+/// Under a live key, but only read at startup
+const STARTUP_KEYS: &[&str] = &[
+    "auto_sell.monitor",
+    "auto_sell.early_weakness",
+    "notifications.queue_size",
+];
+
+/// `key` is `prefix` or sits under it
+fn under(key: &str, prefix: &str) -> bool {
+    key.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Whether a change to `key` is applied without a restart
+pub fn is_live(key: &str) -> bool {
+    LIVE_KEYS.iter().any(|p| under(key, p)) && !STARTUP_KEYS.iter().any(|p| under(key, p))
+}
+
+/// One changed value (None = not set in the file)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| {
+            value
+                .as_ref()
+                .map_or_else(|| "(unset)".to_string(), Value::to_string)
+        };
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.key,
+            show(&self.before),
+            show(&self.after)
+        )
+    }
+}
+
+/// Changed leaf values between two configs (tables are walked, arrays
+/// compare whole)
+pub fn diff(before: &Value, after: &Value) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    diff_at("", Some(before), Some(after), &mut changes);
+    changes
+}
+
+fn diff_at(key: &str, before: Option<&Value>, after: Option<&Value>, out: &mut Vec<ConfigChange>) {
+    // A table on one side and nothing on the other is walked too
+    if let (Some(Value::Object(_)) | None, Some(Value::Object(_)) | None) = (before, after) {
+        let empty = serde_json::Map::new();
+        let before = before.and_then(Value::as_object).unwrap_or(&empty);
+        let after = after.and_then(Value::as_object).unwrap_or(&empty);
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for name in keys {
+            let child = if key.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", key, name)
+            };
+            diff_at(&child, before.get(name), after.get(name), out);
+        }
+        return;
+    }
+    if before != after {
+        out.push(ConfigChange {
+            key: key.to_string(),
+            before: before.cloned(),
+            after: after.cloned(),
+        });
+    }
+}
+
+/// Set (or with None, remove) the value at a dotted key
+fn set_at(values: &mut Value, key: &str, value: Option<Value>) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(last) = parts.pop() else {
+        return;
+    };
+    let mut table = values;
+    for part in parts {
+        let Some(object) = table.as_object_mut() else {
+            return;
+        };
+        table = object
+            .entry(part)
+            .or_insert_with(|| Value::Object(Default::default()));
+    }
+    if let Some(object) = table.as_object_mut() {
+        match value {
+            Some(value) => object.insert(last.to_string(), value),
+            None => object.remove(last),
+        };
+    }
+}
+
+/// `trading.buy_amount_sol` (or `buy_amount_usd` at the current SOL price),
+/// updated by reloads
+pub struct BuyAmount {
+    sol: RwLock<f64>,
+    usd: RwLock<Option<f64>>,
+    sol_price: Option<Arc<SolUsdPrice>>,
+}
+
+impl BuyAmount {
+    pub fn new(sol: f64) -> Self {
+        Self {
+            sol: RwLock::new(sol),
+            usd: RwLock::new(None),
+            sol_price: None,
+        }
+    }
+
+    /// Size buys in `usd` (if set) at `price`
+    pub fn with_usd(mut self, usd: Option<f64>, price: Arc<SolUsdPrice>) -> Self {
+        *self.usd.get_mut().unwrap() = usd;
+        self.sol_price = Some(price);
+        self
+    }
+
+    /// Buy size in SOL, converted now when set in USD
+    pub fn get(&self) -> f64 {
+        usd_or_sol(
+            self.sol_price.as_deref(),
+            *self.usd.read().unwrap(),
+            *self.sol.read().unwrap(),
+        )
+    }
+
+    fn set(&self, sol: f64) {
+        *self.sol.write().unwrap() = sol;
+    }
+
+    fn set_usd(&self, usd: Option<f64>) {
+        *self.usd.write().unwrap() = usd;
+        if let (Some(_), Some(price)) = (usd, &self.sol_price) {
+            price.spawn_refresh();
+        }
+    }
+}
+
+/// Running components a reload updates
+pub struct ReloadTargets {
+    pub adaptive_filter: Option<Arc<AdaptiveFilter>>,
+    pub auto_sell: watch::Sender<AutoSellConfig>,
+    pub position_manager: Arc<PositionManager>,
+    pub cooldowns: Arc<CooldownTracker>,
+    pub notifier: Notifier,
+    pub throttle: Arc<LaunchThrottle>,
+    pub buy_amount: Arc<BuyAmount>,
+}
+
+/// What one reload did
+#[derive(Debug, Default)]
+pub struct ReloadOutcome {
+    pub applied: Vec<ConfigChange>,
+    /// Changes that only take effect after a restart
+    pub pending_restart: Vec<ConfigChange>,
+}
+
+impl fmt::Display for ReloadOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.applied.is_empty() && self.pending_restart.is_empty() {
+            return write!(f, "no changes");
+        }
+        write!(
+            f,
+            "{} applied, {} need a restart",
+            self.applied.len(),
+            self.pending_restart.len()
+        )?;
+        for change in &self.applied {
+            write!(f, "\n  applied: {}", change)?;
+        }
+        for change in &self.pending_restart {
+            write!(f, "\n  restart: {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Re-reads the config files and applies the live subset
+pub struct ConfigReloader {
+    source: ConfigSource,
+    /// Values the running bot uses (keys needing a restart keep their old value)
+    values: Value,
+    targets: ReloadTargets,
+}
+
+impl ConfigReloader {
+    pub fn new(source: &ConfigSource, targets: ReloadTargets) -> Result<Self> {
+        let values =
+            Config::load_values_from(source).map_err(|e| Error::Config(format!("{:#}", e)))?;
+        Ok(Self {
+            source: source.clone(),
+            values,
+            targets,
+        })
+    }
+
+    /// Re-read the files and apply what changed
+    ///
+    /// A config that fails to load or validate changes nothing.
+    pub fn reload(&mut self) -> Result<ReloadOutcome> {
+        let config =
+            Config::load_from(&self.source).map_err(|e| Error::Config(format!("{:#}", e)))?;
+        let report = ConfigReport::check(&config);
+        if report.errors() > 0 {
+            let errors: Vec<String> = report
+                .sorted()
+                .iter()
+                .take(report.errors())
+                .map(|f| f.to_string())
+                .collect();
+            return Err(Error::Config(errors.join("; ")));
+        }
+        let values = Config::load_values_from(&self.source)
+            .map_err(|e| Error::Config(format!("{:#}", e)))?;
+
+        let mut outcome = ReloadOutcome::default();
+        for change in diff(&self.values, &values) {
+            if is_live(&change.key) {
+                outcome.applied.push(change);
+            } else {
+                outcome.pending_restart.push(change);
+            }
+        }
+        self.apply(&config, &outcome.applied);
+
+        for change in &outcome.applied {
+            info!("Config reload: {}", change);
+        }
+        for change in &outcome.pending_restart {
+            warn!("Config reload: {} (needs a restart)", change);
+        }
+        self.values = values;
+        for change in &outcome.pending_restart {
+            set_at(&mut self.values, &change.key, change.before.clone());
+        }
+        Ok(outcome)
+    }
+
+    fn apply(&self, config: &Config, applied: &[ConfigChange]) {
+        let changed = |prefix: &str| applied.iter().any(|c| under(&c.key, prefix));
+        let targets = &self.targets;
+
+        if changed("adaptive_filter") {
+            if let Some(ref filter) = targets.adaptive_filter {
+                filter.set_scoring(&config.adaptive_filter);
+            }
+        }
+        if changed("auto_sell") {
+            targets.auto_sell.send_replace(config.auto_sell.clone());
+            targets
+                .position_manager
+                .set_exit_thresholds(config.auto_sell.entry_types.clone());
+        }
+        if changed("trading.buy_amount_sol") {
+            targets.buy_amount.set(config.trading.buy_amount_sol);
+        }
+        if changed("trading.buy_amount_usd") {
+            targets.buy_amount.set_usd(config.trading.buy_amount_usd);
+        }
+        if changed("cooldowns") {
+            targets.cooldowns.set_config(config.cooldowns.clone());
+        }
+        if changed("notifications") {
+            targets.notifier.reconfigure(&config.notifications);
+        }
+        if changed("strategy.throttle") {
+            targets
+                .throttle
+                .set_config(config.strategy.throttle.clone());
+        }
+    }
+
+    /// Reload whenever the base or profile file's modification time changes
+    pub fn spawn_watch(reloader: Arc<Mutex<Self>>) {
+        tokio::spawn(async move {
+            let source = reloader.lock().await.source.clone();
+            let path = source.path.clone();
+            let modified = || {
+                [Some(source.path.clone()), source.profile_path()]
+                    .map(|p| p.and_then(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok()))
+            };
+            let mut last = modified();
+            let mut interval = tokio::time::interval(Duration::from_secs(RELOAD_POLL_SECS));
+            loop {
+                interval.tick().await;
+                let current = modified();
+                if current == last {
+                    continue;
+                }
+                last = current;
+                if let Err(e) = reloader.lock().await.reload() {
+                    warn!(
+                        "{} changed but could not be reloaded: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_live_keys() {
+        assert!(is_live("auto_sell.take_profit_pct"));
+        assert!(is_live("auto_sell.entry_types.probe.stop_loss_pct"));
+        assert!(is_live("adaptive_filter.weights.bundle"));
+        assert!(!is_live("auto_sell.monitor.start.poll_interval_ms"));
+        assert!(!is_live("adaptive_filter.enabled"));
+        assert!(!is_live("trading.buy_amount_sol_max"));
+        assert!(is_live("trading.buy_amount_usd"));
+        assert!(!is_live("safety.daily_loss_limit_sol"));
+        assert!(!is_live("wallet.hot_wallet"));
+    }
+
+    #[test]
+    fn test_diff_and_restore() {
+        let before = json!({
+            "auto_sell": {"take_profit_pct": 25.0, "stop_loss_pct": 15.0},
+            "rpc": {"endpoint": "a"},
+        });
+        let after = json!({
+            "auto_sell": {"take_profit_pct": 30.0},
+            "rpc": {"endpoint": "b"},
+            "cooldowns": {"sold_ttl_secs": 60},
+        });
+        let changes = diff(&before, &after);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "auto_sell.stop_loss_pct",
+                "auto_sell.take_profit_pct",
+                "cooldowns.sold_ttl_secs",
+                "rpc.endpoint",
+            ]
+        );
+        assert_eq!(
+            changes[0].to_string(),
+            "auto_sell.stop_loss_pct: 15.0 -> (unset)"
+        );
+
+        // A key needing a restart keeps its running value
+        let mut running = after.clone();
+        set_at(&mut running, "rpc.endpoint", Some(json!("a")));
+        assert_eq!(running["rpc"]["endpoint"], "a");
+        set_at(&mut running, "cooldowns.sold_ttl_secs", None);
+        assert_eq!(diff(&running, &after).len(), 2);
+    }
+}
@@ -0,0 +1,622 @@
+//! Local control channel for a running bot
+//!
+//! `snipe start` listens on a localhost TCP port and accepts one JSON
+//! command per line. CLI commands such as `snipe position set-stop` send
+//! a command and print the JSON response. The socket is bound to loopback
+//! only; it is never exposed to the network.
+//!
+//! Loopback is reachable by every local user, so each command must also
+//! carry the session token the bot generates at startup and writes to
+//! `control.token` (mode 0600) in the credentials dir. A connection that
+//! sends a command without it is answered with an error and closed.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
+
+use crate::config_reload::ConfigReloader;
+use crate::error::{Error, Result};
+use crate::filter::{HeliusClient, WalletTracker};
+use crate::position::auto_sell::{ExitRequest, ExitSignal};
+use crate::position::manager::PositionManager;
+use crate::position::{CooldownKind, CooldownTracker};
+use crate::strategy::StrategyEngine;
+use crate::wallet::advisor::AiAdvisor;
+
+/// Control channel configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct ControlConfig {
+    /// Enable the control channel in `snipe start`
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Loopback address to listen on
+    #[serde(default = "default_listen_addr")]
+    pub listen_addr: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_listen_addr() -> String {
+    "127.0.0.1:7878".to_string()
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            listen_addr: default_listen_addr(),
+        }
+    }
+}
+
+/// Session token file name (paper runs prefix it with `paper_`)
+pub const TOKEN_FILE: &str = "control.token";
+
+/// Where to reach a running bot
+#[derive(Debug, Clone)]
+pub struct ControlEndpoint {
+    pub addr: String,
+    /// File the bot wrote its session token to
+    pub token_path: PathBuf,
+}
+
+/// Command sent to a running bot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Override exit thresholds for one position
+    SetExitOverride {
+        mint: String,
+        stop_loss_pct: Option<f64>,
+        take_profit_pct: Option<f64>,
+        trailing_pct: Option<f64>,
+    },
+    /// Suspend automatic exits for one position
+    Hold { mint: String, until_secs: u64 },
+    /// Helius request counts and circuit breaker state (JSON message)
+    HeliusStatus,
+    /// Switch the strategy engine to a named profile (`base` = no overrides)
+    UseStrategy { profile: String },
+    /// Approve a pending AI proposal and execute it
+    ApproveProposal { id: String },
+    /// Reject a pending AI proposal
+    RejectProposal { id: String },
+    /// Re-read the config file and apply the live subset
+    Reload,
+    /// Sell all of one position through the position monitor
+    ClosePosition { mint: String },
+    /// Close one position with zero proceeds (its sell can't succeed)
+    AbandonPosition { mint: String },
+    /// Full position record (JSON message)
+    ShowPosition { mint: String },
+    /// Copy-trade a wallet (or update its settings)
+    TrackWallet {
+        address: String,
+        size_mult: f64,
+        mirror_sells: Option<bool>,
+    },
+    /// Stop copy-trading a wallet added with `track_wallet`
+    UntrackWallet { address: String },
+}
+
+/// Response from the running bot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+    pub message: String,
+}
+
+impl ControlResponse {
+    fn ok(message: impl Into<String>) -> Self {
+        Self {
+            ok: true,
+            message: message.into(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            message: message.into(),
+        }
+    }
+}
+
+/// Control server embedded in `snipe start`
+pub struct ControlServer {
+    config: ControlConfig,
+    position_manager: Arc<PositionManager>,
+    helius: Option<Arc<HeliusClient>>,
+    strategy_engine: Option<Arc<tokio::sync::RwLock<StrategyEngine>>>,
+    advisor: Option<Arc<AiAdvisor>>,
+    reloader: Option<Arc<tokio::sync::Mutex<ConfigReloader>>>,
+    exit_requests: Option<tokio::sync::mpsc::Sender<ExitRequest>>,
+    cooldowns: Option<Arc<CooldownTracker>>,
+    wallet_tracker: Option<Arc<WalletTracker>>,
+    /// Session token every command must carry
+    token: String,
+}
+
+impl ControlServer {
+    pub fn new(config: ControlConfig, position_manager: Arc<PositionManager>) -> Self {
+        Self {
+            config,
+            position_manager,
+            helius: None,
+            strategy_engine: None,
+            advisor: None,
+            reloader: None,
+            exit_requests: None,
+            cooldowns: None,
+            wallet_tracker: None,
+            token: generate_token(),
+        }
+    }
+
+    /// Report this client's request accounting on `helius_status`
+    pub fn with_helius(mut self, helius: Arc<HeliusClient>) -> Self {
+        self.helius = Some(helius);
+        self
+    }
+
+    /// Accept `use_strategy` profile switches for this engine
+    pub fn with_strategy_engine(
+        mut self,
+        engine: Arc<tokio::sync::RwLock<StrategyEngine>>,
+    ) -> Self {
+        self.strategy_engine = Some(engine);
+        self
+    }
+
+    /// Accept proposal approvals for this advisor
+    pub fn with_advisor(mut self, advisor: Arc<AiAdvisor>) -> Self {
+        self.advisor = Some(advisor);
+        self
+    }
+
+    /// Accept `reload` requests for this reloader
+    pub fn with_reloader(mut self, reloader: Arc<tokio::sync::Mutex<ConfigReloader>>) -> Self {
+        self.reloader = Some(reloader);
+        self
+    }
+
+    /// Send `close` requests to the position monitor on this channel
+    pub fn with_exit_requests(mut self, requests: tokio::sync::mpsc::Sender<ExitRequest>) -> Self {
+        self.exit_requests = Some(requests);
+        self
+    }
+
+    /// Put abandoned mints on re-entry cooldown here
+    pub fn with_cooldowns(mut self, cooldowns: Arc<CooldownTracker>) -> Self {
+        self.cooldowns = Some(cooldowns);
+        self
+    }
+
+    /// Accept `track_wallet` / `untrack_wallet` for this tracker
+    pub fn with_wallet_tracker(mut self, tracker: Arc<WalletTracker>) -> Self {
+        self.wallet_tracker = Some(tracker);
+        self
+    }
+
+    /// Write the session token to `token_path`, bind the listener and serve
+    /// commands in the background
+    pub async fn start(self, token_path: &Path) -> Result<()> {
+        write_token(token_path, &self.token)?;
+        let listener = TcpListener::bind(&self.config.listen_addr)
+            .await
+            .map_err(|e| Error::Control(format!("bind {}: {}", self.config.listen_addr, e)))?;
+        let local = listener.local_addr()?;
+        if !local.ip().is_loopback() {
+            return Err(Error::Control(format!(
+                "refusing to listen on non-loopback address {}",
+                local
+            )));
+        }
+        info!("Control channel listening on {}", local);
+
+        let server = Arc::new(self);
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        let server = server.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = server.handle_connection(stream).await {
+                                warn!("Control connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("Control accept failed: {}", e),
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn handle_connection(&self, stream: TcpStream) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            let Some(request) = self.authorize(&line) else {
+                let response = ControlResponse::error("missing or wrong control token");
+                write_response(&mut writer, &response).await?;
+                return Err(Error::Control(
+                    "closed a connection that sent no valid token".to_string(),
+                ));
+            };
+            let response = match serde_json::from_value::<ControlCommand>(request) {
+                Ok(command) => self.handle(command).await,
+                Err(e) => ControlResponse::error(format!("invalid command: {}", e)),
+            };
+            write_response(&mut writer, &response).await?;
+        }
+
+        Ok(())
+    }
+
+    /// The request on `line`, without its token, if the token is this session's
+    fn authorize(&self, line: &str) -> Option<serde_json::Value> {
+        let mut request: serde_json::Value = serde_json::from_str(line).ok()?;
+        let token = request.as_object_mut()?.remove("token")?;
+        tokens_match(token.as_str()?, &self.token).then_some(request)
+    }
+
+    async fn handle(&self, command: ControlCommand) -> ControlResponse {
+        info!("Control command: {:?}", command);
+        match command {
+            ControlCommand::SetExitOverride {
+                mint,
+                stop_loss_pct,
+                take_profit_pct,
+                trailing_pct,
+            } => match self
+                .position_manager
+                .set_exit_override(&mint, stop_loss_pct, take_profit_pct, trailing_pct)
+                .await
+            {
+                Ok(o) => ControlResponse::ok(format!("override set for {}: {:?}", mint, o)),
+                Err(e) => ControlResponse::error(e.to_string()),
+            },
+            ControlCommand::Hold { mint, until_secs } => {
+                match self.position_manager.hold_position(&mint, until_secs).await {
+                    Ok(_) => ControlResponse::ok(format!(
+                        "holding {} for {}s (kill-switch still active)",
+                        mint, until_secs
+                    )),
+                    Err(e) => ControlResponse::error(e.to_string()),
+                }
+            }
+            ControlCommand::HeliusStatus => match self.helius {
+                Some(ref helius) => match serde_json::to_string(&helius.budget().stats()) {
+                    Ok(stats) => ControlResponse::ok(stats),
+                    Err(e) => ControlResponse::error(e.to_string()),
+                },
+                None => ControlResponse::error("Helius client not configured (smart_money off?)"),
+            },
+            ControlCommand::UseStrategy { profile } => match self.strategy_engine {
+                Some(ref engine) => {
+                    let mut engine = engine.write().await;
+                    let previous = engine.active_profile().to_string();
+                    match engine.use_profile(&profile).await {
+                        Ok(()) => ControlResponse::ok(format!(
+                            "strategy profile {} -> {} (open positions keep their exits)",
+                            previous, profile
+                        )),
+                        Err(e) => ControlResponse::error(e),
+                    }
+                }
+                None => ControlResponse::error("strategy engine not enabled"),
+            },
+            ControlCommand::ApproveProposal { id } => match self.advisor {
+                Some(ref advisor) => match advisor.approve(&id).await {
+                    Ok(p) => {
+                        let message = format!(
+                            "{} {}: {}",
+                            p.status,
+                            p.action,
+                            p.outcome.unwrap_or_default()
+                        );
+                        if p.status == crate::wallet::ProposalStatus::Executed {
+                            ControlResponse::ok(message)
+                        } else {
+                            ControlResponse::error(message)
+                        }
+                    }
+                    Err(e) => ControlResponse::error(e.to_string()),
+                },
+                None => ControlResponse::error("AI advisor not running"),
+            },
+            ControlCommand::RejectProposal { id } => match self.advisor {
+                Some(ref advisor) => match advisor.reject(&id).await {
+                    Ok(p) => ControlResponse::ok(format!("rejected {} ({})", p.id, p.action)),
+                    Err(e) => ControlResponse::error(e.to_string()),
+                },
+                None => ControlResponse::error("AI advisor not running"),
+            },
+            ControlCommand::Reload => match self.reloader {
+                Some(ref reloader) => match reloader.lock().await.reload() {
+                    Ok(outcome) => ControlResponse::ok(outcome.to_string()),
+                    Err(e) => ControlResponse::error(format!("config not reloaded: {}", e)),
+                },
+                None => ControlResponse::error("config reloads not available"),
+            },
+            ControlCommand::ClosePosition { mint } => self.close_position(mint).await,
+            ControlCommand::AbandonPosition { mint } => self.abandon_position(&mint).await,
+            ControlCommand::ShowPosition { mint } => {
+                match self.position_manager.get_position(&mint).await {
+                    Some(position) => match serde_json::to_string(&position) {
+                        Ok(json) => ControlResponse::ok(json),
+                        Err(e) => ControlResponse::error(e.to_string()),
+                    },
+                    None => ControlResponse::error(format!("no open position in {}", mint)),
+                }
+            }
+            ControlCommand::TrackWallet {
+                address,
+                size_mult,
+                mirror_sells,
+            } => match self.wallet_tracker {
+                Some(ref tracker) => match tracker.add_wallet(&address, size_mult, mirror_sells) {
+                    Ok(true) => ControlResponse::ok(format!("tracking {}", address)),
+                    Ok(false) => ControlResponse::ok(format!("updated {}", address)),
+                    Err(e) => ControlResponse::error(e.to_string()),
+                },
+                None => ControlResponse::error("wallet tracker not running"),
+            },
+            ControlCommand::UntrackWallet { address } => match self.wallet_tracker {
+                Some(ref tracker) => match tracker.remove_wallet(&address) {
+                    Ok(true) => ControlResponse::ok(format!("stopped tracking {}", address)),
+                    Ok(false) => ControlResponse::error(format!("{} is not tracked", address)),
+                    Err(e) => ControlResponse::error(e.to_string()),
+                },
+                None => ControlResponse::error("wallet tracker not running"),
+            },
+        }
+    }
+
+    async fn close_position(&self, mint: String) -> ControlResponse {
+        let Some(ref requests) = self.exit_requests else {
+            return ControlResponse::error("position monitor not running");
+        };
+        if self.position_manager.get_position(&mint).await.is_none() {
+            return ControlResponse::error(format!("no open position in {}", mint));
+        }
+        if self.position_manager.is_sell_in_flight(&mint) {
+            return ControlResponse::error(format!("a sell of {} is in flight", mint));
+        }
+        // A manual hold would keep the monitor from acting on the request
+        if let Err(e) = self.position_manager.hold_position(&mint, 0).await {
+            return ControlResponse::error(e.to_string());
+        }
+        let request = ExitRequest {
+            mint: mint.clone(),
+            signal: ExitSignal::manual(),
+        };
+        match requests.send(request).await {
+            Ok(()) => ControlResponse::ok(format!(
+                "close of {} queued; the position monitor sells it on its next pass",
+                mint
+            )),
+            Err(_) => ControlResponse::error("position monitor not running"),
+        }
+    }
+
+    async fn abandon_position(&self, mint: &str) -> ControlResponse {
+        let net_pnl_sol = match self.position_manager.abandon_with_loss(mint).await {
+            Ok(pnl) => pnl,
+            Err(e) => return ControlResponse::error(e.to_string()),
+        };
+        if let Some(ref cooldowns) = self.cooldowns {
+            let reason = Some("abandoned by operator".to_string());
+            if let Err(e) = cooldowns.mark(mint, CooldownKind::Failed, reason).await {
+                warn!("Failed to record cooldown for {}: {}", mint, e);
+            }
+        }
+        ControlResponse::ok(format!(
+            "abandoned {} ({:+.4} SOL recorded)",
+            mint, net_pnl_sol
+        ))
+    }
+}
+
+async fn write_response(
+    writer: &mut tokio::net::tcp::OwnedWriteHalf,
+    response: &ControlResponse,
+) -> Result<()> {
+    let mut data = serde_json::to_string(response)?;
+    data.push('\n');
+    writer.write_all(data.as_bytes()).await?;
+    Ok(())
+}
+
+/// 32 random bytes, hex-encoded
+fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare without an early exit on the first differing byte
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Write the token readable by the owner only, replacing any earlier one
+fn write_token(path: &Path, token: &str) -> Result<()> {
+    let tmp_path = path.with_extension("token.tmp");
+    let _ = std::fs::remove_file(&tmp_path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let write = || -> std::io::Result<()> {
+        use std::io::Write;
+        options.open(&tmp_path)?.write_all(token.as_bytes())?;
+        std::fs::rename(&tmp_path, path)
+    };
+    write().map_err(|e| Error::Control(format!("write {}: {}", path.display(), e)))
+}
+
+/// Send a command to a running bot
+///
+/// Returns `Error::Control` if no bot is listening on the endpoint or its
+/// token file can't be read.
+pub async fn send_command(
+    endpoint: &ControlEndpoint,
+    command: &ControlCommand,
+) -> Result<ControlResponse> {
+    let token = std::fs::read_to_string(&endpoint.token_path).map_err(|e| {
+        Error::Control(format!(
+            "read control token {}: {}",
+            endpoint.token_path.display(),
+            e
+        ))
+    })?;
+    let stream = TcpStream::connect(&endpoint.addr)
+        .await
+        .map_err(|e| Error::Control(format!("no running bot at {}: {}", endpoint.addr, e)))?;
+    let (reader, mut writer) = stream.into_split();
+
+    let mut request = serde_json::to_value(command)?;
+    if let Some(fields) = request.as_object_mut() {
+        fields.insert("token".to_string(), token.trim().into());
+    }
+    let mut data = serde_json::to_string(&request)?;
+    data.push('\n');
+    writer.write_all(data.as_bytes()).await?;
+
+    let mut lines = BufReader::new(reader).lines();
+    let line = lines
+        .next_line()
+        .await?
+        .ok_or_else(|| Error::Control("connection closed without response".to_string()))?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_roundtrip() {
+        let cmd = ControlCommand::Hold {
+            mint: "mint".to_string(),
+            until_secs: 60,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains(r#""command":"hold""#));
+        assert_eq!(
+            serde_json::to_string(&ControlCommand::HeliusStatus).unwrap(),
+            r#"{"command":"helius_status"}"#
+        );
+
+        let parsed: ControlCommand = serde_json::from_str(
+            r#"{"command":"set_exit_override","mint":"m","stop_loss_pct":25.0,"take_profit_pct":null,"trailing_pct":null}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            parsed,
+            ControlCommand::SetExitOverride { stop_loss_pct: Some(_), .. }
+        ));
+
+        let parsed: ControlCommand =
+            serde_json::from_str(r#"{"command":"use_strategy","profile":"cautious"}"#).unwrap();
+        assert!(matches!(parsed, ControlCommand::UseStrategy { profile } if profile == "cautious"));
+        assert_eq!(
+            serde_json::to_string(&ControlCommand::Reload).unwrap(),
+            r#"{"command":"reload"}"#
+        );
+        let parsed: ControlCommand =
+            serde_json::from_str(r#"{"command":"abandon_position","mint":"m"}"#).unwrap();
+        assert!(matches!(parsed, ControlCommand::AbandonPosition { mint } if mint == "m"));
+        let parsed: ControlCommand = serde_json::from_str(
+            r#"{"command":"track_wallet","address":"w","size_mult":0.5,"mirror_sells":null}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            parsed,
+            ControlCommand::TrackWallet { size_mult, mirror_sells: None, .. } if size_mult == 0.5
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_without_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = ControlEndpoint {
+            addr: "127.0.0.1:1".to_string(),
+            token_path: dir.path().join(TOKEN_FILE),
+        };
+        write_token(&endpoint.token_path, &generate_token()).unwrap();
+        let cmd = ControlCommand::Hold {
+            mint: "mint".to_string(),
+            until_secs: 1,
+        };
+        let result = send_command(&endpoint, &cmd).await;
+        assert!(matches!(result, Err(Error::Control(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_commands_without_token() {
+        let manager = Arc::new(PositionManager::new(
+            crate::config::Config::default().safety,
+            None,
+        ));
+        let server = Arc::new(ControlServer::new(ControlConfig::default(), manager));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let serving = server.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let _ = serving.handle_connection(stream).await;
+            }
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let endpoint = ControlEndpoint {
+            addr,
+            token_path: dir.path().join(TOKEN_FILE),
+        };
+        let cmd = ControlCommand::ShowPosition {
+            mint: "m".to_string(),
+        };
+
+        // No token file: nothing is sent
+        assert!(matches!(
+            send_command(&endpoint, &cmd).await,
+            Err(Error::Control(_))
+        ));
+        assert!(server.authorize(r#"{"command":"reload"}"#).is_none());
+
+        std::fs::write(&endpoint.token_path, "not-the-token").unwrap();
+        let response = send_command(&endpoint, &cmd).await.unwrap();
+        assert!(!response.ok);
+        assert!(response.message.contains("control token"));
+
+        write_token(&endpoint.token_path, &server.token).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&endpoint.token_path)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let response = send_command(&endpoint, &cmd).await.unwrap();
+        assert_eq!(response.message, "no open position in m");
+    }
+}
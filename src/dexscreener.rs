@@ -1,10 +1,49 @@
 // DexScreener API client for hot token discovery
+//
+// One client is meant to be shared (Arc) by everything that prices tokens
+// in a process. Requests go through token buckets sized to DexScreener's
+// published limits, token lookups are cached for a few seconds by mint, and
+// `get_tokens` prices up to 30 mints per request.
 use anyhow::Result;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
+use crate::filter::types::{DiscoverySource, OrderFlowAnalysis, SignalContext};
+
 const DEXSCREENER_BASE: &str = "https://api.dexscreener.com";
 
+/// Pair and token endpoints: 300 requests per minute
+const PAIRS_PER_MINUTE: f64 = 300.0;
+
+/// Profile and boost endpoints: 60 requests per minute
+const LISTINGS_PER_MINUTE: f64 = 60.0;
+
+/// Requests a bucket lets through back to back
+const BURST: f64 = 5.0;
+
+/// Addresses per `/tokens/v1` request
+const MAX_BATCH: usize = 30;
+
+/// How long a token lookup is reused
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Pause after a 429 without a Retry-After header
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(5);
+
+/// Cached lookups kept before expired ones are dropped
+const MAX_CACHE_ENTRIES: usize = 1024;
+
+/// Hourly volume up to this multiple of pool liquidity counts as organic
+const ORGANIC_TURNOVER: f64 = 5.0;
+
+/// Hourly volume at this multiple of pool liquidity counts as all wash
+const WASH_TURNOVER: f64 = 20.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenProfile {
     pub url: Option<String>,
@@ -188,6 +227,61 @@ impl HotToken {
 
         momentum_score + activity_score + ratio_score + boost_score + h1_bonus
     }
+
+    /// Market cap in SOL (pump.fun mints have a fixed 1B supply)
+    pub fn market_cap_sol(&self) -> f64 {
+        self.price_native * (crate::pump::program::TOKEN_TOTAL_SUPPLY / 1_000_000) as f64
+    }
+
+    /// The 5-minute transaction counts as an order flow analysis
+    ///
+    /// DexScreener has no per-wallet data, so unique buyers stay 0 and wash
+    /// trading is estimated from hourly volume against pool liquidity.
+    pub fn order_flow(&self) -> OrderFlowAnalysis {
+        let trades = (self.buys_5m + self.sells_5m) as f64;
+        let turnover = if self.liquidity_usd > 0.0 {
+            self.volume_h1 / self.liquidity_usd
+        } else {
+            0.0
+        };
+        let wash =
+            ((turnover - ORGANIC_TURNOVER) / (WASH_TURNOVER - ORGANIC_TURNOVER)).clamp(0.0, 1.0);
+        OrderFlowAnalysis {
+            mint: self.mint.clone(),
+            analysis_window_secs: 300,
+            buy_sell_ratio: self.buy_sell_ratio,
+            buy_sell_ratio_10s: self.buy_sell_ratio,
+            trades_per_minute: trades / 5.0,
+            wash_trading_score: wash,
+            organic_score: 1.0 - wash,
+            early_sell_pressure: if trades > 0.0 {
+                self.sells_5m as f64 / trades
+            } else {
+                0.0
+            },
+            sustained_buying: self.buy_sell_ratio > 1.0 && self.h1_change > 0.0,
+            analyzed_at: chrono::Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    /// Adaptive filter context for this token, with its order flow attached
+    pub fn signal_context(&self, creator: &str) -> SignalContext {
+        let mut context = SignalContext::from_new_token(
+            self.mint.clone(),
+            self.name.clone(),
+            self.symbol.clone(),
+            String::new(),
+            creator.to_string(),
+            String::new(),
+            0,
+            0,
+            0,
+            self.market_cap_sol(),
+        );
+        context.order_flow = Some(self.order_flow());
+        context.with_discovery(DiscoverySource::HotScan, None)
+    }
 }
 
 /// Configuration for hot token scanning
@@ -230,8 +324,149 @@ impl Default for HotScanConfig {
     }
 }
 
+/// Token bucket for one family of endpoints
+///
+/// Callers reserve a slot and sleep until it comes up, so concurrent users
+/// queue instead of bursting into 429s. A 429 blocks the bucket for the
+/// server's Retry-After; requests in that window fail fast.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    per_sec: f64,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    /// Negative while requests are queued
+    tokens: f64,
+    refilled_at: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(per_minute: f64, burst: f64) -> Self {
+        Self {
+            per_sec: per_minute / 60.0,
+            burst,
+            state: Mutex::new(BucketState {
+                tokens: burst,
+                refilled_at: Instant::now(),
+                blocked_until: None,
+            }),
+        }
+    }
+
+    /// Take a slot: how long to wait for it, or Err with the time left on a 429 block
+    fn reserve_at(&self, now: Instant) -> std::result::Result<Duration, Duration> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(until) = state.blocked_until {
+            if now < until {
+                return Err(until - now);
+            }
+            state.blocked_until = None;
+        }
+        let elapsed = now
+            .saturating_duration_since(state.refilled_at)
+            .as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.per_sec).min(self.burst);
+        state.refilled_at = now;
+        state.tokens -= 1.0;
+        Ok(if state.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.tokens / self.per_sec)
+        })
+    }
+
+    pub(crate) async fn acquire(&self) -> Result<()> {
+        match self.reserve_at(Instant::now()) {
+            Ok(wait) => {
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+                Ok(())
+            }
+            Err(left) => anyhow::bail!("Rate limited, retrying in {}s", left.as_secs().max(1)),
+        }
+    }
+
+    /// Stop sending for `retry_after`
+    pub(crate) fn block_at(&self, now: Instant, retry_after: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.blocked_until = Some(now + retry_after);
+        state.tokens = state.tokens.min(0.0);
+    }
+}
+
+/// How long a 429 response asks us to back off
+pub(crate) fn retry_after(resp: &reqwest::Response) -> Duration {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .map_or(DEFAULT_RETRY_AFTER, Duration::from_secs)
+}
+
+/// Request and cache counters
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    rate_limited: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+}
+
+/// Client counters since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct DexScreenerStats {
+    /// HTTP requests sent
+    pub requests: u64,
+    /// 429 responses
+    pub rate_limited: u64,
+    /// Token lookups answered from the cache
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+}
+
+impl DexScreenerStats {
+    /// Share of token lookups answered from the cache (None before the first)
+    pub fn hit_rate(&self) -> Option<f64> {
+        let total = self.cache_hits + self.cache_misses;
+        (total > 0).then(|| self.cache_hits as f64 / total as f64)
+    }
+}
+
+impl std::fmt::Display for DexScreenerStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requests, {} rate limited",
+            self.requests, self.rate_limited
+        )?;
+        if let Some(rate) = self.hit_rate() {
+            write!(f, ", {:.0}% cache hits", rate * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// The pair to price a token from: pump.fun / PumpSwap first
+fn preferred_pair(pairs: &[DexPair]) -> Option<&DexPair> {
+    pairs
+        .iter()
+        .find(|p| p.dex_id == "pumpswap" || p.dex_id == "pumpfun")
+        .or_else(|| pairs.first())
+}
+
 pub struct DexScreenerClient {
     client: reqwest::Client,
+    pairs_limiter: RateLimiter,
+    listings_limiter: RateLimiter,
+    cache_ttl: Duration,
+    /// mint -> (fetched at, preferred pair; None = not listed)
+    cache: Mutex<HashMap<String, (Instant, Option<DexPair>)>>,
+    counters: Counters,
 }
 
 impl DexScreenerClient {
@@ -241,41 +476,150 @@ impl DexScreenerClient {
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .unwrap_or_default(),
+            pairs_limiter: RateLimiter::new(PAIRS_PER_MINUTE, BURST),
+            listings_limiter: RateLimiter::new(LISTINGS_PER_MINUTE, BURST),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache: Mutex::new(HashMap::new()),
+            counters: Counters::default(),
+        }
+    }
+
+    /// Reuse token lookups for `ttl` (zero disables the cache)
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    pub fn stats(&self) -> DexScreenerStats {
+        let c = &self.counters;
+        DexScreenerStats {
+            requests: c.requests.load(Ordering::Relaxed),
+            rate_limited: c.rate_limited.load(Ordering::Relaxed),
+            cache_hits: c.cache_hits.load(Ordering::Relaxed),
+            cache_misses: c.cache_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// GET `url` once `limiter` allows it
+    async fn fetch<T: DeserializeOwned>(&self, limiter: &RateLimiter, url: &str) -> Result<T> {
+        limiter.acquire().await?;
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+        let resp = self.client.get(url).send().await?;
+        if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            self.counters.rate_limited.fetch_add(1, Ordering::Relaxed);
+            let retry_after = retry_after(&resp);
+            limiter.block_at(Instant::now(), retry_after);
+            anyhow::bail!(
+                "DexScreener rate limited (429), pausing {}s",
+                retry_after.as_secs()
+            );
+        }
+        Ok(resp.error_for_status()?.json().await?)
+    }
+
+    /// The cached lookup of `mint`, if still fresh (Some(None) = not listed)
+    fn cached(&self, mint: &str) -> Option<Option<DexPair>> {
+        let cache = self.cache.lock().unwrap();
+        let hit = cache
+            .get(mint)
+            .filter(|(at, _)| at.elapsed() < self.cache_ttl)
+            .map(|(_, pair)| pair.clone());
+        let counter = if hit.is_some() {
+            &self.counters.cache_hits
+        } else {
+            &self.counters.cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        hit
+    }
+
+    fn store(&self, mint: &str, pair: Option<DexPair>) {
+        if self.cache_ttl.is_zero() {
+            return;
+        }
+        let mut cache = self.cache.lock().unwrap();
+        if cache.len() >= MAX_CACHE_ENTRIES {
+            let ttl = self.cache_ttl;
+            cache.retain(|_, (at, _)| at.elapsed() < ttl);
         }
+        cache.insert(mint.to_string(), (Instant::now(), pair));
     }
 
     /// Fetch latest token profiles
     pub async fn get_latest_profiles(&self) -> Result<Vec<TokenProfile>> {
         let url = format!("{}/token-profiles/latest/v1", DEXSCREENER_BASE);
-        let resp = self.client.get(&url).send().await?;
-        let profiles: Vec<TokenProfile> = resp.json().await?;
-        Ok(profiles)
+        self.fetch(&self.listings_limiter, &url).await
     }
 
     /// Fetch top boosted tokens
     pub async fn get_top_boosts(&self) -> Result<Vec<TokenBoost>> {
         let url = format!("{}/token-boosts/top/v1", DEXSCREENER_BASE);
-        let resp = self.client.get(&url).send().await?;
-        let boosts: Vec<TokenBoost> = resp.json().await?;
-        Ok(boosts)
+        self.fetch(&self.listings_limiter, &url).await
     }
 
     /// Fetch token pairs/details
     pub async fn get_token_pairs(&self, mint: &str) -> Result<Option<DexPair>> {
+        if let Some(pair) = self.cached(mint) {
+            return Ok(pair);
+        }
         let url = format!("{}/latest/dex/tokens/{}", DEXSCREENER_BASE, mint);
-        let resp = self.client.get(&url).send().await?;
-        let data: TokenPairsResponse = resp.json().await?;
+        let data: TokenPairsResponse = self.fetch(&self.pairs_limiter, &url).await?;
 
         // Prefer pumpswap/pumpfun pairs
-        if let Some(pairs) = data.pairs {
-            let pair = pairs
-                .iter()
-                .find(|p| p.dex_id == "pumpswap" || p.dex_id == "pumpfun")
-                .or_else(|| pairs.first())
-                .cloned();
-            return Ok(pair);
+        let pair = data.pairs.as_deref().and_then(preferred_pair).cloned();
+        self.store(mint, pair.clone());
+        Ok(pair)
+    }
+
+    /// Preferred pair of each listed Solana mint, batched `MAX_BATCH` per request
+    ///
+    /// Mints DexScreener doesn't list are left out of the result (and cached
+    /// as not listed, so a `get_token_pairs` right after doesn't refetch them).
+    pub async fn get_tokens(&self, mints: &[String]) -> Result<HashMap<String, DexPair>> {
+        let mut found = HashMap::new();
+        let mut missing = Vec::new();
+        for mint in mints {
+            match self.cached(mint) {
+                Some(Some(pair)) => {
+                    found.insert(mint.clone(), pair);
+                }
+                Some(None) => {}
+                None if !missing.contains(mint) => missing.push(mint.clone()),
+                None => {}
+            }
+        }
+
+        for chunk in missing.chunks(MAX_BATCH) {
+            let url = format!("{}/tokens/v1/solana/{}", DEXSCREENER_BASE, chunk.join(","));
+            let pairs: Vec<DexPair> = self.fetch(&self.pairs_limiter, &url).await?;
+            let mut by_mint: HashMap<&str, Vec<DexPair>> = HashMap::new();
+            for pair in pairs {
+                if let Some(mint) = chunk.iter().find(|m| **m == pair.base_token.address) {
+                    by_mint.entry(mint.as_str()).or_default().push(pair);
+                }
+            }
+            for mint in chunk {
+                let pair = by_mint
+                    .get(mint.as_str())
+                    .and_then(|pairs| preferred_pair(pairs))
+                    .cloned();
+                self.store(mint, pair.clone());
+                if let Some(pair) = pair {
+                    found.insert(mint.clone(), pair);
+                }
+            }
         }
-        Ok(None)
+        Ok(found)
+    }
+
+    /// Fetch one pair by its pair address
+    pub async fn get_pair(&self, chain: &str, pair_address: &str) -> Result<Option<DexPair>> {
+        let url = format!(
+            "{}/latest/dex/pairs/{}/{}",
+            DEXSCREENER_BASE, chain, pair_address
+        );
+        let data: TokenPairsResponse = self.fetch(&self.pairs_limiter, &url).await?;
+        Ok(data.pairs.and_then(|pairs| pairs.into_iter().next()))
     }
 
     /// Convert DexPair to HotToken with metrics
@@ -369,22 +713,24 @@ impl DexScreenerClient {
                         solana_profiles.len()
                     );
 
-                    for profile in solana_profiles {
-                        if seen_mints.contains(&profile.token_address) {
-                            continue;
-                        }
-                        seen_mints.insert(profile.token_address.clone());
-
-                        if let Ok(Some(pair)) = self.get_token_pairs(&profile.token_address).await {
-                            let hot =
-                                self.pair_to_hot_token(&profile.token_address, &pair, false, 0.0);
-                            if hot.is_hot(config) {
-                                hot_tokens.push(hot);
+                    let mints: Vec<String> = solana_profiles
+                        .into_iter()
+                        .map(|p| p.token_address)
+                        .filter(|mint| seen_mints.insert(mint.clone()))
+                        .collect();
+                    match self.get_tokens(&mints).await {
+                        Ok(pairs) => {
+                            for mint in &mints {
+                                let Some(pair) = pairs.get(mint) else {
+                                    continue;
+                                };
+                                let hot = self.pair_to_hot_token(mint, pair, false, 0.0);
+                                if hot.is_hot(config) {
+                                    hot_tokens.push(hot);
+                                }
                             }
                         }
-
-                        // Rate limiting
-                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        Err(e) => warn!("Failed to price profiles: {}", e),
                     }
                 }
                 Err(e) => warn!("Failed to fetch profiles: {}", e),
@@ -404,27 +750,31 @@ impl DexScreenerClient {
 
                     info!("Checking {} boosted Solana tokens", solana_boosts.len());
 
-                    for boost in solana_boosts {
-                        if seen_mints.contains(&boost.token_address) {
-                            continue;
-                        }
-                        seen_mints.insert(boost.token_address.clone());
-
-                        if let Ok(Some(pair)) = self.get_token_pairs(&boost.token_address).await {
-                            let boost_amount = boost.total_amount.unwrap_or(0.0);
-                            let hot = self.pair_to_hot_token(
-                                &boost.token_address,
-                                &pair,
-                                true,
-                                boost_amount,
-                            );
-                            if hot.is_hot(config) {
-                                hot_tokens.push(hot);
+                    let boosts: Vec<TokenBoost> = solana_boosts
+                        .into_iter()
+                        .filter(|b| seen_mints.insert(b.token_address.clone()))
+                        .collect();
+                    let mints: Vec<String> =
+                        boosts.iter().map(|b| b.token_address.clone()).collect();
+                    match self.get_tokens(&mints).await {
+                        Ok(pairs) => {
+                            for boost in &boosts {
+                                let Some(pair) = pairs.get(&boost.token_address) else {
+                                    continue;
+                                };
+                                let boost_amount = boost.total_amount.unwrap_or(0.0);
+                                let hot = self.pair_to_hot_token(
+                                    &boost.token_address,
+                                    pair,
+                                    true,
+                                    boost_amount,
+                                );
+                                if hot.is_hot(config) {
+                                    hot_tokens.push(hot);
+                                }
                             }
                         }
-
-                        // Rate limiting
-                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        Err(e) => warn!("Failed to price boosts: {}", e),
                     }
                 }
                 Err(e) => warn!("Failed to fetch boosts: {}", e),
@@ -456,3 +806,71 @@ impl Default for DexScreenerClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_queues_and_blocks() {
+        let limiter = RateLimiter::new(60.0, 2.0);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve_at(now), Ok(Duration::ZERO));
+        assert_eq!(limiter.reserve_at(now), Ok(Duration::ZERO));
+        // Bucket empty: the next two wait one and two refills (1/s)
+        assert_eq!(limiter.reserve_at(now), Ok(Duration::from_secs(1)));
+        assert_eq!(limiter.reserve_at(now), Ok(Duration::from_secs(2)));
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.reserve_at(later), Ok(Duration::ZERO));
+        limiter.block_at(later, Duration::from_secs(5));
+        assert_eq!(
+            limiter.reserve_at(later + Duration::from_secs(2)),
+            Err(Duration::from_secs(3))
+        );
+        assert!(limiter.reserve_at(later + Duration::from_secs(6)).is_ok());
+
+        let stats = DexScreenerStats {
+            cache_hits: 3,
+            cache_misses: 1,
+            ..Default::default()
+        };
+        assert_eq!(stats.hit_rate(), Some(0.75));
+        assert_eq!(DexScreenerStats::default().hit_rate(), None);
+    }
+
+    #[test]
+    fn test_hot_token_order_flow() {
+        let mut token = HotToken {
+            mint: "Mint1111pump".to_string(),
+            symbol: "HOT".to_string(),
+            name: "Hot".to_string(),
+            price_native: 0.00000005,
+            m5_change: 12.0,
+            h1_change: 30.0,
+            buys_5m: 60,
+            sells_5m: 20,
+            buy_sell_ratio: 3.0,
+            market_cap: 50_000.0,
+            liquidity_usd: 20_000.0,
+            volume_h1: 60_000.0,
+            is_boosted: false,
+            boost_amount: 0.0,
+            dex_id: "pumpswap".to_string(),
+        };
+        let flow = token.order_flow();
+        assert_eq!(flow.trades_per_minute, 16.0);
+        assert_eq!(flow.early_sell_pressure, 0.25);
+        assert_eq!(flow.wash_trading_score, 0.0);
+        assert!((token.market_cap_sol() - 50.0).abs() < 1e-9);
+
+        // 15x hourly turnover on a thin pool is mostly wash
+        token.volume_h1 = 300_000.0;
+        let flow = token.order_flow();
+        assert!((flow.wash_trading_score - 2.0 / 3.0).abs() < 1e-9);
+
+        let context = token.signal_context("");
+        assert_eq!(context.discovery_source, DiscoverySource::HotScan);
+        assert!(context.order_flow.is_some());
+    }
+}
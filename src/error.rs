@@ -1,7 +1,21 @@
 //! Error types for the sniper bot
-
+//!
+//! Every variant belongs to an `ErrorCategory`, which decides whether the
+//! failed operation is worth retrying and how long to wait first. Errors
+//! from outside (RPC client errors, failed transactions, PumpPortal and
+//! Jupiter responses) are classified when they are converted, so retry
+//! loops branch on `is_retryable()` instead of matching message text.
+
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_request::RpcError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
+use crate::pump::program::PROGRAM_ERRORS;
+
 /// Result type alias using our custom Error
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -21,10 +35,28 @@ pub enum Error {
     #[error("Insecure keypair permissions: {0}")]
     InsecureKeypair(String),
 
-    // RPC errors
+    #[error("Keypair decryption failed: {0}")]
+    KeypairDecryption(String),
+
+    #[error("Signing failed ({backend}): {reason}")]
+    Signing { backend: String, reason: String },
+
+    // Network / RPC errors
+    #[error("Network error: {0}")]
+    Network(String),
+
     #[error("RPC error: {0}")]
     Rpc(String),
 
+    #[error("RPC request rejected: {0}")]
+    RpcFatal(String),
+
+    #[error("{api} rate limited")]
+    ApiRateLimited {
+        api: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("RPC timeout after {0}ms")]
     RpcTimeout(u64),
 
@@ -48,6 +80,9 @@ pub enum Error {
     #[error("Bonding curve decode failed: {0}")]
     BondingCurveDecode(String),
 
+    #[error("PumpSwap pool decode failed: {0}")]
+    PoolDecode(String),
+
     #[error("Price calculation overflow")]
     PriceOverflow,
 
@@ -64,8 +99,14 @@ pub enum Error {
     #[error("Transaction send failed: {0}")]
     TransactionSend(String),
 
-    #[error("Slippage exceeded: expected {expected}, got {actual}")]
-    SlippageExceeded { expected: u64, actual: u64 },
+    #[error("Slippage exceeded: {0}")]
+    SlippageExceeded(String),
+
+    #[error("Bonding curve complete: {0}")]
+    CurveComplete(String),
+
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
 
     // Jito errors
     #[error("Jito bundle submission failed: {0}")]
@@ -84,6 +125,29 @@ pub enum Error {
     #[error("Position persistence failed: {0}")]
     PositionPersistence(String),
 
+    #[error("Sell already in flight for {0}")]
+    SellInFlight(String),
+
+    #[error("Mint {0} is locked by another trade ({1})")]
+    MintLocked(String, String),
+
+    // Control channel errors
+    #[error("Control channel error: {0}")]
+    Control(String),
+
+    #[error("Another snipe instance is running: {0}")]
+    AlreadyRunning(String),
+
+    #[error("Shutting down: no new entries")]
+    ShuttingDown,
+
+    #[error("New entries paused: {0}")]
+    EntriesPaused(String),
+
+    // Notification errors
+    #[error("Notification failed: {0}")]
+    Notification(String),
+
     // Safety limit errors
     #[error("Safety limit exceeded: {0}")]
     SafetyLimitExceeded(String),
@@ -91,9 +155,19 @@ pub enum Error {
     #[error("Daily loss limit reached: lost {lost}SOL, limit is {limit}SOL")]
     DailyLossLimitReached { lost: f64, limit: f64 },
 
+    #[error("Daily {kind} limit reached: {count} today, limit is {limit}")]
+    DailyTradeLimitReached {
+        kind: &'static str,
+        count: u32,
+        limit: u32,
+    },
+
     #[error("Max position size exceeded: current {current}SOL + buy {buy}SOL > max {max}SOL")]
     MaxPositionExceeded { current: f64, buy: f64, max: f64 },
 
+    #[error("Max concurrent positions reached: {current} open/reserved, max {max}")]
+    MaxConcurrentPositions { current: usize, max: usize },
+
     // Wallet management errors
     #[error("Wallet not found: {0}")]
     WalletNotFound(String),
@@ -139,26 +213,166 @@ pub enum Error {
     Anyhow(#[from] anyhow::Error),
 }
 
-impl Error {
-    /// Check if this error is retryable (transient)
+/// What kind of failure an error is, for retry decisions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Connection, timeout or upstream outage
+    Network,
+    /// The RPC node failed the request but may not next time
+    RpcTransient,
+    /// The request or transaction was rejected; resending it fails again
+    RpcFatal,
+    /// The price moved past the slippage tolerance
+    SlippageExceeded,
+    /// The bonding curve completed; trade the migrated pool instead
+    CurveComplete,
+    /// Not enough SOL or tokens
+    InsufficientFunds,
+    /// A safety limit, lock or shutdown refused the operation
+    SafetyBlocked,
+    /// On-chain or API data didn't decode
+    ProtocolDecode,
+    /// An API asked us to slow down
+    ApiRateLimited,
+    /// Missing or invalid configuration
+    Config,
+    /// Local failures (persistence, I/O, control channel, ...)
+    Internal,
+}
+
+impl ErrorCategory {
+    /// Worth retrying the same operation
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            Error::Rpc(_)
-                | Error::RpcTimeout(_)
-                | Error::RpcConnection(_)
-                | Error::ShredStreamDisconnected
-                | Error::TransactionSend(_)
-                | Error::JitoBundleSubmission(_)
+            ErrorCategory::Network
+                | ErrorCategory::RpcTransient
+                | ErrorCategory::SlippageExceeded
+                | ErrorCategory::ApiRateLimited
         )
     }
 
+    /// Wait before retrying (None = don't retry)
+    pub fn suggested_backoff(&self) -> Option<Duration> {
+        match self {
+            ErrorCategory::Network => Some(Duration::from_secs(1)),
+            ErrorCategory::RpcTransient => Some(Duration::from_millis(500)),
+            // Retry right away with a fresh quote / more slippage
+            ErrorCategory::SlippageExceeded => Some(Duration::ZERO),
+            ErrorCategory::ApiRateLimited => Some(Duration::from_secs(5)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ErrorCategory::Network => "network",
+            ErrorCategory::RpcTransient => "rpc_transient",
+            ErrorCategory::RpcFatal => "rpc_fatal",
+            ErrorCategory::SlippageExceeded => "slippage_exceeded",
+            ErrorCategory::CurveComplete => "curve_complete",
+            ErrorCategory::InsufficientFunds => "insufficient_funds",
+            ErrorCategory::SafetyBlocked => "safety_blocked",
+            ErrorCategory::ProtocolDecode => "protocol_decode",
+            ErrorCategory::ApiRateLimited => "api_rate_limited",
+            ErrorCategory::Config => "config",
+            ErrorCategory::Internal => "internal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Error {
+    /// Classify this error
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::Network(_)
+            | Error::RpcConnection(_)
+            | Error::ShredStreamConnection(_)
+            | Error::ShredStreamDisconnected
+            | Error::Signing { .. } => ErrorCategory::Network,
+            Error::Rpc(_)
+            | Error::RpcTimeout(_)
+            | Error::TransactionSend(_)
+            | Error::JitoBundleSubmission(_) => ErrorCategory::RpcTransient,
+            Error::RpcFatal(_)
+            | Error::TransactionBuild(_)
+            | Error::TransactionSimulation(_)
+            | Error::JitoBundleRejected(_) => ErrorCategory::RpcFatal,
+            Error::SlippageExceeded(_) => ErrorCategory::SlippageExceeded,
+            Error::CurveComplete(_) => ErrorCategory::CurveComplete,
+            Error::InsufficientFunds(_) | Error::InsufficientBalance { .. } => {
+                ErrorCategory::InsufficientFunds
+            }
+            Error::SafetyLimitExceeded(_)
+            | Error::DailyLossLimitReached { .. }
+            | Error::DailyTradeLimitReached { .. }
+            | Error::MaxPositionExceeded { .. }
+            | Error::MaxConcurrentPositions { .. }
+            | Error::InsecureKeypair(_)
+            | Error::EmergencyLockActive(_)
+            | Error::AiAuthorityExceeded(_)
+            | Error::VaultWithdrawalBlocked
+            | Error::AlreadyRunning(_)
+            | Error::ShuttingDown
+            | Error::EntriesPaused(_)
+            | Error::SellInFlight(_)
+            | Error::MintLocked(..)
+            | Error::TokenFiltered { .. } => ErrorCategory::SafetyBlocked,
+            Error::InvalidInstruction(_)
+            | Error::BondingCurveDecode(_)
+            | Error::PoolDecode(_)
+            | Error::PriceOverflow
+            | Error::UnknownDiscriminator(_)
+            | Error::ShredStreamDecode(_)
+            | Error::Serialization(_)
+            | Error::Deserialization(_) => ErrorCategory::ProtocolDecode,
+            Error::ApiRateLimited { .. } => ErrorCategory::ApiRateLimited,
+            Error::Config(_)
+            | Error::MissingEnvVar(_)
+            | Error::InvalidKeypair(_)
+            | Error::KeypairDecryption(_)
+            | Error::InvalidRegex(_)
+            | Error::WalletNotFound(_)
+            | Error::JitoTipAccountNotFound => ErrorCategory::Config,
+            Error::PositionNotFound(_)
+            | Error::PositionPersistence(_)
+            | Error::Control(_)
+            | Error::Notification(_)
+            | Error::WalletTransfer(_)
+            | Error::Io(_)
+            | Error::Internal(_)
+            | Error::Anyhow(_) => ErrorCategory::Internal,
+        }
+    }
+
+    /// Check if this error is retryable (transient)
+    pub fn is_retryable(&self) -> bool {
+        self.category().is_retryable()
+    }
+
+    /// Wait before retrying (None = permanent, don't retry)
+    ///
+    /// Rate limits use the API's Retry-After when it sent one.
+    pub fn suggested_backoff(&self) -> Option<Duration> {
+        match self {
+            Error::ApiRateLimited {
+                retry_after_secs: Some(secs),
+                ..
+            } => Some(Duration::from_secs(*secs)),
+            _ => self.category().suggested_backoff(),
+        }
+    }
+
     /// Check if this error is a safety violation
     pub fn is_safety_violation(&self) -> bool {
         matches!(
             self,
             Error::SafetyLimitExceeded(_)
                 | Error::DailyLossLimitReached { .. }
+                | Error::DailyTradeLimitReached { .. }
                 | Error::MaxPositionExceeded { .. }
                 | Error::InsecureKeypair(_)
                 | Error::EmergencyLockActive(_)
@@ -167,12 +381,149 @@ impl Error {
                 | Error::InsufficientBalance { .. }
         )
     }
+
+    /// Classify an RPC client error; `context` prefixes the message
+    pub fn from_client_error(context: &str, e: ClientError) -> Self {
+        let message = format!("{}: {}", context, e);
+        if let Some(tx_err) = e.get_transaction_error() {
+            return Error::from_transaction_error(&tx_err, message);
+        }
+        match e.kind() {
+            ClientErrorKind::Io(_) => Error::Network(message),
+            ClientErrorKind::Reqwest(r) if r.status().map(|s| s.as_u16()) == Some(429) => {
+                Error::ApiRateLimited {
+                    api: "RPC".to_string(),
+                    retry_after_secs: None,
+                }
+            }
+            ClientErrorKind::Reqwest(r) if r.is_timeout() || r.is_connect() => {
+                Error::Network(message)
+            }
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => match *code {
+                // Rate limited (some providers answer 429 as a JSON-RPC code)
+                429 => Error::ApiRateLimited {
+                    api: "RPC".to_string(),
+                    retry_after_secs: None,
+                },
+                // Block / slot not available yet, node unhealthy or behind
+                -32004 | -32005 | -32007 | -32014 | -32016 => Error::Rpc(message),
+                // Preflight failure, bad signature, malformed request
+                -32002 | -32003 | -32600 | -32601 | -32602 => Error::RpcFatal(message),
+                _ => Error::Rpc(message),
+            },
+            ClientErrorKind::SerdeJson(_) => Error::Deserialization(message),
+            ClientErrorKind::SigningError(_) => Error::RpcFatal(message),
+            _ => Error::Rpc(message),
+        }
+    }
+
+    /// Classify a failed transaction, including pump.fun's custom errors
+    pub fn from_transaction_error(e: &TransactionError, message: String) -> Self {
+        match e {
+            TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+                match *code {
+                    PROGRAM_ERRORS::TOO_MUCH_SOL_REQUIRED
+                    | PROGRAM_ERRORS::TOO_LITTLE_SOL_RECEIVED => Error::SlippageExceeded(message),
+                    PROGRAM_ERRORS::BONDING_CURVE_COMPLETE => Error::CurveComplete(message),
+                    // Token program InsufficientFunds / system ResultWithNegativeLamports
+                    1 => Error::InsufficientFunds(message),
+                    _ => Error::RpcFatal(message),
+                }
+            }
+            TransactionError::InstructionError(_, InstructionError::InsufficientFunds)
+            | TransactionError::InsufficientFundsForFee
+            | TransactionError::InsufficientFundsForRent { .. } => {
+                Error::InsufficientFunds(message)
+            }
+            // Expired blockhash or a full block: a fresh transaction may land
+            TransactionError::BlockhashNotFound
+            | TransactionError::WouldExceedMaxBlockCostLimit
+            | TransactionError::WouldExceedMaxAccountCostLimit
+            | TransactionError::WouldExceedMaxVoteCostLimit
+            | TransactionError::WouldExceedAccountDataBlockLimit => Error::Rpc(message),
+            _ => Error::RpcFatal(message),
+        }
+    }
+
+    /// Classify an error response from a trading API (PumpPortal, Jupiter)
+    ///
+    /// Decided by the HTTP status, the program error code in the simulation
+    /// logs (`custom program error: 0x1772`, `Error Number: 6002`), the
+    /// runtime's own errors and Jupiter's error codes. Other text is treated
+    /// as transient: wording like "insufficient liquidity" says nothing
+    /// reliable about the wallet.
+    pub fn from_api_response(api: &str, status: u16, body: &str) -> Self {
+        let message = format!("{} error ({}): {}", api, status, body);
+        if status == 429 {
+            return Error::ApiRateLimited {
+                api: api.to_string(),
+                retry_after_secs: None,
+            };
+        }
+        if let Some(code) = program_error_code(body) {
+            // Jupiter's own SlippageToleranceExceeded shares pump.fun's range
+            if api.starts_with("Jupiter") && code == JUPITER_SLIPPAGE_TOLERANCE_EXCEEDED {
+                return Error::SlippageExceeded(message);
+            }
+            let e = TransactionError::InstructionError(0, InstructionError::Custom(code));
+            return Error::from_transaction_error(&e, message);
+        }
+        if RUNTIME_INSUFFICIENT_FUNDS.iter().any(|e| body.contains(e)) {
+            return Error::InsufficientFunds(message);
+        }
+        if JUPITER_NO_ROUTE.iter().any(|code| body.contains(code)) {
+            return Error::RpcFatal(message);
+        }
+        match status {
+            401 | 403 => Error::Config(message),
+            408 | 500..=599 => Error::Network(message),
+            _ => Error::Rpc(message),
+        }
+    }
+}
+
+/// Jupiter program's SlippageToleranceExceeded
+const JUPITER_SLIPPAGE_TOLERANCE_EXCEEDED: u32 = 6001;
+
+/// Runtime and system program errors for an underfunded payer
+const RUNTIME_INSUFFICIENT_FUNDS: &[&str] = &[
+    "InsufficientFundsForFee",
+    "InsufficientFundsForRent",
+    "insufficient lamports",
+    "Attempt to debit an account but found no record of a prior credit",
+];
+
+/// Jupiter error codes for a token it can't route
+const JUPITER_NO_ROUTE: &[&str] = &[
+    "COULD_NOT_FIND_ANY_ROUTE",
+    "NO_ROUTES_FOUND",
+    "TOKEN_NOT_TRADABLE",
+];
+
+/// The custom program error in simulation logs, hex or Anchor's decimal
+fn program_error_code(body: &str) -> Option<u32> {
+    let after = |marker: &str| body.find(marker).map(|i| &body[i + marker.len()..]);
+    let digits = |s: &str, radix: u32| {
+        let end = s.find(|c: char| !c.is_digit(radix)).unwrap_or(s.len());
+        u32::from_str_radix(&s[..end], radix).ok()
+    };
+    after("custom program error: 0x")
+        .and_then(|s| digits(s, 16))
+        .or_else(|| after("Error Number: ").and_then(|s| digits(s, 10)))
 }
 
 // Conversion from solana_client errors
-impl From<solana_client::client_error::ClientError> for Error {
-    fn from(e: solana_client::client_error::ClientError) -> Self {
-        Error::Rpc(e.to_string())
+impl From<ClientError> for Error {
+    fn from(e: ClientError) -> Self {
+        Error::from_client_error("request failed", e)
+    }
+}
+
+// Conversion from failed transactions
+impl From<TransactionError> for Error {
+    fn from(e: TransactionError) -> Self {
+        let message = format!("Transaction failed: {}", e);
+        Error::from_transaction_error(&e, message)
     }
 }
 
@@ -189,3 +540,89 @@ impl From<std::io::Error> for Error {
         Error::Io(e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pump_program_errors() {
+        let failed = |code| {
+            Error::from(TransactionError::InstructionError(
+                2,
+                InstructionError::Custom(code),
+            ))
+        };
+        assert_eq!(failed(6002).category(), ErrorCategory::SlippageExceeded);
+        assert_eq!(failed(6003).category(), ErrorCategory::SlippageExceeded);
+        assert_eq!(failed(6005).category(), ErrorCategory::CurveComplete);
+        assert_eq!(failed(1).category(), ErrorCategory::InsufficientFunds);
+        assert_eq!(failed(6000).category(), ErrorCategory::RpcFatal);
+
+        assert!(failed(6002).is_retryable());
+        assert!(!failed(6005).is_retryable());
+        assert_eq!(failed(6005).suggested_backoff(), None);
+
+        let expired = Error::from(TransactionError::BlockhashNotFound);
+        assert_eq!(expired.category(), ErrorCategory::RpcTransient);
+        let broke = Error::from(TransactionError::InsufficientFundsForFee);
+        assert_eq!(broke.category(), ErrorCategory::InsufficientFunds);
+    }
+
+    #[test]
+    fn test_api_responses() {
+        let classify = |status: u16, body: &str| {
+            Error::from_api_response("PumpPortal", status, body).category()
+        };
+        assert_eq!(classify(429, ""), ErrorCategory::ApiRateLimited);
+        assert_eq!(
+            classify(400, "Program log: Error: custom program error: 0x1772"),
+            ErrorCategory::SlippageExceeded
+        );
+        assert_eq!(
+            classify(
+                400,
+                "AnchorError occurred. Error Code: BondingCurveComplete. Error Number: 6005."
+            ),
+            ErrorCategory::CurveComplete
+        );
+        assert_eq!(
+            classify(400, "Program log: Error: custom program error: 0x1"),
+            ErrorCategory::InsufficientFunds
+        );
+        assert_eq!(
+            classify(400, "Transfer: insufficient lamports 1000, need 5000"),
+            ErrorCategory::InsufficientFunds
+        );
+        assert_eq!(
+            classify(400, "Insufficient liquidity"),
+            ErrorCategory::RpcTransient
+        );
+        assert_eq!(classify(502, "Bad Gateway"), ErrorCategory::Network);
+        assert_eq!(classify(401, "Invalid API key"), ErrorCategory::Config);
+        assert_eq!(classify(400, "Invalid mint"), ErrorCategory::RpcTransient);
+
+        let jupiter = |body: &str| Error::from_api_response("Jupiter swap", 400, body).category();
+        assert_eq!(
+            jupiter("custom program error: 0x1771"),
+            ErrorCategory::SlippageExceeded
+        );
+        assert_eq!(
+            jupiter(r#"{"error":"No routes found","errorCode":"COULD_NOT_FIND_ANY_ROUTE"}"#),
+            ErrorCategory::RpcFatal
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        let limited = Error::ApiRateLimited {
+            api: "Helius".to_string(),
+            retry_after_secs: Some(30),
+        };
+        assert_eq!(limited.suggested_backoff(), Some(Duration::from_secs(30)));
+        assert!(Error::RpcTimeout(5000).is_retryable());
+        assert!(!Error::Config("missing key".to_string()).is_retryable());
+        assert!(!Error::ShuttingDown.is_retryable());
+        assert_eq!(Error::ShuttingDown.category(), ErrorCategory::SafetyBlocked);
+    }
+}
@@ -3,7 +3,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::filter::scoring::ScoringThresholds;
+use crate::filter::creator_launches::CreatorLaunchConfig;
+use crate::filter::enrichment::EnrichmentConfig;
+use crate::filter::momentum::MomentumConfig;
+use crate::filter::scoring::{Recommendation, ScoringThresholds};
+use crate::filter::signals::registry::BUILTIN_PROVIDERS;
 use crate::filter::signals::SignalType;
 
 /// Main configuration for adaptive filtering
@@ -21,6 +25,10 @@ pub struct AdaptiveFilterConfig {
     #[serde(default)]
     pub background: BackgroundConfig,
 
+    /// Helius enrichment lanes and their rate budgets
+    #[serde(default)]
+    pub enrichment: EnrichmentConfig,
+
     /// Signal weights (overrides defaults)
     #[serde(default)]
     pub weights: HashMap<String, f64>,
@@ -33,6 +41,14 @@ pub struct AdaptiveFilterConfig {
     #[serde(default)]
     pub reassessment: ReassessmentConfig,
 
+    /// Deferred re-scoring after entry
+    #[serde(default)]
+    pub rescoring: RescoringConfig,
+
+    /// Confirm momentum before committing full position size
+    #[serde(default)]
+    pub momentum: MomentumGateConfig,
+
     /// Cache configuration
     #[serde(default)]
     pub cache: CacheConfig,
@@ -40,28 +56,109 @@ pub struct AdaptiveFilterConfig {
     /// Known actors configuration
     #[serde(default)]
     pub known_actors: KnownActorsConfig,
+
+    /// Creator funding-source tracing
+    #[serde(default)]
+    pub funding: FundingConfig,
+
+    /// Launches per creator over rolling 1h / 24h windows
+    #[serde(default)]
+    pub creator_launches: CreatorLaunchConfig,
+
+    /// Machine-readable log of scoring decisions
+    #[serde(default)]
+    pub decision_log: DecisionLogConfig,
+
+    /// Name / symbol quality heuristics
+    #[serde(default)]
+    pub name_rules: NameRulesConfig,
+
+    /// On-chain check of `snipe hot-scan` candidates before auto-buy
+    #[serde(default)]
+    pub hot_scan: HotScanVerifyConfig,
+
+    /// Signal providers to run, by registry name
+    #[serde(default = "default_providers")]
+    pub providers: Vec<String>,
+
+    /// Per-provider settings, by registry name
+    #[serde(default)]
+    pub provider_settings: HashMap<String, ProviderSettings>,
 }
 
 fn default_enabled() -> bool {
     true
 }
 
+fn default_providers() -> Vec<String> {
+    BUILTIN_PROVIDERS
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+/// `[adaptive_filter.provider_settings.<name>]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderSettings {
+    /// False = not run even though listed in `providers`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Scales the confidence of the provider's signals (0-1)
+    #[serde(default = "default_provider_weight")]
+    pub weight: f64,
+    /// Timeout for the provider (None = the provider's own)
+    #[serde(default)]
+    pub max_latency_ms: Option<u64>,
+}
+
+fn default_provider_weight() -> f64 {
+    1.0
+}
+
+impl Default for ProviderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            weight: default_provider_weight(),
+            max_latency_ms: None,
+        }
+    }
+}
+
 impl Default for AdaptiveFilterConfig {
     fn default() -> Self {
         Self {
             enabled: true,
             hot_path: HotPathConfig::default(),
             background: BackgroundConfig::default(),
+            enrichment: EnrichmentConfig::default(),
             weights: HashMap::new(),
             thresholds: ScoringThresholds::default(),
             reassessment: ReassessmentConfig::default(),
+            rescoring: RescoringConfig::default(),
+            momentum: MomentumGateConfig::default(),
             cache: CacheConfig::default(),
             known_actors: KnownActorsConfig::default(),
+            funding: FundingConfig::default(),
+            creator_launches: CreatorLaunchConfig::default(),
+            decision_log: DecisionLogConfig::default(),
+            name_rules: NameRulesConfig::default(),
+            hot_scan: HotScanVerifyConfig::default(),
+            providers: default_providers(),
+            provider_settings: HashMap::new(),
         }
     }
 }
 
 impl AdaptiveFilterConfig {
+    /// Settings of the provider called `name` (defaults if it has no table)
+    pub fn provider_settings(&self, name: &str) -> ProviderSettings {
+        self.provider_settings
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Parse signal weights from string keys to SignalType
     pub fn signal_weights(&self) -> HashMap<SignalType, f64> {
         let mut result = HashMap::new();
@@ -75,34 +172,9 @@ impl AdaptiveFilterConfig {
         result
     }
 
-    /// Parse a string key to SignalType
+    /// Parse a string key (snake_case signal type name) to SignalType
     fn parse_signal_type(key: &str) -> Option<SignalType> {
-        match key.to_lowercase().as_str() {
-            "wallet_age" => Some(SignalType::WalletAge),
-            "wallet_history" => Some(SignalType::WalletHistory),
-            "wallet_prior_performance" => Some(SignalType::WalletPriorPerformance),
-            "known_deployer" => Some(SignalType::KnownDeployer),
-            "known_sniper" => Some(SignalType::KnownSniper),
-            "wallet_clustering" => Some(SignalType::WalletClustering),
-            "supply_dispersion" => Some(SignalType::SupplyDispersion),
-            "concentration_risk" => Some(SignalType::ConcentrationRisk),
-            "early_accumulation" => Some(SignalType::EarlyAccumulation),
-            "buy_timing" => Some(SignalType::BuyTiming),
-            "sell_timing" => Some(SignalType::SellTiming),
-            "burst_detection" => Some(SignalType::BurstDetection),
-            "wash_trading" => Some(SignalType::WashTrading),
-            "velocity_metrics" => Some(SignalType::VelocityMetrics),
-            "transaction_size_ratio" => Some(SignalType::TransactionSizeRatio),
-            "coordinated_funding" => Some(SignalType::CoordinatedFunding),
-            "deployer_pattern" => Some(SignalType::DeployerPattern),
-            "liquidity_seeding" => Some(SignalType::LiquiditySeeding),
-            "early_sell_pressure" => Some(SignalType::EarlySellPressure),
-            "organic_demand" => Some(SignalType::OrganicDemand),
-            "name_quality" => Some(SignalType::NameQuality),
-            "symbol_quality" => Some(SignalType::SymbolQuality),
-            "uri_analysis" => Some(SignalType::UriAnalysis),
-            _ => None,
-        }
+        serde_json::from_value(serde_json::Value::String(key.to_lowercase())).ok()
     }
 }
 
@@ -234,6 +306,182 @@ impl Default for ReassessmentConfig {
     }
 }
 
+/// Deferred re-scoring configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RescoringConfig {
+    /// Re-score Probe and Opportunity entries once enrichment has landed
+    #[serde(default = "default_rescoring_enabled")]
+    pub enabled: bool,
+
+    /// Seconds after entry at which to run `score_full`
+    #[serde(default = "default_rescore_delays")]
+    pub delays_secs: Vec<u64>,
+
+    /// Allow a one-time add-on buy when a re-score upgrades to StrongBuy
+    #[serde(default)]
+    pub add_on_enabled: bool,
+
+    /// Cap on the add-on buy (SOL)
+    #[serde(default = "default_add_on_max_sol")]
+    pub add_on_max_sol: f64,
+}
+
+fn default_rescoring_enabled() -> bool {
+    true
+}
+
+fn default_rescore_delays() -> Vec<u64> {
+    vec![10, 30]
+}
+
+fn default_add_on_max_sol() -> f64 {
+    0.1
+}
+
+impl Default for RescoringConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_rescoring_enabled(),
+            delays_secs: default_rescore_delays(),
+            add_on_enabled: false,
+            add_on_max_sol: default_add_on_max_sol(),
+        }
+    }
+}
+
+/// Momentum confirmation before full size
+///
+/// StrongBuy / Opportunity entries buy `starter_fraction` of the planned size,
+/// then the MomentumValidator watches the next `window_secs` of trades.
+/// Confirmed momentum tops up to full size; a failure exits the starter; a
+/// timeout keeps the starter under the normal exit rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MomentumGateConfig {
+    /// Enter with a starter position and wait for momentum
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Fraction of the planned size bought immediately
+    #[serde(default = "default_starter_fraction")]
+    pub starter_fraction: f64,
+
+    /// Seconds of trades to watch before giving up (timeout)
+    #[serde(default = "default_momentum_window_secs")]
+    pub window_secs: u64,
+
+    /// Seconds of trades required before confirming or failing
+    #[serde(default = "default_momentum_min_observation_secs")]
+    pub min_observation_secs: u64,
+
+    /// Unique traders required to confirm
+    #[serde(default = "default_momentum_min_unique_traders")]
+    pub min_unique_traders: u32,
+
+    /// Net SOL inflow (buys - sells) required to confirm
+    #[serde(default = "default_momentum_min_net_inflow_sol")]
+    pub min_net_inflow_sol: f64,
+
+    /// Price change since the first watched trade required to confirm (%)
+    #[serde(default = "default_momentum_min_price_change_pct")]
+    pub min_price_change_pct: f64,
+
+    /// Price drop since the first watched trade that fails the entry (%)
+    #[serde(default = "default_momentum_fail_price_drop_pct")]
+    pub fail_price_drop_pct: f64,
+
+    /// Net SOL outflow that fails the entry
+    #[serde(default = "default_momentum_fail_net_outflow_sol")]
+    pub fail_net_outflow_sol: f64,
+
+    /// Organic demand (holder growth, -1.0 to 1.0) required to confirm
+    #[serde(default = "default_momentum_min_organic_demand")]
+    pub min_organic_demand: f64,
+}
+
+fn default_starter_fraction() -> f64 {
+    0.3
+}
+
+fn default_momentum_window_secs() -> u64 {
+    30
+}
+
+fn default_momentum_min_observation_secs() -> u64 {
+    5
+}
+
+fn default_momentum_min_unique_traders() -> u32 {
+    5
+}
+
+fn default_momentum_min_net_inflow_sol() -> f64 {
+    0.5
+}
+
+fn default_momentum_min_price_change_pct() -> f64 {
+    5.0
+}
+
+fn default_momentum_fail_price_drop_pct() -> f64 {
+    15.0
+}
+
+fn default_momentum_fail_net_outflow_sol() -> f64 {
+    0.5
+}
+
+fn default_momentum_min_organic_demand() -> f64 {
+    -1.0
+}
+
+impl Default for MomentumGateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            starter_fraction: default_starter_fraction(),
+            window_secs: default_momentum_window_secs(),
+            min_observation_secs: default_momentum_min_observation_secs(),
+            min_unique_traders: default_momentum_min_unique_traders(),
+            min_net_inflow_sol: default_momentum_min_net_inflow_sol(),
+            min_price_change_pct: default_momentum_min_price_change_pct(),
+            fail_price_drop_pct: default_momentum_fail_price_drop_pct(),
+            fail_net_outflow_sol: default_momentum_fail_net_outflow_sol(),
+            min_organic_demand: default_momentum_min_organic_demand(),
+        }
+    }
+}
+
+impl MomentumGateConfig {
+    /// Size to buy now for a planned entry (None = gate disabled)
+    pub fn starter_size(&self, planned_sol: f64) -> Option<f64> {
+        if !self.enabled {
+            return None;
+        }
+        let fraction = self.starter_fraction.clamp(0.0, 1.0);
+        (fraction < 1.0).then_some(planned_sol * fraction)
+    }
+
+    /// Validator thresholds for confirming a starter position
+    pub fn validator_config(&self) -> MomentumConfig {
+        MomentumConfig {
+            min_observation_secs: self.min_observation_secs,
+            max_observation_secs: self.window_secs,
+            min_trade_count: self.min_unique_traders,
+            min_volume_sol: self.min_net_inflow_sol,
+            min_price_change_pct: self.min_price_change_pct,
+            min_unique_traders: self.min_unique_traders,
+            min_buy_ratio: 0.5,
+            min_volatility: 0.0,
+            max_holder_concentration: 1.0,
+            min_second_wave_ratio: 0.0,
+            min_net_flow_sol: self.min_net_inflow_sol,
+            require_holder_data: false,
+            min_organic_demand: self.min_organic_demand,
+            ..Default::default()
+        }
+    }
+}
+
 /// Cache configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {
@@ -245,9 +493,29 @@ pub struct CacheConfig {
     #[serde(default = "default_wallet_cache_ttl")]
     pub wallet_cache_ttl_secs: u64,
 
+    /// Score cache size (entries)
+    #[serde(default = "default_score_cache_size")]
+    pub score_cache_size: usize,
+
+    /// Score cache TTL (seconds); also used for holders and mint info
+    #[serde(default = "default_score_cache_ttl")]
+    pub score_cache_ttl_secs: u64,
+
     /// Trade flow buffer size per token
     #[serde(default = "default_trade_flow_buffer")]
     pub trade_flow_buffer_size: usize,
+
+    /// Snapshot file for wallet histories and mint info (None = no persistence)
+    #[serde(default)]
+    pub persist_path: Option<String>,
+
+    /// Interval between cache snapshots (seconds)
+    #[serde(default = "default_snapshot_interval")]
+    pub snapshot_interval_secs: u64,
+
+    /// Interval between sweeps that drop expired entries (seconds)
+    #[serde(default = "default_sweep_interval")]
+    pub sweep_interval_secs: u64,
 }
 
 fn default_wallet_cache_size() -> usize {
@@ -258,16 +526,276 @@ fn default_wallet_cache_ttl() -> u64 {
     3600
 }
 
+fn default_score_cache_size() -> usize {
+    1000
+}
+
+fn default_score_cache_ttl() -> u64 {
+    300
+}
+
 fn default_trade_flow_buffer() -> usize {
     1000
 }
 
+fn default_snapshot_interval() -> u64 {
+    300
+}
+
+fn default_sweep_interval() -> u64 {
+    60
+}
+
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             wallet_cache_size: default_wallet_cache_size(),
             wallet_cache_ttl_secs: default_wallet_cache_ttl(),
+            score_cache_size: default_score_cache_size(),
+            score_cache_ttl_secs: default_score_cache_ttl(),
             trade_flow_buffer_size: default_trade_flow_buffer(),
+            persist_path: None,
+            snapshot_interval_secs: default_snapshot_interval(),
+            sweep_interval_secs: default_sweep_interval(),
+        }
+    }
+}
+
+/// Creator funding-source tracing configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingConfig {
+    /// Trace where the creator's SOL came from (requires Helius)
+    #[serde(default = "default_funding_enabled")]
+    pub enabled: bool,
+
+    /// Funding hops to follow back from the creator (1 or 2)
+    #[serde(default = "default_funding_max_hops")]
+    pub max_hops: u32,
+
+    /// How long a traced wallet's funder is cached (seconds)
+    #[serde(default = "default_funding_cache_ttl")]
+    pub cache_ttl_secs: u64,
+
+    /// A funder whose own funding is younger than this is a fresh wallet (seconds)
+    #[serde(default = "default_fresh_wallet_secs")]
+    pub fresh_wallet_secs: u64,
+
+    /// Creator funded within this long before launch counts as just-funded (seconds)
+    #[serde(default = "default_recent_funding_secs")]
+    pub recent_funding_secs: u64,
+
+    /// Incoming transfers smaller than this are ignored as dust (SOL)
+    #[serde(default = "default_min_funding_sol")]
+    pub min_funding_sol: f64,
+
+    /// Exchange hot wallets in addition to the built-in list
+    #[serde(default)]
+    pub cex_wallets: Vec<String>,
+
+    /// Bridge payout wallets (no built-in list: payout accounts vary by bridge)
+    #[serde(default)]
+    pub bridge_wallets: Vec<String>,
+}
+
+fn default_funding_enabled() -> bool {
+    true
+}
+
+fn default_funding_max_hops() -> u32 {
+    2
+}
+
+fn default_funding_cache_ttl() -> u64 {
+    6 * 3600
+}
+
+fn default_fresh_wallet_secs() -> u64 {
+    24 * 3600
+}
+
+fn default_recent_funding_secs() -> u64 {
+    3600
+}
+
+fn default_min_funding_sol() -> f64 {
+    0.05
+}
+
+impl Default for FundingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_funding_enabled(),
+            max_hops: default_funding_max_hops(),
+            cache_ttl_secs: default_funding_cache_ttl(),
+            fresh_wallet_secs: default_fresh_wallet_secs(),
+            recent_funding_secs: default_recent_funding_secs(),
+            min_funding_sol: default_min_funding_sol(),
+            cex_wallets: Vec::new(),
+            bridge_wallets: Vec::new(),
+        }
+    }
+}
+
+/// Name quality rules (compiled once when the filter is built)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NameRulesConfig {
+    /// Penalty keywords (matched case-insensitively after Unicode normalization) -> signal value
+    #[serde(default = "default_name_keywords")]
+    pub keywords: HashMap<String, f64>,
+
+    /// Terms that never trigger a keyword (e.g. "freedom" for "free")
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+
+    /// Regex patterns tested against the name and symbol
+    #[serde(default)]
+    pub patterns: Vec<NamePatternRule>,
+
+    /// Shorter name or symbol is penalized (characters)
+    #[serde(default = "default_name_min_length")]
+    pub min_length: usize,
+
+    /// Longer name is penalized (characters)
+    #[serde(default = "default_name_max_length")]
+    pub max_length: usize,
+
+    /// Signal value for an all-caps name longer than 4 letters
+    #[serde(default = "default_all_caps_penalty")]
+    pub all_caps_penalty: f64,
+
+    /// Signal value for zero-width characters or lookalike homoglyphs
+    #[serde(default = "default_obfuscation_penalty")]
+    pub obfuscation_penalty: f64,
+
+    /// Signal value for a name with no letters or digits (emoji only)
+    #[serde(default = "default_emoji_only_penalty")]
+    pub emoji_only_penalty: f64,
+}
+
+/// A regex name rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamePatternRule {
+    /// Regex (use `(?i)` for case-insensitive)
+    pub pattern: String,
+    /// Signal value when it matches (-1.0 to 1.0)
+    pub score: f64,
+}
+
+fn default_name_keywords() -> HashMap<String, f64> {
+    ["scam", "rug", "honeypot", "free", "airdrop", "1000x"]
+        .into_iter()
+        .map(|k| (k.to_string(), -0.7))
+        .collect()
+}
+
+fn default_name_min_length() -> usize {
+    2
+}
+
+fn default_name_max_length() -> usize {
+    30
+}
+
+fn default_all_caps_penalty() -> f64 {
+    -0.1
+}
+
+fn default_obfuscation_penalty() -> f64 {
+    -0.6
+}
+
+fn default_emoji_only_penalty() -> f64 {
+    -0.3
+}
+
+impl Default for NameRulesConfig {
+    fn default() -> Self {
+        Self {
+            keywords: default_name_keywords(),
+            allowlist: Vec::new(),
+            patterns: Vec::new(),
+            min_length: default_name_min_length(),
+            max_length: default_name_max_length(),
+            all_caps_penalty: default_all_caps_penalty(),
+            obfuscation_penalty: default_obfuscation_penalty(),
+            emoji_only_penalty: default_emoji_only_penalty(),
+        }
+    }
+}
+
+/// Decision log configuration (one JSON line per scored token)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionLogConfig {
+    /// Append every decision-stage scoring result to the log
+    #[serde(default = "default_decision_log_enabled")]
+    pub enabled: bool,
+
+    /// Path of the active log file (rotated files get `.1`, `.2`, ...)
+    #[serde(default = "default_decision_log_path")]
+    pub path: String,
+
+    /// Rotate once the active file reaches this size (MB)
+    #[serde(default = "default_decision_log_max_file_mb")]
+    pub max_file_mb: u64,
+
+    /// Rotated files to keep
+    #[serde(default = "default_decision_log_max_files")]
+    pub max_files: usize,
+}
+
+fn default_decision_log_enabled() -> bool {
+    true
+}
+
+fn default_decision_log_path() -> String {
+    "data/decisions.jsonl".to_string()
+}
+
+fn default_decision_log_max_file_mb() -> u64 {
+    20
+}
+
+fn default_decision_log_max_files() -> usize {
+    5
+}
+
+impl Default for DecisionLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_decision_log_enabled(),
+            path: default_decision_log_path(),
+            max_file_mb: default_decision_log_max_file_mb(),
+            max_files: default_decision_log_max_files(),
+        }
+    }
+}
+
+/// Hot-scan verification: mint info, holders and creator profile are fetched
+/// and scored together with the DexScreener order flow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotScanVerifyConfig {
+    /// Score candidates before buying (false = DexScreener numbers only)
+    #[serde(default = "default_hot_scan_verify_enabled")]
+    pub enabled: bool,
+
+    /// Weakest recommendation still bought (strong_buy, opportunity or probe)
+    #[serde(default = "default_hot_scan_min_recommendation")]
+    pub min_recommendation: Recommendation,
+}
+
+fn default_hot_scan_verify_enabled() -> bool {
+    true
+}
+
+fn default_hot_scan_min_recommendation() -> Recommendation {
+    Recommendation::Probe
+}
+
+impl Default for HotScanVerifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_hot_scan_verify_enabled(),
+            min_recommendation: default_hot_scan_min_recommendation(),
         }
     }
 }
@@ -287,9 +815,46 @@ pub struct KnownActorsConfig {
     #[serde(default = "default_trusted_file")]
     pub trusted_file: String,
 
-    /// Refresh interval (seconds)
+    /// Refresh interval for local files and remote lists (seconds)
     #[serde(default = "default_refresh_interval")]
     pub refresh_interval_secs: u64,
+
+    /// Remote lists merged with the local files on every refresh
+    #[serde(default)]
+    pub remote_urls: RemoteActorUrls,
+
+    /// Operator deny list: creators never traded (`snipe list`)
+    #[serde(default = "default_deny_creators_file")]
+    pub deny_creators_file: String,
+
+    /// Operator deny list: mints never traded
+    #[serde(default = "default_deny_mints_file")]
+    pub deny_mints_file: String,
+
+    /// Operator deny list: regexes matched against name and symbol
+    #[serde(default = "default_deny_symbols_file")]
+    pub deny_symbols_file: String,
+
+    /// Operator allow list: creators whose tokens get `allow_creator_bonus`
+    #[serde(default = "default_allow_creators_file")]
+    pub allow_creators_file: String,
+
+    /// Score added for allow-listed creators (not applied past a fatal signal)
+    #[serde(default = "default_allow_creator_bonus")]
+    pub allow_creator_bonus: f64,
+}
+
+/// URLs of plain-text address lists (one address per line, `#` comments)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteActorUrls {
+    #[serde(default)]
+    pub deployers: Vec<String>,
+
+    #[serde(default)]
+    pub snipers: Vec<String>,
+
+    #[serde(default)]
+    pub trusted: Vec<String>,
 }
 
 fn default_deployers_file() -> String {
@@ -308,6 +873,26 @@ fn default_refresh_interval() -> u64 {
     3600
 }
 
+fn default_deny_creators_file() -> String {
+    "data/deny_creators.txt".to_string()
+}
+
+fn default_deny_mints_file() -> String {
+    "data/deny_mints.txt".to_string()
+}
+
+fn default_deny_symbols_file() -> String {
+    "data/deny_symbols.txt".to_string()
+}
+
+fn default_allow_creators_file() -> String {
+    "data/allow_creators.txt".to_string()
+}
+
+fn default_allow_creator_bonus() -> f64 {
+    0.15
+}
+
 impl Default for KnownActorsConfig {
     fn default() -> Self {
         Self {
@@ -315,6 +900,12 @@ impl Default for KnownActorsConfig {
             snipers_file: default_snipers_file(),
             trusted_file: default_trusted_file(),
             refresh_interval_secs: default_refresh_interval(),
+            remote_urls: RemoteActorUrls::default(),
+            deny_creators_file: default_deny_creators_file(),
+            deny_mints_file: default_deny_mints_file(),
+            deny_symbols_file: default_deny_symbols_file(),
+            allow_creators_file: default_allow_creators_file(),
+            allow_creator_bonus: default_allow_creator_bonus(),
         }
     }
 }
@@ -337,9 +928,11 @@ mod tests {
         config.weights.insert("known_deployer".to_string(), 2.5);
         config.weights.insert("name_quality".to_string(), 0.3);
         config.weights.insert("invalid_signal".to_string(), 1.0);
+        config.weights.insert("volume_spike".to_string(), 2.0);
 
         let parsed = config.signal_weights();
         assert_eq!(parsed.get(&SignalType::KnownDeployer), Some(&2.5));
+        assert_eq!(parsed.get(&SignalType::VolumeSpike), Some(&2.0));
         assert_eq!(parsed.get(&SignalType::NameQuality), Some(&0.3));
         assert!(!parsed.contains_key(&SignalType::WalletAge)); // Not in config
     }
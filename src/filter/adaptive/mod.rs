@@ -6,20 +6,34 @@
 
 pub mod config;
 
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use futures::future::join_all;
 use tokio::sync::RwLock;
 
 use crate::error::Result;
 use crate::filter::cache::FilterCache;
 use crate::filter::enrichment::EnrichmentService;
-use crate::filter::scoring::{Recommendation, ScoringEngine, ScoringResult};
+use crate::filter::name_rules::NameRules;
+use crate::filter::scoring::{Recommendation, ScoringEngine, ScoringResult, ScoringThresholds};
 use crate::filter::signals::{Signal, SignalProvider, SignalType};
 use crate::filter::types::SignalContext;
 
 pub use config::AdaptiveFilterConfig;
 
+/// Cached items needed before the cache counts as warm
+const WARM_CACHE_ITEMS: usize = 10;
+
+/// Curve progress treated as close to graduation
+const NEAR_GRADUATION_PCT: f64 = 90.0;
+
+/// Supply share (%) accumulated ahead of entry that earns the full
+/// EarlyAccumulation signal
+const FULL_ACCUMULATION_FLOAT_PCT: f64 = 5.0;
+
 /// The main adaptive filter coordinator
 ///
 /// Manages signal providers, caching, scoring, and background enrichment.
@@ -36,8 +50,11 @@ pub struct AdaptiveFilter {
     /// Shared cache for all providers
     cache: Arc<FilterCache>,
 
-    /// Scoring engine
-    scoring_engine: ScoringEngine,
+    /// Scoring engine (replaced by config reloads)
+    scoring_engine: std::sync::RwLock<ScoringEngine>,
+
+    /// Compiled name / symbol quality rules
+    name_rules: NameRules,
 
     /// Optional enrichment service for fetching data from Helius
     enrichment: Option<Arc<EnrichmentService>>,
@@ -52,6 +69,10 @@ pub struct DegradedMode {
     pub background_unavailable: bool,
     pub cache_cold: bool,
     pub known_actors_failed: bool,
+    /// Helius is rate limiting / failing, so enrichment data is stale or missing
+    pub enrichment_throttled: bool,
+    /// Confidence multiplier applied while `enrichment_throttled`
+    pub throttled_penalty: f64,
     pub reason: Option<String>,
 }
 
@@ -70,41 +91,66 @@ impl DegradedMode {
         if self.known_actors_failed {
             penalty *= 0.95; // 5% reduction (was 10%)
         }
+        if self.enrichment_throttled {
+            penalty *= self.throttled_penalty.clamp(0.0, 1.0);
+        }
 
         penalty
     }
 
     /// Check if we're in any degraded mode
     pub fn is_degraded(&self) -> bool {
-        self.background_unavailable || self.cache_cold || self.known_actors_failed
+        self.background_unavailable
+            || self.cache_cold
+            || self.known_actors_failed
+            || self.enrichment_throttled
     }
 }
 
 impl AdaptiveFilter {
     /// Create a new adaptive filter with configuration
     pub async fn new(config: AdaptiveFilterConfig) -> Result<Self> {
+        // Compile name rules up front so a bad regex fails startup
+        let name_rules = NameRules::compile(&config.name_rules)?;
+
         let cache = Arc::new(FilterCache::with_config(
             crate::filter::cache::CacheConfig {
                 wallet_cache_size: config.cache.wallet_cache_size,
                 wallet_cache_ttl_secs: config.cache.wallet_cache_ttl_secs,
+                score_cache_size: config.cache.score_cache_size,
+                score_cache_ttl_secs: config.cache.score_cache_ttl_secs,
                 trade_flow_buffer_size: config.cache.trade_flow_buffer_size,
-                ..Default::default()
             },
         ));
 
-        // Load known actors
+        // Load known actors and the operator deny / allow lists
         cache
-            .load_known_actors(
-                Some(&config.known_actors.deployers_file),
-                Some(&config.known_actors.snipers_file),
-                Some(&config.known_actors.trusted_file),
-            )
+            .load_known_actors(crate::filter::known_actors::load_local(
+                &config.known_actors,
+            ))
             .await;
 
         // Check if known actors loaded (files might not exist yet)
         let known_actors_failed = cache.wallet_cache_size() == 0
             && !std::path::Path::new(&config.known_actors.deployers_file).exists();
 
+        // Restore wallet histories and mint info from the last run
+        let mut restored = 0;
+        if let Some(path) = &config.cache.persist_path {
+            let path = std::path::PathBuf::from(path);
+            restored = cache.load_from(&path);
+            spawn_cache_snapshots(
+                Arc::clone(&cache),
+                path,
+                Duration::from_secs(config.cache.snapshot_interval_secs.max(1)),
+            );
+        }
+
+        spawn_cache_sweeper(
+            Arc::clone(&cache),
+            Duration::from_secs(config.cache.sweep_interval_secs.max(1)),
+        );
+
         // Initialize scoring engine with configured weights
         let mut scoring_engine = ScoringEngine::with_thresholds(config.thresholds.clone());
         scoring_engine.set_weights(config.signal_weights());
@@ -112,8 +158,10 @@ impl AdaptiveFilter {
         // Initialize degraded mode tracking
         let degraded_mode = DegradedMode {
             background_unavailable: false, // Will be set if workers fail to start
-            cache_cold: true,              // Starts cold
+            cache_cold: restored <= WARM_CACHE_ITEMS, // Cold unless the snapshot refilled it
             known_actors_failed,
+            enrichment_throttled: false, // Set while Helius is throttled
+            throttled_penalty: config.enrichment.throttled_confidence_penalty,
             reason: if known_actors_failed {
                 Some("Known actors files not found".to_string())
             } else {
@@ -135,7 +183,8 @@ impl AdaptiveFilter {
             hot_path_providers: Vec::new(),
             background_providers: Vec::new(),
             cache,
-            scoring_engine,
+            scoring_engine: std::sync::RwLock::new(scoring_engine),
+            name_rules,
             enrichment: None,
             degraded_mode: Arc::new(RwLock::new(degraded_mode)),
         })
@@ -171,8 +220,11 @@ impl AdaptiveFilter {
         let start = Instant::now();
 
         // Check for protocol errors first (fail-closed)
-        if context.mint.is_empty() {
-            return ScoringResult::fail_closed("Empty mint address");
+        if let Some(error) = protocol_error(context) {
+            return ScoringResult::fail_closed(error);
+        }
+        if let Some(denied) = self.check_deny_lists(context).await {
+            return denied;
         }
 
         // Enrich token data if enrichment service is available
@@ -180,41 +232,34 @@ impl AdaptiveFilter {
             if !self.cache.has_token_data(&context.mint) {
                 let enriched = enrichment.enrich_token(context).await;
                 if enriched {
-                    // Mark cache as warming up (not cold anymore)
-                    let mut degraded = self.degraded_mode.write().await;
-                    if degraded.cache_cold && self.cache.total_cached_items() > 10 {
-                        degraded.cache_cold = false;
-                        tracing::info!("Cache warmed up, exiting cold mode");
+                    // Mark cache as warming up (not cold anymore); only take
+                    // the write lock when there is something to flip
+                    let cold = self.degraded_mode.read().await.cache_cold;
+                    if cold && self.cache.total_cached_items() > WARM_CACHE_ITEMS {
+                        let mut degraded = self.degraded_mode.write().await;
+                        if degraded.cache_cold {
+                            degraded.cache_cold = false;
+                            tracing::info!("Cache warmed up, exiting cold mode");
+                        }
                     }
                 }
             }
         }
 
-        // Collect signals from hot-path providers (parallel)
-        let mut signals = Vec::new();
-
-        // Add built-in fast signals (now with enriched data available)
-        signals.extend(self.compute_builtin_hot_signals(context).await);
-
-        // Add custom provider signals
-        for provider in &self.hot_path_providers {
-            let timeout = Duration::from_millis(provider.max_latency_ms());
-            match tokio::time::timeout(timeout, provider.compute_token_signals(context)).await {
-                Ok(provider_signals) => signals.extend(provider_signals),
-                Err(_) => {
-                    tracing::warn!(provider = provider.name(), "Hot-path provider timed out");
-                    // Add a penalty signal for timeout
-                    signals.push(Signal::unavailable(
-                        SignalType::WalletHistory,
-                        format!("Provider {} timed out", provider.name()),
-                    ));
-                }
-            }
-        }
+        // Built-in signals (now with enriched data available) and hot-path
+        // providers run concurrently, each provider under its own budget
+        let (builtin, provider_signals) = tokio::join!(
+            self.compute_builtin_hot_signals(context),
+            collect_provider_signals(self.hot_path_providers.iter(), context, true)
+        );
+        let mut signals = builtin;
+        signals.extend(provider_signals);
 
         // Apply degraded mode adjustments
-        let mut result = self.scoring_engine.score(signals);
+        let mut result = self.scoring_engine.read().unwrap().score(signals);
+        self.apply_allow_bonus(context, &mut result).await;
         self.apply_degraded_mode_adjustments(&mut result).await;
+        Self::apply_fatal_vetoes(&mut result);
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -229,6 +274,27 @@ impl AdaptiveFilter {
         result
     }
 
+    /// Fast scoring that reuses a recent result for the same mint
+    ///
+    /// Results are cached for `score_cache_ttl_secs` and dropped as soon as
+    /// new enrichment data arrives for the mint. Hits have `cached` set.
+    pub async fn score_fast_cached(&self, context: &SignalContext) -> ScoringResult {
+        // A cached verdict may predate a deny-list addition
+        if let Some(denied) = self.check_deny_lists(context).await {
+            return denied;
+        }
+        if let Some(mut result) = self.cache.get_score(&context.mint) {
+            result.cached = true;
+            return result;
+        }
+
+        let result = self.score_fast(context).await;
+        if !context.mint.is_empty() {
+            self.cache.set_score(&context.mint, result.clone());
+        }
+        result
+    }
+
     /// Full scoring with all providers (for detailed analysis)
     ///
     /// Target latency: <2s
@@ -236,38 +302,32 @@ impl AdaptiveFilter {
         let start = Instant::now();
 
         // Check for protocol errors first (fail-closed)
-        if context.mint.is_empty() {
-            return ScoringResult::fail_closed("Empty mint address");
+        if let Some(error) = protocol_error(context) {
+            return ScoringResult::fail_closed(error);
         }
-
-        let mut signals = Vec::new();
-
-        // Add built-in signals
-        signals.extend(self.compute_builtin_hot_signals(context).await);
-
-        // Collect from all providers with timeout
-        let all_providers: Vec<_> = self
-            .hot_path_providers
-            .iter()
-            .chain(self.background_providers.iter())
-            .collect();
-
-        for provider in all_providers {
-            let timeout = Duration::from_millis(provider.max_latency_ms());
-            match tokio::time::timeout(timeout, provider.compute_token_signals(context)).await {
-                Ok(provider_signals) => signals.extend(provider_signals),
-                Err(_) => {
-                    tracing::warn!(
-                        provider = provider.name(),
-                        "Provider timed out during full scoring"
-                    );
-                }
-            }
+        if let Some(denied) = self.check_deny_lists(context).await {
+            return denied;
         }
 
+        // Built-in signals and every provider run concurrently
+        let (builtin, provider_signals) = tokio::join!(
+            self.compute_builtin_hot_signals(context),
+            collect_provider_signals(
+                self.hot_path_providers
+                    .iter()
+                    .chain(self.background_providers.iter()),
+                context,
+                false
+            )
+        );
+        let mut signals = builtin;
+        signals.extend(provider_signals);
+
         // Apply degraded mode adjustments
-        let mut result = self.scoring_engine.score(signals);
+        let mut result = self.scoring_engine.read().unwrap().score(signals);
+        self.apply_allow_bonus(context, &mut result).await;
         self.apply_degraded_mode_adjustments(&mut result).await;
+        Self::apply_fatal_vetoes(&mut result);
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -287,8 +347,11 @@ impl AdaptiveFilter {
         let mut signals = Vec::new();
         let start = Instant::now();
 
+        // One known-actors read for both checks, released before building signals
+        let (known_deployer, known_sniper) = self.cache.known_actor_flags(&context.creator).await;
+
         // Known deployer check
-        if self.cache.is_known_deployer(&context.creator).await {
+        if known_deployer {
             signals.push(
                 Signal::extreme_risk(SignalType::KnownDeployer, "Known rug deployer")
                     .with_latency(start.elapsed())
@@ -306,7 +369,7 @@ impl AdaptiveFilter {
         }
 
         // Known sniper check (creator being a sniper is suspicious)
-        if self.cache.is_known_sniper(&context.creator).await {
+        if known_sniper {
             signals.push(
                 Signal::new(
                     SignalType::KnownSniper,
@@ -327,6 +390,37 @@ impl AdaptiveFilter {
         let name_signal = self.compute_name_quality_signal(context);
         signals.push(name_signal);
 
+        // Honeypot round trip (run by the caller before scoring)
+        if let Some(ref trip) = context.round_trip {
+            let signal = if trip.failure.is_some() {
+                Signal::extreme_risk(
+                    SignalType::HoneypotSimulation,
+                    format!("FATAL: Honeypot - {}", trip.describe()),
+                )
+            } else {
+                Signal::new(SignalType::HoneypotSimulation, 0.1, 0.9, trip.describe())
+            };
+            signals.push(signal.with_latency(start.elapsed()));
+        }
+
+        // Front-run accumulation (attached by the caller for accumulation entries)
+        if let Some(ref accumulation) = context.accumulation {
+            signals.push(
+                Signal::new(
+                    SignalType::EarlyAccumulation,
+                    (accumulation.float_pct / FULL_ACCUMULATION_FLOAT_PCT).min(1.0),
+                    accumulation.confidence,
+                    format!(
+                        "{:.2} SOL accumulated by {} buyers ({:.2}% of supply)",
+                        accumulation.accumulated_sol,
+                        accumulation.unique_buyers,
+                        accumulation.float_pct
+                    ),
+                )
+                .with_latency(start.elapsed()),
+            );
+        }
+
         // === Enriched data signals (from Helius API) ===
 
         // Mint authority check (CRITICAL - can mint more tokens)
@@ -363,6 +457,19 @@ impl AdaptiveFilter {
                     .with_cached(true),
                 );
             }
+
+            // Token-2022 extensions can seize, freeze or tax tokens regardless of authorities
+            let risky = mint_info.risky_extensions();
+            if !risky.is_empty() {
+                signals.push(
+                    Signal::extreme_risk(
+                        SignalType::FreezeAuthority,
+                        format!("FATAL: Token-2022 extensions {}", risky.join(", ")),
+                    )
+                    .with_latency(start.elapsed())
+                    .with_cached(true),
+                );
+            }
         }
 
         // Creator wallet history analysis
@@ -504,6 +611,19 @@ impl AdaptiveFilter {
     fn compute_liquidity_signal(&self, context: &SignalContext) -> Signal {
         let market_cap = context.market_cap_sol;
 
+        // Close to graduation: little upside left on the curve
+        if context.curve_progress_pct >= NEAR_GRADUATION_PCT {
+            return Signal::new(
+                SignalType::LiquiditySeeding,
+                -0.2,
+                0.6,
+                format!(
+                    "Near graduation: curve {:.0}% ({:.2} SOL mcap)",
+                    context.curve_progress_pct, market_cap
+                ),
+            );
+        }
+
         // Very low liquidity is suspicious
         if market_cap < 0.1 {
             return Signal::new(
@@ -520,7 +640,10 @@ impl AdaptiveFilter {
                 SignalType::LiquiditySeeding,
                 0.2,
                 0.7,
-                format!("Normal liquidity: {:.2} SOL", market_cap),
+                format!(
+                    "Normal liquidity: {:.2} SOL, curve {:.0}%",
+                    market_cap, context.curve_progress_pct
+                ),
             );
         }
 
@@ -530,7 +653,10 @@ impl AdaptiveFilter {
                 SignalType::LiquiditySeeding,
                 0.3,
                 0.6,
-                format!("High liquidity: {:.2} SOL", market_cap),
+                format!(
+                    "High liquidity: {:.2} SOL, curve {:.0}%",
+                    market_cap, context.curve_progress_pct
+                ),
             );
         }
 
@@ -542,46 +668,50 @@ impl AdaptiveFilter {
 
     /// Compute basic name quality signal
     fn compute_name_quality_signal(&self, context: &SignalContext) -> Signal {
-        let name = &context.name;
-        let symbol = &context.symbol;
-
-        // Check for common scam patterns
-        let scam_keywords = ["scam", "rug", "honeypot", "free", "airdrop", "1000x"];
-        let name_lower = name.to_lowercase();
-        let symbol_lower = symbol.to_lowercase();
-
-        for keyword in scam_keywords {
-            if name_lower.contains(keyword) || symbol_lower.contains(keyword) {
-                return Signal::new(
-                    SignalType::NameQuality,
-                    -0.7,
-                    0.9,
-                    format!("Name contains suspicious keyword: {}", keyword),
-                );
-            }
-        }
+        self.name_rules.evaluate(&context.name, &context.symbol)
+    }
 
-        // Check for very short or very long names
-        if name.len() < 2 || symbol.len() < 2 {
-            return Signal::new(SignalType::NameQuality, -0.3, 0.6, "Very short name/symbol");
-        }
+    /// Operator deny lists reject a token before any other scoring
+    async fn check_deny_lists(&self, context: &SignalContext) -> Option<ScoringResult> {
+        let reason = self
+            .cache
+            .deny_reason(&context.mint, &context.creator)
+            .await?;
+        Some(ScoringResult::denied(&reason))
+    }
 
-        if name.len() > 30 {
-            return Signal::new(SignalType::NameQuality, -0.2, 0.5, "Unusually long name");
+    /// Allow-listed creators get a score bonus, unless a signal is fatal
+    async fn apply_allow_bonus(&self, context: &SignalContext, result: &mut ScoringResult) {
+        let bonus = self.config.known_actors.allow_creator_bonus;
+        if bonus == 0.0 || !self.cache.is_allowed_creator(&context.creator).await {
+            return;
         }
-
-        // Check for all caps (often spam)
-        if name
-            .chars()
-            .filter(|c| c.is_alphabetic())
-            .all(|c| c.is_uppercase())
-            && name.len() > 4
-        {
-            return Signal::new(SignalType::NameQuality, -0.1, 0.4, "All caps name");
+        if result.signals.iter().any(|s| s.value <= -1.0) {
+            tracing::debug!(
+                mint = %context.mint,
+                "Allow-listed creator, but a fatal signal blocks the bonus"
+            );
+            return;
         }
+        self.scoring_engine
+            .read()
+            .unwrap()
+            .add_bonus(result, bonus, "allow-listed creator");
+    }
+
+    /// A failed honeypot round trip rejects the token whatever else scored well
+    fn apply_fatal_vetoes(result: &mut ScoringResult) {
+        let veto = result
+            .signals
+            .iter()
+            .find(|s| s.signal_type == SignalType::HoneypotSimulation && s.value <= -1.0)
+            .map(|s| s.reason.clone());
 
-        // Default neutral
-        Signal::neutral(SignalType::NameQuality, "Name appears normal")
+        if let Some(reason) = veto {
+            result.recommendation = Recommendation::Avoid;
+            result.position_size_multiplier = 0.0;
+            result.summary = reason;
+        }
     }
 
     /// Apply degraded mode adjustments to scoring result
@@ -602,7 +732,7 @@ impl AdaptiveFilter {
 
         // Downgrade recommendations under low confidence
         // When uncertain: watch, don't trade
-        if result.confidence < self.config.thresholds.min_confidence {
+        if result.confidence < self.thresholds().min_confidence {
             if matches!(result.recommendation, Recommendation::Opportunity) {
                 // Insufficient confidence for full position -> Observe
                 result.recommendation = Recommendation::Observe;
@@ -639,16 +769,126 @@ impl AdaptiveFilter {
         self.degraded_mode.write().await.cache_cold = false;
     }
 
-    /// Get configuration
+    /// Flag Helius enrichment as throttled (or recovered)
+    ///
+    /// Returns true if the flag changed.
+    pub async fn set_enrichment_throttled(&self, throttled: bool) -> bool {
+        if self.degraded_mode.read().await.enrichment_throttled == throttled {
+            return false;
+        }
+        self.degraded_mode.write().await.enrichment_throttled = throttled;
+        true
+    }
+
+    /// Score thresholds in use (the configured ones until a reload)
+    pub fn thresholds(&self) -> ScoringThresholds {
+        self.scoring_engine.read().unwrap().thresholds().clone()
+    }
+
+    /// Replace the score thresholds and signal weights (config reload)
+    pub fn set_scoring(&self, config: &AdaptiveFilterConfig) {
+        let mut engine = ScoringEngine::with_thresholds(config.thresholds.clone());
+        engine.set_weights(config.signal_weights());
+        *self.scoring_engine.write().unwrap() = engine;
+    }
+
+    /// Get configuration (as loaded at startup)
     pub fn config(&self) -> &AdaptiveFilterConfig {
         &self.config
     }
 }
 
+/// Periodically drop expired cache entries
+fn spawn_cache_sweeper(cache: Arc<FilterCache>, every: Duration) {
+    // Hold a weak reference so the sweeper stops once the filter is dropped
+    let cache = Arc::downgrade(&cache);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let Some(cache) = cache.upgrade() else {
+                break;
+            };
+            let removed = cache.sweep_expired();
+            if removed > 0 {
+                tracing::debug!(removed, "Swept expired cache entries");
+            }
+        }
+    });
+}
+
+/// Periodically snapshot the cache to disk
+fn spawn_cache_snapshots(cache: Arc<FilterCache>, path: std::path::PathBuf, every: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+        interval.tick().await; // First tick fires immediately; nothing new to save yet
+        loop {
+            interval.tick().await;
+            let cache = Arc::clone(&cache);
+            let path = path.clone();
+            match tokio::task::spawn_blocking(move || cache.snapshot_to(&path)).await {
+                Ok(Err(e)) => tracing::warn!(error = %e, "Failed to snapshot filter cache"),
+                Err(e) => tracing::warn!(error = %e, "Cache snapshot task panicked"),
+                Ok(Ok(_)) => {}
+            }
+        }
+    });
+}
+
+/// Malformed event data that must never be scored (fail-closed)
+///
+/// An empty creator is allowed (trade-event entries may not know it); one
+/// that isn't a valid base58 pubkey means the payload is corrupt.
+fn protocol_error(context: &SignalContext) -> Option<&'static str> {
+    if context.mint.is_empty() {
+        return Some("Empty mint address");
+    }
+    if !context.creator.is_empty() && Pubkey::from_str(&context.creator).is_err() {
+        return Some("Invalid creator address");
+    }
+    None
+}
+
+/// Run providers concurrently, each under its own `max_latency_ms`
+///
+/// A provider that overruns contributes nothing; with `penalize_timeouts`
+/// (hot path) it is replaced by an unavailable signal instead.
+async fn collect_provider_signals<'a>(
+    providers: impl Iterator<Item = &'a Arc<dyn SignalProvider>>,
+    context: &SignalContext,
+    penalize_timeouts: bool,
+) -> Vec<Signal> {
+    let runs = providers.map(|provider| async move {
+        let timeout = Duration::from_millis(provider.max_latency_ms());
+        match tokio::time::timeout(timeout, provider.compute_token_signals(context)).await {
+            Ok(provider_signals) => provider_signals,
+            Err(_) if penalize_timeouts => {
+                tracing::warn!(provider = provider.name(), "Hot-path provider timed out");
+                // Add a penalty signal for timeout
+                vec![Signal::unavailable(
+                    SignalType::WalletHistory,
+                    format!("Provider {} timed out", provider.name()),
+                )]
+            }
+            Err(_) => {
+                tracing::warn!(
+                    provider = provider.name(),
+                    "Provider timed out during full scoring"
+                );
+                Vec::new()
+            }
+        }
+    });
+    join_all(runs).await.into_iter().flatten().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const TEST_CREATOR: &str = "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK";
+
     #[tokio::test]
     async fn test_adaptive_filter_creation() {
         let config = AdaptiveFilterConfig::default();
@@ -658,6 +898,24 @@ mod tests {
         assert!(filter.is_degraded().await);
     }
 
+    #[tokio::test]
+    async fn test_enrichment_throttled() {
+        let mut config = AdaptiveFilterConfig::default();
+        config.enrichment.throttled_confidence_penalty = 0.8;
+        let filter = AdaptiveFilter::new(config).await.unwrap();
+
+        assert!(filter.set_enrichment_throttled(true).await);
+        assert!(!filter.set_enrichment_throttled(true).await);
+        let degraded = filter.degraded_mode.read().await;
+        let unthrottled = DegradedMode {
+            enrichment_throttled: false,
+            reason: None,
+            ..*degraded
+        };
+        let ratio = degraded.confidence_penalty() / unthrottled.confidence_penalty();
+        assert!((ratio - 0.8).abs() < 1e-9);
+    }
+
     #[tokio::test]
     async fn test_fast_scoring() {
         let config = AdaptiveFilterConfig::default();
@@ -668,7 +926,7 @@ mod tests {
             "Test Token".to_string(),
             "TEST".to_string(),
             "https://example.com/meta.json".to_string(),
-            "Creator123".to_string(),
+            TEST_CREATOR.to_string(),
             "BondingCurve123".to_string(),
             1000,
             1_000_000_000,
@@ -695,7 +953,7 @@ mod tests {
             "FREE MONEY SCAM".to_string(),
             "SCAM".to_string(),
             "https://example.com/meta.json".to_string(),
-            "Creator123".to_string(),
+            TEST_CREATOR.to_string(),
             "BondingCurve123".to_string(),
             1000,
             1_000_000_000,
@@ -731,5 +989,219 @@ mod tests {
 
         assert_eq!(result.score, -1.0);
         assert_eq!(result.recommendation, Recommendation::Avoid);
+
+        // A creator that isn't a pubkey means the payload is corrupt
+        let context = SignalContext::from_new_token(
+            "Mint".to_string(),
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            "not-a-pubkey!".to_string(),
+            "curve".to_string(),
+            0,
+            0,
+            0,
+            0.0,
+        );
+        let result = filter.score_full(&context).await;
+        assert_eq!(result.recommendation, Recommendation::Avoid);
+        assert!(result.summary.contains("Invalid creator"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_round_trip_vetoes() {
+        use crate::pump::BondingCurve;
+
+        let config = AdaptiveFilterConfig::default();
+        let filter = AdaptiveFilter::new(config).await.unwrap();
+
+        let mut context = SignalContext::from_new_token(
+            "HoneyMint".to_string(),
+            "Good Token".to_string(),
+            "GOOD".to_string(),
+            "https://example.com/meta.json".to_string(),
+            TEST_CREATOR.to_string(),
+            "BondingCurve123".to_string(),
+            1000,
+            1_000_000_000,
+            100_000_000,
+            1.0,
+        );
+        let complete = BondingCurve::new_for_test(1, 1, 0, 0, 0, true);
+        context.round_trip = Some(crate::filter::honeypot::simulate_round_trip(
+            &complete, 1_000_000,
+        ));
+
+        let result = filter.score_fast(&context).await;
+
+        assert_eq!(result.recommendation, Recommendation::Avoid);
+        assert!(result.summary.contains("Honeypot"));
+    }
+
+    #[tokio::test]
+    async fn test_accumulation_signal() {
+        let filter = AdaptiveFilter::new(AdaptiveFilterConfig::default())
+            .await
+            .unwrap();
+        let mut context = SignalContext::from_trade(
+            "AccumMint".to_string(),
+            "curve".to_string(),
+            1_000_000_000,
+            100_000_000,
+            30.0,
+        );
+
+        let signal = |result: &ScoringResult| {
+            result
+                .signals
+                .iter()
+                .find(|s| s.signal_type == SignalType::EarlyAccumulation)
+                .map(|s| s.value)
+        };
+        assert_eq!(signal(&filter.score_fast(&context).await), None);
+
+        context.accumulation = Some(crate::filter::AccumulationMetrics {
+            accumulated_sol: 2.0,
+            float_pct: FULL_ACCUMULATION_FLOAT_PCT / 2.0,
+            unique_buyers: 4,
+            confidence: 0.7,
+        });
+        let value = signal(&filter.score_fast(&context).await).unwrap();
+        assert!((value - 0.5).abs() < 1e-9);
+
+        // Capped at a full-strength signal
+        context.accumulation.as_mut().unwrap().float_pct = FULL_ACCUMULATION_FLOAT_PCT * 3.0;
+        assert_eq!(signal(&filter.score_fast(&context).await), Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_cache_snapshot_warms_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        let seed = FilterCache::new();
+        for i in 0..=WARM_CACHE_ITEMS {
+            let address = format!("wallet{}", i);
+            seed.set_wallet(
+                &address,
+                crate::filter::types::WalletHistory {
+                    address: address.clone(),
+                    fetched_at: chrono::Utc::now(),
+                    ..Default::default()
+                },
+            );
+        }
+        seed.snapshot_to(&path).unwrap();
+
+        let mut config = AdaptiveFilterConfig::default();
+        config.cache.persist_path = Some(path.to_string_lossy().to_string());
+        let filter = AdaptiveFilter::new(config).await.unwrap();
+
+        assert_eq!(filter.cache().wallet_cache_size(), WARM_CACHE_ITEMS + 1);
+        assert!(!filter.degraded_mode.read().await.cache_cold);
+    }
+
+    #[tokio::test]
+    async fn test_score_fast_cached() {
+        let filter = AdaptiveFilter::new(AdaptiveFilterConfig::default())
+            .await
+            .unwrap();
+        let context = SignalContext::from_new_token(
+            "CachedMint".to_string(),
+            "Cached Token".to_string(),
+            "CACHE".to_string(),
+            "https://example.com/meta.json".to_string(),
+            TEST_CREATOR.to_string(),
+            "BondingCurve123".to_string(),
+            1000,
+            1_000_000_000,
+            100_000_000,
+            1.0,
+        );
+
+        let fresh = filter.score_fast_cached(&context).await;
+        let hit = filter.score_fast_cached(&context).await;
+        assert!(!fresh.cached);
+        assert!(hit.cached);
+        assert_eq!(hit.score, fresh.score);
+
+        // New enrichment data forces a rescore
+        filter.cache().set_holders("CachedMint", Vec::new());
+        assert!(!filter.score_fast_cached(&context).await.cached);
+    }
+
+    /// Hot-path provider that sleeps before returning one signal
+    struct SleepyProvider {
+        name: &'static str,
+        delay_ms: u64,
+    }
+
+    #[async_trait::async_trait]
+    impl SignalProvider for SleepyProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn signal_types(&self) -> &[SignalType] {
+            &[SignalType::VelocityMetrics]
+        }
+
+        fn is_hot_path(&self) -> bool {
+            true
+        }
+
+        async fn compute_token_signals(&self, _context: &SignalContext) -> Vec<Signal> {
+            tokio::time::sleep(Duration::from_millis(self.delay_ms)).await;
+            vec![Signal::neutral(SignalType::VelocityMetrics, self.name)]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hot_path_providers_run_concurrently() {
+        let mut filter = AdaptiveFilter::new(AdaptiveFilterConfig::default())
+            .await
+            .unwrap();
+        for name in ["slow_a", "slow_b"] {
+            filter.register_provider(Arc::new(SleepyProvider { name, delay_ms: 30 }));
+        }
+        let context = SignalContext::from_trade(
+            "ConcurrentMint".to_string(),
+            "curve".to_string(),
+            0,
+            0,
+            10.0,
+        );
+
+        let start = Instant::now();
+        let result = filter.score_fast(&context).await;
+        let elapsed = start.elapsed();
+
+        assert!(
+            elapsed < Duration::from_millis(40),
+            "two 30ms providers took {:?}",
+            elapsed
+        );
+        assert!(["slow_a", "slow_b"]
+            .iter()
+            .all(|name| result.signals.iter().any(|s| s.reason == *name)));
+    }
+
+    #[tokio::test]
+    async fn test_hot_path_timeout_penalty() {
+        let mut filter = AdaptiveFilter::new(AdaptiveFilterConfig::default())
+            .await
+            .unwrap();
+        filter.register_provider(Arc::new(SleepyProvider {
+            name: "stuck",
+            delay_ms: 500,
+        }));
+        let context =
+            SignalContext::from_trade("StuckMint".to_string(), "curve".to_string(), 0, 0, 10.0);
+
+        let result = filter.score_fast(&context).await;
+        assert!(result
+            .signals
+            .iter()
+            .any(|s| s.reason == "Provider stuck timed out"));
     }
 }
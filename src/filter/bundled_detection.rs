@@ -8,12 +8,15 @@
 //! 2. Identical amounts: Buy amounts within 1% variance
 //! 3. Common funding: Same SOL source within 24h
 //!
-//! When bundled wallets sell together -> kill-switch
+//! Buys in the first `early_window_secs` after a launch are collected per
+//! mint and analyzed when the window closes. When bundled wallets sell
+//! together -> kill-switch
 
 use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -49,6 +52,14 @@ pub struct BundledDetectionConfig {
     /// Window in seconds for coordinated sell detection
     #[serde(default = "default_sell_window_secs")]
     pub sell_window_secs: u64,
+
+    /// Seconds after launch during which buys are collected for analysis
+    #[serde(default = "default_early_window_secs")]
+    pub early_window_secs: u64,
+
+    /// Maximum early buys kept per launch
+    #[serde(default = "default_max_early_buys")]
+    pub max_early_buys: usize,
 }
 
 fn default_enabled() -> bool {
@@ -72,6 +83,12 @@ fn default_sell_together_count() -> u32 {
 fn default_sell_window_secs() -> u64 {
     30
 }
+fn default_early_window_secs() -> u64 {
+    10
+}
+fn default_max_early_buys() -> usize {
+    30
+}
 
 impl Default for BundledDetectionConfig {
     fn default() -> Self {
@@ -83,6 +100,8 @@ impl Default for BundledDetectionConfig {
             common_funding_threshold: default_common_funding_threshold(),
             sell_together_count: default_sell_together_count(),
             sell_window_secs: default_sell_window_secs(),
+            early_window_secs: default_early_window_secs(),
+            max_early_buys: default_max_early_buys(),
         }
     }
 }
@@ -141,6 +160,12 @@ pub struct BundleSellAlert {
     pub window_secs: u64,
 }
 
+/// Buys collected for a launch while its detection window is open
+struct LaunchWindow {
+    opened_at: DateTime<Utc>,
+    buys: Vec<EarlyBuy>,
+}
+
 /// Bundled wallet detector
 pub struct BundledDetector {
     config: BundledDetectionConfig,
@@ -149,6 +174,11 @@ pub struct BundledDetector {
     known_bundles: DashMap<String, BundleGroup>,
     /// Funding sources cache: wallet -> [(source, amount, timestamp)]
     funding_cache: DashMap<String, Vec<(String, f64, DateTime<Utc>)>>,
+    /// Launches still inside their detection window
+    windows: DashMap<String, LaunchWindow>,
+    /// Launches analyzed / flagged since the last `take_flag_counts`
+    launches_analyzed: AtomicU64,
+    launches_flagged: AtomicU64,
 }
 
 impl BundledDetector {
@@ -159,9 +189,83 @@ impl BundledDetector {
             helius,
             known_bundles: DashMap::new(),
             funding_cache: DashMap::new(),
+            windows: DashMap::new(),
+            launches_analyzed: AtomicU64::new(0),
+            launches_flagged: AtomicU64::new(0),
         }
     }
 
+    /// Detector configuration
+    pub fn config(&self) -> &BundledDetectionConfig {
+        &self.config
+    }
+
+    /// Start collecting early buys for a new launch
+    pub fn open_window(&self, mint: &str) {
+        if !self.config.enabled {
+            return;
+        }
+        self.windows.insert(
+            mint.to_string(),
+            LaunchWindow {
+                opened_at: Utc::now(),
+                buys: Vec::new(),
+            },
+        );
+    }
+
+    /// Add a buy to the launch's window, returning false if no window is open
+    pub fn record_early_buy(&self, mint: &str, buy: EarlyBuy) -> bool {
+        let mut window = match self.windows.get_mut(mint) {
+            Some(window) => window,
+            None => return false,
+        };
+        let deadline = window.opened_at + Duration::seconds(self.config.early_window_secs as i64);
+        if buy.timestamp > deadline || window.buys.len() >= self.config.max_early_buys {
+            return false;
+        }
+        window.buys.push(buy);
+        true
+    }
+
+    /// Close the launch's window and analyze the buys it collected
+    pub async fn close_window(&self, mint: &str) -> Option<BundleGroup> {
+        let (_, window) = self.windows.remove(mint)?;
+        self.launches_analyzed.fetch_add(1, Ordering::Relaxed);
+        let bundle = self.analyze_early_buyers(mint, &window.buys).await;
+        if bundle.is_some() {
+            self.launches_flagged.fetch_add(1, Ordering::Relaxed);
+        }
+        bundle
+    }
+
+    /// Open a window for `mint` and analyze it once `early_window_secs` pass
+    pub fn spawn_window<F>(self: &Arc<Self>, mint: String, on_bundle: F)
+    where
+        F: FnOnce(BundleGroup) + Send + 'static,
+    {
+        if !self.config.enabled {
+            return;
+        }
+        self.open_window(&mint);
+        let detector = Arc::clone(self);
+        let window = std::time::Duration::from_secs(self.config.early_window_secs);
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            if let Some(bundle) = detector.close_window(&mint).await {
+                on_bundle(bundle);
+            }
+        });
+    }
+
+    /// (analyzed, flagged) launch counts since the last call
+    pub fn take_flag_counts(&self) -> (u64, u64) {
+        (
+            self.launches_analyzed.swap(0, Ordering::Relaxed),
+            self.launches_flagged.swap(0, Ordering::Relaxed),
+        )
+    }
+
     /// Analyze early buyers for coordinated behavior
     pub async fn analyze_early_buyers(
         &self,
@@ -195,10 +299,10 @@ impl BundledDetector {
             return None;
         }
 
-        // Collect all suspicious wallets
+        // Collect the wallets each check implicated
         let mut bundled_wallets: HashSet<String> = HashSet::new();
-        for buy in early_buys {
-            bundled_wallets.insert(buy.wallet.clone());
+        for reason in &reasons {
+            bundled_wallets.extend(self.implicated_wallets(reason, early_buys));
         }
 
         let detection_reason = if reasons.len() == 1 {
@@ -231,6 +335,39 @@ impl BundledDetector {
         Some(bundle)
     }
 
+    /// Wallets among `early_buys` that a detection reason points at
+    fn implicated_wallets(
+        &self,
+        reason: &BundleDetectionReason,
+        early_buys: &[EarlyBuy],
+    ) -> HashSet<String> {
+        let matching = |f: &dyn Fn(&EarlyBuy) -> bool| -> HashSet<String> {
+            early_buys
+                .iter()
+                .filter(|b| f(b))
+                .map(|b| b.wallet.clone())
+                .collect()
+        };
+        match reason {
+            BundleDetectionReason::SameSlotBuys { slot, .. } => {
+                matching(&|b| b.slot == Some(*slot))
+            }
+            BundleDetectionReason::IdenticalAmounts { amount_sol, .. } => matching(&|b| {
+                (b.amount_sol - amount_sol).abs() / amount_sol <= self.config.amount_variance
+            }),
+            BundleDetectionReason::CommonFunding { source, .. } => matching(&|b| {
+                self.funding_cache
+                    .get(&b.wallet)
+                    .map(|sources| sources.iter().any(|(s, _, _)| s == source))
+                    .unwrap_or(false)
+            }),
+            BundleDetectionReason::Multiple(reasons) => reasons
+                .iter()
+                .flat_map(|r| self.implicated_wallets(r, early_buys))
+                .collect(),
+        }
+    }
+
     /// Check for same-slot buys
     fn check_same_slot_buys(&self, early_buys: &[EarlyBuy]) -> Option<BundleDetectionReason> {
         // Group buys by slot
@@ -466,10 +603,23 @@ impl BundledDetector {
         self.known_bundles.remove(mint);
     }
 
+    /// Drop bundles older than `max_age` unless `keep` says the mint is still needed
+    pub fn prune<F>(&self, max_age: Duration, keep: F) -> usize
+    where
+        F: Fn(&str) -> bool,
+    {
+        let cutoff = Utc::now() - max_age;
+        let before = self.known_bundles.len();
+        self.known_bundles
+            .retain(|mint, bundle| bundle.detected_at > cutoff || keep(mint));
+        before - self.known_bundles.len()
+    }
+
     /// Clear all tracked bundles
     pub fn clear(&self) {
         self.known_bundles.clear();
         self.funding_cache.clear();
+        self.windows.clear();
     }
 }
 
@@ -574,4 +724,50 @@ mod tests {
         assert!(!detector.is_bundled("token1", "wallet3"));
         assert!(!detector.is_bundled("token2", "wallet1"));
     }
+
+    fn buy(wallet: &str, amount_sol: f64, timestamp: DateTime<Utc>) -> EarlyBuy {
+        EarlyBuy {
+            wallet: wallet.to_string(),
+            amount_sol,
+            slot: None,
+            timestamp,
+            signature: format!("sig-{}", wallet),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_launch_window() {
+        let config = BundledDetectionConfig {
+            max_early_buys: 3,
+            ..Default::default()
+        };
+        let detector = BundledDetector::new(config, None);
+        let now = Utc::now();
+
+        // No window, no buys
+        assert!(!detector.record_early_buy("token1", buy("w0", 1.0, now)));
+
+        detector.open_window("token1");
+        assert!(detector.record_early_buy("token1", buy("w1", 1.0, now)));
+        assert!(detector.record_early_buy("token1", buy("w2", 1.0, now)));
+        assert!(detector.record_early_buy("token1", buy("w3", 0.2, now)));
+        // Over the cap, then past the window
+        assert!(!detector.record_early_buy("token1", buy("w4", 1.0, now)));
+        assert!(!detector.record_early_buy(
+            "token1",
+            buy("w5", 1.0, now + Duration::seconds(60))
+        ));
+
+        let bundle = detector.close_window("token1").await.unwrap();
+        let mut wallets = bundle.wallets.clone();
+        wallets.sort();
+        assert_eq!(wallets, vec!["w1".to_string(), "w2".to_string()]);
+
+        detector.open_window("token2");
+        detector.record_early_buy("token2", buy("w6", 0.3, now));
+        assert!(detector.close_window("token2").await.is_none());
+
+        assert_eq!(detector.take_flag_counts(), (2, 1));
+        assert_eq!(detector.take_flag_counts(), (0, 0));
+    }
 }
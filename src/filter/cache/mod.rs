@@ -3,14 +3,19 @@
 //! Provides fast access to pre-computed data that would be too slow
 //! to fetch during the hot path.
 
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::error::{Error, Result};
 use crate::filter::helius::MintInfo;
-use crate::filter::types::{TokenHolderInfo, WalletHistory};
+use crate::filter::scoring::ScoringResult;
+use crate::filter::types::{TokenHolderInfo, TradeRecord, WalletHistory};
 
 // Submodules for specific cache types
 // pub mod known_actors;
@@ -44,19 +49,60 @@ impl Default for CacheConfig {
     }
 }
 
+/// TTL cache entry that tracks when it was last read
+trait CacheEntry {
+    fn is_expired(&self) -> bool;
+    fn last_accessed(&self) -> Instant;
+}
+
+/// Remove the `count` least recently accessed entries
+fn evict_stalest<V: CacheEntry>(map: &DashMap<String, V>, count: usize) {
+    let mut entries: Vec<(Instant, String)> = map
+        .iter()
+        .map(|e| (e.value().last_accessed(), e.key().clone()))
+        .collect();
+    if count < entries.len() {
+        entries.select_nth_unstable_by_key(count, |(accessed, _)| *accessed);
+        entries.truncate(count);
+    }
+    for (_, key) in entries {
+        map.remove(&key);
+    }
+}
+
+/// Make room for one insert, evicting ~10% of capacity (stalest first)
+fn make_room<V: CacheEntry>(map: &DashMap<String, V>, key: &str, capacity: usize) {
+    if map.len() >= capacity && !map.contains_key(key) {
+        // Expired entries go first; only evict live ones if that wasn't enough
+        if remove_expired(map) == 0 || map.len() >= capacity {
+            evict_stalest(map, (capacity / 10).max(1));
+        }
+    }
+}
+
+/// Remove expired entries, returning how many were dropped
+fn remove_expired<V: CacheEntry>(map: &DashMap<String, V>) -> usize {
+    let before = map.len();
+    map.retain(|_, entry| !entry.is_expired());
+    before.saturating_sub(map.len())
+}
+
 /// Entry in the wallet cache with TTL
 #[derive(Clone)]
 pub struct CachedWallet {
     pub history: WalletHistory,
     pub cached_at: Instant,
+    pub last_accessed: Instant,
     pub ttl: Duration,
 }
 
 impl CachedWallet {
     pub fn new(history: WalletHistory, ttl: Duration) -> Self {
+        let now = Instant::now();
         Self {
             history,
-            cached_at: Instant::now(),
+            cached_at: now,
+            last_accessed: now,
             ttl,
         }
     }
@@ -66,19 +112,32 @@ impl CachedWallet {
     }
 }
 
+impl CacheEntry for CachedWallet {
+    fn is_expired(&self) -> bool {
+        CachedWallet::is_expired(self)
+    }
+
+    fn last_accessed(&self) -> Instant {
+        self.last_accessed
+    }
+}
+
 /// Entry in the holder cache with TTL
 #[derive(Clone)]
 pub struct CachedHolders {
     pub holders: Vec<TokenHolderInfo>,
     pub cached_at: Instant,
+    pub last_accessed: Instant,
     pub ttl: Duration,
 }
 
 impl CachedHolders {
     pub fn new(holders: Vec<TokenHolderInfo>, ttl: Duration) -> Self {
+        let now = Instant::now();
         Self {
             holders,
-            cached_at: Instant::now(),
+            cached_at: now,
+            last_accessed: now,
             ttl,
         }
     }
@@ -88,19 +147,32 @@ impl CachedHolders {
     }
 }
 
+impl CacheEntry for CachedHolders {
+    fn is_expired(&self) -> bool {
+        CachedHolders::is_expired(self)
+    }
+
+    fn last_accessed(&self) -> Instant {
+        self.last_accessed
+    }
+}
+
 /// Entry in the mint info cache with TTL
 #[derive(Clone)]
 pub struct CachedMintInfo {
     pub info: MintInfo,
     pub cached_at: Instant,
+    pub last_accessed: Instant,
     pub ttl: Duration,
 }
 
 impl CachedMintInfo {
     pub fn new(info: MintInfo, ttl: Duration) -> Self {
+        let now = Instant::now();
         Self {
             info,
-            cached_at: Instant::now(),
+            cached_at: now,
+            last_accessed: now,
             ttl,
         }
     }
@@ -110,8 +182,81 @@ impl CachedMintInfo {
     }
 }
 
+impl CacheEntry for CachedMintInfo {
+    fn is_expired(&self) -> bool {
+        CachedMintInfo::is_expired(self)
+    }
+
+    fn last_accessed(&self) -> Instant {
+        self.last_accessed
+    }
+}
+
+/// Entry in the score cache with TTL
+#[derive(Clone)]
+pub struct CachedScore {
+    pub result: ScoringResult,
+    pub cached_at: Instant,
+    pub last_accessed: Instant,
+    pub ttl: Duration,
+}
+
+impl CachedScore {
+    pub fn new(result: ScoringResult, ttl: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            result,
+            cached_at: now,
+            last_accessed: now,
+            ttl,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.cached_at.elapsed() > self.ttl
+    }
+}
+
+impl CacheEntry for CachedScore {
+    fn is_expired(&self) -> bool {
+        CachedScore::is_expired(self)
+    }
+
+    fn last_accessed(&self) -> Instant {
+        self.last_accessed
+    }
+}
+
+/// On-disk snapshot of the wallet and mint info caches
+#[derive(Serialize, Deserialize)]
+struct CacheSnapshot {
+    saved_at: DateTime<Utc>,
+    wallets: Vec<SnapshotEntry<WalletHistory>>,
+    mint_infos: Vec<SnapshotEntry<MintInfo>>,
+}
+
+/// Snapshot entry with wall-clock cache time (`Instant` doesn't survive restarts)
+#[derive(Serialize, Deserialize)]
+struct SnapshotEntry<T> {
+    key: String,
+    cached_at: DateTime<Utc>,
+    value: T,
+}
+
+/// Convert a monotonic cache time to wall-clock time
+fn instant_to_utc(cached_at: Instant, now: DateTime<Utc>) -> DateTime<Utc> {
+    now - chrono::Duration::from_std(cached_at.elapsed()).unwrap_or_default()
+}
+
+/// Age of a snapshot entry, or None if it has outlived `ttl`
+fn surviving_age(cached_at: DateTime<Utc>, now: DateTime<Utc>, ttl: Duration) -> Option<Duration> {
+    // Entries stamped in the future (clock skew) count as fresh
+    let age = (now - cached_at).to_std().unwrap_or_default();
+    (age <= ttl).then_some(age)
+}
+
 /// Known actors (deployers, snipers, trusted wallets)
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct KnownActors {
     /// Known rug deployer addresses
     pub deployers: HashSet<String>,
@@ -119,6 +264,12 @@ pub struct KnownActors {
     pub snipers: HashSet<String>,
     /// Trusted wallets for copy-trading
     pub trusted: HashSet<String>,
+    /// Operator deny list: creators
+    pub deny_creators: HashSet<String>,
+    /// Operator deny list: mints
+    pub deny_mints: HashSet<String>,
+    /// Operator allow list: creators
+    pub allow_creators: HashSet<String>,
     /// Last refresh time
     pub last_refresh: Option<Instant>,
 }
@@ -139,6 +290,22 @@ impl KnownActors {
         self.trusted.contains(address)
     }
 
+    /// Why the operator deny lists reject this token, if they do
+    pub fn deny_reason(&self, mint: &str, creator: &str) -> Option<String> {
+        if self.deny_mints.contains(mint) {
+            Some(format!("mint {} is deny-listed", mint))
+        } else if self.deny_creators.contains(creator) {
+            Some(format!("creator {} is deny-listed", creator))
+        } else {
+            None
+        }
+    }
+
+    /// Check if a creator is on the operator allow list
+    pub fn is_allowed_creator(&self, address: &str) -> bool {
+        self.allow_creators.contains(address)
+    }
+
     /// Add a deployer to the blacklist
     pub fn add_deployer(&mut self, address: String) {
         self.deployers.insert(address);
@@ -154,49 +321,54 @@ impl KnownActors {
         self.trusted.insert(address);
     }
 
+    /// Parse an address list: one address per line, `#` starts a comment
+    pub fn parse_list(content: &str) -> HashSet<String> {
+        content
+            .lines()
+            .filter_map(|line| {
+                let addr = line.split('#').next().unwrap_or_default().trim();
+                (!addr.is_empty()).then(|| addr.to_string())
+            })
+            .collect()
+    }
+
     /// Load from files
     pub fn load_from_files(
         deployers_path: Option<&str>,
         snipers_path: Option<&str>,
         trusted_path: Option<&str>,
     ) -> Self {
-        let mut actors = Self::default();
-
-        if let Some(path) = deployers_path {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for line in content.lines() {
-                    let addr = line.trim();
-                    if !addr.is_empty() && !addr.starts_with('#') {
-                        actors.deployers.insert(addr.to_string());
-                    }
-                }
-            }
-        }
-
-        if let Some(path) = snipers_path {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for line in content.lines() {
-                    let addr = line.trim();
-                    if !addr.is_empty() && !addr.starts_with('#') {
-                        actors.snipers.insert(addr.to_string());
-                    }
-                }
-            }
-        }
+        let read = |path: Option<&str>| {
+            path.and_then(|p| std::fs::read_to_string(p).ok())
+                .map(|content| Self::parse_list(&content))
+                .unwrap_or_default()
+        };
 
-        if let Some(path) = trusted_path {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                for line in content.lines() {
-                    let addr = line.trim();
-                    if !addr.is_empty() && !addr.starts_with('#') {
-                        actors.trusted.insert(addr.to_string());
-                    }
-                }
-            }
+        Self {
+            deployers: read(deployers_path),
+            snipers: read(snipers_path),
+            trusted: read(trusted_path),
+            last_refresh: Some(Instant::now()),
+            ..Default::default()
         }
+    }
 
-        actors.last_refresh = Some(Instant::now());
-        actors
+    /// Entries added and removed going from `self` to `other`
+    pub fn diff(&self, other: &KnownActors) -> (usize, usize) {
+        let pairs = [
+            (&self.deployers, &other.deployers),
+            (&self.snipers, &other.snipers),
+            (&self.trusted, &other.trusted),
+            (&self.deny_creators, &other.deny_creators),
+            (&self.deny_mints, &other.deny_mints),
+            (&self.allow_creators, &other.allow_creators),
+        ];
+        pairs.iter().fold((0, 0), |(added, removed), (old, new)| {
+            (
+                added + new.difference(old).count(),
+                removed + old.difference(new).count(),
+            )
+        })
     }
 
     /// Get statistics
@@ -219,6 +391,12 @@ pub struct FilterCache {
     /// Mint info cache (mint -> mint authority info)
     mint_info_cache: DashMap<String, CachedMintInfo>,
 
+    /// Fast-path scoring results (mint -> result)
+    score_cache: DashMap<String, CachedScore>,
+
+    /// Recent trades per token (ring buffer of `trade_flow_buffer_size`)
+    trade_flow: DashMap<String, VecDeque<TradeRecord>>,
+
     /// Known actors (loaded at startup, refreshed periodically)
     known_actors: Arc<RwLock<KnownActors>>,
 
@@ -276,6 +454,8 @@ impl FilterCache {
             wallet_cache: DashMap::with_capacity(config.wallet_cache_size),
             holder_cache: DashMap::with_capacity(config.score_cache_size),
             mint_info_cache: DashMap::with_capacity(config.score_cache_size),
+            score_cache: DashMap::with_capacity(config.score_cache_size),
+            trade_flow: DashMap::with_capacity(config.score_cache_size),
             known_actors: Arc::new(RwLock::new(KnownActors::default())),
             stats: Arc::new(CacheStats::default()),
             config,
@@ -284,8 +464,9 @@ impl FilterCache {
 
     /// Get wallet history from cache
     pub fn get_wallet(&self, address: &str) -> Option<WalletHistory> {
-        if let Some(entry) = self.wallet_cache.get(address) {
+        if let Some(mut entry) = self.wallet_cache.get_mut(address) {
             if !entry.is_expired() {
+                entry.last_accessed = Instant::now();
                 self.stats.record_wallet_hit();
                 return Some(entry.history.clone());
             }
@@ -301,29 +482,15 @@ impl FilterCache {
     pub fn set_wallet(&self, address: &str, history: WalletHistory) {
         let ttl = Duration::from_secs(self.config.wallet_cache_ttl_secs);
         let entry = CachedWallet::new(history, ttl);
-
-        // Evict if over capacity (simple random eviction)
-        if self.wallet_cache.len() >= self.config.wallet_cache_size {
-            // Remove ~10% of entries
-            let to_remove = self.config.wallet_cache_size / 10;
-            let keys: Vec<_> = self
-                .wallet_cache
-                .iter()
-                .take(to_remove)
-                .map(|r| r.key().clone())
-                .collect();
-            for key in keys {
-                self.wallet_cache.remove(&key);
-            }
-        }
-
+        make_room(&self.wallet_cache, address, self.config.wallet_cache_size);
         self.wallet_cache.insert(address.to_string(), entry);
     }
 
     /// Get token holders from cache
     pub fn get_holders(&self, mint: &str) -> Option<Vec<TokenHolderInfo>> {
-        if let Some(entry) = self.holder_cache.get(mint) {
+        if let Some(mut entry) = self.holder_cache.get_mut(mint) {
             if !entry.is_expired() {
+                entry.last_accessed = Instant::now();
                 return Some(entry.holders.clone());
             }
             drop(entry);
@@ -336,13 +503,16 @@ impl FilterCache {
     pub fn set_holders(&self, mint: &str, holders: Vec<TokenHolderInfo>) {
         let ttl = Duration::from_secs(self.config.score_cache_ttl_secs);
         let entry = CachedHolders::new(holders, ttl);
+        make_room(&self.holder_cache, mint, self.config.score_cache_size);
         self.holder_cache.insert(mint.to_string(), entry);
+        self.invalidate_score(mint);
     }
 
     /// Get mint info from cache
     pub fn get_mint_info(&self, mint: &str) -> Option<MintInfo> {
-        if let Some(entry) = self.mint_info_cache.get(mint) {
+        if let Some(mut entry) = self.mint_info_cache.get_mut(mint) {
             if !entry.is_expired() {
+                entry.last_accessed = Instant::now();
                 return Some(entry.info.clone());
             }
             drop(entry);
@@ -355,7 +525,75 @@ impl FilterCache {
     pub fn set_mint_info(&self, mint: &str, info: MintInfo) {
         let ttl = Duration::from_secs(self.config.score_cache_ttl_secs);
         let entry = CachedMintInfo::new(info, ttl);
+        make_room(&self.mint_info_cache, mint, self.config.score_cache_size);
         self.mint_info_cache.insert(mint.to_string(), entry);
+        self.invalidate_score(mint);
+    }
+
+    /// Get a cached scoring result
+    pub fn get_score(&self, mint: &str) -> Option<ScoringResult> {
+        if let Some(mut entry) = self.score_cache.get_mut(mint) {
+            if !entry.is_expired() {
+                entry.last_accessed = Instant::now();
+                return Some(entry.result.clone());
+            }
+            drop(entry);
+            self.score_cache.remove(mint);
+        }
+        None
+    }
+
+    /// Store a scoring result
+    pub fn set_score(&self, mint: &str, result: ScoringResult) {
+        let ttl = Duration::from_secs(self.config.score_cache_ttl_secs);
+        let entry = CachedScore::new(result, ttl);
+        make_room(&self.score_cache, mint, self.config.score_cache_size);
+        self.score_cache.insert(mint.to_string(), entry);
+    }
+
+    /// Drop the cached score so new enrichment data is picked up on next scoring
+    pub fn invalidate_score(&self, mint: &str) {
+        self.score_cache.remove(mint);
+    }
+
+    /// Append a trade to the token's flow buffer, dropping the oldest when full
+    pub fn record_trade(&self, mint: &str, trade: TradeRecord) {
+        // Evict whole tokens once too many are tracked (simple random eviction)
+        if !self.trade_flow.contains_key(mint)
+            && self.trade_flow.len() >= self.config.score_cache_size
+        {
+            let to_remove = (self.config.score_cache_size / 10).max(1);
+            let keys: Vec<_> = self
+                .trade_flow
+                .iter()
+                .take(to_remove)
+                .map(|r| r.key().clone())
+                .collect();
+            for key in keys {
+                self.trade_flow.remove(&key);
+            }
+        }
+
+        let mut buffer = self.trade_flow.entry(mint.to_string()).or_default();
+        if buffer.len() >= self.config.trade_flow_buffer_size {
+            buffer.pop_front();
+        }
+        buffer.push_back(trade);
+    }
+
+    /// Get buffered trades for a token, oldest first
+    pub fn get_trades(&self, mint: &str) -> Vec<TradeRecord> {
+        self.trade_flow
+            .get(mint)
+            .map(|buffer| buffer.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Timestamp of the oldest buffered trade for a token
+    pub fn first_trade_time(&self, mint: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.trade_flow
+            .get(mint)
+            .and_then(|buffer| buffer.front().map(|t| t.timestamp))
     }
 
     /// Check if wallet is a known deployer (fast, cached)
@@ -372,25 +610,43 @@ impl FilterCache {
         actors.is_known_sniper(address)
     }
 
+    /// Known deployer and known sniper flags under a single read lock
+    pub async fn known_actor_flags(&self, address: &str) -> (bool, bool) {
+        self.stats.record_known_actor_check();
+        self.stats.record_known_actor_check();
+        let actors = self.known_actors.read().await;
+        (
+            actors.is_known_deployer(address),
+            actors.is_known_sniper(address),
+        )
+    }
+
     /// Check if wallet is trusted (fast, cached)
     pub async fn is_trusted(&self, address: &str) -> bool {
         let actors = self.known_actors.read().await;
         actors.is_trusted(address)
     }
 
-    /// Load known actors from files
-    pub async fn load_known_actors(
-        &self,
-        deployers_path: Option<&str>,
-        snipers_path: Option<&str>,
-        trusted_path: Option<&str>,
-    ) {
-        let actors = KnownActors::load_from_files(deployers_path, snipers_path, trusted_path);
+    /// Why the operator deny lists reject this token, if they do
+    pub async fn deny_reason(&self, mint: &str, creator: &str) -> Option<String> {
+        self.known_actors.read().await.deny_reason(mint, creator)
+    }
+
+    /// Check if a creator is on the operator allow list
+    pub async fn is_allowed_creator(&self, address: &str) -> bool {
+        self.known_actors.read().await.is_allowed_creator(address)
+    }
+
+    /// Install the known actors loaded at startup
+    pub async fn load_known_actors(&self, actors: KnownActors) {
         let (d, s, t) = actors.stats();
         tracing::info!(
             deployers = d,
             snipers = s,
             trusted = t,
+            deny_creators = actors.deny_creators.len(),
+            deny_mints = actors.deny_mints.len(),
+            allow_creators = actors.allow_creators.len(),
             "Loaded known actors"
         );
         *self.known_actors.write().await = actors;
@@ -406,6 +662,19 @@ impl FilterCache {
         self.known_actors.write().await.add_sniper(address);
     }
 
+    /// Copy of the current known actor lists
+    pub async fn known_actors(&self) -> KnownActors {
+        self.known_actors.read().await.clone()
+    }
+
+    /// Swap in freshly loaded known actors, returning (added, removed)
+    pub async fn replace_known_actors(&self, actors: KnownActors) -> (usize, usize) {
+        let mut current = self.known_actors.write().await;
+        let diff = current.diff(&actors);
+        *current = actors;
+        diff
+    }
+
     /// Get cache statistics
     pub fn stats(&self) -> &CacheStats {
         &self.stats
@@ -421,6 +690,8 @@ impl FilterCache {
         self.wallet_cache.clear();
         self.holder_cache.clear();
         self.mint_info_cache.clear();
+        self.score_cache.clear();
+        self.trade_flow.clear();
         *self.known_actors.write().await = KnownActors::default();
     }
 
@@ -433,6 +704,136 @@ impl FilterCache {
     pub fn total_cached_items(&self) -> usize {
         self.wallet_cache.len() + self.holder_cache.len() + self.mint_info_cache.len()
     }
+
+    /// Drop expired wallet, holder, mint info and score entries
+    ///
+    /// Reads only evict the entry they touch, so entries nobody asks for
+    /// again would otherwise linger until capacity eviction. Returns the
+    /// number of entries removed.
+    pub fn sweep_expired(&self) -> usize {
+        remove_expired(&self.wallet_cache)
+            + remove_expired(&self.holder_cache)
+            + remove_expired(&self.mint_info_cache)
+            + remove_expired(&self.score_cache)
+    }
+
+    /// Write unexpired wallet histories and mint info to disk
+    ///
+    /// The snapshot is written to a temporary file and renamed into place so
+    /// a crash mid-write never leaves a truncated file. Returns the number of
+    /// entries saved.
+    pub fn snapshot_to(&self, path: &Path) -> Result<usize> {
+        let now = Utc::now();
+        let snapshot = CacheSnapshot {
+            saved_at: now,
+            wallets: self
+                .wallet_cache
+                .iter()
+                .filter(|e| !e.is_expired())
+                .map(|e| SnapshotEntry {
+                    key: e.key().clone(),
+                    cached_at: instant_to_utc(e.cached_at, now),
+                    value: e.history.clone(),
+                })
+                .collect(),
+            mint_infos: self
+                .mint_info_cache
+                .iter()
+                .filter(|e| !e.is_expired())
+                .map(|e| SnapshotEntry {
+                    key: e.key().clone(),
+                    cached_at: instant_to_utc(e.cached_at, now),
+                    value: e.info.clone(),
+                })
+                .collect(),
+        };
+        let count = snapshot.wallets.len() + snapshot.mint_infos.len();
+
+        let data = serde_json::to_vec(&snapshot)
+            .map_err(|e| Error::Serialization(format!("Cache snapshot: {}", e)))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| Error::Io(e.to_string()))?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, data).map_err(|e| Error::Io(e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| Error::Io(e.to_string()))?;
+
+        tracing::debug!(entries = count, path = %path.display(), "Saved cache snapshot");
+        Ok(count)
+    }
+
+    /// Load a snapshot written by `snapshot_to`, dropping expired entries
+    ///
+    /// A missing or corrupted file is ignored. Entries keep their original
+    /// cache time, so they expire as if the process had never restarted.
+    /// Returns the number of entries restored.
+    pub fn load_from(&self, path: &Path) -> usize {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return 0,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to read cache snapshot");
+                return 0;
+            }
+        };
+        let snapshot: CacheSnapshot = match serde_json::from_slice(&data) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring corrupted cache snapshot");
+                return 0;
+            }
+        };
+
+        let now = Utc::now();
+        let wallet_ttl = Duration::from_secs(self.config.wallet_cache_ttl_secs);
+        let mint_ttl = Duration::from_secs(self.config.score_cache_ttl_secs);
+        let restore_time =
+            |age: Duration| Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+        let mut restored = 0;
+
+        for entry in snapshot.wallets {
+            if self.wallet_cache.len() >= self.config.wallet_cache_size {
+                break;
+            }
+            if let Some(age) = surviving_age(entry.cached_at, now, wallet_ttl) {
+                let cached_at = restore_time(age);
+                self.wallet_cache.insert(
+                    entry.key,
+                    CachedWallet {
+                        history: entry.value,
+                        cached_at,
+                        last_accessed: cached_at,
+                        ttl: wallet_ttl,
+                    },
+                );
+                restored += 1;
+            }
+        }
+
+        for entry in snapshot.mint_infos {
+            if let Some(age) = surviving_age(entry.cached_at, now, mint_ttl) {
+                let cached_at = restore_time(age);
+                self.mint_info_cache.insert(
+                    entry.key,
+                    CachedMintInfo {
+                        info: entry.value,
+                        cached_at,
+                        last_accessed: cached_at,
+                        ttl: mint_ttl,
+                    },
+                );
+                restored += 1;
+            }
+        }
+
+        tracing::info!(
+            restored,
+            saved_at = %snapshot.saved_at,
+            path = %path.display(),
+            "Loaded cache snapshot"
+        );
+        restored
+    }
 }
 
 impl Default for FilterCache {
@@ -477,6 +878,24 @@ mod tests {
         assert_eq!(retrieved.unwrap().total_trades, 100);
     }
 
+    #[test]
+    fn test_parse_list_and_diff() {
+        let parsed = KnownActors::parse_list("# header\nabc\n  def  # rugged twice\n\nabc\n");
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed.contains("def"));
+
+        let old = KnownActors {
+            deployers: parsed,
+            ..Default::default()
+        };
+        let new = KnownActors {
+            deployers: KnownActors::parse_list("def\nghi\n"),
+            snipers: KnownActors::parse_list("sniper\n"),
+            ..Default::default()
+        };
+        assert_eq!(old.diff(&new), (2, 1));
+    }
+
     #[tokio::test]
     async fn test_known_actors_async() {
         let cache = FilterCache::new();
@@ -513,4 +932,184 @@ mod tests {
         assert_eq!(misses, 1);
         assert!((stats.hit_rate() - 0.666).abs() < 0.01);
     }
+
+    #[test]
+    fn test_trade_flow_ring_buffer() {
+        let cache = FilterCache::with_config(CacheConfig {
+            trade_flow_buffer_size: 3,
+            ..Default::default()
+        });
+
+        for i in 0..5 {
+            cache.record_trade(
+                "mint",
+                TradeRecord {
+                    trader: format!("wallet{}", i),
+                    is_buy: true,
+                    sol_amount: 1_000_000,
+                    token_amount: 1_000,
+                    timestamp: Utc::now(),
+                    time_since_launch_ms: 0,
+                    signature: String::new(),
+                },
+            );
+        }
+
+        let trades = cache.get_trades("mint");
+        assert_eq!(trades.len(), 3);
+        assert_eq!(trades[0].trader, "wallet2");
+        assert!(cache.get_trades("other").is_empty());
+    }
+
+    fn mint_info(mint: &str) -> MintInfo {
+        MintInfo {
+            mint: mint.to_string(),
+            mint_authority: None,
+            freeze_authority: None,
+            supply: 1_000_000_000_000_000,
+            decimals: 6,
+            extensions: Vec::new(),
+        }
+    }
+
+    fn wallet(address: &str) -> WalletHistory {
+        WalletHistory {
+            address: address.to_string(),
+            fetched_at: Utc::now(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hot_wallets_survive_eviction() {
+        let cache = FilterCache::with_config(CacheConfig {
+            wallet_cache_size: 100,
+            ..Default::default()
+        });
+        let hot: Vec<String> = (0..10).map(|i| format!("deployer{}", i)).collect();
+        for address in &hot {
+            cache.set_wallet(address, wallet(address));
+        }
+
+        // Churn 20x capacity of one-off wallets while the hot set keeps being read
+        for i in 0..2_000 {
+            let address = format!("oneoff{}", i);
+            cache.set_wallet(&address, wallet(&address));
+            if i % 20 == 0 {
+                for address in &hot {
+                    assert!(cache.get_wallet(address).is_some(), "{} evicted", address);
+                }
+            }
+        }
+
+        assert!(cache.wallet_cache_size() <= 100);
+        assert!(hot
+            .iter()
+            .all(|address| cache.get_wallet(address).is_some()));
+        assert!(cache.get_wallet("oneoff0").is_none());
+    }
+
+    #[test]
+    fn test_score_cache_invalidated_by_enrichment() {
+        let cache = FilterCache::new();
+        cache.set_score("mint", ScoringResult::default());
+        assert!(cache.get_score("mint").is_some());
+
+        cache.set_mint_info("mint", mint_info("mint"));
+        assert!(cache.get_score("mint").is_none());
+
+        cache.set_score("mint", ScoringResult::default());
+        cache.set_holders("other", Vec::new());
+        assert!(cache.get_score("mint").is_some());
+        cache.set_holders("mint", Vec::new());
+        assert!(cache.get_score("mint").is_none());
+    }
+
+    #[test]
+    fn test_sweep_expired() {
+        let cache = FilterCache::with_config(CacheConfig {
+            wallet_cache_ttl_secs: 0,
+            ..Default::default()
+        });
+        cache.set_wallet("a", wallet("a"));
+        cache.set_wallet("b", wallet("b"));
+        cache.set_mint_info("mint", mint_info("mint"));
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(cache.sweep_expired(), 2);
+        assert_eq!(cache.wallet_cache_size(), 0);
+        assert!(cache.get_mint_info("mint").is_some());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+
+        let cache = FilterCache::new();
+        cache.set_wallet(
+            "creator",
+            WalletHistory {
+                address: "creator".to_string(),
+                total_trades: 42,
+                fetched_at: Utc::now(),
+                ..Default::default()
+            },
+        );
+        cache.set_mint_info("mint", mint_info("mint"));
+        assert_eq!(cache.snapshot_to(&path).unwrap(), 2);
+
+        let restored = FilterCache::new();
+        assert_eq!(restored.load_from(&path), 2);
+        assert_eq!(restored.get_wallet("creator").unwrap().total_trades, 42);
+        assert_eq!(restored.get_mint_info("mint").unwrap().decimals, 6);
+    }
+
+    #[test]
+    fn test_snapshot_drops_expired_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let now = Utc::now();
+
+        let snapshot = CacheSnapshot {
+            saved_at: now,
+            wallets: vec![
+                SnapshotEntry {
+                    key: "fresh".to_string(),
+                    cached_at: now - chrono::Duration::seconds(60),
+                    value: WalletHistory::default(),
+                },
+                SnapshotEntry {
+                    key: "stale".to_string(),
+                    cached_at: now - chrono::Duration::hours(2),
+                    value: WalletHistory::default(),
+                },
+            ],
+            mint_infos: vec![SnapshotEntry {
+                key: "old_mint".to_string(),
+                cached_at: now - chrono::Duration::minutes(10),
+                value: mint_info("old_mint"),
+            }],
+        };
+        std::fs::write(&path, serde_json::to_vec(&snapshot).unwrap()).unwrap();
+
+        // Default TTLs: wallets 1 hour, mint info 5 minutes
+        let cache = FilterCache::new();
+        assert_eq!(cache.load_from(&path), 1);
+        assert!(cache.get_wallet("fresh").is_some());
+        assert!(cache.get_wallet("stale").is_none());
+        assert!(cache.get_mint_info("old_mint").is_none());
+    }
+
+    #[test]
+    fn test_corrupted_snapshot_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        std::fs::write(&path, b"{not json").unwrap();
+
+        let cache = FilterCache::new();
+        assert_eq!(cache.load_from(&path), 0);
+        assert_eq!(cache.load_from(&dir.path().join("missing.json")), 0);
+        assert_eq!(cache.total_cached_items(), 0);
+    }
 }
@@ -0,0 +1,444 @@
+//! Creator launch history
+//!
+//! `WalletHistory::tokens_deployed` and `deployed_rug_count` feed the
+//! creator risk signals. They are filled here: the creator's recent
+//! transactions are fetched through Helius and decoded for pump.fun creates
+//! and the creator's own sells, then every prior token's bonding curve is
+//! sampled once to classify how it ended:
+//!
+//! - completed: the curve is complete (graduated)
+//! - rugged: the creator sold at least `min_rug_sol` within `rug_window_hours`
+//!   of the launch, and the curve is drained or its price is down
+//!   `rug_drop_pct` from the peak. The curve held at least what is left plus
+//!   what the creator took out, which bounds the peak from below.
+//! - active: younger than the rug window and not rugged
+//! - abandoned: everything else
+//!
+//! History only grows, so each creator's result is cached for
+//! `cache_ttl_secs` (a day by default), longer than the wallet cache.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::filter::helius::HeliusClient;
+use crate::filter::types::WalletHistory;
+use crate::pump::accounts::BondingCurve;
+use crate::pump::instruction::{parse_transaction, PumpEventKind};
+use crate::pump::program::INITIAL_VIRTUAL_SOL_RESERVES;
+
+/// Creators cached before expired entries are pruned
+const PRUNE_AT_CREATORS: usize = 5_000;
+
+/// `[adaptive_filter.enrichment.creator_history]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatorHistoryConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Creator transactions searched for prior launches
+    #[serde(default = "default_tx_limit")]
+    pub tx_limit: u32,
+    /// Most recent prior launches sampled (at most 100)
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+    /// How long a creator's history is reused (seconds)
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Drop from the peak (price or curve SOL) that makes a creator exit a rug (%)
+    #[serde(default = "default_rug_drop_pct")]
+    pub rug_drop_pct: f64,
+    /// Creator sells this soon after the launch can make it a rug (hours)
+    #[serde(default = "default_rug_window_hours")]
+    pub rug_window_hours: u64,
+    /// Creator sells below this never make a rug (SOL)
+    #[serde(default = "default_min_rug_sol")]
+    pub min_rug_sol: f64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_tx_limit() -> u32 {
+    100
+}
+
+fn default_max_tokens() -> usize {
+    20
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    86_400
+}
+
+fn default_rug_drop_pct() -> f64 {
+    90.0
+}
+
+fn default_rug_window_hours() -> u64 {
+    24
+}
+
+fn default_min_rug_sol() -> f64 {
+    0.5
+}
+
+impl Default for CreatorHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tx_limit: default_tx_limit(),
+            max_tokens: default_max_tokens(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+            rug_drop_pct: default_rug_drop_pct(),
+            rug_window_hours: default_rug_window_hours(),
+            min_rug_sol: default_min_rug_sol(),
+        }
+    }
+}
+
+impl CreatorHistoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_tokens > 100 {
+            return Err("max_tokens must be at most 100".to_string());
+        }
+        if self.rug_drop_pct <= 0.0 || self.rug_drop_pct > 100.0 {
+            return Err("rug_drop_pct must be in (0, 100]".to_string());
+        }
+        if self.min_rug_sol < 0.0 {
+            return Err("min_rug_sol must not be negative".to_string());
+        }
+        Ok(())
+    }
+
+    fn rug_window(&self) -> ChronoDuration {
+        ChronoDuration::hours(self.rug_window_hours as i64)
+    }
+}
+
+/// How a prior launch ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchOutcome {
+    Rugged,
+    Completed,
+    Abandoned,
+    Active,
+    /// Curve account not found
+    Unknown,
+}
+
+/// A token the creator launched
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorLaunch {
+    pub mint: String,
+    pub created_at: Option<DateTime<Utc>>,
+    /// SOL the creator took out within the rug window
+    pub creator_sold_sol: f64,
+    pub outcome: LaunchOutcome,
+}
+
+/// A creator's prior launches, newest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CreatorHistory {
+    pub launches: Vec<PriorLaunch>,
+    /// Oldest creator transaction seen
+    pub first_seen: Option<DateTime<Utc>>,
+}
+
+impl CreatorHistory {
+    /// Fill the deployer fields of `history`, leaving out `mint` (the token
+    /// being scored)
+    pub fn apply(&self, history: &mut WalletHistory, mint: &str) {
+        let prior = || self.launches.iter().filter(|launch| launch.mint != mint);
+        history.tokens_deployed = prior().count() as u32;
+        history.deployed_rug_count = prior()
+            .filter(|launch| launch.outcome == LaunchOutcome::Rugged)
+            .count() as u32;
+        history.first_seen = match (history.first_seen, self.first_seen) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// A create found in the creator's transactions, with the creator's sells
+/// of the token
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundLaunch {
+    pub mint: String,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Creator sells of the token: (time, lamports)
+    pub sells: Vec<(Option<DateTime<Utc>>, u64)>,
+}
+
+impl FoundLaunch {
+    /// Lamports the creator took out within `window` of the launch
+    fn sold_within(&self, window: ChronoDuration) -> u64 {
+        self.sells
+            .iter()
+            .filter(|(at, _)| match (self.created_at, at) {
+                (Some(created), Some(at)) => *at - created <= window,
+                _ => true,
+            })
+            .map(|(_, lamports)| lamports)
+            .sum()
+    }
+}
+
+/// Tokens `creator` created in `txs` (newest first, as Helius returns
+/// them) and the oldest transaction time
+pub fn find_launches(
+    creator: &str,
+    txs: &[EncodedConfirmedTransactionWithStatusMeta],
+) -> (Vec<FoundLaunch>, Option<DateTime<Utc>>) {
+    let mut launches: Vec<FoundLaunch> = Vec::new();
+    let mut sells: HashMap<String, Vec<(Option<DateTime<Utc>>, u64)>> = HashMap::new();
+    let mut first_seen: Option<DateTime<Utc>> = None;
+    for tx in txs {
+        let at = tx.block_time.and_then(|t| DateTime::from_timestamp(t, 0));
+        if let Some(at) = at {
+            first_seen = Some(first_seen.map_or(at, |seen| seen.min(at)));
+        }
+        for event in parse_transaction(tx) {
+            let Some(mint) = event.mint else {
+                continue;
+            };
+            if event.user.to_string() != creator {
+                continue;
+            }
+            match event.kind {
+                PumpEventKind::Create { .. } => launches.push(FoundLaunch {
+                    mint: mint.to_string(),
+                    created_at: at,
+                    sells: Vec::new(),
+                }),
+                PumpEventKind::Sell {
+                    sol_amount: Some(lamports),
+                    ..
+                } => sells
+                    .entry(mint.to_string())
+                    .or_default()
+                    .push((at, lamports)),
+                _ => {}
+            }
+        }
+    }
+    for launch in &mut launches {
+        launch.sells = sells.remove(&launch.mint).unwrap_or_default();
+    }
+    (launches, first_seen)
+}
+
+/// How `launch` ended, from its curve sampled now
+pub fn classify(
+    launch: &FoundLaunch,
+    curve: Option<&BondingCurve>,
+    now: DateTime<Utc>,
+    config: &CreatorHistoryConfig,
+) -> LaunchOutcome {
+    let Some(curve) = curve else {
+        return LaunchOutcome::Unknown;
+    };
+    if curve.is_complete() {
+        return LaunchOutcome::Completed;
+    }
+    let sold = launch.sold_within(config.rug_window());
+    if sold as f64 / 1e9 >= config.min_rug_sol {
+        let kept = 1.0 - config.rug_drop_pct / 100.0;
+        let left = curve.real_sol_reserves as f64;
+        let peak = left + sold as f64;
+        // Price grows with the square of the virtual SOL reserves
+        let initial = INITIAL_VIRTUAL_SOL_RESERVES as f64;
+        let price_kept = ((initial + left) / (initial + peak)).powi(2);
+        if left <= peak * kept || price_kept <= kept {
+            return LaunchOutcome::Rugged;
+        }
+    }
+    match launch.created_at {
+        Some(created) if now - created < config.rug_window() => LaunchOutcome::Active,
+        _ => LaunchOutcome::Abandoned,
+    }
+}
+
+/// Fetch `creator`'s transactions and classify the launches found in them
+/// (three Helius requests)
+pub async fn fetch_creator_history(
+    helius: &HeliusClient,
+    creator: &str,
+    config: &CreatorHistoryConfig,
+) -> Result<CreatorHistory> {
+    let txs = helius.get_transactions(creator, config.tx_limit).await?;
+    let (mut found, first_seen) = find_launches(creator, &txs);
+    found.truncate(config.max_tokens);
+
+    let mints: Vec<Pubkey> = found
+        .iter()
+        .filter_map(|launch| Pubkey::from_str(&launch.mint).ok())
+        .collect();
+    let curves: HashMap<String, BondingCurve> = mints
+        .iter()
+        .zip(helius.get_bonding_curves(&mints).await?)
+        .filter_map(|(mint, curve)| Some((mint.to_string(), curve?)))
+        .collect();
+
+    let now = Utc::now();
+    let launches = found
+        .iter()
+        .map(|launch| PriorLaunch {
+            mint: launch.mint.clone(),
+            created_at: launch.created_at,
+            creator_sold_sol: launch.sold_within(config.rug_window()) as f64 / 1e9,
+            outcome: classify(launch, curves.get(&launch.mint), now, config),
+        })
+        .collect();
+    Ok(CreatorHistory {
+        launches,
+        first_seen,
+    })
+}
+
+/// Creator histories by address, reused for `cache_ttl_secs`
+#[derive(Default)]
+pub struct CreatorHistoryCache {
+    entries: DashMap<String, (CreatorHistory, Instant)>,
+}
+
+impl CreatorHistoryCache {
+    pub fn get(&self, creator: &str, ttl: Duration) -> Option<CreatorHistory> {
+        let entry = self.entries.get(creator)?;
+        let (history, cached_at) = entry.value();
+        (cached_at.elapsed() < ttl).then(|| history.clone())
+    }
+
+    pub fn insert(&self, creator: &str, history: CreatorHistory, ttl: Duration) {
+        if self.entries.len() >= PRUNE_AT_CREATORS {
+            self.entries
+                .retain(|_, (_, cached_at)| cached_at.elapsed() < ttl);
+        }
+        self.entries
+            .insert(creator.to_string(), (history, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(hours_ago: i64, sold_sol: f64, sold_after_hours: i64) -> FoundLaunch {
+        let created = Utc::now() - ChronoDuration::hours(hours_ago);
+        let sells = if sold_sol > 0.0 {
+            vec![(
+                Some(created + ChronoDuration::hours(sold_after_hours)),
+                (sold_sol * 1e9) as u64,
+            )]
+        } else {
+            Vec::new()
+        };
+        FoundLaunch {
+            mint: "mint".to_string(),
+            created_at: Some(created),
+            sells,
+        }
+    }
+
+    fn curve(real_sol: f64, complete: bool) -> BondingCurve {
+        let real = (real_sol * 1e9) as u64;
+        BondingCurve::new_for_test(
+            INITIAL_VIRTUAL_SOL_RESERVES + real,
+            1_000_000_000_000_000,
+            real,
+            700_000_000_000_000,
+            1_000_000_000_000_000,
+            complete,
+        )
+    }
+
+    #[test]
+    fn test_classify_outcomes() {
+        let config = CreatorHistoryConfig::default();
+        let now = Utc::now();
+
+        // Graduated, whatever the creator did
+        let outcome = classify(&launch(48, 5.0, 1), Some(&curve(0.0, true)), now, &config);
+        assert_eq!(outcome, LaunchOutcome::Completed);
+
+        // Creator took 10 SOL out an hour in, 0.2 SOL left: drained
+        let outcome = classify(&launch(48, 10.0, 1), Some(&curve(0.2, false)), now, &config);
+        assert_eq!(outcome, LaunchOutcome::Rugged);
+
+        // 20 SOL left after the creator took 120 out: price down ~91%
+        let outcome = classify(
+            &launch(48, 120.0, 1),
+            Some(&curve(20.0, false)),
+            now,
+            &config,
+        );
+        assert_eq!(outcome, LaunchOutcome::Rugged);
+
+        // Same exit a week later is outside the rug window
+        let outcome = classify(
+            &launch(400, 10.0, 200),
+            Some(&curve(0.2, false)),
+            now,
+            &config,
+        );
+        assert_eq!(outcome, LaunchOutcome::Abandoned);
+
+        // Dev buy taken back out: below min_rug_sol
+        let outcome = classify(&launch(48, 0.3, 1), Some(&curve(0.0, false)), now, &config);
+        assert_eq!(outcome, LaunchOutcome::Abandoned);
+
+        let outcome = classify(&launch(2, 0.0, 0), Some(&curve(3.0, false)), now, &config);
+        assert_eq!(outcome, LaunchOutcome::Active);
+        assert_eq!(
+            classify(&launch(48, 10.0, 1), None, now, &config),
+            LaunchOutcome::Unknown
+        );
+    }
+
+    #[test]
+    fn test_apply_fills_wallet_history() {
+        let old = Utc::now() - ChronoDuration::days(30);
+        let prior = |mint: &str, outcome| PriorLaunch {
+            mint: mint.to_string(),
+            created_at: None,
+            creator_sold_sol: 0.0,
+            outcome,
+        };
+        let creator = CreatorHistory {
+            launches: vec![
+                prior("current", LaunchOutcome::Active),
+                prior("a", LaunchOutcome::Rugged),
+                prior("b", LaunchOutcome::Completed),
+                prior("c", LaunchOutcome::Rugged),
+            ],
+            first_seen: Some(old),
+        };
+        let mut history = WalletHistory {
+            first_seen: Some(Utc::now()),
+            ..Default::default()
+        };
+        creator.apply(&mut history, "current");
+        assert_eq!(history.tokens_deployed, 3);
+        assert_eq!(history.deployed_rug_count, 2);
+        assert!(history.is_likely_rug_deployer());
+        assert!(history.age_days().unwrap() > 29.0);
+    }
+
+    #[test]
+    fn test_cache_expires() {
+        let cache = CreatorHistoryCache::default();
+        let ttl = Duration::from_secs(60);
+        cache.insert("dev", CreatorHistory::default(), ttl);
+        assert!(cache.get("dev", ttl).is_some());
+        assert!(cache.get("dev", Duration::ZERO).is_none());
+        assert!(cache.get("other", ttl).is_none());
+    }
+}
@@ -0,0 +1,326 @@
+//! Creator launch frequency
+//!
+//! Serial deployers launch 5-10 tokens an hour, and each one looks like a
+//! fresh opportunity. Every new-token event (bought or not) is recorded
+//! against its creator; the launches over the last hour and day become a
+//! `DeployerPattern` signal. The strategy engine separately allows at most
+//! one entry per creator per `[strategy.portfolio_risk]
+//! creator_entry_window_mins`.
+//!
+//! The history is saved to `creator_launches.json` so a restart keeps it;
+//! `snipe actors stats <creator>` prints it.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::filter::signals::{Signal, SignalType};
+
+/// History file name in the credentials dir
+pub const CREATOR_LAUNCHES_FILE: &str = "creator_launches.json";
+
+/// Minimum time between saves
+const SAVE_INTERVAL_SECS: i64 = 30;
+
+/// Launches kept per creator (oldest dropped first)
+const MAX_LAUNCHES_PER_CREATOR: usize = 200;
+
+/// `[adaptive_filter.creator_launches]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreatorLaunchConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Launches older than this are forgotten (hours)
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: u64,
+    /// Launches in the last hour (this one included) that mark a serial
+    /// deployer: extreme-risk signal
+    #[serde(default = "default_serial_launches_1h")]
+    pub serial_launches_1h: usize,
+    /// Launches in the last hour that count as a repeat deployer
+    #[serde(default = "default_repeat_launches_1h")]
+    pub repeat_launches_1h: usize,
+    /// Launches in the last 24 hours that count as a repeat deployer
+    #[serde(default = "default_repeat_launches_24h")]
+    pub repeat_launches_24h: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_retention_hours() -> u64 {
+    168
+}
+
+fn default_serial_launches_1h() -> usize {
+    4
+}
+
+fn default_repeat_launches_1h() -> usize {
+    2
+}
+
+fn default_repeat_launches_24h() -> usize {
+    5
+}
+
+impl Default for CreatorLaunchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_hours: default_retention_hours(),
+            serial_launches_1h: default_serial_launches_1h(),
+            repeat_launches_1h: default_repeat_launches_1h(),
+            repeat_launches_24h: default_repeat_launches_24h(),
+        }
+    }
+}
+
+/// One observed launch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Launch {
+    pub mint: String,
+    pub symbol: String,
+    pub at: DateTime<Utc>,
+}
+
+/// A creator's launches over the rolling windows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LaunchCounts {
+    pub last_1h: usize,
+    pub last_24h: usize,
+    /// Everything still retained
+    pub retained: usize,
+}
+
+impl LaunchCounts {
+    /// `DeployerPattern` signal for these counts (None = not a repeat deployer)
+    pub fn signal(&self, config: &CreatorLaunchConfig) -> Option<Signal> {
+        let reason = format!(
+            "Creator launched {} tokens in 1h, {} in 24h",
+            self.last_1h, self.last_24h
+        );
+        if self.last_1h >= config.serial_launches_1h {
+            Some(Signal::extreme_risk(SignalType::DeployerPattern, reason))
+        } else if self.last_1h >= config.repeat_launches_1h {
+            Some(Signal::new(SignalType::DeployerPattern, -0.7, 0.85, reason))
+        } else if self.last_24h >= config.repeat_launches_24h {
+            Some(Signal::new(SignalType::DeployerPattern, -0.5, 0.75, reason))
+        } else {
+            None
+        }
+    }
+}
+
+/// What `creator_launches.json` holds
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LaunchHistory {
+    creators: HashMap<String, VecDeque<Launch>>,
+}
+
+/// Launches per creator over the retention window
+pub struct CreatorLaunches {
+    config: CreatorLaunchConfig,
+    history: Mutex<LaunchHistory>,
+    saved_at: Mutex<Option<DateTime<Utc>>>,
+    /// Where the history is saved (None = memory only)
+    persistence_path: Option<PathBuf>,
+}
+
+impl CreatorLaunches {
+    pub fn new(config: CreatorLaunchConfig) -> Self {
+        Self {
+            config,
+            history: Mutex::new(LaunchHistory::default()),
+            saved_at: Mutex::new(None),
+            persistence_path: None,
+        }
+    }
+
+    /// Restore the history saved at `path` (if any) and keep saving there
+    pub fn with_persistence(mut self, path: PathBuf) -> Self {
+        match load_history(&path) {
+            Ok(history) => *self.history.get_mut().unwrap() = history,
+            Err(e) => warn!("Ignoring creator launch history: {}", e),
+        }
+        self.persistence_path = Some(path);
+        self
+    }
+
+    pub fn config(&self) -> &CreatorLaunchConfig {
+        &self.config
+    }
+
+    /// Record a new-token event; returns the creator's counts including it
+    pub fn record(
+        &self,
+        creator: &str,
+        mint: &str,
+        symbol: &str,
+        at: DateTime<Utc>,
+    ) -> LaunchCounts {
+        if !self.config.enabled || creator.is_empty() {
+            return LaunchCounts::default();
+        }
+        let counts = {
+            let mut history = self.history.lock().unwrap();
+            let launches = history.creators.entry(creator.to_string()).or_default();
+            if !launches.iter().any(|l| l.mint == mint) {
+                launches.push_back(Launch {
+                    mint: mint.to_string(),
+                    symbol: symbol.to_string(),
+                    at,
+                });
+                if launches.len() > MAX_LAUNCHES_PER_CREATOR {
+                    launches.pop_front();
+                }
+            }
+            counts_at(launches, at)
+        };
+        self.save_if_due(at);
+        counts
+    }
+
+    /// A creator's counts as of `now`
+    pub fn counts(&self, creator: &str, now: DateTime<Utc>) -> LaunchCounts {
+        let history = self.history.lock().unwrap();
+        history
+            .creators
+            .get(creator)
+            .map(|launches| counts_at(launches, now))
+            .unwrap_or_default()
+    }
+
+    /// Drop launches past the retention window and save
+    fn save_if_due(&self, now: DateTime<Utc>) {
+        let Some(ref path) = self.persistence_path else {
+            return;
+        };
+        {
+            let mut saved_at = self.saved_at.lock().unwrap();
+            if matches!(*saved_at, Some(t) if now - t < Duration::seconds(SAVE_INTERVAL_SECS)) {
+                return;
+            }
+            *saved_at = Some(now);
+        }
+        let cutoff = now - Duration::hours(self.config.retention_hours as i64);
+        let data = {
+            let mut history = self.history.lock().unwrap();
+            history.creators.retain(|_, launches| {
+                while launches.front().is_some_and(|l| l.at < cutoff) {
+                    launches.pop_front();
+                }
+                !launches.is_empty()
+            });
+            serde_json::to_string(&*history)
+        };
+        let result = data
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!(
+                "Failed to save creator launches to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn counts_at(launches: &VecDeque<Launch>, now: DateTime<Utc>) -> LaunchCounts {
+    let within = |hours| {
+        let cutoff = now - Duration::hours(hours);
+        launches
+            .iter()
+            .filter(|l| l.at > cutoff && l.at <= now)
+            .count()
+    };
+    LaunchCounts {
+        last_1h: within(1),
+        last_24h: within(24),
+        retained: launches.len(),
+    }
+}
+
+fn load_history(path: &Path) -> Result<LaunchHistory> {
+    if !path.exists() {
+        return Ok(LaunchHistory::default());
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// A creator's saved launches, oldest first (for `snipe actors stats`)
+pub fn load_launches(path: &Path, creator: &str) -> Result<Vec<Launch>> {
+    let mut history = load_history(path)?;
+    Ok(history
+        .creators
+        .remove(creator)
+        .map(Vec::from)
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_signal() {
+        let launches = CreatorLaunches::new(CreatorLaunchConfig::default());
+        let config = launches.config().clone();
+        let now = Utc::now();
+
+        let first = launches.record("dev", "mint0", "A", now - Duration::hours(5));
+        assert_eq!((first.last_1h, first.last_24h), (1, 1));
+        assert!(first.signal(&config).is_none());
+
+        let second = launches.record("dev", "mint1", "B", now - Duration::minutes(30));
+        assert_eq!((second.last_1h, second.last_24h), (1, 2));
+        assert!(second.signal(&config).is_none());
+
+        // The same token reported twice counts once
+        launches.record("dev", "mint1", "B", now - Duration::minutes(30));
+        let third = launches.record("dev", "mint2", "C", now);
+        assert_eq!((third.last_1h, third.last_24h, third.retained), (2, 3, 3));
+        let signal = third.signal(&config).unwrap();
+        assert_eq!(signal.signal_type, SignalType::DeployerPattern);
+        assert!(signal.value < -0.5);
+
+        for i in 3..5 {
+            launches.record("dev", &format!("mint{}", i), "D", now);
+        }
+        let serial = launches.counts("dev", now).signal(&config).unwrap();
+        assert_eq!(serial.value, -1.0);
+        assert_eq!(launches.counts("other", now), LaunchCounts::default());
+    }
+
+    #[test]
+    fn test_history_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CREATOR_LAUNCHES_FILE);
+        let config = CreatorLaunchConfig {
+            retention_hours: 24,
+            ..Default::default()
+        };
+        let now = Utc::now();
+
+        let launches = CreatorLaunches::new(config.clone()).with_persistence(path.clone());
+        launches.record("dev", "old", "OLD", now - Duration::hours(30));
+        // Saves are throttled; the next save prunes past retention
+        *launches.saved_at.lock().unwrap() = None;
+        launches.record("dev", "new", "NEW", now);
+
+        let restarted = CreatorLaunches::new(config).with_persistence(path.clone());
+        assert_eq!(restarted.counts("dev", now).retained, 1);
+        let saved = load_launches(&path, "dev").unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].symbol, "NEW");
+        assert!(load_launches(&path, "nobody").unwrap().is_empty());
+    }
+}
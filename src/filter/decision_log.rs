@@ -0,0 +1,483 @@
+//! Machine-readable decision log
+//!
+//! Every token that reaches the decision stage gets one JSON line: the full
+//! `ScoringResult` (score, confidence, recommendation and each signal's
+//! type/value/confidence/weight/reason) plus the action the main loop took.
+//! Lines are written by a background task so the hot loop never touches the
+//! disk, and the file rotates at `max_file_mb` (`decisions.jsonl.1`, `.2`, ...).
+//!
+//! When the filter, strategy, tactics and kill-switch disagreed about a
+//! token, the record also carries the arbitration: each component's proposal
+//! and the rule that picked the winner.
+//!
+//! `snipe start --dry-run=simulate` adds a `simulated` line for every buy
+//! and sell it built, signed and simulated: compute units, logs and the
+//! error the transaction would have failed with.
+//!
+//! `snipe decisions tail` / `snipe decisions show <mint>` read them back;
+//! `snipe decisions conflicts` counts who overrode whom.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::error::Result;
+use crate::filter::adaptive::config::DecisionLogConfig;
+use crate::filter::scoring::ScoringResult;
+use crate::strategy::types::ArbitrationRecord;
+use crate::trading::simulation::SimulationResult;
+
+/// Records buffered before new ones are dropped
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// What the main loop did with a scored token
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionAction {
+    /// Filter said Avoid
+    Avoid,
+    /// Filter said Observe (watch only)
+    Observe,
+    /// Strategy engine skipped or rejected the entry
+    StrategySkip,
+    /// Re-entry cooldown or blacklist
+    Cooldown,
+    /// No free position slot / exposure limit
+    NoSlot,
+    /// Trading window closed, entry rate cap reached or hot wallet below
+    /// the entry floor
+    Throttled,
+    /// Buy landed and the position was recorded
+    Bought,
+    /// Buy errored or delivered no tokens
+    BuyFailed,
+    /// Dry run: would have bought
+    DryRun,
+    /// Simulate dry run: a buy or sell was simulated, never sent
+    Simulated,
+}
+
+impl std::fmt::Display for DecisionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DecisionAction::Avoid => "avoid",
+            DecisionAction::Observe => "observe",
+            DecisionAction::StrategySkip => "strategy_skip",
+            DecisionAction::Cooldown => "cooldown",
+            DecisionAction::NoSlot => "no_slot",
+            DecisionAction::Throttled => "throttled",
+            DecisionAction::Bought => "bought",
+            DecisionAction::BuyFailed => "buy_failed",
+            DecisionAction::DryRun => "dry_run",
+            DecisionAction::Simulated => "simulated",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One line of the decision log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    pub timestamp: DateTime<Utc>,
+    pub mint: String,
+    pub symbol: String,
+    pub action: DecisionAction,
+    /// Why the action was taken, when the result doesn't say
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Proposals and winning rule, when components disagreed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arbitration: Option<ArbitrationRecord>,
+    /// Simulation of the buy or sell (`simulated` records)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub simulation: Option<SimulationResult>,
+    #[serde(flatten)]
+    pub result: ScoringResult,
+}
+
+/// Handle for appending decisions (no-op when disabled)
+#[derive(Clone)]
+pub struct DecisionLog {
+    sender: Option<mpsc::Sender<DecisionRecord>>,
+}
+
+impl DecisionLog {
+    /// Start the writer task (call from within the runtime)
+    pub fn spawn(config: &DecisionLogConfig) -> Self {
+        if !config.enabled {
+            return Self::disabled();
+        }
+        let (sender, mut receiver) = mpsc::channel::<DecisionRecord>(CHANNEL_CAPACITY);
+        let mut writer = RotatingWriter::new(config);
+        tokio::spawn(async move {
+            while let Some(record) = receiver.recv().await {
+                if let Err(e) = writer.append(&record) {
+                    warn!(path = %writer.path.display(), error = %e, "Failed to write decision log");
+                }
+            }
+        });
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    /// A log that records nothing
+    pub fn disabled() -> Self {
+        Self { sender: None }
+    }
+
+    /// Append a decision (ignored without a scoring result)
+    pub fn record(
+        &self,
+        mint: &str,
+        symbol: &str,
+        result: Option<&ScoringResult>,
+        action: DecisionAction,
+        detail: Option<String>,
+    ) {
+        self.record_arbitrated(mint, symbol, result, action, detail, None);
+    }
+
+    /// Append a decision with the arbitration behind it (kept only if it was
+    /// a conflict)
+    pub fn record_arbitrated(
+        &self,
+        mint: &str,
+        symbol: &str,
+        result: Option<&ScoringResult>,
+        action: DecisionAction,
+        detail: Option<String>,
+        arbitration: Option<&ArbitrationRecord>,
+    ) {
+        let Some(result) = result else {
+            return;
+        };
+        self.send(DecisionRecord {
+            timestamp: Utc::now(),
+            mint: mint.to_string(),
+            symbol: symbol.to_string(),
+            action,
+            detail,
+            arbitration: arbitration.filter(|a| a.is_conflict()).cloned(),
+            simulation: None,
+            result: result.clone(),
+        });
+    }
+
+    /// Append a simulated buy or sell (`detail` says which)
+    ///
+    /// Kept without a scoring result: sells have none.
+    pub fn record_simulation(&self, mint: &str, detail: String, simulation: SimulationResult) {
+        self.send(DecisionRecord {
+            timestamp: Utc::now(),
+            mint: mint.to_string(),
+            symbol: String::new(),
+            action: DecisionAction::Simulated,
+            detail: Some(detail),
+            arbitration: None,
+            simulation: Some(simulation),
+            result: ScoringResult::default(),
+        });
+    }
+
+    fn send(&self, record: DecisionRecord) {
+        let Some(ref sender) = self.sender else {
+            return;
+        };
+        let mint = record.mint.clone();
+        if sender.try_send(record).is_err() {
+            debug!(mint = %mint, "Decision log backlogged - record dropped");
+        }
+    }
+}
+
+/// Size-rotated JSON-lines file
+struct RotatingWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingWriter {
+    fn new(config: &DecisionLogConfig) -> Self {
+        Self {
+            path: PathBuf::from(&config.path),
+            max_bytes: config.max_file_mb.max(1) * 1024 * 1024,
+            max_files: config.max_files,
+            file: None,
+            size: 0,
+        }
+    }
+
+    fn append(&mut self, record: &DecisionRecord) -> Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        if self.file.is_some() && self.size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let file = match self.file {
+            Some(ref mut file) => file,
+            None => {
+                if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                self.size = file.metadata()?.len();
+                self.file.insert(file)
+            }
+        };
+        file.write_all(&line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Shift `path.N-1` -> `path.N`, ..., `path` -> `path.1`
+    fn rotate(&mut self) -> Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            std::fs::remove_file(&self.path)?;
+            return Ok(());
+        }
+        let _ = std::fs::remove_file(rotated_path(&self.path, self.max_files));
+        for n in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, n);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(&self.path, n + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        Ok(())
+    }
+}
+
+/// `decisions.jsonl` -> `decisions.jsonl.N`
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+/// Read every record, oldest first (rotated files, then the active one)
+///
+/// Malformed lines (e.g. a partial write) are skipped.
+pub fn read_records(config: &DecisionLogConfig) -> Result<Vec<DecisionRecord>> {
+    let path = Path::new(&config.path);
+    let mut files: Vec<PathBuf> = (1..=config.max_files)
+        .rev()
+        .map(|n| rotated_path(path, n))
+        .collect();
+    files.push(path.to_path_buf());
+
+    let mut records = Vec::new();
+    for file in files.iter().filter(|f| f.exists()) {
+        for line in BufReader::new(File::open(file)?).lines() {
+            if let Ok(record) = serde_json::from_str::<DecisionRecord>(&line?) {
+                records.push(record);
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Proposals, wins and overrides of one component
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComponentTally {
+    pub proposed: usize,
+    pub won: usize,
+    pub overridden: usize,
+}
+
+/// Who overrode whom across the logged conflicts
+#[derive(Debug, Clone, Default)]
+pub struct ConflictSummary {
+    /// Decisions read
+    pub decisions: usize,
+    /// Decisions where components disagreed
+    pub conflicts: usize,
+    /// By component name
+    pub components: BTreeMap<String, ComponentTally>,
+    /// (overridden component, winner, rule) -> times
+    pub overrides: BTreeMap<(String, String, String), usize>,
+}
+
+impl ConflictSummary {
+    pub fn build(records: &[DecisionRecord]) -> Self {
+        let mut summary = Self {
+            decisions: records.len(),
+            ..Default::default()
+        };
+        for arbitration in records.iter().filter_map(|r| r.arbitration.as_ref()) {
+            summary.conflicts += 1;
+            let winner = arbitration.winner.to_string();
+            for proposal in &arbitration.proposals {
+                summary
+                    .components
+                    .entry(proposal.source.to_string())
+                    .or_default()
+                    .proposed += 1;
+            }
+            summary.components.entry(winner.clone()).or_default().won += 1;
+            for proposal in arbitration.overridden() {
+                let source = proposal.source.to_string();
+                summary
+                    .components
+                    .entry(source.clone())
+                    .or_default()
+                    .overridden += 1;
+                *summary
+                    .overrides
+                    .entry((source, winner.clone(), arbitration.rule.clone()))
+                    .or_insert(0) += 1;
+            }
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::scoring::Recommendation;
+    use crate::filter::signals::{Signal, SignalType};
+    use crate::strategy::types::{DecisionSource, Proposal, ProposedAction};
+
+    fn record(mint: &str, action: DecisionAction) -> DecisionRecord {
+        DecisionRecord {
+            timestamp: Utc::now(),
+            mint: mint.to_string(),
+            symbol: "TKN".to_string(),
+            action,
+            detail: None,
+            arbitration: None,
+            simulation: None,
+            result: ScoringResult {
+                score: -0.6,
+                recommendation: Recommendation::Avoid,
+                signals: vec![Signal::new(
+                    SignalType::KnownDeployer,
+                    -1.0,
+                    1.0,
+                    "Known rug deployer",
+                )],
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DecisionLogConfig {
+            path: dir.path().join("decisions.jsonl").display().to_string(),
+            max_file_mb: 1,
+            max_files: 2,
+            ..Default::default()
+        };
+        let mut writer = RotatingWriter::new(&config);
+        writer.max_bytes = 600; // A couple of records per file
+
+        for i in 0..10 {
+            writer
+                .append(&record(&format!("mint{}", i), DecisionAction::Avoid))
+                .unwrap();
+        }
+        assert!(rotated_path(&writer.path, 1).exists());
+        assert!(!rotated_path(&writer.path, 3).exists());
+
+        // Oldest records rotated out, newest kept in order
+        let records = read_records(&config).unwrap();
+        assert!(!records.is_empty() && records.len() < 10);
+        assert_eq!(records.last().unwrap().mint, "mint9");
+        let last = &records.last().unwrap().result;
+        assert_eq!(last.recommendation, Recommendation::Avoid);
+        assert_eq!(last.signals[0].signal_type, SignalType::KnownDeployer);
+    }
+
+    #[test]
+    fn test_conflict_summary() {
+        let blocked = ArbitrationRecord {
+            proposals: vec![
+                Proposal::new(
+                    DecisionSource::Tactics,
+                    ProposedAction::Enter,
+                    0.7,
+                    "piggyback",
+                ),
+                Proposal::new(
+                    DecisionSource::PortfolioRisk,
+                    ProposedAction::Skip,
+                    1.0,
+                    "full",
+                ),
+            ],
+            winner: DecisionSource::PortfolioRisk,
+            action: ProposedAction::Skip,
+            rule: "portfolio_block".to_string(),
+        };
+        let mut conflict = record("mint1", DecisionAction::NoSlot);
+        conflict.arbitration = Some(blocked.clone());
+        let records = vec![
+            conflict.clone(),
+            conflict,
+            record("mint2", DecisionAction::Avoid),
+        ];
+
+        // Round-trips through the log format
+        let line = serde_json::to_string(&records[0]).unwrap();
+        let parsed: DecisionRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.arbitration, Some(blocked));
+
+        let summary = ConflictSummary::build(&records);
+        assert_eq!(summary.decisions, 3);
+        assert_eq!(summary.conflicts, 2);
+        let tactics = summary.components["tactics"];
+        assert_eq!(
+            (tactics.proposed, tactics.won, tactics.overridden),
+            (2, 0, 2)
+        );
+        assert_eq!(summary.components["portfolio_risk"].won, 2);
+        let key = (
+            "tactics".to_string(),
+            "portfolio_risk".to_string(),
+            "portfolio_block".to_string(),
+        );
+        assert_eq!(summary.overrides[&key], 2);
+    }
+
+    #[test]
+    fn test_simulation_round_trip() {
+        let mut simulated = record("mint1", DecisionAction::Simulated);
+        simulated.detail = Some("buy 0.0500 SOL".to_string());
+        simulated.simulation = Some(SimulationResult {
+            success: false,
+            error: Some("Error processing Instruction 2: custom program error: 0x1772".to_string()),
+            compute_units: Some(48_211),
+            logs: vec!["Program log: Instruction: Buy".to_string()],
+        });
+
+        let line = serde_json::to_string(&simulated).unwrap();
+        let parsed: DecisionRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.action, DecisionAction::Simulated);
+        assert_eq!(parsed.simulation, simulated.simulation);
+        assert!(parsed
+            .simulation
+            .unwrap()
+            .summary()
+            .starts_with("would fail: Error processing Instruction 2"));
+
+        // Other records carry no simulation
+        let line = serde_json::to_string(&record("mint2", DecisionAction::Avoid)).unwrap();
+        assert!(!line.contains("simulation"));
+    }
+}
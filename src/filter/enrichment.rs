@@ -2,17 +2,47 @@
 //!
 //! Provides background enrichment of token and wallet data to populate
 //! the cache and exit degraded mode.
+//!
+//! Requests are queued in three priority lanes, each with its own rate
+//! budget so Helius credits go where they matter: Critical refreshes mint
+//! info and holders of held positions (the refreshed holder list is handed
+//! to the holder watcher), High enriches tokens pending a buy decision, and
+//! Low covers everything else. A Critical request that carries the creator
+//! also fetches the creator's token transfers so the kill-switch can watch
+//! wallets the launch allocation was moved to. A mint is queued at most once; re-requesting
+//! it at a higher priority moves it up.
+//!
+//! A creator's wallet history also carries the outcomes of their earlier
+//! launches (see `creator_history`), cached per creator for a day.
 
-use std::sync::Arc;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::filter::cache::FilterCache;
-use crate::filter::helius::HeliusClient;
-use crate::filter::types::SignalContext;
-use tokio::sync::mpsc;
+use crate::filter::creator_history::{
+    fetch_creator_history, CreatorHistoryCache, CreatorHistoryConfig,
+};
+use crate::filter::helius::{HeliusClient, SplTransfer};
+use crate::filter::types::{SignalContext, TokenHolderInfo, WalletHistory};
+use tokio::sync::{Notify, Semaphore};
 use tokio::time::timeout;
 use tracing::{debug, info, warn};
 
+/// How often an idle worker re-checks lanes waiting on their budget
+const IDLE_POLL: Duration = Duration::from_millis(250);
+
+/// Number of priority lanes
+const LANES: usize = 3;
+
+/// Called with the fresh holder list of a held position
+pub type HolderRefreshHandler = Arc<dyn Fn(&str, &[TokenHolderInfo]) + Send + Sync>;
+
+/// Called with a held position's mint, its creator and the creator's token transfers
+pub type CreatorTransferHandler = Arc<dyn Fn(&str, &str, &[SplTransfer]) + Send + Sync>;
+
 /// Request to enrich data for a token
 #[derive(Debug, Clone)]
 pub struct EnrichmentRequest {
@@ -21,46 +51,157 @@ pub struct EnrichmentRequest {
     pub priority: EnrichmentPriority,
 }
 
-/// Priority level for enrichment requests
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Priority level for enrichment requests (most urgent first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EnrichmentPriority {
-    /// High priority - needed for immediate trading decision
+    /// Held position - mint info and holders refreshed on an interval
+    Critical,
+    /// Pending a buy decision
     High,
-    /// Normal priority - background enrichment
-    Normal,
-    /// Low priority - opportunistic enrichment
+    /// Opportunistic background enrichment
     Low,
 }
 
+impl EnrichmentPriority {
+    /// All lanes, most urgent first
+    pub const ALL: [EnrichmentPriority; LANES] = [
+        EnrichmentPriority::Critical,
+        EnrichmentPriority::High,
+        EnrichmentPriority::Low,
+    ];
+
+    fn lane(self) -> usize {
+        self as usize
+    }
+}
+
+impl std::fmt::Display for EnrichmentPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            EnrichmentPriority::Critical => "critical",
+            EnrichmentPriority::High => "high",
+            EnrichmentPriority::Low => "low",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Configuration for the enrichment service
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnrichmentConfig {
     /// Maximum concurrent enrichment requests
+    #[serde(default = "default_max_concurrent")]
     pub max_concurrent: usize,
     /// Timeout for individual API calls
+    #[serde(default = "default_api_timeout_ms")]
     pub api_timeout_ms: u64,
     /// Default number of holders to fetch
+    #[serde(default = "default_holder_limit")]
     pub holder_limit: u32,
     /// Default number of wallet transactions to fetch
+    #[serde(default = "default_wallet_tx_limit")]
     pub wallet_tx_limit: u32,
     /// Whether to fetch mint authority info
+    #[serde(default = "default_true")]
     pub fetch_mint_info: bool,
     /// Whether to fetch creator wallet history
+    #[serde(default = "default_true")]
     pub fetch_creator_history: bool,
     /// Whether to fetch token holders
+    #[serde(default = "default_true")]
     pub fetch_holders: bool,
+    /// Held-position refreshes per minute (0 = lane paused)
+    #[serde(default = "default_critical_per_min")]
+    pub critical_per_min: u32,
+    /// Pending-decision enrichments per minute (0 = lane paused)
+    #[serde(default = "default_high_per_min")]
+    pub high_per_min: u32,
+    /// Background enrichments per minute (0 = lane paused)
+    #[serde(default = "default_low_per_min")]
+    pub low_per_min: u32,
+    /// Deprecated: moved to `smart_money.holder_watcher.refresh_secs`, which
+    /// it replaces when set
+    #[serde(default)]
+    pub critical_refresh_secs: Option<u64>,
+    /// Queued requests across all lanes; when full, the oldest Low request
+    /// makes room for a more urgent one
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Confidence multiplier while Helius is throttled (degraded mode)
+    #[serde(default = "default_throttled_confidence_penalty")]
+    pub throttled_confidence_penalty: f64,
+    /// Prior launch outcomes of the creator
+    #[serde(default)]
+    pub creator_history: CreatorHistoryConfig,
+}
+
+fn default_max_concurrent() -> usize {
+    5
+}
+
+fn default_api_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_holder_limit() -> u32 {
+    20
+}
+
+fn default_wallet_tx_limit() -> u32 {
+    50
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_critical_per_min() -> u32 {
+    60
+}
+
+fn default_high_per_min() -> u32 {
+    60
+}
+
+fn default_low_per_min() -> u32 {
+    20
+}
+
+fn default_queue_capacity() -> usize {
+    500
+}
+
+fn default_throttled_confidence_penalty() -> f64 {
+    0.85
 }
 
 impl Default for EnrichmentConfig {
     fn default() -> Self {
         Self {
-            max_concurrent: 5,
-            api_timeout_ms: 5000,
-            holder_limit: 20,
-            wallet_tx_limit: 50,
+            max_concurrent: default_max_concurrent(),
+            api_timeout_ms: default_api_timeout_ms(),
+            holder_limit: default_holder_limit(),
+            wallet_tx_limit: default_wallet_tx_limit(),
             fetch_mint_info: true,
             fetch_creator_history: true,
             fetch_holders: true,
+            critical_per_min: default_critical_per_min(),
+            high_per_min: default_high_per_min(),
+            low_per_min: default_low_per_min(),
+            critical_refresh_secs: None,
+            queue_capacity: default_queue_capacity(),
+            throttled_confidence_penalty: default_throttled_confidence_penalty(),
+            creator_history: CreatorHistoryConfig::default(),
+        }
+    }
+}
+
+impl EnrichmentConfig {
+    fn per_min(&self, priority: EnrichmentPriority) -> u32 {
+        match priority {
+            EnrichmentPriority::Critical => self.critical_per_min,
+            EnrichmentPriority::High => self.high_per_min,
+            EnrichmentPriority::Low => self.low_per_min,
         }
     }
 }
@@ -73,15 +214,22 @@ pub struct EnrichmentService {
     cache: Arc<FilterCache>,
     /// Configuration
     config: EnrichmentConfig,
+    /// Prior launch outcomes by creator
+    creator_histories: CreatorHistoryCache,
 }
 
 impl EnrichmentService {
     /// Create a new enrichment service
-    pub fn new(helius: HeliusClient, cache: Arc<FilterCache>, config: EnrichmentConfig) -> Self {
+    pub fn new(
+        helius: Arc<HeliusClient>,
+        cache: Arc<FilterCache>,
+        config: EnrichmentConfig,
+    ) -> Self {
         Self {
-            helius: Arc::new(helius),
+            helius,
             cache,
             config,
+            creator_histories: CreatorHistoryCache::default(),
         }
     }
 
@@ -91,7 +239,7 @@ impl EnrichmentService {
         cache: Arc<FilterCache>,
         config: EnrichmentConfig,
     ) -> Option<Self> {
-        HeliusClient::from_rpc_url(rpc_url).map(|helius| Self::new(helius, cache, config))
+        HeliusClient::from_rpc_url(rpc_url).map(|helius| Self::new(Arc::new(helius), cache, config))
     }
 
     /// Enrich data for a new token (synchronous, for hot path)
@@ -141,13 +289,16 @@ impl EnrichmentService {
             )
             .await
             {
-                Ok(Ok(history)) => {
+                Ok(Ok(mut history)) => {
                     debug!(
                         creator = %creator,
                         total_trades = history.total_trades,
                         "Fetched creator wallet history"
                     );
+                    self.apply_creator_history(creator, mint, &mut history)
+                        .await;
                     self.cache.set_wallet(creator, history);
+                    self.cache.invalidate_score(mint);
                     success_count += 1;
                 }
                 Ok(Err(e)) => {
@@ -206,6 +357,45 @@ impl EnrichmentService {
         success
     }
 
+    /// Fill the deployer fields of `history` from the creator's prior
+    /// launches (left as they are if those cannot be fetched)
+    async fn apply_creator_history(&self, creator: &str, mint: &str, history: &mut WalletHistory) {
+        let config = &self.config.creator_history;
+        if !config.enabled {
+            return;
+        }
+        let ttl = Duration::from_secs(config.cache_ttl_secs);
+        let launches = match self.creator_histories.get(creator, ttl) {
+            Some(launches) => launches,
+            None => {
+                let timeout_duration = Duration::from_millis(self.config.api_timeout_ms);
+                let fetch = fetch_creator_history(&self.helius, creator, config);
+                match timeout(timeout_duration, fetch).await {
+                    Ok(Ok(launches)) => {
+                        self.creator_histories
+                            .insert(creator, launches.clone(), ttl);
+                        launches
+                    }
+                    Ok(Err(e)) => {
+                        warn!(creator = %creator, error = %e, "Failed to fetch creator launches");
+                        return;
+                    }
+                    Err(_) => {
+                        warn!(creator = %creator, "Creator launches request timed out");
+                        return;
+                    }
+                }
+            }
+        };
+        launches.apply(history, mint);
+        debug!(
+            creator = %creator,
+            tokens_deployed = history.tokens_deployed,
+            rugged = history.deployed_rug_count,
+            "Applied creator launch history"
+        );
+    }
+
     /// Enrich a single wallet (for background processing)
     pub async fn enrich_wallet(&self, address: &str) -> bool {
         if self.cache.get_wallet(address).is_some() {
@@ -241,6 +431,65 @@ impl EnrichmentService {
         }
     }
 
+    /// Refetch mint info and holders of a held position, bypassing the cache
+    ///
+    /// Returns the fresh holder list when it could be fetched.
+    pub async fn refresh_position(&self, mint: &str) -> Option<Vec<TokenHolderInfo>> {
+        let timeout_duration = Duration::from_millis(self.config.api_timeout_ms);
+
+        if self.config.fetch_mint_info {
+            match timeout(timeout_duration, self.helius.get_mint_info(mint)).await {
+                Ok(Ok(info)) => self.cache.set_mint_info(mint, info),
+                Ok(Err(e)) => warn!(mint = %mint, error = %e, "Failed to refresh mint info"),
+                Err(_) => warn!(mint = %mint, "Mint info refresh timed out"),
+            }
+        }
+
+        match timeout(
+            timeout_duration,
+            self.helius
+                .get_token_holders(mint, self.config.holder_limit),
+        )
+        .await
+        {
+            Ok(Ok(holders)) => {
+                debug!(mint = %mint, holder_count = holders.len(), "Refreshed token holders");
+                self.cache.set_holders(mint, holders.clone());
+                Some(holders)
+            }
+            Ok(Err(e)) => {
+                warn!(mint = %mint, error = %e, "Failed to refresh holders");
+                None
+            }
+            Err(_) => {
+                warn!(mint = %mint, "Holders refresh timed out");
+                None
+            }
+        }
+    }
+
+    /// Fetch a creator's recent token transfers
+    pub async fn creator_transfers(&self, creator: &str) -> Option<Vec<SplTransfer>> {
+        let timeout_duration = Duration::from_millis(self.config.api_timeout_ms);
+        match timeout(
+            timeout_duration,
+            self.helius
+                .get_token_transfers(creator, self.config.wallet_tx_limit),
+        )
+        .await
+        {
+            Ok(Ok(transfers)) => Some(transfers),
+            Ok(Err(e)) => {
+                warn!(creator = %creator, error = %e, "Failed to fetch creator transfers");
+                None
+            }
+            Err(_) => {
+                warn!(creator = %creator, "Creator transfers fetch timed out");
+                None
+            }
+        }
+    }
+
     /// Get the Helius client
     pub fn helius(&self) -> &HeliusClient {
         &self.helius
@@ -252,54 +501,317 @@ impl EnrichmentService {
     }
 }
 
+/// Token bucket refilled at `per_min` requests per minute
+///
+/// Bursts up to ten seconds' worth so an idle lane can catch up quickly
+/// without spending a whole minute of credits at once.
+#[derive(Debug)]
+struct RateBudget {
+    per_min: u32,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateBudget {
+    fn new(per_min: u32, now: Instant) -> Self {
+        let mut budget = Self {
+            per_min,
+            tokens: 0.0,
+            last: now,
+        };
+        budget.tokens = budget.burst();
+        budget
+    }
+
+    fn burst(&self) -> f64 {
+        (self.per_min as f64 / 6.0).max(1.0)
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        if self.per_min == 0 {
+            return false;
+        }
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_min as f64 / 60.0).min(self.burst());
+        self.last = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Lanes and budgets, guarded together
+struct LaneState {
+    lanes: [VecDeque<EnrichmentRequest>; LANES],
+    /// Mint -> lane it is queued in
+    queued: HashMap<String, EnrichmentPriority>,
+    budgets: [RateBudget; LANES],
+}
+
+/// Priority queue shared by the handles and the worker
+struct EnrichmentQueue {
+    state: Mutex<LaneState>,
+    notify: Notify,
+    capacity: usize,
+    processed: [AtomicU64; LANES],
+    dropped: AtomicU64,
+    started: Instant,
+}
+
+impl EnrichmentQueue {
+    fn new(config: &EnrichmentConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            state: Mutex::new(LaneState {
+                lanes: Default::default(),
+                queued: HashMap::new(),
+                budgets: EnrichmentPriority::ALL.map(|p| RateBudget::new(config.per_min(p), now)),
+            }),
+            notify: Notify::new(),
+            capacity: config.queue_capacity.max(1),
+            processed: Default::default(),
+            dropped: AtomicU64::new(0),
+            started: now,
+        }
+    }
+
+    /// Queue a request; false if it was dropped because the queue is full
+    fn push(&self, request: EnrichmentRequest) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.queued.get(&request.mint).copied() {
+            // Already queued at the same or a more urgent priority; keep
+            // the creator if the queued request didn't carry one
+            Some(queued) if queued <= request.priority => {
+                if !request.creator.is_empty() {
+                    if let Some(existing) = state.lanes[queued.lane()]
+                        .iter_mut()
+                        .find(|r| r.mint == request.mint && r.creator.is_empty())
+                    {
+                        existing.creator = request.creator;
+                    }
+                }
+                return true;
+            }
+            // Promote: take it out of the slower lane
+            Some(queued) => {
+                state.lanes[queued.lane()].retain(|r| r.mint != request.mint);
+                state.queued.remove(&request.mint);
+            }
+            None => {}
+        }
+
+        if state.queued.len() >= self.capacity {
+            // Evict the oldest request from the slowest lane below this one
+            let victim = (request.priority.lane() + 1..LANES)
+                .rev()
+                .find(|&lane| !state.lanes[lane].is_empty());
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            match victim.and_then(|lane| state.lanes[lane].pop_front()) {
+                Some(evicted) => {
+                    debug!(mint = %evicted.mint, "Enrichment queue full - evicted request");
+                    state.queued.remove(&evicted.mint);
+                }
+                None => {
+                    debug!(mint = %request.mint, "Enrichment queue full - request dropped");
+                    return false;
+                }
+            }
+        }
+
+        state.queued.insert(request.mint.clone(), request.priority);
+        state.lanes[request.priority.lane()].push_back(request);
+        drop(state);
+        self.notify.notify_one();
+        true
+    }
+
+    /// Next request from the most urgent lane that has budget left
+    fn pop(&self, now: Instant) -> Option<EnrichmentRequest> {
+        let mut state = self.state.lock().unwrap();
+        for lane in 0..LANES {
+            if state.lanes[lane].is_empty() || !state.budgets[lane].try_take(now) {
+                continue;
+            }
+            if let Some(request) = state.lanes[lane].pop_front() {
+                state.queued.remove(&request.mint);
+                return Some(request);
+            }
+        }
+        None
+    }
+
+    fn record_processed(&self, priority: EnrichmentPriority) {
+        self.processed[priority.lane()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn stats(&self) -> EnrichmentStats {
+        let state = self.state.lock().unwrap();
+        let minutes = (self.started.elapsed().as_secs_f64() / 60.0).max(1.0 / 60.0);
+        let lanes = EnrichmentPriority::ALL.map(|priority| {
+            let processed = self.processed[priority.lane()].load(Ordering::Relaxed);
+            LaneStats {
+                queued: state.lanes[priority.lane()].len(),
+                processed,
+                per_min: processed as f64 / minutes,
+            }
+        });
+        EnrichmentStats {
+            lanes,
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Queue depth and throughput of one lane
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaneStats {
+    /// Requests waiting
+    pub queued: usize,
+    /// Requests processed since start
+    pub processed: u64,
+    /// Average requests processed per minute since start
+    pub per_min: f64,
+}
+
+/// Enrichment queue statistics
+#[derive(Debug, Clone)]
+pub struct EnrichmentStats {
+    /// Per lane, most urgent first
+    pub lanes: [LaneStats; LANES],
+    /// Requests dropped or evicted because the queue was full
+    pub dropped: u64,
+}
+
+impl EnrichmentStats {
+    /// Stats for one lane
+    pub fn lane(&self, priority: EnrichmentPriority) -> &LaneStats {
+        &self.lanes[priority.lane()]
+    }
+
+    /// Requests waiting across all lanes
+    pub fn queue_depth(&self) -> usize {
+        self.lanes.iter().map(|l| l.queued).sum()
+    }
+}
+
+impl std::fmt::Display for EnrichmentStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "queue depth {}", self.queue_depth())?;
+        for priority in EnrichmentPriority::ALL {
+            let lane = self.lane(priority);
+            write!(
+                f,
+                ", {} {} queued / {} done ({:.1}/min)",
+                priority, lane.queued, lane.processed, lane.per_min
+            )?;
+        }
+        write!(f, ", {} dropped", self.dropped)
+    }
+}
+
 /// Background worker that processes enrichment requests
 pub struct EnrichmentWorker {
     service: Arc<EnrichmentService>,
-    receiver: mpsc::Receiver<EnrichmentRequest>,
+    queue: Arc<EnrichmentQueue>,
+    holder_refresh: Option<HolderRefreshHandler>,
+    creator_transfers: Option<CreatorTransferHandler>,
 }
 
 impl EnrichmentWorker {
-    /// Create a new worker with a channel receiver
-    pub fn new(
-        service: Arc<EnrichmentService>,
-        receiver: mpsc::Receiver<EnrichmentRequest>,
-    ) -> Self {
-        Self { service, receiver }
+    /// Hand refreshed holder lists of held positions to `handler`
+    pub fn with_holder_refresh(mut self, handler: HolderRefreshHandler) -> Self {
+        self.holder_refresh = Some(handler);
+        self
+    }
+
+    /// Hand the creator's token transfers to `handler` for Critical
+    /// requests that name the creator
+    pub fn with_creator_transfers(mut self, handler: CreatorTransferHandler) -> Self {
+        self.creator_transfers = Some(handler);
+        self
     }
 
     /// Run the worker (consumes self)
-    pub async fn run(mut self) {
+    ///
+    /// Up to `max_concurrent` requests are in flight; each lane is drained
+    /// in priority order as far as its rate budget allows.
+    pub async fn run(self) {
         info!("Enrichment worker started");
+        let permits = Arc::new(Semaphore::new(self.service.config.max_concurrent.max(1)));
+
+        loop {
+            let permit = match permits.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => break,
+            };
+            let request = loop {
+                // Don't spend lane budget on requests the breaker would refuse
+                if self.service.helius.budget().is_open() {
+                    tokio::time::sleep(IDLE_POLL).await;
+                    continue;
+                }
+                if let Some(request) = self.queue.pop(Instant::now()) {
+                    break request;
+                }
+                // Wait for a new request or for a lane's budget to refill
+                let _ = timeout(IDLE_POLL, self.queue.notify.notified()).await;
+            };
 
-        while let Some(request) = self.receiver.recv().await {
             debug!(
                 mint = %request.mint,
                 creator = %request.creator,
-                priority = ?request.priority,
+                priority = %request.priority,
                 "Processing enrichment request"
             );
 
-            // Create a minimal context for enrichment
-            let context = SignalContext::from_new_token(
-                request.mint.clone(),
-                String::new(),
-                String::new(),
-                String::new(),
-                request.creator.clone(),
-                String::new(),
-                0,
-                0,
-                0,
-                0.0,
-            );
-
-            let success = self.service.enrich_token(&context).await;
+            let service = self.service.clone();
+            let queue = self.queue.clone();
+            let holder_refresh = self.holder_refresh.clone();
+            let creator_transfers = self.creator_transfers.clone();
+            tokio::spawn(async move {
+                let _permit = permit;
+                if request.priority == EnrichmentPriority::Critical {
+                    if let Some(holders) = service.refresh_position(&request.mint).await {
+                        if let Some(handler) = holder_refresh {
+                            handler(&request.mint, &holders);
+                        }
+                    }
+                    match creator_transfers {
+                        Some(handler) if !request.creator.is_empty() => {
+                            if let Some(transfers) =
+                                service.creator_transfers(&request.creator).await
+                            {
+                                handler(&request.mint, &request.creator, &transfers);
+                            }
+                        }
+                        _ => {}
+                    }
+                } else {
+                    // Create a minimal context for enrichment
+                    let context = SignalContext::from_new_token(
+                        request.mint.clone(),
+                        String::new(),
+                        String::new(),
+                        String::new(),
+                        request.creator.clone(),
+                        String::new(),
+                        0,
+                        0,
+                        0,
+                        0.0,
+                    );
 
-            if success {
-                debug!(mint = %request.mint, "Enrichment request completed");
-            } else {
-                debug!(mint = %request.mint, "Enrichment request partially failed");
-            }
+                    if service.enrich_token(&context).await {
+                        debug!(mint = %request.mint, "Enrichment request completed");
+                    } else {
+                        debug!(mint = %request.mint, "Enrichment request partially failed");
+                    }
+                }
+                queue.record_processed(request.priority);
+            });
         }
 
         info!("Enrichment worker stopped");
@@ -309,45 +821,52 @@ impl EnrichmentWorker {
 /// Handle for sending enrichment requests
 #[derive(Clone)]
 pub struct EnrichmentHandle {
-    sender: mpsc::Sender<EnrichmentRequest>,
+    queue: Arc<EnrichmentQueue>,
 }
 
 impl EnrichmentHandle {
-    /// Create a new handle
-    pub fn new(sender: mpsc::Sender<EnrichmentRequest>) -> Self {
-        Self { sender }
-    }
-
     /// Request enrichment for a token
-    pub async fn request_enrichment(
+    ///
+    /// Returns false if the queue is full of more urgent requests.
+    pub fn request_enrichment(
         &self,
         mint: String,
         creator: String,
         priority: EnrichmentPriority,
     ) -> bool {
-        let request = EnrichmentRequest {
+        self.queue.push(EnrichmentRequest {
             mint,
             creator,
             priority,
-        };
+        })
+    }
 
-        self.sender.send(request).await.is_ok()
+    /// Queue depth and per-lane throughput
+    pub fn stats(&self) -> EnrichmentStats {
+        self.queue.stats()
     }
 }
 
 /// Create an enrichment service with a background worker
 ///
-/// Returns the service (for synchronous use) and a handle (for async requests)
+/// Returns the service (for synchronous use), a handle (for queued
+/// requests) and the worker to spawn.
 pub fn create_enrichment_system(
-    helius: HeliusClient,
+    helius: Arc<HeliusClient>,
     cache: Arc<FilterCache>,
     config: EnrichmentConfig,
 ) -> (Arc<EnrichmentService>, EnrichmentHandle, EnrichmentWorker) {
-    let (sender, receiver) = mpsc::channel(100);
-
+    let queue = Arc::new(EnrichmentQueue::new(&config));
     let service = Arc::new(EnrichmentService::new(helius, cache, config));
-    let handle = EnrichmentHandle::new(sender);
-    let worker = EnrichmentWorker::new(service.clone(), receiver);
+    let handle = EnrichmentHandle {
+        queue: queue.clone(),
+    };
+    let worker = EnrichmentWorker {
+        service: service.clone(),
+        queue,
+        holder_refresh: None,
+        creator_transfers: None,
+    };
 
     (service, handle, worker)
 }
@@ -356,6 +875,14 @@ pub fn create_enrichment_system(
 mod tests {
     use super::*;
 
+    fn request(mint: &str, priority: EnrichmentPriority) -> EnrichmentRequest {
+        EnrichmentRequest {
+            mint: mint.to_string(),
+            creator: String::new(),
+            priority,
+        }
+    }
+
     #[test]
     fn test_enrichment_config_default() {
         let config = EnrichmentConfig::default();
@@ -363,4 +890,89 @@ mod tests {
         assert_eq!(config.holder_limit, 20);
         assert!(config.fetch_mint_info);
     }
+
+    #[test]
+    fn test_lanes_drain_most_urgent_first() {
+        let queue = EnrichmentQueue::new(&EnrichmentConfig::default());
+        queue.push(request("low", EnrichmentPriority::Low));
+        queue.push(request("high", EnrichmentPriority::High));
+        queue.push(request("held", EnrichmentPriority::Critical));
+
+        let now = Instant::now();
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop(now))
+            .map(|r| r.mint)
+            .collect();
+        assert_eq!(order, ["held", "high", "low"]);
+    }
+
+    #[test]
+    fn test_dedupe_and_promotion() {
+        let queue = EnrichmentQueue::new(&EnrichmentConfig::default());
+        assert!(queue.push(request("mint", EnrichmentPriority::Low)));
+        assert!(queue.push(request("mint", EnrichmentPriority::Low)));
+        assert_eq!(queue.stats().queue_depth(), 1);
+
+        // A buy decision is pending: moves up to the High lane
+        queue.push(request("mint", EnrichmentPriority::High));
+        let stats = queue.stats();
+        assert_eq!(stats.queue_depth(), 1);
+        assert_eq!(stats.lane(EnrichmentPriority::High).queued, 1);
+
+        // Never demoted
+        queue.push(request("mint", EnrichmentPriority::Low));
+        assert_eq!(queue.stats().lane(EnrichmentPriority::High).queued, 1);
+
+        // A duplicate naming the creator fills it in
+        queue.push(EnrichmentRequest {
+            creator: "creator".to_string(),
+            ..request("mint", EnrichmentPriority::High)
+        });
+        let popped = queue.pop(Instant::now()).unwrap();
+        assert_eq!(popped.creator, "creator");
+    }
+
+    #[test]
+    fn test_lane_budgets() {
+        let config = EnrichmentConfig {
+            critical_per_min: 6, // Burst of one, one every 10s
+            low_per_min: 0,
+            ..Default::default()
+        };
+        let queue = EnrichmentQueue::new(&config);
+        queue.push(request("a", EnrichmentPriority::Critical));
+        queue.push(request("b", EnrichmentPriority::Critical));
+        queue.push(request("c", EnrichmentPriority::High));
+        queue.push(request("d", EnrichmentPriority::Low));
+
+        // Critical budget spent: High goes next, paused Low never does
+        let now = Instant::now();
+        assert_eq!(queue.pop(now).unwrap().mint, "a");
+        assert_eq!(queue.pop(now).unwrap().mint, "c");
+        assert!(queue.pop(now).is_none());
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(queue.pop(later).unwrap().mint, "b");
+        assert!(queue.pop(later).is_none());
+        assert_eq!(queue.stats().lane(EnrichmentPriority::Low).queued, 1);
+    }
+
+    #[test]
+    fn test_full_queue_evicts_low() {
+        let config = EnrichmentConfig {
+            queue_capacity: 2,
+            ..Default::default()
+        };
+        let queue = EnrichmentQueue::new(&config);
+        queue.push(request("a", EnrichmentPriority::Low));
+        queue.push(request("b", EnrichmentPriority::Low));
+
+        assert!(queue.push(request("held", EnrichmentPriority::Critical)));
+        assert!(!queue.push(request("c", EnrichmentPriority::Low)));
+
+        let stats = queue.stats();
+        assert_eq!(stats.queue_depth(), 2);
+        assert_eq!(stats.dropped, 2);
+        assert_eq!(queue.pop(Instant::now()).unwrap().mint, "held");
+        assert_eq!(queue.pop(Instant::now()).unwrap().mint, "b");
+    }
 }
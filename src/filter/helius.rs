@@ -2,17 +2,27 @@
 //!
 //! Provides access to:
 //! - Wallet transaction history (for creator analysis)
+//! - Raw wallet transactions (for pump.fun instruction decoding)
+//! - Bonding curve accounts (for creator launch outcomes)
 //! - Token holder data (for distribution scoring)
 //! - Enhanced transaction parsing
 
+use base64::Engine;
 use chrono::{DateTime, Utc};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::sync::Mutex;
 use std::time::Duration;
 use tracing::debug;
 
 use crate::error::{Error, Result};
+use crate::filter::helius_budget::{
+    HeliusBudget, HeliusBudgetConfig, HeliusEndpoint, RequestOutcome,
+};
 use crate::filter::types::{TokenHolderInfo, WalletHistory, WalletTrade};
+use crate::pump::accounts::BondingCurve;
 
 /// Helius API client
 pub struct HeliusClient {
@@ -26,6 +36,10 @@ pub struct HeliusClient {
     rpc_base_url: String,
     /// Request timeout
     timeout: Duration,
+    /// Request accounting and circuit breaker
+    budget: HeliusBudget,
+    /// Rate limit headroom from the last response's headers, if sent
+    rate_limit_remaining: Mutex<Option<u64>>,
 }
 
 impl HeliusClient {
@@ -42,6 +56,8 @@ impl HeliusClient {
             rest_base_url: "https://api.helius.xyz".to_string(),
             rpc_base_url: format!("https://mainnet.helius-rpc.com/?api-key={}", api_key),
             timeout: Duration::from_secs(10),
+            budget: HeliusBudget::new(HeliusBudgetConfig::default()),
+            rate_limit_remaining: Mutex::new(None),
         }
     }
 
@@ -59,6 +75,74 @@ impl HeliusClient {
         None
     }
 
+    /// Use the given circuit breaker settings
+    pub fn with_budget(mut self, config: HeliusBudgetConfig) -> Self {
+        self.budget = HeliusBudget::new(config);
+        self
+    }
+
+    /// Request accounting and breaker state
+    pub fn budget(&self) -> &HeliusBudget {
+        &self.budget
+    }
+
+    /// Requests left in the current rate limit window, when the API says
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        *self.rate_limit_remaining.lock().unwrap()
+    }
+
+    /// Send a request through the circuit breaker and record the outcome
+    async fn send(
+        &self,
+        endpoint: HeliusEndpoint,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        self.budget.before_request(endpoint)?;
+
+        let response = match request.timeout(self.timeout).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.budget.record(endpoint, RequestOutcome::Failure);
+                return Err(Error::Network(format!(
+                    "Helius {} request failed: {}",
+                    endpoint, e
+                )));
+            }
+        };
+
+        let remaining = ["x-ratelimit-remaining", "ratelimit-remaining"]
+            .iter()
+            .find_map(|name| response.headers().get(*name))
+            .and_then(|value| value.to_str().ok()?.parse().ok());
+        if remaining.is_some() {
+            *self.rate_limit_remaining.lock().unwrap() = remaining;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after_secs = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()?.parse().ok());
+            let body = response.text().await.unwrap_or_default();
+            let message = format!("Helius {} error {}: {}", endpoint, status, body);
+            let error = match status.as_u16() {
+                429 => Error::ApiRateLimited {
+                    api: format!("Helius {}", endpoint),
+                    retry_after_secs,
+                },
+                408 | 500..=599 => Error::Network(message),
+                _ => Error::RpcFatal(message),
+            };
+            self.budget
+                .record(endpoint, RequestOutcome::from_error(&error));
+            return Err(error);
+        }
+
+        self.budget.record(endpoint, RequestOutcome::Success);
+        Ok(response)
+    }
+
     /// Fetch wallet transaction history
     ///
     /// Returns recent transactions for analysis of trading patterns
@@ -71,18 +155,8 @@ impl HeliusClient {
         debug!("Fetching wallet history for {}", address);
 
         let response = self
-            .client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius API error {}: {}", status, body)));
-        }
+            .send(HeliusEndpoint::WalletHistory, self.client.get(&url))
+            .await?;
 
         let transactions: Vec<HeliusTransaction> = response
             .json()
@@ -120,6 +194,80 @@ impl HeliusClient {
         })
     }
 
+    /// Fetch a wallet's recent successful transactions, newest first
+    ///
+    /// Raw (base64) transactions for `pump::instruction::parse_transaction`,
+    /// fetched with one batched getTransaction request.
+    pub async fn get_transactions(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<EncodedConfirmedTransactionWithStatusMeta>> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "helius-signatures",
+            "method": "getSignaturesForAddress",
+            "params": [address, { "limit": limit }]
+        });
+
+        debug!("Fetching transactions for {}", address);
+
+        let response = self
+            .send(
+                HeliusEndpoint::Transactions,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
+
+        let rpc_response: HeliusRpcResponse<Vec<SignatureInfo>> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse RPC response: {}", e)))?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(Error::Rpc(format!("Helius RPC error: {}", error.message)));
+        }
+
+        let batch: Vec<serde_json::Value> = rpc_response
+            .result
+            .unwrap_or_default()
+            .iter()
+            .filter(|s| s.err.is_none())
+            .map(|s| {
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": s.signature,
+                    "method": "getTransaction",
+                    "params": [
+                        s.signature,
+                        {
+                            "encoding": "base64",
+                            "commitment": "confirmed",
+                            "maxSupportedTransactionVersion": 0
+                        }
+                    ]
+                })
+            })
+            .collect();
+        if batch.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self
+            .send(
+                HeliusEndpoint::Transactions,
+                self.client.post(&self.rpc_base_url).json(&batch),
+            )
+            .await?;
+
+        let responses: Vec<HeliusRpcResponse<EncodedConfirmedTransactionWithStatusMeta>> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse transactions: {}", e)))?;
+
+        Ok(responses.into_iter().filter_map(|r| r.result).collect())
+    }
+
     /// Fetch token holders for a mint
     ///
     /// Returns top holders and distribution metrics
@@ -141,19 +289,11 @@ impl HeliusClient {
         debug!("Fetching token holders for {}", mint);
 
         let response = self
-            .client
-            .post(&self.rpc_base_url)
-            .json(&request)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius RPC request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius RPC error {}: {}", status, body)));
-        }
+            .send(
+                HeliusEndpoint::TokenHolders,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
 
         let rpc_response: HeliusRpcResponse<TokenAccountsResult> = response
             .json()
@@ -196,6 +336,54 @@ impl HeliusClient {
         Ok(holders)
     }
 
+    /// Bonding curves of `mints` in order, in one getMultipleAccounts request
+    /// (at most 100; None = account missing or not decodable)
+    pub async fn get_bonding_curves(&self, mints: &[Pubkey]) -> Result<Vec<Option<BondingCurve>>> {
+        if mints.is_empty() {
+            return Ok(Vec::new());
+        }
+        let addresses: Vec<String> = mints
+            .iter()
+            .map(|mint| BondingCurve::address(mint).to_string())
+            .collect();
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "helius-curves",
+            "method": "getMultipleAccounts",
+            "params": [addresses, { "encoding": "base64" }]
+        });
+
+        debug!("Fetching {} bonding curves", mints.len());
+
+        let response = self
+            .send(
+                HeliusEndpoint::BondingCurves,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
+
+        let rpc_response: HeliusRpcResponse<MultipleAccountsResult> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse RPC response: {}", e)))?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(Error::Rpc(format!("Helius RPC error: {}", error.message)));
+        }
+
+        let accounts = rpc_response.result.map(|r| r.value).unwrap_or_default();
+        Ok(accounts
+            .into_iter()
+            .map(|account| {
+                let (data, _) = account?.data;
+                let data = base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .ok()?;
+                BondingCurve::try_from_slice(&data).ok()
+            })
+            .collect())
+    }
+
     /// Get mint account info to check authorities
     pub async fn get_mint_info(&self, mint: &str) -> Result<MintInfo> {
         let request = serde_json::json!({
@@ -213,19 +401,11 @@ impl HeliusClient {
         debug!("Fetching mint info for {}", mint);
 
         let response = self
-            .client
-            .post(&self.rpc_base_url)
-            .json(&request)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius RPC request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius RPC error {}: {}", status, body)));
-        }
+            .send(
+                HeliusEndpoint::MintInfo,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
 
         let rpc_response: HeliusRpcResponse<AccountInfoResult> = response
             .json()
@@ -253,6 +433,7 @@ impl HeliusClient {
                     freeze_authority: info.freeze_authority,
                     supply: info.supply.parse().unwrap_or(0),
                     decimals: info.decimals,
+                    extensions: info.extensions.into_iter().map(|e| e.extension).collect(),
                 });
             }
         }
@@ -353,18 +534,8 @@ impl HeliusClient {
         debug!("Fetching funding transfers for {}", address);
 
         let response = self
-            .client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius API error {}: {}", status, body)));
-        }
+            .send(HeliusEndpoint::FundingTransfers, self.client.get(&url))
+            .await?;
 
         let transactions: Vec<HeliusTransaction> = response
             .json()
@@ -385,9 +556,9 @@ impl HeliusClient {
                                 to: address.to_string(),
                                 amount_lamports: transfer.amount,
                                 amount_sol: transfer.amount as f64 / 1e9,
-                                timestamp: tx.timestamp.and_then(|ts| {
-                                    DateTime::from_timestamp(ts, 0)
-                                }),
+                                timestamp: tx
+                                    .timestamp
+                                    .and_then(|ts| DateTime::from_timestamp(ts, 0)),
                             });
                         }
                     }
@@ -398,6 +569,52 @@ impl HeliusClient {
         Ok(transfers)
     }
 
+    /// Get token transfers sent or received by a wallet
+    ///
+    /// Used to follow a creator's allocation to the wallets it was moved to
+    pub async fn get_token_transfers(&self, address: &str, limit: u32) -> Result<Vec<SplTransfer>> {
+        let url = format!(
+            "{}/v0/addresses/{}/transactions?api-key={}&limit={}",
+            self.rest_base_url, address, self.api_key, limit
+        );
+
+        debug!("Fetching token transfers for {}", address);
+
+        let response = self
+            .send(HeliusEndpoint::TokenTransfers, self.client.get(&url))
+            .await?;
+
+        let transactions: Vec<HeliusTransaction> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse Helius response: {}", e)))?;
+
+        let mut transfers = Vec::new();
+        for tx in &transactions {
+            for transfer in tx.token_transfers.iter().flatten() {
+                let (Some(from), Some(to)) =
+                    (&transfer.from_user_account, &transfer.to_user_account)
+                else {
+                    continue;
+                };
+                if from != address && to != address {
+                    continue;
+                }
+                transfers.push(SplTransfer {
+                    signature: tx.signature.clone(),
+                    tx_type: tx.r#type.clone(),
+                    mint: transfer.mint.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                    amount: transfer.token_amount,
+                    timestamp: tx.timestamp.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+                });
+            }
+        }
+
+        Ok(transfers)
+    }
+
     /// Get the creator (deployer) of a token
     ///
     /// Fetches the first transaction for the mint to identify who created it.
@@ -422,19 +639,11 @@ impl HeliusClient {
 
         // Get the earliest signature by fetching with commitment
         let response = self
-            .client
-            .post(&self.rpc_base_url)
-            .json(&request)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius RPC request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius RPC error {}: {}", status, body)));
-        }
+            .send(
+                HeliusEndpoint::TokenCreator,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
 
         let rpc_response: HeliusRpcResponse<Vec<SignatureInfo>> = response
             .json()
@@ -461,18 +670,8 @@ impl HeliusClient {
         );
 
         let response = self
-            .client
-            .get(&url)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|e| Error::Rpc(format!("Helius request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Rpc(format!("Helius API error {}: {}", status, body)));
-        }
+            .send(HeliusEndpoint::TokenCreator, self.client.get(&url))
+            .await?;
 
         let transactions: Vec<HeliusTransaction> = response
             .json()
@@ -485,6 +684,47 @@ impl HeliusClient {
             .and_then(|tx| tx.fee_payer.clone())
             .ok_or_else(|| Error::Rpc("Could not determine token creator".to_string()))
     }
+
+    /// Get a token's name, symbol and metadata URI (DAS `getAsset`)
+    pub async fn get_asset_metadata(&self, mint: &str) -> Result<AssetMetadata> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "helius-asset",
+            "method": "getAsset",
+            "params": { "id": mint }
+        });
+
+        debug!("Fetching asset metadata for {}", mint);
+
+        let response = self
+            .send(
+                HeliusEndpoint::AssetMetadata,
+                self.client.post(&self.rpc_base_url).json(&request),
+            )
+            .await?;
+
+        let rpc_response: HeliusRpcResponse<AssetResult> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse RPC response: {}", e)))?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(Error::Rpc(format!("Helius RPC error: {}", error.message)));
+        }
+
+        let content = rpc_response
+            .result
+            .and_then(|r| r.content)
+            .ok_or_else(|| Error::Rpc("No content in asset response".to_string()))?;
+        let metadata = content.metadata.unwrap_or_default();
+
+        Ok(AssetMetadata {
+            mint: mint.to_string(),
+            name: metadata.name.unwrap_or_default(),
+            symbol: metadata.symbol.unwrap_or_default(),
+            uri: content.json_uri.unwrap_or_default(),
+        })
+    }
 }
 
 /// Signature info from getSignaturesForAddress
@@ -495,6 +735,8 @@ struct SignatureInfo {
     slot: u64,
     #[serde(rename = "blockTime")]
     block_time: Option<i64>,
+    #[serde(default)]
+    err: Option<serde_json::Value>,
 }
 
 /// SOL transfer record for funding analysis
@@ -508,6 +750,20 @@ pub struct SolTransfer {
     pub timestamp: Option<DateTime<Utc>>,
 }
 
+/// SPL token transfer record for allocation tracking
+#[derive(Debug, Clone)]
+pub struct SplTransfer {
+    pub signature: String,
+    /// Helius transaction type (`TRANSFER`, `SWAP`, ...)
+    pub tx_type: Option<String>,
+    pub mint: String,
+    pub from: String,
+    pub to: String,
+    /// UI amount (decimals applied)
+    pub amount: f64,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
 // ============ Helius API Response Types ============
 // These structs are for API deserialization - not all fields are used but are required for parsing
 
@@ -586,6 +842,17 @@ struct TokenAccount {
     frozen: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct MultipleAccountsResult {
+    value: Vec<Option<EncodedAccount>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncodedAccount {
+    /// Data and its encoding ("base64")
+    data: (String, String),
+}
+
 #[derive(Debug, Deserialize)]
 struct AccountInfoResult {
     value: Option<AccountValue>,
@@ -619,16 +886,63 @@ struct MintInfoData {
     #[serde(rename = "mintAuthority")]
     mint_authority: Option<String>,
     supply: String,
+    /// Token-2022 mints only
+    #[serde(default)]
+    extensions: Vec<MintExtensionData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MintExtensionData {
+    extension: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetResult {
+    content: Option<AssetContent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AssetContent {
+    json_uri: Option<String>,
+    metadata: Option<AssetContentMetadata>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AssetContentMetadata {
+    name: Option<String>,
+    symbol: Option<String>,
+}
+
+/// Token metadata from the DAS API
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetMetadata {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
 }
 
+/// Token-2022 extensions that let the creator take, block or tax holders' tokens
+const RISKY_EXTENSIONS: &[&str] = &[
+    "permanentDelegate",
+    "transferHook",
+    "transferFeeConfig",
+    "defaultAccountState",
+    "nonTransferable",
+    "pausableConfig",
+];
+
 /// Parsed mint information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MintInfo {
     pub mint: String,
     pub mint_authority: Option<String>,
     pub freeze_authority: Option<String>,
     pub supply: u64,
     pub decimals: u8,
+    /// Token-2022 extension names (empty for SPL Token mints)
+    #[serde(default)]
+    pub extensions: Vec<String>,
 }
 
 impl MintInfo {
@@ -646,6 +960,15 @@ impl MintInfo {
     pub fn is_fully_renounced(&self) -> bool {
         self.mint_authority.is_none() && self.freeze_authority.is_none()
     }
+
+    /// Token-2022 extensions that give the creator control over holders' tokens
+    pub fn risky_extensions(&self) -> Vec<&str> {
+        self.extensions
+            .iter()
+            .map(String::as_str)
+            .filter(|e| RISKY_EXTENSIONS.contains(e))
+            .collect()
+    }
 }
 
 #[cfg(test)]
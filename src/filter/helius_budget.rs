@@ -0,0 +1,504 @@
+//! Helius request accounting and circuit breaker
+//!
+//! Every `HeliusClient` call is counted per endpoint together with a rolling
+//! error rate and the number of 429 (rate limited) responses. After
+//! `failure_threshold` consecutive failures the breaker opens and requests
+//! fail fast; it half-opens after a backoff that doubles on every re-trip
+//! (capped at `max_backoff_secs`) and a single successful probe closes it.
+//! Only retryable failures (transport errors, 5xx, 429) count toward the
+//! breaker: a request Helius rejects outright (4xx) shows the API is up.
+//!
+//! `is_throttled()` feeds the adaptive filter's `enrichment_throttled`
+//! degraded-mode flag, and `stats()` is what `snipe health` prints.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::error::{Error, ErrorCategory, Result};
+
+/// Requests needed in the window before the error rate counts
+const MIN_RATE_SAMPLES: usize = 5;
+
+/// A probe that never reported back (caller timed out) is replaced after this
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Helius endpoint families counted separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeliusEndpoint {
+    WalletHistory,
+    TokenHolders,
+    MintInfo,
+    FundingTransfers,
+    TokenTransfers,
+    TokenCreator,
+    AssetMetadata,
+    Transactions,
+    BondingCurves,
+}
+
+impl std::fmt::Display for HeliusEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HeliusEndpoint::WalletHistory => "wallet_history",
+            HeliusEndpoint::TokenHolders => "token_holders",
+            HeliusEndpoint::MintInfo => "mint_info",
+            HeliusEndpoint::FundingTransfers => "funding_transfers",
+            HeliusEndpoint::TokenTransfers => "token_transfers",
+            HeliusEndpoint::TokenCreator => "token_creator",
+            HeliusEndpoint::AssetMetadata => "asset_metadata",
+            HeliusEndpoint::Transactions => "transactions",
+            HeliusEndpoint::BondingCurves => "bonding_curves",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a request ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    /// HTTP 429 - rate limit or credits exhausted
+    RateLimited,
+    /// Transport error or server error
+    Failure,
+    /// Permanent error (bad request, not found); counted, but the API answered
+    Rejected,
+}
+
+impl RequestOutcome {
+    /// Outcome of a request that failed with `error`
+    pub fn from_error(error: &Error) -> Self {
+        match error.category() {
+            ErrorCategory::ApiRateLimited => RequestOutcome::RateLimited,
+            _ if error.is_retryable() => RequestOutcome::Failure,
+            _ => RequestOutcome::Rejected,
+        }
+    }
+}
+
+/// Circuit breaker and error-rate settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeliusBudgetConfig {
+    /// Consecutive failures that open the breaker
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// First backoff once open (doubles on every re-trip)
+    #[serde(default = "default_base_backoff_secs")]
+    pub base_backoff_secs: u64,
+    /// Backoff cap
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// Window for the rolling error rate
+    #[serde(default = "default_error_window_secs")]
+    pub error_window_secs: u64,
+    /// Rolling error rate at which enrichment counts as throttled
+    #[serde(default = "default_throttled_error_rate")]
+    pub throttled_error_rate: f64,
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_base_backoff_secs() -> u64 {
+    5
+}
+
+fn default_max_backoff_secs() -> u64 {
+    300
+}
+
+fn default_error_window_secs() -> u64 {
+    60
+}
+
+fn default_throttled_error_rate() -> f64 {
+    0.5
+}
+
+impl Default for HeliusBudgetConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            base_backoff_secs: default_base_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+            error_window_secs: default_error_window_secs(),
+            throttled_error_rate: default_throttled_error_rate(),
+        }
+    }
+}
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Breaker {
+    Closed,
+    Open { until: Instant },
+    HalfOpen { probe_started: Instant },
+}
+
+/// Counts for one endpoint
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct EndpointCounts {
+    pub requests: u64,
+    pub errors: u64,
+    pub rate_limited: u64,
+}
+
+/// Mutable accounting state
+struct BudgetState {
+    endpoints: BTreeMap<HeliusEndpoint, EndpointCounts>,
+    /// (when, failed) for the rolling error rate
+    recent: VecDeque<(Instant, bool)>,
+    breaker: Breaker,
+    consecutive_failures: u32,
+    /// Re-trips since the breaker last closed (sets the backoff)
+    trips_in_row: u32,
+    total_trips: u64,
+    short_circuited: u64,
+}
+
+/// Request accounting and circuit breaker for one Helius client
+pub struct HeliusBudget {
+    config: HeliusBudgetConfig,
+    state: Mutex<BudgetState>,
+}
+
+impl HeliusBudget {
+    pub fn new(config: HeliusBudgetConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BudgetState {
+                endpoints: BTreeMap::new(),
+                recent: VecDeque::new(),
+                breaker: Breaker::Closed,
+                consecutive_failures: 0,
+                trips_in_row: 0,
+                total_trips: 0,
+                short_circuited: 0,
+            }),
+        }
+    }
+
+    /// Fail fast while the breaker is open
+    pub fn before_request(&self, endpoint: HeliusEndpoint) -> Result<()> {
+        self.before_request_at(endpoint, Instant::now())
+    }
+
+    fn before_request_at(&self, endpoint: HeliusEndpoint, now: Instant) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        match state.breaker {
+            Breaker::Closed => Ok(()),
+            // Backoff elapsed: let one probe through
+            Breaker::Open { until } if now >= until => {
+                info!(endpoint = %endpoint, "Helius circuit breaker half-open - probing");
+                state.breaker = Breaker::HalfOpen { probe_started: now };
+                Ok(())
+            }
+            // The probe never reported back (its caller timed out): try another
+            Breaker::HalfOpen { probe_started }
+                if now.saturating_duration_since(probe_started) >= PROBE_TIMEOUT =>
+            {
+                state.breaker = Breaker::HalfOpen { probe_started: now };
+                Ok(())
+            }
+            Breaker::Open { until } => {
+                state.short_circuited += 1;
+                Err(Error::Rpc(format!(
+                    "Helius circuit breaker open ({} skipped, retry in {}s)",
+                    endpoint,
+                    until.saturating_duration_since(now).as_secs()
+                )))
+            }
+            Breaker::HalfOpen { .. } => {
+                state.short_circuited += 1;
+                Err(Error::Rpc(format!(
+                    "Helius circuit breaker half-open ({} skipped while probing)",
+                    endpoint
+                )))
+            }
+        }
+    }
+
+    /// Record how a request that was let through ended
+    pub fn record(&self, endpoint: HeliusEndpoint, outcome: RequestOutcome) {
+        self.record_at(endpoint, outcome, Instant::now())
+    }
+
+    fn record_at(&self, endpoint: HeliusEndpoint, outcome: RequestOutcome, now: Instant) {
+        let mut state = self.state.lock().unwrap();
+        let failed = outcome != RequestOutcome::Success;
+
+        let counts = state.endpoints.entry(endpoint).or_default();
+        counts.requests += 1;
+        counts.errors += u64::from(failed);
+        counts.rate_limited += u64::from(outcome == RequestOutcome::RateLimited);
+
+        // A rejected request got an answer: it doesn't count against the API
+        let healthy = matches!(outcome, RequestOutcome::Success | RequestOutcome::Rejected);
+        state.recent.push_back((now, !healthy));
+        let window = Duration::from_secs(self.config.error_window_secs);
+        while let Some(&(at, _)) = state.recent.front() {
+            if now.saturating_duration_since(at) <= window {
+                break;
+            }
+            state.recent.pop_front();
+        }
+
+        if healthy {
+            if state.breaker != Breaker::Closed {
+                info!("Helius circuit breaker closed");
+            }
+            state.breaker = Breaker::Closed;
+            state.consecutive_failures = 0;
+            state.trips_in_row = 0;
+            return;
+        }
+
+        state.consecutive_failures += 1;
+        let probe_failed = matches!(state.breaker, Breaker::HalfOpen { .. });
+        if probe_failed || state.consecutive_failures >= self.config.failure_threshold.max(1) {
+            let backoff = self.backoff(state.trips_in_row);
+            state.breaker = Breaker::Open {
+                until: now + backoff,
+            };
+            state.trips_in_row += 1;
+            state.total_trips += 1;
+            warn!(
+                endpoint = %endpoint,
+                failures = state.consecutive_failures,
+                rate_limited = outcome == RequestOutcome::RateLimited,
+                backoff_secs = backoff.as_secs(),
+                "Helius circuit breaker open"
+            );
+        }
+    }
+
+    /// Backoff after `trips` re-trips in a row
+    fn backoff(&self, trips: u32) -> Duration {
+        let secs = self
+            .config
+            .base_backoff_secs
+            .saturating_mul(1u64 << trips.min(16))
+            .min(self.config.max_backoff_secs);
+        Duration::from_secs(secs)
+    }
+
+    /// Breaker open (requests are failing fast)
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.state.lock().unwrap().breaker,
+            Breaker::Open { until } if Instant::now() < until
+        )
+    }
+
+    /// Breaker not closed, or the rolling error rate is over the threshold
+    pub fn is_throttled(&self) -> bool {
+        let stats = self.stats();
+        stats.breaker != BreakerState::Closed
+            || stats
+                .error_rate
+                .is_some_and(|rate| rate >= self.config.throttled_error_rate)
+    }
+
+    /// Snapshot for health output
+    pub fn stats(&self) -> HeliusBudgetStats {
+        self.stats_at(Instant::now())
+    }
+
+    fn stats_at(&self, now: Instant) -> HeliusBudgetStats {
+        let state = self.state.lock().unwrap();
+        let window = Duration::from_secs(self.config.error_window_secs);
+        let recent: Vec<bool> = state
+            .recent
+            .iter()
+            .filter(|(at, _)| now.saturating_duration_since(*at) <= window)
+            .map(|&(_, failed)| failed)
+            .collect();
+        let error_rate = (recent.len() >= MIN_RATE_SAMPLES)
+            .then(|| recent.iter().filter(|&&f| f).count() as f64 / recent.len() as f64);
+
+        let (breaker, retry_in_secs) = match state.breaker {
+            Breaker::Closed => (BreakerState::Closed, None),
+            Breaker::Open { until } if now < until => (
+                BreakerState::Open,
+                Some(until.saturating_duration_since(now).as_secs()),
+            ),
+            // Backoff elapsed: the next request probes
+            Breaker::Open { .. } | Breaker::HalfOpen { .. } => (BreakerState::HalfOpen, None),
+        };
+
+        HeliusBudgetStats {
+            endpoints: state.endpoints.clone(),
+            error_rate,
+            window_secs: self.config.error_window_secs,
+            breaker,
+            retry_in_secs,
+            consecutive_failures: state.consecutive_failures,
+            total_trips: state.total_trips,
+            short_circuited: state.short_circuited,
+        }
+    }
+}
+
+/// Breaker state as reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl std::fmt::Display for BreakerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half-open",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Accounting snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeliusBudgetStats {
+    pub endpoints: BTreeMap<HeliusEndpoint, EndpointCounts>,
+    /// Failed share of requests in the window (None = too few requests)
+    pub error_rate: Option<f64>,
+    pub window_secs: u64,
+    pub breaker: BreakerState,
+    /// Seconds until the open breaker half-opens
+    pub retry_in_secs: Option<u64>,
+    pub consecutive_failures: u32,
+    /// Times the breaker has opened
+    pub total_trips: u64,
+    /// Requests refused while open
+    pub short_circuited: u64,
+}
+
+impl HeliusBudgetStats {
+    /// Totals across endpoints
+    pub fn totals(&self) -> EndpointCounts {
+        self.endpoints
+            .values()
+            .fold(EndpointCounts::default(), |acc, c| EndpointCounts {
+                requests: acc.requests + c.requests,
+                errors: acc.errors + c.errors,
+                rate_limited: acc.rate_limited + c.rate_limited,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: HeliusEndpoint = HeliusEndpoint::MintInfo;
+    const HOLDERS: HeliusEndpoint = HeliusEndpoint::TokenHolders;
+
+    fn budget() -> HeliusBudget {
+        HeliusBudget::new(HeliusBudgetConfig {
+            failure_threshold: 3,
+            base_backoff_secs: 10,
+            max_backoff_secs: 25,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_counts_and_error_rate() {
+        let budget = budget();
+        let now = Instant::now();
+        for outcome in [
+            RequestOutcome::Success,
+            RequestOutcome::RateLimited,
+            RequestOutcome::Success,
+            RequestOutcome::Failure,
+        ] {
+            budget.record_at(HeliusEndpoint::MintInfo, outcome, now);
+        }
+        // Too few samples for a rate yet
+        assert_eq!(budget.stats_at(now).error_rate, None);
+
+        budget.record_at(HeliusEndpoint::TokenHolders, RequestOutcome::Success, now);
+        let stats = budget.stats_at(now);
+        assert_eq!(stats.error_rate, Some(0.4));
+        let mint = stats.endpoints[&HeliusEndpoint::MintInfo];
+        assert_eq!((mint.requests, mint.errors, mint.rate_limited), (4, 2, 1));
+        assert_eq!(stats.totals().requests, 5);
+        assert_eq!(stats.breaker, BreakerState::Closed);
+
+        // Old requests leave the window
+        let later = now + Duration::from_secs(120);
+        assert_eq!(budget.stats_at(later).error_rate, None);
+    }
+
+    #[test]
+    fn test_breaker_opens_and_backs_off() {
+        let budget = budget();
+        let now = Instant::now();
+        for _ in 0..3 {
+            budget.before_request_at(MINT, now).unwrap();
+            budget.record_at(MINT, RequestOutcome::RateLimited, now);
+        }
+        assert_eq!(budget.stats_at(now).breaker, BreakerState::Open);
+        assert!(budget.before_request_at(MINT, now).is_err());
+
+        // Half-open after 10s: one probe, which fails -> open for 20s
+        let probe = now + Duration::from_secs(10);
+        budget.before_request_at(MINT, probe).unwrap();
+        assert!(budget.before_request_at(HOLDERS, probe).is_err());
+        budget.record_at(MINT, RequestOutcome::Failure, probe);
+        let stats = budget.stats_at(probe);
+        assert_eq!(stats.retry_in_secs, Some(20));
+        assert_eq!(stats.total_trips, 2);
+        assert_eq!(stats.short_circuited, 2);
+
+        // Third trip is capped at 25s
+        let probe = probe + Duration::from_secs(20);
+        budget.before_request_at(MINT, probe).unwrap();
+        budget.record_at(MINT, RequestOutcome::Failure, probe);
+        assert_eq!(budget.stats_at(probe).retry_in_secs, Some(25));
+
+        // A successful probe closes it
+        let probe = probe + Duration::from_secs(25);
+        budget.before_request_at(MINT, probe).unwrap();
+        budget.record_at(MINT, RequestOutcome::Success, probe);
+        assert_eq!(budget.stats_at(probe).breaker, BreakerState::Closed);
+        assert!(budget.before_request_at(MINT, probe).is_ok());
+    }
+
+    #[test]
+    fn test_rejected_requests_do_not_trip() {
+        let budget = budget();
+        let now = Instant::now();
+        for _ in 0..5 {
+            budget.record_at(MINT, RequestOutcome::Rejected, now);
+        }
+        let stats = budget.stats_at(now);
+        assert_eq!(stats.breaker, BreakerState::Closed);
+        assert_eq!(stats.endpoints[&MINT].errors, 5);
+        assert_eq!(stats.error_rate, Some(0.0));
+
+        let rejected = Error::RpcFatal("Helius mint_info error 400".to_string());
+        let limited = Error::ApiRateLimited {
+            api: "Helius".to_string(),
+            retry_after_secs: None,
+        };
+        let down = Error::Network("Helius mint_info error 503".to_string());
+        assert_eq!(
+            RequestOutcome::from_error(&rejected),
+            RequestOutcome::Rejected
+        );
+        assert_eq!(
+            RequestOutcome::from_error(&limited),
+            RequestOutcome::RateLimited
+        );
+        assert_eq!(RequestOutcome::from_error(&down), RequestOutcome::Failure);
+    }
+}
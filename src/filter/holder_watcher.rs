@@ -2,6 +2,14 @@
 //!
 //! The key to survival: detect when top holders start selling and EXIT FIRST.
 //! This module watches holder wallets and triggers immediate exits when they dump.
+//!
+//! Holder lists come from Helius and age quickly; alerts carry a confidence
+//! that decays once the list is older than `holder_list_ttl_secs`, and a
+//! stale list never produces a Critical alert.
+//!
+//! The same config sets how often held positions' holders are refreshed and
+//! how far the top holder's share may climb after entry before the exit
+//! rules are escalated (see `position::concentration_watch`).
 
 use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
@@ -32,6 +40,35 @@ pub struct HolderWatcherConfig {
     /// How long to track a holder's pattern after they sell
     #[serde(default = "default_pattern_tracking_mins")]
     pub pattern_tracking_mins: u64,
+
+    /// Holder lists older than this lower alert confidence and urgency
+    #[serde(default = "default_holder_list_ttl_secs")]
+    pub holder_list_ttl_secs: u64,
+
+    /// Seconds between holder refreshes of each held position
+    #[serde(default = "default_refresh_secs")]
+    pub refresh_secs: u64,
+
+    /// Top holder share (% of circulating supply) that tightens the trailing
+    /// stop once it has risen since entry (0 = disabled)
+    #[serde(default = "default_concentration_warning_pct")]
+    pub concentration_warning_pct: f64,
+
+    /// Top holder share that also sells part of the position (0 = disabled)
+    #[serde(default = "default_concentration_critical_pct")]
+    pub concentration_critical_pct: f64,
+
+    /// Points the top holder share must have gained since entry to escalate
+    #[serde(default = "default_concentration_min_rise_pct")]
+    pub concentration_min_rise_pct: f64,
+
+    /// Trailing stop (%) set on a warning
+    #[serde(default = "default_concentration_trailing_pct")]
+    pub concentration_trailing_pct: f64,
+
+    /// Share of the remaining position sold on a critical escalation (%)
+    #[serde(default = "default_concentration_exit_pct")]
+    pub concentration_exit_pct: u8,
 }
 
 fn default_holders_to_watch() -> usize {
@@ -49,6 +86,27 @@ fn default_exit_on_any_sell() -> bool {
 fn default_pattern_tracking_mins() -> u64 {
     30
 }
+fn default_holder_list_ttl_secs() -> u64 {
+    60
+}
+fn default_refresh_secs() -> u64 {
+    15
+}
+fn default_concentration_warning_pct() -> f64 {
+    30.0
+}
+fn default_concentration_critical_pct() -> f64 {
+    45.0
+}
+fn default_concentration_min_rise_pct() -> f64 {
+    10.0
+}
+fn default_concentration_trailing_pct() -> f64 {
+    5.0
+}
+fn default_concentration_exit_pct() -> u8 {
+    50
+}
 
 impl Default for HolderWatcherConfig {
     fn default() -> Self {
@@ -58,10 +116,73 @@ impl Default for HolderWatcherConfig {
             exit_threshold_pct: default_exit_threshold_pct(),
             exit_on_any_sell: default_exit_on_any_sell(),
             pattern_tracking_mins: default_pattern_tracking_mins(),
+            holder_list_ttl_secs: default_holder_list_ttl_secs(),
+            refresh_secs: default_refresh_secs(),
+            concentration_warning_pct: default_concentration_warning_pct(),
+            concentration_critical_pct: default_concentration_critical_pct(),
+            concentration_min_rise_pct: default_concentration_min_rise_pct(),
+            concentration_trailing_pct: default_concentration_trailing_pct(),
+            concentration_exit_pct: default_concentration_exit_pct(),
+        }
+    }
+}
+
+impl HolderWatcherConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.refresh_secs == 0 {
+            return Err("refresh_secs must be positive".to_string());
+        }
+        let warning = self.concentration_warning_pct;
+        let critical = self.concentration_critical_pct;
+        if !(0.0..=100.0).contains(&warning) || !(0.0..=100.0).contains(&critical) {
+            return Err("concentration thresholds must be between 0 and 100".to_string());
+        }
+        if warning > 0.0 && critical > 0.0 && critical < warning {
+            return Err(
+                "concentration_critical_pct must not be below concentration_warning_pct"
+                    .to_string(),
+            );
+        }
+        if self.concentration_trailing_pct <= 0.0 || self.concentration_trailing_pct >= 100.0 {
+            return Err("concentration_trailing_pct must be between 0 and 100".to_string());
+        }
+        if !(1..=99).contains(&self.concentration_exit_pct) {
+            return Err("concentration_exit_pct must be between 1 and 99".to_string());
+        }
+        Ok(())
+    }
+
+    /// Escalation for a top holder share of `top_pct` that was `entry_pct`
+    /// when the position was opened
+    pub fn concentration_escalation(
+        &self,
+        entry_pct: f64,
+        top_pct: f64,
+    ) -> ConcentrationEscalation {
+        if top_pct - entry_pct < self.concentration_min_rise_pct {
+            return ConcentrationEscalation::None;
+        }
+        let reached = |threshold: f64| threshold > 0.0 && top_pct >= threshold;
+        if reached(self.concentration_critical_pct) {
+            ConcentrationEscalation::PartialExit
+        } else if reached(self.concentration_warning_pct) {
+            ConcentrationEscalation::TightenTrail
+        } else {
+            ConcentrationEscalation::None
         }
     }
 }
 
+/// Exit rule change for a held position whose holders concentrated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConcentrationEscalation {
+    None,
+    /// Trailing stop tightened
+    TightenTrail,
+    /// Trailing stop tightened and part of the position sold
+    PartialExit,
+}
+
 /// A holder being watched for a specific token
 #[derive(Debug, Clone)]
 pub struct WatchedHolder {
@@ -73,6 +194,8 @@ pub struct WatchedHolder {
     pub original_amount: u64,
     /// Original holding percentage
     pub original_pct: f64,
+    /// Rank in the latest holder list (1 = top holder)
+    pub rank: usize,
     /// Current estimated amount (updated on sells)
     pub current_amount: u64,
     /// When we started watching
@@ -103,6 +226,8 @@ pub struct HolderSellAlert {
     pub total_sold_pct: f64, // Total % sold across all sells
     pub is_first_sell: bool,
     pub urgency: AlertUrgency,
+    /// 1.0 while the holder list is fresh, lower as it ages past the TTL
+    pub confidence: f64,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -116,6 +241,27 @@ pub enum AlertUrgency {
     Medium,
 }
 
+impl AlertUrgency {
+    /// Urgency of a sell by the holder at `rank`
+    fn for_rank(rank: usize, original_pct: f64) -> Self {
+        if rank == 1 {
+            AlertUrgency::Critical
+        } else if rank <= 3 || original_pct > 10.0 {
+            AlertUrgency::High
+        } else {
+            AlertUrgency::Medium
+        }
+    }
+
+    /// One step less urgent
+    fn downgrade(self) -> Self {
+        match self {
+            AlertUrgency::Critical => AlertUrgency::High,
+            AlertUrgency::High | AlertUrgency::Medium => AlertUrgency::Medium,
+        }
+    }
+}
+
 /// Holder sell pattern tracking
 #[derive(Debug, Clone, Default)]
 pub struct HolderPattern {
@@ -149,6 +295,9 @@ pub struct HolderWatcher {
     /// All watched holder addresses (for quick lookup when trade comes in)
     watched_addresses: RwLock<HashSet<String>>,
 
+    /// When each token's holder list was last fetched
+    refreshed_at: RwLock<HashMap<String, DateTime<Utc>>>,
+
     /// Holder patterns we've learned
     patterns: RwLock<HashMap<String, HolderPattern>>,
 
@@ -162,6 +311,7 @@ impl HolderWatcher {
             config,
             watched: RwLock::new(HashMap::new()),
             watched_addresses: RwLock::new(HashSet::new()),
+            refreshed_at: RwLock::new(HashMap::new()),
             patterns: RwLock::new(HashMap::new()),
             alerts: RwLock::new(Vec::new()),
         }
@@ -175,7 +325,11 @@ impl HolderWatcher {
         let now = Utc::now();
         let mut token_holders = Vec::new();
 
-        for (address, amount, pct) in holders.into_iter().take(self.config.holders_to_watch) {
+        for (rank, (address, amount, pct)) in holders
+            .into_iter()
+            .take(self.config.holders_to_watch)
+            .enumerate()
+        {
             if pct >= self.config.min_holding_pct {
                 info!(
                     mint = %mint,
@@ -190,6 +344,7 @@ impl HolderWatcher {
                     mint: mint.to_string(),
                     original_amount: amount,
                     original_pct: pct,
+                    rank: rank + 1,
                     current_amount: amount,
                     watch_started: now,
                     sells: Vec::new(),
@@ -204,6 +359,101 @@ impl HolderWatcher {
                 "Started watching top holders"
             );
             watched.insert(mint.to_string(), token_holders);
+            self.refreshed_at
+                .write()
+                .unwrap()
+                .insert(mint.to_string(), now);
+        }
+    }
+
+    /// Merge a freshly fetched holder list into a watched token
+    ///
+    /// Holders that rose into the top ranks since entry are added (up to
+    /// `holders_to_watch`); existing ones keep their baseline so sells already
+    /// seen still count, but take their rank from the new list. A token not
+    /// yet watched is watched from this list. Returns the number of holders
+    /// added.
+    pub fn refresh_holders(&self, mint: &str, holders: Vec<(String, u64, f64)>) -> usize {
+        let mut watched = self.watched.write().unwrap();
+        let mut addresses = self.watched_addresses.write().unwrap();
+
+        let now = Utc::now();
+        let token_holders = watched.entry(mint.to_string()).or_default();
+        let mut added = 0;
+
+        for (rank, (address, amount, pct)) in holders
+            .into_iter()
+            .take(self.config.holders_to_watch)
+            .enumerate()
+        {
+            if let Some(existing) = token_holders.iter_mut().find(|h| h.address == address) {
+                existing.rank = rank + 1;
+                continue;
+            }
+            if token_holders.len() >= self.config.holders_to_watch
+                || pct < self.config.min_holding_pct
+            {
+                continue;
+            }
+            debug!(
+                mint = %mint,
+                holder = %address,
+                pct = %format!("{:.2}%", pct),
+                "Watching new top holder"
+            );
+            addresses.insert(address.clone());
+            token_holders.push(WatchedHolder {
+                address,
+                mint: mint.to_string(),
+                original_amount: amount,
+                original_pct: pct,
+                rank: rank + 1,
+                current_amount: amount,
+                watch_started: now,
+                sells: Vec::new(),
+            });
+            added += 1;
+        }
+
+        if token_holders.is_empty() {
+            watched.remove(mint);
+            return 0;
+        }
+        if added > 0 {
+            info!(mint = %mint, added, "Refreshed top holders");
+        }
+        self.refreshed_at
+            .write()
+            .unwrap()
+            .insert(mint.to_string(), now);
+        added
+    }
+
+    /// Seconds since a token's holder list was fetched
+    pub fn list_age_secs(&self, mint: &str) -> Option<u64> {
+        self.refreshed_at
+            .read()
+            .unwrap()
+            .get(mint)
+            .map(|at| (Utc::now() - *at).num_seconds().max(0) as u64)
+    }
+
+    /// Alert confidence for a token: 1.0 within the TTL, then ttl / age
+    fn list_confidence(&self, mint: &str) -> f64 {
+        let ttl = self.config.holder_list_ttl_secs.max(1) as f64;
+        match self.list_age_secs(mint) {
+            Some(age) if age as f64 > ttl => ttl / age as f64,
+            Some(_) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Urgency for a sell, one step lower when the holder list is stale
+    fn urgency(&self, base: AlertUrgency, confidence: f64) -> AlertUrgency {
+        if confidence < 1.0 {
+            base.downgrade()
+        } else {
+            base
         }
     }
 
@@ -212,6 +462,7 @@ impl HolderWatcher {
         let mut watched = self.watched.write().unwrap();
         let mut addresses = self.watched_addresses.write().unwrap();
 
+        self.refreshed_at.write().unwrap().remove(mint);
         if let Some(holders) = watched.remove(mint) {
             // Update patterns before removing
             self.update_patterns_on_exit(&holders);
@@ -294,25 +545,29 @@ impl HolderWatcher {
             100.0
         };
 
-        // Determine urgency
-        let urgency = if holder_idx == 0 {
-            AlertUrgency::Critical // TOP holder selling
-        } else if holder_idx < 3 || holder.original_pct > 10.0 {
-            AlertUrgency::High
+        // Urgency by rank once the sell crosses the configured fraction,
+        // lowered when the holder list is stale
+        let crossed =
+            self.config.exit_on_any_sell || total_sold_pct >= self.config.exit_threshold_pct;
+        let base = if crossed {
+            AlertUrgency::for_rank(holder.rank, holder.original_pct)
         } else {
             AlertUrgency::Medium
         };
+        let confidence = self.list_confidence(mint);
+        let urgency = self.urgency(base, confidence);
 
         let alert = HolderSellAlert {
             mint: mint.to_string(),
             holder: trader.to_string(),
-            holder_rank: holder_idx + 1,
+            holder_rank: holder.rank,
             original_pct: holder.original_pct,
             amount_sold: token_amount,
             pct_sold,
             total_sold_pct,
             is_first_sell: holder.sells.len() == 1,
             urgency,
+            confidence,
             timestamp: Utc::now(),
         };
 
@@ -331,8 +586,9 @@ impl HolderWatcher {
                 warn!(
                     mint = %mint,
                     holder = %trader,
-                    rank = holder_idx + 1,
+                    rank = holder.rank,
                     pct_sold = %format!("{:.1}%", pct_sold),
+                    confidence = %format!("{:.2}", confidence),
                     "HIGH: Major holder selling"
                 );
             }
@@ -356,8 +612,9 @@ impl HolderWatcher {
     pub fn should_exit(&self, mint: &str) -> Option<HolderSellAlert> {
         let watched = self.watched.read().unwrap();
         let holders = watched.get(mint)?;
+        let confidence = self.list_confidence(mint);
 
-        for (idx, holder) in holders.iter().enumerate() {
+        for holder in holders {
             if holder.sells.is_empty() {
                 continue;
             }
@@ -371,20 +628,22 @@ impl HolderWatcher {
 
             // Exit if configured to exit on any sell
             if self.config.exit_on_any_sell {
+                let base = if holder.rank == 1 {
+                    AlertUrgency::Critical
+                } else {
+                    AlertUrgency::High
+                };
                 return Some(HolderSellAlert {
                     mint: mint.to_string(),
                     holder: holder.address.clone(),
-                    holder_rank: idx + 1,
+                    holder_rank: holder.rank,
                     original_pct: holder.original_pct,
                     amount_sold: total_sold,
                     pct_sold: total_sold_pct,
                     total_sold_pct,
                     is_first_sell: false,
-                    urgency: if idx == 0 {
-                        AlertUrgency::Critical
-                    } else {
-                        AlertUrgency::High
-                    },
+                    urgency: self.urgency(base, confidence),
+                    confidence,
                     timestamp: holder
                         .sells
                         .last()
@@ -398,13 +657,14 @@ impl HolderWatcher {
                 return Some(HolderSellAlert {
                     mint: mint.to_string(),
                     holder: holder.address.clone(),
-                    holder_rank: idx + 1,
+                    holder_rank: holder.rank,
                     original_pct: holder.original_pct,
                     amount_sold: total_sold,
                     pct_sold: total_sold_pct,
                     total_sold_pct,
                     is_first_sell: false,
-                    urgency: AlertUrgency::High,
+                    urgency: self.urgency(AlertUrgency::High, confidence),
+                    confidence,
                     timestamp: holder
                         .sells
                         .last()
@@ -417,6 +677,31 @@ impl HolderWatcher {
         None
     }
 
+    /// Summarize sells by a watched token's holders since entry
+    pub fn holder_changes(&self, mint: &str) -> Option<HolderChanges> {
+        let watched = self.watched.read().unwrap();
+        let holders = watched.get(mint)?;
+
+        let original: u64 = holders.iter().map(|h| h.original_amount).sum();
+        let sold: u64 = holders
+            .iter()
+            .flat_map(|h| h.sells.iter())
+            .map(|s| s.amount_sold)
+            .sum();
+        Some(HolderChanges {
+            top_holder_pct: holders
+                .iter()
+                .find(|h| h.rank == 1)
+                .map_or(0.0, |h| h.original_pct),
+            holders_selling: holders.iter().filter(|h| !h.sells.is_empty()).count() as u32,
+            sold_pct: if original > 0 {
+                sold as f64 / original as f64 * 100.0
+            } else {
+                0.0
+            },
+        })
+    }
+
     /// Get and clear pending alerts
     pub fn take_alerts(&self) -> Vec<HolderSellAlert> {
         std::mem::take(&mut *self.alerts.write().unwrap())
@@ -518,6 +803,17 @@ impl HolderWatcher {
     }
 }
 
+/// Holder activity on one watched token
+#[derive(Debug, Clone, Default)]
+pub struct HolderChanges {
+    /// Supply % held by the top holder when the list was fetched
+    pub top_holder_pct: f64,
+    /// Watched holders that have sold
+    pub holders_selling: u32,
+    /// Share of the watched holders' tokens sold
+    pub sold_pct: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct HolderWatcherStats {
     pub tokens_watched: usize,
@@ -560,6 +856,26 @@ mod tests {
         assert!((alert.pct_sold - 50.0).abs() < 0.1);
     }
 
+    #[test]
+    fn test_holder_changes() {
+        let watcher = HolderWatcher::new(HolderWatcherConfig::default());
+        assert!(watcher.holder_changes("token1").is_none());
+
+        watcher.watch_token(
+            "token1",
+            vec![
+                ("holder1".to_string(), 1000000, 50.0),
+                ("holder2".to_string(), 1000000, 50.0),
+            ],
+        );
+        watcher.process_sell("holder2", "token1", 500000, 5.0, "sig1");
+
+        let changes = watcher.holder_changes("token1").unwrap();
+        assert_eq!(changes.top_holder_pct, 50.0);
+        assert_eq!(changes.holders_selling, 1);
+        assert!((changes.sold_pct - 25.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_should_exit_on_any_sell() {
         let config = HolderWatcherConfig {
@@ -579,6 +895,141 @@ mod tests {
         assert!(watcher.should_exit("token1").is_some());
     }
 
+    #[test]
+    fn test_refresh_holders() {
+        let watcher = HolderWatcher::new(HolderWatcherConfig::default());
+
+        // Entered before holders were known
+        watcher.refresh_holders("token1", vec![("holder1".to_string(), 1000000, 50.0)]);
+        assert!(watcher.is_watched("holder1"));
+        watcher.process_sell("holder1", "token1", 100000, 1.0, "sig1");
+
+        // Refresh adds the new top holder and keeps holder1's sell history
+        let added = watcher.refresh_holders(
+            "token1",
+            vec![
+                ("holder1".to_string(), 900000, 45.0),
+                ("holder2".to_string(), 400000, 20.0),
+                ("dust".to_string(), 1000, 0.05),
+            ],
+        );
+        assert_eq!(added, 1);
+        assert!(watcher.is_watched("holder2"));
+        assert!(!watcher.is_watched("dust"));
+        assert!(watcher.should_exit("token1").is_some());
+        assert_eq!(watcher.stats().total_holders_watched, 2);
+    }
+
+    #[test]
+    fn test_rank_follows_refresh() {
+        let watcher = HolderWatcher::new(HolderWatcherConfig::default());
+        watcher.watch_token(
+            "token1",
+            vec![
+                ("holder1".to_string(), 1000000, 50.0),
+                ("holder2".to_string(), 500000, 25.0),
+            ],
+        );
+
+        // holder1 sold down; holder2 is now the top holder
+        watcher.refresh_holders(
+            "token1",
+            vec![
+                ("holder2".to_string(), 500000, 40.0),
+                ("holder1".to_string(), 200000, 15.0),
+            ],
+        );
+        let alert = watcher
+            .process_sell("holder2", "token1", 100000, 1.0, "sig1")
+            .unwrap();
+        assert_eq!(alert.holder_rank, 1);
+        assert_eq!(alert.urgency, AlertUrgency::Critical);
+    }
+
+    #[test]
+    fn test_sell_below_threshold() {
+        let config = HolderWatcherConfig {
+            exit_on_any_sell: false,
+            exit_threshold_pct: 10.0,
+            ..Default::default()
+        };
+        let watcher = HolderWatcher::new(config);
+        watcher.watch_token("token1", vec![("holder1".to_string(), 1000000, 50.0)]);
+
+        // 5% of the position: noted, but no exit
+        let alert = watcher
+            .process_sell("holder1", "token1", 50000, 0.5, "sig1")
+            .unwrap();
+        assert_eq!(alert.urgency, AlertUrgency::Medium);
+        assert!(watcher.should_exit("token1").is_none());
+
+        // Another 10% crosses the threshold
+        let alert = watcher
+            .process_sell("holder1", "token1", 100000, 1.0, "sig2")
+            .unwrap();
+        assert_eq!(alert.urgency, AlertUrgency::Critical);
+        assert!(watcher.should_exit("token1").is_some());
+    }
+
+    #[test]
+    fn test_stale_list_lowers_confidence() {
+        let watcher = HolderWatcher::new(HolderWatcherConfig::default());
+        watcher.watch_token("token1", vec![("holder1".to_string(), 1000000, 50.0)]);
+
+        // Last fetched four TTLs ago
+        watcher.refreshed_at.write().unwrap().insert(
+            "token1".to_string(),
+            Utc::now() - chrono::Duration::seconds(240),
+        );
+        let alert = watcher
+            .process_sell("holder1", "token1", 500000, 5.0, "sig1")
+            .unwrap();
+        assert_eq!(alert.urgency, AlertUrgency::High);
+        assert!((alert.confidence - 0.25).abs() < 0.01);
+
+        // A fresh list restores full confidence
+        watcher.refresh_holders("token1", vec![("holder1".to_string(), 500000, 40.0)]);
+        let alert = watcher.should_exit("token1").unwrap();
+        assert_eq!(alert.urgency, AlertUrgency::Critical);
+        assert_eq!(alert.confidence, 1.0);
+    }
+
+    #[test]
+    fn test_concentration_escalation() {
+        let config = HolderWatcherConfig::default();
+        assert!(config.validate().is_ok());
+
+        // Entered at 12%: escalates as the top holder accumulates
+        assert_eq!(
+            config.concentration_escalation(12.0, 20.0),
+            ConcentrationEscalation::None
+        );
+        assert_eq!(
+            config.concentration_escalation(12.0, 32.0),
+            ConcentrationEscalation::TightenTrail
+        );
+        assert_eq!(
+            config.concentration_escalation(12.0, 48.0),
+            ConcentrationEscalation::PartialExit
+        );
+
+        // Already concentrated at entry and barely moved
+        assert_eq!(
+            config.concentration_escalation(44.0, 50.0),
+            ConcentrationEscalation::None
+        );
+
+        let disabled = HolderWatcherConfig {
+            concentration_warning_pct: 0.0,
+            concentration_critical_pct: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            disabled.concentration_escalation(0.0, 90.0),
+            ConcentrationEscalation::None
+        );
+    }
+
     #[test]
     fn test_pattern_tracking() {
         let watcher = HolderWatcher::new(HolderWatcherConfig::default());
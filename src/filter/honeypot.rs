@@ -0,0 +1,303 @@
+//! Honeypot round-trip check
+//!
+//! Simulates a tiny buy followed by an immediate sell before any capital is
+//! committed. The curve math runs in microseconds and rejects tokens whose
+//! curve can't pay a buyer back (completed or drained curves, broken reserve
+//! data). When `trading.simulate_before_send` is on, the sell leg is also
+//! simulated on-chain from the creator's wallet, which catches sells the
+//! program itself refuses.
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::transaction::VersionedTransaction;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::config::{Config, HoneypotConfig};
+use crate::error::{Error, Result};
+use crate::pump::price::{self, DEFAULT_TOKEN_DECIMALS};
+use crate::pump::program::FEE_BASIS_POINTS;
+use crate::pump::BondingCurve;
+use crate::trading::simulation;
+use crate::trading::PumpPortalTrader;
+
+/// Slippage for the simulated sell (only the program's verdict matters)
+const SIMULATED_SELL_SLIPPAGE_PCT: u32 = 50;
+
+/// Outcome of a simulated buy-then-sell
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundTrip {
+    /// SOL spent on the probe buy (lamports, fee included)
+    pub sol_in: u64,
+    /// Tokens received from the probe buy (raw units)
+    pub tokens: u64,
+    /// SOL returned by selling those tokens (lamports, after fee)
+    pub sol_out: u64,
+    /// Total round-trip loss as a percentage of `sol_in`
+    pub loss_pct: f64,
+    /// Loss beyond the protocol fee on both legs
+    pub excess_loss_pct: f64,
+    /// Why the round trip could not complete, if it couldn't
+    pub failure: Option<String>,
+}
+
+impl RoundTrip {
+    /// A round trip that returns nothing
+    fn failed(sol_in: u64, reason: impl Into<String>) -> Self {
+        Self {
+            sol_in,
+            tokens: 0,
+            sol_out: 0,
+            loss_pct: 100.0,
+            excess_loss_pct: 100.0 - expected_fee_loss_pct(),
+            failure: Some(reason.into()),
+        }
+    }
+
+    /// True if the token should be treated as a honeypot
+    pub fn is_honeypot(&self, max_excess_loss_pct: f64) -> bool {
+        self.failure.is_some() || self.excess_loss_pct > max_excess_loss_pct
+    }
+
+    /// Short human-readable summary
+    pub fn describe(&self) -> String {
+        match &self.failure {
+            Some(reason) => format!("round trip failed: {}", reason),
+            None => format!(
+                "round trip loses {:.2}% ({:.2}% beyond fees)",
+                self.loss_pct, self.excess_loss_pct
+            ),
+        }
+    }
+}
+
+/// Round-trip loss explained by the protocol fee on the buy and the sell
+///
+/// The buy fee is charged on top of the SOL the curve receives, the sell fee
+/// is taken out of the proceeds.
+pub fn expected_fee_loss_pct() -> f64 {
+    let fee = FEE_BASIS_POINTS as f64 / 10_000.0;
+    (1.0 - (1.0 - fee) / (1.0 + fee)) * 100.0
+}
+
+/// Build curve state from PumpPortal event reserves
+///
+/// PumpPortal usually reports reserves in SOL and whole tokens, but some
+/// payloads carry raw lamports; both are normalised to raw units.
+pub fn curve_from_event(v_sol: f64, v_tokens: f64) -> BondingCurve {
+    let virtual_sol = if v_sol < 1000.0 {
+        price::sol_to_lamports(v_sol)
+    } else {
+        v_sol as u64
+    };
+    let virtual_tokens = if v_tokens < 1e10 {
+        price::human_to_tokens(v_tokens, DEFAULT_TOKEN_DECIMALS)
+    } else {
+        v_tokens as u64
+    };
+    BondingCurve::from_virtual_reserves(virtual_sol, virtual_tokens)
+}
+
+/// Buy `sol_in` lamports of tokens and sell them straight back, on paper
+pub fn simulate_round_trip(curve: &BondingCurve, sol_in: u64) -> RoundTrip {
+    if curve.complete {
+        return RoundTrip::failed(sol_in, "bonding curve complete");
+    }
+
+    let buy = match price::quote_buy(curve, sol_in) {
+        Ok(buy) => buy,
+        Err(_) => return RoundTrip::failed(sol_in, "invalid curve reserves"),
+    };
+    let (tokens, net_in) = (buy.tokens_out, sol_in - buy.fee_lamports);
+    if tokens == 0 {
+        return RoundTrip::failed(sol_in, "buy returns no tokens");
+    }
+    if tokens > curve.real_token_reserves {
+        return RoundTrip::failed(sol_in, "curve cannot fill the buy");
+    }
+
+    let mut after = curve.clone();
+    after.virtual_sol_reserves = after.virtual_sol_reserves.saturating_add(net_in);
+    after.virtual_token_reserves -= tokens;
+    after.real_sol_reserves = after.real_sol_reserves.saturating_add(net_in);
+    after.real_token_reserves -= tokens;
+
+    let sell = match price::quote_sell(&after, tokens) {
+        Ok(sell) => sell,
+        Err(_) => return RoundTrip::failed(sol_in, "invalid curve reserves after buy"),
+    };
+    // The program can only pay out SOL it actually holds
+    let gross_out = (sell.sol_out + sell.fee_lamports).min(after.real_sol_reserves);
+    let sol_out = gross_out - gross_out * FEE_BASIS_POINTS / 10_000;
+
+    let loss_pct = sol_in.saturating_sub(sol_out) as f64 / sol_in as f64 * 100.0;
+    RoundTrip {
+        sol_in,
+        tokens,
+        sol_out,
+        loss_pct,
+        excess_loss_pct: loss_pct - expected_fee_loss_pct(),
+        failure: None,
+    }
+}
+
+/// Runs the configured round-trip check for new tokens
+pub struct HoneypotChecker {
+    config: HoneypotConfig,
+    simulate_sells: bool,
+    trader: Option<Arc<PumpPortalTrader>>,
+    rpc_client: Arc<RpcClient>,
+}
+
+impl HoneypotChecker {
+    pub fn new(
+        config: &Config,
+        trader: Option<Arc<PumpPortalTrader>>,
+        rpc_client: Arc<RpcClient>,
+    ) -> Self {
+        Self {
+            config: config.filters.honeypot.clone(),
+            simulate_sells: config.trading.simulate_before_send,
+            trader,
+            rpc_client,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Check a token, optionally confirming the sell leg on-chain
+    ///
+    /// The returned `failure` is set when the token should be rejected.
+    /// `creator_tokens` is the creator's holding in whole tokens; the on-chain
+    /// sell is simulated from their wallet and skipped when they hold nothing.
+    /// RPC or API errors leave the curve-math result unchanged.
+    pub async fn check(
+        &self,
+        mint: &str,
+        creator: &str,
+        creator_tokens: f64,
+        curve: &BondingCurve,
+    ) -> RoundTrip {
+        let sol_in = price::sol_to_lamports(self.config.probe_sol);
+        let mut trip = simulate_round_trip(curve, sol_in);
+        if trip.failure.is_none() && trip.is_honeypot(self.config.max_round_trip_loss_pct) {
+            trip.failure = Some(format!(
+                "loses {:.2}% beyond fees (max {:.2}%)",
+                trip.excess_loss_pct, self.config.max_round_trip_loss_pct
+            ));
+        }
+        if trip.failure.is_some() || !self.simulate_sells || creator_tokens <= 0.0 {
+            return trip;
+        }
+        let Some(trader) = &self.trader else {
+            return trip;
+        };
+
+        let amount =
+            price::tokens_to_human(trip.tokens, DEFAULT_TOKEN_DECIMALS).min(creator_tokens);
+        match self.simulate_sell(trader, mint, creator, amount).await {
+            Ok(None) => trip,
+            Ok(Some(error)) => RoundTrip::failed(sol_in, format!("sell simulation: {}", error)),
+            Err(e) => {
+                debug!("Sell simulation for {} inconclusive: {}", mint, e);
+                trip
+            }
+        }
+    }
+
+    /// Returns the program error if selling from `holder` would fail
+    async fn simulate_sell(
+        &self,
+        trader: &PumpPortalTrader,
+        mint: &str,
+        holder: &str,
+        amount: f64,
+    ) -> Result<Option<String>> {
+        let tx_bytes = trader
+            .get_sell_transaction(
+                mint,
+                &format!("{:.6}", amount),
+                SIMULATED_SELL_SLIPPAGE_PCT,
+                0.0,
+                holder,
+            )
+            .await?;
+        let tx: VersionedTransaction = bincode::deserialize(&tx_bytes).map_err(|e| {
+            Error::Deserialization(format!("Failed to deserialize transaction: {}", e))
+        })?;
+
+        let result = simulation::simulate_unsigned(&self.rpc_client, &tx).await?;
+        Ok(result.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROBE: u64 = 1_000_000; // 0.001 SOL
+
+    #[test]
+    fn test_fresh_curve_loses_only_fees() {
+        let curve = curve_from_event(30.0, 1_073_000_000.0);
+        let trip = simulate_round_trip(&curve, PROBE);
+
+        assert!(trip.failure.is_none());
+        assert!(trip.tokens > 0);
+        assert!((trip.loss_pct - expected_fee_loss_pct()).abs() < 0.01);
+        assert!(!trip.is_honeypot(5.0));
+    }
+
+    #[test]
+    fn test_event_units_normalised() {
+        let ui = curve_from_event(30.0, 1_073_000_000.0);
+        let raw = curve_from_event(30_000_000_000.0, 1_073_000_000_000_000.0);
+
+        assert_eq!(ui.virtual_sol_reserves, raw.virtual_sol_reserves);
+        assert_eq!(ui.virtual_token_reserves, raw.virtual_token_reserves);
+        assert_eq!(ui.real_sol_reserves, 0);
+        assert_eq!(ui.real_token_reserves, 793_100_000_000_000);
+    }
+
+    #[test]
+    fn test_complete_curve_is_honeypot() {
+        let curve = BondingCurve::new_for_test(
+            85_000_000_000,
+            279_900_000_000_000,
+            55_000_000_000,
+            0,
+            1_000_000_000_000_000,
+            true,
+        );
+        let trip = simulate_round_trip(&curve, PROBE);
+
+        assert!(trip.failure.is_some());
+        assert!(trip.is_honeypot(5.0));
+    }
+
+    #[test]
+    fn test_unfillable_buy_is_honeypot() {
+        // Virtual reserves claim liquidity the curve doesn't actually hold
+        let curve = BondingCurve::new_for_test(
+            30_000_000_000,
+            1_073_000_000_000_000,
+            0,
+            0,
+            1_000_000_000_000_000,
+            false,
+        );
+        let trip = simulate_round_trip(&curve, PROBE);
+
+        assert_eq!(trip.failure.as_deref(), Some("curve cannot fill the buy"));
+    }
+
+    #[test]
+    fn test_excess_loss_threshold() {
+        let mut trip = simulate_round_trip(&curve_from_event(30.0, 1_073_000_000.0), PROBE);
+        trip.excess_loss_pct = 6.0;
+
+        assert!(trip.is_honeypot(5.0));
+        assert!(!trip.is_honeypot(10.0));
+    }
+}
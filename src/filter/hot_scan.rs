@@ -0,0 +1,78 @@
+//! On-chain check of `snipe hot-scan` candidates
+//!
+//! DexScreener's m5 change, buy/sell ratio and liquidity are easy to inflate
+//! with wash volume. Before an auto-buy, the candidate's mint info
+//! (authorities and Token-2022 extensions), top holders and creator history
+//! are fetched from Helius and scored by a reduced adaptive filter, with the
+//! DexScreener numbers standing in for the trade feed as order flow.
+//! Candidates below `adaptive_filter.hot_scan.min_recommendation` are skipped.
+
+use std::sync::Arc;
+
+use crate::dexscreener::HotToken;
+use crate::error::Result;
+use crate::filter::adaptive::{AdaptiveFilter, AdaptiveFilterConfig};
+use crate::filter::enrichment::EnrichmentService;
+use crate::filter::helius::HeliusClient;
+use crate::filter::scoring::{Recommendation, ScoringResult};
+use crate::filter::signals::{
+    DistributionSignalProvider, MetadataSignalProvider, OrderFlowSignalProvider,
+    SmartMoneySignalProvider, WalletBehaviorSignalProvider,
+};
+use crate::filter::smart_money::WalletProfiler;
+
+/// Scores hot-scan candidates on on-chain data plus DexScreener order flow
+pub struct HotScanVerifier {
+    filter: AdaptiveFilter,
+    /// None without a Helius RPC URL: only the DexScreener data is scored
+    enrichment: Option<EnrichmentService>,
+    min_recommendation: Recommendation,
+}
+
+impl HotScanVerifier {
+    pub async fn new(
+        config: &AdaptiveFilterConfig,
+        helius: Option<Arc<HeliusClient>>,
+        profiler: Option<Arc<WalletProfiler>>,
+    ) -> Result<Self> {
+        // The running bot owns the cache snapshot
+        let mut config = config.clone();
+        config.cache.persist_path = None;
+
+        let mut filter = AdaptiveFilter::new(config.clone()).await?;
+        let cache = filter.cache().clone();
+        filter.register_provider(Arc::new(MetadataSignalProvider::new()));
+        filter.register_provider(Arc::new(WalletBehaviorSignalProvider::new(cache.clone())));
+        filter.register_provider(Arc::new(OrderFlowSignalProvider::new(cache.clone())));
+        filter.register_provider(Arc::new(DistributionSignalProvider::new(cache.clone())));
+        if let Some(profiler) = profiler {
+            filter.register_provider(Arc::new(SmartMoneySignalProvider::new(profiler)));
+        }
+
+        let enrichment =
+            helius.map(|helius| EnrichmentService::new(helius, cache, config.enrichment.clone()));
+        Ok(Self {
+            filter,
+            enrichment,
+            min_recommendation: config.hot_scan.min_recommendation,
+        })
+    }
+
+    /// Fetch mint info, holders and creator history for `token`, then score it
+    pub async fn score(&self, token: &HotToken, creator: &str) -> ScoringResult {
+        let context = token.signal_context(creator);
+        if let Some(ref enrichment) = self.enrichment {
+            enrichment.enrich_token(&context).await;
+        }
+        self.filter.score_full(&context).await
+    }
+
+    /// Whether `result` is at least the configured recommendation
+    pub fn passes(&self, result: &ScoringResult) -> bool {
+        result.recommendation.at_least(self.min_recommendation)
+    }
+
+    pub fn min_recommendation(&self) -> Recommendation {
+        self.min_recommendation
+    }
+}
@@ -4,16 +4,28 @@
 //! When a kill-switch fires, we EXIT immediately - no debate.
 //!
 //! Kill-switch triggers:
-//! - Deployer sells ANY amount
+//! - Deployer sells ANY amount (from the creator wallet or any wallet the
+//!   creator moved its launch allocation to)
 //! - Top holder sells (Critical urgency)
-//! - Bundled wallets selling together (future)
+//! - Bundled wallets selling together (from `BundledDetector`)
+//! - Bonding-curve SOL draining in one burst (Critical: panic sell)
 //! - Sniper wallets exiting before graduation (future)
 
+use chrono::Duration;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
 use tracing::{info, warn};
 
+use crate::filter::bundled_detection::BundledDetector;
+use crate::filter::helius::SplTransfer;
 use crate::filter::holder_watcher::{AlertUrgency, HolderWatcher, HolderWatcherConfig};
+use crate::filter::types::TokenHolderInfo;
+use crate::trading::transaction::derive_bonding_curve;
 
 /// Kill-switch configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +49,20 @@ pub struct KillSwitchConfig {
     /// Window in seconds for bundled sell detection
     #[serde(default = "default_bundled_sell_window_secs")]
     pub bundled_sell_window_secs: u64,
+
+    /// Wallets that received tokens from the creator this soon after launch
+    /// count as the deployer
+    #[serde(default = "default_creator_transfer_window_secs")]
+    pub creator_transfer_window_secs: u64,
+
+    /// Exit if the curve's SOL drops by more than this percentage within
+    /// `liquidity_drain_window_secs` (0 = disabled)
+    #[serde(default = "default_liquidity_drain_pct")]
+    pub liquidity_drain_pct: f64,
+
+    /// Window in seconds for liquidity drain detection
+    #[serde(default = "default_liquidity_drain_window_secs")]
+    pub liquidity_drain_window_secs: u64,
 }
 
 fn default_enabled() -> bool { true }
@@ -44,6 +70,9 @@ fn default_deployer_sell_any() -> bool { true }
 fn default_top_holder_sell() -> bool { true }
 fn default_bundled_sell_count() -> u32 { 2 }
 fn default_bundled_sell_window_secs() -> u64 { 30 }
+fn default_creator_transfer_window_secs() -> u64 { 120 }
+fn default_liquidity_drain_pct() -> f64 { 40.0 }
+fn default_liquidity_drain_window_secs() -> u64 { 5 }
 
 impl Default for KillSwitchConfig {
     fn default() -> Self {
@@ -53,6 +82,9 @@ impl Default for KillSwitchConfig {
             top_holder_sell: default_top_holder_sell(),
             bundled_sell_count: default_bundled_sell_count(),
             bundled_sell_window_secs: default_bundled_sell_window_secs(),
+            creator_transfer_window_secs: default_creator_transfer_window_secs(),
+            liquidity_drain_pct: default_liquidity_drain_pct(),
+            liquidity_drain_window_secs: default_liquidity_drain_window_secs(),
         }
     }
 }
@@ -60,6 +92,8 @@ impl Default for KillSwitchConfig {
 /// Kill-switch urgency level
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum KillSwitchUrgency {
+    /// Liquidity is leaving - sign locally at panic slippage, no retries
+    Critical,
     /// Exit NOW - no delay
     Immediate,
     /// Exit within 1-2 seconds
@@ -73,6 +107,9 @@ pub enum KillSwitchUrgency {
 pub enum KillSwitchType {
     /// Deployer/creator sold tokens
     DeployerSell {
+        seller: String,
+        /// Sold from a wallet the creator moved tokens to
+        linked_wallet: bool,
         amount_tokens: u64,
         amount_pct: f64,
     },
@@ -82,10 +119,16 @@ pub enum KillSwitchType {
         rank: usize,
         amount_pct: f64,
     },
-    /// Bundled wallets selling together (future)
+    /// Bundled wallets selling together
     BundledWalletsSelling {
         wallets_selling: u32,
-        total_pct: f64,
+        total_sell_sol: f64,
+    },
+    /// Bonding-curve SOL dropped sharply within the drain window
+    LiquidityDrain {
+        from_sol: f64,
+        to_sol: f64,
+        drop_pct: f64,
     },
     /// Sniper wallets exiting before graduation (future)
     SniperExit {
@@ -116,13 +159,84 @@ pub enum KillSwitchDecision {
 pub struct DeployerTracker {
     /// mint -> creator address
     deployers: DashMap<String, String>,
+    /// mint -> wallets holding the creator's launch allocation
+    linked: DashMap<String, HashSet<String>>,
 }
 
 impl DeployerTracker {
     pub fn new() -> Self {
         Self {
             deployers: DashMap::new(),
+            linked: DashMap::new(),
+        }
+    }
+
+    /// Wallets the creator sent `mint` tokens to within `window_secs` of
+    /// launch (the creator's first transfer of the mint, i.e. the dev buy)
+    ///
+    /// Swaps are skipped so the bonding curve never counts as a recipient.
+    pub fn allocation_recipients(
+        mint: &str,
+        creator: &str,
+        transfers: &[SplTransfer],
+        window_secs: u64,
+    ) -> Vec<String> {
+        let launch = transfers
+            .iter()
+            .filter(|t| t.mint == mint)
+            .filter_map(|t| t.timestamp)
+            .min();
+        let Some(launch) = launch else {
+            return Vec::new();
+        };
+        let cutoff = launch + Duration::seconds(window_secs as i64);
+
+        let mut recipients = Vec::new();
+        for t in transfers {
+            let in_window = t.timestamp.is_some_and(|ts| ts <= cutoff);
+            if t.mint == mint
+                && t.from == creator
+                && t.to != creator
+                && t.tx_type.as_deref() == Some("TRANSFER")
+                && in_window
+                && !recipients.contains(&t.to)
+            {
+                recipients.push(t.to.clone());
+            }
+        }
+        recipients
+    }
+
+    /// Treat `wallets` as holding the deployer's allocation
+    ///
+    /// Returns how many were not linked before.
+    pub fn link_wallets(&self, mint: &str, wallets: &[String]) -> usize {
+        if !self.deployers.contains_key(mint) {
+            return 0;
         }
+        let mut linked = self.linked.entry(mint.to_string()).or_default();
+        wallets
+            .iter()
+            .filter(|w| linked.insert(w.to_string()))
+            .count()
+    }
+
+    /// Check if a wallet is the deployer or holds its allocation
+    pub fn is_creator_wallet(&self, mint: &str, wallet: &str) -> bool {
+        self.is_deployer(mint, wallet)
+            || self
+                .linked
+                .get(mint)
+                .map(|l| l.contains(wallet))
+                .unwrap_or(false)
+    }
+
+    /// Wallets linked to the deployer of a token
+    pub fn linked_wallets(&self, mint: &str) -> Vec<String> {
+        self.linked
+            .get(mint)
+            .map(|l| l.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
     /// Track the deployer for a token
@@ -134,6 +248,7 @@ impl DeployerTracker {
     /// Stop tracking a token
     pub fn untrack(&self, mint: &str) {
         self.deployers.remove(mint);
+        self.linked.remove(mint);
     }
 
     /// Check if a wallet is the deployer for a token
@@ -156,11 +271,28 @@ impl Default for DeployerTracker {
     }
 }
 
+/// Drop the bonding curve from a holder list - it holds most of the supply
+/// and never sells through the trade feed
+fn without_curve(mint: &str, holders: Vec<(String, u64, f64)>) -> Vec<(String, u64, f64)> {
+    let curve = Pubkey::from_str(mint)
+        .ok()
+        .and_then(|mint| derive_bonding_curve(&mint).ok())
+        .map(|(pda, _)| pda.to_string());
+    holders
+        .into_iter()
+        .filter(|(address, _, _)| Some(address) != curve.as_ref())
+        .collect()
+}
+
 /// Kill-switch evaluator - checks trades for kill-switch conditions
 pub struct KillSwitchEvaluator {
     config: KillSwitchConfig,
     deployer_tracker: DeployerTracker,
     holder_watcher: HolderWatcher,
+    /// Bundles found in launch windows (sell thresholds come from its config)
+    bundled_detector: Option<Arc<BundledDetector>>,
+    /// mint -> recent curve SOL readings of watched positions (oldest first)
+    liquidity: DashMap<String, VecDeque<(Instant, f64)>>,
 }
 
 impl KillSwitchEvaluator {
@@ -169,21 +301,71 @@ impl KillSwitchEvaluator {
             config,
             deployer_tracker: DeployerTracker::new(),
             holder_watcher: HolderWatcher::new(holder_watcher_config),
+            bundled_detector: None,
+            liquidity: DashMap::new(),
         }
     }
 
+    /// Exit when wallets of a detected bundle start selling together
+    pub fn with_bundled_detector(mut self, detector: Arc<BundledDetector>) -> Self {
+        self.bundled_detector = Some(detector);
+        self
+    }
+
     /// Track a new position - start monitoring deployer and holders
     pub fn watch_position(&self, mint: &str, creator: &str, holders: Vec<(String, u64, f64)>) {
         // Track deployer
         self.deployer_tracker.track(mint, creator);
 
         // Track top holders
-        self.holder_watcher.watch_token(mint, holders);
+        self.holder_watcher
+            .watch_token(mint, without_curve(mint, holders));
+    }
+
+    /// Merge a fresh holder list (from enrichment) into a watched position
+    ///
+    /// Ignored once the position is closed. Returns the number of holders
+    /// added to the watch list.
+    pub fn refresh_holders(&self, mint: &str, holders: &[TokenHolderInfo]) -> usize {
+        if self.deployer_tracker.get_deployer(mint).is_none() {
+            return 0;
+        }
+        let holders = holders
+            .iter()
+            .map(|h| (h.address.clone(), h.amount, h.percentage))
+            .collect();
+        self.holder_watcher
+            .refresh_holders(mint, without_curve(mint, holders))
+    }
+
+    /// Add the wallets the creator moved its allocation to (from the
+    /// creator's token transfers) to the position's deployer watch set
+    pub fn watch_creator_transfers(&self, mint: &str, transfers: &[SplTransfer]) -> usize {
+        let Some(creator) = self.deployer_tracker.get_deployer(mint) else {
+            return 0;
+        };
+        let recipients = DeployerTracker::allocation_recipients(
+            mint,
+            &creator,
+            transfers,
+            self.config.creator_transfer_window_secs,
+        );
+        let added = self.deployer_tracker.link_wallets(mint, &recipients);
+        if added > 0 {
+            info!(
+                mint = %mint,
+                creator = %creator,
+                wallets = ?recipients,
+                "Watching wallets holding the creator allocation"
+            );
+        }
+        added
     }
 
     /// Stop watching a position (we exited)
     pub fn unwatch_position(&self, mint: &str) {
         self.deployer_tracker.untrack(mint);
+        self.liquidity.remove(mint);
         self.holder_watcher.unwatch_token(mint);
     }
 
@@ -201,22 +383,34 @@ impl KillSwitchEvaluator {
             return KillSwitchDecision::Continue;
         }
 
-        // Check 1: Is deployer selling?
-        if self.config.deployer_sell_any && self.deployer_tracker.is_deployer(mint, trader) {
+        // Check 1: Is deployer (or a wallet holding its allocation) selling?
+        if self.config.deployer_sell_any && self.deployer_tracker.is_creator_wallet(mint, trader) {
+            let linked_wallet = !self.deployer_tracker.is_deployer(mint, trader);
             warn!(
                 mint = %mint,
                 trader = %trader,
+                linked_wallet = %linked_wallet,
                 amount = %token_amount,
                 "KILL-SWITCH: DEPLOYER SELLING - EXIT NOW"
             );
+            let reason = if linked_wallet {
+                format!(
+                    "Deployer-funded wallet {} sold {} tokens",
+                    trader, token_amount
+                )
+            } else {
+                format!("Deployer {} sold {} tokens", trader, token_amount)
+            };
             return KillSwitchDecision::Exit(KillSwitchAlert {
                 alert_type: KillSwitchType::DeployerSell {
+                    seller: trader.to_string(),
+                    linked_wallet,
                     amount_tokens: token_amount,
                     amount_pct: 0.0, // TODO: Calculate from total supply
                 },
                 mint: mint.to_string(),
                 urgency: KillSwitchUrgency::Immediate,
-                reason: format!("Deployer {} sold {} tokens", trader, token_amount),
+                reason,
                 auto_exit: true,
             });
         }
@@ -256,12 +450,94 @@ impl KillSwitchEvaluator {
             }
         }
 
-        // TODO: Check 3: Bundled wallets selling together
+        // Check 3: Are bundled wallets selling together?
+        if let Some(ref detector) = self.bundled_detector {
+            if let Some(alert) = detector.record_sell(mint, trader, sol_amount, signature) {
+                warn!(
+                    mint = %mint,
+                    wallets_selling = %alert.wallets_selling,
+                    "KILL-SWITCH: BUNDLE DISTRIBUTING - EXIT NOW"
+                );
+                return KillSwitchDecision::Exit(KillSwitchAlert {
+                    alert_type: KillSwitchType::BundledWalletsSelling {
+                        wallets_selling: alert.wallets_selling,
+                        total_sell_sol: alert.total_sell_sol,
+                    },
+                    mint: mint.to_string(),
+                    urgency: KillSwitchUrgency::Immediate,
+                    reason: format!(
+                        "{} bundled wallets sold {:.3} SOL within {}s",
+                        alert.wallets_selling, alert.total_sell_sol, alert.window_secs
+                    ),
+                    auto_exit: true,
+                });
+            }
+        }
+
         // TODO: Check 4: Sniper exit before graduation
 
         KillSwitchDecision::Continue
     }
 
+    /// Record the curve's SOL reserve from a trade on a watched position
+    ///
+    /// Exits when it fell more than `liquidity_drain_pct` below the highest
+    /// reading of the last `liquidity_drain_window_secs`.
+    pub fn record_liquidity(&self, mint: &str, v_sol_in_curve: f64) -> KillSwitchDecision {
+        self.record_liquidity_at(mint, v_sol_in_curve, Instant::now())
+    }
+
+    fn record_liquidity_at(&self, mint: &str, sol: f64, now: Instant) -> KillSwitchDecision {
+        if !self.config.enabled
+            || self.config.liquidity_drain_pct <= 0.0
+            || !sol.is_finite()
+            || self.deployer_tracker.get_deployer(mint).is_none()
+        {
+            return KillSwitchDecision::Continue;
+        }
+
+        let window = std::time::Duration::from_secs(self.config.liquidity_drain_window_secs);
+        let mut samples = self.liquidity.entry(mint.to_string()).or_default();
+        while samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > window)
+        {
+            samples.pop_front();
+        }
+        let peak = samples.iter().map(|(_, s)| *s).fold(0.0, f64::max);
+        samples.push_back((now, sol));
+
+        if peak <= 0.0 {
+            return KillSwitchDecision::Continue;
+        }
+        let drop_pct = (peak - sol) / peak * 100.0;
+        if drop_pct <= self.config.liquidity_drain_pct {
+            return KillSwitchDecision::Continue;
+        }
+
+        warn!(
+            mint = %mint,
+            from_sol = %peak,
+            to_sol = %sol,
+            drop_pct = %format!("{:.1}%", drop_pct),
+            "KILL-SWITCH: LIQUIDITY DRAINING - PANIC EXIT"
+        );
+        KillSwitchDecision::Exit(KillSwitchAlert {
+            alert_type: KillSwitchType::LiquidityDrain {
+                from_sol: peak,
+                to_sol: sol,
+                drop_pct,
+            },
+            mint: mint.to_string(),
+            urgency: KillSwitchUrgency::Critical,
+            reason: format!(
+                "Curve SOL fell {:.0}% ({:.2} -> {:.2}) within {}s",
+                drop_pct, peak, sol, self.config.liquidity_drain_window_secs
+            ),
+            auto_exit: true,
+        })
+    }
+
     /// Check if we should exit based on accumulated holder activity
     pub fn should_exit(&self, mint: &str) -> KillSwitchDecision {
         if !self.config.enabled {
@@ -354,4 +630,183 @@ mod tests {
             KillSwitchDecision::Continue => panic!("Should trigger exit"),
         }
     }
+
+    fn transfer(from: &str, to: &str, secs: i64, tx_type: &str) -> SplTransfer {
+        SplTransfer {
+            signature: format!("{}-{}-{}", from, to, secs),
+            tx_type: Some(tx_type.to_string()),
+            mint: "token1".to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: 1_000.0,
+            timestamp: chrono::DateTime::from_timestamp(1_700_000_000 + secs, 0),
+        }
+    }
+
+    #[test]
+    fn test_allocation_recipients() {
+        let transfers = vec![
+            // Dev buy: bonding curve -> creator
+            transfer("curve", "creator", 0, "SWAP"),
+            transfer("creator", "alt1", 20, "TRANSFER"),
+            transfer("creator", "alt1", 30, "TRANSFER"),
+            // Selling into the curve is not a handoff
+            transfer("creator", "curve", 40, "SWAP"),
+            transfer("creator", "late", 500, "TRANSFER"),
+        ];
+
+        let recipients =
+            DeployerTracker::allocation_recipients("token1", "creator", &transfers, 120);
+        assert_eq!(recipients, vec!["alt1".to_string()]);
+        let other = DeployerTracker::allocation_recipients("other", "creator", &transfers, 120);
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn test_linked_wallet_sell_trigger() {
+        let evaluator =
+            KillSwitchEvaluator::new(KillSwitchConfig::default(), HolderWatcherConfig::default());
+        let transfers = vec![
+            transfer("curve", "creator", 0, "SWAP"),
+            transfer("creator", "alt1", 10, "TRANSFER"),
+        ];
+
+        // Nothing to link until the position is watched
+        assert_eq!(evaluator.watch_creator_transfers("token1", &transfers), 0);
+        evaluator.watch_position("token1", "creator", vec![]);
+        assert_eq!(evaluator.watch_creator_transfers("token1", &transfers), 1);
+        assert_eq!(evaluator.watch_creator_transfers("token1", &transfers), 0);
+
+        match evaluator.evaluate_sell("token1", "alt1", 1000, 1.0, "sig1") {
+            KillSwitchDecision::Exit(alert) => assert!(matches!(
+                alert.alert_type,
+                KillSwitchType::DeployerSell { linked_wallet: true, .. }
+            )),
+            KillSwitchDecision::Continue => panic!("Should trigger exit"),
+        }
+
+        evaluator.unwatch_position("token1");
+        let tracker = evaluator.deployer_tracker();
+        assert!(!tracker.is_creator_wallet("token1", "alt1"));
+    }
+
+    #[test]
+    fn test_refresh_skips_curve() {
+        let mint = "So11111111111111111111111111111111111111112";
+        let (pda, _) = derive_bonding_curve(&Pubkey::from_str(mint).unwrap()).unwrap();
+        let holder = |address: &str, pct: f64| TokenHolderInfo {
+            address: address.to_string(),
+            amount: (pct * 10_000.0) as u64,
+            percentage: pct,
+        };
+        let holders = vec![holder(&pda.to_string(), 80.0), holder("whale", 10.0)];
+
+        let evaluator =
+            KillSwitchEvaluator::new(KillSwitchConfig::default(), HolderWatcherConfig::default());
+        // Closed positions are not refreshed
+        assert_eq!(evaluator.refresh_holders(mint, &holders), 0);
+
+        evaluator.watch_position(mint, "creator", vec![]);
+        assert_eq!(evaluator.refresh_holders(mint, &holders), 1);
+        assert!(!evaluator.holder_watcher().is_watched(&pda.to_string()));
+
+        // The largest non-curve holder is the top holder
+        match evaluator.evaluate_sell(mint, "whale", 50_000, 1.0, "sig1") {
+            KillSwitchDecision::Exit(alert) => assert!(matches!(
+                alert.alert_type,
+                KillSwitchType::TopHolderSell { rank: 1, .. }
+            )),
+            KillSwitchDecision::Continue => panic!("Should trigger exit"),
+        }
+    }
+
+    #[test]
+    fn test_liquidity_drain_trigger() {
+        let evaluator =
+            KillSwitchEvaluator::new(KillSwitchConfig::default(), HolderWatcherConfig::default());
+        let start = Instant::now();
+        let record = |mint: &str, sol: f64, ms: u64| {
+            let now = start + std::time::Duration::from_millis(ms);
+            match evaluator.record_liquidity_at(mint, sol, now) {
+                KillSwitchDecision::Exit(alert) => Some(alert),
+                KillSwitchDecision::Continue => None,
+            }
+        };
+
+        // Unwatched mints are ignored
+        assert!(record("token1", 50.0, 0).is_none());
+        assert!(record("token1", 10.0, 100).is_none());
+
+        evaluator.watch_position("token1", "creator", vec![]);
+        // Gradual selling: 60 -> 40 over 12s never loses 40% within 5s
+        for (i, sol) in [60.0, 56.0, 52.0, 48.0, 44.0, 40.0].into_iter().enumerate() {
+            assert!(record("token1", sol, i as u64 * 2_400).is_none());
+        }
+
+        // One sell halves the curve against the window's peak (44 SOL at 9.6s)
+        let alert = record("token1", 22.0, 13_000).expect("Should trigger exit");
+        assert_eq!(alert.urgency, KillSwitchUrgency::Critical);
+        match alert.alert_type {
+            KillSwitchType::LiquidityDrain {
+                from_sol,
+                to_sol,
+                drop_pct,
+            } => {
+                assert_eq!((from_sol, to_sol), (44.0, 22.0));
+                assert!((drop_pct - 50.0).abs() < 1e-9);
+            }
+            other => panic!("unexpected alert {:?}", other),
+        }
+
+        // The drop is measured from the window's peak, not the last reading
+        evaluator.watch_position("token2", "creator", vec![]);
+        for (ms, sol) in [(0, 50.0), (1_000, 40.0), (2_000, 35.0)] {
+            assert!(record("token2", sol, ms).is_none());
+        }
+        assert!(record("token2", 28.0, 3_000).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_bundled_sell_trigger() {
+        use crate::filter::bundled_detection::{BundledDetectionConfig, EarlyBuy};
+
+        let detector = Arc::new(BundledDetector::new(BundledDetectionConfig::default(), None));
+        let evaluator = KillSwitchEvaluator::new(
+            KillSwitchConfig::default(),
+            HolderWatcherConfig::default(),
+        )
+        .with_bundled_detector(detector.clone());
+
+        detector.open_window("token1");
+        for (wallet, amount) in [("bundle1", 1.0), ("bundle2", 1.0), ("retail", 0.3)] {
+            detector.record_early_buy(
+                "token1",
+                EarlyBuy {
+                    wallet: wallet.to_string(),
+                    amount_sol: amount,
+                    slot: None,
+                    timestamp: chrono::Utc::now(),
+                    signature: format!("buy-{}", wallet),
+                },
+            );
+        }
+        detector.close_window("token1").await.unwrap();
+
+        // Wallets outside the bundle never count towards the alert
+        assert!(matches!(
+            evaluator.evaluate_sell("token1", "retail", 1000, 0.3, "sig1"),
+            KillSwitchDecision::Continue
+        ));
+        assert!(matches!(
+            evaluator.evaluate_sell("token1", "bundle1", 1000, 1.0, "sig2"),
+            KillSwitchDecision::Continue
+        ));
+        match evaluator.evaluate_sell("token1", "bundle2", 1000, 1.0, "sig3") {
+            KillSwitchDecision::Exit(alert) => assert!(matches!(
+                alert.alert_type,
+                KillSwitchType::BundledWalletsSelling { wallets_selling: 2, .. }
+            )),
+            KillSwitchDecision::Continue => panic!("Should trigger exit"),
+        }
+    }
 }
@@ -0,0 +1,491 @@
+//! Known actor list maintenance
+//!
+//! Keeps the deployer / sniper / trusted lists current while the bot runs.
+//! Every `refresh_interval_secs` the local files are re-read and the remote
+//! lists (plain text, one address per line, `#` comments) are fetched and
+//! merged in. Deployers of tokens that rugged a held position are appended
+//! to the local deployers file so the next refresh keeps them.
+//!
+//! The operator's own deny / allow lists (`snipe list`) are local files
+//! reloaded on the same schedule.
+
+use reqwest::Client;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::filter::adaptive::config::KnownActorsConfig;
+use crate::filter::cache::{FilterCache, KnownActors};
+use crate::filter::token_filter::DenySymbols;
+
+/// Which known actor list an address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorKind {
+    Deployer,
+    Sniper,
+    Trusted,
+}
+
+impl ActorKind {
+    /// Local file backing this list
+    pub fn local_file(self, config: &KnownActorsConfig) -> &str {
+        match self {
+            ActorKind::Deployer => &config.deployers_file,
+            ActorKind::Sniper => &config.snipers_file,
+            ActorKind::Trusted => &config.trusted_file,
+        }
+    }
+
+    fn remote_urls(self, config: &KnownActorsConfig) -> &[String] {
+        match self {
+            ActorKind::Deployer => &config.remote_urls.deployers,
+            ActorKind::Sniper => &config.remote_urls.snipers,
+            ActorKind::Trusted => &config.remote_urls.trusted,
+        }
+    }
+
+    fn set(self, actors: &KnownActors) -> &HashSet<String> {
+        match self {
+            ActorKind::Deployer => &actors.deployers,
+            ActorKind::Sniper => &actors.snipers,
+            ActorKind::Trusted => &actors.trusted,
+        }
+    }
+
+    fn set_mut(self, actors: &mut KnownActors) -> &mut HashSet<String> {
+        match self {
+            ActorKind::Deployer => &mut actors.deployers,
+            ActorKind::Sniper => &mut actors.snipers,
+            ActorKind::Trusted => &mut actors.trusted,
+        }
+    }
+
+    const ALL: [ActorKind; 3] = [ActorKind::Deployer, ActorKind::Sniper, ActorKind::Trusted];
+}
+
+impl std::fmt::Display for ActorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ActorKind::Deployer => "deployer",
+            ActorKind::Sniper => "sniper",
+            ActorKind::Trusted => "trusted",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for ActorKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "deployer" | "deployers" => Ok(ActorKind::Deployer),
+            "sniper" | "snipers" => Ok(ActorKind::Sniper),
+            "trusted" => Ok(ActorKind::Trusted),
+            other => Err(Error::Config(format!(
+                "unknown actor kind '{}' (expected deployer, sniper or trusted)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Operator deny / allow list managed with `snipe list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    DenyCreator,
+    DenyMint,
+    DenySymbolRegex,
+    AllowCreator,
+}
+
+impl ListKind {
+    /// Local file backing this list
+    pub fn local_file(self, config: &KnownActorsConfig) -> &str {
+        match self {
+            ListKind::DenyCreator => &config.deny_creators_file,
+            ListKind::DenyMint => &config.deny_mints_file,
+            ListKind::DenySymbolRegex => &config.deny_symbols_file,
+            ListKind::AllowCreator => &config.allow_creators_file,
+        }
+    }
+
+    /// Check an entry before it is written to the list
+    pub fn validate(self, entry: &str) -> Result<()> {
+        match self {
+            ListKind::DenySymbolRegex => {
+                // `#` starts a comment in list files
+                if entry.contains('#') {
+                    return Err(Error::InvalidRegex(format!(
+                        "'{}': patterns can't contain '#'",
+                        entry
+                    )));
+                }
+                regex::Regex::new(entry)
+                    .map(|_| ())
+                    .map_err(|e| Error::InvalidRegex(e.to_string()))
+            }
+            _ => solana_sdk::pubkey::Pubkey::from_str(entry)
+                .map(|_| ())
+                .map_err(|e| Error::Config(format!("invalid address {}: {}", entry, e))),
+        }
+    }
+
+    pub const ALL: [ListKind; 4] = [
+        ListKind::DenyCreator,
+        ListKind::DenyMint,
+        ListKind::DenySymbolRegex,
+        ListKind::AllowCreator,
+    ];
+}
+
+impl std::fmt::Display for ListKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ListKind::DenyCreator => "deny-creator",
+            ListKind::DenyMint => "deny-mint",
+            ListKind::DenySymbolRegex => "deny-symbol-regex",
+            ListKind::AllowCreator => "allow-creator",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for ListKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "deny-creator" => Ok(ListKind::DenyCreator),
+            "deny-mint" => Ok(ListKind::DenyMint),
+            "deny-symbol-regex" | "deny-symbol" => Ok(ListKind::DenySymbolRegex),
+            "allow-creator" => Ok(ListKind::AllowCreator),
+            other => Err(Error::Config(format!(
+                "unknown list '{}' (expected deny-creator, deny-mint, deny-symbol-regex or allow-creator)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Load every local list: known actors plus the operator's deny / allow lists
+pub fn load_local(config: &KnownActorsConfig) -> KnownActors {
+    KnownActors {
+        deny_creators: read_list(Path::new(&config.deny_creators_file)),
+        deny_mints: read_list(Path::new(&config.deny_mints_file)),
+        allow_creators: read_list(Path::new(&config.allow_creators_file)),
+        ..KnownActors::load_from_files(
+            Some(&config.deployers_file),
+            Some(&config.snipers_file),
+            Some(&config.trusted_file),
+        )
+    }
+}
+
+/// Read the addresses in a local list (missing file = empty list)
+pub fn read_list(path: &Path) -> HashSet<String> {
+    std::fs::read_to_string(path)
+        .map(|content| KnownActors::parse_list(&content))
+        .unwrap_or_default()
+}
+
+/// Append an address to a local list, returning false if it was already there
+pub fn append_address(path: &Path, address: &str, note: Option<&str>) -> Result<bool> {
+    if read_list(path).contains(address) {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| Error::Io(e.to_string()))?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Error::Io(e.to_string()))?;
+    let line = match note {
+        Some(note) => format!("{} # {}\n", address, note.replace('\n', " ")),
+        None => format!("{}\n", address),
+    };
+    file.write_all(line.as_bytes())
+        .map_err(|e| Error::Io(e.to_string()))?;
+    Ok(true)
+}
+
+/// Remove an address from a local list, keeping comments and other entries
+pub fn remove_address(path: &Path, address: &str) -> Result<bool> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(Error::Io(e.to_string())),
+    };
+
+    let mut removed = false;
+    let kept: String = content
+        .lines()
+        .filter(|line| {
+            let matches = line.split('#').next().unwrap_or_default().trim() == address;
+            removed |= matches;
+            !matches
+        })
+        .map(|line| format!("{}\n", line))
+        .collect();
+    if removed {
+        std::fs::write(path, kept).map_err(|e| Error::Io(e.to_string()))?;
+    }
+    Ok(removed)
+}
+
+/// Fetch a remote address list
+pub async fn fetch_list(client: &Client, url: &str) -> Result<HashSet<String>> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Error::Rpc(format!("Known actors fetch failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Rpc(format!(
+            "Known actors fetch returned {}",
+            response.status()
+        )));
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|e| Error::Rpc(format!("Known actors fetch failed: {}", e)))?;
+    Ok(KnownActors::parse_list(&body))
+}
+
+/// Periodically reloads known actors from local files and remote URLs
+pub struct KnownActorsRefresher {
+    /// None when the adaptive filter is off (only the deny symbols reload)
+    cache: Option<Arc<FilterCache>>,
+    deny_symbols: Option<Arc<DenySymbols>>,
+    config: KnownActorsConfig,
+    client: Client,
+}
+
+impl KnownActorsRefresher {
+    pub fn new(cache: Option<Arc<FilterCache>>, config: KnownActorsConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            cache,
+            deny_symbols: None,
+            config,
+            client,
+        }
+    }
+
+    /// Also reload the token filter's deny-symbol patterns
+    pub fn with_deny_symbols(mut self, deny_symbols: Arc<DenySymbols>) -> Self {
+        self.deny_symbols = Some(deny_symbols);
+        self
+    }
+
+    /// Reload every list and swap the result in, returning (added, removed)
+    ///
+    /// If any remote list for a kind can't be fetched, that kind keeps its
+    /// current entries so a flaky host never empties the blacklist.
+    pub async fn refresh(&self) -> (usize, usize) {
+        if let Some(deny_symbols) = &self.deny_symbols {
+            deny_symbols.reload(Path::new(&self.config.deny_symbols_file));
+        }
+        let Some(cache) = &self.cache else {
+            return (0, 0);
+        };
+        let current = cache.known_actors().await;
+        let mut actors = load_local(&self.config);
+
+        for kind in ActorKind::ALL {
+            let mut fetch_failed = false;
+            for url in kind.remote_urls(&self.config) {
+                match fetch_list(&self.client, url).await {
+                    Ok(list) => {
+                        debug!(kind = %kind, url = %url, entries = list.len(), "Fetched known actors");
+                        kind.set_mut(&mut actors).extend(list);
+                    }
+                    Err(e) => {
+                        warn!(kind = %kind, url = %url, error = %e, "Failed to fetch known actors");
+                        fetch_failed = true;
+                    }
+                }
+            }
+            if fetch_failed {
+                let kept = kind.set(&current).iter().cloned();
+                kind.set_mut(&mut actors).extend(kept);
+            }
+        }
+
+        let (added, removed) = cache.replace_known_actors(actors).await;
+        if added > 0 || removed > 0 {
+            let (d, s, t) = cache.known_actors().await.stats();
+            info!(
+                added,
+                removed,
+                deployers = d,
+                snipers = s,
+                trusted = t,
+                "Refreshed known actors"
+            );
+        }
+        (added, removed)
+    }
+
+    /// Refresh now and then every `refresh_interval_secs`
+    pub fn spawn(self) -> JoinHandle<()> {
+        let every = Duration::from_secs(self.config.refresh_interval_secs.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(every);
+            loop {
+                interval.tick().await;
+                self.refresh().await;
+            }
+        })
+    }
+}
+
+/// Blacklists the deployer when a held position is confirmed rugged
+pub struct RugRecorder {
+    cache: Option<Arc<FilterCache>>,
+    deployers_file: PathBuf,
+}
+
+impl RugRecorder {
+    pub fn new(cache: Option<Arc<FilterCache>>, config: &KnownActorsConfig) -> Self {
+        Self {
+            cache,
+            deployers_file: PathBuf::from(&config.deployers_file),
+        }
+    }
+
+    /// Add the creator to the in-memory set and the local deployers file
+    pub async fn record_rug(&self, creator: &str, mint: &str, reason: &str) {
+        if creator.is_empty() {
+            return;
+        }
+        if let Some(cache) = &self.cache {
+            cache.add_known_deployer(creator.to_string()).await;
+        }
+
+        let note = format!("rugged {}: {}", mint, reason);
+        match append_address(&self.deployers_file, creator, Some(&note)) {
+            Ok(true) => warn!(
+                creator = %creator,
+                mint = %mint,
+                "Blacklisted deployer after rug: {}",
+                reason
+            ),
+            Ok(false) => debug!(creator = %creator, "Deployer already blacklisted"),
+            Err(e) => warn!(
+                creator = %creator,
+                file = %self.deployers_file.display(),
+                error = %e,
+                "Failed to append deployer to blacklist file"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deployers.txt");
+        std::fs::write(&path, "# team blacklist\nabc\n").unwrap();
+
+        assert!(append_address(&path, "def", Some("rugged mint1")).unwrap());
+        assert!(!append_address(&path, "abc", None).unwrap());
+        assert_eq!(read_list(&path).len(), 2);
+
+        assert!(remove_address(&path, "def").unwrap());
+        assert!(!remove_address(&path, "def").unwrap());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "# team blacklist\nabc\n");
+    }
+
+    #[tokio::test]
+    async fn test_record_rug_blacklists_deployer() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = KnownActorsConfig {
+            deployers_file: dir
+                .path()
+                .join("deployers.txt")
+                .to_string_lossy()
+                .to_string(),
+            ..Default::default()
+        };
+        let cache = Arc::new(FilterCache::new());
+        let recorder = RugRecorder::new(Some(cache.clone()), &config);
+
+        recorder
+            .record_rug("creator1", "mint1", "deployer sold")
+            .await;
+        recorder
+            .record_rug("creator1", "mint2", "deployer sold")
+            .await;
+
+        assert!(cache.is_known_deployer("creator1").await);
+        let listed = read_list(Path::new(&config.deployers_file));
+        assert_eq!(listed.len(), 1);
+
+        // A refresh from the same files keeps the runtime addition
+        let refresher = KnownActorsRefresher::new(Some(cache.clone()), config);
+        refresher.refresh().await;
+        assert!(cache.is_known_deployer("creator1").await);
+    }
+
+    #[tokio::test]
+    async fn test_operator_lists_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+        let config = KnownActorsConfig {
+            deny_creators_file: file("deny_creators.txt"),
+            deny_symbols_file: file("deny_symbols.txt"),
+            allow_creators_file: file("allow_creators.txt"),
+            ..Default::default()
+        };
+        let creator = solana_sdk::pubkey::Pubkey::new_unique().to_string();
+        let kind: ListKind = "deny-creator".parse().unwrap();
+        assert!(kind.validate("not-an-address").is_err());
+        assert!(ListKind::DenySymbolRegex.validate("(?i)pepe#2").is_err());
+        assert!(ListKind::DenySymbolRegex.validate("(?i)^pepe").is_ok());
+
+        let cache = Arc::new(FilterCache::new());
+        let deny_symbols = Arc::new(DenySymbols::default());
+        let refresher = KnownActorsRefresher::new(Some(cache.clone()), config.clone())
+            .with_deny_symbols(deny_symbols.clone());
+        append_address(Path::new(kind.local_file(&config)), &creator, None).unwrap();
+        append_address(Path::new(&config.deny_symbols_file), "(?i)^pepe", None).unwrap();
+        refresher.refresh().await;
+
+        assert!(cache.deny_reason("mint1", &creator).await.is_some());
+        assert!(cache.deny_reason("mint1", "other").await.is_none());
+        assert!(deny_symbols.matching("Pepe Classic", "PC").is_some());
+
+        remove_address(Path::new(kind.local_file(&config)), &creator).unwrap();
+        refresher.refresh().await;
+        assert!(cache.deny_reason("mint1", &creator).await.is_none());
+    }
+
+    #[test]
+    fn test_actor_kind_parse() {
+        assert_eq!(
+            "deployers".parse::<ActorKind>().unwrap(),
+            ActorKind::Deployer
+        );
+        assert_eq!("Sniper".parse::<ActorKind>().unwrap(), ActorKind::Sniper);
+        assert!("whale".parse::<ActorKind>().is_err());
+    }
+}
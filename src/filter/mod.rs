@@ -13,45 +13,72 @@ pub mod wallet_tracker;
 pub mod adaptive;
 pub mod bundled_detection;
 pub mod cache;
+pub mod creator_history;
+pub mod creator_launches;
+pub mod decision_log;
 pub mod enrichment;
 pub mod helius;
+pub mod helius_budget;
+pub mod honeypot;
+pub mod hot_scan;
+pub mod known_actors;
 pub mod momentum;
+pub mod momentum_gate;
+pub mod name_rules;
+pub mod rescoring;
 pub mod scoring;
 pub mod signals;
 pub mod smart_money;
+pub mod tuning;
 pub mod types;
 
 // Re-exports for basic filtering
-pub use holder_watcher::{AlertUrgency, HolderSellAlert, HolderWatcher, HolderWatcherConfig};
+pub use holder_watcher::{
+    AlertUrgency, ConcentrationEscalation, HolderChanges, HolderSellAlert, HolderWatcher,
+    HolderWatcherConfig,
+};
 pub use kill_switch::{
     DeployerTracker, KillSwitchAlert, KillSwitchConfig, KillSwitchDecision,
     KillSwitchEvaluator, KillSwitchType, KillSwitchUrgency,
 };
-pub use token_filter::TokenFilter;
-pub use wallet_tracker::WalletTracker;
+pub use token_filter::{DenySymbols, TokenFilter};
+pub use wallet_tracker::{TrackedHoldings, WalletTracker};
 
 // Re-exports for adaptive filtering
 pub use adaptive::{AdaptiveFilter, AdaptiveFilterConfig};
 pub use cache::FilterCache;
+pub use creator_launches::{CreatorLaunchConfig, CreatorLaunches, LaunchCounts};
+pub use decision_log::{DecisionAction, DecisionLog, DecisionRecord};
 pub use enrichment::{
-    create_enrichment_system, EnrichmentConfig, EnrichmentHandle, EnrichmentPriority,
-    EnrichmentService, EnrichmentWorker,
+    create_enrichment_system, CreatorTransferHandler, EnrichmentConfig, EnrichmentHandle,
+    EnrichmentPriority, EnrichmentService, EnrichmentStats, EnrichmentWorker,
+    HolderRefreshHandler,
 };
 pub use bundled_detection::{
     BundleDetectionReason, BundleGroup, BundleSellAlert, BundledDetectionConfig, BundledDetector,
     EarlyBuy,
 };
-pub use helius::{HeliusClient, MintInfo, SolTransfer};
+pub use helius::{AssetMetadata, HeliusClient, MintInfo, SolTransfer, SplTransfer};
+pub use helius_budget::{BreakerState, HeliusBudget, HeliusBudgetConfig, HeliusBudgetStats};
+pub use honeypot::{HoneypotChecker, RoundTrip};
+pub use hot_scan::HotScanVerifier;
+pub use known_actors::{ActorKind, KnownActorsRefresher, ListKind, RugRecorder};
 pub use momentum::{MomentumConfig, MomentumMetrics, MomentumStatus, MomentumValidator};
+pub use momentum_gate::{GateOutcome, MomentumGate};
+pub use rescoring::{RescoreOutcome, Rescorer};
 pub use scoring::{
     ReadinessState, Recommendation, ScoringEngine, ScoringResult, ScoringThresholds,
 };
 pub use signals::{
-    MetadataSignalProvider, Signal, SignalProvider, SignalType, SmartMoneySignalProvider,
-    WalletBehaviorSignalProvider,
+    BundleSignalProvider, DistributionSignalProvider, FundingSourceProvider,
+    MetadataSignalProvider, OrderFlowSignalProvider, Signal, SignalProvider, SignalType,
+    SmartMoneySignalProvider, WalletBehaviorSignalProvider,
 };
 pub use smart_money::{
-    AlphaScore, ClusteringStats, WalletCategory, WalletCluster, WalletClusterConfig,
-    WalletClusterer, WalletProfile, WalletProfiler, WalletProfilerConfig,
+    AlphaScore, ClusteringStats, DiscoveredWallet, DiscoveryStatus, WalletCategory, WalletCluster,
+    WalletClusterConfig, WalletClusterer, WalletDiscovery, WalletDiscoveryConfig, WalletProfile,
+    WalletProfiler, WalletProfilerConfig,
+};
+pub use types::{
+    AccumulationMetrics, DiscoverySource, SignalContext, TokenHolderInfo, WalletHistory, WalletTrade,
 };
-pub use types::{SignalContext, TokenHolderInfo, WalletHistory, WalletTrade};
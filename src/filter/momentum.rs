@@ -10,6 +10,9 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+use crate::filter::signals::order_flow::holder_growth;
+use crate::filter::types::HolderGrowthMetrics;
+
 /// Configuration for momentum validation
 #[derive(Debug, Clone)]
 pub struct MomentumConfig {
@@ -37,6 +40,12 @@ pub struct MomentumConfig {
     pub second_wave_window_pct: f64,
     /// SURVIVOR: Minimum buy ratio in second wave (must have recent buying activity)
     pub min_second_wave_ratio: f64,
+    /// Minimum net SOL inflow (buy volume - sell volume)
+    pub min_net_flow_sol: f64,
+    /// Require holder data before entry (set via `set_holder_concentration`)
+    pub require_holder_data: bool,
+    /// Minimum organic demand once there are enough buys (-1.0 = any)
+    pub min_organic_demand: f64,
 }
 
 impl Default for MomentumConfig {
@@ -54,6 +63,9 @@ impl Default for MomentumConfig {
             min_survival_ratio: 0.70,       // SURVIVOR: Price >= 70% of peak
             second_wave_window_pct: 0.30,   // SURVIVOR: Check last 30% of observation
             min_second_wave_ratio: 0.40,    // SURVIVOR: At least 40% buys in recent window
+            min_net_flow_sol: 0.0,          // DATA-DRIVEN: More buying than selling
+            require_holder_data: true,      // SURVIVOR: Holder data before entry
+            min_organic_demand: -1.0,       // Holder growth not required
         }
     }
 }
@@ -96,6 +108,8 @@ pub struct MomentumMetrics {
     pub holder_concentration: f64,  // top holder as % of supply (set externally)
     pub holder_data_fetched: bool,  // whether holder data has been fetched
     pub second_wave_buy_ratio: f64, // buy ratio in last 30% of observation window
+    /// New buyers vs repeat buys since the first watched trade
+    pub holder_growth: HolderGrowthMetrics,
 }
 
 impl MomentumMetrics {
@@ -135,7 +149,7 @@ impl MomentumMetrics {
         }
 
         // DATA-DRIVEN: Require positive net flow (more buying than selling by volume)
-        if self.net_flow_sol < 0.0 {
+        if self.net_flow_sol < config.min_net_flow_sol {
             return false;
         }
 
@@ -150,7 +164,7 @@ impl MomentumMetrics {
         }
 
         // SURVIVOR: Require holder data to be fetched before entry
-        if !self.holder_data_fetched {
+        if config.require_holder_data && !self.holder_data_fetched {
             return false;
         }
 
@@ -167,6 +181,15 @@ impl MomentumMetrics {
             }
         }
 
+        // Buys must come from new wallets, not a few recycling volume
+        if self
+            .holder_growth
+            .organic_demand()
+            .is_some_and(|demand| demand < config.min_organic_demand)
+        {
+            return false;
+        }
+
         true
     }
 
@@ -214,8 +237,11 @@ impl MomentumMetrics {
             ));
         }
         // DATA-DRIVEN: Show net flow requirement
-        if self.net_flow_sol < 0.0 {
-            missing.push(format!("net_flow:{:+.2}SOL<0", self.net_flow_sol));
+        if self.net_flow_sol < config.min_net_flow_sol {
+            missing.push(format!(
+                "net_flow:{:+.2}SOL<{:.2}",
+                self.net_flow_sol, config.min_net_flow_sol
+            ));
         }
         if self.volatility < config.min_volatility {
             missing.push(format!(
@@ -233,7 +259,9 @@ impl MomentumMetrics {
         }
         // SURVIVOR: Holder data must be fetched
         if !self.holder_data_fetched {
-            missing.push("holder_data:pending".to_string());
+            if config.require_holder_data {
+                missing.push("holder_data:pending".to_string());
+            }
         } else if self.holder_concentration > config.max_holder_concentration {
             // SURVIVOR: Holder concentration check (only if data fetched)
             missing.push(format!(
@@ -253,6 +281,15 @@ impl MomentumMetrics {
             }
         }
 
+        if let Some(demand) = self.holder_growth.organic_demand() {
+            if demand < config.min_organic_demand {
+                missing.push(format!(
+                    "demand:{:+.2}<{:+.2}",
+                    demand, config.min_organic_demand
+                ));
+            }
+        }
+
         if missing.is_empty() {
             "READY".to_string()
         } else {
@@ -398,6 +435,17 @@ impl WatchedToken {
             sw_buys as f64 / second_wave_trades.len() as f64
         };
 
+        let first_at = self.trades[0].timestamp;
+        let holder_growth = holder_growth(
+            self.trades.iter().filter(|t| t.is_buy).map(|t| {
+                (
+                    t.trader.as_str(),
+                    t.timestamp.duration_since(first_at).as_secs_f64(),
+                )
+            }),
+            first_at.elapsed().as_secs_f64(),
+        );
+
         MomentumMetrics {
             observation_started: Some(self.started),
             trade_count: self.trades.len() as u32,
@@ -423,6 +471,7 @@ impl WatchedToken {
             holder_concentration: self.holder_concentration,
             holder_data_fetched: self.holder_data_fetched,
             second_wave_buy_ratio,
+            holder_growth,
         }
     }
 }
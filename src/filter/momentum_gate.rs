@@ -0,0 +1,328 @@
+//! Momentum confirmation before full position size
+//!
+//! With `[adaptive_filter.momentum]` enabled, StrongBuy / Opportunity entries
+//! buy a starter fraction and hand the rest to this gate. Each pending entry
+//! is watched by the `MomentumValidator` (fed from the trade stream) until it
+//! confirms (top up to full size), fails (kill-switch the starter) or times
+//! out (keep the starter as is).
+
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::filter::adaptive::config::MomentumGateConfig;
+use crate::filter::momentum::{MomentumMetrics, MomentumStatus, MomentumValidator};
+use crate::filter::rescoring::AddOnHandler;
+use crate::filter::types::SignalContext;
+use crate::position::manager::PositionManager;
+
+/// How often pending entries are re-checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Final state of a pending entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum GateOutcome {
+    /// Momentum confirmed: top up to full size
+    Confirmed,
+    /// Momentum failed: exit the starter
+    Failed { reason: String },
+    /// Window ran out without a verdict: keep the starter
+    Timeout,
+}
+
+impl std::fmt::Display for GateOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GateOutcome::Confirmed => write!(f, "confirmed"),
+            GateOutcome::Failed { .. } => write!(f, "failed"),
+            GateOutcome::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+/// Decide a pending entry from the validator's status (None = keep watching)
+pub fn evaluate(status: &MomentumStatus, config: &MomentumGateConfig) -> Option<GateOutcome> {
+    match status {
+        MomentumStatus::Ready { .. } => Some(GateOutcome::Confirmed),
+        MomentumStatus::Expired { .. } => Some(GateOutcome::Timeout),
+        MomentumStatus::Observing { metrics, .. } => {
+            failure(metrics, config).map(|reason| GateOutcome::Failed { reason })
+        }
+        MomentumStatus::NotWatched => Some(GateOutcome::Timeout),
+    }
+}
+
+/// Why the starter's momentum failed, if it did
+fn failure(metrics: &MomentumMetrics, config: &MomentumGateConfig) -> Option<String> {
+    if metrics.observation_secs < config.min_observation_secs as f64 {
+        return None;
+    }
+    if metrics.price_change_pct <= -config.fail_price_drop_pct {
+        return Some(format!(
+            "price {:+.1}% since entry",
+            metrics.price_change_pct
+        ));
+    }
+    if metrics.net_flow_sol <= -config.fail_net_outflow_sol {
+        return Some(format!("net outflow {:.2} SOL", -metrics.net_flow_sol));
+    }
+    None
+}
+
+/// Watches starter positions and tops up or exits them
+pub struct MomentumGate {
+    validator: MomentumValidator,
+    position_manager: Arc<PositionManager>,
+    config: MomentumGateConfig,
+    top_up: Option<AddOnHandler>,
+}
+
+impl MomentumGate {
+    pub fn new(position_manager: Arc<PositionManager>, config: MomentumGateConfig) -> Self {
+        Self {
+            validator: MomentumValidator::new(config.validator_config()),
+            position_manager,
+            config,
+            top_up: None,
+        }
+    }
+
+    /// Handler that buys the remaining size once momentum confirms
+    pub fn with_top_up(mut self, handler: AddOnHandler) -> Self {
+        self.top_up = Some(handler);
+        self
+    }
+
+    /// Size to buy now for a planned entry (None = gate doesn't apply)
+    pub fn starter_size(&self, planned_sol: f64) -> Option<f64> {
+        self.config.starter_size(planned_sol)
+    }
+
+    /// Feed a trade from the stream (ignored unless the mint is pending)
+    pub async fn record_trade(
+        &self,
+        mint: &str,
+        is_buy: bool,
+        sol_amount: f64,
+        token_amount: f64,
+        trader: &str,
+    ) {
+        self.validator
+            .record_trade(mint, is_buy, sol_amount, token_amount, trader)
+            .await;
+    }
+
+    /// Watch the starter position for `context` and top up by `top_up_sol`
+    pub async fn watch(self: &Arc<Self>, context: SignalContext, top_up_sol: f64) {
+        self.validator
+            .watch_token(
+                &context.mint,
+                &context.symbol,
+                &context.name,
+                &context.bonding_curve,
+                context.market_cap_sol,
+            )
+            .await;
+        let gate = Arc::clone(self);
+        tokio::spawn(async move { gate.run(context, top_up_sol).await });
+    }
+
+    async fn run(&self, context: SignalContext, top_up_sol: f64) {
+        let mint = context.mint.clone();
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+        let (outcome, metrics) = loop {
+            interval.tick().await;
+
+            match self.position_manager.get_position(&mint).await {
+                Some(p) if !p.kill_switch_triggered => {}
+                _ => {
+                    debug!(mint = %mint, "Starter closed before momentum verdict");
+                    self.validator.remove_token(&mint).await;
+                    return;
+                }
+            }
+
+            let status = self.validator.check_momentum(&mint).await;
+            if let Some(outcome) = evaluate(&status, &self.config) {
+                let metrics = match status {
+                    MomentumStatus::Ready { metrics }
+                    | MomentumStatus::Expired { metrics }
+                    | MomentumStatus::Observing { metrics, .. } => metrics,
+                    MomentumStatus::NotWatched => MomentumMetrics::default(),
+                };
+                break (outcome, metrics);
+            }
+        };
+        self.validator.remove_token(&mint).await;
+
+        info!(
+            mint = %mint,
+            symbol = %context.symbol,
+            outcome = %outcome,
+            secs = %format!("{:.1}", metrics.observation_secs),
+            traders = metrics.unique_traders,
+            net_flow_sol = %format!("{:+.3}", metrics.net_flow_sol),
+            price_change_pct = %format!("{:+.1}", metrics.price_change_pct),
+            "Momentum gate outcome"
+        );
+
+        match outcome {
+            GateOutcome::Confirmed => {
+                if top_up_sol <= 0.0 {
+                    return;
+                }
+                match self.top_up {
+                    Some(ref handler) => {
+                        info!(
+                            "[{}] Momentum confirmed - topping up {:.4} SOL",
+                            context.symbol, top_up_sol
+                        );
+                        handler(&context, top_up_sol);
+                    }
+                    None => info!(
+                        "[{}] Momentum confirmed - no top-up handler, keeping starter",
+                        context.symbol
+                    ),
+                }
+            }
+            GateOutcome::Failed { reason } => {
+                warn!(
+                    "[{}] Momentum failed ({}) - exiting starter",
+                    context.symbol, reason
+                );
+                let _ = self
+                    .position_manager
+                    .trigger_kill_switch(&mint, &format!("momentum failed: {}", reason))
+                    .await;
+            }
+            GateOutcome::Timeout => {
+                info!(
+                    "[{}] Momentum not confirmed within {}s - keeping starter only",
+                    context.symbol, self.config.window_secs
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observing(secs: f64, price_change_pct: f64, net_flow_sol: f64) -> MomentumStatus {
+        MomentumStatus::Observing {
+            metrics: MomentumMetrics {
+                observation_secs: secs,
+                price_change_pct,
+                net_flow_sol,
+                ..Default::default()
+            },
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let config = MomentumGateConfig::default();
+
+        assert_eq!(
+            evaluate(
+                &MomentumStatus::Ready {
+                    metrics: MomentumMetrics::default()
+                },
+                &config
+            ),
+            Some(GateOutcome::Confirmed)
+        );
+        assert_eq!(
+            evaluate(
+                &MomentumStatus::Expired {
+                    metrics: MomentumMetrics::default()
+                },
+                &config
+            ),
+            Some(GateOutcome::Timeout)
+        );
+
+        // Too early to fail, then a dump fails it
+        assert_eq!(evaluate(&observing(1.0, -50.0, 0.0), &config), None);
+        assert!(matches!(
+            evaluate(&observing(10.0, -20.0, 0.0), &config),
+            Some(GateOutcome::Failed { .. })
+        ));
+        assert!(matches!(
+            evaluate(&observing(10.0, 0.0, -1.0), &config),
+            Some(GateOutcome::Failed { .. })
+        ));
+        assert_eq!(evaluate(&observing(10.0, 2.0, 0.2), &config), None);
+    }
+
+    #[tokio::test]
+    async fn test_validator_confirms_on_inflow() {
+        let config = MomentumGateConfig {
+            min_observation_secs: 0,
+            ..Default::default()
+        };
+        let validator = MomentumValidator::new(config.validator_config());
+        validator
+            .watch_token("mint1", "TKN", "Token", "curve", 30.0)
+            .await;
+
+        // Rising price, five buyers, 1 SOL net inflow
+        for (i, tokens) in [1000.0, 900.0, 800.0, 700.0, 600.0].iter().enumerate() {
+            validator
+                .record_trade("mint1", true, 0.2, *tokens, &format!("trader{}", i))
+                .await;
+        }
+
+        let status = validator.check_momentum("mint1").await;
+        assert_eq!(evaluate(&status, &config), Some(GateOutcome::Confirmed));
+    }
+
+    #[tokio::test]
+    async fn test_validator_rejects_recycled_buys() {
+        let status = |min_organic_demand: f64| async move {
+            let config = MomentumGateConfig {
+                min_observation_secs: 0,
+                min_organic_demand,
+                ..Default::default()
+            };
+            let validator = MomentumValidator::new(config.validator_config());
+            validator
+                .watch_token("mint1", "TKN", "Token", "curve", 30.0)
+                .await;
+
+            // Rising price and inflow, but five wallets buying over and over
+            for i in 0..30 {
+                let tokens = 1000.0 - i as f64 * 30.0;
+                validator
+                    .record_trade("mint1", true, 0.2, tokens, &format!("trader{}", i % 5))
+                    .await;
+            }
+            (validator.check_momentum("mint1").await, config)
+        };
+
+        let (any_demand, config) = status(-1.0).await;
+        assert_eq!(evaluate(&any_demand, &config), Some(GateOutcome::Confirmed));
+
+        match status(0.0).await.0 {
+            MomentumStatus::Observing { metrics, reason } => {
+                assert!(metrics.holder_growth.organic_demand().unwrap() < 0.0);
+                assert!(reason.contains("demand:"), "{}", reason);
+            }
+            other => panic!("expected to keep observing, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_starter_size() {
+        assert_eq!(MomentumGateConfig::default().starter_size(1.0), None);
+
+        let config = MomentumGateConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!((config.starter_size(1.0).unwrap() - 0.3).abs() < 1e-9);
+    }
+}
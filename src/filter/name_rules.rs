@@ -0,0 +1,321 @@
+//! Name quality rules for the adaptive filter
+//!
+//! Keywords, allowlisted terms, regex patterns and length bounds come from
+//! `[adaptive_filter.name_rules]` and are compiled once when the filter is
+//! built. Names are normalized first (zero-width characters stripped,
+//! Cyrillic / Greek lookalikes mapped to Latin) so "ЅCAM" still hits "scam",
+//! and the obfuscation itself is penalized.
+
+use regex::Regex;
+
+use crate::error::{Error, Result};
+use crate::filter::adaptive::config::NameRulesConfig;
+use crate::filter::signals::{Signal, SignalType};
+
+/// Invisible characters used to dodge keyword filters
+const ZERO_WIDTH: &[char] = &[
+    '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}', '\u{00AD}',
+];
+
+/// Lookalike letter -> the Latin letter it imitates
+fn homoglyph(c: char) -> Option<char> {
+    let latin = match c {
+        // Cyrillic
+        'а' | 'А' => 'a',
+        'В' | 'в' => 'b',
+        'с' | 'С' => 'c',
+        'ԁ' => 'd',
+        'е' | 'Е' => 'e',
+        'Н' | 'н' => 'h',
+        'і' | 'І' => 'i',
+        'ј' | 'Ј' => 'j',
+        'К' | 'к' => 'k',
+        'ӏ' => 'l',
+        'М' | 'м' => 'm',
+        'о' | 'О' => 'o',
+        'р' | 'Р' => 'p',
+        'ѕ' | 'Ѕ' => 's',
+        'Т' | 'т' => 't',
+        'у' | 'У' => 'y',
+        'х' | 'Х' => 'x',
+        // Greek
+        'Α' | 'α' => 'a',
+        'Β' | 'β' => 'b',
+        'Ε' => 'e',
+        'Ζ' => 'z',
+        'Η' => 'h',
+        'Ι' | 'ι' => 'i',
+        'Κ' | 'κ' => 'k',
+        'Μ' => 'm',
+        'Ν' | 'ν' => 'n',
+        'Ο' | 'ο' => 'o',
+        'Ρ' | 'ρ' => 'p',
+        'Τ' | 'τ' => 't',
+        'Υ' | 'υ' => 'y',
+        'Χ' | 'χ' => 'x',
+        _ => return None,
+    };
+    Some(latin)
+}
+
+/// A name after the normalization pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedName {
+    /// Lowercase, zero-width stripped, lookalikes mapped to Latin
+    pub text: String,
+    /// Visible characters (zero-width stripped)
+    pub visible: String,
+    /// Zero-width characters were present
+    pub zero_width: bool,
+    /// Reads as Latin but uses lookalike letters
+    pub homoglyphs: bool,
+    /// No letters or digits at all (emoji / symbols only)
+    pub symbols_only: bool,
+}
+
+/// Strip invisible characters and fold lookalikes to Latin
+pub fn normalize(raw: &str) -> NormalizedName {
+    let visible: String = raw.chars().filter(|c| !ZERO_WIDTH.contains(c)).collect();
+    let zero_width = visible.len() != raw.len();
+
+    let mut lookalikes = 0;
+    let mut other_non_ascii_letters = 0;
+    let mut text = String::with_capacity(visible.len());
+    for c in visible.chars() {
+        match homoglyph(c) {
+            Some(latin) => {
+                lookalikes += 1;
+                text.push(latin);
+            }
+            None => {
+                if c.is_alphabetic() && !c.is_ascii() {
+                    other_non_ascii_letters += 1;
+                }
+                text.extend(c.to_lowercase());
+            }
+        }
+    }
+
+    NormalizedName {
+        // A genuine Cyrillic / Greek name has letters with no Latin twin
+        homoglyphs: lookalikes > 0 && other_non_ascii_letters == 0,
+        symbols_only: !visible.trim().is_empty() && !visible.chars().any(char::is_alphanumeric),
+        zero_width,
+        visible,
+        text,
+    }
+}
+
+/// Compiled name rules
+pub struct NameRules {
+    /// (keyword, value), most severe first
+    keywords: Vec<(String, f64)>,
+    allowlist: Vec<String>,
+    patterns: Vec<(Regex, f64)>,
+    config: NameRulesConfig,
+}
+
+impl NameRules {
+    /// Compile the configured rules (invalid regexes are a startup error)
+    pub fn compile(config: &NameRulesConfig) -> Result<Self> {
+        let mut keywords: Vec<(String, f64)> = config
+            .keywords
+            .iter()
+            .map(|(k, v)| (normalize(k).text, v.clamp(-1.0, 1.0)))
+            .collect();
+        keywords.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+        let patterns = config
+            .patterns
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .map(|re| (re, rule.score.clamp(-1.0, 1.0)))
+                    .map_err(|e| {
+                        Error::InvalidRegex(format!(
+                            "adaptive_filter.name_rules pattern '{}': {}",
+                            rule.pattern, e
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            keywords,
+            allowlist: config.allowlist.iter().map(|t| normalize(t).text).collect(),
+            patterns,
+            config: config.clone(),
+        })
+    }
+
+    /// Score a token's name and symbol
+    pub fn evaluate(&self, name: &str, symbol: &str) -> Signal {
+        let name_norm = normalize(name);
+        let symbol_norm = normalize(symbol);
+
+        // Keywords, ignoring any that only occur inside an allowlisted term
+        let mut haystack = format!("{} {}", name_norm.text, symbol_norm.text);
+        for term in &self.allowlist {
+            haystack = haystack.replace(term.as_str(), " ");
+        }
+        if let Some((keyword, value)) = self
+            .keywords
+            .iter()
+            .find(|(keyword, _)| haystack.contains(keyword.as_str()))
+        {
+            return Signal::new(
+                SignalType::NameQuality,
+                *value,
+                0.9,
+                format!("Name contains suspicious keyword: {}", keyword),
+            );
+        }
+
+        // Obfuscation: invisible characters or Latin lookalikes
+        if name_norm.zero_width || symbol_norm.zero_width {
+            return Signal::new(
+                SignalType::NameQuality,
+                self.config.obfuscation_penalty,
+                0.85,
+                "Name contains zero-width characters",
+            );
+        }
+        if name_norm.homoglyphs || symbol_norm.homoglyphs {
+            return Signal::new(
+                SignalType::NameQuality,
+                self.config.obfuscation_penalty,
+                0.85,
+                format!(
+                    "Name uses lookalike characters ({} / {} reads as {} / {})",
+                    name, symbol, name_norm.text, symbol_norm.text
+                ),
+            );
+        }
+
+        // Configured patterns: the strongest match wins
+        let matched = self
+            .patterns
+            .iter()
+            .filter(|(re, _)| re.is_match(&name_norm.visible) || re.is_match(&symbol_norm.visible))
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+        if let Some((re, value)) = matched {
+            return Signal::new(
+                SignalType::NameQuality,
+                *value,
+                0.8,
+                format!("Name matches pattern {}", re.as_str()),
+            );
+        }
+
+        if name_norm.symbols_only {
+            return Signal::new(
+                SignalType::NameQuality,
+                self.config.emoji_only_penalty,
+                0.6,
+                "Name has no letters or digits (emoji only)",
+            );
+        }
+
+        // Length bounds (characters, not bytes)
+        let name_len = name_norm.visible.chars().count();
+        let symbol_len = symbol_norm.visible.chars().count();
+        if name_len < self.config.min_length || symbol_len < self.config.min_length {
+            return Signal::new(SignalType::NameQuality, -0.3, 0.6, "Very short name/symbol");
+        }
+        if name_len > self.config.max_length {
+            return Signal::new(SignalType::NameQuality, -0.2, 0.5, "Unusually long name");
+        }
+
+        // Check for all caps (often spam)
+        if name_len > 4
+            && name_norm
+                .visible
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .all(|c| c.is_uppercase())
+        {
+            return Signal::new(
+                SignalType::NameQuality,
+                self.config.all_caps_penalty,
+                0.4,
+                "All caps name",
+            );
+        }
+
+        Signal::neutral(SignalType::NameQuality, "Name appears normal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::adaptive::config::NamePatternRule;
+
+    fn rules(config: NameRulesConfig) -> NameRules {
+        NameRules::compile(&config).unwrap()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cyrillic = normalize("ЅOL");
+        assert_eq!(cyrillic.text, "sol");
+        assert!(cyrillic.homoglyphs);
+
+        // A real Russian word has letters with no Latin twin
+        assert!(!normalize("Привет").homoglyphs);
+
+        let hidden = normalize("sc\u{200B}am");
+        assert!(hidden.zero_width);
+        assert_eq!(hidden.text, "scam");
+
+        assert!(normalize("🚀🌕").symbols_only);
+        assert!(!normalize("Moon 🚀").symbols_only);
+    }
+
+    #[test]
+    fn test_keywords_and_allowlist() {
+        let default = rules(NameRulesConfig::default());
+        assert_eq!(default.evaluate("Free Money", "FREE").value, -0.7);
+        assert_eq!(default.evaluate("ЅCAM Coin", "SC").value, -0.7);
+
+        let config = NameRulesConfig {
+            allowlist: vec!["freedom".to_string()],
+            keywords: [("free".to_string(), -0.4)].into_iter().collect(),
+            ..Default::default()
+        };
+        let custom = rules(config);
+        assert_eq!(custom.evaluate("Freedom Eagle", "EAGLE").value, 0.0);
+        assert_eq!(custom.evaluate("Free Eagle", "EAGLE").value, -0.4);
+    }
+
+    #[test]
+    fn test_unicode_obfuscation() {
+        let default = rules(NameRulesConfig::default());
+        assert!(default.evaluate("ЅOLANA", "ЅOL").value < 0.0);
+        assert!(default.evaluate("Pe\u{200D}pe", "PEPE").value < 0.0);
+        assert!(default.evaluate("🚀🚀🚀", "🚀").value < 0.0);
+        assert_eq!(default.evaluate("Moon Cat", "MCAT").value, 0.0);
+    }
+
+    #[test]
+    fn test_patterns() {
+        let config = NameRulesConfig {
+            patterns: vec![NamePatternRule {
+                pattern: "(?i)elon".to_string(),
+                score: -0.3,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(rules(config).evaluate("Elon Dog", "EDOG").value, -0.3);
+
+        let invalid = NameRulesConfig {
+            patterns: vec![NamePatternRule {
+                pattern: "(unclosed".to_string(),
+                score: -0.3,
+            }],
+            ..Default::default()
+        };
+        let err = NameRules::compile(&invalid).err().unwrap();
+        assert!(err.to_string().contains("(unclosed"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Deferred re-scoring of fresh positions
+//!
+//! Entry decisions come from `score_fast`, usually before enrichment has
+//! landed. Probe and Opportunity entries are re-scored with `score_full` on
+//! a schedule (T+10s and T+30s by default). A downgrade to Avoid flags the
+//! position's kill-switch so the monitor exits on its next pass; an upgrade
+//! to StrongBuy can trigger one add-on buy sized by the change in position
+//! multiplier.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::filter::adaptive::config::RescoringConfig;
+use crate::filter::adaptive::AdaptiveFilter;
+use crate::filter::scoring::{Recommendation, ScoringResult};
+use crate::filter::types::SignalContext;
+use crate::position::manager::PositionManager;
+
+/// Places an add-on buy of the given size (SOL) for the context's token
+pub type AddOnHandler = Arc<dyn Fn(&SignalContext, f64) + Send + Sync>;
+
+/// What a re-score means for an open position
+#[derive(Debug, Clone, PartialEq)]
+pub enum RescoreOutcome {
+    /// Verdict didn't cross a threshold we act on
+    Unchanged,
+    /// Re-score says Avoid: exit now
+    Downgrade { reason: String },
+    /// Re-score says StrongBuy: optionally add to the position
+    Upgrade { add_on_sol: f64 },
+}
+
+/// Compare the entry verdict with a re-score
+pub fn evaluate(
+    initial: &ScoringResult,
+    rescored: &ScoringResult,
+    entry_cost_sol: f64,
+    config: &RescoringConfig,
+) -> RescoreOutcome {
+    if rescored.recommendation == Recommendation::Avoid
+        && initial.recommendation != Recommendation::Avoid
+    {
+        return RescoreOutcome::Downgrade {
+            reason: rescored.summary.clone(),
+        };
+    }
+
+    if rescored.recommendation == Recommendation::StrongBuy
+        && initial.recommendation != Recommendation::StrongBuy
+    {
+        let delta = (rescored.position_size_multiplier - initial.position_size_multiplier).max(0.0);
+        let add_on_sol = if config.add_on_enabled {
+            (entry_cost_sol * delta).min(config.add_on_max_sol)
+        } else {
+            0.0
+        };
+        return RescoreOutcome::Upgrade { add_on_sol };
+    }
+
+    RescoreOutcome::Unchanged
+}
+
+/// Schedules `score_full` re-runs for newly opened positions
+pub struct Rescorer {
+    filter: Arc<AdaptiveFilter>,
+    position_manager: Arc<PositionManager>,
+    config: RescoringConfig,
+    add_on: Option<AddOnHandler>,
+}
+
+impl Rescorer {
+    pub fn new(
+        filter: Arc<AdaptiveFilter>,
+        position_manager: Arc<PositionManager>,
+        config: RescoringConfig,
+    ) -> Self {
+        Self {
+            filter,
+            position_manager,
+            config,
+            add_on: None,
+        }
+    }
+
+    /// Handler for add-on buys (only called when `add_on_enabled` is set)
+    pub fn with_add_on(mut self, handler: AddOnHandler) -> Self {
+        self.add_on = Some(handler);
+        self
+    }
+
+    /// Whether an entry with this recommendation gets re-scored
+    pub fn applies_to(&self, recommendation: Recommendation) -> bool {
+        self.config.enabled
+            && matches!(
+                recommendation,
+                Recommendation::Probe | Recommendation::Opportunity
+            )
+    }
+
+    /// Re-score the position for `context` on the configured schedule
+    pub fn schedule(self: &Arc<Self>, context: SignalContext, initial: ScoringResult) {
+        if !self.applies_to(initial.recommendation) {
+            return;
+        }
+        let rescorer = Arc::clone(self);
+        tokio::spawn(async move { rescorer.run(context, initial).await });
+    }
+
+    async fn run(&self, context: SignalContext, initial: ScoringResult) {
+        let start = tokio::time::Instant::now();
+        let mut delays = self.config.delays_secs.clone();
+        delays.sort_unstable();
+        let mut added = false;
+
+        for delay in delays {
+            tokio::time::sleep_until(start + Duration::from_secs(delay)).await;
+
+            let position = match self.position_manager.get_position(&context.mint).await {
+                Some(p) if !p.kill_switch_triggered => p,
+                _ => break, // Closed or already exiting
+            };
+
+            let rescored = self.filter.score_full(&context).await;
+            match evaluate(&initial, &rescored, position.total_cost_sol, &self.config) {
+                RescoreOutcome::Downgrade { reason } => {
+                    warn!(
+                        "[{}] Re-score at T+{}s downgraded {:?} -> Avoid: {}",
+                        position.symbol, delay, initial.recommendation, reason
+                    );
+                    let _ = self
+                        .position_manager
+                        .trigger_kill_switch(&context.mint, &format!("re-score: {}", reason))
+                        .await;
+                    break;
+                }
+                RescoreOutcome::Upgrade { add_on_sol } => {
+                    info!(
+                        "[{}] Re-score at T+{}s upgraded {:?} -> StrongBuy (score {:.2} -> {:.2})",
+                        position.symbol,
+                        delay,
+                        initial.recommendation,
+                        initial.score,
+                        rescored.score
+                    );
+                    if !added && add_on_sol > 0.0 {
+                        if let Some(ref handler) = self.add_on {
+                            info!(
+                                "[{}] Adding {:.4} SOL after upgrade",
+                                position.symbol, add_on_sol
+                            );
+                            handler(&context, add_on_sol);
+                            added = true;
+                        }
+                    }
+                }
+                RescoreOutcome::Unchanged => {
+                    debug!(
+                        mint = %context.mint,
+                        delay_secs = delay,
+                        recommendation = ?rescored.recommendation,
+                        score = %rescored.score,
+                        "Re-score unchanged"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(recommendation: Recommendation, multiplier: f64) -> ScoringResult {
+        ScoringResult {
+            recommendation,
+            position_size_multiplier: multiplier,
+            summary: format!("{:?}", recommendation),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_downgrade_to_avoid() {
+        let outcome = evaluate(
+            &result(Recommendation::Probe, 0.05),
+            &result(Recommendation::Avoid, 0.0),
+            0.1,
+            &RescoringConfig::default(),
+        );
+        assert!(matches!(outcome, RescoreOutcome::Downgrade { .. }));
+    }
+
+    #[test]
+    fn test_upgrade_add_on_sized_by_delta() {
+        let initial = result(Recommendation::Opportunity, 1.0);
+        let rescored = result(Recommendation::StrongBuy, 1.5);
+
+        // Gated off by default
+        let outcome = evaluate(&initial, &rescored, 0.1, &RescoringConfig::default());
+        assert_eq!(outcome, RescoreOutcome::Upgrade { add_on_sol: 0.0 });
+
+        let config = RescoringConfig {
+            add_on_enabled: true,
+            add_on_max_sol: 0.03,
+            ..Default::default()
+        };
+        match evaluate(&initial, &rescored, 0.04, &config) {
+            RescoreOutcome::Upgrade { add_on_sol } => assert!((add_on_sol - 0.02).abs() < 1e-9),
+            other => panic!("expected upgrade, got {:?}", other),
+        }
+        match evaluate(&initial, &rescored, 1.0, &config) {
+            RescoreOutcome::Upgrade { add_on_sol } => assert!((add_on_sol - 0.03).abs() < 1e-9),
+            other => panic!("expected upgrade, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let outcome = evaluate(
+            &result(Recommendation::Opportunity, 1.0),
+            &result(Recommendation::Probe, 0.05),
+            0.1,
+            &RescoringConfig::default(),
+        );
+        assert_eq!(outcome, RescoreOutcome::Unchanged);
+    }
+}
@@ -7,9 +7,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::filter::signals::{Signal, SignalCategory, SignalType};
+use crate::filter::types::SignalContext;
 
 /// Final scoring result with recommendation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoringResult {
     /// Overall score (-1.0 = extreme risk, +1.0 = extreme opportunity)
     pub score: f64,
@@ -28,6 +29,8 @@ pub struct ScoringResult {
     pub position_size_multiplier: f64,
     /// Human-readable summary
     pub summary: String,
+    /// Served from the score cache rather than freshly computed
+    pub cached: bool,
 }
 
 impl Default for ScoringResult {
@@ -41,6 +44,7 @@ impl Default for ScoringResult {
             recommendation: Recommendation::Observe, // Default: watch, don't trade
             position_size_multiplier: 0.0,
             summary: "No signals available".to_string(),
+            cached: false,
         }
     }
 }
@@ -131,6 +135,38 @@ impl ReadinessState {
             readiness_reason,
         }
     }
+
+    /// Evaluate readiness using the token's age and curve progress
+    ///
+    /// Tokens discovered mid-curve (unknown age) have clearly been trading
+    /// for a while, so the launch delay doesn't hold them back; a curve near
+    /// graduation does.
+    pub fn evaluate_for_context(
+        thresholds: &ScoringThresholds,
+        context: &SignalContext,
+        data_completeness: f64,
+        enriched_components: u32,
+    ) -> Self {
+        let age_secs = context
+            .age_secs
+            .unwrap_or(thresholds.min_time_since_launch_secs);
+        let mut state =
+            Self::evaluate(thresholds, data_completeness, enriched_components, age_secs);
+
+        if context.curve_progress_pct > thresholds.max_curve_progress_pct {
+            let reason = format!(
+                "curve {:.0}% > {:.0}%",
+                context.curve_progress_pct, thresholds.max_curve_progress_pct
+            );
+            state.readiness_reason = Some(match state.readiness_reason.take() {
+                Some(existing) => format!("{}, {}", existing, reason),
+                None => reason,
+            });
+            state.is_ready_for_trading = false;
+            state.is_ready_for_full_position = false;
+        }
+        state
+    }
 }
 
 impl ScoringResult {
@@ -145,6 +181,15 @@ impl ScoringResult {
             recommendation: Recommendation::Avoid,
             position_size_multiplier: 0.0,
             summary: format!("FAIL-CLOSED: {}", reason),
+            cached: false,
+        }
+    }
+
+    /// Result for a token on the operator deny lists
+    pub fn denied(reason: &str) -> Self {
+        Self {
+            summary: format!("DENY-LISTED: {}", reason),
+            ..Self::fail_closed(reason)
         }
     }
 
@@ -229,6 +274,21 @@ impl Recommendation {
         )
     }
 
+    /// Whether this is `min` or a stronger recommendation
+    pub fn at_least(&self, min: Recommendation) -> bool {
+        self.strength() >= min.strength()
+    }
+
+    fn strength(&self) -> u8 {
+        match self {
+            Recommendation::StrongBuy => 4,
+            Recommendation::Opportunity => 3,
+            Recommendation::Probe => 2,
+            Recommendation::Observe => 1,
+            Recommendation::Avoid => 0,
+        }
+    }
+
     /// Get position size multiplier for this recommendation
     pub fn position_multiplier(&self) -> f64 {
         match self {
@@ -269,6 +329,13 @@ pub struct ScoringThresholds {
     pub min_enriched_components: u32,
     /// Minimum time since launch before trading (seconds)
     pub min_time_since_launch_secs: u64,
+    /// No new entries past this bonding curve progress (graduation risk)
+    #[serde(default = "default_max_curve_progress_pct")]
+    pub max_curve_progress_pct: f64,
+}
+
+fn default_max_curve_progress_pct() -> f64 {
+    80.0
 }
 
 impl Default for ScoringThresholds {
@@ -282,6 +349,7 @@ impl Default for ScoringThresholds {
             min_data_completeness: 0.3,    // Lowered from 0.5 - trade with less data
             min_enriched_components: 1,    // Lowered from 2 - need less enrichment
             min_time_since_launch_secs: 5, // Lowered from 15s - faster entry
+            max_curve_progress_pct: default_max_curve_progress_pct(),
         }
     }
 }
@@ -321,6 +389,24 @@ impl ScoringEngine {
         self.weights = weights;
     }
 
+    /// Score thresholds in use
+    pub fn thresholds(&self) -> &ScoringThresholds {
+        &self.thresholds
+    }
+
+    /// Add `bonus` to a result's score and grade it again
+    pub fn add_bonus(&self, result: &mut ScoringResult, bonus: f64, reason: &str) {
+        result.score += bonus;
+        result.recommendation = self.score_to_recommendation(result.score, result.confidence);
+        result.position_size_multiplier = self.calculate_position_multiplier(
+            result.score,
+            result.confidence,
+            result.risk_score,
+            &result.recommendation,
+        );
+        result.summary = format!("{} ({} {:+.2})", result.summary, reason, bonus);
+    }
+
     /// Get effective weight for a signal type
     fn get_weight(&self, signal_type: SignalType) -> f64 {
         self.weights
@@ -407,6 +493,7 @@ impl ScoringEngine {
             recommendation,
             position_size_multiplier,
             summary,
+            cached: false,
         }
     }
 
@@ -567,6 +654,7 @@ impl Default for ScoringEngine {
 mod tests {
     use super::*;
     use crate::filter::signals::Signal;
+    use crate::filter::types::DiscoverySource;
 
     #[test]
     fn test_scoring_empty() {
@@ -671,6 +759,14 @@ mod tests {
         assert_eq!(Recommendation::Avoid.position_multiplier(), 0.0);
     }
 
+    #[test]
+    fn test_recommendation_at_least() {
+        assert!(Recommendation::StrongBuy.at_least(Recommendation::Probe));
+        assert!(Recommendation::Probe.at_least(Recommendation::Probe));
+        assert!(!Recommendation::Observe.at_least(Recommendation::Probe));
+        assert!(!Recommendation::Probe.at_least(Recommendation::Opportunity));
+    }
+
     #[test]
     fn test_fail_closed() {
         let result = ScoringResult::fail_closed("Protocol decode error");
@@ -699,6 +795,41 @@ mod tests {
         assert!(state.is_ready_for_full_position);
     }
 
+    #[test]
+    fn test_readiness_for_context() {
+        let thresholds = ScoringThresholds::default();
+        let fresh = SignalContext::from_new_token(
+            "mint".to_string(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            String::new(),
+            "creator".to_string(),
+            "curve".to_string(),
+            0,
+            1_073_000_000,
+            30,
+            28.0,
+        );
+
+        // Just created: held back by the launch delay
+        let state = ReadinessState::evaluate_for_context(&thresholds, &fresh, 0.8, 4);
+        assert!(!state.is_ready_for_trading);
+
+        // Seen mid-curve with unknown age: no launch delay
+        let mid_curve = fresh
+            .clone()
+            .with_discovery(DiscoverySource::TradeEvent, None);
+        let state = ReadinessState::evaluate_for_context(&thresholds, &mid_curve, 0.8, 4);
+        assert!(state.is_ready_for_full_position);
+
+        // Near graduation: not ready whatever the data says
+        let mut late = mid_curve;
+        late.curve_progress_pct = 95.0;
+        let state = ReadinessState::evaluate_for_context(&thresholds, &late, 0.8, 4);
+        assert!(!state.is_ready_for_trading);
+        assert!(state.readiness_reason.unwrap().contains("curve"));
+    }
+
     #[test]
     fn test_custom_weights() {
         let mut engine = ScoringEngine::new();
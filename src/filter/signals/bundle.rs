@@ -0,0 +1,112 @@
+//! Bundled launch signal provider
+//!
+//! Reads the bundles the `BundledDetector` found in each launch's early buy
+//! window. A flagged launch gets a strong negative signal; launches still
+//! inside their window (or clean ones) contribute nothing.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::filter::bundled_detection::{BundleDetectionReason, BundleGroup, BundledDetector};
+use crate::filter::signals::{Signal, SignalProvider, SignalType};
+use crate::filter::types::SignalContext;
+
+/// Bundle signal provider backed by the detector's known bundles
+pub struct BundleSignalProvider {
+    detector: Arc<BundledDetector>,
+}
+
+impl BundleSignalProvider {
+    pub fn new(detector: Arc<BundledDetector>) -> Self {
+        Self { detector }
+    }
+}
+
+/// Turn a detected bundle into a scoring signal
+fn bundle_signal(bundle: &BundleGroup) -> Signal {
+    // Several independent heuristics agreeing is as bad as it gets
+    let value = match bundle.detection_reason {
+        BundleDetectionReason::Multiple(_) => -1.0,
+        _ => -0.8,
+    };
+    Signal::new(
+        SignalType::BundledBuys,
+        value,
+        0.9,
+        format!(
+            "Bundled launch: {} wallets bought {:.2} SOL ({:?})",
+            bundle.wallets.len(),
+            bundle.total_buy_sol,
+            bundle.detection_reason
+        ),
+    )
+}
+
+#[async_trait]
+impl SignalProvider for BundleSignalProvider {
+    fn name(&self) -> &'static str {
+        "bundle"
+    }
+
+    fn signal_types(&self) -> &[SignalType] {
+        &[SignalType::BundledBuys]
+    }
+
+    fn is_hot_path(&self) -> bool {
+        true
+    }
+
+    async fn compute_token_signals(&self, context: &SignalContext) -> Vec<Signal> {
+        self.detector
+            .get_bundle(&context.mint)
+            .map(|bundle| vec![bundle_signal(&bundle)])
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::bundled_detection::{BundledDetectionConfig, EarlyBuy};
+    use chrono::Utc;
+
+    fn context(mint: &str) -> SignalContext {
+        SignalContext::from_trade(mint.to_string(), "curve".to_string(), 0, 0, 0.0)
+    }
+
+    #[tokio::test]
+    async fn test_flagged_launch_is_negative() {
+        let detector = Arc::new(BundledDetector::new(
+            BundledDetectionConfig::default(),
+            None,
+        ));
+        let provider = BundleSignalProvider::new(detector.clone());
+
+        detector.open_window("mint1");
+        for wallet in ["a", "b"] {
+            detector.record_early_buy(
+                "mint1",
+                EarlyBuy {
+                    wallet: wallet.to_string(),
+                    amount_sol: 0.5,
+                    slot: None,
+                    timestamp: Utc::now(),
+                    signature: format!("sig-{}", wallet),
+                },
+            );
+        }
+        assert!(provider
+            .compute_token_signals(&context("mint1"))
+            .await
+            .is_empty());
+
+        detector.close_window("mint1").await.unwrap();
+        let signals = provider.compute_token_signals(&context("mint1")).await;
+        assert_eq!(signals.len(), 1);
+        assert!(signals[0].value <= -0.8);
+        assert!(provider
+            .compute_token_signals(&context("mint2"))
+            .await
+            .is_empty());
+    }
+}
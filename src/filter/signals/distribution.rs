@@ -0,0 +1,400 @@
+//! Holder distribution signal provider
+//!
+//! Uses the holder lists that the enrichment service caches from Helius.
+//! The bonding curve's own token account is excluded, so percentages are
+//! of circulating supply. Known snipers come from the cache's KnownActors.
+//!
+//! For held positions the position-signal pass compares the holders cached
+//! by the latest Critical refresh with the distribution at entry and reports
+//! how far the top holder's share has moved (`HolderConcentration`).
+
+use async_trait::async_trait;
+use chrono::Utc;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::filter::cache::FilterCache;
+use crate::filter::signals::{Signal, SignalProvider, SignalType};
+use crate::filter::types::{
+    HolderInfo, PositionSignalContext, SignalContext, TokenDistribution, TokenHolderInfo,
+    WalletType,
+};
+use crate::trading::transaction::derive_bonding_curve;
+
+/// Holders with at least this share are whales
+const WHALE_PCT: f64 = 5.0;
+/// Holders below this share count as retail
+const RETAIL_PCT: f64 = 1.0;
+/// Holder count for full signal confidence
+const FULL_CONFIDENCE_HOLDERS: f64 = 20.0;
+/// Top holders kept in `TokenDistribution::holders`
+const TOP_HOLDERS_KEPT: usize = 10;
+/// Rise in the top holder's share (points) that scores as extreme risk
+const FULL_RISK_RISE_PCT: f64 = 40.0;
+
+/// Distribution signal provider backed by cached holder lists
+pub struct DistributionSignalProvider {
+    cache: Arc<FilterCache>,
+}
+
+impl DistributionSignalProvider {
+    pub fn new(cache: Arc<FilterCache>) -> Self {
+        Self { cache }
+    }
+
+    /// Build the distribution for a token (None until holders are cached)
+    pub async fn distribution(
+        &self,
+        mint: &str,
+        creator: &str,
+        bonding_curve: &str,
+    ) -> Option<TokenDistribution> {
+        let holders = self.cache.get_holders(mint)?;
+
+        let mut snipers = HashSet::new();
+        for holder in &holders {
+            if self.cache.is_known_sniper(&holder.address).await {
+                snipers.insert(holder.address.clone());
+            }
+        }
+
+        compute_distribution(mint, &holders, creator, bonding_curve, &snipers)
+    }
+
+    /// Distribution of a held token, its bonding curve derived from the mint
+    pub async fn position_distribution(&self, mint: &str) -> Option<TokenDistribution> {
+        let curve = Pubkey::from_str(mint)
+            .ok()
+            .and_then(|mint| derive_bonding_curve(&mint).ok())
+            .map(|(pda, _)| pda.to_string())
+            .unwrap_or_default();
+        self.distribution(mint, "", &curve).await
+    }
+}
+
+/// HolderConcentration signal for a held token: how far the top holder's
+/// share moved since entry
+fn concentration_signal(entry: &TokenDistribution, current: &TokenDistribution) -> Signal {
+    let rise = current.top_holder_pct - entry.top_holder_pct;
+    let confidence = (current.holder_count as f64 / FULL_CONFIDENCE_HOLDERS).clamp(0.3, 1.0);
+    Signal::new(
+        SignalType::HolderConcentration,
+        (-rise / FULL_RISK_RISE_PCT).clamp(-1.0, 0.3),
+        confidence,
+        format!(
+            "Top holder {:.1}% at entry, now {:.1}% ({:+.1} pts, {} holders)",
+            entry.top_holder_pct, current.top_holder_pct, rise, current.holder_count
+        ),
+    )
+}
+
+/// Gini coefficient of holdings (0 = equal, 1 = one wallet holds everything)
+pub fn gini_coefficient(amounts: &[u64]) -> f64 {
+    let n = amounts.len();
+    let total: u128 = amounts.iter().map(|&a| a as u128).sum();
+    if n < 2 || total == 0 {
+        return 0.0;
+    }
+
+    let mut sorted = amounts.to_vec();
+    sorted.sort_unstable();
+    let weighted: u128 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &a)| (i as u128 + 1) * a as u128)
+        .sum();
+
+    let n = n as f64;
+    (2.0 * weighted as f64 / (n * total as f64) - (n + 1.0) / n).clamp(0.0, 1.0)
+}
+
+/// Compute distribution metrics, excluding the bonding curve's holdings
+pub fn compute_distribution(
+    mint: &str,
+    holders: &[TokenHolderInfo],
+    creator: &str,
+    bonding_curve: &str,
+    snipers: &HashSet<String>,
+) -> Option<TokenDistribution> {
+    let mut circulating: Vec<&TokenHolderInfo> = holders
+        .iter()
+        .filter(|h| h.address != bonding_curve && h.amount > 0)
+        .collect();
+    let total: u64 = circulating.iter().map(|h| h.amount).sum();
+    if total == 0 {
+        return None;
+    }
+    circulating.sort_by(|a, b| b.amount.cmp(&a.amount));
+
+    let pct = |amount: u64| amount as f64 / total as f64 * 100.0;
+    let top_pct = |n: usize| pct(circulating.iter().take(n).map(|h| h.amount).sum());
+    let deployer_holdings_pct = pct(circulating
+        .iter()
+        .filter(|h| h.address == creator)
+        .map(|h| h.amount)
+        .sum());
+    let sniper_holdings_pct = pct(circulating
+        .iter()
+        .filter(|h| snipers.contains(&h.address))
+        .map(|h| h.amount)
+        .sum());
+
+    let amounts: Vec<u64> = circulating.iter().map(|h| h.amount).collect();
+    let holders_info = circulating
+        .iter()
+        .take(TOP_HOLDERS_KEPT)
+        .map(|h| {
+            let share = pct(h.amount);
+            let wallet_type = if h.address == creator {
+                WalletType::Deployer
+            } else if snipers.contains(&h.address) {
+                WalletType::Sniper
+            } else if share >= WHALE_PCT {
+                WalletType::Whale
+            } else {
+                WalletType::Retail
+            };
+            HolderInfo {
+                address: h.address.clone(),
+                balance: h.amount,
+                pct_of_supply: share,
+                acquisition_time: None,
+                wallet_type: Some(wallet_type),
+            }
+        })
+        .collect();
+
+    Some(TokenDistribution {
+        mint: mint.to_string(),
+        total_supply: total,
+        holder_count: circulating.len() as u32,
+        top_holder_pct: top_pct(1),
+        top_5_holders_pct: top_pct(5),
+        top_10_holders_pct: top_pct(10),
+        gini_coefficient: gini_coefficient(&amounts),
+        deployer_holdings_pct,
+        sniper_holdings_pct,
+        retail_holdings_pct: pct(circulating
+            .iter()
+            .filter(|h| pct(h.amount) < RETAIL_PCT)
+            .map(|h| h.amount)
+            .sum()),
+        holders: holders_info,
+        fetched_at: Utc::now(),
+    })
+}
+
+/// Turn a distribution into scoring signals
+fn distribution_signals(dist: &TokenDistribution) -> Vec<Signal> {
+    let confidence = (dist.holder_count as f64 / FULL_CONFIDENCE_HOLDERS).clamp(0.3, 1.0);
+    let mut signals = Vec::new();
+
+    signals.push(Signal::new(
+        SignalType::SupplyDispersion,
+        (0.6 - dist.gini_coefficient) * 1.5,
+        confidence,
+        format!(
+            "Gini {:.2} across {} holders",
+            dist.gini_coefficient, dist.holder_count
+        ),
+    ));
+
+    let (value, reason) = if dist.top_holder_pct > 50.0 {
+        (-0.8, format!("Top holder owns {:.1}%", dist.top_holder_pct))
+    } else if dist.top_5_holders_pct > 70.0 {
+        (
+            -0.5,
+            format!("Top 5 holders own {:.1}%", dist.top_5_holders_pct),
+        )
+    } else if dist.top_10_holders_pct > 85.0 {
+        (
+            -0.3,
+            format!("Top 10 holders own {:.1}%", dist.top_10_holders_pct),
+        )
+    } else if dist.top_10_holders_pct < 50.0 {
+        (
+            0.3,
+            format!("Top 10 holders own only {:.1}%", dist.top_10_holders_pct),
+        )
+    } else {
+        (
+            0.0,
+            format!("Top 10 holders own {:.1}%", dist.top_10_holders_pct),
+        )
+    };
+    signals.push(Signal::new(
+        SignalType::ConcentrationRisk,
+        value,
+        confidence,
+        reason,
+    ));
+
+    let insider_pct = dist.deployer_holdings_pct + dist.sniper_holdings_pct;
+    signals.push(Signal::new(
+        SignalType::EarlyAccumulation,
+        -(insider_pct / 50.0).min(1.0),
+        confidence,
+        format!(
+            "Deployer {:.1}% + snipers {:.1}% of circulating supply",
+            dist.deployer_holdings_pct, dist.sniper_holdings_pct
+        ),
+    ));
+
+    signals
+}
+
+#[async_trait]
+impl SignalProvider for DistributionSignalProvider {
+    fn name(&self) -> &'static str {
+        "distribution"
+    }
+
+    fn signal_types(&self) -> &[SignalType] {
+        &[
+            SignalType::SupplyDispersion,
+            SignalType::ConcentrationRisk,
+            SignalType::EarlyAccumulation,
+        ]
+    }
+
+    fn is_hot_path(&self) -> bool {
+        false
+    }
+
+    async fn compute_token_signals(&self, context: &SignalContext) -> Vec<Signal> {
+        match self
+            .distribution(&context.mint, &context.creator, &context.bonding_curve)
+            .await
+        {
+            Some(dist) => distribution_signals(&dist),
+            None => self
+                .signal_types()
+                .iter()
+                .map(|&signal_type| Signal::unavailable(signal_type, "Holder data not cached"))
+                .collect(),
+        }
+    }
+
+    /// Re-evaluate concentration against the distribution at entry
+    /// (`context.holder_distribution`)
+    async fn compute_position_signals(&self, context: &PositionSignalContext) -> Vec<Signal> {
+        let signal = match (
+            &context.holder_distribution,
+            self.position_distribution(&context.mint).await,
+        ) {
+            (Some(entry), Some(current)) => concentration_signal(entry, &current),
+            (None, _) => {
+                Signal::unavailable(SignalType::HolderConcentration, "No holder data from entry")
+            }
+            (_, None) => {
+                Signal::unavailable(SignalType::HolderConcentration, "Holder data not cached")
+            }
+        };
+        vec![signal]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(address: &str, amount: u64) -> TokenHolderInfo {
+        TokenHolderInfo {
+            address: address.to_string(),
+            amount,
+            percentage: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_gini() {
+        assert_eq!(gini_coefficient(&[100, 100, 100, 100]), 0.0);
+        assert!(gini_coefficient(&[0, 0, 0, 1_000]) > 0.7);
+        assert_eq!(gini_coefficient(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_excludes_bonding_curve() {
+        let holders = vec![
+            holder("curve", 800),
+            holder("creator", 100),
+            holder("sniper", 50),
+            holder("a", 25),
+            holder("b", 25),
+        ];
+        let snipers: HashSet<String> = ["sniper".to_string()].into_iter().collect();
+
+        let dist = compute_distribution("mint", &holders, "creator", "curve", &snipers).unwrap();
+        assert_eq!(dist.holder_count, 4);
+        assert_eq!(dist.total_supply, 200);
+        assert!((dist.top_holder_pct - 50.0).abs() < 1e-9);
+        assert!((dist.deployer_holdings_pct - 50.0).abs() < 1e-9);
+        assert!((dist.sniper_holdings_pct - 25.0).abs() < 1e-9);
+        assert_eq!(dist.holders[0].wallet_type, Some(WalletType::Deployer));
+    }
+
+    #[test]
+    fn test_curve_only_is_unavailable() {
+        let holders = vec![holder("curve", 1_000)];
+        assert!(compute_distribution("mint", &holders, "c", "curve", &HashSet::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_without_holders() {
+        let provider = DistributionSignalProvider::new(Arc::new(FilterCache::new()));
+        let context = SignalContext::from_new_token(
+            "mint".to_string(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            "uri".to_string(),
+            "creator".to_string(),
+            "curve".to_string(),
+            0,
+            0,
+            0,
+            0.0,
+        );
+
+        let signals = provider.compute_token_signals(&context).await;
+        assert_eq!(signals.len(), 3);
+        assert!(signals.iter().all(|s| s.confidence == 0.0));
+    }
+
+    #[tokio::test]
+    async fn test_position_concentration_follows_refresh() {
+        let cache = Arc::new(FilterCache::new());
+        let provider = DistributionSignalProvider::new(cache.clone());
+        let mut holders: Vec<TokenHolderInfo> =
+            (0..10).map(|i| holder(&format!("h{}", i), 100)).collect();
+        cache.set_holders("mint", holders.clone());
+        let entry = provider.position_distribution("mint").await.unwrap();
+        assert!((entry.top_holder_pct - 10.0).abs() < 1e-9);
+
+        let mut context = PositionSignalContext {
+            mint: "mint".to_string(),
+            entry_time: Utc::now(),
+            entry_price: 1.0,
+            current_price: 1.0,
+            position_size_sol: 0.1,
+            unrealized_pnl_pct: 0.0,
+            recent_trades: Vec::new(),
+            holder_distribution: Some(entry),
+            order_flow: None,
+        };
+        let signals = provider.compute_position_signals(&context).await;
+        assert_eq!(signals[0].value, 0.0);
+
+        // A whale buys up to 50% and the refresh lands in the cache
+        holders.push(holder("whale", 1_000));
+        cache.set_holders("mint", holders);
+        let signals = provider.compute_position_signals(&context).await;
+        assert_eq!(signals[0].signal_type, SignalType::HolderConcentration);
+        assert!((signals[0].value + 1.0).abs() < 1e-9, "{}", signals[0]);
+
+        context.holder_distribution = None;
+        let signals = provider.compute_position_signals(&context).await;
+        assert_eq!(signals[0].confidence, 0.0);
+    }
+}
@@ -413,11 +413,8 @@ impl SignalProvider for EarlyMomentumSignalProvider {
             signals.push(self.compute_first_trades(&context.mint));
         }
 
-        // Bonding curve position (use virtual_sol_reserves as proxy if available)
-        // This would need to be passed via context
-        if let Some(bc_pct) = context.bonding_curve_pct {
-            signals.push(self.compute_bonding_curve(bc_pct));
-        }
+        // Bonding curve position
+        signals.push(self.compute_bonding_curve(context.curve_progress_pct));
 
         // Creator buyback
         if self.config.creator_buying_back {
@@ -0,0 +1,495 @@
+//! Creator funding-source signal provider
+//!
+//! Traces where the creator's SOL came from, one or two hops back, using
+//! Helius funding transfers. Each funder is classified as an exchange hot
+//! wallet, a bridge, a known deployer / sniper (KnownActors), a fresh wallet
+//! or unknown. A creator funded minutes before launch by a fresh wallet or
+//! by another deployer is the classic rug setup.
+//!
+//! The same funders show up launch after launch, so every traced wallet's
+//! funder is cached for `cache_ttl_secs`, and exchange / bridge wallets are
+//! never fetched at all.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use dashmap::DashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::filter::adaptive::config::FundingConfig;
+use crate::filter::cache::FilterCache;
+use crate::filter::helius::{HeliusClient, SolTransfer};
+use crate::filter::signals::{Signal, SignalProvider, SignalType};
+use crate::filter::types::SignalContext;
+
+/// Well-known exchange hot wallets (extend with `cex_wallets`)
+const CEX_WALLETS: &[(&str, &str)] = &[
+    ("5tzFkiKscXHK5ZXCGbXZxdw7gTjjD1mBwuoFbhUvuAi9", "Binance"),
+    ("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM", "Binance"),
+    ("2ojv9BAiHUrvsm9gxDe7fJSzbNZSJcxZvf8dqmWGHG8S", "Binance"),
+    ("H8sMJSCQxfKiFTCfDR3DUMLPwcRbM61LGFJ8N4dK3WjS", "Coinbase"),
+    ("GJRs4FwHtemZ5ZE9x3FNvJ8TMwitKTh21yxdRPqn7npE", "Coinbase"),
+    ("2AQdpHJ2JpcEgPiATUXjQxA8QmafFegfQwSLWSprPicm", "Coinbase"),
+    ("5VCwKtCXgCJ6kit5FybXjvriW3xELsFDhYrPSqtJNmcD", "OKX"),
+    ("is6MTRHEgyFLNTfYcuV4QBWLjrZBfmhVNYR6ccgr8KV", "OKX"),
+    ("AC5RDfQFmDS1deWZos921JfqscXdByf8BKHs5ACWjtW2", "Bybit"),
+    ("FWznbcNXWQuHTawe9RxvQ2LdCENssh12dsznf4RiouN5", "Kraken"),
+    ("BmFdpraQhkiDQE6SnfG5omcA1VwzqfXrwtNYBwWTymy6", "KuCoin"),
+    ("ASTyfSima4LLAdDgoFGkgqoKowG1LZFDr9fAQrg7iaJZ", "MEXC"),
+    ("u6PJ8DtQuPFnfmwHbGFULQ4u4EgjDiyYKjVEsynXq2w", "Gate"),
+    ("A77HErqtfN1hLLpvZ9pCtu66FEtM8BveoaKbbMoZ4RiR", "Bitget"),
+];
+
+/// Transactions fetched per wallet when looking for its funder
+const FUNDING_TX_LIMIT: u32 = 50;
+/// Creators funded by the same wallet before it counts as a cluster
+const CLUSTER_MIN_CREATORS: usize = 2;
+/// Funders tracked for clustering before stale ones are dropped
+const MAX_TRACKED_FUNDERS: usize = 10_000;
+
+/// What kind of wallet funded the one being traced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FundingSource {
+    /// Exchange hot wallet (named)
+    Cex(String),
+    /// Bridge payout wallet
+    Bridge,
+    /// In the known deployer list
+    KnownDeployer,
+    /// In the known sniper list
+    KnownSniper,
+    /// Was itself funded within `fresh_wallet_secs`
+    FreshWallet,
+    /// Nothing notable
+    Unknown,
+}
+
+impl FundingSource {
+    /// Whether tracing should stop at this source
+    fn is_terminal(&self) -> bool {
+        !matches!(self, FundingSource::FreshWallet | FundingSource::Unknown)
+    }
+
+    /// Higher = worse (picks the signal when hops disagree)
+    fn severity(&self) -> u8 {
+        match self {
+            FundingSource::KnownDeployer => 5,
+            FundingSource::KnownSniper => 4,
+            FundingSource::FreshWallet => 3,
+            FundingSource::Bridge => 2,
+            FundingSource::Unknown => 1,
+            FundingSource::Cex(_) => 0,
+        }
+    }
+}
+
+/// The largest recent incoming transfer to a wallet
+#[derive(Debug, Clone)]
+pub struct FundingHop {
+    pub funder: String,
+    pub amount_sol: f64,
+    pub funded_at: Option<DateTime<Utc>>,
+}
+
+/// One classified hop of a funding trace (hop 1 = creator's funder)
+#[derive(Debug, Clone)]
+pub struct TracedHop {
+    pub hop: FundingHop,
+    pub source: FundingSource,
+}
+
+/// Cached funder lookup (None = no funding found)
+struct CachedFunder {
+    hop: Option<FundingHop>,
+    fetched_at: Instant,
+}
+
+/// Creators a funder has bankrolled
+struct FundedCreators {
+    creators: HashSet<String>,
+    last_seen: Instant,
+}
+
+/// Pick the wallet's funder from its incoming transfers
+pub fn select_funder(wallet: &str, transfers: &[SolTransfer], min_sol: f64) -> Option<FundingHop> {
+    transfers
+        .iter()
+        .filter(|t| t.from != wallet && t.amount_sol >= min_sol)
+        .max_by(|a, b| a.amount_sol.total_cmp(&b.amount_sol))
+        .map(|t| FundingHop {
+            funder: t.from.clone(),
+            amount_sol: t.amount_sol,
+            funded_at: t.timestamp,
+        })
+}
+
+/// Creator funding-source provider (background tier, calls Helius)
+pub struct FundingSourceProvider {
+    helius: Arc<HeliusClient>,
+    cache: Arc<FilterCache>,
+    config: FundingConfig,
+    /// wallet -> its funder
+    funders: DashMap<String, CachedFunder>,
+    /// funder -> creators it funded (for clustering)
+    funded_creators: DashMap<String, FundedCreators>,
+}
+
+impl FundingSourceProvider {
+    pub fn new(helius: Arc<HeliusClient>, cache: Arc<FilterCache>, config: FundingConfig) -> Self {
+        Self {
+            helius,
+            cache,
+            config,
+            funders: DashMap::new(),
+            funded_creators: DashMap::new(),
+        }
+    }
+
+    fn cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.config.cache_ttl_secs)
+    }
+
+    /// Exchange name if the address is a known hot wallet
+    fn cex_name(&self, address: &str) -> Option<String> {
+        CEX_WALLETS
+            .iter()
+            .find(|(wallet, _)| *wallet == address)
+            .map(|(_, name)| name.to_string())
+            .or_else(|| {
+                self.config
+                    .cex_wallets
+                    .iter()
+                    .any(|w| w == address)
+                    .then(|| "CEX".to_string())
+            })
+    }
+
+    /// The wallet's funder (cached per wallet)
+    async fn funder_of(&self, wallet: &str) -> Option<FundingHop> {
+        if let Some(cached) = self.funders.get(wallet) {
+            if cached.fetched_at.elapsed() < self.cache_ttl() {
+                return cached.hop.clone();
+            }
+        }
+
+        let hop = match self
+            .helius
+            .get_funding_transfers(wallet, FUNDING_TX_LIMIT)
+            .await
+        {
+            Ok(transfers) => select_funder(wallet, &transfers, self.config.min_funding_sol),
+            Err(e) => {
+                // Not cached: retry on the next launch that needs it
+                warn!(wallet = %wallet, error = %e, "Failed to fetch funding transfers");
+                return None;
+            }
+        };
+
+        if self.funders.len() >= MAX_TRACKED_FUNDERS {
+            let ttl = self.cache_ttl();
+            self.funders.retain(|_, c| c.fetched_at.elapsed() < ttl);
+        }
+        self.funders.insert(
+            wallet.to_string(),
+            CachedFunder {
+                hop: hop.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        hop
+    }
+
+    /// Classify a funding wallet
+    async fn classify(&self, funder: &str, now: DateTime<Utc>) -> FundingSource {
+        if let Some(name) = self.cex_name(funder) {
+            return FundingSource::Cex(name);
+        }
+        if self.config.bridge_wallets.iter().any(|w| w == funder) {
+            return FundingSource::Bridge;
+        }
+        if self.cache.is_known_deployer(funder).await {
+            return FundingSource::KnownDeployer;
+        }
+        if self.cache.is_known_sniper(funder).await {
+            return FundingSource::KnownSniper;
+        }
+
+        let fresh_cutoff = now - ChronoDuration::seconds(self.config.fresh_wallet_secs as i64);
+        match self.funder_of(funder).await.and_then(|hop| hop.funded_at) {
+            Some(funded_at) if funded_at > fresh_cutoff => FundingSource::FreshWallet,
+            _ => FundingSource::Unknown,
+        }
+    }
+
+    /// Follow the creator's funding back up to `max_hops`
+    pub async fn trace(&self, creator: &str, now: DateTime<Utc>) -> Vec<TracedHop> {
+        let mut hops = Vec::new();
+        let mut wallet = creator.to_string();
+
+        for _ in 0..self.config.max_hops.clamp(1, 2) {
+            let hop = match self.funder_of(&wallet).await {
+                Some(hop) => hop,
+                None => break,
+            };
+            let source = self.classify(&hop.funder, now).await;
+            let terminal = source.is_terminal();
+            wallet = hop.funder.clone();
+            hops.push(TracedHop { hop, source });
+            if terminal {
+                break;
+            }
+        }
+        hops
+    }
+
+    /// The creator's cached funder, unless it is an exchange or bridge
+    ///
+    /// Never calls Helius: creators not traced yet have no cluster.
+    pub fn funding_cluster(&self, creator: &str) -> Option<String> {
+        let cached = self.funders.get(creator)?;
+        if cached.fetched_at.elapsed() >= self.cache_ttl() {
+            return None;
+        }
+        let funder = cached.hop.as_ref()?.funder.clone();
+        drop(cached);
+        let shared_funder =
+            self.cex_name(&funder).is_none() && !self.config.bridge_wallets.contains(&funder);
+        shared_funder.then_some(funder)
+    }
+
+    /// Remember that `funder` bankrolled `creator`, returning how many creators it funded
+    fn record_funded_creator(&self, funder: &str, creator: &str) -> usize {
+        if self.funded_creators.len() >= MAX_TRACKED_FUNDERS {
+            let ttl = self.cache_ttl();
+            self.funded_creators
+                .retain(|_, f| f.last_seen.elapsed() < ttl);
+        }
+        let mut entry = self
+            .funded_creators
+            .entry(funder.to_string())
+            .or_insert_with(|| FundedCreators {
+                creators: HashSet::new(),
+                last_seen: Instant::now(),
+            });
+        entry.creators.insert(creator.to_string());
+        entry.last_seen = Instant::now();
+        entry.creators.len()
+    }
+}
+
+/// CoordinatedFunding signal for a trace
+pub fn funding_signal(
+    hops: &[TracedHop],
+    launched_at: DateTime<Utc>,
+    config: &FundingConfig,
+) -> Signal {
+    let worst = match hops
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, h)| h.source.severity())
+    {
+        Some(worst) => worst,
+        None => {
+            return Signal::unavailable(SignalType::CoordinatedFunding, "No creator funding found")
+        }
+    };
+    let (index, traced) = worst;
+    let hop_no = index + 1;
+    let funder = &traced.hop.funder;
+
+    // Creator topped up right before launch
+    let just_funded = hops[0]
+        .hop
+        .funded_at
+        .map(|at| launched_at - at <= ChronoDuration::seconds(config.recent_funding_secs as i64))
+        .unwrap_or(false);
+
+    let (value, confidence, reason) = match traced.source {
+        FundingSource::KnownDeployer => (
+            if hop_no == 1 { -1.0 } else { -0.8 },
+            0.95,
+            format!(
+                "Creator funded by known deployer {} (hop {})",
+                funder, hop_no
+            ),
+        ),
+        FundingSource::KnownSniper => (
+            if hop_no == 1 { -0.6 } else { -0.4 },
+            0.9,
+            format!("Creator funded by known sniper {} (hop {})", funder, hop_no),
+        ),
+        FundingSource::FreshWallet if just_funded => (
+            -0.6,
+            0.8,
+            format!(
+                "Creator funded just before launch by fresh wallet {} (hop {})",
+                funder, hop_no
+            ),
+        ),
+        FundingSource::FreshWallet => (
+            -0.3,
+            0.7,
+            format!("Creator funded by fresh wallet {} (hop {})", funder, hop_no),
+        ),
+        FundingSource::Bridge => (
+            0.0,
+            0.5,
+            format!("Creator funded via bridge {} (hop {})", funder, hop_no),
+        ),
+        FundingSource::Unknown => (
+            0.0,
+            0.4,
+            format!("Creator funded by {} (hop {})", funder, hop_no),
+        ),
+        FundingSource::Cex(ref name) => (
+            0.2,
+            0.7,
+            format!("Creator funded from {} (hop {})", name, hop_no),
+        ),
+    };
+    Signal::new(SignalType::CoordinatedFunding, value, confidence, reason)
+}
+
+#[async_trait]
+impl SignalProvider for FundingSourceProvider {
+    fn name(&self) -> &'static str {
+        "funding"
+    }
+
+    fn signal_types(&self) -> &[SignalType] {
+        &[SignalType::CoordinatedFunding, SignalType::WalletClustering]
+    }
+
+    fn is_hot_path(&self) -> bool {
+        false
+    }
+
+    fn max_latency_ms(&self) -> u64 {
+        3000 // Up to three Helius lookups on a cold cache
+    }
+
+    async fn compute_token_signals(&self, context: &SignalContext) -> Vec<Signal> {
+        if !self.config.enabled || context.creator.is_empty() {
+            return Vec::new();
+        }
+        let start = Instant::now();
+        let hops = self.trace(&context.creator, context.timestamp).await;
+        let mut signals =
+            vec![funding_signal(&hops, context.timestamp, &self.config)
+                .with_latency(start.elapsed())];
+
+        // Same non-exchange funder behind several creators = one operator
+        let cluster = hops
+            .iter()
+            .filter(|h| !matches!(h.source, FundingSource::Cex(_) | FundingSource::Bridge))
+            .map(|h| {
+                (
+                    h.hop.funder.as_str(),
+                    self.record_funded_creator(&h.hop.funder, &context.creator),
+                )
+            })
+            .max_by_key(|(_, count)| *count);
+        let clustering = match cluster {
+            Some((funder, count)) if count >= CLUSTER_MIN_CREATORS => {
+                debug!(funder = %funder, creators = count, "Funding cluster detected");
+                Signal::new(
+                    SignalType::WalletClustering,
+                    -0.7,
+                    0.8,
+                    format!("Funder {} has bankrolled {} creators", funder, count),
+                )
+            }
+            Some(_) => Signal::neutral(
+                SignalType::WalletClustering,
+                "Funder not seen behind other creators",
+            ),
+            None => Signal::unavailable(SignalType::WalletClustering, "No traceable funder"),
+        };
+        signals.push(clustering.with_latency(start.elapsed()));
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(from: &str, amount_sol: f64, at: DateTime<Utc>) -> SolTransfer {
+        SolTransfer {
+            signature: format!("sig-{}", from),
+            from: from.to_string(),
+            to: "creator".to_string(),
+            amount_lamports: (amount_sol * 1e9) as u64,
+            amount_sol,
+            timestamp: Some(at),
+        }
+    }
+
+    fn hop(funder: &str, source: FundingSource, funded_at: DateTime<Utc>) -> TracedHop {
+        TracedHop {
+            hop: FundingHop {
+                funder: funder.to_string(),
+                amount_sol: 1.0,
+                funded_at: Some(funded_at),
+            },
+            source,
+        }
+    }
+
+    #[test]
+    fn test_select_funder_skips_dust_and_self() {
+        let now = Utc::now();
+        let transfers = vec![
+            transfer("dust", 0.001, now),
+            transfer("creator", 5.0, now),
+            transfer("small", 0.5, now),
+            transfer("big", 2.0, now),
+        ];
+        let hop = select_funder("creator", &transfers, 0.05).unwrap();
+        assert_eq!(hop.funder, "big");
+        assert!(select_funder("creator", &transfers[..1], 0.05).is_none());
+    }
+
+    #[test]
+    fn test_funding_signal_severity() {
+        let config = FundingConfig::default();
+        let now = Utc::now();
+
+        // Fresh wallet funded by a known deployer: the deployer wins
+        let hops = vec![
+            hop(
+                "fresh",
+                FundingSource::FreshWallet,
+                now - ChronoDuration::minutes(5),
+            ),
+            hop(
+                "rugger",
+                FundingSource::KnownDeployer,
+                now - ChronoDuration::days(3),
+            ),
+        ];
+        let signal = funding_signal(&hops, now, &config);
+        assert_eq!(signal.value, -0.8);
+        assert!(signal.reason.contains("known deployer"));
+
+        // Just-funded by a fresh wallet
+        let hops = vec![hop(
+            "fresh",
+            FundingSource::FreshWallet,
+            now - ChronoDuration::minutes(5),
+        )];
+        assert_eq!(funding_signal(&hops, now, &config).value, -0.6);
+
+        // Straight from an exchange
+        let hops = vec![hop(
+            "binance",
+            FundingSource::Cex("Binance".to_string()),
+            now - ChronoDuration::days(30),
+        )];
+        assert!(funding_signal(&hops, now, &config).value > 0.0);
+
+        assert_eq!(funding_signal(&[], now, &config).confidence, 0.0);
+    }
+}
@@ -15,8 +15,11 @@ pub mod metadata;
 pub mod smart_money;
 pub mod wallet_behavior;
 pub mod early_momentum;
-// pub mod distribution;
-// pub mod order_flow;
+pub mod order_flow;
+pub mod distribution;
+pub mod bundle;
+pub mod funding;
+pub mod registry;
 // pub mod wallet_profile;
 // pub mod pumpfun_specific;
 
@@ -25,6 +28,11 @@ pub use metadata::MetadataSignalProvider;
 pub use smart_money::SmartMoneySignalProvider;
 pub use wallet_behavior::WalletBehaviorSignalProvider;
 pub use early_momentum::EarlyMomentumSignalProvider;
+pub use distribution::DistributionSignalProvider;
+pub use order_flow::OrderFlowSignalProvider;
+pub use bundle::BundleSignalProvider;
+pub use funding::FundingSourceProvider;
+pub use registry::{ProviderDeps, ProviderRegistry};
 
 /// Signal value range: -1.0 (extreme risk) to +1.0 (extreme opportunity)
 pub type SignalValue = f64;
@@ -85,6 +93,10 @@ pub enum SignalType {
     EarlySellPressure,
     /// Sustained organic demand vs artificial pumping
     OrganicDemand,
+    /// Simulated buy-then-sell round trip (fatal if the sell fails)
+    HoneypotSimulation,
+    /// Coordinated bundle of wallets buying right after launch
+    BundledBuys,
 
     // === Token Metadata Signals ===
     /// Token name quality/heuristics
@@ -138,6 +150,8 @@ impl SignalType {
                 | SignalType::VolumeSpike         // From trade stream
                 | SignalType::FirstTradesQuality  // From trade stream
                 | SignalType::CreatorBuyback      // From trade stream
+                | SignalType::HoneypotSimulation  // Pure curve math
+                | SignalType::BundledBuys         // From trade stream
         )
     }
 
@@ -176,6 +190,8 @@ impl SignalType {
             // CRITICAL - Token authority signals
             SignalType::MintAuthority => 2.5, // Can mint more = instant rug
             SignalType::FreezeAuthority => 2.0, // Can freeze accounts
+            SignalType::HoneypotSimulation => 2.5, // Can't sell = total loss
+            SignalType::BundledBuys => 2.0,        // Team wallets dump together
 
             // Holder distribution signals
             SignalType::HolderConcentration => 1.5,
@@ -216,7 +232,9 @@ impl SignalType {
             SignalType::DeployerPattern
             | SignalType::LiquiditySeeding
             | SignalType::EarlySellPressure
-            | SignalType::OrganicDemand => SignalCategory::PumpfunSpecific,
+            | SignalType::OrganicDemand
+            | SignalType::HoneypotSimulation
+            | SignalType::BundledBuys => SignalCategory::PumpfunSpecific,
 
             SignalType::NameQuality | SignalType::SymbolQuality | SignalType::UriAnalysis => {
                 SignalCategory::Metadata
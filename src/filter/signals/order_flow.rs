@@ -0,0 +1,727 @@
+//! Order flow signal provider
+//!
+//! Reads the per-token trade buffer that the PumpPortal trade feed fills in
+//! the filter cache and turns it into buy/sell pressure, wash trading and
+//! burst signals, plus an organic demand signal from how fast new buyers
+//! arrive in the first minutes. The same analysis feeds the strategy engine's
+//! regime classifier. Tokens without a trade feed (hot scan) can carry an aggregate
+//! analysis in `SignalContext::order_flow` instead.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::filter::cache::FilterCache;
+use crate::filter::signals::{Signal, SignalProvider, SignalType};
+use crate::filter::types::{
+    HolderGrowthMetrics, OrderFlowAnalysis, SignalContext, TradeRecord, WashTradingMetrics,
+};
+
+/// Long rolling window (seconds)
+const LONG_WINDOW_SECS: i64 = 60;
+/// Short rolling window (seconds)
+const SHORT_WINDOW_SECS: i64 = 10;
+/// Sells this soon after the first trade count as early sell pressure
+const EARLY_WINDOW_SECS: i64 = 300;
+/// Ratio reported when there is buying but no selling
+const MAX_BUY_SELL_RATIO: f64 = 10.0;
+/// Buy-then-sell cycles before a wallet counts as a wash trader
+const WASH_MIN_CYCLES: u32 = 2;
+/// Opposite-side trades this close together count as a round trip (seconds)
+const ROUND_TRIP_WINDOW_SECS: i64 = 60;
+/// Back-and-forth handoffs before two wallets count as wash partners
+const PARTNER_MIN_HANDOFFS: u32 = 3;
+/// Most active wallets considered for partner detection
+const PARTNER_MAX_WALLETS: usize = 30;
+/// Wallets whose combined volume is checked for concentration
+const CONCENTRATION_WALLETS: usize = 3;
+/// Volume share above which those wallets dominate the token
+const CONCENTRATION_SHARE: f64 = 0.7;
+/// Trades needed before concentration means anything
+const CONCENTRATION_MIN_TRADES: usize = 10;
+/// Holder growth is measured over this much of the token's first trading
+const GROWTH_WINDOW_SECS: f64 = 300.0;
+/// Growth rates are taken over at least this long (seconds)
+const GROWTH_MIN_SPAN_SECS: f64 = 60.0;
+/// Slice length for growth steadiness (seconds)
+const GROWTH_SLICE_SECS: f64 = 30.0;
+/// Trades needed for full signal confidence
+const FULL_CONFIDENCE_TRADES: f64 = 20.0;
+
+/// Order flow signal provider backed by the cache's trade buffer
+pub struct OrderFlowSignalProvider {
+    cache: Arc<FilterCache>,
+}
+
+impl OrderFlowSignalProvider {
+    pub fn new(cache: Arc<FilterCache>) -> Self {
+        Self { cache }
+    }
+
+    /// Buffer a trade from the trade feed (`sol_amount` in lamports)
+    pub fn record_trade(
+        &self,
+        mint: &str,
+        trader: &str,
+        is_buy: bool,
+        sol_amount: u64,
+        token_amount: u64,
+        signature: &str,
+    ) {
+        let now = Utc::now();
+        let time_since_launch_ms = self
+            .cache
+            .first_trade_time(mint)
+            .map(|first| (now - first).num_milliseconds().max(0) as u64)
+            .unwrap_or(0);
+
+        self.cache.record_trade(
+            mint,
+            TradeRecord {
+                trader: trader.to_string(),
+                is_buy,
+                sol_amount,
+                token_amount,
+                timestamp: now,
+                time_since_launch_ms,
+                signature: signature.to_string(),
+            },
+        );
+    }
+
+    /// Analyze buffered trades for a token (None until trades arrive)
+    pub fn analyze(&self, mint: &str) -> Option<OrderFlowAnalysis> {
+        analyze_trades(mint, &self.cache.get_trades(mint), Utc::now())
+    }
+}
+
+fn buy_sell_ratio(buy: f64, sell: f64) -> f64 {
+    if sell > 0.0 {
+        (buy / sell).min(MAX_BUY_SELL_RATIO)
+    } else if buy > 0.0 {
+        MAX_BUY_SELL_RATIO
+    } else {
+        1.0
+    }
+}
+
+fn lamports_to_sol(lamports: u64) -> f64 {
+    lamports as f64 / 1_000_000_000.0
+}
+
+/// Compute order flow metrics from trades (oldest first) as of `now`
+pub fn analyze_trades(
+    mint: &str,
+    trades: &[TradeRecord],
+    now: DateTime<Utc>,
+) -> Option<OrderFlowAnalysis> {
+    let first = trades.first()?.timestamp;
+    let within = |secs: i64| {
+        trades
+            .iter()
+            .filter(move |t| now - t.timestamp <= Duration::seconds(secs))
+    };
+    let volumes = |secs: i64| {
+        within(secs).fold((0.0, 0.0), |(buy, sell), t| {
+            let sol = lamports_to_sol(t.sol_amount);
+            if t.is_buy {
+                (buy + sol, sell)
+            } else {
+                (buy, sell + sol)
+            }
+        })
+    };
+
+    let (buy_60, sell_60) = volumes(LONG_WINDOW_SECS);
+    let (buy_10, sell_10) = volumes(SHORT_WINDOW_SECS);
+
+    let buy_count = trades.iter().filter(|t| t.is_buy).count();
+    let unique_buyers = trades
+        .iter()
+        .filter(|t| t.is_buy)
+        .map(|t| t.trader.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+
+    let wash = wash_metrics(trades);
+    let since_first = |t: &TradeRecord| (t.timestamp - first).num_milliseconds() as f64 / 1000.0;
+    let holder_growth = holder_growth(
+        trades
+            .iter()
+            .filter(|t| t.is_buy)
+            .map(|t| (t.trader.as_str(), since_first(t))),
+        (now - first).num_milliseconds() as f64 / 1000.0,
+    );
+    let wash_cycles = wash_cycle_count(trades);
+    let wash_trading_score = wash.circular_fraction();
+
+    // Burst: short-window trade rate against the rate over the long window
+    // (or the token's whole life if it is younger than that)
+    let trades_60 = within(LONG_WINDOW_SECS).count();
+    let trades_10 = within(SHORT_WINDOW_SECS).count();
+    let span_secs = ((now - first).num_milliseconds() as f64 / 1000.0)
+        .clamp(SHORT_WINDOW_SECS as f64, LONG_WINDOW_SECS as f64);
+    let rate_long = trades_60 as f64 / span_secs;
+    let rate_short = trades_10 as f64 / SHORT_WINDOW_SECS as f64;
+    let burst_ratio = if rate_long > 0.0 {
+        rate_short / rate_long
+    } else {
+        0.0
+    };
+    let burst_detected = trades_10 >= 5 && burst_ratio >= 2.0;
+    let burst_intensity = ((burst_ratio - 1.0) / 4.0).clamp(0.0, 1.0);
+
+    let early: Vec<&TradeRecord> = trades
+        .iter()
+        .filter(|t| t.timestamp - first <= Duration::seconds(EARLY_WINDOW_SECS))
+        .collect();
+    let early_sell_pressure =
+        early.iter().filter(|t| !t.is_buy).count() as f64 / early.len() as f64;
+
+    let sizes: Vec<f64> = trades
+        .iter()
+        .map(|t| lamports_to_sol(t.sol_amount))
+        .collect();
+    let avg_trade_size_sol = sizes.iter().sum::<f64>() / sizes.len() as f64;
+    let trade_size_variance = sizes
+        .iter()
+        .map(|s| (s - avg_trade_size_sol).powi(2))
+        .sum::<f64>()
+        / sizes.len() as f64;
+
+    let buyer_breadth = if buy_count > 0 {
+        unique_buyers as f64 / buy_count as f64
+    } else {
+        0.5
+    };
+
+    Some(OrderFlowAnalysis {
+        mint: mint.to_string(),
+        analysis_window_secs: LONG_WINDOW_SECS as u64,
+        buy_volume_sol: buy_60,
+        sell_volume_sol: sell_60,
+        net_flow_sol: buy_60 - sell_60,
+        buy_sell_ratio: buy_sell_ratio(buy_60, sell_60),
+        buy_sell_ratio_10s: buy_sell_ratio(buy_10, sell_10),
+        unique_buyers: unique_buyers as u32,
+        trades_per_minute: trades_60 as f64 / span_secs * 60.0,
+        avg_trade_size_sol,
+        trade_size_variance,
+        burst_detected,
+        burst_intensity,
+        wash_trading_score,
+        organic_score: (buyer_breadth * (1.0 - wash_trading_score)).clamp(0.0, 1.0),
+        wash_cycles,
+        wash,
+        holder_growth,
+        early_sell_pressure,
+        sustained_buying: buy_10 >= sell_10 && buy_60 > sell_60,
+        analyzed_at: now,
+    })
+}
+
+/// Holder growth from buys given as (buyer, seconds since the first trade),
+/// oldest first, with `span_secs` of trading so far
+pub fn holder_growth<'a>(
+    buys: impl IntoIterator<Item = (&'a str, f64)>,
+    span_secs: f64,
+) -> HolderGrowthMetrics {
+    let span = span_secs.clamp(GROWTH_MIN_SPAN_SECS, GROWTH_WINDOW_SECS);
+    let slices = (span / GROWTH_SLICE_SECS).ceil();
+    let mut buyers = HashSet::new();
+    let mut growing_slices = HashSet::new();
+    let mut buy_count = 0u32;
+    for (buyer, at) in buys {
+        if !(0.0..GROWTH_WINDOW_SECS).contains(&at) {
+            continue;
+        }
+        buy_count += 1;
+        if buyers.insert(buyer) {
+            growing_slices.insert((at / GROWTH_SLICE_SECS) as u32);
+        }
+    }
+
+    let unique_buyers = buyers.len() as u32;
+    HolderGrowthMetrics {
+        window_secs: span,
+        buys: buy_count,
+        unique_buyers,
+        repeat_buys: buy_count - unique_buyers,
+        new_buyers_per_min: unique_buyers as f64 / (span / 60.0),
+        new_buyer_ratio: if buy_count > 0 {
+            unique_buyers as f64 / buy_count as f64
+        } else {
+            0.0
+        },
+        steadiness: (growing_slices.len() as f64 / slices).min(1.0),
+    }
+}
+
+/// Buy-then-sell cycles per wallet, in trade order
+fn cycles_by_wallet(trades: &[TradeRecord]) -> HashMap<&str, u32> {
+    let mut holding: HashMap<&str, bool> = HashMap::new();
+    let mut cycles: HashMap<&str, u32> = HashMap::new();
+    for t in trades {
+        let was_holding = holding.insert(t.trader.as_str(), t.is_buy).unwrap_or(false);
+        if !t.is_buy && was_holding {
+            *cycles.entry(t.trader.as_str()).or_default() += 1;
+        }
+    }
+    cycles
+}
+
+/// Cycles by wallets that cycled at least `WASH_MIN_CYCLES` times
+fn wash_cycle_count(trades: &[TradeRecord]) -> u32 {
+    cycles_by_wallet(trades)
+        .values()
+        .filter(|count| **count >= WASH_MIN_CYCLES)
+        .sum()
+}
+
+/// Back-and-forth handoffs between two wallets: consecutive trades within
+/// the pair where the other wallet takes the opposite side soon after
+fn handoffs(trades: &[TradeRecord], a: &[usize], b: &[usize]) -> u32 {
+    let mut merged: Vec<usize> = a.iter().chain(b).copied().collect();
+    merged.sort_unstable();
+    merged
+        .windows(2)
+        .filter(|pair| {
+            let (prev, next) = (&trades[pair[0]], &trades[pair[1]]);
+            prev.trader != next.trader
+                && prev.is_buy != next.is_buy
+                && next.timestamp - prev.timestamp <= Duration::seconds(ROUND_TRIP_WINDOW_SECS)
+        })
+        .count() as u32
+}
+
+/// Find circular volume: round-trip wallets, wash partners, repeat cyclers
+/// and (when volume is concentrated) dominant wallets trading both sides
+fn wash_metrics(trades: &[TradeRecord]) -> WashTradingMetrics {
+    let mut wallet_trades: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut wallet_volume: HashMap<&str, f64> = HashMap::new();
+    for (i, t) in trades.iter().enumerate() {
+        wallet_trades.entry(t.trader.as_str()).or_default().push(i);
+        *wallet_volume.entry(t.trader.as_str()).or_default() += lamports_to_sol(t.sol_amount);
+    }
+    let volume_of = |wallets: &HashSet<&str>| wallets.iter().map(|w| wallet_volume[w]).sum::<f64>();
+    let trades_both_sides = |wallet: &str| {
+        let indices = &wallet_trades[wallet];
+        indices.iter().any(|&i| trades[i].is_buy) && indices.iter().any(|&i| !trades[i].is_buy)
+    };
+
+    let mut flagged: HashSet<&str> = cycles_by_wallet(trades)
+        .into_iter()
+        .filter(|(_, count)| *count >= WASH_MIN_CYCLES)
+        .map(|(wallet, _)| wallet)
+        .collect();
+
+    let round_trippers: HashSet<&str> = wallet_trades
+        .iter()
+        .filter(|(_, indices)| {
+            indices.windows(2).any(|pair| {
+                let (prev, next) = (&trades[pair[0]], &trades[pair[1]]);
+                prev.is_buy != next.is_buy
+                    && next.timestamp - prev.timestamp <= Duration::seconds(ROUND_TRIP_WINDOW_SECS)
+            })
+        })
+        .map(|(wallet, _)| *wallet)
+        .collect();
+
+    let mut by_volume: Vec<(&str, f64)> = wallet_volume.iter().map(|(w, v)| (*w, *v)).collect();
+    by_volume.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let candidates: Vec<&str> = by_volume
+        .iter()
+        .map(|(wallet, _)| *wallet)
+        .filter(|wallet| wallet_trades[wallet].len() >= 2)
+        .take(PARTNER_MAX_WALLETS)
+        .collect();
+    let mut partner_pairs = 0;
+    let mut partners: HashSet<&str> = HashSet::new();
+    for (i, a) in candidates.iter().enumerate() {
+        for b in &candidates[i + 1..] {
+            if handoffs(trades, &wallet_trades[a], &wallet_trades[b]) >= PARTNER_MIN_HANDOFFS {
+                partner_pairs += 1;
+                partners.insert(*a);
+                partners.insert(*b);
+            }
+        }
+    }
+
+    let total_volume_sol: f64 = wallet_volume.values().sum();
+    let top: Vec<&str> = by_volume
+        .iter()
+        .take(CONCENTRATION_WALLETS)
+        .map(|(wallet, _)| *wallet)
+        .collect();
+    let top_volume: f64 = by_volume
+        .iter()
+        .take(CONCENTRATION_WALLETS)
+        .map(|(_, v)| v)
+        .sum();
+    let top_wallet_volume_share = if total_volume_sol > 0.0 {
+        top_volume / total_volume_sol
+    } else {
+        0.0
+    };
+    let concentrated = trades.len() >= CONCENTRATION_MIN_TRADES
+        && wallet_volume.len() > CONCENTRATION_WALLETS
+        && top_wallet_volume_share > CONCENTRATION_SHARE;
+    if concentrated {
+        flagged.extend(top.into_iter().filter(|w| trades_both_sides(w)));
+    }
+
+    flagged.extend(&round_trippers);
+    flagged.extend(&partners);
+
+    WashTradingMetrics {
+        round_trip_wallets: round_trippers.len() as u32,
+        round_trip_volume_sol: volume_of(&round_trippers),
+        partner_pairs,
+        partner_volume_sol: volume_of(&partners),
+        top_wallet_volume_share,
+        concentrated,
+        circular_volume_sol: volume_of(&flagged),
+        total_volume_sol,
+    }
+}
+
+/// Turn an analysis into scoring signals
+fn flow_signals(flow: &OrderFlowAnalysis, trade_count: usize) -> Vec<Signal> {
+    let confidence = (trade_count as f64 / FULL_CONFIDENCE_TRADES).min(1.0);
+    let mut signals = Vec::new();
+
+    signals.push(Signal::new(
+        SignalType::BuyTiming,
+        (flow.buy_sell_ratio_10s - 1.0) / 3.0,
+        confidence,
+        format!("10s buy/sell ratio {:.2}", flow.buy_sell_ratio_10s),
+    ));
+
+    signals.push(Signal::new(
+        SignalType::SellTiming,
+        ((0.3 - flow.early_sell_pressure) * 2.0).min(0.5),
+        confidence,
+        format!(
+            "{:.0}% of early trades are sells",
+            flow.early_sell_pressure * 100.0
+        ),
+    ));
+
+    let burst = if !flow.burst_detected {
+        Signal::new(
+            SignalType::BurstDetection,
+            0.0,
+            confidence,
+            "No trade burst",
+        )
+    } else if flow.unique_buyers < 5 {
+        Signal::new(
+            SignalType::BurstDetection,
+            -flow.burst_intensity,
+            confidence,
+            format!("Burst from only {} buyers", flow.unique_buyers),
+        )
+    } else {
+        Signal::new(
+            SignalType::BurstDetection,
+            0.3 * flow.burst_intensity,
+            confidence,
+            format!("Burst across {} buyers", flow.unique_buyers),
+        )
+    };
+    signals.push(burst);
+
+    let wash_reason = format!(
+        "{:.0}% circular volume ({} round-trip wallets, {} partner pairs, {} cycles, top {} wallets {:.0}%)",
+        flow.wash_trading_score * 100.0,
+        flow.wash.round_trip_wallets,
+        flow.wash.partner_pairs,
+        flow.wash_cycles,
+        CONCENTRATION_WALLETS,
+        flow.wash.top_wallet_volume_share * 100.0
+    );
+    if flow.wash_trading_score >= 0.8 {
+        signals.push(Signal::extreme_risk(SignalType::WashTrading, wash_reason));
+    } else {
+        signals.push(Signal::new(
+            SignalType::WashTrading,
+            -flow.wash_trading_score,
+            confidence,
+            wash_reason,
+        ));
+    }
+
+    let velocity = if flow.buy_sell_ratio >= 1.0 {
+        (flow.unique_buyers as f64 / 20.0).min(1.0) * 0.6
+    } else {
+        -0.3
+    };
+    signals.push(Signal::new(
+        SignalType::VelocityMetrics,
+        velocity,
+        confidence,
+        format!(
+            "{:.1} trades/min, {} unique buyers, 60s ratio {:.2}",
+            flow.trades_per_minute, flow.unique_buyers, flow.buy_sell_ratio
+        ),
+    ));
+
+    let growth = &flow.holder_growth;
+    signals.push(match growth.organic_demand() {
+        Some(demand) => Signal::new(
+            SignalType::OrganicDemand,
+            demand,
+            confidence,
+            format!(
+                "{} new buyers in {:.0}s ({:.1}/min), {} repeat buys, {:.0}% of 30s slices growing",
+                growth.unique_buyers,
+                growth.window_secs,
+                growth.new_buyers_per_min,
+                growth.repeat_buys,
+                growth.steadiness * 100.0
+            ),
+        ),
+        None => Signal::unavailable(
+            SignalType::OrganicDemand,
+            format!(
+                "{} early buys, need {}",
+                growth.buys,
+                HolderGrowthMetrics::MIN_BUYS
+            ),
+        ),
+    });
+
+    signals
+}
+
+#[async_trait]
+impl SignalProvider for OrderFlowSignalProvider {
+    fn name(&self) -> &'static str {
+        "order_flow"
+    }
+
+    fn signal_types(&self) -> &[SignalType] {
+        &[
+            SignalType::BuyTiming,
+            SignalType::SellTiming,
+            SignalType::BurstDetection,
+            SignalType::WashTrading,
+            SignalType::VelocityMetrics,
+            SignalType::OrganicDemand,
+        ]
+    }
+
+    fn is_hot_path(&self) -> bool {
+        false
+    }
+
+    async fn compute_token_signals(&self, context: &SignalContext) -> Vec<Signal> {
+        let trades = self.cache.get_trades(&context.mint);
+        if let Some(flow) = analyze_trades(&context.mint, &trades, Utc::now()) {
+            return flow_signals(&flow, trades.len());
+        }
+        // No trade feed for this token: use the caller's aggregate flow
+        if let Some(ref flow) = context.order_flow {
+            let minutes = flow.analysis_window_secs as f64 / 60.0;
+            return flow_signals(flow, (flow.trades_per_minute * minutes) as usize);
+        }
+        self.signal_types()
+            .iter()
+            .map(|&signal_type| Signal::unavailable(signal_type, "No trades buffered yet"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(
+        now: DateTime<Utc>,
+        secs_ago: i64,
+        trader: &str,
+        is_buy: bool,
+        sol: f64,
+    ) -> TradeRecord {
+        TradeRecord {
+            trader: trader.to_string(),
+            is_buy,
+            sol_amount: (sol * 1_000_000_000.0) as u64,
+            token_amount: 1_000,
+            timestamp: now - Duration::seconds(secs_ago),
+            time_since_launch_ms: 0,
+            signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_empty_buffer() {
+        assert!(analyze_trades("mint", &[], Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let now = Utc::now();
+        let trades = vec![
+            trade(now, 50, "a", false, 1.0),
+            trade(now, 40, "b", true, 1.0),
+            trade(now, 5, "c", true, 2.0),
+            trade(now, 2, "d", true, 1.0),
+        ];
+
+        let flow = analyze_trades("mint", &trades, now).unwrap();
+        assert_eq!(flow.unique_buyers, 3);
+        assert!((flow.buy_sell_ratio - 4.0).abs() < 1e-9);
+        assert_eq!(flow.buy_sell_ratio_10s, MAX_BUY_SELL_RATIO);
+        assert_eq!(flow.wash_cycles, 0);
+    }
+
+    #[test]
+    fn test_wash_cycles() {
+        let now = Utc::now();
+        let mut trades = Vec::new();
+        for i in 0..3 {
+            trades.push(trade(now, 30 - i * 4, "washer", true, 1.0));
+            trades.push(trade(now, 28 - i * 4, "washer", false, 1.0));
+        }
+        trades.push(trade(now, 1, "organic", true, 1.0));
+
+        let flow = analyze_trades("mint", &trades, now).unwrap();
+        assert_eq!(flow.wash_cycles, 3);
+        assert!((flow.wash_trading_score - 6.0 / 7.0).abs() < 1e-9);
+        assert!(flow.organic_score < 0.2);
+    }
+
+    #[test]
+    fn test_round_trip_volume() {
+        let now = Utc::now();
+        let mut trades = vec![
+            // Too slow to count as a round trip
+            trade(now, 200, "slow", true, 1.0),
+            trade(now, 50, "flip", true, 2.0),
+            trade(now, 30, "flip", false, 2.0),
+            trade(now, 10, "slow", false, 1.0),
+        ];
+        for i in 0..6 {
+            trades.push(trade(now, 20 - i, &format!("o{}", i), true, 1.0));
+        }
+
+        let flow = analyze_trades("mint", &trades, now).unwrap();
+        assert_eq!(flow.wash.round_trip_wallets, 1);
+        assert!((flow.wash.round_trip_volume_sol - 4.0).abs() < 1e-9);
+        assert!((flow.wash_trading_score - 4.0 / 12.0).abs() < 1e-9);
+
+        let wash = flow_signals(&flow, trades.len())
+            .into_iter()
+            .find(|s| s.signal_type == SignalType::WashTrading)
+            .unwrap();
+        assert!((wash.value + 4.0 / 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partner_wallets() {
+        let now = Utc::now();
+        let trades = vec![
+            trade(now, 50, "a", true, 1.0),
+            trade(now, 48, "o1", true, 1.5),
+            trade(now, 45, "b", false, 1.0),
+            trade(now, 40, "a", true, 1.0),
+            trade(now, 38, "o2", true, 1.5),
+            trade(now, 35, "b", false, 1.0),
+            trade(now, 20, "o3", true, 1.5),
+            trade(now, 10, "o4", true, 1.5),
+        ];
+
+        let flow = analyze_trades("mint", &trades, now).unwrap();
+        assert_eq!(flow.wash.round_trip_wallets, 0);
+        assert_eq!(flow.wash.partner_pairs, 1);
+        assert!((flow.wash.partner_volume_sol - 4.0).abs() < 1e-9);
+        assert!((flow.wash_trading_score - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_volume_concentration() {
+        let concentrated = |organic_sol: f64| {
+            let now = Utc::now();
+            let mut trades = vec![
+                trade(now, 300, "w1", true, 5.0),
+                trade(now, 290, "w2", true, 5.0),
+                trade(now, 100, "w1", false, 5.0),
+                trade(now, 90, "w2", false, 5.0),
+            ];
+            for i in 0..8 {
+                trades.push(trade(now, 80 - i, &format!("o{}", i), true, organic_sol));
+            }
+            analyze_trades("mint", &trades, now).unwrap()
+        };
+
+        // Two wallets trading both sides carry 20 of 24 SOL
+        let flow = concentrated(0.5);
+        assert!(flow.wash.concentrated);
+        assert_eq!(flow.wash.partner_pairs, 0);
+        assert!((flow.wash.top_wallet_volume_share - 20.5 / 24.0).abs() < 1e-9);
+        assert!((flow.wash_trading_score - 20.0 / 24.0).abs() < 1e-9);
+
+        // Same wallets with real volume around them are not flagged
+        let flow = concentrated(3.0);
+        assert!(!flow.wash.concentrated);
+        assert_eq!(flow.wash_trading_score, 0.0);
+    }
+
+    #[test]
+    fn test_organic_demand_separates_bundles() {
+        let now = Utc::now();
+        let demand = |trades: &[TradeRecord]| {
+            let flow = analyze_trades("mint", trades, now).unwrap();
+            flow_signals(&flow, trades.len())
+                .into_iter()
+                .find(|s| s.signal_type == SignalType::OrganicDemand)
+                .unwrap()
+                .value
+        };
+
+        // Twenty wallets arriving steadily over four minutes, two buying again
+        let mut organic: Vec<_> = (0..20)
+            .map(|i| trade(now, 240 - i * 12, &format!("o{}", i), true, 0.3))
+            .collect();
+        organic.push(trade(now, 30, "o3", true, 0.3));
+        organic.push(trade(now, 10, "o7", true, 0.3));
+        organic.sort_by_key(|t| t.timestamp);
+
+        // Three wallets recycling the same volume over the same four minutes
+        let bundled: Vec<_> = (0..24)
+            .map(|i| trade(now, 240 - i * 10, &format!("b{}", i % 3), true, 0.3))
+            .collect();
+
+        let organic = demand(&organic);
+        let bundled = demand(&bundled);
+        assert!(organic > 0.5, "organic demand {}", organic);
+        assert!(bundled < -0.2, "bundled demand {}", bundled);
+
+        // Too few buys to judge
+        let few: Vec<_> = (0..3)
+            .map(|i| trade(now, 30 - i, &format!("f{}", i), true, 0.3))
+            .collect();
+        assert_eq!(demand(&few), 0.0);
+    }
+
+    #[test]
+    fn test_burst_detection() {
+        let now = Utc::now();
+        let mut trades = vec![trade(now, 59, "early", true, 0.1)];
+        for i in 0..8 {
+            trades.push(trade(now, i, &format!("w{}", i), true, 0.1));
+        }
+
+        let flow = analyze_trades("mint", &trades, now).unwrap();
+        assert!(flow.burst_detected);
+        assert!(flow.burst_intensity > 0.5);
+
+        // A token younger than the short window can't burst against itself
+        let young: Vec<_> = trades.into_iter().skip(1).collect();
+        let flow = analyze_trades("mint", &young, now).unwrap();
+        assert!(!flow.burst_detected);
+    }
+}
@@ -0,0 +1,341 @@
+//! Signal provider registry
+//!
+//! The adaptive filter runs the providers named in `adaptive_filter.providers`,
+//! looked up here by name. The built-ins are registered by `with_builtins`; a
+//! custom provider is one `register` call plus its name in the config list,
+//! with no change to `snipe start`. Each listed provider also reads its
+//! `[adaptive_filter.provider_settings.<name>]` table: `enabled`, `weight`
+//! (scales the confidence of its signals) and `max_latency_ms` (replaces the
+//! provider's own timeout).
+
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::config::EarlyDetectionConfig;
+use crate::error::{Error, Result};
+use crate::filter::adaptive::config::{AdaptiveFilterConfig, ProviderSettings};
+use crate::filter::bundled_detection::BundledDetector;
+use crate::filter::cache::FilterCache;
+use crate::filter::creator_launches::CreatorLaunches;
+use crate::filter::signals::{
+    BundleSignalProvider, DistributionSignalProvider, EarlyMomentumSignalProvider,
+    FundingSourceProvider, MetadataSignalProvider, OrderFlowSignalProvider, Signal, SignalProvider,
+    SignalType, SmartMoneySignalProvider, WalletBehaviorSignalProvider,
+};
+use crate::filter::smart_money::WalletProfiler;
+use crate::filter::types::{PositionSignalContext, SignalContext, TradeSignalContext};
+
+/// Built-in provider names, in the default `adaptive_filter.providers` order
+pub const BUILTIN_PROVIDERS: &[&str] = &[
+    "metadata",
+    "wallet_behavior",
+    "early_momentum",
+    "order_flow",
+    "distribution",
+    "smart_money",
+    "bundle",
+    "funding",
+];
+
+/// What providers are built from
+pub struct ProviderDeps {
+    pub cache: Arc<FilterCache>,
+    pub early_detection: EarlyDetectionConfig,
+    pub creator_launches: Option<Arc<CreatorLaunches>>,
+    pub wallet_profiler: Option<Arc<WalletProfiler>>,
+    pub bundled_detector: Option<Arc<BundledDetector>>,
+    /// Also fed by the trade feed
+    pub order_flow: Arc<OrderFlowSignalProvider>,
+    /// Also read by the strategy engine
+    pub distribution: Arc<DistributionSignalProvider>,
+    /// Also read by the portfolio governor (None = no Helius client or
+    /// `adaptive_filter.funding` disabled)
+    pub funding: Option<Arc<FundingSourceProvider>>,
+}
+
+/// Builds a provider (None = its dependencies are not available)
+pub type ProviderFactory =
+    Box<dyn Fn(&ProviderDeps) -> Option<Arc<dyn SignalProvider>> + Send + Sync>;
+
+/// Signal provider factories by name
+#[derive(Default)]
+pub struct ProviderRegistry {
+    factories: BTreeMap<String, ProviderFactory>,
+}
+
+impl ProviderRegistry {
+    /// Registry holding the built-in providers
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::default();
+        registry.register("metadata", |_| {
+            Some(Arc::new(MetadataSignalProvider::new()))
+        });
+        registry.register("wallet_behavior", |deps| {
+            let provider = WalletBehaviorSignalProvider::new(deps.cache.clone());
+            Some(Arc::new(match deps.creator_launches {
+                Some(ref launches) => provider.with_creator_launches(launches.clone()),
+                None => provider,
+            }))
+        });
+        registry.register("early_momentum", |deps| {
+            Some(Arc::new(EarlyMomentumSignalProvider::new(
+                deps.early_detection.clone(),
+            )))
+        });
+        registry.register("order_flow", |deps| Some(deps.order_flow.clone()));
+        registry.register("distribution", |deps| Some(deps.distribution.clone()));
+        registry.register("smart_money", |deps| {
+            let profiler = deps.wallet_profiler.clone()?;
+            Some(Arc::new(SmartMoneySignalProvider::new(profiler)))
+        });
+        registry.register("bundle", |deps| {
+            let detector = deps.bundled_detector.clone()?;
+            Some(Arc::new(BundleSignalProvider::new(detector)))
+        });
+        registry.register("funding", |deps| {
+            let funding = deps.funding.clone()?;
+            Some(funding)
+        });
+        registry
+    }
+
+    /// Register (or replace) the provider called `name`
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&ProviderDeps) -> Option<Arc<dyn SignalProvider>> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    /// Check every provider named in `config` is registered and its
+    /// settings are in range
+    pub fn validate(&self, config: &AdaptiveFilterConfig) -> Result<()> {
+        let named = config
+            .providers
+            .iter()
+            .chain(config.provider_settings.keys());
+        for name in named {
+            if !self.factories.contains_key(name) {
+                return Err(Error::Config(format!(
+                    "adaptive_filter: unknown signal provider '{}' (valid: {})",
+                    name,
+                    self.names().join(", ")
+                )));
+            }
+        }
+        for (name, settings) in &config.provider_settings {
+            if !(0.0..=1.0).contains(&settings.weight) {
+                return Err(Error::Config(format!(
+                    "adaptive_filter.provider_settings.{}: weight must be between 0 and 1",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the enabled providers of `config.providers`, with their
+    /// settings applied; providers whose dependencies are missing are skipped
+    pub fn build(
+        &self,
+        config: &AdaptiveFilterConfig,
+        deps: &ProviderDeps,
+    ) -> Result<Vec<(String, Arc<dyn SignalProvider>)>> {
+        self.validate(config)?;
+        let mut built = Vec::new();
+        for name in &config.providers {
+            let settings = config.provider_settings(name);
+            if !settings.enabled || built.iter().any(|(built, _)| built == name) {
+                continue;
+            }
+            let Some(provider) = (self.factories[name])(deps) else {
+                tracing::info!(provider = %name, "Signal provider skipped: not available");
+                continue;
+            };
+            let provider: Arc<dyn SignalProvider> = if settings == ProviderSettings::default() {
+                provider
+            } else {
+                Arc::new(ConfiguredProvider {
+                    inner: provider,
+                    settings,
+                })
+            };
+            built.push((name.clone(), provider));
+        }
+        Ok(built)
+    }
+}
+
+/// A provider with its `provider_settings` applied
+struct ConfiguredProvider {
+    inner: Arc<dyn SignalProvider>,
+    settings: ProviderSettings,
+}
+
+impl ConfiguredProvider {
+    fn weigh(&self, mut signals: Vec<Signal>) -> Vec<Signal> {
+        for signal in &mut signals {
+            signal.confidence = (signal.confidence * self.settings.weight).clamp(0.0, 1.0);
+        }
+        signals
+    }
+}
+
+#[async_trait]
+impl SignalProvider for ConfiguredProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn signal_types(&self) -> &[SignalType] {
+        self.inner.signal_types()
+    }
+
+    fn is_hot_path(&self) -> bool {
+        self.inner.is_hot_path()
+    }
+
+    fn max_latency_ms(&self) -> u64 {
+        self.settings
+            .max_latency_ms
+            .unwrap_or_else(|| self.inner.max_latency_ms())
+    }
+
+    async fn compute_token_signals(&self, context: &SignalContext) -> Vec<Signal> {
+        self.weigh(self.inner.compute_token_signals(context).await)
+    }
+
+    async fn compute_trade_signals(&self, context: &TradeSignalContext) -> Vec<Signal> {
+        self.weigh(self.inner.compute_trade_signals(context).await)
+    }
+
+    async fn compute_position_signals(&self, context: &PositionSignalContext) -> Vec<Signal> {
+        self.weigh(self.inner.compute_position_signals(context).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn deps() -> ProviderDeps {
+        let cache = Arc::new(FilterCache::new());
+        ProviderDeps {
+            order_flow: Arc::new(OrderFlowSignalProvider::new(cache.clone())),
+            distribution: Arc::new(DistributionSignalProvider::new(cache.clone())),
+            cache,
+            early_detection: Default::default(),
+            creator_launches: None,
+            wallet_profiler: None,
+            bundled_detector: None,
+            funding: None,
+        }
+    }
+
+    #[test]
+    fn test_builtins_from_config() {
+        let registry = ProviderRegistry::with_builtins();
+        let mut names = registry.names();
+        names.sort_unstable();
+        let mut builtins = BUILTIN_PROVIDERS.to_vec();
+        builtins.sort_unstable();
+        assert_eq!(names, builtins);
+
+        // Providers without their dependencies are skipped
+        let config = AdaptiveFilterConfig::default();
+        let built = registry.build(&config, &deps()).unwrap();
+        let built: Vec<&str> = built.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            built,
+            [
+                "metadata",
+                "wallet_behavior",
+                "early_momentum",
+                "order_flow",
+                "distribution"
+            ]
+        );
+
+        let config = AdaptiveFilterConfig {
+            providers: vec!["metadata".to_string(), "order_flow".to_string()],
+            provider_settings: HashMap::from([(
+                "order_flow".to_string(),
+                ProviderSettings {
+                    enabled: false,
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let built = registry.build(&config, &deps()).unwrap();
+        assert_eq!(built.len(), 1);
+        assert_eq!(built[0].0, "metadata");
+    }
+
+    #[test]
+    fn test_unknown_provider_lists_valid_names() {
+        let registry = ProviderRegistry::with_builtins();
+        let config = AdaptiveFilterConfig {
+            providers: vec!["metadata".to_string(), "sentiment".to_string()],
+            ..Default::default()
+        };
+        let err = registry.build(&config, &deps()).err().unwrap().to_string();
+        assert!(err.contains("'sentiment'"), "{}", err);
+        assert!(err.contains("order_flow, smart_money"), "{}", err);
+
+        // A registered custom provider is accepted
+        let mut registry = registry;
+        registry.register("sentiment", |_| {
+            Some(Arc::new(MetadataSignalProvider::new()))
+        });
+        assert_eq!(registry.build(&config, &deps()).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_settings_override_latency_and_weight() {
+        let registry = ProviderRegistry::with_builtins();
+        let config = AdaptiveFilterConfig {
+            providers: vec!["metadata".to_string()],
+            provider_settings: HashMap::from([(
+                "metadata".to_string(),
+                ProviderSettings {
+                    weight: 0.5,
+                    max_latency_ms: Some(5),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let built = registry.build(&config, &deps()).unwrap();
+        let provider = &built[0].1;
+        assert_eq!(provider.max_latency_ms(), 5);
+
+        let context = SignalContext::from_new_token(
+            "TestMint".to_string(),
+            "Test Token".to_string(),
+            "TEST".to_string(),
+            String::new(),
+            "Creator".to_string(),
+            "BondingCurve".to_string(),
+            1000,
+            1_000_000_000,
+            100_000_000,
+            1.0,
+        );
+        let plain = MetadataSignalProvider::new()
+            .compute_token_signals(&context)
+            .await;
+        let weighed = provider.compute_token_signals(&context).await;
+        assert_eq!(plain.len(), weighed.len());
+        for (plain, weighed) in plain.iter().zip(&weighed) {
+            assert!((weighed.confidence - plain.confidence * 0.5).abs() < 1e-9);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::filter::cache::FilterCache;
+use crate::filter::creator_launches::CreatorLaunches;
 use crate::filter::signals::{Signal, SignalProvider, SignalType};
 use crate::filter::types::SignalContext;
 
@@ -16,6 +17,8 @@ pub struct WalletBehaviorSignalProvider {
     cache: Arc<FilterCache>,
     /// Whether to operate in hot-path mode (cached only)
     hot_path_mode: bool,
+    /// Launches per creator (None = launch frequency not scored)
+    creator_launches: Option<Arc<CreatorLaunches>>,
 }
 
 impl WalletBehaviorSignalProvider {
@@ -24,6 +27,7 @@ impl WalletBehaviorSignalProvider {
         Self {
             cache,
             hot_path_mode: true,
+            creator_launches: None,
         }
     }
 
@@ -32,9 +36,29 @@ impl WalletBehaviorSignalProvider {
         Self {
             cache,
             hot_path_mode: false,
+            creator_launches: None,
         }
     }
 
+    /// Score how often the creator has launched recently
+    pub fn with_creator_launches(mut self, launches: Arc<CreatorLaunches>) -> Self {
+        self.creator_launches = Some(launches);
+        self
+    }
+
+    /// Serial deployers: launches by this creator over the last hour / day
+    fn check_launch_frequency(&self, context: &SignalContext) -> Option<Signal> {
+        let launches = self.creator_launches.as_ref()?;
+        if context.creator.is_empty() {
+            return None;
+        }
+        let start = std::time::Instant::now();
+        launches
+            .counts(&context.creator, context.timestamp)
+            .signal(launches.config())
+            .map(|signal| signal.with_latency(start.elapsed()).with_cached(true))
+    }
+
     /// Check if creator is a known bad actor
     async fn check_known_actors(&self, context: &SignalContext) -> Vec<Signal> {
         let mut signals = Vec::new();
@@ -279,6 +303,8 @@ impl SignalProvider for WalletBehaviorSignalProvider {
         // Check cached history
         signals.extend(self.analyze_cached_history(context).await);
 
+        signals.extend(self.check_launch_frequency(context));
+
         signals
     }
 }
@@ -338,6 +364,35 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_serial_deployer_signal() {
+        use crate::filter::creator_launches::CreatorLaunchConfig;
+
+        let launches = Arc::new(CreatorLaunches::new(CreatorLaunchConfig::default()));
+        let provider = WalletBehaviorSignalProvider::new(Arc::new(FilterCache::new()))
+            .with_creator_launches(launches.clone());
+        let context = make_context("serial_dev");
+        let launch_signal = |signals: Vec<Signal>| {
+            signals
+                .into_iter()
+                .find(|s| s.signal_type == SignalType::DeployerPattern)
+        };
+
+        launches.record("serial_dev", "TestMint", "TEST", context.timestamp);
+        let signals = provider.compute_token_signals(&context).await;
+        assert!(
+            launch_signal(signals).is_none(),
+            "First launch is not a pattern"
+        );
+
+        for i in 0..3 {
+            let mint = format!("Earlier{}", i);
+            launches.record("serial_dev", &mint, "OLD", context.timestamp);
+        }
+        let signals = provider.compute_token_signals(&context).await;
+        assert_eq!(launch_signal(signals).unwrap().value, -1.0);
+    }
+
     #[tokio::test]
     async fn test_known_sniper_creator() {
         let cache = Arc::new(FilterCache::new());
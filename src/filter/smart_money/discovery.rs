@@ -0,0 +1,648 @@
+//! Trusted Wallet Discovery
+//!
+//! Buyers seen in the first `early_window_secs` of launches the bot
+//! evaluates are queued for profiling. A background job profiles at most
+//! `max_profiles_per_hour` of them through the `WalletProfiler` (each
+//! uncached profile costs a Helius history request), persists the results,
+//! and promotes wallets whose alpha score and trade count clear the
+//! thresholds into the discovered trusted set. Promoted wallets are
+//! re-profiled every `reprofile_interval_secs` and demoted when their most
+//! recent trades degrade.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::filter::smart_money::alpha_score::WalletCategory;
+use crate::filter::smart_money::wallet_profiler::{WalletProfile, WalletProfiler};
+
+/// Configuration for trusted wallet discovery
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletDiscoveryConfig {
+    /// Enabled flag (needs Helius)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Buys this many seconds after a launch make the buyer a candidate
+    #[serde(default = "default_early_window_secs")]
+    pub early_window_secs: u64,
+
+    /// Helius-backed profiles per hour (cached profiles are free)
+    #[serde(default = "default_max_profiles_per_hour")]
+    pub max_profiles_per_hour: u32,
+
+    /// Candidates waiting for a profile beyond this are dropped
+    #[serde(default = "default_max_pending")]
+    pub max_pending: usize,
+
+    /// Alpha score needed for promotion
+    #[serde(default = "default_min_alpha_score")]
+    pub min_alpha_score: f64,
+
+    /// Completed trades needed for promotion
+    #[serde(default = "default_min_trades")]
+    pub min_trades: u32,
+
+    /// Most recent completed trades judged for demotion
+    #[serde(default = "default_recent_window_trades")]
+    pub recent_window_trades: usize,
+
+    /// Demote when the recent window's win rate falls below this
+    #[serde(default = "default_demote_min_win_rate")]
+    pub demote_min_win_rate: f64,
+
+    /// Demote when the alpha score falls below this
+    #[serde(default = "default_demote_alpha_score")]
+    pub demote_alpha_score: f64,
+
+    /// Re-profile promoted wallets (and re-queue known ones) after this long
+    #[serde(default = "default_reprofile_interval_secs")]
+    pub reprofile_interval_secs: u64,
+
+    /// Copy-trade discovered trusted wallets like `[wallet_tracking]` wallets
+    #[serde(default)]
+    pub copy_trade: bool,
+
+    /// Where profiles are persisted
+    #[serde(default = "default_persist_path")]
+    pub persist_path: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+fn default_early_window_secs() -> u64 {
+    30
+}
+fn default_max_profiles_per_hour() -> u32 {
+    30
+}
+fn default_max_pending() -> usize {
+    500
+}
+fn default_min_alpha_score() -> f64 {
+    0.4
+}
+fn default_min_trades() -> u32 {
+    20
+}
+fn default_recent_window_trades() -> usize {
+    10
+}
+fn default_demote_min_win_rate() -> f64 {
+    0.45
+}
+fn default_demote_alpha_score() -> f64 {
+    0.1
+}
+fn default_reprofile_interval_secs() -> u64 {
+    21_600 // 6 hours
+}
+fn default_persist_path() -> String {
+    "data/discovered_wallets.json".to_string()
+}
+
+impl Default for WalletDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            early_window_secs: default_early_window_secs(),
+            max_profiles_per_hour: default_max_profiles_per_hour(),
+            max_pending: default_max_pending(),
+            min_alpha_score: default_min_alpha_score(),
+            min_trades: default_min_trades(),
+            recent_window_trades: default_recent_window_trades(),
+            demote_min_win_rate: default_demote_min_win_rate(),
+            demote_alpha_score: default_demote_alpha_score(),
+            reprofile_interval_secs: default_reprofile_interval_secs(),
+            copy_trade: false,
+            persist_path: default_persist_path(),
+        }
+    }
+}
+
+/// Where a profiled wallet stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryStatus {
+    /// Profiled, below the promotion thresholds
+    Candidate,
+    /// In the discovered trusted set
+    Promoted,
+    /// Was promoted, recent window degraded
+    Demoted,
+}
+
+impl std::fmt::Display for DiscoveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            DiscoveryStatus::Candidate => "candidate",
+            DiscoveryStatus::Promoted => "promoted",
+            DiscoveryStatus::Demoted => "demoted",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Persisted profile summary of a discovered wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredWallet {
+    pub address: String,
+    /// Launch whose early buyers it was found among
+    pub found_on_mint: String,
+    pub alpha_score: f64,
+    pub category: WalletCategory,
+    pub total_trades: u32,
+    pub win_rate: f64,
+    pub avg_r_multiple: f64,
+    /// Win rate / R-multiple over the most recent completed trades
+    pub recent_win_rate: f64,
+    pub recent_r_multiple: f64,
+    pub status: DiscoveryStatus,
+    /// Why the wallet has its status
+    pub reason: String,
+    pub profiled_at: DateTime<Utc>,
+    pub status_changed_at: DateTime<Utc>,
+}
+
+/// Win rate and average R-multiple of the most recent `window` trades
+/// (None with fewer trades than that)
+fn recent_performance(profile: &WalletProfile, window: usize) -> Option<(f64, f64)> {
+    if window == 0 || profile.completed_trades.len() < window {
+        return None;
+    }
+    let mut trades: Vec<_> = profile.completed_trades.iter().collect();
+    trades.sort_by_key(|t| std::cmp::Reverse(t.sell_time));
+    let recent = &trades[..window];
+    let wins = recent.iter().filter(|t| t.profit_sol > 0.0).count();
+    let r_multiple = recent.iter().map(|t| t.r_multiple).sum::<f64>() / window as f64;
+    Some((wins as f64 / window as f64, r_multiple))
+}
+
+/// New status for a freshly profiled wallet, with the reason
+pub fn assess(
+    config: &WalletDiscoveryConfig,
+    profile: &WalletProfile,
+    current: Option<DiscoveryStatus>,
+) -> (DiscoveryStatus, String) {
+    let alpha = profile.alpha_score.value;
+    let recent = recent_performance(profile, config.recent_window_trades);
+
+    if current == Some(DiscoveryStatus::Promoted) {
+        if alpha < config.demote_alpha_score {
+            return (
+                DiscoveryStatus::Demoted,
+                format!(
+                    "alpha {:.2} fell below {:.2}",
+                    alpha, config.demote_alpha_score
+                ),
+            );
+        }
+        if let Some((win_rate, _)) = recent.filter(|(wr, _)| *wr < config.demote_min_win_rate) {
+            return (
+                DiscoveryStatus::Demoted,
+                format!(
+                    "last {} trades won {:.0}% (min {:.0}%)",
+                    config.recent_window_trades,
+                    win_rate * 100.0,
+                    config.demote_min_win_rate * 100.0
+                ),
+            );
+        }
+        return (
+            DiscoveryStatus::Promoted,
+            format!(
+                "still alpha {:.2} over {} trades",
+                alpha, profile.total_trades
+            ),
+        );
+    }
+
+    if profile.alpha_score.is_avoid() {
+        return (
+            DiscoveryStatus::Candidate,
+            format!("{:?} wallet", profile.alpha_score.category),
+        );
+    }
+    if alpha >= config.min_alpha_score && profile.total_trades >= config.min_trades {
+        // A wallet demoted for a bad run needs the run to recover first
+        let recent_ok = recent.map_or(true, |(wr, _)| wr >= config.demote_min_win_rate);
+        if recent_ok {
+            return (
+                DiscoveryStatus::Promoted,
+                format!(
+                    "alpha {:.2} >= {:.2} over {} trades ({:.0}% win rate)",
+                    alpha,
+                    config.min_alpha_score,
+                    profile.total_trades,
+                    profile.win_rate * 100.0
+                ),
+            );
+        }
+    }
+    let status = current.unwrap_or(DiscoveryStatus::Candidate);
+    (
+        status,
+        format!(
+            "alpha {:.2} over {} trades (needs {:.2} over {})",
+            alpha, profile.total_trades, config.min_alpha_score, config.min_trades
+        ),
+    )
+}
+
+/// Profiles allowed per rolling hour
+struct HourlyBudget {
+    limit: u32,
+    started: Instant,
+    used: u32,
+}
+
+impl HourlyBudget {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            started: Instant::now(),
+            used: 0,
+        }
+    }
+
+    /// Take one unit, false when the hour's budget is spent
+    fn try_take(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.started) >= Duration::from_secs(3600) {
+            self.started = now;
+            self.used = 0;
+        }
+        if self.used >= self.limit {
+            return false;
+        }
+        self.used += 1;
+        true
+    }
+}
+
+/// Read persisted discovered wallets (missing file = none)
+pub fn read_discovered(path: &Path) -> Result<Vec<DiscoveredWallet>> {
+    let data = match std::fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::Io(e.to_string())),
+    };
+    let wallets: HashMap<String, DiscoveredWallet> =
+        serde_json::from_str(&data).map_err(|e| Error::Config(e.to_string()))?;
+    Ok(wallets.into_values().collect())
+}
+
+/// Finds trusted wallets among early launch buyers
+pub struct WalletDiscovery {
+    config: WalletDiscoveryConfig,
+    profiler: Arc<WalletProfiler>,
+    /// Launch time by mint, while inside the early window
+    launches: DashMap<String, DateTime<Utc>>,
+    /// (wallet, mint it was found on) waiting for a profile
+    pending: Mutex<VecDeque<(String, String)>>,
+    wallets: DashMap<String, DiscoveredWallet>,
+    budget: Mutex<HourlyBudget>,
+}
+
+impl WalletDiscovery {
+    pub fn new(config: WalletDiscoveryConfig, profiler: Arc<WalletProfiler>) -> Self {
+        let budget = HourlyBudget::new(config.max_profiles_per_hour);
+        Self {
+            config,
+            profiler,
+            launches: DashMap::new(),
+            pending: Mutex::new(VecDeque::new()),
+            wallets: DashMap::new(),
+            budget: Mutex::new(budget),
+        }
+    }
+
+    /// Discovery configuration
+    pub fn config(&self) -> &WalletDiscoveryConfig {
+        &self.config
+    }
+
+    /// Load persisted profiles, returning how many are promoted
+    pub fn load(&self) -> Result<usize> {
+        let loaded = read_discovered(Path::new(&self.config.persist_path))?;
+        for wallet in loaded {
+            self.wallets.insert(wallet.address.clone(), wallet);
+        }
+        let promoted = self.trusted().len();
+        info!(
+            "Loaded {} discovered wallets from {} ({} trusted)",
+            self.wallets.len(),
+            self.config.persist_path,
+            promoted
+        );
+        Ok(promoted)
+    }
+
+    /// Persist profiles
+    pub fn save(&self) -> Result<()> {
+        let path = Path::new(&self.config.persist_path);
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| Error::Io(e.to_string()))?;
+        }
+        let wallets: HashMap<String, DiscoveredWallet> = self
+            .wallets
+            .iter()
+            .map(|w| (w.key().clone(), w.value().clone()))
+            .collect();
+        let data =
+            serde_json::to_string_pretty(&wallets).map_err(|e| Error::Config(e.to_string()))?;
+        std::fs::write(path, data).map_err(|e| Error::Io(e.to_string()))?;
+        debug!("Saved {} discovered wallets", wallets.len());
+        Ok(())
+    }
+
+    /// Start watching a launch's early buyers
+    pub fn observe_launch(&self, mint: &str) {
+        if self.config.enabled {
+            self.launches.insert(mint.to_string(), Utc::now());
+        }
+    }
+
+    /// A buy on a watched launch, returning true when the buyer was queued
+    pub fn record_buy(&self, mint: &str, wallet: &str) -> bool {
+        self.record_buy_at(mint, wallet, Utc::now())
+    }
+
+    fn record_buy_at(&self, mint: &str, wallet: &str, at: DateTime<Utc>) -> bool {
+        let Some(launched) = self.launches.get(mint).map(|l| *l) else {
+            return false;
+        };
+        if at - launched > chrono::Duration::seconds(self.config.early_window_secs as i64) {
+            self.launches.remove(mint);
+            return false;
+        }
+        // Known wallets are only re-queued once their profile is due
+        let fresh = self.wallets.get(wallet).is_some_and(|w| {
+            at - w.profiled_at
+                < chrono::Duration::seconds(self.config.reprofile_interval_secs as i64)
+        });
+        if fresh {
+            return false;
+        }
+        let mut pending = self.pending.lock().expect("discovery queue poisoned");
+        if pending.len() >= self.config.max_pending || pending.iter().any(|(w, _)| w == wallet) {
+            return false;
+        }
+        pending.push_back((wallet.to_string(), mint.to_string()));
+        true
+    }
+
+    /// Whether a wallet is in the discovered trusted set
+    pub fn is_trusted(&self, wallet: &str) -> bool {
+        self.wallets
+            .get(wallet)
+            .is_some_and(|w| w.status == DiscoveryStatus::Promoted)
+    }
+
+    /// The discovered trusted set
+    pub fn trusted(&self) -> HashSet<String> {
+        self.wallets
+            .iter()
+            .filter(|w| w.status == DiscoveryStatus::Promoted)
+            .map(|w| w.key().clone())
+            .collect()
+    }
+
+    /// Candidates waiting for a profile
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().expect("discovery queue poisoned").len()
+    }
+
+    /// Apply a profile to the stored wallet, returning the new status
+    fn apply(&self, profile: &WalletProfile, found_on_mint: &str) -> DiscoveryStatus {
+        let previous = self.wallets.get(&profile.address).map(|w| w.status);
+        let (status, reason) = assess(&self.config, profile, previous);
+        let (recent_win_rate, recent_r_multiple) =
+            recent_performance(profile, self.config.recent_window_trades)
+                .unwrap_or((profile.win_rate, profile.avg_r_multiple));
+        let now = Utc::now();
+        let status_changed_at = match self.wallets.get(&profile.address) {
+            Some(w) if w.status == status => w.status_changed_at,
+            _ => now,
+        };
+        match (previous, status) {
+            (Some(DiscoveryStatus::Promoted), DiscoveryStatus::Demoted) => {
+                warn!("Demoted discovered wallet {}: {}", profile.address, reason)
+            }
+            (prev, DiscoveryStatus::Promoted) if prev != Some(DiscoveryStatus::Promoted) => {
+                info!("Promoted discovered wallet {}: {}", profile.address, reason)
+            }
+            _ => {}
+        }
+        self.wallets.insert(
+            profile.address.clone(),
+            DiscoveredWallet {
+                address: profile.address.clone(),
+                found_on_mint: found_on_mint.to_string(),
+                alpha_score: profile.alpha_score.value,
+                category: profile.alpha_score.category,
+                total_trades: profile.total_trades,
+                win_rate: profile.win_rate,
+                avg_r_multiple: profile.avg_r_multiple,
+                recent_win_rate,
+                recent_r_multiple,
+                status,
+                reason,
+                profiled_at: now,
+                status_changed_at,
+            },
+        );
+        status
+    }
+
+    /// Promoted wallets whose profile is due for a refresh
+    fn due_reprofiles(&self) -> Vec<(String, String)> {
+        let cutoff =
+            Utc::now() - chrono::Duration::seconds(self.config.reprofile_interval_secs as i64);
+        self.wallets
+            .iter()
+            .filter(|w| w.status == DiscoveryStatus::Promoted && w.profiled_at < cutoff)
+            .map(|w| (w.address.clone(), w.found_on_mint.clone()))
+            .collect()
+    }
+
+    /// Profile due wallets and queued candidates within the hourly budget,
+    /// returning how many were profiled
+    pub async fn run_once(&self) -> usize {
+        let window = chrono::Duration::seconds(self.config.early_window_secs as i64);
+        let now = Utc::now();
+        self.launches
+            .retain(|_, launched| now - *launched <= window);
+
+        // Re-profiles first: demotions matter more than new candidates
+        let mut work: VecDeque<(String, String)> = self.due_reprofiles().into();
+        for (address, _) in &work {
+            self.profiler.invalidate(address);
+        }
+        let mut profiled = 0;
+        loop {
+            let next = match work.pop_front() {
+                Some(next) => Some(next),
+                None => self
+                    .pending
+                    .lock()
+                    .expect("discovery queue poisoned")
+                    .pop_front(),
+            };
+            let Some((address, mint)) = next else {
+                break;
+            };
+            let cached = self.profiler.get_cached(&address).is_some();
+            if !cached
+                && !self
+                    .budget
+                    .lock()
+                    .expect("discovery budget poisoned")
+                    .try_take(Instant::now())
+            {
+                // Out of budget: put it back for the next hour
+                self.pending
+                    .lock()
+                    .expect("discovery queue poisoned")
+                    .push_front((address, mint));
+                break;
+            }
+            match self.profiler.get_or_compute(&address).await {
+                Ok(profile) => {
+                    self.apply(&profile, &mint);
+                    profiled += 1;
+                }
+                Err(e) => debug!(wallet = %address, error = %e, "Discovery profile failed"),
+            }
+        }
+
+        if profiled > 0 {
+            if let Err(e) = self.save() {
+                warn!("Could not save discovered wallets: {}", e);
+            }
+        }
+        profiled
+    }
+
+    /// Load persisted profiles and run every minute in the background
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        if let Err(e) = self.load() {
+            warn!("Could not load discovered wallets: {} (starting fresh)", e);
+        }
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                let profiled = self.run_once().await;
+                if profiled > 0 {
+                    debug!(
+                        profiled,
+                        pending = self.pending_count(),
+                        trusted = self.trusted().len(),
+                        "Wallet discovery pass"
+                    );
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::smart_money::alpha_score::AlphaScore;
+    use crate::filter::smart_money::wallet_profiler::CompletedTrade;
+
+    /// Profile with `wins` winning then `losses` losing trades, most recent last
+    fn profile(alpha: f64, wins: usize, losses: usize) -> WalletProfile {
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let completed_trades: Vec<_> = (0..wins + losses)
+            .map(|i| {
+                let profit_sol = if i < wins { 0.05 } else { -0.05 };
+                CompletedTrade {
+                    token_mint: format!("mint{}", i),
+                    buy_time: start,
+                    sell_time: start + chrono::Duration::seconds(i as i64),
+                    buy_sol: 0.1,
+                    sell_sol: 0.1 + profit_sol,
+                    profit_sol,
+                    profit_pct: profit_sol * 1000.0,
+                    hold_time_secs: 60,
+                    r_multiple: profit_sol / 0.1,
+                }
+            })
+            .collect();
+        let total_trades = completed_trades.len() as u32;
+        WalletProfile {
+            address: "wallet".to_string(),
+            total_trades,
+            win_rate: wins as f64 / total_trades as f64,
+            completed_trades,
+            alpha_score: AlphaScore {
+                value: alpha,
+                category: WalletCategory::Profitable,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_promotion_thresholds() {
+        let config = WalletDiscoveryConfig::default();
+
+        let (status, _) = assess(&config, &profile(0.6, 20, 5), None);
+        assert_eq!(status, DiscoveryStatus::Promoted);
+
+        // Too few trades, or not enough alpha
+        let (status, reason) = assess(&config, &profile(0.6, 10, 2), None);
+        assert_eq!(status, DiscoveryStatus::Candidate);
+        assert!(reason.contains("needs"));
+        let (status, _) = assess(&config, &profile(0.2, 20, 5), None);
+        assert_eq!(status, DiscoveryStatus::Candidate);
+
+        // Avoid categories are never promoted
+        let mut mev = profile(0.9, 30, 0);
+        mev.alpha_score.category = WalletCategory::MevBot;
+        assert_eq!(assess(&config, &mev, None).0, DiscoveryStatus::Candidate);
+    }
+
+    #[test]
+    fn test_demotion_on_recent_window() {
+        let config = WalletDiscoveryConfig::default();
+        let promoted = Some(DiscoveryStatus::Promoted);
+
+        // Lifetime alpha still fine, but the last 10 trades won 20%
+        let (status, reason) = assess(&config, &profile(0.5, 20, 8), promoted);
+        assert_eq!(status, DiscoveryStatus::Demoted);
+        assert!(reason.contains("last 10 trades won 20%"));
+
+        let (status, _) = assess(&config, &profile(0.05, 30, 0), promoted);
+        assert_eq!(status, DiscoveryStatus::Demoted);
+
+        let (status, _) = assess(&config, &profile(0.5, 25, 3), promoted);
+        assert_eq!(status, DiscoveryStatus::Promoted);
+
+        // A demoted wallet still on a bad run stays demoted
+        let demoted = Some(DiscoveryStatus::Demoted);
+        let (status, _) = assess(&config, &profile(0.5, 20, 8), demoted);
+        assert_eq!(status, DiscoveryStatus::Demoted);
+    }
+
+    #[test]
+    fn test_hourly_budget() {
+        let mut budget = HourlyBudget::new(2);
+        let now = Instant::now();
+        assert!(budget.try_take(now));
+        assert!(budget.try_take(now));
+        assert!(!budget.try_take(now));
+        assert!(budget.try_take(now + Duration::from_secs(3601)));
+    }
+}
@@ -5,11 +5,14 @@
 //! - Alpha Score computation for wallet quality
 //! - Wallet categorization (True Signal, Bundled/Team, MEV bots)
 //! - Wallet clustering by funding relationships
+//! - Trusted wallet discovery from early launch buyers
 
 pub mod alpha_score;
 pub mod clustering;
+pub mod discovery;
 pub mod wallet_profiler;
 
 pub use alpha_score::{AlphaScore, WalletCategory};
 pub use clustering::{ClusteringStats, WalletCluster, WalletClusterConfig, WalletClusterer};
+pub use discovery::{DiscoveredWallet, DiscoveryStatus, WalletDiscovery, WalletDiscoveryConfig};
 pub use wallet_profiler::{WalletProfile, WalletProfiler, WalletProfilerConfig};
@@ -1,6 +1,8 @@
 //! Wallet Profiler - P&L Calculation and Profiling
 //!
-//! Analyzes wallet transaction history to compute:
+//! Decodes the wallet's pump.fun buys and sells from its raw transactions
+//! (`pump::instruction::parse_transaction`, CPI routes included) and
+//! analyzes them to compute:
 //! - Realized P&L using FIFO matching
 //! - Win rate and R-multiple
 //! - Trading patterns and behavior
@@ -9,6 +11,7 @@
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::debug;
@@ -17,6 +20,7 @@ use crate::error::Result;
 use crate::filter::helius::HeliusClient;
 use crate::filter::smart_money::alpha_score::{AlphaScore, AlphaScoreConfig};
 use crate::filter::types::WalletTrade;
+use crate::pump::instruction::{parse_transaction, PumpEventKind};
 
 /// Configuration for wallet profiler
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,6 +170,17 @@ impl WalletProfile {
     pub fn should_avoid(&self) -> bool {
         self.alpha_score.is_avoid()
     }
+
+    /// Position size multiplier for tokens this wallet created
+    pub fn creator_size_multiplier(&self) -> f64 {
+        if self.is_elite() {
+            1.5 // 50% more for elite wallets
+        } else if self.win_rate >= 0.5 {
+            1.0 // Normal for decent wallets
+        } else {
+            0.7 // 30% less for weak wallets
+        }
+    }
 }
 
 /// Wallet Profiler - computes P&L and profiles wallets
@@ -217,20 +232,21 @@ impl WalletProfiler {
     pub async fn compute_profile(&self, address: &str) -> Result<WalletProfile> {
         debug!(address = %address, "Computing wallet profile");
 
-        // Fetch transaction history
-        let history = self
+        // Fetch and decode transaction history
+        let txs = self
             .helius
-            .get_wallet_history(address, self.config.tx_limit)
+            .get_transactions(address, self.config.tx_limit)
             .await?;
+        let trades = wallet_trades(address, &txs);
 
         // Group trades by token
-        let trades_by_token = self.group_trades_by_token(&history.recent_trades);
+        let trades_by_token = self.group_trades_by_token(&trades);
 
         // Match buys to sells using FIFO
         let completed_trades = self.match_trades_fifo(&trades_by_token);
 
         // Calculate metrics
-        let profile = self.calculate_metrics(address, completed_trades, &history.recent_trades);
+        let profile = self.calculate_metrics(address, completed_trades, &trades);
 
         Ok(profile)
     }
@@ -476,6 +492,53 @@ impl WalletProfiler {
     }
 }
 
+/// pump.fun buys and sells made by `wallet` in `txs`
+///
+/// Amounts are the SOL actually traded (fees excluded), from each trade's
+/// event; trades without one are skipped.
+fn wallet_trades(
+    wallet: &str,
+    txs: &[EncodedConfirmedTransactionWithStatusMeta],
+) -> Vec<WalletTrade> {
+    let mut trades = Vec::new();
+    for tx in txs {
+        let Some(signature) = tx
+            .transaction
+            .transaction
+            .decode()
+            .and_then(|t| t.signatures.first().map(|s| s.to_string()))
+        else {
+            continue;
+        };
+        let timestamp = tx.block_time.and_then(|t| DateTime::from_timestamp(t, 0));
+        for event in parse_transaction(tx) {
+            if event.user.to_string() != wallet {
+                continue;
+            }
+            let (is_buy, lamports) = match event.kind {
+                PumpEventKind::Buy {
+                    sol_amount: Some(sol),
+                    ..
+                } => (true, sol),
+                PumpEventKind::Sell {
+                    sol_amount: Some(sol),
+                    ..
+                } => (false, sol),
+                _ => continue,
+            };
+            trades.push(WalletTrade {
+                signature: signature.clone(),
+                timestamp,
+                is_buy,
+                sol_amount: lamports as f64 / 1e9,
+                token_mint: event.mint.map(|m| m.to_string()),
+                profit_sol: None,
+            });
+        }
+    }
+    trades
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,4 +570,31 @@ mod tests {
         assert!(profile.is_stale(3600)); // 1 hour TTL
         assert!(!profile.is_stale(10800)); // 3 hour TTL
     }
+
+    #[test]
+    fn test_wallet_trades_from_fixtures() {
+        let txs: Vec<EncodedConfirmedTransactionWithStatusMeta> = [
+            include_str!("../../pump/fixtures/pump_buy_cpi.json"),
+            include_str!("../../pump/fixtures/pump_sell.json"),
+        ]
+        .iter()
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect();
+        let trader = "Bsz8gTcuut8FowdhiNvsGVh27kGm61TN8vec5HWCMD7h";
+
+        let trades = wallet_trades(trader, &txs);
+        assert_eq!(trades.len(), 2);
+        assert!(trades[0].is_buy);
+        assert!((trades[0].sol_amount - 0.25).abs() < 1e-9);
+        assert!(!trades[1].is_buy);
+        assert!((trades[1].sol_amount - 0.231877402).abs() < 1e-9);
+        assert_eq!(
+            trades[0].token_mint.as_deref(),
+            Some("ZPP7SiG7LGHXFNKBEfoyKDkCNvzmAs7ecqTitE4PuCP")
+        );
+        assert_eq!(trades[1].timestamp.unwrap().timestamp(), 1760000180);
+
+        // Someone else's trades
+        assert!(wallet_trades("3TzddA8xcMD2N2QwWWtXQSY9RE62ffz7RAzX16Lsbyqx", &txs).is_empty());
+    }
 }
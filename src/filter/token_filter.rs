@@ -4,10 +4,13 @@
 //! sniping low-quality or suspicious tokens.
 
 use regex::Regex;
-use tracing::debug;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, warn};
 
 use crate::config::FilterConfig;
 use crate::error::{Error, Result};
+use crate::filter::cache::KnownActors;
 use crate::stream::decoder::TokenCreatedEvent;
 
 /// Reason why a token was filtered
@@ -17,6 +20,8 @@ pub enum FilterReason {
     Disabled,
     /// Token name matches blocked pattern
     BlockedName(String),
+    /// Name or symbol matches an operator deny-symbol pattern
+    DenyListed(String),
     /// Token name doesn't match required pattern
     NamePatternMismatch,
     /// Dev holdings exceed maximum
@@ -40,6 +45,9 @@ impl std::fmt::Display for FilterReason {
             FilterReason::BlockedName(pattern) => {
                 write!(f, "name matches blocked pattern: {}", pattern)
             }
+            FilterReason::DenyListed(pattern) => {
+                write!(f, "name/symbol matches deny-listed pattern: {}", pattern)
+            }
             FilterReason::NamePatternMismatch => write!(f, "name doesn't match required patterns"),
             FilterReason::DevHoldingsExceeded(pct) => write!(f, "dev holdings {}% exceed max", pct),
             FilterReason::LiquidityBelowMinimum(sol) => {
@@ -78,11 +86,58 @@ impl FilterResult {
     }
 }
 
+/// Operator deny-symbol patterns (`snipe list`), reloaded while running
+#[derive(Default)]
+pub struct DenySymbols {
+    patterns: RwLock<Vec<Regex>>,
+}
+
+impl DenySymbols {
+    /// Load patterns from a list file (missing file = no patterns)
+    pub fn load(path: &Path) -> Self {
+        let list = Self::default();
+        list.reload(path);
+        list
+    }
+
+    /// Swap in the patterns from `path`, skipping ones that don't compile
+    pub fn reload(&self, path: &Path) -> usize {
+        let mut patterns: Vec<String> = std::fs::read_to_string(path)
+            .map(|content| KnownActors::parse_list(&content).into_iter().collect())
+            .unwrap_or_default();
+        patterns.sort();
+        let compiled: Vec<Regex> = patterns
+            .iter()
+            .filter_map(|p| match Regex::new(p) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("Skipping deny-symbol pattern {}: {}", p, e);
+                    None
+                }
+            })
+            .collect();
+        let count = compiled.len();
+        *self.patterns.write().unwrap() = compiled;
+        count
+    }
+
+    /// First pattern matching the name or symbol
+    pub fn matching(&self, name: &str, symbol: &str) -> Option<String> {
+        self.patterns
+            .read()
+            .unwrap()
+            .iter()
+            .find(|p| p.is_match(name) || p.is_match(symbol))
+            .map(|p| p.to_string())
+    }
+}
+
 /// Token filter based on configuration
 pub struct TokenFilter {
     config: FilterConfig,
     name_patterns: Vec<Regex>,
     blocked_patterns: Vec<Regex>,
+    deny_symbols: Option<Arc<DenySymbols>>,
 }
 
 impl TokenFilter {
@@ -106,9 +161,16 @@ impl TokenFilter {
             config,
             name_patterns,
             blocked_patterns,
+            deny_symbols: None,
         })
     }
 
+    /// Also reject names / symbols on the operator deny list
+    pub fn with_deny_symbols(mut self, deny_symbols: Arc<DenySymbols>) -> Self {
+        self.deny_symbols = Some(deny_symbols);
+        self
+    }
+
     /// Filter a newly created token
     pub fn filter(&self, event: &TokenCreatedEvent) -> FilterResult {
         // Check if filtering is enabled
@@ -116,6 +178,19 @@ impl TokenFilter {
             return FilterResult::Pass;
         }
 
+        // Operator deny list before the configured patterns
+        if let Some(pattern) = self
+            .deny_symbols
+            .as_ref()
+            .and_then(|list| list.matching(&event.name, &event.symbol))
+        {
+            debug!(
+                "Token {} ({}) deny-listed by pattern: {}",
+                event.name, event.symbol, pattern
+            );
+            return FilterResult::Filtered(FilterReason::DenyListed(pattern));
+        }
+
         // Check blocked patterns first
         for pattern in &self.blocked_patterns {
             if pattern.is_match(&event.name) || pattern.is_match(&event.symbol) {
@@ -0,0 +1,346 @@
+//! Offline signal weight tuning
+//!
+//! Joins `bought` decisions from the decision log with closed-trade outcomes
+//! and measures, per signal type, how well the signal's value at entry
+//! predicted a winning trade: win rate when the signal was positive vs
+//! negative, and the correlation between its (confidence-weighted) value and
+//! the outcome. The result is a *proposed* `[adaptive_filter.weights]` block
+//! for `snipe tune` to print; live weights are never touched.
+
+use chrono::Duration;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::filter::decision_log::{DecisionAction, DecisionRecord};
+use crate::filter::signals::{Signal, SignalType};
+use crate::position::history::ClosedTrade;
+
+/// Samples per signal needed before a weight change is proposed
+pub const DEFAULT_MIN_SAMPLES: usize = 30;
+
+/// Proposed weights stay within this factor of the current weight
+const MAX_ADJUSTMENT: f64 = 2.0;
+
+/// A buy decision may be logged a little before or after the recorded entry
+const ENTRY_MATCH_SLACK_SECS: i64 = 300;
+
+/// One position: the signals at entry and how it ended
+#[derive(Debug, Clone)]
+pub struct TradeOutcome {
+    pub mint: String,
+    pub signals: Vec<Signal>,
+    pub pnl_sol: f64,
+}
+
+impl TradeOutcome {
+    pub fn won(&self) -> bool {
+        self.pnl_sol > 0.0
+    }
+}
+
+/// Join closed trades with the `bought` decision that opened them
+///
+/// Partial closes of one position (same entry signature) are summed. Trades
+/// without a matching decision (entered before the log existed) are dropped.
+pub fn join_outcomes(records: &[DecisionRecord], trades: &[ClosedTrade]) -> Vec<TradeOutcome> {
+    let mut positions: BTreeMap<&str, (&ClosedTrade, f64)> = BTreeMap::new();
+    for trade in trades {
+        positions
+            .entry(trade.entry_signature.as_str())
+            .and_modify(|(_, pnl)| *pnl += trade.pnl_sol)
+            .or_insert((trade, trade.pnl_sol));
+    }
+
+    let slack = Duration::seconds(ENTRY_MATCH_SLACK_SECS);
+    positions
+        .into_values()
+        .filter_map(|(trade, pnl_sol)| {
+            let decision = records
+                .iter()
+                .filter(|r| r.action == DecisionAction::Bought && r.mint == trade.mint)
+                .filter(|r| (r.timestamp - trade.entry_time).abs() <= slack)
+                .min_by_key(|r| (r.timestamp - trade.entry_time).abs())?;
+            Some(TradeOutcome {
+                mint: trade.mint.clone(),
+                signals: decision.result.signals.clone(),
+                pnl_sol,
+            })
+        })
+        .collect()
+}
+
+/// Trades and wins in one bucket
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bucket {
+    pub trades: usize,
+    pub wins: usize,
+}
+
+impl Bucket {
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.trades > 0).then(|| self.wins as f64 / self.trades as f64)
+    }
+}
+
+/// Predictive power of one signal type
+#[derive(Debug, Clone)]
+pub struct SignalReport {
+    pub signal_type: SignalType,
+    /// Trades where the signal was available
+    pub samples: usize,
+    /// Signal leaned bullish at entry
+    pub positive: Bucket,
+    /// Signal leaned bearish at entry
+    pub negative: Bucket,
+    /// Correlation of value x confidence with winning (None = no variation)
+    pub correlation: Option<f64>,
+    pub current_weight: f64,
+    /// None when there are too few samples or nothing to learn from
+    pub suggested_weight: Option<f64>,
+}
+
+impl SignalReport {
+    /// Win-rate difference between bullish and bearish readings
+    pub fn lift(&self) -> Option<f64> {
+        Some(self.positive.win_rate()? - self.negative.win_rate()?)
+    }
+}
+
+/// Per-signal tuning report over a set of outcomes
+#[derive(Debug, Clone)]
+pub struct TuningReport {
+    pub trades: usize,
+    pub wins: usize,
+    pub min_samples: usize,
+    /// Most predictive first
+    pub signals: Vec<SignalReport>,
+}
+
+impl TuningReport {
+    /// Analyse outcomes against the current weights (missing = type default)
+    pub fn build(
+        outcomes: &[TradeOutcome],
+        current_weights: &HashMap<SignalType, f64>,
+        min_samples: usize,
+    ) -> Self {
+        // (value x confidence, won) per signal type, one sample per trade
+        let mut samples: HashMap<SignalType, Vec<(f64, bool)>> = HashMap::new();
+        for outcome in outcomes {
+            let mut seen = Vec::new();
+            for signal in outcome.signals.iter().filter(|s| s.confidence > 0.0) {
+                if seen.contains(&signal.signal_type) {
+                    continue;
+                }
+                seen.push(signal.signal_type);
+                samples
+                    .entry(signal.signal_type)
+                    .or_default()
+                    .push((signal.value * signal.confidence, outcome.won()));
+            }
+        }
+
+        let mut signals: Vec<SignalReport> = samples
+            .into_iter()
+            .map(|(signal_type, points)| {
+                let current_weight = current_weights
+                    .get(&signal_type)
+                    .copied()
+                    .unwrap_or_else(|| signal_type.default_weight());
+                let mut positive = Bucket::default();
+                let mut negative = Bucket::default();
+                for &(x, won) in &points {
+                    let bucket = if x > 0.0 {
+                        &mut positive
+                    } else if x < 0.0 {
+                        &mut negative
+                    } else {
+                        continue;
+                    };
+                    bucket.trades += 1;
+                    bucket.wins += usize::from(won);
+                }
+                let correlation = correlation(&points);
+                let suggested_weight = correlation
+                    .filter(|_| points.len() >= min_samples)
+                    .map(|r| suggest_weight(current_weight, r));
+                SignalReport {
+                    signal_type,
+                    samples: points.len(),
+                    positive,
+                    negative,
+                    correlation,
+                    current_weight,
+                    suggested_weight,
+                }
+            })
+            .collect();
+        signals.sort_by(|a, b| {
+            let strength = |r: &SignalReport| r.correlation.map_or(-1.0, f64::abs);
+            strength(b)
+                .total_cmp(&strength(a))
+                .then_with(|| config_key(a.signal_type).cmp(&config_key(b.signal_type)))
+        });
+
+        Self {
+            trades: outcomes.len(),
+            wins: outcomes.iter().filter(|o| o.won()).count(),
+            min_samples,
+            signals,
+        }
+    }
+
+    /// Signals below `min_samples` (their weights are left alone)
+    pub fn low_sample_signals(&self) -> impl Iterator<Item = &SignalReport> {
+        self.signals
+            .iter()
+            .filter(move |s| s.samples < self.min_samples)
+    }
+
+    /// Proposed config block (changed weights only; the rest as comments)
+    pub fn to_toml(&self) -> String {
+        let mut out = format!(
+            "# Proposed by `snipe tune` from {} trades ({} wins) - review before use\n[adaptive_filter.weights]\n",
+            self.trades, self.wins
+        );
+        for report in &self.signals {
+            let key = config_key(report.signal_type);
+            match report.suggested_weight {
+                Some(weight) => out.push_str(&format!(
+                    "{} = {:.2}  # was {:.2}, r={:+.2}\n",
+                    key,
+                    weight,
+                    report.current_weight,
+                    report.correlation.unwrap_or(0.0)
+                )),
+                None if report.samples < self.min_samples => out.push_str(&format!(
+                    "# {} = {:.2}  # unchanged: {} samples < {}\n",
+                    key, report.current_weight, report.samples, self.min_samples
+                )),
+                None => out.push_str(&format!(
+                    "# {} = {:.2}  # unchanged: no variation at entry\n",
+                    key, report.current_weight
+                )),
+            }
+        }
+        out
+    }
+}
+
+/// Key used for a signal type in `[adaptive_filter.weights]`
+pub fn config_key(signal_type: SignalType) -> String {
+    serde_json::to_value(signal_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| signal_type.to_string())
+}
+
+/// Scale the weight by how well the signal tracked outcomes
+fn suggest_weight(current: f64, correlation: f64) -> f64 {
+    let factor = (1.0 + correlation).clamp(1.0 / MAX_ADJUSTMENT, MAX_ADJUSTMENT);
+    (current * factor * 100.0).round() / 100.0
+}
+
+/// Pearson correlation between signal value and winning (point-biserial)
+fn correlation(points: &[(f64, bool)]) -> Option<f64> {
+    let n = points.len() as f64;
+    if points.len() < 2 {
+        return None;
+    }
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().filter(|p| p.1).count() as f64 / n;
+
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for &(x, won) in points {
+        let dx = x - mean_x;
+        let dy = if won { 1.0 } else { 0.0 } - mean_y;
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x < 1e-12 || var_y < 1e-12 {
+        return None;
+    }
+    Some(cov / (var_x.sqrt() * var_y.sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(signals: Vec<Signal>, pnl_sol: f64) -> TradeOutcome {
+        TradeOutcome {
+            mint: "mint".to_string(),
+            signals,
+            pnl_sol,
+        }
+    }
+
+    #[test]
+    fn test_predictive_signal_gets_more_weight() {
+        let mut outcomes = Vec::new();
+        for i in 0..40 {
+            // Volume spike bullish on winners, bearish on losers; name is noise
+            let won = i % 2 == 0;
+            outcomes.push(outcome(
+                vec![
+                    Signal::new(
+                        SignalType::VolumeSpike,
+                        if won { 0.8 } else { -0.4 },
+                        1.0,
+                        "",
+                    ),
+                    Signal::new(SignalType::NameQuality, -0.1, 0.5, ""),
+                    Signal::unavailable(SignalType::WalletAge, "no data"),
+                ],
+                if won { 0.05 } else { -0.05 },
+            ));
+        }
+
+        let report = TuningReport::build(&outcomes, &HashMap::new(), DEFAULT_MIN_SAMPLES);
+        assert_eq!(report.trades, 40);
+        assert_eq!(report.wins, 20);
+
+        let spike = &report.signals[0];
+        assert_eq!(spike.signal_type, SignalType::VolumeSpike);
+        assert_eq!(spike.lift(), Some(1.0));
+        let default = SignalType::VolumeSpike.default_weight();
+        assert!(spike.suggested_weight.unwrap() > default);
+
+        // Constant signal: nothing to learn; unavailable signals aren't samples
+        let name = report
+            .signals
+            .iter()
+            .find(|s| s.signal_type == SignalType::NameQuality)
+            .unwrap();
+        assert_eq!(name.suggested_weight, None);
+        assert!(report
+            .signals
+            .iter()
+            .all(|s| s.signal_type != SignalType::WalletAge));
+
+        let toml = report.to_toml();
+        assert!(toml.contains("[adaptive_filter.weights]"));
+        assert!(toml.contains("\nvolume_spike = "));
+        assert!(toml.contains("# name_quality"));
+    }
+
+    #[test]
+    fn test_small_samples_are_not_tuned() {
+        let outcomes: Vec<_> = (0..6)
+            .map(|i| {
+                outcome(
+                    vec![Signal::new(
+                        SignalType::BundledBuys,
+                        -(i as f64) / 6.0,
+                        1.0,
+                        "",
+                    )],
+                    if i < 3 { 0.1 } else { -0.1 },
+                )
+            })
+            .collect();
+        let report = TuningReport::build(&outcomes, &HashMap::new(), DEFAULT_MIN_SAMPLES);
+        assert_eq!(report.low_sample_signals().count(), 1);
+        assert_eq!(report.signals[0].suggested_weight, None);
+        assert!(report.to_toml().contains("6 samples < 30"));
+    }
+}
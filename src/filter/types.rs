@@ -4,6 +4,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+use super::honeypot::RoundTrip;
+
 /// Wallet historical analysis data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletHistory {
@@ -220,6 +222,9 @@ pub struct OrderFlowAnalysis {
     pub sell_volume_sol: f64,
     pub net_flow_sol: f64,
     pub buy_sell_ratio: f64,
+    /// Buy/sell volume ratio over the last 10 seconds
+    pub buy_sell_ratio_10s: f64,
+    pub unique_buyers: u32,
 
     // Velocity metrics
     pub trades_per_minute: f64,
@@ -231,6 +236,14 @@ pub struct OrderFlowAnalysis {
     pub burst_intensity: f64,    // 0.0 to 1.0
     pub wash_trading_score: f64, // 0.0 to 1.0
     pub organic_score: f64,      // 0.0 to 1.0
+    /// Buy-then-sell cycles by wallets that cycled more than once
+    pub wash_cycles: u32,
+    /// Breakdown behind `wash_trading_score`
+    #[serde(default)]
+    pub wash: WashTradingMetrics,
+    /// Buyer growth over the first minutes of trading
+    #[serde(default)]
+    pub holder_growth: HolderGrowthMetrics,
 
     // Timing analysis
     pub early_sell_pressure: f64, // Sells in first 5 min as ratio
@@ -249,6 +262,8 @@ impl Default for OrderFlowAnalysis {
             sell_volume_sol: 0.0,
             net_flow_sol: 0.0,
             buy_sell_ratio: 1.0,
+            buy_sell_ratio_10s: 1.0,
+            unique_buyers: 0,
             trades_per_minute: 0.0,
             avg_trade_size_sol: 0.0,
             trade_size_variance: 0.0,
@@ -256,6 +271,9 @@ impl Default for OrderFlowAnalysis {
             burst_intensity: 0.0,
             wash_trading_score: 0.0,
             organic_score: 0.5,
+            wash_cycles: 0,
+            wash: WashTradingMetrics::default(),
+            holder_growth: HolderGrowthMetrics::default(),
             early_sell_pressure: 0.0,
             sustained_buying: false,
             analyzed_at: Utc::now(),
@@ -263,6 +281,73 @@ impl Default for OrderFlowAnalysis {
     }
 }
 
+/// Circular volume patterns found in a token's trade buffer
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WashTradingMetrics {
+    /// Wallets that bought and sold (either order) within the round-trip window
+    pub round_trip_wallets: u32,
+    /// SOL traded by round-trip wallets
+    pub round_trip_volume_sol: f64,
+    /// Wallet pairs repeatedly taking the opposite side of each other
+    pub partner_pairs: u32,
+    /// SOL traded by partner wallets
+    pub partner_volume_sol: f64,
+    /// Share of SOL volume from the most active wallets
+    pub top_wallet_volume_share: f64,
+    /// Volume is concentrated in a handful of wallets
+    pub concentrated: bool,
+    /// SOL traded by any flagged wallet (each wallet counted once)
+    pub circular_volume_sol: f64,
+    /// Total SOL volume in the buffer
+    pub total_volume_sol: f64,
+}
+
+impl WashTradingMetrics {
+    /// Fraction of volume that looks circular (0.0 to 1.0)
+    pub fn circular_fraction(&self) -> f64 {
+        if self.total_volume_sol > 0.0 {
+            (self.circular_volume_sol / self.total_volume_sol).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// How fast new buyers arrive in a token's first minutes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HolderGrowthMetrics {
+    /// Trading time measured (seconds)
+    pub window_secs: f64,
+    /// Buys in the window
+    pub buys: u32,
+    /// Distinct buyers in the window
+    pub unique_buyers: u32,
+    /// Buys by wallets that had already bought
+    pub repeat_buys: u32,
+    /// New buyers per minute
+    pub new_buyers_per_min: f64,
+    /// First buys over all buys (1.0 = every buy from a new wallet)
+    pub new_buyer_ratio: f64,
+    /// Share of 30s slices that brought a new buyer
+    pub steadiness: f64,
+}
+
+impl HolderGrowthMetrics {
+    /// Buys needed before growth means anything
+    pub const MIN_BUYS: u32 = 6;
+
+    /// Organic demand from -1.0 (a few wallets recycling volume) to 1.0
+    /// (steady, broad accumulation); None until there are enough buys
+    pub fn organic_demand(&self) -> Option<f64> {
+        if self.buys < Self::MIN_BUYS {
+            return None;
+        }
+        let breadth = (self.new_buyer_ratio - 0.5) * 2.0;
+        let velocity = (self.new_buyers_per_min / 10.0).min(1.0);
+        Some((0.5 * breadth + 0.25 * velocity + 0.25 * self.steadiness).clamp(-1.0, 1.0))
+    }
+}
+
 /// Wallet cluster (coordinated wallets)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletCluster {
@@ -302,6 +387,46 @@ pub struct TradeRecord {
     pub signature: String,
 }
 
+/// Where a scored token was discovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoverySource {
+    /// PumpPortal creation event (token is seconds old)
+    NewTokenEvent,
+    /// Trade on a token whose creation wasn't seen (possibly mid-curve)
+    TradeEvent,
+    /// DexScreener hot-token scan
+    HotScan,
+    /// Buy by a proven sniper we piggyback on
+    Piggyback,
+    /// Whale or clustered buying caught by the front-run detector
+    Accumulation,
+}
+
+impl std::fmt::Display for DiscoverySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiscoverySource::NewTokenEvent => write!(f, "new_token"),
+            DiscoverySource::TradeEvent => write!(f, "trade"),
+            DiscoverySource::HotScan => write!(f, "hot_scan"),
+            DiscoverySource::Piggyback => write!(f, "piggyback"),
+            DiscoverySource::Accumulation => write!(f, "accumulation"),
+        }
+    }
+}
+
+/// Buying pressure the front-run detector saw ahead of the entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccumulationMetrics {
+    /// SOL bought inside the detector's cluster window
+    pub accumulated_sol: f64,
+    /// Share of the token supply those buys took (%)
+    pub float_pct: f64,
+    pub unique_buyers: u32,
+    /// Detector confidence (0.0 to 1.0)
+    pub confidence: f64,
+}
+
 /// Context provided to signal providers for new token analysis
 #[derive(Debug, Clone)]
 pub struct SignalContext {
@@ -318,15 +443,23 @@ pub struct SignalContext {
     pub market_cap_sol: f64,
     pub timestamp: DateTime<Utc>,
 
-    // Early detection data
-    /// Bonding curve progress percentage (0-100%)
-    pub bonding_curve_pct: Option<f64>,
+    // Launch context
+    /// Bonding curve progress percentage (0-100%, from virtual token reserves)
+    pub curve_progress_pct: f64,
+    /// Seconds since the token was created (None = creation not seen)
+    pub age_secs: Option<u64>,
+    /// Which feed surfaced the token
+    pub discovery_source: DiscoverySource,
 
     // Enriched data (may be None in hot path)
     pub creator_history: Option<WalletHistory>,
     pub token_distribution: Option<TokenDistribution>,
     pub recent_trades: Option<Vec<TradeRecord>>,
     pub order_flow: Option<OrderFlowAnalysis>,
+    /// Honeypot round-trip result (None = check disabled)
+    pub round_trip: Option<RoundTrip>,
+    /// Accumulation behind the candidate (None = not an accumulation entry)
+    pub accumulation: Option<AccumulationMetrics>,
 }
 
 impl SignalContext {
@@ -343,10 +476,13 @@ impl SignalContext {
         v_sol_in_bonding_curve: u64,
         market_cap_sol: f64,
     ) -> Self {
-        // Calculate bonding curve percentage
-        // pump.fun bonding curve completes at ~85 SOL, starts at ~30 SOL virtual
-        // Progress = (current_sol - 30) / (85 - 30) * 100
-        let bc_pct = Self::calculate_bonding_curve_pct(v_sol_in_bonding_curve);
+        // Reserves may be whole tokens (PumpPortal) or raw units
+        let curve = crate::filter::honeypot::curve_from_event(
+            v_sol_in_bonding_curve as f64,
+            v_tokens_in_bonding_curve as f64,
+        );
+        let curve_progress_pct =
+            crate::pump::price::curve_progress_pct(curve.virtual_token_reserves);
 
         Self {
             mint,
@@ -360,24 +496,50 @@ impl SignalContext {
             v_sol_in_bonding_curve,
             market_cap_sol,
             timestamp: Utc::now(),
-            bonding_curve_pct: Some(bc_pct),
+            curve_progress_pct,
+            age_secs: Some(0),
+            discovery_source: DiscoverySource::NewTokenEvent,
             creator_history: None,
             token_distribution: None,
             recent_trades: None,
             order_flow: None,
+            round_trip: None,
+            accumulation: None,
         }
     }
 
-    /// Calculate bonding curve progress percentage
-    /// pump.fun bonding curve: starts at ~30 SOL virtual, completes at ~85 SOL
-    pub fn calculate_bonding_curve_pct(v_sol_in_bonding_curve: u64) -> f64 {
-        const INITIAL_VIRTUAL_SOL: f64 = 30.0;
-        const COMPLETION_SOL: f64 = 85.0;
-        const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+    /// Create a context from a trade on a token whose creation event wasn't seen
+    ///
+    /// Metadata and creator are unknown, so only curve-based signals have data.
+    pub fn from_trade(
+        mint: String,
+        bonding_curve: String,
+        v_tokens_in_bonding_curve: u64,
+        v_sol_in_bonding_curve: u64,
+        market_cap_sol: f64,
+    ) -> Self {
+        let mut context = Self::from_new_token(
+            mint,
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            bonding_curve,
+            0,
+            v_tokens_in_bonding_curve,
+            v_sol_in_bonding_curve,
+            market_cap_sol,
+        );
+        context.age_secs = None;
+        context.discovery_source = DiscoverySource::TradeEvent;
+        context
+    }
 
-        let current_sol = v_sol_in_bonding_curve as f64 / LAMPORTS_PER_SOL;
-        let progress = ((current_sol - INITIAL_VIRTUAL_SOL) / (COMPLETION_SOL - INITIAL_VIRTUAL_SOL)) * 100.0;
-        progress.clamp(0.0, 100.0)
+    /// Set how the token was found and how old it is
+    pub fn with_discovery(mut self, source: DiscoverySource, age_secs: Option<u64>) -> Self {
+        self.discovery_source = source;
+        self.age_secs = age_secs;
+        self
     }
 
     /// Calculate estimated token price from bonding curve
@@ -413,6 +575,7 @@ pub struct PositionSignalContext {
     pub position_size_sol: f64,
     pub unrealized_pnl_pct: f64,
     pub recent_trades: Vec<TradeRecord>,
+    /// Holder distribution when the position was opened
     pub holder_distribution: Option<TokenDistribution>,
     pub order_flow: Option<OrderFlowAnalysis>,
 }
@@ -454,6 +617,38 @@ mod tests {
         assert!(age >= 29.9 && age <= 30.1);
     }
 
+    #[test]
+    fn test_curve_progress_and_discovery() {
+        // PumpPortal whole-token reserves at creation
+        let fresh = SignalContext::from_new_token(
+            "mint".to_string(),
+            "Token".to_string(),
+            "TKN".to_string(),
+            String::new(),
+            "creator".to_string(),
+            "curve".to_string(),
+            0,
+            1_073_000_000,
+            30,
+            28.0,
+        );
+        assert!(fresh.curve_progress_pct < 0.1);
+        assert_eq!(fresh.age_secs, Some(0));
+        assert_eq!(fresh.discovery_source, DiscoverySource::NewTokenEvent);
+
+        // Raw reserves halfway through the curve's real tokens
+        let trade = SignalContext::from_trade(
+            "mint".to_string(),
+            "curve".to_string(),
+            1_073_000_000_000_000 - 396_550_000_000_000,
+            50_000_000_000,
+            50.0,
+        );
+        assert!((trade.curve_progress_pct - 50.0).abs() < 0.5);
+        assert_eq!(trade.age_secs, None);
+        assert_eq!(trade.discovery_source, DiscoverySource::TradeEvent);
+    }
+
     #[test]
     fn test_signal_context_price() {
         let ctx = SignalContext::from_new_token(
@@ -1,51 +1,172 @@
 //! Wallet tracking for copy-trading
 //!
 //! Monitors specific wallet addresses and prioritizes their trades.
+//!
+//! Wallets come from `[wallet_tracking] wallets` plus those added at runtime
+//! with `snipe track add` (saved to `tracked_wallets.json`, shared by paper
+//! and live runs). A running bot applies `snipe track` changes through the
+//! control channel. Copies are attributed to the wallet they copied, and the
+//! closed copies of each wallet are summed in `copy_stats.json`; a wallet
+//! whose last `prune_sample` closed copies lost money in total is suggested
+//! for removal.
 
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use tracing::info;
+use std::sync::Mutex;
+use tracing::{info, warn};
 
-use crate::config::WalletTrackingConfig;
+use crate::config::{CopySettings, WalletTrackingConfig};
 use crate::error::{Error, Result};
 use crate::stream::decoder::TokenTradeEvent;
 
+/// Runtime-tracked wallets file in the credentials dir
+pub const TRACKED_WALLETS_FILE: &str = "tracked_wallets.json";
+
+/// Per-wallet copy statistics file in the credentials dir
+pub const COPY_STATS_FILE: &str = "copy_stats.json";
+
+/// A wallet added with `snipe track add`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedWallet {
+    /// Multiplier on the copy buy size (still capped by `max_copy_sol`)
+    #[serde(default = "default_size_mult")]
+    pub size_mult: f64,
+    /// Overrides `[wallet_tracking] mirror_sells` (None = use it)
+    #[serde(default)]
+    pub mirror_sells: Option<bool>,
+    pub added_at: DateTime<Utc>,
+}
+
+fn default_size_mult() -> f64 {
+    1.0
+}
+
+/// Copy trades attributed to one tracked wallet
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WalletCopyStats {
+    /// Copy buys sent
+    pub copied_trades: u32,
+    /// Copies fully closed
+    pub closed_trades: u32,
+    /// Closed copies with a positive net P&L
+    pub wins: u32,
+    /// Net P&L of all copy sells, partial exits included (SOL)
+    pub pnl_sol: f64,
+    /// Net P&L of the most recent closed copies, oldest first (SOL)
+    #[serde(default)]
+    pub recent_pnl_sol: VecDeque<f64>,
+}
+
+/// One wallet as shown by `snipe track list`
+#[derive(Debug, Clone)]
+pub struct TrackedWalletStatus {
+    pub address: String,
+    /// Listed in `[wallet_tracking] wallets` (can't be removed at runtime)
+    pub from_config: bool,
+    /// Runtime settings (None = config wallet with no `snipe track add`)
+    pub settings: Option<TrackedWallet>,
+    pub stats: WalletCopyStats,
+    /// Its last `prune_sample` closed copies lost money
+    pub prune_suggested: bool,
+}
+
+/// An open copy position and the wallet it copied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpenCopy {
+    wallet: String,
+    /// P&L of partial exits so far
+    pnl_sol: f64,
+}
+
+/// What `copy_stats.json` holds
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CopyStats {
+    wallets: HashMap<String, WalletCopyStats>,
+    /// Open copies by mint
+    open: HashMap<String, OpenCopy>,
+}
+
 /// Wallet tracker for copy-trading
 pub struct WalletTracker {
     config: WalletTrackingConfig,
-    tracked_wallets: HashSet<Pubkey>,
+    /// `[wallet_tracking] wallets`
+    config_wallets: HashSet<String>,
+    /// Wallets added at runtime, by address
+    wallets: Mutex<HashMap<String, TrackedWallet>>,
+    stats: Mutex<CopyStats>,
+    /// Where runtime wallets are saved (None = memory only)
+    persistence_path: Option<PathBuf>,
+    /// Where copy stats are saved (None = memory only)
+    stats_path: Option<PathBuf>,
 }
 
 impl WalletTracker {
     /// Create a new wallet tracker from config
     pub fn new(config: WalletTrackingConfig) -> Result<Self> {
-        let tracked_wallets = config
+        let config_wallets = config
             .wallets
             .iter()
-            .map(|w| Pubkey::from_str(w))
-            .collect::<std::result::Result<HashSet<_>, _>>()
-            .map_err(|e| Error::Config(format!("Invalid wallet address: {}", e)))?;
+            .map(|w| parse_wallet(w).map(|_| w.clone()))
+            .collect::<Result<HashSet<_>>>()?;
 
         info!(
             "Wallet tracker initialized with {} wallets",
-            tracked_wallets.len()
+            config_wallets.len()
         );
 
         Ok(Self {
             config,
-            tracked_wallets,
+            config_wallets,
+            wallets: Mutex::new(HashMap::new()),
+            stats: Mutex::new(CopyStats::default()),
+            persistence_path: None,
+            stats_path: None,
         })
     }
 
+    /// Restore runtime wallets saved at `path` (if any) and keep saving there
+    pub fn with_persistence(mut self, path: PathBuf) -> Self {
+        match load_json::<HashMap<String, TrackedWallet>>(&path) {
+            Ok(wallets) => {
+                if !wallets.is_empty() {
+                    info!("Restored {} runtime-tracked wallets", wallets.len());
+                }
+                *self.wallets.get_mut().unwrap() = wallets;
+            }
+            Err(e) => warn!("Ignoring tracked wallets file: {}", e),
+        }
+        self.persistence_path = Some(path);
+        self
+    }
+
+    /// Restore copy stats saved at `path` (if any) and keep saving there
+    pub fn with_stats(mut self, path: PathBuf) -> Self {
+        match load_json::<CopyStats>(&path) {
+            Ok(stats) => *self.stats.get_mut().unwrap() = stats,
+            Err(e) => warn!("Ignoring copy stats file: {}", e),
+        }
+        self.stats_path = Some(path);
+        self
+    }
+
     /// Check if wallet tracking is enabled
     pub fn is_enabled(&self) -> bool {
-        self.config.enabled && !self.tracked_wallets.is_empty()
+        self.config.enabled && self.wallet_count() > 0
     }
 
     /// Check if a wallet is being tracked
     pub fn is_tracked(&self, wallet: &Pubkey) -> bool {
-        self.tracked_wallets.contains(wallet)
+        self.is_tracked_address(&wallet.to_string())
+    }
+
+    /// Check if a wallet address is being tracked
+    pub fn is_tracked_address(&self, wallet: &str) -> bool {
+        self.config_wallets.contains(wallet) || self.wallets.lock().unwrap().contains_key(wallet)
     }
 
     /// Check if a trade event is from a tracked wallet
@@ -62,29 +183,277 @@ impl WalletTracker {
         self.config.priority_boost && self.is_tracked(wallet)
     }
 
-    /// Add a wallet to track
-    pub fn add_wallet(&mut self, wallet: Pubkey) {
-        self.tracked_wallets.insert(wallet);
-        info!("Added wallet to tracker: {}", wallet);
+    /// Copy-trading settings for a wallet: config overrides, then its
+    /// runtime settings
+    pub fn copy_settings(&self, wallet: &str) -> CopySettings {
+        let mut settings = self.config.copy_settings(wallet);
+        if let Some(tracked) = self.wallets.lock().unwrap().get(wallet) {
+            settings.size_mult = tracked.size_mult;
+            settings.mirror_sells = tracked.mirror_sells.unwrap_or(settings.mirror_sells);
+        }
+        settings
     }
 
-    /// Remove a wallet from tracking
-    pub fn remove_wallet(&mut self, wallet: &Pubkey) -> bool {
-        let removed = self.tracked_wallets.remove(wallet);
+    /// Track a wallet (or update its settings if already tracked)
+    ///
+    /// Returns true if the wallet was not tracked before.
+    pub fn add_wallet(
+        &self,
+        wallet: &str,
+        size_mult: f64,
+        mirror_sells: Option<bool>,
+    ) -> Result<bool> {
+        parse_wallet(wallet)?;
+        if !size_mult.is_finite() || size_mult <= 0.0 {
+            return Err(Error::Config(format!(
+                "size multiplier must be positive, got {}",
+                size_mult
+            )));
+        }
+        let was_tracked = self.is_tracked_address(wallet);
+        {
+            let mut wallets = self.wallets.lock().unwrap();
+            let added_at = wallets.get(wallet).map_or_else(Utc::now, |w| w.added_at);
+            wallets.insert(
+                wallet.to_string(),
+                TrackedWallet {
+                    size_mult,
+                    mirror_sells,
+                    added_at,
+                },
+            );
+            self.save_wallets(&wallets)?;
+        }
+        info!(
+            "Tracking wallet {} (size x{}, mirror sells: {})",
+            wallet,
+            size_mult,
+            mirror_sells.map_or("config".to_string(), |m| m.to_string())
+        );
+        Ok(!was_tracked)
+    }
+
+    /// Stop tracking a wallet added at runtime
+    ///
+    /// Wallets listed in `[wallet_tracking] wallets` are refused.
+    pub fn remove_wallet(&self, wallet: &str) -> Result<bool> {
+        if self.config_wallets.contains(wallet) {
+            return Err(Error::Config(format!(
+                "{} is listed in [wallet_tracking] wallets; remove it there",
+                wallet
+            )));
+        }
+        let removed = {
+            let mut wallets = self.wallets.lock().unwrap();
+            let removed = wallets.remove(wallet).is_some();
+            if removed {
+                self.save_wallets(&wallets)?;
+            }
+            removed
+        };
         if removed {
             info!("Removed wallet from tracker: {}", wallet);
         }
-        removed
+        Ok(removed)
     }
 
     /// Get all tracked wallets
-    pub fn get_wallets(&self) -> Vec<Pubkey> {
-        self.tracked_wallets.iter().cloned().collect()
+    pub fn get_wallets(&self) -> Vec<String> {
+        let wallets = self.wallets.lock().unwrap();
+        let mut all: Vec<String> = self
+            .config_wallets
+            .iter()
+            .chain(wallets.keys().filter(|w| !self.config_wallets.contains(*w)))
+            .cloned()
+            .collect();
+        all.sort();
+        all
     }
 
     /// Get number of tracked wallets
     pub fn wallet_count(&self) -> usize {
-        self.tracked_wallets.len()
+        let wallets = self.wallets.lock().unwrap();
+        self.config_wallets.len()
+            + wallets
+                .keys()
+                .filter(|w| !self.config_wallets.contains(*w))
+                .count()
+    }
+
+    /// Record a copy buy of `mint` made after `wallet` bought it
+    ///
+    /// Copies of wallets not tracked here (discovered wallets) are ignored.
+    pub fn record_copy(&self, wallet: &str, mint: &str) {
+        if !self.is_tracked_address(wallet) {
+            return;
+        }
+        let mut stats = self.stats.lock().unwrap();
+        stats
+            .wallets
+            .entry(wallet.to_string())
+            .or_default()
+            .copied_trades += 1;
+        stats.open.insert(
+            mint.to_string(),
+            OpenCopy {
+                wallet: wallet.to_string(),
+                pnl_sol: 0.0,
+            },
+        );
+        self.save_stats(&stats);
+    }
+
+    /// Attribute a full or partial close of a copy position to its wallet
+    ///
+    /// Returns the wallet if this close makes it a pruning candidate.
+    pub fn record_close(&self, mint: &str, pnl_sol: f64, fully_closed: bool) -> Option<String> {
+        let mut stats = self.stats.lock().unwrap();
+        let open = {
+            let open = stats.open.get_mut(mint)?;
+            open.pnl_sol += pnl_sol;
+            open.clone()
+        };
+        if fully_closed {
+            stats.open.remove(mint);
+        }
+        let sample = self.config.prune_sample;
+        let wallet_stats = stats.wallets.entry(open.wallet.clone()).or_default();
+        wallet_stats.pnl_sol += pnl_sol;
+        let mut newly_suggested = false;
+        if fully_closed {
+            let before = prune_suggested(wallet_stats, sample);
+            wallet_stats.closed_trades += 1;
+            if open.pnl_sol > 0.0 {
+                wallet_stats.wins += 1;
+            }
+            wallet_stats.recent_pnl_sol.push_back(open.pnl_sol);
+            while wallet_stats.recent_pnl_sol.len() > sample.max(1) {
+                wallet_stats.recent_pnl_sol.pop_front();
+            }
+            newly_suggested = !before && prune_suggested(wallet_stats, sample);
+        }
+        self.save_stats(&stats);
+        newly_suggested.then_some(open.wallet)
+    }
+
+    /// Copy stats of one wallet
+    pub fn stats(&self, wallet: &str) -> WalletCopyStats {
+        self.stats
+            .lock()
+            .unwrap()
+            .wallets
+            .get(wallet)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Every tracked wallet with its settings and stats
+    pub fn list(&self) -> Vec<TrackedWalletStatus> {
+        let wallets = self.wallets.lock().unwrap().clone();
+        self.get_wallets()
+            .into_iter()
+            .map(|address| {
+                let stats = self.stats(&address);
+                TrackedWalletStatus {
+                    from_config: self.config_wallets.contains(&address),
+                    settings: wallets.get(&address).cloned(),
+                    prune_suggested: prune_suggested(&stats, self.config.prune_sample),
+                    stats,
+                    address,
+                }
+            })
+            .collect()
+    }
+
+    fn save_wallets(&self, wallets: &HashMap<String, TrackedWallet>) -> Result<()> {
+        let Some(ref path) = self.persistence_path else {
+            return Ok(());
+        };
+        let data = serde_json::to_string_pretty(wallets)
+            .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+        std::fs::write(path, data).map_err(|e| Error::PositionPersistence(e.to_string()))
+    }
+
+    fn save_stats(&self, stats: &CopyStats) {
+        let Some(ref path) = self.stats_path else {
+            return;
+        };
+        let result = serde_json::to_string(stats)
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to save copy stats to {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// The last `sample` closed copies lost money in total (sample 0 = never)
+fn prune_suggested(stats: &WalletCopyStats, sample: usize) -> bool {
+    sample > 0
+        && stats.recent_pnl_sol.len() >= sample
+        && stats.recent_pnl_sol.iter().sum::<f64>() < 0.0
+}
+
+fn parse_wallet(wallet: &str) -> Result<Pubkey> {
+    Pubkey::from_str(wallet)
+        .map_err(|e| Error::Config(format!("Invalid wallet address {}: {}", wallet, e)))
+}
+
+fn load_json<T: Default + for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Token balances of tracked wallets, built from their trades in the feed
+///
+/// Used to turn a tracked wallet's sell into the share of its holding it
+/// sold, so a copy position can sell the same share.
+#[derive(Default)]
+pub struct TrackedHoldings {
+    /// (wallet, mint) -> tokens bought and not yet sold
+    holdings: DashMap<(String, String), u64>,
+}
+
+impl TrackedHoldings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a tracked wallet's buy
+    pub fn record_buy(&self, wallet: &str, mint: &str, token_amount: u64) {
+        *self
+            .holdings
+            .entry((wallet.to_string(), mint.to_string()))
+            .or_insert(0) += token_amount;
+    }
+
+    /// Record a tracked wallet's sell
+    ///
+    /// Returns the fraction of its holding the sell covered (capped at 1.0),
+    /// or None when the buy was never seen.
+    pub fn record_sell(&self, wallet: &str, mint: &str, token_amount: u64) -> Option<f64> {
+        let key = (wallet.to_string(), mint.to_string());
+        let held = self.holdings.get(&key).map(|h| *h).filter(|&h| h > 0)?;
+        let fraction = (token_amount as f64 / held as f64).min(1.0);
+        let remaining = held.saturating_sub(token_amount);
+        if remaining == 0 {
+            self.holdings.remove(&key);
+        } else {
+            self.holdings.insert(key, remaining);
+        }
+        Some(fraction)
+    }
+
+    /// Tokens a tracked wallet is known to hold
+    pub fn holding(&self, wallet: &str, mint: &str) -> u64 {
+        self.holdings
+            .get(&(wallet.to_string(), mint.to_string()))
+            .map(|h| *h)
+            .unwrap_or(0)
     }
 }
 
@@ -126,7 +495,8 @@ mod tests {
         WalletTrackingConfig {
             enabled: true,
             wallets: vec!["DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK".to_string()],
-            priority_boost: true,
+            auto_copy_trade: true,
+            ..Default::default()
         }
     }
 
@@ -143,21 +513,102 @@ mod tests {
 
     #[test]
     fn test_add_remove_wallet() {
-        let mut tracker = WalletTracker::new(WalletTrackingConfig {
-            enabled: true,
-            wallets: vec![],
-            priority_boost: true,
-        })
-        .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TRACKED_WALLETS_FILE);
+        let tracker = WalletTracker::new(test_config())
+            .unwrap()
+            .with_persistence(path.clone());
+
+        let wallet = Pubkey::new_unique().to_string();
+
+        assert!(!tracker.is_tracked_address(&wallet));
+        assert!(tracker.add_wallet("not-a-wallet", 1.0, None).is_err());
+        assert!(tracker.add_wallet(&wallet, 0.0, None).is_err());
+
+        assert!(tracker.add_wallet(&wallet, 2.0, Some(false)).unwrap());
+        assert!(tracker.is_tracked_address(&wallet));
+        assert_eq!(tracker.wallet_count(), 2);
+        let copy = tracker.copy_settings(&wallet);
+        assert!(!copy.mirror_sells);
+        // 10% of 0.5 SOL, doubled
+        assert!((copy.copy_amount_sol(0.5, 0.05) - 0.1).abs() < 1e-9);
+
+        // Picked up by the next start
+        let restarted = WalletTracker::new(test_config())
+            .unwrap()
+            .with_persistence(path.clone());
+        assert!(restarted.is_tracked_address(&wallet));
+        assert_eq!(restarted.copy_settings(&wallet).size_mult, 2.0);
 
-        let wallet = Pubkey::new_unique();
+        // Config wallets stay until removed from the config
+        assert!(tracker
+            .remove_wallet("DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK")
+            .is_err());
+        assert!(tracker.remove_wallet(&wallet).unwrap());
+        assert!(!tracker.remove_wallet(&wallet).unwrap());
+        assert!(!tracker.is_tracked_address(&wallet));
+        let restarted = WalletTracker::new(test_config())
+            .unwrap()
+            .with_persistence(path);
+        assert_eq!(restarted.wallet_count(), 1);
+    }
+
+    #[test]
+    fn test_copy_stats_and_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(COPY_STATS_FILE);
+        let config = WalletTrackingConfig {
+            prune_sample: 3,
+            ..test_config()
+        };
+        let wallet = "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK";
+        let tracker = WalletTracker::new(config.clone())
+            .unwrap()
+            .with_stats(path.clone());
+
+        // Untracked wallets and positions that aren't copies are ignored
+        tracker.record_copy("someone-else", "m0");
+        assert_eq!(tracker.record_close("m0", -1.0, true), None);
+        assert_eq!(tracker.stats("someone-else"), WalletCopyStats::default());
+
+        tracker.record_copy(wallet, "m1");
+        assert_eq!(tracker.record_close("m1", 0.05, false), None);
+        assert_eq!(tracker.record_close("m1", -0.02, true), None);
+        tracker.record_copy(wallet, "m2");
+        assert_eq!(tracker.record_close("m2", -0.04, true), None);
+        tracker.record_copy(wallet, "m3");
+        // Third closed copy: 0.03 - 0.04 - 0.01 < 0
+        assert_eq!(
+            tracker.record_close("m3", -0.01, true).as_deref(),
+            Some(wallet)
+        );
 
-        assert!(!tracker.is_tracked(&wallet));
+        let stats = tracker.stats(wallet);
+        assert_eq!(
+            (stats.copied_trades, stats.closed_trades, stats.wins),
+            (3, 3, 1)
+        );
+        assert!((stats.pnl_sol + 0.02).abs() < 1e-9);
+        assert!(tracker.list()[0].prune_suggested);
+
+        // Survives a restart
+        let restarted = WalletTracker::new(config).unwrap().with_stats(path);
+        assert_eq!(restarted.stats(wallet), stats);
+    }
+
+    #[test]
+    fn test_tracked_holdings() {
+        let holdings = TrackedHoldings::new();
+        assert_eq!(holdings.record_sell("w", "m", 100), None);
 
-        tracker.add_wallet(wallet);
-        assert!(tracker.is_tracked(&wallet));
+        holdings.record_buy("w", "m", 600);
+        holdings.record_buy("w", "m", 400);
+        assert_eq!(holdings.record_sell("w", "m", 250), Some(0.25));
+        assert_eq!(holdings.holding("w", "m"), 750);
 
-        tracker.remove_wallet(&wallet);
-        assert!(!tracker.is_tracked(&wallet));
+        // Selling more than we saw them buy is a full exit
+        assert_eq!(holdings.record_sell("w", "m", 1_000), Some(1.0));
+        assert_eq!(holdings.holding("w", "m"), 0);
+        assert_eq!(holdings.record_sell("w", "m", 1), None);
     }
 }
@@ -0,0 +1,158 @@
+//! Results of `snipe health`
+//!
+//! Each probe produces one [`HealthCheck`]. A failed critical check (the bot
+//! cannot trade without it) makes the command exit nonzero, so it can run
+//! from cron; failed optional checks only degrade the report.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Working, but needs attention (low balance, congestion, ...)
+    Warn,
+    Failed,
+    /// Not used by this config
+    Skipped,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Failed => "FAILED",
+            CheckStatus::Skipped => "SKIPPED",
+        })
+    }
+}
+
+/// One probed component
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    /// A failure means the bot cannot trade
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    pub message: String,
+    /// Extra lines (per-endpoint counters, ...)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+impl HealthCheck {
+    pub fn new(name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            critical: false,
+            latency_ms: None,
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    pub fn ok(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Ok, message)
+    }
+
+    pub fn warn(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, message)
+    }
+
+    pub fn failed(name: &str, message: impl fmt::Display) -> Self {
+        Self::new(name, CheckStatus::Failed, message.to_string())
+    }
+
+    pub fn skipped(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Skipped, message)
+    }
+
+    /// Mark as needed for trading
+    pub fn critical(mut self) -> Self {
+        self.critical = true;
+        self
+    }
+
+    pub fn with_latency(mut self, latency_ms: u64) -> Self {
+        self.latency_ms = Some(latency_ms);
+        self
+    }
+
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+impl fmt::Display for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<24} {:<7}", self.name, self.status)?;
+        if let Some(latency) = self.latency_ms {
+            write!(f, " ({}ms)", latency)?;
+        }
+        if !self.message.is_empty() {
+            write!(f, " {}", self.message)?;
+        }
+        for line in &self.details {
+            write!(f, "\n  {}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// All checks of one run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    pub fn push(&mut self, check: HealthCheck) {
+        self.checks.push(check);
+    }
+
+    /// Critical checks that failed
+    pub fn critical_failures(&self) -> Vec<&HealthCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.critical && c.status == CheckStatus::Failed)
+            .collect()
+    }
+
+    /// Nothing failed and nothing warned
+    pub fn healthy(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|c| matches!(c.status, CheckStatus::Ok | CheckStatus::Skipped))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_critical_failures_count() {
+        let mut report = HealthReport::default();
+        report.push(HealthCheck::ok("RPC", "").critical().with_latency(40));
+        report.push(HealthCheck::failed("DexScreener", "timed out"));
+        assert!(report.critical_failures().is_empty());
+        assert!(!report.healthy());
+
+        report.push(HealthCheck::failed("Keypair", "not found").critical());
+        let failures = report.critical_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "Keypair");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["checks"][0]["status"], "ok");
+        assert_eq!(json["checks"][0]["latency_ms"], 40);
+        assert!(json["checks"][1].get("latency_ms").is_none());
+    }
+}
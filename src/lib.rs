@@ -2,13 +2,23 @@
 //!
 //! High-performance token sniper for pump.fun using Jito ShredStream.
 
+pub mod backtest;
 pub mod cli;
 pub mod config;
+pub mod config_check;
+pub mod config_reload;
+pub mod control;
 pub mod dexscreener;
 pub mod error;
 pub mod filter;
+pub mod health;
+pub mod notify;
 pub mod position;
+pub mod price_source;
 pub mod pump;
+pub mod run_state;
+pub mod scan_history;
+pub mod sol_price;
 pub mod strategy;
 pub mod stream;
 pub mod trading;
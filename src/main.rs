@@ -7,12 +7,13 @@
 //! - Testnet success does NOT equal mainnet success.
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tracing::{error, info, warn};
 
 // Use the library crate
 use pumpfun_sniper::cli::commands;
-use pumpfun_sniper::config::Config;
+use pumpfun_sniper::config::{Config, ConfigSource};
+use pumpfun_sniper::run_state::RunMode;
 
 /// Pump.fun Sniper Bot - High-performance token sniper
 #[derive(Parser)]
@@ -20,9 +21,17 @@ use pumpfun_sniper::config::Config;
 #[command(author, version, about, long_about = None)]
 struct Cli {
     /// Path to config file
-    #[arg(short, long, default_value = "config.toml")]
+    #[arg(short, long, default_value = "config.toml", global = true)]
     config: String,
 
+    /// Overlay config.<PROFILE>.toml on the config file (default: $SNIPE_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Load wallets.json even if it doesn't match the keypair files
+    #[arg(long, global = true)]
+    allow_mismatch: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -31,9 +40,33 @@ struct Cli {
 enum Commands {
     /// Start the sniper bot
     Start {
-        /// Run in dry-run mode (no real trades)
+        /// Run in dry-run mode (no real trades); `--dry-run=simulate` also
+        /// builds, signs and simulates every buy and sell without sending it
+        #[arg(
+            long,
+            value_enum,
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "light"
+        )]
+        dry_run: Option<DryRunLevel>,
+
+        /// Trade with simulated fills against the live curves (positions in paper_positions.json)
+        #[arg(long, conflicts_with = "dry_run")]
+        paper: bool,
+
+        /// Append every new-token and trade event to this file for `snipe backtest`
         #[arg(long)]
-        dry_run: bool,
+        record: Option<String>,
+
+        /// Skip the single-instance run lock (only for instances that keep
+        /// their positions apart)
+        #[arg(long)]
+        allow_multiple: bool,
+
+        /// Sell every open position on Ctrl-C before exiting
+        #[arg(long)]
+        flatten: bool,
     },
 
     /// Manually sell a token position
@@ -54,14 +87,170 @@ enum Commands {
         dry_run: bool,
     },
 
+    /// Emergency: sell 100% of every open position now (works under the emergency lock)
+    SellAll {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+
+        /// Simulate only, don't execute
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Show current positions and P&L
-    Status,
+    Status {
+        /// Show execution quality and slippage/size adjustments instead
+        #[arg(long)]
+        execution: bool,
 
-    /// Show current configuration (secrets masked)
-    Config,
+        /// Print the positions and P&L as JSON
+        #[arg(long, conflicts_with = "execution")]
+        json: bool,
 
-    /// Check system health (RPC, ShredStream, Jito)
-    Health,
+        /// Refresh continuously
+        #[arg(long, conflicts_with = "execution")]
+        watch: bool,
+
+        /// Refresh interval in seconds for watch mode
+        #[arg(long, default_value = "5")]
+        interval: u64,
+    },
+
+    /// Show the resolved configuration and its overrides (secrets masked)
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+
+    /// Check system health (RPC, feeds, Jito, Helius, DexScreener, keypair)
+    ///
+    /// Exits nonzero when a check needed for trading fails.
+    Health {
+        /// Print the checks as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect, override or close one position
+    #[command(alias = "positions")]
+    Position {
+        #[command(subcommand)]
+        action: PositionAction,
+    },
+
+    /// Strategy profiles of the running bot
+    Strategy {
+        #[command(subcommand)]
+        action: StrategyAction,
+    },
+
+    /// Make the running bot re-read the config file now
+    Reload,
+
+    /// Export closed trade history for tax/analysis
+    Export {
+        /// Output format: csv, json
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Only trades exited on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Only trades exited on or before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(long)]
+        out: Option<String>,
+
+        /// Only trades for this mint
+        #[arg(long)]
+        mint: Option<String>,
+
+        /// Only trades with this exit reason (e.g. stop_loss, take_profit, kill_switch)
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Inspect or clear re-entry cooldowns and mint blacklists
+    Cooldowns {
+        #[command(subcommand)]
+        action: CooldownAction,
+    },
+
+    /// Manage known deployer / sniper / trusted wallet lists
+    Actors {
+        #[command(subcommand)]
+        action: ActorsAction,
+    },
+
+    /// Manage copy-traded wallets (applied live to a running bot)
+    Track {
+        #[command(subcommand)]
+        action: TrackAction,
+    },
+
+    /// Manage the operator deny / allow lists (creators, mints, symbol regexes)
+    List {
+        #[command(subcommand)]
+        action: ListAction,
+    },
+
+    /// Inspect wallets found by trusted wallet discovery
+    SmartMoney {
+        #[command(subcommand)]
+        action: SmartMoneyAction,
+    },
+
+    /// Inspect the adaptive filter's decision log
+    Decisions {
+        #[command(subcommand)]
+        action: DecisionsAction,
+    },
+
+    /// Propose signal weights from logged entries and trade outcomes (never applies them)
+    Tune {
+        /// Samples per signal needed before proposing a change
+        #[arg(long, default_value_t = pumpfun_sniper::filter::tuning::DEFAULT_MIN_SAMPLES)]
+        min_samples: usize,
+    },
+
+    /// Peak/drawdown analytics and shadow win rates over closed trades
+    Stats {
+        #[command(subcommand)]
+        view: Option<StatsView>,
+
+        /// Only trades exited on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, global = true)]
+        from: Option<String>,
+
+        /// Only trades exited on or before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, global = true)]
+        to: Option<String>,
+    },
+
+    /// Replay a session recorded with `start --record` through the filters,
+    /// strategy engine and exit rules with simulated fills
+    Backtest {
+        /// Recorded session (JSON lines)
+        #[arg(long)]
+        events: String,
+    },
+
+    /// Telegram / Discord notifications
+    Notify {
+        #[command(subcommand)]
+        action: NotifyAction,
+    },
+
+    /// Deployed pump.fun program checks
+    Pump {
+        #[command(subcommand)]
+        action: PumpAction,
+    },
 
     /// Wallet management commands
     Wallet {
@@ -106,6 +295,9 @@ enum Commands {
         /// Scan interval in seconds for watch mode
         #[arg(long, default_value = "30")]
         interval: u64,
+
+        #[command(subcommand)]
+        action: Option<ScanAction>,
     },
 
     /// Scan DexScreener for hot tokens with momentum (uses Survivor Mode validation)
@@ -152,6 +344,269 @@ enum Commands {
     },
 }
 
+/// What `snipe start --dry-run` exercises
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DryRunLevel {
+    /// Decide and log only, nothing is built
+    Light,
+    /// Paper fills; each buy and sell is also built, signed and simulated
+    /// against the cluster, never sent
+    Simulate,
+}
+
+#[derive(Subcommand)]
+enum PositionAction {
+    /// Override stop loss / take profit / trailing stop for one position
+    SetStop {
+        /// Token mint address
+        mint: String,
+
+        /// Stop loss % below entry
+        #[arg(long)]
+        stop_loss_pct: Option<f64>,
+
+        /// Take profit % above entry
+        #[arg(long)]
+        take_profit_pct: Option<f64>,
+
+        /// Trailing stop distance % from peak
+        #[arg(long)]
+        trailing_pct: Option<f64>,
+    },
+
+    /// Suspend automatic exits (kill-switch stays active)
+    Hold {
+        /// Token mint address
+        mint: String,
+
+        /// Seconds to hold
+        #[arg(long)]
+        until_secs: u64,
+    },
+
+    /// Sell the whole position and record the proceeds
+    Close {
+        /// Token mint address
+        mint: String,
+
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Close with zero proceeds without selling (rugged or unsellable)
+    Abandon {
+        /// Token mint address
+        mint: String,
+
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print the full position record as JSON
+    Show {
+        /// Token mint address
+        mint: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum StrategyAction {
+    /// Switch to a named profile without restarting (`base` = no overrides)
+    Use {
+        /// Profile name from [strategy.profiles]
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CooldownAction {
+    /// List active cooldowns (sold, failed, rugged)
+    List,
+
+    /// Remove cooldowns by mint and/or kind
+    Clear {
+        /// Token mint address
+        mint: Option<String>,
+
+        /// Only entries of this kind: sold, failed, rugged
+        #[arg(long)]
+        kind: Option<String>,
+
+        /// Clear every entry
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ListAction {
+    /// Add an entry to a list
+    Add {
+        /// Address, or a regex for deny-symbol-regex
+        entry: String,
+
+        /// List: deny-creator, deny-mint, deny-symbol-regex, allow-creator
+        #[arg(long)]
+        kind: String,
+
+        /// Comment stored next to the entry
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Remove an entry from a list
+    Remove {
+        /// Address or regex, as added
+        entry: String,
+
+        /// List: deny-creator, deny-mint, deny-symbol-regex, allow-creator
+        #[arg(long)]
+        kind: String,
+    },
+
+    /// Show the lists
+    Show {
+        /// Only this list
+        #[arg(long)]
+        kind: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ActorsAction {
+    /// Add an address to a local list
+    Add {
+        /// Wallet address
+        address: String,
+
+        /// List: deployer, sniper, trusted
+        #[arg(long, default_value = "deployer")]
+        kind: String,
+
+        /// Comment stored next to the address
+        #[arg(long)]
+        note: Option<String>,
+    },
+
+    /// Remove an address from a local list
+    Remove {
+        /// Wallet address
+        address: String,
+
+        /// List: deployer, sniper, trusted
+        #[arg(long, default_value = "deployer")]
+        kind: String,
+    },
+
+    /// Show the local lists
+    List {
+        /// Only this list: deployer, sniper, trusted
+        #[arg(long)]
+        kind: Option<String>,
+    },
+
+    /// Print a creator's observed launch history
+    Stats {
+        /// Creator wallet address
+        creator: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrackAction {
+    /// Copy-trade a wallet (or update the settings of a tracked one)
+    Add {
+        /// Wallet address
+        address: String,
+
+        /// Multiplier on the copy buy size (still capped by max_copy_sol)
+        #[arg(long, default_value_t = 1.0)]
+        size_mult: f64,
+
+        /// Mirror its sells (default: [wallet_tracking] mirror_sells)
+        #[arg(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+        mirror_sells: Option<bool>,
+    },
+
+    /// Stop copy-trading a wallet added with `track add`
+    Remove {
+        /// Wallet address
+        address: String,
+    },
+
+    /// Show tracked wallets with the P&L of their copies
+    List,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check the configuration for bad values and risky combinations
+    Validate,
+}
+
+#[derive(Subcommand)]
+enum ScanAction {
+    /// Print a token's score, m5 and ratio across recorded scan cycles
+    History {
+        /// Token mint address
+        mint: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum NotifyAction {
+    /// Send a test message to every configured sink
+    Test,
+}
+
+#[derive(Subcommand)]
+enum PumpAction {
+    /// Pin the deployed program as known good (after reviewing the new version)
+    AcceptCurrent,
+}
+
+#[derive(Subcommand)]
+enum StatsView {
+    /// Precision and recall of rug predictions against later price drops
+    RugPredictor,
+}
+
+#[derive(Subcommand)]
+enum SmartMoneyAction {
+    /// Show discovered wallets and why they have their status
+    List {
+        /// Only this category: elite (promoted), demoted, candidate, or an
+        /// alpha category (true_signal, profitable, neutral, unprofitable, ...)
+        #[arg(long)]
+        category: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DecisionsAction {
+    /// Show the most recent decisions
+    Tail {
+        /// Number of decisions to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Keep printing new decisions as they are logged
+        #[arg(short, long)]
+        follow: bool,
+    },
+
+    /// Show every decision for a mint with its signal breakdown
+    Show {
+        /// Token mint address
+        mint: String,
+    },
+
+    /// Summarize arbitration conflicts: how often each component was overridden
+    Conflicts,
+}
+
 #[derive(Subcommand)]
 enum WalletAction {
     /// Show wallet status (all wallets, balances)
@@ -182,10 +637,39 @@ enum WalletAction {
         generate: bool,
     },
 
+    /// Encrypt a wallet's keypair file in place (passphrase from
+    /// SNIPER_KEYPAIR_PASSPHRASE or prompted)
+    Encrypt {
+        /// Wallet name
+        name: String,
+
+        /// Also save the passphrase in the OS keyring
+        #[arg(long)]
+        keyring: bool,
+    },
+
+    /// Check wallets.json against the keypair files (address and hash)
+    Verify,
+
+    /// Set a wallet's daily spend cap (buys and transfers)
+    SetLimit {
+        /// Wallet name
+        name: String,
+
+        /// Most SOL the wallet may spend per day
+        #[arg(long, value_name = "SOL", required_unless_present = "clear")]
+        daily_spend: Option<f64>,
+
+        /// Remove the cap
+        #[arg(long, conflicts_with = "daily_spend")]
+        clear: bool,
+    },
+
     /// Extract SOL to vault
     Extract {
         /// Amount in SOL
-        amount: f64,
+        #[arg(required_unless_present = "auto_status")]
+        amount: Option<f64>,
 
         /// Skip confirmation prompt
         #[arg(long)]
@@ -194,6 +678,10 @@ enum WalletAction {
         /// Simulate only, don't execute
         #[arg(long)]
         dry_run: bool,
+
+        /// Show the auto-extraction rules and today's extracted total
+        #[arg(long)]
+        auto_status: bool,
     },
 
     /// Transfer SOL between wallets
@@ -210,6 +698,33 @@ enum WalletAction {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+
+        /// Allow a transfer out of the vault (always asks for confirmation)
+        #[arg(long)]
+        unlock_vault: bool,
+    },
+
+    /// Sweep stray tokens to the vault, burn dust and close empty accounts
+    Sweep {
+        /// Destination (only "vault" or the vault address)
+        #[arg(long)]
+        to: String,
+
+        /// Minimum value in USD to transfer a balance
+        #[arg(long, default_value = "1.0")]
+        min_value_usd: f64,
+
+        /// Burn balances below the minimum value
+        #[arg(long)]
+        burn_dust: bool,
+
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+
+        /// Show the plan only, don't execute
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// View transfer history
@@ -261,9 +776,25 @@ async fn main() -> Result<()> {
 
     // Parse CLI arguments
     let cli = Cli::parse();
+    pumpfun_sniper::wallet::credentials::set_allow_mismatch(cli.allow_mismatch);
+    let config_source = ConfigSource::new(&cli.config, cli.profile.clone());
+
+    // Validation reports load errors itself, so it runs before the load below
+    if matches!(
+        cli.command,
+        Commands::Config {
+            action: Some(ConfigAction::Validate)
+        }
+    ) {
+        if let Err(e) = commands::config_validate(&config_source) {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
     // Load configuration
-    let config = match Config::load(&cli.config) {
+    let config = match Config::load_from(&config_source) {
         Ok(cfg) => cfg,
         Err(e) => {
             error!("Failed to load configuration: {}", e);
@@ -279,16 +810,53 @@ async fn main() -> Result<()> {
 
     // Execute command
     let result = match cli.command {
-        Commands::Start { dry_run } => commands::start(&config, dry_run).await,
+        Commands::Start {
+            dry_run,
+            paper,
+            record,
+            allow_multiple,
+            flatten,
+        } => {
+            let mode = match dry_run {
+                Some(DryRunLevel::Light) => RunMode::DryRun,
+                Some(DryRunLevel::Simulate) => RunMode::Simulate,
+                None if paper => RunMode::Paper,
+                None => RunMode::Live,
+            };
+            commands::start(
+                &config,
+                &config_source,
+                mode,
+                record.as_deref(),
+                allow_multiple,
+                flatten,
+            )
+            .await
+        }
         Commands::Sell {
             token,
             amount,
             force,
             dry_run,
         } => commands::sell(&config, &token, &amount, force, dry_run).await,
-        Commands::Status => commands::status(&config).await,
-        Commands::Config => commands::show_config(&config),
-        Commands::Health => commands::health(&config).await,
+        Commands::SellAll { force, dry_run } => commands::sell_all(&config, force, dry_run).await,
+        Commands::Status {
+            execution,
+            json,
+            watch,
+            interval,
+        } => {
+            if execution {
+                commands::execution_status(&config)
+            } else {
+                commands::status(&config, json, watch, interval).await
+            }
+        }
+        Commands::Config { action: None } => commands::show_config(&config, &config_source).await,
+        Commands::Config {
+            action: Some(ConfigAction::Validate),
+        } => commands::config_validate(&config_source),
+        Commands::Health { json } => commands::health(&config, json).await,
         Commands::Scan {
             min_liquidity,
             max_liquidity,
@@ -299,6 +867,7 @@ async fn main() -> Result<()> {
             format,
             watch,
             interval,
+            action: None,
         } => {
             commands::scan(
                 &config,
@@ -314,6 +883,10 @@ async fn main() -> Result<()> {
             )
             .await
         }
+        Commands::Scan {
+            action: Some(ScanAction::History { mint }),
+            ..
+        } => commands::scan_history(&config, &mint),
         Commands::HotScan {
             min_m5,
             min_ratio,
@@ -341,6 +914,116 @@ async fn main() -> Result<()> {
             )
             .await
         }
+        Commands::Position { action } => match action {
+            PositionAction::SetStop {
+                mint,
+                stop_loss_pct,
+                take_profit_pct,
+                trailing_pct,
+            } => {
+                commands::position_set_stop(
+                    &config,
+                    &mint,
+                    stop_loss_pct,
+                    take_profit_pct,
+                    trailing_pct,
+                )
+                .await
+            }
+            PositionAction::Hold { mint, until_secs } => {
+                commands::position_hold(&config, &mint, until_secs).await
+            }
+            PositionAction::Close { mint, force } => {
+                commands::position_close(&config, &mint, force).await
+            }
+            PositionAction::Abandon { mint, force } => {
+                commands::position_abandon(&config, &mint, force).await
+            }
+            PositionAction::Show { mint } => commands::position_show(&config, &mint).await,
+        },
+        Commands::Strategy { action } => match action {
+            StrategyAction::Use { name } => commands::strategy_use(&config, &name).await,
+        },
+        Commands::Reload => commands::reload(&config).await,
+        Commands::Export {
+            format,
+            from,
+            to,
+            out,
+            mint,
+            reason,
+        } => {
+            commands::export(
+                &config,
+                &format,
+                from.as_deref(),
+                to.as_deref(),
+                out.as_deref(),
+                mint,
+                reason.as_deref(),
+            )
+            .await
+        }
+        Commands::Cooldowns { action } => match action {
+            CooldownAction::List => commands::cooldowns_list(&config).await,
+            CooldownAction::Clear { mint, kind, all } => {
+                commands::cooldowns_clear(&config, mint.as_deref(), kind.as_deref(), all).await
+            }
+        },
+        Commands::Actors { action } => match action {
+            ActorsAction::Add {
+                address,
+                kind,
+                note,
+            } => commands::actors_add(&config, &address, &kind, note.as_deref()),
+            ActorsAction::Remove { address, kind } => {
+                commands::actors_remove(&config, &address, &kind)
+            }
+            ActorsAction::List { kind } => commands::actors_list(&config, kind.as_deref()),
+            ActorsAction::Stats { creator } => commands::actors_stats(&config, &creator),
+        },
+        Commands::Track { action } => match action {
+            TrackAction::Add {
+                address,
+                size_mult,
+                mirror_sells,
+            } => commands::track_add(&config, &address, size_mult, mirror_sells).await,
+            TrackAction::Remove { address } => commands::track_remove(&config, &address).await,
+            TrackAction::List => commands::track_list(&config),
+        },
+        Commands::List { action } => match action {
+            ListAction::Add { entry, kind, note } => {
+                commands::list_add(&config, &kind, &entry, note.as_deref())
+            }
+            ListAction::Remove { entry, kind } => commands::list_remove(&config, &kind, &entry),
+            ListAction::Show { kind } => commands::list_show(&config, kind.as_deref()),
+        },
+        Commands::SmartMoney { action } => match action {
+            SmartMoneyAction::List { category } => {
+                commands::smart_money_list(&config, category.as_deref())
+            }
+        },
+        Commands::Decisions { action } => match action {
+            DecisionsAction::Tail { limit, follow } => {
+                commands::decisions_tail(&config, limit, follow).await
+            }
+            DecisionsAction::Show { mint } => commands::decisions_show(&config, &mint),
+            DecisionsAction::Conflicts => commands::decisions_conflicts(&config),
+        },
+        Commands::Tune { min_samples } => commands::tune(&config, min_samples),
+        Commands::Stats { view, from, to } => match view {
+            Some(StatsView::RugPredictor) => {
+                commands::stats_rug_predictor(&config, from.as_deref(), to.as_deref())
+            }
+            None => commands::stats(&config, from.as_deref(), to.as_deref()).await,
+        },
+        Commands::Backtest { events } => commands::backtest(&config, &events).await,
+        Commands::Notify { action } => match action {
+            NotifyAction::Test => commands::notify_test(&config).await,
+        },
+        Commands::Pump { action } => match action {
+            PumpAction::AcceptCurrent => commands::pump_accept_current(&config, &config_source),
+        },
         Commands::Wallet { action } => match action {
             WalletAction::Status => commands::wallet_status(&config).await,
             WalletAction::List => commands::wallet_list(&config).await,
@@ -353,17 +1036,42 @@ async fn main() -> Result<()> {
             } => {
                 commands::wallet_add(&config, &name, &alias, &wallet_type, address, generate).await
             }
+            WalletAction::Encrypt { name, keyring } => {
+                commands::wallet_encrypt(&config, &name, keyring).await
+            }
+            WalletAction::Verify => commands::wallet_verify(&config).await,
+            WalletAction::SetLimit {
+                name,
+                daily_spend,
+                clear: _,
+            } => commands::wallet_set_limit(&config, &name, daily_spend).await,
             WalletAction::Extract {
                 amount,
                 force,
                 dry_run,
-            } => commands::wallet_extract(&config, amount, force, dry_run).await,
+                auto_status,
+            } => match amount {
+                Some(amount) if !auto_status => {
+                    commands::wallet_extract(&config, amount, force, dry_run).await
+                }
+                _ => commands::wallet_extract_status(&config).await,
+            },
             WalletAction::Transfer {
                 from,
                 to,
                 amount,
                 force,
-            } => commands::wallet_transfer(&config, &from, &to, amount, force).await,
+                unlock_vault,
+            } => commands::wallet_transfer(&config, &from, &to, amount, force, unlock_vault).await,
+            WalletAction::Sweep {
+                to,
+                min_value_usd,
+                burn_dust,
+                force,
+                dry_run,
+            } => {
+                commands::wallet_sweep(&config, &to, min_value_usd, burn_dust, force, dry_run).await
+            }
             WalletAction::History { limit } => commands::wallet_history(&config, limit).await,
             WalletAction::Proposals { approve, reject } => {
                 commands::wallet_proposals(&config, approve, reject).await
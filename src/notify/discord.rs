@@ -0,0 +1,56 @@
+//! Discord webhook sink
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::{post_json, truncate, NotifySink};
+use crate::error::Result;
+
+/// Discord's message length limit
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// `[notifications.discord]`
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscordConfig {
+    /// Channel webhook URL (Channel settings > Integrations > Webhooks)
+    pub webhook_url: String,
+}
+
+impl DiscordConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.webhook_url.is_empty()
+    }
+}
+
+/// Posts messages to a Discord channel webhook
+pub struct DiscordSink {
+    config: DiscordConfig,
+    client: reqwest::Client,
+}
+
+impl DiscordSink {
+    pub fn new(config: DiscordConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl NotifySink for DiscordSink {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    async fn send(&self, text: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "content": truncate(text, MAX_MESSAGE_CHARS),
+        });
+        post_json(&self.client, &self.config.webhook_url, &body).await
+    }
+}
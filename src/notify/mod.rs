@@ -0,0 +1,642 @@
+//! Trade and alert notifications (Telegram, Discord)
+//!
+//! `Notifier` is a cheap handle the trading code reports events to. Events
+//! go through a bounded queue to a background task, so a slow or failing
+//! sink never blocks a trade; when the queue is full the event is dropped.
+//! The task batches everything arriving within `batch_window_secs` into one
+//! message and keeps `min_interval_secs` between sends, which keeps busy
+//! periods under the APIs' rate limits.
+
+pub mod discord;
+pub mod telegram;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::position::manager::EntryType;
+use crate::wallet::manager::BalanceAlert;
+
+pub use discord::{DiscordConfig, DiscordSink};
+pub use telegram::{TelegramConfig, TelegramSink};
+
+/// `[notifications]` configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationsConfig {
+    /// Send notifications from `snipe start` / `snipe hot-scan`
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub telegram: Option<TelegramConfig>,
+    #[serde(default)]
+    pub discord: Option<DiscordConfig>,
+    /// Events arriving within this window go out as one message
+    #[serde(default = "default_batch_window_secs")]
+    pub batch_window_secs: u64,
+    /// Minimum time between two messages
+    #[serde(default = "default_min_interval_secs")]
+    pub min_interval_secs: u64,
+    /// Events waiting to be sent; more are dropped
+    #[serde(default = "default_queue_size")]
+    pub queue_size: usize,
+    /// Events listed per message; the rest are counted
+    #[serde(default = "default_max_batch")]
+    pub max_batch: usize,
+    /// Report a stream disconnect once it has lasted this long
+    #[serde(default = "default_stream_down_secs")]
+    pub stream_down_secs: u64,
+}
+
+fn default_batch_window_secs() -> u64 {
+    5
+}
+
+fn default_min_interval_secs() -> u64 {
+    3
+}
+
+fn default_queue_size() -> usize {
+    256
+}
+
+fn default_max_batch() -> usize {
+    20
+}
+
+fn default_stream_down_secs() -> u64 {
+    30
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            telegram: None,
+            discord: None,
+            batch_window_secs: default_batch_window_secs(),
+            min_interval_secs: default_min_interval_secs(),
+            queue_size: default_queue_size(),
+            max_batch: default_max_batch(),
+            stream_down_secs: default_stream_down_secs(),
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// Sinks with complete settings
+    pub fn sinks(&self) -> Vec<Box<dyn NotifySink>> {
+        let mut sinks: Vec<Box<dyn NotifySink>> = Vec::new();
+        if let Some(telegram) = self.telegram.as_ref().filter(|t| t.is_configured()) {
+            sinks.push(Box::new(TelegramSink::new(telegram.clone())));
+        }
+        if let Some(discord) = self.discord.as_ref().filter(|d| d.is_configured()) {
+            sinks.push(Box::new(DiscordSink::new(discord.clone())));
+        }
+        sinks
+    }
+}
+
+/// Somewhere to deliver notification text
+#[async_trait]
+pub trait NotifySink: Send + Sync {
+    /// Sink name for logs
+    fn name(&self) -> &'static str;
+
+    async fn send(&self, text: &str) -> Result<()>;
+}
+
+/// Longest wait honoured when an API answers 429
+const MAX_RETRY_AFTER_SECS: f64 = 30.0;
+
+/// POST a JSON body, waiting out one 429 before giving up
+pub(crate) async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<()> {
+    for attempt in 0..2 {
+        let response = client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| Error::Notification(e.without_url().to_string()))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let detail: serde_json::Value = response.json().await.unwrap_or_default();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS && attempt == 0 {
+            // Discord: retry_after; Telegram: parameters.retry_after
+            let retry_after = detail["retry_after"]
+                .as_f64()
+                .or_else(|| detail["parameters"]["retry_after"].as_f64())
+                .unwrap_or(1.0)
+                .clamp(0.0, MAX_RETRY_AFTER_SECS);
+            debug!("Rate limited, retrying in {:.1}s", retry_after);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+            continue;
+        }
+        return Err(Error::Notification(format!("HTTP {}: {}", status, detail)));
+    }
+    Err(Error::Notification("rate limited".to_string()))
+}
+
+/// Cut `text` to at most `max_chars` characters, marking the cut
+pub(crate) fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars.saturating_sub(2)).collect();
+    cut.push_str(" …");
+    cut
+}
+
+/// Something worth telling the operator about
+#[derive(Debug, Clone)]
+pub enum Notification {
+    PositionOpened {
+        symbol: String,
+        mint: String,
+        size_sol: f64,
+        entry_type: EntryType,
+        /// Filter score and recommendation, if the entry was scored
+        score: Option<String>,
+    },
+    PositionClosed {
+        symbol: String,
+        mint: String,
+        pnl_sol: f64,
+        pnl_pct: f64,
+        reason: String,
+        fully_closed: bool,
+    },
+    KillSwitch {
+        symbol: String,
+        mint: String,
+        reason: String,
+    },
+    DailyLossLimit {
+        lost_sol: f64,
+        limit_sol: f64,
+    },
+    /// `kind` is "entry" or "trade"
+    DailyTradeLimit {
+        kind: String,
+        count: u32,
+        limit: u32,
+    },
+    EmergencyLock,
+    StreamDown {
+        stream: String,
+        down_secs: u64,
+    },
+    Balance(BalanceAlert),
+    /// Scan score or buy/sell ratio jumped since the previous cycle
+    SecondWind {
+        symbol: String,
+        mint: String,
+        score_delta: f64,
+        ratio_delta: f64,
+    },
+    /// The pump.fun program changed or no longer decodes; new buys paused
+    ProgramDrift {
+        reason: String,
+    },
+    Test,
+}
+
+impl std::fmt::Display for Notification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Notification::PositionOpened {
+                symbol,
+                mint,
+                size_sol,
+                entry_type,
+                score,
+            } => {
+                write!(
+                    f,
+                    "BUY {} ({}) {:.4} SOL [{:?}]",
+                    symbol,
+                    short(mint),
+                    size_sol,
+                    entry_type
+                )?;
+                if let Some(score) = score {
+                    write!(f, " - {}", score)?;
+                }
+                Ok(())
+            }
+            Notification::PositionClosed {
+                symbol,
+                mint,
+                pnl_sol,
+                pnl_pct,
+                reason,
+                fully_closed,
+            } => write!(
+                f,
+                "{} {} ({}) {:+.4} SOL ({:+.1}%) - {}",
+                if *fully_closed {
+                    "SOLD"
+                } else {
+                    "PARTIAL SELL"
+                },
+                symbol,
+                short(mint),
+                pnl_sol,
+                pnl_pct,
+                reason
+            ),
+            Notification::KillSwitch {
+                symbol,
+                mint,
+                reason,
+            } => write!(f, "KILL-SWITCH {} ({}) - {}", symbol, short(mint), reason),
+            Notification::DailyLossLimit {
+                lost_sol,
+                limit_sol,
+            } => write!(
+                f,
+                "DAILY LOSS LIMIT reached: {:.4} / {} SOL lost - new buys paused",
+                lost_sol, limit_sol
+            ),
+            Notification::DailyTradeLimit { kind, count, limit } => write!(
+                f,
+                "DAILY {} LIMIT reached: {} / {} today - new buys paused",
+                kind.to_uppercase(),
+                count,
+                limit
+            ),
+            Notification::EmergencyLock => {
+                write!(f, "EMERGENCY LOCK activated - new buys are refused")
+            }
+            Notification::StreamDown { stream, down_secs } => {
+                write!(f, "{} stream down for {}s", stream, down_secs)
+            }
+            Notification::Balance(alert) => write!(f, "WALLET {}", alert),
+            Notification::SecondWind {
+                symbol,
+                mint,
+                score_delta,
+                ratio_delta,
+            } => write!(
+                f,
+                "SECOND WIND {} ({}) score {:+.1}, buy/sell ratio {:+.1}",
+                symbol,
+                short(mint),
+                score_delta,
+                ratio_delta
+            ),
+            Notification::ProgramDrift { reason } => {
+                write!(f, "PUMP.FUN PROGRAM DRIFT: {} - new buys paused", reason)
+            }
+            Notification::Test => write!(f, "Test notification from snipe"),
+        }
+    }
+}
+
+fn short(mint: &str) -> &str {
+    &mint[..8.min(mint.len())]
+}
+
+/// One message for a batch of events
+fn render_batch(batch: &[Notification], max_batch: usize) -> String {
+    if let [only] = batch {
+        return only.to_string();
+    }
+    let mut lines = vec![format!("{} events:", batch.len())];
+    lines.extend(batch.iter().take(max_batch).map(|n| format!("- {}", n)));
+    if batch.len() > max_batch {
+        lines.push(format!("... and {} more", batch.len() - max_batch));
+    }
+    lines.join("\n")
+}
+
+/// Send `text` to every sink, logging failures
+async fn deliver(sinks: &[Box<dyn NotifySink>], text: &str) {
+    for sink in sinks {
+        if let Err(e) = sink.send(text).await {
+            warn!("{} notification failed: {}", sink.name(), e);
+        }
+    }
+}
+
+/// Settings and sinks the sender task delivers with
+#[derive(Default)]
+struct Delivery {
+    config: NotificationsConfig,
+    sinks: Arc<Vec<Box<dyn NotifySink>>>,
+}
+
+impl Delivery {
+    fn new(config: &NotificationsConfig) -> Self {
+        let delivery = Self {
+            config: config.clone(),
+            sinks: Arc::new(config.sinks()),
+        };
+        if !config.enabled {
+            info!("Notifications disabled");
+        } else if delivery.sinks.is_empty() {
+            warn!("Notifications enabled but no Telegram or Discord sink is configured");
+        } else {
+            info!(
+                "Notifications enabled: {}",
+                delivery
+                    .sinks
+                    .iter()
+                    .map(|s| s.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        delivery
+    }
+
+    fn active(&self) -> bool {
+        self.config.enabled && !self.sinks.is_empty()
+    }
+}
+
+/// Handle for queueing notifications (clones share the queue)
+#[derive(Clone, Default)]
+pub struct Notifier {
+    tx: Option<mpsc::Sender<Notification>>,
+    delivery: Arc<RwLock<Delivery>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl Notifier {
+    /// A notifier that discards everything
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Start the sender task
+    ///
+    /// The task runs even while notifications are off, so a config reload
+    /// can turn them on. `queue_size` only applies here.
+    pub fn spawn(config: &NotificationsConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.queue_size.max(1));
+        let delivery = Arc::new(RwLock::new(Delivery::new(config)));
+        tokio::spawn(Self::run(delivery.clone(), rx));
+        Self {
+            tx: Some(tx),
+            delivery,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Switch to reloaded settings and sinks (no effect on a disabled notifier)
+    pub fn reconfigure(&self, config: &NotificationsConfig) {
+        if self.tx.is_some() {
+            *self.delivery.write().unwrap() = Delivery::new(config);
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.tx.is_some() && self.delivery.read().unwrap().active()
+    }
+
+    /// Queue an event; never waits (a full queue drops it)
+    pub fn notify(&self, notification: Notification) {
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        if !self.delivery.read().unwrap().active() {
+            return;
+        }
+        if tx.try_send(notification).is_err() {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            if dropped.is_power_of_two() {
+                warn!("Notification queue full: {} events dropped", dropped);
+            }
+        }
+    }
+
+    async fn run(delivery: Arc<RwLock<Delivery>>, mut rx: mpsc::Receiver<Notification>) {
+        let mut last_sent: Option<tokio::time::Instant> = None;
+
+        while let Some(first) = rx.recv().await {
+            let (batch_window, min_interval) = {
+                let config = &delivery.read().unwrap().config;
+                (
+                    Duration::from_secs(config.batch_window_secs),
+                    Duration::from_secs(config.min_interval_secs),
+                )
+            };
+            let mut batch = vec![first];
+            let mut send_at = tokio::time::Instant::now() + batch_window;
+            if let Some(last) = last_sent {
+                send_at = send_at.max(last + min_interval);
+            }
+            let mut closed = false;
+            while !closed {
+                tokio::select! {
+                    next = rx.recv() => match next {
+                        Some(notification) => batch.push(notification),
+                        None => closed = true,
+                    },
+                    _ = tokio::time::sleep_until(send_at) => break,
+                }
+            }
+
+            // Sinks as of now: a reload while batching applies to this batch
+            let (sinks, max_batch) = {
+                let delivery = delivery.read().unwrap();
+                (delivery.sinks.clone(), delivery.config.max_batch)
+            };
+            debug!("Sending {} notifications", batch.len());
+            deliver(&sinks, &render_batch(&batch, max_batch)).await;
+            last_sent = Some(tokio::time::Instant::now());
+            if closed {
+                break;
+            }
+        }
+    }
+
+    /// Report hot wallet balance alerts
+    pub fn watch_balance(&self, mut alerts: broadcast::Receiver<BalanceAlert>) {
+        if self.tx.is_none() {
+            return;
+        }
+        let notifier = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match alerts.recv().await {
+                    Ok(alert) => notifier.notify(Notification::Balance(alert)),
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+/// Send one notification right away, bypassing the queue (CLI commands)
+///
+/// Returns each configured sink's result.
+pub async fn send_now(
+    config: &NotificationsConfig,
+    notification: &Notification,
+) -> Vec<(&'static str, Result<()>)> {
+    let text = notification.to_string();
+    let mut results = Vec::new();
+    for sink in config.sinks() {
+        results.push((sink.name(), sink.send(&text).await));
+    }
+    results
+}
+
+/// Reports a stream that stays disconnected longer than the threshold
+#[derive(Clone)]
+pub struct DisconnectWatch {
+    notifier: Notifier,
+    stream: String,
+    after: Duration,
+    down_since: Arc<Mutex<Option<Instant>>>,
+}
+
+impl DisconnectWatch {
+    pub fn new(notifier: Notifier, stream: &str, after_secs: u64) -> Self {
+        Self {
+            notifier,
+            stream: stream.to_string(),
+            after: Duration::from_secs(after_secs),
+            down_since: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The stream dropped (repeats while reconnecting are ignored)
+    pub fn disconnected(&self) {
+        if !self.notifier.is_enabled() {
+            return;
+        }
+        let since = {
+            let mut down_since = self.down_since.lock().unwrap();
+            if down_since.is_some() {
+                return;
+            }
+            let now = Instant::now();
+            *down_since = Some(now);
+            now
+        };
+        let watch = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(watch.after).await;
+            if *watch.down_since.lock().unwrap() == Some(since) {
+                watch.notifier.notify(Notification::StreamDown {
+                    stream: watch.stream.clone(),
+                    down_secs: since.elapsed().as_secs(),
+                });
+            }
+        });
+    }
+
+    /// The stream is back
+    pub fn connected(&self) {
+        *self.down_since.lock().unwrap() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl NotifySink for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
+
+        async fn send(&self, text: &str) -> Result<()> {
+            self.0.lock().unwrap().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    fn kill_switch(mint: &str) -> Notification {
+        Notification::KillSwitch {
+            symbol: "RUG".to_string(),
+            mint: mint.to_string(),
+            reason: "deployer sold".to_string(),
+        }
+    }
+
+    fn recording(sent: Arc<Mutex<Vec<String>>>) -> Arc<RwLock<Delivery>> {
+        Arc::new(RwLock::new(Delivery {
+            config: NotificationsConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            sinks: Arc::new(vec![Box::new(Recorder(sent))]),
+        }))
+    }
+
+    #[test]
+    fn test_render_batch() {
+        assert_eq!(
+            render_batch(&[Notification::Test], 2),
+            "Test notification from snipe"
+        );
+        let batch: Vec<Notification> = (0..3).map(|i| kill_switch(&format!("mint{}", i))).collect();
+        let text = render_batch(&batch, 2);
+        assert!(text.starts_with("3 events:"));
+        assert_eq!(text.lines().count(), 4);
+        assert!(text.ends_with("... and 1 more"));
+        assert_eq!(truncate("abcdef", 4), "ab …");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_events_are_batched() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let (tx, rx) = mpsc::channel(2);
+        let delivery = recording(sent.clone());
+        let task = tokio::spawn(Notifier::run(delivery.clone(), rx));
+        let notifier = Notifier {
+            tx: Some(tx),
+            delivery,
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+
+        notifier.notify(kill_switch("a"));
+        notifier.notify(kill_switch("b"));
+        // Queue is full: dropped without waiting
+        notifier.notify(kill_switch("c"));
+        assert_eq!(notifier.dropped.load(Ordering::Relaxed), 1);
+
+        drop(notifier);
+        task.await.unwrap();
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].starts_with("2 events:"));
+    }
+
+    #[tokio::test]
+    async fn test_reconfigure_turns_off() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let notifier = Notifier {
+            tx: Some(tx),
+            delivery: recording(Arc::new(Mutex::new(Vec::new()))),
+            dropped: Arc::new(AtomicU64::new(0)),
+        };
+        notifier.notify(kill_switch("a"));
+        assert!(notifier.is_enabled());
+
+        notifier.reconfigure(&NotificationsConfig::default());
+        notifier.notify(kill_switch("b"));
+        assert!(!notifier.is_enabled());
+        drop(notifier);
+        assert!(rx.recv().await.is_some());
+        assert!(rx.recv().await.is_none());
+    }
+}
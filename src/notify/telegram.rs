@@ -0,0 +1,192 @@
+//! Telegram Bot API sink
+//!
+//! Also carries dual confirmation of staged transfers: the one-time code is
+//! sent privately to each approver, and an approver's `/confirm <code>` reply
+//! is read back with `getUpdates`. The Telegram user ids identify who staged
+//! and who confirmed.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::{post_json, truncate, NotifySink};
+use crate::error::{Error, Result};
+
+/// Telegram's message length limit
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// `[notifications.telegram]`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramConfig {
+    /// Token from @BotFather
+    pub bot_token: String,
+    /// Chat (user, group or channel) the bot posts to
+    pub chat_id: String,
+    /// Telegram user id of the operator who stages transfers
+    #[serde(default)]
+    pub operator_user_id: Option<i64>,
+    /// Telegram users who may confirm staged transfers (each must have
+    /// started a private chat with the bot)
+    #[serde(default)]
+    pub approver_user_ids: Vec<i64>,
+}
+
+impl TelegramConfig {
+    pub fn is_configured(&self) -> bool {
+        !self.bot_token.is_empty() && !self.chat_id.is_empty()
+    }
+
+    /// Approvers other than the operator
+    pub fn approvers(&self) -> Vec<i64> {
+        self.approver_user_ids
+            .iter()
+            .copied()
+            .filter(|id| Some(*id) != self.operator_user_id)
+            .collect()
+    }
+}
+
+/// Identity recorded for a Telegram user
+pub fn user_identity(user_id: i64) -> String {
+    format!("telegram:{}", user_id)
+}
+
+/// A text message the bot received
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelegramMessage {
+    pub update_id: i64,
+    pub from_id: i64,
+    pub text: String,
+}
+
+/// Text messages in a `getUpdates` response
+fn parse_updates(body: &serde_json::Value) -> Vec<TelegramMessage> {
+    body["result"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|update| {
+            let message = &update["message"];
+            Some(TelegramMessage {
+                update_id: update["update_id"].as_i64()?,
+                from_id: message["from"]["id"].as_i64().unwrap_or_default(),
+                text: message["text"].as_str().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Code in a `/confirm <code>` (or `/confirm@bot <code>`) message
+pub fn confirm_code(text: &str) -> Option<&str> {
+    let (command, code) = text.trim().split_once(char::is_whitespace)?;
+    let command = command.split('@').next().unwrap_or_default();
+    (command == "/confirm" && !code.trim().is_empty()).then(|| code.trim())
+}
+
+/// Posts messages through a Telegram bot
+pub struct TelegramSink {
+    config: TelegramConfig,
+    client: reqwest::Client,
+}
+
+/// Longest `getUpdates` long poll
+const MAX_POLL_SECS: u64 = 25;
+
+impl TelegramSink {
+    pub fn new(config: TelegramConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(MAX_POLL_SECS + 10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn config(&self) -> &TelegramConfig {
+        &self.config
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!(
+            "https://api.telegram.org/bot{}/{}",
+            self.config.bot_token, method
+        )
+    }
+
+    /// Message a chat other than the configured one (a user id for a
+    /// private chat)
+    pub async fn send_to(&self, chat_id: &str, text: &str) -> Result<()> {
+        let body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": truncate(text, MAX_MESSAGE_CHARS),
+            "disable_web_page_preview": true,
+        });
+        post_json(&self.client, &self.url("sendMessage"), &body).await
+    }
+
+    /// Messages from `offset` on, waiting up to `wait_secs` for the first
+    pub async fn updates(&self, offset: i64, wait_secs: u64) -> Result<Vec<TelegramMessage>> {
+        let body = serde_json::json!({
+            "offset": offset,
+            "timeout": wait_secs.min(MAX_POLL_SECS),
+            "allowed_updates": ["message"],
+        });
+        let response = self
+            .client
+            .post(self.url("getUpdates"))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| Error::Notification(e.without_url().to_string()))?;
+        let status = response.status();
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Notification(e.without_url().to_string()))?;
+        if !status.is_success() {
+            return Err(Error::Notification(format!("HTTP {}: {}", status, body)));
+        }
+        Ok(parse_updates(&body))
+    }
+}
+
+#[async_trait]
+impl NotifySink for TelegramSink {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    async fn send(&self, text: &str) -> Result<()> {
+        self.send_to(&self.config.chat_id, text).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_replies() {
+        let body = serde_json::json!({
+            "ok": true,
+            "result": [
+                {"update_id": 7, "message": {"from": {"id": 42}, "text": "/confirm ABCD-EFGH"}},
+                {"update_id": 8, "edited_message": {"from": {"id": 42}, "text": "x"}},
+                {"update_id": 9, "message": {"from": {"id": 43}, "photo": []}}
+            ]
+        });
+        let messages = parse_updates(&body);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].from_id, 42);
+        assert_eq!(confirm_code(&messages[0].text), Some("ABCD-EFGH"));
+        assert_eq!(confirm_code(&messages[2].text), None);
+
+        assert_eq!(
+            confirm_code("/confirm@snipe_bot abcd efgh"),
+            Some("abcd efgh")
+        );
+        assert_eq!(confirm_code("/confirm"), None);
+        assert_eq!(confirm_code("/cancel ABCD"), None);
+    }
+}
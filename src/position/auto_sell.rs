@@ -1,185 +1,2062 @@
-//! Auto-sell logic for take-profit and stop-loss
+//! Position monitor: automatic exits for open positions
 //!
-//! WARNING: TP/SL is best-effort, not guaranteed. At 1-second polling,
-//! fast rugs can gap through your stop-loss before detection. This is
-//! unavoidable without sub-second polling (expensive) or on-chain
-//! stop-loss mechanisms (not available on pump.fun).
+//! `AutoSeller` owns the monitor loop shared by `snipe start` and
+//! `snipe hot-scan`. Each command plugs in where prices come from
+//! (`PriceProvider`) and how sells are sent (`PositionSeller`); everything
+//! else that used to differ between the two is a `MonitorConfig` profile
+//! under `[auto_sell.monitor.*]`.
+//!
+//! Exit checks run in priority order: kill-switch, stop loss, trailing stop,
+//! take profit, quick profit (50%), second profit (25%), no movement, max
+//! hold. Positions given an exit style at entry (scalp, trail, ladder,
+//! time-boxed) keep the stop loss and max hold but let an `ExitAdvisor`
+//! (the strategy engine's exit manager) decide the rest; positions without
+//! one use the rules above ("legacy"). With a `FlowWatch`, an early-weakness
+//! exit runs ahead of either when order flow turns negative between
+//! break-even and the stop. A manual hold suspends everything
+//! but the kill-switch. Sells
+//! requested from outside (mirrored copy-trade sells) come in as
+//! `ExitRequest`s and go through the same retry / give-up handling. With a
+//! migration watch, held positions' bonding curves are checked every few
+//! seconds; once one completes the position is priced from the migrated pool
+//! and the seller is told to sell there. Post-migration entries start there.
+//!
+//! WARNING: TP/SL is best-effort, not guaranteed. Between polls, fast rugs
+//! can gap through your stop-loss before detection. This is unavoidable
+//! without on-chain stop-loss mechanisms (not available on pump.fun).
 
+use async_trait::async_trait;
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{error, info};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::config::{
+    AutoSellConfig, Config, EntryTypeThresholds, KillSwitchExecutionPolicy, MonitorConfig,
+    PanicConfig,
+};
+use crate::error::{Error, ErrorCategory, Result};
+use crate::filter::{KillSwitchDecision, KillSwitchEvaluator, KillSwitchUrgency, RugRecorder};
+use crate::position::cooldowns::{CooldownKind, CooldownTracker};
+use crate::position::early_weakness::{check_early_weakness, FlowWatch};
+use crate::position::fees::TradeFees;
+use crate::position::history::{ExitDetails, SellFill, TradeExitReason};
+use crate::position::manager::{EntryType, Position, PositionManager};
+use crate::position::price_feed::PriceSource;
+use crate::position::reconcile::{query_token_balance, spawn_confirmation};
+use crate::price_source::CompositePriceSource;
+use crate::pump::accounts::BondingCurve;
+use crate::strategy::engine::StrategyEngine;
+use crate::strategy::exit_manager::ExitAction;
+use crate::strategy::types::{ExitStyle, TradingStrategy};
+use crate::trading::jito::JitoClient;
+use crate::trading::jupiter::JupiterClient;
+use crate::trading::paper::PaperTrader;
+use crate::trading::pumpportal_api::PumpPortalTrader;
+use crate::trading::pumpswap::PumpSwapTrader;
+use crate::wallet::{MultiWalletManager, SharedSigner};
+
+/// Trailing stop distance when dynamic trailing is off
+const FIXED_TRAILING_STOP_PCT: f64 = 5.0;
+
+/// Sell attempts before a position is given up
+pub const MAX_SELL_ATTEMPTS: u32 = 5;
+
+/// How often held positions' bonding curves are checked for completion
+const MIGRATION_CHECK_SECS: u64 = 10;
+
+/// Where the monitor gets a position's current price
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    /// Current price in SOL per token, or None to skip the position this pass
+    async fn price(&self, position: &Position) -> Option<f64>;
+
+    /// Called once per pass before `price`, so a provider can fetch all
+    /// positions in one request
+    async fn prefetch(&self, _positions: &[Position]) {}
+}
+
+/// Sends sells for the monitor (PumpPortal today, Jito bundles later)
+#[async_trait]
+pub trait PositionSeller: Send + Sync {
+    /// Sell `amount` ("100%", "50%", ...) of a position; `attempt` counts from 1
+    async fn sell(&self, position: &Position, amount: &str, attempt: u32) -> Result<String>;
+
+    /// Kill-switch sell routed and priced by its urgency's policy
+    async fn sell_with_policy(
+        &self,
+        position: &Position,
+        amount: &str,
+        attempt: u32,
+        _policy: &KillSwitchExecutionPolicy,
+    ) -> Result<String> {
+        self.sell(position, amount, attempt).await
+    }
+
+    /// Wallet holding the position's tokens
+    fn wallet_for(&self, position: &Position) -> Pubkey;
+
+    /// Estimated fees of the sell `signature` receiving `received_sol`,
+    /// with the Jito tip it actually paid
+    fn estimate_fees(&self, signature: &str, received_sol: f64) -> TradeFees;
+
+    /// SOL a landed sell received net of fees, when the seller knows it
+    /// without a balance query (paper fills); each sell is reported once
+    fn take_proceeds(&self, _signature: &str) -> Option<f64> {
+        None
+    }
+
+    /// The position's bonding curve completed; later sells go to its migrated pool
+    fn mark_migrated(&self, _mint: &str) {}
+
+    /// Attempt `attempt + 1` goes through another route, so a permanent
+    /// error on this one is still worth another try; `lightning_attempts` is
+    /// the kill-switch policy's (None = the seller's ladder)
+    fn has_fallback(
+        &self,
+        _position: &Position,
+        _attempt: u32,
+        _lightning_attempts: Option<u32>,
+    ) -> bool {
+        false
+    }
+}
+
+/// Prices kept current by the PumpPortal trade feed (`snipe start`)
+pub struct TradeFeedPrices;
+
+#[async_trait]
+impl PriceProvider for TradeFeedPrices {
+    async fn price(&self, position: &Position) -> Option<f64> {
+        (position.current_price > 0.0).then_some(position.current_price)
+    }
+}
+
+/// Prices from the price APIs in `[price_sources]` order, falling back to
+/// the last known price
+///
+/// All open positions are priced with one DexScreener batch request per
+/// pass; `price` then reads its cache before trying the other providers.
+pub struct ApiPrices {
+    sources: Arc<CompositePriceSource>,
+    /// Provider that last priced each mint, to log switches
+    last_source: std::sync::Mutex<HashMap<String, PriceSource>>,
+}
+
+impl ApiPrices {
+    pub fn new(sources: Arc<CompositePriceSource>) -> Self {
+        Self {
+            sources,
+            last_source: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for ApiPrices {
+    async fn prefetch(&self, positions: &[Position]) {
+        let mints: Vec<String> = positions.iter().map(|p| p.mint.clone()).collect();
+        self.sources.prefetch(&mints).await;
+        self.last_source
+            .lock()
+            .unwrap()
+            .retain(|mint, _| mints.contains(mint));
+    }
+
+    async fn price(&self, position: &Position) -> Option<f64> {
+        let Some(price) = self.sources.price(&position.mint).await else {
+            if position.current_price > 0.0 {
+                warn!(
+                    "[{}] No price from any source, using last known: {:.10}",
+                    position.symbol, position.current_price
+                );
+                return Some(position.current_price);
+            }
+            error!(
+                "[{}] No price from any source and no last price - skipping",
+                position.symbol
+            );
+            return None;
+        };
+        let previous = self
+            .last_source
+            .lock()
+            .unwrap()
+            .insert(position.mint.clone(), price.source);
+        if previous.is_some_and(|source| source != price.source) {
+            info!("[{}] Now priced by {}", position.symbol, price.source);
+        }
+        Some(price.price)
+    }
+}
+
+/// Where positions with an exit style get their exit decision
+#[async_trait]
+pub trait ExitAdvisor: Send + Sync {
+    async fn advise(
+        &self,
+        position: &Position,
+        style: &ExitStyle,
+        current_price: f64,
+        hold_time_secs: u64,
+    ) -> ExitAction;
+}
+
+/// The strategy engine's exit manager, with its delta trackers and price action
+pub struct StrategyExits {
+    engine: Arc<tokio::sync::RwLock<StrategyEngine>>,
+}
+
+impl StrategyExits {
+    pub fn new(engine: Arc<tokio::sync::RwLock<StrategyEngine>>) -> Self {
+        Self { engine }
+    }
+}
+
+#[async_trait]
+impl ExitAdvisor for StrategyExits {
+    async fn advise(
+        &self,
+        position: &Position,
+        style: &ExitStyle,
+        current_price: f64,
+        hold_time_secs: u64,
+    ) -> ExitAction {
+        let held = crate::strategy::types::Position {
+            mint: position.mint.clone(),
+            entry_price: position.entry_price,
+            entry_time: position.entry_time,
+            size_sol: position.total_cost_sol,
+            tokens_held: position.token_amount,
+            strategy: TradingStrategy::Adaptive,
+            exit_style: style.clone(),
+            highest_price: position.peak_price,
+            lowest_price: position.trough_price,
+            exit_levels_hit: position.exit_levels_hit.clone(),
+            creator: None,
+            funding_cluster: None,
+        };
+        self.engine
+            .write()
+            .await
+            .evaluate_exit_style(&held, style, current_price, hold_time_secs)
+            .await
+    }
+}
+
+/// Where one sell attempt is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SellRoute {
+    PumpSwap,
+    Jupiter,
+    Lightning,
+    Local,
+}
+
+/// Pricing and routing of one sell attempt
+struct SellPlan {
+    slippage_pct: u32,
+    priority_fee_sol: f64,
+    /// Lightning attempts before local signing (None = the seller's ladder)
+    lightning_attempts: Option<u32>,
+    preflight: bool,
+    jito_bundle: bool,
+}
+
+/// Slippage % and priority fee (SOL) of a kill-switch sell: `[trading.panic]`
+/// when its policy asks for it, the seller's own otherwise
+fn policy_pricing(
+    policy: &KillSwitchExecutionPolicy,
+    panic: &PanicConfig,
+    slippage_pct: u32,
+    priority_fee_sol: f64,
+) -> (u32, f64) {
+    if policy.panic_pricing {
+        (
+            panic.slippage_bps / 100,
+            panic.priority_fee_lamports as f64 / 1e9,
+        )
+    } else {
+        (slippage_pct, priority_fee_sol)
+    }
+}
+
+/// Sells through PumpPortal
+///
+/// Local API mode signs sells locally after
+/// `local_api_lightning_attempts` Lightning attempts (none by default).
+/// Lightning mode tries the Lightning API three times, then falls back to
+/// local signing (or signs locally first when built `for_liquidation`). Positions whose curve
+/// completed are sold natively against their PumpSwap pool on the first
+/// attempt (with a `PumpSwapTrader`), through Jupiter until the second (with
+/// a `JupiterClient`), then through PumpPortal's auto pool selection.
+/// Kill-switch sells follow their `[auto_sell.kill_switch_execution]` policy
+/// (the Lightning ladder when it sets no `lightning_attempts`).
+pub struct PumpPortalSeller {
+    trader: Arc<PumpPortalTrader>,
+    rpc_client: Arc<RpcClient>,
+    signer: SharedSigner,
+    multi_wallet: Option<Arc<MultiWalletManager>>,
+    default_wallet: Pubkey,
+    use_local_api: bool,
+    local_first: bool,
+    /// Lightning attempts before local signing in Local API mode
+    local_api_lightning_attempts: u32,
+    slippage_pct: u32,
+    priority_fee_sol: f64,
+    platform_fee_pct: f64,
+    panic: PanicConfig,
+    jupiter: Option<Arc<JupiterClient>>,
+    pumpswap: Option<Arc<PumpSwapTrader>>,
+    /// Bundles for kill-switch policies with `jito_bundle`
+    jito: Option<Arc<JitoClient>>,
+    /// Mints whose bonding curve completed
+    migrated: std::sync::Mutex<HashSet<String>>,
+}
+
+impl PumpPortalSeller {
+    pub fn new(
+        trader: Arc<PumpPortalTrader>,
+        rpc_client: Arc<RpcClient>,
+        signer: SharedSigner,
+        config: &Config,
+    ) -> Self {
+        let use_local_api =
+            config.pumpportal.api_key.is_empty() || config.pumpportal.force_local_api;
+        // Lightning trades settle in the wallet tied to the API key
+        let default_wallet = if use_local_api || config.pumpportal.lightning_wallet.is_empty() {
+            signer.pubkey()
+        } else {
+            Pubkey::from_str(&config.pumpportal.lightning_wallet).unwrap_or_else(|_| signer.pubkey())
+        };
+        Self {
+            trader,
+            rpc_client,
+            signer,
+            multi_wallet: None,
+            default_wallet,
+            use_local_api,
+            local_first: false,
+            local_api_lightning_attempts: 0,
+            slippage_pct: config.trading.slippage_bps / 100,
+            priority_fee_sol: config.trading.priority_fee_lamports as f64 / 1e9,
+            platform_fee_pct: config.pumpportal.platform_fee_pct(use_local_api),
+            panic: config.trading.panic.clone(),
+            jupiter: None,
+            pumpswap: None,
+            jito: None,
+            migrated: std::sync::Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Sign sells with the wallet that bought each position
+    pub fn with_multi_wallet(mut self, multi_wallet: Option<Arc<MultiWalletManager>>) -> Self {
+        self.multi_wallet = multi_wallet;
+        self
+    }
+
+    /// Sell migrated positions through Jupiter
+    pub fn with_jupiter(mut self, jupiter: Arc<JupiterClient>) -> Self {
+        self.jupiter = Some(jupiter);
+        self
+    }
+
+    /// Sell migrated positions directly against their PumpSwap pool
+    pub fn with_pumpswap(mut self, pumpswap: Arc<PumpSwapTrader>) -> Self {
+        self.pumpswap = Some(pumpswap);
+        self
+    }
+
+    /// Send kill-switch sells whose policy asks for it as Jito bundles
+    pub fn with_jito(mut self, jito: Option<Arc<JitoClient>>) -> Self {
+        self.jito = jito;
+        self
+    }
+
+    /// Try Lightning `attempts` times before signing locally in Local API
+    /// mode (the monitor profile's `local_api_lightning_attempts`)
+    pub fn with_local_api_lightning_attempts(mut self, attempts: u32) -> Self {
+        self.local_api_lightning_attempts = attempts;
+        self
+    }
+
+    /// Emergency liquidation: `[trading.panic]` slippage and priority fee,
+    /// local signing on the first attempt and Lightning only as the fallback
+    pub fn for_liquidation(mut self, panic: &PanicConfig) -> Self {
+        self.slippage_pct = panic.slippage_bps / 100;
+        self.priority_fee_sol = panic.priority_fee_lamports as f64 / 1e9;
+        self.local_first = true;
+        self
+    }
+
+    /// Signer for a position (multi-wallet lookup, primary signer otherwise)
+    fn signer_for(&self, position: &Position) -> SharedSigner {
+        if position.wallet_pubkey.is_empty() {
+            return self.signer.clone();
+        }
+        let Some(ref multi_wallet) = self.multi_wallet else {
+            return self.signer.clone();
+        };
+        match multi_wallet.find_by_address(&position.wallet_pubkey) {
+            Some(wallet) => Keypair::from_bytes(&wallet.keypair.to_bytes())
+                .map(|keypair| Arc::new(keypair) as SharedSigner)
+                .unwrap_or_else(|_| self.signer.clone()),
+            None => {
+                warn!(
+                    "[{}] Position wallet {} not found in multi-wallet, using primary",
+                    position.symbol,
+                    &position.wallet_pubkey[..8]
+                );
+                self.signer.clone()
+            }
+        }
+    }
+
+    /// The seller's own pricing and Lightning ladder
+    fn default_plan(&self) -> SellPlan {
+        SellPlan {
+            slippage_pct: self.slippage_pct,
+            priority_fee_sol: self.priority_fee_sol,
+            lightning_attempts: None,
+            preflight: false,
+            jito_bundle: false,
+        }
+    }
+
+    /// Where attempt `attempt` of a sell signed by `signer` goes
+    fn route(
+        &self,
+        position: &Position,
+        signer: &Pubkey,
+        attempt: u32,
+        lightning_attempts: Option<u32>,
+    ) -> SellRoute {
+        // Lightning positions sit in the API key's wallet, which only PumpPortal can sign for
+        let migrated = self.migrated.lock().unwrap().contains(&position.mint)
+            && self.wallet_for(position) == *signer;
+        if migrated && attempt == 1 && self.pumpswap.is_some() {
+            return SellRoute::PumpSwap;
+        }
+        if migrated && attempt <= 2 && self.jupiter.is_some() {
+            return SellRoute::Jupiter;
+        }
+        let use_lightning = match lightning_attempts {
+            Some(attempts) => !self.use_local_api && attempt <= attempts,
+            None if self.local_first => !self.use_local_api && attempt > 1,
+            None => attempt <= self.ladder_attempts(),
+        };
+        if use_lightning {
+            SellRoute::Lightning
+        } else {
+            SellRoute::Local
+        }
+    }
+
+    /// Lightning attempts of the seller's own ladder
+    fn ladder_attempts(&self) -> u32 {
+        if self.use_local_api {
+            self.local_api_lightning_attempts
+        } else {
+            3
+        }
+    }
+
+    /// Raw token units in `amount` ("NN%") of the signer's balance
+    fn tokens_to_sell(
+        &self,
+        position: &Position,
+        amount: &str,
+        signer: &SharedSigner,
+    ) -> Result<u64> {
+        let pct: u64 = amount
+            .trim_end_matches('%')
+            .parse()
+            .map_err(|_| Error::TransactionBuild(format!("Invalid sell amount: {}", amount)))?;
+        let balance = query_token_balance(&self.rpc_client, &signer.pubkey(), &position.mint);
+        let tokens = (balance as u128 * pct.min(100) as u128 / 100) as u64;
+        if tokens == 0 {
+            return Err(Error::InsufficientFunds(format!(
+                "No {} balance to sell",
+                position.symbol
+            )));
+        }
+        Ok(tokens)
+    }
+
+    /// Send attempt `attempt` of a sell as `plan` says
+    async fn send(
+        &self,
+        position: &Position,
+        amount: &str,
+        attempt: u32,
+        plan: &SellPlan,
+    ) -> Result<String> {
+        let signer = self.signer_for(position);
+        match self.route(position, &signer.pubkey(), attempt, plan.lightning_attempts) {
+            SellRoute::PumpSwap => {
+                if let Some(ref pumpswap) = self.pumpswap {
+                    info!("Attempting PumpSwap sell (attempt {})", attempt);
+                    let tokens = self.tokens_to_sell(position, amount, &signer)?;
+                    return pumpswap
+                        .sell(
+                            &position.mint,
+                            tokens,
+                            plan.slippage_pct * 100,
+                            signer.as_ref(),
+                            &self.rpc_client,
+                        )
+                        .await;
+                }
+            }
+            SellRoute::Jupiter => {
+                if let Some(ref jupiter) = self.jupiter {
+                    info!("Attempting Jupiter sell (attempt {})", attempt);
+                    let tokens = self.tokens_to_sell(position, amount, &signer)?;
+                    return jupiter
+                        .sell(
+                            &position.mint,
+                            tokens,
+                            plan.slippage_pct * 100,
+                            (plan.priority_fee_sol * 1e9) as u64,
+                            signer.as_ref(),
+                            &self.rpc_client,
+                        )
+                        .await;
+                }
+            }
+            SellRoute::Lightning => {
+                info!("Attempting Lightning API sell (attempt {})", attempt);
+                return self
+                    .trader
+                    .sell(
+                        &position.mint,
+                        amount,
+                        plan.slippage_pct,
+                        plan.priority_fee_sol,
+                    )
+                    .await;
+            }
+            SellRoute::Local => {}
+        }
+        let lightning_attempts = match plan.lightning_attempts {
+            Some(_) if self.use_local_api => 0,
+            Some(attempts) => attempts,
+            None => self.ladder_attempts(),
+        };
+        if self.local_first || lightning_attempts == 0 {
+            info!(
+                "Attempting Local API sell (attempt {}, wallet: {})",
+                attempt,
+                &signer.pubkey().to_string()[..8]
+            );
+        } else {
+            warn!(
+                "Lightning failed {}x, trying LOCAL SIGNING fallback (attempt {})",
+                lightning_attempts, attempt
+            );
+        }
+        if let Some(jito) = self.jito.as_ref().filter(|_| plan.jito_bundle) {
+            return self
+                .trader
+                .sell_with_jito(
+                    &position.mint,
+                    amount,
+                    plan.slippage_pct,
+                    signer.as_ref(),
+                    jito,
+                    &self.rpc_client,
+                    Some(jito.config().max_tip_lamports),
+                )
+                .await;
+        }
+        self.trader
+            .sell_local_with_preflight(
+                &position.mint,
+                amount,
+                plan.slippage_pct,
+                plan.priority_fee_sol,
+                &signer,
+                &self.rpc_client,
+                plan.preflight,
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl PositionSeller for PumpPortalSeller {
+    async fn sell(&self, position: &Position, amount: &str, attempt: u32) -> Result<String> {
+        self.send(position, amount, attempt, &self.default_plan())
+            .await
+    }
+
+    async fn sell_with_policy(
+        &self,
+        position: &Position,
+        amount: &str,
+        attempt: u32,
+        policy: &KillSwitchExecutionPolicy,
+    ) -> Result<String> {
+        let (slippage_pct, priority_fee_sol) = policy_pricing(
+            policy,
+            &self.panic,
+            self.slippage_pct,
+            self.priority_fee_sol,
+        );
+        let plan = SellPlan {
+            slippage_pct,
+            priority_fee_sol,
+            lightning_attempts: policy.lightning_attempts,
+            preflight: policy.preflight,
+            jito_bundle: policy.jito_bundle,
+        };
+        self.send(position, amount, attempt, &plan).await
+    }
+
+    fn wallet_for(&self, position: &Position) -> Pubkey {
+        if position.wallet_pubkey.is_empty() {
+            self.default_wallet
+        } else {
+            Pubkey::from_str(&position.wallet_pubkey).unwrap_or(self.default_wallet)
+        }
+    }
+
+    fn estimate_fees(&self, signature: &str, received_sol: f64) -> TradeFees {
+        TradeFees::estimate(
+            self.priority_fee_sol,
+            self.trader.take_jito_tip(signature),
+            received_sol,
+            self.platform_fee_pct,
+        )
+    }
 
-use crate::config::AutoSellConfig;
-use crate::error::Result;
-use crate::position::manager::{Position, PositionManager};
-use crate::position::price_feed::PriceUpdate;
+    fn mark_migrated(&self, mint: &str) {
+        self.migrated.lock().unwrap().insert(mint.to_string());
+    }
+
+    fn has_fallback(
+        &self,
+        position: &Position,
+        attempt: u32,
+        lightning_attempts: Option<u32>,
+    ) -> bool {
+        let signer = self.signer_for(position).pubkey();
+        self.route(position, &signer, attempt + 1, lightning_attempts)
+            != self.route(position, &signer, attempt, lightning_attempts)
+    }
+}
+
+/// Sells paper positions through a `PaperTrader` (`snipe start --paper`)
+pub struct PaperSeller {
+    trader: Arc<PaperTrader>,
+    slippage_pct: u32,
+    priority_fee_sol: f64,
+    platform_fee_pct: f64,
+    panic: PanicConfig,
+    /// signature -> SOL received net of fees, until `record_sell` takes it
+    proceeds: std::sync::Mutex<HashMap<String, f64>>,
+}
 
-/// Auto-sell trigger type
+impl PaperSeller {
+    pub fn new(trader: Arc<PaperTrader>, config: &Config, platform_fee_pct: f64) -> Self {
+        Self {
+            trader,
+            slippage_pct: config.trading.slippage_bps / 100,
+            priority_fee_sol: config.trading.priority_fee_lamports as f64 / 1e9,
+            platform_fee_pct,
+            panic: config.trading.panic.clone(),
+            proceeds: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn fill(
+        &self,
+        position: &Position,
+        amount: &str,
+        attempt: u32,
+        slippage_pct: u32,
+        priority_fee_sol: f64,
+    ) -> Result<String> {
+        info!(
+            "Paper sell of {} {} (attempt {})",
+            amount, position.symbol, attempt
+        );
+        let fill = self
+            .trader
+            .sell_fill(&position.mint, amount, slippage_pct, priority_fee_sol)
+            .await?;
+        self.proceeds
+            .lock()
+            .unwrap()
+            .insert(fill.signature.clone(), fill.sol - fill.fees.total_sol());
+        Ok(fill.signature)
+    }
+}
+
+#[async_trait]
+impl PositionSeller for PaperSeller {
+    async fn sell(&self, position: &Position, amount: &str, attempt: u32) -> Result<String> {
+        self.fill(
+            position,
+            amount,
+            attempt,
+            self.slippage_pct,
+            self.priority_fee_sol,
+        )
+        .await
+    }
+
+    /// Paper fills only pick up the policy's pricing
+    async fn sell_with_policy(
+        &self,
+        position: &Position,
+        amount: &str,
+        attempt: u32,
+        policy: &KillSwitchExecutionPolicy,
+    ) -> Result<String> {
+        let (slippage_pct, priority_fee_sol) = policy_pricing(
+            policy,
+            &self.panic,
+            self.slippage_pct,
+            self.priority_fee_sol,
+        );
+        self.fill(position, amount, attempt, slippage_pct, priority_fee_sol)
+            .await
+    }
+
+    fn wallet_for(&self, _position: &Position) -> Pubkey {
+        Pubkey::default()
+    }
+
+    fn estimate_fees(&self, _signature: &str, received_sol: f64) -> TradeFees {
+        TradeFees::estimate(
+            self.priority_fee_sol,
+            0,
+            received_sol,
+            self.platform_fee_pct,
+        )
+    }
+
+    fn take_proceeds(&self, signature: &str) -> Option<f64> {
+        self.proceeds.lock().unwrap().remove(signature)
+    }
+}
+
+/// Which part of a position an exit sells
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub enum TriggerType {
-    /// Take profit triggered
-    TakeProfit,
-    /// Stop loss triggered
-    StopLoss,
-}
-
-/// Auto-sell event
-#[derive(Debug, Clone)]
-pub struct AutoSellEvent {
-    /// Token mint
-    pub mint: Pubkey,
-    /// Trigger type
-    pub trigger: TriggerType,
-    /// Entry price
-    pub entry_price: f64,
-    /// Current price
-    pub current_price: f64,
-    /// P&L percentage
-    pub pnl_pct: f64,
-    /// Amount to sell (may be partial for take-profit)
-    pub sell_amount: u64,
-    /// Total position size
-    pub total_amount: u64,
-}
-
-/// Auto-seller that monitors positions and triggers sells
+pub enum ExitLayer {
+    /// Everything that's left
+    Full,
+    /// Layer 1: half the position
+    QuickProfit,
+    /// Layer 2: half of what's left after layer 1
+    SecondProfit,
+    /// This % (1-99) of what's left, mirroring a tracked wallet's sell
+    Mirror(u8),
+    /// This % (1-99) of what's left for the ladder tier at `level` % gain
+    Tier { level: f64, pct: u8 },
+    /// This % (1-99) of what's left, one slice of a split full exit
+    Slice(u8),
+    /// This % (1-99) of what's left, cut because a risk escalated
+    Reduce(u8),
+}
+
+impl ExitLayer {
+    /// Amount argument for the seller
+    pub fn sell_pct(&self) -> String {
+        match self {
+            ExitLayer::Full => "100%".to_string(),
+            ExitLayer::QuickProfit => "50%".to_string(),
+            ExitLayer::SecondProfit => "25%".to_string(),
+            ExitLayer::Mirror(pct)
+            | ExitLayer::Tier { pct, .. }
+            | ExitLayer::Slice(pct)
+            | ExitLayer::Reduce(pct) => format!("{}%", pct),
+        }
+    }
+}
+
+/// An exit the monitor decided on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitSignal {
+    pub layer: ExitLayer,
+    /// Log line; also classified into the `TradeExitReason`
+    pub reason: String,
+}
+
+impl ExitSignal {
+    fn full(reason: String) -> Self {
+        Self {
+            layer: ExitLayer::Full,
+            reason,
+        }
+    }
+
+    /// Full exit asked for by the operator (`snipe position close`)
+    pub fn manual() -> Self {
+        Self::full("MANUAL CLOSE requested by operator".to_string())
+    }
+
+    /// Sell `fraction` of the position because a tracked wallet sold that
+    /// share of its holding (rounded to whole %, a full exit from 99.5%)
+    pub fn mirror(fraction: f64, wallet: &str) -> Self {
+        let pct = (fraction * 100.0).round().clamp(1.0, 100.0) as u8;
+        let reason = format!("MIRRORED SELL: tracked wallet {} sold {}%", wallet, pct);
+        if pct >= 100 {
+            Self::full(reason)
+        } else {
+            Self {
+                layer: ExitLayer::Mirror(pct),
+                reason,
+            }
+        }
+    }
+
+    /// Sell `pct`% (1-99) of what's left because a risk escalated
+    pub fn reduce(pct: u8, reason: String) -> Self {
+        Self {
+            layer: ExitLayer::Reduce(pct.clamp(1, 99)),
+            reason,
+        }
+    }
+}
+
+impl ExitSignal {
+    /// Sell `sold + 1` of this full exit split into `slices` equal sells
+    /// (the last one sells whatever is left)
+    fn slice(&self, sold: u32, slices: u32) -> Self {
+        let left = slices.saturating_sub(sold).max(1);
+        let reason = format!("{} (slice {}/{})", self.reason, sold + 1, slices);
+        if left == 1 {
+            Self::full(reason)
+        } else {
+            Self {
+                layer: ExitLayer::Slice((100.0 / left as f64).round() as u8),
+                reason,
+            }
+        }
+    }
+}
+
+/// Failed sells of one position
+struct SellRetry {
+    attempts: u32,
+    /// Earliest next attempt (the last error's suggested backoff)
+    next_at: Instant,
+    /// First attempt
+    started_at: Instant,
+}
+
+/// What the monitor does after a failed sell
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SellFailure {
+    /// Try again once the backoff has passed
+    Retry(Duration),
+    /// The curve completed: sell from the migrated pool next
+    Migrated,
+    /// Permanent: retrying can't help, stop tracking the position
+    GiveUp,
+}
+
+/// Classify a failed sell; `has_fallback` = the next attempt takes another route
+fn on_sell_error(error: &Error, has_fallback: bool) -> SellFailure {
+    if let Some(backoff) = error.suggested_backoff() {
+        return SellFailure::Retry(backoff);
+    }
+    match error.category() {
+        ErrorCategory::CurveComplete => SellFailure::Migrated,
+        // Specific to the route that failed (no Jupiter route, pool decode)
+        ErrorCategory::RpcFatal | ErrorCategory::ProtocolDecode if has_fallback => {
+            SellFailure::Retry(Duration::ZERO)
+        }
+        _ => SellFailure::GiveUp,
+    }
+}
+
+/// Log when a kill-switch sell lands on-chain, measured from `triggered_at`
+///
+/// `label` names the position and the execution policy applied, so the
+/// logs show how fast each urgency tier actually gets out. Paper
+/// signatures aren't on-chain and are skipped.
+pub fn spawn_landing_check(
+    rpc_client: Arc<RpcClient>,
+    label: String,
+    signature: String,
+    triggered_at: Instant,
+) {
+    let Ok(parsed) = Signature::from_str(&signature) else {
+        return;
+    };
+    tokio::spawn(async move {
+        // Poll twice a second for up to ~30s
+        for _ in 0..60 {
+            match rpc_client.get_signature_status(&parsed) {
+                Ok(Some(Ok(()))) => {
+                    info!(
+                        "KILL-SWITCH EXIT LANDED: {} in {} ms - {}",
+                        label,
+                        triggered_at.elapsed().as_millis(),
+                        signature
+                    );
+                    return;
+                }
+                Ok(Some(Err(e))) => {
+                    error!(
+                        "KILL-SWITCH EXIT FAILED ON-CHAIN: {} after {} ms: {} - {}",
+                        label,
+                        triggered_at.elapsed().as_millis(),
+                        e,
+                        signature
+                    );
+                    return;
+                }
+                Ok(None) | Err(_) => {}
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        warn!(
+            "KILL-SWITCH EXIT NOT CONFIRMED: {} after {} ms - {}",
+            label,
+            triggered_at.elapsed().as_millis(),
+            signature
+        );
+    });
+}
+
+/// A full exit being sold in slices
+struct SplitExit {
+    slices: u32,
+    sold: u32,
+    next_at: Instant,
+}
+
+/// Exit asked for by another component, executed on the monitor's next pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitRequest {
+    pub mint: String,
+    pub signal: ExitSignal,
+}
+
+/// Notable monitor outcomes, for callers that keep their own per-mint state
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorEvent {
+    /// Position fully sold
+    Closed { mint: String },
+    /// Position dropped (buy never landed or sell kept failing)
+    Abandoned { mint: String },
+    /// Position's bonding curve completed; it now trades in the migrated pool
+    Migrated { mint: String },
+}
+
+/// Trailing stop distance for the current P&L
+fn trailing_stop_pct(config: &AutoSellConfig, profile: &MonitorConfig, pnl_pct: f64) -> f64 {
+    if !(profile.dynamic_trailing && config.dynamic_trailing_enabled) {
+        return FIXED_TRAILING_STOP_PCT;
+    }
+    // Tighten trailing stop as profit grows to prevent round-tripping
+    if pnl_pct >= 25.0 {
+        config.trailing_stop_tight_pct
+    } else if pnl_pct >= 15.0 {
+        config.trailing_stop_medium_pct
+    } else {
+        config.trailing_stop_base_pct
+    }
+}
+
+/// Price-based exit checks for one position (kill-switch and manual hold
+/// are handled by the monitor loop)
+pub fn check_exit(
+    config: &AutoSellConfig,
+    profile: &MonitorConfig,
+    thresholds: &EntryTypeThresholds,
+    position: &Position,
+    current_price: f64,
+    hold_time_secs: u64,
+) -> Option<ExitSignal> {
+    let pnl_pct = if position.entry_price > 0.0 {
+        ((current_price - position.entry_price) / position.entry_price) * 100.0
+    } else {
+        0.0
+    };
+    let peak_price = if position.peak_price > 0.0 {
+        position.peak_price
+    } else {
+        position.entry_price
+    };
+    let drop_from_peak_pct = if peak_price > 0.0 {
+        ((peak_price - current_price) / peak_price) * 100.0
+    } else {
+        0.0
+    };
+
+    // Entry-type thresholds (manual overrides take precedence)
+    let tp_pct = position.effective_take_profit_pct(thresholds);
+    let sl_pct = position.effective_stop_loss_pct(thresholds);
+    let exits = thresholds.get(position.exit_profile());
+    let trailing_stop_pct =
+        position.effective_trailing_pct(trailing_stop_pct(config, profile, pnl_pct));
+
+    // 1. Stop loss (cut losses first)
+    if pnl_pct <= -sl_pct {
+        return Some(ExitSignal::full(format!(
+            "STOP LOSS at {:.1}% (limit: -{:.0}%)",
+            pnl_pct, sl_pct
+        )));
+    }
+
+    // 2. Trailing stop (only if in profit and dropped from peak)
+    if pnl_pct > 0.0 && drop_from_peak_pct >= trailing_stop_pct {
+        return Some(ExitSignal::full(format!(
+            "TRAILING STOP: dropped {:.1}% from peak (P&L: +{:.1}%, trail: {:.0}%)",
+            drop_from_peak_pct, pnl_pct, trailing_stop_pct
+        )));
+    }
+
+    // 3. Take profit (final exit)
+    if pnl_pct >= tp_pct {
+        return Some(ExitSignal::full(format!(
+            "TAKE PROFIT at {:.1}% (target: {:.0}%)",
+            pnl_pct, tp_pct
+        )));
+    }
+
+    // 4. Quick profit - first layer, up to the second layer (or take profit)
+    let quick_profit_ceiling = if profile.second_profit_layer {
+        config.second_profit_pct
+    } else {
+        tp_pct
+    };
+    if !position.quick_profit_taken
+        && pnl_pct >= exits.quick_profit_pct
+        && pnl_pct < quick_profit_ceiling
+    {
+        return Some(ExitSignal {
+            layer: ExitLayer::QuickProfit,
+            reason: format!("LAYER 1: Quick profit at {:.1}% - selling 50%", pnl_pct),
+        });
+    }
+
+    // 5. Second profit - second layer
+    if profile.second_profit_layer
+        && position.quick_profit_taken
+        && !position.second_profit_taken
+        && pnl_pct >= config.second_profit_pct
+        && pnl_pct < tp_pct
+    {
+        return Some(ExitSignal {
+            layer: ExitLayer::SecondProfit,
+            reason: format!("LAYER 2: Second profit at {:.1}% - selling 25%", pnl_pct),
+        });
+    }
+
+    // 6. No-movement exit
+    if profile.no_movement_exit
+        && hold_time_secs >= config.no_movement_secs
+        && pnl_pct.abs() < config.no_movement_threshold_pct
+    {
+        return Some(ExitSignal::full(format!(
+            "NO MOVEMENT: {:.1}% after {}s",
+            pnl_pct, hold_time_secs
+        )));
+    }
+
+    // 7. Max hold time last (safety net)
+    if let Some(max_secs) = exits.max_hold() {
+        if hold_time_secs >= max_secs {
+            return Some(ExitSignal::full(format!(
+                "MAX HOLD TIME ({} secs) P&L: {:.1}%",
+                max_secs, pnl_pct
+            )));
+        }
+    }
+
+    None
+}
+
+/// Exit checks for a position with an exit style: stop loss and manual
+/// overrides first, then the advisor's action, then the max hold safety net
+pub fn check_styled_exit(
+    thresholds: &EntryTypeThresholds,
+    position: &Position,
+    current_price: f64,
+    hold_time_secs: u64,
+    action: &ExitAction,
+) -> Option<ExitSignal> {
+    let pnl_pct = if position.entry_price > 0.0 {
+        ((current_price - position.entry_price) / position.entry_price) * 100.0
+    } else {
+        0.0
+    };
+
+    let sl_pct = position.effective_stop_loss_pct(thresholds);
+    if pnl_pct <= -sl_pct {
+        return Some(ExitSignal::full(format!(
+            "STOP LOSS at {:.1}% (limit: -{:.0}%)",
+            pnl_pct, sl_pct
+        )));
+    }
+    if let Some(tp_pct) = position.exit_override.take_profit_pct {
+        if pnl_pct >= tp_pct {
+            return Some(ExitSignal::full(format!(
+                "TAKE PROFIT at {:.1}% (manual target: {:.0}%)",
+                pnl_pct, tp_pct
+            )));
+        }
+    }
+    if let Some(trail_pct) = position.exit_override.trailing_pct {
+        let peak_price = position.peak_price.max(position.entry_price);
+        let drop_from_peak_pct = ((peak_price - current_price) / peak_price) * 100.0;
+        if pnl_pct > 0.0 && drop_from_peak_pct >= trail_pct {
+            return Some(ExitSignal::full(format!(
+                "TRAILING STOP: dropped {:.1}% from peak (P&L: +{:.1}%, manual trail: {:.0}%)",
+                drop_from_peak_pct, pnl_pct, trail_pct
+            )));
+        }
+    }
+
+    match action {
+        ExitAction::Full { reason } => return Some(ExitSignal::full(reason.clone())),
+        ExitAction::Partial { pct, level, reason } => {
+            let pct = pct.round().clamp(1.0, 100.0) as u8;
+            if pct >= 100 {
+                return Some(ExitSignal::full(reason.clone()));
+            }
+            return Some(ExitSignal {
+                layer: ExitLayer::Tier { level: *level, pct },
+                reason: reason.clone(),
+            });
+        }
+        ExitAction::Hold | ExitAction::TightenTrail { .. } => {}
+    }
+
+    if let Some(max_secs) = thresholds.get(position.exit_profile()).max_hold() {
+        if hold_time_secs >= max_secs {
+            return Some(ExitSignal::full(format!(
+                "MAX HOLD TIME ({} secs) P&L: {:.1}%",
+                max_secs, pnl_pct
+            )));
+        }
+    }
+
+    None
+}
+
+/// Position monitor shared by every command that holds positions
 pub struct AutoSeller {
     config: AutoSellConfig,
+    profile: MonitorConfig,
     position_manager: Arc<PositionManager>,
+    prices: Arc<dyn PriceProvider>,
+    seller: Arc<dyn PositionSeller>,
+    rpc_client: Arc<RpcClient>,
+    cooldowns: Arc<CooldownTracker>,
+    kill_switch: Option<Arc<KillSwitchEvaluator>>,
+    rug_recorder: Option<Arc<RugRecorder>>,
+    exits: Option<Arc<dyn ExitAdvisor>>,
+    flow: Option<Arc<FlowWatch>>,
+    events: Option<mpsc::Sender<MonitorEvent>>,
+    requests: Option<mpsc::Receiver<ExitRequest>>,
+    config_updates: Option<watch::Receiver<AutoSellConfig>>,
+    /// Prices for positions whose curve completed (None = no migration watch)
+    migrated_prices: Option<Arc<dyn PriceProvider>>,
 }
 
 impl AutoSeller {
-    pub fn new(config: AutoSellConfig, position_manager: Arc<PositionManager>) -> Self {
+    pub fn new(
+        config: AutoSellConfig,
+        profile: MonitorConfig,
+        position_manager: Arc<PositionManager>,
+        prices: Arc<dyn PriceProvider>,
+        seller: Arc<dyn PositionSeller>,
+        rpc_client: Arc<RpcClient>,
+        cooldowns: Arc<CooldownTracker>,
+    ) -> Self {
         Self {
             config,
+            profile,
             position_manager,
+            prices,
+            seller,
+            rpc_client,
+            cooldowns,
+            kill_switch: None,
+            rug_recorder: None,
+            exits: None,
+            flow: None,
+            events: None,
+            requests: None,
+            config_updates: None,
+            migrated_prices: None,
         }
     }
 
-    /// Start the auto-sell monitor
-    pub async fn start(
-        &self,
-        mut price_rx: mpsc::Receiver<PriceUpdate>,
-        sell_tx: mpsc::Sender<AutoSellEvent>,
-    ) -> Result<()> {
-        if !self.config.enabled {
-            info!("Auto-sell disabled");
-            return Ok(());
+    /// Evaluator checked each pass when the profile enables kill-switches
+    pub fn with_kill_switch(mut self, evaluator: Option<Arc<KillSwitchEvaluator>>) -> Self {
+        self.kill_switch = evaluator;
+        self
+    }
+
+    /// Blacklist the deployer when a kill-switch exit closes a position
+    pub fn with_rug_recorder(mut self, recorder: Arc<RugRecorder>) -> Self {
+        self.rug_recorder = Some(recorder);
+        self
+    }
+
+    /// Decide exits for positions with an exit style (without one they fall
+    /// back to the legacy rules)
+    pub fn with_exit_advisor(mut self, advisor: Option<Arc<dyn ExitAdvisor>>) -> Self {
+        self.exits = advisor;
+        self
+    }
+
+    /// Exit early on negative order flow (the watch must be fed trades;
+    /// positions are synced to it each pass)
+    pub fn with_flow_watch(mut self, flow: Option<Arc<FlowWatch>>) -> Self {
+        self.flow = flow;
+        self
+    }
+
+    /// Report full closes and abandoned positions on this channel
+    pub fn with_events(mut self, events: mpsc::Sender<MonitorEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Execute exits sent on this channel (after the kill-switch, before
+    /// price-based exits)
+    pub fn with_exit_requests(mut self, requests: mpsc::Receiver<ExitRequest>) -> Self {
+        self.requests = Some(requests);
+        self
+    }
+
+    /// Switch to the `[auto_sell]` values sent on this channel (config reload)
+    pub fn with_config_updates(mut self, updates: watch::Receiver<AutoSellConfig>) -> Self {
+        self.config_updates = Some(updates);
+        self
+    }
+
+    /// Watch positions' bonding curves; once one completes, price the
+    /// position with `prices` and tell the seller to use the migrated pool
+    pub fn with_migration_watch(mut self, prices: Arc<dyn PriceProvider>) -> Self {
+        self.migrated_prices = Some(prices);
+        self
+    }
+
+    /// Run the monitor loop in the background
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move { self.run().await })
+    }
+
+    /// Feature summary for the startup log
+    fn features(&self) -> String {
+        let mut features = Vec::new();
+        if self.profile.dynamic_trailing && self.config.dynamic_trailing_enabled {
+            features.push(format!(
+                "Dynamic Trailing ({}%-{}%)",
+                self.config.trailing_stop_base_pct, self.config.trailing_stop_tight_pct
+            ));
+        } else {
+            features.push(format!("Trailing Stop ({}%)", FIXED_TRAILING_STOP_PCT));
+        }
+        if self.profile.second_profit_layer {
+            features.push(format!(
+                "Layered Exits ({}%/{}%)",
+                self.config.quick_profit_pct, self.config.second_profit_pct
+            ));
+        } else {
+            features.push("Quick Profit".to_string());
+        }
+        if self.profile.kill_switch {
+            features.push("Kill-Switch".to_string());
+        }
+        if !self.profile.no_movement_exit {
+            features.push("No-Movement Exit DISABLED".to_string());
         }
+        if self.flow.is_some() {
+            features.push("Early Weakness".to_string());
+        }
+        features.join(", ")
+    }
 
-        info!(
-            "Auto-sell enabled: TP={}%, SL={}%",
-            self.config.take_profit_pct, self.config.stop_loss_pct
-        );
+    async fn run(mut self) {
+        let mut poll_interval_ms = self.profile.poll_interval_ms(&self.config);
+        info!("=== POSITION MONITOR STARTED ===");
+        info!("Features: {}", self.features());
+        info!("Poll interval: {}ms", poll_interval_ms);
 
-        let config = self.config.clone();
-        let position_manager = self.position_manager.clone();
+        // Failed sells per mint for retry / give-up handling
+        let mut sell_attempts: HashMap<String, SellRetry> = HashMap::new();
+        // Positions whose buy is confirmed (exits may be evaluated)
+        let mut confirmed: HashSet<String> = HashSet::new();
+        // Requested exits, kept until the sell lands or is given up
+        let mut requests = self.requests.take();
+        let mut pending: HashMap<String, ExitSignal> = HashMap::new();
+        // Full exits being sold in slices (their signal stays pending)
+        let mut splits: HashMap<String, SplitExit> = HashMap::new();
+        let mut slice_interval = Duration::from_millis(self.config.exit_slice_interval_ms);
+        // Positions whose bonding curve completed
+        let mut migrated: HashSet<String> = HashSet::new();
+        let mut last_migration_check = Instant::now();
 
-        tokio::spawn(async move {
-            while let Some(update) = price_rx.recv().await {
-                // Get position for this token
-                let position = match position_manager
-                    .get_position(&update.mint.to_string())
-                    .await
-                {
-                    Some(p) => p,
-                    None => continue, // No position, skip
-                };
+        loop {
+            tokio::time::sleep(Duration::from_millis(poll_interval_ms)).await;
+
+            if let Some(updates) = self.config_updates.as_mut() {
+                if updates.has_changed().unwrap_or(false) {
+                    self.config = updates.borrow_and_update().clone();
+                    poll_interval_ms = self.profile.poll_interval_ms(&self.config);
+                    slice_interval = Duration::from_millis(self.config.exit_slice_interval_ms);
+                    info!("Auto-sell config reloaded: {}", self.features());
+                }
+            }
 
-                // Update price in position manager
-                position_manager
-                    .update_price(&update.mint.to_string(), update.price)
+            if let Some(ref mut requests) = requests {
+                while let Ok(request) = requests.try_recv() {
+                    pending.insert(request.mint, request.signal);
+                }
+            }
+
+            let positions = self.position_manager.get_all_positions().await;
+            pending.retain(|mint, _| positions.iter().any(|p| &p.mint == mint));
+            splits.retain(|mint, _| pending.contains_key(mint));
+            if let Some(ref flow) = self.flow {
+                flow.sync_positions(&positions);
+            }
+            if positions.is_empty() {
+                continue;
+            }
+            migrated.retain(|mint| positions.iter().any(|p| &p.mint == mint));
+            if self.migrated_prices.is_some()
+                && last_migration_check.elapsed() >= Duration::from_secs(MIGRATION_CHECK_SECS)
+            {
+                last_migration_check = Instant::now();
+                self.check_migrations(&positions, &mut migrated).await;
+            }
+
+            let (graduated, on_curve): (Vec<Position>, Vec<Position>) = positions
+                .iter()
+                .cloned()
+                .partition(|p| migrated.contains(&p.mint));
+            self.prices.prefetch(&on_curve).await;
+            if let Some(ref prices) = self.migrated_prices {
+                prices.prefetch(&graduated).await;
+            }
+            for position in positions {
+                let prices = match self.migrated_prices {
+                    Some(ref prices) if migrated.contains(&position.mint) => prices,
+                    _ => &self.prices,
+                };
+                let Some(current_price) = prices.price(&position).await else {
+                    continue;
+                };
+                self.position_manager
+                    .update_price(&position.mint, current_price)
                     .await;
 
-                // Check for triggers
-                if let Some(event) = Self::check_triggers(&config, &position, update.price) {
-                    info!(
-                        "Auto-sell triggered for {}: {:?} at {}% P&L",
-                        update.mint, event.trigger, event.pnl_pct
-                    );
+                // Re-read to pick up the tracked peak
+                let Some(position) = self.position_manager.get_position(&position.mint).await
+                else {
+                    continue;
+                };
+
+                if !self.is_confirmed(&position, &mut confirmed).await {
+                    continue;
+                }
 
-                    if sell_tx.send(event).await.is_err() {
-                        error!("Failed to send auto-sell event");
-                        break;
+                let hold_time_secs = (chrono::Utc::now() - position.entry_time)
+                    .num_seconds()
+                    .max(0) as u64;
+
+                if self.profile.status_log && hold_time_secs % 15 == 0 {
+                    self.log_status(&position, current_price, hold_time_secs);
+                }
+
+                let kill_switch = if self.profile.kill_switch {
+                    self.kill_switch_reason(&position).await
+                } else {
+                    None
+                };
+                let urgency = kill_switch.as_ref().map(|(_, urgency)| *urgency);
+                let killed = urgency.is_some();
+                let signal = match kill_switch {
+                    Some((reason, _)) => ExitSignal::full(reason),
+                    // Manual hold suspends every automatic exit except the kill-switch
+                    None if position.is_held() => continue,
+                    None if pending.contains_key(&position.mint) => pending[&position.mint].clone(),
+                    None => match self
+                        .check_exit(&position, current_price, hold_time_secs)
+                        .await
+                    {
+                        Some(signal) => signal,
+                        None => continue,
+                    },
+                };
+
+                // Full exits too large for the curve go out in slices; the
+                // kill-switch always sells everything at once
+                if signal.layer == ExitLayer::Full && !killed {
+                    match splits.get(&position.mint) {
+                        Some(split) if Instant::now() < split.next_at => continue,
+                        Some(_) => {}
+                        None => {
+                            if let Some(slices) = self.exit_slices(&position) {
+                                splits.insert(
+                                    position.mint.clone(),
+                                    SplitExit {
+                                        slices,
+                                        sold: 0,
+                                        next_at: Instant::now(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+                let split = if killed {
+                    None
+                } else {
+                    splits.get(&position.mint)
+                };
+                let order = match split {
+                    Some(split) => {
+                        pending.insert(position.mint.clone(), signal.clone());
+                        signal.slice(split.sold, split.slices)
+                    }
+                    None => signal,
+                };
+
+                warn!(
+                    "AUTO-SELL TRIGGERED: {} ({}) - {}",
+                    position.symbol, position.mint, order.reason
+                );
+                if self
+                    .execute(
+                        &position,
+                        current_price,
+                        &order,
+                        urgency,
+                        &mut sell_attempts,
+                    )
+                    .await
+                {
+                    match splits.get_mut(&position.mint) {
+                        Some(split) if !killed && order.layer != ExitLayer::Full => {
+                            split.sold += 1;
+                            split.next_at = Instant::now() + slice_interval;
+                        }
+                        _ => {
+                            pending.remove(&position.mint);
+                            splits.remove(&position.mint);
+                        }
                     }
                 }
             }
+        }
+    }
 
-            info!("Auto-sell monitor stopped");
-        });
+    /// Fetch the curves of positions not yet migrated (one getMultipleAccounts
+    /// request) and switch the completed ones to the migrated pool
+    async fn check_migrations(&self, positions: &[Position], migrated: &mut HashSet<String>) {
+        let mut watched: Vec<(&Position, Pubkey)> = Vec::new();
+        for position in positions.iter().filter(|p| !migrated.contains(&p.mint)) {
+            // Bought in the pool, so there is no curve left to watch
+            if position.entry_type == EntryType::PostMigration {
+                self.switch_to_pool(position, migrated).await;
+            } else if let Ok(mint) = Pubkey::from_str(&position.mint) {
+                watched.push((position, mint));
+            }
+        }
+        if watched.is_empty() {
+            return;
+        }
+        let mints: Vec<Pubkey> = watched.iter().map(|(_, mint)| *mint).collect();
+        let curves = match BondingCurve::fetch_many(&self.rpc_client, &mints) {
+            Ok(curves) => curves,
+            Err(e) => {
+                warn!("Bonding curve check failed: {}", e);
+                return;
+            }
+        };
+        for ((position, _), curve) in watched.into_iter().zip(curves) {
+            if !curve.is_some_and(|c| c.is_complete()) {
+                continue;
+            }
+            info!(
+                "[{}] Bonding curve complete, switching to the migrated pool",
+                position.symbol
+            );
+            self.switch_to_pool(position, migrated).await;
+        }
+    }
+
+    /// Price and sell `position` through its migrated pool from now on
+    async fn switch_to_pool(&self, position: &Position, migrated: &mut HashSet<String>) {
+        migrated.insert(position.mint.clone());
+        self.seller.mark_migrated(&position.mint);
+        self.emit(MonitorEvent::Migrated {
+            mint: position.mint.clone(),
+        })
+        .await;
+    }
 
-        Ok(())
+    /// Sells to split a full exit of `position` into (None = one is fine)
+    fn exit_slices(&self, position: &Position) -> Option<u32> {
+        let exit = position.exit_liquidity?;
+        let slices = exit.slices(position.current_value(), self.config.max_exit_slices);
+        if slices <= 1 {
+            return None;
+        }
+        info!(
+            "[{}] Splitting exit of {:.4} SOL into {} sells ({:.1}% impact at once, {:.4} SOL max per sell)",
+            position.symbol,
+            position.current_value(),
+            slices,
+            exit.full_exit_impact_pct,
+            exit.max_sell_sol
+        );
+        Some(slices)
     }
 
-    /// Check if any triggers should fire
-    fn check_triggers(
-        config: &AutoSellConfig,
+    /// Price-based exit for a position: early weakness, then its exit
+    /// style's advisor or the legacy rules
+    async fn check_exit(
+        &self,
         position: &Position,
         current_price: f64,
-    ) -> Option<AutoSellEvent> {
-        let entry_price = position.entry_price;
-        let pnl_pct = ((current_price - entry_price) / entry_price) * 100.0;
-
-        // Check take-profit
-        if pnl_pct >= config.take_profit_pct {
-            let sell_amount = if config.partial_take_profit {
-                // Sell half on first TP
-                position.token_amount / 2
-            } else {
-                position.token_amount
-            };
-
-            return Some(AutoSellEvent {
-                mint: Pubkey::default(), // Will be filled by caller
-                trigger: TriggerType::TakeProfit,
-                entry_price,
+        hold_time_secs: u64,
+    ) -> Option<ExitSignal> {
+        let thresholds = &self.position_manager.exit_thresholds();
+        if let Some(signal) = self.check_early_weakness(position, current_price, hold_time_secs) {
+            return Some(signal);
+        }
+        let (Some(style), Some(exits)) = (&position.exit_style, &self.exits) else {
+            return check_exit(
+                &self.config,
+                &self.profile,
+                thresholds,
+                position,
                 current_price,
-                pnl_pct,
-                sell_amount,
-                total_amount: position.token_amount,
-            });
+                hold_time_secs,
+            );
+        };
+
+        let action = exits
+            .advise(position, style, current_price, hold_time_secs)
+            .await;
+        if let ExitAction::TightenTrail { style, reason } = &action {
+            info!(
+                "[{}] Tightening exit to {:?}: {}",
+                position.symbol, style, reason
+            );
+            if let Err(e) = self
+                .position_manager
+                .set_exit_style(&position.mint, style.clone())
+                .await
+            {
+                warn!("[{}] Could not tighten exit: {}", position.symbol, e);
+            }
         }
+        check_styled_exit(thresholds, position, current_price, hold_time_secs, &action)
+    }
 
-        // Check stop-loss
-        if pnl_pct <= -config.stop_loss_pct {
-            return Some(AutoSellEvent {
-                mint: Pubkey::default(),
-                trigger: TriggerType::StopLoss,
-                entry_price,
-                current_price,
-                pnl_pct,
-                sell_amount: position.token_amount, // Always sell all on SL
-                total_amount: position.token_amount,
-            });
+    fn check_early_weakness(
+        &self,
+        position: &Position,
+        current_price: f64,
+        hold_time_secs: u64,
+    ) -> Option<ExitSignal> {
+        let flow = self.flow.as_ref()?;
+        let snapshot = flow.snapshot(&position.mint)?;
+        if position.entry_price <= 0.0 {
+            return None;
         }
+        let pnl_pct = (current_price - position.entry_price) / position.entry_price * 100.0;
+        let sl_pct = position.effective_stop_loss_pct(&self.position_manager.exit_thresholds());
+        check_early_weakness(
+            flow.config(),
+            &snapshot,
+            pnl_pct,
+            sl_pct,
+            hold_time_secs,
+            self.profile.confirm_wait_secs,
+        )
+        .map(ExitSignal::full)
+    }
 
+    /// Whether the buy has landed and exits may be evaluated
+    ///
+    /// Positions are skipped until `confirm_wait_secs` old. With
+    /// `verify_balance`, the wallet must also hold tokens; positions still
+    /// empty after `unconfirmed_timeout_secs` are dropped and blacklisted.
+    async fn is_confirmed(&self, position: &Position, confirmed: &mut HashSet<String>) -> bool {
+        if confirmed.contains(&position.mint) {
+            return true;
+        }
+        let position_age_secs = (chrono::Utc::now() - position.entry_time)
+            .num_seconds()
+            .max(0) as u64;
+        if position_age_secs < self.profile.confirm_wait_secs {
+            return false;
+        }
+        if !self.profile.verify_balance {
+            confirmed.insert(position.mint.clone());
+            return true;
+        }
+
+        let wallet = self.seller.wallet_for(position);
+        let token_balance = query_token_balance(&self.rpc_client, &wallet, &position.mint);
+        if token_balance > 0 {
+            info!(
+                "[{}] TX CONFIRMED - token balance: {}",
+                position.symbol, token_balance
+            );
+            confirmed.insert(position.mint.clone());
+            return true;
+        }
+
+        if position_age_secs > self.profile.unconfirmed_timeout_secs {
+            warn!(
+                "[{}] TX LIKELY FAILED - no tokens after {}s, removing position",
+                position.symbol, self.profile.unconfirmed_timeout_secs
+            );
+            let _ = self.position_manager.abandon_position(&position.mint).await;
+            // Blacklist to prevent repeated failures
+            let _ = self
+                .cooldowns
+                .mark(
+                    &position.mint,
+                    CooldownKind::Failed,
+                    Some(format!(
+                        "no tokens after {}s",
+                        self.profile.unconfirmed_timeout_secs
+                    )),
+                )
+                .await;
+            self.emit(MonitorEvent::Abandoned {
+                mint: position.mint.clone(),
+            })
+            .await;
+        }
+        false
+    }
+
+    /// Blacklist the deployer of a rugged position, if it is known
+    async fn record_rug(&self, mint: &str, reason: &str) {
+        let (Some(recorder), Some(evaluator)) = (&self.rug_recorder, &self.kill_switch) else {
+            return;
+        };
+        if let Some(creator) = evaluator.deployer_tracker().get_deployer(mint) {
+            recorder.record_rug(&creator, mint, reason).await;
+        }
+    }
+
+    /// Exit reason and urgency when the kill-switch fired for `position`
+    async fn kill_switch_reason(&self, position: &Position) -> Option<(String, KillSwitchUrgency)> {
+        // Position flag first (set by other systems, which carry no urgency)
+        if let Some(reason) = self
+            .position_manager
+            .is_kill_switch_triggered(&position.mint)
+            .await
+        {
+            warn!("KILL-SWITCH EXIT: {} - {}", position.symbol, reason);
+            return Some((format!("KILL-SWITCH: {}", reason), KillSwitchUrgency::High));
+        }
+        // Then actively evaluate kill-switch conditions
+        if let Some(ref evaluator) = self.kill_switch {
+            if let KillSwitchDecision::Exit(alert) = evaluator.should_exit(&position.mint) {
+                warn!(
+                    "KILL-SWITCH EXIT: {} - {} [{:?}]",
+                    position.symbol, alert.reason, alert.urgency
+                );
+                return Some((
+                    format!(
+                        "KILL-SWITCH: {} (urgency: {:?})",
+                        alert.reason, alert.urgency
+                    ),
+                    alert.urgency,
+                ));
+            }
+        }
         None
     }
 
-    /// Manually check a position for triggers
-    pub async fn check_position(&self, mint: &str) -> Option<AutoSellEvent> {
-        let position = self.position_manager.get_position(mint).await?;
+    fn log_status(&self, position: &Position, current_price: f64, hold_time_secs: u64) {
+        let pnl_pct = if position.entry_price > 0.0 {
+            ((current_price - position.entry_price) / position.entry_price) * 100.0
+        } else {
+            0.0
+        };
+        let peak_pct = if position.peak_price > position.entry_price {
+            ((position.peak_price - position.entry_price) / position.entry_price) * 100.0
+        } else {
+            0.0
+        };
+        info!(
+            "[{}] Price: {:.10} | P&L: {:+.1}% | Peak: {:+.1}% | Hold: {}s",
+            position.symbol, current_price, pnl_pct, peak_pct, hold_time_secs
+        );
+    }
+
+    /// Sell with retries; true once the sell landed or the position was given up
+    ///
+    /// Transient errors are retried on a later pass once their backoff has
+    /// passed, permanent ones give the position up right away unless the
+    /// seller has another route to try. Kill-switch exits (`urgency`) are
+    /// sent under their urgency's execution policy.
+    async fn execute(
+        &self,
+        position: &Position,
+        current_price: f64,
+        signal: &ExitSignal,
+        urgency: Option<KillSwitchUrgency>,
+        sell_attempts: &mut HashMap<String, SellRetry>,
+    ) -> bool {
+        // A manual abandon of this mint is being recorded
+        let Some(_lock) = self.position_manager.try_lock_sell(&position.mint) else {
+            return false;
+        };
+        let (attempts, started_at) = {
+            let now = Instant::now();
+            let retry = sell_attempts
+                .entry(position.mint.clone())
+                .or_insert(SellRetry {
+                    attempts: 0,
+                    next_at: now,
+                    started_at: now,
+                });
+            if now < retry.next_at {
+                return false;
+            }
+            retry.attempts += 1;
+            (retry.attempts, retry.started_at)
+        };
+
+        if attempts > MAX_SELL_ATTEMPTS {
+            error!(
+                "AUTO-SELL GAVE UP for {} after {} attempts - removing from tracking",
+                position.symbol, MAX_SELL_ATTEMPTS
+            );
+            self.give_up(position, format!("sell failed {}x", MAX_SELL_ATTEMPTS))
+                .await;
+            sell_attempts.remove(&position.mint);
+            return true;
+        }
+
+        // SOL balance BEFORE the sell for real P&L tracking
+        let sol_before = self.profile.measure_proceeds.then(|| {
+            self.rpc_client
+                .get_balance(&self.seller.wallet_for(position))
+                .unwrap_or(0) as f64
+                / 1_000_000_000.0
+        });
+
+        let kill_switch_execution = &self.config.kill_switch_execution;
+        let policy = urgency.map(|urgency| kill_switch_execution.policy(urgency));
+        let submitted_at = Instant::now();
+        let result = match policy {
+            Some((name, policy)) => {
+                info!(
+                    "[{}] Kill-switch policy '{}' (attempt {}): {:?}",
+                    position.symbol, name, attempts, policy
+                );
+                self.seller
+                    .sell_with_policy(position, &signal.layer.sell_pct(), attempts, policy)
+                    .await
+            }
+            None => {
+                self.seller
+                    .sell(position, &signal.layer.sell_pct(), attempts)
+                    .await
+            }
+        };
+        match result {
+            Ok(sig) => {
+                info!("AUTO-SELL EXECUTED: {} - {}", position.symbol, sig);
+                if let Some((name, _)) = policy {
+                    spawn_landing_check(
+                        self.rpc_client.clone(),
+                        format!(
+                            "{} policy '{}' (attempt {})",
+                            position.symbol, name, attempts
+                        ),
+                        sig.clone(),
+                        started_at,
+                    );
+                }
+                sell_attempts.remove(&position.mint);
+                self.record_sell(
+                    position,
+                    current_price,
+                    signal,
+                    &sig,
+                    sol_before,
+                    submitted_at,
+                )
+                .await;
+                true
+            }
+            Err(e) => {
+                error!(
+                    "AUTO-SELL FAILED for {} (attempt {}, {}): {}",
+                    position.symbol,
+                    attempts,
+                    e.category(),
+                    e
+                );
+                let lightning_attempts = policy.and_then(|(_, policy)| policy.lightning_attempts);
+                let has_fallback = self
+                    .seller
+                    .has_fallback(position, attempts, lightning_attempts);
+                match on_sell_error(&e, has_fallback) {
+                    SellFailure::Retry(backoff) => {
+                        if let Some(retry) = sell_attempts.get_mut(&position.mint) {
+                            retry.next_at = Instant::now() + backoff;
+                        }
+                        false
+                    }
+                    SellFailure::Migrated => {
+                        warn!(
+                            "[{}] Bonding curve complete - selling from the migrated pool",
+                            position.symbol
+                        );
+                        self.seller.mark_migrated(&position.mint);
+                        false
+                    }
+                    SellFailure::GiveUp => {
+                        error!(
+                            "AUTO-SELL GAVE UP for {} - {} error won't clear on retry",
+                            position.symbol,
+                            e.category()
+                        );
+                        self.give_up(position, format!("sell failed: {}", e.category()))
+                            .await;
+                        sell_attempts.remove(&position.mint);
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    /// Stop tracking a position whose sell keeps failing
+    async fn give_up(&self, position: &Position, reason: String) {
+        if self.profile.record_abandoned {
+            // Nothing was received since the sell failed
+            let _ = self
+                .position_manager
+                .close_position_with_exit(
+                    &position.mint,
+                    position.token_amount,
+                    0.0,
+                    ExitDetails::new(TradeExitReason::Abandoned),
+                )
+                .await;
+        } else {
+            let _ = self.position_manager.abandon_position(&position.mint).await;
+        }
+        let _ = self
+            .cooldowns
+            .mark(&position.mint, CooldownKind::Failed, Some(reason))
+            .await;
+        self.emit(MonitorEvent::Abandoned {
+            mint: position.mint.clone(),
+        })
+        .await;
+    }
+
+    /// Book a landed sell: trade record, layer flags, cooldown
+    async fn record_sell(
+        &self,
+        position: &Position,
+        current_price: f64,
+        signal: &ExitSignal,
+        signature: &str,
+        sol_before: Option<f64>,
+        submitted_at: Instant,
+    ) {
+        // Measured SOL received (0 = unavailable, estimate from price instead)
+        let simulated = self.seller.take_proceeds(signature);
+        let actual_received = match (simulated, sol_before) {
+            (Some(received), _) => received,
+            (None, Some(sol_before)) => {
+                // Wait for tx confirmation then query actual SOL received
+                tokio::time::sleep(Duration::from_millis(2000)).await;
+                let sol_after = self
+                    .rpc_client
+                    .get_balance(&self.seller.wallet_for(position))
+                    .unwrap_or(0) as f64
+                    / 1_000_000_000.0;
+                let raw_received = (sol_after - sol_before).max(0.0);
+
+                // Received SOL shouldn't be more than 10x position cost; if it
+                // is, the balance query likely failed
+                if raw_received > position.total_cost_sol * 10.0 {
+                    warn!(
+                        "[{}] Balance query anomaly: before={:.4}, after={:.4}, diff={:.4} - using estimate",
+                        position.symbol, sol_before, sol_after, raw_received
+                    );
+                    0.0
+                } else {
+                    raw_received
+                }
+            }
+            (None, None) => 0.0,
+        };
+
+        let (sell_amount, cost_basis) = match signal.layer {
+            ExitLayer::Full => (position.token_amount, position.total_cost_sol),
+            ExitLayer::QuickProfit => (position.token_amount / 2, position.total_cost_sol / 2.0),
+            ExitLayer::SecondProfit => {
+                // Half of what's left, cost basis proportional to the remaining position
+                let sell_amount = position.token_amount / 2;
+                let cost_ratio = sell_amount as f64 / position.token_amount as f64;
+                (sell_amount, position.total_cost_sol * cost_ratio)
+            }
+            ExitLayer::Mirror(pct)
+            | ExitLayer::Tier { pct, .. }
+            | ExitLayer::Slice(pct)
+            | ExitLayer::Reduce(pct) => (
+                position.token_amount * pct as u64 / 100,
+                position.total_cost_sol * pct as f64 / 100.0,
+            ),
+        };
+
+        // Estimate: current price minus ~2% slippage
+        let received = if actual_received > 0.0 {
+            actual_received
+        } else {
+            (sell_amount as f64 * current_price) * 0.98
+        };
+        let exit_fees = self.seller.estimate_fees(signature, received);
+        // Measured balance delta is already net of sell fees
+        let proceeds = if actual_received > 0.0 {
+            received + exit_fees.total_sol()
+        } else {
+            received
+        };
+        let pnl_sol = proceeds - cost_basis;
+        let exit_reason = TradeExitReason::from_description(&signal.reason);
+        let mut exit = ExitDetails::new(exit_reason)
+            .with_signature(signature)
+            .with_fees(exit_fees);
+        if actual_received > 0.0 {
+            // Latency is submit to measured balance (an upper bound on landing)
+            exit = exit.with_fill(SellFill {
+                expected_sol: sell_amount as f64 * current_price,
+                received_sol: proceeds,
+                latency_ms: submitted_at.elapsed().as_millis() as u64,
+            });
+        }
+        let net_pnl_sol = self
+            .position_manager
+            .close_position_with_exit(&position.mint, sell_amount, proceeds, exit)
+            .await
+            .unwrap_or(pnl_sol);
+        if simulated.is_none() {
+            spawn_confirmation(
+                self.rpc_client.clone(),
+                self.position_manager.clone(),
+                signature.to_string(),
+            );
+        }
+
+        let hold_secs = (chrono::Utc::now() - position.entry_time).num_seconds();
+        let price_change_pct =
+            ((current_price - position.entry_price) / position.entry_price) * 100.0;
+        let headline = match signal.layer {
+            ExitLayer::QuickProfit => {
+                let _ = self
+                    .position_manager
+                    .mark_quick_profit_taken(&position.mint)
+                    .await;
+                "=== LAYER 1 PROFIT TAKEN (50%) ==="
+            }
+            ExitLayer::SecondProfit => {
+                let _ = self
+                    .position_manager
+                    .mark_second_profit_taken(&position.mint)
+                    .await;
+                "=== LAYER 2 PROFIT TAKEN (25%) ==="
+            }
+            ExitLayer::Mirror(_) => "=== MIRRORED SELL ===",
+            ExitLayer::Slice(_) => "=== EXIT SLICE ===",
+            ExitLayer::Reduce(_) => "=== POSITION REDUCED ===",
+            ExitLayer::Tier { level, .. } => {
+                let _ = self
+                    .position_manager
+                    .mark_exit_level_hit(&position.mint, level)
+                    .await;
+                "=== LADDER TIER TAKEN ==="
+            }
+            ExitLayer::Full => {
+                // Re-entry cooldown so a restart or new signal doesn't re-buy the top
+                // (permanent blacklist after a kill-switch exit)
+                let kind = if exit_reason == TradeExitReason::KillSwitch {
+                    CooldownKind::Rugged
+                } else {
+                    CooldownKind::Sold
+                };
+                let _ = self
+                    .cooldowns
+                    .mark(&position.mint, kind, Some(signal.reason.clone()))
+                    .await;
+                if exit_reason == TradeExitReason::KillSwitch {
+                    self.record_rug(&position.mint, &signal.reason).await;
+                }
+                self.emit(MonitorEvent::Closed {
+                    mint: position.mint.clone(),
+                })
+                .await;
+                "=== TRADE CLOSED (Full) ==="
+            }
+        };
 
-        Self::check_triggers(&self.config, &position, position.current_price)
+        info!("{}", headline);
+        info!(
+            "  {} | Entry: {:.10} | Exit: {:.10} | Change: {:+.2}%",
+            position.symbol, position.entry_price, current_price, price_change_pct
+        );
+        if signal.layer == ExitLayer::Full {
+            let pnl_pct = (pnl_sol / position.total_cost_sol) * 100.0;
+            let net_pnl_pct = (net_pnl_sol / position.total_cost_sol) * 100.0;
+            info!(
+                "  Cost: {:.4} SOL | Received: {:.4} SOL{} | P&L: {:+.4} SOL ({:+.1}%) net, {:+.4} SOL ({:+.1}%) gross | Hold: {}s",
+                position.total_cost_sol,
+                received,
+                if actual_received > 0.0 { " (actual)" } else { "" },
+                net_pnl_sol,
+                net_pnl_pct,
+                pnl_sol,
+                pnl_pct,
+                hold_secs
+            );
+        } else {
+            info!(
+                "  Tokens: {} | Received: {:.4} SOL | P&L: {:+.4} SOL net ({:+.4} gross) | Hold: {}s",
+                sell_amount, received, net_pnl_sol, pnl_sol, hold_secs
+            );
+        }
+    }
+
+    async fn emit(&self, event: MonitorEvent) {
+        if let Some(ref events) = self.events {
+            let _ = events.send(event).await;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::position::manager::{EntryType, ExitOverride};
 
-    fn test_config() -> AutoSellConfig {
-        AutoSellConfig {
-            enabled: true,
-            take_profit_pct: 50.0,
-            stop_loss_pct: 30.0,
-            partial_take_profit: false,
-            price_poll_interval_ms: 1000,
-        }
-    }
-
-    fn test_position(entry_price: f64, current_price: f64) -> Position {
+    fn position(entry_price: f64, peak_price: f64) -> Position {
         Position {
             mint: "test".to_string(),
             name: "Test".to_string(),
@@ -190,55 +2067,242 @@ mod tests {
             total_cost_sol: 0.01,
             entry_time: chrono::Utc::now(),
             entry_signature: "sig".to_string(),
-            current_price,
+            current_price: entry_price,
+            entry_type: EntryType::Opportunity, // TP 10%, SL 15%, quick 5%
+            quick_profit_taken: false,
+            second_profit_taken: false,
+            peak_price,
+            trough_price: entry_price,
+            kill_switch_triggered: false,
+            kill_switch_reason: None,
+            wallet_pubkey: String::new(),
+            fills: vec![],
+            scale_in: None,
+            exit_override: ExitOverride::default(),
+            partial_fill: false,
+            regime: None,
+            exit_style: None,
+            exit_levels_hit: vec![],
+            paper: false,
+            privileges: None,
+            exit_liquidity: None,
+            slippage: None,
+            strategy_profile: None,
+            holder_distribution: None,
+            fees: TradeFees::default(),
         }
     }
 
+    fn profiles() -> (AutoSellConfig, MonitorConfig, MonitorConfig) {
+        let config = crate::config::Config::default().auto_sell;
+        let start = config.monitor.start;
+        let hot_scan = config.monitor.hot_scan;
+        (config, start, hot_scan)
+    }
+
+    #[test]
+    fn test_stop_loss_before_everything() {
+        let (config, start, _) = profiles();
+        let thresholds = EntryTypeThresholds::default();
+        // Opportunity: SL 15%
+        let signal =
+            check_exit(&config, &start, &thresholds, &position(1.0, 1.0), 0.8, 20).unwrap();
+        assert_eq!(signal.layer, ExitLayer::Full);
+        assert!(signal.reason.starts_with("STOP LOSS"));
+    }
+
+    #[test]
+    fn test_styled_exit() {
+        let thresholds = EntryTypeThresholds::default();
+        let position = position(1.0, 1.6);
+
+        // Stop loss still comes first
+        let signal = check_styled_exit(&thresholds, &position, 0.8, 20, &ExitAction::Hold).unwrap();
+        assert!(signal.reason.starts_with("STOP LOSS"));
+
+        // Ladder tiers sell part of what's left
+        let tier = ExitAction::Partial {
+            pct: 50.0,
+            level: 50.0,
+            reason: "TAKE PROFIT tier 50%".to_string(),
+        };
+        let signal = check_styled_exit(&thresholds, &position, 1.55, 20, &tier).unwrap();
+        assert_eq!(
+            signal.layer,
+            ExitLayer::Tier {
+                level: 50.0,
+                pct: 50
+            }
+        );
+        assert_eq!(signal.layer.sell_pct(), "50%");
+
+        // Legacy take profit (10%) and trailing stop don't apply
+        assert_eq!(
+            check_styled_exit(&thresholds, &position, 1.3, 20, &ExitAction::Hold),
+            None
+        );
+    }
+
     #[test]
-    fn test_take_profit_trigger() {
-        let config = test_config();
-        let position = test_position(0.0001, 0.00016); // +60%
+    fn test_mirror_signal() {
+        let signal = ExitSignal::mirror(0.404, "wallet");
+        assert_eq!(signal.layer, ExitLayer::Mirror(40));
+        assert_eq!(signal.layer.sell_pct(), "40%");
+        assert_eq!(
+            TradeExitReason::from_description(&signal.reason),
+            TradeExitReason::MirroredSell
+        );
 
-        let event = AutoSeller::check_triggers(&config, &position, 0.00016);
+        // Near-complete sells close the position
+        assert_eq!(ExitSignal::mirror(0.997, "wallet").layer, ExitLayer::Full);
+        assert_eq!(
+            ExitSignal::mirror(0.001, "wallet").layer,
+            ExitLayer::Mirror(1)
+        );
+    }
 
-        assert!(event.is_some());
-        let event = event.unwrap();
-        assert_eq!(event.trigger, TriggerType::TakeProfit);
-        assert!(event.pnl_pct >= 50.0);
+    #[test]
+    fn test_exit_slices() {
+        let signal = ExitSignal::full("STOP LOSS: -20.0%".to_string());
+        let layers: Vec<ExitLayer> = (0..4).map(|sold| signal.slice(sold, 4).layer).collect();
+        // Equal quarters of the original position
+        assert_eq!(
+            layers,
+            vec![
+                ExitLayer::Slice(25),
+                ExitLayer::Slice(33),
+                ExitLayer::Slice(50),
+                ExitLayer::Full
+            ]
+        );
+
+        let last = signal.slice(3, 4);
+        assert!(last.reason.ends_with("(slice 4/4)"));
+        assert_eq!(
+            TradeExitReason::from_description(&last.reason),
+            TradeExitReason::StopLoss
+        );
     }
 
     #[test]
-    fn test_stop_loss_trigger() {
-        let config = test_config();
-        let position = test_position(0.0001, 0.00006); // -40%
+    fn test_sell_failure_classification() {
+        let slippage = Error::SlippageExceeded("TooLittleSolReceived".to_string());
+        assert_eq!(
+            on_sell_error(&slippage, false),
+            SellFailure::Retry(Duration::ZERO)
+        );
+        let network = Error::Network("connection reset".to_string());
+        assert_eq!(
+            on_sell_error(&network, false),
+            SellFailure::Retry(Duration::from_secs(1))
+        );
+        let complete = Error::CurveComplete("custom program error: 0x1775".to_string());
+        assert_eq!(on_sell_error(&complete, false), SellFailure::Migrated);
+
+        // Permanent errors: another route may still work, the same one won't
+        let no_route = Error::RpcFatal("Jupiter quote error (400)".to_string());
+        assert_eq!(
+            on_sell_error(&no_route, true),
+            SellFailure::Retry(Duration::ZERO)
+        );
+        assert_eq!(on_sell_error(&no_route, false), SellFailure::GiveUp);
+        let empty = Error::InsufficientFunds("No TEST balance to sell".to_string());
+        assert_eq!(on_sell_error(&empty, true), SellFailure::GiveUp);
+    }
+
+    #[test]
+    fn test_quick_profit_ceiling_follows_profile() {
+        let (mut config, start, hot_scan) = profiles();
+        config.second_profit_pct = 7.0;
+        let thresholds = EntryTypeThresholds::default();
+        // Opportunity: quick 5%, TP 10%; +8% with no drop from peak
+        let p = position(1.0, 1.08);
+
+        let signal = check_exit(&config, &start, &thresholds, &p, 1.08, 20).unwrap();
+        assert_eq!(signal.layer, ExitLayer::QuickProfit);
+        assert_eq!(signal.layer.sell_pct(), "50%");
 
-        let event = AutoSeller::check_triggers(&config, &position, 0.00006);
+        // Above the second layer: layer 1 doesn't fire, layer 2 needs layer 1 first
+        assert!(check_exit(&config, &hot_scan, &thresholds, &p, 1.08, 20).is_none());
 
-        assert!(event.is_some());
-        let event = event.unwrap();
-        assert_eq!(event.trigger, TriggerType::StopLoss);
-        assert!(event.pnl_pct <= -30.0);
+        let mut taken = p.clone();
+        taken.quick_profit_taken = true;
+        let signal = check_exit(&config, &hot_scan, &thresholds, &taken, 1.08, 20).unwrap();
+        assert_eq!(signal.layer, ExitLayer::SecondProfit);
+        assert!(check_exit(&config, &start, &thresholds, &taken, 1.08, 20).is_none());
     }
 
     #[test]
-    fn test_no_trigger() {
-        let config = test_config();
-        let position = test_position(0.0001, 0.00012); // +20%
+    fn test_dynamic_trailing_only_with_profile() {
+        let (config, start, hot_scan) = profiles();
+        assert_eq!(trailing_stop_pct(&config, &start, 30.0), FIXED_TRAILING_STOP_PCT);
+        assert_eq!(
+            trailing_stop_pct(&config, &hot_scan, 30.0),
+            config.trailing_stop_tight_pct
+        );
+        assert_eq!(
+            trailing_stop_pct(&config, &hot_scan, 20.0),
+            config.trailing_stop_medium_pct
+        );
 
-        let event = AutoSeller::check_triggers(&config, &position, 0.00012);
+        // +6% with the peak at +12%: 5.4% off the peak trips the fixed 5% trail
+        let thresholds = EntryTypeThresholds::default();
+        let signal =
+            check_exit(&config, &start, &thresholds, &position(1.0, 1.12), 1.06, 20).unwrap();
+        assert!(signal.reason.starts_with("TRAILING STOP"));
+    }
 
-        assert!(event.is_none());
+    #[test]
+    fn test_no_movement_exit_only_with_profile() {
+        let (mut config, start, hot_scan) = profiles();
+        config.no_movement_secs = 60;
+        config.no_movement_threshold_pct = 2.0;
+        let thresholds = EntryTypeThresholds::default();
+        let p = position(1.0, 1.0);
+
+        assert!(check_exit(&config, &start, &thresholds, &p, 1.01, 90).is_none());
+        let signal = check_exit(&config, &hot_scan, &thresholds, &p, 1.01, 90).unwrap();
+        assert!(signal.reason.starts_with("NO MOVEMENT"));
+        assert_eq!(
+            TradeExitReason::from_description(&signal.reason),
+            TradeExitReason::NoMovement
+        );
     }
 
     #[test]
-    fn test_partial_take_profit() {
-        let mut config = test_config();
-        config.partial_take_profit = true;
+    fn test_local_api_lightning_ladder() {
+        let config = crate::config::Config::default();
+        let seller = |attempts: u32| {
+            PumpPortalSeller::new(
+                Arc::new(PumpPortalTrader::new(None, true)),
+                Arc::new(RpcClient::new("http://localhost:8899".to_string())),
+                Arc::new(Keypair::new()),
+                &config,
+            )
+            .with_local_api_lightning_attempts(attempts)
+        };
+        let profiles = config.auto_sell.monitor.clone();
+        let p = position(1.0, 1.0);
+        let route = |seller: &PumpPortalSeller, attempt| {
+            seller.route(&p, &seller.signer.pubkey(), attempt, None)
+        };
 
-        let position = test_position(0.0001, 0.00016); // +60%
-        let event = AutoSeller::check_triggers(&config, &position, 0.00016).unwrap();
+        // Both commands sign locally from the first attempt
+        for attempts in [
+            profiles.start.local_api_lightning_attempts,
+            profiles.hot_scan.local_api_lightning_attempts,
+        ] {
+            let local = seller(attempts);
+            assert_eq!(route(&local, 1), SellRoute::Local);
+            assert!(!local.has_fallback(&p, 1, None));
+        }
 
-        // Should sell half
-        assert_eq!(event.sell_amount, 500_000);
+        // Lightning three times, then local signing
+        let ladder = seller(3);
+        assert_eq!(route(&ladder, 3), SellRoute::Lightning);
+        assert_eq!(route(&ladder, 4), SellRoute::Local);
+        assert!(ladder.has_fallback(&p, 3, None));
+        // A kill-switch policy's attempts are Lightning-only: Local API mode signs locally
+        assert!(!ladder.has_fallback(&p, 3, Some(3)));
     }
 }
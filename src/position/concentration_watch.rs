@@ -0,0 +1,281 @@
+//! Holder concentration on held positions
+//!
+//! Entry scoring only sees the holders cached at entry; a whale that buys up
+//! the supply afterwards never shows up there. The distribution scored at
+//! entry is kept on the position, and each held position's holders are
+//! refetched every `holder_watcher.refresh_secs` through the Critical
+//! enrichment lane. Once the fresh list is in the cache the distribution
+//! provider's position-signal pass compares it with the entry distribution
+//! (positions opened without cached holders are not watched). A top holder
+//! whose share climbed by `concentration_min_rise_pct` to
+//! `concentration_warning_pct` tightens the trailing stop to
+//! `concentration_trailing_pct`; at `concentration_critical_pct` the position
+//! monitor also sells `concentration_exit_pct`% of what's left. Each level
+//! fires once per position and run.
+
+use dashmap::DashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::filter::signals::{DistributionSignalProvider, Signal, SignalProvider, SignalType};
+use crate::filter::types::PositionSignalContext;
+use crate::filter::{ConcentrationEscalation, HolderWatcherConfig};
+use crate::position::auto_sell::{ExitRequest, ExitSignal};
+use crate::position::manager::{Position, PositionManager};
+
+/// Escalates exits when a held token's holders concentrate
+pub struct ConcentrationWatch {
+    config: HolderWatcherConfig,
+    distribution: Arc<DistributionSignalProvider>,
+    position_manager: Arc<PositionManager>,
+    exit_requests: Option<mpsc::Sender<ExitRequest>>,
+    /// Level already acted on, by mint
+    acted: DashMap<String, ConcentrationEscalation>,
+}
+
+impl ConcentrationWatch {
+    pub fn new(
+        config: HolderWatcherConfig,
+        distribution: Arc<DistributionSignalProvider>,
+        position_manager: Arc<PositionManager>,
+    ) -> Self {
+        Self {
+            config,
+            distribution,
+            position_manager,
+            exit_requests: None,
+            acted: DashMap::new(),
+        }
+    }
+
+    /// Send partial exits to the position monitor
+    pub fn with_exit_requests(mut self, requests: mpsc::Sender<ExitRequest>) -> Self {
+        self.exit_requests = Some(requests);
+        self
+    }
+
+    /// Forget the positions no longer held
+    pub fn sync_positions(&self, positions: &[Position]) {
+        self.acted
+            .retain(|mint, _| positions.iter().any(|p| &p.mint == mint));
+    }
+
+    /// Re-evaluate a held position once its refreshed holders are cached
+    pub async fn evaluate(&self, mint: &str) -> ConcentrationEscalation {
+        let Some(position) = self.position_manager.get_position(mint).await else {
+            return ConcentrationEscalation::None;
+        };
+        let Some(ref entry) = position.holder_distribution else {
+            return ConcentrationEscalation::None;
+        };
+        let context = PositionSignalContext {
+            mint: position.mint.clone(),
+            entry_time: position.entry_time,
+            entry_price: position.entry_price,
+            current_price: position.current_price,
+            position_size_sol: position.total_cost_sol,
+            unrealized_pnl_pct: position.unrealized_pnl_pct(),
+            recent_trades: Vec::new(),
+            holder_distribution: Some(entry.clone()),
+            order_flow: None,
+        };
+        let Some((signal, current_pct)) = self.concentration(&context).await else {
+            return ConcentrationEscalation::None;
+        };
+        debug!("[{}] {}", position.symbol, signal);
+
+        let escalation = self
+            .config
+            .concentration_escalation(entry.top_holder_pct, current_pct);
+        let acted = self
+            .acted
+            .get(mint)
+            .map_or(ConcentrationEscalation::None, |acted| *acted);
+        if escalation <= acted || position.kill_switch_triggered {
+            return escalation;
+        }
+        self.acted.insert(mint.to_string(), escalation);
+        self.act(&position, escalation, &signal.reason).await;
+        escalation
+    }
+
+    /// The position-signal pass's HolderConcentration signal and the current
+    /// top holder share (None = no refreshed holders cached)
+    async fn concentration(&self, context: &PositionSignalContext) -> Option<(Signal, f64)> {
+        let signal = self
+            .distribution
+            .compute_position_signals(context)
+            .await
+            .into_iter()
+            .find(|signal| signal.signal_type == SignalType::HolderConcentration)
+            .filter(|signal| signal.confidence > 0.0)?;
+        let current = self
+            .distribution
+            .position_distribution(&context.mint)
+            .await?;
+        Some((signal, current.top_holder_pct))
+    }
+
+    async fn act(&self, position: &Position, escalation: ConcentrationEscalation, reason: &str) {
+        let trailing_pct = self.config.concentration_trailing_pct;
+        let tighter = !position
+            .exit_override
+            .trailing_pct
+            .is_some_and(|t| t <= trailing_pct);
+        if tighter {
+            warn!(
+                "[{}] Holder concentration rising ({}) - tightening trailing stop to {:.1}%",
+                position.symbol, reason, trailing_pct
+            );
+            if let Err(e) = self
+                .position_manager
+                .set_exit_override(&position.mint, None, None, Some(trailing_pct))
+                .await
+            {
+                warn!(
+                    "[{}] Failed to tighten trailing stop: {}",
+                    position.symbol, e
+                );
+            }
+        }
+
+        if escalation != ConcentrationEscalation::PartialExit {
+            return;
+        }
+        let Some(ref requests) = self.exit_requests else {
+            return;
+        };
+        let pct = self.config.concentration_exit_pct;
+        warn!(
+            "[{}] Holder concentration critical - selling {}% of the position",
+            position.symbol, pct
+        );
+        let request = ExitRequest {
+            mint: position.mint.clone(),
+            signal: ExitSignal::reduce(pct, format!("HOLDER CONCENTRATION: {}", reason)),
+        };
+        if requests.send(request).await.is_err() {
+            warn!(
+                "[{}] Position monitor gone - partial exit not sent",
+                position.symbol
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::cache::FilterCache;
+    use crate::filter::types::{TokenDistribution, TokenHolderInfo};
+    use crate::position::fees::TradeFees;
+    use crate::position::manager::{EntryType, ExitOverride};
+
+    fn position(mint: &str, holder_distribution: Option<TokenDistribution>) -> Position {
+        Position {
+            mint: mint.to_string(),
+            name: "Test".to_string(),
+            symbol: "TEST".to_string(),
+            bonding_curve: "curve".to_string(),
+            token_amount: 1_000_000,
+            entry_price: 1e-8,
+            total_cost_sol: 0.01,
+            entry_time: chrono::Utc::now(),
+            entry_signature: "sig".to_string(),
+            current_price: 1e-8,
+            entry_type: EntryType::Opportunity,
+            quick_profit_taken: false,
+            second_profit_taken: false,
+            peak_price: 1e-8,
+            trough_price: 1e-8,
+            kill_switch_triggered: false,
+            kill_switch_reason: None,
+            wallet_pubkey: String::new(),
+            fills: vec![],
+            scale_in: None,
+            exit_override: ExitOverride::default(),
+            partial_fill: false,
+            regime: None,
+            exit_style: None,
+            exit_levels_hit: vec![],
+            paper: false,
+            privileges: None,
+            exit_liquidity: None,
+            slippage: None,
+            strategy_profile: None,
+            holder_distribution,
+            fees: TradeFees::default(),
+        }
+    }
+
+    /// Ten holders of 100 and a whale of `whale`
+    fn holders(whale: u64) -> Vec<TokenHolderInfo> {
+        (0..10)
+            .map(|i| (format!("h{}", i), 100))
+            .chain((whale > 0).then(|| ("whale".to_string(), whale)))
+            .map(|(address, amount)| TokenHolderInfo {
+                address,
+                amount,
+                percentage: 0.0,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_escalates_once_per_level() {
+        let cache = Arc::new(FilterCache::new());
+        let manager = Arc::new(PositionManager::new(
+            crate::config::Config::default().safety,
+            None,
+        ));
+        let distribution = Arc::new(DistributionSignalProvider::new(cache.clone()));
+        let (tx, mut rx) = mpsc::channel(4);
+        let watch = ConcentrationWatch::new(
+            HolderWatcherConfig::default(),
+            distribution.clone(),
+            manager.clone(),
+        )
+        .with_exit_requests(tx);
+
+        // Entered with ten equal holders cached; "bare" without holders
+        cache.set_holders("mint", holders(0));
+        let entry = distribution.position_distribution("mint").await;
+        manager
+            .open_position(position("mint", entry))
+            .await
+            .unwrap();
+        manager.open_position(position("bare", None)).await.unwrap();
+        assert_eq!(watch.evaluate("mint").await, ConcentrationEscalation::None);
+
+        // Whale at a third of the supply: trail tightened, nothing sold
+        cache.set_holders("mint", holders(500));
+        cache.set_holders("bare", holders(500));
+        assert_eq!(watch.evaluate("bare").await, ConcentrationEscalation::None);
+        assert_eq!(
+            watch.evaluate("mint").await,
+            ConcentrationEscalation::TightenTrail
+        );
+        let held = manager.get_position("mint").await.unwrap();
+        assert_eq!(held.exit_override.trailing_pct, Some(5.0));
+        assert!(rx.try_recv().is_err());
+
+        // Half the supply: partial exit, sent once
+        cache.set_holders("mint", holders(1_000));
+        assert_eq!(
+            watch.evaluate("mint").await,
+            ConcentrationEscalation::PartialExit
+        );
+        let request = rx.try_recv().unwrap();
+        assert!(matches!(
+            request.signal.layer,
+            crate::position::auto_sell::ExitLayer::Reduce(50)
+        ));
+        watch.evaluate("mint").await;
+        assert!(rx.try_recv().is_err());
+
+        // Closed positions are forgotten
+        watch.sync_positions(&[]);
+        assert!(watch.acted.is_empty());
+    }
+}
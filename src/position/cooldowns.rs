@@ -0,0 +1,304 @@
+//! Re-entry cooldowns and mint blacklists
+//!
+//! Every buy path consults the `CooldownTracker` before spending SOL on a
+//! mint. Entries are persisted to `cooldowns.json` (next to
+//! `bought_mints.json`) so a restart doesn't re-buy a token the bot just
+//! exited at the top.
+//!
+//! - **sold**: re-entry cooldown after a full exit
+//! - **failed**: buy landed no tokens (or kept failing)
+//! - **rugged**: kill-switch exit; permanent unless a TTL is configured
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use crate::config::CooldownConfig;
+use crate::error::{Error, Result};
+
+/// Why a mint is blocked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CooldownKind {
+    Sold,
+    Failed,
+    Rugged,
+}
+
+impl std::fmt::Display for CooldownKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CooldownKind::Sold => "sold",
+            CooldownKind::Failed => "failed",
+            CooldownKind::Rugged => "rugged",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for CooldownKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "sold" => Ok(CooldownKind::Sold),
+            "failed" => Ok(CooldownKind::Failed),
+            "rugged" => Ok(CooldownKind::Rugged),
+            other => Err(Error::Config(format!(
+                "unknown cooldown kind '{}' (expected sold, failed or rugged)",
+                other
+            ))),
+        }
+    }
+}
+
+/// One blocked mint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CooldownEntry {
+    pub kind: CooldownKind,
+    /// Unix timestamp the entry was recorded
+    pub since: i64,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Active cooldown returned by `CooldownTracker::check`
+#[derive(Debug, Clone)]
+pub struct CooldownHit {
+    pub kind: CooldownKind,
+    pub elapsed_secs: i64,
+    /// None for permanent entries
+    pub remaining_secs: Option<i64>,
+}
+
+impl std::fmt::Display for CooldownHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.remaining_secs {
+            Some(remaining) => write!(
+                f,
+                "{} {}s ago, cooldown {}s remaining",
+                self.kind, self.elapsed_secs, remaining
+            ),
+            None => write!(f, "{} (permanent)", self.kind),
+        }
+    }
+}
+
+/// Shared, persistent cooldown / blacklist store
+pub struct CooldownTracker {
+    config: std::sync::RwLock<CooldownConfig>,
+    entries: Arc<RwLock<HashMap<String, CooldownEntry>>>,
+    persistence_path: Option<String>,
+}
+
+impl CooldownTracker {
+    pub fn new(config: CooldownConfig, persistence_path: Option<String>) -> Self {
+        Self {
+            config: std::sync::RwLock::new(config),
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            persistence_path,
+        }
+    }
+
+    /// Apply reloaded TTLs (existing entries expire by the new ones)
+    pub fn set_config(&self, config: CooldownConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    /// TTL for a kind in seconds (None = never expires)
+    fn ttl_secs(&self, kind: CooldownKind) -> Option<i64> {
+        let config = self.config.read().unwrap();
+        let ttl = match kind {
+            CooldownKind::Sold => config.sold_ttl_secs,
+            CooldownKind::Failed => config.failed_ttl_secs,
+            CooldownKind::Rugged => config.rugged_ttl_secs,
+        };
+        (ttl > 0).then_some(ttl as i64)
+    }
+
+    fn hit(&self, entry: &CooldownEntry, now: i64) -> Option<CooldownHit> {
+        let elapsed_secs = (now - entry.since).max(0);
+        match self.ttl_secs(entry.kind) {
+            Some(ttl) if elapsed_secs >= ttl => None,
+            ttl => Some(CooldownHit {
+                kind: entry.kind,
+                elapsed_secs,
+                remaining_secs: ttl.map(|t| t - elapsed_secs),
+            }),
+        }
+    }
+
+    /// Load entries from disk, dropping expired ones
+    pub async fn load(&self) -> Result<()> {
+        if let Some(path) = &self.persistence_path {
+            if Path::new(path).exists() {
+                let data = tokio::fs::read_to_string(path)
+                    .await
+                    .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+                let loaded: HashMap<String, CooldownEntry> = serde_json::from_str(&data)
+                    .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+
+                let now = chrono::Utc::now().timestamp();
+                let before = loaded.len();
+                let active: HashMap<String, CooldownEntry> = loaded
+                    .into_iter()
+                    .filter(|(_, entry)| self.hit(entry, now).is_some())
+                    .collect();
+                info!(
+                    "Loaded {} cooldowns from {} ({} expired)",
+                    active.len(),
+                    path,
+                    before - active.len()
+                );
+                *self.entries.write().await = active;
+            }
+        }
+        Ok(())
+    }
+
+    /// Save entries to disk
+    pub async fn save(&self) -> Result<()> {
+        if let Some(path) = &self.persistence_path {
+            let entries = self.entries.read().await;
+            let data = serde_json::to_string_pretty(&*entries)
+                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            tokio::fs::write(path, data)
+                .await
+                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            debug!("Saved {} cooldowns to {}", entries.len(), path);
+        }
+        Ok(())
+    }
+
+    /// Block a mint
+    ///
+    /// A newer entry replaces an older one, except that an active rugged
+    /// entry is never downgraded to a temporary cooldown.
+    pub async fn mark(&self, mint: &str, kind: CooldownKind, reason: Option<String>) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        let mut entries = self.entries.write().await;
+        if let Some(existing) = entries.get(mint) {
+            if existing.kind == CooldownKind::Rugged
+                && kind != CooldownKind::Rugged
+                && self.hit(existing, now).is_some()
+            {
+                return Ok(());
+            }
+        }
+        entries.insert(
+            mint.to_string(),
+            CooldownEntry {
+                kind,
+                since: now,
+                reason,
+            },
+        );
+        drop(entries);
+
+        info!("Cooldown: {} marked {}", mint, kind);
+        self.save().await
+    }
+
+    /// Active cooldown for a mint, if any
+    pub async fn check(&self, mint: &str) -> Option<CooldownHit> {
+        let now = chrono::Utc::now().timestamp();
+        let entries = self.entries.read().await;
+        entries.get(mint).and_then(|entry| self.hit(entry, now))
+    }
+
+    /// All active entries, most recent first
+    pub async fn list(&self) -> Vec<(String, CooldownEntry, CooldownHit)> {
+        let now = chrono::Utc::now().timestamp();
+        let entries = self.entries.read().await;
+        let mut active: Vec<_> = entries
+            .iter()
+            .filter_map(|(mint, entry)| {
+                self.hit(entry, now)
+                    .map(|hit| (mint.clone(), entry.clone(), hit))
+            })
+            .collect();
+        active.sort_by(|a, b| b.1.since.cmp(&a.1.since));
+        active
+    }
+
+    /// Remove entries matching a mint and/or kind (both None = everything)
+    pub async fn clear(&self, mint: Option<&str>, kind: Option<CooldownKind>) -> Result<usize> {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|m, entry| {
+            !(mint.map_or(true, |target| m == target) && kind.map_or(true, |k| entry.kind == k))
+        });
+        let removed = before - entries.len();
+        drop(entries);
+
+        self.save().await?;
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> CooldownTracker {
+        CooldownTracker::new(
+            CooldownConfig {
+                sold_ttl_secs: 300,
+                failed_ttl_secs: 1800,
+                rugged_ttl_secs: 0,
+            },
+            None,
+        )
+    }
+
+    async fn backdate(tracker: &CooldownTracker, mint: &str, secs: i64) {
+        let mut entries = tracker.entries.write().await;
+        entries.get_mut(mint).unwrap().since -= secs;
+    }
+
+    #[tokio::test]
+    async fn test_sold_cooldown_expires() {
+        let tracker = tracker();
+        tracker.mark("m", CooldownKind::Sold, None).await.unwrap();
+
+        let hit = tracker.check("m").await.unwrap();
+        assert_eq!(hit.kind, CooldownKind::Sold);
+        assert!(hit.remaining_secs.unwrap() > 290);
+
+        backdate(&tracker, "m", 301).await;
+        assert!(tracker.check("m").await.is_none());
+        assert!(tracker.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rugged_is_permanent_and_sticky() {
+        let tracker = tracker();
+        tracker
+            .mark("m", CooldownKind::Rugged, Some("deployer sold".into()))
+            .await
+            .unwrap();
+        backdate(&tracker, "m", 10 * 86_400).await;
+
+        // A later sold cooldown must not downgrade the blacklist
+        tracker.mark("m", CooldownKind::Sold, None).await.unwrap();
+        let hit = tracker.check("m").await.unwrap();
+        assert_eq!(hit.kind, CooldownKind::Rugged);
+        assert!(hit.remaining_secs.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_clear_by_kind() {
+        let tracker = tracker();
+        tracker.mark("a", CooldownKind::Sold, None).await.unwrap();
+        tracker.mark("b", CooldownKind::Failed, None).await.unwrap();
+        tracker.mark("c", CooldownKind::Failed, None).await.unwrap();
+
+        assert_eq!(tracker.clear(None, Some(CooldownKind::Failed)).await.unwrap(), 2);
+        assert!(tracker.check("a").await.is_some());
+        assert_eq!(tracker.clear(Some("a"), None).await.unwrap(), 1);
+        assert!(tracker.list().await.is_empty());
+    }
+}
@@ -0,0 +1,344 @@
+//! Early-weakness exit
+//!
+//! `FlowWatch` keeps buy/sell SOL and price for every held position from the
+//! trade feed over a short and a long window (15s / 60s by default). When net
+//! flow turns strongly negative on both windows and price is falling while
+//! the position sits between break-even and its stop loss,
+//! `check_early_weakness` exits before the stop is hit. It never fires inside
+//! the monitor's confirmation window or `grace_secs` after entry.
+
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+
+use crate::config::EarlyWeaknessConfig;
+use crate::position::manager::Position;
+use crate::strategy::RollingWindow;
+use crate::stream::pumpportal::TradeEvent;
+
+/// Buy/sell SOL and price over one window
+struct FlowWindow {
+    buy_sol: RollingWindow,
+    sell_sol: RollingWindow,
+    price: RollingWindow,
+}
+
+impl FlowWindow {
+    fn new(secs: u64) -> Self {
+        let duration = Duration::from_secs(secs);
+        Self {
+            buy_sol: RollingWindow::new(duration),
+            sell_sol: RollingWindow::new(duration),
+            price: RollingWindow::new(duration),
+        }
+    }
+
+    /// Both sides get a sample so they prune together
+    fn add(&mut self, time: Instant, is_buy: bool, sol: f64, price: f64) {
+        if is_buy {
+            self.buy_sol.add_at(time, sol);
+            self.sell_sol.add_at(time, 0.0);
+        } else {
+            self.buy_sol.add_at(time, 0.0);
+            self.sell_sol.add_at(time, sol);
+        }
+        if price > 0.0 {
+            self.price.add_at(time, price);
+        }
+    }
+
+    /// (buys - sells) / (buys + sells), from -1 (all sells) to 1 (all buys)
+    fn net_flow(&self) -> f64 {
+        let buys = self.buy_sol.sum();
+        let sells = self.sell_sol.sum();
+        if buys + sells > 0.0 {
+            (buys - sells) / (buys + sells)
+        } else {
+            0.0
+        }
+    }
+
+    fn price_change_pct(&self) -> f64 {
+        let oldest = self.price.oldest();
+        if oldest > 0.0 {
+            self.price.delta() / oldest * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Trade-feed state for one held position
+struct FlowState {
+    short: FlowWindow,
+    long: FlowWindow,
+    last_trade: Instant,
+}
+
+/// Order flow for a position over the short and long windows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowSnapshot {
+    /// Net flow over the short window (-1 all sells .. 1 all buys)
+    pub short_net_flow: f64,
+    /// Net flow over the long window
+    pub long_net_flow: f64,
+    /// Sell SOL over the short window
+    pub short_sell_sol: f64,
+    /// Trades over the short window
+    pub short_trades: usize,
+    /// Price change over the short window, in %
+    pub short_price_change_pct: f64,
+    /// Price change over the long window, in %
+    pub long_price_change_pct: f64,
+}
+
+/// Order flow for held positions, fed by the trade feed
+pub struct FlowWatch {
+    config: EarlyWeaknessConfig,
+    flows: DashMap<String, FlowState>,
+}
+
+impl FlowWatch {
+    pub fn new(config: EarlyWeaknessConfig) -> Self {
+        Self {
+            config,
+            flows: DashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &EarlyWeaknessConfig {
+        &self.config
+    }
+
+    /// Feed a trade from the stream (ignored unless the mint is held)
+    pub fn record_trade(&self, trade: &TradeEvent) {
+        self.record_trade_at(trade, Instant::now());
+    }
+
+    /// `record_trade` for a trade that arrived at `time` (replays)
+    pub fn record_trade_at(&self, trade: &TradeEvent, time: Instant) {
+        let Some(mut flow) = self.flows.get_mut(&trade.mint) else {
+            return;
+        };
+        let is_buy = trade.tx_type == "buy";
+        // pump.fun supply is 1B tokens
+        let price = trade.market_cap_sol / 1_000_000_000.0;
+        flow.short.add(time, is_buy, trade.sol_amount, price);
+        flow.long.add(time, is_buy, trade.sol_amount, price);
+        flow.last_trade = time;
+    }
+
+    /// Start watching new positions and forget closed ones
+    pub fn sync_positions(&self, positions: &[Position]) {
+        for position in positions {
+            if !self.flows.contains_key(&position.mint) {
+                self.flows.insert(
+                    position.mint.clone(),
+                    FlowState {
+                        short: FlowWindow::new(self.config.short_window_secs),
+                        long: FlowWindow::new(self.config.long_window_secs),
+                        last_trade: Instant::now(),
+                    },
+                );
+            }
+        }
+        self.flows
+            .retain(|mint, _| positions.iter().any(|p| &p.mint == mint));
+    }
+
+    /// None until the mint has traded, or once the short window has gone
+    /// quiet (windows only prune when a trade arrives)
+    pub fn snapshot(&self, mint: &str) -> Option<FlowSnapshot> {
+        self.snapshot_at(mint, Instant::now())
+    }
+
+    /// `snapshot` as of `now` (replays)
+    pub fn snapshot_at(&self, mint: &str, now: Instant) -> Option<FlowSnapshot> {
+        let flow = self.flows.get(mint)?;
+        if flow.short.price.is_empty()
+            || now.saturating_duration_since(flow.last_trade)
+                > Duration::from_secs(self.config.short_window_secs)
+        {
+            return None;
+        }
+        Some(FlowSnapshot {
+            short_net_flow: flow.short.net_flow(),
+            long_net_flow: flow.long.net_flow(),
+            short_sell_sol: flow.short.sell_sol.sum(),
+            short_trades: flow.short.buy_sol.count(),
+            short_price_change_pct: flow.short.price_change_pct(),
+            long_price_change_pct: flow.long.price_change_pct(),
+        })
+    }
+}
+
+/// Exit reason when order flow turns against a position still above its
+/// stop loss, or None
+///
+/// `sl_pct` is the position's effective stop loss and `confirm_secs` the
+/// monitor's confirmation window.
+pub fn check_early_weakness(
+    config: &EarlyWeaknessConfig,
+    flow: &FlowSnapshot,
+    pnl_pct: f64,
+    sl_pct: f64,
+    hold_time_secs: u64,
+    confirm_secs: u64,
+) -> Option<String> {
+    if hold_time_secs < config.grace_secs.max(confirm_secs) {
+        return None;
+    }
+    // Only between break-even and the stop; the stop loss handles the rest
+    if pnl_pct > 0.0 || pnl_pct <= -sl_pct {
+        return None;
+    }
+    if flow.short_trades < config.min_trades
+        || flow.short_sell_sol < config.min_sell_sol
+        || flow.short_net_flow > config.short_net_flow
+        || flow.long_net_flow > config.long_net_flow
+        || flow.short_price_change_pct > config.max_price_change_pct
+    {
+        return None;
+    }
+    Some(format!(
+        "EARLY WEAKNESS at {:.1}% (stop: -{:.0}%): net flow {:+.2} ({}s) / {:+.2} ({}s), price {:+.1}% over {}s",
+        pnl_pct,
+        sl_pct,
+        flow.short_net_flow,
+        config.short_window_secs,
+        flow.long_net_flow,
+        config.long_window_secs,
+        flow.short_price_change_pct,
+        config.short_window_secs
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weak_flow() -> FlowSnapshot {
+        FlowSnapshot {
+            short_net_flow: -0.8,
+            long_net_flow: -0.4,
+            short_sell_sol: 2.0,
+            short_trades: 8,
+            short_price_change_pct: -4.0,
+            long_price_change_pct: -6.0,
+        }
+    }
+
+    fn trade(mint: &str, tx_type: &str, sol_amount: f64, market_cap_sol: f64) -> TradeEvent {
+        TradeEvent {
+            mint: mint.to_string(),
+            tx_type: tx_type.to_string(),
+            signature: String::new(),
+            trader_public_key: String::new(),
+            token_amount: 0.0,
+            sol_amount,
+            bonding_curve_key: String::new(),
+            v_tokens_in_bonding_curve: 0.0,
+            v_sol_in_bonding_curve: 0.0,
+            market_cap_sol,
+        }
+    }
+
+    #[test]
+    fn test_fires_between_break_even_and_stop() {
+        let config = EarlyWeaknessConfig::default();
+        let flow = weak_flow();
+
+        let reason = check_early_weakness(&config, &flow, -5.0, 15.0, 60, 10).unwrap();
+        assert!(reason.starts_with("EARLY WEAKNESS"));
+
+        // In profit: trailing stop territory
+        assert!(check_early_weakness(&config, &flow, 2.0, 15.0, 60, 10).is_none());
+        // At or past the stop: the stop loss fires instead
+        assert!(check_early_weakness(&config, &flow, -15.0, 15.0, 60, 10).is_none());
+    }
+
+    #[test]
+    fn test_never_fires_inside_confirmation_window() {
+        let config = EarlyWeaknessConfig::default();
+        let flow = weak_flow();
+        assert!(
+            check_early_weakness(&config, &flow, -5.0, 15.0, config.grace_secs - 1, 0).is_none()
+        );
+        // A longer confirmation window wins over grace_secs
+        assert!(check_early_weakness(&config, &flow, -5.0, 15.0, config.grace_secs, 120).is_none());
+    }
+
+    #[test]
+    fn test_needs_strong_negative_flow() {
+        let config = EarlyWeaknessConfig::default();
+        let mixed = FlowSnapshot {
+            short_net_flow: -0.2,
+            ..weak_flow()
+        };
+        assert!(check_early_weakness(&config, &mixed, -5.0, 15.0, 60, 10).is_none());
+        let thin = FlowSnapshot {
+            short_trades: 1,
+            ..weak_flow()
+        };
+        assert!(check_early_weakness(&config, &thin, -5.0, 15.0, 60, 10).is_none());
+        let rising = FlowSnapshot {
+            short_price_change_pct: 3.0,
+            ..weak_flow()
+        };
+        assert!(check_early_weakness(&config, &rising, -5.0, 15.0, 60, 10).is_none());
+    }
+
+    #[test]
+    fn test_flow_watch_tracks_held_mints() {
+        let watch = FlowWatch::new(EarlyWeaknessConfig::default());
+        watch.record_trade(&trade("held", "sell", 1.0, 30.0));
+        assert!(watch.snapshot("held").is_none());
+
+        watch.flows.insert(
+            "held".to_string(),
+            FlowState {
+                short: FlowWindow::new(15),
+                long: FlowWindow::new(60),
+                last_trade: Instant::now(),
+            },
+        );
+        watch.record_trade(&trade("held", "buy", 1.0, 30.0));
+        watch.record_trade(&trade("held", "sell", 3.0, 27.0));
+        watch.record_trade(&trade("other", "buy", 5.0, 30.0));
+
+        let flow = watch.snapshot("held").unwrap();
+        assert!((flow.short_net_flow - -0.5).abs() < 1e-9);
+        assert!((flow.short_sell_sol - 3.0).abs() < 1e-9);
+        assert_eq!(flow.short_trades, 2);
+        assert!((flow.short_price_change_pct - -10.0).abs() < 1e-9);
+
+        watch.sync_positions(&[]);
+        assert!(watch.snapshot("held").is_none());
+    }
+
+    #[test]
+    fn test_replay_time() {
+        let watch = FlowWatch::new(EarlyWeaknessConfig::default());
+        let start = Instant::now();
+        watch.flows.insert(
+            "held".to_string(),
+            FlowState {
+                short: FlowWindow::new(15),
+                long: FlowWindow::new(60),
+                last_trade: start,
+            },
+        );
+        watch.record_trade_at(&trade("held", "buy", 2.0, 30.0), start);
+        let later = start + Duration::from_secs(20);
+        watch.record_trade_at(&trade("held", "sell", 1.0, 27.0), later);
+
+        // The buy has left the short window but not the long one
+        let flow = watch.snapshot_at("held", later).unwrap();
+        assert!((flow.short_net_flow - -1.0).abs() < 1e-9);
+        assert!((flow.long_net_flow - 1.0 / 3.0).abs() < 1e-9);
+
+        // Quiet for longer than the short window
+        assert!(watch
+            .snapshot_at("held", later + Duration::from_secs(16))
+            .is_none());
+    }
+}
@@ -0,0 +1,127 @@
+//! Per-trade fee accounting
+//!
+//! Fees are estimated when a buy or sell is sent (configured priority fee,
+//! Jito tip, PumpPortal platform fee) and the network fee is refined from
+//! the confirmed transaction meta once `reconcile::spawn_confirmation` has
+//! fetched it. P&L is reported both gross (price movement only) and net of
+//! these fees.
+
+use serde::{Deserialize, Serialize};
+
+/// Fees paid on one or more transactions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TradeFees {
+    /// Network fee (base + priority) in lamports
+    pub priority_fee_lamports: u64,
+    /// Jito tip in lamports
+    pub tip_lamports: u64,
+    /// PumpPortal platform fee in SOL
+    pub platform_fee_sol: f64,
+    /// Network fee was taken from confirmed transaction meta
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+impl TradeFees {
+    /// Estimate fees at send time
+    ///
+    /// `trade_sol` is the SOL spent (buy) or received (sell) the platform
+    /// fee is charged on.
+    pub fn estimate(
+        priority_fee_sol: f64,
+        tip_lamports: u64,
+        trade_sol: f64,
+        platform_fee_pct: f64,
+    ) -> Self {
+        Self {
+            priority_fee_lamports: (priority_fee_sol * 1e9).round() as u64,
+            tip_lamports,
+            platform_fee_sol: trade_sol * platform_fee_pct / 100.0,
+            confirmed: false,
+        }
+    }
+
+    /// Total fees in SOL
+    pub fn total_sol(&self) -> f64 {
+        (self.priority_fee_lamports + self.tip_lamports) as f64 / 1e9 + self.platform_fee_sol
+    }
+
+    /// Accumulate fees of another transaction (e.g. a scale-in tranche)
+    pub fn add(&mut self, other: &TradeFees) {
+        self.priority_fee_lamports += other.priority_fee_lamports;
+        self.tip_lamports += other.tip_lamports;
+        self.platform_fee_sol += other.platform_fee_sol;
+        self.confirmed = self.confirmed && other.confirmed;
+    }
+
+    /// Portion of these fees attributable to a partial close
+    pub fn scaled(&self, ratio: f64) -> TradeFees {
+        let ratio = ratio.clamp(0.0, 1.0);
+        TradeFees {
+            priority_fee_lamports: (self.priority_fee_lamports as f64 * ratio).round() as u64,
+            tip_lamports: (self.tip_lamports as f64 * ratio).round() as u64,
+            platform_fee_sol: self.platform_fee_sol * ratio,
+            confirmed: self.confirmed,
+        }
+    }
+
+    /// Remove fees that were attributed to a closed portion
+    pub fn subtract(&mut self, other: &TradeFees) {
+        self.priority_fee_lamports = self
+            .priority_fee_lamports
+            .saturating_sub(other.priority_fee_lamports);
+        self.tip_lamports = self.tip_lamports.saturating_sub(other.tip_lamports);
+        self.platform_fee_sol = (self.platform_fee_sol - other.platform_fee_sol).max(0.0);
+    }
+
+    /// Replace the estimated network fee with the actual one from tx meta
+    pub fn refine(&mut self, network_fee_lamports: u64) {
+        self.priority_fee_lamports = network_fee_lamports;
+        self.confirmed = true;
+    }
+
+    /// One-line breakdown for logs and status output
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.6} SOL (network {} lamports{}, tip {} lamports, platform {:.6} SOL)",
+            self.total_sol(),
+            self.priority_fee_lamports,
+            if self.confirmed { "" } else { " est." },
+            self.tip_lamports,
+            self.platform_fee_sol
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_and_total() {
+        let fees = TradeFees::estimate(0.0001, 50_000, 0.5, 1.0);
+        assert_eq!(fees.priority_fee_lamports, 100_000);
+        assert!((fees.platform_fee_sol - 0.005).abs() < 1e-12);
+        assert!((fees.total_sol() - 0.00515).abs() < 1e-12);
+        assert!(!fees.confirmed);
+    }
+
+    #[test]
+    fn test_scale_and_subtract() {
+        let mut fees = TradeFees::estimate(0.0002, 0, 0.2, 0.5);
+        let half = fees.scaled(0.5);
+        assert_eq!(half.priority_fee_lamports, 100_000);
+        fees.subtract(&half);
+        assert_eq!(fees.priority_fee_lamports, 100_000);
+        assert!((fees.platform_fee_sol - 0.0005).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_refine() {
+        let mut fees = TradeFees::estimate(0.0001, 0, 0.1, 0.5);
+        fees.refine(85_000);
+        assert_eq!(fees.priority_fee_lamports, 85_000);
+        assert!(fees.confirmed);
+        assert!(fees.summary().contains("network 85000 lamports,"));
+    }
+}
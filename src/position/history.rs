@@ -0,0 +1,637 @@
+//! Closed trade history
+//!
+//! Every full or partial close is appended to `trades.json` (next to
+//! `positions.json`) as a typed `ClosedTrade` record. `snipe export` reads
+//! this file and writes it out as CSV or JSON for tax and analysis tooling.
+//! Each record also carries the max favorable / adverse excursion seen while
+//! the position was open, which `snipe stats` aggregates.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::position::fees::TradeFees;
+use crate::position::manager::EntryType;
+use crate::strategy::types::RegimeKind;
+
+/// Why a position (or part of it) was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeExitReason {
+    StopLoss,
+    TrailingStop,
+    TakeProfit,
+    QuickProfit,
+    MaxHold,
+    NoMovement,
+    KillSwitch,
+    /// Copy position sold because the tracked wallet sold
+    MirroredSell,
+    Manual,
+    /// Sell kept failing and the position was dropped
+    Abandoned,
+    /// Order flow turned against the position (exit style)
+    MomentumFade,
+    /// Order flow turned negative before the stop loss was hit
+    EarlyWeakness,
+    /// Part sold because the top holder's share climbed after entry
+    HolderConcentration,
+    Unknown,
+}
+
+impl TradeExitReason {
+    /// Classify a free-form exit description as logged by the monitors
+    pub fn from_description(description: &str) -> Self {
+        let d = description.to_uppercase();
+        if d.contains("KILL") {
+            TradeExitReason::KillSwitch
+        } else if d.contains("MIRROR") {
+            TradeExitReason::MirroredSell
+        } else if d.contains("HOLDER CONCENTRATION") {
+            TradeExitReason::HolderConcentration
+        } else if d.contains("TRAILING") {
+            TradeExitReason::TrailingStop
+        } else if d.contains("STOP LOSS") || d.contains("STOP_LOSS") {
+            TradeExitReason::StopLoss
+        } else if d.contains("QUICK PROFIT") || d.contains("SECOND PROFIT") {
+            TradeExitReason::QuickProfit
+        } else if d.contains("TAKE PROFIT") {
+            TradeExitReason::TakeProfit
+        } else if d.contains("MAX HOLD") {
+            TradeExitReason::MaxHold
+        } else if d.contains("NO MOVEMENT") {
+            TradeExitReason::NoMovement
+        } else if d.contains("MOMENTUM FADE") {
+            TradeExitReason::MomentumFade
+        } else if d.contains("EARLY WEAKNESS") {
+            TradeExitReason::EarlyWeakness
+        } else if d.contains("MANUAL") {
+            TradeExitReason::Manual
+        } else {
+            TradeExitReason::Unknown
+        }
+    }
+}
+
+impl std::fmt::Display for TradeExitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            TradeExitReason::StopLoss => "stop_loss",
+            TradeExitReason::TrailingStop => "trailing_stop",
+            TradeExitReason::TakeProfit => "take_profit",
+            TradeExitReason::QuickProfit => "quick_profit",
+            TradeExitReason::MaxHold => "max_hold",
+            TradeExitReason::NoMovement => "no_movement",
+            TradeExitReason::KillSwitch => "kill_switch",
+            TradeExitReason::MirroredSell => "mirrored_sell",
+            TradeExitReason::Manual => "manual",
+            TradeExitReason::Abandoned => "abandoned",
+            TradeExitReason::MomentumFade => "momentum_fade",
+            TradeExitReason::EarlyWeakness => "early_weakness",
+            TradeExitReason::HolderConcentration => "holder_concentration",
+            TradeExitReason::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for TradeExitReason {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_value(serde_json::Value::String(s.to_lowercase().replace('-', "_")))
+            .map_err(|_| Error::Config(format!("unknown exit reason: {}", s)))
+    }
+}
+
+/// A sell whose proceeds were measured on-chain (for execution feedback)
+#[derive(Debug, Clone, Copy)]
+pub struct SellFill {
+    /// SOL expected at the trigger price
+    pub expected_sol: f64,
+    /// SOL actually received
+    pub received_sol: f64,
+    /// Sell submitted to proceeds measured (ms)
+    pub latency_ms: u64,
+}
+
+/// Details of an exit supplied by the caller of `close_position_with_exit`
+#[derive(Debug, Clone)]
+pub struct ExitDetails {
+    pub reason: TradeExitReason,
+    pub signature: Option<String>,
+    /// Fees paid on the sell
+    pub fees: TradeFees,
+    /// Measured fill (None = proceeds were estimated)
+    pub fill: Option<SellFill>,
+}
+
+impl ExitDetails {
+    pub fn new(reason: TradeExitReason) -> Self {
+        Self {
+            reason,
+            signature: None,
+            fees: TradeFees::default(),
+            fill: None,
+        }
+    }
+
+    pub fn with_signature(mut self, signature: &str) -> Self {
+        self.signature = Some(signature.to_string());
+        self
+    }
+
+    pub fn with_fees(mut self, fees: TradeFees) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    pub fn with_fill(mut self, fill: SellFill) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+}
+
+impl Default for ExitDetails {
+    fn default() -> Self {
+        Self::new(TradeExitReason::Unknown)
+    }
+}
+
+/// One full or partial close
+///
+/// `proceeds_sol` is before exit fees. `gross_pnl_*` is price movement
+/// only; `pnl_*` is net of entry and exit fees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedTrade {
+    pub mint: String,
+    pub symbol: String,
+    pub entry_time: DateTime<Utc>,
+    pub exit_time: DateTime<Utc>,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub tokens: u64,
+    pub cost_sol: f64,
+    pub proceeds_sol: f64,
+    /// Entry fees attributable to the sold portion
+    #[serde(default)]
+    pub entry_fees: TradeFees,
+    #[serde(default)]
+    pub exit_fees: TradeFees,
+    /// Total of entry and exit fees
+    pub fees_sol: f64,
+    #[serde(default)]
+    pub gross_pnl_sol: f64,
+    #[serde(default)]
+    pub gross_pnl_pct: f64,
+    pub pnl_sol: f64,
+    pub pnl_pct: f64,
+    pub entry_type: EntryType,
+    pub exit_reason: TradeExitReason,
+    pub entry_signature: String,
+    pub exit_signature: Option<String>,
+    /// Highest price seen while the position was open (0 = not tracked)
+    #[serde(default)]
+    pub peak_price: f64,
+    /// Lowest price seen while the position was open
+    #[serde(default)]
+    pub trough_price: f64,
+    /// Max favorable excursion: % above entry at the peak
+    #[serde(default)]
+    pub mfe_pct: f64,
+    #[serde(default)]
+    pub mfe_sol: f64,
+    /// Max adverse excursion: % below entry at the trough (zero or negative)
+    #[serde(default)]
+    pub mae_pct: f64,
+    #[serde(default)]
+    pub mae_sol: f64,
+    /// Regime the position was entered under (None = not recorded)
+    #[serde(default)]
+    pub regime: Option<RegimeKind>,
+    /// Strategy profile the position was entered under (None = not recorded)
+    #[serde(default)]
+    pub strategy_profile: Option<String>,
+}
+
+impl ClosedTrade {
+    /// Record the price extremes seen while open and derive MFE / MAE
+    ///
+    /// The entry and exit prices bound the range, so a position that never
+    /// got a price update still gets a consistent (if narrow) excursion.
+    pub fn set_excursions(&mut self, peak_price: f64, trough_price: f64) {
+        if self.entry_price <= 0.0 {
+            return;
+        }
+        let mut peak = peak_price.max(self.entry_price);
+        let mut trough = if trough_price > 0.0 {
+            trough_price.min(self.entry_price)
+        } else {
+            self.entry_price
+        };
+        if self.exit_price > 0.0 {
+            peak = peak.max(self.exit_price);
+            trough = trough.min(self.exit_price);
+        }
+
+        self.peak_price = peak;
+        self.trough_price = trough;
+        self.mfe_pct = (peak - self.entry_price) / self.entry_price * 100.0;
+        self.mae_pct = (trough - self.entry_price) / self.entry_price * 100.0;
+        self.mfe_sol = self.cost_sol * self.mfe_pct / 100.0;
+        self.mae_sol = self.cost_sol * self.mae_pct / 100.0;
+    }
+
+    /// Whether this record carries excursion data (older records don't)
+    pub fn has_excursions(&self) -> bool {
+        self.peak_price > 0.0
+    }
+
+    /// Percentage points of the peak gain given back by the exit
+    pub fn given_back_pct(&self) -> f64 {
+        (self.mfe_pct - self.gross_pnl_pct).max(0.0)
+    }
+
+    /// SOL of the peak gain given back by the exit
+    pub fn given_back_sol(&self) -> f64 {
+        (self.mfe_sol - self.gross_pnl_sol).max(0.0)
+    }
+
+    /// Seconds between entry and exit
+    pub fn hold_secs(&self) -> i64 {
+        (self.exit_time - self.entry_time).num_seconds().max(0)
+    }
+
+    /// Recompute fee totals and P&L after fees changed
+    pub fn recompute_pnl(&mut self) {
+        self.fees_sol = self.entry_fees.total_sol() + self.exit_fees.total_sol();
+        self.gross_pnl_sol = self.proceeds_sol - self.cost_sol;
+        self.pnl_sol = self.gross_pnl_sol - self.fees_sol;
+        let pct = |pnl: f64| {
+            if self.cost_sol > 0.0 {
+                pnl / self.cost_sol * 100.0
+            } else {
+                0.0
+            }
+        };
+        self.gross_pnl_pct = pct(self.gross_pnl_sol);
+        self.pnl_pct = pct(self.pnl_sol);
+    }
+
+    const CSV_HEADER: [&'static str; 23] = [
+        "mint",
+        "symbol",
+        "entry_time",
+        "exit_time",
+        "entry_price",
+        "exit_price",
+        "tokens",
+        "cost_sol",
+        "proceeds_sol",
+        "fees_sol",
+        "gross_pnl_sol",
+        "gross_pnl_pct",
+        "pnl_sol",
+        "pnl_pct",
+        "entry_type",
+        "exit_reason",
+        "entry_signature",
+        "exit_signature",
+        "mfe_pct",
+        "mfe_sol",
+        "mae_pct",
+        "mae_sol",
+        "regime",
+    ];
+
+    fn csv_fields(&self) -> [String; 23] {
+        [
+            self.mint.clone(),
+            self.symbol.clone(),
+            self.entry_time.to_rfc3339(),
+            self.exit_time.to_rfc3339(),
+            format!("{:.12}", self.entry_price),
+            format!("{:.12}", self.exit_price),
+            self.tokens.to_string(),
+            format!("{:.9}", self.cost_sol),
+            format!("{:.9}", self.proceeds_sol),
+            format!("{:.9}", self.fees_sol),
+            format!("{:.9}", self.gross_pnl_sol),
+            format!("{:.2}", self.gross_pnl_pct),
+            format!("{:.9}", self.pnl_sol),
+            format!("{:.2}", self.pnl_pct),
+            format!("{:?}", self.entry_type),
+            self.exit_reason.to_string(),
+            self.entry_signature.clone(),
+            self.exit_signature.clone().unwrap_or_default(),
+            format!("{:.2}", self.mfe_pct),
+            format!("{:.9}", self.mfe_sol),
+            format!("{:.2}", self.mae_pct),
+            format!("{:.9}", self.mae_sol),
+            self.regime.map(|r| r.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+/// Load all closed trades (empty if the file doesn't exist yet)
+pub fn load_trades(path: &Path) -> Result<Vec<ClosedTrade>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Append one closed trade to the history file
+pub fn append_trade(path: &Path, trade: ClosedTrade) -> Result<()> {
+    let mut trades = load_trades(path)?;
+    trades.push(trade);
+    save_trades(path, &trades)
+}
+
+/// Write the history to a temp file and swap it in, so a crash mid-write
+/// never truncates it
+fn save_trades(path: &Path, trades: &[ClosedTrade]) -> Result<()> {
+    let data = serde_json::to_string_pretty(trades)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, data)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Replace the estimated network fee of a sell with the confirmed one
+pub fn refine_exit_fees(path: &Path, signature: &str, network_fee_lamports: u64) -> Result<bool> {
+    let mut trades = load_trades(path)?;
+    let Some(trade) = trades
+        .iter_mut()
+        .find(|t| t.exit_signature.as_deref() == Some(signature))
+    else {
+        return Ok(false);
+    };
+    trade.exit_fees.refine(network_fee_lamports);
+    trade.recompute_pnl();
+
+    save_trades(path, &trades)?;
+    Ok(true)
+}
+
+/// Export output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(Error::Config(format!(
+                "unknown export format '{}' (expected csv or json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Filter applied before exporting
+#[derive(Debug, Clone, Default)]
+pub struct TradeFilter {
+    /// Exit time at or after
+    pub from: Option<DateTime<Utc>>,
+    /// Exit time before
+    pub to: Option<DateTime<Utc>>,
+    pub mint: Option<String>,
+    pub reason: Option<TradeExitReason>,
+}
+
+impl TradeFilter {
+    pub fn matches(&self, trade: &ClosedTrade) -> bool {
+        self.from.map_or(true, |from| trade.exit_time >= from)
+            && self.to.map_or(true, |to| trade.exit_time < to)
+            && self.mint.as_ref().map_or(true, |m| &trade.mint == m)
+            && self.reason.map_or(true, |r| trade.exit_reason == r)
+    }
+}
+
+/// Parse a CLI date: `YYYY-MM-DD` (midnight UTC) or RFC 3339
+///
+/// With `end_of_day`, a plain date covers the whole day (exclusive bound is
+/// the next midnight).
+pub fn parse_date(s: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| Error::Config(format!("invalid date '{}' (use YYYY-MM-DD)", s)))?;
+    let date = if end_of_day {
+        date.succ_opt().unwrap_or(date)
+    } else {
+        date
+    };
+    Ok(date
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is valid")
+        .and_utc())
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Render trades as CSV with a header row
+pub fn to_csv(trades: &[ClosedTrade]) -> String {
+    let mut out = ClosedTrade::CSV_HEADER.join(",");
+    out.push('\n');
+    for trade in trades {
+        let row: Vec<String> = trade.csv_fields().iter().map(|f| csv_escape(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Render trades in the requested format
+pub fn render(trades: &[ClosedTrade], format: ExportFormat) -> Result<String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(trades)),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(trades)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(mint: &str, symbol: &str, reason: TradeExitReason, exit: &str) -> ClosedTrade {
+        ClosedTrade {
+            mint: mint.to_string(),
+            symbol: symbol.to_string(),
+            entry_time: parse_date("2025-01-01", false).unwrap(),
+            exit_time: DateTime::parse_from_rfc3339(exit).unwrap().with_timezone(&Utc),
+            entry_price: 0.00000003,
+            exit_price: 0.00000004,
+            tokens: 1_000_000,
+            cost_sol: 0.1,
+            proceeds_sol: 0.13,
+            entry_fees: TradeFees::estimate(0.0001, 0, 0.1, 0.5),
+            exit_fees: TradeFees::estimate(0.0001, 0, 0.13, 0.5),
+            fees_sol: 0.0,
+            gross_pnl_sol: 0.0,
+            gross_pnl_pct: 0.0,
+            pnl_sol: 0.0,
+            pnl_pct: 0.0,
+            entry_type: EntryType::Opportunity,
+            exit_reason: reason,
+            entry_signature: "sig_in".to_string(),
+            exit_signature: None,
+            peak_price: 0.0,
+            trough_price: 0.0,
+            mfe_pct: 0.0,
+            mfe_sol: 0.0,
+            mae_pct: 0.0,
+            mae_sol: 0.0,
+            regime: None,
+            strategy_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let t = trade("m1", "A,\"B\"", TradeExitReason::TakeProfit, "2025-01-02T00:00:00Z");
+        let csv = to_csv(&[t]);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("m1,\"A,\"\"B\"\"\","));
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn test_filter() {
+        let trades = vec![
+            trade("m1", "A", TradeExitReason::StopLoss, "2025-01-02T10:00:00Z"),
+            trade("m2", "B", TradeExitReason::TakeProfit, "2025-01-03T10:00:00Z"),
+        ];
+
+        let by_date = TradeFilter {
+            from: Some(parse_date("2025-01-02", false).unwrap()),
+            to: Some(parse_date("2025-01-02", true).unwrap()),
+            ..Default::default()
+        };
+        let kept: Vec<_> = trades.iter().filter(|t| by_date.matches(t)).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].mint, "m1");
+
+        let by_reason = TradeFilter {
+            reason: Some("take-profit".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(!by_reason.matches(&trades[0]));
+        assert!(by_reason.matches(&trades[1]));
+    }
+
+    #[test]
+    fn test_reason_classification() {
+        assert_eq!(
+            TradeExitReason::from_description("TRAILING STOP: dropped 6.0% from peak"),
+            TradeExitReason::TrailingStop
+        );
+        assert_eq!(
+            TradeExitReason::from_description("STOP LOSS at -31.0% (limit: -30%)"),
+            TradeExitReason::StopLoss
+        );
+        assert_eq!(
+            TradeExitReason::from_description("QUICK PROFIT at 25.0% - selling 50%"),
+            TradeExitReason::QuickProfit
+        );
+        assert_eq!(
+            TradeExitReason::from_description("MIRRORED SELL: tracked wallet sold 40%"),
+            TradeExitReason::MirroredSell
+        );
+        assert_eq!(
+            TradeExitReason::from_description("MANUAL CLOSE requested by operator"),
+            TradeExitReason::Manual
+        );
+        assert_eq!(
+            TradeExitReason::from_description(
+                "HOLDER CONCENTRATION: top holder 12.0% at entry, now 48.0%"
+            ),
+            TradeExitReason::HolderConcentration
+        );
+        assert!("bogus".parse::<TradeExitReason>().is_err());
+    }
+
+    #[test]
+    fn test_net_pnl_after_fees() {
+        let mut t = trade("m1", "A", TradeExitReason::TakeProfit, "2025-01-02T00:00:00Z");
+        t.recompute_pnl();
+        // 0.0001 + 0.0005 entry, 0.0001 + 0.00065 exit
+        assert!((t.fees_sol - 0.00135).abs() < 1e-12);
+        assert!((t.gross_pnl_sol - 0.03).abs() < 1e-12);
+        assert!((t.pnl_sol - 0.02865).abs() < 1e-12);
+        assert!(t.pnl_pct < t.gross_pnl_pct);
+    }
+
+    #[test]
+    fn test_excursions() {
+        let mut t = trade("m1", "A", TradeExitReason::TrailingStop, "2025-01-01T00:02:00Z");
+        t.recompute_pnl();
+        assert!(!t.has_excursions());
+
+        // Peaked at +100%, dipped to -20%, exited at +30% gross
+        t.set_excursions(0.00000006, 0.000000024);
+        assert!(t.has_excursions());
+        assert!((t.mfe_pct - 100.0).abs() < 1e-9);
+        assert!((t.mae_pct + 20.0).abs() < 1e-9);
+        assert!((t.mfe_sol - 0.1).abs() < 1e-12);
+        assert!((t.given_back_pct() - 70.0).abs() < 1e-9);
+        assert!((t.given_back_sol() - 0.07).abs() < 1e-12);
+        assert_eq!(t.hold_secs(), 120);
+
+        // Untracked trough falls back to the entry / exit range
+        t.set_excursions(0.0, 0.0);
+        assert!((t.mfe_pct - (t.exit_price / t.entry_price - 1.0) * 100.0).abs() < 1e-9);
+        assert_eq!(t.mae_pct, 0.0);
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let trades = vec![trade("m1", "A", TradeExitReason::Manual, "2025-01-02T10:00:00Z")];
+        let json = render(&trades, ExportFormat::Json).unwrap();
+        assert!(json.contains("\"exit_reason\": \"manual\""));
+        let parsed: Vec<ClosedTrade> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0].tokens, 1_000_000);
+    }
+
+    #[test]
+    fn test_append_and_refine_replace_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trade_history.json");
+        let mut sold = trade("m1", "A", TradeExitReason::Manual, "2025-01-02T10:00:00Z");
+        sold.exit_signature = Some("sig_out".to_string());
+        append_trade(&path, sold).unwrap();
+        append_trade(
+            &path,
+            trade("m2", "B", TradeExitReason::Manual, "2025-01-02T11:00:00Z"),
+        )
+        .unwrap();
+        assert!(refine_exit_fees(&path, "sig_out", 5_000).unwrap());
+        assert!(!refine_exit_fees(&path, "unknown", 5_000).unwrap());
+
+        let trades = load_trades(&path).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].exit_fees.priority_fee_lamports, 5_000);
+        assert!(trades[0].exit_fees.confirmed);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+}
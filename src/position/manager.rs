@@ -3,14 +3,32 @@
 //! Tracks open positions and provides P&L calculation.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use crate::config::SafetyConfig;
+use crate::config::{EntryTypeThresholds, SafetyConfig};
 use crate::error::{Error, Result};
+use crate::filter::types::TokenDistribution;
+use crate::notify::{Notification, Notifier};
+use crate::position::fees::TradeFees;
+use crate::position::history::{self, ClosedTrade, ExitDetails, SellFill, TradeExitReason};
+use crate::position::reconcile::{self, OrphanHolding, ReconciliationReport};
+use crate::sol_price::{usd_or_sol, SolUsdPrice};
+use crate::strategy::creator_privileges::CreatorPrivileges;
+use crate::strategy::liquidity::ExitLiquidity;
+use crate::strategy::portfolio_risk::trading_date;
+use crate::strategy::scale_in::ScaleInPlan;
+use crate::strategy::sizing::EquitySnapshot;
+use crate::strategy::slippage::SlippageEstimate;
+use crate::strategy::types::{ExitStyle, RegimeKind};
+use crate::trading::locks::{MintGuard, MintLocks};
+use crate::wallet::safety::SafetyEnforcer;
 
 /// Entry recommendation that led to opening this position
 /// Used for context-aware auto-sell strategies
@@ -25,6 +43,10 @@ pub enum EntryType {
     Probe,
     /// Legacy entry (before entry type tracking)
     Legacy,
+    /// Copy of a tracked wallet's buy
+    CopyTrade,
+    /// Bought in the PumpSwap pool after the bonding curve completed
+    PostMigration,
 }
 
 impl Default for EntryType {
@@ -50,9 +72,21 @@ impl EntryType {
         }
     }
 
+    /// Map an adaptive filter recommendation to entry type
+    /// Non-trading recommendations have no entry tier (Legacy)
+    pub fn from_recommendation(recommendation: crate::filter::scoring::Recommendation) -> Self {
+        use crate::filter::scoring::Recommendation;
+        match recommendation {
+            Recommendation::StrongBuy => EntryType::StrongBuy,
+            Recommendation::Opportunity => EntryType::Opportunity,
+            Recommendation::Probe => EntryType::Probe,
+            Recommendation::Observe | Recommendation::Avoid => EntryType::Legacy,
+        }
+    }
+
     /// Get adjusted stop loss for elite wallet entries
     /// Elite wallets tend to re-enter quickly, so use tighter stops
-    pub fn stop_loss_pct_for_elite(&self, is_elite: bool) -> f64 {
+    pub fn stop_loss_pct_for_elite(&self, is_elite: bool, thresholds: &EntryTypeThresholds) -> f64 {
         if is_elite {
             // Tighter stops for elite entries - they'll re-enter if needed
             match self {
@@ -60,57 +94,71 @@ impl EntryType {
                 EntryType::Opportunity => 12.0, // Was 15% - now 12%
                 EntryType::Probe => 8.0,        // Was 12% - now 8%
                 EntryType::Legacy => 12.0,
+                EntryType::CopyTrade => 12.0,
+                EntryType::PostMigration => 12.0,
             }
         } else {
-            self.stop_loss_pct()
+            thresholds.get(*self).stop_loss_pct
         }
     }
 
-    /// Get the take profit target for this entry type
-    /// DATA-DRIVEN: Lowered for realistic 2-minute holds
-    pub fn take_profit_pct(&self) -> f64 {
-        match self {
-            EntryType::StrongBuy => 15.0,   // Was 100% - now 15% realistic
-            EntryType::Opportunity => 10.0, // Was 50% - now 10% for quick profit
-            EntryType::Probe => 8.0,        // Was 25% - now 8% quick scalp
-            EntryType::Legacy => 10.0,      // Default
-        }
+    /// Should use tiered exit strategy?
+    pub fn use_tiered_exit(&self) -> bool {
+        matches!(self, EntryType::StrongBuy)
     }
 
-    /// Get the QUICK profit level - exit 50% of position at this level
-    /// This secures profits early before potential dump
-    pub fn quick_profit_pct(&self) -> f64 {
+    /// Config key (`strategy.exits.entry_type_styles`)
+    pub fn as_str(&self) -> &'static str {
         match self {
-            EntryType::StrongBuy => 8.0,   // Take 50% off at 8% profit
-            EntryType::Opportunity => 5.0, // Take 50% off at 5% profit
-            EntryType::Probe => 4.0,       // Take 50% off at 4% profit (very quick)
-            EntryType::Legacy => 5.0,      // Default
+            EntryType::StrongBuy => "strong_buy",
+            EntryType::Opportunity => "opportunity",
+            EntryType::Probe => "probe",
+            EntryType::Legacy => "legacy",
+            EntryType::CopyTrade => "copy_trade",
+            EntryType::PostMigration => "post_migration",
         }
     }
+}
 
-    /// Get the stop loss threshold for this entry type
-    /// WIDENED: Give trades more room to breathe
-    pub fn stop_loss_pct(&self) -> f64 {
-        match self {
-            EntryType::StrongBuy => 15.0,   // Widened from 10% to 15%
-            EntryType::Opportunity => 15.0, // Widened from 12% to 15%
-            EntryType::Probe => 12.0,       // Widened from 10% to 12%
-            EntryType::Legacy => 15.0,      // Widened from 12% to 15%
-        }
-    }
+/// Manual exit parameters set while the bot is running
+///
+/// Any value set here takes precedence over the configured entry type
+/// thresholds in the position monitor.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExitOverride {
+    /// Stop loss % below entry
+    pub stop_loss_pct: Option<f64>,
+    /// Take profit % above entry
+    pub take_profit_pct: Option<f64>,
+    /// Trailing stop distance % from peak
+    pub trailing_pct: Option<f64>,
+    /// Suspend automatic exits (except kill-switch) until this time
+    pub hold_until: Option<chrono::DateTime<chrono::Utc>>,
+}
 
-    /// Get the max hold time in seconds for this entry type
-    /// DISABLED: Time-based exits were causing exits right before price spikes.
-    /// Let the trailing stop and take-profit do their job instead.
-    pub fn max_hold_secs(&self) -> Option<u64> {
-        // Return None for all entry types - rely on trailing stop and TP/SL instead
-        None
+impl ExitOverride {
+    /// Check if any override is set
+    pub fn is_empty(&self) -> bool {
+        *self == ExitOverride::default()
     }
+}
 
-    /// Should use tiered exit strategy?
-    pub fn use_tiered_exit(&self) -> bool {
-        matches!(self, EntryType::StrongBuy)
-    }
+/// A single buy that contributed to a position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fill {
+    /// Tokens received
+    pub token_amount: u64,
+    /// SOL spent
+    pub cost_sol: f64,
+    /// Fill price in SOL per token
+    pub price: f64,
+    /// Transaction signature
+    pub signature: String,
+    /// Fill timestamp
+    pub time: chrono::DateTime<chrono::Utc>,
+    /// Fees paid on this buy
+    #[serde(default)]
+    pub fees: TradeFees,
 }
 
 /// A single position in a token
@@ -146,6 +194,9 @@ pub struct Position {
     /// Peak price seen since entry (for trailing stop)
     #[serde(default)]
     pub peak_price: f64,
+    /// Lowest price seen since entry (for max adverse excursion)
+    #[serde(default)]
+    pub trough_price: f64,
     /// Current price (updated by price feed)
     #[serde(skip)]
     pub current_price: f64,
@@ -158,6 +209,50 @@ pub struct Position {
     /// Wallet pubkey that holds this position (for multi-wallet support)
     #[serde(default)]
     pub wallet_pubkey: String,
+    /// Individual buys making up this position (empty for single-entry positions)
+    #[serde(default)]
+    pub fills: Vec<Fill>,
+    /// Scale-in plan if this position is being built in tranches
+    #[serde(default)]
+    pub scale_in: Option<ScaleInPlan>,
+    /// Manual exit overrides (set via `snipe position ...`)
+    #[serde(default)]
+    pub exit_override: ExitOverride,
+    /// Entry fees of the open portion (all fills)
+    #[serde(default)]
+    pub fees: TradeFees,
+    /// Buy landed well below the intended size; exits use Probe targets
+    #[serde(default)]
+    pub partial_fill: bool,
+    /// Regime the strategy engine classified at entry (None = engine off)
+    #[serde(default)]
+    pub regime: Option<RegimeKind>,
+    /// Exit style chosen at entry (None = the monitor's legacy rules)
+    #[serde(default)]
+    pub exit_style: Option<ExitStyle>,
+    /// Ladder tiers already sold (% gain)
+    #[serde(default)]
+    pub exit_levels_hit: Vec<f64>,
+    /// Opened by `snipe start --paper` with simulated fills
+    #[serde(default)]
+    pub paper: bool,
+    /// Creator privileges checked at entry; one regained later trips the kill-switch
+    #[serde(default)]
+    pub privileges: Option<CreatorPrivileges>,
+    /// Expected exit impact from the curve at entry; large exits are split
+    #[serde(default)]
+    pub exit_liquidity: Option<ExitLiquidity>,
+    /// Buy slippage chosen at entry, with the realized slippage once the
+    /// fill is checked (None = not recorded)
+    #[serde(default)]
+    pub slippage: Option<SlippageEstimate>,
+    /// Strategy profile active at entry (None = not recorded)
+    #[serde(default)]
+    pub strategy_profile: Option<String>,
+    /// Holder distribution at entry, which refreshed holder lists are
+    /// compared with (None = no holders cached at entry)
+    #[serde(default)]
+    pub holder_distribution: Option<TokenDistribution>,
 }
 
 impl Position {
@@ -179,10 +274,124 @@ impl Position {
         (self.unrealized_pnl() / self.total_cost_sol) * 100.0
     }
 
+    /// Unrealized P&L after entry fees
+    pub fn net_unrealized_pnl(&self) -> f64 {
+        self.unrealized_pnl() - self.fees.total_sol()
+    }
+
+    /// Unrealized P&L percentage after entry fees
+    pub fn net_unrealized_pnl_pct(&self) -> f64 {
+        if self.total_cost_sol == 0.0 {
+            return 0.0;
+        }
+        (self.net_unrealized_pnl() / self.total_cost_sol) * 100.0
+    }
+
     /// Check if position is in profit
     pub fn is_profitable(&self) -> bool {
         self.unrealized_pnl() > 0.0
     }
+
+    /// Add another buy to this position
+    ///
+    /// Entry price becomes the token-weighted average of the remaining
+    /// position and the new fill; cost is cumulative.
+    pub fn add_fill(&mut self, fill: Fill) {
+        self.record_entry_fill();
+
+        let held = self.token_amount as f64;
+        let added = fill.token_amount as f64;
+        if held + added > 0.0 {
+            self.entry_price = (self.entry_price * held + fill.price * added) / (held + added);
+        }
+        self.token_amount += fill.token_amount;
+        self.total_cost_sol += fill.cost_sol;
+        self.fees.add(&fill.fees);
+        self.fills.push(fill);
+    }
+
+    /// Record the original entry as the first fill, so the fill history is
+    /// complete (no-op once there are fills)
+    fn record_entry_fill(&mut self) {
+        if self.fills.is_empty() && self.token_amount > 0 {
+            self.fills.push(Fill {
+                token_amount: self.token_amount,
+                cost_sol: self.total_cost_sol,
+                price: self.entry_price,
+                signature: self.entry_signature.clone(),
+                time: self.entry_time,
+                fees: self.fees.clone(),
+            });
+        }
+    }
+
+    /// No part of the buys in `fills` has been sold yet
+    fn is_whole(&self) -> bool {
+        let bought: f64 = self.fills.iter().map(|f| f.cost_sol).sum();
+        self.fills.is_empty() || (bought - self.total_cost_sol).abs() <= 1e-9 * bought.max(1.0)
+    }
+
+    /// Number of buys that built this position
+    pub fn fill_count(&self) -> usize {
+        self.fills.len().max(1)
+    }
+
+    /// Entry type whose exit targets apply (Probe for partial fills)
+    pub fn exit_profile(&self) -> EntryType {
+        if self.partial_fill {
+            EntryType::Probe
+        } else {
+            self.entry_type
+        }
+    }
+
+    /// Flag the position as a partial fill if `token_amount` is below
+    /// `min_fill_ratio` of `intended_tokens`
+    pub fn check_partial_fill(&mut self, intended_tokens: u64, min_fill_ratio: f64) -> bool {
+        if intended_tokens == 0 {
+            return self.partial_fill;
+        }
+        let ratio = self.token_amount as f64 / intended_tokens as f64;
+        if ratio < min_fill_ratio {
+            warn!(
+                "PARTIAL FILL: {} intended {} tokens, got {} ({:.0}% of intended, {:.4} SOL position) - using Probe exits",
+                self.symbol,
+                intended_tokens,
+                self.token_amount,
+                ratio * 100.0,
+                self.total_cost_sol
+            );
+            self.partial_fill = true;
+        }
+        self.partial_fill
+    }
+
+    /// Stop loss %, honouring any manual override
+    pub fn effective_stop_loss_pct(&self, thresholds: &EntryTypeThresholds) -> f64 {
+        self.exit_override
+            .stop_loss_pct
+            .unwrap_or_else(|| thresholds.get(self.exit_profile()).stop_loss_pct)
+    }
+
+    /// Take profit %, honouring any manual override
+    pub fn effective_take_profit_pct(&self, thresholds: &EntryTypeThresholds) -> f64 {
+        self.exit_override
+            .take_profit_pct
+            .unwrap_or_else(|| thresholds.get(self.exit_profile()).take_profit_pct)
+    }
+
+    /// Trailing stop %, honouring any manual override
+    pub fn effective_trailing_pct(&self, default_pct: f64) -> f64 {
+        self.exit_override.trailing_pct.unwrap_or(default_pct)
+    }
+
+    /// Check if automatic exits are suspended by a manual hold
+    pub fn is_held(&self) -> bool {
+        self.exit_override
+            .hold_until
+            .map(|until| chrono::Utc::now() < until)
+            .unwrap_or(false)
+    }
 }
 
 /// Daily trading statistics
@@ -199,16 +408,41 @@ pub struct DailyStats {
     pub realized_profit_pending_extraction: f64,
     /// Total profits extracted to vault today
     pub extracted_today_sol: f64,
+    /// Positions opened today
+    #[serde(default)]
+    pub entries: u32,
+    /// Buy and sell transactions sent today (entries, scale-in tranches, sells)
+    #[serde(default)]
+    pub transactions: u32,
 }
 
 impl DailyStats {
     pub fn new() -> Self {
+        Self::for_offset(0)
+    }
+
+    /// Stats for today, with the day starting at midnight in this UTC offset
+    pub fn for_offset(utc_offset_hours: i32) -> Self {
         Self {
-            date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+            date: trading_date(chrono::Utc::now(), utc_offset_hours),
             ..Default::default()
         }
     }
 
+    /// Start a new day if the date in this UTC offset has changed; profits
+    /// not yet extracted carry over. Returns true if it rolled over.
+    pub fn roll_over(&mut self, utc_offset_hours: i32) -> bool {
+        let today = Self::for_offset(utc_offset_hours);
+        if self.date == today.date {
+            return false;
+        }
+        *self = Self {
+            realized_profit_pending_extraction: self.realized_profit_pending_extraction,
+            ..today
+        };
+        true
+    }
+
     pub fn record_trade(&mut self, pnl_sol: f64) {
         self.total_trades += 1;
         if pnl_sol >= 0.0 {
@@ -243,12 +477,133 @@ impl DailyStats {
     }
 }
 
+/// Slots and SOL held by buys that are in flight
+#[derive(Debug, Default)]
+struct Reservations {
+    count: usize,
+    sol: f64,
+}
+
+/// Position slot held while a buy is in flight
+///
+/// Counts against max positions and exposure until it is committed with
+/// the resulting position, or released (explicitly or on drop) if the buy
+/// failed.
+pub struct SlotReservation<'a> {
+    manager: &'a PositionManager,
+    size_sol: f64,
+    /// Position slots held (0 when adding to an open position)
+    slots: usize,
+    active: bool,
+}
+
+impl SlotReservation<'_> {
+    /// SOL reserved for this buy
+    pub fn size_sol(&self) -> f64 {
+        self.size_sol
+    }
+
+    /// Open the position bought with this slot
+    ///
+    /// Limits were checked at reservation time, so they are not re-checked.
+    pub async fn commit(self, position: Position) -> Result<()> {
+        self.commit_scored(position, None).await
+    }
+
+    /// `commit`, with the entry's score summary for the notification
+    pub async fn commit_scored(mut self, position: Position, score: Option<String>) -> Result<()> {
+        let mint = position.mint.clone();
+        let opened = Notification::PositionOpened {
+            symbol: position.symbol.clone(),
+            mint: mint.clone(),
+            size_sol: position.total_cost_sol,
+            entry_type: position.entry_type,
+            score,
+        };
+        let mut positions = self.manager.positions.write().await;
+        positions.insert(mint.clone(), position);
+        // Hand over the slot while the write lock is still held
+        self.manager.release_reservation(self.slots, self.size_sol);
+        self.active = false;
+        drop(positions);
+
+        info!("Opened position in {}", mint);
+        self.manager.notifier.notify(opened);
+        let mut stats = self.manager.today_stats().await;
+        self.manager.count_transaction(&mut stats, true);
+        drop(stats);
+        self.manager.save().await
+    }
+
+    /// Give the slot back (buy failed or was skipped)
+    pub fn release(mut self) {
+        self.manager.release_reservation(self.slots, self.size_sol);
+        self.active = false;
+    }
+}
+
+impl Drop for SlotReservation<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            self.manager.release_reservation(self.slots, self.size_sol);
+        }
+    }
+}
+
+/// Marks a sell of one mint as in flight until dropped
+///
+/// Manual closes and abandons refuse to touch a mint while one is held. It
+/// also holds the mint's trade lock, so no buy of the mint runs meanwhile.
+pub struct SellLock<'a> {
+    manager: &'a PositionManager,
+    mint: String,
+    _mint_lock: MintGuard,
+}
+
+impl Drop for SellLock<'_> {
+    fn drop(&mut self) {
+        self.manager
+            .sells_in_flight
+            .lock()
+            .expect("sells in flight lock poisoned")
+            .remove(&self.mint);
+    }
+}
+
+/// A close reported to `subscribe_closes` receivers
+#[derive(Debug, Clone)]
+pub struct PositionClose {
+    pub trade: ClosedTrade,
+    /// False for a partial close (the position stays open)
+    pub fully_closed: bool,
+    /// Measured sell fill, if the caller had one
+    pub fill: Option<SellFill>,
+}
+
 /// Position manager
 pub struct PositionManager {
     positions: Arc<RwLock<HashMap<String, Position>>>,
+    reservations: std::sync::Mutex<Reservations>,
     daily_stats: Arc<RwLock<DailyStats>>,
     safety_config: SafetyConfig,
+    /// Replaced by config reloads
+    exit_thresholds: std::sync::RwLock<EntryTypeThresholds>,
     persistence_path: Option<String>,
+    closes: tokio::sync::broadcast::Sender<PositionClose>,
+    /// Buys debit this wallet's daily spend cap (None = not capped)
+    spend_guard: Option<(Arc<SafetyEnforcer>, Pubkey)>,
+    /// Opens, closes, kill-switch flags and the daily loss limit are reported here
+    notifier: Notifier,
+    /// Mints with a sell being sent right now (see `SellLock`)
+    sells_in_flight: std::sync::Mutex<HashSet<String>>,
+    /// Per-mint locks every buy and sell takes before trading
+    mint_locks: MintLocks,
+    /// Set on shutdown: new buys are refused
+    entries_closed: AtomicBool,
+    /// Why new buys are paused (None = not paused)
+    entries_paused: std::sync::Mutex<Option<String>>,
+    /// Converts the `safety.*_usd` limits (None = SOL limits only)
+    sol_price: Option<Arc<SolUsdPrice>>,
 }
 
 impl PositionManager {
@@ -256,12 +611,135 @@ impl PositionManager {
     pub fn new(safety_config: SafetyConfig, persistence_path: Option<String>) -> Self {
         Self {
             positions: Arc::new(RwLock::new(HashMap::new())),
-            daily_stats: Arc::new(RwLock::new(DailyStats::new())),
+            reservations: std::sync::Mutex::new(Reservations::default()),
+            daily_stats: Arc::new(RwLock::new(DailyStats::for_offset(
+                safety_config.daily_reset_utc_offset_hours,
+            ))),
             safety_config,
+            exit_thresholds: std::sync::RwLock::new(EntryTypeThresholds::default()),
             persistence_path,
+            closes: tokio::sync::broadcast::channel(64).0,
+            spend_guard: None,
+            notifier: Notifier::disabled(),
+            sells_in_flight: std::sync::Mutex::new(HashSet::new()),
+            mint_locks: MintLocks::default(),
+            entries_closed: AtomicBool::new(false),
+            entries_paused: std::sync::Mutex::new(None),
+            sol_price: None,
         }
     }
 
+    /// Receive every full and partial close as it is recorded
+    pub fn subscribe_closes(&self) -> tokio::sync::broadcast::Receiver<PositionClose> {
+        self.closes.subscribe()
+    }
+
+    /// Use configured per-entry-type exit thresholds
+    pub fn with_exit_thresholds(self, thresholds: EntryTypeThresholds) -> Self {
+        self.set_exit_thresholds(thresholds);
+        self
+    }
+
+    /// Apply reloaded exit thresholds (open positions use them from the next check)
+    pub fn set_exit_thresholds(&self, thresholds: EntryTypeThresholds) {
+        *self.exit_thresholds.write().unwrap() = thresholds;
+    }
+
+    /// Debit every buy from `wallet`'s daily spend cap
+    pub fn with_spend_guard(mut self, safety: Arc<SafetyEnforcer>, wallet: Pubkey) -> Self {
+        self.spend_guard = Some((safety, wallet));
+        self
+    }
+
+    /// Report position events to `notifier`
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Apply `safety.max_position_usd` / `daily_loss_limit_usd` at this price
+    pub fn with_sol_price(mut self, price: Arc<SolUsdPrice>) -> Self {
+        self.sol_price = Some(price);
+        self
+    }
+
+    /// Total exposure limit in SOL, converted now when set in USD
+    pub fn max_position_sol(&self) -> f64 {
+        usd_or_sol(
+            self.sol_price.as_deref(),
+            self.safety_config.max_position_usd,
+            self.safety_config.max_position_sol,
+        )
+    }
+
+    /// Daily loss limit in SOL, converted now when set in USD
+    pub fn daily_loss_limit_sol(&self) -> f64 {
+        usd_or_sol(
+            self.sol_price.as_deref(),
+            self.safety_config.daily_loss_limit_usd,
+            self.safety_config.daily_loss_limit_sol,
+        )
+    }
+
+    /// Debit a buy from the trading wallet's daily spend cap
+    ///
+    /// `try_reserve_slot` does this itself; buys without a slot (scale-in
+    /// tranches) call it directly.
+    pub fn debit_spend(&self, amount_sol: f64) -> Result<()> {
+        self.check_entries_open()?;
+        self.debit_spend_from(None, amount_sol)
+    }
+
+    /// Refuse every new buy from now on (shutdown); sells are unaffected
+    pub fn close_entries(&self) {
+        self.entries_closed.store(true, Ordering::SeqCst);
+    }
+
+    /// Refuse new buys for `reason` until called with None; sells are unaffected
+    pub fn set_entries_paused(&self, reason: Option<String>) {
+        *self.entries_paused.lock().unwrap() = reason;
+    }
+
+    fn check_entries_open(&self) -> Result<()> {
+        if self.entries_closed.load(Ordering::SeqCst) {
+            return Err(Error::ShuttingDown);
+        }
+        if let Some(ref reason) = *self.entries_paused.lock().unwrap() {
+            return Err(Error::EntriesPaused(reason.clone()));
+        }
+        Ok(())
+    }
+
+    /// Buys holding a slot and sells holding a sell lock right now
+    pub fn in_flight(&self) -> (usize, usize) {
+        let buys = self
+            .reservations
+            .lock()
+            .expect("reservations lock poisoned")
+            .count;
+        let sells = self
+            .sells_in_flight
+            .lock()
+            .expect("sells in flight lock poisoned")
+            .len();
+        (buys, sells)
+    }
+
+    /// Debit a buy from `wallet` (None = the trading wallet)
+    fn debit_spend_from(&self, wallet: Option<&Pubkey>, amount_sol: f64) -> Result<()> {
+        let Some((safety, default_wallet)) = &self.spend_guard else {
+            return Ok(());
+        };
+        safety
+            .debit_spend(wallet.unwrap_or(default_wallet), amount_sol)
+            .map_err(|v| Error::SafetyLimitExceeded(v.to_string()))
+    }
+
+    /// Per-entry-type exit thresholds the monitors read
+    pub fn exit_thresholds(&self) -> EntryTypeThresholds {
+        self.exit_thresholds.read().unwrap().clone()
+    }
+
     /// Load positions from disk
     pub async fn load(&self) -> Result<()> {
         if let Some(path) = &self.persistence_path {
@@ -279,6 +757,17 @@ impl PositionManager {
                 info!("Loaded {} positions from {}", guard.len(), path);
             }
         }
+        if let Some(path) = self.daily_stats_path() {
+            if path.exists() {
+                let data = tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+                let mut stats: DailyStats = serde_json::from_str(&data)
+                    .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+                stats.roll_over(self.safety_config.daily_reset_utc_offset_hours);
+                *self.daily_stats.write().await = stats;
+            }
+        }
         Ok(())
     }
 
@@ -289,35 +778,123 @@ impl PositionManager {
             let data = serde_json::to_string_pretty(&*positions)
                 .map_err(|e| Error::PositionPersistence(e.to_string()))?;
 
-            tokio::fs::write(path, data)
-                .await
-                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            write_replacing(path, data).await?;
 
             debug!("Saved {} positions to {}", positions.len(), path);
         }
+        if let Some(path) = self.daily_stats_path() {
+            let data = serde_json::to_string_pretty(&*self.daily_stats.read().await)
+                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            write_replacing(path, data).await?;
+        }
         Ok(())
     }
 
     /// Open a new position
     pub async fn open_position(&self, position: Position) -> Result<()> {
-        // Check safety limits
-        self.check_risk_limits(position.total_cost_sol).await?;
+        let slot = self.try_reserve_slot(position.total_cost_sol).await?;
+        slot.commit(position).await
+    }
 
-        // Add position
-        let mint = position.mint.clone();
-        let mut positions = self.positions.write().await;
-        positions.insert(mint.clone(), position);
-        drop(positions);
+    /// Reserve a position slot before sending a buy
+    ///
+    /// Atomically checks the emergency lock, max concurrent positions, max
+    /// exposure, the daily loss limit and the daily entry and trade caps,
+    /// counting buys that are still in flight, then debits the trading
+    /// wallet's spend cap. Every entry path
+    /// must hold a reservation while its buy is pending.
+    pub async fn try_reserve_slot(&self, size_sol: f64) -> Result<SlotReservation<'_>> {
+        self.try_reserve_slot_for(size_sol, None).await
+    }
 
-        info!("Opened position in {}", mint);
+    /// `try_reserve_slot` for a buy from `wallet` (None = the trading wallet)
+    pub async fn try_reserve_slot_for(
+        &self,
+        size_sol: f64,
+        wallet: Option<&Pubkey>,
+    ) -> Result<SlotReservation<'_>> {
+        self.reserve(size_sol, wallet, true).await
+    }
 
-        // Persist
-        self.save().await?;
+    /// Reserve capital for a buy that adds to an open position (e.g. a
+    /// scale-in tranche)
+    ///
+    /// Same checks as `try_reserve_slot` except the position limit and the
+    /// daily entry cap, since no position is opened. Release it once the
+    /// fill is recorded.
+    pub async fn try_reserve_add(&self, size_sol: f64) -> Result<SlotReservation<'_>> {
+        self.reserve(size_sol, None, false).await
+    }
 
-        Ok(())
+    async fn reserve(
+        &self,
+        size_sol: f64,
+        wallet: Option<&Pubkey>,
+        new_position: bool,
+    ) -> Result<SlotReservation<'_>> {
+        self.check_entries_open()?;
+        self.check_emergency_lock()?;
+
+        // Read lock excludes concurrent commits while we check and reserve
+        let positions = self.positions.read().await;
+        let stats = self.today_stats().await;
+        let mut reserved = self.reservations.lock().expect("reservations lock poisoned");
+
+        let open = positions.len() + reserved.count;
+        if new_position && open >= self.safety_config.max_concurrent_positions {
+            return Err(Error::MaxConcurrentPositions {
+                current: open,
+                max: self.safety_config.max_concurrent_positions,
+            });
+        }
+
+        let exposure = positions.values().map(|p| p.total_cost_sol).sum::<f64>() + reserved.sol;
+        let max_position_sol = self.max_position_sol();
+        if exposure + size_sol > max_position_sol {
+            return Err(Error::MaxPositionExceeded {
+                current: exposure,
+                buy: size_sol,
+                max: max_position_sol,
+            });
+        }
+
+        let limit = self.daily_loss_limit_sol();
+        if stats.total_loss_sol >= limit {
+            return Err(Error::DailyLossLimitReached {
+                lost: stats.total_loss_sol,
+                limit,
+            });
+        }
+
+        self.check_daily_trade_limits(&stats, reserved.count, new_position)?;
+
+        self.debit_spend_from(wallet, size_sol)?;
+
+        let slots = usize::from(new_position);
+        reserved.count += slots;
+        reserved.sol += size_sol;
+        debug!(
+            "Reserved {:.4} SOL ({} open, {} in flight)",
+            size_sol,
+            positions.len(),
+            reserved.count
+        );
+
+        Ok(SlotReservation {
+            manager: self,
+            size_sol,
+            slots,
+            active: true,
+        })
+    }
+
+    fn release_reservation(&self, slots: usize, size_sol: f64) {
+        let mut reserved = self.reservations.lock().expect("reservations lock poisoned");
+        reserved.count = reserved.count.saturating_sub(slots);
+        reserved.sol = (reserved.sol - size_sol).max(0.0);
     }
 
-    /// Verify limits before sending a new buy
+    /// Verify limits before adding to an existing position (e.g. scale-in tranches)
     pub async fn can_open_position(&self, buy_amount: f64) -> Result<()> {
         self.check_risk_limits(buy_amount).await
     }
@@ -328,6 +905,18 @@ impl PositionManager {
         mint: &str,
         sold_amount: u64,
         received_sol: f64,
+    ) -> Result<f64> {
+        self.close_position_with_exit(mint, sold_amount, received_sol, ExitDetails::default())
+            .await
+    }
+
+    /// Close a position and record the exit in the trade history
+    pub async fn close_position_with_exit(
+        &self,
+        mint: &str,
+        sold_amount: u64,
+        received_sol: f64,
+        exit: ExitDetails,
     ) -> Result<f64> {
         let mut positions = self.positions.write().await;
 
@@ -335,39 +924,281 @@ impl PositionManager {
             .get_mut(mint)
             .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
 
-        // Calculate P&L for sold portion
+        // Keep the entry's own record; the open portion's fees are scaled below
+        if sold_amount < position.token_amount {
+            position.record_entry_fill();
+        }
+
+        // Calculate P&L for sold portion (gross = price only, net = after all fees)
         let sold_ratio = sold_amount as f64 / position.token_amount as f64;
         let cost_basis = position.total_cost_sol * sold_ratio;
-        let pnl = received_sol - cost_basis;
+        let gross_pnl = received_sol - cost_basis;
+        let entry_fees = position.fees.scaled(sold_ratio);
+        let pnl = gross_pnl - entry_fees.total_sol() - exit.fees.total_sol();
+        let fill = exit.fill;
+        position.fees.subtract(&entry_fees);
+
+        let mut closed = ClosedTrade {
+            mint: position.mint.clone(),
+            symbol: position.symbol.clone(),
+            entry_time: position.entry_time,
+            exit_time: chrono::Utc::now(),
+            entry_price: position.entry_price,
+            exit_price: position.current_price,
+            tokens: sold_amount,
+            cost_sol: cost_basis,
+            proceeds_sol: received_sol,
+            entry_fees,
+            exit_fees: exit.fees,
+            fees_sol: 0.0,
+            gross_pnl_sol: gross_pnl,
+            gross_pnl_pct: 0.0,
+            pnl_sol: 0.0,
+            pnl_pct: 0.0,
+            entry_type: position.entry_type,
+            exit_reason: exit.reason,
+            entry_signature: position.entry_signature.clone(),
+            exit_signature: exit.signature,
+            peak_price: 0.0,
+            trough_price: 0.0,
+            mfe_pct: 0.0,
+            mfe_sol: 0.0,
+            mae_pct: 0.0,
+            mae_sol: 0.0,
+            regime: position.regime,
+            strategy_profile: position.strategy_profile.clone(),
+        };
+        closed.recompute_pnl();
+        closed.set_excursions(position.peak_price, position.trough_price);
 
         // Update position
         position.token_amount -= sold_amount;
         position.total_cost_sol -= cost_basis;
 
         // Remove if fully closed
-        if position.token_amount == 0 {
+        let fully_closed = position.token_amount == 0;
+        if fully_closed {
             positions.remove(mint);
-            info!("Closed position in {} with P&L: {} SOL", mint, pnl);
+            info!(
+                "Closed position in {} with P&L: {} SOL net ({} SOL gross)",
+                mint, pnl, gross_pnl
+            );
         } else {
             info!(
-                "Partial close in {}, remaining: {} tokens, P&L: {} SOL",
-                mint, position.token_amount, pnl
+                "Partial close in {}, remaining: {} tokens, P&L: {} SOL net ({} SOL gross)",
+                mint, position.token_amount, pnl, gross_pnl
             );
         }
 
         drop(positions);
 
         // Update daily stats
-        let mut stats = self.daily_stats.write().await;
+        let mut stats = self.today_stats().await;
+        if closed.exit_signature.is_some() {
+            self.count_transaction(&mut stats, false);
+        }
+        let limit = self.daily_loss_limit_sol();
+        let was_below = stats.total_loss_sol < limit;
         stats.record_trade(pnl);
+        if was_below && stats.total_loss_sol >= limit {
+            self.notifier.notify(Notification::DailyLossLimit {
+                lost_sol: stats.total_loss_sol,
+                limit_sol: limit,
+            });
+        }
         drop(stats);
 
+        self.notifier.notify(Notification::PositionClosed {
+            symbol: closed.symbol.clone(),
+            mint: closed.mint.clone(),
+            pnl_sol: closed.pnl_sol,
+            pnl_pct: closed.pnl_pct,
+            reason: closed.exit_reason.to_string(),
+            fully_closed,
+        });
+
+        // No receivers is fine (nothing subscribed)
+        let _ = self.closes.send(PositionClose {
+            trade: closed.clone(),
+            fully_closed,
+            fill,
+        });
+
+        // Record in trade history (failure here must not block the close)
+        if let Some(path) = self.trade_history_path() {
+            if let Err(e) = history::append_trade(&path, closed) {
+                warn!("Failed to record closed trade for {}: {}", mint, e);
+            }
+        }
+
         // Persist
         self.save().await?;
 
         Ok(pnl)
     }
 
+    /// Path of the lock file written by `snipe wallet emergency --shutdown`
+    /// (next to positions.json)
+    pub fn emergency_lock_path(&self) -> Option<std::path::PathBuf> {
+        self.persistence_path
+            .as_ref()
+            .map(|p| Path::new(p).with_file_name("emergency.lock"))
+    }
+
+    /// Refuse new buys while the emergency lock is active (sells are unaffected)
+    fn check_emergency_lock(&self) -> Result<()> {
+        if let Some(path) = self.emergency_lock_path() {
+            if path.exists() {
+                let since = std::fs::read_to_string(&path).unwrap_or_default();
+                return Err(Error::EmergencyLockActive(format!(
+                    "since {} (snipe wallet emergency --resume to clear)",
+                    since.trim()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Path of today's stats (next to positions.json, named after it like
+    /// the trade history)
+    pub fn daily_stats_path(&self) -> Option<std::path::PathBuf> {
+        self.persistence_path.as_ref().map(|p| {
+            let path = Path::new(p);
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().replace("positions", "daily_stats"))
+                .filter(|name| name.contains("daily_stats"))
+                .unwrap_or_else(|| "daily_stats.json".to_string());
+            path.with_file_name(name)
+        })
+    }
+
+    /// Path of the closed trade history (next to positions.json, named after
+    /// it: paper_positions.json keeps paper_trades.json)
+    pub fn trade_history_path(&self) -> Option<std::path::PathBuf> {
+        self.persistence_path.as_ref().map(|p| {
+            let path = Path::new(p);
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().replace("positions", "trades"))
+                .filter(|name| name.contains("trades"))
+                .unwrap_or_else(|| "trades.json".to_string());
+            path.with_file_name(name)
+        })
+    }
+
+    /// Replace the estimated network fee of a buy or sell with the confirmed one
+    ///
+    /// Looks for the signature among open positions (entry or scale-in fill)
+    /// first, then among closed trades. Returns false if it wasn't found.
+    /// Once part of a position was sold, only the buy's own fill record is
+    /// refined; the open portion keeps its (scaled) estimate.
+    pub async fn refine_fees(&self, signature: &str, network_fee_lamports: u64) -> Result<bool> {
+        let mut found = false;
+        {
+            let mut positions = self.positions.write().await;
+            for position in positions.values_mut() {
+                let whole = position.is_whole();
+                if let Some(fill) = position.fills.iter_mut().find(|f| f.signature == signature) {
+                    let estimated = fill.fees.priority_fee_lamports;
+                    fill.fees.refine(network_fee_lamports);
+                    if whole {
+                        position.fees.priority_fee_lamports = (position.fees.priority_fee_lamports
+                            + network_fee_lamports)
+                            .saturating_sub(estimated);
+                    }
+                    found = true;
+                } else if position.fills.is_empty() && position.entry_signature == signature {
+                    position.fees.refine(network_fee_lamports);
+                    found = true;
+                }
+                if found {
+                    debug!(
+                        "Refined fee for {} ({}): {} lamports",
+                        position.symbol, signature, network_fee_lamports
+                    );
+                    break;
+                }
+            }
+        }
+        if found {
+            self.save().await?;
+            return Ok(true);
+        }
+
+        match self.trade_history_path() {
+            Some(path) => history::refine_exit_fees(&path, signature, network_fee_lamports),
+            None => Ok(false),
+        }
+    }
+
+    /// Mark a sell of `mint` as in flight (None = one already is, or
+    /// another trade holds the mint's lock)
+    pub fn try_lock_sell(&self, mint: &str) -> Option<SellLock<'_>> {
+        let mut in_flight = self
+            .sells_in_flight
+            .lock()
+            .expect("sells in flight lock poisoned");
+        if in_flight.contains(mint) {
+            return None;
+        }
+        let mint_lock = self.mint_locks.try_acquire(mint, "sell")?;
+        in_flight.insert(mint.to_string());
+        Some(SellLock {
+            manager: self,
+            mint: mint.to_string(),
+            _mint_lock: mint_lock,
+        })
+    }
+
+    /// Take `mint`'s trade lock for a buy by `holder` ("scale-in", ...),
+    /// waiting up to the lock timeout
+    ///
+    /// Only for spawned tasks: an event loop that waits here stalls every
+    /// other event behind one busy mint. Check for an existing position only
+    /// once it is held.
+    pub async fn lock_mint(&self, mint: &str, holder: &'static str) -> Result<MintGuard> {
+        self.mint_locks.acquire(mint, holder).await
+    }
+
+    /// Take `mint`'s trade lock for a buy by `holder` ("new-token",
+    /// "copy-trade", ...) if nobody holds it (None = skip the event)
+    pub fn try_lock_mint(&self, mint: &str, holder: &'static str) -> Option<MintGuard> {
+        self.mint_locks.try_acquire(mint, holder)
+    }
+
+    /// Path trading `mint` right now, if any
+    pub fn mint_lock_holder(&self, mint: &str) -> Option<&'static str> {
+        self.mint_locks.holder(mint)
+    }
+
+    /// Whether a sell of `mint` is being sent right now
+    pub fn is_sell_in_flight(&self, mint: &str) -> bool {
+        self.sells_in_flight
+            .lock()
+            .expect("sells in flight lock poisoned")
+            .contains(mint)
+    }
+
+    /// Close a position that can't be sold with zero proceeds, recording the
+    /// full cost as a loss (exit reason Abandoned)
+    pub async fn abandon_with_loss(&self, mint: &str) -> Result<f64> {
+        let _lock = self
+            .try_lock_sell(mint)
+            .ok_or_else(|| Error::SellInFlight(mint.to_string()))?;
+        let position = self
+            .get_position(mint)
+            .await
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        self.close_position_with_exit(
+            mint,
+            position.token_amount,
+            0.0,
+            ExitDetails::new(TradeExitReason::Abandoned),
+        )
+        .await
+    }
+
     /// Remove a position without affecting daily stats (e.g., when a fill never landed)
     pub async fn abandon_position(&self, mint: &str) -> Result<()> {
         let mut positions = self.positions.write().await;
@@ -379,6 +1210,273 @@ impl PositionManager {
         Ok(())
     }
 
+    /// Add a fill (e.g. a scale-in tranche) to an existing position
+    pub async fn add_fill(&self, mint: &str, fill: Fill) -> Result<()> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        position.add_fill(fill);
+        info!(
+            "Added fill to {}: {} tokens, avg entry {:.10}, cost {:.4} SOL ({} fills)",
+            mint,
+            position.token_amount,
+            position.entry_price,
+            position.total_cost_sol,
+            position.fill_count()
+        );
+        drop(positions);
+        let mut stats = self.today_stats().await;
+        self.count_transaction(&mut stats, false);
+        drop(stats);
+        self.save().await
+    }
+
+    /// Apply a change to a position's scale-in plan
+    pub async fn update_scale_in<F>(&self, mint: &str, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut ScaleInPlan),
+    {
+        let mut positions = self.positions.write().await;
+        if let Some(plan) = positions.get_mut(mint).and_then(|p| p.scale_in.as_mut()) {
+            f(plan);
+        }
+        drop(positions);
+        self.save().await
+    }
+
+    /// Attach a scale-in plan unless the position already has one in progress
+    ///
+    /// Returns false if the position is gone or its current plan isn't complete.
+    pub async fn start_scale_in(&self, mint: &str, plan: ScaleInPlan) -> Result<bool> {
+        let mut positions = self.positions.write().await;
+        let started = match positions.get_mut(mint) {
+            Some(position)
+                if position
+                    .scale_in
+                    .as_ref()
+                    .map(|plan| plan.is_complete())
+                    .unwrap_or(true) =>
+            {
+                position.scale_in = Some(plan);
+                true
+            }
+            _ => false,
+        };
+        drop(positions);
+        if started {
+            self.save().await?;
+        }
+        Ok(started)
+    }
+
+    /// Cancel remaining scale-in tranches for a position
+    pub async fn cancel_scale_in(&self, mint: &str, reason: &str) -> Result<()> {
+        let mut positions = self.positions.write().await;
+        let cancelled = match positions.get_mut(mint).and_then(|p| p.scale_in.as_mut()) {
+            Some(plan) if !plan.is_complete() => {
+                plan.cancel_remaining(reason);
+                true
+            }
+            _ => false,
+        };
+        drop(positions);
+        if cancelled {
+            info!("Cancelled remaining scale-in tranches for {}: {}", mint, reason);
+            self.save().await?;
+        }
+        Ok(())
+    }
+
+    /// Set manual exit thresholds for a position (None leaves a value unchanged)
+    pub async fn set_exit_override(
+        &self,
+        mint: &str,
+        stop_loss_pct: Option<f64>,
+        take_profit_pct: Option<f64>,
+        trailing_pct: Option<f64>,
+    ) -> Result<ExitOverride> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        if stop_loss_pct.is_some() {
+            position.exit_override.stop_loss_pct = stop_loss_pct;
+        }
+        if take_profit_pct.is_some() {
+            position.exit_override.take_profit_pct = take_profit_pct;
+        }
+        if trailing_pct.is_some() {
+            position.exit_override.trailing_pct = trailing_pct;
+        }
+        let updated = position.exit_override.clone();
+        info!("Exit override for {}: {:?}", position.symbol, updated);
+        drop(positions);
+        self.save().await?;
+        Ok(updated)
+    }
+
+    /// Replace a position's exit style (e.g. a tightened trail)
+    pub async fn set_exit_style(&self, mint: &str, style: ExitStyle) -> Result<()> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        position.exit_style = Some(style);
+        drop(positions);
+        self.save().await
+    }
+
+    /// Record a ladder tier as sold
+    pub async fn mark_exit_level_hit(&self, mint: &str, level: f64) -> Result<()> {
+        let mut positions = self.positions.write().await;
+        if let Some(position) = positions.get_mut(mint) {
+            position.exit_levels_hit.push(level);
+        }
+        drop(positions);
+        self.save().await
+    }
+
+    /// Suspend automatic exits for a position for `secs` seconds
+    pub async fn hold_position(&self, mint: &str, secs: u64) -> Result<ExitOverride> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        position.exit_override.hold_until =
+            Some(chrono::Utc::now() + chrono::Duration::seconds(secs as i64));
+        let updated = position.exit_override.clone();
+        info!("Holding {} for {}s (auto-exits suspended)", position.symbol, secs);
+        drop(positions);
+        self.save().await?;
+        Ok(updated)
+    }
+
+    /// Path of the last reconciliation report (next to positions.json)
+    pub fn reconciliation_report_path(&self) -> Option<std::path::PathBuf> {
+        self.persistence_path
+            .as_ref()
+            .map(|p| Path::new(p).with_file_name("reconciliation.json"))
+    }
+
+    /// Compare tracked positions with token balances held by `wallet`
+    ///
+    /// Orphans (held but untracked) are adopted as Legacy positions when
+    /// `adopt_orphans` is set, otherwise only reported. Ghosts (tracked but
+    /// zero balance) are always only reported. The report is persisted for
+    /// `snipe status`.
+    pub async fn reconcile_with_chain(
+        &self,
+        rpc: &RpcClient,
+        wallet: &Pubkey,
+        adopt_orphans: bool,
+    ) -> Result<ReconciliationReport> {
+        let holdings = reconcile::fetch_holdings(rpc, wallet)?;
+        let wallet_str = wallet.to_string();
+
+        // Only positions held by this wallet (empty = pre multi-wallet)
+        let tracked: Vec<String> = self
+            .positions
+            .read()
+            .await
+            .values()
+            .filter(|p| p.wallet_pubkey.is_empty() || p.wallet_pubkey == wallet_str)
+            .map(|p| p.mint.clone())
+            .collect();
+
+        let (matched, mut orphan_holdings, ghosts) =
+            reconcile::diff_holdings(&tracked, &holdings);
+
+        // Never adopt over a position tracked under another wallet
+        {
+            let positions = self.positions.read().await;
+            orphan_holdings.retain(|h| !positions.contains_key(&h.mint));
+        }
+
+        let mut orphans = Vec::with_capacity(orphan_holdings.len());
+        for holding in orphan_holdings {
+            let entry = reconcile::recover_entry(rpc, wallet, &holding);
+            let adopted = adopt_orphans;
+            if adopt_orphans {
+                let (entry_price, total_cost_sol, entry_time, entry_signature) = match &entry {
+                    Some(e) => (e.price, e.cost_sol, e.time, e.signature.clone()),
+                    None => (0.0, 0.0, chrono::Utc::now(), String::new()),
+                };
+                let position = Position {
+                    mint: holding.mint.clone(),
+                    name: String::new(),
+                    symbol: holding.mint.chars().take(6).collect(),
+                    bonding_curve: String::new(),
+                    token_amount: holding.raw_amount,
+                    entry_price,
+                    total_cost_sol,
+                    entry_time,
+                    entry_signature,
+                    entry_type: EntryType::Legacy,
+                    quick_profit_taken: false,
+                    second_profit_taken: false,
+                    peak_price: entry_price,
+                    trough_price: entry_price,
+                    current_price: entry_price,
+                    kill_switch_triggered: false,
+                    kill_switch_reason: None,
+                    wallet_pubkey: wallet_str.clone(),
+                    fills: vec![],
+                    scale_in: None,
+                    exit_override: ExitOverride::default(),
+                    partial_fill: false,
+                    regime: None,
+                    exit_style: None,
+                    exit_levels_hit: vec![],
+                    paper: false,
+                    privileges: None,
+                    exit_liquidity: None,
+                    slippage: None,
+                    strategy_profile: None,
+                    holder_distribution: None,
+                    fees: TradeFees::default(),
+                };
+                // Adoption bypasses risk limits: the tokens are already held
+                self.positions
+                    .write()
+                    .await
+                    .insert(holding.mint.clone(), position);
+                info!(
+                    "Adopted orphan {} ({:.2} tokens, entry {})",
+                    holding.mint,
+                    holding.ui_amount(),
+                    entry
+                        .as_ref()
+                        .map(|e| format!("{:.10} SOL", e.price))
+                        .unwrap_or_else(|| "unknown".to_string())
+                );
+            }
+            orphans.push(OrphanHolding {
+                holding,
+                entry,
+                adopted,
+            });
+        }
+
+        if adopt_orphans && !orphans.is_empty() {
+            self.save().await?;
+        }
+
+        let report = ReconciliationReport {
+            checked_at: Some(chrono::Utc::now()),
+            wallet: wallet_str,
+            matched,
+            orphans,
+            ghosts,
+        };
+
+        if let Some(path) = self.reconciliation_report_path() {
+            report.save(&path)?;
+        }
+
+        Ok(report)
+    }
+
     /// Update current price for a position and track peak price
     pub async fn update_price(&self, mint: &str, price: f64) {
         let mut positions = self.positions.write().await;
@@ -388,6 +1486,9 @@ impl PositionManager {
             if price > position.peak_price {
                 position.peak_price = price;
             }
+            if price > 0.0 && (position.trough_price <= 0.0 || price < position.trough_price) {
+                position.trough_price = price;
+            }
         }
     }
 
@@ -415,6 +1516,13 @@ impl PositionManager {
     pub async fn trigger_kill_switch(&self, mint: &str, reason: &str) -> Result<()> {
         let mut positions = self.positions.write().await;
         if let Some(position) = positions.get_mut(mint) {
+            if !position.kill_switch_triggered {
+                self.notifier.notify(Notification::KillSwitch {
+                    symbol: position.symbol.clone(),
+                    mint: mint.to_string(),
+                    reason: reason.to_string(),
+                });
+            }
             position.kill_switch_triggered = true;
             position.kill_switch_reason = Some(reason.to_string());
             info!(
@@ -459,6 +1567,57 @@ impl PositionManager {
         self.save().await
     }
 
+    /// Record the token amount a buy actually landed
+    ///
+    /// Returns true if the fill is below `min_fill_ratio` of the intended
+    /// size, in which case the position is flagged as a partial fill.
+    pub async fn record_actual_fill(
+        &self,
+        mint: &str,
+        actual_amount: u64,
+        intended_amount: u64,
+        min_fill_ratio: f64,
+    ) -> Result<bool> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        if position.token_amount != actual_amount {
+            info!(
+                "Updated {} token amount: {} -> {} (actual fill)",
+                mint, position.token_amount, actual_amount
+            );
+            position.token_amount = actual_amount;
+        }
+        let partial = position.check_partial_fill(intended_amount, min_fill_ratio);
+        drop(positions);
+
+        self.save().await?;
+        Ok(partial)
+    }
+
+    /// Check the raw tokens a buy received against its slippage estimate;
+    /// returns the estimate with the realized slippage
+    pub async fn record_fill_slippage(
+        &self,
+        mint: &str,
+        raw_tokens: u64,
+    ) -> Result<Option<SlippageEstimate>> {
+        let mut positions = self.positions.write().await;
+        let position = positions
+            .get_mut(mint)
+            .ok_or_else(|| Error::PositionNotFound(mint.to_string()))?;
+        let Some(slippage) = position.slippage.as_mut() else {
+            return Ok(None);
+        };
+        slippage.record_fill(raw_tokens);
+        let slippage = *slippage;
+        drop(positions);
+
+        self.save().await?;
+        Ok(Some(slippage))
+    }
+
     /// Get a position by mint
     pub async fn get_position(&self, mint: &str) -> Option<Position> {
         let positions = self.positions.read().await;
@@ -477,6 +1636,32 @@ impl PositionManager {
         positions.values().map(|p| p.total_cost_sol).sum()
     }
 
+    /// Hot-wallet equity for bankroll sizing: `balance_sol` plus open
+    /// positions at their last price (cost until priced), with today's
+    /// realized loss against the daily limit
+    pub async fn equity_snapshot(&self, balance_sol: f64) -> EquitySnapshot {
+        let positions_value_sol = self
+            .positions
+            .read()
+            .await
+            .values()
+            .map(|p| {
+                if p.current_price > 0.0 {
+                    p.current_value()
+                } else {
+                    p.total_cost_sol
+                }
+            })
+            .sum();
+        let daily_loss_sol = self.today_stats().await.total_loss_sol;
+        EquitySnapshot {
+            balance_sol,
+            positions_value_sol,
+            daily_loss_sol,
+            daily_loss_limit_sol: self.daily_loss_limit_sol(),
+        }
+    }
+
     /// Get total unrealized P&L
     pub async fn total_unrealized_pnl(&self) -> f64 {
         let positions = self.positions.read().await;
@@ -485,7 +1670,7 @@ impl PositionManager {
 
     /// Get daily statistics
     pub async fn get_daily_stats(&self) -> DailyStats {
-        self.daily_stats.read().await.clone()
+        self.today_stats().await.clone()
     }
 
     /// Get realized profits pending extraction
@@ -502,26 +1687,101 @@ impl PositionManager {
 
     /// Check if daily loss limit is reached
     pub async fn is_daily_loss_limit_reached(&self) -> bool {
-        let stats = self.daily_stats.read().await;
-        stats.total_loss_sol >= self.safety_config.daily_loss_limit_sol
+        let stats = self.today_stats().await;
+        stats.total_loss_sol >= self.daily_loss_limit_sol()
     }
 
     /// Get remaining capacity for new positions
     pub async fn remaining_position_capacity(&self) -> f64 {
         let total = self.total_position_value().await;
-        (self.safety_config.max_position_sol - total).max(0.0)
+        (self.max_position_sol() - total).max(0.0)
     }
 
     /// Get daily loss remaining before limit
     pub async fn remaining_daily_loss(&self) -> f64 {
-        let stats = self.daily_stats.read().await;
-        (self.safety_config.daily_loss_limit_sol - stats.total_loss_sol).max(0.0)
+        let stats = self.today_stats().await;
+        (self.daily_loss_limit_sol() - stats.total_loss_sol).max(0.0)
+    }
+
+    /// Today's stats, rolled over first if the day has changed
+    async fn today_stats(&self) -> tokio::sync::RwLockWriteGuard<'_, DailyStats> {
+        let mut stats = self.daily_stats.write().await;
+        if stats.roll_over(self.safety_config.daily_reset_utc_offset_hours) {
+            info!("Daily stats reset for {}", stats.date);
+        }
+        stats
+    }
+
+    /// Count a sent transaction (`entry` = it opened a position) toward
+    /// today's caps, reporting a cap the first time it is reached
+    fn count_transaction(&self, stats: &mut DailyStats, entry: bool) {
+        if entry {
+            stats.entries += 1;
+        }
+        stats.transactions += 1;
+        let reached = [
+            (
+                "entry",
+                self.safety_config.max_daily_entries.filter(|_| entry),
+                stats.entries,
+            ),
+            (
+                "trade",
+                self.safety_config.max_daily_trades,
+                stats.transactions,
+            ),
+        ];
+        for (kind, limit, count) in reached {
+            if limit == Some(count) {
+                warn!(
+                    "==== DAILY {} LIMIT REACHED: {} today - new buys paused until UTC midnight, sells continue ====",
+                    kind.to_uppercase(),
+                    count
+                );
+                self.notifier.notify(Notification::DailyTradeLimit {
+                    kind: kind.to_string(),
+                    count,
+                    limit: count,
+                });
+            }
+        }
+    }
+
+    /// Refuse a buy at the daily trade cap, or at the entry cap when it
+    /// opens a position; `in_flight` reserved buys count toward both
+    fn check_daily_trade_limits(
+        &self,
+        stats: &DailyStats,
+        in_flight: usize,
+        new_position: bool,
+    ) -> Result<()> {
+        let caps = [
+            (
+                "entry",
+                self.safety_config
+                    .max_daily_entries
+                    .filter(|_| new_position),
+                stats.entries,
+            ),
+            (
+                "trade",
+                self.safety_config.max_daily_trades,
+                stats.transactions,
+            ),
+        ];
+        for (kind, limit, count) in caps {
+            let count = count + in_flight as u32;
+            if let Some(limit) = limit.filter(|&limit| count >= limit) {
+                return Err(Error::DailyTradeLimitReached { kind, count, limit });
+            }
+        }
+        Ok(())
     }
 
-    /// Reset daily stats (call at UTC midnight)
+    /// Reset daily stats
     pub async fn reset_daily_stats(&self) {
         let mut stats = self.daily_stats.write().await;
-        *stats = DailyStats::new();
+        *stats = DailyStats::for_offset(self.safety_config.daily_reset_utc_offset_hours);
         info!("Daily stats reset");
     }
 
@@ -531,27 +1791,51 @@ impl PositionManager {
     }
 
     async fn check_risk_limits(&self, buy_amount: f64) -> Result<()> {
-        let total_position_value = self.total_position_value().await;
-        if total_position_value + buy_amount > self.safety_config.max_position_sol {
+        self.check_emergency_lock()?;
+
+        let (reserved_count, reserved_sol) = {
+            let reserved = self
+                .reservations
+                .lock()
+                .expect("reservations lock poisoned");
+            (reserved.count, reserved.sol)
+        };
+        let total_position_value = self.total_position_value().await + reserved_sol;
+        let max_position_sol = self.max_position_sol();
+        if total_position_value + buy_amount > max_position_sol {
             return Err(Error::MaxPositionExceeded {
                 current: total_position_value,
                 buy: buy_amount,
-                max: self.safety_config.max_position_sol,
+                max: max_position_sol,
             });
         }
 
-        let stats = self.daily_stats.read().await;
-        if stats.total_loss_sol >= self.safety_config.daily_loss_limit_sol {
+        let stats = self.today_stats().await;
+        let limit = self.daily_loss_limit_sol();
+        if stats.total_loss_sol >= limit {
             return Err(Error::DailyLossLimitReached {
                 lost: stats.total_loss_sol,
-                limit: self.safety_config.daily_loss_limit_sol,
+                limit,
             });
         }
 
-        Ok(())
+        self.check_daily_trade_limits(&stats, reserved_count, false)
     }
 }
 
+/// Write to a temp file and swap it in, so a crash mid-write never
+/// truncates the state file
+async fn write_replacing(path: impl AsRef<Path>, data: String) -> Result<()> {
+    let path = path.as_ref();
+    let tmp = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp, data)
+        .await
+        .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -568,6 +1852,28 @@ mod tests {
             entry_time: chrono::Utc::now(),
             entry_signature: "test_sig".to_string(),
             current_price: 0.000000015, // 50% profit: 0.015 SOL for 1M tokens
+            entry_type: EntryType::StrongBuy,
+            quick_profit_taken: false,
+            second_profit_taken: false,
+            peak_price: 0.00000001,
+            trough_price: 0.00000001,
+            kill_switch_triggered: false,
+            kill_switch_reason: None,
+            wallet_pubkey: String::new(),
+            fills: vec![],
+            scale_in: None,
+            exit_override: ExitOverride::default(),
+            partial_fill: false,
+            regime: None,
+            exit_style: None,
+            exit_levels_hit: vec![],
+            paper: false,
+            privileges: None,
+            exit_liquidity: None,
+            slippage: None,
+            strategy_profile: None,
+            holder_distribution: None,
+            fees: TradeFees::default(),
         }
     }
 
@@ -585,6 +1891,111 @@ mod tests {
         assert!(position.is_profitable());
     }
 
+    #[test]
+    fn test_entry_type_from_recommendation() {
+        use crate::filter::scoring::Recommendation;
+        assert_eq!(
+            EntryType::from_recommendation(Recommendation::Opportunity),
+            EntryType::Opportunity
+        );
+        assert_eq!(
+            EntryType::from_recommendation(Recommendation::Probe),
+            EntryType::Probe
+        );
+        assert_eq!(
+            EntryType::from_recommendation(Recommendation::Observe),
+            EntryType::Legacy
+        );
+    }
+
+    #[test]
+    fn test_partial_fill_uses_probe_exits() {
+        let mut position = test_position();
+        position.entry_type = EntryType::StrongBuy;
+
+        // 90% fill is fine
+        assert!(!position.check_partial_fill(1_111_111, 0.8));
+        assert_eq!(position.exit_profile(), EntryType::StrongBuy);
+
+        // 50% fill drops to Probe targets
+        assert!(position.check_partial_fill(2_000_000, 0.8));
+        assert_eq!(position.exit_profile(), EntryType::Probe);
+        let thresholds = EntryTypeThresholds::default();
+        assert_eq!(
+            position.effective_take_profit_pct(&thresholds),
+            thresholds.probe.take_profit_pct
+        );
+    }
+
+    #[test]
+    fn test_add_fill_averages_entry() {
+        let mut position = test_position();
+
+        // Second tranche: 1M tokens at twice the price for 0.02 SOL
+        position.add_fill(Fill {
+            token_amount: 1_000_000,
+            cost_sol: 0.02,
+            price: 0.00000002,
+            signature: "tranche_2".to_string(),
+            time: chrono::Utc::now(),
+            fees: TradeFees::estimate(0.0001, 0, 0.02, 0.5),
+        });
+
+        assert_eq!(position.token_amount, 2_000_000);
+        assert!((position.total_cost_sol - 0.03).abs() < 1e-9);
+        assert!((position.entry_price - 0.000000015).abs() < 1e-15);
+        // Original entry is back-filled into the history
+        assert_eq!(position.fills.len(), 2);
+        assert_eq!(position.fills[0].signature, "test_sig");
+        assert_eq!(position.fees.priority_fee_lamports, 100_000);
+    }
+
+    #[tokio::test]
+    async fn test_refine_fees_after_partial_close() {
+        let manager = PositionManager::new(test_safety(4, 100.0), None);
+        let mut position = test_position();
+        position.fees = TradeFees::estimate(0.0001, 0, 0.01, 0.5);
+        manager.open_position(position).await.unwrap();
+
+        // Half sold before the entry's fee is confirmed
+        let exit = ExitDetails::new(TradeExitReason::Manual);
+        manager
+            .close_position_with_exit("test_mint", 500_000, 0.008, exit)
+            .await
+            .unwrap();
+        assert!(manager.refine_fees("test_sig", 80_000).await.unwrap());
+
+        let held = manager.get_position("test_mint").await.unwrap();
+        assert_eq!(held.fills.len(), 1);
+        assert_eq!(held.fills[0].fees.priority_fee_lamports, 80_000);
+        assert!(held.fills[0].fees.confirmed);
+        // The open half keeps half the estimate instead of the whole fee
+        assert_eq!(held.fees.priority_fee_lamports, 50_000);
+    }
+
+    #[test]
+    fn test_exit_override_precedence() {
+        let mut position = test_position();
+        let thresholds = EntryTypeThresholds::default();
+        assert_eq!(
+            position.effective_stop_loss_pct(&thresholds),
+            thresholds.strong_buy.stop_loss_pct
+        );
+
+        position.exit_override.stop_loss_pct = Some(40.0);
+        position.exit_override.trailing_pct = Some(12.0);
+        assert_eq!(position.effective_stop_loss_pct(&thresholds), 40.0);
+        assert_eq!(
+            position.effective_take_profit_pct(&thresholds),
+            thresholds.strong_buy.take_profit_pct
+        );
+        assert_eq!(position.effective_trailing_pct(5.0), 12.0);
+
+        assert!(!position.is_held());
+        position.exit_override.hold_until = Some(chrono::Utc::now() + chrono::Duration::seconds(60));
+        assert!(position.is_held());
+    }
+
     #[test]
     fn test_daily_stats() {
         let mut stats = DailyStats::new();
@@ -597,5 +2008,293 @@ mod tests {
         assert_eq!(stats.winning_trades, 2);
         assert_eq!(stats.losing_trades, 1);
         assert!((stats.win_rate() - 66.67).abs() < 0.1);
+
+        // A new day starts from zero; profits not yet extracted carry over
+        assert!(!stats.roll_over(0));
+        stats.date = "2000-01-01".to_string();
+        assert!(stats.roll_over(0));
+        assert_eq!(stats.total_trades, 0);
+        assert!((stats.pending_extraction() - 0.03).abs() < 1e-9);
+
+        // The day follows the configured offset
+        let offset = DailyStats::for_offset(14);
+        let expected = (chrono::Utc::now() + chrono::Duration::hours(14))
+            .format("%Y-%m-%d")
+            .to_string();
+        assert_eq!(offset.date, expected);
+    }
+
+    fn test_safety(max_positions: usize, max_sol: f64) -> SafetyConfig {
+        SafetyConfig {
+            max_concurrent_positions: max_positions,
+            max_position_sol: max_sol,
+            ..crate::config::Config::default().safety
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_reservations_respect_max_positions() {
+        let manager = Arc::new(PositionManager::new(test_safety(4, 100.0), None));
+        let attempts = 16;
+        let barrier = Arc::new(tokio::sync::Barrier::new(attempts));
+
+        let handles: Vec<_> = (0..attempts)
+            .map(|_| {
+                let manager = manager.clone();
+                let barrier = barrier.clone();
+                tokio::spawn(async move {
+                    let slot = manager.try_reserve_slot(0.1).await;
+                    // Keep every successful reservation alive until all attempts ran
+                    barrier.wait().await;
+                    slot.is_ok()
+                })
+            })
+            .collect();
+
+        let mut granted = 0;
+        for handle in handles {
+            if handle.await.unwrap() {
+                granted += 1;
+            }
+        }
+        assert_eq!(granted, 4);
+
+        // All guards dropped: slots are free again
+        assert!(manager.try_reserve_slot(0.1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reservation_commit_and_release() {
+        let manager = PositionManager::new(test_safety(2, 100.0), None);
+
+        let first = manager.try_reserve_slot(0.01).await.unwrap();
+        let second = manager.try_reserve_slot(0.01).await.unwrap();
+        assert!(matches!(
+            manager.try_reserve_slot(0.01).await,
+            Err(Error::MaxConcurrentPositions { current: 2, max: 2 })
+        ));
+
+        // Committed slot becomes an open position and still counts
+        first.commit(test_position()).await.unwrap();
+        assert_eq!(manager.get_all_positions().await.len(), 1);
+        assert!(manager.try_reserve_slot(0.01).await.is_err());
+
+        // Released slot frees capacity
+        second.release();
+        assert!(manager.try_reserve_slot(0.01).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_closed_entries_drain() {
+        let manager = PositionManager::new(test_safety(4, 100.0), None);
+
+        let pending = manager.try_reserve_slot(0.01).await.unwrap();
+        let selling = manager.try_lock_sell("mint").unwrap();
+        assert_eq!(manager.in_flight(), (1, 1));
+
+        manager.close_entries();
+        assert!(matches!(
+            manager.try_reserve_slot(0.01).await,
+            Err(Error::ShuttingDown)
+        ));
+        assert!(manager.debit_spend(0.01).is_err());
+
+        // Buys already in flight still land
+        pending.commit(test_position()).await.unwrap();
+        drop(selling);
+        assert_eq!(manager.in_flight(), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_reservation_counts_in_flight_exposure() {
+        let manager = PositionManager::new(test_safety(10, 0.25), None);
+
+        let _a = manager.try_reserve_slot(0.1).await.unwrap();
+        let b = manager.try_reserve_slot(0.1).await.unwrap();
+        assert!(matches!(
+            manager.try_reserve_slot(0.1).await,
+            Err(Error::MaxPositionExceeded { .. })
+        ));
+        assert!(manager.can_open_position(0.1).await.is_err());
+
+        drop(b);
+        assert!(manager.try_reserve_slot(0.1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_reservation_holds_exposure_not_slot() {
+        let manager = PositionManager::new(test_safety(1, 0.25), None);
+        manager.open_position(test_position()).await.unwrap();
+
+        // Adding to the open position is not blocked by the position limit
+        let tranche = manager.try_reserve_add(0.1).await.unwrap();
+        assert!(matches!(
+            manager.try_reserve_add(0.1).await,
+            Err(Error::MaxPositionExceeded { .. })
+        ));
+
+        tranche.release();
+        assert!(manager.try_reserve_add(0.1).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reservation_debits_spend_cap() {
+        let wallet = Pubkey::new_unique();
+        let safety = Arc::new(SafetyEnforcer::new(Default::default()));
+        safety.set_spend_cap(
+            wallet,
+            crate::wallet::safety::SpendCap {
+                wallet: "hot-trading".to_string(),
+                daily_limit_sol: 0.25,
+            },
+        );
+        let manager = PositionManager::new(test_safety(10, 100.0), None)
+            .with_spend_guard(safety.clone(), wallet);
+
+        manager.try_reserve_slot(0.1).await.unwrap().release();
+        // Released slots stay spent: the cap bounds what was sent
+        manager.try_reserve_slot(0.1).await.unwrap().release();
+        assert!(matches!(
+            manager.try_reserve_slot(0.1).await,
+            Err(Error::SafetyLimitExceeded(_))
+        ));
+        assert!(manager.debit_spend(0.05).is_ok());
+        assert!((safety.spent_today(&wallet) - 0.25).abs() < 1e-9);
+
+        // Other wallets have their own allowance
+        let other = Pubkey::new_unique();
+        assert!(manager
+            .try_reserve_slot_for(0.1, Some(&other))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_emergency_lock_blocks_buys_not_sells() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("positions.json");
+        let manager = PositionManager::new(
+            test_safety(10, 100.0),
+            Some(path.to_string_lossy().to_string()),
+        );
+        manager.open_position(test_position()).await.unwrap();
+
+        std::fs::write(manager.emergency_lock_path().unwrap(), "2024-01-01T00:00:00Z").unwrap();
+        assert!(matches!(
+            manager.try_reserve_slot(0.01).await,
+            Err(Error::EmergencyLockActive(_))
+        ));
+        assert!(manager.can_open_position(0.01).await.is_err());
+
+        // Liquidation still goes through
+        manager
+            .close_position("test_mint", 1_000_000, 0.012)
+            .await
+            .unwrap();
+        assert!(manager.get_all_positions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_daily_trade_caps_block_buys_not_sells() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("positions.json");
+        let path = Some(path.to_string_lossy().to_string());
+        let safety = SafetyConfig {
+            max_daily_entries: Some(2),
+            max_daily_trades: Some(3),
+            ..test_safety(10, 100.0)
+        };
+        let manager = PositionManager::new(safety.clone(), path.clone());
+        manager.open_position(test_position()).await.unwrap();
+
+        // A buy in flight counts toward the entry cap
+        let pending = manager.try_reserve_slot(0.01).await.unwrap();
+        assert!(matches!(
+            manager.try_reserve_slot(0.01).await,
+            Err(Error::DailyTradeLimitReached {
+                kind: "entry",
+                count: 2,
+                limit: 2
+            })
+        ));
+        let mut second = test_position();
+        second.mint = "second_mint".to_string();
+        pending.commit(second).await.unwrap();
+        // Adding to a position is a trade, not an entry
+        assert!(manager.can_open_position(0.01).await.is_ok());
+
+        // Sells go through at and past the trade cap
+        let sold = ExitDetails {
+            signature: Some("sig".to_string()),
+            ..Default::default()
+        };
+        manager
+            .close_position_with_exit("test_mint", 1_000_000, 0.012, sold.clone())
+            .await
+            .unwrap();
+        assert!(matches!(
+            manager.can_open_position(0.01).await,
+            Err(Error::DailyTradeLimitReached { kind: "trade", .. })
+        ));
+        manager
+            .close_position_with_exit("second_mint", 1_000_000, 0.012, sold)
+            .await
+            .unwrap();
+
+        // Today's counts survive a restart
+        let restarted = PositionManager::new(safety, path);
+        restarted.load().await.unwrap();
+        let stats = restarted.get_daily_stats().await;
+        assert_eq!((stats.entries, stats.transactions), (2, 4));
+        assert!(restarted.try_reserve_slot(0.01).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_closes_are_broadcast() {
+        let manager = PositionManager::new(test_safety(10, 100.0), None);
+        let mut closes = manager.subscribe_closes();
+        manager.open_position(test_position()).await.unwrap();
+
+        manager
+            .close_position("test_mint", 400_000, 0.006)
+            .await
+            .unwrap();
+        let partial = closes.try_recv().unwrap();
+        assert!(!partial.fully_closed);
+        assert_eq!(partial.trade.tokens, 400_000);
+
+        manager
+            .close_position("test_mint", 600_000, 0.009)
+            .await
+            .unwrap();
+        assert!(closes.try_recv().unwrap().fully_closed);
+    }
+
+    #[tokio::test]
+    async fn test_abandon_refused_while_selling() {
+        let manager = PositionManager::new(test_safety(10, 100.0), None);
+        manager.open_position(test_position()).await.unwrap();
+
+        let lock = manager.try_lock_sell("test_mint").unwrap();
+        assert!(manager.try_lock_sell("test_mint").is_none());
+        assert!(matches!(
+            manager.abandon_with_loss("test_mint").await,
+            Err(Error::SellInFlight(_))
+        ));
+        drop(lock);
+        assert!(!manager.is_sell_in_flight("test_mint"));
+
+        // A buy holding the mint's lock keeps sells out, and the other way round
+        let buying = manager.lock_mint("test_mint", "new-token").await.unwrap();
+        assert!(manager.try_lock_sell("test_mint").is_none());
+        drop(buying);
+        let selling = manager.try_lock_sell("test_mint").unwrap();
+        assert!(manager.try_lock_mint("test_mint", "copy-trade").is_none());
+        drop(selling);
+        assert!(manager.try_lock_mint("test_mint", "copy-trade").is_some());
+
+        let pnl = manager.abandon_with_loss("test_mint").await.unwrap();
+        assert!(pnl < 0.0);
+        assert!(manager.get_position("test_mint").await.is_none());
     }
 }
@@ -1,9 +1,29 @@
 //! Position management module
 
 pub mod auto_sell;
+pub mod concentration_watch;
+pub mod cooldowns;
+pub mod early_weakness;
+pub mod fees;
+pub mod history;
 pub mod manager;
 pub mod price_feed;
+pub mod reconcile;
+pub mod rug_watch;
+pub mod shadow;
+pub mod stats;
+pub mod status;
 
 pub use auto_sell::AutoSeller;
-pub use manager::PositionManager;
+pub use concentration_watch::ConcentrationWatch;
+pub use cooldowns::{CooldownKind, CooldownTracker};
+pub use early_weakness::{FlowSnapshot, FlowWatch};
+pub use fees::TradeFees;
+pub use history::{ClosedTrade, ExitDetails, SellFill, TradeExitReason};
+pub use manager::{PositionClose, PositionManager};
 pub use price_feed::{MonitoredToken, PriceFeed, PriceSource};
+pub use reconcile::ReconciliationReport;
+pub use rug_watch::{RugAction, RugPredictionRecord, RugWatch};
+pub use shadow::{ShadowOutcome, ShadowTracker};
+pub use stats::ExcursionStats;
+pub use status::StatusSnapshot;
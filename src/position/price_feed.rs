@@ -1,12 +1,15 @@
 //! Price feed for position monitoring
 //!
-//! Polls bonding curve accounts to get current token prices.
-//! Falls back to DexScreener API for graduated tokens.
+//! Polls bonding curve accounts (one getMultipleAccounts request per tick)
+//! to get current token prices.
+//! Falls back to the price APIs (DexScreener, Birdeye, GeckoTerminal) for
+//! graduated tokens.
 //! This is used for auto-sell (take-profit / stop-loss) triggers.
 //!
 //! WARNING: TP/SL is best-effort, not guaranteed. At 1-second polling,
 //! fast rugs can gap through your stop-loss before detection.
 
+use serde::{Deserialize, Serialize};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use std::collections::HashMap;
@@ -19,15 +22,31 @@ use tracing::{debug, info, warn};
 use crate::config::AutoSellConfig;
 use crate::dexscreener::DexScreenerClient;
 use crate::error::{Error, Result};
+use crate::price_source::{CompositePriceSource, PriceSourcesConfig};
 use crate::pump::accounts::BondingCurve;
 
-/// Token price source - bonding curve or DexScreener
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Token price source - bonding curve or a price API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PriceSource {
     /// Token is on pump.fun bonding curve
     BondingCurve,
     /// Token graduated - using DexScreener API
     DexScreener,
+    Birdeye,
+    #[serde(rename = "geckoterminal")]
+    GeckoTerminal,
+}
+
+impl std::fmt::Display for PriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            PriceSource::BondingCurve => "curve",
+            PriceSource::DexScreener => "dexscreener",
+            PriceSource::Birdeye => "birdeye",
+            PriceSource::GeckoTerminal => "geckoterminal",
+        })
+    }
 }
 
 /// Monitored token info
@@ -48,7 +67,7 @@ pub struct PriceUpdate {
 }
 
 /// Price feed that polls bonding curves for current prices
-/// Falls back to DexScreener API for graduated tokens
+/// Falls back to the price APIs for graduated tokens
 pub struct PriceFeed {
     rpc_client: Arc<RpcClient>,
     config: AutoSellConfig,
@@ -56,8 +75,8 @@ pub struct PriceFeed {
     monitored: Arc<RwLock<HashMap<Pubkey, MonitoredToken>>>,
     /// Cached prices
     prices: Arc<RwLock<HashMap<Pubkey, f64>>>,
-    /// DexScreener client for graduated tokens
-    dexscreener: Arc<DexScreenerClient>,
+    /// Price APIs for graduated tokens
+    price_sources: Arc<CompositePriceSource>,
     /// Shutdown signal
     shutdown: tokio::sync::broadcast::Sender<()>,
 }
@@ -71,11 +90,20 @@ impl PriceFeed {
             config,
             monitored: Arc::new(RwLock::new(HashMap::new())),
             prices: Arc::new(RwLock::new(HashMap::new())),
-            dexscreener: Arc::new(DexScreenerClient::new()),
+            price_sources: Arc::new(CompositePriceSource::new(
+                &PriceSourcesConfig::default(),
+                Arc::new(DexScreenerClient::new()),
+            )),
             shutdown,
         }
     }
 
+    /// Use configured price APIs (shared rate limits and caches)
+    pub fn with_price_sources(mut self, price_sources: Arc<CompositePriceSource>) -> Self {
+        self.price_sources = price_sources;
+        self
+    }
+
     /// Start the price feed polling loop
     pub async fn start(&self, update_tx: mpsc::Sender<PriceUpdate>) -> Result<()> {
         if !self.config.enabled {
@@ -84,14 +112,14 @@ impl PriceFeed {
         }
 
         info!(
-            "Starting price feed with {}ms poll interval (with DexScreener fallback)",
+            "Starting price feed with {}ms poll interval (with price API fallback)",
             self.config.price_poll_interval_ms
         );
 
         let rpc_client = self.rpc_client.clone();
         let monitored = self.monitored.clone();
         let prices = self.prices.clone();
-        let dexscreener = self.dexscreener.clone();
+        let price_sources = self.price_sources.clone();
         let poll_interval = Duration::from_millis(self.config.price_poll_interval_ms);
         let mut shutdown_rx = self.shutdown.subscribe();
 
@@ -111,45 +139,54 @@ impl PriceFeed {
                             continue;
                         }
 
+                        // One getMultipleAccounts request for every token still on its curve
+                        let curve_mints: Vec<Pubkey> = tokens
+                            .iter()
+                            .filter(|t| t.source == PriceSource::BondingCurve)
+                            .map(|t| t.mint)
+                            .collect();
+                        let mut curves: HashMap<Pubkey, Option<BondingCurve>> = match BondingCurve::fetch_many(&rpc_client, &curve_mints) {
+                            Ok(curves) => curve_mints.into_iter().zip(curves).collect(),
+                            Err(e) => {
+                                debug!("Bonding curve batch fetch failed: {}", e);
+                                HashMap::new()
+                            }
+                        };
+
                         // Poll each token using appropriate source
                         for token in tokens {
                             let price_result = match token.source {
-                                PriceSource::BondingCurve => {
-                                    // Try bonding curve first
-                                    match Self::fetch_bonding_curve_price(&rpc_client, &token.bonding_curve).await {
-                                        Ok((price, graduated)) => {
-                                            if graduated {
-                                                // Token has graduated, update source and try DexScreener
-                                                info!("Token {} graduated, switching to DexScreener", token.mint);
-                                                let mut guard = monitored.write().await;
-                                                if let Some(t) = guard.get_mut(&token.mint) {
-                                                    t.source = PriceSource::DexScreener;
-                                                }
-                                                drop(guard);
-                                                Self::fetch_dexscreener_price(&dexscreener, &token.mint).await
-                                            } else {
-                                                Ok(price)
-                                            }
-                                        }
+                                PriceSource::BondingCurve => match curves.remove(&token.mint).flatten() {
+                                    Some(curve) if curve.is_complete() => {
+                                        // Token has graduated, switch to the price APIs
+                                        info!("Token {} graduated, switching to price APIs", token.mint);
+                                        Self::fetch_api_price(&price_sources, &token.mint).await
+                                    }
+                                    Some(curve) => match curve.get_price() {
+                                        Ok(price) => Ok((price, PriceSource::BondingCurve)),
                                         Err(e) => {
-                                            // Bonding curve failed, try DexScreener
-                                            debug!("Bonding curve fetch failed for {}: {}, trying DexScreener", token.mint, e);
-                                            let mut guard = monitored.write().await;
-                                            if let Some(t) = guard.get_mut(&token.mint) {
-                                                t.source = PriceSource::DexScreener;
-                                            }
-                                            drop(guard);
-                                            Self::fetch_dexscreener_price(&dexscreener, &token.mint).await
+                                            debug!("Bad bonding curve for {}: {}, trying price APIs", token.mint, e);
+                                            Self::fetch_api_price(&price_sources, &token.mint).await
                                         }
+                                    },
+                                    None => {
+                                        // Bonding curve failed, try the price APIs
+                                        debug!("No bonding curve for {}, trying price APIs", token.mint);
+                                        Self::fetch_api_price(&price_sources, &token.mint).await
                                     }
-                                }
-                                PriceSource::DexScreener => {
-                                    Self::fetch_dexscreener_price(&dexscreener, &token.mint).await
-                                }
+                                },
+                                _ => Self::fetch_api_price(&price_sources, &token.mint).await,
                             };
 
                             match price_result {
-                                Ok(price) => {
+                                Ok((price, source)) => {
+                                    if source != token.source {
+                                        let mut guard = monitored.write().await;
+                                        if let Some(t) = guard.get_mut(&token.mint) {
+                                            t.source = source;
+                                        }
+                                    }
+
                                     // Update cache
                                     {
                                         let mut cache = prices.write().await;
@@ -203,7 +240,7 @@ impl PriceFeed {
         info!("Added {} to price feed (will auto-detect graduation)", mint);
     }
 
-    /// Add a token that's already known to be graduated
+    /// Add a token that's already known to be graduated (priced by the APIs)
     pub async fn add_graduated_token(&self, mint: Pubkey, bonding_curve: Pubkey) {
         let token = MonitoredToken {
             mint,
@@ -212,7 +249,7 @@ impl PriceFeed {
         };
         let mut monitored = self.monitored.write().await;
         monitored.insert(mint, token);
-        info!("Added {} to price feed (using DexScreener)", mint);
+        info!("Added {} to price feed (using price APIs)", mint);
     }
 
     /// Remove a token from monitoring
@@ -243,6 +280,24 @@ impl PriceFeed {
         monitored.get(mint).map(|t| t.source)
     }
 
+    /// One-off price for a token: its bonding curve, or the price APIs once
+    /// it has graduated (or the curve can't be read)
+    pub async fn fetch_price(
+        &self,
+        mint: &Pubkey,
+        bonding_curve: &Pubkey,
+    ) -> Result<(f64, PriceSource)> {
+        match Self::fetch_bonding_curve_price(&self.rpc_client, bonding_curve).await {
+            Ok((price, false)) => return Ok((price, PriceSource::BondingCurve)),
+            Ok((_, true)) => {}
+            Err(e) => debug!(
+                "Bonding curve fetch failed for {}: {}, trying price APIs",
+                mint, e
+            ),
+        }
+        Self::fetch_api_price(&self.price_sources, mint).await
+    }
+
     /// Fetch price from bonding curve, also returns whether the curve is complete (graduated)
     async fn fetch_bonding_curve_price(
         rpc_client: &RpcClient,
@@ -257,27 +312,28 @@ impl PriceFeed {
         Ok((price, curve.complete))
     }
 
-    /// Fetch price from DexScreener API (for graduated tokens)
-    async fn fetch_dexscreener_price(
-        dexscreener: &DexScreenerClient,
+    /// Fetch price from the first price API that has one (for graduated tokens)
+    async fn fetch_api_price(
+        price_sources: &CompositePriceSource,
         mint: &Pubkey,
-    ) -> Result<f64> {
-        let mint_str = mint.to_string();
-        let token_info = dexscreener
-            .get_token_info(&mint_str)
-            .await
-            .map_err(|e| Error::Rpc(format!("DexScreener API error: {}", e)))?;
-
-        match token_info {
-            Some(info) if info.price_native > 0.0 => {
+    ) -> Result<(f64, PriceSource)> {
+        match price_sources.price(&mint.to_string()).await {
+            Some(price) => {
                 debug!(
-                    "Got DexScreener price for {}: {} SOL",
-                    mint, info.price_native
+                    "Got {} price for {}: {} SOL",
+                    price.source, mint, price.price
                 );
-                Ok(info.price_native)
+                Ok((price.price, price.source))
             }
-            Some(_) => Err(Error::Rpc("DexScreener returned zero price".to_string())),
-            None => Err(Error::Rpc("Token not found on DexScreener".to_string())),
+            None => Err(Error::Rpc(format!(
+                "No price from {}",
+                price_sources
+                    .sources()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
         }
     }
 
@@ -0,0 +1,471 @@
+//! On-chain reconciliation of tracked positions
+//!
+//! After a crash or a manual sell the positions file can drift from what the
+//! wallet actually holds. Reconciliation enumerates every SPL / Token-2022
+//! account owned by the trading wallet and compares it with tracked positions:
+//!
+//! - **orphans**: tokens held on-chain with no tracked position
+//! - **ghosts**: tracked positions whose on-chain balance is zero
+//!
+//! Orphans can be adopted as `Legacy` positions, with the entry price
+//! recovered from the most recent pump.fun buy in the token account's
+//! history, decoded with `pump::instruction::parse_transaction`.
+//!
+//! Each trade the bot sends is also confirmed here: once its transaction
+//! is fetched, the estimated network fee is replaced with the one charged
+//! and the parsed pump.fun events are handed back to the sender.
+
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::error::{Error, Result};
+use crate::position::manager::PositionManager;
+use crate::pump::instruction::{parse_transaction, PumpEvent, PumpEventKind};
+
+/// Token-2022 program (pump.fun tokens)
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+/// Wrapped SOL is never a position
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// How many recent signatures to inspect when recovering an entry price
+const MAX_HISTORY_LOOKUP: usize = 20;
+
+/// Non-zero token balance found on the wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHolding {
+    pub mint: String,
+    pub token_account: String,
+    pub raw_amount: u64,
+    pub decimals: u8,
+}
+
+impl TokenHolding {
+    /// Balance in whole tokens
+    pub fn ui_amount(&self) -> f64 {
+        self.raw_amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// Entry recovered from transaction history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveredEntry {
+    /// SOL per whole token
+    pub price: f64,
+    pub cost_sol: f64,
+    pub signature: String,
+    pub time: chrono::DateTime<chrono::Utc>,
+}
+
+/// On-chain holding without a tracked position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanHolding {
+    pub holding: TokenHolding,
+    pub entry: Option<RecoveredEntry>,
+    /// Adopted as a Legacy position during reconciliation
+    pub adopted: bool,
+}
+
+/// Result of a reconciliation pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub checked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub wallet: String,
+    /// Mints tracked and held on-chain
+    pub matched: Vec<String>,
+    /// Held on-chain, not tracked
+    pub orphans: Vec<OrphanHolding>,
+    /// Tracked, zero balance on-chain
+    pub ghosts: Vec<String>,
+}
+
+impl ReconciliationReport {
+    /// Check if tracked positions and on-chain balances disagree
+    pub fn has_discrepancies(&self) -> bool {
+        !self.ghosts.is_empty() || self.orphans.iter().any(|o| !o.adopted)
+    }
+
+    /// Load the last report written by `snipe start`
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = std::fs::read_to_string(path)?;
+        let report = serde_json::from_str(&data)
+            .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+        Ok(Some(report))
+    }
+
+    /// Persist the report for `snipe status`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let data = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+/// Enumerate all non-zero SPL and Token-2022 balances owned by `wallet`
+pub fn fetch_holdings(rpc: &RpcClient, wallet: &Pubkey) -> Result<Vec<TokenHolding>> {
+    let programs = [
+        spl_token::id(),
+        Pubkey::from_str(TOKEN_2022_PROGRAM).expect("valid program id"),
+    ];
+
+    let mut holdings = Vec::new();
+    for program in programs {
+        let accounts =
+            rpc.get_token_accounts_by_owner(wallet, TokenAccountsFilter::ProgramId(program))?;
+
+        for account in accounts {
+            let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data else {
+                continue;
+            };
+            let Some(info) = parsed.parsed.get("info") else {
+                continue;
+            };
+            let mint = info.get("mint").and_then(|m| m.as_str()).unwrap_or_default();
+            let token_amount = info.get("tokenAmount");
+            let raw_amount = token_amount
+                .and_then(|t| t.get("amount"))
+                .and_then(|a| a.as_str())
+                .and_then(|a| a.parse::<u64>().ok())
+                .unwrap_or(0);
+            let decimals = token_amount
+                .and_then(|t| t.get("decimals"))
+                .and_then(|d| d.as_u64())
+                .unwrap_or(6) as u8;
+
+            if mint.is_empty() || mint == WSOL_MINT || raw_amount == 0 {
+                continue;
+            }
+
+            holdings.push(TokenHolding {
+                mint: mint.to_string(),
+                token_account: account.pubkey.clone(),
+                raw_amount,
+                decimals,
+            });
+        }
+    }
+
+    Ok(holdings)
+}
+
+/// Raw token balance a wallet holds for one mint (0 if none or on error)
+///
+/// Looks the mint up directly first, then scans Token-2022 accounts (pump.fun
+/// tokens) in case the mint filter misses them.
+pub fn query_token_balance(rpc: &RpcClient, wallet: &Pubkey, mint: &str) -> u64 {
+    let Ok(mint_pubkey) = Pubkey::from_str(mint) else {
+        return 0;
+    };
+    let token_2022 = Pubkey::from_str(TOKEN_2022_PROGRAM).expect("valid program id");
+
+    let filters = [
+        TokenAccountsFilter::Mint(mint_pubkey),
+        TokenAccountsFilter::ProgramId(token_2022),
+    ];
+    for filter in filters {
+        let Ok(accounts) = rpc.get_token_accounts_by_owner(wallet, filter) else {
+            continue;
+        };
+        for account in &accounts {
+            let solana_account_decoder::UiAccountData::Json(parsed) = &account.account.data else {
+                continue;
+            };
+            let Some(info) = parsed.parsed.get("info") else {
+                continue;
+            };
+            if info.get("mint").and_then(|m| m.as_str()) != Some(mint) {
+                continue;
+            }
+            let balance = info
+                .get("tokenAmount")
+                .and_then(|t| t.get("amount"))
+                .and_then(|a| a.as_str())
+                .and_then(|a| a.parse::<u64>().ok())
+                .unwrap_or(0);
+            if balance > 0 {
+                return balance;
+            }
+        }
+    }
+
+    0
+}
+
+/// Split tracked mints and on-chain holdings into (matched, orphans, ghosts)
+pub fn diff_holdings(
+    tracked: &[String],
+    holdings: &[TokenHolding],
+) -> (Vec<String>, Vec<TokenHolding>, Vec<String>) {
+    let held: std::collections::HashSet<&str> = holdings.iter().map(|h| h.mint.as_str()).collect();
+
+    let (matched, ghosts): (Vec<String>, Vec<String>) = tracked
+        .iter()
+        .cloned()
+        .partition(|mint| held.contains(mint.as_str()));
+    let orphans = holdings
+        .iter()
+        .filter(|h| !tracked.contains(&h.mint))
+        .cloned()
+        .collect();
+
+    (matched, orphans, ghosts)
+}
+
+/// Entry of `wallet`'s pump.fun buy of `holding.mint` in `tx`, if any
+///
+/// The cost is the SOL paid into the curve, from the buy's trade event
+/// (fees excluded); a buy made through another program counts too.
+fn buy_entry(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+    wallet: &Pubkey,
+    holding: &TokenHolding,
+) -> Option<RecoveredEntry> {
+    let mint = Pubkey::from_str(&holding.mint).ok()?;
+    let (lamports, tokens) = parse_transaction(tx).into_iter().find_map(|event| {
+        if event.user != *wallet || event.mint != Some(mint) {
+            return None;
+        }
+        match event.kind {
+            PumpEventKind::Buy {
+                token_amount,
+                sol_amount: Some(sol),
+                ..
+            } if token_amount > 0 => Some((sol, token_amount)),
+            _ => None,
+        }
+    })?;
+    let signature = tx
+        .transaction
+        .transaction
+        .decode()?
+        .signatures
+        .first()?
+        .to_string();
+
+    let cost_sol = lamports as f64 / 1e9;
+    let tokens = tokens as f64 / 10f64.powi(holding.decimals as i32);
+    let time = tx
+        .block_time
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .unwrap_or_else(chrono::Utc::now);
+
+    Some(RecoveredEntry {
+        price: cost_sol / tokens,
+        cost_sol,
+        signature,
+        time,
+    })
+}
+
+/// Recover the entry of the most recent buy from the token account's history
+///
+/// Returns None if no pump.fun buy by `wallet` is found in recent history.
+pub fn recover_entry(
+    rpc: &RpcClient,
+    wallet: &Pubkey,
+    holding: &TokenHolding,
+) -> Option<RecoveredEntry> {
+    let token_account = Pubkey::from_str(&holding.token_account).ok()?;
+    let signatures = rpc.get_signatures_for_address(&token_account).ok()?;
+
+    for status in signatures.iter().take(MAX_HISTORY_LOOKUP) {
+        if status.err.is_some() {
+            continue;
+        }
+        let Ok(signature) = Signature::from_str(&status.signature) else {
+            continue;
+        };
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let Ok(tx) = rpc.get_transaction_with_config(&signature, config) else {
+            debug!("Could not fetch {} for entry recovery", status.signature);
+            continue;
+        };
+        if let Some(entry) = buy_entry(&tx, wallet, holding) {
+            return Some(entry);
+        }
+    }
+
+    None
+}
+
+/// One of the bot's trades as confirmed on-chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmedTrade {
+    pub signature: String,
+    /// Network fee charged, from the transaction meta
+    pub network_fee_lamports: u64,
+    /// pump.fun events in the transaction (empty for other venues and
+    /// failed transactions)
+    pub events: Vec<PumpEvent>,
+}
+
+impl ConfirmedTrade {
+    /// Decode a transaction fetched with a binary encoding
+    pub fn from_transaction(
+        signature: &str,
+        tx: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Option<Self> {
+        let meta = tx.transaction.meta.as_ref()?;
+        Some(Self {
+            signature: signature.to_string(),
+            network_fee_lamports: meta.fee,
+            events: parse_transaction(tx),
+        })
+    }
+
+    /// Raw tokens of `mint` bought (None = no pump.fun buy of it)
+    pub fn tokens_bought(&self, mint: &str) -> Option<u64> {
+        let mint = Pubkey::from_str(mint).ok()?;
+        self.events
+            .iter()
+            .filter(|event| event.mint == Some(mint))
+            .find_map(|event| match event.kind {
+                PumpEventKind::Buy { token_amount, .. } => Some(token_amount),
+                _ => None,
+            })
+    }
+}
+
+/// The trade `signature`, if it is confirmed
+pub fn fetch_confirmed_trade(rpc: &RpcClient, signature: &str) -> Option<ConfirmedTrade> {
+    let parsed = Signature::from_str(signature).ok()?;
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let tx = rpc.get_transaction_with_config(&parsed, config).ok()?;
+    ConfirmedTrade::from_transaction(signature, &tx)
+}
+
+/// Wait for the trade `signature` to confirm, then refine its fees
+///
+/// Runs in the background so entry and exit paths don't wait on
+/// confirmation. The task yields the confirmed trade (None if it didn't
+/// confirm within ~30s), for callers that need the realized fill.
+pub fn spawn_confirmation(
+    rpc_client: Arc<RpcClient>,
+    position_manager: Arc<PositionManager>,
+    signature: String,
+) -> tokio::task::JoinHandle<Option<ConfirmedTrade>> {
+    tokio::spawn(async move {
+        // Transactions usually confirm within a few seconds; give up after ~30s
+        for _ in 0..6 {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            let Some(trade) = fetch_confirmed_trade(&rpc_client, &signature) else {
+                continue;
+            };
+            match position_manager
+                .refine_fees(&signature, trade.network_fee_lamports)
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => warn!("No trade found for fee refinement of {}", signature),
+                Err(e) => warn!("Fee refinement for {} failed: {}", signature, e),
+            }
+            return Some(trade);
+        }
+        warn!("{} not confirmed, keeping fee estimate", signature);
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hand-built fixture (see the note in pump::instruction's tests)
+    #[test]
+    fn test_buy_entry_from_fixture() {
+        let tx: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../pump/fixtures/pump_buy_cpi.json")).unwrap();
+        let trader = Pubkey::from_str("Bsz8gTcuut8FowdhiNvsGVh27kGm61TN8vec5HWCMD7h").unwrap();
+        let held = holding("ZPP7SiG7LGHXFNKBEfoyKDkCNvzmAs7ecqTitE4PuCP");
+
+        let entry = buy_entry(&tx, &trader, &held).unwrap();
+        assert!((entry.cost_sol - 0.25).abs() < 1e-9);
+        assert!((entry.price - 0.25 / 7_981_334.112045).abs() < 1e-15);
+        assert_eq!(entry.time.timestamp(), 1760000042);
+
+        // Other wallets and mints don't match
+        assert!(buy_entry(&tx, &Pubkey::new_unique(), &held).is_none());
+        assert!(buy_entry(&tx, &trader, &holding(&Pubkey::new_unique().to_string())).is_none());
+
+        // A sell is not an entry
+        let sell: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../pump/fixtures/pump_sell.json")).unwrap();
+        assert!(buy_entry(&sell, &trader, &held).is_none());
+    }
+
+    #[test]
+    fn test_confirmed_trade_from_fixture() {
+        let tx: EncodedConfirmedTransactionWithStatusMeta =
+            serde_json::from_str(include_str!("../pump/fixtures/pump_buy_cpi.json")).unwrap();
+        let trade = ConfirmedTrade::from_transaction("sig", &tx).unwrap();
+        let fee = tx.transaction.meta.as_ref().unwrap().fee;
+        assert_eq!(trade.network_fee_lamports, fee);
+        assert_eq!(
+            trade.tokens_bought("ZPP7SiG7LGHXFNKBEfoyKDkCNvzmAs7ecqTitE4PuCP"),
+            Some(7_981_334_112_045)
+        );
+        assert_eq!(trade.tokens_bought(&Pubkey::new_unique().to_string()), None);
+    }
+
+    fn holding(mint: &str) -> TokenHolding {
+        TokenHolding {
+            mint: mint.to_string(),
+            token_account: format!("{}_ata", mint),
+            raw_amount: 1_000_000,
+            decimals: 6,
+        }
+    }
+
+    #[test]
+    fn test_diff_holdings() {
+        let tracked = vec!["a".to_string(), "ghost".to_string()];
+        let holdings = vec![holding("a"), holding("orphan")];
+
+        let (matched, orphans, ghosts) = diff_holdings(&tracked, &holdings);
+        assert_eq!(matched, vec!["a".to_string()]);
+        assert_eq!(ghosts, vec!["ghost".to_string()]);
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].mint, "orphan");
+    }
+
+    #[test]
+    fn test_report_discrepancies() {
+        let mut report = ReconciliationReport::default();
+        assert!(!report.has_discrepancies());
+
+        report.orphans.push(OrphanHolding {
+            holding: TokenHolding {
+                raw_amount: 1_500_000,
+                ..holding("mint")
+            },
+            entry: None,
+            adopted: true,
+        });
+        assert!(!report.has_discrepancies());
+        assert!((report.orphans[0].holding.ui_amount() - 1.5).abs() < 1e-9);
+
+        report.ghosts.push("ghost".to_string());
+        assert!(report.has_discrepancies());
+    }
+}
@@ -0,0 +1,546 @@
+//! Rug prediction for held positions
+//!
+//! `RugWatch` keeps order-flow deltas for every held position from the trade
+//! feed (price, sell count and volume over a rolling 30s, curve SOL,
+//! creator sells) and every `eval_interval_secs` combines them with holder
+//! changes from the kill-switch's holder watcher into a `RugPredictor`
+//! prediction. Above `warning_threshold` the trailing stop is tightened and
+//! pending scale-in tranches are cancelled; above `critical_threshold` the
+//! position's kill-switch is triggered and the position monitor exits.
+//!
+//! Each prediction is followed for `outcome_window_mins` and appended to
+//! `rug_predictions.json` with whether price fell more than
+//! `outcome_drop_pct`, so `snipe stats rug-predictor` can report precision
+//! and recall. Pending outcomes live in memory; a restart drops them.
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::filter::{HolderChanges, KillSwitchEvaluator};
+use crate::position::manager::{Position, PositionManager};
+use crate::strategy::tactics::{
+    RugPrediction, RugPredictionContext, RugPredictor, RugPredictorConfig, RugWarningSignal,
+};
+use crate::strategy::RollingWindow;
+use crate::stream::pumpportal::TradeEvent;
+
+/// Window for sell count, volume and price change (the predictor's
+/// selloff rule looks at sells within under a minute)
+const FLOW_WINDOW_SECS: u64 = 30;
+
+/// What a prediction triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RugAction {
+    None,
+    /// Trailing stop tightened, scale-in cancelled
+    Warning,
+    /// Kill-switch exit
+    Critical,
+}
+
+impl RugAction {
+    /// Action for a prediction probability
+    pub fn for_probability(config: &RugPredictorConfig, probability: f64) -> Self {
+        if probability >= config.critical_threshold {
+            RugAction::Critical
+        } else if probability >= config.warning_threshold {
+            RugAction::Warning
+        } else {
+            RugAction::None
+        }
+    }
+}
+
+/// A prediction and what the price did afterwards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RugPredictionRecord {
+    pub mint: String,
+    pub symbol: String,
+    pub time: DateTime<Utc>,
+    pub probability: f64,
+    pub action: RugAction,
+    pub warnings: Vec<RugWarningSignal>,
+    /// Price when the prediction was made
+    pub price: f64,
+    /// Lowest price seen within the outcome window
+    pub trough_price: f64,
+    /// Price fell more than `outcome_drop_pct` within the window
+    pub dropped: bool,
+}
+
+impl RugPredictionRecord {
+    /// Largest drop from the prediction price, in %
+    pub fn max_drop_pct(&self) -> f64 {
+        if self.price > 0.0 {
+            (self.price - self.trough_price) / self.price * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Trade-feed state for one held position
+struct FlowState {
+    price: RollingWindow,
+    sells: RollingWindow,
+    volume: RollingWindow,
+    curve_sol: f64,
+    creator_bought: u64,
+    creator_sold: u64,
+    creator_sells: u32,
+}
+
+impl FlowState {
+    fn new() -> Self {
+        Self {
+            price: RollingWindow::new(Duration::from_secs(FLOW_WINDOW_SECS)),
+            sells: RollingWindow::new(Duration::from_secs(FLOW_WINDOW_SECS)),
+            volume: RollingWindow::new(Duration::from_secs(FLOW_WINDOW_SECS)),
+            curve_sol: 0.0,
+            creator_bought: 0,
+            creator_sold: 0,
+            creator_sells: 0,
+        }
+    }
+
+    /// Predictor input from the flow and the holder watcher
+    fn context(&self, mint: &str, holders: Option<HolderChanges>) -> RugPredictionContext {
+        let holders = holders.unwrap_or_default();
+        // Without the creator's buy in view, any sale counts as all of it
+        let creator_held = self.creator_bought.max(self.creator_sold);
+        let oldest = self.price.oldest();
+        RugPredictionContext {
+            mint: mint.to_string(),
+            creator_sold_pct: if creator_held > 0 {
+                self.creator_sold as f64 / creator_held as f64 * 100.0
+            } else {
+                0.0
+            },
+            creator_sell_count: self.creator_sells,
+            liquidity_sol: self.curve_sol,
+            top_holder_pct: holders.top_holder_pct,
+            top_holder_sells: holders.holders_selling,
+            total_insider_sold_pct: holders.sold_pct,
+            recent_volume_sol: self.volume.sum(),
+            price_change_pct: if oldest > 0.0 {
+                self.price.delta() / oldest * 100.0
+            } else {
+                0.0
+            },
+            recent_sell_count: self.sells.count() as u32,
+            recent_sell_window_secs: FLOW_WINDOW_SECS,
+            ..Default::default()
+        }
+    }
+}
+
+/// A prediction waiting for its outcome window to close
+struct PendingOutcome {
+    record: RugPredictionRecord,
+    due: DateTime<Utc>,
+}
+
+/// Runs the rug predictor over held positions
+pub struct RugWatch {
+    config: RugPredictorConfig,
+    predictor: Mutex<RugPredictor>,
+    flows: DashMap<String, FlowState>,
+    /// mint -> predictions awaiting their outcome
+    pending: DashMap<String, Vec<PendingOutcome>>,
+    position_manager: Arc<PositionManager>,
+    kill_switch: Option<Arc<KillSwitchEvaluator>>,
+    persistence_path: Option<PathBuf>,
+}
+
+impl RugWatch {
+    pub fn new(
+        config: RugPredictorConfig,
+        position_manager: Arc<PositionManager>,
+        kill_switch: Option<Arc<KillSwitchEvaluator>>,
+        persistence_path: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            predictor: Mutex::new(RugPredictor::new(config.clone())),
+            config,
+            flows: DashMap::new(),
+            pending: DashMap::new(),
+            position_manager,
+            kill_switch,
+            persistence_path,
+        }
+    }
+
+    /// Feed a trade from the stream
+    ///
+    /// Prices every pending outcome for the mint; flow state is only kept
+    /// for held positions.
+    pub fn record_trade(&self, trade: &TradeEvent) {
+        let mint = trade.mint.as_str();
+        let is_buy = trade.tx_type == "buy";
+        // pump.fun supply is 1B tokens
+        let price = trade.market_cap_sol / 1_000_000_000.0;
+        if price > 0.0 {
+            if let Some(mut pending) = self.pending.get_mut(mint) {
+                for outcome in pending.iter_mut() {
+                    outcome.record.trough_price = outcome.record.trough_price.min(price);
+                }
+            }
+        }
+
+        let Some(mut flow) = self.flows.get_mut(mint) else {
+            return;
+        };
+        if price > 0.0 {
+            flow.price.add(price);
+        }
+        flow.volume.add(trade.sol_amount);
+        if !is_buy {
+            flow.sells.add(1.0);
+        }
+        if trade.v_sol_in_bonding_curve > 0.0 {
+            flow.curve_sol = trade.v_sol_in_bonding_curve;
+            let mut predictor = self.predictor.lock().unwrap();
+            predictor.update_liquidity(mint, trade.v_sol_in_bonding_curve);
+        }
+
+        let is_creator = self.kill_switch.as_ref().is_some_and(|ks| {
+            ks.deployer_tracker()
+                .is_creator_wallet(mint, &trade.trader_public_key)
+        });
+        if is_creator {
+            let tokens = trade.token_amount as u64;
+            if is_buy {
+                flow.creator_bought += tokens;
+            } else {
+                flow.creator_sold += tokens;
+                flow.creator_sells += 1;
+            }
+        }
+    }
+
+    /// Predict for every held position, act on the result, and record
+    /// outcomes whose window has closed
+    pub async fn run_once(&self) -> Vec<RugPrediction> {
+        let positions = self.position_manager.get_all_positions().await;
+        self.sync_positions(&positions);
+
+        let mut predictions = Vec::new();
+        for position in positions.iter().filter(|p| !p.kill_switch_triggered) {
+            let Some(prediction) = self.predict(position) else {
+                continue;
+            };
+            let action = RugAction::for_probability(&self.config, prediction.probability);
+            self.act(position, &prediction, action).await;
+            self.track_outcome(position, &prediction, action);
+            predictions.push(prediction);
+        }
+
+        self.resolve_due(Utc::now());
+        predictions
+    }
+
+    /// Start watching new positions and forget closed ones
+    ///
+    /// The predictor lock is never taken while a flow entry is held here;
+    /// `record_trade` takes them in the other order.
+    fn sync_positions(&self, positions: &[Position]) {
+        for position in positions {
+            if !self.flows.contains_key(&position.mint) {
+                self.flows.insert(position.mint.clone(), FlowState::new());
+                let mut predictor = self.predictor.lock().unwrap();
+                predictor.track_token(&position.mint, 0.0, 0.0);
+            }
+        }
+        let mut closed = Vec::new();
+        self.flows.retain(|mint, _| {
+            let held = positions.iter().any(|p| &p.mint == mint);
+            if !held {
+                closed.push(mint.clone());
+            }
+            held
+        });
+        let mut predictor = self.predictor.lock().unwrap();
+        for mint in closed {
+            predictor.clear(&mint);
+        }
+    }
+
+    /// None until the trade feed has priced the position
+    fn predict(&self, position: &Position) -> Option<RugPrediction> {
+        let context = {
+            let flow = self.flows.get(&position.mint)?;
+            if flow.price.is_empty() {
+                return None;
+            }
+            let holders = self
+                .kill_switch
+                .as_ref()
+                .and_then(|ks| ks.holder_watcher().holder_changes(&position.mint));
+            flow.context(&position.mint, holders)
+        };
+        Some(self.predictor.lock().unwrap().predict(&context))
+    }
+
+    async fn act(&self, position: &Position, prediction: &RugPrediction, action: RugAction) {
+        let reason = format!(
+            "rug predicted ({:.0}%): {}",
+            prediction.probability * 100.0,
+            prediction.recommendation
+        );
+        match action {
+            RugAction::None => {}
+            RugAction::Warning => {
+                let trailing_pct = self.config.warning_trailing_pct;
+                let tighter = !position
+                    .exit_override
+                    .trailing_pct
+                    .is_some_and(|t| t <= trailing_pct);
+                if tighter {
+                    warn!(
+                        "[{}] Rug warning {:.0}% - tightening trailing stop to {:.1}%",
+                        position.symbol,
+                        prediction.probability * 100.0,
+                        trailing_pct
+                    );
+                    if let Err(e) = self
+                        .position_manager
+                        .set_exit_override(&position.mint, None, None, Some(trailing_pct))
+                        .await
+                    {
+                        warn!(
+                            "[{}] Failed to tighten trailing stop: {}",
+                            position.symbol, e
+                        );
+                    }
+                }
+                if let Err(e) = self
+                    .position_manager
+                    .cancel_scale_in(&position.mint, &reason)
+                    .await
+                {
+                    warn!("[{}] Failed to cancel scale-in: {}", position.symbol, e);
+                }
+            }
+            RugAction::Critical => {
+                warn!(
+                    "[{}] Rug prediction CRITICAL {:.0}% - triggering kill-switch",
+                    position.symbol,
+                    prediction.probability * 100.0
+                );
+                if let Err(e) = self
+                    .position_manager
+                    .trigger_kill_switch(&position.mint, &reason)
+                    .await
+                {
+                    warn!("[{}] Failed to trigger kill-switch: {}", position.symbol, e);
+                }
+            }
+        }
+    }
+
+    fn track_outcome(&self, position: &Position, prediction: &RugPrediction, action: RugAction) {
+        let price = position.current_price;
+        if price <= 0.0 {
+            return;
+        }
+        let now = Utc::now();
+        let record = RugPredictionRecord {
+            mint: position.mint.clone(),
+            symbol: position.symbol.clone(),
+            time: now,
+            probability: prediction.probability,
+            action,
+            warnings: prediction.warnings.clone(),
+            price,
+            trough_price: price,
+            dropped: false,
+        };
+        let due = now + chrono::Duration::minutes(self.config.outcome_window_mins as i64);
+        self.pending
+            .entry(position.mint.clone())
+            .or_default()
+            .push(PendingOutcome { record, due });
+    }
+
+    /// Close outcome windows that ended by `now` and append them to the log
+    fn resolve_due(&self, now: DateTime<Utc>) -> Vec<RugPredictionRecord> {
+        let mut resolved = Vec::new();
+        for mut pending in self.pending.iter_mut() {
+            pending.retain(|outcome| {
+                if outcome.due > now {
+                    return true;
+                }
+                let mut record = outcome.record.clone();
+                record.dropped = record.max_drop_pct() > self.config.outcome_drop_pct;
+                resolved.push(record);
+                false
+            });
+        }
+        self.pending.retain(|_, pending| !pending.is_empty());
+
+        if resolved.is_empty() {
+            return resolved;
+        }
+        debug!("Resolved {} rug prediction outcomes", resolved.len());
+        if let Some(path) = &self.persistence_path {
+            if let Err(e) = append_rug_predictions(path, &resolved) {
+                warn!("Failed to record rug prediction outcomes: {}", e);
+            }
+        }
+        resolved
+    }
+
+    /// Predict every `eval_interval_secs` in the background
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        info!(
+            "Rug predictor watching held positions (warning {:.0}%, critical {:.0}%)",
+            self.config.warning_threshold * 100.0,
+            self.config.critical_threshold * 100.0
+        );
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(self.config.eval_interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                self.run_once().await;
+            }
+        })
+    }
+}
+
+/// Load all prediction outcomes (empty if the file doesn't exist yet)
+pub fn load_rug_predictions(path: &Path) -> Result<Vec<RugPredictionRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Append prediction outcomes to the log
+pub fn append_rug_predictions(path: &Path, records: &[RugPredictionRecord]) -> Result<()> {
+    let mut all = load_rug_predictions(path)?;
+    all.extend_from_slice(records);
+    let data = serde_json::to_string_pretty(&all)?;
+    std::fs::write(path, data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Confusion counts for predictions at or above a probability threshold
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PredictionScore {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub true_negatives: usize,
+}
+
+impl PredictionScore {
+    pub fn at_threshold(records: &[RugPredictionRecord], threshold: f64) -> Self {
+        let mut score = Self::default();
+        for record in records {
+            match (record.probability >= threshold, record.dropped) {
+                (true, true) => score.true_positives += 1,
+                (true, false) => score.false_positives += 1,
+                (false, true) => score.false_negatives += 1,
+                (false, false) => score.true_negatives += 1,
+            }
+        }
+        score
+    }
+
+    /// Share of flagged predictions followed by a drop
+    pub fn precision(&self) -> Option<f64> {
+        let flagged = self.true_positives + self.false_positives;
+        (flagged > 0).then(|| self.true_positives as f64 / flagged as f64)
+    }
+
+    /// Share of drops that were flagged
+    pub fn recall(&self) -> Option<f64> {
+        let drops = self.true_positives + self.false_negatives;
+        (drops > 0).then(|| self.true_positives as f64 / drops as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(probability: f64, dropped: bool) -> RugPredictionRecord {
+        RugPredictionRecord {
+            mint: "mint".to_string(),
+            symbol: "TEST".to_string(),
+            time: Utc::now(),
+            probability,
+            action: RugAction::None,
+            warnings: vec![],
+            price: 1.0,
+            trough_price: if dropped { 0.5 } else { 1.0 },
+            dropped,
+        }
+    }
+
+    #[test]
+    fn test_action_thresholds() {
+        let config = RugPredictorConfig::default();
+        assert_eq!(RugAction::for_probability(&config, 0.2), RugAction::None);
+        assert_eq!(RugAction::for_probability(&config, 0.5), RugAction::Warning);
+        assert_eq!(
+            RugAction::for_probability(&config, 0.85),
+            RugAction::Critical
+        );
+    }
+
+    #[test]
+    fn test_flow_context() {
+        let mut flow = FlowState::new();
+        flow.price.add(1.0);
+        flow.price.add(0.5);
+        for _ in 0..12 {
+            flow.sells.add(1.0);
+        }
+        flow.creator_sold = 100;
+        flow.creator_sells = 1;
+
+        let ctx = flow.context("mint", None);
+        assert!((ctx.price_change_pct + 50.0).abs() < 1e-9);
+        assert_eq!(ctx.recent_sell_count, 12);
+        assert_eq!(ctx.creator_sold_pct, 100.0);
+
+        // A selloff with the creator out is at least a warning
+        let prediction = RugPredictor::default().predict(&ctx);
+        assert!(prediction
+            .warnings
+            .iter()
+            .any(|w| matches!(w, RugWarningSignal::RapidSelloff { .. })));
+        assert_ne!(
+            RugAction::for_probability(&RugPredictorConfig::default(), prediction.probability),
+            RugAction::None
+        );
+    }
+
+    #[test]
+    fn test_precision_recall() {
+        let records = vec![
+            record(0.9, true),
+            record(0.6, false),
+            record(0.2, true),
+            record(0.1, false),
+        ];
+        let score = PredictionScore::at_threshold(&records, 0.5);
+        assert_eq!(score.precision(), Some(0.5));
+        assert_eq!(score.recall(), Some(0.5));
+
+        let score = PredictionScore::at_threshold(&records, 0.8);
+        assert_eq!(score.precision(), Some(1.0));
+        assert_eq!(score.recall(), Some(0.5));
+        assert_eq!(PredictionScore::default().precision(), None);
+    }
+}
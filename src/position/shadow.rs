@@ -0,0 +1,446 @@
+//! Watch-only shadow positions
+//!
+//! Tokens the adaptive filter rates Observe are not bought. Instead a
+//! `ShadowPosition` records the entry price with no funds behind it, the
+//! trade feed keeps it priced for `shadow.window_secs`, and the Probe exit
+//! thresholds decide which rule a hypothetical Probe entry would have hit.
+//! Outcomes are appended to `shadow_trades.json` (next to `trades.json`) so
+//! `snipe stats` can compare the real win rate with what the skipped tokens
+//! would have done.
+//!
+//! Entry sources under evaluation (e.g. front-run accumulation) shadow the
+//! candidates they raise but don't buy, tagged with their `source`.
+//!
+//! Open shadows live in memory only; a restart drops them unrecorded.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::config::{ExitThresholds, ShadowConfig};
+use crate::error::{Error, Result};
+use crate::filter::scoring::Recommendation;
+use crate::filter::DiscoverySource;
+use crate::position::history::{ClosedTrade, TradeExitReason};
+use crate::position::manager::EntryType;
+
+/// A tracked, non-capitalized entry
+#[derive(Debug, Clone)]
+pub struct ShadowPosition {
+    pub mint: String,
+    pub symbol: String,
+    pub recommendation: Recommendation,
+    pub entry_price: f64,
+    pub entry_time: DateTime<Utc>,
+    pub last_price: f64,
+    pub peak_price: f64,
+    pub trough_price: f64,
+    /// Quick profit layer would have sold half
+    pub quick_profit_hit: bool,
+    /// Entry source whose skipped candidate this is (None = Observe rating)
+    pub source: Option<DiscoverySource>,
+}
+
+impl ShadowPosition {
+    fn pnl_pct_at(&self, price: f64) -> f64 {
+        (price - self.entry_price) / self.entry_price * 100.0
+    }
+}
+
+/// What a hypothetical Probe entry would have done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowOutcome {
+    pub mint: String,
+    pub symbol: String,
+    pub recommendation: Recommendation,
+    pub entry_time: DateTime<Utc>,
+    pub exit_time: DateTime<Utc>,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub peak_price: f64,
+    pub trough_price: f64,
+    /// Rule that would have closed the position (None = window expired first)
+    pub exit_rule: Option<TradeExitReason>,
+    pub quick_profit_hit: bool,
+    /// Blended gross % (half at the quick profit layer if it was hit)
+    pub pnl_pct: f64,
+    /// Entry source whose skipped candidate this was (None = Observe rating)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<DiscoverySource>,
+}
+
+impl ShadowOutcome {
+    pub fn is_win(&self) -> bool {
+        self.pnl_pct > 0.0
+    }
+
+    /// Exit rule label for output
+    pub fn exit_label(&self) -> String {
+        self.exit_rule
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "window_expired".to_string())
+    }
+}
+
+/// Open shadow positions plus the outcome log
+pub struct ShadowTracker {
+    config: ShadowConfig,
+    thresholds: ExitThresholds,
+    open: Arc<RwLock<HashMap<String, ShadowPosition>>>,
+    persistence_path: Option<PathBuf>,
+}
+
+impl ShadowTracker {
+    /// `thresholds` are the exits a Probe entry would have used
+    pub fn new(config: ShadowConfig, thresholds: ExitThresholds, persistence_path: Option<PathBuf>) -> Self {
+        Self {
+            config,
+            thresholds,
+            open: Arc::new(RwLock::new(HashMap::new())),
+            persistence_path,
+        }
+    }
+
+    /// Start shadowing a token (no-op if already tracked or at capacity)
+    pub async fn open(&self, mint: &str, symbol: &str, recommendation: Recommendation, price: f64) {
+        self.insert(mint, symbol, recommendation, price, None).await;
+    }
+
+    /// Shadow a candidate raised by `source`, discarded again if it is bought
+    pub async fn open_from(
+        &self,
+        source: DiscoverySource,
+        mint: &str,
+        symbol: &str,
+        recommendation: Recommendation,
+        price: f64,
+    ) {
+        self.insert(mint, symbol, recommendation, price, Some(source)).await;
+    }
+
+    /// Drop an open shadow without recording an outcome
+    pub async fn discard(&self, mint: &str) -> bool {
+        self.open.write().await.remove(mint).is_some()
+    }
+
+    async fn insert(
+        &self,
+        mint: &str,
+        symbol: &str,
+        recommendation: Recommendation,
+        price: f64,
+        source: Option<DiscoverySource>,
+    ) {
+        if price <= 0.0 {
+            return;
+        }
+        let mut open = self.open.write().await;
+        if open.contains_key(mint) {
+            return;
+        }
+        if open.len() >= self.config.max_open {
+            debug!("Shadow capacity reached ({}), not tracking {}", self.config.max_open, symbol);
+            return;
+        }
+        open.insert(
+            mint.to_string(),
+            ShadowPosition {
+                mint: mint.to_string(),
+                symbol: symbol.to_string(),
+                recommendation,
+                entry_price: price,
+                entry_time: Utc::now(),
+                last_price: price,
+                peak_price: price,
+                trough_price: price,
+                quick_profit_hit: false,
+                source,
+            },
+        );
+        debug!("Shadowing {} at {:.12} SOL", symbol, price);
+    }
+
+    /// Feed a price tick; records and returns the outcome if an exit rule fired
+    pub async fn update_price(&self, mint: &str, price: f64) -> Option<ShadowOutcome> {
+        if price <= 0.0 {
+            return None;
+        }
+        let mut open = self.open.write().await;
+        let shadow = open.get_mut(mint)?;
+        shadow.last_price = price;
+        shadow.peak_price = shadow.peak_price.max(price);
+        shadow.trough_price = shadow.trough_price.min(price);
+
+        let rule = self.exit_rule(shadow, Utc::now())?;
+        let shadow = open.remove(mint)?;
+        drop(open);
+
+        Some(self.record(shadow, Some(rule)).await)
+    }
+
+    /// Close shadows whose window (or Probe max hold) has elapsed
+    pub async fn expire(&self) -> Vec<ShadowOutcome> {
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(self.config.window_secs as i64);
+
+        let mut open = self.open.write().await;
+        let mut due = Vec::new();
+        open.retain(|_, shadow| {
+            let rule = self.exit_rule(shadow, now);
+            if rule.is_some() || now - shadow.entry_time >= window {
+                due.push((shadow.clone(), rule));
+                false
+            } else {
+                true
+            }
+        });
+        drop(open);
+
+        let mut outcomes = Vec::with_capacity(due.len());
+        for (shadow, rule) in due {
+            outcomes.push(self.record(shadow, rule).await);
+        }
+        outcomes
+    }
+
+    pub async fn open_count(&self) -> usize {
+        self.open.read().await.len()
+    }
+
+    /// Which Probe exit would fire at the shadow's last price
+    ///
+    /// Also marks the quick profit layer, which only sells half and so
+    /// never closes the shadow by itself.
+    fn exit_rule(&self, shadow: &mut ShadowPosition, now: DateTime<Utc>) -> Option<TradeExitReason> {
+        let pnl_pct = shadow.pnl_pct_at(shadow.last_price);
+        if pnl_pct <= -self.thresholds.stop_loss_pct {
+            return Some(TradeExitReason::StopLoss);
+        }
+        if pnl_pct >= self.thresholds.take_profit_pct {
+            return Some(TradeExitReason::TakeProfit);
+        }
+        if pnl_pct >= self.thresholds.quick_profit_pct {
+            shadow.quick_profit_hit = true;
+        }
+        match self.thresholds.max_hold() {
+            Some(max_hold) if (now - shadow.entry_time).num_seconds() >= max_hold as i64 => {
+                Some(TradeExitReason::MaxHold)
+            }
+            _ => None,
+        }
+    }
+
+    fn outcome(&self, shadow: ShadowPosition, exit_rule: Option<TradeExitReason>) -> ShadowOutcome {
+        let final_pct = shadow.pnl_pct_at(shadow.last_price);
+        let pnl_pct = if shadow.quick_profit_hit {
+            (self.thresholds.quick_profit_pct + final_pct) / 2.0
+        } else {
+            final_pct
+        };
+        ShadowOutcome {
+            mint: shadow.mint,
+            symbol: shadow.symbol,
+            recommendation: shadow.recommendation,
+            entry_time: shadow.entry_time,
+            exit_time: Utc::now(),
+            entry_price: shadow.entry_price,
+            exit_price: shadow.last_price,
+            peak_price: shadow.peak_price,
+            trough_price: shadow.trough_price,
+            exit_rule,
+            quick_profit_hit: shadow.quick_profit_hit,
+            pnl_pct,
+            source: shadow.source,
+        }
+    }
+
+    async fn record(&self, shadow: ShadowPosition, exit_rule: Option<TradeExitReason>) -> ShadowOutcome {
+        let outcome = self.outcome(shadow, exit_rule);
+        info!(
+            "SHADOW {} closed: {} {:+.1}% (peak {:+.1}%)",
+            outcome.symbol,
+            outcome.exit_label(),
+            outcome.pnl_pct,
+            (outcome.peak_price - outcome.entry_price) / outcome.entry_price * 100.0
+        );
+        if let Some(path) = &self.persistence_path {
+            if let Err(e) = append_shadow_outcome(path, outcome.clone()) {
+                warn!("Failed to record shadow outcome for {}: {}", outcome.mint, e);
+            }
+        }
+        outcome
+    }
+}
+
+/// Load all shadow outcomes (empty if the file doesn't exist yet)
+pub fn load_shadow_outcomes(path: &Path) -> Result<Vec<ShadowOutcome>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = std::fs::read_to_string(path)?;
+    serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Append one shadow outcome to the log
+pub fn append_shadow_outcome(path: &Path, outcome: ShadowOutcome) -> Result<()> {
+    let mut outcomes = load_shadow_outcomes(path)?;
+    outcomes.push(outcome);
+    let data = serde_json::to_string_pretty(&outcomes)?;
+    std::fs::write(path, data).map_err(|e| Error::PositionPersistence(e.to_string()))
+}
+
+/// Win rate for one entry bucket
+#[derive(Debug, Clone)]
+pub struct WinRate {
+    pub label: &'static str,
+    pub shadow: bool,
+    pub trades: usize,
+    pub wins: usize,
+    pub avg_pnl_pct: f64,
+}
+
+impl WinRate {
+    fn from_pnls(label: &'static str, shadow: bool, pnls: &[f64]) -> Self {
+        let trades = pnls.len();
+        Self {
+            label,
+            shadow,
+            trades,
+            wins: pnls.iter().filter(|p| **p > 0.0).count(),
+            avg_pnl_pct: if trades == 0 {
+                0.0
+            } else {
+                pnls.iter().sum::<f64>() / trades as f64
+            },
+        }
+    }
+
+    pub fn win_rate_pct(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64 * 100.0
+        }
+    }
+}
+
+/// Real closes grouped by entry type next to Observe and skipped-accumulation
+/// shadow outcomes (empty buckets are omitted)
+///
+/// Real trades use net P&L; shadows are gross since no fees were paid.
+pub fn compare_win_rates(real: &[ClosedTrade], shadows: &[ShadowOutcome]) -> Vec<WinRate> {
+    const REAL: [(EntryType, &str); 6] = [
+        (EntryType::StrongBuy, "strong_buy"),
+        (EntryType::Opportunity, "opportunity"),
+        (EntryType::Probe, "probe"),
+        (EntryType::Legacy, "legacy"),
+        (EntryType::CopyTrade, "copy_trade"),
+        (EntryType::PostMigration, "post_migration"),
+    ];
+    let mut rows: Vec<WinRate> = REAL
+        .iter()
+        .map(|&(entry_type, label)| {
+            let pnls: Vec<f64> = real
+                .iter()
+                .filter(|t| t.entry_type == entry_type)
+                .map(|t| t.pnl_pct)
+                .collect();
+            WinRate::from_pnls(label, false, &pnls)
+        })
+        .collect();
+    let observed: Vec<f64> = shadows
+        .iter()
+        .filter(|s| s.source.is_none() && s.recommendation == Recommendation::Observe)
+        .map(|s| s.pnl_pct)
+        .collect();
+    rows.push(WinRate::from_pnls("observe", true, &observed));
+    let accumulation: Vec<f64> = shadows
+        .iter()
+        .filter(|s| s.source == Some(DiscoverySource::Accumulation))
+        .map(|s| s.pnl_pct)
+        .collect();
+    rows.push(WinRate::from_pnls("accumulation", true, &accumulation));
+    rows.retain(|r| r.trades > 0);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> ShadowTracker {
+        ShadowTracker::new(
+            ShadowConfig::default(),
+            ExitThresholds {
+                take_profit_pct: 8.0,
+                stop_loss_pct: 12.0,
+                quick_profit_pct: 4.0,
+                max_hold_secs: 0,
+            },
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_after_quick_profit() {
+        let tracker = tracker();
+        tracker.open("m", "SYM", Recommendation::Observe, 1.0).await;
+
+        assert!(tracker.update_price("m", 1.05).await.is_none());
+        let outcome = tracker.update_price("m", 1.10).await.unwrap();
+
+        assert_eq!(outcome.exit_rule, Some(TradeExitReason::TakeProfit));
+        assert!(outcome.quick_profit_hit);
+        // Half at +4%, half at +10%
+        assert!((outcome.pnl_pct - 7.0).abs() < 1e-9);
+        assert_eq!(tracker.open_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stop_loss_and_window_expiry() {
+        let tracker = tracker();
+        tracker.open("a", "A", Recommendation::Observe, 1.0).await;
+        tracker.open("b", "B", Recommendation::Observe, 1.0).await;
+
+        let stopped = tracker.update_price("a", 0.85).await.unwrap();
+        assert_eq!(stopped.exit_rule, Some(TradeExitReason::StopLoss));
+        assert!(!stopped.is_win());
+
+        tracker.update_price("b", 1.02).await;
+        tracker.open.write().await.get_mut("b").unwrap().entry_time -=
+            chrono::Duration::seconds(ShadowConfig::default().window_secs as i64);
+        let expired = tracker.expire().await;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].exit_rule, None);
+        assert_eq!(expired[0].exit_label(), "window_expired");
+        assert!((expired[0].pnl_pct - 2.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_source_tagged_shadows() {
+        let tracker = tracker();
+        tracker.open("a", "A", Recommendation::Observe, 1.0).await;
+        tracker
+            .open_from(DiscoverySource::Accumulation, "b", "B", Recommendation::Observe, 1.0)
+            .await;
+        tracker
+            .open_from(DiscoverySource::Accumulation, "c", "C", Recommendation::Probe, 1.0)
+            .await;
+
+        // Bought after all: no outcome
+        assert!(tracker.discard("c").await);
+        assert!(!tracker.discard("c").await);
+
+        let observed = tracker.update_price("a", 1.10).await.unwrap();
+        let accumulated = tracker.update_price("b", 0.85).await.unwrap();
+        assert_eq!(accumulated.source, Some(DiscoverySource::Accumulation));
+
+        let rows = compare_win_rates(&[], &[observed, accumulated]);
+        let labels: Vec<(&str, usize, usize)> = rows.iter().map(|r| (r.label, r.trades, r.wins)).collect();
+        assert_eq!(labels, vec![("observe", 1, 1), ("accumulation", 1, 0)]);
+    }
+}
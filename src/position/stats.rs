@@ -0,0 +1,272 @@
+//! Peak / drawdown analytics over closed trades
+//!
+//! Aggregates the max favorable excursion (MFE) and max adverse excursion
+//! (MAE) recorded on each `ClosedTrade` to show how much of the peak the
+//! exits actually captured, which exit reasons give back the most, and how
+//! long positions are held. Records written before excursions were tracked
+//! are counted in the hold-time distribution only. Win rates are also broken
+//! down by the regime each position was entered under.
+
+use std::collections::HashMap;
+
+use crate::position::history::{ClosedTrade, TradeExitReason};
+use crate::strategy::types::RegimeKind;
+
+/// Hold-time buckets as (upper bound in seconds, label)
+const HOLD_BUCKETS: [(i64, &str); 7] = [
+    (30, "< 30s"),
+    (60, "30s - 1m"),
+    (120, "1m - 2m"),
+    (300, "2m - 5m"),
+    (900, "5m - 15m"),
+    (3600, "15m - 1h"),
+    (i64::MAX, "> 1h"),
+];
+
+/// Excursion aggregates for one exit reason
+#[derive(Debug, Clone)]
+pub struct ReasonStats {
+    pub reason: TradeExitReason,
+    pub trades: usize,
+    pub avg_mfe_pct: f64,
+    pub avg_exit_pct: f64,
+    pub avg_given_back_pct: f64,
+    pub given_back_sol: f64,
+}
+
+/// Win rate for one entry regime
+#[derive(Debug, Clone)]
+pub struct RegimeStats {
+    /// None = regime not recorded (strategy engine off or older records)
+    pub regime: Option<RegimeKind>,
+    pub trades: usize,
+    pub wins: usize,
+    pub avg_pnl_pct: f64,
+    pub pnl_sol: f64,
+}
+
+impl RegimeStats {
+    pub fn label(&self) -> &'static str {
+        self.regime.map_or("unrecorded", |r| r.as_str())
+    }
+
+    pub fn win_rate_pct(&self) -> f64 {
+        if self.trades == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.trades as f64 * 100.0
+        }
+    }
+}
+
+/// Session-level peak / drawdown summary
+#[derive(Debug, Clone, Default)]
+pub struct ExcursionStats {
+    /// All closes in the selection
+    pub trades: usize,
+    /// Closes with excursion data
+    pub tracked: usize,
+    pub avg_mfe_pct: f64,
+    pub avg_mae_pct: f64,
+    /// Average realized gross % at exit
+    pub avg_exit_pct: f64,
+    pub avg_given_back_pct: f64,
+    pub total_mfe_sol: f64,
+    pub total_given_back_sol: f64,
+    /// Sorted by SOL given back, most first
+    pub by_reason: Vec<ReasonStats>,
+    /// (label, count) per hold-time bucket
+    pub hold_distribution: Vec<(&'static str, usize)>,
+    pub median_hold_secs: i64,
+    /// Sorted by net P&L, worst first
+    pub by_regime: Vec<RegimeStats>,
+}
+
+impl ExcursionStats {
+    pub fn from_trades(trades: &[ClosedTrade]) -> Self {
+        let tracked: Vec<&ClosedTrade> = trades.iter().filter(|t| t.has_excursions()).collect();
+        let avg = |f: &dyn Fn(&ClosedTrade) -> f64, list: &[&ClosedTrade]| -> f64 {
+            if list.is_empty() {
+                0.0
+            } else {
+                list.iter().map(|t| f(t)).sum::<f64>() / list.len() as f64
+            }
+        };
+
+        let mut grouped: HashMap<TradeExitReason, Vec<&ClosedTrade>> = HashMap::new();
+        for &trade in &tracked {
+            grouped.entry(trade.exit_reason).or_default().push(trade);
+        }
+        let mut by_reason: Vec<ReasonStats> = grouped
+            .into_iter()
+            .map(|(reason, list)| ReasonStats {
+                reason,
+                trades: list.len(),
+                avg_mfe_pct: avg(&|t| t.mfe_pct, &list),
+                avg_exit_pct: avg(&|t| t.gross_pnl_pct, &list),
+                avg_given_back_pct: avg(&|t| t.given_back_pct(), &list),
+                given_back_sol: list.iter().map(|t| t.given_back_sol()).sum(),
+            })
+            .collect();
+        by_reason.sort_by(|a, b| b.given_back_sol.total_cmp(&a.given_back_sol));
+
+        let mut regimes: HashMap<Option<RegimeKind>, Vec<&ClosedTrade>> = HashMap::new();
+        for trade in trades {
+            regimes.entry(trade.regime).or_default().push(trade);
+        }
+        let mut by_regime: Vec<RegimeStats> = regimes
+            .into_iter()
+            .map(|(regime, list)| RegimeStats {
+                regime,
+                trades: list.len(),
+                wins: list.iter().filter(|t| t.pnl_sol > 0.0).count(),
+                avg_pnl_pct: avg(&|t| t.pnl_pct, &list),
+                pnl_sol: list.iter().map(|t| t.pnl_sol).sum(),
+            })
+            .collect();
+        by_regime.sort_by(|a, b| a.pnl_sol.total_cmp(&b.pnl_sol));
+
+        let mut holds: Vec<i64> = trades.iter().map(|t| t.hold_secs()).collect();
+        holds.sort_unstable();
+        let hold_distribution = HOLD_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, &(upper, label))| {
+                let lower = if i == 0 { 0 } else { HOLD_BUCKETS[i - 1].0 };
+                let count = holds.iter().filter(|h| **h >= lower && **h < upper).count();
+                (label, count)
+            })
+            .collect();
+
+        Self {
+            trades: trades.len(),
+            tracked: tracked.len(),
+            avg_mfe_pct: avg(&|t| t.mfe_pct, &tracked),
+            avg_mae_pct: avg(&|t| t.mae_pct, &tracked),
+            avg_exit_pct: avg(&|t| t.gross_pnl_pct, &tracked),
+            avg_given_back_pct: avg(&|t| t.given_back_pct(), &tracked),
+            total_mfe_sol: tracked.iter().map(|t| t.mfe_sol.max(0.0)).sum(),
+            total_given_back_sol: tracked.iter().map(|t| t.given_back_sol()).sum(),
+            by_reason,
+            hold_distribution,
+            median_hold_secs: holds.get(holds.len() / 2).copied().unwrap_or(0),
+            by_regime,
+        }
+    }
+
+    /// Share of the average peak gain the exits captured (None if no peak gain)
+    pub fn capture_ratio(&self) -> Option<f64> {
+        if self.avg_mfe_pct > 0.0 {
+            Some(self.avg_exit_pct / self.avg_mfe_pct)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::fees::TradeFees;
+    use crate::position::manager::EntryType;
+
+    fn trade(reason: TradeExitReason, exit_price: f64, peak: f64, hold_secs: i64) -> ClosedTrade {
+        let entry_time = chrono::Utc::now();
+        let mut t = ClosedTrade {
+            mint: "mint".to_string(),
+            symbol: "SYM".to_string(),
+            entry_time,
+            exit_time: entry_time + chrono::Duration::seconds(hold_secs),
+            entry_price: 1.0,
+            exit_price,
+            tokens: 100,
+            cost_sol: 0.1,
+            proceeds_sol: 0.1 * exit_price,
+            entry_fees: TradeFees::default(),
+            exit_fees: TradeFees::default(),
+            fees_sol: 0.0,
+            gross_pnl_sol: 0.0,
+            gross_pnl_pct: 0.0,
+            pnl_sol: 0.0,
+            pnl_pct: 0.0,
+            entry_type: EntryType::Opportunity,
+            exit_reason: reason,
+            entry_signature: "sig".to_string(),
+            exit_signature: None,
+            peak_price: 0.0,
+            trough_price: 0.0,
+            mfe_pct: 0.0,
+            mfe_sol: 0.0,
+            mae_pct: 0.0,
+            mae_sol: 0.0,
+            regime: None,
+            strategy_profile: None,
+        };
+        t.recompute_pnl();
+        t.set_excursions(peak, 0.9);
+        t
+    }
+
+    #[test]
+    fn test_capture_and_reason_ranking() {
+        let trades = vec![
+            // Peak +100%, exit +40%: gave back 60 points
+            trade(TradeExitReason::TrailingStop, 1.4, 2.0, 45),
+            // Peak +50%, exit +50%: captured everything
+            trade(TradeExitReason::TakeProfit, 1.5, 1.5, 200),
+        ];
+        let stats = ExcursionStats::from_trades(&trades);
+
+        assert_eq!(stats.tracked, 2);
+        assert!((stats.avg_mfe_pct - 75.0).abs() < 1e-9);
+        assert!((stats.avg_exit_pct - 45.0).abs() < 1e-9);
+        assert!((stats.capture_ratio().unwrap() - 0.6).abs() < 1e-9);
+        assert!((stats.avg_mae_pct + 10.0).abs() < 1e-9);
+
+        assert_eq!(stats.by_reason[0].reason, TradeExitReason::TrailingStop);
+        assert!((stats.by_reason[0].given_back_sol - 0.06).abs() < 1e-9);
+        assert!(stats.by_reason[1].given_back_sol.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_hold_distribution_includes_untracked() {
+        let mut old = trade(TradeExitReason::Manual, 1.0, 1.0, 10);
+        old.peak_price = 0.0;
+        let trades = vec![old, trade(TradeExitReason::StopLoss, 0.7, 1.0, 4000)];
+        let stats = ExcursionStats::from_trades(&trades);
+
+        assert_eq!(stats.trades, 2);
+        assert_eq!(stats.tracked, 1);
+        assert_eq!(stats.hold_distribution[0], ("< 30s", 1));
+        assert_eq!(stats.hold_distribution[6], ("> 1h", 1));
+        assert_eq!(stats.capture_ratio(), None);
+    }
+
+    #[test]
+    fn test_by_regime() {
+        let mut organic_win = trade(TradeExitReason::TakeProfit, 1.5, 1.5, 60);
+        organic_win.regime = Some(RegimeKind::OrganicPump);
+        let mut organic_loss = trade(TradeExitReason::StopLoss, 0.9, 1.0, 60);
+        organic_loss.regime = Some(RegimeKind::OrganicPump);
+        let mut flip = trade(TradeExitReason::StopLoss, 0.5, 1.0, 60);
+        flip.regime = Some(RegimeKind::SniperFlip);
+        let untagged = trade(TradeExitReason::TakeProfit, 1.1, 1.1, 60);
+
+        let stats = ExcursionStats::from_trades(&[organic_win, organic_loss, flip, untagged]);
+
+        let rows: Vec<(&str, usize, usize)> = stats
+            .by_regime
+            .iter()
+            .map(|r| (r.label(), r.trades, r.wins))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("sniper_flip", 1, 0),
+                ("unrecorded", 1, 1),
+                ("organic_pump", 2, 1)
+            ]
+        );
+        assert!((stats.by_regime[2].win_rate_pct() - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,263 @@
+//! Portfolio snapshot for `snipe status`
+//!
+//! Marks the persisted open positions to the latest prices and sums the
+//! trades closed today against the daily loss limit. Built from state files
+//! and public price sources only, so it works with or without a running bot.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::run_state::RunState;
+
+use super::history::ClosedTrade;
+use super::manager::{DailyStats, EntryType, Position};
+use super::price_feed::PriceSource;
+
+/// One open position marked to market
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionStatus {
+    pub symbol: String,
+    pub mint: String,
+    pub entry_type: EntryType,
+    pub entry_price: f64,
+    /// None = no price from any source
+    pub current_price: Option<f64>,
+    pub price_source: Option<PriceSource>,
+    /// Highest price seen, including the current one
+    pub peak_price: f64,
+    pub cost_sol: f64,
+    pub pnl_sol: Option<f64>,
+    pub pnl_pct: Option<f64>,
+    pub hold_secs: i64,
+}
+
+impl PositionStatus {
+    /// First 8 characters of the mint
+    pub fn mint_short(&self) -> &str {
+        &self.mint[..8.min(self.mint.len())]
+    }
+}
+
+/// Open positions, totals and today's realized P&L
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusSnapshot {
+    pub generated_at: DateTime<Utc>,
+    pub positions: Vec<PositionStatus>,
+    pub total_cost_sol: f64,
+    /// Value of the positions that have a price
+    pub total_value_sol: f64,
+    /// Unrealized P&L of the positions that have a price
+    pub unrealized_pnl_sol: f64,
+    /// Positions without a price (left out of value and P&L)
+    pub unpriced: usize,
+    /// Trades closed in the current trading day
+    pub today: DailyStats,
+    pub daily_loss_limit_sol: f64,
+    /// The running bot, if one holds the run lock
+    pub instance: Option<RunState>,
+    /// SOL/USD price (None = no fresh price)
+    pub sol_usd: Option<f64>,
+}
+
+impl StatusSnapshot {
+    /// Mark `positions` to `prices` (by mint)
+    pub fn build(
+        positions: &[Position],
+        prices: &HashMap<String, (f64, PriceSource)>,
+        today: DailyStats,
+        daily_loss_limit_sol: f64,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut statuses: Vec<PositionStatus> = positions
+            .iter()
+            .map(|position| {
+                let price = prices.get(&position.mint).copied();
+                let marked = price.map(|(current, _)| {
+                    let mut marked = position.clone();
+                    marked.current_price = current;
+                    marked
+                });
+                PositionStatus {
+                    symbol: position.symbol.clone(),
+                    mint: position.mint.clone(),
+                    entry_type: position.entry_type,
+                    entry_price: position.entry_price,
+                    current_price: price.map(|(current, _)| current),
+                    price_source: price.map(|(_, source)| source),
+                    peak_price: price.map_or(position.peak_price, |(current, _)| {
+                        position.peak_price.max(current)
+                    }),
+                    cost_sol: position.total_cost_sol,
+                    pnl_sol: marked.as_ref().map(Position::unrealized_pnl),
+                    pnl_pct: marked.as_ref().map(Position::unrealized_pnl_pct),
+                    hold_secs: (now - position.entry_time).num_seconds().max(0),
+                }
+            })
+            .collect();
+        statuses.sort_by(|a, b| b.hold_secs.cmp(&a.hold_secs));
+
+        let priced = || statuses.iter().filter(|s| s.current_price.is_some());
+        let unrealized_pnl_sol: f64 = priced().filter_map(|s| s.pnl_sol).sum();
+        let total_value_sol: f64 = priced().map(|s| s.cost_sol).sum::<f64>() + unrealized_pnl_sol;
+        let unpriced = statuses.len() - priced().count();
+
+        Self {
+            generated_at: now,
+            total_cost_sol: statuses.iter().map(|s| s.cost_sol).sum(),
+            total_value_sol,
+            unrealized_pnl_sol,
+            unpriced,
+            positions: statuses,
+            today,
+            daily_loss_limit_sol,
+            instance: None,
+            sol_usd: None,
+        }
+    }
+
+    /// Share of the daily loss limit used today (1.0 = limit reached)
+    pub fn loss_limit_used(&self) -> f64 {
+        if self.daily_loss_limit_sol <= 0.0 {
+            return 0.0;
+        }
+        self.today.total_loss_sol / self.daily_loss_limit_sol
+    }
+
+    /// Loss limit utilization as a `[####------]  40%` bar
+    pub fn loss_limit_bar(&self, width: usize) -> String {
+        let used = self.loss_limit_used();
+        let filled = ((used.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+        format!(
+            "[{}{}] {:>3.0}%",
+            "#".repeat(filled),
+            "-".repeat(width - filled),
+            used * 100.0
+        )
+    }
+}
+
+/// Trades closed since `day_start`, as daily stats labelled `date`
+pub fn daily_stats(trades: &[ClosedTrade], day_start: DateTime<Utc>, date: String) -> DailyStats {
+    let mut stats = DailyStats {
+        date,
+        ..Default::default()
+    };
+    for trade in trades.iter().filter(|t| t.exit_time >= day_start) {
+        stats.record_trade(trade.pnl_sol);
+    }
+    stats
+}
+
+/// Hold time as `45s`, `12m05s` or `3h07m`
+pub fn format_hold(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{}h{:02}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::fees::TradeFees;
+    use crate::position::history::TradeExitReason;
+
+    fn position(mint: &str, entry_time: DateTime<Utc>, peak_price: f64) -> Position {
+        serde_json::from_value(serde_json::json!({
+            "mint": mint,
+            "name": mint,
+            "symbol": mint.to_uppercase(),
+            "bonding_curve": "",
+            "token_amount": 1_000_000,
+            "entry_price": 0.0000001,
+            "total_cost_sol": 0.1,
+            "entry_time": entry_time,
+            "entry_signature": "sig",
+            "peak_price": peak_price,
+        }))
+        .unwrap()
+    }
+
+    fn trade(exit_time: DateTime<Utc>, pnl_sol: f64) -> ClosedTrade {
+        ClosedTrade {
+            mint: "m".to_string(),
+            symbol: "M".to_string(),
+            entry_time: exit_time - chrono::Duration::seconds(60),
+            exit_time,
+            entry_price: 0.0,
+            exit_price: 0.0,
+            tokens: 0,
+            cost_sol: 0.1,
+            proceeds_sol: 0.1 + pnl_sol,
+            entry_fees: TradeFees::default(),
+            exit_fees: TradeFees::default(),
+            fees_sol: 0.0,
+            gross_pnl_sol: pnl_sol,
+            gross_pnl_pct: 0.0,
+            pnl_sol,
+            pnl_pct: 0.0,
+            entry_type: EntryType::Opportunity,
+            exit_reason: TradeExitReason::Manual,
+            entry_signature: String::new(),
+            exit_signature: None,
+            peak_price: 0.0,
+            trough_price: 0.0,
+            mfe_pct: 0.0,
+            mfe_sol: 0.0,
+            mae_pct: 0.0,
+            mae_sol: 0.0,
+            regime: None,
+            strategy_profile: None,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_marks_positions() {
+        let now = Utc::now();
+        let entry_time = now - chrono::Duration::seconds(90);
+        let positions = vec![
+            position("up", entry_time, 0.00000012),
+            position("dark", entry_time, 0.0000001),
+        ];
+        let prices = HashMap::from([("up".to_string(), (0.00000015, PriceSource::BondingCurve))]);
+        let today = daily_stats(
+            &[
+                trade(now - chrono::Duration::days(2), -5.0),
+                trade(now, -0.5),
+                trade(now, 0.2),
+            ],
+            now - chrono::Duration::hours(1),
+            "today".to_string(),
+        );
+        let snapshot = StatusSnapshot::build(&positions, &prices, today, 2.0, now);
+
+        let up = snapshot.positions.iter().find(|p| p.mint == "up").unwrap();
+        assert!((up.pnl_pct.unwrap() - 50.0).abs() < 1e-6);
+        assert!((up.pnl_sol.unwrap() - 0.05).abs() < 1e-9);
+        assert_eq!(up.peak_price, 0.00000015);
+        assert_eq!(up.hold_secs, 90);
+        let dark = snapshot
+            .positions
+            .iter()
+            .find(|p| p.mint == "dark")
+            .unwrap();
+        assert!(dark.pnl_sol.is_none());
+
+        assert_eq!(snapshot.unpriced, 1);
+        assert!((snapshot.total_cost_sol - 0.2).abs() < 1e-9);
+        assert!((snapshot.total_value_sol - 0.15).abs() < 1e-9);
+        // Only today's closes count against the limit
+        assert_eq!(snapshot.today.total_trades, 2);
+        assert!((snapshot.loss_limit_used() - 0.25).abs() < 1e-9);
+        assert_eq!(snapshot.loss_limit_bar(8), "[##------]  25%");
+    }
+
+    #[test]
+    fn test_format_hold() {
+        assert_eq!(format_hold(45), "45s");
+        assert_eq!(format_hold(725), "12m05s");
+        assert_eq!(format_hold(11_220), "3h07m");
+    }
+}
@@ -0,0 +1,610 @@
+//! Token prices from public price APIs
+//!
+//! DexScreener often lags a new pair for the first minutes after migration,
+//! which left the monitor on stale last-known prices. `CompositePriceSource`
+//! asks DexScreener, Birdeye and GeckoTerminal in `[price_sources]` order and
+//! falls through on not-found, errors, timeouts and prices older than
+//! `max_age_secs`. Each provider has its own rate limit and error budget: after
+//! `max_consecutive_failures` it is skipped for `failure_cooldown_secs`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::dexscreener::{DexScreenerClient, RateLimiter};
+use crate::position::PriceSource;
+
+const BIRDEYE_BASE: &str = "https://public-api.birdeye.so";
+const GECKOTERMINAL_BASE: &str = "https://api.geckoterminal.com/api/v2";
+
+/// Wrapped SOL, for converting Birdeye's USD prices to SOL
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// Price API settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceSourcesConfig {
+    /// Providers in the order they are asked (dexscreener, birdeye, geckoterminal)
+    #[serde(default = "default_order")]
+    pub order: Vec<PriceSource>,
+    /// Per-request timeout before falling through to the next provider
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Prices older than this fall through as well
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: u64,
+    /// Failures in a row before a provider is skipped
+    #[serde(default = "default_max_consecutive_failures")]
+    pub max_consecutive_failures: u32,
+    /// How long a provider is skipped once over its error budget
+    #[serde(default = "default_failure_cooldown_secs")]
+    pub failure_cooldown_secs: u64,
+    /// Birdeye is skipped without a key
+    #[serde(default)]
+    pub birdeye_api_key: String,
+    #[serde(default = "default_birdeye_rpm")]
+    pub birdeye_requests_per_minute: u32,
+    #[serde(default = "default_geckoterminal_rpm")]
+    pub geckoterminal_requests_per_minute: u32,
+}
+
+fn default_order() -> Vec<PriceSource> {
+    vec![
+        PriceSource::DexScreener,
+        PriceSource::Birdeye,
+        PriceSource::GeckoTerminal,
+    ]
+}
+
+fn default_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_max_age_secs() -> u64 {
+    60
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    5
+}
+
+fn default_failure_cooldown_secs() -> u64 {
+    60
+}
+
+fn default_birdeye_rpm() -> u32 {
+    60
+}
+
+fn default_geckoterminal_rpm() -> u32 {
+    30
+}
+
+impl Default for PriceSourcesConfig {
+    fn default() -> Self {
+        Self {
+            order: default_order(),
+            timeout_ms: default_timeout_ms(),
+            max_age_secs: default_max_age_secs(),
+            max_consecutive_failures: default_max_consecutive_failures(),
+            failure_cooldown_secs: default_failure_cooldown_secs(),
+            birdeye_api_key: String::new(),
+            birdeye_requests_per_minute: default_birdeye_rpm(),
+            geckoterminal_requests_per_minute: default_geckoterminal_rpm(),
+        }
+    }
+}
+
+/// A price and where it came from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SourcedPrice {
+    /// SOL per token
+    pub price: f64,
+    pub source: PriceSource,
+    /// When the provider last updated it (fetch time when it doesn't say)
+    pub as_of: DateTime<Utc>,
+}
+
+impl SourcedPrice {
+    pub fn new(price: f64, source: PriceSource) -> Self {
+        Self {
+            price,
+            source,
+            as_of: Utc::now(),
+        }
+    }
+
+    pub fn age_secs(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.as_of).num_seconds().max(0)
+    }
+}
+
+/// One price API
+#[async_trait]
+pub trait TokenPriceSource: Send + Sync {
+    fn source(&self) -> PriceSource;
+
+    /// Price of `mint` in SOL, or None if the provider doesn't know it
+    async fn price(&self, mint: &str) -> Result<Option<SourcedPrice>>;
+
+    /// Fetch several mints at once ahead of `price` calls, where supported
+    async fn prefetch(&self, _mints: &[String]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// DexScreener through the shared client (batched and cached)
+pub struct DexScreenerSource {
+    client: Arc<DexScreenerClient>,
+}
+
+impl DexScreenerSource {
+    pub fn new(client: Arc<DexScreenerClient>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl TokenPriceSource for DexScreenerSource {
+    fn source(&self) -> PriceSource {
+        PriceSource::DexScreener
+    }
+
+    async fn price(&self, mint: &str) -> Result<Option<SourcedPrice>> {
+        let info = self.client.get_token_info(mint).await?;
+        Ok(info
+            .filter(|t| t.price_native > 0.0)
+            .map(|t| SourcedPrice::new(t.price_native, PriceSource::DexScreener)))
+    }
+
+    async fn prefetch(&self, mints: &[String]) -> Result<()> {
+        self.client.get_tokens(mints).await.map(|_| ())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeResponse {
+    #[serde(default)]
+    data: HashMap<String, Option<BirdeyePrice>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyePrice {
+    value: f64,
+    #[serde(rename = "updateUnixTime")]
+    update_unix_time: Option<i64>,
+}
+
+/// Birdeye `multi_price`: the token and SOL in USD, in one request
+pub struct BirdeyeSource {
+    client: reqwest::Client,
+    api_key: String,
+    limiter: RateLimiter,
+}
+
+impl BirdeyeSource {
+    pub fn new(api_key: &str, requests_per_minute: u32) -> Self {
+        Self {
+            client: http_client(),
+            api_key: api_key.to_string(),
+            limiter: RateLimiter::new(requests_per_minute as f64, 1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceSource for BirdeyeSource {
+    fn source(&self) -> PriceSource {
+        PriceSource::Birdeye
+    }
+
+    async fn price(&self, mint: &str) -> Result<Option<SourcedPrice>> {
+        let url = format!(
+            "{}/defi/multi_price?list_address={},{}",
+            BIRDEYE_BASE, mint, WSOL_MINT
+        );
+        let request = self
+            .client
+            .get(&url)
+            .header("X-API-KEY", &self.api_key)
+            .header("x-chain", "solana");
+        let resp: BirdeyeResponse = send(&self.limiter, "Birdeye", request).await?;
+
+        let usd = |address: &str| resp.data.get(address).and_then(Option::as_ref);
+        let (Some(token), Some(sol)) = (usd(mint), usd(WSOL_MINT)) else {
+            return Ok(None);
+        };
+        if token.value <= 0.0 || sol.value <= 0.0 {
+            return Ok(None);
+        }
+        let as_of = token
+            .update_unix_time
+            .and_then(|t| Utc.timestamp_opt(t, 0).single())
+            .unwrap_or_else(Utc::now);
+        Ok(Some(SourcedPrice {
+            price: token.value / sol.value,
+            source: PriceSource::Birdeye,
+            as_of,
+        }))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPoolsResponse {
+    #[serde(default)]
+    data: Vec<GeckoPool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPool {
+    attributes: GeckoPoolAttributes,
+    relationships: GeckoPoolRelationships,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPoolAttributes {
+    base_token_price_native_currency: Option<String>,
+    quote_token_price_native_currency: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoPoolRelationships {
+    base_token: GeckoRelation,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoRelation {
+    data: GeckoRelationData,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeckoRelationData {
+    /// `solana_<mint>`
+    id: String,
+}
+
+/// GeckoTerminal: the native price in the token's top pool
+pub struct GeckoTerminalSource {
+    client: reqwest::Client,
+    limiter: RateLimiter,
+}
+
+impl GeckoTerminalSource {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            client: http_client(),
+            limiter: RateLimiter::new(requests_per_minute as f64, 1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenPriceSource for GeckoTerminalSource {
+    fn source(&self) -> PriceSource {
+        PriceSource::GeckoTerminal
+    }
+
+    async fn price(&self, mint: &str) -> Result<Option<SourcedPrice>> {
+        let url = format!(
+            "{}/networks/solana/tokens/{}/pools?page=1",
+            GECKOTERMINAL_BASE, mint
+        );
+        let request = self
+            .client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/json");
+        let resp: Option<GeckoPoolsResponse> =
+            send_optional(&self.limiter, "GeckoTerminal", request).await?;
+
+        let Some(pool) = resp.and_then(|r| r.data.into_iter().next()) else {
+            return Ok(None);
+        };
+        let is_base = pool.relationships.base_token.data.id == format!("solana_{}", mint);
+        let attributes = pool.attributes;
+        let price = if is_base {
+            attributes.base_token_price_native_currency
+        } else {
+            attributes.quote_token_price_native_currency
+        };
+        Ok(price
+            .and_then(|p| p.parse::<f64>().ok())
+            .filter(|p| *p > 0.0)
+            .map(|p| SourcedPrice::new(p, PriceSource::GeckoTerminal)))
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Send once `limiter` allows it; a 429 pauses the limiter
+async fn send<T: serde::de::DeserializeOwned>(
+    limiter: &RateLimiter,
+    name: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<T> {
+    send_optional(limiter, name, request)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("{} returned 404", name))
+}
+
+/// `send`, with 404 as None
+async fn send_optional<T: serde::de::DeserializeOwned>(
+    limiter: &RateLimiter,
+    name: &str,
+    request: reqwest::RequestBuilder,
+) -> Result<Option<T>> {
+    limiter.acquire().await?;
+    let resp = request.send().await?;
+    match resp.status() {
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = crate::dexscreener::retry_after(&resp);
+            limiter.block_at(Instant::now(), retry_after);
+            anyhow::bail!(
+                "{} rate limited (429), pausing {}s",
+                name,
+                retry_after.as_secs()
+            )
+        }
+        _ => Ok(Some(resp.error_for_status()?.json().await?)),
+    }
+}
+
+/// Consecutive failures of one provider, and the cooldown once over budget
+#[derive(Debug)]
+struct ErrorBudget {
+    max_failures: u32,
+    cooldown: Duration,
+    state: Mutex<(u32, Option<Instant>)>,
+}
+
+impl ErrorBudget {
+    fn new(max_failures: u32, cooldown: Duration) -> Self {
+        Self {
+            max_failures: max_failures.max(1),
+            cooldown,
+            state: Mutex::new((0, None)),
+        }
+    }
+
+    /// Whether the provider may be asked
+    fn allows_at(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match state.1 {
+            Some(until) if now < until => false,
+            Some(_) => {
+                // Cooldown over: one more failure skips it again
+                *state = (self.max_failures - 1, None);
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// Returns true when this failure used up the budget
+    fn record_at(&self, now: Instant, ok: bool) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if ok {
+            *state = (0, None);
+            return false;
+        }
+        state.0 += 1;
+        if state.0 >= self.max_failures && state.1.is_none() {
+            state.1 = Some(now + self.cooldown);
+            return true;
+        }
+        false
+    }
+}
+
+struct Provider {
+    source: Arc<dyn TokenPriceSource>,
+    budget: ErrorBudget,
+}
+
+/// Providers asked in priority order until one has a fresh price
+pub struct CompositePriceSource {
+    providers: Vec<Provider>,
+    timeout: Duration,
+    max_age_secs: i64,
+}
+
+impl CompositePriceSource {
+    /// The providers in `config.order` (Birdeye only with an API key)
+    pub fn new(config: &PriceSourcesConfig, dexscreener: Arc<DexScreenerClient>) -> Self {
+        let mut sources: Vec<Arc<dyn TokenPriceSource>> = Vec::new();
+        for source in &config.order {
+            match source {
+                PriceSource::DexScreener => {
+                    sources.push(Arc::new(DexScreenerSource::new(dexscreener.clone())))
+                }
+                PriceSource::Birdeye if config.birdeye_api_key.is_empty() => {
+                    debug!("Birdeye price source skipped: no price_sources.birdeye_api_key")
+                }
+                PriceSource::Birdeye => sources.push(Arc::new(BirdeyeSource::new(
+                    &config.birdeye_api_key,
+                    config.birdeye_requests_per_minute,
+                ))),
+                PriceSource::GeckoTerminal => sources.push(Arc::new(GeckoTerminalSource::new(
+                    config.geckoterminal_requests_per_minute,
+                ))),
+                PriceSource::BondingCurve => {
+                    warn!("price_sources.order: bonding_curve is not a price API, ignored")
+                }
+            }
+        }
+        Self::with_sources(config, sources)
+    }
+
+    pub fn with_sources(
+        config: &PriceSourcesConfig,
+        sources: Vec<Arc<dyn TokenPriceSource>>,
+    ) -> Self {
+        let cooldown = Duration::from_secs(config.failure_cooldown_secs);
+        Self {
+            providers: sources
+                .into_iter()
+                .map(|source| Provider {
+                    source,
+                    budget: ErrorBudget::new(config.max_consecutive_failures, cooldown),
+                })
+                .collect(),
+            timeout: Duration::from_millis(config.timeout_ms),
+            max_age_secs: config.max_age_secs as i64,
+        }
+    }
+
+    /// Providers in the order they are asked
+    pub fn sources(&self) -> Vec<PriceSource> {
+        self.providers.iter().map(|p| p.source.source()).collect()
+    }
+
+    /// Let providers that batch fetch all of `mints` ahead of `price`
+    pub async fn prefetch(&self, mints: &[String]) {
+        for provider in &self.providers {
+            if !provider.budget.allows_at(Instant::now()) {
+                continue;
+            }
+            let result = tokio::time::timeout(self.timeout, provider.source.prefetch(mints)).await;
+            if let Ok(Err(e)) = result {
+                debug!(
+                    "{} batch price lookup failed: {}",
+                    provider.source.source(),
+                    e
+                );
+            }
+        }
+    }
+
+    /// The first fresh price, or None if no provider has one
+    pub async fn price(&self, mint: &str) -> Option<SourcedPrice> {
+        for provider in &self.providers {
+            let name = provider.source.source();
+            if !provider.budget.allows_at(Instant::now()) {
+                continue;
+            }
+            let result = tokio::time::timeout(self.timeout, provider.source.price(mint)).await;
+            let failed = match result {
+                Ok(Ok(Some(price))) if price.age_secs(Utc::now()) <= self.max_age_secs => {
+                    provider.budget.record_at(Instant::now(), true);
+                    return Some(price);
+                }
+                Ok(Ok(Some(price))) => {
+                    debug!(
+                        "{} price for {} is {}s old, trying the next source",
+                        name,
+                        mint,
+                        price.age_secs(Utc::now())
+                    );
+                    false
+                }
+                Ok(Ok(None)) => {
+                    debug!("{} has no price for {}", name, mint);
+                    false
+                }
+                Ok(Err(e)) => {
+                    debug!("{} price lookup for {} failed: {}", name, mint, e);
+                    true
+                }
+                Err(_) => {
+                    debug!("{} price lookup for {} timed out", name, mint);
+                    true
+                }
+            };
+            if provider.budget.record_at(Instant::now(), !failed) {
+                info!(
+                    "{} price source failing, skipped for {}s",
+                    name,
+                    provider.budget.cooldown.as_secs()
+                );
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers from a fixed script, failing when it says so
+    struct Scripted {
+        source: PriceSource,
+        answer: Option<f64>,
+        fail: bool,
+        calls: Mutex<u32>,
+    }
+
+    impl Scripted {
+        fn new(source: PriceSource, answer: Option<f64>, fail: bool) -> Arc<Self> {
+            Arc::new(Self {
+                source,
+                answer,
+                fail,
+                calls: Mutex::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl TokenPriceSource for Scripted {
+        fn source(&self) -> PriceSource {
+            self.source
+        }
+
+        async fn price(&self, _mint: &str) -> Result<Option<SourcedPrice>> {
+            *self.calls.lock().unwrap() += 1;
+            if self.fail {
+                anyhow::bail!("down");
+            }
+            Ok(self.answer.map(|p| SourcedPrice::new(p, self.source)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_falls_through_in_order() {
+        let config = PriceSourcesConfig {
+            max_consecutive_failures: 2,
+            ..Default::default()
+        };
+        let dex = Scripted::new(PriceSource::DexScreener, None, false);
+        let birdeye = Scripted::new(PriceSource::Birdeye, None, true);
+        let gecko = Scripted::new(PriceSource::GeckoTerminal, Some(0.000002), false);
+        let sources: Vec<Arc<dyn TokenPriceSource>> = vec![dex.clone(), birdeye.clone(), gecko];
+        let composite = CompositePriceSource::with_sources(&config, sources);
+
+        let price = composite.price("mint").await.unwrap();
+        assert_eq!(price.source, PriceSource::GeckoTerminal);
+        assert_eq!(price.price, 0.000002);
+
+        // Birdeye goes over its error budget and is skipped; not-found doesn't count
+        composite.price("mint").await;
+        composite.price("mint").await;
+        assert_eq!(*birdeye.calls.lock().unwrap(), 2);
+        assert_eq!(*dex.calls.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_error_budget_cooldown() {
+        let budget = ErrorBudget::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+        assert!(!budget.record_at(now, false));
+        assert!(budget.record_at(now, false));
+        assert!(!budget.allows_at(now + Duration::from_secs(30)));
+
+        // After the cooldown one failure skips it again, a success resets
+        assert!(budget.allows_at(now + Duration::from_secs(61)));
+        assert!(budget.record_at(now + Duration::from_secs(61), false));
+        assert!(budget.allows_at(now + Duration::from_secs(200)));
+        budget.record_at(now + Duration::from_secs(200), true);
+        assert!(!budget.record_at(now + Duration::from_secs(201), false));
+    }
+}
@@ -5,11 +5,19 @@
 //! If deserialization fails, these structures may need updating.
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 
-use super::program::ACCOUNT_DISCRIMINATORS;
+use super::program::{
+    ACCOUNT_DISCRIMINATORS, BONDING_CURVE_SEED, INITIAL_REAL_TOKEN_RESERVES,
+    INITIAL_VIRTUAL_SOL_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES, PUMP_PROGRAM_ID,
+    TOKEN_TOTAL_SUPPLY,
+};
 use crate::error::{Error, Result};
 
+/// Accounts per getMultipleAccounts request (RPC limit)
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
 /// BondingCurve account - stores the bonding curve state for a token
 ///
 /// This account holds:
@@ -64,6 +72,23 @@ impl BondingCurve {
         }
     }
 
+    /// Reconstruct curve state from the virtual reserves in a trade/create event
+    ///
+    /// Real reserves are derived from the launch offsets, so this is exact for
+    /// curves created with the current Global parameters.
+    pub fn from_virtual_reserves(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> Self {
+        let sold_tokens = INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(virtual_token_reserves);
+        Self {
+            _discriminator: ACCOUNT_DISCRIMINATORS::BONDING_CURVE,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves: virtual_sol_reserves.saturating_sub(INITIAL_VIRTUAL_SOL_RESERVES),
+            real_token_reserves: INITIAL_REAL_TOKEN_RESERVES.saturating_sub(sold_tokens),
+            token_total_supply: TOKEN_TOTAL_SUPPLY,
+            complete: false,
+        }
+    }
+
     /// Deserialize from account data
     pub fn try_from_slice(data: &[u8]) -> Result<Self> {
         // Check minimum length
@@ -92,8 +117,48 @@ impl BondingCurve {
     }
 
     /// Deserialize without checking discriminator (for performance)
+    ///
+    /// Bytes after the known fields (the creator and padding of newer
+    /// accounts) are ignored.
     pub fn try_from_slice_unchecked(data: &[u8]) -> std::result::Result<Self, borsh::io::Error> {
-        BorshDeserialize::try_from_slice(data)
+        BorshDeserialize::deserialize(&mut &data[..])
+    }
+
+    /// Curve account address of `mint`
+    pub fn address(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[BONDING_CURVE_SEED, mint.as_ref()], &PUMP_PROGRAM_ID).0
+    }
+
+    /// Fetch and decode the live curve of `mint`
+    pub fn fetch(rpc: &RpcClient, mint: &Pubkey) -> Result<Self> {
+        let address = Self::address(mint);
+        let account = rpc
+            .get_account(&address)
+            .map_err(|e| Error::Rpc(format!("Failed to fetch bonding curve {}: {}", address, e)))?;
+        Self::try_from_slice(&account.data)
+    }
+
+    /// Curves of `mints` in order, batched through getMultipleAccounts
+    /// (None = account missing or not decodable)
+    pub fn fetch_many(rpc: &RpcClient, mints: &[Pubkey]) -> Result<Vec<Option<Self>>> {
+        let mut curves = Vec::with_capacity(mints.len());
+        for chunk in mints.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let addresses: Vec<Pubkey> = chunk.iter().map(Self::address).collect();
+            let accounts = rpc
+                .get_multiple_accounts(&addresses)
+                .map_err(|e| Error::Rpc(format!("Failed to fetch bonding curves: {}", e)))?;
+            curves.extend(
+                accounts
+                    .into_iter()
+                    .map(|account| account.and_then(|a| Self::try_from_slice(&a.data).ok())),
+            );
+        }
+        Ok(curves)
+    }
+
+    /// The curve is full and the token has migrated to its AMM pool
+    pub fn is_complete(&self) -> bool {
+        self.complete
     }
 
     /// Calculate current token price in SOL
@@ -245,6 +310,32 @@ mod tests {
         assert!((price - 0.03).abs() < 0.001);
     }
 
+    #[test]
+    fn test_decode_captured_account() {
+        use base64::Engine;
+
+        // getAccountInfo data of a mid-curve token in the current layout:
+        // the fields above, then the creator and zero padding
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(include_str!("fixtures/bonding_curve.b64").trim())
+            .unwrap();
+        assert!(data.len() > 49);
+
+        let curve = BondingCurve::try_from_slice(&data).unwrap();
+        assert_eq!(curve.virtual_sol_reserves, 42_512_812_345);
+        assert_eq!(curve.virtual_token_reserves, 757_210_444_123_456);
+        assert_eq!(curve.real_sol_reserves, 12_512_812_345);
+        assert_eq!(curve.real_token_reserves, 477_310_444_123_456);
+        assert_eq!(curve.token_total_supply, TOKEN_TOTAL_SUPPLY);
+        assert!(!curve.is_complete());
+
+        let mut complete = data.clone();
+        complete[48] = 1;
+        let curve = BondingCurve::try_from_slice(&complete).unwrap();
+        assert!(curve.is_complete());
+        assert!(BondingCurve::try_from_slice(&data[8..]).is_err());
+    }
+
     #[test]
     fn test_buy_calculation() {
         let curve = BondingCurve {
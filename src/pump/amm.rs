@@ -0,0 +1,434 @@
+//! PumpSwap (pump.fun AMM) pools
+//!
+//! A completed bonding curve migrates its token into a PumpSwap pool paired
+//! with wrapped SOL. The canonical pool of a mint is a PDA of the pool
+//! authority the pump.fun program uses for the migration, so it can be found
+//! from the mint alone. Pool reserves are the balances of its two token
+//! accounts; swaps are constant-product with LP, protocol and coin-creator
+//! fees from the AMM's global config.
+//!
+//! # WARNING: These layouts may change without notice
+//! Like the bonding curve, PumpSwap's accounts and instructions have grown
+//! fields over time. The builders below follow the layout with coin-creator
+//! fees.
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use std::str::FromStr;
+
+use super::price::{mul_div, BuyQuote, SellQuote};
+use super::program::{DISCRIMINATORS, PUMP_PROGRAM_ID};
+use crate::error::{Error, Result};
+
+/// PumpSwap program ID
+pub const PUMP_AMM_PROGRAM_ID_STR: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+
+/// Wrapped SOL mint, the quote side of every pump.fun pool
+pub const WSOL_MINT_STR: &str = "So11111111111111111111111111111111111111112";
+
+lazy_static::lazy_static! {
+    /// PumpSwap program ID as Pubkey
+    pub static ref PUMP_AMM_PROGRAM_ID: Pubkey =
+        Pubkey::from_str(PUMP_AMM_PROGRAM_ID_STR).expect("Invalid PumpSwap program ID");
+
+    /// Wrapped SOL mint as Pubkey
+    pub static ref WSOL_MINT: Pubkey = Pubkey::from_str(WSOL_MINT_STR).expect("Invalid WSOL mint");
+}
+
+/// PDA seeds
+const POOL_SEED: &[u8] = b"pool";
+const POOL_AUTHORITY_SEED: &[u8] = b"pool-authority";
+const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+const CREATOR_VAULT_SEED: &[u8] = b"creator_vault";
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Account discriminators (SHA-256("account:<Name>")[0..8])
+#[allow(non_snake_case)]
+pub mod AMM_ACCOUNT_DISCRIMINATORS {
+    pub const POOL: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+    pub const GLOBAL_CONFIG: [u8; 8] = [149, 8, 156, 202, 160, 252, 176, 217];
+}
+
+/// Pool account size up to and including `coin_creator`
+const POOL_LEN: usize = 243;
+
+/// Global config account size up to and including `coin_creator_fee_basis_points`
+const GLOBAL_CONFIG_LEN: usize = 321;
+
+/// Token account `amount` offset (after mint and owner)
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().expect("32 bytes"))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().expect("8 bytes"))
+}
+
+fn check_account(data: &[u8], discriminator: [u8; 8], len: usize, name: &str) -> Result<()> {
+    if data.len() < len {
+        return Err(Error::PoolDecode(format!(
+            "{} account too short: {} bytes",
+            name,
+            data.len()
+        )));
+    }
+    if data[..8] != discriminator {
+        return Err(Error::PoolDecode(format!("Wrong {} discriminator", name)));
+    }
+    Ok(())
+}
+
+/// PumpSwap pool account
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmmPool {
+    pub address: Pubkey,
+    pub index: u16,
+    pub creator: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    /// Receives the coin-creator fee (default = no creator fee)
+    pub coin_creator: Pubkey,
+}
+
+impl AmmPool {
+    pub fn parse(address: Pubkey, data: &[u8]) -> Result<Self> {
+        check_account(data, AMM_ACCOUNT_DISCRIMINATORS::POOL, POOL_LEN, "pool")?;
+        Ok(Self {
+            address,
+            index: u16::from_le_bytes([data[9], data[10]]),
+            creator: read_pubkey(data, 11),
+            base_mint: read_pubkey(data, 43),
+            quote_mint: read_pubkey(data, 75),
+            pool_base_token_account: read_pubkey(data, 139),
+            pool_quote_token_account: read_pubkey(data, 171),
+            coin_creator: read_pubkey(data, 211),
+        })
+    }
+
+    /// Pool the pump.fun migration creates for `mint`
+    pub fn canonical_address(mint: &Pubkey) -> Pubkey {
+        let authority =
+            Pubkey::find_program_address(&[POOL_AUTHORITY_SEED, mint.as_ref()], &PUMP_PROGRAM_ID).0;
+        Pubkey::find_program_address(
+            &[
+                POOL_SEED,
+                &0u16.to_le_bytes(),
+                authority.as_ref(),
+                mint.as_ref(),
+                WSOL_MINT.as_ref(),
+            ],
+            &PUMP_AMM_PROGRAM_ID,
+        )
+        .0
+    }
+}
+
+/// PumpSwap global config (fees and fee recipients)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmmGlobalConfig {
+    pub lp_fee_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub coin_creator_fee_bps: u64,
+    /// First configured protocol fee recipient
+    pub protocol_fee_recipient: Pubkey,
+}
+
+impl AmmGlobalConfig {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        check_account(
+            data,
+            AMM_ACCOUNT_DISCRIMINATORS::GLOBAL_CONFIG,
+            GLOBAL_CONFIG_LEN,
+            "global config",
+        )?;
+        let protocol_fee_recipient = (0..8)
+            .map(|i| read_pubkey(data, 57 + i * 32))
+            .find(|recipient| *recipient != Pubkey::default())
+            .ok_or_else(|| Error::PoolDecode("No protocol fee recipient".to_string()))?;
+        Ok(Self {
+            lp_fee_bps: read_u64(data, 40),
+            protocol_fee_bps: read_u64(data, 48),
+            coin_creator_fee_bps: read_u64(data, 313),
+            protocol_fee_recipient,
+        })
+    }
+
+    pub fn address() -> Pubkey {
+        Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &PUMP_AMM_PROGRAM_ID).0
+    }
+}
+
+/// Everything a swap against a pool needs, read in two getMultipleAccounts
+/// requests
+#[derive(Debug, Clone)]
+pub struct PoolState {
+    pub pool: AmmPool,
+    pub global: AmmGlobalConfig,
+    /// Raw token units in the pool
+    pub base_reserves: u64,
+    /// Lamports (wrapped SOL) in the pool
+    pub quote_reserves: u64,
+    /// Token program of the base mint (SPL Token or Token-2022)
+    pub base_token_program: Pubkey,
+}
+
+impl PoolState {
+    /// Fetch the canonical pool of `mint` with its reserves
+    pub fn fetch(rpc: &RpcClient, mint: &Pubkey) -> Result<Self> {
+        let address = AmmPool::canonical_address(mint);
+        let accounts = rpc
+            .get_multiple_accounts(&[address, AmmGlobalConfig::address()])
+            .map_err(|e| Error::Rpc(format!("Failed to fetch PumpSwap pool: {}", e)))?;
+        let [Some(pool), Some(global)] = [&accounts[0], &accounts[1]] else {
+            return Err(Error::PoolDecode(format!("No PumpSwap pool for {}", mint)));
+        };
+        let pool = AmmPool::parse(address, &pool.data)?;
+        let global = AmmGlobalConfig::parse(&global.data)?;
+
+        let accounts = rpc
+            .get_multiple_accounts(&[
+                pool.pool_base_token_account,
+                pool.pool_quote_token_account,
+                pool.base_mint,
+            ])
+            .map_err(|e| Error::Rpc(format!("Failed to fetch PumpSwap reserves: {}", e)))?;
+        let [Some(base), Some(quote), Some(base_mint)] = [&accounts[0], &accounts[1], &accounts[2]]
+        else {
+            return Err(Error::PoolDecode(format!(
+                "Missing reserve accounts of pool {}",
+                address
+            )));
+        };
+        if base.data.len() < TOKEN_AMOUNT_OFFSET + 8 || quote.data.len() < TOKEN_AMOUNT_OFFSET + 8 {
+            return Err(Error::PoolDecode("Reserve account too short".to_string()));
+        }
+        Ok(Self {
+            base_reserves: read_u64(&base.data, TOKEN_AMOUNT_OFFSET),
+            quote_reserves: read_u64(&quote.data, TOKEN_AMOUNT_OFFSET),
+            base_token_program: base_mint.owner,
+            pool,
+            global,
+        })
+    }
+
+    /// LP + protocol + coin-creator fee
+    pub fn fee_bps(&self) -> u64 {
+        let creator_fee = if self.pool.coin_creator == Pubkey::default() {
+            0
+        } else {
+            self.global.coin_creator_fee_bps
+        };
+        self.global.lp_fee_bps + self.global.protocol_fee_bps + creator_fee
+    }
+
+    /// Spot price in SOL per whole token (6 decimals)
+    pub fn price(&self) -> Result<f64> {
+        if self.base_reserves == 0 {
+            return Err(Error::PriceOverflow);
+        }
+        Ok(self.quote_reserves as f64 / self.base_reserves as f64 * 1e-3)
+    }
+
+    /// Buy with `quote_in` lamports, fees included (charged on top of what
+    /// the pool receives)
+    pub fn quote_buy(&self, quote_in: u64) -> Result<BuyQuote> {
+        if self.base_reserves == 0 || self.quote_reserves == 0 {
+            return Err(Error::PriceOverflow);
+        }
+        let net_in = mul_div(quote_in, 10_000, 10_000 + self.fee_bps())?;
+        let new_quote_reserves = self
+            .quote_reserves
+            .checked_add(net_in)
+            .ok_or(Error::PriceOverflow)?;
+        Ok(BuyQuote {
+            tokens_out: mul_div(net_in, self.base_reserves, new_quote_reserves)?,
+            price_impact_bps: mul_div(net_in, 10_000, self.quote_reserves)?,
+            fee_lamports: quote_in - net_in,
+        })
+    }
+
+    /// Sell `base_in` raw token units, fees deducted from the proceeds
+    pub fn quote_sell(&self, base_in: u64) -> Result<SellQuote> {
+        if self.base_reserves == 0 || self.quote_reserves == 0 {
+            return Err(Error::PriceOverflow);
+        }
+        let new_base_reserves = self
+            .base_reserves
+            .checked_add(base_in)
+            .ok_or(Error::PriceOverflow)?;
+        let gross_out = mul_div(base_in, self.quote_reserves, new_base_reserves)?;
+        let fee_lamports = mul_div(gross_out, self.fee_bps(), 10_000)?;
+        Ok(SellQuote {
+            sol_out: gross_out - fee_lamports,
+            price_impact_bps: mul_div(base_in, 10_000, new_base_reserves)?,
+            fee_lamports,
+        })
+    }
+
+    /// Swap accounts in the order the program expects
+    fn swap_accounts(&self, user: &Pubkey) -> Vec<AccountMeta> {
+        let pool = &self.pool;
+        let quote_program = spl_token::ID;
+        let fee_recipient = self.global.protocol_fee_recipient;
+        let event_authority =
+            Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &PUMP_AMM_PROGRAM_ID).0;
+        let creator_vault = Pubkey::find_program_address(
+            &[CREATOR_VAULT_SEED, pool.coin_creator.as_ref()],
+            &PUMP_AMM_PROGRAM_ID,
+        )
+        .0;
+        let ata = |owner: &Pubkey, mint: &Pubkey, program: &Pubkey| {
+            get_associated_token_address_with_program_id(owner, mint, program)
+        };
+        vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(AmmGlobalConfig::address(), false),
+            AccountMeta::new_readonly(pool.base_mint, false),
+            AccountMeta::new_readonly(pool.quote_mint, false),
+            AccountMeta::new(ata(user, &pool.base_mint, &self.base_token_program), false),
+            AccountMeta::new(ata(user, &pool.quote_mint, &quote_program), false),
+            AccountMeta::new(pool.pool_base_token_account, false),
+            AccountMeta::new(pool.pool_quote_token_account, false),
+            AccountMeta::new_readonly(fee_recipient, false),
+            AccountMeta::new(ata(&fee_recipient, &pool.quote_mint, &quote_program), false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(quote_program, false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+            AccountMeta::new_readonly(event_authority, false),
+            AccountMeta::new_readonly(*PUMP_AMM_PROGRAM_ID, false),
+            AccountMeta::new(ata(&creator_vault, &pool.quote_mint, &quote_program), false),
+            AccountMeta::new_readonly(creator_vault, false),
+        ]
+    }
+
+    /// Buy exactly `base_amount_out` tokens for at most `max_quote_amount_in`
+    /// wrapped SOL
+    pub fn buy_instruction(
+        &self,
+        user: &Pubkey,
+        base_amount_out: u64,
+        max_quote_amount_in: u64,
+    ) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+        data.extend_from_slice(&DISCRIMINATORS::BUY);
+        data.extend_from_slice(&base_amount_out.to_le_bytes());
+        data.extend_from_slice(&max_quote_amount_in.to_le_bytes());
+        Instruction {
+            program_id: *PUMP_AMM_PROGRAM_ID,
+            accounts: self.swap_accounts(user),
+            data,
+        }
+    }
+
+    /// Sell `base_amount_in` tokens for at least `min_quote_amount_out`
+    /// wrapped SOL
+    pub fn sell_instruction(
+        &self,
+        user: &Pubkey,
+        base_amount_in: u64,
+        min_quote_amount_out: u64,
+    ) -> Instruction {
+        let mut data = Vec::with_capacity(24);
+        data.extend_from_slice(&DISCRIMINATORS::SELL);
+        data.extend_from_slice(&base_amount_in.to_le_bytes());
+        data.extend_from_slice(&min_quote_amount_out.to_le_bytes());
+        Instruction {
+            program_id: *PUMP_AMM_PROGRAM_ID,
+            accounts: self.swap_accounts(user),
+            data,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_data(coin_creator: Pubkey) -> Vec<u8> {
+        let mut data = AMM_ACCOUNT_DISCRIMINATORS::POOL.to_vec();
+        data.push(254); // bump
+        data.extend_from_slice(&0u16.to_le_bytes());
+        for _ in 0..6 {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+        }
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // lp supply
+        data.extend_from_slice(coin_creator.as_ref());
+        data
+    }
+
+    fn state(coin_creator: Pubkey) -> PoolState {
+        PoolState {
+            pool: AmmPool::parse(Pubkey::new_unique(), &pool_data(coin_creator)).unwrap(),
+            global: AmmGlobalConfig {
+                lp_fee_bps: 20,
+                protocol_fee_bps: 5,
+                coin_creator_fee_bps: 5,
+                protocol_fee_recipient: Pubkey::new_unique(),
+            },
+            // 80 SOL against 200M tokens: 4e-7 SOL per token
+            base_reserves: 200_000_000_000_000,
+            quote_reserves: 80_000_000_000,
+            base_token_program: spl_token::ID,
+        }
+    }
+
+    #[test]
+    fn test_discriminators() {
+        use sha2::{Digest, Sha256};
+        let account = |name: &str| -> [u8; 8] {
+            Sha256::digest(format!("account:{}", name).as_bytes())[..8]
+                .try_into()
+                .unwrap()
+        };
+        assert_eq!(account("Pool"), AMM_ACCOUNT_DISCRIMINATORS::POOL);
+        assert_eq!(
+            account("GlobalConfig"),
+            AMM_ACCOUNT_DISCRIMINATORS::GLOBAL_CONFIG
+        );
+    }
+
+    #[test]
+    fn test_parse_pool() {
+        let creator = Pubkey::new_unique();
+        let data = pool_data(creator);
+        assert_eq!(data.len(), POOL_LEN);
+        let pool = AmmPool::parse(Pubkey::new_unique(), &data).unwrap();
+        assert_eq!(pool.coin_creator, creator);
+        assert_eq!(pool.base_mint, read_pubkey(&data, 43));
+        assert!(AmmPool::parse(pool.address, &data[..POOL_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn test_swap_quotes() {
+        let with_creator = state(Pubkey::new_unique());
+        assert_eq!(with_creator.fee_bps(), 30);
+        assert_eq!(state(Pubkey::default()).fee_bps(), 25);
+        assert!((with_creator.price().unwrap() - 4e-7).abs() < 1e-12);
+
+        let buy = with_creator.quote_buy(1_000_000_000).unwrap();
+        // 1 SOL of 80 in the pool: ~1.2% impact, 0.3% fee
+        assert_eq!(buy.fee_lamports, 2_991_027);
+        assert_eq!(buy.price_impact_bps, 124);
+        assert!(buy.tokens_out < 2_500_000_000_000);
+
+        // Selling what was bought returns less than was paid
+        let sell = with_creator.quote_sell(buy.tokens_out).unwrap();
+        assert!(sell.sol_out < 1_000_000_000);
+        assert_eq!(
+            sell.fee_lamports,
+            (sell.sol_out + sell.fee_lamports) * 30 / 10_000
+        );
+
+        let ix = with_creator.sell_instruction(&Pubkey::new_unique(), buy.tokens_out, sell.sol_out);
+        assert_eq!(ix.accounts.len(), 19);
+        assert_eq!(ix.data[..8], DISCRIMINATORS::SELL);
+    }
+}
@@ -0,0 +1,338 @@
+//! Pump.fun program drift detection
+//!
+//! pump.fun upgrades its program without notice, and a new version can move
+//! instruction layouts or discriminators out from under the decoder. The
+//! check hashes the deployed program's executable data and compares it with
+//! `[pump_program].known_hash`, then looks for a create among the program's
+//! recent transactions and decodes it with our `DISCRIMINATORS`.
+//! `snipe start` runs it at startup and every `check_interval_secs`; while
+//! a check fails, new entries are paused (open positions are still sold).
+//! `snipe pump accept-current` writes the deployed hash to config.toml once
+//! the new version has been reviewed.
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::bpf_loader_upgradeable::UpgradeableLoaderState;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+use std::fmt;
+use std::str::FromStr;
+use tracing::debug;
+
+use super::instruction::PumpInstruction;
+use super::program::PUMP_PROGRAM_ID;
+use crate::error::{Error, Result};
+
+/// Anchor logs every instruction by name; `create` and any newer variant
+/// (`create_v2`) start with this
+const CREATE_LOG_PREFIX: &str = "Program log: Instruction: Create";
+
+/// `[pump_program]` configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct PumpProgramConfig {
+    /// Check the deployed program before and while trading
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// SHA-256 of the reviewed program's executable data (empty = not
+    /// pinned: the hash is logged but not compared)
+    #[serde(default)]
+    pub known_hash: String,
+    /// Seconds between checks after the startup one (0 = startup only)
+    #[serde(default = "default_check_interval_secs")]
+    pub check_interval_secs: u64,
+    /// Recent program transactions searched for a create
+    #[serde(default = "default_recent_transactions")]
+    pub recent_transactions: usize,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_check_interval_secs() -> u64 {
+    900
+}
+
+fn default_recent_transactions() -> usize {
+    50
+}
+
+impl Default for PumpProgramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            known_hash: String::new(),
+            check_interval_secs: default_check_interval_secs(),
+            recent_transactions: default_recent_transactions(),
+        }
+    }
+}
+
+/// A create found among the program's recent transactions
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateSample {
+    pub signature: String,
+    /// Our discriminators decoded it as a create
+    pub decoded: bool,
+}
+
+/// Result of one drift check
+#[derive(Debug, Clone)]
+pub struct DriftReport {
+    /// SHA-256 of the deployed program's executable data
+    pub program_hash: String,
+    /// None = no top-level create among the recent transactions
+    pub create: Option<CreateSample>,
+    /// What no longer matches (empty = the program looks unchanged)
+    pub problems: Vec<String>,
+}
+
+impl DriftReport {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_ok() {
+            return write!(f, "{}", self.problems.join("; "));
+        }
+        write!(f, "program {}", &self.program_hash[..16])?;
+        match self.create {
+            Some(ref create) => write!(f, ", create {} decoded", &create.signature[..16]),
+            None => write!(f, ", no recent create to decode"),
+        }
+    }
+}
+
+/// Hash the executable data of the deployed pump.fun program
+pub fn program_hash(rpc: &RpcClient) -> Result<String> {
+    let program = rpc
+        .get_account(&PUMP_PROGRAM_ID)
+        .map_err(|e| Error::Rpc(format!("Failed to fetch pump program: {}", e)))?;
+    let programdata_address = match bincode::deserialize(&program.data) {
+        Ok(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) => programdata_address,
+        _ => {
+            return Err(Error::Deserialization(
+                "pump program is not an upgradeable program".to_string(),
+            ))
+        }
+    };
+    let programdata = rpc
+        .get_account(&programdata_address)
+        .map_err(|e| Error::Rpc(format!("Failed to fetch pump program data: {}", e)))?;
+    let executable = programdata
+        .data
+        .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+        .ok_or_else(|| Error::Deserialization("pump program data too short".to_string()))?;
+    Ok(format!("{:x}", Sha256::digest(executable)))
+}
+
+/// Decode the top-level pump.fun instructions of a create transaction
+///
+/// Some(true) when one decodes as a create, Some(false) when none does and
+/// one has a discriminator we don't know, None when the create was made
+/// through another program (CPI) and only known instructions are left.
+fn decode_create(tx: &VersionedTransaction) -> Option<bool> {
+    let keys = tx.message.static_account_keys();
+    let parsed: Vec<Result<PumpInstruction>> = tx
+        .message
+        .instructions()
+        .iter()
+        .filter(|ix| keys.get(ix.program_id_index as usize) == Some(&PUMP_PROGRAM_ID))
+        .map(|ix| PumpInstruction::parse(&ix.data))
+        .collect();
+    if parsed
+        .iter()
+        .any(|p| matches!(p, Ok(PumpInstruction::Create(_))))
+    {
+        return Some(true);
+    }
+    parsed
+        .iter()
+        .any(|p| !matches!(p, Ok(PumpInstruction::Buy(_) | PumpInstruction::Sell(_))))
+        .then_some(false)
+}
+
+/// Most recent create among the program's last `limit` transactions
+///
+/// Creates made through another program (CPI) are skipped: only top-level
+/// instructions are decoded.
+pub fn find_recent_create(rpc: &RpcClient, limit: usize) -> Result<Option<CreateSample>> {
+    let signatures = rpc
+        .get_signatures_for_address_with_config(
+            &PUMP_PROGRAM_ID,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(limit),
+                commitment: Some(CommitmentConfig::confirmed()),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| Error::Rpc(format!("Failed to fetch pump signatures: {}", e)))?;
+
+    for status in signatures.iter().filter(|s| s.err.is_none()) {
+        let Ok(signature) = Signature::from_str(&status.signature) else {
+            continue;
+        };
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let Ok(tx) = rpc.get_transaction_with_config(&signature, config) else {
+            debug!("Could not fetch {} for the drift check", status.signature);
+            continue;
+        };
+        let is_create = match tx.transaction.meta.map(|m| m.log_messages) {
+            Some(OptionSerializer::Some(logs)) => {
+                logs.iter().any(|l| l.starts_with(CREATE_LOG_PREFIX))
+            }
+            _ => false,
+        };
+        if !is_create {
+            continue;
+        }
+        let Some(decoded) = tx
+            .transaction
+            .transaction
+            .decode()
+            .and_then(|tx| decode_create(&tx))
+        else {
+            continue;
+        };
+        return Ok(Some(CreateSample {
+            signature: status.signature.clone(),
+            decoded,
+        }));
+    }
+    Ok(None)
+}
+
+/// Hash the deployed program and decode a recent create
+pub fn check(rpc: &RpcClient, config: &PumpProgramConfig) -> Result<DriftReport> {
+    let program_hash = program_hash(rpc)?;
+    let create = find_recent_create(rpc, config.recent_transactions)?;
+
+    let mut problems = Vec::new();
+    if !config.known_hash.is_empty() && !config.known_hash.eq_ignore_ascii_case(&program_hash) {
+        problems.push(format!(
+            "pump.fun program changed: hash {} (known good {})",
+            &program_hash[..16],
+            &config.known_hash[..16.min(config.known_hash.len())]
+        ));
+    }
+    if let Some(ref create) = create {
+        if !create.decoded {
+            problems.push(format!(
+                "create {} does not decode with our discriminators",
+                create.signature
+            ));
+        }
+    }
+    Ok(DriftReport {
+        program_hash,
+        create,
+        problems,
+    })
+}
+
+/// `config` (the text of config.toml) with `[pump_program].known_hash` set
+/// to `hash`, adding the key or the section when missing
+pub fn set_known_hash(config: &str, hash: &str) -> String {
+    let entry = format!("known_hash = \"{}\"", hash);
+    let mut lines: Vec<String> = config.lines().map(str::to_string).collect();
+    let Some(header) = lines.iter().position(|l| l.trim() == "[pump_program]") else {
+        let mut text = config.trim_end().to_string();
+        text.push_str(&format!("\n\n[pump_program]\n{}\n", entry));
+        return text;
+    };
+    let end = lines[header + 1..]
+        .iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .map_or(lines.len(), |i| header + 1 + i);
+    let existing = (header + 1..end).find(|&i| {
+        let line = lines[i].trim_start();
+        line.strip_prefix("known_hash")
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+    match existing {
+        Some(i) => lines[i] = entry,
+        None => lines.insert(header + 1, entry),
+    }
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pump::program::DISCRIMINATORS;
+    use solana_sdk::instruction::{AccountMeta, Instruction};
+    use solana_sdk::message::{v0, VersionedMessage};
+    use solana_sdk::pubkey::Pubkey;
+
+    fn transaction(data: Vec<u8>) -> VersionedTransaction {
+        let payer = Pubkey::new_unique();
+        let ix = Instruction::new_with_bytes(
+            *PUMP_PROGRAM_ID,
+            &data,
+            vec![AccountMeta::new(payer, true)],
+        );
+        let message = v0::Message::try_compile(&payer, &[ix], &[], Default::default()).unwrap();
+        VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(message),
+        }
+    }
+
+    #[test]
+    fn test_decodes_create() {
+        let mut data = DISCRIMINATORS::CREATE.to_vec();
+        for field in ["Test", "TEST", "https://example.com/meta.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        assert_eq!(decode_create(&transaction(data.clone())), Some(true));
+
+        // Same arguments behind a discriminator we don't know
+        data[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(decode_create(&transaction(data)), Some(false));
+
+        // Buy next to a create made through another program
+        let mut buy = DISCRIMINATORS::BUY.to_vec();
+        buy.extend_from_slice(&[0; 16]);
+        assert_eq!(decode_create(&transaction(buy)), None);
+    }
+
+    #[test]
+    fn test_set_known_hash() {
+        let config = "[rpc]\nendpoint = \"x\"\n";
+        let added = set_known_hash(config, "aa");
+        assert!(added.ends_with("[pump_program]\nknown_hash = \"aa\"\n"));
+
+        let replaced = set_known_hash(
+            "[pump_program]\nenabled = true\nknown_hash = \"aa\"\n\n[rpc]\nknown_hash = 1\n",
+            "bb",
+        );
+        assert_eq!(
+            replaced,
+            "[pump_program]\nenabled = true\nknown_hash = \"bb\"\n\n[rpc]\nknown_hash = 1\n"
+        );
+
+        let inserted = set_known_hash("[pump_program]\nenabled = true\n", "cc");
+        assert_eq!(
+            inserted,
+            "[pump_program]\nknown_hash = \"cc\"\nenabled = true\n"
+        );
+    }
+}
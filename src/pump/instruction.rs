@@ -1,10 +1,18 @@
 //! Pump.fun instruction parsing
 //!
-//! Parses pump.fun instructions from transaction data.
+//! Parses pump.fun instructions from transaction data. `parse_transaction`
+//! turns a confirmed transaction into typed events, including instructions
+//! reached through another program (routers, bundlers), with buy and sell
+//! amounts taken from the trade event the program emits.
 
 use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiInstruction};
+use std::str::FromStr;
 
-use super::program::{match_discriminator, InstructionType};
+use super::program::{
+    match_discriminator, InstructionType, EVENT_DISCRIMINATORS, EVENT_IX_TAG, PUMP_PROGRAM_ID,
+};
 use crate::error::{Error, Result};
 
 /// Parsed pump.fun instruction
@@ -291,6 +299,276 @@ impl SellAccounts {
     }
 }
 
+/// What a pump.fun instruction in a confirmed transaction did
+#[derive(Debug, Clone, PartialEq)]
+pub enum PumpEventKind {
+    Create {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// `sol_amount` (fee excluded) and `token_amount` come from the trade
+    /// event; without one, `token_amount` is the instruction's and
+    /// `sol_amount` is None
+    Buy {
+        token_amount: u64,
+        sol_amount: Option<u64>,
+        max_sol_cost: u64,
+    },
+    Sell {
+        token_amount: u64,
+        sol_amount: Option<u64>,
+        min_sol_output: u64,
+    },
+    /// Liquidity withdrawn from a completed curve for migration
+    Withdraw,
+    /// Global parameters changed by the program authority
+    SetParams,
+}
+
+/// One pump.fun instruction of a confirmed transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpEvent {
+    pub kind: PumpEventKind,
+    /// None for SetParams
+    pub mint: Option<Pubkey>,
+    /// Trader, creator or authority the instruction acts for
+    pub user: Pubkey,
+    /// `user` signed the transaction (false when a program signed for it)
+    pub user_signed: bool,
+    /// Transaction fee payer
+    pub fee_payer: Pubkey,
+    /// Top-level instruction this is, or was called from
+    pub instruction_index: usize,
+    /// Reached through another program (CPI)
+    pub inner: bool,
+}
+
+/// Instruction with its accounts resolved
+struct RawInstruction {
+    program: Pubkey,
+    accounts: Vec<Pubkey>,
+    data: Vec<u8>,
+    inner: bool,
+}
+
+/// Amounts from a buy or sell's `TradeEvent`
+struct TradeEvent {
+    mint: Pubkey,
+    sol_amount: u64,
+    token_amount: u64,
+    is_buy: bool,
+    user: Pubkey,
+}
+
+impl TradeEvent {
+    /// Parse a self-CPI event instruction (None for any other event)
+    fn parse(data: &[u8]) -> Option<Self> {
+        let body = data
+            .strip_prefix(&EVENT_IX_TAG)?
+            .strip_prefix(&EVENT_DISCRIMINATORS::TRADE)?;
+        if body.len() < 81 {
+            return None;
+        }
+        let u64_at = |offset: usize| {
+            u64::from_le_bytes(body[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        Some(Self {
+            mint: Pubkey::try_from(&body[0..32]).ok()?,
+            sol_amount: u64_at(32),
+            token_amount: u64_at(40),
+            is_buy: body[48] != 0,
+            user: Pubkey::try_from(&body[49..81]).ok()?,
+        })
+    }
+}
+
+/// Kind, mint and user of a pump.fun instruction (None if not one we track)
+fn decode_instruction(ix: &RawInstruction) -> Option<(PumpEventKind, Option<Pubkey>, Pubkey)> {
+    let account = |i: usize| ix.accounts.get(i).copied();
+    let args = ix.data.get(8..)?;
+    match match_discriminator(&ix.data)? {
+        InstructionType::Create => {
+            let create = CreateInstruction::parse(args).ok()?;
+            let kind = PumpEventKind::Create {
+                name: create.name,
+                symbol: create.symbol,
+                uri: create.uri,
+            };
+            Some((kind, account(0), account(7)?))
+        }
+        InstructionType::Buy => {
+            let buy = BuyInstruction::parse(args).ok()?;
+            let kind = PumpEventKind::Buy {
+                token_amount: buy.amount,
+                sol_amount: None,
+                max_sol_cost: buy.max_sol_cost,
+            };
+            Some((kind, account(2), account(6)?))
+        }
+        InstructionType::Sell => {
+            let sell = SellInstruction::parse(args).ok()?;
+            let kind = PumpEventKind::Sell {
+                token_amount: sell.amount,
+                sol_amount: None,
+                min_sol_output: sell.min_sol_output,
+            };
+            Some((kind, account(2), account(6)?))
+        }
+        InstructionType::Withdraw => Some((PumpEventKind::Withdraw, account(2), account(6)?)),
+        InstructionType::SetParams => Some((PumpEventKind::SetParams, None, account(1)?)),
+        InstructionType::Initialize => None,
+    }
+}
+
+/// Take the amounts of a buy or sell from its trade event
+fn apply_trade_event(kind: &mut PumpEventKind, event: &TradeEvent) {
+    if let PumpEventKind::Buy {
+        token_amount,
+        sol_amount,
+        ..
+    }
+    | PumpEventKind::Sell {
+        token_amount,
+        sol_amount,
+        ..
+    } = kind
+    {
+        *token_amount = event.token_amount;
+        *sol_amount = Some(event.sol_amount);
+    }
+}
+
+/// Decode every pump.fun instruction of a confirmed transaction, top-level
+/// and inner, in execution order
+///
+/// The transaction must be fetched with a binary encoding (base64 or
+/// base58) and, for v0 transactions, `max_supported_transaction_version`.
+/// Failed transactions have no events.
+pub fn parse_transaction(tx: &EncodedConfirmedTransactionWithStatusMeta) -> Vec<PumpEvent> {
+    let Some(ref meta) = tx.transaction.meta else {
+        return Vec::new();
+    };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+    let Some(decoded) = tx.transaction.transaction.decode() else {
+        return Vec::new();
+    };
+    let message = &decoded.message;
+
+    // Static keys, then addresses loaded from lookup tables
+    let mut keys = message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(ref loaded) = meta.loaded_addresses {
+        keys.extend(
+            loaded
+                .writable
+                .iter()
+                .chain(&loaded.readonly)
+                .map(|key| Pubkey::from_str(key).unwrap_or_default()),
+        );
+    }
+    let signer_count = message.header().num_required_signatures as usize;
+    let signers = &keys[..signer_count.min(keys.len())];
+    if signers.is_empty() {
+        return Vec::new();
+    }
+    let resolve = |program_index: u8, accounts: &[u8], data: Vec<u8>, inner: bool| {
+        Some(RawInstruction {
+            program: *keys.get(program_index as usize)?,
+            accounts: accounts
+                .iter()
+                .map(|&i| keys.get(i as usize).copied())
+                .collect::<Option<Vec<_>>>()?,
+            data,
+            inner,
+        })
+    };
+
+    let inner_instructions = match meta.inner_instructions {
+        OptionSerializer::Some(ref inner) => inner.as_slice(),
+        _ => &[],
+    };
+    let mut events = Vec::new();
+    for (index, top) in message.instructions().iter().enumerate() {
+        let mut sequence: Vec<RawInstruction> = Vec::new();
+        sequence.extend(resolve(
+            top.program_id_index,
+            &top.accounts,
+            top.data.clone(),
+            false,
+        ));
+        let called = inner_instructions
+            .iter()
+            .filter(|group| group.index as usize == index)
+            .flat_map(|group| &group.instructions);
+        for instruction in called {
+            let UiInstruction::Compiled(ref compiled) = instruction else {
+                continue;
+            };
+            let Ok(data) = bs58::decode(&compiled.data).into_vec() else {
+                continue;
+            };
+            sequence.extend(resolve(
+                compiled.program_id_index,
+                &compiled.accounts,
+                data,
+                true,
+            ));
+        }
+        events.extend(decode_sequence(&sequence, index, signers));
+    }
+    events
+}
+
+/// Pump.fun instructions of top-level instruction `index` and what it called
+fn decode_sequence(
+    sequence: &[RawInstruction],
+    index: usize,
+    signers: &[Pubkey],
+) -> Vec<PumpEvent> {
+    let pump: Vec<&RawInstruction> = sequence
+        .iter()
+        .filter(|ix| ix.program == *PUMP_PROGRAM_ID)
+        .collect();
+    let mut used = vec![false; pump.len()];
+    let mut decoded = Vec::new();
+    for (position, ix) in pump.iter().enumerate() {
+        let Some((mut kind, mint, user)) = decode_instruction(ix) else {
+            continue;
+        };
+        let is_buy = matches!(kind, PumpEventKind::Buy { .. });
+        if is_buy || matches!(kind, PumpEventKind::Sell { .. }) {
+            // The trade event follows its instruction as a self-CPI
+            let event = pump
+                .iter()
+                .enumerate()
+                .skip(position + 1)
+                .find_map(|(i, ix)| {
+                    let event = TradeEvent::parse(&ix.data)?;
+                    let matches = !used[i] && Some(event.mint) == mint && event.is_buy == is_buy;
+                    matches.then_some((i, event))
+                });
+            if let Some((i, event)) = event {
+                used[i] = true;
+                if event.user == user {
+                    apply_trade_event(&mut kind, &event);
+                }
+            }
+        }
+        decoded.push(PumpEvent {
+            kind,
+            mint,
+            user,
+            user_signed: signers.contains(&user),
+            fee_payer: signers[0],
+            instruction_index: index,
+            inner: ix.inner,
+        });
+    }
+    decoded
+}
+
 /// Helper function to read a borsh-encoded string
 fn read_string(data: &[u8], offset: &mut usize) -> Result<String> {
     if *offset + 4 > data.len() {
@@ -350,4 +628,121 @@ mod tests {
 
         assert!(matches!(instruction, PumpInstruction::Unknown(_)));
     }
+
+    // The fixtures/pump_*.json transactions are hand-built, not captured from
+    // mainnet: they pin the decoding of each instruction layout, inner (CPI)
+    // instructions and signer flags, but not real account orders, balance
+    // changes or layout drift. They are to be replaced by confirmed mainnet
+    // transactions (`getTransaction`, json encoding) with their signatures.
+    fn fixture(json: &str) -> EncodedConfirmedTransactionWithStatusMeta {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn key(address: &str) -> Pubkey {
+        Pubkey::from_str(address).unwrap()
+    }
+
+    const CREATOR: &str = "3TzddA8xcMD2N2QwWWtXQSY9RE62ffz7RAzX16Lsbyqx";
+    const MINT: &str = "ZPP7SiG7LGHXFNKBEfoyKDkCNvzmAs7ecqTitE4PuCP";
+    const TRADER: &str = "Bsz8gTcuut8FowdhiNvsGVh27kGm61TN8vec5HWCMD7h";
+
+    #[test]
+    fn test_parse_create_and_dev_buy() {
+        let events = parse_transaction(&fixture(include_str!("fixtures/pump_create.json")));
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(
+            events[0].kind,
+            PumpEventKind::Create {
+                name: "Fixture Cat".to_string(),
+                symbol: "FCAT".to_string(),
+                uri: "https://ipfs.io/ipfs/QmFixtureCatMetadata".to_string(),
+            }
+        );
+        assert_eq!(events[0].mint, Some(key(MINT)));
+        assert_eq!(events[0].user, key(CREATOR));
+        assert!(events[0].user_signed);
+        assert_eq!(events[0].instruction_index, 1);
+        assert!(!events[0].inner);
+
+        assert_eq!(
+            events[1].kind,
+            PumpEventKind::Buy {
+                token_amount: 34_612_903_225_806,
+                sol_amount: Some(1_000_000_000),
+                max_sol_cost: 1_050_000_000,
+            }
+        );
+        assert_eq!(events[1].user, key(CREATOR));
+        assert_eq!(events[1].instruction_index, 2);
+    }
+
+    #[test]
+    fn test_parse_buy_through_cpi() {
+        let events = parse_transaction(&fixture(include_str!("fixtures/pump_buy_cpi.json")));
+        assert_eq!(events.len(), 1);
+
+        let buy = &events[0];
+        assert_eq!(
+            buy.kind,
+            PumpEventKind::Buy {
+                token_amount: 7_981_334_112_045,
+                sol_amount: Some(250_000_000),
+                max_sol_cost: 275_000_000,
+            }
+        );
+        assert_eq!(buy.mint, Some(key(MINT)));
+        assert_eq!(buy.user, key(TRADER));
+        assert!(buy.user_signed);
+        assert_eq!(buy.fee_payer, key(TRADER));
+        assert_eq!(buy.instruction_index, 1);
+        assert!(buy.inner);
+    }
+
+    #[test]
+    fn test_parse_sell() {
+        let events = parse_transaction(&fixture(include_str!("fixtures/pump_sell.json")));
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].kind,
+            PumpEventKind::Sell {
+                token_amount: 7_981_334_112_045,
+                sol_amount: Some(231_877_402),
+                min_sol_output: 190_000_000,
+            }
+        );
+        assert_eq!(events[0].user, key(TRADER));
+        assert!(!events[0].inner);
+    }
+
+    #[test]
+    fn test_parse_withdraw_and_set_params() {
+        let events = parse_transaction(&fixture(include_str!("fixtures/pump_withdraw.json")));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, PumpEventKind::Withdraw);
+        assert_eq!(events[0].mint, Some(key(MINT)));
+        assert_eq!(
+            events[0].user,
+            key("66mAt9fTBmNcNw8o2DqDs5BNkHAZbjizjWbtYWqPoGCc")
+        );
+
+        let events = parse_transaction(&fixture(include_str!("fixtures/pump_set_params.json")));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, PumpEventKind::SetParams);
+        assert_eq!(events[0].mint, None);
+        assert_eq!(
+            events[0].user,
+            key("8QaWpuCcn6UZm5KnU4PwPaWaugtntRxNH71UqDGw1fZ")
+        );
+        assert!(events[0].user_signed);
+    }
+
+    #[test]
+    fn test_failed_transaction_has_no_events() {
+        let mut tx = fixture(include_str!("fixtures/pump_sell.json"));
+        if let Some(ref mut meta) = tx.transaction.meta {
+            meta.err = Some(solana_sdk::transaction::TransactionError::AccountNotFound);
+        }
+        assert!(parse_transaction(&tx).is_empty());
+    }
 }
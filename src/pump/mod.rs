@@ -4,8 +4,12 @@
 //! Pump.fun has historically changed program behavior without notice.
 //! The constants and structures in this module may break silently.
 //! Monitor pump.fun announcements and be prepared to update.
+//!
+//! `amm` covers PumpSwap, where tokens trade once their curve completes.
 
 pub mod accounts;
+pub mod amm;
+pub mod drift;
 pub mod instruction;
 pub mod mint;
 pub mod price;
@@ -1,7 +1,10 @@
 //! Price calculation utilities for pump.fun bonding curves
 
 use super::accounts::BondingCurve;
-use crate::error::Result;
+use super::program::{
+    FEE_BASIS_POINTS, INITIAL_REAL_TOKEN_RESERVES, INITIAL_VIRTUAL_TOKEN_RESERVES,
+};
+use crate::error::{Error, Result};
 
 /// Token decimals - pump.fun uses 6 decimals (not Solana's standard 9)
 /// WARNING: This is the default, but should be read from mint for accuracy
@@ -49,23 +52,109 @@ pub fn calculate_sell_impact(curve: &BondingCurve, token_amount: u64) -> Result<
     Ok((sol, price_impact))
 }
 
+/// Expected result of spending `sol_in` lamports on a curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuyQuote {
+    /// Raw token units received
+    pub tokens_out: u64,
+    /// Execution price above spot (fee excluded)
+    pub price_impact_bps: u64,
+    /// Protocol fee, included in `sol_in`
+    pub fee_lamports: u64,
+}
+
+impl BuyQuote {
+    /// Minimum tokens to accept at `slippage_bps` tolerance
+    pub fn min_tokens_out(&self, slippage_bps: u32) -> u64 {
+        calculate_min_tokens_with_slippage(self.tokens_out, slippage_bps)
+    }
+}
+
+/// Expected result of selling tokens into a curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SellQuote {
+    /// Lamports received, after the protocol fee
+    pub sol_out: u64,
+    /// Execution price below spot (fee excluded)
+    pub price_impact_bps: u64,
+    /// Protocol fee deducted from the proceeds
+    pub fee_lamports: u64,
+}
+
+impl SellQuote {
+    /// Minimum lamports to accept at `slippage_bps` tolerance
+    pub fn min_sol_out(&self, slippage_bps: u32) -> u64 {
+        calculate_min_sol_with_slippage(self.sol_out, slippage_bps)
+    }
+}
+
+/// `a * b / c`, rounded down
+pub(crate) fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    let value = (a as u128 * b as u128)
+        .checked_div(c as u128)
+        .ok_or(Error::PriceOverflow)?;
+    u64::try_from(value).map_err(|_| Error::PriceOverflow)
+}
+
+/// Exact buy of `sol_in` lamports (fee included) against the virtual reserves
+///
+/// The fee is charged on top of the SOL the curve receives, as the program
+/// does. Amounts round in the curve's favour. The result is not capped at
+/// `real_token_reserves`; a buy larger than that cannot fill on-chain.
+pub fn quote_buy(curve: &BondingCurve, sol_in: u64) -> Result<BuyQuote> {
+    let (sol_reserves, token_reserves) = (curve.virtual_sol_reserves, curve.virtual_token_reserves);
+    if sol_reserves == 0 || token_reserves == 0 {
+        return Err(Error::PriceOverflow);
+    }
+    let net_in = mul_div(sol_in, 10_000, 10_000 + FEE_BASIS_POINTS)?;
+    let new_sol_reserves = sol_reserves
+        .checked_add(net_in)
+        .ok_or(Error::PriceOverflow)?;
+    Ok(BuyQuote {
+        tokens_out: mul_div(net_in, token_reserves, new_sol_reserves)?,
+        price_impact_bps: mul_div(net_in, 10_000, sol_reserves)?,
+        fee_lamports: sol_in - net_in,
+    })
+}
+
+/// Exact sell of `token_amount` raw units against the virtual reserves
+///
+/// The result is not capped at `real_sol_reserves`; the program can only pay
+/// out SOL the curve actually holds.
+pub fn quote_sell(curve: &BondingCurve, token_amount: u64) -> Result<SellQuote> {
+    let (sol_reserves, token_reserves) = (curve.virtual_sol_reserves, curve.virtual_token_reserves);
+    if sol_reserves == 0 || token_reserves == 0 {
+        return Err(Error::PriceOverflow);
+    }
+    let new_token_reserves = token_reserves
+        .checked_add(token_amount)
+        .ok_or(Error::PriceOverflow)?;
+    let gross_out = mul_div(token_amount, sol_reserves, new_token_reserves)?;
+    let fee_lamports = mul_div(gross_out, FEE_BASIS_POINTS, 10_000)?;
+    Ok(SellQuote {
+        sol_out: gross_out - fee_lamports,
+        price_impact_bps: mul_div(token_amount, 10_000, new_token_reserves)?,
+        fee_lamports,
+    })
+}
+
 /// Calculate minimum tokens to receive for a buy with slippage
 pub fn calculate_min_tokens_with_slippage(expected_tokens: u64, slippage_bps: u32) -> u64 {
     // slippage_bps is in basis points (100 bps = 1%)
-    let slippage_factor = 10000 - slippage_bps as u64;
-    (expected_tokens * slippage_factor) / 10000
+    let slippage_factor = 10_000u64.saturating_sub(slippage_bps as u64);
+    (expected_tokens as u128 * slippage_factor as u128 / 10_000) as u64
 }
 
 /// Calculate minimum SOL to receive for a sell with slippage
 pub fn calculate_min_sol_with_slippage(expected_sol: u64, slippage_bps: u32) -> u64 {
-    let slippage_factor = 10000 - slippage_bps as u64;
-    (expected_sol * slippage_factor) / 10000
+    let slippage_factor = 10_000u64.saturating_sub(slippage_bps as u64);
+    (expected_sol as u128 * slippage_factor as u128 / 10_000) as u64
 }
 
 /// Calculate maximum SOL to spend for a buy with slippage
 pub fn calculate_max_sol_with_slippage(expected_sol: u64, slippage_bps: u32) -> u64 {
-    let slippage_factor = 10000 + slippage_bps as u64;
-    (expected_sol * slippage_factor) / 10000
+    let slippage_factor = 10_000 + slippage_bps as u128;
+    (expected_sol as u128 * slippage_factor / 10_000).min(u64::MAX as u128) as u64
 }
 
 /// Convert lamports to SOL
@@ -88,6 +177,18 @@ pub fn human_to_tokens(amount: f64, decimals: u8) -> u64 {
     (amount * 10f64.powi(decimals as i32)) as u64
 }
 
+/// Bonding curve progress (0-100%) from raw virtual token reserves
+///
+/// Progress is the share of the initially sellable tokens already bought;
+/// 100% means the curve is ready to graduate. Zero reserves = unknown (0%).
+pub fn curve_progress_pct(virtual_token_reserves: u64) -> f64 {
+    if virtual_token_reserves == 0 {
+        return 0.0;
+    }
+    let sold = INITIAL_VIRTUAL_TOKEN_RESERVES.saturating_sub(virtual_token_reserves);
+    (sold as f64 / INITIAL_REAL_TOKEN_RESERVES as f64 * 100.0).clamp(0.0, 100.0)
+}
+
 /// Format price for display
 pub fn format_price(price: f64) -> String {
     if price < 0.000001 {
@@ -138,12 +239,68 @@ mod tests {
         assert_eq!(sol_to_lamports(1.0), 1_000_000_000);
     }
 
+    #[test]
+    fn test_curve_progress() {
+        assert_eq!(curve_progress_pct(INITIAL_VIRTUAL_TOKEN_RESERVES), 0.0);
+        let halfway = INITIAL_VIRTUAL_TOKEN_RESERVES - INITIAL_REAL_TOKEN_RESERVES / 2;
+        assert!((curve_progress_pct(halfway) - 50.0).abs() < 1e-9);
+        assert_eq!(curve_progress_pct(1), 100.0);
+        assert_eq!(curve_progress_pct(0), 0.0);
+    }
+
     #[test]
     fn test_percent_change() {
         assert_eq!(calculate_percent_change(100.0, 150.0), 50.0);
         assert_eq!(calculate_percent_change(100.0, 50.0), -50.0);
     }
 
+    #[test]
+    fn test_quote_buy_and_sell() {
+        let curve = BondingCurve::from_virtual_reserves(
+            crate::pump::program::INITIAL_VIRTUAL_SOL_RESERVES,
+            INITIAL_VIRTUAL_TOKEN_RESERVES,
+        );
+        let quote = quote_buy(&curve, 1_010_000_000).unwrap();
+        assert_eq!(quote.fee_lamports, 10_000_000);
+        // 1 SOL into 30 SOL of virtual depth
+        assert_eq!(quote.price_impact_bps, 333);
+        assert_eq!(quote.tokens_out, 34_612_903_225_806);
+        assert!(quote.min_tokens_out(500) < quote.tokens_out);
+
+        let sell = quote_sell(&curve, 1_000_000_000_000).unwrap();
+        assert_eq!(sell.fee_lamports, (sell.sol_out + sell.fee_lamports) / 100);
+        assert_eq!(sell.min_sol_out(10_000), 0);
+    }
+
+    proptest::proptest! {
+        /// Buy then sell back at the depth the buy left, across curve
+        /// states and sizes
+        #[test]
+        fn test_round_trip_never_gains(
+            sold in 0..INITIAL_REAL_TOKEN_RESERVES,
+            sol_in in 1u64..=25_000_000_000,
+        ) {
+            let token_reserves = INITIAL_VIRTUAL_TOKEN_RESERVES - sold;
+            let k = crate::pump::program::INITIAL_VIRTUAL_SOL_RESERVES as u128
+                * INITIAL_VIRTUAL_TOKEN_RESERVES as u128;
+            let sol_reserves = (k / token_reserves as u128) as u64;
+            let curve = BondingCurve::from_virtual_reserves(sol_reserves, token_reserves);
+
+            let buy = quote_buy(&curve, sol_in).unwrap();
+            let mut after = curve.clone();
+            after.virtual_sol_reserves += sol_in - buy.fee_lamports;
+            after.virtual_token_reserves -= buy.tokens_out;
+            let sell = quote_sell(&after, buy.tokens_out).unwrap();
+            proptest::prop_assert!(
+                sell.sol_out + sell.fee_lamports + buy.fee_lamports <= sol_in,
+                "{} lamports with {} sold returned {}",
+                sol_in,
+                sold,
+                sell.sol_out
+            );
+        }
+    }
+
     #[test]
     fn test_buy_impact() {
         let curve = test_curve();
@@ -22,6 +22,9 @@ lazy_static::lazy_static! {
         Pubkey::from_str(PUMP_PROGRAM_ID_STR).expect("Invalid pump program ID");
 }
 
+/// PDA seed of a mint's bonding curve account (with the mint address)
+pub const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+
 /// Instruction discriminators (first 8 bytes of instruction data)
 /// Calculated as: SHA-256("global:<instruction_name>")[0..8]
 #[allow(non_snake_case)]
@@ -43,6 +46,37 @@ pub mod DISCRIMINATORS {
 
     /// Withdraw instruction discriminator
     pub const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+    /// Set global parameters instruction discriminator
+    /// SHA-256("global:set_params")[0..8]
+    pub const SET_PARAMS: [u8; 8] = [27, 234, 178, 52, 147, 2, 187, 141];
+}
+
+/// Prefix of Anchor's self-CPI event instructions (`EVENT_IX_TAG`, little-endian)
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// Event discriminators (after `EVENT_IX_TAG`)
+/// Calculated as: SHA-256("event:<EventName>")[0..8]
+#[allow(non_snake_case)]
+pub mod EVENT_DISCRIMINATORS {
+    /// Emitted by every buy and sell with the amounts actually traded
+    pub const TRADE: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+}
+
+/// Custom program errors (Anchor error codes in `InstructionError::Custom`)
+#[allow(non_snake_case)]
+pub mod PROGRAM_ERRORS {
+    pub const NOT_AUTHORIZED: u32 = 6000;
+    pub const ALREADY_INITIALIZED: u32 = 6001;
+    /// Buy slippage: the tokens cost more than `max_sol_cost`
+    pub const TOO_MUCH_SOL_REQUIRED: u32 = 6002;
+    /// Sell slippage: the tokens fetch less than `min_sol_output`
+    pub const TOO_LITTLE_SOL_RECEIVED: u32 = 6003;
+    pub const MINT_DOES_NOT_MATCH_BONDING_CURVE: u32 = 6004;
+    /// The curve completed; the token trades in its migrated pool
+    pub const BONDING_CURVE_COMPLETE: u32 = 6005;
+    pub const BONDING_CURVE_NOT_COMPLETE: u32 = 6006;
+    pub const NOT_INITIALIZED: u32 = 6007;
 }
 
 /// Account discriminators (first 8 bytes of account data)
@@ -59,6 +93,16 @@ pub mod ACCOUNT_DISCRIMINATORS {
     pub const VOLUME_TRACKER: [u8; 8] = [202, 42, 246, 43, 142, 190, 30, 255];
 }
 
+/// Initial bonding curve state, set by the Global account at launch
+/// WARNING: Pump.fun can change these via the Global account
+pub const INITIAL_VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000;
+pub const INITIAL_VIRTUAL_TOKEN_RESERVES: u64 = 1_073_000_000_000_000;
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+pub const TOKEN_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// Protocol fee charged on every buy and sell (Global.fee_basis_points)
+pub const FEE_BASIS_POINTS: u64 = 100;
+
 /// Jito tip accounts - use one of these for bundle tips
 /// Tip should be in the LAST transaction of your bundle
 /// Do NOT use Address Lookup Tables for tip accounts
@@ -94,6 +138,7 @@ pub fn match_discriminator(data: &[u8]) -> Option<InstructionType> {
         DISCRIMINATORS::SELL => Some(InstructionType::Sell),
         DISCRIMINATORS::INITIALIZE => Some(InstructionType::Initialize),
         DISCRIMINATORS::WITHDRAW => Some(InstructionType::Withdraw),
+        DISCRIMINATORS::SET_PARAMS => Some(InstructionType::SetParams),
         _ => None,
     }
 }
@@ -106,6 +151,7 @@ pub enum InstructionType {
     Sell,
     Initialize,
     Withdraw,
+    SetParams,
 }
 
 impl std::fmt::Display for InstructionType {
@@ -116,6 +162,7 @@ impl std::fmt::Display for InstructionType {
             InstructionType::Sell => write!(f, "sell"),
             InstructionType::Initialize => write!(f, "initialize"),
             InstructionType::Withdraw => write!(f, "withdraw"),
+            InstructionType::SetParams => write!(f, "set_params"),
         }
     }
 }
@@ -143,6 +190,14 @@ mod tests {
         assert_eq!(calculate_discriminator("create"), DISCRIMINATORS::CREATE);
         assert_eq!(calculate_discriminator("buy"), DISCRIMINATORS::BUY);
         assert_eq!(calculate_discriminator("sell"), DISCRIMINATORS::SELL);
+        assert_eq!(
+            calculate_discriminator("withdraw"),
+            DISCRIMINATORS::WITHDRAW
+        );
+        assert_eq!(
+            calculate_discriminator("set_params"),
+            DISCRIMINATORS::SET_PARAMS
+        );
     }
 
     #[test]
@@ -0,0 +1,187 @@
+//! Single-instance lock for `snipe start`
+//!
+//! The running bot holds an exclusive lock on `run.lock` in the credentials
+//! dir (`paper_run.lock` for paper and simulate runs) and records its pid, mode, start
+//! time and control address in it. A second `snipe start` against the same
+//! state refuses to run, and other CLI commands read the file to find the
+//! running instance. The lock is released by the OS when the process exits,
+//! so a file left behind by a crash is not mistaken for a live bot.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+/// Lock file name (paper runs prefix it with `paper_`)
+pub const RUN_LOCK_FILE: &str = "run.lock";
+
+/// How the running bot trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunMode {
+    Live,
+    DryRun,
+    Paper,
+    /// Paper fills, plus each order built, signed and simulated on-chain
+    Simulate,
+}
+
+impl RunMode {
+    /// Orders fill against the simulated curves (paper state files)
+    pub fn is_paper(self) -> bool {
+        matches!(self, RunMode::Paper | RunMode::Simulate)
+    }
+}
+
+impl fmt::Display for RunMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RunMode::Live => "live",
+            RunMode::DryRun => "dry-run",
+            RunMode::Paper => "paper",
+            RunMode::Simulate => "simulate",
+        })
+    }
+}
+
+/// What the running instance recorded in its lock file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunState {
+    pub pid: u32,
+    pub mode: RunMode,
+    pub started_at: DateTime<Utc>,
+    /// Control channel address (None = control channel disabled)
+    pub control_addr: Option<String>,
+}
+
+impl RunState {
+    /// State of this process
+    pub fn current(mode: RunMode, control_addr: Option<String>) -> Self {
+        Self {
+            pid: std::process::id(),
+            mode,
+            started_at: Utc::now(),
+            control_addr,
+        }
+    }
+
+    /// The instance holding the lock at `path`, if one is running
+    pub fn running(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        match file.try_lock() {
+            // Nobody holds it: left behind by a bot that exited
+            Ok(()) => None,
+            Err(TryLockError::WouldBlock) => read_state(&mut file),
+            Err(TryLockError::Error(_)) => None,
+        }
+    }
+}
+
+impl fmt::Display for RunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pid {}, {} mode, started {}",
+            self.pid,
+            self.mode,
+            self.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+        )?;
+        match self.control_addr {
+            Some(ref addr) => write!(f, ", control channel {}", addr),
+            None => write!(f, ", control channel disabled"),
+        }
+    }
+}
+
+fn read_state(file: &mut File) -> Option<RunState> {
+    let mut data = String::new();
+    file.read_to_string(&mut data).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Held by `snipe start` for as long as it runs
+#[derive(Debug)]
+pub struct RunLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl RunLock {
+    /// Take the lock and record `state`, or fail if another instance holds it
+    pub fn acquire(path: &Path, state: &RunState) -> Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(|e| Error::Config(format!("open {}: {}", path.display(), e)))?;
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = read_state(&mut file)
+                    .map_or_else(|| "unknown pid".to_string(), |s| s.to_string());
+                return Err(Error::AlreadyRunning(format!(
+                    "{} ({})",
+                    holder,
+                    path.display()
+                )));
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(Error::Config(format!("lock {}: {}", path.display(), e)));
+            }
+        }
+
+        let data = serde_json::to_string_pretty(state)?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(data.as_bytes())?;
+        file.sync_all()?;
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        // Clear the state while still holding the lock; the OS releases it on close
+        let _ = self.file.set_len(0);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_instance_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RUN_LOCK_FILE);
+
+        let state = RunState::current(RunMode::DryRun, Some("127.0.0.1:7878".to_string()));
+        let lock = RunLock::acquire(&path, &state).unwrap();
+        assert_eq!(RunState::running(&path), Some(state.clone()));
+
+        let err = RunLock::acquire(&path, &state).unwrap_err();
+        assert!(matches!(err, Error::AlreadyRunning(ref msg) if msg.contains("dry-run mode")));
+
+        drop(lock);
+        assert_eq!(RunState::running(&path), None);
+        assert!(RunLock::acquire(&path, &state).is_ok());
+    }
+
+    #[test]
+    fn test_simulate_runs_on_paper_state() {
+        assert!(RunMode::Simulate.is_paper() && RunMode::Paper.is_paper());
+        assert!(!RunMode::DryRun.is_paper() && !RunMode::Live.is_paper());
+        let state = RunState::current(RunMode::Simulate, None);
+        assert!(state.to_string().contains("simulate mode"));
+    }
+}
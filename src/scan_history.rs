@@ -0,0 +1,299 @@
+//! Scan results kept between `snipe scan` / `snipe hot-scan` cycles
+//!
+//! Each cycle's tokens are appended to `scan_history.json` in the credentials
+//! dir (score, m5 change, buy/sell ratio, liquidity and market cap), keeping
+//! the last `max_snapshots_per_mint` per mint for `retention_hours`. A token
+//! is compared with its snapshot from the previous cycle; when its score or
+//! buy/sell ratio jumped by more than the configured deltas it is a "second
+//! wind" token, which is alerted and optionally moved to the front of the
+//! hot-scan auto-buy queue. `snipe scan history <mint>` prints the trajectory.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::dexscreener::HotToken;
+use crate::error::Result;
+
+/// History file name in the credentials dir
+pub const SCAN_HISTORY_FILE: &str = "scan_history.json";
+
+/// `[scan_history]` configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScanHistoryConfig {
+    /// Record scan cycles and alert on second-wind tokens
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Score increase since the previous cycle that counts as a second wind
+    #[serde(default = "default_min_score_delta")]
+    pub min_score_delta: f64,
+    /// Buy/sell ratio increase since the previous cycle that counts as a second wind
+    #[serde(default = "default_min_ratio_delta")]
+    pub min_ratio_delta: f64,
+    /// A previous snapshot older than this is not the previous cycle
+    #[serde(default = "default_max_gap_secs")]
+    pub max_gap_secs: u64,
+    #[serde(default = "default_max_snapshots_per_mint")]
+    pub max_snapshots_per_mint: usize,
+    /// Snapshots older than this are dropped
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: u64,
+    /// `snipe hot-scan --auto-buy` tries second-wind tokens before the top 3
+    #[serde(default)]
+    pub auto_buy: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_score_delta() -> f64 {
+    30.0
+}
+
+fn default_min_ratio_delta() -> f64 {
+    0.5
+}
+
+fn default_max_gap_secs() -> u64 {
+    300
+}
+
+fn default_max_snapshots_per_mint() -> usize {
+    120
+}
+
+fn default_retention_hours() -> u64 {
+    24
+}
+
+impl Default for ScanHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_score_delta: default_min_score_delta(),
+            min_ratio_delta: default_min_ratio_delta(),
+            max_gap_secs: default_max_gap_secs(),
+            max_snapshots_per_mint: default_max_snapshots_per_mint(),
+            retention_hours: default_retention_hours(),
+            auto_buy: false,
+        }
+    }
+}
+
+/// One token as seen in one scan cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    pub symbol: String,
+    pub score: f64,
+    pub m5_change: f64,
+    pub buy_sell_ratio: f64,
+    pub liquidity_usd: f64,
+    pub market_cap: f64,
+    pub at: DateTime<Utc>,
+}
+
+impl ScanSnapshot {
+    pub fn new(token: &HotToken, at: DateTime<Utc>) -> Self {
+        Self {
+            symbol: token.symbol.clone(),
+            score: token.score(),
+            m5_change: token.m5_change,
+            buy_sell_ratio: token.buy_sell_ratio,
+            liquidity_usd: token.liquidity_usd,
+            market_cap: token.market_cap,
+            at,
+        }
+    }
+}
+
+impl fmt::Display for ScanSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} | Score: {:>6.1} | M5: {:+6.1}% | Ratio: {:>4.1} | MCap: ${:.0}k | Liq: ${:.0}k",
+            self.at.format("%Y-%m-%d %H:%M:%S"),
+            self.score,
+            self.m5_change,
+            self.buy_sell_ratio,
+            self.market_cap / 1000.0,
+            self.liquidity_usd / 1000.0
+        )
+    }
+}
+
+/// A token's change since the previous cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanDelta {
+    pub mint: String,
+    pub previous: ScanSnapshot,
+    pub current: ScanSnapshot,
+}
+
+impl ScanDelta {
+    pub fn score_delta(&self) -> f64 {
+        self.current.score - self.previous.score
+    }
+
+    pub fn ratio_delta(&self) -> f64 {
+        self.current.buy_sell_ratio - self.previous.buy_sell_ratio
+    }
+
+    /// Score or buy/sell ratio improved by more than the configured amount
+    pub fn is_second_wind(&self, config: &ScanHistoryConfig) -> bool {
+        self.score_delta() > config.min_score_delta || self.ratio_delta() > config.min_ratio_delta
+    }
+}
+
+impl fmt::Display for ScanDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) score {:.1} -> {:.1} ({:+.1}), ratio {:.1} -> {:.1} ({:+.1})",
+            self.current.symbol,
+            &self.mint[..8.min(self.mint.len())],
+            self.previous.score,
+            self.current.score,
+            self.score_delta(),
+            self.previous.buy_sell_ratio,
+            self.current.buy_sell_ratio,
+            self.ratio_delta()
+        )
+    }
+}
+
+/// Snapshots per mint, persisted to `scan_history.json`
+#[derive(Debug)]
+pub struct ScanHistory {
+    path: PathBuf,
+    config: ScanHistoryConfig,
+    tokens: HashMap<String, Vec<ScanSnapshot>>,
+}
+
+impl ScanHistory {
+    /// Load the history file (empty if it doesn't exist yet)
+    pub fn load(path: &Path, config: ScanHistoryConfig) -> Result<Self> {
+        let tokens = if path.exists() {
+            serde_json::from_str(&std::fs::read_to_string(path)?)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            config,
+            tokens,
+        })
+    }
+
+    pub fn config(&self) -> &ScanHistoryConfig {
+        &self.config
+    }
+
+    /// Add one cycle's tokens, returning their changes since the previous cycle
+    pub fn record(&mut self, tokens: &[HotToken], at: DateTime<Utc>) -> Vec<ScanDelta> {
+        let max_gap = Duration::seconds(self.config.max_gap_secs as i64);
+        let mut deltas = Vec::new();
+        for token in tokens {
+            let current = ScanSnapshot::new(token, at);
+            let snapshots = self.tokens.entry(token.mint.clone()).or_default();
+            if let Some(previous) = snapshots.last().filter(|p| at - p.at <= max_gap) {
+                deltas.push(ScanDelta {
+                    mint: token.mint.clone(),
+                    previous: previous.clone(),
+                    current: current.clone(),
+                });
+            }
+            snapshots.push(current);
+        }
+        self.prune(at);
+        deltas
+    }
+
+    /// Drop snapshots past retention or over the per-mint limit
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::hours(self.config.retention_hours as i64);
+        let max = self.config.max_snapshots_per_mint;
+        self.tokens.retain(|_, snapshots| {
+            snapshots.retain(|s| s.at >= cutoff);
+            let excess = snapshots.len().saturating_sub(max);
+            snapshots.drain(..excess);
+            !snapshots.is_empty()
+        });
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let data = serde_json::to_string_pretty(&self.tokens)?;
+        std::fs::write(&self.path, data)?;
+        Ok(())
+    }
+
+    /// Snapshots of `mint`, oldest first
+    pub fn trajectory(&self, mint: &str) -> &[ScanSnapshot] {
+        self.tokens.get(mint).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(mint: &str, m5_change: f64, buys_5m: u32, sells_5m: u32) -> HotToken {
+        HotToken {
+            mint: mint.to_string(),
+            symbol: "TEST".to_string(),
+            name: "Test".to_string(),
+            price_native: 0.000001,
+            m5_change,
+            h1_change: 20.0,
+            buys_5m,
+            sells_5m,
+            buy_sell_ratio: buys_5m as f64 / sells_5m.max(1) as f64,
+            market_cap: 80_000.0,
+            liquidity_usd: 20_000.0,
+            volume_h1: 50_000.0,
+            is_boosted: false,
+            boost_amount: 0.0,
+            dex_id: "pumpswap".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_second_wind_across_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SCAN_HISTORY_FILE);
+        let config = ScanHistoryConfig {
+            max_snapshots_per_mint: 2,
+            ..Default::default()
+        };
+        let mut history = ScanHistory::load(&path, config.clone()).unwrap();
+        let start = Utc::now();
+
+        // First sighting has nothing to compare with
+        assert!(history
+            .record(&[token("Apump", 12.0, 20, 20)], start)
+            .is_empty());
+
+        // Flat cycle, then a renewed push
+        let at = start + Duration::seconds(30);
+        let deltas = history.record(&[token("Apump", 12.0, 20, 18)], at);
+        assert!(!deltas[0].is_second_wind(&config));
+        let at = start + Duration::seconds(60);
+        let deltas = history.record(&[token("Apump", 25.0, 40, 15)], at);
+        assert!(deltas[0].is_second_wind(&config));
+        assert!(deltas[0].ratio_delta() > 1.0);
+        history.save().unwrap();
+
+        let mut reloaded = ScanHistory::load(&path, config.clone()).unwrap();
+        let trajectory = reloaded.trajectory("Apump");
+        assert_eq!(trajectory.len(), 2);
+        assert_eq!(trajectory[1].m5_change, 25.0);
+
+        // A gap longer than one cycle is not compared
+        let later = at + Duration::seconds(config.max_gap_secs as i64 + 1);
+        assert!(reloaded
+            .record(&[token("Apump", 50.0, 80, 10)], later)
+            .is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+//! SOL/USD price for USD-denominated sizing
+//!
+//! `trading.buy_amount_usd`, `safety.max_position_usd` and
+//! `safety.daily_loss_limit_usd` are converted to SOL at decision time with
+//! the price cached here. It comes from Pyth's SOL/USD price feed account
+//! (read over RPC) or DexScreener's SOL/USDC pair and is refreshed every
+//! `ttl_secs`. A price older than `max_age_secs` is stale: USD values fall
+//! back to their SOL settings and a warning is logged.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::dexscreener::DexScreenerClient;
+
+/// Where the SOL/USD price comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SolPriceOracle {
+    /// Pyth price feed account, read over RPC
+    Pyth,
+    /// DexScreener SOL/USDC pair
+    Dexscreener,
+}
+
+impl fmt::Display for SolPriceOracle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolPriceOracle::Pyth => write!(f, "pyth"),
+            SolPriceOracle::Dexscreener => write!(f, "dexscreener"),
+        }
+    }
+}
+
+/// `[sol_price]` configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct SolPriceConfig {
+    #[serde(default = "default_oracle")]
+    pub oracle: SolPriceOracle,
+    /// Seconds between refreshes
+    #[serde(default = "default_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Prices older than this are stale (USD values fall back to SOL)
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: u64,
+    /// Pyth SOL/USD price feed account (PriceUpdateV2)
+    #[serde(default = "default_pyth_account")]
+    pub pyth_account: String,
+    /// DexScreener SOL/USDC pair address
+    #[serde(default = "default_dexscreener_pair")]
+    pub dexscreener_pair: String,
+}
+
+fn default_oracle() -> SolPriceOracle {
+    SolPriceOracle::Pyth
+}
+
+fn default_ttl_secs() -> u64 {
+    30
+}
+
+fn default_max_age_secs() -> u64 {
+    300
+}
+
+fn default_pyth_account() -> String {
+    "7UVimffxr9ow1uXYxsr4LHAcV58mLzhmwaeKvJ1pjLiE".to_string()
+}
+
+/// SOL/USDC on Raydium
+fn default_dexscreener_pair() -> String {
+    "58oQChx4yWmvKdwLLZzBi4ChoCc2fqCUWBkwMihLYQo2".to_string()
+}
+
+impl Default for SolPriceConfig {
+    fn default() -> Self {
+        Self {
+            oracle: default_oracle(),
+            ttl_secs: default_ttl_secs(),
+            max_age_secs: default_max_age_secs(),
+            pyth_account: default_pyth_account(),
+            dexscreener_pair: default_dexscreener_pair(),
+        }
+    }
+}
+
+/// One SOL/USD reading
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolQuote {
+    pub usd_per_sol: f64,
+    /// When the oracle published it (DexScreener: when it was fetched)
+    pub published_at: DateTime<Utc>,
+}
+
+/// Cached SOL/USD price
+pub struct SolUsdPrice {
+    config: SolPriceConfig,
+    rpc_client: Arc<RpcClient>,
+    dexscreener: DexScreenerClient,
+    latest: Mutex<Option<SolQuote>>,
+    /// A stale-price warning was logged (cleared by the next fresh quote)
+    stale_warned: AtomicBool,
+    /// The background refresh is running
+    refreshing: AtomicBool,
+}
+
+impl SolUsdPrice {
+    pub fn new(config: SolPriceConfig, rpc_client: Arc<RpcClient>) -> Self {
+        Self {
+            config,
+            rpc_client,
+            dexscreener: DexScreenerClient::new(),
+            latest: Mutex::new(None),
+            stale_warned: AtomicBool::new(false),
+            refreshing: AtomicBool::new(false),
+        }
+    }
+
+    pub fn config(&self) -> &SolPriceConfig {
+        &self.config
+    }
+
+    /// Fetch a new quote from the oracle and cache it
+    pub async fn refresh(&self) -> Result<SolQuote> {
+        let quote = match self.config.oracle {
+            SolPriceOracle::Pyth => {
+                let account = Pubkey::from_str(&self.config.pyth_account)
+                    .map_err(|e| anyhow!("invalid sol_price.pyth_account: {}", e))?;
+                let data = self.rpc_client.get_account_data(&account)?;
+                parse_pyth_price_update(&data)?
+            }
+            SolPriceOracle::Dexscreener => {
+                let pair = self
+                    .dexscreener
+                    .get_pair("solana", &self.config.dexscreener_pair)
+                    .await?
+                    .ok_or_else(|| anyhow!("pair {} not returned", self.config.dexscreener_pair))?;
+                let usd_per_sol = pair
+                    .price_usd
+                    .as_deref()
+                    .and_then(|p| p.parse::<f64>().ok())
+                    .ok_or_else(|| anyhow!("pair has no USD price"))?;
+                SolQuote {
+                    usd_per_sol,
+                    published_at: Utc::now(),
+                }
+            }
+        };
+        if !quote.usd_per_sol.is_finite() || quote.usd_per_sol <= 0.0 {
+            return Err(anyhow!("bad SOL/USD price {}", quote.usd_per_sol));
+        }
+        debug!(
+            "SOL/USD ${:.2} from {}",
+            quote.usd_per_sol, self.config.oracle
+        );
+        self.set_quote(quote);
+        Ok(quote)
+    }
+
+    /// Cached quote if fetched within `ttl_secs`, else a new one
+    pub async fn refresh_if_due(&self) -> Result<SolQuote> {
+        let ttl = chrono::Duration::seconds(self.config.ttl_secs as i64);
+        match self.latest() {
+            Some(quote) if Utc::now() - quote.published_at < ttl => Ok(quote),
+            _ => self.refresh().await,
+        }
+    }
+
+    /// Refresh every `ttl_secs` in the background (later calls do nothing)
+    pub fn spawn_refresh(self: &Arc<Self>) {
+        if self.refreshing.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let price = self.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(price.config.ttl_secs.max(1)));
+            loop {
+                interval.tick().await;
+                if let Err(e) = price.refresh().await {
+                    warn!("SOL/USD refresh from {} failed: {}", price.config.oracle, e);
+                }
+            }
+        });
+    }
+
+    fn set_quote(&self, quote: SolQuote) {
+        let mut latest = self.latest.lock().unwrap();
+        // Never replace a newer quote with an older one
+        if latest.map_or(true, |l| l.published_at <= quote.published_at) {
+            *latest = Some(quote);
+        }
+    }
+
+    /// Latest quote, stale or not
+    pub fn latest(&self) -> Option<SolQuote> {
+        *self.latest.lock().unwrap()
+    }
+
+    /// Latest quote if no older than `max_age_secs` at `now`
+    pub fn fresh_quote(&self, now: DateTime<Utc>) -> Option<SolQuote> {
+        self.latest()
+            .filter(|q| (now - q.published_at).num_seconds() <= self.config.max_age_secs as i64)
+    }
+
+    /// `usd` in SOL at the current price (None = no fresh price)
+    pub fn usd_to_sol(&self, usd: f64) -> Option<f64> {
+        match self.fresh_quote(Utc::now()) {
+            Some(quote) => {
+                if self.stale_warned.swap(false, Ordering::Relaxed) {
+                    info!("SOL/USD price fresh again (${:.2})", quote.usd_per_sol);
+                }
+                Some(usd / quote.usd_per_sol)
+            }
+            None => {
+                if !self.stale_warned.swap(true, Ordering::Relaxed) {
+                    warn!(
+                        "SOL/USD price from {} is older than {}s ({}); USD amounts fall back to their SOL settings",
+                        self.config.oracle,
+                        self.config.max_age_secs,
+                        self.latest()
+                            .map(|q| format!("last ${:.2} at {}", q.usd_per_sol, q.published_at))
+                            .unwrap_or_else(|| "none yet".to_string())
+                    );
+                }
+                None
+            }
+        }
+    }
+}
+
+/// A limit set in SOL and optionally in USD: the USD value converted at the
+/// current price, else the SOL value
+pub fn usd_or_sol(price: Option<&SolUsdPrice>, usd: Option<f64>, sol: f64) -> f64 {
+    usd.and_then(|usd| price.and_then(|p| p.usd_to_sol(usd)))
+        .unwrap_or(sol)
+}
+
+/// Anchor discriminator + write authority
+const PRICE_UPDATE_HEADER_LEN: usize = 8 + 32;
+
+/// SOL/USD from a Pyth receiver `PriceUpdateV2` account
+///
+/// Layout after the header: verification level (borsh enum; `Partial`
+/// carries a signature count), then the price message: feed id (32),
+/// price i64, conf u64, exponent i32, publish_time i64.
+pub fn parse_pyth_price_update(data: &[u8]) -> Result<SolQuote> {
+    let level = *data
+        .get(PRICE_UPDATE_HEADER_LEN)
+        .ok_or_else(|| anyhow!("price account too short"))?;
+    let message = match level {
+        0 => PRICE_UPDATE_HEADER_LEN + 2,
+        1 => PRICE_UPDATE_HEADER_LEN + 1,
+        other => return Err(anyhow!("unknown verification level {}", other)),
+    };
+    let read = |offset: usize, len: usize| {
+        data.get(message + offset..message + offset + len)
+            .ok_or_else(|| anyhow!("price account too short"))
+    };
+    let price = i64::from_le_bytes(read(32, 8)?.try_into()?);
+    let exponent = i32::from_le_bytes(read(48, 4)?.try_into()?);
+    let publish_time = i64::from_le_bytes(read(52, 8)?.try_into()?);
+    let published_at = Utc
+        .timestamp_opt(publish_time, 0)
+        .single()
+        .ok_or_else(|| anyhow!("bad publish time {}", publish_time))?;
+    Ok(SolQuote {
+        usd_per_sol: price as f64 * 10f64.powi(exponent),
+        published_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price_update(level: &[u8], price: i64, exponent: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = vec![0u8; PRICE_UPDATE_HEADER_LEN];
+        data.extend_from_slice(level);
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&[0u8; 32]);
+        data
+    }
+
+    #[test]
+    fn test_parse_pyth_price_update() {
+        let full = parse_pyth_price_update(&price_update(&[1], 15_012_345_678, -8, 1_700_000_000))
+            .unwrap();
+        assert!((full.usd_per_sol - 150.12345678).abs() < 1e-9);
+        assert_eq!(full.published_at.timestamp(), 1_700_000_000);
+
+        let partial = parse_pyth_price_update(&price_update(&[0, 5], 2_000_000, -4, 1)).unwrap();
+        assert!((partial.usd_per_sol - 200.0).abs() < 1e-9);
+
+        assert!(parse_pyth_price_update(&price_update(&[9], 1, 0, 1)).is_err());
+        assert!(parse_pyth_price_update(&[0u8; 50]).is_err());
+    }
+
+    #[test]
+    fn test_usd_falls_back_to_sol_when_stale() {
+        let rpc = Arc::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        let price = SolUsdPrice::new(SolPriceConfig::default(), rpc);
+
+        // No quote yet
+        assert_eq!(usd_or_sol(Some(&price), Some(30.0), 0.1), 0.1);
+        // No USD setting
+        assert_eq!(usd_or_sol(Some(&price), None, 0.1), 0.1);
+
+        price.set_quote(SolQuote {
+            usd_per_sol: 150.0,
+            published_at: Utc::now() - chrono::Duration::seconds(10),
+        });
+        assert!((usd_or_sol(Some(&price), Some(30.0), 0.1) - 0.2).abs() < 1e-9);
+        assert_eq!(usd_or_sol(None, Some(30.0), 0.1), 0.1);
+
+        // An older quote doesn't replace it
+        price.set_quote(SolQuote {
+            usd_per_sol: 100.0,
+            published_at: Utc::now() - chrono::Duration::seconds(20),
+        });
+        assert_eq!(price.latest().unwrap().usd_per_sol, 150.0);
+
+        let stale = SolUsdPrice::new(
+            SolPriceConfig::default(),
+            Arc::new(RpcClient::new("http://127.0.0.1:1".to_string())),
+        );
+        stale.set_quote(SolQuote {
+            usd_per_sol: 150.0,
+            published_at: Utc::now() - chrono::Duration::seconds(600),
+        });
+        assert!(stale.fresh_quote(Utc::now()).is_none());
+        assert_eq!(usd_or_sol(Some(&stale), Some(30.0), 0.1), 0.1);
+    }
+}
@@ -5,16 +5,22 @@
 //! 1. Fatal risks (absolute veto)
 //! 2. Chain health (network conditions)
 //! 3. Portfolio risk (capital protection)
-//! 4. Rug predictor (position safety)
-//! 5. Exit manager (position management)
-//! 6. Strategy signals (entry/exit)
-//! 7. Regime optimizations (sizing/style)
+//! 4. Kill-switch (liquidity drain, insider sells)
+//! 5. Rug predictor (position safety)
+//! 6. Exit manager (position management)
+//! 7. Strategy signals (entry/exit)
+//! 8. Regime optimizations (sizing/style)
+//! 9. Entry tactics and the adaptive filter (entries the strategy holds on)
+//!
+//! Every decision carries an `ArbitrationRecord`: what each component
+//! proposed and the rule that picked the winner. Records where components
+//! disagreed are written to the decision log (`snipe decisions conflicts`).
 
 use super::fatal_risk::FatalRisk;
 use super::portfolio_risk::PortfolioBlock;
 use super::types::{
-    ArbitratedDecision, ChainAction, DecisionSource, EntrySignal, ExitSignal, TokenRegime,
-    TradingAction,
+    ArbitratedDecision, ArbitrationRecord, ChainAction, DecisionSource, EntrySignal, ExitSignal,
+    Proposal, ProposedAction, TokenRegime, TradingAction,
 };
 
 /// Rug prediction result
@@ -56,8 +62,43 @@ impl DecisionArbitrator {
         portfolio_result: Result<(), PortfolioBlock>,
         strategy_signal: Option<EntrySignal>,
         regime: &TokenRegime,
+    ) -> ArbitratedDecision {
+        self.arbitrate_entry_with(
+            mint,
+            fatal_result,
+            chain_action,
+            portfolio_result,
+            strategy_signal,
+            regime,
+            &[],
+        )
+    }
+
+    /// Arbitrate an entry, with proposals from components outside the engine
+    ///
+    /// A `Skip` from a component that outranks the strategy (the kill-switch)
+    /// vetoes the entry. When the strategy has no signal, an outside `Enter`
+    /// (filter, tactics) is the entry the caller goes ahead with.
+    #[allow(clippy::too_many_arguments)]
+    pub fn arbitrate_entry_with(
+        &self,
+        mint: &str,
+        fatal_result: Option<FatalRisk>,
+        chain_action: &ChainAction,
+        portfolio_result: Result<(), PortfolioBlock>,
+        strategy_signal: Option<EntrySignal>,
+        regime: &TokenRegime,
+        outside: &[Proposal],
     ) -> ArbitratedDecision {
         let mut overridden = vec![];
+        let proposals = entry_proposals(
+            fatal_result.as_ref(),
+            chain_action,
+            portfolio_result.as_ref().err(),
+            strategy_signal.as_ref(),
+            regime,
+            outside,
+        );
 
         // Priority 1: Fatal risks are absolute
         if let Some(fatal) = fatal_result {
@@ -87,6 +128,12 @@ impl DecisionArbitrator {
                 source: DecisionSource::FatalRisk,
                 overridden,
                 confidence: 1.0,
+                arbitration: settle(
+                    proposals,
+                    DecisionSource::FatalRisk,
+                    ProposedAction::Skip,
+                    "fatal_risk",
+                ),
             };
         }
 
@@ -113,6 +160,12 @@ impl DecisionArbitrator {
                     source: DecisionSource::ChainHealth,
                     overridden,
                     confidence: 1.0,
+                    arbitration: settle(
+                        proposals,
+                        DecisionSource::ChainHealth,
+                        ProposedAction::Skip,
+                        "chain_exit_only",
+                    ),
                 };
             }
             ChainAction::PauseNewEntries => {
@@ -136,6 +189,12 @@ impl DecisionArbitrator {
                     source: DecisionSource::ChainHealth,
                     overridden,
                     confidence: 1.0,
+                    arbitration: settle(
+                        proposals,
+                        DecisionSource::ChainHealth,
+                        ProposedAction::Skip,
+                        "chain_paused",
+                    ),
                 };
             }
             _ => {}
@@ -166,10 +225,55 @@ impl DecisionArbitrator {
                 source: DecisionSource::PortfolioRisk,
                 overridden,
                 confidence: 1.0,
+                arbitration: settle(
+                    proposals,
+                    DecisionSource::PortfolioRisk,
+                    ProposedAction::Skip,
+                    "portfolio_block",
+                ),
+            };
+        }
+
+        // Priority 4: Outside vetoes (kill-switch)
+        let veto = outside
+            .iter()
+            .filter(|p| {
+                p.action == ProposedAction::Skip
+                    && p.source.priority() < DecisionSource::Strategy.priority()
+            })
+            .min_by_key(|p| p.source.priority())
+            .cloned();
+        if let Some(veto) = veto {
+            if let Some(entry) = &strategy_signal {
+                self.log_override(
+                    DecisionSource::Strategy,
+                    &format!("Entry signal for {}", mint),
+                    veto.source,
+                    &veto.reason,
+                );
+                overridden.push((
+                    DecisionSource::Strategy,
+                    format!("Entry: {:?}", entry.strategy),
+                    format!("Overridden by {}: {}", veto.source, veto.reason),
+                ));
+            }
+            return ArbitratedDecision {
+                action: TradingAction::Skip {
+                    reason: veto.reason.clone(),
+                },
+                source: veto.source,
+                overridden,
+                confidence: veto.confidence,
+                arbitration: settle(
+                    proposals,
+                    veto.source,
+                    ProposedAction::Skip,
+                    veto.source.as_str(),
+                ),
             };
         }
 
-        // Priority 4: Regime blocks (wash trade, deployer bleed)
+        // Priority 5: Regime blocks (wash trade, deployer bleed)
         if regime.should_avoid() {
             if let Some(entry) = &strategy_signal {
                 let regime_name = match regime {
@@ -203,10 +307,16 @@ impl DecisionArbitrator {
                 source: DecisionSource::RegimeOptimization,
                 overridden,
                 confidence: regime.confidence(),
+                arbitration: settle(
+                    proposals,
+                    DecisionSource::RegimeOptimization,
+                    ProposedAction::Skip,
+                    "regime_avoid",
+                ),
             };
         }
 
-        // Priority 5: Strategy entry signal
+        // Priority 6: Strategy entry signal
         if let Some(entry) = strategy_signal {
             return ArbitratedDecision {
                 action: TradingAction::Enter {
@@ -217,15 +327,40 @@ impl DecisionArbitrator {
                 source: DecisionSource::Strategy,
                 overridden,
                 confidence: entry.confidence,
+                arbitration: settle(
+                    proposals,
+                    DecisionSource::Strategy,
+                    ProposedAction::Enter,
+                    "strategy_entry",
+                ),
             };
         }
 
-        // No entry signal
+        // No entry signal: an outside entry (filter, tactics) stands
+        let arbitration = match outside
+            .iter()
+            .filter(|p| p.action == ProposedAction::Enter)
+            .min_by_key(|p| p.source.priority())
+        {
+            Some(entry) => settle(
+                proposals,
+                entry.source,
+                ProposedAction::Enter,
+                "strategy_hold_defers",
+            ),
+            None => settle(
+                proposals,
+                DecisionSource::Strategy,
+                ProposedAction::Hold,
+                "no_signal",
+            ),
+        };
         ArbitratedDecision {
             action: TradingAction::Hold,
             source: DecisionSource::Strategy,
             overridden,
             confidence: 1.0,
+            arbitration,
         }
     }
 
@@ -238,6 +373,28 @@ impl DecisionArbitrator {
         chain_action: &ChainAction,
     ) -> ArbitratedDecision {
         let overridden = vec![];
+        let mut proposals = vec![];
+        if let Some(rug) = &rug_prediction {
+            let action = if rug.probability > 0.6 {
+                ProposedAction::Exit
+            } else {
+                ProposedAction::Hold
+            };
+            proposals.push(Proposal::new(
+                DecisionSource::RugPredictor,
+                action,
+                rug.probability,
+                rug.recommendation,
+            ));
+        }
+        if let Some(exit) = &exit_signal {
+            proposals.push(Proposal::new(
+                DecisionSource::ExitManager,
+                ProposedAction::Exit,
+                0.9,
+                format!("{:?}", exit.reason),
+            ));
+        }
 
         // Priority 1: Rug prediction overrides everything
         if let Some(rug) = rug_prediction {
@@ -267,6 +424,12 @@ impl DecisionArbitrator {
                     source: DecisionSource::RugPredictor,
                     overridden,
                     confidence: rug.probability,
+                    arbitration: settle(
+                        proposals,
+                        DecisionSource::RugPredictor,
+                        ProposedAction::Exit,
+                        "rug_predicted",
+                    ),
                 };
             }
         }
@@ -287,6 +450,12 @@ impl DecisionArbitrator {
                 source: DecisionSource::ExitManager,
                 overridden,
                 confidence: 0.9,
+                arbitration: settle(
+                    proposals,
+                    DecisionSource::ExitManager,
+                    ProposedAction::Exit,
+                    "exit_signal",
+                ),
             };
         }
 
@@ -296,6 +465,12 @@ impl DecisionArbitrator {
             source: DecisionSource::Strategy,
             overridden,
             confidence: 1.0,
+            arbitration: settle(
+                proposals,
+                DecisionSource::Strategy,
+                ProposedAction::Hold,
+                "no_signal",
+            ),
         }
     }
 
@@ -352,6 +527,81 @@ impl DecisionArbitrator {
     }
 }
 
+/// What each input to an entry proposed
+fn entry_proposals(
+    fatal_result: Option<&FatalRisk>,
+    chain_action: &ChainAction,
+    portfolio_block: Option<&PortfolioBlock>,
+    strategy_signal: Option<&EntrySignal>,
+    regime: &TokenRegime,
+    outside: &[Proposal],
+) -> Vec<Proposal> {
+    let skip = |source, confidence, reason: String| {
+        Proposal::new(source, ProposedAction::Skip, confidence, reason)
+    };
+    let mut proposals = vec![];
+    if let Some(fatal) = fatal_result {
+        proposals.push(skip(DecisionSource::FatalRisk, 1.0, fatal.description()));
+    }
+    match chain_action {
+        ChainAction::ExitOnlyMode => proposals.push(skip(
+            DecisionSource::ChainHealth,
+            1.0,
+            "exit-only mode".to_string(),
+        )),
+        ChainAction::PauseNewEntries => proposals.push(skip(
+            DecisionSource::ChainHealth,
+            1.0,
+            "entries paused".to_string(),
+        )),
+        _ => {}
+    }
+    if let Some(block) = portfolio_block {
+        proposals.push(skip(
+            DecisionSource::PortfolioRisk,
+            1.0,
+            block.description(),
+        ));
+    }
+    if regime.should_avoid() {
+        proposals.push(skip(
+            DecisionSource::RegimeOptimization,
+            regime.confidence(),
+            format!("{} regime", regime.kind()),
+        ));
+    }
+    proposals.push(match strategy_signal {
+        Some(entry) => Proposal::new(
+            DecisionSource::Strategy,
+            ProposedAction::Enter,
+            entry.confidence,
+            format!("{} at {:.3} SOL", entry.strategy, entry.suggested_size_sol),
+        ),
+        None => Proposal::new(
+            DecisionSource::Strategy,
+            ProposedAction::Hold,
+            1.0,
+            "no entry signal",
+        ),
+    });
+    proposals.extend_from_slice(outside);
+    proposals
+}
+
+fn settle(
+    proposals: Vec<Proposal>,
+    winner: DecisionSource,
+    action: ProposedAction,
+    rule: &str,
+) -> Option<ArbitrationRecord> {
+    Some(ArbitrationRecord {
+        proposals,
+        winner,
+        action,
+        rule: rule.to_string(),
+    })
+}
+
 impl Default for DecisionArbitrator {
     fn default() -> Self {
         Self::new()
@@ -542,4 +792,111 @@ mod tests {
         // Should be fatal risk, not any of the others
         assert_eq!(decision.source, DecisionSource::FatalRisk);
     }
+
+    #[test]
+    fn test_kill_switch_vetoes_entry() {
+        let arbitrator = DecisionArbitrator::quiet();
+
+        let decision = arbitrator.arbitrate_entry_with(
+            "test_mint",
+            None,
+            &ChainAction::ProceedNormally,
+            Ok(()),
+            Some(make_entry_signal("test_mint", 0.1)),
+            &TokenRegime::default(),
+            &[
+                Proposal::new(
+                    DecisionSource::Tactics,
+                    ProposedAction::Enter,
+                    0.7,
+                    "piggyback",
+                ),
+                Proposal::new(
+                    DecisionSource::KillSwitch,
+                    ProposedAction::Skip,
+                    1.0,
+                    "liquidity draining",
+                ),
+            ],
+        );
+
+        assert!(matches!(decision.action, TradingAction::Skip { .. }));
+        assert_eq!(decision.source, DecisionSource::KillSwitch);
+        let record = decision.conflict().unwrap();
+        assert_eq!(record.winner, DecisionSource::KillSwitch);
+        assert_eq!(record.rule, "kill_switch");
+        let overridden: Vec<_> = record.overridden().map(|p| p.source).collect();
+        assert_eq!(
+            overridden,
+            vec![DecisionSource::Strategy, DecisionSource::Tactics]
+        );
+    }
+
+    #[test]
+    fn test_portfolio_block_records_conflict() {
+        let arbitrator = DecisionArbitrator::quiet();
+
+        let decision = arbitrator.arbitrate_entry_with(
+            "test_mint",
+            None,
+            &ChainAction::ProceedNormally,
+            Err(PortfolioBlock::MaxPositionsReached { current: 5, max: 5 }),
+            None,
+            &TokenRegime::default(),
+            &[Proposal::new(
+                DecisionSource::Tactics,
+                ProposedAction::Enter,
+                0.7,
+                "piggyback",
+            )],
+        );
+
+        let record = decision.conflict().unwrap();
+        assert_eq!(record.winner, DecisionSource::PortfolioRisk);
+        assert_eq!(record.action, ProposedAction::Skip);
+        assert_eq!(record.rule, "portfolio_block");
+    }
+
+    #[test]
+    fn test_strategy_hold_defers_to_filter() {
+        let arbitrator = DecisionArbitrator::quiet();
+
+        let decision = arbitrator.arbitrate_entry_with(
+            "test_mint",
+            None,
+            &ChainAction::ProceedNormally,
+            Ok(()),
+            None,
+            &TokenRegime::default(),
+            &[Proposal::new(
+                DecisionSource::Filter,
+                ProposedAction::Enter,
+                0.6,
+                "StrongBuy",
+            )],
+        );
+
+        assert!(matches!(decision.action, TradingAction::Hold));
+        let record = decision.conflict().unwrap();
+        assert_eq!(record.winner, DecisionSource::Filter);
+        assert_eq!(record.action, ProposedAction::Enter);
+        assert_eq!(record.rule, "strategy_hold_defers");
+    }
+
+    #[test]
+    fn test_agreement_is_not_a_conflict() {
+        let arbitrator = DecisionArbitrator::quiet();
+
+        let decision = arbitrator.arbitrate_entry(
+            "test_mint",
+            None,
+            &ChainAction::ProceedNormally,
+            Ok(()),
+            Some(make_entry_signal("test_mint", 0.1)),
+            &TokenRegime::default(),
+        );
+
+        assert!(decision.arbitration.is_some());
+        assert!(decision.conflict().is_none());
+    }
 }
@@ -2,6 +2,11 @@
 //!
 //! Solana congestion awareness to avoid trading in bad conditions.
 //! Monitors slot times, transaction failures, and priority fees.
+//!
+//! A background sampler calls `fetch_sample` (performance samples,
+//! prioritization fees and the RPC node's slot lag) without holding any lock
+//! and pushes the result in with `record_sample`. The derived priority-fee
+//! floor raises the fee used for buys.
 
 use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
@@ -13,12 +18,28 @@ use super::types::{ChainAction, CongestionLevel};
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ChainState {
     pub avg_slot_time_ms: u64,
+    /// Transactions per second from recent performance samples (0 = no data)
+    pub tps: f64,
+    /// Slots the RPC node is behind the cluster
+    pub slot_lag: u64,
     pub tx_failure_rate: f64,
     pub priority_fee_lamports: u64,
+    /// Recommended minimum priority fee (0 = no fee data yet)
+    pub priority_fee_floor_lamports: u64,
     pub congestion_level: CongestionLevel,
     pub recommended_action: ChainAction,
 }
 
+/// One round of chain measurements (None = the RPC call failed)
+#[derive(Debug, Clone, Default)]
+pub struct ChainSample {
+    pub tps: Option<f64>,
+    pub avg_slot_time_ms: Option<f64>,
+    pub slot_lag: Option<u64>,
+    /// Prioritization fee at `fee_percentile` (micro-lamports per compute unit)
+    pub priority_fee_micro_lamports: Option<u64>,
+}
+
 /// Configuration for chain health monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChainHealthConfig {
@@ -27,6 +48,34 @@ pub struct ChainHealthConfig {
     pub pause_on_severe: bool,
     pub exit_only_on_critical: bool,
     pub congestion_size_factor: f64,
+    /// Percentile of recent prioritization fees used for the fee floor
+    #[serde(default = "default_fee_percentile")]
+    pub fee_percentile: u8,
+    /// Compute units a buy is priced at when converting fees to lamports
+    #[serde(default = "default_fee_compute_units")]
+    pub fee_compute_units: u64,
+    /// Cap on the priority-fee floor (lamports)
+    #[serde(default = "default_max_fee_floor_lamports")]
+    pub max_fee_floor_lamports: u64,
+    /// RPC node this many slots behind counts as severe congestion
+    #[serde(default = "default_max_slot_lag")]
+    pub max_slot_lag: u64,
+}
+
+fn default_fee_percentile() -> u8 {
+    75
+}
+
+fn default_fee_compute_units() -> u64 {
+    100_000
+}
+
+fn default_max_fee_floor_lamports() -> u64 {
+    5_000_000 // 0.005 SOL
+}
+
+fn default_max_slot_lag() -> u64 {
+    50
 }
 
 impl Default for ChainHealthConfig {
@@ -37,16 +86,74 @@ impl Default for ChainHealthConfig {
             pause_on_severe: true,
             exit_only_on_critical: true,
             congestion_size_factor: 0.5,
+            fee_percentile: default_fee_percentile(),
+            fee_compute_units: default_fee_compute_units(),
+            max_fee_floor_lamports: default_max_fee_floor_lamports(),
+            max_slot_lag: default_max_slot_lag(),
         }
     }
 }
 
+/// Value at `percentile` (0-100) of unsorted values
+fn percentile(values: &mut [u64], percentile: u8) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let rank = (percentile.min(100) as usize * (values.len() - 1)) / 100;
+    Some(values[rank])
+}
+
+/// Measure the chain: TPS and slot time, slot lag and prioritization fees
+pub async fn fetch_sample(rpc: &RpcClient, fee_percentile: u8) -> ChainSample {
+    let mut sample = ChainSample::default();
+
+    if let Ok(samples) = rpc.get_recent_performance_samples(Some(5)).await {
+        let samples: Vec<_> = samples
+            .iter()
+            .filter(|s| s.num_slots > 0 && s.sample_period_secs > 0)
+            .collect();
+        if !samples.is_empty() {
+            let count = samples.len() as f64;
+            sample.avg_slot_time_ms = Some(
+                samples
+                    .iter()
+                    .map(|s| (s.sample_period_secs as f64 / s.num_slots as f64) * 1000.0)
+                    .sum::<f64>()
+                    / count,
+            );
+            sample.tps = Some(
+                samples
+                    .iter()
+                    .map(|s| s.num_transactions as f64 / s.sample_period_secs as f64)
+                    .sum::<f64>()
+                    / count,
+            );
+        }
+    }
+
+    // Max shred insert slot is the cluster tip as far as this node has seen it
+    if let (Ok(tip), Ok(slot)) = (rpc.get_max_shred_insert_slot().await, rpc.get_slot().await) {
+        sample.slot_lag = Some(tip.saturating_sub(slot));
+    }
+
+    // Note: This may not work on all RPC endpoints
+    if let Ok(fees) = rpc.get_recent_prioritization_fees(&[]).await {
+        let mut fees: Vec<u64> = fees.iter().map(|f| f.prioritization_fee).collect();
+        sample.priority_fee_micro_lamports = percentile(&mut fees, fee_percentile);
+    }
+
+    sample
+}
+
 /// Chain Health Monitor
 pub struct ChainHealth {
     config: ChainHealthConfig,
     recent_slot_times: RollingWindow,
     recent_tx_failures: RollingWindow,
     recent_priority_fees: RollingWindow,
+    recent_tps: RollingWindow,
+    slot_lag: u64,
     our_tx_count: u32,
     our_tx_failures: u32,
 }
@@ -59,38 +166,40 @@ impl ChainHealth {
             recent_slot_times: RollingWindow::new(std::time::Duration::from_secs(300)),
             recent_tx_failures: RollingWindow::new(std::time::Duration::from_secs(300)),
             recent_priority_fees: RollingWindow::new(std::time::Duration::from_secs(300)),
+            recent_tps: RollingWindow::new(std::time::Duration::from_secs(300)),
+            slot_lag: 0,
             our_tx_count: 0,
             our_tx_failures: 0,
         }
     }
 
     /// Sample chain health metrics from RPC
+    ///
+    /// Holds `&mut self` across the RPC calls; callers sharing the monitor
+    /// should `fetch_sample` first and then `record_sample`.
     pub async fn sample(&mut self, rpc: &RpcClient) {
         if !self.config.enabled {
             return;
         }
+        let sample = fetch_sample(rpc, self.config.fee_percentile).await;
+        self.record_sample(&sample);
+    }
 
-        // Get recent performance samples
-        if let Ok(samples) = rpc.get_recent_performance_samples(Some(5)).await {
-            if !samples.is_empty() {
-                let avg_slot_time = samples
-                    .iter()
-                    .filter(|s| s.num_slots > 0)
-                    .map(|s| (s.sample_period_secs as f64 / s.num_slots as f64) * 1000.0)
-                    .sum::<f64>()
-                    / samples.len() as f64;
-                self.recent_slot_times.add(avg_slot_time);
-            }
+    /// Fold a chain sample into the rolling windows
+    pub fn record_sample(&mut self, sample: &ChainSample) {
+        if let Some(slot_time) = sample.avg_slot_time_ms {
+            self.recent_slot_times.add(slot_time);
         }
-
-        // Get recent priority fees
-        // Note: This may not work on all RPC endpoints
-        if let Ok(fees) = rpc.get_recent_prioritization_fees(&[]).await {
-            if !fees.is_empty() {
-                let avg_fee =
-                    fees.iter().map(|f| f.prioritization_fee).sum::<u64>() / fees.len() as u64;
-                self.recent_priority_fees.add(avg_fee as f64);
-            }
+        if let Some(tps) = sample.tps {
+            self.recent_tps.add(tps);
+        }
+        if let Some(lag) = sample.slot_lag {
+            self.slot_lag = lag;
+        }
+        if let Some(micro_lamports) = sample.priority_fee_micro_lamports {
+            // Micro-lamports per compute unit -> lamports for a whole buy
+            let lamports = micro_lamports.saturating_mul(self.config.fee_compute_units) / 1_000_000;
+            self.recent_priority_fees.add(lamports as f64);
         }
     }
 
@@ -125,13 +234,34 @@ impl ChainHealth {
             1000 // Default 1000 lamports
         };
 
-        let congestion_level = self.calculate_congestion(avg_slot_time, failure_rate);
+        // A lagging RPC node sees stale prices and lands late
+        let mut congestion_level = self.calculate_congestion(avg_slot_time, failure_rate);
+        if self.slot_lag > self.config.max_slot_lag {
+            congestion_level = congestion_level.max(CongestionLevel::Severe);
+        }
         let recommended_action = self.get_action(congestion_level, priority_fee);
 
+        let priority_fee_floor = if self.recent_priority_fees.count() > 0 {
+            let floor = match recommended_action {
+                ChainAction::IncreasePriorityFee { to_lamports } => to_lamports,
+                _ => priority_fee,
+            };
+            floor.min(self.config.max_fee_floor_lamports)
+        } else {
+            0
+        };
+
         ChainState {
             avg_slot_time_ms: avg_slot_time,
+            tps: if self.recent_tps.count() > 0 {
+                self.recent_tps.latest()
+            } else {
+                0.0
+            },
+            slot_lag: self.slot_lag,
             tx_failure_rate: failure_rate,
             priority_fee_lamports: priority_fee,
+            priority_fee_floor_lamports: priority_fee_floor,
             congestion_level,
             recommended_action,
         }
@@ -332,6 +462,40 @@ mod tests {
         assert!((health.get_size_multiplier() - 1.0).abs() < 0.1);
     }
 
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&mut [], 75), None);
+        assert_eq!(percentile(&mut [40, 10, 30, 20, 50], 50), Some(30));
+        assert_eq!(percentile(&mut [40, 10, 30, 20, 50], 100), Some(50));
+    }
+
+    #[test]
+    fn test_record_sample() {
+        let mut health = ChainHealth::default();
+        assert_eq!(health.get_state().priority_fee_floor_lamports, 0);
+
+        health.record_sample(&ChainSample {
+            tps: Some(3000.0),
+            avg_slot_time_ms: Some(400.0),
+            slot_lag: Some(2),
+            priority_fee_micro_lamports: Some(50_000),
+        });
+        let state = health.get_state();
+        assert_eq!(state.tps, 3000.0);
+        assert_eq!(state.slot_lag, 2);
+        assert_eq!(state.congestion_level, CongestionLevel::Normal);
+        // 50k micro-lamports/CU over 100k CU
+        assert_eq!(state.priority_fee_floor_lamports, 5_000);
+
+        // A node far behind the cluster is severe congestion
+        health.record_sample(&ChainSample {
+            slot_lag: Some(120),
+            ..Default::default()
+        });
+        assert_eq!(health.get_state().congestion_level, CongestionLevel::Severe);
+        assert!(health.should_block_entries());
+    }
+
     #[test]
     fn test_record_tx() {
         let mut health = ChainHealth::default();
@@ -2,11 +2,23 @@
 //!
 //! Detect creator capability, not just behavior.
 //! Checks for mint authority, freeze authority, and other dangerous privileges.
+//!
+//! The strategy engine runs the check before every entry: a privilege on the
+//! `blocked_privileges` list that is still active is a fatal reject, unless
+//! the creator is in `allowed_creators`. The privileges seen at entry are kept
+//! on the position; one that comes back later trips the kill-switch.
 
 use serde::{Deserialize, Serialize};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::filter::helius::MintInfo;
+
+/// Metaplex token metadata program
+const METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
 
 /// Token creator privileges
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -25,9 +37,59 @@ pub struct CreatorPrivileges {
     pub mint_authority: Option<String>,
     /// The freeze authority address if active
     pub freeze_authority: Option<String>,
+    /// The metadata update authority if the metadata is mutable
+    #[serde(default)]
+    pub update_authority: Option<String>,
 }
 
 impl CreatorPrivileges {
+    /// From mint info cached by enrichment (metadata not checked)
+    pub fn from_mint_info(info: &MintInfo) -> Self {
+        let mut remaining_privileges = vec![];
+        if info.has_mint_authority() {
+            remaining_privileges.push(Privilege::MintTokens);
+        }
+        if info.has_freeze_authority() {
+            remaining_privileges.push(Privilege::FreezeAccounts);
+        }
+        Self {
+            mint_authority_active: info.has_mint_authority(),
+            freeze_authority_active: info.has_freeze_authority(),
+            metadata_update_authority: false,
+            authority_renounced: info.is_fully_renounced(),
+            remaining_privileges,
+            mint_authority: info.mint_authority.clone(),
+            freeze_authority: info.freeze_authority.clone(),
+            update_authority: None,
+        }
+    }
+
+    /// Privileges currently active
+    pub fn active(&self) -> Vec<Privilege> {
+        let mut active = self.remaining_privileges.clone();
+        let flags = [
+            (self.mint_authority_active, Privilege::MintTokens),
+            (self.freeze_authority_active, Privilege::FreezeAccounts),
+            (self.metadata_update_authority, Privilege::UpdateMetadata),
+        ];
+        for (set, privilege) in flags {
+            if set && !active.contains(&privilege) {
+                active.push(privilege);
+            }
+        }
+        active
+    }
+
+    /// Privileges active in `current` that were not active here
+    pub fn regained(&self, current: &CreatorPrivileges) -> Vec<Privilege> {
+        let before = self.active();
+        current
+            .active()
+            .into_iter()
+            .filter(|privilege| !before.contains(privilege))
+            .collect()
+    }
+
     /// Check if any dangerous authorities are active
     pub fn has_dangerous_authorities(&self) -> bool {
         self.mint_authority_active || self.freeze_authority_active
@@ -108,6 +170,18 @@ pub enum Privilege {
     Unknown(String),
 }
 
+impl std::fmt::Display for Privilege {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Privilege::MintTokens => write!(f, "mint"),
+            Privilege::FreezeAccounts => write!(f, "freeze"),
+            Privilege::UpdateMetadata => write!(f, "metadata update"),
+            Privilege::CloseAccounts => write!(f, "close"),
+            Privilege::Unknown(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 impl Privilege {
     /// Get risk level (0-10)
     pub fn risk_level(&self) -> u8 {
@@ -124,25 +198,54 @@ impl Privilege {
 /// Configuration for privilege checking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrivilegeCheckConfig {
-    /// Fail if mint authority is active
-    pub reject_mint_authority: bool,
-    /// Fail if freeze authority is active
-    pub reject_freeze_authority: bool,
+    /// Check privileges before every entry
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Privileges that reject an entry while still active
+    #[serde(default = "default_blocked_privileges")]
+    pub blocked_privileges: Vec<Privilege>,
+    /// Creators whose tokens are entered whatever privileges they keep
+    #[serde(default)]
+    pub allowed_creators: Vec<String>,
     /// Timeout for RPC calls in milliseconds
+    #[serde(default = "default_rpc_timeout_ms")]
     pub rpc_timeout_ms: u64,
+    /// Re-check open positions this often; a privilege regained since entry
+    /// triggers the kill-switch (0 = off)
+    #[serde(default = "default_recheck_interval_secs")]
+    pub recheck_interval_secs: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_blocked_privileges() -> Vec<Privilege> {
+    vec![Privilege::MintTokens, Privilege::FreezeAccounts]
+}
+
+fn default_rpc_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_recheck_interval_secs() -> u64 {
+    60
 }
 
 impl Default for PrivilegeCheckConfig {
     fn default() -> Self {
         Self {
-            reject_mint_authority: true,
-            reject_freeze_authority: true,
-            rpc_timeout_ms: 5000,
+            enabled: default_enabled(),
+            blocked_privileges: default_blocked_privileges(),
+            allowed_creators: Vec::new(),
+            rpc_timeout_ms: default_rpc_timeout_ms(),
+            recheck_interval_secs: default_recheck_interval_secs(),
         }
     }
 }
 
 /// Creator privilege checker
+#[derive(Debug, Clone)]
 pub struct CreatorPrivilegeChecker {
     config: PrivilegeCheckConfig,
 }
@@ -158,6 +261,24 @@ impl CreatorPrivilegeChecker {
         Self::new(PrivilegeCheckConfig::default())
     }
 
+    pub fn config(&self) -> &PrivilegeCheckConfig {
+        &self.config
+    }
+
+    /// Check a mint, logging failures (None = disabled or unknown)
+    pub async fn lookup(&self, rpc: &RpcClient, mint_address: &str) -> Option<CreatorPrivileges> {
+        if !self.config.enabled {
+            return None;
+        }
+        match self.check(rpc, mint_address).await {
+            Ok(privileges) => Some(privileges),
+            Err(e) => {
+                debug!("Privilege check for {} failed: {}", mint_address, e);
+                None
+            }
+        }
+    }
+
     /// Check creator privileges for a token mint
     pub async fn check(
         &self,
@@ -166,11 +287,12 @@ impl CreatorPrivilegeChecker {
     ) -> Result<CreatorPrivileges, PrivilegeCheckError> {
         let mint_pubkey = Pubkey::from_str(mint_address)
             .map_err(|e| PrivilegeCheckError::InvalidAddress(e.to_string()))?;
+        let timeout = Duration::from_millis(self.config.rpc_timeout_ms);
 
         // Fetch mint account
-        let account = rpc
-            .get_account(&mint_pubkey)
+        let account = tokio::time::timeout(timeout, rpc.get_account(&mint_pubkey))
             .await
+            .map_err(|_| PrivilegeCheckError::Timeout)?
             .map_err(|e| PrivilegeCheckError::RpcError(e.to_string()))?;
 
         // Parse mint data
@@ -221,10 +343,25 @@ impl CreatorPrivilegeChecker {
             remaining_privileges.push(Privilege::FreezeAccounts);
         }
 
-        let authority_renounced = !mint_authority_active && !freeze_authority_active;
+        // Mutable Metaplex metadata leaves the update authority in control
+        // (no metadata account = nothing to update)
+        let metadata_pda = metadata_address(&mint_pubkey);
+        let update_authority = match tokio::time::timeout(timeout, rpc.get_account(&metadata_pda))
+            .await
+            .map_err(|_| PrivilegeCheckError::Timeout)?
+        {
+            Ok(account) => parse_metadata(&account.data)
+                .filter(|(_, is_mutable)| *is_mutable)
+                .map(|(authority, _)| authority),
+            Err(_) => None,
+        };
+        let metadata_update_authority = update_authority.is_some();
+        if metadata_update_authority {
+            remaining_privileges.push(Privilege::UpdateMetadata);
+        }
 
-        // TODO: Check metadata update authority from Metaplex metadata account
-        let metadata_update_authority = false; // Would need additional RPC call
+        let authority_renounced =
+            !mint_authority_active && !freeze_authority_active && !metadata_update_authority;
 
         Ok(CreatorPrivileges {
             mint_authority_active,
@@ -234,6 +371,7 @@ impl CreatorPrivilegeChecker {
             remaining_privileges,
             mint_authority,
             freeze_authority,
+            update_authority,
         })
     }
 
@@ -283,23 +421,69 @@ impl CreatorPrivilegeChecker {
             remaining_privileges,
             mint_authority,
             freeze_authority,
+            update_authority: None,
         })
     }
 
+    /// First blocked privilege still active (None for allowed creators)
+    pub fn blocked_privilege(
+        &self,
+        creator: &str,
+        privileges: &CreatorPrivileges,
+    ) -> Option<Privilege> {
+        if !self.config.enabled || self.config.allowed_creators.iter().any(|c| c == creator) {
+            return None;
+        }
+        privileges
+            .active()
+            .into_iter()
+            .find(|privilege| self.config.blocked_privileges.contains(privilege))
+    }
+
     /// Should this token be rejected based on privileges?
     pub fn should_reject(&self, privileges: &CreatorPrivileges) -> Option<String> {
-        if self.config.reject_mint_authority && privileges.mint_authority_active {
-            return Some(
-                "Mint authority is active - creator can mint unlimited tokens".to_string(),
-            );
-        }
-        if self.config.reject_freeze_authority && privileges.freeze_authority_active {
-            return Some("Freeze authority is active - creator can freeze your tokens".to_string());
-        }
-        None
+        self.blocked_privilege("", privileges)
+            .map(|privilege| match privilege {
+                Privilege::MintTokens => {
+                    "Mint authority is active - creator can mint unlimited tokens".to_string()
+                }
+                Privilege::FreezeAccounts => {
+                    "Freeze authority is active - creator can freeze your tokens".to_string()
+                }
+                other => format!("Creator retains {} authority", other),
+            })
     }
 }
 
+/// Metaplex metadata PDA of a mint
+fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program = Pubkey::from_str(METADATA_PROGRAM_ID).expect("valid metadata program id");
+    Pubkey::find_program_address(&[b"metadata", program.as_ref(), mint.as_ref()], &program).0
+}
+
+/// Update authority and `is_mutable` from a Metaplex metadata account
+///
+/// Layout: key (1), update authority (32), mint (32), name, symbol and uri
+/// (u32 length + bytes each), seller fee (2), creators (Option<Vec<34 bytes>>),
+/// primary sale happened (1), is_mutable (1).
+fn parse_metadata(data: &[u8]) -> Option<(String, bool)> {
+    let update_authority = bs58::encode(data.get(1..33)?).into_string();
+    let mut offset = 65;
+    for _ in 0..3 {
+        let len = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?) as usize;
+        offset += 4 + len;
+    }
+    offset += 2;
+    if *data.get(offset)? == 1 {
+        let count = data.get(offset + 1..offset + 5)?;
+        offset += 4 + u32::from_le_bytes(count.try_into().ok()?) as usize * 34;
+    }
+    // Creators tag, primary sale happened
+    offset += 2;
+    let is_mutable = *data.get(offset)? != 0;
+    Some((update_authority, is_mutable))
+}
+
 /// Errors during privilege checking
 #[derive(Debug, Clone)]
 pub enum PrivilegeCheckError {
@@ -423,6 +607,65 @@ mod tests {
         assert!(checker.should_reject(&privileges).is_some());
     }
 
+    #[test]
+    fn test_blocklist_and_allowlist() {
+        let checker = CreatorPrivilegeChecker::new(PrivilegeCheckConfig {
+            blocked_privileges: vec![Privilege::FreezeAccounts, Privilege::UpdateMetadata],
+            allowed_creators: vec!["trusted".to_string()],
+            ..Default::default()
+        });
+
+        let mut privileges = CreatorPrivileges {
+            mint_authority_active: true,
+            ..Default::default()
+        };
+        // Mint authority isn't on this blocklist
+        assert!(checker.blocked_privilege("creator", &privileges).is_none());
+
+        privileges.metadata_update_authority = true;
+        assert_eq!(
+            checker.blocked_privilege("creator", &privileges),
+            Some(Privilege::UpdateMetadata)
+        );
+        assert!(checker.blocked_privilege("trusted", &privileges).is_none());
+    }
+
+    #[test]
+    fn test_regained_privileges() {
+        let at_entry = CreatorPrivileges {
+            authority_renounced: true,
+            ..Default::default()
+        };
+        let now = CreatorPrivileges {
+            metadata_update_authority: true,
+            ..Default::default()
+        };
+        assert_eq!(at_entry.regained(&now), vec![Privilege::UpdateMetadata]);
+        assert!(now.regained(&at_entry).is_empty());
+    }
+
+    #[test]
+    fn test_parse_metadata() {
+        let mut data = vec![4u8];
+        data.extend_from_slice(&[7u8; 32]); // update authority
+        data.extend_from_slice(&[9u8; 32]); // mint
+        for field in ["Token", "TKN", "https://example.com/t.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.push(1); // one creator
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[1u8; 34]);
+        data.push(0); // primary sale happened
+        data.push(1); // is_mutable
+
+        let (authority, is_mutable) = parse_metadata(&data).unwrap();
+        assert_eq!(authority, bs58::encode([7u8; 32]).into_string());
+        assert!(is_mutable);
+        assert!(parse_metadata(&data[..70]).is_none());
+    }
+
     #[test]
     fn test_summary() {
         let mut privileges = CreatorPrivileges::default();
@@ -38,11 +38,15 @@ impl RollingWindow {
 
     /// Add a new sample
     pub fn add(&mut self, value: f64) {
-        let now = Instant::now();
-        self.samples.push_back((now, value));
+        self.add_at(Instant::now(), value);
+    }
+
+    /// Add a sample taken at `time` (replays), pruning relative to it
+    pub fn add_at(&mut self, time: Instant, value: f64) {
+        self.samples.push_back((time, value));
 
         // Remove old samples
-        self.prune();
+        self.prune(time);
 
         // Cap at max samples
         while self.samples.len() > self.max_samples {
@@ -51,8 +55,10 @@ impl RollingWindow {
     }
 
     /// Remove samples older than window duration
-    fn prune(&mut self) {
-        let cutoff = Instant::now() - self.window_duration;
+    fn prune(&mut self, now: Instant) {
+        let Some(cutoff) = now.checked_sub(self.window_duration) else {
+            return;
+        };
         while let Some((time, _)) = self.samples.front() {
             if *time < cutoff {
                 self.samples.pop_front();
@@ -10,21 +10,28 @@ use tokio::sync::RwLock;
 
 use super::arbitrator::DecisionArbitrator;
 use super::chain_health::{ChainHealth, ChainHealthConfig};
+use super::creator_privileges::{CreatorPrivilegeChecker, CreatorPrivileges, PrivilegeCheckConfig};
 use super::delta_tracker::DeltaTracker;
 use super::execution_feedback::{ExecutionFeedback, ExecutionFeedbackConfig};
-use super::exit_manager::{ExitManager, ExitManagerConfig, PositionContext};
-use super::fatal_risk::{FatalRiskConfig, FatalRiskContext, FatalRiskEngine};
-use super::liquidity::{LiquidityAnalyzer, LiquidityConfig};
+use super::exit_manager::{ExitAction, ExitManager, ExitManagerConfig, PositionContext};
+use super::fatal_risk::{FatalRisk, FatalRiskConfig, FatalRiskContext, FatalRiskEngine};
+use super::liquidity::{LiquidityAnalysis, LiquidityAnalyzer, LiquidityConfig};
 use super::portfolio_risk::{PortfolioRiskConfig, PortfolioRiskGovernor};
 use super::price_action::{PriceAction, PriceActionAnalyzer};
-use super::randomization::{RandomizationConfig, Randomizer};
+use super::profiles::{StrategyProfile, BASE_PROFILE};
+use super::randomization::{RandomizationConfig, RandomizedBuy, Randomizer};
 use super::regime::{
-    CreatorBehavior, OrderFlowAnalysis, RegimeClassification, RegimeClassifier, TokenDistribution,
+    CreatorBehavior, OrderFlowAnalysis, RegimeClassification, RegimeClassifier, RegimeStrategy,
+    TokenDistribution,
 };
-use super::sizing::{PositionSizer, PositionSizingConfig, SizingContext};
+use super::scale_in::{ScaleInConfig, ScaleInPlan};
+use super::sizing::{EquitySnapshot, PositionSizer, PositionSizingConfig, SizingContext};
+use super::slippage::SlippageEstimatorConfig;
+use super::tactics::TacticsConfig;
+use super::throttle::ThrottleConfig;
 use super::types::{
-    ArbitratedDecision, DecisionExplanation, EntrySignal, ExitSignal, Position, TokenRegime,
-    TradingAction, TradingStrategy,
+    ArbitratedDecision, DecisionExplanation, DecisionSource, EntrySignal, ExitSignal, ExitStyle,
+    Position, Proposal, ProposedAction, RegimeKind, TokenRegime, TradingAction, TradingStrategy,
 };
 
 /// Strategy engine configuration
@@ -34,6 +41,10 @@ pub struct StrategyEngineConfig {
     pub enabled: bool,
     #[serde(default)]
     pub default_strategy: TradingStrategy,
+    /// Strategy and sizing per classified regime (unmapped regimes use
+    /// `default_strategy`)
+    #[serde(default)]
+    pub regime_strategies: HashMap<RegimeKind, RegimeStrategy>,
     #[serde(default)]
     pub position_sizing: PositionSizingConfig,
     #[serde(default)]
@@ -41,6 +52,8 @@ pub struct StrategyEngineConfig {
     #[serde(default)]
     pub fatal_risks: FatalRiskConfig,
     #[serde(default)]
+    pub creator_privileges: PrivilegeCheckConfig,
+    #[serde(default)]
     pub portfolio_risk: PortfolioRiskConfig,
     #[serde(default)]
     pub chain_health: ChainHealthConfig,
@@ -50,6 +63,22 @@ pub struct StrategyEngineConfig {
     pub randomization: RandomizationConfig,
     #[serde(default)]
     pub liquidity: LiquidityConfig,
+    #[serde(default)]
+    pub scale_in: ScaleInConfig,
+    #[serde(default)]
+    pub tactics: TacticsConfig,
+    /// Trading windows and launch-rate governor (applied by `snipe start`)
+    #[serde(default)]
+    pub throttle: ThrottleConfig,
+    /// Per-buy slippage estimate (applied by `snipe start`)
+    #[serde(default)]
+    pub slippage: SlippageEstimatorConfig,
+    /// Named overrides of the fields above (`[strategy.profiles.<name>]`)
+    #[serde(default)]
+    pub profiles: HashMap<String, StrategyProfile>,
+    /// Profile applied at startup (unset = base config)
+    #[serde(default)]
+    pub active_profile: Option<String>,
 }
 
 fn default_enabled() -> bool {
@@ -61,14 +90,22 @@ impl Default for StrategyEngineConfig {
         Self {
             enabled: true,
             default_strategy: TradingStrategy::Adaptive,
+            regime_strategies: HashMap::new(),
             position_sizing: PositionSizingConfig::default(),
             exits: ExitManagerConfig::default(),
             fatal_risks: FatalRiskConfig::default(),
+            creator_privileges: PrivilegeCheckConfig::default(),
             portfolio_risk: PortfolioRiskConfig::default(),
             chain_health: ChainHealthConfig::default(),
             execution_feedback: ExecutionFeedbackConfig::default(),
             randomization: RandomizationConfig::default(),
             liquidity: LiquidityConfig::default(),
+            scale_in: ScaleInConfig::default(),
+            tactics: TacticsConfig::default(),
+            throttle: ThrottleConfig::default(),
+            slippage: SlippageEstimatorConfig::default(),
+            profiles: HashMap::new(),
+            active_profile: None,
         }
     }
 }
@@ -77,6 +114,10 @@ impl Default for StrategyEngineConfig {
 #[derive(Debug, Clone)]
 pub struct TokenAnalysisContext {
     pub mint: String,
+    /// Token creator (empty = unknown)
+    pub creator: String,
+    /// Creator's funder, when traced
+    pub funding_cluster: Option<String>,
     pub order_flow: OrderFlowAnalysis,
     pub distribution: TokenDistribution,
     pub creator_behavior: CreatorBehavior,
@@ -84,6 +125,14 @@ pub struct TokenAnalysisContext {
     pub sol_reserves: f64,
     pub token_reserves: f64,
     pub confidence_score: f64,
+    /// Honeypot round-trip loss beyond fees (None = not checked)
+    pub round_trip_excess_loss_pct: Option<f64>,
+    /// Mint and metadata authorities (None = read the cached mint info)
+    pub privileges: Option<CreatorPrivileges>,
+    /// What the filter, entry tactics and kill-switch proposed
+    pub proposals: Vec<Proposal>,
+    /// Hot-wallet equity for bankroll sizing (None = fixed base size)
+    pub equity: Option<EquitySnapshot>,
 }
 
 /// Entry evaluation result
@@ -93,6 +142,10 @@ pub struct EntryEvaluation {
     pub regime: RegimeClassification,
     pub position_size: f64,
     pub explanation: DecisionExplanation,
+    /// Creator privileges the decision saw (None = unknown)
+    pub privileges: Option<CreatorPrivileges>,
+    /// Curve depth at entry, for the expected exit impact
+    pub liquidity: LiquidityAnalysis,
 }
 
 /// Position evaluation result
@@ -106,10 +159,15 @@ pub struct PositionEvaluation {
 
 /// Main Strategy Engine
 pub struct StrategyEngine {
+    /// Config with the active profile applied
     config: StrategyEngineConfig,
+    /// Config as loaded, profiles resolve against it
+    base_config: StrategyEngineConfig,
+    active_profile: String,
 
     // Core components
     fatal_risk: FatalRiskEngine,
+    privilege_checker: CreatorPrivilegeChecker,
     liquidity: LiquidityAnalyzer,
     portfolio_risk: Arc<RwLock<PortfolioRiskGovernor>>,
     chain_health: Arc<RwLock<ChainHealth>>,
@@ -125,13 +183,25 @@ pub struct StrategyEngine {
     // Per-token trackers
     delta_trackers: HashMap<String, DeltaTracker>,
     price_analyzers: HashMap<String, PriceActionAnalyzer>,
+
+    /// Hot wallet balance floor (None = not watched)
+    balance_watch: Option<Arc<crate::wallet::manager::BalanceWatcher>>,
 }
 
 impl StrategyEngine {
     /// Create a new strategy engine
-    pub fn new(config: StrategyEngineConfig) -> Self {
+    pub fn new(base_config: StrategyEngineConfig) -> Self {
+        let active = base_config.active_profile.as_deref();
+        let (config, active_profile) = match base_config.resolve(active) {
+            Ok(config) => (config, active.unwrap_or(BASE_PROFILE).to_string()),
+            Err(e) => {
+                tracing::warn!("{}, using the base strategy config", e);
+                (base_config.clone(), BASE_PROFILE.to_string())
+            }
+        };
         Self {
             fatal_risk: FatalRiskEngine::new(config.fatal_risks.clone()),
+            privilege_checker: CreatorPrivilegeChecker::new(config.creator_privileges.clone()),
             liquidity: LiquidityAnalyzer::new(config.liquidity.clone()),
             portfolio_risk: Arc::new(RwLock::new(PortfolioRiskGovernor::new(
                 config.portfolio_risk.clone(),
@@ -144,12 +214,16 @@ impl StrategyEngine {
             position_sizer: PositionSizer::new(config.position_sizing.clone()),
             exit_manager: Arc::new(RwLock::new(ExitManager::new(config.exits.clone()))),
             arbitrator: DecisionArbitrator::default(),
-            randomizer: Arc::new(RwLock::new(Randomizer::from_entropy(
+            randomizer: Arc::new(RwLock::new(Randomizer::new(
                 config.randomization.clone(),
+                config.randomization.seed,
             ))),
             delta_trackers: HashMap::new(),
             price_analyzers: HashMap::new(),
+            balance_watch: None,
             config,
+            base_config,
+            active_profile,
         }
     }
 
@@ -158,6 +232,44 @@ impl StrategyEngine {
         self.config.enabled
     }
 
+    /// Name of the strategy profile in effect (`base` if none)
+    pub fn active_profile(&self) -> &str {
+        &self.active_profile
+    }
+
+    /// Default strategy of the active profile
+    pub fn default_strategy(&self) -> TradingStrategy {
+        self.config.default_strategy
+    }
+
+    /// Sizing config of the active profile
+    pub fn position_sizing(&self) -> &PositionSizingConfig {
+        &self.config.position_sizing
+    }
+
+    /// Switch to another strategy profile; sizing, exits and playbooks
+    /// follow from the next decision on. Open positions keep their
+    /// recorded strategy.
+    pub async fn use_profile(&mut self, name: &str) -> Result<(), String> {
+        let config = self.base_config.resolve(Some(name))?;
+        super::profiles::validate_exits(&config.exits)
+            .map_err(|e| format!("profile {}: {}", name, e))?;
+        self.position_sizer = PositionSizer::new(config.position_sizing.clone());
+        self.exit_manager
+            .write()
+            .await
+            .set_config(config.exits.clone());
+        self.config = config;
+        tracing::info!(
+            "Strategy profile: {} -> {} (default strategy {})",
+            self.active_profile,
+            name,
+            self.config.default_strategy
+        );
+        self.active_profile = name.to_string();
+        Ok(())
+    }
+
     /// Evaluate a token for potential entry
     pub async fn evaluate_entry(&mut self, ctx: &TokenAnalysisContext) -> EntryEvaluation {
         // 1. Build fatal risk context
@@ -173,7 +285,7 @@ impl StrategyEngine {
         let fatal_context = FatalRiskContext {
             mint: ctx.mint.clone(),
             creator: String::new(),       // Would come from token data
-            mint_authority_active: false, // Would come from RPC
+            mint_authority_active: false, // Covered by the privilege check
             freeze_authority_active: false,
             creator_sell_info,
             effective_liquidity_sol: ctx.sol_reserves,
@@ -182,12 +294,24 @@ impl StrategyEngine {
             liquidity_drop_pct: None, // Would track over time
             wash_trading_score: ctx.order_flow.wash_trading_score,
             failed_sell_count: 0,
+            round_trip_excess_loss_pct: ctx.round_trip_excess_loss_pct,
             price_drop_from_ath: 0.0,
             chain_congestion_critical: false,
         };
 
-        // 2. Check fatal risks
-        let fatal_result = self.fatal_risk.check(&fatal_context).await;
+        // 2. Check fatal risks, creator privileges first
+        let privileges = ctx
+            .privileges
+            .clone()
+            .or_else(|| self.fatal_risk.cached_privileges(&ctx.mint));
+        let privilege_risk = privileges
+            .as_ref()
+            .and_then(|p| self.privilege_checker.blocked_privilege(&ctx.creator, p))
+            .map(|privilege| FatalRisk::CreatorPrivilege { privilege });
+        let fatal_result = match privilege_risk {
+            Some(risk) => Some(risk),
+            None => self.fatal_risk.check(&fatal_context).await,
+        };
 
         // 3. Get chain state
         let chain_health = self.chain_health.read().await;
@@ -204,6 +328,7 @@ impl StrategyEngine {
             &ctx.creator_behavior,
             &delta_metrics,
         );
+        let playbook = self.playbook_for(&regime.regime);
 
         // Calculate preliminary size for portfolio check
         let liquidity = self
@@ -231,13 +356,20 @@ impl StrategyEngine {
             portfolio_remaining_sol: 1.0, // Will be updated
             chain_size_factor,
             execution_size_factor,
+            equity: ctx.equity.clone(),
         };
+        let sizing = self.position_sizer.explain_size(&sizing_ctx);
 
-        let position_size = self.position_sizer.calculate_size(&sizing_ctx);
-
-        // Check portfolio limits
+        // Check portfolio limits; entries after a loss streak are sized down.
+        // The round-trip impact cap holds whatever the multipliers.
         let portfolio = self.portfolio_risk.read().await;
-        let portfolio_result = portfolio.can_open_position(position_size);
+        let loss_streak_multiplier = portfolio.size_multiplier();
+        let impact_cap = self.position_sizer.impact_cap(&liquidity);
+        let position_size = (sizing.final_size * playbook.size_multiplier * loss_streak_multiplier)
+            .min(impact_cap.unwrap_or(f64::MAX));
+        let portfolio_result = portfolio.can_open_position(position_size).and_then(|()| {
+            portfolio.check_correlation(&ctx.creator, ctx.funding_cluster.as_deref())
+        });
         let _portfolio_state = portfolio.get_state();
         drop(portfolio);
 
@@ -245,7 +377,7 @@ impl StrategyEngine {
         let strategy_signal = if regime.should_enter && ctx.confidence_score > 0.5 {
             Some(EntrySignal {
                 mint: ctx.mint.clone(),
-                strategy: self.config.default_strategy.clone(),
+                strategy: playbook.strategy,
                 confidence: ctx.confidence_score,
                 suggested_size_sol: position_size,
                 urgency: super::types::Urgency::Normal,
@@ -257,51 +389,122 @@ impl StrategyEngine {
         };
 
         // 6. Arbitrate decision
-        let decision = self.arbitrator.arbitrate_entry(
+        let decision = self.arbitrator.arbitrate_entry_with(
             &ctx.mint,
             fatal_result,
             &chain_state.recommended_action,
             portfolio_result,
             strategy_signal,
             &regime.regime,
+            &ctx.proposals,
         );
 
-        // 7. Apply randomization if entering
-        let final_size = if matches!(decision.action, TradingAction::Enter { .. }) {
-            let mut randomizer = self.randomizer.write().await;
-            let jittered = randomizer.jitter_entry(position_size);
+        // A regime mapped to a zero multiplier is not traded at all
+        let decision = if playbook.size_multiplier <= 0.0
+            && !matches!(
+                decision.action,
+                TradingAction::Skip { .. } | TradingAction::FatalReject { .. }
+            ) {
+            let reason = format!(
+                "Regime {} disabled in regime_strategies",
+                regime.regime.kind()
+            );
+            let disabled = Proposal::new(
+                DecisionSource::RegimeOptimization,
+                ProposedAction::Skip,
+                1.0,
+                reason.clone(),
+            );
+            ArbitratedDecision {
+                action: TradingAction::Skip { reason },
+                source: DecisionSource::RegimeOptimization,
+                arbitration: decision
+                    .arbitration
+                    .map(|a| a.overruled(disabled, "regime_disabled")),
+                ..decision
+            }
+        } else {
+            decision
+        };
 
-            if jittered.should_skip {
+        // 7. Random skip if entering (size and fee jitter happen per buy, see
+        // `randomize_buy`, so every entry path gets the same treatment)
+        if matches!(decision.action, TradingAction::Enter { .. }) {
+            let should_skip = self.randomizer.write().await.should_skip_randomly();
+            if should_skip {
                 // Randomly skip for adversarial resistance
+                let reason = "Random skip for adversarial resistance".to_string();
+                let random_skip = Proposal::new(
+                    DecisionSource::Strategy,
+                    ProposedAction::Skip,
+                    1.0,
+                    reason.clone(),
+                );
                 return EntryEvaluation {
                     decision: ArbitratedDecision {
-                        action: TradingAction::Skip {
-                            reason: "Random skip for adversarial resistance".to_string(),
-                        },
+                        action: TradingAction::Skip { reason },
+                        arbitration: decision
+                            .arbitration
+                            .map(|a| a.overruled(random_skip, "random_skip")),
                         ..decision
                     },
                     regime: regime.clone(),
                     position_size: 0.0,
-                    explanation: self.build_explanation(&ctx, &regime, 0.0, &chain_state),
+                    explanation: self.build_explanation(
+                        &ctx,
+                        &regime,
+                        playbook.strategy,
+                        0.0,
+                        &chain_state,
+                    ),
+                    privileges,
+                    liquidity,
                 };
             }
+        }
 
-            jittered.size
-        } else {
-            position_size
-        };
+        if let TradingAction::Enter { size_sol, .. } = &decision.action {
+            tracing::info!(
+                "Entry size for {}: {} x playbook {:.2}x x loss streak {:.2}x = {:.4} SOL",
+                ctx.mint,
+                sizing.derivation(),
+                playbook.size_multiplier,
+                loss_streak_multiplier,
+                size_sol
+            );
+        }
 
         // 8. Build explanation
-        let explanation = self.build_explanation(&ctx, &regime, final_size, &chain_state);
+        let explanation = self.build_explanation(
+            &ctx,
+            &regime,
+            playbook.strategy,
+            position_size,
+            &chain_state,
+        );
 
         EntryEvaluation {
             decision,
             regime,
-            position_size: final_size,
+            position_size,
             explanation,
+            privileges,
+            liquidity,
         }
     }
 
+    /// Strategy and extra size multiplier configured for a regime
+    pub fn playbook_for(&self, regime: &TokenRegime) -> RegimeStrategy {
+        self.config
+            .regime_strategies
+            .get(&regime.kind())
+            .cloned()
+            .unwrap_or(RegimeStrategy {
+                strategy: self.config.default_strategy,
+                size_multiplier: 1.0,
+            })
+    }
+
     /// Evaluate an existing position for potential exit
     pub async fn evaluate_position(&mut self, position: &Position) -> PositionEvaluation {
         // Get current price and calculate PnL
@@ -379,6 +582,64 @@ impl StrategyEngine {
         }
     }
 
+    /// Exit style for a new position (None = the monitor's legacy rules)
+    pub async fn select_exit_style(
+        &self,
+        entry_type: &str,
+        regime: Option<RegimeKind>,
+    ) -> Option<ExitStyle> {
+        self.exit_manager
+            .read()
+            .await
+            .style_for_entry(entry_type, regime)
+    }
+
+    /// Concrete exit action for a held position under its exit style
+    ///
+    /// Uses the token's delta tracker and price action; `position.highest_price`
+    /// and `exit_levels_hit` come from the position monitor.
+    pub async fn evaluate_exit_style(
+        &mut self,
+        position: &Position,
+        style: &ExitStyle,
+        current_price: f64,
+        hold_time_secs: u64,
+    ) -> ExitAction {
+        let pnl_pct = if position.entry_price > 0.0 {
+            ((current_price - position.entry_price) / position.entry_price) * 100.0
+        } else {
+            0.0
+        };
+        let delta = self
+            .get_or_create_delta_tracker(&position.mint)
+            .compute_metrics(&position.mint);
+        let price_action = self.get_or_create_price_analyzer(&position.mint).analyze();
+
+        let ctx = PositionContext {
+            position: position.clone(),
+            current_price,
+            high_price: position.highest_price.max(current_price),
+            pnl_pct,
+            hold_time_secs,
+            entry_strategy: position.strategy,
+            // Styles are chosen at entry; the regime isn't re-classified here
+            regime: RegimeClassification {
+                regime: TokenRegime::Unknown {
+                    data_completeness: 0.5,
+                },
+                confidence: 0.5,
+                reasons: vec![],
+                should_enter: false,
+                size_multiplier: 1.0,
+            },
+            delta,
+            price_action,
+            levels_hit: position.exit_levels_hit.clone(),
+        };
+
+        self.exit_manager.read().await.evaluate(&ctx, style)
+    }
+
     /// Record a successful entry
     pub async fn record_entry(&mut self, position: Position) {
         let mut portfolio = self.portfolio_risk.write().await;
@@ -402,9 +663,22 @@ impl StrategyEngine {
         self.price_analyzers.remove(mint);
     }
 
+    /// Record realized P&L from a partial exit (the position stays open)
+    pub async fn record_partial_exit(&mut self, pnl_sol: f64) {
+        self.portfolio_risk.write().await.record_pnl(pnl_sol);
+    }
+
+    /// Restore portfolio risk state from `path` and persist it there
+    pub async fn set_portfolio_persistence(
+        &self,
+        path: std::path::PathBuf,
+    ) -> crate::error::Result<()> {
+        self.portfolio_risk.write().await.set_persistence_path(path)
+    }
+
     /// Record execution result for feedback
     pub async fn record_execution(
-        &mut self,
+        &self,
         mint: &str,
         is_buy: bool,
         size_sol: f64,
@@ -439,9 +713,21 @@ impl StrategyEngine {
         chain_health.record_tx(true);
     }
 
+    /// Attach a buy's slippage estimate to its recorded fill
+    pub async fn record_slippage_estimate(
+        &self,
+        tx_sig: &str,
+        estimate: super::slippage::SlippageEstimate,
+    ) {
+        self.execution_feedback
+            .write()
+            .await
+            .record_estimate(tx_sig, estimate);
+    }
+
     /// Record a failed transaction
     pub async fn record_tx_failure(
-        &mut self,
+        &self,
         mint: &str,
         is_buy: bool,
         size_sol: f64,
@@ -496,6 +782,11 @@ impl StrategyEngine {
             super::fatal_risk::FatalRiskEngine::with_cache(self.config.fatal_risks.clone(), cache);
     }
 
+    /// Pause entries while the hot wallet balance is below the entry floor
+    pub fn set_balance_watcher(&mut self, watcher: Arc<crate::wallet::manager::BalanceWatcher>) {
+        self.balance_watch = Some(watcher);
+    }
+
     /// Check for exit signals on an existing position
     pub async fn check_exit(
         &mut self,
@@ -508,6 +799,14 @@ impl StrategyEngine {
         use super::exit_manager::PositionContext;
         use super::types::{ExitStyle, Position};
 
+        // Exits follow the strategy the position was entered with
+        let strategy = self
+            .portfolio_risk
+            .read()
+            .await
+            .get_position(mint)
+            .map_or(self.config.default_strategy, |p| p.strategy);
+
         // Get or create analyzers for this token
         let delta_metrics = self.get_or_create_delta_tracker(mint).compute_metrics(mint);
         let price_action = self.get_or_create_price_analyzer(mint).analyze();
@@ -519,11 +818,13 @@ impl StrategyEngine {
             entry_time: chrono::Utc::now() - chrono::Duration::seconds(hold_time_secs as i64),
             size_sol: 0.1, // Placeholder
             tokens_held: 0,
-            strategy: self.config.default_strategy.clone(),
+            strategy,
             exit_style: ExitStyle::default(),
             highest_price: current_price,
             lowest_price: entry_price.min(current_price),
             exit_levels_hit: vec![],
+            creator: None,
+            funding_cluster: None,
         };
 
         // Get regime classification with default values
@@ -551,7 +852,7 @@ impl StrategyEngine {
             high_price,
             pnl_pct,
             hold_time_secs,
-            entry_strategy: self.config.default_strategy.clone(),
+            entry_strategy: strategy,
             regime,
             delta: delta_metrics,
             price_action,
@@ -571,6 +872,14 @@ impl StrategyEngine {
         randomizer.jitter_entry_delay()
     }
 
+    /// Randomize a buy's size and priority fee; send and record the result
+    pub async fn randomize_buy(&self, size_sol: f64, priority_fee_lamports: u64) -> RandomizedBuy {
+        self.randomizer
+            .write()
+            .await
+            .randomize_buy(size_sol, priority_fee_lamports)
+    }
+
     /// Get exit delay for randomization
     pub async fn get_exit_delay(&self) -> std::time::Duration {
         let mut randomizer = self.randomizer.write().await;
@@ -579,6 +888,15 @@ impl StrategyEngine {
 
     /// Check if trading should be paused, returning the reason if paused
     pub async fn should_pause_trading_with_reason(&self) -> Option<String> {
+        // Check hot wallet balance
+        if let Some(reason) = self
+            .balance_watch
+            .as_ref()
+            .and_then(|watcher| watcher.entry_block_reason())
+        {
+            return Some(reason);
+        }
+
         // Check chain health
         let chain_health = self.chain_health.read().await;
         let chain_state = chain_health.get_state();
@@ -656,13 +974,57 @@ impl StrategyEngine {
         feedback.get_quality()
     }
 
+    /// Buy slippage (bps): the configured slippage, widened while fills run hot
+    pub async fn slippage_bps(&self, base_bps: u32) -> u32 {
+        self.execution_feedback.read().await.slippage_bps(base_bps)
+    }
+
+    /// Restore the execution adjustment from `path` and save the report there
+    pub async fn set_execution_persistence(
+        &self,
+        path: std::path::PathBuf,
+    ) -> crate::error::Result<()> {
+        self.execution_feedback
+            .write()
+            .await
+            .set_persistence_path(path)
+    }
+
     /// Sample chain health (call periodically)
     pub async fn sample_chain_health(
         &self,
         rpc: &solana_client::nonblocking::rpc_client::RpcClient,
-    ) {
+    ) -> super::chain_health::ChainState {
+        let sample =
+            super::chain_health::fetch_sample(rpc, self.config.chain_health.fee_percentile).await;
+        self.record_chain_sample(&sample).await
+    }
+
+    /// Push a chain sample taken elsewhere, returning the updated state
+    pub async fn record_chain_sample(
+        &self,
+        sample: &super::chain_health::ChainSample,
+    ) -> super::chain_health::ChainState {
         let mut chain_health = self.chain_health.write().await;
-        chain_health.sample(rpc).await;
+        chain_health.record_sample(sample);
+        chain_health.get_state()
+    }
+
+    /// Build a scale-in plan for a StrongBuy entry (None if scale-in is disabled)
+    pub fn plan_scale_in(&self, size_sol: f64) -> Option<ScaleInPlan> {
+        let config = &self.config.scale_in;
+        if !config.enabled || config.tranche_count <= 1 || size_sol <= 0.0 {
+            return None;
+        }
+        Some(ScaleInPlan::new(size_sol, config))
+    }
+
+    /// Quick fatal-risk check for a token we are scaling into
+    pub async fn quick_fatal_check(&self, mint: &str, creator: &str) -> Option<String> {
+        self.fatal_risk
+            .quick_check(mint, creator)
+            .await
+            .map(|risk| risk.description())
     }
 
     /// Mark a tiered exit level as hit
@@ -695,6 +1057,7 @@ impl StrategyEngine {
         &self,
         ctx: &TokenAnalysisContext,
         regime: &RegimeClassification,
+        strategy: TradingStrategy,
         position_size: f64,
         chain_state: &super::chain_health::ChainState,
     ) -> DecisionExplanation {
@@ -709,11 +1072,11 @@ impl StrategyEngine {
             regime_confidence: regime.confidence,
             data_completeness: ctx.confidence_score,
             missing_data: vec![],
-            selected_strategy: self.config.default_strategy.clone(),
+            selected_strategy: strategy,
             strategy_reason: regime.reasons.join(", "),
             position_size_sol: position_size,
             exit_style: super::types::ExitStyle::default(),
-            decision_source: super::types::DecisionSource::Strategy,
+            decision_source: DecisionSource::Strategy,
             overridden_signals: vec![],
             open_position_count: 0,
             total_exposure_sol: 0.0,
@@ -785,6 +1148,8 @@ mod tests {
             highest_price: 0.001,
             lowest_price: 0.001,
             exit_levels_hit: vec![],
+            creator: None,
+            funding_cluster: None,
         };
 
         engine.record_entry(position).await;
@@ -815,6 +1180,8 @@ mod tests {
 
         let ctx = TokenAnalysisContext {
             mint: "wash_mint".to_string(),
+            creator: String::new(),
+            funding_cluster: None,
             order_flow: OrderFlowAnalysis {
                 wash_trading_score: 0.9, // High wash trading
                 organic_score: 0.1,
@@ -826,6 +1193,10 @@ mod tests {
             sol_reserves: 100.0,
             token_reserves: 1_000_000.0,
             confidence_score: 0.8,
+            round_trip_excess_loss_pct: None,
+            privileges: None,
+            proposals: vec![],
+            equity: None,
         };
 
         let evaluation = engine.evaluate_entry(&ctx).await;
@@ -837,4 +1208,108 @@ mod tests {
         ));
         assert!(!evaluation.regime.should_enter);
     }
+
+    #[tokio::test]
+    async fn test_evaluate_entry_creator_privileges() {
+        let ctx = TokenAnalysisContext {
+            mint: "freeze_mint".to_string(),
+            creator: "creator".to_string(),
+            funding_cluster: None,
+            order_flow: OrderFlowAnalysis::default(),
+            distribution: TokenDistribution::default(),
+            creator_behavior: CreatorBehavior::default(),
+            price_action: PriceAction::default(),
+            sol_reserves: 100.0,
+            token_reserves: 1_000_000.0,
+            confidence_score: 0.8,
+            round_trip_excess_loss_pct: None,
+            privileges: Some(CreatorPrivileges {
+                freeze_authority_active: true,
+                ..Default::default()
+            }),
+            proposals: vec![],
+            equity: None,
+        };
+
+        let mut engine = StrategyEngine::default();
+        let evaluation = engine.evaluate_entry(&ctx).await;
+        assert!(matches!(
+            evaluation.decision.action,
+            TradingAction::FatalReject { .. }
+        ));
+        assert!(evaluation.privileges.unwrap().freeze_authority_active);
+
+        // Allowlisted creators pass the privilege check
+        let mut config = StrategyEngineConfig::default();
+        config.creator_privileges.allowed_creators = vec!["creator".to_string()];
+        let mut engine = StrategyEngine::new(config);
+        let evaluation = engine.evaluate_entry(&ctx).await;
+        assert!(!matches!(
+            evaluation.decision.action,
+            TradingAction::FatalReject { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_regime_playbook() {
+        let organic = TokenAnalysisContext {
+            mint: "organic_mint".to_string(),
+            creator: String::new(),
+            funding_cluster: None,
+            order_flow: OrderFlowAnalysis {
+                organic_score: 0.9,
+                buy_sell_ratio: 0.8,
+                ..Default::default()
+            },
+            distribution: TokenDistribution {
+                gini_coefficient: 0.5,
+                holder_count: 50,
+                ..Default::default()
+            },
+            creator_behavior: CreatorBehavior::default(),
+            price_action: PriceAction::default(),
+            sol_reserves: 100.0,
+            token_reserves: 1_000_000.0,
+            confidence_score: 0.8,
+            round_trip_excess_loss_pct: None,
+            privileges: None,
+            proposals: vec![],
+            equity: None,
+        };
+
+        let mut config = StrategyEngineConfig::default();
+        config.regime_strategies.insert(
+            RegimeKind::OrganicPump,
+            RegimeStrategy {
+                strategy: TradingStrategy::MomentumSurfing,
+                size_multiplier: 1.0,
+            },
+        );
+        let mut engine = StrategyEngine::new(config.clone());
+        let evaluation = engine.evaluate_entry(&organic).await;
+        assert_eq!(evaluation.regime.regime.kind(), RegimeKind::OrganicPump);
+        assert_eq!(
+            evaluation.explanation.selected_strategy,
+            TradingStrategy::MomentumSurfing
+        );
+        // Unmapped regimes keep the default strategy
+        let unknown = TokenRegime::default();
+        assert_eq!(
+            engine.playbook_for(&unknown).strategy,
+            TradingStrategy::Adaptive
+        );
+
+        // Zero multiplier: the regime is not traded
+        config
+            .regime_strategies
+            .get_mut(&RegimeKind::OrganicPump)
+            .unwrap()
+            .size_multiplier = 0.0;
+        let mut engine = StrategyEngine::new(config);
+        let evaluation = engine.evaluate_entry(&organic).await;
+        match evaluation.decision.action {
+            TradingAction::Skip { reason } => assert!(reason.contains("organic_pump")),
+            other => panic!("expected skip, got {:?}", other),
+        }
+    }
 }
@@ -2,11 +2,23 @@
 //!
 //! Track fill quality to adjust confidence and detect adverse conditions.
 //! Records slippage, latency, and fill rates.
-
+//!
+//! Fills also drive an adjustment loop: when the last `adjust_window` buy
+//! fills all slip past `slippage_penalty_threshold_pct`, slippage is widened
+//! by `slippage_step_bps` (up to `max_extra_slippage_bps`), then entry size is
+//! reduced. The same number of clean fills undoes one step at a time. Every
+//! adjustment is logged with its trigger and saved for `snipe status
+//! --execution`.
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use crate::error::Error;
 
 use super::delta_tracker::RollingWindow;
+use super::slippage::SlippageEstimate;
 use super::types::ExecutionRecord;
 
 /// Execution quality metrics
@@ -18,6 +30,10 @@ pub struct ExecutionQuality {
     pub confidence_adjustment: f64,
     pub should_reduce_size: bool,
     pub should_pause_trading: bool,
+    /// Share of recent estimated buys that slipped past the estimate
+    /// (None = no estimated buy checked yet)
+    #[serde(default)]
+    pub estimator_miss_rate: Option<f64>,
 }
 
 /// Configuration for execution feedback
@@ -28,6 +44,34 @@ pub struct ExecutionFeedbackConfig {
     pub slippage_penalty_threshold_pct: f64,
     pub fill_rate_penalty_threshold: f64,
     pub pause_on_severe_slippage: bool,
+    /// Consecutive buy fills that must agree before slippage or size changes
+    #[serde(default = "default_adjust_window")]
+    pub adjust_window: usize,
+    /// Slippage widened or tightened per adjustment (bps)
+    #[serde(default = "default_slippage_step_bps")]
+    pub slippage_step_bps: u32,
+    /// Slippage is never widened more than this above the configured one (bps)
+    #[serde(default = "default_max_extra_slippage_bps")]
+    pub max_extra_slippage_bps: u32,
+    /// Entry size factor once slippage is at the cap
+    #[serde(default = "default_reduced_size_factor")]
+    pub reduced_size_factor: f64,
+}
+
+fn default_adjust_window() -> usize {
+    5
+}
+
+fn default_slippage_step_bps() -> u32 {
+    500
+}
+
+fn default_max_extra_slippage_bps() -> u32 {
+    1500
+}
+
+fn default_reduced_size_factor() -> f64 {
+    0.5
 }
 
 impl Default for ExecutionFeedbackConfig {
@@ -38,10 +82,79 @@ impl Default for ExecutionFeedbackConfig {
             slippage_penalty_threshold_pct: 5.0,
             fill_rate_penalty_threshold: 0.8,
             pause_on_severe_slippage: true,
+            adjust_window: default_adjust_window(),
+            slippage_step_bps: default_slippage_step_bps(),
+            max_extra_slippage_bps: default_max_extra_slippage_bps(),
+            reduced_size_factor: default_reduced_size_factor(),
+        }
+    }
+}
+
+/// What an adjustment changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentAction {
+    WidenSlippage,
+    ReduceSize,
+    RestoreSize,
+    TightenSlippage,
+}
+
+/// One logged slippage / size adjustment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionAdjustment {
+    pub at: DateTime<Utc>,
+    pub action: AdjustmentAction,
+    /// Extra slippage on top of the configured slippage after the change (bps)
+    pub extra_slippage_bps: u32,
+    pub size_factor: f64,
+    /// The fills that triggered it
+    pub trigger: String,
+}
+
+/// Current slippage / size adjustment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionAdjustmentState {
+    pub extra_slippage_bps: u32,
+    pub size_factor: f64,
+}
+
+impl Default for ExecutionAdjustmentState {
+    fn default() -> Self {
+        Self {
+            extra_slippage_bps: 0,
+            size_factor: 1.0,
         }
     }
 }
 
+/// What `snipe status --execution` reads
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub quality: ExecutionQuality,
+    pub adjustment: ExecutionAdjustmentState,
+    pub adjustments: Vec<ExecutionAdjustment>,
+    pub recent: Vec<ExecutionRecord>,
+}
+
+/// Adjustments kept in the log
+const MAX_LOGGED_ADJUSTMENTS: usize = 50;
+
+/// Load the report written by a running bot (None if there is none yet)
+pub fn load_execution_report(
+    path: &std::path::Path,
+) -> crate::error::Result<Option<ExecutionReport>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    let report =
+        serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    Ok(Some(report))
+}
+
 /// Execution Feedback Tracker
 pub struct ExecutionFeedback {
     config: ExecutionFeedbackConfig,
@@ -49,6 +162,12 @@ pub struct ExecutionFeedback {
     avg_slippage_pct: RollingWindow,
     avg_latency_ms: RollingWindow,
     fill_rate: RollingWindow,
+    adjustment: ExecutionAdjustmentState,
+    adjustments: VecDeque<ExecutionAdjustment>,
+    /// Buy fills seen since the last adjustment
+    fills_since_adjustment: usize,
+    /// Where the report is saved (None = memory only)
+    persistence_path: Option<PathBuf>,
 }
 
 impl ExecutionFeedback {
@@ -60,19 +179,151 @@ impl ExecutionFeedback {
             avg_slippage_pct: RollingWindow::new(std::time::Duration::from_secs(3600)),
             avg_latency_ms: RollingWindow::new(std::time::Duration::from_secs(3600)),
             fill_rate: RollingWindow::new(std::time::Duration::from_secs(3600)),
+            adjustment: ExecutionAdjustmentState::default(),
+            adjustments: VecDeque::new(),
+            fills_since_adjustment: 0,
+            persistence_path: None,
+        }
+    }
+
+    /// Restore the adjustment saved at `path` (if any) and save the report there
+    pub fn set_persistence_path(&mut self, path: PathBuf) -> crate::error::Result<()> {
+        if let Some(report) = load_execution_report(&path)? {
+            self.adjustment = report.adjustment;
+            self.adjustments = report.adjustments.into();
+        }
+        self.persistence_path = Some(path);
+        Ok(())
+    }
+
+    /// Snapshot for `snipe status --execution`
+    pub fn report(&self) -> ExecutionReport {
+        ExecutionReport {
+            updated_at: Some(Utc::now()),
+            quality: self.get_quality(),
+            adjustment: self.adjustment.clone(),
+            adjustments: self.adjustments.iter().cloned().collect(),
+            recent: self.executions.iter().cloned().collect(),
+        }
+    }
+
+    /// Save the report (failures are logged and never block trading)
+    fn save(&self) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&self.report())
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to save execution report to {}: {}",
+                path.display(),
+                e
+            );
         }
     }
 
+    /// Current slippage / size adjustment
+    pub fn adjustment(&self) -> &ExecutionAdjustmentState {
+        &self.adjustment
+    }
+
+    /// Slippage to use for the next buy, given the configured slippage (bps)
+    pub fn slippage_bps(&self, base_bps: u32) -> u32 {
+        (base_bps + self.adjustment.extra_slippage_bps).min(10_000)
+    }
+
+    /// Widen / reduce or tighten / restore once the recent buy fills agree
+    fn adjust(&mut self) {
+        let window = self.config.adjust_window.max(1);
+        if self.fills_since_adjustment < window {
+            return;
+        }
+        let recent: Vec<f64> = self
+            .executions
+            .iter()
+            .rev()
+            .filter(|e| e.success && e.side == super::types::Side::Buy)
+            .take(window)
+            .map(|e| e.slippage_pct)
+            .collect();
+        if recent.len() < window {
+            return;
+        }
+
+        let tolerance = self.config.slippage_penalty_threshold_pct;
+        let avg = recent.iter().sum::<f64>() / recent.len() as f64;
+        let step = self.config.slippage_step_bps;
+        let headroom = self
+            .config
+            .max_extra_slippage_bps
+            .saturating_sub(self.adjustment.extra_slippage_bps);
+
+        let action = if recent.iter().all(|&slip| slip > tolerance) {
+            if headroom > 0 && step > 0 {
+                self.adjustment.extra_slippage_bps += step.min(headroom);
+                AdjustmentAction::WidenSlippage
+            } else if self.adjustment.size_factor > self.config.reduced_size_factor {
+                self.adjustment.size_factor = self.config.reduced_size_factor;
+                AdjustmentAction::ReduceSize
+            } else {
+                return;
+            }
+        } else if recent.iter().all(|&slip| slip <= tolerance / 2.0) {
+            if self.adjustment.size_factor < 1.0 {
+                self.adjustment.size_factor = 1.0;
+                AdjustmentAction::RestoreSize
+            } else if self.adjustment.extra_slippage_bps > 0 {
+                self.adjustment.extra_slippage_bps =
+                    self.adjustment.extra_slippage_bps.saturating_sub(step);
+                AdjustmentAction::TightenSlippage
+            } else {
+                return;
+            }
+        } else {
+            return;
+        };
+
+        let trigger = format!(
+            "last {} buy fills averaged {:.1}% slippage (tolerance {:.1}%)",
+            window, avg, tolerance
+        );
+        tracing::warn!(
+            "Execution feedback: {:?} -> +{} bps slippage, {:.0}% size ({})",
+            action,
+            self.adjustment.extra_slippage_bps,
+            self.adjustment.size_factor * 100.0,
+            trigger
+        );
+        self.adjustments.push_back(ExecutionAdjustment {
+            at: Utc::now(),
+            action,
+            extra_slippage_bps: self.adjustment.extra_slippage_bps,
+            size_factor: self.adjustment.size_factor,
+            trigger,
+        });
+        while self.adjustments.len() > MAX_LOGGED_ADJUSTMENTS {
+            self.adjustments.pop_front();
+        }
+        self.fills_since_adjustment = 0;
+    }
+
     /// Record an execution
     pub fn record(&mut self, record: ExecutionRecord) {
         if !self.config.enabled {
             return;
         }
 
-        // Add to rolling windows
-        self.avg_slippage_pct.add(record.slippage_pct);
-        self.avg_latency_ms.add(record.latency_ms as f64);
+        // Add to rolling windows (failures have no price, latency 0 = not measured)
+        if record.success {
+            self.avg_slippage_pct.add(record.slippage_pct);
+        }
+        if record.latency_ms > 0 {
+            self.avg_latency_ms.add(record.latency_ms as f64);
+        }
         self.fill_rate.add(if record.success { 1.0 } else { 0.0 });
+        let buy_fill = record.success && record.side == super::types::Side::Buy;
 
         // Add to history
         self.executions.push_back(record);
@@ -81,6 +332,12 @@ impl ExecutionFeedback {
         while self.executions.len() > self.config.track_last_n {
             self.executions.pop_front();
         }
+
+        if buy_fill {
+            self.fills_since_adjustment += 1;
+            self.adjust();
+        }
+        self.save();
     }
 
     /// Record a successful buy
@@ -112,6 +369,7 @@ impl ExecutionFeedback {
             success: true,
             failure_reason: None,
             tx_signature: Some(tx_sig.to_string()),
+            slippage_estimate: None,
         });
     }
 
@@ -144,6 +402,7 @@ impl ExecutionFeedback {
             success: true,
             failure_reason: None,
             tx_signature: Some(tx_sig.to_string()),
+            slippage_estimate: None,
         });
     }
 
@@ -169,9 +428,46 @@ impl ExecutionFeedback {
             success: false,
             failure_reason: Some(reason.to_string()),
             tx_signature: None,
+            slippage_estimate: None,
         });
     }
 
+    /// Attach a buy's slippage estimate (realized slippage included) to its fill
+    pub fn record_estimate(&mut self, tx_sig: &str, estimate: SlippageEstimate) {
+        let Some(record) = self
+            .executions
+            .iter_mut()
+            .rev()
+            .find(|e| e.tx_signature.as_deref() == Some(tx_sig))
+        else {
+            return;
+        };
+        record.slippage_estimate = Some(estimate);
+        if estimate.missed() == Some(true) {
+            tracing::info!(
+                "Slippage estimator miss on {}: estimated {} bps, realized {} bps",
+                record.mint,
+                estimate.estimated_bps(),
+                estimate.realized_bps.unwrap_or_default()
+            );
+        }
+        self.save();
+    }
+
+    /// Share of checked estimates the fill slipped past
+    fn estimator_miss_rate(&self) -> Option<f64> {
+        let checked: Vec<bool> = self
+            .executions
+            .iter()
+            .filter_map(|e| e.slippage_estimate.and_then(|s| s.missed()))
+            .collect();
+        if checked.is_empty() {
+            return None;
+        }
+        let misses = checked.iter().filter(|&&missed| missed).count();
+        Some(misses as f64 / checked.len() as f64)
+    }
+
     /// Get current execution quality
     pub fn get_quality(&self) -> ExecutionQuality {
         let avg_slippage = self.avg_slippage_pct.average();
@@ -216,6 +512,7 @@ impl ExecutionFeedback {
             confidence_adjustment,
             should_reduce_size: should_reduce,
             should_pause_trading: should_pause,
+            estimator_miss_rate: self.estimator_miss_rate(),
         }
     }
 
@@ -228,11 +525,11 @@ impl ExecutionFeedback {
         }
 
         if quality.should_reduce_size {
-            return 0.5;
+            return 0.5 * self.adjustment.size_factor;
         }
 
         // Gradual reduction based on slippage
-        if quality.recent_avg_slippage > 10.0 {
+        let factor = if quality.recent_avg_slippage > 10.0 {
             0.3
         } else if quality.recent_avg_slippage > 5.0 {
             0.6
@@ -240,7 +537,8 @@ impl ExecutionFeedback {
             0.8
         } else {
             1.0
-        }
+        };
+        factor * self.adjustment.size_factor
     }
 
     /// Get recent execution history
@@ -369,4 +667,106 @@ mod tests {
         let quality2 = feedback2.get_quality();
         assert!(quality2.confidence_adjustment < -0.1);
     }
+
+    #[test]
+    fn test_slippage_adjustment_loop() {
+        let mut feedback = ExecutionFeedback::new(ExecutionFeedbackConfig {
+            adjust_window: 2,
+            slippage_step_bps: 500,
+            max_extra_slippage_bps: 500,
+            ..Default::default()
+        });
+
+        // One hot fill is not consistent yet
+        feedback.record_buy("mint", 0.1, 0.001, 0.0011, 100, "sig1"); // 10%
+        assert_eq!(feedback.slippage_bps(2500), 2500);
+
+        // Second hot fill widens slippage
+        feedback.record_buy("mint", 0.1, 0.001, 0.0011, 100, "sig2");
+        assert_eq!(feedback.slippage_bps(2500), 3000);
+
+        // At the cap, the next hot window reduces size instead
+        feedback.record_buy("mint", 0.1, 0.001, 0.0011, 100, "sig3");
+        feedback.record_buy("mint", 0.1, 0.001, 0.0011, 100, "sig4");
+        assert_eq!(feedback.slippage_bps(2500), 3000);
+        assert_eq!(feedback.adjustment().size_factor, 0.5);
+
+        // Clean fills restore size first, then tighten slippage
+        for sig in ["sig5", "sig6"] {
+            feedback.record_buy("mint", 0.1, 0.001, 0.001, 100, sig);
+        }
+        assert_eq!(feedback.adjustment().size_factor, 1.0);
+        assert_eq!(feedback.slippage_bps(2500), 3000);
+        for sig in ["sig7", "sig8"] {
+            feedback.record_buy("mint", 0.1, 0.001, 0.001, 100, sig);
+        }
+        assert_eq!(feedback.slippage_bps(2500), 2500);
+
+        let actions: Vec<_> = feedback
+            .report()
+            .adjustments
+            .iter()
+            .map(|a| a.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                AdjustmentAction::WidenSlippage,
+                AdjustmentAction::ReduceSize,
+                AdjustmentAction::RestoreSize,
+                AdjustmentAction::TightenSlippage,
+            ]
+        );
+        assert!(feedback.report().adjustments[0]
+            .trigger
+            .contains("last 2 buy fills averaged 10.0%"));
+    }
+
+    #[test]
+    fn test_adjustment_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution_feedback.json");
+        let config = ExecutionFeedbackConfig {
+            adjust_window: 1,
+            ..Default::default()
+        };
+
+        let mut feedback = ExecutionFeedback::new(config.clone());
+        feedback.set_persistence_path(path.clone()).unwrap();
+        feedback.record_buy("mint", 0.1, 0.001, 0.0011, 100, "sig1");
+        assert_eq!(feedback.slippage_bps(2500), 3000);
+
+        let mut restarted = ExecutionFeedback::new(config);
+        restarted.set_persistence_path(path.clone()).unwrap();
+        assert_eq!(restarted.slippage_bps(2500), 3000);
+
+        let report = load_execution_report(&path).unwrap().unwrap();
+        assert_eq!(report.recent.len(), 1);
+        assert_eq!(report.adjustments.len(), 1);
+    }
+
+    #[test]
+    fn test_estimator_miss_rate() {
+        let mut feedback = ExecutionFeedback::default();
+        feedback.record_buy("a", 0.1, 0.001, 0.00105, 100, "sig1");
+        feedback.record_buy("b", 0.1, 0.001, 0.00101, 100, "sig2");
+        assert_eq!(feedback.get_quality().estimator_miss_rate, None);
+
+        let estimate = |realized_bps| SlippageEstimate {
+            impact_bps: 200,
+            volatility_bps: 100,
+            chosen_bps: 300,
+            estimated: true,
+            spot_tokens: 1_000,
+            realized_bps: Some(realized_bps),
+        };
+        feedback.record_estimate("sig1", estimate(500));
+        feedback.record_estimate("sig2", estimate(120));
+        feedback.record_estimate("unknown", estimate(900));
+
+        assert_eq!(feedback.get_quality().estimator_miss_rate, Some(0.5));
+        let recent = feedback.recent_executions();
+        assert_eq!(recent[0].slippage_estimate.unwrap().missed(), Some(true));
+        assert_eq!(recent[1].slippage_estimate.unwrap().missed(), Some(false));
+    }
 }
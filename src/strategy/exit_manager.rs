@@ -2,6 +2,11 @@
 //!
 //! Exit style depends on position state and market conditions.
 //! Supports quick scalps, tiered exits, trailing stops, and condition-based exits.
+//!
+//! The position monitor asks for a style at entry (`style_for_entry`, keyed
+//! by entry type and regime) and, for positions that got one, for the
+//! concrete action on every pass (`evaluate`). Positions without a style keep
+//! the monitor's fixed trailing / layered rules ("legacy").
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -10,9 +15,26 @@ use super::delta_tracker::DeltaMetrics;
 use super::price_action::PriceAction;
 use super::regime::RegimeClassification;
 use super::types::{
-    ExitCondition, ExitReason, ExitSignal, ExitStyle, Position, TradingStrategy, Urgency,
+    ExitCondition, ExitReason, ExitSignal, ExitStyle, Position, RegimeKind, TradingStrategy,
+    Urgency,
 };
 
+/// Exit style a position is given at entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitStyleKind {
+    /// The position monitor's fixed trailing / layered rules
+    Legacy,
+    /// Sell everything at `quick_scalp_target_pct`
+    Scalp,
+    /// Trailing stop once `trailing_activation_pct` is reached
+    Trail,
+    /// Sell into `tiered_levels`, trail the rest
+    Ladder,
+    /// Sell everything after `max_hold_secs`
+    TimeBoxed,
+}
+
 /// Exit manager configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExitManagerConfig {
@@ -23,6 +45,21 @@ pub struct ExitManagerConfig {
     pub trailing_activation_pct: f64,
     pub max_hold_secs: u64,
     pub stop_loss_pct: f64,
+    /// Exit style per entry type (strong_buy, opportunity, probe,
+    /// copy_trade, post_migration, legacy); unmapped entry types use the
+    /// legacy rules
+    #[serde(default)]
+    pub entry_type_styles: HashMap<String, ExitStyleKind>,
+    /// Exit style per entry regime, ahead of the entry type
+    #[serde(default)]
+    pub regime_styles: HashMap<RegimeKind, ExitStyleKind>,
+    /// A weakening trail is tightened to this share of `trailing_stop_pct`
+    #[serde(default = "default_trail_tighten_factor")]
+    pub trail_tighten_factor: f64,
+}
+
+fn default_trail_tighten_factor() -> f64 {
+    0.5
 }
 
 impl Default for ExitManagerConfig {
@@ -35,10 +72,34 @@ impl Default for ExitManagerConfig {
             trailing_activation_pct: 30.0,
             max_hold_secs: 300,
             stop_loss_pct: 15.0,
+            entry_type_styles: HashMap::new(),
+            regime_styles: HashMap::new(),
+            trail_tighten_factor: default_trail_tighten_factor(),
         }
     }
 }
 
+/// What the monitor should do with a styled position this pass
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitAction {
+    Hold,
+    /// Sell `pct` of what's left for the ladder tier at `level` % gain
+    Partial {
+        pct: f64,
+        level: f64,
+        reason: String,
+    },
+    /// Sell everything
+    Full {
+        reason: String,
+    },
+    /// Keep holding under this tighter trailing stop from now on
+    TightenTrail {
+        style: ExitStyle,
+        reason: String,
+    },
+}
+
 /// Position context for exit decisions
 #[derive(Debug, Clone)]
 pub struct PositionContext {
@@ -78,6 +139,12 @@ impl ExitManager {
         }
     }
 
+    /// Swap in a new config (strategy profile switch); tracked highs and
+    /// ladder levels are kept
+    pub fn set_config(&mut self, config: ExitManagerConfig) {
+        self.config = config;
+    }
+
     /// Update position tracking with new price
     pub fn update_price(&mut self, mint: &str, price: f64) {
         let high = self
@@ -116,6 +183,122 @@ impl ExitManager {
         self.position_levels_hit.remove(mint);
     }
 
+    /// Exit style for a new position (None = the monitor's legacy rules)
+    pub fn style_for_entry(
+        &self,
+        entry_type: &str,
+        regime: Option<RegimeKind>,
+    ) -> Option<ExitStyle> {
+        let kind = regime
+            .and_then(|regime| self.config.regime_styles.get(&regime))
+            .or_else(|| self.config.entry_type_styles.get(entry_type))
+            .copied()
+            .unwrap_or(ExitStyleKind::Legacy);
+
+        match kind {
+            ExitStyleKind::Legacy => None,
+            ExitStyleKind::Scalp => Some(ExitStyle::QuickScalp {
+                target_pct: self.config.quick_scalp_target_pct,
+            }),
+            ExitStyleKind::Trail => Some(ExitStyle::TrailingStop {
+                trail_pct: self.config.trailing_stop_pct,
+                activation_pct: self.config.trailing_activation_pct,
+            }),
+            ExitStyleKind::Ladder => Some(ExitStyle::TieredExit {
+                levels: self.config.tiered_levels.clone(),
+            }),
+            ExitStyleKind::TimeBoxed => Some(ExitStyle::TimeBased {
+                max_hold_secs: self.config.max_hold_secs,
+            }),
+        }
+    }
+
+    /// Concrete action for a held position under its exit style
+    ///
+    /// Stop loss and the entry type's max hold stay with the position monitor
+    /// (so manual overrides keep working); this covers the style's own
+    /// targets and the order-flow trend.
+    pub fn evaluate(&self, ctx: &PositionContext, style: &ExitStyle) -> ExitAction {
+        // Ladder: the next tier not taken yet
+        if let ExitStyle::TieredExit { levels } = style {
+            if let Some((level, pct)) = levels
+                .iter()
+                .find(|(level, _)| ctx.pnl_pct >= *level && !ctx.level_hit(*level))
+            {
+                return ExitAction::Partial {
+                    pct: *pct,
+                    level: *level,
+                    reason: format!(
+                        "TAKE PROFIT tier {:.0}% at {:.1}% - selling {:.0}%",
+                        level, ctx.pnl_pct, pct
+                    ),
+                };
+            }
+        } else if let Some(signal) = self.check_style_exit(ctx, style) {
+            return ExitAction::Full {
+                reason: describe_exit(&signal.reason),
+            };
+        }
+
+        // Order flow turning against a profitable position
+        if ctx.pnl_pct > 10.0
+            && (ctx.delta.price_velocity < -0.5 || ctx.delta.sell_pressure_building)
+        {
+            return ExitAction::Full {
+                reason: format!(
+                    "MOMENTUM FADE at {:.1}% (price velocity {:.2}, sell pressure {})",
+                    ctx.pnl_pct, ctx.delta.price_velocity, ctx.delta.sell_pressure_building
+                ),
+            };
+        }
+
+        let peak_drop_pct = if ctx.high_price > 0.0 {
+            (ctx.high_price - ctx.current_price) / ctx.high_price * 100.0
+        } else {
+            0.0
+        };
+        match style {
+            // Weakening structure: tighten the trail once
+            ExitStyle::TrailingStop {
+                trail_pct,
+                activation_pct,
+            } if ctx.pnl_pct > 0.0
+                && (ctx.price_action.lower_highs || ctx.delta.top_holder_pct_delta > 5.0) =>
+            {
+                let tightened = self.config.trailing_stop_pct * self.config.trail_tighten_factor;
+                if *trail_pct > tightened {
+                    return ExitAction::TightenTrail {
+                        style: ExitStyle::TrailingStop {
+                            trail_pct: tightened,
+                            activation_pct: *activation_pct,
+                        },
+                        reason: if ctx.price_action.lower_highs {
+                            "lower highs".to_string()
+                        } else {
+                            format!("top holder share up {:.1}%", ctx.delta.top_holder_pct_delta)
+                        },
+                    };
+                }
+            }
+            // Ladder runner: trail what's left once a tier is taken
+            ExitStyle::TieredExit { .. }
+                if !ctx.levels_hit.is_empty()
+                    && ctx.pnl_pct > 0.0
+                    && peak_drop_pct >= self.config.trailing_stop_pct =>
+            {
+                return ExitAction::Full {
+                    reason: format!(
+                        "TRAILING STOP (ladder runner): dropped {:.1}% from peak (P&L: {:+.1}%)",
+                        peak_drop_pct, ctx.pnl_pct
+                    ),
+                };
+            }
+            _ => {}
+        }
+
+        ExitAction::Hold
+    }
+
     /// Select exit style based on position context
     pub fn select_exit_style(&self, ctx: &PositionContext) -> ExitStyle {
         match ctx.entry_strategy {
@@ -408,6 +591,26 @@ impl Default for ExitManager {
     }
 }
 
+/// Monitor log line for an exit (classified into the trade's exit reason)
+fn describe_exit(reason: &ExitReason) -> String {
+    match reason {
+        ExitReason::TakeProfit { pnl_pct } => format!("TAKE PROFIT at {:.1}%", pnl_pct),
+        ExitReason::TrailingStopHit {
+            peak_pnl_pct,
+            current_pnl_pct,
+        } => format!(
+            "TRAILING STOP: peak {:+.1}%, now {:+.1}%",
+            peak_pnl_pct, current_pnl_pct
+        ),
+        ExitReason::StopLoss { loss_pct } => format!("STOP LOSS at -{:.1}%", loss_pct),
+        ExitReason::MaxHoldTime { held_secs } => {
+            format!("MAX HOLD TIME ({} secs, time-boxed)", held_secs)
+        }
+        ExitReason::MomentumFade => "MOMENTUM FADE".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::types::TokenRegime;
@@ -428,6 +631,8 @@ mod tests {
                 highest_price: current_price,
                 lowest_price: entry_price,
                 exit_levels_hit: vec![],
+                creator: None,
+                funding_cluster: None,
             },
             current_price,
             high_price: current_price,
@@ -550,6 +755,79 @@ mod tests {
         assert!(levels.contains(&100.0));
     }
 
+    #[test]
+    fn test_style_for_entry() {
+        let mut config = ExitManagerConfig::default();
+        config
+            .entry_type_styles
+            .insert("strong_buy".to_string(), ExitStyleKind::Ladder);
+        config
+            .regime_styles
+            .insert(RegimeKind::SniperFlip, ExitStyleKind::Scalp);
+        let exit_manager = ExitManager::new(config);
+
+        // Unmapped entry types keep the legacy rules
+        assert_eq!(exit_manager.style_for_entry("probe", None), None);
+        assert!(matches!(
+            exit_manager.style_for_entry("strong_buy", Some(RegimeKind::OrganicPump)),
+            Some(ExitStyle::TieredExit { .. })
+        ));
+        // The regime comes first
+        assert!(matches!(
+            exit_manager.style_for_entry("strong_buy", Some(RegimeKind::SniperFlip)),
+            Some(ExitStyle::QuickScalp { .. })
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_ladder() {
+        let exit_manager = ExitManager::default();
+        let style = ExitStyle::TieredExit {
+            levels: vec![(50.0, 50.0), (100.0, 50.0)],
+        };
+
+        let mut ctx = create_test_context(60.0, TradingStrategy::Adaptive);
+        assert!(matches!(
+            exit_manager.evaluate(&ctx, &style),
+            ExitAction::Partial { level, .. } if level == 50.0
+        ));
+
+        // Tier taken: hold, then trail the runner
+        ctx.levels_hit = vec![50.0];
+        assert_eq!(exit_manager.evaluate(&ctx, &style), ExitAction::Hold);
+        ctx.high_price = ctx.current_price * 1.25;
+        assert!(matches!(
+            exit_manager.evaluate(&ctx, &style),
+            ExitAction::Full { .. }
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_tightens_trail_once() {
+        let exit_manager = ExitManager::default();
+        let style = ExitStyle::TrailingStop {
+            trail_pct: 15.0,
+            activation_pct: 30.0,
+        };
+        let mut ctx = create_test_context(10.0, TradingStrategy::Adaptive);
+        ctx.price_action.lower_highs = true;
+
+        let ExitAction::TightenTrail {
+            style: tightened, ..
+        } = exit_manager.evaluate(&ctx, &style)
+        else {
+            panic!("expected a tighter trail");
+        };
+        assert_eq!(
+            tightened,
+            ExitStyle::TrailingStop {
+                trail_pct: 7.5,
+                activation_pct: 30.0
+            }
+        );
+        assert_eq!(exit_manager.evaluate(&ctx, &tightened), ExitAction::Hold);
+    }
+
     #[test]
     fn test_clear_position() {
         let mut exit_manager = ExitManager::default();
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use super::creator_privileges::{CreatorPrivileges, Privilege};
 use crate::filter::FilterCache;
 
 /// Fatal risk flags that trigger immediate rejection
@@ -20,6 +21,9 @@ pub enum FatalRisk {
     /// Creator can freeze accounts
     FreezeAuthorityActive,
 
+    /// Creator keeps a privilege on the privilege checker's blocklist
+    CreatorPrivilege { privilege: Privilege },
+
     /// Creator is in known rug deployer blacklist
     KnownRugDeployer { prior_rugs: u32 },
 
@@ -55,6 +59,9 @@ pub enum FatalRisk {
     /// Sell transactions failing (honeypot)
     HoneypotDetected { failed_sells: u32 },
 
+    /// Simulated buy-then-sell loses far more than fees (honeypot)
+    RoundTripLoss { excess_loss_pct: f64 },
+
     /// Token is already rugged
     AlreadyRugged { price_drop_pct: f64 },
 
@@ -76,6 +83,9 @@ impl FatalRisk {
             FatalRisk::FreezeAuthorityActive => {
                 "Creator retains freeze authority - can freeze your tokens".to_string()
             }
+            FatalRisk::CreatorPrivilege { privilege } => {
+                format!("Creator retains {} authority", privilege)
+            }
             FatalRisk::KnownRugDeployer { prior_rugs } => {
                 format!("Known rug deployer with {} prior rugs", prior_rugs)
             }
@@ -128,6 +138,12 @@ impl FatalRisk {
                     failed_sells
                 )
             }
+            FatalRisk::RoundTripLoss { excess_loss_pct } => {
+                format!(
+                    "Honeypot simulation - round trip loses {:.1}% beyond fees",
+                    excess_loss_pct
+                )
+            }
             FatalRisk::AlreadyRugged { price_drop_pct } => {
                 format!(
                     "Token already rugged - price dropped {:.1}%",
@@ -149,6 +165,7 @@ impl FatalRisk {
             self,
             FatalRisk::MintAuthorityActive
                 | FatalRisk::FreezeAuthorityActive
+                | FatalRisk::CreatorPrivilege { .. }
                 | FatalRisk::KnownRugDeployer { .. }
                 | FatalRisk::CreatorDumpedEarly { .. }
         )
@@ -184,6 +201,13 @@ pub struct FatalRiskConfig {
     pub wash_trading_threshold: f64,
     /// Price drop threshold for already rugged
     pub rug_price_drop_threshold: f64,
+    /// Max simulated round-trip loss beyond fees before honeypot
+    #[serde(default = "default_max_round_trip_loss_pct")]
+    pub max_round_trip_loss_pct: f64,
+}
+
+fn default_max_round_trip_loss_pct() -> f64 {
+    5.0
 }
 
 impl Default for FatalRiskConfig {
@@ -197,6 +221,7 @@ impl Default for FatalRiskConfig {
             max_exit_slippage_pct: 50.0,
             wash_trading_threshold: 0.8,
             rug_price_drop_threshold: 80.0,
+            max_round_trip_loss_pct: default_max_round_trip_loss_pct(),
         }
     }
 }
@@ -237,6 +262,12 @@ impl FatalRiskEngine {
         self.cache = Some(cache);
     }
 
+    /// Privileges from the mint info enrichment cached (metadata not checked)
+    pub fn cached_privileges(&self, mint: &str) -> Option<CreatorPrivileges> {
+        let info = self.cache.as_ref()?.get_mint_info(mint)?;
+        Some(CreatorPrivileges::from_mint_info(&info))
+    }
+
     /// Add a known rug deployer address
     pub fn add_rug_deployer(&mut self, address: String) {
         self.known_rug_deployers.insert(address);
@@ -336,6 +367,13 @@ impl FatalRiskEngine {
             });
         }
 
+        // Check simulated round trip
+        if let Some(excess_loss_pct) = context.round_trip_excess_loss_pct {
+            if excess_loss_pct > self.config.max_round_trip_loss_pct {
+                return Some(FatalRisk::RoundTripLoss { excess_loss_pct });
+            }
+        }
+
         // Check already rugged
         if context.price_drop_from_ath > self.config.rug_price_drop_threshold {
             return Some(FatalRisk::AlreadyRugged {
@@ -400,6 +438,8 @@ pub struct FatalRiskContext {
     // Patterns
     pub wash_trading_score: f64,
     pub failed_sell_count: u32,
+    /// Simulated round-trip loss beyond fees (None = not checked)
+    pub round_trip_excess_loss_pct: Option<f64>,
     pub price_drop_from_ath: f64,
 
     // Chain
@@ -520,6 +560,21 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_round_trip_loss_fatal() {
+        let cache = make_cache();
+        let engine = FatalRiskEngine::with_cache(FatalRiskConfig::default(), cache);
+
+        let mut context = FatalRiskContext::new("mint".to_string(), "creator".to_string())
+            .with_liquidity(1.0, 5.0, 0.1);
+        context.round_trip_excess_loss_pct = Some(2.0);
+        assert!(engine.check(&context).await.is_none());
+
+        context.round_trip_excess_loss_pct = Some(98.0);
+        let result = engine.check(&context).await;
+        assert!(matches!(result, Some(FatalRisk::RoundTripLoss { .. })));
+    }
+
     #[tokio::test]
     async fn test_no_fatal_risks() {
         let cache = make_cache();
@@ -85,6 +85,31 @@ impl LiquidityAnalysis {
         }
     }
 
+    /// Price impact of buying `size_sol` and selling the tokens back at the
+    /// depth the buy leaves (%)
+    ///
+    /// With `S` SOL in the curve, a buy of `x` pays `x / S` over spot and the
+    /// sell gets `x / (S + x)` under it.
+    pub fn round_trip_impact_pct(&self, size_sol: f64) -> f64 {
+        if self.sol_reserves <= 0.0 {
+            return 100.0;
+        }
+        let x = size_sol.max(0.0);
+        let impact = x / self.sol_reserves + x / (self.sol_reserves + x);
+        (impact * 100.0).min(100.0)
+    }
+
+    /// Largest buy whose round-trip impact stays within `max_impact_pct`
+    pub fn max_size_for_round_trip(&self, max_impact_pct: f64) -> f64 {
+        if self.sol_reserves <= 0.0 || max_impact_pct <= 0.0 {
+            return 0.0;
+        }
+        // u + u / (1 + u) = b, with u = x / S
+        let b = max_impact_pct / 100.0;
+        let u = (-(2.0 - b) + ((2.0 - b).powi(2) + 4.0 * b).sqrt()) / 2.0;
+        u * self.sol_reserves
+    }
+
     /// Get a risk assessment string
     pub fn risk_assessment(&self) -> &'static str {
         if !self.exit_feasible {
@@ -101,6 +126,47 @@ impl LiquidityAnalysis {
     }
 }
 
+/// What exiting a position is expected to cost, from the curve at entry
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExitLiquidity {
+    /// Curve SOL reserves once our buy landed
+    pub sol_reserves: f64,
+    /// Impact of selling the whole position in one go (%)
+    pub full_exit_impact_pct: f64,
+    /// Largest single sell (SOL at spot) within the per-sell impact budget
+    pub max_sell_sol: f64,
+}
+
+impl ExitLiquidity {
+    /// Exit expectations for a `size_sol` buy where each sell may move the
+    /// price `max_sell_impact_pct` (None without reserves or a budget)
+    pub fn at_entry(
+        analysis: &LiquidityAnalysis,
+        size_sol: f64,
+        max_sell_impact_pct: f64,
+    ) -> Option<Self> {
+        if analysis.sol_reserves <= 0.0 || !(0.0..100.0).contains(&max_sell_impact_pct) {
+            return None;
+        }
+        // Selling `v` SOL worth (at spot) into `S` SOL of depth costs v / (S + v)
+        let sol_reserves = analysis.sol_reserves + size_sol.max(0.0);
+        let budget = max_sell_impact_pct / 100.0;
+        Some(Self {
+            sol_reserves,
+            full_exit_impact_pct: size_sol.max(0.0) / sol_reserves * 100.0,
+            max_sell_sol: budget * sol_reserves / (1.0 - budget),
+        })
+    }
+
+    /// Sells to split an exit worth `value_sol` into, at most `max_slices`
+    pub fn slices(&self, value_sol: f64, max_slices: u32) -> u32 {
+        if self.max_sell_sol <= 0.0 || value_sol <= self.max_sell_sol {
+            return 1;
+        }
+        ((value_sol / self.max_sell_sol).ceil() as u32).clamp(1, max_slices.max(1))
+    }
+}
+
 /// Configuration for liquidity analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiquidityConfig {
@@ -248,6 +314,10 @@ impl LiquidityAnalyzer {
     ///   new_sol_reserve = k / new_token_reserve
     ///   sol_received = old_sol - new_sol_reserve
     ///   slippage = (expected_sol - sol_received) / expected_sol * 100
+    ///
+    /// This works out to `target_sol / sol_reserves`: more conservative than
+    /// the impact of selling `target_sol` worth at spot (`quote_sell`), which
+    /// is what exit sizing wants. The protocol fee is not included.
     fn calculate_sell_slippage(
         &self,
         sol_reserves: f64,
@@ -400,6 +470,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sell_slippage_is_conservative() {
+        use crate::pump::price::{human_to_tokens, quote_sell, DEFAULT_TOKEN_DECIMALS};
+
+        let analyzer = LiquidityAnalyzer::default_config();
+        // Getting 1 SOL out of 10 costs 10%
+        let slippage = analyzer.calculate_sell_slippage(10.0, 1_000_000.0, 1.0);
+        assert!((slippage - 10.0).abs() < 1e-9);
+
+        // Above the exact impact of selling 1 SOL worth at spot
+        let curve =
+            crate::pump::BondingCurve::from_virtual_reserves(10_000_000_000, 1_000_000_000_000);
+        let quote = quote_sell(&curve, human_to_tokens(100_000.0, DEFAULT_TOKEN_DECIMALS)).unwrap();
+        assert!(slippage > quote.price_impact_bps as f64 / 100.0);
+    }
+
     #[test]
     fn test_low_liquidity_detection() {
         let analyzer = LiquidityAnalyzer::default_config();
@@ -473,6 +559,40 @@ mod tests {
         assert_eq!(analysis.risk_assessment(), "LOW - Good exit capacity");
     }
 
+    #[test]
+    fn test_round_trip_impact() {
+        let analyzer = LiquidityAnalyzer::default_config();
+        let analysis = analyzer.analyze_simple(1.2, 1_000_000.0);
+
+        // 0.5 SOL into 1.2 SOL of depth: ~42% in, ~29% out
+        let impact = analysis.round_trip_impact_pct(0.5);
+        assert!((impact - (0.5 / 1.2 + 0.5 / 1.7) * 100.0).abs() < 1e-9);
+
+        // The largest size within a budget spends exactly the budget
+        let max = analysis.max_size_for_round_trip(10.0);
+        assert!((analysis.round_trip_impact_pct(max) - 10.0).abs() < 1e-6);
+        assert!(max < 0.5);
+        assert_eq!(analysis.max_size_for_round_trip(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_exit_liquidity_slices() {
+        let analyzer = LiquidityAnalyzer::default_config();
+        let analysis = analyzer.analyze_simple(1.2, 1_000_000.0);
+
+        let exit = ExitLiquidity::at_entry(&analysis, 0.5, 5.0).unwrap();
+        assert!((exit.sol_reserves - 1.7).abs() < 1e-9);
+        assert!((exit.full_exit_impact_pct - 0.5 / 1.7 * 100.0).abs() < 1e-9);
+        // A max-size sell moves the price exactly the budget
+        let v = exit.max_sell_sol;
+        assert!((v / (exit.sol_reserves + v) * 100.0 - 5.0).abs() < 1e-9);
+
+        assert_eq!(exit.slices(v * 0.5, 4), 1);
+        assert_eq!(exit.slices(v * 2.5, 4), 3);
+        assert_eq!(exit.slices(v * 10.0, 4), 4);
+        assert!(ExitLiquidity::at_entry(&LiquidityAnalysis::default(), 0.5, 5.0).is_none());
+    }
+
     #[test]
     fn test_price_per_token() {
         let curve = make_curve(10_000_000_000, 1_000_000_000_000); // 10 SOL, 1M tokens
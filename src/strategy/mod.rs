@@ -16,12 +16,16 @@
 //! - `regime` - Regime classification
 //! - `chain_health` - Solana congestion monitoring
 //! - `execution_feedback` - Fill quality tracking
+//! - `slippage` - Per-buy slippage estimate
 //!
 //! ## Strategy (P1)
 //! - `engine` - Strategy coordinator
 //! - `sizing` - Dynamic position calculator
 //! - `exit_manager` - Adaptive exit selection
 //! - `randomization` - Adversarial resistance
+//! - `scale_in` - Tranche-based DCA entries
+//! - `throttle` - Trading windows & launch-rate governor
+//! - `profiles` - Named strategy profiles
 //!
 //! ## Tactics (P2)
 //! - `tactics` - Cunning tactics (frontrun, rug_predict, piggyback)
@@ -42,12 +46,16 @@ pub mod delta_tracker;
 pub mod execution_feedback;
 pub mod price_action;
 pub mod regime;
+pub mod slippage;
 
 // Strategy (P1)
 pub mod engine;
 pub mod exit_manager;
+pub mod profiles;
 pub mod randomization;
+pub mod scale_in;
 pub mod sizing;
+pub mod throttle;
 
 // Tactics (P2)
 pub mod tactics;
@@ -61,18 +69,25 @@ pub use engine::{
     EntryEvaluation, PositionEvaluation, StrategyEngine, StrategyEngineConfig, TokenAnalysisContext,
 };
 pub use execution_feedback::{ExecutionFeedback, ExecutionQuality};
-pub use exit_manager::{ExitManager, ExitManagerConfig, PositionContext};
+pub use exit_manager::{
+    ExitAction, ExitManager, ExitManagerConfig, ExitStyleKind, PositionContext,
+};
 pub use fatal_risk::{FatalRisk, FatalRiskContext, FatalRiskEngine};
-pub use liquidity::{LiquidityAnalysis, LiquidityAnalyzer};
+pub use liquidity::{ExitLiquidity, LiquidityAnalysis, LiquidityAnalyzer};
 pub use portfolio_risk::{PortfolioBlock, PortfolioRiskGovernor, PortfolioState};
 pub use price_action::{PriceAction, PriceActionAnalyzer};
-pub use randomization::{RandomizationConfig, Randomizer};
+pub use profiles::{StrategyProfile, BASE_PROFILE};
+pub use randomization::{RandomizationConfig, RandomizedBuy, Randomizer};
 pub use regime::{
-    CreatorBehavior, OrderFlowAnalysis, RegimeClassification, RegimeClassifier, TokenDistribution,
+    CreatorBehavior, OrderFlowAnalysis, RegimeClassification, RegimeClassifier, RegimeStrategy,
+    TokenDistribution,
 };
-pub use sizing::{PositionSizer, PositionSizingConfig, SizingContext};
+pub use scale_in::{ScaleInConfig, ScaleInPlan, Tranche, TrancheStatus};
+pub use sizing::{EquitySnapshot, PositionSizer, PositionSizingConfig, SizingContext, SizingMode};
+pub use slippage::{SlippageEstimate, SlippageEstimator, SlippageEstimatorConfig};
+pub use throttle::{LaunchThrottle, ThrottleConfig, ThrottleReport, TradingWindow};
 pub use tactics::{
     AccumulationSignal, FrontRunDetector, PiggybackSignal, RugPrediction, RugPredictor,
-    RugWarningSignal, SniperPiggyback, SniperStat,
+    RugWarningSignal, SniperPiggyback, SniperStat, TacticsConfig,
 };
 pub use types::*;
@@ -2,9 +2,20 @@
 //!
 //! Global capital control to prevent ruin from correlated losses.
 //! Enforces max concurrent positions, exposure limits, and circuit breakers.
-
+//!
+//! A loss streak (N losses within M minutes) blocks entries for a cooldown
+//! and sizes the next few entries down. Creator and funding-cluster limits
+//! cap how many open positions can share one operator, and a creator gets
+//! at most one entry per `creator_entry_window_mins` whatever the score.
+//! Loss-streak, daily and creator-entry state is saved to
+//! `portfolio_risk.json` so a restart doesn't reset it.
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+
+use crate::error::Error;
 
 use super::delta_tracker::RollingWindow;
 use super::types::Position;
@@ -27,6 +38,30 @@ pub enum PortfolioBlock {
     PositionTooLarge { requested_sol: f64, max_sol: f64 },
     /// Paused due to adverse conditions
     TradingPaused { reason: String, resume_in_secs: u64 },
+    /// Cooling down after a streak of losses within a short window
+    LossStreakCooldown {
+        losses: u32,
+        window_mins: u64,
+        resume_in_secs: u64,
+    },
+    /// Too many open positions from the same creator
+    CreatorConcentration {
+        creator: String,
+        current: usize,
+        max: usize,
+    },
+    /// Too many open positions whose creators share a funder
+    FundingClusterConcentration {
+        funder: String,
+        current: usize,
+        max: usize,
+    },
+    /// Already entered a token from this creator within the window
+    CreatorEntryWindow {
+        creator: String,
+        window_mins: u64,
+        resume_in_secs: u64,
+    },
 }
 
 impl PortfolioBlock {
@@ -78,6 +113,46 @@ impl PortfolioBlock {
             } => {
                 format!("Trading paused: {} (resume in {}s)", reason, resume_in_secs)
             }
+            PortfolioBlock::LossStreakCooldown {
+                losses,
+                window_mins,
+                resume_in_secs,
+            } => {
+                format!(
+                    "Loss streak cooldown: {} losses within {}m (resume in {}s)",
+                    losses, window_mins, resume_in_secs
+                )
+            }
+            PortfolioBlock::CreatorConcentration {
+                creator,
+                current,
+                max,
+            } => {
+                format!(
+                    "Creator limit: {}/{} open positions from creator {}",
+                    current, max, creator
+                )
+            }
+            PortfolioBlock::FundingClusterConcentration {
+                funder,
+                current,
+                max,
+            } => {
+                format!(
+                    "Funding cluster limit: {}/{} open positions from creators funded by {}",
+                    current, max, funder
+                )
+            }
+            PortfolioBlock::CreatorEntryWindow {
+                creator,
+                window_mins,
+                resume_in_secs,
+            } => {
+                format!(
+                    "Creator {} already entered within {}m (next entry in {}s)",
+                    creator, window_mins, resume_in_secs
+                )
+            }
         }
     }
 }
@@ -91,6 +166,8 @@ pub struct PortfolioState {
     pub hourly_realized_pnl_sol: f64,
     pub daily_realized_pnl_sol: f64,
     pub consecutive_losses: u32,
+    /// Entries still sized down after a loss streak
+    pub reduced_size_trades_left: u32,
     pub can_open_new: bool,
     pub reason_if_blocked: Option<String>,
 }
@@ -112,6 +189,67 @@ pub struct PortfolioRiskConfig {
     pub consecutive_loss_limit: u32,
     /// Cooldown after circuit breaker (seconds)
     pub circuit_breaker_cooldown_secs: u64,
+    /// Losses within `loss_streak_window_mins` that start a cooldown (0 = off)
+    #[serde(default = "default_loss_streak_count")]
+    pub loss_streak_count: u32,
+    /// Window the loss streak is counted over (minutes)
+    #[serde(default = "default_loss_streak_window_mins")]
+    pub loss_streak_window_mins: u64,
+    /// Entries are blocked this long after a loss streak (seconds)
+    #[serde(default = "default_loss_streak_cooldown_secs")]
+    pub loss_streak_cooldown_secs: u64,
+    /// Entries after a loss streak that are sized down (0 = none)
+    #[serde(default = "default_reduced_size_trades")]
+    pub reduced_size_trades: u32,
+    /// Size multiplier for those entries
+    #[serde(default = "default_reduced_size_multiplier")]
+    pub reduced_size_multiplier: f64,
+    /// Max open positions from the same creator (0 = unlimited)
+    #[serde(default = "default_max_positions_per_creator")]
+    pub max_positions_per_creator: usize,
+    /// Max open positions whose creators share a funder (0 = unlimited)
+    #[serde(default = "default_max_positions_per_funding_cluster")]
+    pub max_positions_per_funding_cluster: usize,
+    /// At most one entry per creator within this many minutes, open or
+    /// closed (0 = off)
+    #[serde(default = "default_creator_entry_window_mins")]
+    pub creator_entry_window_mins: u64,
+    /// The day resets at midnight in this UTC offset (hours); wallet spend
+    /// caps reset with it
+    #[serde(default)]
+    pub daily_reset_utc_offset_hours: i32,
+}
+
+fn default_loss_streak_count() -> u32 {
+    3
+}
+
+fn default_loss_streak_window_mins() -> u64 {
+    30
+}
+
+fn default_loss_streak_cooldown_secs() -> u64 {
+    900 // 15 minutes
+}
+
+fn default_reduced_size_trades() -> u32 {
+    3
+}
+
+fn default_reduced_size_multiplier() -> f64 {
+    0.5
+}
+
+fn default_max_positions_per_creator() -> usize {
+    1
+}
+
+fn default_max_positions_per_funding_cluster() -> usize {
+    2
+}
+
+fn default_creator_entry_window_mins() -> u64 {
+    60
 }
 
 impl Default for PortfolioRiskConfig {
@@ -124,10 +262,53 @@ impl Default for PortfolioRiskConfig {
             daily_loss_limit_sol: 1.0,
             consecutive_loss_limit: 5,
             circuit_breaker_cooldown_secs: 300, // 5 minutes
+            loss_streak_count: default_loss_streak_count(),
+            loss_streak_window_mins: default_loss_streak_window_mins(),
+            loss_streak_cooldown_secs: default_loss_streak_cooldown_secs(),
+            reduced_size_trades: default_reduced_size_trades(),
+            reduced_size_multiplier: default_reduced_size_multiplier(),
+            max_positions_per_creator: default_max_positions_per_creator(),
+            max_positions_per_funding_cluster: default_max_positions_per_funding_cluster(),
+            creator_entry_window_mins: default_creator_entry_window_mins(),
+            daily_reset_utc_offset_hours: 0,
         }
     }
 }
 
+/// Start of the day containing `now`, for days that begin at midnight in
+/// `utc_offset_hours`
+pub fn trading_day_start(now: DateTime<Utc>, utc_offset_hours: i32) -> DateTime<Utc> {
+    let offset = chrono::Duration::hours(utc_offset_hours as i64);
+    (now + offset)
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        - offset
+}
+
+/// Date (`%Y-%m-%d`) of the trading day `now` falls in
+pub fn trading_date(now: DateTime<Utc>, utc_offset_hours: i32) -> String {
+    (now + chrono::Duration::hours(utc_offset_hours as i64))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+/// Governor state that survives a restart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PersistedRiskState {
+    pub day_start: Option<DateTime<Utc>>,
+    pub daily_pnl_sol: f64,
+    pub consecutive_losses: u32,
+    /// Losses still inside the loss-streak window
+    pub recent_losses: Vec<DateTime<Utc>>,
+    pub loss_streak_until: Option<DateTime<Utc>>,
+    pub reduced_size_trades_left: u32,
+    /// Last entry per creator still inside the creator entry window
+    #[serde(default)]
+    pub creator_entries: HashMap<String, DateTime<Utc>>,
+}
+
 /// Portfolio Risk Governor
 pub struct PortfolioRiskGovernor {
     config: PortfolioRiskConfig,
@@ -144,11 +325,22 @@ pub struct PortfolioRiskGovernor {
     pause_reason: Option<String>,
     /// Day start timestamp for daily reset
     day_start: chrono::DateTime<chrono::Utc>,
+    /// Loss times inside the loss-streak window
+    recent_losses: VecDeque<DateTime<Utc>>,
+    /// Entries blocked until this time after a loss streak
+    loss_streak_until: Option<DateTime<Utc>>,
+    /// Entries still sized down after a loss streak
+    reduced_size_trades_left: u32,
+    /// Last entry time per creator
+    creator_entries: HashMap<String, DateTime<Utc>>,
+    /// Where state is saved (None = memory only)
+    persistence_path: Option<PathBuf>,
 }
 
 impl PortfolioRiskGovernor {
     /// Create a new portfolio risk governor
     pub fn new(config: PortfolioRiskConfig) -> Self {
+        let day_start = trading_day_start(chrono::Utc::now(), config.daily_reset_utc_offset_hours);
         Self {
             config,
             positions: HashMap::new(),
@@ -157,11 +349,70 @@ impl PortfolioRiskGovernor {
             consecutive_losses: 0,
             paused_until: None,
             pause_reason: None,
-            day_start: chrono::Utc::now()
-                .date_naive()
-                .and_hms_opt(0, 0, 0)
-                .unwrap()
-                .and_utc(),
+            day_start,
+            recent_losses: VecDeque::new(),
+            loss_streak_until: None,
+            reduced_size_trades_left: 0,
+            creator_entries: HashMap::new(),
+            persistence_path: None,
+        }
+    }
+
+    /// Restore state saved at `path` (if any) and save there on every change
+    pub fn set_persistence_path(&mut self, path: PathBuf) -> crate::error::Result<()> {
+        if path.exists() {
+            let data = std::fs::read_to_string(&path)
+                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            let state: PersistedRiskState = serde_json::from_str(&data)
+                .map_err(|e| Error::PositionPersistence(e.to_string()))?;
+            self.restore(state);
+        }
+        self.persistence_path = Some(path);
+        // Drops a previous day's P&L right away
+        self.check_daily_reset();
+        Ok(())
+    }
+
+    /// Apply persisted state
+    fn restore(&mut self, state: PersistedRiskState) {
+        if let Some(day_start) = state.day_start {
+            self.day_start = day_start;
+        }
+        self.daily_pnl = state.daily_pnl_sol;
+        self.consecutive_losses = state.consecutive_losses;
+        self.recent_losses = state.recent_losses.into();
+        self.loss_streak_until = state.loss_streak_until;
+        self.reduced_size_trades_left = state.reduced_size_trades_left;
+        self.creator_entries = state.creator_entries;
+    }
+
+    /// State to persist
+    fn persisted_state(&self) -> PersistedRiskState {
+        PersistedRiskState {
+            day_start: Some(self.day_start),
+            daily_pnl_sol: self.daily_pnl,
+            consecutive_losses: self.consecutive_losses,
+            recent_losses: self.recent_losses.iter().copied().collect(),
+            loss_streak_until: self.loss_streak_until,
+            reduced_size_trades_left: self.reduced_size_trades_left,
+            creator_entries: self.creator_entries.clone(),
+        }
+    }
+
+    /// Save state (failures are logged and never block trading)
+    fn save(&self) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&self.persisted_state())
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to save portfolio risk state to {}: {}",
+                path.display(),
+                e
+            );
         }
     }
 
@@ -178,6 +429,18 @@ impl PortfolioRiskGovernor {
             }
         }
 
+        // Check loss-streak cooldown
+        if let Some(until) = self.loss_streak_until {
+            let now = Utc::now();
+            if now < until {
+                return Err(PortfolioBlock::LossStreakCooldown {
+                    losses: self.config.loss_streak_count,
+                    window_mins: self.config.loss_streak_window_mins,
+                    resume_in_secs: (until - now).num_seconds().max(0) as u64,
+                });
+            }
+        }
+
         // Check position count
         if self.positions.len() >= self.config.max_concurrent_positions {
             return Err(PortfolioBlock::MaxPositionsReached {
@@ -232,9 +495,92 @@ impl PortfolioRiskGovernor {
         Ok(())
     }
 
+    /// Check creator and funding-cluster concentration for a new entry
+    pub fn check_correlation(
+        &self,
+        creator: &str,
+        funding_cluster: Option<&str>,
+    ) -> Result<(), PortfolioBlock> {
+        let window_mins = self.config.creator_entry_window_mins;
+        if let Some(entered) = self
+            .creator_entries
+            .get(creator)
+            .filter(|_| window_mins > 0)
+        {
+            let until = *entered + chrono::Duration::minutes(window_mins as i64);
+            let now = Utc::now();
+            if now < until {
+                return Err(PortfolioBlock::CreatorEntryWindow {
+                    creator: creator.to_string(),
+                    window_mins,
+                    resume_in_secs: (until - now).num_seconds().max(0) as u64,
+                });
+            }
+        }
+
+        let max = self.config.max_positions_per_creator;
+        if max > 0 && !creator.is_empty() {
+            let current = self
+                .positions
+                .values()
+                .filter(|p| p.creator.as_deref() == Some(creator))
+                .count();
+            if current >= max {
+                return Err(PortfolioBlock::CreatorConcentration {
+                    creator: creator.to_string(),
+                    current,
+                    max,
+                });
+            }
+        }
+
+        let max = self.config.max_positions_per_funding_cluster;
+        if let Some(funder) = funding_cluster.filter(|_| max > 0) {
+            let current = self
+                .positions
+                .values()
+                .filter(|p| p.funding_cluster.as_deref() == Some(funder))
+                .count();
+            if current >= max {
+                return Err(PortfolioBlock::FundingClusterConcentration {
+                    funder: funder.to_string(),
+                    current,
+                    max,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Size multiplier for the next entry (reduced for a few entries after a loss streak)
+    pub fn size_multiplier(&self) -> f64 {
+        if self.reduced_size_trades_left > 0 {
+            self.config.reduced_size_multiplier
+        } else {
+            1.0
+        }
+    }
+
     /// Register a new position
     pub fn open_position(&mut self, position: Position) {
+        let mut changed = false;
+        if let Some(creator) = position.creator.as_ref().filter(|c| !c.is_empty()) {
+            let window = chrono::Duration::minutes(self.config.creator_entry_window_mins as i64);
+            self.creator_entries
+                .retain(|_, entered| position.entry_time - *entered < window);
+            self.creator_entries
+                .insert(creator.clone(), position.entry_time);
+            changed = self.config.creator_entry_window_mins > 0;
+        }
         self.positions.insert(position.mint.clone(), position);
+        if self.reduced_size_trades_left > 0 {
+            self.reduced_size_trades_left -= 1;
+            changed = true;
+        }
+        if changed {
+            self.save();
+        }
     }
 
     /// Close a position and record PnL
@@ -254,6 +600,7 @@ impl PortfolioRiskGovernor {
         // Update consecutive loss counter
         if pnl_sol < 0.0 {
             self.consecutive_losses += 1;
+            self.record_loss(Utc::now());
 
             // Check if we need to pause trading
             if self.consecutive_losses >= self.config.consecutive_loss_limit {
@@ -274,6 +621,39 @@ impl PortfolioRiskGovernor {
                 self.config.circuit_breaker_cooldown_secs,
             );
         }
+
+        self.save();
+    }
+
+    /// Track a loss for the streak rule, starting a cooldown when it fires
+    fn record_loss(&mut self, at: DateTime<Utc>) {
+        if self.config.loss_streak_count == 0 {
+            return;
+        }
+        let window = chrono::Duration::minutes(self.config.loss_streak_window_mins as i64);
+        self.recent_losses.push_back(at);
+        while self
+            .recent_losses
+            .front()
+            .map_or(false, |first| at - *first > window)
+        {
+            self.recent_losses.pop_front();
+        }
+
+        if self.recent_losses.len() >= self.config.loss_streak_count as usize {
+            tracing::warn!(
+                "Loss streak: {} losses within {}m - blocking entries for {}s, next {} entries at {:.0}% size",
+                self.recent_losses.len(),
+                self.config.loss_streak_window_mins,
+                self.config.loss_streak_cooldown_secs,
+                self.config.reduced_size_trades,
+                self.config.reduced_size_multiplier * 100.0
+            );
+            self.loss_streak_until =
+                Some(at + chrono::Duration::seconds(self.config.loss_streak_cooldown_secs as i64));
+            self.reduced_size_trades_left = self.config.reduced_size_trades;
+            self.recent_losses.clear();
+        }
     }
 
     /// Pause trading for a duration
@@ -298,8 +678,8 @@ impl PortfolioRiskGovernor {
 
     /// Check and reset daily counters if new day
     pub fn check_daily_reset(&mut self) {
-        let now = chrono::Utc::now();
-        let today_start = now.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let today_start =
+            trading_day_start(chrono::Utc::now(), self.config.daily_reset_utc_offset_hours);
 
         if today_start > self.day_start {
             tracing::info!(
@@ -318,6 +698,7 @@ impl PortfolioRiskGovernor {
             {
                 self.resume_trading();
             }
+            self.save();
         }
     }
 
@@ -337,6 +718,7 @@ impl PortfolioRiskGovernor {
             hourly_realized_pnl_sol: self.hourly_pnl.sum(),
             daily_realized_pnl_sol: self.daily_pnl,
             consecutive_losses: self.consecutive_losses,
+            reduced_size_trades_left: self.reduced_size_trades_left,
             can_open_new: can_open.is_ok(),
             reason_if_blocked: can_open.err().map(|b| b.description()),
         }
@@ -396,6 +778,8 @@ mod tests {
             highest_price: 0.001,
             lowest_price: 0.001,
             exit_levels_hit: vec![],
+            creator: None,
+            funding_cluster: None,
         }
     }
 
@@ -553,4 +937,173 @@ mod tests {
         assert_eq!(state.consecutive_losses, 1);
         assert!(state.can_open_new);
     }
+
+    fn make_creator_position(mint: &str, creator: &str, funder: Option<&str>) -> Position {
+        Position {
+            creator: Some(creator.to_string()),
+            funding_cluster: funder.map(str::to_string),
+            ..make_position(mint, 0.1)
+        }
+    }
+
+    #[test]
+    fn test_loss_streak_cooldown() {
+        let config = PortfolioRiskConfig {
+            loss_streak_count: 2,
+            loss_streak_cooldown_secs: 600,
+            reduced_size_trades: 2,
+            ..Default::default()
+        };
+        let mut governor = PortfolioRiskGovernor::new(config);
+
+        governor.record_pnl(-0.01);
+        assert!(governor.can_open_position(0.1).is_ok());
+        governor.record_pnl(-0.01);
+
+        let block = governor.can_open_position(0.1).unwrap_err();
+        assert!(matches!(
+            block,
+            PortfolioBlock::LossStreakCooldown { losses: 2, .. }
+        ));
+        assert!(block.description().contains("2 losses within 30m"));
+        assert_eq!(governor.size_multiplier(), 0.5);
+    }
+
+    #[test]
+    fn test_reduced_size_after_cooldown() {
+        let config = PortfolioRiskConfig {
+            loss_streak_count: 2,
+            loss_streak_cooldown_secs: 0,
+            reduced_size_trades: 2,
+            ..Default::default()
+        };
+        let mut governor = PortfolioRiskGovernor::new(config);
+
+        // Losses outside the window don't count towards a streak
+        let old = Utc::now() - chrono::Duration::minutes(45);
+        governor.record_loss(old);
+        governor.record_loss(Utc::now());
+        assert_eq!(governor.size_multiplier(), 1.0);
+
+        governor.record_pnl(-0.01);
+        assert!(governor.can_open_position(0.1).is_ok());
+
+        // Sized down for the next two entries only
+        governor.open_position(make_position("mint1", 0.1));
+        assert_eq!(governor.size_multiplier(), 0.5);
+        governor.open_position(make_position("mint2", 0.1));
+        assert_eq!(governor.size_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_creator_and_cluster_limits() {
+        let config = PortfolioRiskConfig {
+            max_positions_per_creator: 1,
+            max_positions_per_funding_cluster: 2,
+            creator_entry_window_mins: 0,
+            ..Default::default()
+        };
+        let mut governor = PortfolioRiskGovernor::new(config);
+
+        governor.open_position(make_creator_position("mint1", "dev1", Some("funder")));
+        assert!(matches!(
+            governor.check_correlation("dev1", None),
+            Err(PortfolioBlock::CreatorConcentration { current: 1, .. })
+        ));
+        assert!(governor.check_correlation("dev2", Some("funder")).is_ok());
+
+        governor.open_position(make_creator_position("mint2", "dev2", Some("funder")));
+        let block = governor
+            .check_correlation("dev3", Some("funder"))
+            .unwrap_err();
+        assert!(matches!(
+            block,
+            PortfolioBlock::FundingClusterConcentration { current: 2, .. }
+        ));
+        assert!(block.description().contains("funded by funder"));
+        assert!(governor.check_correlation("dev3", Some("other")).is_ok());
+
+        // Closing frees the slot
+        governor.close_position("mint1", 0.01);
+        assert!(governor.check_correlation("dev1", Some("funder")).is_ok());
+    }
+
+    #[test]
+    fn test_one_entry_per_creator_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("portfolio_risk.json");
+        let config = PortfolioRiskConfig {
+            max_positions_per_creator: 0,
+            creator_entry_window_mins: 60,
+            ..Default::default()
+        };
+        let mut governor = PortfolioRiskGovernor::new(config.clone());
+        governor.set_persistence_path(path.clone()).unwrap();
+
+        governor.open_position(make_creator_position("mint1", "dev1", None));
+        governor.close_position("mint1", 0.05);
+        // Closed, but still inside the window
+        let block = governor.check_correlation("dev1", None).unwrap_err();
+        assert!(matches!(
+            block,
+            PortfolioBlock::CreatorEntryWindow {
+                window_mins: 60,
+                ..
+            }
+        ));
+        assert!(governor.check_correlation("dev2", None).is_ok());
+
+        let mut restarted = PortfolioRiskGovernor::new(config);
+        restarted.set_persistence_path(path).unwrap();
+        assert!(restarted.check_correlation("dev1", None).is_err());
+
+        // An entry older than the window no longer blocks
+        let mut old = make_creator_position("mint2", "dev2", None);
+        old.entry_time = Utc::now() - chrono::Duration::minutes(61);
+        restarted.open_position(old);
+        assert!(restarted.check_correlation("dev2", None).is_ok());
+    }
+
+    #[test]
+    fn test_state_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("portfolio_risk.json");
+        let config = PortfolioRiskConfig {
+            loss_streak_count: 2,
+            ..Default::default()
+        };
+
+        let mut governor = PortfolioRiskGovernor::new(config.clone());
+        governor.set_persistence_path(path.clone()).unwrap();
+        governor.record_pnl(-0.01);
+        governor.record_pnl(-0.02);
+        assert!(governor.can_open_position(0.1).is_err());
+
+        let mut restarted = PortfolioRiskGovernor::new(config);
+        restarted.set_persistence_path(path).unwrap();
+        assert!(matches!(
+            restarted.can_open_position(0.1),
+            Err(PortfolioBlock::LossStreakCooldown { .. })
+        ));
+        assert!((restarted.daily_pnl + 0.03).abs() < 1e-9);
+        assert_eq!(restarted.reduced_size_trades_left, 3);
+    }
+
+    #[test]
+    fn test_trading_day_start() {
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            trading_day_start(at("2024-05-02T03:00:00Z"), 0),
+            at("2024-05-02T00:00:00Z")
+        );
+        // UTC-5: 03:00 UTC is still the previous local day
+        assert_eq!(
+            trading_day_start(at("2024-05-02T03:00:00Z"), -5),
+            at("2024-05-01T05:00:00Z")
+        );
+        assert_eq!(
+            trading_day_start(at("2024-05-02T23:00:00Z"), 2),
+            at("2024-05-02T22:00:00Z")
+        );
+    }
 }
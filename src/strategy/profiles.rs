@@ -0,0 +1,303 @@
+//! Named strategy profiles
+//!
+//! `[strategy.profiles.<name>]` tables override parts of the base strategy
+//! config: the default strategy, regime playbooks, position sizing and exits.
+//! Only the fields a profile sets change; the map fields
+//! (`regime_strategies`, `exits.entry_type_styles`, `exits.regime_styles`)
+//! are merged over the base entries. `strategy.active_profile` picks the
+//! profile at startup and `snipe strategy use <name>` switches a running bot
+//! (`base` = no overrides). Positions record the profile they were entered
+//! under.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::engine::StrategyEngineConfig;
+use super::exit_manager::{ExitManagerConfig, ExitStyleKind};
+use super::regime::RegimeStrategy;
+use super::sizing::PositionSizingConfig;
+use super::types::{RegimeKind, TradingStrategy};
+
+/// Profile name for the base config without overrides
+pub const BASE_PROFILE: &str = "base";
+
+/// Accepted `exits.default_style` values
+pub const EXIT_STYLES: &[&str] = &[
+    "adaptive",
+    "legacy",
+    "scalp",
+    "trail",
+    "ladder",
+    "time_boxed",
+];
+
+/// Entry types `exits.entry_type_styles` can map
+const ENTRY_TYPES: &[&str] = &[
+    "strong_buy",
+    "opportunity",
+    "probe",
+    "copy_trade",
+    "post_migration",
+    "legacy",
+];
+
+/// Overrides for one named profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyProfile {
+    /// Shown by `snipe config`
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default_strategy: Option<TradingStrategy>,
+    /// Merged over the base playbooks
+    #[serde(default)]
+    pub regime_strategies: HashMap<RegimeKind, RegimeStrategy>,
+    #[serde(default)]
+    pub position_sizing: SizingOverrides,
+    #[serde(default)]
+    pub exits: ExitOverrides,
+}
+
+/// `PositionSizingConfig` fields a profile may set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SizingOverrides {
+    pub base_size_sol: Option<f64>,
+    pub min_size_sol: Option<f64>,
+    pub max_size_sol: Option<f64>,
+    pub confidence_scaling: Option<bool>,
+    pub max_round_trip_impact_pct: Option<f64>,
+    pub bankroll_pct: Option<f64>,
+}
+
+/// `ExitManagerConfig` fields a profile may set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExitOverrides {
+    pub default_style: Option<String>,
+    pub quick_scalp_target_pct: Option<f64>,
+    pub tiered_levels: Option<Vec<(f64, f64)>>,
+    pub trailing_stop_pct: Option<f64>,
+    pub trailing_activation_pct: Option<f64>,
+    pub max_hold_secs: Option<u64>,
+    pub stop_loss_pct: Option<f64>,
+    /// Merged over the base entry type styles
+    #[serde(default)]
+    pub entry_type_styles: HashMap<String, ExitStyleKind>,
+    /// Merged over the base regime styles
+    #[serde(default)]
+    pub regime_styles: HashMap<RegimeKind, ExitStyleKind>,
+    pub trail_tighten_factor: Option<f64>,
+}
+
+fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *target = value.clone();
+    }
+}
+
+impl SizingOverrides {
+    fn apply(&self, config: &mut PositionSizingConfig) {
+        set(&mut config.base_size_sol, &self.base_size_sol);
+        set(&mut config.min_size_sol, &self.min_size_sol);
+        set(&mut config.max_size_sol, &self.max_size_sol);
+        set(&mut config.confidence_scaling, &self.confidence_scaling);
+        set(
+            &mut config.max_round_trip_impact_pct,
+            &self.max_round_trip_impact_pct,
+        );
+        set(&mut config.bankroll_pct, &self.bankroll_pct);
+    }
+}
+
+impl ExitOverrides {
+    fn apply(&self, config: &mut ExitManagerConfig) {
+        set(&mut config.default_style, &self.default_style);
+        set(
+            &mut config.quick_scalp_target_pct,
+            &self.quick_scalp_target_pct,
+        );
+        set(&mut config.tiered_levels, &self.tiered_levels);
+        set(&mut config.trailing_stop_pct, &self.trailing_stop_pct);
+        set(
+            &mut config.trailing_activation_pct,
+            &self.trailing_activation_pct,
+        );
+        set(&mut config.max_hold_secs, &self.max_hold_secs);
+        set(&mut config.stop_loss_pct, &self.stop_loss_pct);
+        config
+            .entry_type_styles
+            .extend(self.entry_type_styles.clone());
+        config.regime_styles.extend(self.regime_styles.clone());
+        set(&mut config.trail_tighten_factor, &self.trail_tighten_factor);
+    }
+}
+
+impl StrategyProfile {
+    /// Apply the overrides to `config`
+    pub fn apply(&self, config: &mut StrategyEngineConfig) {
+        set(&mut config.default_strategy, &self.default_strategy);
+        config
+            .regime_strategies
+            .extend(self.regime_strategies.clone());
+        self.position_sizing.apply(&mut config.position_sizing);
+        self.exits.apply(&mut config.exits);
+    }
+}
+
+/// Reject exit settings that name styles or entry types that don't exist
+pub fn validate_exits(exits: &ExitManagerConfig) -> Result<(), String> {
+    if !EXIT_STYLES.contains(&exits.default_style.as_str()) {
+        return Err(format!(
+            "unknown exit style '{}' (expected one of {})",
+            exits.default_style,
+            EXIT_STYLES.join(", ")
+        ));
+    }
+    for entry_type in exits.entry_type_styles.keys() {
+        if !ENTRY_TYPES.contains(&entry_type.as_str()) {
+            return Err(format!(
+                "unknown entry type '{}' in entry_type_styles (expected one of {})",
+                entry_type,
+                ENTRY_TYPES.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl StrategyEngineConfig {
+    /// This config with profile `name` applied (None or `base` = as is)
+    pub fn resolve(&self, name: Option<&str>) -> Result<Self, String> {
+        let mut config = self.clone();
+        match name {
+            None | Some(BASE_PROFILE) => {}
+            Some(name) => {
+                let profile = self.profiles.get(name).ok_or_else(|| {
+                    format!(
+                        "unknown strategy profile '{}' (defined: {})",
+                        name,
+                        self.profile_names().join(", ")
+                    )
+                })?;
+                profile.apply(&mut config);
+            }
+        }
+        Ok(config)
+    }
+
+    /// `base` and the defined profiles, sorted
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles.keys().cloned().collect();
+        names.sort();
+        names.insert(0, BASE_PROFILE.to_string());
+        names
+    }
+
+    /// Every profile must resolve to valid exits; the active one must exist
+    pub fn validate_profiles(&self) -> Result<(), String> {
+        validate_exits(&self.exits).map_err(|e| format!("exits: {}", e))?;
+        if self.profiles.contains_key(BASE_PROFILE) {
+            return Err(format!(
+                "'{}' is reserved for the base config",
+                BASE_PROFILE
+            ));
+        }
+        for name in self.profiles.keys() {
+            let resolved = self.resolve(Some(name))?;
+            validate_exits(&resolved.exits).map_err(|e| format!("profile {}: {}", name, e))?;
+            resolved
+                .position_sizing
+                .validate()
+                .map_err(|e| format!("profile {}: position_sizing: {}", name, e))?;
+        }
+        self.resolve(self.active_profile.as_deref()).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(name: &str, profile: StrategyProfile) -> StrategyEngineConfig {
+        let mut config = StrategyEngineConfig::default();
+        config.profiles.insert(name.to_string(), profile);
+        config
+    }
+
+    fn exits(exits: ExitOverrides) -> StrategyProfile {
+        StrategyProfile {
+            exits,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_profile_overrides() {
+        let profile = StrategyProfile {
+            default_strategy: Some(TradingStrategy::SnipeAndScalp),
+            position_sizing: SizingOverrides {
+                max_size_sol: Some(0.1),
+                ..Default::default()
+            },
+            exits: ExitOverrides {
+                stop_loss_pct: Some(8.0),
+                entry_type_styles: HashMap::from([("probe".to_string(), ExitStyleKind::Scalp)]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let config = config_with("cautious", profile);
+        let resolved = config.resolve(Some("cautious")).unwrap();
+        assert_eq!(resolved.default_strategy, TradingStrategy::SnipeAndScalp);
+        assert_eq!(resolved.position_sizing.max_size_sol, 0.1);
+        // Untouched fields keep the base values
+        assert_eq!(
+            resolved.position_sizing.base_size_sol,
+            config.position_sizing.base_size_sol
+        );
+        assert_eq!(resolved.exits.stop_loss_pct, 8.0);
+        assert_eq!(
+            resolved.exits.entry_type_styles.get("probe"),
+            Some(&ExitStyleKind::Scalp)
+        );
+
+        let base = config.resolve(Some(BASE_PROFILE)).unwrap();
+        assert_eq!(base.exits.stop_loss_pct, config.exits.stop_loss_pct);
+        assert!(config.resolve(Some("missing")).is_err());
+        assert_eq!(config.profile_names(), vec!["base", "cautious"]);
+    }
+
+    #[test]
+    fn test_validate_profiles() {
+        let config = config_with(
+            "fast",
+            exits(ExitOverrides {
+                default_style: Some("scalp".to_string()),
+                ..Default::default()
+            }),
+        );
+        assert!(config.validate_profiles().is_ok());
+
+        let config = config_with(
+            "bad",
+            exits(ExitOverrides {
+                default_style: Some("yolo".to_string()),
+                ..Default::default()
+            }),
+        );
+        let err = config.validate_profiles().unwrap_err();
+        assert!(err.contains("profile bad") && err.contains("yolo"));
+
+        let config = config_with(
+            "bad",
+            exits(ExitOverrides {
+                entry_type_styles: HashMap::from([("whale".to_string(), ExitStyleKind::Trail)]),
+                ..Default::default()
+            }),
+        );
+        assert!(config.validate_profiles().is_err());
+
+        let mut config = config_with("fast", StrategyProfile::default());
+        config.active_profile = Some("slow".to_string());
+        assert!(config.validate_profiles().is_err());
+    }
+}
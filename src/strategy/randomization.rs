@@ -32,8 +32,32 @@ pub struct RandomizationConfig {
     // Timing
     pub vary_check_interval: bool,
     pub check_interval_jitter_pct: f64,
+
+    // Buy amounts and fees
+    /// Max lamports added to or taken from a buy so it never lands on a round
+    /// 0.0001 SOL amount (0 = leave amounts as sized)
+    #[serde(default = "default_odd_amount_lamports")]
+    pub odd_amount_lamports: u64,
+    /// Priority fees are raised by up to this much (never lowered, so the
+    /// chain fee floor holds)
+    #[serde(default = "default_priority_fee_jitter_pct")]
+    pub priority_fee_jitter_pct: f64,
+    /// Fixed RNG seed (unset = seeded from entropy)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_odd_amount_lamports() -> u64 {
+    250_000
+}
+
+fn default_priority_fee_jitter_pct() -> f64 {
+    10.0
 }
 
+/// Lamports in the smallest "round" amount a buy must avoid (0.0001 SOL)
+const ROUND_AMOUNT_LAMPORTS: u64 = 100_000;
+
 impl Default for RandomizationConfig {
     fn default() -> Self {
         Self {
@@ -48,6 +72,9 @@ impl Default for RandomizationConfig {
             skip_probability: 0.02,
             vary_check_interval: true,
             check_interval_jitter_pct: 10.0,
+            odd_amount_lamports: default_odd_amount_lamports(),
+            priority_fee_jitter_pct: default_priority_fee_jitter_pct(),
+            seed: None,
         }
     }
 }
@@ -222,6 +249,47 @@ impl Randomizer {
         (base_fee as f64 * factor) as u64
     }
 
+    /// Move an amount off round values by a random few lamports
+    pub fn obscure_amount(&mut self, size_sol: f64) -> f64 {
+        let max_offset = self.config.odd_amount_lamports;
+        if !self.config.enabled || max_offset == 0 {
+            return size_sol;
+        }
+
+        let lamports = (size_sol * 1e9).round() as u64;
+        loop {
+            let offset = self.rng.gen_range(1..=max_offset);
+            let odd = if self.rng.gen::<bool>() && lamports > offset {
+                lamports - offset
+            } else {
+                lamports + offset
+            };
+            if odd % ROUND_AMOUNT_LAMPORTS != 0 {
+                return odd as f64 / 1e9;
+            }
+        }
+    }
+
+    /// Randomize a buy: size jitter, an odd amount and a slightly higher fee
+    ///
+    /// The returned values are the ones to send and to record on the position.
+    pub fn randomize_buy(&mut self, size_sol: f64, priority_fee_lamports: u64) -> RandomizedBuy {
+        if !self.config.enabled {
+            return RandomizedBuy {
+                size_sol,
+                priority_fee_lamports,
+            };
+        }
+
+        let jittered = self.jitter_entry_size(size_sol);
+        let fee_jitter = self.config.priority_fee_jitter_pct.max(0.0) / 100.0;
+        let fee_factor = self.rng.gen_range(1.0..=(1.0 + fee_jitter));
+        RandomizedBuy {
+            size_sol: self.obscure_amount(jittered),
+            priority_fee_lamports: (priority_fee_lamports as f64 * fee_factor).round() as u64,
+        }
+    }
+
     /// Reset the RNG with a new seed
     pub fn reseed(&mut self, seed: u64) {
         self.rng = StdRng::seed_from_u64(seed);
@@ -242,6 +310,13 @@ pub struct JitteredEntry {
     pub should_skip: bool,
 }
 
+/// A buy's size and priority fee after randomization
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomizedBuy {
+    pub size_sol: f64,
+    pub priority_fee_lamports: u64,
+}
+
 /// Jittered exit parameters
 #[derive(Debug, Clone)]
 pub struct JitteredExit {
@@ -402,6 +477,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_randomize_buy() {
+        let config = RandomizationConfig {
+            entry_size_jitter_pct: 10.0,
+            priority_fee_jitter_pct: 10.0,
+            ..Default::default()
+        };
+        let mut r1 = Randomizer::new(config.clone(), Some(7));
+        let mut r2 = Randomizer::new(config, Some(7));
+
+        for _ in 0..200 {
+            let buy = r1.randomize_buy(0.05, 100_000);
+            assert_eq!(buy, r2.randomize_buy(0.05, 100_000));
+
+            // Jitter band plus the odd-amount offset
+            assert!(buy.size_sol >= 0.045 - 0.00025 && buy.size_sol <= 0.055 + 0.00025);
+            let lamports = (buy.size_sol * 1e9).round() as u64;
+            assert_ne!(lamports % ROUND_AMOUNT_LAMPORTS, 0);
+
+            assert!(buy.priority_fee_lamports >= 100_000);
+            assert!(buy.priority_fee_lamports <= 110_000);
+        }
+    }
+
+    #[test]
+    fn test_randomize_buy_disabled() {
+        let config = RandomizationConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        let mut randomizer = Randomizer::new(config, Some(42));
+
+        let buy = randomizer.randomize_buy(0.05, 100_000);
+        assert_eq!(buy.size_sol, 0.05);
+        assert_eq!(buy.priority_fee_lamports, 100_000);
+    }
+
     #[test]
     fn test_reseed() {
         let config = RandomizationConfig::default();
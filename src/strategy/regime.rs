@@ -6,7 +6,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::delta_tracker::DeltaMetrics;
-use super::types::TokenRegime;
+use super::types::{TokenRegime, TradingStrategy};
 
 /// Order flow analysis for regime classification
 #[derive(Debug, Clone, Default)]
@@ -17,6 +17,22 @@ pub struct OrderFlowAnalysis {
     pub early_sell_pressure: f64,
     pub burst_detected: bool,
     pub burst_intensity: f64,
+    /// Holder growth score (-1.0 recycled volume to 1.0 broad accumulation)
+    pub organic_demand: Option<f64>,
+}
+
+impl From<&crate::filter::types::OrderFlowAnalysis> for OrderFlowAnalysis {
+    fn from(flow: &crate::filter::types::OrderFlowAnalysis) -> Self {
+        Self {
+            organic_score: flow.organic_score,
+            wash_trading_score: flow.wash_trading_score,
+            buy_sell_ratio: flow.buy_sell_ratio,
+            early_sell_pressure: flow.early_sell_pressure,
+            burst_detected: flow.burst_detected,
+            burst_intensity: flow.burst_intensity,
+            organic_demand: flow.holder_growth.organic_demand(),
+        }
+    }
 }
 
 /// Token distribution analysis
@@ -30,6 +46,19 @@ pub struct TokenDistribution {
     pub gini_coefficient: f64,
 }
 
+impl From<&crate::filter::types::TokenDistribution> for TokenDistribution {
+    fn from(dist: &crate::filter::types::TokenDistribution) -> Self {
+        Self {
+            top_holder_pct: dist.top_holder_pct,
+            top_10_holders_pct: dist.top_10_holders_pct,
+            sniper_holdings_pct: dist.sniper_holdings_pct,
+            deployer_holdings_pct: dist.deployer_holdings_pct,
+            holder_count: dist.holder_count,
+            gini_coefficient: dist.gini_coefficient,
+        }
+    }
+}
+
 /// Creator behavior analysis
 #[derive(Debug, Clone, Default)]
 pub struct CreatorBehavior {
@@ -39,6 +68,19 @@ pub struct CreatorBehavior {
     pub sell_count: u32,
 }
 
+/// Playbook for one regime (`[strategy.regime_strategies.<regime>]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeStrategy {
+    pub strategy: TradingStrategy,
+    /// Applied on top of the sizer's own regime multiplier (0 = don't trade)
+    #[serde(default = "default_regime_size_multiplier")]
+    pub size_multiplier: f64,
+}
+
+fn default_regime_size_multiplier() -> f64 {
+    1.0
+}
+
 /// Regime classification result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegimeClassification {
@@ -182,6 +224,20 @@ impl RegimeClassifier {
                 ));
             }
 
+            // Broad buyer growth backs the pump; a few wallets recycling
+            // buys undermines it
+            match order_flow.organic_demand {
+                Some(demand) if demand >= 0.3 => {
+                    confidence += 0.1;
+                    reasons.push(format!("Broad buyer growth ({:+.2})", demand));
+                }
+                Some(demand) if demand < 0.0 => {
+                    confidence -= 0.2;
+                    reasons.push(format!("Few wallets recycling buys ({:+.2})", demand));
+                }
+                _ => {}
+            }
+
             // Check momentum
             if delta.overall_trend.is_positive() {
                 confidence += 0.05;
@@ -365,6 +421,30 @@ mod tests {
         assert!(result.size_multiplier >= 1.0);
     }
 
+    #[test]
+    fn test_organic_demand_adjusts_confidence() {
+        let classifier = RegimeClassifier::new();
+        let confidence = |organic_demand| {
+            let order_flow = OrderFlowAnalysis {
+                organic_score: 0.6,
+                organic_demand,
+                ..Default::default()
+            };
+            classifier
+                .classify(
+                    &order_flow,
+                    &TokenDistribution::default(),
+                    &CreatorBehavior::default(),
+                    &DeltaMetrics::default(),
+                )
+                .confidence
+        };
+
+        let unknown = confidence(None);
+        assert!(confidence(Some(0.7)) > unknown);
+        assert!(confidence(Some(-0.5)) < unknown);
+    }
+
     #[test]
     fn test_unknown_regime() {
         let classifier = RegimeClassifier::new();
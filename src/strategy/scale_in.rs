@@ -0,0 +1,297 @@
+//! Scale-In / DCA Entries
+//!
+//! Splits a high-conviction entry into several tranches instead of one
+//! market buy at full size. The first tranche fills immediately; the rest
+//! are released either on a timer or when price drops a configured amount
+//! below the last fill. Remaining tranches are cancelled as soon as a
+//! fatal-risk or kill-switch signal appears.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Scale-in configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleInConfig {
+    /// Enable scale-in entries for StrongBuy tokens
+    pub enabled: bool,
+    /// Number of tranches to split the entry into
+    pub tranche_count: u32,
+    /// Seconds between tranches (time-based spacing)
+    pub spacing_secs: u64,
+    /// Price drop % below last fill that releases the next tranche
+    /// (0 = use time-based spacing only)
+    pub spacing_price_drop_pct: f64,
+    /// Cancel remaining price-drop tranches after this many seconds
+    pub tranche_timeout_secs: u64,
+}
+
+impl Default for ScaleInConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tranche_count: 3,
+            spacing_secs: 5,
+            spacing_price_drop_pct: 0.0,
+            tranche_timeout_secs: 120,
+        }
+    }
+}
+
+/// Status of a single tranche
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrancheStatus {
+    Pending,
+    Filled,
+    Failed,
+    Cancelled,
+}
+
+impl std::fmt::Display for TrancheStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrancheStatus::Pending => write!(f, "pending"),
+            TrancheStatus::Filled => write!(f, "filled"),
+            TrancheStatus::Failed => write!(f, "failed"),
+            TrancheStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// A single scheduled buy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tranche {
+    pub index: u32,
+    pub size_sol: f64,
+    pub status: TrancheStatus,
+    pub signature: Option<String>,
+    pub filled_at: Option<DateTime<Utc>>,
+}
+
+/// Scale-in plan for one position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleInPlan {
+    pub total_size_sol: f64,
+    pub tranches: Vec<Tranche>,
+    pub spacing_secs: u64,
+    pub spacing_price_drop_pct: f64,
+    pub tranche_timeout_secs: u64,
+    pub created_at: DateTime<Utc>,
+    /// Price of the most recent fill (reference for price-drop spacing)
+    pub last_fill_price: f64,
+    pub last_fill_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+}
+
+impl ScaleInPlan {
+    /// Build a plan splitting `total_size_sol` evenly across the configured tranches
+    pub fn new(total_size_sol: f64, config: &ScaleInConfig) -> Self {
+        let count = config.tranche_count.max(1);
+        let size = total_size_sol / count as f64;
+        let tranches = (0..count)
+            .map(|index| Tranche {
+                index,
+                size_sol: size,
+                status: TrancheStatus::Pending,
+                signature: None,
+                filled_at: None,
+            })
+            .collect();
+
+        Self {
+            total_size_sol,
+            tranches,
+            spacing_secs: config.spacing_secs,
+            spacing_price_drop_pct: config.spacing_price_drop_pct,
+            tranche_timeout_secs: config.tranche_timeout_secs,
+            created_at: Utc::now(),
+            last_fill_price: 0.0,
+            last_fill_at: None,
+            cancel_reason: None,
+        }
+    }
+
+    /// Single tranche placed right away (add-on buy after a re-score upgrade)
+    pub fn add_on(size_sol: f64) -> Self {
+        Self::new(
+            size_sol,
+            &ScaleInConfig {
+                enabled: true,
+                tranche_count: 1,
+                spacing_secs: 0,
+                spacing_price_drop_pct: 0.0,
+                tranche_timeout_secs: 0,
+            },
+        )
+    }
+
+    /// Size of the first tranche (bought immediately)
+    pub fn first_size(&self) -> f64 {
+        self.tranches.first().map(|t| t.size_sol).unwrap_or(0.0)
+    }
+
+    /// Next tranche still waiting to be placed
+    pub fn next_pending(&self) -> Option<&Tranche> {
+        self.tranches
+            .iter()
+            .find(|t| t.status == TrancheStatus::Pending)
+    }
+
+    /// Check whether the next pending tranche should be placed now
+    pub fn is_next_due(&self, now: DateTime<Utc>, current_price: f64) -> bool {
+        if self.next_pending().is_none() || self.cancel_reason.is_some() {
+            return false;
+        }
+        let last = self.last_fill_at.unwrap_or(self.created_at);
+        let elapsed = (now - last).num_seconds().max(0) as u64;
+
+        if self.spacing_price_drop_pct > 0.0 {
+            if self.last_fill_price <= 0.0 || current_price <= 0.0 {
+                return false;
+            }
+            let drop_pct = (self.last_fill_price - current_price) / self.last_fill_price * 100.0;
+            drop_pct >= self.spacing_price_drop_pct
+        } else {
+            elapsed >= self.spacing_secs
+        }
+    }
+
+    /// Price-drop tranches that never trigger are abandoned after the timeout
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        if self.spacing_price_drop_pct <= 0.0 {
+            return false;
+        }
+        let last = self.last_fill_at.unwrap_or(self.created_at);
+        (now - last).num_seconds().max(0) as u64 >= self.tranche_timeout_secs
+    }
+
+    /// Record a successful fill for a tranche
+    pub fn mark_filled(&mut self, index: u32, signature: &str, fill_price: f64) {
+        if let Some(tranche) = self.tranches.iter_mut().find(|t| t.index == index) {
+            tranche.status = TrancheStatus::Filled;
+            tranche.signature = Some(signature.to_string());
+            tranche.filled_at = Some(Utc::now());
+        }
+        self.last_fill_price = fill_price;
+        self.last_fill_at = Some(Utc::now());
+    }
+
+    /// Record a failed tranche (buy error or no tokens received)
+    pub fn mark_failed(&mut self, index: u32) {
+        if let Some(tranche) = self.tranches.iter_mut().find(|t| t.index == index) {
+            tranche.status = TrancheStatus::Failed;
+        }
+    }
+
+    /// Cancel every tranche that hasn't been placed yet
+    pub fn cancel_remaining(&mut self, reason: &str) {
+        for tranche in self.tranches.iter_mut() {
+            if tranche.status == TrancheStatus::Pending {
+                tranche.status = TrancheStatus::Cancelled;
+            }
+        }
+        if self.cancel_reason.is_none() {
+            self.cancel_reason = Some(reason.to_string());
+        }
+    }
+
+    /// Number of filled tranches
+    pub fn filled_count(&self) -> usize {
+        self.tranches
+            .iter()
+            .filter(|t| t.status == TrancheStatus::Filled)
+            .count()
+    }
+
+    /// SOL actually committed through filled tranches
+    pub fn filled_sol(&self) -> f64 {
+        self.tranches
+            .iter()
+            .filter(|t| t.status == TrancheStatus::Filled)
+            .map(|t| t.size_sol)
+            .sum()
+    }
+
+    /// No more tranches will be placed
+    pub fn is_complete(&self) -> bool {
+        self.next_pending().is_none()
+    }
+
+    /// One-line summary for status output
+    pub fn summary(&self) -> String {
+        let mut s = format!(
+            "{}/{} tranches filled ({:.4}/{:.4} SOL)",
+            self.filled_count(),
+            self.tranches.len(),
+            self.filled_sol(),
+            self.total_size_sol
+        );
+        if let Some(ref reason) = self.cancel_reason {
+            s.push_str(&format!(", cancelled: {}", reason));
+        } else if let Some(next) = self.next_pending() {
+            s.push_str(&format!(", next: #{} {:.4} SOL", next.index + 1, next.size_sol));
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_config() -> ScaleInConfig {
+        ScaleInConfig {
+            enabled: true,
+            tranche_count: 3,
+            spacing_secs: 5,
+            spacing_price_drop_pct: 0.0,
+            tranche_timeout_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_plan_splits_evenly() {
+        let plan = ScaleInPlan::new(0.3, &time_config());
+        assert_eq!(plan.tranches.len(), 3);
+        assert!((plan.first_size() - 0.1).abs() < 1e-9);
+        assert_eq!(plan.next_pending().unwrap().index, 0);
+    }
+
+    #[test]
+    fn test_time_spacing() {
+        let mut plan = ScaleInPlan::new(0.3, &time_config());
+        plan.mark_filled(0, "sig0", 0.001);
+
+        let now = Utc::now();
+        assert!(!plan.is_next_due(now, 0.001));
+        assert!(plan.is_next_due(now + chrono::Duration::seconds(6), 0.001));
+    }
+
+    #[test]
+    fn test_price_drop_spacing() {
+        let config = ScaleInConfig {
+            spacing_price_drop_pct: 10.0,
+            ..time_config()
+        };
+        let mut plan = ScaleInPlan::new(0.3, &config);
+        plan.mark_filled(0, "sig0", 0.001);
+
+        let now = Utc::now();
+        assert!(!plan.is_next_due(now, 0.00095));
+        assert!(plan.is_next_due(now, 0.00089));
+        assert!(plan.is_expired(now + chrono::Duration::seconds(61)));
+    }
+
+    #[test]
+    fn test_cancel_remaining() {
+        let mut plan = ScaleInPlan::new(0.3, &time_config());
+        plan.mark_filled(0, "sig0", 0.001);
+        plan.cancel_remaining("kill-switch");
+
+        assert!(plan.is_complete());
+        assert_eq!(plan.filled_count(), 1);
+        assert!((plan.filled_sol() - 0.1).abs() < 1e-9);
+        assert!(!plan.is_next_due(Utc::now() + chrono::Duration::seconds(60), 0.001));
+        assert!(plan.summary().contains("cancelled: kill-switch"));
+    }
+}
@@ -2,29 +2,143 @@
 //!
 //! Bet big on high-conviction, small on speculative.
 //! Adjusts position size based on confidence, regime, liquidity, and portfolio state.
+//!
+//! The base size is either fixed (`base_size_sol`) or, in bankroll mode, a
+//! percentage of hot-wallet equity (balance plus the marked value of open
+//! positions) taken at each entry. Bankroll sizes shrink as the day's
+//! realized loss approaches the daily limit.
 
 use serde::{Deserialize, Serialize};
+use tracing::info;
 
 use super::liquidity::LiquidityAnalysis;
 use super::types::TokenRegime;
 
+/// Where the base size comes from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMode {
+    /// `base_size_sol`
+    #[default]
+    Fixed,
+    /// `bankroll_pct` of hot-wallet equity
+    Bankroll,
+}
+
+impl std::fmt::Display for SizingMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SizingMode::Fixed => write!(f, "fixed"),
+            SizingMode::Bankroll => write!(f, "bankroll"),
+        }
+    }
+}
+
 /// Position sizing configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionSizingConfig {
+    #[serde(default)]
+    pub mode: SizingMode,
     pub base_size_sol: f64,
     pub min_size_sol: f64,
     pub max_size_sol: f64,
     pub confidence_scaling: bool,
+    /// Cap size so entry plus exit price impact at the current curve depth
+    /// stays within this % (0 = no cap). Exits are split so each sell stays
+    /// within half of it.
+    #[serde(default = "default_max_round_trip_impact_pct")]
+    pub max_round_trip_impact_pct: f64,
+    /// Size multiplier for post-migration entries (PumpSwap pool)
+    #[serde(default = "default_post_migration_multiplier")]
+    pub post_migration_multiplier: f64,
+    /// Bankroll mode: base size as a % of equity (min/max_size_sol still clamp)
+    #[serde(default = "default_bankroll_pct")]
+    pub bankroll_pct: f64,
+    /// Bankroll mode: size multiplier once the day's realized loss reaches
+    /// the daily limit, scaled linearly from 1.0 at no loss (1.0 = off)
+    #[serde(default = "default_drawdown_floor_multiplier")]
+    pub drawdown_floor_multiplier: f64,
+}
+
+fn default_max_round_trip_impact_pct() -> f64 {
+    10.0
+}
+
+fn default_post_migration_multiplier() -> f64 {
+    0.5
+}
+
+fn default_bankroll_pct() -> f64 {
+    2.0
+}
+
+fn default_drawdown_floor_multiplier() -> f64 {
+    0.25
+}
+
+fn default_multiplier() -> f64 {
+    1.0
+}
+
+impl PositionSizingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_size_sol > self.max_size_sol {
+            return Err("min_size_sol must not exceed max_size_sol".to_string());
+        }
+        if self.bankroll_pct <= 0.0 || self.bankroll_pct > 100.0 {
+            return Err("bankroll_pct must be in (0, 100]".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.drawdown_floor_multiplier) {
+            return Err("drawdown_floor_multiplier must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+
+    /// Price impact budget for a single exit sell (%)
+    pub fn max_sell_impact_pct(&self) -> f64 {
+        self.max_round_trip_impact_pct / 2.0
+    }
 }
 
 impl Default for PositionSizingConfig {
     fn default() -> Self {
         Self {
+            mode: SizingMode::Fixed,
             base_size_sol: 0.1,
             min_size_sol: 0.01,
             max_size_sol: 0.5,
             confidence_scaling: true,
+            max_round_trip_impact_pct: default_max_round_trip_impact_pct(),
+            post_migration_multiplier: default_post_migration_multiplier(),
+            bankroll_pct: default_bankroll_pct(),
+            drawdown_floor_multiplier: default_drawdown_floor_multiplier(),
+        }
+    }
+}
+
+/// Hot-wallet equity at an entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EquitySnapshot {
+    pub balance_sol: f64,
+    /// Open positions at their current price
+    pub positions_value_sol: f64,
+    /// Today's realized loss so far
+    pub daily_loss_sol: f64,
+    /// 0 = no limit
+    pub daily_loss_limit_sol: f64,
+}
+
+impl EquitySnapshot {
+    pub fn total_sol(&self) -> f64 {
+        self.balance_sol + self.positions_value_sol
+    }
+
+    /// Share of the daily loss limit already lost (0-1)
+    pub fn drawdown_fraction(&self) -> f64 {
+        if self.daily_loss_limit_sol <= 0.0 {
+            return 0.0;
         }
+        (self.daily_loss_sol / self.daily_loss_limit_sol).clamp(0.0, 1.0)
     }
 }
 
@@ -37,6 +151,8 @@ pub struct SizingContext {
     pub portfolio_remaining_sol: f64,
     pub chain_size_factor: f64,
     pub execution_size_factor: f64,
+    /// Bankroll mode input (None = fall back to `base_size_sol`)
+    pub equity: Option<EquitySnapshot>,
 }
 
 impl Default for SizingContext {
@@ -53,6 +169,7 @@ impl Default for SizingContext {
             portfolio_remaining_sol: 1.0,
             chain_size_factor: 1.0,
             execution_size_factor: 1.0,
+            equity: None,
         }
     }
 }
@@ -68,9 +185,22 @@ impl PositionSizer {
         Self { config }
     }
 
+    /// Base size before the multipliers, and the drawdown multiplier in it
+    pub fn base_size(&self, ctx: &SizingContext) -> (f64, f64) {
+        match (self.config.mode, &ctx.equity) {
+            (SizingMode::Bankroll, Some(equity)) => {
+                let floor = self.config.drawdown_floor_multiplier;
+                let drawdown = 1.0 - equity.drawdown_fraction() * (1.0 - floor);
+                let base = equity.total_sol() * self.config.bankroll_pct / 100.0;
+                (base * drawdown, drawdown)
+            }
+            _ => (self.config.base_size_sol, 1.0),
+        }
+    }
+
     /// Calculate position size
     pub fn calculate_size(&self, ctx: &SizingContext) -> f64 {
-        let mut size = self.config.base_size_sol;
+        let (mut size, _) = self.base_size(ctx);
 
         // 1. Confidence scaling (0.5x to 2.0x)
         if self.config.confidence_scaling {
@@ -102,15 +232,35 @@ impl PositionSizer {
             size = self.config.min_size_sol;
         }
 
-        // 6. Portfolio constraint
+        // 6. Round-trip impact budget at the current depth
+        if let Some(cap) = self.impact_cap(&ctx.liquidity) {
+            if size > cap {
+                info!(
+                    "Size capped at {:.4} SOL (from {:.4}): {:.1}% round-trip impact budget at {:.2} SOL depth",
+                    cap, size, self.config.max_round_trip_impact_pct, ctx.liquidity.sol_reserves
+                );
+                size = cap;
+            }
+        }
+
+        // 7. Portfolio constraint
         size = size.min(ctx.portfolio_remaining_sol);
 
-        // 7. Clamp to configured limits
+        // 8. Clamp to configured limits
         size = size.clamp(self.config.min_size_sol, self.config.max_size_sol);
 
         size
     }
 
+    /// Largest size whose round trip fits the impact budget (None = no cap,
+    /// or no reserves to measure)
+    pub fn impact_cap(&self, liquidity: &LiquidityAnalysis) -> Option<f64> {
+        if self.config.max_round_trip_impact_pct <= 0.0 || liquidity.sol_reserves <= 0.0 {
+            return None;
+        }
+        Some(liquidity.max_size_for_round_trip(self.config.max_round_trip_impact_pct))
+    }
+
     /// Calculate size with simple inputs
     pub fn calculate_simple(&self, confidence: f64, regime: &TokenRegime) -> f64 {
         let ctx = SizingContext {
@@ -123,7 +273,11 @@ impl PositionSizer {
 
     /// Get size breakdown for explanation
     pub fn explain_size(&self, ctx: &SizingContext) -> SizeExplanation {
-        let base = self.config.base_size_sol;
+        let (base, drawdown_mult) = self.base_size(ctx);
+        let equity = match self.config.mode {
+            SizingMode::Bankroll => ctx.equity.clone(),
+            SizingMode::Fixed => None,
+        };
 
         let conf_mult = if self.config.confidence_scaling {
             0.5 + (ctx.confidence * 1.5)
@@ -141,17 +295,23 @@ impl PositionSizer {
             None
         };
 
+        let impact_cap = self.impact_cap(&ctx.liquidity);
         let portfolio_cap = ctx.portfolio_remaining_sol;
 
         let final_size = self.calculate_size(ctx);
 
         SizeExplanation {
+            mode: self.config.mode,
+            equity,
+            bankroll_pct: self.config.bankroll_pct,
+            drawdown_multiplier: drawdown_mult,
             base_size: base,
             confidence_multiplier: conf_mult,
             regime_multiplier: regime_mult,
             chain_multiplier: chain_mult,
             execution_multiplier: exec_mult,
             liquidity_cap,
+            impact_cap,
             portfolio_cap,
             final_size,
         }
@@ -167,20 +327,87 @@ impl Default for PositionSizer {
 /// Explanation of how size was calculated
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SizeExplanation {
+    #[serde(default)]
+    pub mode: SizingMode,
+    /// Equity the bankroll base size was taken from
+    #[serde(default)]
+    pub equity: Option<EquitySnapshot>,
+    #[serde(default)]
+    pub bankroll_pct: f64,
+    /// Daily drawdown reduction included in `base_size`
+    #[serde(default = "default_multiplier")]
+    pub drawdown_multiplier: f64,
     pub base_size: f64,
     pub confidence_multiplier: f64,
     pub regime_multiplier: f64,
     pub chain_multiplier: f64,
     pub execution_multiplier: f64,
     pub liquidity_cap: Option<f64>,
+    /// Round-trip impact budget cap
+    #[serde(default)]
+    pub impact_cap: Option<f64>,
     pub portfolio_cap: f64,
     pub final_size: f64,
 }
 
+impl SizeExplanation {
+    /// One-line derivation for the entry log
+    pub fn derivation(&self) -> String {
+        let base = match self.equity {
+            Some(ref equity) => format!(
+                "equity {:.4} SOL ({:.4} balance + {:.4} in positions) x {:.2}% x drawdown {:.2}x",
+                equity.total_sol(),
+                equity.balance_sol,
+                equity.positions_value_sol,
+                self.bankroll_pct,
+                self.drawdown_multiplier
+            ),
+            None => format!("{} base", self.mode),
+        };
+        let mut line = format!(
+            "{} = {:.4} SOL x confidence {:.2}x x regime {:.2}x x chain {:.2}x x execution {:.2}x",
+            base,
+            self.base_size,
+            self.confidence_multiplier,
+            self.regime_multiplier,
+            self.chain_multiplier,
+            self.execution_multiplier
+        );
+        for (name, cap) in [
+            ("liquidity", self.liquidity_cap),
+            ("impact", self.impact_cap),
+        ] {
+            if let Some(cap) = cap {
+                line.push_str(&format!(", {} cap {:.4}", name, cap));
+            }
+        }
+        line.push_str(&format!(
+            ", portfolio cap {:.4} -> {:.4} SOL",
+            self.portfolio_cap, self.final_size
+        ));
+        line
+    }
+}
+
 impl std::fmt::Display for SizeExplanation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Position Size Calculation:")?;
-        writeln!(f, "  Base size: {:.4} SOL", self.base_size)?;
+        if let Some(ref equity) = self.equity {
+            writeln!(
+                f,
+                "  Equity: {:.4} SOL ({:.4} balance + {:.4} in positions) × {:.2}%",
+                equity.total_sol(),
+                equity.balance_sol,
+                equity.positions_value_sol,
+                self.bankroll_pct
+            )?;
+            writeln!(
+                f,
+                "  × Drawdown ({:.2}x, {:.4} of {:.4} SOL daily loss)",
+                self.drawdown_multiplier, equity.daily_loss_sol, equity.daily_loss_limit_sol
+            )?;
+        }
+        writeln!(f, "  Base size: {:.4} SOL ({})", self.base_size, self.mode)?;
         writeln!(f, "  × Confidence ({:.2}x)", self.confidence_multiplier)?;
         writeln!(f, "  × Regime ({:.2}x)", self.regime_multiplier)?;
         writeln!(f, "  × Chain health ({:.2}x)", self.chain_multiplier)?;
@@ -188,6 +415,9 @@ impl std::fmt::Display for SizeExplanation {
         if let Some(cap) = self.liquidity_cap {
             writeln!(f, "  Liquidity cap: {:.4} SOL", cap)?;
         }
+        if let Some(cap) = self.impact_cap {
+            writeln!(f, "  Impact cap: {:.4} SOL", cap)?;
+        }
         writeln!(f, "  Portfolio cap: {:.4} SOL", self.portfolio_cap)?;
         writeln!(f, "  = Final: {:.4} SOL", self.final_size)
     }
@@ -296,6 +526,7 @@ mod tests {
             max_size_sol: 2.0,
             min_size_sol: 0.01,
             confidence_scaling: false,
+            ..Default::default()
         });
 
         let mut liquidity = LiquidityAnalysis::default();
@@ -313,6 +544,7 @@ mod tests {
             portfolio_remaining_sol: 10.0,
             chain_size_factor: 1.0,
             execution_size_factor: 1.0,
+            equity: None,
         };
 
         let size = sizer.calculate_size(&ctx);
@@ -320,6 +552,43 @@ mod tests {
         assert!(size <= 0.161, "Expected size <= 0.16, got {}", size);
     }
 
+    #[test]
+    fn test_round_trip_impact_cap() {
+        let sizer = PositionSizer::new(PositionSizingConfig {
+            base_size_sol: 1.0,
+            max_size_sol: 2.0,
+            confidence_scaling: false,
+            max_round_trip_impact_pct: 10.0,
+            ..Default::default()
+        });
+
+        // Thin curve: 2 SOL of depth
+        let liquidity = crate::strategy::liquidity::LiquidityAnalyzer::default_config()
+            .analyze_simple(2.0, 1_000_000.0);
+        let cap = sizer.impact_cap(&liquidity).unwrap();
+        assert!((liquidity.round_trip_impact_pct(cap) - 10.0).abs() < 1e-6);
+
+        let ctx = SizingContext {
+            regime: TokenRegime::OrganicPump {
+                confidence: 0.8,
+                expected_duration_secs: 60,
+            },
+            liquidity,
+            portfolio_remaining_sol: 10.0,
+            ..Default::default()
+        };
+        let size = sizer.calculate_size(&ctx);
+        assert!(size <= cap + 1e-9, "Expected size <= {}, got {}", cap, size);
+        assert_eq!(sizer.explain_size(&ctx).impact_cap, Some(cap));
+
+        // Disabled
+        let uncapped = PositionSizer::new(PositionSizingConfig {
+            max_round_trip_impact_pct: 0.0,
+            ..Default::default()
+        });
+        assert!(uncapped.impact_cap(&ctx.liquidity).is_none());
+    }
+
     #[test]
     fn test_portfolio_cap() {
         let sizer = PositionSizer::new(PositionSizingConfig {
@@ -342,6 +611,54 @@ mod tests {
         assert!(size <= 0.3);
     }
 
+    #[test]
+    fn test_bankroll_size_follows_equity_and_drawdown() {
+        let sizer = PositionSizer::new(PositionSizingConfig {
+            mode: SizingMode::Bankroll,
+            bankroll_pct: 2.0,
+            min_size_sol: 0.01,
+            max_size_sol: 0.5,
+            confidence_scaling: false,
+            max_round_trip_impact_pct: 0.0,
+            ..Default::default()
+        });
+        let ctx = |balance_sol: f64, daily_loss_sol: f64| SizingContext {
+            regime: TokenRegime::OrganicPump {
+                confidence: 0.5,
+                expected_duration_secs: 60,
+            },
+            portfolio_remaining_sol: 10.0,
+            equity: Some(EquitySnapshot {
+                balance_sol,
+                positions_value_sol: 1.0,
+                daily_loss_sol,
+                daily_loss_limit_sol: 1.0,
+            }),
+            ..Default::default()
+        };
+
+        // 2% of 5 SOL
+        let size = sizer.calculate_size(&ctx(4.0, 0.0));
+        assert!((size - 0.1).abs() < 1e-9, "{}", size);
+        // Grows with the bankroll, up to max_size_sol
+        assert!((sizer.calculate_size(&ctx(9.0, 0.0)) - 0.2).abs() < 1e-9);
+        assert_eq!(sizer.calculate_size(&ctx(99.0, 0.0)), 0.5);
+        // Half the daily limit lost: 1 - 0.5 * 0.75
+        let explained = sizer.explain_size(&ctx(4.0, 0.5));
+        assert!((explained.drawdown_multiplier - 0.625).abs() < 1e-9);
+        assert!((explained.final_size - 0.0625).abs() < 1e-9);
+        assert_eq!(explained.equity.unwrap().total_sol(), 5.0);
+        // Floor clamp at the limit
+        assert_eq!(sizer.calculate_size(&ctx(0.0, 1.0)), 0.01);
+
+        // No equity: fixed base size
+        let fixed = sizer.calculate_size(&SizingContext {
+            equity: None,
+            ..ctx(4.0, 0.0)
+        });
+        assert!((fixed - 0.1).abs() < 1e-9);
+    }
+
     #[test]
     fn test_explain_size() {
         let sizer = PositionSizer::default();
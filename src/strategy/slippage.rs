@@ -0,0 +1,355 @@
+//! Per-buy slippage estimate
+//!
+//! A fixed `trading.slippage_bps` is too loose on a quiet curve and too tight
+//! on a busy one. Before each buy the estimator quotes it against the curve
+//! reserves last seen on the trade feed (`pump::price::quote_buy`, fee
+//! included) and adds a volatility buffer: the token's price spread over the
+//! last `volatility_window_secs` of trades (standard deviation over mean)
+//! times `volatility_mult`. The sum, clamped to `floor_bps..=ceiling_bps`, is
+//! the buy's slippage tolerance; execution feedback still widens it while
+//! fills run hot.
+//!
+//! The estimate is kept on the position. The fill check adds the realized
+//! slippage and feeds both to execution feedback, so a post-mortem can tell
+//! an estimator miss (realized past the estimate) from a market move.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::delta_tracker::DeltaTracker;
+use crate::pump::accounts::BondingCurve;
+use crate::pump::price::{mul_div, quote_buy};
+
+/// Curves not traded for this long are forgotten
+const STALE_CURVE_SECS: u64 = 300;
+
+/// Tracked curves before stale ones are pruned
+const PRUNE_AT_CURVES: usize = 2_000;
+
+/// `[strategy.slippage]` configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlippageEstimatorConfig {
+    /// Off = every buy uses `trading.slippage_bps`
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Lowest tolerance sent with a buy (bps)
+    #[serde(default = "default_floor_bps")]
+    pub floor_bps: u32,
+    /// Highest tolerance sent with a buy, before execution feedback (bps)
+    #[serde(default = "default_ceiling_bps")]
+    pub ceiling_bps: u32,
+    /// Trades over this window set the volatility buffer
+    #[serde(default = "default_volatility_window_secs")]
+    pub volatility_window_secs: u64,
+    /// Volatility buffer = price standard deviation (relative) x this
+    #[serde(default = "default_volatility_mult")]
+    pub volatility_mult: f64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_floor_bps() -> u32 {
+    300
+}
+
+fn default_ceiling_bps() -> u32 {
+    2500
+}
+
+fn default_volatility_window_secs() -> u64 {
+    30
+}
+
+fn default_volatility_mult() -> f64 {
+    2.0
+}
+
+impl Default for SlippageEstimatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            floor_bps: default_floor_bps(),
+            ceiling_bps: default_ceiling_bps(),
+            volatility_window_secs: default_volatility_window_secs(),
+            volatility_mult: default_volatility_mult(),
+        }
+    }
+}
+
+impl SlippageEstimatorConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.floor_bps > self.ceiling_bps || self.ceiling_bps > 10_000 {
+            return Err("need floor_bps <= ceiling_bps <= 10000".to_string());
+        }
+        if self.volatility_mult < 0.0 {
+            return Err("volatility_mult must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Slippage chosen for one buy, kept on its position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlippageEstimate {
+    /// Quoted fill below the spot price, fee included (bps)
+    pub impact_bps: u32,
+    /// Buffer for price moves before the buy lands (bps)
+    pub volatility_bps: u32,
+    /// Tolerance sent with the buy (bps)
+    pub chosen_bps: u32,
+    /// False = no curve seen for the mint; `chosen_bps` is the fixed slippage
+    pub estimated: bool,
+    /// Raw tokens the buy would get at the spot price
+    #[serde(default)]
+    pub spot_tokens: u64,
+    /// Realized fill below the spot price (bps, None until the fill is checked)
+    #[serde(default)]
+    pub realized_bps: Option<i64>,
+}
+
+impl SlippageEstimate {
+    /// Fixed tolerance, no estimate
+    pub fn fixed(chosen_bps: u32) -> Self {
+        Self {
+            impact_bps: 0,
+            volatility_bps: 0,
+            chosen_bps,
+            estimated: false,
+            spot_tokens: 0,
+            realized_bps: None,
+        }
+    }
+
+    /// Expected slippage: price impact plus the volatility buffer (bps)
+    pub fn estimated_bps(&self) -> u32 {
+        self.impact_bps + self.volatility_bps
+    }
+
+    /// Tolerance in whole percent (rounded up), as the trade APIs take it
+    pub fn pct(&self) -> u32 {
+        self.chosen_bps.div_ceil(100)
+    }
+
+    /// Record the raw tokens actually received
+    pub fn record_fill(&mut self, actual_tokens: u64) {
+        if self.estimated && self.spot_tokens > 0 && actual_tokens > 0 {
+            let ratio = self.spot_tokens as f64 / actual_tokens as f64;
+            self.realized_bps = Some(((ratio - 1.0) * 10_000.0).round() as i64);
+        }
+    }
+
+    /// One line for `snipe status`, e.g. `600 bps sent | est 433 bps (impact
+    /// 433 + volatility 0) | realized 410 bps: within estimate`
+    pub fn summary(&self) -> String {
+        if !self.estimated {
+            return format!("{} bps sent (fixed)", self.chosen_bps);
+        }
+        let realized = match (self.realized_bps, self.missed()) {
+            (Some(realized), Some(true)) => {
+                format!("realized {} bps: estimator miss", realized)
+            }
+            (Some(realized), _) => format!("realized {} bps: within estimate", realized),
+            (None, _) => "fill not checked".to_string(),
+        };
+        format!(
+            "{} bps sent | est {} bps (impact {} + volatility {}) | {}",
+            self.chosen_bps,
+            self.estimated_bps(),
+            self.impact_bps,
+            self.volatility_bps,
+            realized
+        )
+    }
+
+    /// Whether the fill slipped past the estimate (None until the fill is checked)
+    pub fn missed(&self) -> Option<bool> {
+        self.realized_bps
+            .map(|realized| realized > self.estimated_bps() as i64)
+    }
+}
+
+/// Last curve seen for a mint
+#[derive(Debug, Clone)]
+struct CurveSample {
+    curve: BondingCurve,
+    at: Instant,
+}
+
+/// Estimates buy slippage from the trade feed
+pub struct SlippageEstimator {
+    config: SlippageEstimatorConfig,
+    curves: Mutex<HashMap<String, CurveSample>>,
+    /// Spot price per mint over the volatility window
+    prices: Mutex<DeltaTracker>,
+}
+
+impl SlippageEstimator {
+    pub fn new(config: SlippageEstimatorConfig) -> Self {
+        let window = Duration::from_secs(config.volatility_window_secs);
+        Self {
+            config,
+            curves: Mutex::new(HashMap::new()),
+            prices: Mutex::new(DeltaTracker::with_duration(window)),
+        }
+    }
+
+    pub fn config(&self) -> &SlippageEstimatorConfig {
+        &self.config
+    }
+
+    /// Record the curve reserves after a trade (or at creation)
+    pub fn record_curve(&self, mint: &str, curve: BondingCurve) {
+        if !self.config.enabled {
+            return;
+        }
+        let Ok(price) = curve.get_price() else {
+            return;
+        };
+        let now = Instant::now();
+        let stale = {
+            let mut curves = self.curves.lock().unwrap();
+            curves.insert(mint.to_string(), CurveSample { curve, at: now });
+            if curves.len() < PRUNE_AT_CURVES {
+                Vec::new()
+            } else {
+                let cutoff = Duration::from_secs(STALE_CURVE_SECS);
+                let stale: Vec<String> = curves
+                    .iter()
+                    .filter(|(_, sample)| now.duration_since(sample.at) > cutoff)
+                    .map(|(mint, _)| mint.clone())
+                    .collect();
+                for stale_mint in &stale {
+                    curves.remove(stale_mint);
+                }
+                stale
+            }
+        };
+        let mut prices = self.prices.lock().unwrap();
+        for stale_mint in &stale {
+            prices.clear_token(stale_mint);
+        }
+        prices.record(&format!("{}:price", mint), price);
+    }
+
+    /// Slippage for buying `sol_in` lamports of `mint`; `base_bps` is used
+    /// when the estimator is off or has not seen the curve
+    pub fn estimate(&self, mint: &str, sol_in: u64, base_bps: u32) -> SlippageEstimate {
+        if !self.config.enabled {
+            return SlippageEstimate::fixed(base_bps);
+        }
+        let Some(sample) = self.curves.lock().unwrap().get(mint).cloned() else {
+            return SlippageEstimate::fixed(base_bps);
+        };
+        let curve = sample.curve;
+        let spot_tokens = mul_div(
+            sol_in,
+            curve.virtual_token_reserves,
+            curve.virtual_sol_reserves,
+        );
+        let (Ok(quote), Ok(spot_tokens)) = (quote_buy(&curve, sol_in), spot_tokens) else {
+            return SlippageEstimate::fixed(base_bps);
+        };
+        if quote.tokens_out == 0 {
+            return SlippageEstimate::fixed(base_bps);
+        }
+        let impact = (spot_tokens as f64 / quote.tokens_out as f64 - 1.0) * 10_000.0;
+
+        let volatility = {
+            let mut prices = self.prices.lock().unwrap();
+            let window = prices.get_window(&format!("{}:price", mint));
+            if window.count() < 2 || window.average() <= 0.0 {
+                0.0
+            } else {
+                window.std_dev() / window.average() * 10_000.0 * self.config.volatility_mult
+            }
+        };
+
+        let impact_bps = impact.max(0.0).round() as u32;
+        let volatility_bps = volatility.round() as u32;
+        SlippageEstimate {
+            impact_bps,
+            volatility_bps,
+            chosen_bps: (impact_bps + volatility_bps)
+                .clamp(self.config.floor_bps, self.config.ceiling_bps),
+            estimated: true,
+            spot_tokens,
+            realized_bps: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pump::price::sol_to_lamports;
+
+    fn curve(sol: f64, tokens: f64) -> BondingCurve {
+        crate::filter::honeypot::curve_from_event(sol, tokens)
+    }
+
+    #[test]
+    fn test_estimate_from_curve_and_volatility() {
+        let estimator = SlippageEstimator::new(SlippageEstimatorConfig::default());
+        let sol_in = sol_to_lamports(1.0);
+        assert_eq!(
+            estimator.estimate("m", sol_in, 2500),
+            SlippageEstimate::fixed(2500)
+        );
+
+        // 1 SOL into a 30 SOL curve: ~3.3% impact plus the 1% fee, no buffer yet
+        estimator.record_curve("m", curve(30.0, 1_073_000_000.0));
+        let quiet = estimator.estimate("m", sol_in, 2500);
+        assert!(quiet.estimated);
+        assert!((400..500).contains(&quiet.impact_bps), "{:?}", quiet);
+        assert_eq!(quiet.volatility_bps, 0);
+        assert_eq!(quiet.chosen_bps, quiet.impact_bps);
+        assert_eq!(quiet.pct(), 5);
+
+        // Price swings widen the buffer, up to the ceiling
+        estimator.record_curve("m", curve(40.0, 800_000_000.0));
+        estimator.record_curve("m", curve(30.0, 1_073_000_000.0));
+        let busy = estimator.estimate("m", sol_in, 2500);
+        assert!(busy.volatility_bps > 1000, "{:?}", busy);
+        assert_eq!(busy.chosen_bps, 2500);
+
+        // A small buy on a quiet curve is held at the floor
+        let other = SlippageEstimator::new(SlippageEstimatorConfig::default());
+        other.record_curve("n", curve(30.0, 1_073_000_000.0));
+        assert_eq!(
+            other.estimate("n", sol_to_lamports(0.01), 2500).chosen_bps,
+            300
+        );
+
+        let off = SlippageEstimator::new(SlippageEstimatorConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        off.record_curve("m", curve(30.0, 1_073_000_000.0));
+        assert!(!off.estimate("m", sol_in, 2500).estimated);
+    }
+
+    #[test]
+    fn test_realized_slippage() {
+        let estimator = SlippageEstimator::new(SlippageEstimatorConfig::default());
+        estimator.record_curve("m", curve(30.0, 1_073_000_000.0));
+        let mut estimate = estimator.estimate("m", sol_to_lamports(1.0), 2500);
+        assert_eq!(estimate.missed(), None);
+
+        // Filled at 90% of spot: ~11% realized, past the ~4.4% estimate
+        estimate.record_fill(estimate.spot_tokens * 9 / 10);
+        assert!((1100..1120).contains(&estimate.realized_bps.unwrap()));
+        assert_eq!(estimate.missed(), Some(true));
+
+        estimate.record_fill(estimate.spot_tokens * 97 / 100);
+        assert_eq!(estimate.missed(), Some(false));
+        assert!(estimate.summary().ends_with("within estimate"));
+
+        let mut fixed = SlippageEstimate::fixed(2500);
+        fixed.record_fill(1_000);
+        assert_eq!(fixed.realized_bps, None);
+    }
+}
@@ -4,7 +4,12 @@
 //! Useful for "riding the wave" of institutional or whale buying.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use crate::filter::AccumulationMetrics;
+
+/// pump.fun token supply in whole tokens (trade feed amounts are whole tokens)
+const TOKEN_SUPPLY: f64 = 1_000_000_000.0;
 
 /// Front-run detector configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +19,8 @@ pub struct FrontRunDetectorConfig {
     pub cluster_window_secs: u64,
     pub min_cluster_size: usize,
     pub whale_threshold_sol: f64,
+    /// Signal confidence that raises an entry candidate
+    pub min_entry_confidence: f64,
 }
 
 impl Default for FrontRunDetectorConfig {
@@ -24,6 +31,7 @@ impl Default for FrontRunDetectorConfig {
             cluster_window_secs: 30,
             min_cluster_size: 3,
             whale_threshold_sol: 0.5,
+            min_entry_confidence: 0.7,
         }
     }
 }
@@ -54,16 +62,36 @@ pub struct AccumulationSignal {
     pub mint: String,
     pub confidence: f64,
     pub total_accumulated_sol: f64,
+    #[serde(default)]
+    pub total_accumulated_tokens: f64,
     pub unique_buyers: u32,
     pub cluster_detected: bool,
     pub whale_detected: bool,
     pub recommendation: String,
 }
 
+impl AccumulationSignal {
+    /// Share of the token supply bought in the window (%)
+    pub fn float_pct(&self) -> f64 {
+        self.total_accumulated_tokens / TOKEN_SUPPLY * 100.0
+    }
+
+    /// Metrics attached to the entry candidate's signal context
+    pub fn metrics(&self) -> AccumulationMetrics {
+        AccumulationMetrics {
+            accumulated_sol: self.total_accumulated_sol,
+            float_pct: self.float_pct(),
+            unique_buyers: self.unique_buyers,
+            confidence: self.confidence,
+        }
+    }
+}
+
 /// Front-run detector
 pub struct FrontRunDetector {
     config: FrontRunDetectorConfig,
     recent_trades: HashMap<String, Vec<TradeRecord>>, // mint -> trades
+    signaled: HashSet<String>,                        // mints that raised a candidate
 }
 
 impl FrontRunDetector {
@@ -72,6 +100,7 @@ impl FrontRunDetector {
         Self {
             config,
             recent_trades: HashMap::new(),
+            signaled: HashSet::new(),
         }
     }
 
@@ -86,6 +115,41 @@ impl FrontRunDetector {
         trades.retain(|t| t.timestamp > cutoff);
     }
 
+    /// Record a feed trade; returns an entry candidate the first time a buy
+    /// pushes the mint's accumulation over `min_entry_confidence`
+    pub fn on_trade(&mut self, mint: &str, trade: TradeRecord) -> Option<AccumulationSignal> {
+        let is_buy = trade.is_buy;
+        self.record_trade(mint, trade);
+        if !is_buy || self.signaled.contains(mint) {
+            return None;
+        }
+
+        let signal = self
+            .detect_accumulation(mint)
+            .filter(|s| s.confidence >= self.config.min_entry_confidence)?;
+        self.signaled.insert(mint.to_string());
+        Some(signal)
+    }
+
+    /// Forget mints with no trades in the retention window
+    ///
+    /// A pruned mint can raise a fresh candidate if accumulation resumes.
+    pub fn prune(&mut self) {
+        let cutoff = chrono::Utc::now()
+            - chrono::Duration::seconds(self.config.cluster_window_secs as i64 * 10);
+        self.recent_trades.retain(|_, trades| {
+            trades.retain(|t| t.timestamp > cutoff);
+            !trades.is_empty()
+        });
+        let recent_trades = &self.recent_trades;
+        self.signaled
+            .retain(|mint| recent_trades.contains_key(mint));
+    }
+
+    pub fn tracked_mints(&self) -> usize {
+        self.recent_trades.len()
+    }
+
     /// Detect accumulation patterns
     pub fn detect_accumulation(&self, mint: &str) -> Option<AccumulationSignal> {
         if !self.config.enabled {
@@ -117,6 +181,7 @@ impl FrontRunDetector {
         }
 
         let total_accumulated: f64 = trader_totals.values().sum();
+        let total_accumulated_tokens: f64 = recent_buys.iter().map(|t| t.token_amount).sum();
         let unique_buyers = trader_totals.len() as u32;
 
         // Check for whale (single large buyer)
@@ -168,6 +233,7 @@ impl FrontRunDetector {
             mint: mint.to_string(),
             confidence,
             total_accumulated_sol: total_accumulated,
+            total_accumulated_tokens,
             unique_buyers,
             cluster_detected,
             whale_detected,
@@ -196,6 +262,7 @@ impl FrontRunDetector {
         // Calculate buy velocity (buys per minute)
         let buy_count = recent_buys.len();
         let total_sol: f64 = recent_buys.iter().map(|t| t.sol_amount).sum();
+        let total_tokens: f64 = recent_buys.iter().map(|t| t.token_amount).sum();
 
         // Unusual if more than 5 buys/min or > 1 SOL/min
         if buy_count < 5 && total_sol < 1.0 {
@@ -206,6 +273,7 @@ impl FrontRunDetector {
             mint: mint.to_string(),
             confidence: 0.6,
             total_accumulated_sol: total_sol,
+            total_accumulated_tokens: total_tokens,
             unique_buyers: buy_count as u32,
             cluster_detected: buy_count >= 5,
             whale_detected: total_sol > 2.0,
@@ -216,11 +284,13 @@ impl FrontRunDetector {
     /// Clear trade history for a token
     pub fn clear(&mut self, mint: &str) {
         self.recent_trades.remove(mint);
+        self.signaled.remove(mint);
     }
 
     /// Clear all trade history
     pub fn clear_all(&mut self) {
         self.recent_trades.clear();
+        self.signaled.clear();
     }
 }
 
@@ -308,4 +378,48 @@ mod tests {
         let signal = detector.detect_accumulation("mint1");
         assert!(signal.is_none());
     }
+
+    #[test]
+    fn test_on_trade_raises_candidate_once() {
+        let mut detector = FrontRunDetector::default();
+
+        // Whale alone (0.4) is below the 0.7 entry confidence
+        assert!(detector
+            .on_trade("mint1", create_buy_trade("whale1", 0.6))
+            .is_none());
+        assert!(detector
+            .on_trade("mint1", create_buy_trade("trader2", 0.2))
+            .is_none());
+        // Third buyer completes the cluster: whale + cluster
+        let signal = detector
+            .on_trade("mint1", create_buy_trade("trader3", 0.3))
+            .unwrap();
+        assert!(signal.confidence >= 0.7);
+        assert_eq!(signal.unique_buyers, 3);
+        // 1.1 SOL * 1000 tokens per SOL out of 1B supply
+        assert!((signal.float_pct() - 1100.0 / TOKEN_SUPPLY * 100.0).abs() < 1e-12);
+        assert_eq!(signal.metrics().unique_buyers, 3);
+
+        // Already raised for this mint
+        assert!(detector
+            .on_trade("mint1", create_buy_trade("trader4", 0.5))
+            .is_none());
+    }
+
+    #[test]
+    fn test_prune_drops_stale_mints() {
+        let mut detector = FrontRunDetector::default();
+
+        let mut stale = create_buy_trade("whale1", 2.0);
+        stale.timestamp -= chrono::Duration::seconds(3600);
+        detector
+            .recent_trades
+            .insert("old".to_string(), vec![stale]);
+        detector.signaled.insert("old".to_string());
+        detector.record_trade("new", create_buy_trade("trader1", 0.1));
+
+        detector.prune();
+        assert_eq!(detector.tracked_mints(), 1);
+        assert!(!detector.signaled.contains("old"));
+    }
 }
@@ -11,4 +11,57 @@ pub mod rug_predict;
 
 pub use frontrun::{AccumulationSignal, FrontRunDetector, FrontRunDetectorConfig};
 pub use piggyback::{PiggybackSignal, SniperPiggyback, SniperPiggybackConfig, SniperStat};
-pub use rug_predict::{RugPrediction, RugPredictor, RugPredictorConfig, RugWarningSignal};
+pub use rug_predict::{
+    RugPrediction, RugPredictionContext, RugPredictor, RugPredictorConfig, RugUrgency,
+    RugWarningSignal,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Tactics configuration (`[strategy.tactics]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TacticsConfig {
+    /// Live entries on whale / clustered accumulation
+    #[serde(default = "default_frontrun")]
+    pub frontrun: FrontRunDetectorConfig,
+    /// Live piggyback entries behind proven snipers
+    #[serde(default = "default_piggyback")]
+    pub piggyback: SniperPiggybackConfig,
+    /// Rug prediction on held positions
+    #[serde(default = "default_rug_predictor")]
+    pub rug_predictor: RugPredictorConfig,
+}
+
+/// Accumulation entries are opt-in until shadow outcomes show an edge
+fn default_frontrun() -> FrontRunDetectorConfig {
+    FrontRunDetectorConfig {
+        enabled: false,
+        ..Default::default()
+    }
+}
+
+/// Piggyback entries are opt-in for live trading
+fn default_piggyback() -> SniperPiggybackConfig {
+    SniperPiggybackConfig {
+        enabled: false,
+        ..Default::default()
+    }
+}
+
+/// Rug prediction acts on live positions, so it is opt-in too
+fn default_rug_predictor() -> RugPredictorConfig {
+    RugPredictorConfig {
+        enabled: false,
+        ..Default::default()
+    }
+}
+
+impl Default for TacticsConfig {
+    fn default() -> Self {
+        Self {
+            frontrun: default_frontrun(),
+            piggyback: default_piggyback(),
+            rug_predictor: default_rug_predictor(),
+        }
+    }
+}
@@ -4,7 +4,14 @@
 //! Track sniper performance and identify high-quality signal sources.
 
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Signal confidence for a trusted wallet without local history
+const TRUSTED_CONFIDENCE: f64 = 0.5;
+
+/// Size multiplier bounds derived from a sniper's R-multiple
+const MIN_SIZE_MULTIPLIER: f64 = 0.5;
+const MAX_SIZE_MULTIPLIER: f64 = 1.5;
 
 /// Sniper piggyback configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +23,10 @@ pub struct SniperPiggybackConfig {
     pub copy_size_ratio: f64,
     pub max_tracked_snipers: usize,
     pub trade_history_limit: usize,
+    /// Piggyback positions held at once
+    pub max_concurrent_positions: usize,
+    /// Trailing stop % applied to a piggyback position once its sniper sells
+    pub exit_trailing_pct: f64,
 }
 
 impl Default for SniperPiggybackConfig {
@@ -26,8 +37,10 @@ impl Default for SniperPiggybackConfig {
             min_trades: 5,
             copy_delay_ms: 100,
             copy_size_ratio: 0.5,
-            max_tracked_snipers: 100,
+            max_tracked_snipers: 2000,
             trade_history_limit: 50,
+            max_concurrent_positions: 2,
+            exit_trailing_pct: 5.0,
         }
     }
 }
@@ -161,6 +174,28 @@ impl SniperStat {
     pub fn is_quality_sniper(&self, min_win_rate: f64, min_trades: u32) -> bool {
         self.total_trades >= min_trades && self.win_rate >= min_win_rate
     }
+
+    /// Expected profit per trade in units of the average loss (R)
+    ///
+    /// None until the sniper has a loss to measure risk against.
+    pub fn r_multiple(&self) -> Option<f64> {
+        if self.avg_loss_pct <= 0.0 {
+            return None;
+        }
+        let expectancy =
+            self.win_rate * self.avg_profit_pct - (1.0 - self.win_rate) * self.avg_loss_pct;
+        Some(expectancy / self.avg_loss_pct)
+    }
+
+    /// Entry size multiplier: 1R of expectancy sizes normally
+    pub fn size_multiplier(&self) -> f64 {
+        match self.r_multiple() {
+            Some(r) => r.clamp(MIN_SIZE_MULTIPLIER, MAX_SIZE_MULTIPLIER),
+            // Winners only: no loss to measure against yet
+            None if self.winning_trades > 0 => MAX_SIZE_MULTIPLIER,
+            None => 1.0,
+        }
+    }
 }
 
 /// Piggyback signal
@@ -174,6 +209,10 @@ pub struct PiggybackSignal {
     pub sniper_avg_profit: f64,
     pub confidence: f64,
     pub suggested_delay_ms: u64,
+    /// Sniper's historical R-multiple (None without a recorded loss)
+    pub r_multiple: Option<f64>,
+    /// Multiplier for our entry size
+    pub size_multiplier: f64,
 }
 
 /// Sniper piggyback tracker
@@ -181,7 +220,9 @@ pub struct SniperPiggyback {
     config: SniperPiggybackConfig,
     sniper_stats: HashMap<String, SniperStat>,
     sniper_trades: HashMap<String, VecDeque<SniperTrade>>,
-    quality_snipers: Vec<String>, // Cached list of quality snipers
+    quality_snipers: Vec<String>,      // Cached list of quality snipers
+    trusted: HashSet<String>,          // Vetted elsewhere (discovered trusted wallets)
+    followed: HashMap<String, String>, // Our piggyback positions: mint -> sniper
 }
 
 impl SniperPiggyback {
@@ -192,6 +233,8 @@ impl SniperPiggyback {
             sniper_stats: HashMap::new(),
             sniper_trades: HashMap::new(),
             quality_snipers: Vec::new(),
+            trusted: HashSet::new(),
+            followed: HashMap::new(),
         }
     }
 
@@ -203,10 +246,14 @@ impl SniperPiggyback {
         sol_amount: f64,
         entry_price: f64,
     ) {
-        // Ensure sniper exists
-        self.sniper_stats
-            .entry(sniper.to_string())
-            .or_insert_with(|| SniperStat::new(sniper.to_string()));
+        // Ensure sniper exists, making room by dropping the stalest unproven one
+        if !self.sniper_stats.contains_key(sniper) {
+            if self.sniper_stats.len() >= self.config.max_tracked_snipers && !self.evict_stalest() {
+                return;
+            }
+            self.sniper_stats
+                .insert(sniper.to_string(), SniperStat::new(sniper.to_string()));
+        }
 
         // Record the trade
         let trades = self
@@ -286,10 +333,19 @@ impl SniperPiggyback {
             return None;
         }
 
-        let stats = self.sniper_stats.get(sniper)?;
+        // One piggyback per token, and only so many at once
+        if self.followed.contains_key(mint)
+            || self.followed.len() >= self.config.max_concurrent_positions
+        {
+            return None;
+        }
+
+        let stats = self.sniper_stats.get(sniper);
 
-        // Check if meets quality threshold
-        if !stats.is_quality_sniper(self.config.min_win_rate, self.config.min_trades) {
+        // Check if meets quality threshold (trusted wallets qualify without local history)
+        let quality = stats
+            .is_some_and(|s| s.is_quality_sniper(self.config.min_win_rate, self.config.min_trades));
+        if !quality && !self.trusted.contains(sniper) {
             return None;
         }
 
@@ -301,13 +357,48 @@ impl SniperPiggyback {
             mint: mint.to_string(),
             sniper_size_sol: sol_amount,
             suggested_size_sol: suggested_size,
-            sniper_win_rate: stats.win_rate,
-            sniper_avg_profit: stats.avg_profit_pct,
-            confidence: stats.quality_score,
+            sniper_win_rate: stats.map_or(0.0, |s| s.win_rate),
+            sniper_avg_profit: stats.map_or(0.0, |s| s.avg_profit_pct),
+            confidence: match stats {
+                Some(s) if quality => s.quality_score,
+                _ => TRUSTED_CONFIDENCE,
+            },
             suggested_delay_ms: self.config.copy_delay_ms,
+            r_multiple: stats.and_then(|s| s.r_multiple()),
+            size_multiplier: match stats {
+                Some(s) if quality => s.size_multiplier(),
+                _ => 1.0,
+            },
         })
     }
 
+    /// Record that we entered `mint` behind `sniper`
+    pub fn follow(&mut self, mint: &str, sniper: &str) {
+        self.followed.insert(mint.to_string(), sniper.to_string());
+    }
+
+    /// Number of piggyback positions currently held
+    pub fn followed_count(&self) -> usize {
+        self.followed.len()
+    }
+
+    /// Check a sell against our piggyback positions
+    ///
+    /// Returns true (and stops following) when `sniper` is the wallet we
+    /// entered `mint` behind, so the caller can bias the position to exit.
+    pub fn on_sniper_sell(&mut self, sniper: &str, mint: &str) -> bool {
+        if self.followed.get(mint).is_some_and(|s| s == sniper) {
+            self.followed.remove(mint);
+            return true;
+        }
+        false
+    }
+
+    /// Drop followed mints we no longer hold
+    pub fn retain_followed(&mut self, mut held: impl FnMut(&str) -> bool) {
+        self.followed.retain(|mint, _| held(mint));
+    }
+
     /// Get all quality snipers
     pub fn get_quality_snipers(&self) -> Vec<&SniperStat> {
         self.sniper_stats
@@ -353,6 +444,30 @@ impl SniperPiggyback {
         }
     }
 
+    /// Replace the wallets vetted outside the tracker (e.g. the discovered
+    /// trusted set); they get signals before building local history
+    pub fn set_trusted(&mut self, trusted: HashSet<String>) {
+        self.trusted = trusted;
+    }
+
+    /// Remove the least recently active sniper that isn't a quality sniper
+    ///
+    /// Returns false when every tracked sniper is a quality sniper.
+    fn evict_stalest(&mut self) -> bool {
+        let stalest = self
+            .sniper_stats
+            .values()
+            .filter(|s| !s.is_quality_sniper(self.config.min_win_rate, self.config.min_trades))
+            .min_by_key(|s| s.last_trade_time)
+            .map(|s| s.address.clone());
+        let Some(address) = stalest else {
+            return false;
+        };
+        self.sniper_stats.remove(&address);
+        self.sniper_trades.remove(&address);
+        true
+    }
+
     /// Refresh cached list of quality snipers
     fn refresh_quality_snipers(&mut self) {
         self.quality_snipers = self
@@ -448,6 +563,17 @@ mod tests {
         assert!(signal.is_none());
     }
 
+    #[test]
+    fn test_trusted_without_history() {
+        let mut piggyback = SniperPiggyback::default();
+        assert!(piggyback.on_sniper_buy("found", "mint", 0.2).is_none());
+
+        piggyback.set_trusted(HashSet::from(["found".to_string()]));
+        let signal = piggyback.on_sniper_buy("found", "mint", 0.2).unwrap();
+        assert_eq!(signal.confidence, TRUSTED_CONFIDENCE);
+        assert!((signal.suggested_size_sol - 0.1).abs() < 0.01);
+    }
+
     #[test]
     fn test_top_snipers() {
         let mut piggyback = SniperPiggyback::default();
@@ -476,6 +602,57 @@ mod tests {
         assert_eq!(top[0].address, "sniper0");
     }
 
+    #[test]
+    fn test_size_multiplier() {
+        let mut stat = SniperStat::new("sniper1".to_string());
+        stat.record_trade(50.0, 30, 0.05);
+        assert_eq!(stat.r_multiple(), None);
+        assert_eq!(stat.size_multiplier(), MAX_SIZE_MULTIPLIER);
+
+        // 50% wins of +50% against -50% losses: zero expectancy
+        stat.record_trade(-50.0, 30, -0.05);
+        assert!(stat.r_multiple().unwrap().abs() < 1e-9);
+        assert_eq!(stat.size_multiplier(), MIN_SIZE_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_follow_limits_and_exit() {
+        let config = SniperPiggybackConfig {
+            max_concurrent_positions: 1,
+            ..Default::default()
+        };
+        let mut piggyback = SniperPiggyback::new(config);
+        piggyback.set_trusted(HashSet::from(["sniper1".to_string()]));
+
+        assert!(piggyback.on_sniper_buy("sniper1", "mint1", 0.2).is_some());
+        piggyback.follow("mint1", "sniper1");
+        assert_eq!(piggyback.followed_count(), 1);
+
+        // At the concurrency cap
+        assert!(piggyback.on_sniper_buy("sniper1", "mint2", 0.2).is_none());
+
+        // Only the followed sniper's sell releases the position
+        assert!(!piggyback.on_sniper_sell("other", "mint1"));
+        assert!(piggyback.on_sniper_sell("sniper1", "mint1"));
+        assert_eq!(piggyback.followed_count(), 0);
+        assert!(piggyback.on_sniper_buy("sniper1", "mint2", 0.2).is_some());
+    }
+
+    #[test]
+    fn test_tracked_sniper_cap() {
+        let config = SniperPiggybackConfig {
+            max_tracked_snipers: 2,
+            ..Default::default()
+        };
+        let mut piggyback = SniperPiggyback::new(config);
+        piggyback.record_sniper_buy("a", "mint1", 0.1, 0.001);
+        piggyback.record_sniper_buy("b", "mint1", 0.1, 0.001);
+        piggyback.record_sniper_buy("c", "mint1", 0.1, 0.001);
+
+        assert!(piggyback.get_sniper_stats("c").is_some());
+        assert_eq!(piggyback.sniper_stats.len(), 2);
+    }
+
     #[test]
     fn test_consecutive_tracking() {
         let mut stat = SniperStat::new("sniper1".to_string());
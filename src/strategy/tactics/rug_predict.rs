@@ -14,6 +14,18 @@ pub struct RugPredictorConfig {
     pub liquidity_drain_warning_pct: f64,
     pub insider_sell_threshold: u32,
     pub wash_volume_threshold_pct: f64,
+    /// Probability that tightens the trailing stop and cancels scale-in tranches
+    pub warning_threshold: f64,
+    /// Probability that triggers the kill-switch exit
+    pub critical_threshold: f64,
+    /// Trailing stop % applied at the warning threshold
+    pub warning_trailing_pct: f64,
+    /// Seconds between predictions for each held position
+    pub eval_interval_secs: u64,
+    /// A prediction is a hit when price falls more than this %...
+    pub outcome_drop_pct: f64,
+    /// ...within this many minutes
+    pub outcome_window_mins: u64,
 }
 
 impl Default for RugPredictorConfig {
@@ -24,6 +36,12 @@ impl Default for RugPredictorConfig {
             liquidity_drain_warning_pct: 20.0,
             insider_sell_threshold: 3,
             wash_volume_threshold_pct: 50.0,
+            warning_threshold: 0.5,
+            critical_threshold: 0.8,
+            warning_trailing_pct: 8.0,
+            eval_interval_secs: 10,
+            outcome_drop_pct: 30.0,
+            outcome_window_mins: 10,
         }
     }
 }
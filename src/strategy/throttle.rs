@@ -0,0 +1,591 @@
+//! Time-of-day and launch-rate throttling
+//!
+//! Launch quality varies by hour, and during launch frenzies most of what
+//! comes through is garbage. Two controls sit in front of new-token and
+//! trade-signal entries:
+//!
+//! - Trading windows: UTC hour ranges, each with a size multiplier (0 stops
+//!   entries). Hours outside every window use `outside_size_multiplier`.
+//! - Launch-rate governor: new-token events are counted over the last
+//!   minute. At `surge_launches_per_min` or more, the opportunity and
+//!   strong-buy score thresholds are raised by `threshold_boost` and entries
+//!   are capped at `surge_max_entries_per_min`.
+//!
+//! `snipe start` re-applies `[strategy.throttle]` whenever config.toml
+//! changes (`set_config`); the current state is saved to `throttle.json`
+//! for `snipe status`.
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::filter::scoring::{Recommendation, ScoringResult, ScoringThresholds};
+
+/// Launches and entries are counted over this window
+const RATE_WINDOW_SECS: i64 = 60;
+
+/// Minimum time between saves of the report while only launches change
+const SAVE_INTERVAL_SECS: i64 = 10;
+
+/// Throttling configuration (`[strategy.throttle]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThrottleConfig {
+    /// UTC trading windows, first match wins (empty = trade around the clock)
+    #[serde(default)]
+    pub windows: Vec<TradingWindow>,
+    /// Size multiplier for hours outside every window (0 = no entries)
+    #[serde(default = "default_outside_size_multiplier")]
+    pub outside_size_multiplier: f64,
+    #[serde(default)]
+    pub launch_rate: LaunchRateConfig,
+}
+
+fn default_outside_size_multiplier() -> f64 {
+    1.0
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            windows: Vec::new(),
+            outside_size_multiplier: default_outside_size_multiplier(),
+            launch_rate: LaunchRateConfig::default(),
+        }
+    }
+}
+
+/// UTC hours `start_hour..end_hour` (wrapping past midnight when
+/// `end_hour <= start_hour`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TradingWindow {
+    pub start_hour: u32,
+    pub end_hour: u32,
+    /// Entry size multiplier inside the window (0 = no entries)
+    pub size_multiplier: f64,
+}
+
+impl TradingWindow {
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start_hour < self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+impl std::fmt::Display for TradingWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:02}:00-{:02}:00 UTC x{:.2}",
+            self.start_hour, self.end_hour, self.size_multiplier
+        )
+    }
+}
+
+/// Launch-rate governor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchRateConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// New tokens per minute at which the governor engages
+    #[serde(default = "default_surge_launches_per_min")]
+    pub surge_launches_per_min: u32,
+    /// Added to the opportunity and strong-buy score thresholds during a surge
+    #[serde(default = "default_threshold_boost")]
+    pub threshold_boost: f64,
+    /// Entries per minute outside a surge (0 = unlimited)
+    #[serde(default)]
+    pub max_entries_per_min: u32,
+    /// Entries per minute during a surge (0 = unlimited)
+    #[serde(default = "default_surge_max_entries_per_min")]
+    pub surge_max_entries_per_min: u32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_surge_launches_per_min() -> u32 {
+    50
+}
+
+fn default_threshold_boost() -> f64 {
+    0.10
+}
+
+fn default_surge_max_entries_per_min() -> u32 {
+    2
+}
+
+impl Default for LaunchRateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            surge_launches_per_min: default_surge_launches_per_min(),
+            threshold_boost: default_threshold_boost(),
+            max_entries_per_min: 0,
+            surge_max_entries_per_min: default_surge_max_entries_per_min(),
+        }
+    }
+}
+
+impl ThrottleConfig {
+    /// Window in force at `time` (None = outside every window)
+    pub fn window_at(&self, time: DateTime<Utc>) -> Option<&TradingWindow> {
+        let hour = time.hour();
+        self.windows.iter().find(|w| w.contains(hour))
+    }
+
+    /// Entry size multiplier at `time`
+    pub fn size_multiplier_at(&self, time: DateTime<Utc>) -> f64 {
+        if self.windows.is_empty() {
+            return 1.0;
+        }
+        self.window_at(time)
+            .map_or(self.outside_size_multiplier, |w| w.size_multiplier)
+            .max(0.0)
+    }
+
+    /// Reject windows that can't be applied
+    pub fn validate(&self) -> Result<(), String> {
+        for window in &self.windows {
+            if window.start_hour > 23 || window.end_hour > 24 {
+                return Err(format!("trading window {}: hours must be 0-24", window));
+            }
+            if window.size_multiplier < 0.0 {
+                return Err(format!("trading window {}: negative multiplier", window));
+            }
+        }
+        if self.outside_size_multiplier < 0.0 {
+            return Err("outside_size_multiplier must not be negative".to_string());
+        }
+        if self.launch_rate.threshold_boost < 0.0 {
+            return Err("launch_rate.threshold_boost must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// What `snipe status` reads
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThrottleReport {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub launches_per_min: usize,
+    pub surging: bool,
+    /// Trading window in force (None = outside every window or no schedule)
+    pub window: Option<TradingWindow>,
+    pub size_multiplier: f64,
+    pub threshold_boost: f64,
+    pub entries_last_min: usize,
+    /// Entry cap in force (None = unlimited)
+    pub max_entries_per_min: Option<u32>,
+}
+
+/// Load the report written by a running bot (None if there is none yet)
+pub fn load_throttle_report(
+    path: &std::path::Path,
+) -> crate::error::Result<Option<ThrottleReport>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    let report =
+        serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    Ok(Some(report))
+}
+
+#[derive(Default)]
+struct RateState {
+    launches: VecDeque<DateTime<Utc>>,
+    entries: VecDeque<DateTime<Utc>>,
+    surging: bool,
+    /// Window logged last (None = outside every window)
+    window: Option<TradingWindow>,
+    saved_at: Option<DateTime<Utc>>,
+}
+
+impl RateState {
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::seconds(RATE_WINDOW_SECS);
+        while self.launches.front().is_some_and(|t| *t <= cutoff) {
+            self.launches.pop_front();
+        }
+        while self.entries.front().is_some_and(|t| *t <= cutoff) {
+            self.entries.pop_front();
+        }
+    }
+}
+
+/// Trading windows and launch-rate governor
+pub struct LaunchThrottle {
+    config: RwLock<ThrottleConfig>,
+    state: Mutex<RateState>,
+    /// Where the report is saved (None = memory only)
+    persistence_path: Option<PathBuf>,
+}
+
+impl LaunchThrottle {
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            state: Mutex::new(RateState::default()),
+            persistence_path: None,
+        }
+    }
+
+    /// Save the report at `path` for `snipe status`
+    pub fn with_persistence(mut self, path: PathBuf) -> Self {
+        self.persistence_path = Some(path);
+        self
+    }
+
+    pub fn config(&self) -> ThrottleConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Apply a reloaded config; returns whether anything changed
+    pub fn set_config(&self, config: ThrottleConfig) -> bool {
+        if let Err(e) = config.validate() {
+            warn!("Throttle config not applied: {}", e);
+            return false;
+        }
+        let mut current = self.config.write().unwrap();
+        if *current == config {
+            return false;
+        }
+        info!(
+            "Throttle config reloaded: windows [{}] -> [{}], surge at {}/min -> {}/min",
+            describe_windows(&current.windows),
+            describe_windows(&config.windows),
+            current.launch_rate.surge_launches_per_min,
+            config.launch_rate.surge_launches_per_min
+        );
+        *current = config;
+        drop(current);
+        self.save(Utc::now());
+        true
+    }
+
+    /// Count a new-token event
+    pub fn record_launch(&self, now: DateTime<Utc>) {
+        let config = self.config();
+        let mut state = self.state.lock().unwrap();
+        state.prune(now);
+        state.launches.push_back(now);
+
+        let rate = state.launches.len();
+        let surging = config.launch_rate.enabled
+            && rate >= config.launch_rate.surge_launches_per_min as usize;
+        let mut changed = false;
+        if surging != state.surging {
+            if surging {
+                warn!(
+                    "Launch surge: {} launches/min - thresholds +{:.2}, entries capped at {}/min",
+                    rate,
+                    config.launch_rate.threshold_boost,
+                    config.launch_rate.surge_max_entries_per_min
+                );
+            } else {
+                info!("Launch surge over: {} launches/min", rate);
+            }
+            state.surging = surging;
+            changed = true;
+        }
+        let window = config.window_at(now).copied();
+        if window != state.window {
+            match window {
+                Some(w) => info!("Trading window {} now in force", w),
+                None => info!(
+                    "Outside trading windows: size x{:.2}",
+                    config.size_multiplier_at(now)
+                ),
+            }
+            state.window = window;
+            changed = true;
+        }
+
+        let due = !matches!(
+            state.saved_at,
+            Some(t) if now - t < Duration::seconds(SAVE_INTERVAL_SECS)
+        );
+        drop(state);
+        if changed || due {
+            self.save(now);
+        }
+    }
+
+    /// Count an entry against the per-minute cap
+    pub fn record_entry(&self, now: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        state.prune(now);
+        state.entries.push_back(now);
+        drop(state);
+        self.save(now);
+    }
+
+    pub fn is_surging(&self) -> bool {
+        self.state.lock().unwrap().surging
+    }
+
+    /// Entry size multiplier for the trading window at `now`
+    pub fn size_multiplier(&self, now: DateTime<Utc>) -> f64 {
+        self.config.read().unwrap().size_multiplier_at(now)
+    }
+
+    /// Score threshold boost in force
+    pub fn threshold_boost(&self) -> f64 {
+        let config = self.config.read().unwrap();
+        if self.is_surging() {
+            config.launch_rate.threshold_boost
+        } else {
+            0.0
+        }
+    }
+
+    /// Entry cap in force (None = unlimited)
+    fn entry_cap(&self, config: &ThrottleConfig) -> Option<u32> {
+        let cap = if self.is_surging() {
+            config.launch_rate.surge_max_entries_per_min
+        } else {
+            config.launch_rate.max_entries_per_min
+        };
+        (config.launch_rate.enabled && cap > 0).then_some(cap)
+    }
+
+    /// Why an entry at `now` is not allowed (None = go ahead)
+    pub fn check_entry(&self, now: DateTime<Utc>) -> Option<String> {
+        let config = self.config();
+        if config.size_multiplier_at(now) <= 0.0 {
+            return Some(format!(
+                "trading disabled at {:02}:00 UTC by the trading windows",
+                now.hour()
+            ));
+        }
+        let cap = self.entry_cap(&config)?;
+        let mut state = self.state.lock().unwrap();
+        state.prune(now);
+        if state.entries.len() >= cap as usize {
+            return Some(format!(
+                "entry rate cap {}/min reached{}",
+                cap,
+                if state.surging { " (launch surge)" } else { "" }
+            ));
+        }
+        None
+    }
+
+    /// Re-grade a score with the surge thresholds; returns whether the
+    /// recommendation was lowered
+    pub fn regrade(&self, result: &mut ScoringResult, thresholds: &ScoringThresholds) -> bool {
+        let boost = self.threshold_boost();
+        if boost <= 0.0 {
+            return false;
+        }
+        let before = result.recommendation;
+        let mut after = before;
+        if after == Recommendation::StrongBuy && result.score < thresholds.strong_buy + boost {
+            after = Recommendation::Opportunity;
+        }
+        if after == Recommendation::Opportunity && result.score < thresholds.opportunity + boost {
+            after = Recommendation::Probe;
+        }
+        if after == before {
+            return false;
+        }
+        result.position_size_multiplier = if after == Recommendation::Probe {
+            after.position_multiplier()
+        } else {
+            result.position_size_multiplier * after.position_multiplier()
+                / before.position_multiplier()
+        };
+        result.recommendation = after;
+        result.summary = format!(
+            "{} (launch surge: {:?} -> {:?})",
+            result.summary, before, after
+        );
+        true
+    }
+
+    /// Snapshot for `snipe status`
+    pub fn report(&self, now: DateTime<Utc>) -> ThrottleReport {
+        let config = self.config();
+        let cap = self.entry_cap(&config);
+        let mut state = self.state.lock().unwrap();
+        state.prune(now);
+        ThrottleReport {
+            updated_at: Some(now),
+            launches_per_min: state.launches.len(),
+            surging: state.surging,
+            window: config.window_at(now).copied(),
+            size_multiplier: config.size_multiplier_at(now),
+            threshold_boost: if state.surging {
+                config.launch_rate.threshold_boost
+            } else {
+                0.0
+            },
+            entries_last_min: state.entries.len(),
+            max_entries_per_min: cap,
+        }
+    }
+
+    /// Save the report (failures are logged and never block trading)
+    fn save(&self, now: DateTime<Utc>) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        let report = self.report(now);
+        self.state.lock().unwrap().saved_at = Some(now);
+        let result = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!(
+                "Failed to save throttle report to {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+fn describe_windows(windows: &[TradingWindow]) -> String {
+    windows
+        .iter()
+        .map(|w| w.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, secs: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, hour, 0, 0).unwrap() + Duration::seconds(secs)
+    }
+
+    #[test]
+    fn test_trading_windows() {
+        let config = ThrottleConfig {
+            windows: vec![
+                TradingWindow {
+                    start_hour: 13,
+                    end_hour: 21,
+                    size_multiplier: 1.0,
+                },
+                TradingWindow {
+                    start_hour: 22,
+                    end_hour: 4,
+                    size_multiplier: 0.0,
+                },
+            ],
+            outside_size_multiplier: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(config.size_multiplier_at(at(14, 0)), 1.0);
+        assert_eq!(config.size_multiplier_at(at(23, 0)), 0.0);
+        assert_eq!(config.size_multiplier_at(at(2, 0)), 0.0);
+        assert_eq!(config.size_multiplier_at(at(8, 0)), 0.5);
+        assert_eq!(ThrottleConfig::default().size_multiplier_at(at(3, 0)), 1.0);
+
+        let throttle = LaunchThrottle::new(config);
+        assert!(throttle
+            .check_entry(at(23, 0))
+            .unwrap()
+            .contains("disabled"));
+        assert!(throttle.check_entry(at(14, 0)).is_none());
+    }
+
+    #[test]
+    fn test_launch_surge() {
+        let throttle = LaunchThrottle::new(ThrottleConfig {
+            launch_rate: LaunchRateConfig {
+                surge_launches_per_min: 5,
+                surge_max_entries_per_min: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        for i in 0..5 {
+            throttle.record_launch(at(12, i));
+        }
+        assert!(throttle.is_surging());
+        assert_eq!(throttle.threshold_boost(), 0.10);
+
+        throttle.record_entry(at(12, 5));
+        assert!(throttle.check_entry(at(12, 6)).unwrap().contains("surge"));
+
+        // A quiet minute later the surge is over and the cap lifted
+        throttle.record_launch(at(12, 90));
+        assert!(!throttle.is_surging());
+        assert!(throttle.check_entry(at(12, 91)).is_none());
+        assert_eq!(throttle.report(at(12, 91)).launches_per_min, 1);
+    }
+
+    #[test]
+    fn test_regrade_during_surge() {
+        let throttle = LaunchThrottle::new(ThrottleConfig {
+            launch_rate: LaunchRateConfig {
+                surge_launches_per_min: 1,
+                threshold_boost: 0.10,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let thresholds = ScoringThresholds::default();
+        let mut result = ScoringResult {
+            score: thresholds.strong_buy + 0.05,
+            recommendation: Recommendation::StrongBuy,
+            position_size_multiplier: 1.5,
+            ..Default::default()
+        };
+
+        // No surge, no change
+        assert!(!throttle.regrade(&mut result, &thresholds));
+
+        throttle.record_launch(at(12, 0));
+        assert!(throttle.regrade(&mut result, &thresholds));
+        assert_eq!(result.recommendation, Recommendation::Opportunity);
+        assert!((result.position_size_multiplier - 1.0).abs() < 1e-9);
+
+        let mut weak = ScoringResult {
+            score: thresholds.opportunity + 0.01,
+            recommendation: Recommendation::Opportunity,
+            position_size_multiplier: 1.0,
+            ..Default::default()
+        };
+        assert!(throttle.regrade(&mut weak, &thresholds));
+        assert_eq!(weak.recommendation, Recommendation::Probe);
+        assert_eq!(weak.position_size_multiplier, 0.5);
+    }
+
+    #[test]
+    fn test_reload() {
+        let throttle = LaunchThrottle::new(ThrottleConfig::default());
+        let mut config = ThrottleConfig::default();
+        assert!(!throttle.set_config(config.clone()));
+
+        config.outside_size_multiplier = 0.0;
+        config.windows.push(TradingWindow {
+            start_hour: 12,
+            end_hour: 18,
+            size_multiplier: 0.8,
+        });
+        assert!(throttle.set_config(config.clone()));
+        assert_eq!(throttle.size_multiplier(at(13, 0)), 0.8);
+        assert_eq!(throttle.size_multiplier(at(20, 0)), 0.0);
+
+        // Invalid configs are not applied
+        config.windows[0].start_hour = 30;
+        assert!(!throttle.set_config(config));
+        assert_eq!(throttle.size_multiplier(at(13, 0)), 0.8);
+    }
+}
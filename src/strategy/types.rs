@@ -99,6 +99,46 @@ impl TokenRegime {
             TokenRegime::Unknown { .. } => 0.5,
         }
     }
+
+    /// The regime without its metrics
+    pub fn kind(&self) -> RegimeKind {
+        match self {
+            TokenRegime::OrganicPump { .. } => RegimeKind::OrganicPump,
+            TokenRegime::SniperFlip { .. } => RegimeKind::SniperFlip,
+            TokenRegime::WashTrade { .. } => RegimeKind::WashTrade,
+            TokenRegime::DeployerBleed { .. } => RegimeKind::DeployerBleed,
+            TokenRegime::Unknown { .. } => RegimeKind::Unknown,
+        }
+    }
+}
+
+/// Token regime without its metrics (config keys, recorded on positions)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegimeKind {
+    OrganicPump,
+    SniperFlip,
+    WashTrade,
+    DeployerBleed,
+    Unknown,
+}
+
+impl RegimeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegimeKind::OrganicPump => "organic_pump",
+            RegimeKind::SniperFlip => "sniper_flip",
+            RegimeKind::WashTrade => "wash_trade",
+            RegimeKind::DeployerBleed => "deployer_bleed",
+            RegimeKind::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for RegimeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Exit style types
@@ -248,10 +288,16 @@ pub enum DecisionSource {
     FatalRisk,
     ChainHealth,
     PortfolioRisk,
+    /// Kill-switch (liquidity drain, deployer / top holder sells)
+    KillSwitch,
     RugPredictor,
     ExitManager,
     Strategy,
     RegimeOptimization,
+    /// Entry tactics (piggyback, accumulation)
+    Tactics,
+    /// Adaptive filter recommendation
+    Filter,
 }
 
 impl DecisionSource {
@@ -261,14 +307,148 @@ impl DecisionSource {
             DecisionSource::FatalRisk => 0,
             DecisionSource::ChainHealth => 1,
             DecisionSource::PortfolioRisk => 2,
-            DecisionSource::RugPredictor => 3,
-            DecisionSource::ExitManager => 4,
-            DecisionSource::Strategy => 5,
-            DecisionSource::RegimeOptimization => 6,
+            DecisionSource::KillSwitch => 3,
+            DecisionSource::RugPredictor => 4,
+            DecisionSource::ExitManager => 5,
+            DecisionSource::Strategy => 6,
+            DecisionSource::RegimeOptimization => 7,
+            DecisionSource::Tactics => 8,
+            DecisionSource::Filter => 9,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionSource::FatalRisk => "fatal_risk",
+            DecisionSource::ChainHealth => "chain_health",
+            DecisionSource::PortfolioRisk => "portfolio_risk",
+            DecisionSource::KillSwitch => "kill_switch",
+            DecisionSource::RugPredictor => "rug_predictor",
+            DecisionSource::ExitManager => "exit_manager",
+            DecisionSource::Strategy => "strategy",
+            DecisionSource::RegimeOptimization => "regime",
+            DecisionSource::Tactics => "tactics",
+            DecisionSource::Filter => "filter",
         }
     }
 }
 
+impl std::fmt::Display for DecisionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a component wanted done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposedAction {
+    Enter,
+    Exit,
+    Hold,
+    Skip,
+}
+
+impl ProposedAction {
+    /// Enter or exit (as opposed to leaving things as they are)
+    pub fn is_trade(&self) -> bool {
+        matches!(self, ProposedAction::Enter | ProposedAction::Exit)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposedAction::Enter => "enter",
+            ProposedAction::Exit => "exit",
+            ProposedAction::Hold => "hold",
+            ProposedAction::Skip => "skip",
+        }
+    }
+}
+
+impl From<&TradingAction> for ProposedAction {
+    fn from(action: &TradingAction) -> Self {
+        match action {
+            TradingAction::Enter { .. } => ProposedAction::Enter,
+            TradingAction::Exit { .. } => ProposedAction::Exit,
+            TradingAction::Hold => ProposedAction::Hold,
+            TradingAction::Skip { .. }
+            | TradingAction::FatalReject { .. }
+            | TradingAction::Pause { .. } => ProposedAction::Skip,
+        }
+    }
+}
+
+impl std::fmt::Display for ProposedAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One component's input to an arbitration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proposal {
+    pub source: DecisionSource,
+    pub action: ProposedAction,
+    pub confidence: f64,
+    pub reason: String,
+}
+
+impl Proposal {
+    pub fn new(
+        source: DecisionSource,
+        action: ProposedAction,
+        confidence: f64,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            source,
+            action,
+            confidence,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Every proposal behind a decision and the rule that settled it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArbitrationRecord {
+    pub proposals: Vec<Proposal>,
+    /// Component whose proposal was carried out
+    pub winner: DecisionSource,
+    /// What was carried out
+    pub action: ProposedAction,
+    /// Arbitration rule that picked the winner (e.g. "portfolio_block")
+    pub rule: String,
+}
+
+impl ArbitrationRecord {
+    /// Components disagreed on whether to trade
+    pub fn is_conflict(&self) -> bool {
+        let trades = self
+            .proposals
+            .iter()
+            .filter(|p| p.action.is_trade())
+            .count();
+        trades > 0 && trades < self.proposals.len()
+    }
+
+    /// A later check took the decision out of the arbitrator's hands
+    pub fn overruled(mut self, proposal: Proposal, rule: &str) -> Self {
+        self.winner = proposal.source;
+        self.action = proposal.action;
+        self.rule = rule.to_string();
+        self.proposals.push(proposal);
+        self
+    }
+
+    /// Proposals that lost (wanted to trade when nothing was done, or the reverse)
+    pub fn overridden(&self) -> impl Iterator<Item = &Proposal> {
+        self.proposals
+            .iter()
+            .filter(move |p| p.action.is_trade() != self.action.is_trade())
+    }
+}
+
 /// Arbitrated decision with audit trail
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitratedDecision {
@@ -277,6 +457,16 @@ pub struct ArbitratedDecision {
     /// (source, original_action_description, override_reason)
     pub overridden: Vec<(DecisionSource, String, String)>,
     pub confidence: f64,
+    /// Proposals and winning rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arbitration: Option<ArbitrationRecord>,
+}
+
+impl ArbitratedDecision {
+    /// The arbitration record, when components disagreed
+    pub fn conflict(&self) -> Option<&ArbitrationRecord> {
+        self.arbitration.as_ref().filter(|a| a.is_conflict())
+    }
 }
 
 /// Trend direction
@@ -323,8 +513,8 @@ impl Trend {
     }
 }
 
-/// Congestion level for chain health
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Congestion level for chain health (ordered from best to worst)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CongestionLevel {
     /// Green light - proceed normally
@@ -375,6 +565,12 @@ pub struct Position {
     pub highest_price: f64,
     pub lowest_price: f64,
     pub exit_levels_hit: Vec<f64>,
+    /// Token creator (for the portfolio creator limit)
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// Creator's funder (for the portfolio funding-cluster limit)
+    #[serde(default)]
+    pub funding_cluster: Option<String>,
 }
 
 impl Position {
@@ -431,6 +627,9 @@ pub struct ExecutionRecord {
     pub success: bool,
     pub failure_reason: Option<String>,
     pub tx_signature: Option<String>,
+    /// Buy slippage estimate with the realized slippage (None = not estimated)
+    #[serde(default)]
+    pub slippage_estimate: Option<super::slippage::SlippageEstimate>,
 }
 
 impl ExecutionRecord {
@@ -464,6 +663,7 @@ impl ExecutionRecord {
             success: true,
             failure_reason: None,
             tx_signature: Some(tx_signature),
+            slippage_estimate: None,
         }
     }
 
@@ -489,6 +689,7 @@ impl ExecutionRecord {
             success: false,
             failure_reason: Some(reason),
             tx_signature: None,
+            slippage_estimate: None,
         }
     }
 }
@@ -626,6 +827,39 @@ mod tests {
     fn test_decision_source_priority() {
         assert!(DecisionSource::FatalRisk.priority() < DecisionSource::PortfolioRisk.priority());
         assert!(DecisionSource::PortfolioRisk.priority() < DecisionSource::Strategy.priority());
+        assert!(DecisionSource::KillSwitch.priority() < DecisionSource::Strategy.priority());
+        assert!(DecisionSource::Strategy.priority() < DecisionSource::Tactics.priority());
+        assert!(DecisionSource::Tactics.priority() < DecisionSource::Filter.priority());
+    }
+
+    #[test]
+    fn test_arbitration_conflict() {
+        let mut record = ArbitrationRecord {
+            proposals: vec![
+                Proposal::new(
+                    DecisionSource::Tactics,
+                    ProposedAction::Enter,
+                    0.7,
+                    "piggyback",
+                ),
+                Proposal::new(
+                    DecisionSource::PortfolioRisk,
+                    ProposedAction::Skip,
+                    1.0,
+                    "max positions",
+                ),
+            ],
+            winner: DecisionSource::PortfolioRisk,
+            action: ProposedAction::Skip,
+            rule: "portfolio_block".to_string(),
+        };
+        assert!(record.is_conflict());
+        let overridden: Vec<_> = record.overridden().map(|p| p.source).collect();
+        assert_eq!(overridden, vec![DecisionSource::Tactics]);
+
+        // Skip and hold both leave things as they are
+        record.proposals[0].action = ProposedAction::Hold;
+        assert!(!record.is_conflict());
     }
 
     #[test]
@@ -650,6 +884,8 @@ mod tests {
             highest_price: 0.001,
             lowest_price: 0.001,
             exit_levels_hit: vec![],
+            creator: None,
+            funding_cluster: None,
         };
 
         // 50% gain
@@ -0,0 +1,158 @@
+//! New-token deduplication
+//!
+//! PumpPortal re-delivers token creations after a reconnect, and several
+//! sources can report the same launch. Each mint is evaluated once: later
+//! deliveries within the TTL are suppressed and counted. Deliveries shortly
+//! after a reconnect are replays (logged at debug); anything else is an
+//! unexpected duplicate.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a mint is remembered
+pub const DEFAULT_DEDUP_TTL: Duration = Duration::from_secs(600);
+
+/// Mints remembered at most (oldest forgotten first)
+pub const DEFAULT_DEDUP_CAPACITY: usize = 20_000;
+
+/// Duplicates this soon after a reconnect are replays
+const REPLAY_WINDOW: Duration = Duration::from_secs(60);
+
+/// What a new-token delivery is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// First delivery of the mint: evaluate it
+    First,
+    /// Re-delivered after a reconnect
+    Replay,
+    /// Delivered again for another reason
+    Duplicate,
+}
+
+#[derive(Default)]
+struct Seen {
+    at: HashMap<String, Instant>,
+    order: VecDeque<(String, Instant)>,
+}
+
+impl Seen {
+    fn forget_oldest(&mut self) {
+        if let Some((mint, at)) = self.order.pop_front() {
+            if self.at.get(&mint) == Some(&at) {
+                self.at.remove(&mint);
+            }
+        }
+    }
+}
+
+/// Mints seen recently (bounded, with a TTL)
+pub struct NewTokenDedup {
+    ttl: Duration,
+    capacity: usize,
+    seen: Mutex<Seen>,
+    reconnected_at: Mutex<Option<Instant>>,
+    suppressed: AtomicU64,
+}
+
+impl NewTokenDedup {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            seen: Mutex::new(Seen::default()),
+            reconnected_at: Mutex::new(None),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a delivery of `mint`
+    pub fn check(&self, mint: &str, now: Instant) -> Delivery {
+        let mut guard = self.seen.lock().expect("dedup poisoned");
+        let seen = &mut *guard;
+        while let Some((_, at)) = seen.order.front() {
+            if now.duration_since(*at) < self.ttl {
+                break;
+            }
+            seen.forget_oldest();
+        }
+
+        if seen.at.contains_key(mint) {
+            drop(guard);
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            let reconnected_at = *self.reconnected_at.lock().expect("dedup poisoned");
+            return match reconnected_at {
+                Some(at) if now.duration_since(at) < REPLAY_WINDOW => Delivery::Replay,
+                _ => Delivery::Duplicate,
+            };
+        }
+        seen.at.insert(mint.to_string(), now);
+        seen.order.push_back((mint.to_string(), now));
+        while seen.order.len() > self.capacity {
+            seen.forget_oldest();
+        }
+        Delivery::First
+    }
+
+    /// The source reconnected (replays may follow)
+    pub fn reconnected(&self, now: Instant) {
+        *self.reconnected_at.lock().expect("dedup poisoned") = Some(now);
+    }
+
+    /// Deliveries suppressed so far
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for NewTokenDedup {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEDUP_TTL, DEFAULT_DEDUP_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates_suppressed_until_ttl() {
+        let dedup = NewTokenDedup::new(Duration::from_secs(600), 100);
+        let start = Instant::now();
+        assert_eq!(dedup.check("mint", start), Delivery::First);
+        assert_eq!(dedup.check("other", start), Delivery::First);
+        assert_eq!(
+            dedup.check("mint", start + Duration::from_secs(5)),
+            Delivery::Duplicate
+        );
+
+        dedup.reconnected(start + Duration::from_secs(10));
+        assert_eq!(
+            dedup.check("mint", start + Duration::from_secs(20)),
+            Delivery::Replay
+        );
+        assert_eq!(
+            dedup.check("mint", start + Duration::from_secs(200)),
+            Delivery::Duplicate
+        );
+        assert_eq!(dedup.suppressed(), 3);
+
+        // Forgotten after the TTL
+        assert_eq!(
+            dedup.check("mint", start + Duration::from_secs(601)),
+            Delivery::First
+        );
+    }
+
+    #[test]
+    fn test_capacity_forgets_oldest() {
+        let dedup = NewTokenDedup::new(Duration::from_secs(600), 2);
+        let now = Instant::now();
+        assert_eq!(dedup.check("a", now), Delivery::First);
+        assert_eq!(dedup.check("b", now), Delivery::First);
+        assert_eq!(dedup.check("c", now), Delivery::First);
+        assert_eq!(dedup.check("a", now), Delivery::First);
+        assert_eq!(dedup.check("c", now), Delivery::Duplicate);
+    }
+}
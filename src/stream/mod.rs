@@ -6,12 +6,14 @@
 
 pub mod backpressure;
 pub mod decoder;
+pub mod dedup;
 pub mod pumpportal;
 
 #[cfg(feature = "shredstream")]
 pub mod shredstream;
 
 pub use backpressure::{BackpressureChannel, DropPolicy};
+pub use dedup::{Delivery, NewTokenDedup};
 pub use pumpportal::{PumpPortalClient, PumpPortalConfig, PumpPortalEvent};
 
 #[cfg(feature = "shredstream")]
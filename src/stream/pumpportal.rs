@@ -10,6 +10,7 @@ use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, Mutex};
@@ -18,6 +19,7 @@ use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
 use crate::error::{Error, Result};
+use crate::pump::program::INITIAL_VIRTUAL_TOKEN_RESERVES;
 
 /// Command to send to the websocket for dynamic subscriptions
 #[derive(Debug, Clone)]
@@ -31,6 +33,17 @@ pub enum SubscriptionCommand {
 /// PumpPortal WebSocket URL
 pub const PUMPPORTAL_WS_URL: &str = "wss://pumpportal.fun/api/data";
 
+/// Largest plausible virtual SOL reserve (curves graduate far below this)
+const MAX_VIRTUAL_SOL: f64 = 1_000.0;
+
+/// Malformed events rejected since startup
+static REJECTED_EVENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of malformed events rejected so far
+pub fn rejected_event_count() -> u64 {
+    REJECTED_EVENTS.load(Ordering::Relaxed)
+}
+
 /// Subscription methods
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -99,7 +112,7 @@ impl SubscriptionMessage {
 }
 
 /// New token event from PumpPortal
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewTokenEvent {
     pub signature: String,
@@ -116,8 +129,25 @@ pub struct NewTokenEvent {
     pub uri: String,
 }
 
+impl NewTokenEvent {
+    /// Check the invariants every genuine creation event satisfies
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        check_pubkey("mint", &self.mint)?;
+        check_pubkey("creator", &self.trader_public_key)?;
+        check_pubkey("bonding curve", &self.bonding_curve_key)?;
+        if self.name.trim().is_empty() || self.symbol.trim().is_empty() {
+            return Err("empty name or symbol".to_string());
+        }
+        check_reserves(
+            self.v_sol_in_bonding_curve as f64,
+            self.v_tokens_in_bonding_curve as f64,
+            self.market_cap_sol,
+        )
+    }
+}
+
 /// Trade event from PumpPortal
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeEvent {
     pub signature: String,
@@ -132,6 +162,88 @@ pub struct TradeEvent {
     pub market_cap_sol: f64,
 }
 
+impl TradeEvent {
+    /// Check the invariants every genuine trade event satisfies
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        check_pubkey("mint", &self.mint)?;
+        check_pubkey("trader", &self.trader_public_key)?;
+        if self.tx_type != "buy" && self.tx_type != "sell" {
+            return Err(format!("unknown txType '{}'", self.tx_type));
+        }
+        if !(self.sol_amount.is_finite() && self.sol_amount >= 0.0)
+            || !(self.token_amount.is_finite() && self.token_amount >= 0.0)
+        {
+            return Err(format!(
+                "invalid amounts: {} SOL / {} tokens",
+                self.sol_amount, self.token_amount
+            ));
+        }
+        check_reserves(
+            self.v_sol_in_bonding_curve,
+            self.v_tokens_in_bonding_curve,
+            self.market_cap_sol,
+        )
+    }
+}
+
+fn check_pubkey(field: &str, value: &str) -> std::result::Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("empty {}", field));
+    }
+    Pubkey::from_str(value)
+        .map(|_| ())
+        .map_err(|_| format!("{} '{}' is not a valid pubkey", field, value))
+}
+
+/// Reserves and market cap must be positive and fit a pump.fun curve
+///
+/// Reserves arrive as SOL / whole tokens or lamports / raw units.
+fn check_reserves(
+    v_sol: f64,
+    v_tokens: f64,
+    market_cap_sol: f64,
+) -> std::result::Result<(), String> {
+    let values = [v_sol, v_tokens, market_cap_sol];
+    if values.iter().any(|v| !v.is_finite() || *v <= 0.0) {
+        return Err(format!(
+            "non-positive reserves or market cap: vSol={} vTokens={} marketCap={}",
+            v_sol, v_tokens, market_cap_sol
+        ));
+    }
+    let sol = if v_sol < MAX_VIRTUAL_SOL {
+        v_sol
+    } else {
+        v_sol / 1e9
+    };
+    let raw_tokens = if v_tokens < 1e10 {
+        v_tokens * 1e6
+    } else {
+        v_tokens
+    };
+    if sol > MAX_VIRTUAL_SOL || raw_tokens > INITIAL_VIRTUAL_TOKEN_RESERVES as f64 {
+        return Err(format!(
+            "reserves out of range: vSol={} vTokens={}",
+            v_sol, v_tokens
+        ));
+    }
+    Ok(())
+}
+
+/// Count a rejected event and turn it into an `Error` for the consumer
+fn reject(kind: &str, reason: String, text: &str) -> PumpPortalEvent {
+    let count = REJECTED_EVENTS.fetch_add(1, Ordering::Relaxed) + 1;
+    debug!(
+        "Rejected malformed {} event: {} - JSON: {}",
+        kind,
+        reason,
+        &text[..text.len().min(300)]
+    );
+    PumpPortalEvent::Error(format!(
+        "Rejected malformed {} event ({} so far): {}",
+        kind, count, reason
+    ))
+}
+
 /// Event from PumpPortal WebSocket
 #[derive(Debug, Clone)]
 pub enum PumpPortalEvent {
@@ -442,52 +554,72 @@ impl PumpPortalClient {
         // Log first 200 chars of incoming message for debugging
         debug!("Incoming message: {}", &text[..text.len().min(200)]);
 
-        // Try parsing as new token event
-        if let Ok(token_event) = serde_json::from_str::<NewTokenEvent>(text) {
-            if token_event.tx_type == "create" {
-                debug!(
-                    "New token: {} ({}) - {}",
-                    token_event.name, token_event.symbol, token_event.mint
-                );
-                event_tx
-                    .send(PumpPortalEvent::NewToken(token_event))
-                    .await
-                    .map_err(|e| Error::Internal(e.to_string()))?;
-                return Ok(());
+        match parse_message(text) {
+            Some(event) => event_tx
+                .send(event)
+                .await
+                .map_err(|e| Error::Internal(e.to_string())),
+            None => {
+                // Unknown message format
+                debug!("Unknown message: {}", &text[..text.len().min(100)]);
+                Ok(())
             }
         }
+    }
+}
 
-        // Try parsing as trade event
-        match serde_json::from_str::<TradeEvent>(text) {
-            Ok(trade_event) => {
-                info!(
-                    "Trade parsed: {} {} {} tokens for {} SOL",
-                    trade_event.tx_type,
-                    trade_event.token_amount,
-                    trade_event.mint,
-                    trade_event.sol_amount
-                );
-                event_tx
-                    .send(PumpPortalEvent::Trade(trade_event))
-                    .await
-                    .map_err(|e| Error::Internal(e.to_string()))?;
-                return Ok(());
-            }
-            Err(e) => {
-                // Only log if it looks like a trade event
-                if text.contains("\"txType\":\"buy\"") || text.contains("\"txType\":\"sell\"") {
-                    warn!(
-                        "Failed to parse trade event: {} - JSON: {}",
-                        e,
-                        &text[..text.len().min(500)]
+/// Parse one PumpPortal message
+///
+/// Events that fail validation come back as a counted `PumpPortalEvent::Error`
+/// so malformed data never reaches the filters. Subscription acks and other
+/// unknown messages return None.
+pub fn parse_message(text: &str) -> Option<PumpPortalEvent> {
+    // Try parsing as new token event
+    match serde_json::from_str::<NewTokenEvent>(text) {
+        Ok(token_event) if token_event.tx_type == "create" => {
+            return Some(match token_event.validate() {
+                Ok(()) => {
+                    debug!(
+                        "New token: {} ({}) - {}",
+                        token_event.name, token_event.symbol, token_event.mint
                     );
+                    PumpPortalEvent::NewToken(token_event)
                 }
-            }
+                Err(reason) => reject("new token", reason, text),
+            });
+        }
+        Err(e) if text.contains("\"txType\":\"create\"") => {
+            return Some(reject("new token", e.to_string(), text));
         }
+        _ => {}
+    }
 
-        // Unknown message format
-        debug!("Unknown message: {}", &text[..text.len().min(100)]);
-        Ok(())
+    // Try parsing as trade event
+    match serde_json::from_str::<TradeEvent>(text) {
+        Ok(trade_event) => {
+            if let Err(reason) = trade_event.validate() {
+                return Some(reject("trade", reason, text));
+            }
+            info!(
+                "Trade parsed: {} {} {} tokens for {} SOL",
+                trade_event.tx_type,
+                trade_event.token_amount,
+                trade_event.mint,
+                trade_event.sol_amount
+            );
+            Some(PumpPortalEvent::Trade(trade_event))
+        }
+        Err(e) => {
+            // Only log if it looks like a trade event
+            if text.contains("\"txType\":\"buy\"") || text.contains("\"txType\":\"sell\"") {
+                warn!(
+                    "Failed to parse trade event: {} - JSON: {}",
+                    e,
+                    &text[..text.len().min(500)]
+                );
+            }
+            None
+        }
     }
 }
 
@@ -569,4 +701,95 @@ mod tests {
         assert_eq!(event.symbol, "TEST");
         assert_eq!(event.tx_type, "create");
     }
+
+    /// A creation event as PumpPortal sends it, with one field overridden
+    fn create_payload(field: &str, value: serde_json::Value) -> String {
+        let mut json = serde_json::json!({
+            "signature": "5Kd3NBUAdUnU5bZ2uSzPYbKvHqZnxZ7s8VvS1yRkz1nKx5JdC4vbd6N7vGQ2Tj4Hk3nqxzKh1sZz6gW4dV6vPq9x",
+            "mint": "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK",
+            "traderPublicKey": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "txType": "create",
+            "initialBuy": 51_000_000,
+            "bondingCurveKey": "So11111111111111111111111111111111111111112",
+            "vTokensInBondingCurve": 1_021_000_000u64,
+            "vSolInBondingCurve": 31,
+            "marketCapSol": 30.4,
+            "name": "Moon Cat",
+            "symbol": "MCAT",
+            "uri": "https://ipfs.io/ipfs/QmTest"
+        });
+        json[field] = value;
+        json.to_string()
+    }
+
+    fn rejection(text: &str) -> Option<String> {
+        match parse_message(text) {
+            Some(PumpPortalEvent::Error(e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_valid_create_passes() {
+        let text = create_payload("name", "Moon Cat".into());
+        assert!(matches!(
+            parse_message(&text),
+            Some(PumpPortalEvent::NewToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_malformed_create_rejected() {
+        let before = rejected_event_count();
+        let cases = [
+            create_payload("mint", "".into()),
+            create_payload("traderPublicKey", "0OIl-not-base58".into()),
+            create_payload("bondingCurveKey", "curve123".into()),
+            create_payload("name", "   ".into()),
+            create_payload("marketCapSol", (-12.5).into()),
+            create_payload("marketCapSol", 0.into()),
+            create_payload("vSolInBondingCurve", 0.into()),
+            create_payload("vTokensInBondingCurve", 9_000_000_000_000_000u64.into()),
+            // Negative reserves don't even deserialize into u64
+            create_payload("vSolInBondingCurve", (-30).into()),
+        ];
+        for text in &cases {
+            assert!(rejection(text).is_some(), "accepted: {}", text);
+        }
+        assert!(rejected_event_count() >= before + cases.len() as u64);
+
+        let reason = rejection(&cases[1]).unwrap();
+        assert!(reason.contains("creator"), "{}", reason);
+    }
+
+    #[test]
+    fn test_malformed_trade_rejected() {
+        let trade = serde_json::json!({
+            "signature": "sig",
+            "mint": "DYw8jCTfwHNRJhhmFcbXvVDTqWMEVFBX6ZKUmG5CNSKK",
+            "traderPublicKey": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+            "txType": "buy",
+            "tokenAmount": 1_500_000.5,
+            "solAmount": 0.05,
+            "bondingCurveKey": "So11111111111111111111111111111111111111112",
+            "vTokensInBondingCurve": 1_019_000_000.25,
+            "vSolInBondingCurve": 31.6,
+            "marketCapSol": 31.0
+        });
+        assert!(matches!(
+            parse_message(&trade.to_string()),
+            Some(PumpPortalEvent::Trade(_))
+        ));
+
+        let mut bad_mint = trade.clone();
+        bad_mint["mint"] = "".into();
+        assert!(rejection(&bad_mint.to_string()).is_some());
+
+        let mut bad_cap = trade;
+        bad_cap["marketCapSol"] = (-1.0).into();
+        assert!(rejection(&bad_cap.to_string()).is_some());
+
+        // Subscription acks are not events
+        assert!(parse_message(r#"{"message":"Successfully subscribed to keys."}"#).is_none());
+    }
 }
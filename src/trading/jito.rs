@@ -483,31 +483,77 @@ impl JitoClient {
 
     /// Get recommended tip amount from Jito
     pub async fn get_recommended_tip(&self) -> Result<u64> {
-        // Try to fetch from tip floor API
-        let url = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
-
-        match self.http_client.get(url).send().await {
-            Ok(response) => {
-                if let Ok(floors) = response.json::<Vec<serde_json::Value>>().await {
-                    // Get the percentile we want
-                    let percentile_key =
-                        format!("landed_tips_{}_percentile", self.config.tip_percentile);
-
-                    if let Some(floor) = floors.first() {
-                        if let Some(tip) = floor.get(&percentile_key).and_then(|v| v.as_f64()) {
-                            let tip_lamports = (tip * 1e9) as u64;
-                            return Ok(self.clamp_tip(tip_lamports));
-                        }
-                    }
-                }
-            }
+        match self.tip_floor().await {
+            Ok(tip_lamports) => Ok(self.clamp_tip(tip_lamports)),
             Err(e) => {
                 debug!("Failed to fetch tip floor: {}", e);
+                // Fallback to configured minimum
+                Ok(self.config.min_tip_lamports)
             }
         }
+    }
+
+    /// Landed tips at the configured percentile, in lamports (not clamped)
+    pub async fn tip_floor(&self) -> Result<u64> {
+        let url = "https://bundles.jito.wtf/api/v1/bundles/tip_floor";
+
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| Error::Rpc(format!("Tip floor request failed: {}", e)))?;
+        let floors: Vec<serde_json::Value> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse tip floor: {}", e)))?;
+
+        // Fields are named like `landed_tips_50th_percentile`, in SOL
+        let percentile_key = format!("landed_tips_{}th_percentile", self.config.tip_percentile);
+        floors
+            .first()
+            .and_then(|floor| floor.get(&percentile_key))
+            .and_then(|v| v.as_f64())
+            .map(|tip| (tip * 1e9) as u64)
+            .ok_or_else(|| Error::Rpc(format!("Tip floor has no {}", percentile_key)))
+    }
+
+    /// Time a cheap block engine call (`getTipAccounts`), in ms
+    pub async fn ping(&self) -> Result<u64> {
+        let url = format!("{}/api/v1/bundles", self.config.block_engine_url);
 
-        // Fallback to configured minimum
-        Ok(self.config.min_tip_lamports)
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method: "getTipAccounts",
+            params: Vec::<String>::new(),
+        };
+
+        let start = std::time::Instant::now();
+        let response = self
+            .http_client
+            .post(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| Error::Rpc(format!("Block engine unreachable: {}", e)))?;
+        let latency = start.elapsed().as_millis() as u64;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Rpc(format!("Block engine returned {}", status)));
+        }
+        let body: JsonRpcResponse<Vec<String>> = response
+            .json()
+            .await
+            .map_err(|e| Error::Serialization(format!("Failed to parse response: {}", e)))?;
+        if let Some(error) = body.error {
+            return Err(Error::Rpc(format!(
+                "Block engine error {}: {}",
+                error.code, error.message
+            )));
+        }
+        Ok(latency)
     }
 
     /// Clamp tip to configured bounds
@@ -0,0 +1,156 @@
+//! Jupiter swap API client
+//!
+//! Once a bonding curve completes, the token trades in its migrated AMM pool.
+//! The position monitor sells those positions through Jupiter, which routes
+//! to whichever pool has the liquidity. Like PumpPortal's Local API, the
+//! transaction is built by the API and signed locally.
+//!
+//! API Documentation: https://dev.jup.ag/docs/swap-api
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::transaction::VersionedTransaction;
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+use crate::wallet::signer::{sign_versioned, TxSigner};
+
+/// Jupiter swap API base URL
+pub const JUPITER_API_URL: &str = "https://lite-api.jup.ag/swap/v1";
+
+/// Wrapped SOL mint (swap output, unwrapped to SOL by the API)
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapResponse {
+    swap_transaction: String,
+}
+
+/// Jupiter swap API client
+pub struct JupiterClient {
+    client: Client,
+}
+
+impl JupiterClient {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+
+    /// Quote for selling `amount` raw token units for SOL
+    async fn quote(&self, mint: &str, amount: u64, slippage_bps: u32) -> Result<Value> {
+        let response = self
+            .client
+            .get(format!("{}/quote", JUPITER_API_URL))
+            .query(&[
+                ("inputMint", mint),
+                ("outputMint", WSOL_MINT),
+                ("amount", &amount.to_string()),
+                ("slippageBps", &slippage_bps.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::from_api_response(
+                "Jupiter quote",
+                status.as_u16(),
+                &text,
+            ));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| Error::Deserialization(format!("Failed to parse quote: {}", e)))
+    }
+
+    /// Sell `amount` raw token units of `mint` for SOL, signing locally
+    pub async fn sell(
+        &self,
+        mint: &str,
+        amount: u64,
+        slippage_bps: u32,
+        priority_fee_lamports: u64,
+        signer: &dyn TxSigner,
+        rpc_client: &RpcClient,
+    ) -> Result<String> {
+        let public_key = signer.pubkey().to_string();
+        info!(
+            "Executing Jupiter sell: {} units of token {} (signer: {})",
+            amount, mint, public_key
+        );
+
+        let quote = self.quote(mint, amount, slippage_bps).await?;
+        debug!("Jupiter quote: {} lamports out", quote["outAmount"]);
+
+        let response = self
+            .client
+            .post(format!("{}/swap", JUPITER_API_URL))
+            .json(&json!({
+                "quoteResponse": quote,
+                "userPublicKey": public_key,
+                "wrapAndUnwrapSol": true,
+                "dynamicComputeUnitLimit": true,
+                "prioritizationFeeLamports": priority_fee_lamports,
+            }))
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::from_api_response(
+                "Jupiter swap",
+                status.as_u16(),
+                &text,
+            ));
+        }
+        let swap: SwapResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::Deserialization(format!("Failed to parse swap: {}", e)))?;
+
+        let tx_bytes = BASE64
+            .decode(&swap.swap_transaction)
+            .map_err(|e| Error::Deserialization(format!("Invalid swap transaction: {}", e)))?;
+        let mut tx: VersionedTransaction = bincode::deserialize(&tx_bytes).map_err(|e| {
+            Error::Deserialization(format!("Failed to deserialize transaction: {}", e))
+        })?;
+
+        sign_versioned(signer, &mut tx).await?;
+
+        // Send the signed transaction with skip_preflight to avoid simulation
+        use solana_client::rpc_config::RpcSendTransactionConfig;
+        use solana_sdk::commitment_config::CommitmentLevel;
+
+        let config = RpcSendTransactionConfig {
+            skip_preflight: true,
+            preflight_commitment: Some(CommitmentLevel::Confirmed),
+            ..Default::default()
+        };
+
+        let signature = rpc_client
+            .send_transaction_with_config(&tx, config)
+            .map_err(|e| Error::from_client_error("RPC send failed", e))?;
+
+        info!("Transaction sent! Signature: {}", signature);
+
+        Ok(signature.to_string())
+    }
+}
+
+impl Default for JupiterClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
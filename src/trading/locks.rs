@@ -0,0 +1,251 @@
+//! Per-mint trade locks
+//!
+//! The new-token, trade-signal, copy-trade and hot-scan paths can all decide
+//! to buy the same mint within milliseconds of each other, and the monitor
+//! and the kill-switch can sell it at the same moment. Every buy and sell
+//! takes the mint's lock before acting and re-checks what it holds once it
+//! has it, so only one of them trades. A waiter gives up after the lock
+//! timeout; contention, and locks held past the timeout, are logged with the
+//! path holding the lock. The `snipe start` event loop only tries the lock
+//! and skips a busy mint, so one slow trade doesn't hold up every event
+//! behind it.
+//!
+//! The locks live in one process: `snipe hot-scan` running next to
+//! `snipe start` has its own and can still trade a mint the other holds.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::OwnedMutexGuard;
+use tracing::{debug, warn};
+
+use crate::error::{Error, Result};
+
+/// How long a trade waits for a mint's lock (and holds it before a warning)
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One mint's lock and who holds it
+struct Slot {
+    lock: Arc<tokio::sync::Mutex<()>>,
+    holder: Option<(&'static str, Instant)>,
+}
+
+type Slots = Arc<Mutex<HashMap<String, Slot>>>;
+
+/// Registry of per-mint locks (clones share the registry)
+#[derive(Clone)]
+pub struct MintLocks {
+    slots: Slots,
+    timeout: Duration,
+}
+
+impl MintLocks {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            slots: Arc::new(Mutex::new(HashMap::new())),
+            timeout,
+        }
+    }
+
+    /// Take the lock of `mint` for `holder` ("new-token", "sell", ...),
+    /// waiting up to the timeout for whoever holds it
+    pub async fn acquire(&self, mint: &str, holder: &'static str) -> Result<MintGuard> {
+        let lock = self.lock_of(mint);
+        let guard = match lock.clone().try_lock_owned() {
+            Ok(guard) => guard,
+            Err(_) => {
+                let (held_by, held_ms) = self.holder_of(mint);
+                warn!(
+                    "[{}] {} waiting for the mint lock ({} has held it {} ms)",
+                    mint, holder, held_by, held_ms
+                );
+                let waited = Instant::now();
+                match tokio::time::timeout(self.timeout, lock.lock_owned()).await {
+                    Ok(guard) => {
+                        debug!(
+                            "[{}] {} got the mint lock after {} ms",
+                            mint,
+                            holder,
+                            waited.elapsed().as_millis()
+                        );
+                        guard
+                    }
+                    Err(_) => {
+                        let (held_by, _) = self.holder_of(mint);
+                        return Err(Error::MintLocked(mint.to_string(), held_by.to_string()));
+                    }
+                }
+            }
+        };
+        Ok(self.hold(mint, holder, guard))
+    }
+
+    /// Take the lock of `mint` for `holder` if nobody holds it
+    pub fn try_acquire(&self, mint: &str, holder: &'static str) -> Option<MintGuard> {
+        let lock = self.lock_of(mint);
+        match lock.try_lock_owned() {
+            Ok(guard) => Some(self.hold(mint, holder, guard)),
+            Err(_) => {
+                let (held_by, held_ms) = self.holder_of(mint);
+                debug!(
+                    "[{}] {} skipped: mint lock held by {} for {} ms",
+                    mint, holder, held_by, held_ms
+                );
+                None
+            }
+        }
+    }
+
+    /// Path holding the lock of `mint`, if any
+    pub fn holder(&self, mint: &str) -> Option<&'static str> {
+        let slots = self.slots.lock().expect("mint locks poisoned");
+        slots
+            .get(mint)
+            .and_then(|slot| slot.holder)
+            .map(|(holder, _)| holder)
+    }
+
+    fn lock_of(&self, mint: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut slots = self.slots.lock().expect("mint locks poisoned");
+        slots
+            .entry(mint.to_string())
+            .or_insert_with(|| Slot {
+                lock: Arc::new(tokio::sync::Mutex::new(())),
+                holder: None,
+            })
+            .lock
+            .clone()
+    }
+
+    fn holder_of(&self, mint: &str) -> (&'static str, u128) {
+        let slots = self.slots.lock().expect("mint locks poisoned");
+        match slots.get(mint).and_then(|slot| slot.holder) {
+            Some((holder, since)) => (holder, since.elapsed().as_millis()),
+            None => ("nobody", 0),
+        }
+    }
+
+    fn hold(&self, mint: &str, holder: &'static str, guard: OwnedMutexGuard<()>) -> MintGuard {
+        let acquired_at = Instant::now();
+        if let Some(slot) = self
+            .slots
+            .lock()
+            .expect("mint locks poisoned")
+            .get_mut(mint)
+        {
+            slot.holder = Some((holder, acquired_at));
+        }
+        MintGuard {
+            slots: self.slots.clone(),
+            mint: mint.to_string(),
+            holder,
+            acquired_at,
+            timeout: self.timeout,
+            _guard: guard,
+        }
+    }
+}
+
+impl Default for MintLocks {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_TIMEOUT)
+    }
+}
+
+/// Holds a mint's lock until dropped
+pub struct MintGuard {
+    slots: Slots,
+    mint: String,
+    holder: &'static str,
+    acquired_at: Instant,
+    timeout: Duration,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl Drop for MintGuard {
+    fn drop(&mut self) {
+        let held = self.acquired_at.elapsed();
+        if held > self.timeout {
+            warn!(
+                "[{}] {} held the mint lock for {} ms (timeout {} ms)",
+                self.mint,
+                self.holder,
+                held.as_millis(),
+                self.timeout.as_millis()
+            );
+        }
+        let mut slots = self.slots.lock().expect("mint locks poisoned");
+        if let Some(slot) = slots.get_mut(&self.mint) {
+            slot.holder = None;
+        }
+        // Only the registry and this guard reference it: nobody is waiting
+        if slots
+            .get(&self.mint)
+            .is_some_and(|slot| Arc::strong_count(&slot.lock) <= 2)
+        {
+            slots.remove(&self.mint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_racing_buys_trade_once() {
+        let locks = MintLocks::default();
+        let bought = Arc::new(Mutex::new(HashSet::new()));
+        let racers = 16;
+        let barrier = Arc::new(tokio::sync::Barrier::new(racers));
+
+        let handles: Vec<_> = (0..racers)
+            .map(|_| {
+                let locks = locks.clone();
+                let bought = bought.clone();
+                let barrier = barrier.clone();
+                tokio::spawn(async move {
+                    barrier.wait().await;
+                    let _guard = locks.acquire("mint", "buy").await.unwrap();
+                    // Re-check under the lock, as the buy paths do
+                    if bought.lock().unwrap().contains("mint") {
+                        return false;
+                    }
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    bought.lock().unwrap().insert("mint");
+                    true
+                })
+            })
+            .collect();
+
+        let mut buys = 0;
+        for handle in handles {
+            if handle.await.unwrap() {
+                buys += 1;
+            }
+        }
+        assert_eq!(buys, 1);
+        // Every guard dropped: the slot is gone
+        assert!(locks.slots.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_waiter_times_out() {
+        let locks = MintLocks::new(Duration::from_millis(20));
+        let held = locks.acquire("mint", "new-token").await.unwrap();
+        assert_eq!(locks.holder("mint"), Some("new-token"));
+        assert!(locks.try_acquire("mint", "sell").is_none());
+        assert!(locks.try_acquire("other", "sell").is_some());
+
+        match locks.acquire("mint", "copy-trade").await {
+            Err(Error::MintLocked(mint, holder)) => {
+                assert_eq!((mint.as_str(), holder.as_str()), ("mint", "new-token"))
+            }
+            _ => panic!("expected the waiter to time out"),
+        }
+        drop(held);
+        assert!(locks.holder("mint").is_none());
+        assert!(locks.acquire("mint", "copy-trade").await.is_ok());
+    }
+}
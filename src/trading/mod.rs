@@ -3,14 +3,29 @@
 //! Supports multiple execution methods:
 //! - Jito bundles (fastest, MEV protected)
 //! - PumpPortal API (easy, 0.5% fee)
+//! - Jupiter swaps (tokens migrated off the bonding curve)
+//! - Native PumpSwap swaps (tokens migrated to pump.fun's AMM)
 //! - Direct RPC (standard)
+//! - Simulated fills against replayed curves (backtests) or live ones (paper)
 
 pub mod jito;
+pub mod jupiter;
+pub mod locks;
+pub mod paper;
 pub mod pumpportal_api;
+pub mod pumpswap;
+pub mod simulated;
 pub mod simulation;
 pub mod tips;
+pub mod trader;
 pub mod transaction;
 
 pub use jito::JitoClient;
+pub use jupiter::JupiterClient;
+pub use locks::{MintGuard, MintLocks};
+pub use paper::PaperTrader;
 pub use pumpportal_api::PumpPortalTrader;
+pub use pumpswap::PumpSwapTrader;
+pub use simulated::SimulatedTrader;
+pub use trader::Trader;
 pub use transaction::TransactionBuilder;
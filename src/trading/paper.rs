@@ -0,0 +1,272 @@
+//! Paper trading
+//!
+//! `PaperTrader` executes `snipe start --paper` orders with a
+//! `SimulatedTrader` fed the live bonding curves from the trade feed: an
+//! order waits `latency_ms`, then fills at the curve as it stands, less
+//! `slippage_pct`. Nothing is sent on-chain.
+//!
+//! `snipe start --dry-run=simulate` adds a `Preflight`: before each fill the
+//! order is also built through the PumpPortal Local API, signed with the
+//! hot wallet and simulated against the cluster. The result goes to the
+//! decision log; the fill happens either way, so positions are still
+//! tracked as paper positions.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::transaction::VersionedTransaction;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::PaperConfig;
+use crate::error::{Error, Result};
+use crate::filter::decision_log::DecisionLog;
+use crate::pump::accounts::BondingCurve;
+use crate::trading::pumpportal_api::PumpPortalTrader;
+use crate::trading::simulated::{SimFill, SimulatedTrader};
+use crate::trading::simulation::{self, SimulationResult};
+use crate::trading::trader::Trader;
+use crate::wallet::signer::{sign_versioned, SharedSigner};
+
+/// Builds, signs and simulates each paper order without sending it
+pub struct Preflight {
+    trader: PumpPortalTrader,
+    signer: SharedSigner,
+    rpc_client: Arc<RpcClient>,
+    decision_log: DecisionLog,
+}
+
+impl Preflight {
+    pub fn new(
+        signer: SharedSigner,
+        rpc_client: Arc<RpcClient>,
+        decision_log: DecisionLog,
+    ) -> Self {
+        Self {
+            trader: PumpPortalTrader::local(),
+            signer,
+            rpc_client,
+            decision_log,
+        }
+    }
+
+    /// Deserialize and sign a Local API transaction
+    async fn sign(&self, tx_bytes: Vec<u8>) -> Result<VersionedTransaction> {
+        let mut tx: VersionedTransaction = bincode::deserialize(&tx_bytes).map_err(|e| {
+            Error::Deserialization(format!("Failed to deserialize transaction: {}", e))
+        })?;
+        sign_versioned(self.signer.as_ref(), &mut tx).await?;
+        Ok(tx)
+    }
+
+    /// Sign the Local API transaction and simulate it
+    async fn simulate(&self, tx_bytes: Result<Vec<u8>>) -> SimulationResult {
+        let signed = match tx_bytes {
+            Ok(tx_bytes) => self.sign(tx_bytes).await,
+            Err(e) => Err(e),
+        };
+        let result = match signed {
+            Ok(tx) => simulation::simulate_signed(&self.rpc_client, &tx).await,
+            Err(e) => return SimulationResult::not_built(e),
+        };
+        result.unwrap_or_else(|e| SimulationResult {
+            success: false,
+            error: Some(format!("simulation request failed: {}", e)),
+            compute_units: None,
+            logs: Vec::new(),
+        })
+    }
+
+    fn record(&self, mint: &str, detail: String, result: SimulationResult) {
+        if result.success {
+            info!("SIMULATE: {} {} - {}", detail, mint, result.summary());
+        } else {
+            warn!("SIMULATE: {} {} - {}", detail, mint, result.summary());
+        }
+        self.decision_log.record_simulation(mint, detail, result);
+    }
+
+    async fn buy(&self, mint: &str, sol_amount: f64, slippage_pct: u32, priority_fee: f64) {
+        let public_key = self.signer.pubkey().to_string();
+        let tx_bytes = self
+            .trader
+            .get_buy_transaction(mint, sol_amount, slippage_pct, priority_fee, &public_key)
+            .await;
+        let result = self.simulate(tx_bytes).await;
+        self.record(mint, format!("buy {:.4} SOL", sol_amount), result);
+    }
+
+    async fn sell(&self, mint: &str, amount: &str, slippage_pct: u32, priority_fee: f64) {
+        let public_key = self.signer.pubkey().to_string();
+        let tx_bytes = self
+            .trader
+            .get_sell_transaction(mint, amount, slippage_pct, priority_fee, &public_key)
+            .await;
+        let result = self.simulate(tx_bytes).await;
+        self.record(mint, format!("sell {}", amount), result);
+    }
+}
+
+/// Simulated execution against the live curves
+pub struct PaperTrader {
+    sim: SimulatedTrader,
+    latency_ms: u64,
+    /// Fills taken from the simulator that their order hasn't claimed yet
+    unclaimed: Mutex<HashMap<String, SimFill>>,
+    /// Set for `--dry-run=simulate`
+    preflight: OnceLock<Preflight>,
+}
+
+impl PaperTrader {
+    pub fn new(config: &PaperConfig, platform_fee_pct: f64) -> Self {
+        Self {
+            sim: SimulatedTrader::new(config.latency_ms, config.slippage_pct, platform_fee_pct),
+            latency_ms: config.latency_ms,
+            unclaimed: Mutex::new(HashMap::new()),
+            preflight: OnceLock::new(),
+        }
+    }
+
+    /// Also simulate every order against the cluster (once; later calls
+    /// are ignored)
+    pub fn enable_preflight(&self, preflight: Preflight) {
+        let _ = self.preflight.set(preflight);
+    }
+
+    /// Latest reserves for a mint (from the trade feed)
+    pub fn set_curve(&self, mint: &str, curve: BondingCurve) {
+        self.sim.set_curve(mint, curve);
+    }
+
+    /// Paper positions carried over from a previous run
+    pub fn restore_holding(&self, mint: &str, tokens: u64) {
+        self.sim.set_holdings(mint, tokens);
+    }
+
+    /// Raw tokens held of a mint
+    pub fn holdings(&self, mint: &str) -> u64 {
+        self.sim.holdings(mint)
+    }
+
+    /// Buy and wait for the fill
+    pub async fn buy_fill(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<SimFill> {
+        if let Some(preflight) = self.preflight.get() {
+            preflight
+                .buy(mint, sol_amount, slippage_pct, priority_fee)
+                .await;
+        }
+        self.sim.advance(Utc::now());
+        let signature = self
+            .sim
+            .buy(mint, sol_amount, slippage_pct, priority_fee)
+            .await?;
+        self.wait_for(&signature).await
+    }
+
+    /// Sell ("50%" or a raw amount) and wait for the fill
+    pub async fn sell_fill(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<SimFill> {
+        if let Some(preflight) = self.preflight.get() {
+            preflight
+                .sell(mint, amount, slippage_pct, priority_fee)
+                .await;
+        }
+        self.sim.advance(Utc::now());
+        let signature = self
+            .sim
+            .sell(mint, amount, slippage_pct, priority_fee)
+            .await?;
+        self.wait_for(&signature).await
+    }
+
+    async fn wait_for(&self, signature: &str) -> Result<SimFill> {
+        tokio::time::sleep(Duration::from_millis(self.latency_ms)).await;
+        self.sim.advance(Utc::now());
+
+        let fill = {
+            let mut unclaimed = self.unclaimed.lock().unwrap();
+            for fill in self.sim.take_fills() {
+                unclaimed.insert(fill.signature.clone(), fill);
+            }
+            unclaimed.remove(signature)
+        };
+        let fill = fill.ok_or_else(|| {
+            Error::TransactionSend(format!("paper order {} did not fill", signature))
+        })?;
+        match fill.error {
+            Some(ref error) => Err(Error::TransactionSend(format!("paper fill: {}", error))),
+            None => Ok(fill),
+        }
+    }
+}
+
+#[async_trait]
+impl Trader for PaperTrader {
+    async fn buy(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        self.buy_fill(mint, sol_amount, slippage_pct, priority_fee)
+            .await
+            .map(|fill| fill.signature)
+    }
+
+    async fn sell(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        self.sell_fill(mint, amount, slippage_pct, priority_fee)
+            .await
+            .map(|fill| fill.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_paper_round_trip() {
+        let config = PaperConfig {
+            latency_ms: 10,
+            slippage_pct: 1.0,
+        };
+        let trader = PaperTrader::new(&config, 1.0);
+        trader.set_curve(
+            "mint",
+            crate::filter::honeypot::curve_from_event(30.0, 1_073_000_000.0),
+        );
+
+        let buy = trader.buy_fill("mint", 0.1, 25, 0.0001).await.unwrap();
+        assert!(buy.tokens > 0);
+        assert_eq!(trader.holdings("mint"), buy.tokens);
+
+        let sell = trader.sell_fill("mint", "100%", 25, 0.0001).await.unwrap();
+        assert_eq!(sell.tokens, buy.tokens);
+        // Slippage is charged on both legs
+        assert!(sell.sol < 0.1 * 0.99);
+        assert_eq!(trader.holdings("mint"), 0);
+
+        // Nothing left to sell
+        assert!(trader.sell_fill("mint", "100%", 25, 0.0).await.is_err());
+    }
+}
@@ -11,13 +11,11 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::{
-    signature::{Keypair, Signer},
-    transaction::VersionedTransaction,
-};
+use solana_sdk::transaction::VersionedTransaction;
 use tracing::{debug, error, info, warn};
 
-use crate::error::{Error, Result};
+use crate::error::{Error, ErrorCategory, Result};
+use crate::wallet::signer::{sign_transaction, sign_versioned, TxSigner};
 
 /// PumpPortal Lightning API endpoint
 pub const PUMPPORTAL_API_URL: &str = "https://pumpportal.fun/api/trade";
@@ -121,6 +119,8 @@ pub struct PumpPortalTrader {
     api_key: Option<String>,
     #[allow(dead_code)]
     use_local_api: bool,
+    /// Jito tip paid by each landed bundle, by signature, until taken
+    jito_tips: std::sync::Mutex<std::collections::HashMap<String, u64>>,
 }
 
 impl PumpPortalTrader {
@@ -134,9 +134,20 @@ impl PumpPortalTrader {
             client: Client::new(),
             api_key,
             use_local_api,
+            jito_tips: Default::default(),
         }
     }
 
+    /// Jito tip paid with the transaction `signature` (0 = not sent as a
+    /// bundle, or the bundle fell back to RPC); each tip is reported once
+    pub fn take_jito_tip(&self, signature: &str) -> u64 {
+        self.jito_tips
+            .lock()
+            .unwrap()
+            .remove(signature)
+            .unwrap_or(0)
+    }
+
     /// Create a trader for Lightning API (easiest, 0.5% fee)
     pub fn lightning(api_key: String) -> Self {
         Self::new(Some(api_key), false)
@@ -204,20 +215,25 @@ impl PumpPortalTrader {
             .json(&request)
             .send()
             .await
-            .map_err(|e| Error::TransactionSend(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
 
+        let status = response.status().as_u16();
         let trade_response: TradeResponse = response
             .json()
             .await
             .map_err(|e| Error::Deserialization(format!("Failed to parse response: {}", e)))?;
 
         if let Some(error) = trade_response.error {
-            return Err(Error::TransactionSend(error));
+            return Err(Error::from_api_response("PumpPortal", status, &error));
         }
 
         if let Some(errors) = trade_response.errors {
             if !errors.is_empty() {
-                return Err(Error::TransactionSend(errors.join(", ")));
+                return Err(Error::from_api_response(
+                    "PumpPortal",
+                    status,
+                    &errors.join(", "),
+                ));
             }
         }
 
@@ -272,8 +288,9 @@ impl PumpPortalTrader {
             .json(&request)
             .send()
             .await
-            .map_err(|e| Error::TransactionSend(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
 
+        let status = response.status().as_u16();
         let trade_response: TradeResponse = response
             .json()
             .await
@@ -281,13 +298,17 @@ impl PumpPortalTrader {
 
         // Check for error (singular)
         if let Some(error) = trade_response.error {
-            return Err(Error::TransactionSend(error));
+            return Err(Error::from_api_response("PumpPortal", status, &error));
         }
 
         // Check for errors (plural) - API sometimes returns errors as array
         if let Some(errors) = trade_response.errors {
             if !errors.is_empty() {
-                return Err(Error::TransactionSend(errors.join("; ")));
+                return Err(Error::from_api_response(
+                    "PumpPortal",
+                    status,
+                    &errors.join("; "),
+                ));
             }
         }
 
@@ -329,16 +350,17 @@ impl PumpPortalTrader {
             .json(&request)
             .send()
             .await
-            .map_err(|e| Error::TransactionBuild(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
 
         // Check for error response (JSON with error field)
         let status = response.status();
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
-            return Err(Error::TransactionBuild(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(Error::from_api_response(
+                "PumpPortal",
+                status.as_u16(),
+                &text,
+            ));
         }
 
         // The API returns raw transaction bytes directly
@@ -391,16 +413,17 @@ impl PumpPortalTrader {
             .json(&request)
             .send()
             .await
-            .map_err(|e| Error::TransactionBuild(format!("HTTP request failed: {}", e)))?;
+            .map_err(|e| Error::Network(format!("HTTP request failed: {}", e)))?;
 
         // Check for error response
         let status = response.status();
         if !status.is_success() {
             let text = response.text().await.unwrap_or_default();
-            return Err(Error::TransactionBuild(format!(
-                "API error ({}): {}",
-                status, text
-            )));
+            return Err(Error::from_api_response(
+                "PumpPortal",
+                status.as_u16(),
+                &text,
+            ));
         }
 
         // The API returns raw transaction bytes directly
@@ -428,7 +451,7 @@ impl PumpPortalTrader {
     /// * `sol_amount` - Amount of SOL to spend
     /// * `slippage_pct` - Slippage percentage (e.g., 25 for 25%)
     /// * `priority_fee` - Priority fee in SOL
-    /// * `keypair` - Keypair to sign the transaction
+    /// * `signer` - Signs the transaction (local keypair, remote or subprocess signer)
     /// * `rpc_client` - RPC client to send the transaction
     pub async fn buy_local(
         &self,
@@ -436,10 +459,10 @@ impl PumpPortalTrader {
         sol_amount: f64,
         slippage_pct: u32,
         priority_fee: f64,
-        keypair: &Keypair,
+        signer: &dyn TxSigner,
         rpc_client: &RpcClient,
     ) -> Result<String> {
-        let public_key = keypair.pubkey().to_string();
+        let public_key = signer.pubkey().to_string();
 
         info!(
             "Executing local buy: {} SOL for token {} (signer: {})",
@@ -463,11 +486,8 @@ impl PumpPortalTrader {
 
         debug!("Deserialized transaction, signing...");
 
-        // Sign the transaction
-        // For VersionedTransaction, we need to sign the message
-        let message_bytes = tx.message.serialize();
-        let signature = keypair.sign_message(&message_bytes);
-        tx.signatures[0] = signature;
+        // Sign the transaction (fee payer signature)
+        sign_versioned(signer, &mut tx).await?;
 
         debug!("Transaction signed, sending...");
 
@@ -483,7 +503,7 @@ impl PumpPortalTrader {
 
         let signature = rpc_client
             .send_transaction_with_config(&tx, config)
-            .map_err(|e| Error::TransactionSend(format!("RPC send failed: {}", e)))?;
+            .map_err(|e| Error::from_client_error("RPC send failed", e))?;
 
         info!("Transaction sent! Signature: {}", signature);
 
@@ -492,15 +512,17 @@ impl PumpPortalTrader {
 
     /// Execute a buy with retry logic and confirmation checking
     ///
-    /// Retries with increasing slippage if transaction fails due to slippage exceeded (error 6005).
-    /// Waits for transaction confirmation before returning.
+    /// Retries with increasing slippage if the transaction fails on slippage
+    /// (TooMuchSolRequired), after the suggested backoff on transient errors,
+    /// and not at all on permanent ones (curve complete, insufficient funds,
+    /// rejected requests). Waits for transaction confirmation before returning.
     ///
     /// # Arguments
     /// * `mint` - Token mint address
     /// * `sol_amount` - Amount of SOL to spend
     /// * `initial_slippage_pct` - Starting slippage percentage (will increase on retry)
     /// * `priority_fee` - Priority fee in SOL
-    /// * `keypair` - Keypair to sign the transaction
+    /// * `signer` - Signs the transaction (local keypair, remote or subprocess signer)
     /// * `rpc_client` - RPC client to send the transaction
     /// * `max_retries` - Maximum number of retry attempts (default 3)
     pub async fn buy_local_with_retry(
@@ -509,7 +531,7 @@ impl PumpPortalTrader {
         sol_amount: f64,
         initial_slippage_pct: u32,
         priority_fee: f64,
-        keypair: &Keypair,
+        signer: &dyn TxSigner,
         rpc_client: &RpcClient,
         max_retries: u32,
     ) -> Result<String> {
@@ -533,19 +555,21 @@ impl PumpPortalTrader {
             }
 
             // Get fresh transaction with current slippage
-            let public_key = keypair.pubkey().to_string();
+            let public_key = signer.pubkey().to_string();
             let tx_bytes = match self
                 .get_buy_transaction(mint, sol_amount, slippage, priority_fee, &public_key)
                 .await
             {
                 Ok(bytes) => bytes,
                 Err(e) => {
-                    tracing::warn!("Failed to get transaction: {}", e);
-                    if attempt < max_retries {
-                        sleep(Duration::from_millis(500)).await;
-                        continue;
+                    tracing::warn!("Failed to get transaction ({}): {}", e.category(), e);
+                    match e.suggested_backoff() {
+                        Some(backoff) if attempt < max_retries => {
+                            sleep(backoff).await;
+                            continue;
+                        }
+                        _ => return Err(e),
                     }
-                    return Err(e);
                 }
             };
 
@@ -553,9 +577,7 @@ impl PumpPortalTrader {
             let mut tx: VersionedTransaction = bincode::deserialize(&tx_bytes)
                 .map_err(|e| Error::Deserialization(format!("Failed to deserialize: {}", e)))?;
 
-            let message_bytes = tx.message.serialize();
-            let signature = keypair.sign_message(&message_bytes);
-            tx.signatures[0] = signature;
+            sign_versioned(signer, &mut tx).await?;
 
             // Send transaction
             use solana_client::rpc_config::RpcSendTransactionConfig;
@@ -570,12 +592,15 @@ impl PumpPortalTrader {
             let sig = match rpc_client.send_transaction_with_config(&tx, config) {
                 Ok(s) => s,
                 Err(e) => {
-                    tracing::warn!("Failed to send transaction: {}", e);
-                    if attempt < max_retries {
-                        sleep(Duration::from_millis(500)).await;
-                        continue;
+                    let e = Error::from_client_error("RPC send failed", e);
+                    tracing::warn!("Failed to send transaction ({}): {}", e.category(), e);
+                    match e.suggested_backoff() {
+                        Some(backoff) if attempt < max_retries => {
+                            sleep(backoff).await;
+                            continue;
+                        }
+                        _ => return Err(e),
                     }
-                    return Err(Error::TransactionSend(format!("RPC send failed: {}", e)));
                 }
             };
 
@@ -596,9 +621,8 @@ impl PumpPortalTrader {
                                 return Ok(sig.to_string());
                             }
                             Err(tx_err) => {
-                                // Check if it's slippage error (Custom 6005)
-                                let err_str = format!("{:?}", tx_err);
-                                if err_str.contains("6005") || err_str.contains("Slippage") {
+                                let e = Error::from(tx_err);
+                                if e.category() == ErrorCategory::SlippageExceeded {
                                     tracing::warn!(
                                         "Slippage exceeded on attempt {}, will retry with higher slippage",
                                         attempt + 1
@@ -606,10 +630,7 @@ impl PumpPortalTrader {
                                     break; // Break inner loop to retry
                                 } else {
                                     // Other error, don't retry
-                                    return Err(Error::TransactionSend(format!(
-                                        "Transaction failed: {:?}",
-                                        tx_err
-                                    )));
+                                    return Err(e);
                                 }
                             }
                         }
@@ -643,10 +664,35 @@ impl PumpPortalTrader {
         amount: &str,
         slippage_pct: u32,
         priority_fee: f64,
-        keypair: &Keypair,
+        signer: &dyn TxSigner,
+        rpc_client: &RpcClient,
+    ) -> Result<String> {
+        self.sell_local_with_preflight(
+            mint,
+            amount,
+            slippage_pct,
+            priority_fee,
+            signer,
+            rpc_client,
+            false,
+        )
+        .await
+    }
+
+    /// Local API sell, simulated by the RPC node before sending when
+    /// `preflight` is set
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sell_local_with_preflight(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+        signer: &dyn TxSigner,
         rpc_client: &RpcClient,
+        preflight: bool,
     ) -> Result<String> {
-        let public_key = keypair.pubkey().to_string();
+        let public_key = signer.pubkey().to_string();
 
         info!(
             "Executing local sell: {} of token {} (signer: {})",
@@ -671,25 +717,23 @@ impl PumpPortalTrader {
         debug!("Deserialized transaction, signing...");
 
         // Sign the transaction
-        let message_bytes = tx.message.serialize();
-        let signature = keypair.sign_message(&message_bytes);
-        tx.signatures[0] = signature;
+        sign_versioned(signer, &mut tx).await?;
 
         debug!("Transaction signed, sending...");
 
-        // Send the signed transaction with skip_preflight to avoid simulation
+        // Skip preflight to avoid simulation unless asked for
         use solana_client::rpc_config::RpcSendTransactionConfig;
         use solana_sdk::commitment_config::CommitmentLevel;
 
         let config = RpcSendTransactionConfig {
-            skip_preflight: true,
+            skip_preflight: !preflight,
             preflight_commitment: Some(CommitmentLevel::Confirmed),
             ..Default::default()
         };
 
         let signature = rpc_client
             .send_transaction_with_config(&tx, config)
-            .map_err(|e| Error::TransactionSend(format!("RPC send failed: {}", e)))?;
+            .map_err(|e| Error::from_client_error("RPC send failed", e))?;
 
         info!("Transaction sent! Signature: {}", signature);
 
@@ -704,14 +748,14 @@ impl PumpPortalTrader {
         mint: &str,
         sol_amount: f64,
         slippage_pct: u32,
-        keypair: &Keypair,
+        signer: &dyn TxSigner,
         jito_client: &crate::trading::jito::JitoClient,
         rpc_client: &RpcClient,
     ) -> Result<String> {
         use crate::trading::jito::BundleStatus;
         use solana_sdk::{message::Message, system_instruction, transaction::Transaction};
 
-        let public_key = keypair.pubkey().to_string();
+        let public_key = signer.pubkey().to_string();
 
         info!(
             "Executing Jito buy: {} SOL for token {} (signer: {})",
@@ -749,9 +793,7 @@ impl PumpPortalTrader {
         })?;
 
         // Sign the buy transaction
-        let message_bytes = buy_tx.message.serialize();
-        let signature = keypair.sign_message(&message_bytes);
-        buy_tx.signatures[0] = signature;
+        sign_versioned(signer, &mut buy_tx).await?;
 
         // Try Jito first
         let jito_result = async {
@@ -761,15 +803,14 @@ impl PumpPortalTrader {
             // Get recent blockhash for tip transaction
             let blockhash = rpc_client
                 .get_latest_blockhash()
-                .map_err(|e| Error::TransactionBuild(format!("Failed to get blockhash: {}", e)))?;
+                .map_err(|e| Error::from_client_error("Failed to get blockhash", e))?;
 
             // Create tip transaction
-            let tip_ix =
-                system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
+            let tip_ix = system_instruction::transfer(&signer.pubkey(), &tip_account, tip_lamports);
 
-            let tip_message = Message::new(&[tip_ix], Some(&keypair.pubkey()));
+            let tip_message = Message::new(&[tip_ix], Some(&signer.pubkey()));
             let mut tip_tx = Transaction::new_unsigned(tip_message);
-            tip_tx.sign(&[keypair], blockhash);
+            sign_transaction(signer, &mut tip_tx, blockhash).await?;
 
             debug!("Created tip transaction to {}", tip_account);
 
@@ -826,6 +867,10 @@ impl PumpPortalTrader {
 
         // If Jito succeeded, return
         if let Ok(sig) = jito_result {
+            self.jito_tips
+                .lock()
+                .unwrap()
+                .insert(sig.clone(), tip_lamports);
             return Ok(sig);
         }
 
@@ -841,7 +886,7 @@ impl PumpPortalTrader {
                 sol_amount,
                 slippage_pct,
                 priority_fee,
-                keypair,
+                signer,
                 rpc_client,
                 3,
             )
@@ -861,19 +906,22 @@ impl PumpPortalTrader {
     /// Execute a sell using Jito bundles with fallback to regular RPC
     ///
     /// Tries Jito first for MEV protection, falls back to regular RPC if Jito fails.
+    /// `tip_lamports` overrides Jito's recommended tip.
+    #[allow(clippy::too_many_arguments)]
     pub async fn sell_with_jito(
         &self,
         mint: &str,
         amount: &str,
         slippage_pct: u32,
-        keypair: &Keypair,
+        signer: &dyn TxSigner,
         jito_client: &crate::trading::jito::JitoClient,
         rpc_client: &RpcClient,
+        tip_lamports: Option<u64>,
     ) -> Result<String> {
         use crate::trading::jito::BundleStatus;
         use solana_sdk::{message::Message, system_instruction, transaction::Transaction};
 
-        let public_key = keypair.pubkey().to_string();
+        let public_key = signer.pubkey().to_string();
 
         info!(
             "Executing Jito sell: {} of token {} (signer: {})",
@@ -881,7 +929,10 @@ impl PumpPortalTrader {
         );
 
         // Get recommended tip from Jito
-        let tip_lamports = jito_client.get_recommended_tip().await.unwrap_or(100000);
+        let tip_lamports = match tip_lamports {
+            Some(tip) => tip,
+            None => jito_client.get_recommended_tip().await.unwrap_or(100000),
+        };
         info!(
             "Using Jito tip: {} lamports ({:.6} SOL)",
             tip_lamports,
@@ -911,9 +962,7 @@ impl PumpPortalTrader {
         })?;
 
         // Sign the sell transaction
-        let message_bytes = sell_tx.message.serialize();
-        let signature = keypair.sign_message(&message_bytes);
-        sell_tx.signatures[0] = signature;
+        sign_versioned(signer, &mut sell_tx).await?;
 
         // Try Jito first
         let jito_result = async {
@@ -923,15 +972,14 @@ impl PumpPortalTrader {
             // Get recent blockhash for tip transaction
             let blockhash = rpc_client
                 .get_latest_blockhash()
-                .map_err(|e| Error::TransactionBuild(format!("Failed to get blockhash: {}", e)))?;
+                .map_err(|e| Error::from_client_error("Failed to get blockhash", e))?;
 
             // Create tip transaction
-            let tip_ix =
-                system_instruction::transfer(&keypair.pubkey(), &tip_account, tip_lamports);
+            let tip_ix = system_instruction::transfer(&signer.pubkey(), &tip_account, tip_lamports);
 
-            let tip_message = Message::new(&[tip_ix], Some(&keypair.pubkey()));
+            let tip_message = Message::new(&[tip_ix], Some(&signer.pubkey()));
             let mut tip_tx = Transaction::new_unsigned(tip_message);
-            tip_tx.sign(&[keypair], blockhash);
+            sign_transaction(signer, &mut tip_tx, blockhash).await?;
 
             debug!("Created tip transaction to {}", tip_account);
 
@@ -988,6 +1036,10 @@ impl PumpPortalTrader {
 
         // If Jito succeeded, return
         if let Ok(sig) = jito_result {
+            self.jito_tips
+                .lock()
+                .unwrap()
+                .insert(sig.clone(), tip_lamports);
             return Ok(sig);
         }
 
@@ -998,14 +1050,7 @@ impl PumpPortalTrader {
         let priority_fee = jito_client.config().min_tip_lamports as f64 / 1e9;
 
         match self
-            .sell_local(
-                mint,
-                amount,
-                slippage_pct,
-                priority_fee,
-                keypair,
-                rpc_client,
-            )
+            .sell_local(mint, amount, slippage_pct, priority_fee, signer, rpc_client)
             .await
         {
             Ok(sig) => {
@@ -0,0 +1,111 @@
+//! Native PumpSwap trades
+//!
+//! Once a bonding curve completes, the token trades in its PumpSwap pool.
+//! Unlike the Jupiter and PumpPortal paths, the swap transaction is built
+//! here from the pool's on-chain state (`pump::amm`) and signed locally, so
+//! entries and exits on migrated tokens need no third-party API.
+
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use tracing::info;
+
+use crate::config::TradingConfig;
+use crate::error::{Error, Result};
+use crate::pump::amm::PoolState;
+use crate::trading::transaction::TransactionBuilder;
+use crate::wallet::signer::TxSigner;
+
+/// Buys and sells migrated tokens directly against their PumpSwap pool
+pub struct PumpSwapTrader {
+    config: TradingConfig,
+}
+
+impl PumpSwapTrader {
+    pub fn new(config: TradingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Builder using `slippage_bps` instead of the configured slippage
+    fn builder(&self, slippage_bps: u32) -> TransactionBuilder {
+        TransactionBuilder::new(TradingConfig {
+            slippage_bps,
+            ..self.config.clone()
+        })
+    }
+
+    /// Pool of `mint`, if it has migrated to PumpSwap
+    pub fn pool(&self, mint: &str, rpc_client: &RpcClient) -> Result<PoolState> {
+        let mint = Pubkey::from_str(mint)
+            .map_err(|e| Error::TransactionBuild(format!("Invalid mint {}: {}", mint, e)))?;
+        PoolState::fetch(rpc_client, &mint)
+    }
+
+    /// Buy `sol_amount` SOL worth of `mint`, fees included
+    pub async fn buy(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_bps: u32,
+        signer: &dyn TxSigner,
+        rpc_client: &RpcClient,
+    ) -> Result<String> {
+        let pool = self.pool(mint, rpc_client)?;
+        let sol_in = (sol_amount * 1e9) as u64;
+        info!(
+            "Executing PumpSwap buy: {} SOL of token {} (pool {})",
+            sol_amount, mint, pool.pool.address
+        );
+        let tx = self
+            .builder(slippage_bps)
+            .build_amm_buy(signer, &pool, sol_in, latest_blockhash(rpc_client)?)
+            .await?;
+        send(&tx, rpc_client)
+    }
+
+    /// Sell `amount` raw token units of `mint` for SOL
+    pub async fn sell(
+        &self,
+        mint: &str,
+        amount: u64,
+        slippage_bps: u32,
+        signer: &dyn TxSigner,
+        rpc_client: &RpcClient,
+    ) -> Result<String> {
+        let pool = self.pool(mint, rpc_client)?;
+        info!(
+            "Executing PumpSwap sell: {} units of token {} (pool {})",
+            amount, mint, pool.pool.address
+        );
+        let tx = self
+            .builder(slippage_bps)
+            .build_amm_sell(signer, &pool, amount, latest_blockhash(rpc_client)?)
+            .await?;
+        send(&tx, rpc_client)
+    }
+}
+
+fn latest_blockhash(rpc_client: &RpcClient) -> Result<solana_sdk::hash::Hash> {
+    rpc_client
+        .get_latest_blockhash()
+        .map_err(|e| Error::from_client_error("Failed to get blockhash", e))
+}
+
+/// Send with skip_preflight to avoid simulation
+fn send(tx: &Transaction, rpc_client: &RpcClient) -> Result<String> {
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        preflight_commitment: Some(CommitmentLevel::Confirmed),
+        ..Default::default()
+    };
+    let signature = rpc_client
+        .send_transaction_with_config(tx, config)
+        .map_err(|e| Error::from_client_error("RPC send failed", e))?;
+
+    info!("Transaction sent! Signature: {}", signature);
+
+    Ok(signature.to_string())
+}
@@ -0,0 +1,354 @@
+//! Simulated execution for backtests
+//!
+//! `SimulatedTrader` fills orders against bonding curve reserves replayed
+//! from a recorded session instead of sending transactions. An order fills
+//! `latency_ms` after it was submitted, at the curve as it stood then, less
+//! `slippage_pct` of adverse slippage; a fill worse than the order's own
+//! slippage tolerance fails, as it would on-chain. Fees are estimated the
+//! same way live trades are (priority fee plus the platform fee).
+//!
+//! The replay drives the clock with `advance`, reports reserves with
+//! `set_curve` and collects results with `take_fills`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::error::{Error, Result};
+use crate::position::fees::TradeFees;
+use crate::pump::accounts::BondingCurve;
+use crate::pump::price;
+use crate::trading::trader::Trader;
+
+/// Buy or sell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimSide {
+    Buy,
+    Sell,
+}
+
+/// Result of a simulated order
+#[derive(Debug, Clone)]
+pub struct SimFill {
+    pub signature: String,
+    pub mint: String,
+    pub side: SimSide,
+    /// SOL spent (buy) or received (sell), before fees
+    pub sol: f64,
+    /// Raw token amount bought or sold
+    pub tokens: u64,
+    /// SOL per whole token
+    pub price: f64,
+    pub fees: TradeFees,
+    /// Why the order failed (nothing was traded)
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum OrderAmount {
+    Sol(f64),
+    Pct(f64),
+    Tokens(u64),
+}
+
+struct Order {
+    signature: String,
+    mint: String,
+    side: SimSide,
+    amount: OrderAmount,
+    slippage_pct: u32,
+    priority_fee: f64,
+    /// Price at submission, for the slippage tolerance
+    quote_price: f64,
+    due: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct SimState {
+    clock: Option<DateTime<Utc>>,
+    curves: HashMap<String, BondingCurve>,
+    holdings: HashMap<String, u64>,
+    orders: Vec<Order>,
+    fills: Vec<SimFill>,
+    next_id: u64,
+}
+
+/// Fills orders against replayed bonding curves
+pub struct SimulatedTrader {
+    latency_ms: u64,
+    slippage_pct: f64,
+    platform_fee_pct: f64,
+    state: Mutex<SimState>,
+}
+
+impl SimulatedTrader {
+    pub fn new(latency_ms: u64, slippage_pct: f64, platform_fee_pct: f64) -> Self {
+        Self {
+            latency_ms,
+            slippage_pct,
+            platform_fee_pct,
+            state: Mutex::new(SimState::default()),
+        }
+    }
+
+    /// Move the clock to `time`, filling orders that are due by then at the
+    /// curves as they stood
+    pub fn advance(&self, time: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        state.clock = Some(time);
+        let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut state.orders)
+            .into_iter()
+            .partition(|o| o.due <= time);
+        state.orders = waiting;
+        for order in due {
+            let fill = self.fill(&mut state, order);
+            state.fills.push(fill);
+        }
+    }
+
+    /// Latest reserves for a mint
+    pub fn set_curve(&self, mint: &str, curve: BondingCurve) {
+        let mut state = self.state.lock().unwrap();
+        state.curves.insert(mint.to_string(), curve);
+    }
+
+    /// Fills (and failures) since the last call, in fill order
+    pub fn take_fills(&self) -> Vec<SimFill> {
+        std::mem::take(&mut self.state.lock().unwrap().fills)
+    }
+
+    /// Holdings carried over from before the simulation started
+    pub fn set_holdings(&self, mint: &str, tokens: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.holdings.insert(mint.to_string(), tokens);
+    }
+
+    /// Raw tokens held of a mint
+    pub fn holdings(&self, mint: &str) -> u64 {
+        let state = self.state.lock().unwrap();
+        state.holdings.get(mint).copied().unwrap_or(0)
+    }
+
+    /// Orders submitted but not yet filled
+    pub fn pending_orders(&self) -> usize {
+        self.state.lock().unwrap().orders.len()
+    }
+
+    fn submit(
+        &self,
+        mint: &str,
+        side: SimSide,
+        amount: OrderAmount,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        let mut state = self.state.lock().unwrap();
+        let clock = state
+            .clock
+            .ok_or_else(|| Error::TransactionSend("simulation clock not started".to_string()))?;
+        let quote_price = state
+            .curves
+            .get(mint)
+            .and_then(spot_price)
+            .ok_or_else(|| Error::TransactionSend(format!("no curve for {}", mint)))?;
+        state.next_id += 1;
+        let signature = format!("sim-{}", state.next_id);
+        state.orders.push(Order {
+            signature: signature.clone(),
+            mint: mint.to_string(),
+            side,
+            amount,
+            slippage_pct,
+            priority_fee,
+            quote_price,
+            due: clock + chrono::Duration::milliseconds(self.latency_ms as i64),
+        });
+        Ok(signature)
+    }
+
+    fn fill(&self, state: &mut SimState, order: Order) -> SimFill {
+        let mut fill = SimFill {
+            signature: order.signature,
+            mint: order.mint,
+            side: order.side,
+            sol: 0.0,
+            tokens: 0,
+            price: 0.0,
+            fees: TradeFees::default(),
+            error: None,
+        };
+        let Some(curve) = state.curves.get(&fill.mint) else {
+            fill.error = Some("no curve".to_string());
+            return fill;
+        };
+        let held = state.holdings.get(&fill.mint).copied().unwrap_or(0);
+        let haircut = 1.0 - self.slippage_pct / 100.0;
+
+        let (sol, tokens) = match (order.side, order.amount) {
+            (SimSide::Buy, OrderAmount::Sol(sol)) => {
+                let tokens = curve
+                    .calculate_buy_tokens(price::sol_to_lamports(sol))
+                    .map(|t| (t as f64 * haircut) as u64);
+                (sol, tokens)
+            }
+            (SimSide::Sell, amount) => {
+                let tokens = match amount {
+                    OrderAmount::Pct(pct) => (held as f64 * pct / 100.0) as u64,
+                    OrderAmount::Tokens(tokens) => tokens.min(held),
+                    OrderAmount::Sol(_) => 0,
+                };
+                let sol = curve
+                    .calculate_sell_sol(tokens)
+                    .map(|lamports| price::lamports_to_sol(lamports) * haircut);
+                match sol {
+                    Ok(sol) => (sol, Ok(tokens)),
+                    Err(e) => (0.0, Err(e)),
+                }
+            }
+            (SimSide::Buy, _) => (0.0, Ok(0)),
+        };
+        let tokens = match tokens {
+            Ok(tokens) if tokens > 0 => tokens,
+            Ok(_) => {
+                fill.error = Some("nothing to trade".to_string());
+                return fill;
+            }
+            Err(e) => {
+                fill.error = Some(e.to_string());
+                return fill;
+            }
+        };
+
+        let fill_price = sol / price::tokens_to_human(tokens, price::DEFAULT_TOKEN_DECIMALS);
+        let worse_pct = match order.side {
+            SimSide::Buy => (fill_price - order.quote_price) / order.quote_price * 100.0,
+            SimSide::Sell => (order.quote_price - fill_price) / order.quote_price * 100.0,
+        };
+        if worse_pct > order.slippage_pct as f64 {
+            fill.error = Some(format!(
+                "slippage {:.1}% exceeds {}% tolerance",
+                worse_pct, order.slippage_pct
+            ));
+            return fill;
+        }
+
+        let holding = state.holdings.entry(fill.mint.clone()).or_insert(0);
+        match order.side {
+            SimSide::Buy => *holding += tokens,
+            SimSide::Sell => *holding -= tokens,
+        }
+        fill.sol = sol;
+        fill.tokens = tokens;
+        fill.price = fill_price;
+        fill.fees = TradeFees::estimate(order.priority_fee, 0, sol, self.platform_fee_pct);
+        fill
+    }
+}
+
+/// SOL per whole token (the curve price is lamports per raw token)
+fn spot_price(curve: &BondingCurve) -> Option<f64> {
+    let price = curve.get_price().ok()?;
+    Some(price * 10f64.powi(price::DEFAULT_TOKEN_DECIMALS as i32) / 1e9)
+}
+
+#[async_trait]
+impl Trader for SimulatedTrader {
+    async fn buy(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        self.submit(
+            mint,
+            SimSide::Buy,
+            OrderAmount::Sol(sol_amount),
+            slippage_pct,
+            priority_fee,
+        )
+    }
+
+    async fn sell(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        let amount = match amount.strip_suffix('%') {
+            Some(pct) => pct.parse().map(OrderAmount::Pct).ok(),
+            None => amount.parse().map(OrderAmount::Tokens).ok(),
+        }
+        .ok_or_else(|| Error::TransactionSend(format!("invalid sell amount: {}", amount)))?;
+        self.submit(mint, SimSide::Sell, amount, slippage_pct, priority_fee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch_curve() -> BondingCurve {
+        crate::filter::honeypot::curve_from_event(30.0, 1_073_000_000.0)
+    }
+
+    #[tokio::test]
+    async fn test_fills_after_latency() {
+        let trader = SimulatedTrader::new(500, 0.0, 1.0);
+        let start = Utc::now();
+        trader.advance(start);
+        trader.set_curve("mint", launch_curve());
+
+        let sig = trader.buy("mint", 0.1, 25, 0.0001).await.unwrap();
+        trader.advance(start + chrono::Duration::milliseconds(100));
+        assert!(trader.take_fills().is_empty());
+
+        trader.advance(start + chrono::Duration::milliseconds(600));
+        let fills = trader.take_fills();
+        assert_eq!(fills.len(), 1);
+        let fill = &fills[0];
+        assert_eq!(fill.signature, sig);
+        assert!(fill.error.is_none());
+        assert!(fill.tokens > 0);
+        assert_eq!(trader.holdings("mint"), fill.tokens);
+        assert!((fill.fees.platform_fee_sol - 0.001).abs() < 1e-12);
+
+        trader.sell("mint", "100%", 25, 0.0001).await.unwrap();
+        trader.advance(start + chrono::Duration::milliseconds(1200));
+        let fills = trader.take_fills();
+        assert_eq!(fills[0].side, SimSide::Sell);
+        assert_eq!(fills[0].tokens, fill.tokens);
+        // Round trip on an unchanged curve returns what was spent
+        assert!((fills[0].sol - 0.1).abs() < 1e-6);
+        assert_eq!(trader.holdings("mint"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_slippage_tolerance() {
+        let trader = SimulatedTrader::new(500, 0.0, 1.0);
+        let start = Utc::now();
+        trader.advance(start);
+        trader.set_curve("mint", launch_curve());
+        trader.buy("mint", 0.1, 5, 0.0).await.unwrap();
+
+        // Price runs 20% before the buy lands
+        trader.set_curve(
+            "mint",
+            crate::filter::honeypot::curve_from_event(33.0, 975_000_000.0),
+        );
+        trader.advance(start + chrono::Duration::seconds(1));
+        let fills = trader.take_fills();
+        assert!(fills[0].error.as_deref().unwrap().contains("slippage"));
+        assert_eq!(trader.holdings("mint"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_mint() {
+        let trader = SimulatedTrader::new(0, 0.0, 1.0);
+        trader.advance(Utc::now());
+        assert!(trader.buy("unknown", 0.1, 25, 0.0).await.is_err());
+        assert!(trader.sell("unknown", "half", 25, 0.0).await.is_err());
+    }
+}
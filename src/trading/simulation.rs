@@ -2,14 +2,16 @@
 //!
 //! Pre-flight simulation of transactions before submission.
 
+use serde::{Deserialize, Serialize};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::transaction::Transaction;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::transaction::{Transaction, VersionedTransaction};
 use tracing::{debug, info, warn};
 
 use crate::error::{Error, Result};
 
 /// Simulation result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SimulationResult {
     /// Whether simulation succeeded
     pub success: bool,
@@ -21,6 +23,30 @@ pub struct SimulationResult {
     pub logs: Vec<String>,
 }
 
+impl SimulationResult {
+    /// A transaction that could not be built or signed, so never simulated
+    pub fn not_built(error: impl std::fmt::Display) -> Self {
+        Self {
+            success: false,
+            error: Some(format!("not built: {}", error)),
+            compute_units: None,
+            logs: Vec::new(),
+        }
+    }
+
+    /// One line for logs, e.g. `ok, 61234 CU` or `would fail: ...`
+    pub fn summary(&self) -> String {
+        let units = self
+            .compute_units
+            .map(|units| format!(", {} CU", units))
+            .unwrap_or_default();
+        match self.error {
+            Some(ref error) => format!("would fail: {}{}", error, units),
+            None => format!("ok{}", units),
+        }
+    }
+}
+
 /// Simulate a transaction before sending
 pub async fn simulate_transaction(
     rpc_client: &RpcClient,
@@ -54,6 +80,70 @@ pub async fn simulate_transaction(
     })
 }
 
+/// Simulate an unsigned transaction on behalf of any fee payer
+///
+/// Signature checks are skipped and the blockhash is replaced, so this can
+/// test what would happen if another wallet sent the transaction.
+pub async fn simulate_unsigned(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> Result<SimulationResult> {
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        ..Default::default()
+    };
+
+    let result = rpc_client
+        .simulate_transaction_with_config(transaction, config)
+        .map_err(|e| Error::TransactionSimulation(e.to_string()))?;
+
+    let success = result.value.err.is_none();
+    let error = result.value.err.map(|e| e.to_string());
+    if !success {
+        debug!("Unsigned simulation failed: {:?}", error);
+    }
+
+    Ok(SimulationResult {
+        success,
+        error,
+        compute_units: result.value.units_consumed,
+        logs: result.value.logs.unwrap_or_default(),
+    })
+}
+
+/// Simulate a signed transaction exactly as it would be sent
+///
+/// Signatures are verified and the transaction's own blockhash is kept, so
+/// this also catches a bad signer or a stale blockhash.
+pub async fn simulate_signed(
+    rpc_client: &RpcClient,
+    transaction: &VersionedTransaction,
+) -> Result<SimulationResult> {
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: true,
+        replace_recent_blockhash: false,
+        ..Default::default()
+    };
+
+    let result = rpc_client
+        .simulate_transaction_with_config(transaction, config)
+        .map_err(|e| Error::from_client_error("Simulation failed", e))?;
+
+    let success = result.value.err.is_none();
+    let error = result.value.err.map(|e| e.to_string());
+    if !success {
+        debug!("Signed simulation failed: {:?}", error);
+    }
+
+    Ok(SimulationResult {
+        success,
+        error,
+        compute_units: result.value.units_consumed,
+        logs: result.value.logs.unwrap_or_default(),
+    })
+}
+
 /// Simulate a Jito bundle
 pub async fn simulate_bundle(
     rpc_client: &RpcClient,
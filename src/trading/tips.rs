@@ -2,6 +2,7 @@
 //!
 //! Handles dynamic tip calculation from Jito tip stream.
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
@@ -25,6 +26,8 @@ pub struct TipPercentiles {
 pub struct TipManager {
     config: JitoConfig,
     current_tips: Arc<RwLock<TipPercentiles>>,
+    /// Chain-health priority-fee floor (lamports, 0 = none)
+    fee_floor: Arc<AtomicU64>,
 }
 
 impl TipManager {
@@ -32,6 +35,7 @@ impl TipManager {
         Self {
             config,
             current_tips: Arc::new(RwLock::new(TipPercentiles::default())),
+            fee_floor: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -64,8 +68,14 @@ impl TipManager {
             base_tip
         };
 
-        // Clamp to configured bounds
-        tip.clamp(self.config.min_tip_lamports, self.config.max_tip_lamports)
+        // Never below the chain's priority-fee floor, then clamp to configured bounds
+        tip.max(self.fee_floor.load(Ordering::Relaxed))
+            .clamp(self.config.min_tip_lamports, self.config.max_tip_lamports)
+    }
+
+    /// Raise tips to at least the chain-health priority-fee floor
+    pub fn set_fee_floor(&self, lamports: u64) {
+        self.fee_floor.store(lamports, Ordering::Relaxed);
     }
 
     /// Get current tip percentiles
@@ -136,5 +146,11 @@ mod tests {
         // Should now return p50
         let tip = manager.get_recommended_tip().await;
         assert_eq!(tip, 5000);
+
+        // A congested chain raises the tip to the fee floor (still clamped)
+        manager.set_fee_floor(20_000);
+        assert_eq!(manager.get_recommended_tip().await, 20_000);
+        manager.set_fee_floor(500_000);
+        assert_eq!(manager.get_recommended_tip().await, 100_000);
     }
 }
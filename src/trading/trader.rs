@@ -0,0 +1,55 @@
+//! Order execution interface
+//!
+//! `Trader` is what a buy or sell goes through: the PumpPortal Lightning
+//! API live, `SimulatedTrader` in backtests.
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::trading::pumpportal_api::PumpPortalTrader;
+
+/// Sends buys and sells, returning the transaction signature
+#[async_trait]
+pub trait Trader: Send + Sync {
+    /// Buy `sol_amount` SOL of `mint`
+    async fn buy(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String>;
+
+    /// Sell `amount` of `mint`: a percentage of the holding ("50%") or a raw
+    /// token amount
+    async fn sell(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String>;
+}
+
+#[async_trait]
+impl Trader for PumpPortalTrader {
+    async fn buy(
+        &self,
+        mint: &str,
+        sol_amount: f64,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        PumpPortalTrader::buy(self, mint, sol_amount, slippage_pct, priority_fee).await
+    }
+
+    async fn sell(
+        &self,
+        mint: &str,
+        amount: &str,
+        slippage_pct: u32,
+        priority_fee: f64,
+    ) -> Result<String> {
+        PumpPortalTrader::sell(self, mint, amount, slippage_pct, priority_fee).await
+    }
+}
@@ -3,17 +3,21 @@
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    signature::Keypair,
-    signer::Signer,
     system_instruction,
     transaction::Transaction,
 };
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 use std::str::FromStr;
 
 use crate::config::TradingConfig;
 use crate::error::{Error, Result};
-use crate::pump::price::{calculate_max_sol_with_slippage, calculate_min_sol_with_slippage};
-use crate::pump::program::{DISCRIMINATORS, PUMP_PROGRAM_ID};
+use crate::pump::amm::{PoolState, WSOL_MINT};
+use crate::pump::price::{
+    calculate_max_sol_with_slippage, calculate_min_sol_with_slippage, quote_buy, quote_sell,
+};
+use crate::pump::program::{BONDING_CURVE_SEED, DISCRIMINATORS, PUMP_PROGRAM_ID};
+use crate::pump::BondingCurve;
+use crate::wallet::signer::{sign_transaction, TxSigner};
 
 /// Transaction builder for pump.fun trades
 pub struct TransactionBuilder {
@@ -25,18 +29,20 @@ impl TransactionBuilder {
         Self { config }
     }
 
-    /// Build a buy transaction
-    pub fn build_buy(
+    /// Build a buy of `sol_in` lamports, quoted on `curve`
+    pub async fn build_buy(
         &self,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         mint: &Pubkey,
         bonding_curve: &Pubkey,
         associated_bonding_curve: &Pubkey,
         user_token_account: &Pubkey,
-        token_amount: u64,
-        max_sol_cost: u64,
+        curve: &BondingCurve,
+        sol_in: u64,
         recent_blockhash: solana_sdk::hash::Hash,
     ) -> Result<Transaction> {
+        let (token_amount, max_sol_cost) = self.buy_amounts(curve, sol_in)?;
+
         // Build buy instruction data
         let mut data = Vec::with_capacity(24);
         data.extend_from_slice(&DISCRIMINATORS::BUY);
@@ -66,29 +72,28 @@ impl TransactionBuilder {
             data,
         };
 
-        // Build transaction
-        let transaction = Transaction::new_signed_with_payer(
-            &[buy_instruction],
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
+        // Build and sign transaction
+        let mut transaction =
+            Transaction::new_with_payer(&[buy_instruction], Some(&payer.pubkey()));
+        sign_transaction(payer, &mut transaction, recent_blockhash).await?;
 
         Ok(transaction)
     }
 
-    /// Build a sell transaction
-    pub fn build_sell(
+    /// Build a sell of `token_amount` raw units, quoted on `curve`
+    pub async fn build_sell(
         &self,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         mint: &Pubkey,
         bonding_curve: &Pubkey,
         associated_bonding_curve: &Pubkey,
         user_token_account: &Pubkey,
+        curve: &BondingCurve,
         token_amount: u64,
-        min_sol_output: u64,
         recent_blockhash: solana_sdk::hash::Hash,
     ) -> Result<Transaction> {
+        let min_sol_output = self.sell_min_output(curve, token_amount)?;
+
         // Build sell instruction data
         let mut data = Vec::with_capacity(24);
         data.extend_from_slice(&DISCRIMINATORS::SELL);
@@ -117,30 +122,30 @@ impl TransactionBuilder {
             data,
         };
 
-        let transaction = Transaction::new_signed_with_payer(
-            &[sell_instruction],
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
+        let mut transaction =
+            Transaction::new_with_payer(&[sell_instruction], Some(&payer.pubkey()));
+        sign_transaction(payer, &mut transaction, recent_blockhash).await?;
 
         Ok(transaction)
     }
 
-    /// Build a buy transaction with tip for Jito bundle
-    pub fn build_buy_with_tip(
+    /// Build a buy of `sol_in` lamports (quoted on `curve`) with a tip for
+    /// a Jito bundle
+    pub async fn build_buy_with_tip(
         &self,
-        payer: &Keypair,
+        payer: &dyn TxSigner,
         mint: &Pubkey,
         bonding_curve: &Pubkey,
         associated_bonding_curve: &Pubkey,
         user_token_account: &Pubkey,
-        token_amount: u64,
-        max_sol_cost: u64,
+        curve: &BondingCurve,
+        sol_in: u64,
         tip_account: &Pubkey,
         tip_lamports: u64,
         recent_blockhash: solana_sdk::hash::Hash,
     ) -> Result<Transaction> {
+        let (token_amount, max_sol_cost) = self.buy_amounts(curve, sol_in)?;
+
         // Build buy instruction
         let mut buy_data = Vec::with_capacity(24);
         buy_data.extend_from_slice(&DISCRIMINATORS::BUY);
@@ -173,12 +178,74 @@ impl TransactionBuilder {
             system_instruction::transfer(&payer.pubkey(), tip_account, tip_lamports);
 
         // Combine: buy first, then tip
-        let transaction = Transaction::new_signed_with_payer(
-            &[buy_instruction, tip_instruction],
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
+        let mut transaction =
+            Transaction::new_with_payer(&[buy_instruction, tip_instruction], Some(&payer.pubkey()));
+        sign_transaction(payer, &mut transaction, recent_blockhash).await?;
+
+        Ok(transaction)
+    }
+
+    /// Build a PumpSwap buy spending `sol_in` lamports (fees included)
+    ///
+    /// The SOL is wrapped into the payer's WSOL account for the swap and
+    /// whatever the pool didn't take is unwrapped afterwards.
+    pub async fn build_amm_buy(
+        &self,
+        payer: &dyn TxSigner,
+        pool: &PoolState,
+        sol_in: u64,
+        recent_blockhash: solana_sdk::hash::Hash,
+    ) -> Result<Transaction> {
+        let user = payer.pubkey();
+        let quote = pool.quote_buy(sol_in)?;
+        let max_sol_cost = self.calculate_max_cost(sol_in);
+        let wsol_account = derive_ata(&user, &WSOL_MINT);
+
+        let instructions = [
+            create_associated_token_account_idempotent(
+                &user,
+                &user,
+                &pool.pool.base_mint,
+                &pool.base_token_program,
+            ),
+            create_associated_token_account_idempotent(&user, &user, &WSOL_MINT, &spl_token::ID),
+            system_instruction::transfer(&user, &wsol_account, max_sol_cost),
+            token_instruction(spl_token::instruction::sync_native(
+                &spl_token::ID,
+                &wsol_account,
+            ))?,
+            pool.buy_instruction(&user, quote.tokens_out, max_sol_cost),
+            close_wsol(&user, &wsol_account)?,
+        ];
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&user));
+        sign_transaction(payer, &mut transaction, recent_blockhash).await?;
+
+        Ok(transaction)
+    }
+
+    /// Build a PumpSwap sell of `token_amount` raw units, unwrapping the proceeds
+    pub async fn build_amm_sell(
+        &self,
+        payer: &dyn TxSigner,
+        pool: &PoolState,
+        token_amount: u64,
+        recent_blockhash: solana_sdk::hash::Hash,
+    ) -> Result<Transaction> {
+        let user = payer.pubkey();
+        let min_sol_output = pool
+            .quote_sell(token_amount)?
+            .min_sol_out(self.config.slippage_bps);
+        let wsol_account = derive_ata(&user, &WSOL_MINT);
+
+        let instructions = [
+            create_associated_token_account_idempotent(&user, &user, &WSOL_MINT, &spl_token::ID),
+            pool.sell_instruction(&user, token_amount, min_sol_output),
+            close_wsol(&user, &wsol_account)?,
+        ];
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&user));
+        sign_transaction(payer, &mut transaction, recent_blockhash).await?;
 
         Ok(transaction)
     }
@@ -192,6 +259,18 @@ impl TransactionBuilder {
     pub fn calculate_min_output(&self, expected_output: u64) -> u64 {
         calculate_min_sol_with_slippage(expected_output, self.config.slippage_bps)
     }
+
+    /// `token_amount` and `max_sol_cost` for a buy of `sol_in` lamports on `curve`
+    pub fn buy_amounts(&self, curve: &BondingCurve, sol_in: u64) -> Result<(u64, u64)> {
+        let quote = quote_buy(curve, sol_in)?;
+        Ok((quote.tokens_out, self.calculate_max_cost(sol_in)))
+    }
+
+    /// `min_sol_output` for selling `token_amount` on `curve`
+    pub fn sell_min_output(&self, curve: &BondingCurve, token_amount: u64) -> Result<u64> {
+        let quote = quote_sell(curve, token_amount)?;
+        Ok(quote.min_sol_out(self.config.slippage_bps))
+    }
 }
 
 /// Get the global config account address
@@ -217,6 +296,23 @@ fn event_authority() -> Result<Pubkey> {
         .map_err(|e| Error::Config(format!("Invalid event authority: {}", e)))
 }
 
+fn token_instruction(
+    instruction: std::result::Result<Instruction, solana_sdk::program_error::ProgramError>,
+) -> Result<Instruction> {
+    instruction.map_err(|e| Error::TransactionBuild(format!("Token instruction: {}", e)))
+}
+
+/// Close the payer's WSOL account, returning its lamports as SOL
+fn close_wsol(user: &Pubkey, wsol_account: &Pubkey) -> Result<Instruction> {
+    token_instruction(spl_token::instruction::close_account(
+        &spl_token::ID,
+        wsol_account,
+        user,
+        user,
+        &[],
+    ))
+}
+
 /// Derive associated token account address
 pub fn derive_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
     spl_associated_token_account::get_associated_token_address(wallet, mint)
@@ -224,7 +320,7 @@ pub fn derive_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
 
 /// Derive bonding curve PDA
 pub fn derive_bonding_curve(mint: &Pubkey) -> Result<(Pubkey, u8)> {
-    let seeds = &[b"bonding-curve", mint.as_ref()];
+    let seeds = &[BONDING_CURVE_SEED, mint.as_ref()];
     Ok(Pubkey::find_program_address(seeds, &PUMP_PROGRAM_ID))
 }
 
@@ -246,9 +342,12 @@ mod tests {
     fn test_slippage_calculation() {
         let config = TradingConfig {
             buy_amount_sol: 0.05,
+            buy_amount_usd: None,
             slippage_bps: 2500, // 25%
             priority_fee_lamports: 100000,
             simulate_before_send: false,
+            fast_trade_entries: false,
+            panic: Default::default(),
         };
         let builder = TransactionBuilder::new(config);
 
@@ -17,8 +17,21 @@
 //! - Withdraw from vault
 //! - Override emergency locks
 //! - Execute large transfers without approval
+//!
+//! # Proposal flow
+//!
+//! In `snipe start` the advisor runs every `interval_secs`: it looks at the
+//! hot wallet balance and the day's realized P&L, and records proposals in
+//! proposals.json with an expiry. Extractions within `ai_max_auto_transfer_sol`
+//! (and `min_confidence_auto`) execute at once; everything else waits for
+//! `snipe wallet proposals --approve <ID>`, which reaches the bot over the
+//! control channel. Approved proposals execute through the `WalletManager`
+//! under the `SafetyEnforcer` limits: extractions get a `TransferRecord`
+//! initiated by the AI advisor, pause/resume set or clear the emergency lock,
+//! and sizing proposals move the buy-size multiplier (never above 1.0).
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
 use tracing::{debug, info, warn};
@@ -26,6 +39,14 @@ use uuid::Uuid;
 
 use super::manager::WalletManager;
 use super::types::{AiProposal, InitiatedBy, ProposalStatus, ProposedAction, TransferReason};
+use crate::position::manager::PositionManager;
+
+/// Smallest extraction worth proposing (SOL)
+const MIN_EXTRACTION_SOL: f64 = 0.01;
+
+/// Marks an emergency lock set by an approved pause proposal; only those are
+/// ever cleared by a resume proposal
+const AI_LOCK_MARKER: &str = "AI proposal";
 
 /// AI Advisor configuration
 #[derive(Debug, Clone)]
@@ -39,11 +60,23 @@ pub struct AdvisorConfig {
     /// Balance threshold to suggest extraction (SOL)
     pub balance_extraction_trigger: f64,
 
-    /// Losing streak threshold (number of losses)
+    /// Losing trades today before suggesting smaller buys
     pub losing_streak_threshold: u32,
 
     /// Minimum confidence for auto-execution
     pub min_confidence_auto: f64,
+
+    /// Seconds between analysis cycles
+    pub interval_secs: u64,
+
+    /// Proposals not decided within this many seconds expire
+    pub proposal_ttl_secs: u64,
+
+    /// Hot balance below which pausing is suggested (SOL)
+    pub low_balance_sol: f64,
+
+    /// Floor for the buy-size multiplier sizing proposals move
+    pub min_buy_size_multiplier: f64,
 }
 
 impl Default for AdvisorConfig {
@@ -54,14 +87,28 @@ impl Default for AdvisorConfig {
             balance_extraction_trigger: 1.5,
             losing_streak_threshold: 5,
             min_confidence_auto: 0.8,
+            interval_secs: 300,
+            proposal_ttl_secs: 3600,
+            low_balance_sol: 0.1,
+            min_buy_size_multiplier: 0.25,
         }
     }
 }
 
+/// Buy-size multiplier after a sizing proposal (halve / double, never above 1.0)
+fn next_size_multiplier(action: &ProposedAction, current: f64, floor: f64) -> f64 {
+    match action {
+        ProposedAction::ReducePosition => (current * 0.5).max(floor),
+        ProposedAction::IncreaseBuyAmount => (current * 2.0).min(1.0),
+        _ => current,
+    }
+}
+
 /// AI Advisor - bounded recommendations for wallet management
 pub struct AiAdvisor {
     config: AdvisorConfig,
     wallet_manager: Arc<WalletManager>,
+    position_manager: Option<Arc<PositionManager>>,
 }
 
 impl AiAdvisor {
@@ -70,9 +117,55 @@ impl AiAdvisor {
         Self {
             config,
             wallet_manager,
+            position_manager: None,
+        }
+    }
+
+    /// Analyze realized P&L, and pause/resume through the emergency lock
+    pub fn with_position_manager(mut self, position_manager: Arc<PositionManager>) -> Self {
+        self.position_manager = Some(position_manager);
+        self
+    }
+
+    fn propose(
+        &self,
+        action: ProposedAction,
+        reasoning: String,
+        confidence: f64,
+        amount_sol: Option<f64>,
+    ) -> AiProposal {
+        let now = Utc::now();
+        AiProposal {
+            id: Uuid::new_v4().to_string(),
+            action,
+            reasoning,
+            confidence,
+            amount_sol,
+            created_at: now,
+            status: ProposalStatus::Pending,
+            status_updated_at: None,
+            expires_at: Some(now + chrono::Duration::seconds(self.config.proposal_ttl_secs as i64)),
+            outcome: None,
         }
     }
 
+    /// Emergency lock state: None = not locked, Some(true) = set by a pause
+    /// proposal
+    fn trading_paused(&self) -> Option<bool> {
+        let path = self.emergency_lock().ok()?;
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(content.contains(AI_LOCK_MARKER))
+    }
+
+    /// Multiplier approved sizing proposals apply to buys
+    pub async fn buy_size_multiplier(&self) -> f64 {
+        self.wallet_manager
+            .proposals()
+            .read()
+            .await
+            .buy_size_multiplier()
+    }
+
     /// Analyze current state and generate proposals
     pub async fn analyze(&self) -> Vec<AiProposal> {
         if !self.config.enabled {
@@ -92,6 +185,12 @@ impl AiAdvisor {
 
         let daily_stats = self.wallet_manager.daily_stats().await;
         let remaining_allowance = self.wallet_manager.remaining_daily_allowance().await;
+        let safety = self.wallet_manager.safety_config();
+        let trading = match self.position_manager {
+            Some(ref pm) => Some(pm.get_daily_stats().await),
+            None => None,
+        };
+        let size_multiplier = self.buy_size_multiplier().await;
 
         debug!(
             "AI analyzing: hot_balance={}, daily_extracted={}, remaining_allowance={}",
@@ -99,66 +198,128 @@ impl AiAdvisor {
         );
 
         // Rule 1: High balance - suggest extraction
+        let pending_profit = trading
+            .as_ref()
+            .map_or(0.0, |stats| stats.pending_extraction());
         if hot_balance > self.config.balance_extraction_trigger {
-            let extract_amount = (hot_balance - self.config.balance_extraction_trigger) * 0.5;
-            let extract_amount = extract_amount.min(remaining_allowance);
-
-            if extract_amount > 0.01 {
-                proposals.push(AiProposal {
-                    id: Uuid::new_v4().to_string(),
-                    action: ProposedAction::ExtractToVault,
-                    reasoning: format!(
+            let extract_amount = safety.bounded_extraction(
+                (hot_balance - self.config.balance_extraction_trigger) * 0.5,
+                hot_balance,
+                remaining_allowance,
+            );
+
+            if extract_amount > MIN_EXTRACTION_SOL {
+                proposals.push(self.propose(
+                    ProposedAction::ExtractToVault,
+                    format!(
                         "Hot wallet balance ({:.4} SOL) exceeds target ({:.4} SOL). \
                          Recommend extracting {:.4} SOL to vault for safety.",
                         hot_balance, self.config.balance_extraction_trigger, extract_amount
                     ),
-                    confidence: 0.85,
-                    amount_sol: Some(extract_amount),
-                    created_at: Utc::now(),
-                    status: ProposalStatus::Pending,
-                    status_updated_at: None,
-                });
+                    0.85,
+                    Some(extract_amount),
+                ));
+            }
+        } else if pending_profit >= self.config.profit_extraction_trigger {
+            // Rule 2: Realized profits waiting - bank half of them
+            let extract_amount =
+                safety.bounded_extraction(pending_profit * 0.5, hot_balance, remaining_allowance);
+
+            if extract_amount > MIN_EXTRACTION_SOL {
+                proposals.push(self.propose(
+                    ProposedAction::ExtractToVault,
+                    format!(
+                        "{:.4} SOL of realized profit not yet extracted. \
+                         Recommend banking {:.4} SOL in the vault.",
+                        pending_profit, extract_amount
+                    ),
+                    0.8,
+                    Some(extract_amount),
+                ));
             }
         }
 
-        // Rule 2: Emergency low balance
-        if hot_balance < 0.1 {
-            proposals.push(AiProposal {
-                id: Uuid::new_v4().to_string(),
-                action: ProposedAction::PauseTrading,
-                reasoning: format!(
+        // Rule 3: Emergency low balance
+        let paused = self.trading_paused();
+        if hot_balance < self.config.low_balance_sol && paused.is_none() {
+            proposals.push(self.propose(
+                ProposedAction::PauseTrading,
+                format!(
                     "Hot wallet balance critically low ({:.4} SOL). \
                      Recommend pausing trading to preserve funds.",
                     hot_balance
                 ),
-                confidence: 0.95,
-                amount_sol: None,
-                created_at: Utc::now(),
-                status: ProposalStatus::Pending,
-                status_updated_at: None,
-            });
+                0.95,
+                None,
+            ));
+        } else if paused == Some(true) && hot_balance >= self.config.low_balance_sol * 2.0 {
+            // Rule 4: Balance recovered while paused by the advisor
+            proposals.push(self.propose(
+                ProposedAction::ResumeTrading,
+                format!(
+                    "Trading is paused but the hot wallet holds {:.4} SOL again. \
+                     Recommend resuming.",
+                    hot_balance
+                ),
+                0.6,
+                None,
+            ));
         }
 
-        // Rule 3: Check for patterns (placeholder for more sophisticated analysis)
-        // In a real implementation, this could analyze:
-        // - Win/loss patterns
-        // - Time-of-day performance
-        // - Token type performance
-        // - Market conditions
+        // Rule 5: Losing day - smaller buys; winning day - restore them
+        if let Some(stats) = trading {
+            let floor = self.config.min_buy_size_multiplier;
+            if stats.losing_trades >= self.config.losing_streak_threshold
+                && stats.net_pnl_sol < 0.0
+                && size_multiplier > floor
+            {
+                let next =
+                    next_size_multiplier(&ProposedAction::ReducePosition, size_multiplier, floor);
+                proposals.push(self.propose(
+                    ProposedAction::ReducePosition,
+                    format!(
+                        "{} losing trades today, net {:.4} SOL. \
+                         Recommend cutting buy size to x{:.2}.",
+                        stats.losing_trades, stats.net_pnl_sol, next
+                    ),
+                    0.7,
+                    None,
+                ));
+            } else if size_multiplier < 1.0 && stats.net_pnl_sol > 0.0 && stats.win_rate() >= 50.0 {
+                let next = next_size_multiplier(
+                    &ProposedAction::IncreaseBuyAmount,
+                    size_multiplier,
+                    floor,
+                );
+                proposals.push(self.propose(
+                    ProposedAction::IncreaseBuyAmount,
+                    format!(
+                        "Net {:.4} SOL today at {:.0}% win rate. \
+                         Recommend restoring buy size to x{:.2}.",
+                        stats.net_pnl_sol,
+                        stats.win_rate(),
+                        next
+                    ),
+                    0.6,
+                    None,
+                ));
+            }
+        }
 
         info!("AI generated {} proposals", proposals.len());
         proposals
     }
 
     /// Execute a proposal if within AI authority bounds
-    pub async fn execute_if_authorized(
-        &self,
-        proposal: &AiProposal,
-    ) -> Result<ProposalStatus, String> {
+    ///
+    /// Leaves it pending when it needs the user: amounts above
+    /// `ai_max_auto_transfer_sol`, low confidence, and every action other
+    /// than an extraction.
+    pub async fn execute_if_authorized(&self, proposal: &mut AiProposal) {
         // Check safety bounds
         if let Err(violation) = self.wallet_manager.safety().validate_ai_authority(proposal) {
-            warn!("AI proposal blocked by safety: {}", violation);
-            return Ok(ProposalStatus::Rejected);
+            info!("AI proposal {} needs approval: {}", proposal.id, violation);
+            return;
         }
 
         // Check confidence threshold for auto-execution
@@ -167,92 +328,227 @@ impl AiAdvisor {
                 "Proposal confidence ({}) below auto-execute threshold ({})",
                 proposal.confidence, self.config.min_confidence_auto
             );
-            return Ok(ProposalStatus::Pending);
+            return;
         }
 
-        // Execute based on action type
-        match &proposal.action {
-            ProposedAction::ExtractToVault => {
-                let amount = proposal.amount_sol.ok_or("No amount specified")?;
+        // Pausing, resuming and sizing always require user approval
+        let (ProposedAction::ExtractToVault, Some(amount)) =
+            (&proposal.action, proposal.amount_sol)
+        else {
+            return;
+        };
 
-                match self
-                    .wallet_manager
-                    .extract_to_vault(
-                        amount,
-                        TransferReason::ProfitExtraction,
-                        InitiatedBy::AiAdvisor {
-                            proposal_id: proposal.id.clone(),
-                        },
-                        true,
-                    )
-                    .await
-                {
-                    Ok(record) => {
-                        info!(
-                            "AI auto-executed extraction: {} SOL (sig: {})",
-                            amount, record.signature
-                        );
-                        Ok(ProposalStatus::AutoExecuted)
-                    }
-                    Err(e) => {
-                        warn!("AI extraction failed: {}", e);
-                        Err(e.to_string())
-                    }
-                }
-            }
-            ProposedAction::PauseTrading => {
-                // Always requires user approval for safety
-                Ok(ProposalStatus::Pending)
-            }
-            ProposedAction::ResumeTrading => {
-                // Always requires user approval for safety
-                Ok(ProposalStatus::Pending)
+        let result = self
+            .wallet_manager
+            .extract_to_vault(
+                amount,
+                TransferReason::ProfitExtraction,
+                InitiatedBy::AiAdvisor {
+                    proposal_id: proposal.id.clone(),
+                },
+                true,
+            )
+            .await;
+        proposal.status_updated_at = Some(Utc::now());
+        match result {
+            Ok(record) => {
+                info!(
+                    "AI auto-executed extraction: {} SOL (sig: {})",
+                    amount, record.signature
+                );
+                self.mark_extracted(amount).await;
+                proposal.status = ProposalStatus::AutoExecuted;
+                proposal.outcome = Some(record.signature);
             }
-            ProposedAction::SkipTrade => {
-                // These are advisory only
-                Ok(ProposalStatus::Pending)
-            }
-            ProposedAction::ReducePosition => {
-                // Advisory - requires user approval
-                Ok(ProposalStatus::Pending)
-            }
-            ProposedAction::IncreaseBuyAmount => {
-                // Advisory - requires user approval
-                Ok(ProposalStatus::Pending)
+            Err(e) => {
+                warn!("AI extraction failed: {}", e);
+                proposal.status = ProposalStatus::Failed;
+                proposal.outcome = Some(e.to_string());
             }
         }
     }
 
+    /// Realized profit banked in the vault is no longer pending extraction
+    async fn mark_extracted(&self, amount: f64) {
+        if let Some(ref pm) = self.position_manager {
+            let pending = pm.get_pending_extraction().await;
+            pm.mark_profits_extracted(amount.min(pending)).await;
+        }
+    }
+
     /// Run analysis and process proposals
     pub async fn run_analysis_cycle(&self) -> Vec<AiProposal> {
+        let expired = self
+            .wallet_manager
+            .proposals()
+            .write()
+            .await
+            .expire(Utc::now());
+        if expired > 0 {
+            info!("{} AI proposals expired", expired);
+        }
+
         let proposals = self.analyze().await;
 
         let mut results = Vec::new();
 
         for mut proposal in proposals {
+            // One open proposal per action
+            if self
+                .wallet_manager
+                .proposals()
+                .read()
+                .await
+                .has_open(&proposal)
+            {
+                continue;
+            }
+
             // Try to auto-execute if within bounds
-            match self.execute_if_authorized(&proposal).await {
-                Ok(status) => {
-                    proposal.status = status;
-                    proposal.status_updated_at = Some(Utc::now());
+            self.execute_if_authorized(&mut proposal).await;
+
+            // Recorded whatever the outcome, for the audit trail
+            self.wallet_manager.add_proposal(proposal.clone()).await;
+
+            results.push(proposal);
+        }
+
+        results
+    }
+
+    /// Carry out one approved proposal; Ok = outcome description
+    async fn execute(&self, proposal: &AiProposal) -> Result<String, String> {
+        match proposal.action {
+            ProposedAction::ExtractToVault => {
+                let amount = proposal.amount_sol.ok_or("No amount specified")?;
+                let record = self
+                    .wallet_manager
+                    .extract_approved(amount, &proposal.id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                self.mark_extracted(amount).await;
+                Ok(format!(
+                    "extracted {} SOL (sig: {})",
+                    amount, record.signature
+                ))
+            }
+            ProposedAction::PauseTrading => {
+                let lock = self.emergency_lock()?;
+                let since = format!(
+                    "{} ({} {})",
+                    Utc::now().to_rfc3339(),
+                    AI_LOCK_MARKER,
+                    proposal.id
+                );
+                std::fs::write(&lock, since).map_err(|e| e.to_string())?;
+                Ok(format!("emergency lock set ({})", lock.display()))
+            }
+            ProposedAction::ResumeTrading => match self.trading_paused() {
+                None => Ok("not paused".to_string()),
+                // The advisor never overrides a lock the user set
+                Some(false) => Err(
+                    "emergency lock was set manually - snipe wallet emergency --resume".to_string(),
+                ),
+                Some(true) => {
+                    std::fs::remove_file(self.emergency_lock()?).map_err(|e| e.to_string())?;
+                    Ok("emergency lock cleared".to_string())
+                }
+            },
+            ProposedAction::ReducePosition | ProposedAction::IncreaseBuyAmount => {
+                let mut store = self.wallet_manager.proposals().write().await;
+                let next = next_size_multiplier(
+                    &proposal.action,
+                    store.buy_size_multiplier(),
+                    self.config.min_buy_size_multiplier,
+                );
+                store.set_buy_size_multiplier(next);
+                Ok(format!("buy size x{:.2}", next))
+            }
+            ProposedAction::SkipTrade => Ok("advisory only".to_string()),
+        }
+    }
+
+    fn emergency_lock(&self) -> Result<std::path::PathBuf, String> {
+        self.position_manager
+            .as_ref()
+            .and_then(|pm| pm.emergency_lock_path())
+            .ok_or_else(|| "no emergency lock path (positions not persisted)".to_string())
+    }
+
+    /// Execute every approved proposal that hasn't expired
+    pub async fn execute_approved(&self) -> Vec<AiProposal> {
+        let approved = {
+            let mut store = self.wallet_manager.proposals().write().await;
+            store.expire(Utc::now());
+            store.with_status(ProposalStatus::Approved)
+        };
+
+        let mut results = Vec::new();
+        for proposal in approved {
+            let (status, outcome) = match self.execute(&proposal).await {
+                Ok(outcome) => {
+                    info!(
+                        "Executed AI proposal {} ({}): {}",
+                        proposal.id, proposal.action, outcome
+                    );
+                    (ProposalStatus::Executed, outcome)
                 }
                 Err(e) => {
-                    warn!("Proposal execution error: {}", e);
-                    proposal.status = ProposalStatus::Rejected;
-                    proposal.status_updated_at = Some(Utc::now());
+                    warn!(
+                        "AI proposal {} ({}) failed: {}",
+                        proposal.id, proposal.action, e
+                    );
+                    (ProposalStatus::Failed, e)
                 }
+            };
+            let mut store = self.wallet_manager.proposals().write().await;
+            store.set_outcome(&proposal.id, status, outcome);
+            if let Err(e) = store.save() {
+                warn!("Could not save proposals: {}", e);
             }
-
-            // Add to wallet manager for tracking
-            if proposal.status == ProposalStatus::Pending {
-                self.wallet_manager.add_proposal(proposal.clone()).await;
+            if let Some(closed) = store.get(&proposal.id) {
+                results.push(closed.clone());
             }
-
-            results.push(proposal);
         }
-
         results
     }
+
+    /// Approve a pending proposal and execute it; returns the closed proposal
+    pub async fn approve(&self, proposal_id: &str) -> crate::error::Result<AiProposal> {
+        self.wallet_manager.approve_proposal(proposal_id).await?;
+        let executed = self.execute_approved().await;
+        executed
+            .into_iter()
+            .find(|p| p.id == proposal_id)
+            .ok_or_else(|| {
+                crate::error::Error::Config(format!(
+                    "Proposal {} expired before execution",
+                    proposal_id
+                ))
+            })
+    }
+
+    /// Reject a pending proposal
+    pub async fn reject(&self, proposal_id: &str) -> crate::error::Result<AiProposal> {
+        self.wallet_manager.reject_proposal(proposal_id).await
+    }
+
+    /// Analysis loop for `snipe start`; also executes proposals approved
+    /// while the bot was down
+    pub async fn run(self: Arc<Self>) {
+        info!(
+            "AI advisor running every {}s (auto-execute up to {} SOL)",
+            self.config.interval_secs,
+            self.wallet_manager.safety_config().ai_max_auto_transfer_sol
+        );
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            self.execute_approved().await;
+            self.run_analysis_cycle().await;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -266,4 +562,15 @@ mod tests {
         assert_eq!(config.profit_extraction_trigger, 0.3);
         assert_eq!(config.min_confidence_auto, 0.8);
     }
+
+    #[test]
+    fn test_size_multiplier_bounds() {
+        let reduce = ProposedAction::ReducePosition;
+        let increase = ProposedAction::IncreaseBuyAmount;
+        assert_eq!(next_size_multiplier(&reduce, 1.0, 0.25), 0.5);
+        assert_eq!(next_size_multiplier(&reduce, 0.4, 0.25), 0.25);
+        assert_eq!(next_size_multiplier(&increase, 0.25, 0.25), 0.5);
+        // Sizing proposals never push buys above the configured size
+        assert_eq!(next_size_multiplier(&increase, 0.75, 0.25), 1.0);
+    }
 }
@@ -1,18 +1,80 @@
 //! Credential management for wallets
 //!
 //! Loads wallet registry from wallets.json and manages keypair access.
+//! Every keypair the bot signs with is read here, plain or encrypted (see
+//! `keystore`).
+//!
+//! Loading verifies the registry against the keypair files: each hot and
+//! vault entry's address must match its keypair and the file must hash to
+//! the recorded `keypair_sha256`. A mismatch refuses to load unless
+//! `--allow-mismatch` was passed.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use sha2::{Digest, Sha256};
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
 use tracing::{debug, info, warn};
 
 use crate::error::{Error, Result};
 
+use super::keystore::{self, EncryptedKeypair, KeypairFile};
+use super::safety::SpendCap;
 use super::types::{WalletEntry, WalletRegistry, WalletType};
 
+/// Set by `--allow-mismatch`: integrity failures are logged instead of
+/// refusing to load
+static ALLOW_MISMATCH: AtomicBool = AtomicBool::new(false);
+
+/// Load wallets.json even when it doesn't match the keypair files
+pub fn set_allow_mismatch(allow: bool) {
+    ALLOW_MISMATCH.store(allow, Ordering::Relaxed);
+}
+
+/// SHA-256 of a keypair file, hex
+pub fn keypair_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Outcome of checking one wallet against its keypair file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityStatus {
+    Pass,
+    Fail,
+    /// No keypair file to check (nothing can be signed with it either)
+    Skipped,
+}
+
+impl std::fmt::Display for IntegrityStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            IntegrityStatus::Pass => "PASS",
+            IntegrityStatus::Fail => "FAIL",
+            IntegrityStatus::Skipped => "SKIP",
+        })
+    }
+}
+
+/// Integrity check of one registry entry
+#[derive(Debug, Clone)]
+pub struct IntegrityCheck {
+    pub name: String,
+    pub wallet_type: WalletType,
+    pub status: IntegrityStatus,
+    pub reasons: Vec<String>,
+    /// Hash recorded by this check (the entry had none)
+    pub recorded_hash: Option<String>,
+}
+
+impl IntegrityCheck {
+    fn fail(&mut self, reason: String) {
+        self.status = IntegrityStatus::Fail;
+        self.reasons.push(reason);
+    }
+}
+
 /// Manages wallet credentials and registry
 pub struct CredentialManager {
     /// Base directory for credentials
@@ -28,8 +90,34 @@ pub struct CredentialManager {
 impl CredentialManager {
     /// Load credential manager from directory
     ///
-    /// Expects wallets.json in the credentials directory.
+    /// Expects wallets.json in the credentials directory. Fails if the
+    /// registry doesn't match the keypair files (see `verify`), unless
+    /// mismatches are allowed.
     pub fn load(credentials_dir: &Path) -> Result<Self> {
+        let mut manager = Self::load_unverified(credentials_dir)?;
+        let failures: Vec<String> = manager
+            .verify()
+            .into_iter()
+            .filter(|check| check.status == IntegrityStatus::Fail)
+            .map(|check| format!("{}: {}", check.name, check.reasons.join("; ")))
+            .collect();
+        if failures.is_empty() {
+            return Ok(manager);
+        }
+        if ALLOW_MISMATCH.load(Ordering::Relaxed) {
+            for failure in &failures {
+                warn!("Wallet integrity check failed (allowed): {}", failure);
+            }
+            return Ok(manager);
+        }
+        Err(Error::Config(format!(
+            "Wallet integrity check failed - {} (see `snipe wallet verify`, or pass --allow-mismatch)",
+            failures.join(", ")
+        )))
+    }
+
+    /// Load the registry without checking it against the keypair files
+    pub fn load_unverified(credentials_dir: &Path) -> Result<Self> {
         let wallets_path = credentials_dir.join("wallets.json");
 
         let registry = if wallets_path.exists() {
@@ -65,7 +153,8 @@ impl CredentialManager {
 
     /// Get wallet address by name
     ///
-    /// For keypair-based wallets with AUTO_DERIVED, derives from keypair.
+    /// For keypair-based wallets with AUTO_DERIVED, derives from keypair
+    /// (encrypted keypairs don't need unlocking for this).
     pub fn get_address(&mut self, name: &str) -> Result<solana_sdk::pubkey::Pubkey> {
         let wallet = self
             .wallets
@@ -73,9 +162,10 @@ impl CredentialManager {
             .ok_or_else(|| Error::Config(format!("Wallet not found: {}", name)))?;
 
         if wallet.address == "AUTO_DERIVED" {
-            // Need to load keypair to derive address
-            let keypair = self.get_keypair(name)?;
-            Ok(keypair.pubkey())
+            if let Some(keypair) = self.loaded_keypairs.get(name) {
+                return Ok(keypair.pubkey());
+            }
+            Self::read_pubkey(&self.keypair_path(name)?)
         } else {
             // Parse stored address
             wallet
@@ -85,68 +175,140 @@ impl CredentialManager {
         }
     }
 
-    /// Load and cache keypair for a wallet
+    /// Keypair file of a wallet: its registry entry, or
+    /// `<credentials_dir>/<name>/keypair.json` for wallets not in the registry
     ///
     /// Fails for external wallets (no keypair).
-    pub fn get_keypair(&mut self, name: &str) -> Result<&Keypair> {
-        // Check if already loaded
-        if self.loaded_keypairs.contains_key(name) {
-            return Ok(self.loaded_keypairs.get(name).unwrap());
-        }
-
-        let wallet = self
-            .wallets
-            .get(name)
-            .ok_or_else(|| Error::Config(format!("Wallet not found: {}", name)))?;
+    pub fn keypair_path(&self, name: &str) -> Result<PathBuf> {
+        let Some(wallet) = self.wallets.get(name) else {
+            return Ok(self.credentials_dir.join(name).join("keypair.json"));
+        };
 
         let keypair_path = wallet.keypair_path.as_ref().ok_or_else(|| {
             Error::Config(format!("Wallet {} is external, no keypair available", name))
         })?;
 
         // Resolve relative path
-        let full_path = if keypair_path.is_absolute() {
+        Ok(if keypair_path.is_absolute() {
             keypair_path.clone()
         } else {
             self.credentials_dir
                 .parent()
                 .unwrap_or(Path::new("."))
                 .join(keypair_path)
+        })
+    }
+
+    /// Load and cache keypair for a wallet
+    ///
+    /// Fails for external wallets (no keypair).
+    pub fn get_keypair(&mut self, name: &str) -> Result<&Keypair> {
+        // Check if already loaded
+        if self.loaded_keypairs.contains_key(name) {
+            return Ok(self.loaded_keypairs.get(name).unwrap());
+        }
+
+        let keypair = Self::read_keypair(&self.keypair_path(name)?, name)?;
+        self.loaded_keypairs.insert(name.to_string(), keypair);
+        Ok(self.loaded_keypairs.get(name).unwrap())
+    }
+
+    /// Owned copy of a wallet's keypair, for signers that keep their own
+    pub fn keypair(&mut self, name: &str) -> Result<Keypair> {
+        Ok(self.get_keypair(name)?.insecure_clone())
+    }
+
+    /// Read a keypair file (plain or encrypted) after checking its permissions
+    ///
+    /// `label` names the keypair in errors and the passphrase prompt.
+    pub fn read_keypair(path: &Path, label: &str) -> Result<Keypair> {
+        debug!("Loading keypair from: {:?}", path);
+        Self::check_permissions(path, label)?;
+
+        let keypair_bytes = std::fs::read(path).map_err(|e| {
+            Error::InvalidKeypair(format!("Failed to read keypair for {}: {}", label, e))
+        })?;
+        match KeypairFile::parse(&keypair_bytes)
+            .map_err(|e| Error::InvalidKeypair(format!("{} ({})", e, label)))?
+        {
+            KeypairFile::Plain(keypair) => Ok(keypair),
+            KeypairFile::Encrypted(keystore) => keystore::unlock(&keystore, label),
+        }
+    }
+
+    /// Public key of a keypair file without unlocking it
+    pub fn read_pubkey(path: &Path) -> Result<solana_sdk::pubkey::Pubkey> {
+        let keypair_bytes = std::fs::read(path).map_err(|e| {
+            Error::InvalidKeypair(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        KeypairFile::parse(&keypair_bytes)?.pubkey()
+    }
+
+    /// Check if a wallet's keypair file is encrypted
+    pub fn is_encrypted(&self, name: &str) -> bool {
+        self.keypair_path(name)
+            .ok()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|bytes| KeypairFile::parse(&bytes).ok())
+            .is_some_and(|file| matches!(file, KeypairFile::Encrypted(_)))
+    }
+
+    /// Encrypt a wallet's keypair file in place; returns its path
+    pub fn encrypt_keypair(&mut self, name: &str, passphrase: &str) -> Result<PathBuf> {
+        let path = self.keypair_path(name)?;
+        Self::check_permissions(&path, name)?;
+        let keypair_bytes = std::fs::read(&path).map_err(|e| {
+            Error::InvalidKeypair(format!("Failed to read keypair for {}: {}", name, e))
+        })?;
+        let keypair = match KeypairFile::parse(&keypair_bytes)? {
+            KeypairFile::Plain(keypair) => keypair,
+            KeypairFile::Encrypted(_) => {
+                return Err(Error::Config(format!(
+                    "Keypair for {} is already encrypted",
+                    name
+                )))
+            }
         };
 
-        debug!("Loading keypair from: {:?}", full_path);
+        let keystore = EncryptedKeypair::encrypt(&keypair, passphrase)?;
+        let json = serde_json::to_string_pretty(&keystore)?;
+
+        // Write next to the original and swap it in, so a failure never
+        // leaves a half-written keypair
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        std::fs::rename(&tmp_path, &path)?;
+        self.record_hash(name, &path)?;
+
+        info!("Encrypted keypair for {}", name);
+        Ok(path)
+    }
 
-        // Validate permissions on Unix
+    /// Refuse keypair files other users can read (Unix only)
+    fn check_permissions(path: &Path, label: &str) -> Result<()> {
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            if let Ok(metadata) = std::fs::metadata(&full_path) {
+            if let Ok(metadata) = std::fs::metadata(path) {
                 let mode = metadata.permissions().mode();
                 if mode & 0o077 != 0 {
                     return Err(Error::InsecureKeypair(format!(
                         "Keypair {} has insecure permissions {:o}. Run 'chmod 600 {}'",
-                        name,
+                        label,
                         mode & 0o777,
-                        full_path.display()
+                        path.display()
                     )));
                 }
             }
         }
-
-        // Load keypair
-        let keypair_bytes = std::fs::read(&full_path).map_err(|e| {
-            Error::InvalidKeypair(format!("Failed to read keypair for {}: {}", name, e))
-        })?;
-
-        let keypair_json: Vec<u8> = serde_json::from_slice(&keypair_bytes).map_err(|e| {
-            Error::InvalidKeypair(format!("Failed to parse keypair JSON for {}: {}", name, e))
-        })?;
-
-        let keypair = Keypair::from_bytes(&keypair_json).map_err(|e| {
-            Error::InvalidKeypair(format!("Invalid keypair bytes for {}: {}", name, e))
-        })?;
-
-        self.loaded_keypairs.insert(name.to_string(), keypair);
-        Ok(self.loaded_keypairs.get(name).unwrap())
+        #[cfg(not(unix))]
+        let _ = (path, label);
+        Ok(())
     }
 
     /// List all wallet entries
@@ -163,7 +325,13 @@ impl CredentialManager {
             )));
         }
 
-        self.wallets.insert(entry.name.clone(), entry);
+        let name = entry.name.clone();
+        self.wallets.insert(name.clone(), entry);
+        if let Ok(path) = self.keypair_path(&name) {
+            if path.exists() {
+                return self.record_hash(&name, &path);
+            }
+        }
         self.save_registry()?;
 
         Ok(())
@@ -181,6 +349,150 @@ impl CredentialManager {
         Ok(())
     }
 
+    /// Set or clear a wallet's daily spend cap
+    pub fn set_daily_spend_limit(&mut self, name: &str, limit_sol: Option<f64>) -> Result<()> {
+        let wallet = self
+            .wallets
+            .get_mut(name)
+            .ok_or_else(|| Error::Config(format!("Wallet not found: {}", name)))?;
+        wallet.daily_spend_limit_sol = limit_sol;
+        self.save_registry()
+    }
+
+    /// Daily spend caps by wallet address
+    ///
+    /// A capped wallet whose address can't be resolved is logged and left
+    /// out.
+    pub fn spend_caps(&mut self) -> HashMap<solana_sdk::pubkey::Pubkey, SpendCap> {
+        let capped: Vec<(String, f64)> = self
+            .wallets
+            .values()
+            .filter_map(|w| Some((w.name.clone(), w.daily_spend_limit_sol?)))
+            .collect();
+        let mut caps = HashMap::new();
+        for (name, daily_limit_sol) in capped {
+            match self.get_address(&name) {
+                Ok(address) => {
+                    caps.insert(
+                        address,
+                        SpendCap {
+                            wallet: name,
+                            daily_limit_sol,
+                        },
+                    );
+                }
+                Err(e) => warn!("Spend cap of {} not applied: {}", name, e),
+            }
+        }
+        caps
+    }
+
+    /// Check every hot and vault wallet against its keypair file
+    ///
+    /// A stored address must match the keypair's public key, and the file
+    /// must hash to the recorded `keypair_sha256`. Entries without a hash
+    /// get the current one recorded (and wallets.json saved).
+    pub fn verify(&mut self) -> Vec<IntegrityCheck> {
+        let mut names: Vec<String> = self
+            .wallets
+            .values()
+            .filter(|w| matches!(w.wallet_type, WalletType::Hot | WalletType::Vault))
+            .map(|w| w.name.clone())
+            .collect();
+        names.sort();
+
+        let checks: Vec<IntegrityCheck> = names.iter().map(|name| self.check_entry(name)).collect();
+        let mut recorded = false;
+        for check in &checks {
+            if let (Some(hash), Some(wallet)) =
+                (&check.recorded_hash, self.wallets.get_mut(&check.name))
+            {
+                wallet.keypair_sha256 = Some(hash.clone());
+                recorded = true;
+            }
+        }
+        if recorded {
+            if let Err(e) = self.save_registry() {
+                warn!("Could not record keypair hashes: {}", e);
+            }
+        }
+        checks
+    }
+
+    /// Record the current hash of a registered wallet's keypair file
+    fn record_hash(&mut self, name: &str, path: &Path) -> Result<()> {
+        let Some(wallet) = self.wallets.get_mut(name) else {
+            return Ok(());
+        };
+        wallet.keypair_sha256 = Some(keypair_hash(&std::fs::read(path)?));
+        self.save_registry()
+    }
+
+    fn check_entry(&self, name: &str) -> IntegrityCheck {
+        let wallet = &self.wallets[name];
+        let mut check = IntegrityCheck {
+            name: name.to_string(),
+            wallet_type: wallet.wallet_type,
+            status: IntegrityStatus::Pass,
+            reasons: Vec::new(),
+            recorded_hash: None,
+        };
+        if wallet.keypair_path.is_none() {
+            check.status = IntegrityStatus::Skipped;
+            check.reasons.push("no keypair file".to_string());
+            return check;
+        }
+
+        let path = match self.keypair_path(name) {
+            Ok(path) => path,
+            Err(e) => {
+                check.fail(e.to_string());
+                return check;
+            }
+        };
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                check.status = IntegrityStatus::Skipped;
+                check
+                    .reasons
+                    .push(format!("keypair file not found: {}", path.display()));
+                return check;
+            }
+            Err(e) => {
+                check.fail(format!("cannot read {}: {}", path.display(), e));
+                return check;
+            }
+        };
+
+        match KeypairFile::parse(&bytes).and_then(|file| file.pubkey()) {
+            Ok(pubkey)
+                if wallet.address != "AUTO_DERIVED" && wallet.address != pubkey.to_string() =>
+            {
+                check.fail(format!(
+                    "address {} does not match keypair {}",
+                    wallet.address, pubkey
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => check.fail(format!("unreadable keypair: {}", e)),
+        }
+
+        let hash = keypair_hash(&bytes);
+        match &wallet.keypair_sha256 {
+            Some(recorded) if *recorded != hash => {
+                check.fail("keypair file changed since its hash was recorded".to_string());
+            }
+            Some(_) => {}
+            None if check.status == IntegrityStatus::Pass => {
+                check.reasons.push("hash recorded".to_string());
+                check.recorded_hash = Some(hash);
+            }
+            None => {}
+        }
+        check
+    }
+
     /// Save registry to wallets.json
     fn save_registry(&self) -> Result<()> {
         let registry = WalletRegistry {
@@ -308,4 +620,120 @@ mod tests {
         assert!(manager.get_wallet("test-wallet").is_some());
         assert!(manager.get_wallet("nonexistent").is_none());
     }
+
+    #[test]
+    fn test_encrypt_keypair_in_place() {
+        let dir = tempdir().unwrap();
+        let mut manager = CredentialManager::load(dir.path()).unwrap();
+
+        // Not in the registry: the conventional <dir>/<name>/keypair.json
+        let keypair = Keypair::new();
+        let path = manager.keypair_path("hot").unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        assert!(!manager.is_encrypted("hot"));
+
+        manager.encrypt_keypair("hot", "hunter2").unwrap();
+        assert!(manager.is_encrypted("hot"));
+        // The address still resolves without the passphrase
+        assert_eq!(
+            CredentialManager::read_pubkey(&path).unwrap(),
+            keypair.pubkey()
+        );
+        let KeypairFile::Encrypted(keystore) =
+            KeypairFile::parse(&std::fs::read(&path).unwrap()).unwrap()
+        else {
+            panic!("expected an encrypted keystore");
+        };
+        assert_eq!(
+            keystore.decrypt("hunter2").unwrap().to_bytes(),
+            keypair.to_bytes()
+        );
+        assert!(manager.encrypt_keypair("hot", "hunter2").is_err());
+    }
+
+    /// Write a plain keypair file (0600) and register it as the hot wallet
+    fn write_hot_wallet(dir: &Path, address: &str) -> (PathBuf, Keypair) {
+        let keypair = Keypair::new();
+        let path = dir.join("hot.json");
+        std::fs::write(
+            &path,
+            serde_json::to_string(&keypair.to_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let address = if address.is_empty() {
+            keypair.pubkey().to_string()
+        } else {
+            address.to_string()
+        };
+        let registry = serde_json::json!({
+            "version": "1.0",
+            "wallets": [{
+                "name": "hot",
+                "alias": "Hot",
+                "type": "hot",
+                "keypair_path": path,
+                "address": address,
+                "created_at": "2025-01-01T00:00:00Z"
+            }]
+        });
+        std::fs::write(dir.join("wallets.json"), registry.to_string()).unwrap();
+        (path, keypair)
+    }
+
+    #[test]
+    fn test_integrity_records_and_checks_hash() {
+        let dir = tempdir().unwrap();
+        let (path, _) = write_hot_wallet(dir.path(), "");
+
+        // First load records the hash
+        let manager = CredentialManager::load(dir.path()).unwrap();
+        let recorded = manager.get_wallet("hot").unwrap().keypair_sha256.clone();
+        assert_eq!(recorded, Some(keypair_hash(&std::fs::read(&path).unwrap())));
+        let mut manager = CredentialManager::load(dir.path()).unwrap();
+        assert!(manager
+            .verify()
+            .iter()
+            .all(|check| check.status == IntegrityStatus::Pass));
+
+        // Swapping the keypair file is caught
+        std::fs::write(
+            &path,
+            serde_json::to_string(&Keypair::new().to_bytes().to_vec()).unwrap(),
+        )
+        .unwrap();
+        assert!(CredentialManager::load(dir.path()).is_err());
+        let mut manager = CredentialManager::load_unverified(dir.path()).unwrap();
+        let checks = manager.verify();
+        assert_eq!(checks[0].status, IntegrityStatus::Fail);
+        assert!(checks[0].reasons.iter().any(|r| r.contains("changed")));
+    }
+
+    #[test]
+    fn test_integrity_address_mismatch() {
+        let dir = tempdir().unwrap();
+        write_hot_wallet(dir.path(), "11111111111111111111111111111111");
+
+        assert!(CredentialManager::load(dir.path()).is_err());
+        let mut manager = CredentialManager::load_unverified(dir.path()).unwrap();
+        let checks = manager.verify();
+        assert_eq!(checks[0].status, IntegrityStatus::Fail);
+        assert!(checks[0].reasons[0].contains("does not match"));
+        // A failing entry never gets a hash recorded
+        assert!(manager.get_wallet("hot").unwrap().keypair_sha256.is_none());
+    }
 }
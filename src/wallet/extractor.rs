@@ -2,18 +2,37 @@
 //!
 //! Monitors conditions and triggers automatic profit extraction to vault.
 //! Integrates with position manager to extract realized profits.
+//!
+//! Rules, checked in order every `check_interval_secs` (plus up to
+//! `jitter_secs`) by the running bot:
+//! - `hot_excess`: the hot wallet holds more than `min_hot_balance_sol +
+//!   hot_buffer_sol` by `excess_trigger_sol` - extract down to that floor
+//! - `daily`: once a day from `daily_hour_utc` - extract down to the floor
+//! - `balance_ceiling`: extract whatever is above the ceiling
+//! - `realized_profits`: bank a share of realized profits
+//!
+//! Every amount is cut to the wallet safety limits (daily allowance, single
+//! transfer cap, minimum hot balance). Nothing is extracted while more than
+//! `max_open_exposure_sol` is in open positions, during an emergency lock,
+//! or within `min_extraction_interval_secs` of the last extraction.
+//! The cooldown and the daily rule's last run come from the transfer history,
+//! so they survive a restart.
 
 use std::sync::Arc;
 use std::time::Duration;
 
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use rand::Rng;
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
 use super::manager::WalletManager;
-use super::types::{InitiatedBy, TransferReason};
+use super::types::{InitiatedBy, TransferReason, TransferRecord};
 use crate::position::manager::PositionManager;
 
+/// Smallest extraction worth a transaction (SOL)
+const MIN_EXTRACTION_SOL: f64 = 0.01;
+
 /// Extraction configuration
 #[derive(Debug, Clone)]
 pub struct ExtractionConfig {
@@ -34,6 +53,21 @@ pub struct ExtractionConfig {
 
     /// Check interval in seconds
     pub check_interval_secs: u64,
+
+    /// Random delay added to each check interval (seconds)
+    pub jitter_secs: u64,
+
+    /// SOL kept in the hot wallet above `min_hot_balance_sol`
+    pub hot_buffer_sol: f64,
+
+    /// Extract once the hot wallet is this far above its floor (0 = off)
+    pub excess_trigger_sol: f64,
+
+    /// UTC hour of the daily extraction down to the floor (None = off)
+    pub daily_hour_utc: Option<u32>,
+
+    /// Skip extraction while more SOL than this is in open positions (0 = no limit)
+    pub max_open_exposure_sol: f64,
 }
 
 impl Default for ExtractionConfig {
@@ -45,16 +79,48 @@ impl Default for ExtractionConfig {
             balance_ceiling_sol: 2.0,
             min_extraction_interval_secs: 3600, // 1 hour
             check_interval_secs: 60,            // 1 minute
+            jitter_secs: 30,
+            hot_buffer_sol: 1.0,
+            excess_trigger_sol: 0.0,
+            daily_hour_utc: None,
+            max_open_exposure_sol: 1.0,
         }
     }
 }
 
+impl ExtractionConfig {
+    /// Balance the `hot_excess` and `daily` rules extract down to
+    pub fn hot_floor(&self, min_hot_balance_sol: f64) -> f64 {
+        min_hot_balance_sol + self.hot_buffer_sol
+    }
+
+    /// Excess over the floor once it passes `excess_trigger_sol`
+    pub fn hot_excess(&self, hot_balance: f64, min_hot_balance_sol: f64) -> Option<f64> {
+        let excess = hot_balance - self.hot_floor(min_hot_balance_sol);
+        (self.excess_trigger_sol > 0.0 && excess > self.excess_trigger_sol).then_some(excess)
+    }
+
+    /// Check if the daily extraction is due (its hour has passed and it
+    /// hasn't run today)
+    pub fn daily_due(&self, now: DateTime<Utc>, last_daily: Option<NaiveDate>) -> bool {
+        self.daily_hour_utc
+            .is_some_and(|hour| now.hour() >= hour && last_daily != Some(now.date_naive()))
+    }
+
+    /// Check if open exposure blocks extraction
+    pub fn exposure_too_high(&self, open_exposure_sol: f64) -> bool {
+        self.max_open_exposure_sol > 0.0 && open_exposure_sol > self.max_open_exposure_sol
+    }
+}
+
 /// Profit extractor - monitors and triggers automatic extractions
 pub struct ProfitExtractor {
     config: ExtractionConfig,
     wallet_manager: Arc<WalletManager>,
     position_manager: Option<Arc<PositionManager>>,
     last_extraction: Option<chrono::DateTime<Utc>>,
+    /// Day the `daily` rule last extracted (or found nothing to extract)
+    last_daily: Option<NaiveDate>,
 }
 
 impl ProfitExtractor {
@@ -65,6 +131,7 @@ impl ProfitExtractor {
             wallet_manager,
             position_manager: None,
             last_extraction: None,
+            last_daily: None,
         }
     }
 
@@ -79,6 +146,7 @@ impl ProfitExtractor {
             wallet_manager,
             position_manager: Some(position_manager),
             last_extraction: None,
+            last_daily: None,
         }
     }
 
@@ -93,13 +161,13 @@ impl ProfitExtractor {
             "Starting profit extractor (profit threshold: {} SOL, balance ceiling: {} SOL)",
             self.config.profit_threshold_sol, self.config.balance_ceiling_sol
         );
-
-        let mut interval =
-            tokio::time::interval(Duration::from_secs(self.config.check_interval_secs));
+        self.restore_state().await;
 
         loop {
+            let jitter = rand::thread_rng().gen_range(0..=self.config.jitter_secs);
+            let delay = Duration::from_secs(self.config.check_interval_secs + jitter);
             tokio::select! {
-                _ = interval.tick() => {
+                _ = tokio::time::sleep(delay) => {
                     if let Err(e) = self.check_extraction_rules().await {
                         error!("Extraction check failed: {}", e);
                     }
@@ -112,6 +180,16 @@ impl ProfitExtractor {
         }
     }
 
+    /// Pick up the cooldown and the daily rule's last run from the history
+    async fn restore_state(&mut self) {
+        self.last_extraction = self.wallet_manager.last_auto_extraction(None).await;
+        self.last_daily = self
+            .wallet_manager
+            .last_auto_extraction(Some("daily"))
+            .await
+            .map(|at| at.date_naive());
+    }
+
     /// Check all extraction rules
     async fn check_extraction_rules(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Check if emergency locked
@@ -132,6 +210,18 @@ impl ProfitExtractor {
             }
         }
 
+        // Leave the SOL alone while a lot is riding on open positions
+        if let Some(ref pm) = self.position_manager {
+            let exposure = pm.total_position_value().await;
+            if self.config.exposure_too_high(exposure) {
+                debug!(
+                    "Skipping extraction - open exposure {:.4} SOL above {:.4} SOL",
+                    exposure, self.config.max_open_exposure_sol
+                );
+                return Ok(());
+            }
+        }
+
         // Get current hot wallet balance
         let hot_balance = match self.wallet_manager.hot_balance().await {
             Ok(b) => b,
@@ -140,8 +230,42 @@ impl ProfitExtractor {
                 return Ok(());
             }
         };
+        let now = Utc::now();
+        let min_hot = self.wallet_manager.safety_config().min_hot_balance_sol;
 
-        // Rule 1: Balance ceiling
+        // Rule 1: Hot wallet well above its floor
+        if let Some(excess) = self.config.hot_excess(hot_balance, min_hot) {
+            info!(
+                "Hot wallet {:.4} SOL is {:.4} SOL above its {:.4} SOL floor",
+                hot_balance,
+                excess,
+                self.config.hot_floor(min_hot)
+            );
+            self.trigger_extraction(excess, hot_balance, "hot_excess")
+                .await;
+            return Ok(());
+        }
+
+        // Rule 2: Daily extraction down to the floor
+        if self.config.daily_due(now, self.last_daily) {
+            let excess = hot_balance - self.config.hot_floor(min_hot);
+            if excess <= MIN_EXTRACTION_SOL {
+                debug!("Daily extraction: nothing above the floor");
+                self.last_daily = Some(now.date_naive());
+                return Ok(());
+            }
+            info!("Daily extraction: {:.4} SOL above the floor", excess);
+            if self
+                .trigger_extraction(excess, hot_balance, "daily")
+                .await
+                .is_some()
+            {
+                self.last_daily = Some(now.date_naive());
+            }
+            return Ok(());
+        }
+
+        // Rule 3: Balance ceiling
         if hot_balance > self.config.balance_ceiling_sol {
             let excess = hot_balance - self.config.balance_ceiling_sol;
             info!(
@@ -149,11 +273,12 @@ impl ProfitExtractor {
                 hot_balance, self.config.balance_ceiling_sol, excess
             );
 
-            self.trigger_extraction(excess, "balance_ceiling").await?;
+            self.trigger_extraction(excess, hot_balance, "balance_ceiling")
+                .await;
             return Ok(());
         }
 
-        // Rule 2: Profit threshold - extract realized profits if above threshold
+        // Rule 4: Profit threshold - extract realized profits if above threshold
         let pending_profits = if let Some(ref pm) = self.position_manager {
             pm.get_pending_extraction().await
         } else {
@@ -164,24 +289,21 @@ impl ProfitExtractor {
             // Calculate extraction amount (percentage of profits)
             let extract_amount = pending_profits * (self.config.profit_percentage / 100.0);
 
-            // Ensure we don't extract more than available
-            let safe_extract = extract_amount.min(hot_balance - 0.1); // Keep minimum 0.1 SOL
-
-            if safe_extract > 0.01 {
-                // Minimum extraction of 0.01 SOL
-                info!(
-                    "Realized profits threshold reached: {:.4} SOL pending, extracting {:.4} SOL ({:.0}%)",
-                    pending_profits, safe_extract, self.config.profit_percentage
-                );
+            info!(
+                "Realized profits threshold reached: {:.4} SOL pending, extracting up to {:.4} SOL ({:.0}%)",
+                pending_profits, extract_amount, self.config.profit_percentage
+            );
 
-                if self.trigger_extraction(safe_extract, "realized_profits").await.is_ok() {
-                    // Mark profits as extracted in position manager
-                    if let Some(ref pm) = self.position_manager {
-                        pm.mark_profits_extracted(safe_extract).await;
-                    }
+            if let Some(record) = self
+                .trigger_extraction(extract_amount, hot_balance, "realized_profits")
+                .await
+            {
+                // Mark profits as extracted in position manager
+                if let Some(ref pm) = self.position_manager {
+                    pm.mark_profits_extracted(record.amount_sol).await;
                 }
-                return Ok(());
             }
+            return Ok(());
         }
 
         debug!(
@@ -192,12 +314,25 @@ impl ProfitExtractor {
         Ok(())
     }
 
-    /// Trigger an extraction
+    /// Trigger an extraction, cut to the safety limits
     async fn trigger_extraction(
         &mut self,
         amount: f64,
+        hot_balance: f64,
         rule: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Option<TransferRecord> {
+        let remaining = self.wallet_manager.remaining_daily_allowance().await;
+        let amount =
+            self.wallet_manager
+                .safety_config()
+                .bounded_extraction(amount, hot_balance, remaining);
+        if amount < MIN_EXTRACTION_SOL {
+            debug!(
+                "Skipping extraction (rule: {}) - safety limits leave {:.4} SOL ({:.4} SOL of today's allowance left)",
+                rule, amount, remaining
+            );
+            return None;
+        }
         info!("Triggering extraction: {} SOL (rule: {})", amount, rule);
 
         let result = self
@@ -215,18 +350,18 @@ impl ProfitExtractor {
         match result {
             Ok(record) => {
                 info!(
-                    "Auto-extraction successful: {} SOL (sig: {})",
-                    record.amount_sol, record.signature
+                    "Auto-extraction successful: {} SOL to vault (rule: {}, sig: {})",
+                    record.amount_sol, rule, record.signature
                 );
                 self.last_extraction = Some(Utc::now());
+                Some(record)
             }
             Err(e) => {
-                warn!("Auto-extraction failed: {}", e);
+                warn!("Auto-extraction failed (rule: {}): {}", rule, e);
                 // Don't update last_extraction on failure
+                None
             }
         }
-
-        Ok(())
     }
 
     /// Manually trigger extraction check
@@ -260,4 +395,42 @@ mod tests {
         assert_eq!(config.profit_percentage, 50.0);
         assert_eq!(config.balance_ceiling_sol, 2.0);
     }
+
+    #[test]
+    fn test_excess_and_exposure_rules() {
+        let mut config = ExtractionConfig::default();
+        // Off by default
+        assert_eq!(config.hot_excess(5.0, 0.1), None);
+
+        config.excess_trigger_sol = 0.5;
+        // Floor is 0.1 + 1.0 buffer; 1.5 is only 0.4 above it
+        assert_eq!(config.hot_excess(1.5, 0.1), None);
+        let excess = config.hot_excess(2.1, 0.1).unwrap();
+        assert!((excess - 1.0).abs() < 1e-9);
+
+        assert!(!config.exposure_too_high(1.0));
+        assert!(config.exposure_too_high(1.5));
+        config.max_open_exposure_sol = 0.0;
+        assert!(!config.exposure_too_high(100.0));
+    }
+
+    #[test]
+    fn test_daily_due() {
+        let mut config = ExtractionConfig::default();
+        let at = |h: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        assert!(!config.daily_due(at(23), None));
+
+        config.daily_hour_utc = Some(18);
+        assert!(!config.daily_due(at(17), None));
+        assert!(config.daily_due(at(18), None));
+        // Once per day
+        assert!(!config.daily_due(at(20), Some(at(18).date_naive())));
+        assert!(config.daily_due(at(20), Some(at(0).date_naive().pred_opt().unwrap())));
+    }
 }
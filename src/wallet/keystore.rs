@@ -0,0 +1,354 @@
+//! Encrypted keypair storage
+//!
+//! `snipe wallet encrypt <name>` rewrites a keypair file (a JSON byte array)
+//! in place as an encrypted keystore: the secret key sealed with
+//! ChaCha20-Poly1305 under a key derived from a passphrase with Argon2id.
+//! The public key stays readable, so addresses resolve without unlocking.
+//! `CredentialManager` reads both formats.
+//!
+//! The passphrase comes from `SNIPER_KEYPAIR_PASSPHRASE`, the OS keyring
+//! (builds with `--features keyring`) or an interactive prompt, in that
+//! order. Once one unlocks a keypair it is reused for the rest of the process.
+
+use std::io::IsTerminal;
+use std::sync::Mutex;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use tracing::debug;
+use zeroize::Zeroizing;
+
+use crate::error::{Error, Result};
+
+/// Env var holding the keystore passphrase
+pub const PASSPHRASE_ENV: &str = "SNIPER_KEYPAIR_PASSPHRASE";
+
+/// OS keyring entry holding the passphrase
+pub const KEYRING_SERVICE: &str = "pumpfun-sniper";
+#[cfg(feature = "keyring")]
+const KEYRING_USER: &str = "keystore";
+
+const KEYSTORE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+/// Largest Argon2 memory cost accepted from a keystore file (1 GiB), so a
+/// tampered file can't make unlocking exhaust memory
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+
+/// Passphrase that last unlocked a keypair, wiped when replaced
+static UNLOCKED_PASSPHRASE: Mutex<Option<Zeroizing<String>>> = Mutex::new(None);
+
+/// Argon2id cost parameters, stored with each keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // OWASP minimum for Argon2id
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Encrypted keypair file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedKeypair {
+    pub version: u32,
+    /// Base58 public key (not secret)
+    pub pubkey: String,
+    pub kdf: KdfParams,
+    /// Base64 fields
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl EncryptedKeypair {
+    /// Seal a keypair with the default cost parameters
+    pub fn encrypt(keypair: &Keypair, passphrase: &str) -> Result<Self> {
+        Self::encrypt_with(keypair, passphrase, KdfParams::default())
+    }
+
+    pub fn encrypt_with(keypair: &Keypair, passphrase: &str, kdf: KdfParams) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let key = derive_key(passphrase, &salt, &kdf)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), keypair.to_bytes().as_slice())
+            .map_err(|_| Error::InvalidKeypair("Failed to encrypt keypair".to_string()))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            pubkey: keypair.pubkey().to_string(),
+            kdf,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    /// Open the keystore; a wrong passphrase fails authentication
+    pub fn decrypt(&self, passphrase: &str) -> Result<Keypair> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(Error::KeypairDecryption(format!(
+                "unsupported keystore version {}",
+                self.version
+            )));
+        }
+        let decode = |field: &str, value: &str| {
+            BASE64
+                .decode(value)
+                .map_err(|e| Error::KeypairDecryption(format!("corrupt {}: {}", field, e)))
+        };
+        let salt = decode("salt", &self.salt)?;
+        let nonce = decode("nonce", &self.nonce)?;
+        let ciphertext = decode("ciphertext", &self.ciphertext)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(Error::KeypairDecryption("corrupt nonce".to_string()));
+        }
+
+        let key = derive_key(passphrase, &salt, &self.kdf)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        let secret = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| {
+                Error::KeypairDecryption("wrong passphrase or corrupted keystore".to_string())
+            })?;
+        let keypair = Keypair::from_bytes(&secret)
+            .map_err(|e| Error::KeypairDecryption(format!("invalid keypair bytes: {}", e)))?;
+        if keypair.pubkey().to_string() != self.pubkey {
+            return Err(Error::KeypairDecryption(
+                "decrypted keypair does not match the stored public key".to_string(),
+            ));
+        }
+        Ok(keypair)
+    }
+
+    pub fn pubkey(&self) -> Result<Pubkey> {
+        self.pubkey
+            .parse()
+            .map_err(|e| Error::InvalidKeypair(format!("Invalid keystore pubkey: {}", e)))
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], kdf: &KdfParams) -> Result<[u8; 32]> {
+    if kdf.memory_kib > MAX_MEMORY_KIB {
+        return Err(Error::KeypairDecryption(format!(
+            "KDF memory cost {} KiB exceeds the {} KiB limit",
+            kdf.memory_kib, MAX_MEMORY_KIB
+        )));
+    }
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| Error::KeypairDecryption(format!("invalid KDF parameters: {}", e)))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| Error::KeypairDecryption(format!("key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Keypair file contents in either format
+pub enum KeypairFile {
+    /// Solana CLI format: JSON array of the 64 secret key bytes
+    Plain(Keypair),
+    Encrypted(EncryptedKeypair),
+}
+
+impl KeypairFile {
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let is_object = bytes
+            .iter()
+            .find(|b| !b.is_ascii_whitespace())
+            .is_some_and(|b| *b == b'{');
+        if is_object {
+            let keystore = serde_json::from_slice(bytes)
+                .map_err(|e| Error::InvalidKeypair(format!("Failed to parse keystore: {}", e)))?;
+            return Ok(Self::Encrypted(keystore));
+        }
+        let secret: Vec<u8> = serde_json::from_slice(bytes)
+            .map_err(|e| Error::InvalidKeypair(format!("Failed to parse keypair JSON: {}", e)))?;
+        let keypair = Keypair::from_bytes(&secret)
+            .map_err(|e| Error::InvalidKeypair(format!("Invalid keypair bytes: {}", e)))?;
+        Ok(Self::Plain(keypair))
+    }
+
+    pub fn pubkey(&self) -> Result<Pubkey> {
+        match self {
+            Self::Plain(keypair) => Ok(keypair.pubkey()),
+            Self::Encrypted(keystore) => keystore.pubkey(),
+        }
+    }
+}
+
+/// Decrypt a keystore with the process passphrase (see the module docs)
+pub fn unlock(keystore: &EncryptedKeypair, label: &str) -> Result<Keypair> {
+    let cached = UNLOCKED_PASSPHRASE.lock().unwrap().clone();
+    if let Some(passphrase) = cached {
+        if let Ok(keypair) = keystore.decrypt(&passphrase) {
+            return Ok(keypair);
+        }
+    }
+
+    let (passphrase, source) = find_passphrase(label)?;
+    let passphrase = Zeroizing::new(passphrase);
+    let keypair = keystore.decrypt(&passphrase).map_err(|e| match e {
+        Error::KeypairDecryption(reason) => Error::KeypairDecryption(format!(
+            "{} (passphrase from {}): {}",
+            label, source, reason
+        )),
+        other => other,
+    })?;
+    debug!("Unlocked keypair {} with passphrase from {}", label, source);
+    *UNLOCKED_PASSPHRASE.lock().unwrap() = Some(passphrase);
+    Ok(keypair)
+}
+
+fn find_passphrase(label: &str) -> Result<(String, &'static str)> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok((passphrase, PASSPHRASE_ENV));
+    }
+    if let Some(passphrase) = keyring_passphrase()? {
+        return Ok((passphrase, "OS keyring"));
+    }
+    if std::io::stdin().is_terminal() {
+        let passphrase = dialoguer::Password::new()
+            .with_prompt(format!("Passphrase for keypair {}", label))
+            .interact()
+            .map_err(|e| Error::KeypairDecryption(format!("passphrase prompt failed: {}", e)))?;
+        return Ok((passphrase, "prompt"));
+    }
+    Err(Error::KeypairDecryption(format!(
+        "keypair {} is encrypted and no passphrase is available: set {}, store it in the OS \
+         keyring (snipe wallet encrypt --keyring) or run interactively",
+        label, PASSPHRASE_ENV
+    )))
+}
+
+/// Passphrase for `snipe wallet encrypt`: the env var, or prompted twice
+pub fn new_passphrase() -> Result<Zeroizing<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(Zeroizing::new(passphrase));
+    }
+    if let Some(passphrase) = UNLOCKED_PASSPHRASE.lock().unwrap().clone() {
+        return Ok(passphrase);
+    }
+    if !std::io::stdin().is_terminal() {
+        return Err(Error::Config(format!(
+            "No passphrase: set {} or run interactively",
+            PASSPHRASE_ENV
+        )));
+    }
+    let passphrase = dialoguer::Password::new()
+        .with_prompt("New keystore passphrase")
+        .with_confirmation("Repeat passphrase", "Passphrases don't match")
+        .interact()
+        .map_err(|e| Error::Config(format!("Passphrase prompt failed: {}", e)))?;
+    if passphrase.is_empty() {
+        return Err(Error::Config("Passphrase must not be empty".to_string()));
+    }
+    Ok(Zeroizing::new(passphrase))
+}
+
+#[cfg(feature = "keyring")]
+fn keyring_passphrase() -> Result<Option<String>> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| Error::KeypairDecryption(format!("OS keyring unavailable: {}", e)))?;
+    match entry.get_password() {
+        Ok(passphrase) => Ok(Some(passphrase)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(Error::KeypairDecryption(format!(
+            "OS keyring read failed: {}",
+            e
+        ))),
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn keyring_passphrase() -> Result<Option<String>> {
+    Ok(None)
+}
+
+/// Save the passphrase in the OS keyring so `snipe start` needs no prompt
+#[cfg(feature = "keyring")]
+pub fn store_in_keyring(passphrase: &str) -> Result<()> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .and_then(|entry| entry.set_password(passphrase))
+        .map_err(|e| Error::Config(format!("OS keyring write failed: {}", e)))
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn store_in_keyring(_passphrase: &str) -> Result<()> {
+    Err(Error::Config(
+        "Built without OS keyring support (cargo build --features keyring)".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap parameters so the tests stay fast
+    const TEST_KDF: KdfParams = KdfParams {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    #[test]
+    fn test_round_trip() {
+        let keypair = Keypair::new();
+        let keystore = EncryptedKeypair::encrypt_with(&keypair, "hunter2", TEST_KDF).unwrap();
+        assert_eq!(keystore.pubkey().unwrap(), keypair.pubkey());
+
+        let json = serde_json::to_vec(&keystore).unwrap();
+        let KeypairFile::Encrypted(parsed) = KeypairFile::parse(&json).unwrap() else {
+            panic!("expected an encrypted keystore");
+        };
+        let decrypted = parsed.decrypt("hunter2").unwrap();
+        assert_eq!(decrypted.to_bytes(), keypair.to_bytes());
+
+        let plain = serde_json::to_vec(&keypair.to_bytes().to_vec()).unwrap();
+        let KeypairFile::Plain(parsed) = KeypairFile::parse(&plain).unwrap() else {
+            panic!("expected a plain keypair");
+        };
+        assert_eq!(parsed.pubkey(), keypair.pubkey());
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let keypair = Keypair::new();
+        let mut keystore = EncryptedKeypair::encrypt_with(&keypair, "hunter2", TEST_KDF).unwrap();
+
+        let err = keystore.decrypt("hunter3").unwrap_err();
+        assert!(matches!(err, Error::KeypairDecryption(_)));
+        assert!(err.to_string().contains("wrong passphrase"));
+
+        // Tampering is caught the same way
+        keystore.ciphertext = BASE64.encode([0u8; 80]);
+        assert!(keystore.decrypt("hunter2").is_err());
+
+        // An inflated memory cost is refused before deriving
+        keystore.kdf.memory_kib = u32::MAX;
+        let err = keystore.decrypt("hunter2").unwrap_err();
+        assert!(err.to_string().contains("exceeds"));
+    }
+}
@@ -1,25 +1,33 @@
 //! Wallet manager - core wallet operations
 //!
 //! Coordinates credential management, safety enforcement, and transfers.
+//! `BalanceWatcher` polls the hot wallet balance for the running bot.
 
-use std::path::Path;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tokio::sync::{broadcast, RwLock};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
 
 use super::credentials::CredentialManager;
-use super::safety::{PendingTransfer, SafetyEnforcer, WalletSafetyConfig};
+use super::proposals::ProposalStore;
+use super::safety::{
+    confirmation_code, confirmation_code_hash, PendingTransfer, SafetyEnforcer, SpendCap,
+    WalletSafetyConfig,
+};
+use super::signer::{SharedSigner, TxSigner};
 use super::transfer::TransferExecutor;
 use super::types::{
-    AiProposal, InitiatedBy, ProposalStatus, TransferHistory, TransferReason, TransferRecord,
-    WalletEntry, WalletStatus, WalletType,
+    AiProposal, DualConfirmation, InitiatedBy, ProposalStatus, TransferHistory, TransferReason,
+    TransferRecord, TransferStatus, WalletEntry, WalletStatus, WalletType,
 };
 
 /// Wallet manager configuration
@@ -49,6 +57,44 @@ impl Default for WalletManagerConfig {
     }
 }
 
+/// Spend ledger file, next to wallets.json
+pub const SPEND_LEDGER_FILE: &str = "spend_ledger.json";
+
+/// Spend cap of the hot wallet from the registry
+fn hot_spend_cap(creds: &CredentialManager, hot_wallet_name: &str) -> Option<SpendCap> {
+    let limit = creds.get_wallet(hot_wallet_name)?.daily_spend_limit_sol?;
+    Some(SpendCap {
+        wallet: hot_wallet_name.to_string(),
+        daily_limit_sol: limit,
+    })
+}
+
+/// Safety enforcer for the buy path, sharing the wallet manager's spend
+/// ledger and caps (the hot wallet's under `hot_address`)
+pub fn spend_enforcer(config: &WalletManagerConfig, hot_address: Pubkey) -> Result<SafetyEnforcer> {
+    let credentials_path = Path::new(&config.credentials_dir);
+    let mut creds = CredentialManager::load(credentials_path)?;
+    let mut safety = SafetyEnforcer::new(config.safety.clone());
+    safety.set_spend_ledger_path(credentials_path.join(SPEND_LEDGER_FILE));
+    safety.set_spend_caps(creds.spend_caps());
+    if let Some(cap) = hot_spend_cap(&creds, &config.hot_wallet_name) {
+        safety.set_spend_cap(hot_address, cap);
+    }
+    Ok(safety)
+}
+
+/// Resolved destination of a transfer
+#[derive(Debug, Clone)]
+pub struct TransferDestination {
+    /// Registry name (the address itself for an unregistered address)
+    pub name: String,
+
+    /// External unless registered
+    pub wallet_type: WalletType,
+
+    pub address: Pubkey,
+}
+
 /// Core wallet manager
 pub struct WalletManager {
     /// Credential manager
@@ -70,11 +116,15 @@ pub struct WalletManager {
     /// Transfer history
     history: Arc<RwLock<TransferHistory>>,
 
-    /// Pending AI proposals
-    pending_proposals: Arc<RwLock<Vec<AiProposal>>>,
+    /// AI proposals (proposals.json once loaded)
+    proposals: Arc<RwLock<ProposalStore>>,
 
     /// Path to history file
     history_path: Option<String>,
+
+    /// Signs for the hot wallet instead of its registry keypair
+    /// (remote/subprocess `[wallet.signer]`)
+    hot_signer: Option<SharedSigner>,
 }
 
 impl WalletManager {
@@ -96,12 +146,15 @@ impl WalletManager {
         if let Ok(vault_addr) = cred_lock.get_address(&config.vault_wallet_name) {
             safety.set_vault_address(vault_addr);
         }
+        safety.set_spend_ledger_path(credentials_path.join(SPEND_LEDGER_FILE));
+        safety.set_spend_caps(cred_lock.spend_caps());
 
         let rpc_client = Arc::new(rpc_client);
-        let transfer_executor = TransferExecutor::new(RpcClient::new_with_timeout(
+        let mut transfer_executor = TransferExecutor::new(RpcClient::new_with_timeout(
             rpc_client.url(),
             std::time::Duration::from_secs(30),
         ));
+        transfer_executor.set_dual_confirm_above_sol(config.safety.dual_confirm_above_sol);
 
         Ok(Self {
             credentials: Arc::new(RwLock::new(cred_lock)),
@@ -110,8 +163,9 @@ impl WalletManager {
             rpc_client,
             config,
             history: Arc::new(RwLock::new(TransferHistory::default())),
-            pending_proposals: Arc::new(RwLock::new(Vec::new())),
+            proposals: Arc::new(RwLock::new(ProposalStore::default())),
             history_path: None,
+            hot_signer: None,
         })
     }
 
@@ -120,19 +174,360 @@ impl WalletManager {
         self.history_path = Some(path);
     }
 
+    /// Sign hot wallet transfers with `signer` instead of the registry keypair
+    pub fn set_hot_signer(&mut self, signer: SharedSigner) {
+        // The hot wallet's spend cap follows it to the signer's address
+        if let Some(cap) = self
+            .credentials
+            .try_read()
+            .ok()
+            .and_then(|creds| hot_spend_cap(&creds, &self.config.hot_wallet_name))
+        {
+            self.safety.set_spend_cap(signer.pubkey(), cap);
+        }
+        self.hot_signer = Some(signer);
+    }
+
     /// Get hot wallet balance in SOL
     pub async fn hot_balance(&self) -> Result<f64> {
-        let mut creds = self.credentials.write().await;
-        let address = creds.get_address(&self.config.hot_wallet_name)?;
+        let address = self.hot_address().await?;
         self.transfer_executor.get_balance_sol(&address)
     }
 
+    /// Hot wallet address (the external signer's, if one is set)
+    async fn hot_address(&self) -> Result<Pubkey> {
+        if let Some(signer) = &self.hot_signer {
+            return Ok(signer.pubkey());
+        }
+        let mut creds = self.credentials.write().await;
+        creds.get_address(&self.config.hot_wallet_name)
+    }
+
     /// Get vault address
     pub async fn vault_address(&self) -> Result<Pubkey> {
         let mut creds = self.credentials.write().await;
         creds.get_address(&self.config.vault_wallet_name)
     }
 
+    /// Address of a registered wallet (the hot signer's for the hot wallet)
+    fn address_of(&self, creds: &mut CredentialManager, name: &str) -> Result<Pubkey> {
+        match &self.hot_signer {
+            Some(signer) if name == self.config.hot_wallet_name => Ok(signer.pubkey()),
+            _ => creds.get_address(name),
+        }
+    }
+
+    /// Registry entry of a wallet
+    pub async fn wallet(&self, name: &str) -> Option<WalletEntry> {
+        let creds = self.credentials.read().await;
+        creds.get_wallet(name).cloned()
+    }
+
+    /// Resolve a transfer destination given by wallet name or address
+    ///
+    /// Addresses of registered wallets resolve to that wallet; an
+    /// unregistered name resolves through its keypair file.
+    pub async fn resolve_destination(&self, to: &str) -> Result<TransferDestination> {
+        let mut creds = self.credentials.write().await;
+        let registered = |creds: &mut CredentialManager, name: &str| -> Result<_> {
+            let wallet_type = creds
+                .get_wallet(name)
+                .map(|w| w.wallet_type)
+                .ok_or_else(|| Error::Config(format!("Wallet not found: {}", name)))?;
+            Ok(TransferDestination {
+                name: name.to_string(),
+                wallet_type,
+                address: self.address_of(creds, name)?,
+            })
+        };
+
+        if creds.get_wallet(to).is_some() {
+            return registered(&mut creds, to);
+        }
+        if let Ok(address) = to.parse::<Pubkey>() {
+            let names: Vec<String> = creds
+                .list_wallets()
+                .iter()
+                .map(|w| w.name.clone())
+                .collect();
+            for name in names {
+                if self.address_of(&mut creds, &name).ok() == Some(address) {
+                    return registered(&mut creds, &name);
+                }
+            }
+            return Ok(TransferDestination {
+                name: to.to_string(),
+                wallet_type: WalletType::External,
+                address,
+            });
+        }
+
+        // Unregistered wallet name - read the pubkey from its keypair file
+        let path = creds.keypair_path(to)?;
+        if !path.exists() {
+            return Err(Error::Config(format!(
+                "Destination wallet '{}' not found",
+                to
+            )));
+        }
+        Ok(TransferDestination {
+            name: to.to_string(),
+            wallet_type: WalletType::External,
+            address: CredentialManager::read_pubkey(&path)?,
+        })
+    }
+
+    /// Transfer SOL from a registered wallet (`snipe wallet transfer`)
+    ///
+    /// `to` is a wallet name or address. Goes through every safety limit and
+    /// is recorded like an extraction; a transfer out of the vault needs
+    /// `unlock_vault`.
+    pub async fn transfer(
+        &self,
+        from: &str,
+        to: &str,
+        amount_sol: f64,
+        initiated_by: InitiatedBy,
+        force: bool,
+        unlock_vault: bool,
+    ) -> Result<TransferRecord> {
+        let (pending, reason) = self
+            .pending_transfer(from, to, amount_sol, &initiated_by, unlock_vault)
+            .await?;
+        info!(
+            "Transferring {} SOL from {} to {} (reason: {}, by: {})",
+            amount_sol, from, pending.to_wallet, reason, initiated_by
+        );
+        self.execute(pending, reason, initiated_by, force).await
+    }
+
+    /// Source and destination of a transfer, with the source balance
+    async fn pending_transfer(
+        &self,
+        from: &str,
+        to: &str,
+        amount_sol: f64,
+        initiated_by: &InitiatedBy,
+        unlock_vault: bool,
+    ) -> Result<(PendingTransfer, TransferReason)> {
+        let destination = self.resolve_destination(to).await?;
+        let source = self
+            .wallet(from)
+            .await
+            .ok_or_else(|| Error::Config(format!("Source wallet '{}' not registered", from)))?;
+        let reason = TransferReason::for_manual(source.wallet_type, destination.wallet_type);
+
+        let from_address = {
+            let mut creds = self.credentials.write().await;
+            self.address_of(&mut creds, from)?
+        };
+        let pending = PendingTransfer {
+            from_wallet: source.name,
+            from_type: source.wallet_type,
+            from_balance: self.transfer_executor.get_balance_sol(&from_address)?,
+            to_wallet: destination.name,
+            to_type: destination.wallet_type,
+            to_address: destination.address,
+            amount_sol,
+            is_ai_initiated: matches!(initiated_by, InitiatedBy::AiAdvisor { .. }),
+            vault_unlocked: unlock_vault,
+        };
+        Ok((pending, reason))
+    }
+
+    /// Stage a transfer above `dual_confirm_above_sol`
+    ///
+    /// The safety limits are checked now and again on confirmation. Returns
+    /// the pending record and its one-time code, which is not stored;
+    /// `staged_by` is the operator's notifier identity.
+    pub async fn stage_transfer(
+        &self,
+        from: &str,
+        to: &str,
+        amount_sol: f64,
+        initiated_by: InitiatedBy,
+        unlock_vault: bool,
+        staged_by: &str,
+    ) -> Result<(TransferRecord, String)> {
+        self.expire_staged(Utc::now()).await;
+        let (pending, reason) = self
+            .pending_transfer(from, to, amount_sol, &initiated_by, unlock_vault)
+            .await?;
+        self.safety
+            .validate_transfer(&pending)
+            .await
+            .map_err(|e| Error::SafetyLimitExceeded(e.to_string()))?;
+
+        let code = confirmation_code();
+        let now = Utc::now();
+        let window = chrono::Duration::seconds(self.config.safety.dual_confirm_window_secs as i64);
+        let record = TransferRecord {
+            id: Uuid::new_v4().to_string(),
+            from_wallet: pending.from_wallet,
+            to_wallet: pending.to_wallet,
+            amount_sol,
+            reason,
+            signature: String::new(),
+            timestamp: now,
+            initiated_by,
+            sweep: None,
+            status: TransferStatus::Pending,
+            dual_confirmation: Some(DualConfirmation {
+                code_hash: confirmation_code_hash(&code),
+                to_address: pending.to_address.to_string(),
+                vault_unlocked: unlock_vault,
+                staged_by: staged_by.to_string(),
+                expires_at: now + window,
+                confirmed_by: None,
+            }),
+        };
+        self.record_transfer(record.clone()).await?;
+
+        info!(
+            "Staged transfer {}: {} SOL from {} to {} (confirm by {})",
+            record.id,
+            amount_sol,
+            record.from_wallet,
+            record.to_wallet,
+            now + window
+        );
+        Ok((record, code))
+    }
+
+    /// Staged transfer waiting for the given code
+    pub async fn staged_transfer(&self, code: &str) -> Option<TransferRecord> {
+        self.expire_staged(Utc::now()).await;
+        let hash = confirmation_code_hash(code);
+        let history = self.history.read().await;
+        history
+            .transfers
+            .iter()
+            .find(|t| {
+                t.status == TransferStatus::Pending
+                    && t.dual_confirmation
+                        .as_ref()
+                        .is_some_and(|c| c.code_hash == hash)
+            })
+            .cloned()
+    }
+
+    /// Confirm a staged transfer with its one-time code and send it
+    ///
+    /// `confirmed_by` (a notifier identity) must differ from the operator
+    /// who staged it.
+    pub async fn confirm_staged(&self, code: &str, confirmed_by: &str) -> Result<TransferRecord> {
+        let staged = self.staged_transfer(code).await.ok_or_else(|| {
+            Error::Config(
+                "No pending transfer matches this code (expired or already confirmed?)".to_string(),
+            )
+        })?;
+        let Some(confirmation) = staged.dual_confirmation.clone() else {
+            return Err(Error::Config(format!(
+                "Transfer {} is not staged",
+                staged.id
+            )));
+        };
+        if confirmation.staged_by == confirmed_by {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Transfer {} was staged by {}; another approver must confirm it",
+                staged.id, confirmation.staged_by
+            )));
+        }
+
+        // Limits again with the current balance, to the address pinned at staging
+        let (pending, _) = self
+            .pending_transfer(
+                &staged.from_wallet,
+                &staged.to_wallet,
+                staged.amount_sol,
+                &staged.initiated_by,
+                confirmation.vault_unlocked,
+            )
+            .await?;
+        if pending.to_address.to_string() != confirmation.to_address {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Destination {} changed since transfer {} was staged",
+                staged.to_wallet, staged.id
+            )));
+        }
+        self.safety
+            .validate_transfer(&pending)
+            .await
+            .map_err(|e| Error::SafetyLimitExceeded(e.to_string()))?;
+
+        let signature = self
+            .send(
+                &pending.from_wallet,
+                &pending.to_address,
+                staged.amount_sol,
+                Some((&staged, code)),
+            )
+            .await?;
+        if staged.reason.is_extraction() {
+            self.safety.record_extraction(staged.amount_sol).await;
+        }
+
+        let mut record = staged;
+        record.status = TransferStatus::Completed;
+        record.signature = signature.to_string();
+        record.timestamp = Utc::now();
+        if let Some(confirmation) = &mut record.dual_confirmation {
+            confirmation.confirmed_by = Some(confirmed_by.to_string());
+        }
+        {
+            let mut history = self.history.write().await;
+            if let Some(entry) = history.transfers.iter_mut().find(|t| t.id == record.id) {
+                *entry = record.clone();
+            }
+        }
+        self.save_history().await?;
+
+        info!(
+            "Confirmed transfer {}: {} SOL to {} by {} (sig: {})",
+            record.id, record.amount_sol, record.to_wallet, confirmed_by, signature
+        );
+        Ok(record)
+    }
+
+    /// Staged transfers still waiting for confirmation
+    pub async fn staged_transfers(&self) -> Vec<TransferRecord> {
+        self.expire_staged(Utc::now()).await;
+        let history = self.history.read().await;
+        history
+            .transfers
+            .iter()
+            .filter(|t| t.status == TransferStatus::Pending)
+            .cloned()
+            .collect()
+    }
+
+    /// Cancel staged transfers past their window; returns how many
+    pub async fn expire_staged(&self, now: chrono::DateTime<Utc>) -> usize {
+        let mut expired = 0;
+        {
+            let mut history = self.history.write().await;
+            for record in history.transfers.iter_mut().filter(|t| {
+                t.status == TransferStatus::Pending
+                    && t.dual_confirmation
+                        .as_ref()
+                        .is_none_or(|c| now > c.expires_at)
+            }) {
+                record.status = TransferStatus::Cancelled;
+                expired += 1;
+                warn!(
+                    "Cancelled unconfirmed transfer {}: {} SOL from {} to {}",
+                    record.id, record.amount_sol, record.from_wallet, record.to_wallet
+                );
+            }
+        }
+        if expired > 0 {
+            if let Err(e) = self.save_history().await {
+                warn!("Could not save transfer history: {}", e);
+            }
+        }
+        expired
+    }
+
     /// Extract SOL to vault
     ///
     /// # Arguments
@@ -146,42 +541,86 @@ impl WalletManager {
         reason: TransferReason,
         initiated_by: InitiatedBy,
         force: bool,
+    ) -> Result<TransferRecord> {
+        let ai_authority = matches!(initiated_by, InitiatedBy::AiAdvisor { .. });
+        self.transfer_to_vault(amount_sol, reason, initiated_by, force, ai_authority)
+            .await
+    }
+
+    /// Extract SOL to vault for an AI proposal the user approved
+    ///
+    /// The approval stands in for the confirmation and lifts the AI
+    /// auto-transfer limit; every other safety limit still applies.
+    pub async fn extract_approved(
+        &self,
+        amount_sol: f64,
+        proposal_id: &str,
+    ) -> Result<TransferRecord> {
+        self.transfer_to_vault(
+            amount_sol,
+            TransferReason::ProfitExtraction,
+            InitiatedBy::AiAdvisor {
+                proposal_id: proposal_id.to_string(),
+            },
+            true,
+            false,
+        )
+        .await
+    }
+
+    async fn transfer_to_vault(
+        &self,
+        amount_sol: f64,
+        reason: TransferReason,
+        initiated_by: InitiatedBy,
+        force: bool,
+        ai_authority: bool,
     ) -> Result<TransferRecord> {
         info!(
             "Extracting {} SOL to vault (reason: {}, by: {})",
             amount_sol, reason, initiated_by
         );
 
-        // Get credentials
-        let mut creds = self.credentials.write().await;
-
-        let hot_wallet = creds
-            .get_wallet(&self.config.hot_wallet_name)
-            .ok_or_else(|| Error::Config("Hot wallet not configured".to_string()))?
-            .clone();
-
-        let vault_wallet = creds
-            .get_wallet(&self.config.vault_wallet_name)
-            .ok_or_else(|| Error::Config("Vault wallet not configured".to_string()))?
-            .clone();
-
-        let hot_address = creds.get_address(&self.config.hot_wallet_name)?;
-        let vault_address = creds.get_address(&self.config.vault_wallet_name)?;
-
-        // Get current balance
-        let hot_balance = self.transfer_executor.get_balance_sol(&hot_address)?;
-
-        // Build pending transfer
-        let pending = PendingTransfer {
-            from_wallet: hot_wallet.name.clone(),
-            from_type: hot_wallet.wallet_type,
-            from_balance: hot_balance,
-            to_wallet: vault_wallet.name.clone(),
-            to_type: vault_wallet.wallet_type,
-            to_address: vault_address,
-            amount_sol,
-            is_ai_initiated: matches!(initiated_by, InitiatedBy::AiAdvisor { .. }),
+        let pending = {
+            let mut creds = self.credentials.write().await;
+
+            let hot_wallet = creds
+                .get_wallet(&self.config.hot_wallet_name)
+                .ok_or_else(|| Error::Config("Hot wallet not configured".to_string()))?
+                .clone();
+
+            let vault_wallet = creds
+                .get_wallet(&self.config.vault_wallet_name)
+                .ok_or_else(|| Error::Config("Vault wallet not configured".to_string()))?
+                .clone();
+
+            let hot_address = self.address_of(&mut creds, &hot_wallet.name)?;
+            let vault_address = creds.get_address(&vault_wallet.name)?;
+
+            PendingTransfer {
+                from_wallet: hot_wallet.name,
+                from_type: hot_wallet.wallet_type,
+                from_balance: self.transfer_executor.get_balance_sol(&hot_address)?,
+                to_wallet: vault_wallet.name,
+                to_type: vault_wallet.wallet_type,
+                to_address: vault_address,
+                amount_sol,
+                is_ai_initiated: ai_authority,
+                vault_unlocked: false,
+            }
         };
+        self.execute(pending, reason, initiated_by, force).await
+    }
+
+    /// Validate, send and record a transfer
+    async fn execute(
+        &self,
+        pending: PendingTransfer,
+        reason: TransferReason,
+        initiated_by: InitiatedBy,
+        force: bool,
+    ) -> Result<TransferRecord> {
+        let amount_sol = pending.amount_sol;
 
         // Validate with safety enforcer
         self.safety
@@ -189,6 +628,13 @@ impl WalletManager {
             .await
             .map_err(|e| Error::SafetyLimitExceeded(e.to_string()))?;
 
+        if self.safety.requires_dual_confirmation(amount_sol) {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Transfer of {} SOL must be staged for dual confirmation",
+                amount_sol
+            )));
+        }
+
         // Check confirmation requirement
         if !force && self.safety.requires_confirmation(amount_sol) {
             return Err(Error::Config(format!(
@@ -198,45 +644,75 @@ impl WalletManager {
         }
 
         // Execute transfer
-        let hot_keypair = creds.get_keypair(&self.config.hot_wallet_name)?;
-        let signature =
-            self.transfer_executor
-                .transfer_sol(hot_keypair, &vault_address, amount_sol)?;
+        let signature = self
+            .send(&pending.from_wallet, &pending.to_address, amount_sol, None)
+            .await?;
 
-        // Record extraction
-        self.safety.record_extraction(amount_sol).await;
+        // Record extraction (the daily cap only counts SOL leaving for good)
+        if reason.is_extraction() {
+            self.safety.record_extraction(amount_sol).await;
+        }
 
         // Create transfer record
         let record = TransferRecord {
             id: Uuid::new_v4().to_string(),
-            from_wallet: hot_wallet.name,
-            to_wallet: vault_wallet.name,
+            from_wallet: pending.from_wallet,
+            to_wallet: pending.to_wallet,
             amount_sol,
             reason,
             signature: signature.to_string(),
             timestamp: Utc::now(),
             initiated_by,
+            sweep: None,
+            status: TransferStatus::Completed,
+            dual_confirmation: None,
         };
 
-        // Save to history
-        {
-            let mut history = self.history.write().await;
-            history.transfers.insert(0, record.clone());
-
-            // Keep only last 1000 records
-            history.transfers.truncate(1000);
-        }
-
-        self.save_history().await?;
+        self.record_transfer(record.clone()).await?;
 
         info!(
-            "Extraction complete: {} SOL (sig: {})",
-            amount_sol, signature
+            "Transfer complete: {} SOL to {} (sig: {})",
+            amount_sol, record.to_wallet, signature
         );
 
         Ok(record)
     }
 
+    /// Sign and send from `from_wallet` (the hot wallet through the external
+    /// signer, if set); a staged transfer goes with its confirmation code
+    async fn send(
+        &self,
+        from_wallet: &str,
+        to_address: &Pubkey,
+        amount_sol: f64,
+        staged: Option<(&TransferRecord, &str)>,
+    ) -> Result<solana_sdk::signature::Signature> {
+        let mut creds;
+        let signer: &dyn TxSigner = match &self.hot_signer {
+            Some(signer) if from_wallet == self.config.hot_wallet_name => signer.as_ref(),
+            _ => {
+                creds = self.credentials.write().await;
+                creds.get_keypair(from_wallet)?
+            }
+        };
+        // Per-wallet daily cap, whatever the other limits allowed
+        self.safety
+            .debit_spend(&signer.pubkey(), amount_sol)
+            .map_err(|v| Error::SafetyLimitExceeded(v.to_string()))?;
+        match staged {
+            Some((record, code)) => {
+                self.transfer_executor
+                    .transfer_confirmed(signer, record, code, Utc::now())
+                    .await
+            }
+            None => {
+                self.transfer_executor
+                    .transfer_sol(signer, to_address, amount_sol)
+                    .await
+            }
+        }
+    }
+
     /// Get wallet status for all wallets
     pub async fn status(&self) -> Vec<WalletStatus> {
         // Collect wallet entries into owned data to release lock before iteration
@@ -297,52 +773,53 @@ impl WalletManager {
         history.transfers.iter().take(limit).cloned().collect()
     }
 
+    /// When an automatic rule last extracted (any rule if None)
+    pub async fn last_auto_extraction(&self, rule: Option<&str>) -> Option<chrono::DateTime<Utc>> {
+        let history = self.history.read().await;
+        history.last_auto(rule).map(|t| t.timestamp)
+    }
+
+    /// Load AI proposals from proposals.json (saved back on every change)
+    pub async fn load_proposals(&self, path: &str) -> Result<()> {
+        let store = ProposalStore::load(path)?;
+        *self.proposals.write().await = store;
+        Ok(())
+    }
+
+    /// The AI proposal store
+    pub fn proposals(&self) -> &Arc<RwLock<ProposalStore>> {
+        &self.proposals
+    }
+
     /// Add an AI proposal
     pub async fn add_proposal(&self, proposal: AiProposal) {
-        let mut proposals = self.pending_proposals.write().await;
-        proposals.push(proposal);
+        let mut proposals = self.proposals.write().await;
+        proposals.add(proposal);
+        if let Err(e) = proposals.save() {
+            warn!("Could not save proposals: {}", e);
+        }
     }
 
     /// Get pending proposals
     pub async fn pending_proposals(&self) -> Vec<AiProposal> {
-        let proposals = self.pending_proposals.read().await;
-        proposals
-            .iter()
-            .filter(|p| p.status == ProposalStatus::Pending)
-            .cloned()
-            .collect()
+        let proposals = self.proposals.read().await;
+        proposals.with_status(ProposalStatus::Pending)
     }
 
     /// Approve a proposal
-    pub async fn approve_proposal(&self, proposal_id: &str) -> Result<()> {
-        let mut proposals = self.pending_proposals.write().await;
-
-        let proposal = proposals
-            .iter_mut()
-            .find(|p| p.id == proposal_id && p.status == ProposalStatus::Pending)
-            .ok_or_else(|| Error::Config(format!("Proposal not found: {}", proposal_id)))?;
-
-        proposal.status = ProposalStatus::Approved;
-        proposal.status_updated_at = Some(Utc::now());
-
-        info!("Approved proposal: {}", proposal_id);
-        Ok(())
+    pub async fn approve_proposal(&self, proposal_id: &str) -> Result<AiProposal> {
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.approve(proposal_id, Utc::now())?;
+        proposals.save()?;
+        Ok(proposal)
     }
 
     /// Reject a proposal
-    pub async fn reject_proposal(&self, proposal_id: &str) -> Result<()> {
-        let mut proposals = self.pending_proposals.write().await;
-
-        let proposal = proposals
-            .iter_mut()
-            .find(|p| p.id == proposal_id && p.status == ProposalStatus::Pending)
-            .ok_or_else(|| Error::Config(format!("Proposal not found: {}", proposal_id)))?;
-
-        proposal.status = ProposalStatus::Rejected;
-        proposal.status_updated_at = Some(Utc::now());
-
-        info!("Rejected proposal: {}", proposal_id);
-        Ok(())
+    pub async fn reject_proposal(&self, proposal_id: &str) -> Result<AiProposal> {
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.reject(proposal_id, Utc::now())?;
+        proposals.save()?;
+        Ok(proposal)
     }
 
     /// Emergency shutdown
@@ -374,12 +851,30 @@ impl WalletManager {
         self.safety.remaining_daily_allowance().await
     }
 
+    /// Safety limits in force
+    pub fn safety_config(&self) -> &WalletSafetyConfig {
+        &self.config.safety
+    }
+
     /// Get the safety enforcer
     pub fn safety(&self) -> &Arc<SafetyEnforcer> {
         &self.safety
     }
 
     /// Save history to file
+    /// Add a record to the transfer history and save it
+    pub async fn record_transfer(&self, record: TransferRecord) -> Result<()> {
+        {
+            let mut history = self.history.write().await;
+            history.transfers.insert(0, record);
+
+            // Keep only last 1000 records
+            history.transfers.truncate(1000);
+        }
+
+        self.save_history().await
+    }
+
     async fn save_history(&self) -> Result<()> {
         if let Some(path) = &self.history_path {
             let history = self.history.read().await;
@@ -396,7 +891,34 @@ impl WalletManager {
         Ok(())
     }
 
+    /// Net SOL the hot wallet sent in transfers recorded after `since`
+    ///
+    /// Read from the history file, so transfers made by other processes
+    /// (`snipe wallet transfer` next to a running bot) count too.
+    pub async fn hot_wallet_outflow_sol(
+        &self,
+        since: chrono::DateTime<Utc>,
+        until: chrono::DateTime<Utc>,
+    ) -> f64 {
+        let hot = &self.config.hot_wallet_name;
+        if let Some(path) = &self.history_path {
+            match tokio::fs::read_to_string(path)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|data| {
+                    serde_json::from_str::<TransferHistory>(&data).map_err(|e| e.to_string())
+                }) {
+                Ok(history) => return history.net_outflow_sol(hot, since, until),
+                Err(e) => warn!("Could not read transfer history {}: {}", path, e),
+            }
+        }
+        self.history.read().await.net_outflow_sol(hot, since, until)
+    }
+
     /// Load history from file
+    ///
+    /// Today's extraction totals are rebuilt from it, so the daily limit
+    /// holds across restarts.
     pub async fn load_history(&self, path: &str) -> Result<()> {
         if let Ok(content) = tokio::fs::read_to_string(path).await {
             let loaded: TransferHistory = serde_json::from_str(&content).map_err(|e| {
@@ -407,7 +929,11 @@ impl WalletManager {
             *history = loaded;
 
             info!("Loaded {} transfer records", history.transfers.len());
+            self.safety
+                .restore_daily_stats(history.daily_stats(Utc::now()))
+                .await;
         }
+        self.expire_staged(Utc::now()).await;
         Ok(())
     }
 
@@ -423,3 +949,434 @@ impl WalletManager {
         creds.add_wallet(entry)
     }
 }
+
+/// Balance watcher settings (`[wallet.balance_watch]`)
+#[derive(Debug, Clone)]
+pub struct BalanceWatchConfig {
+    pub poll_interval_secs: u64,
+
+    /// New entries are blocked below this balance
+    pub entry_floor_sol: f64,
+
+    /// Warn below this balance
+    pub warn_below_sol: f64,
+
+    /// Alert when balance plus open position cost falls more than this
+    /// within an hour (0 = off)
+    pub max_drain_sol_per_hour: f64,
+}
+
+impl Default for BalanceWatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: 30,
+            entry_floor_sol: 0.02,
+            warn_below_sol: 0.1,
+            max_drain_sol_per_hour: 2.0,
+        }
+    }
+}
+
+impl BalanceWatchConfig {
+    pub fn level_for(&self, balance_sol: f64) -> BalanceLevel {
+        if balance_sol < self.entry_floor_sol {
+            BalanceLevel::Blocked
+        } else if balance_sol < self.warn_below_sol {
+            BalanceLevel::Low
+        } else {
+            BalanceLevel::Ok
+        }
+    }
+}
+
+/// Hot wallet balance against the watcher thresholds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceLevel {
+    #[default]
+    Ok,
+    /// Below `warn_below_sol`
+    Low,
+    /// Below `entry_floor_sol`: new entries blocked
+    Blocked,
+}
+
+impl std::fmt::Display for BalanceLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceLevel::Ok => write!(f, "ok"),
+            BalanceLevel::Low => write!(f, "low"),
+            BalanceLevel::Blocked => write!(f, "entries blocked"),
+        }
+    }
+}
+
+/// Alert raised by the balance watcher
+#[derive(Debug, Clone, PartialEq)]
+pub enum BalanceAlert {
+    /// The balance crossed a threshold
+    LevelChanged {
+        from: BalanceLevel,
+        to: BalanceLevel,
+        balance_sol: f64,
+    },
+    /// Balance plus open position cost fell faster than allowed
+    RapidDrain {
+        lost_last_hour_sol: f64,
+        balance_sol: f64,
+    },
+}
+
+impl std::fmt::Display for BalanceAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BalanceAlert::LevelChanged {
+                from,
+                to,
+                balance_sol,
+            } => write!(
+                f,
+                "Hot wallet balance {:.4} SOL: {} -> {}",
+                balance_sol, from, to
+            ),
+            BalanceAlert::RapidDrain {
+                lost_last_hour_sol,
+                balance_sol,
+            } => write!(
+                f,
+                "Hot wallet lost {:.4} SOL in the last hour (balance {:.4} SOL) - possible key compromise",
+                lost_last_hour_sol, balance_sol
+            ),
+        }
+    }
+}
+
+/// Balance watcher state (balance_watch.json, read by `snipe status`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BalanceReport {
+    /// Last poll (None = not polled yet)
+    pub updated_at: Option<chrono::DateTime<Utc>>,
+    pub balance_sol: f64,
+    /// UTC day `day_start_balance_sol` belongs to
+    pub day: Option<chrono::NaiveDate>,
+    /// First balance seen that day
+    pub day_start_balance_sol: f64,
+    pub level: BalanceLevel,
+    /// Balance plus open position cost lost within the last hour
+    pub lost_last_hour_sol: f64,
+}
+
+impl BalanceReport {
+    /// Balance change since the first poll of the UTC day
+    pub fn net_change_today_sol(&self) -> f64 {
+        self.balance_sol - self.day_start_balance_sol
+    }
+}
+
+/// Load the report written by a running bot (None if there is none yet)
+pub fn load_balance_report(path: &Path) -> Result<Option<BalanceReport>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    let report =
+        serde_json::from_str(&data).map_err(|e| Error::PositionPersistence(e.to_string()))?;
+    Ok(Some(report))
+}
+
+#[derive(Default)]
+struct WatchState {
+    report: BalanceReport,
+    /// Balance plus open position cost over the last hour
+    samples: VecDeque<(chrono::DateTime<Utc>, f64)>,
+    drain_alerted: bool,
+    /// Net SOL sent in recorded transfers since the first poll
+    transferred_sol: f64,
+}
+
+/// Polls the hot wallet balance
+///
+/// Entries are blocked below `entry_floor_sol`, which the strategy engine
+/// and the buy paths check before every entry. Threshold crossings and
+/// fast drains are logged and broadcast to subscribers. Open position cost
+/// and the transfers recorded in the history since the watcher started are
+/// added back before measuring a drain, so buys and transfers don't count
+/// as losses.
+pub struct BalanceWatcher {
+    config: BalanceWatchConfig,
+    address: Pubkey,
+    rpc_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    position_manager: Option<Arc<crate::position::manager::PositionManager>>,
+    wallet_manager: Option<Arc<WalletManager>>,
+    persistence_path: Option<PathBuf>,
+    state: std::sync::RwLock<WatchState>,
+    alerts: broadcast::Sender<BalanceAlert>,
+}
+
+impl BalanceWatcher {
+    pub fn new(
+        config: BalanceWatchConfig,
+        address: Pubkey,
+        rpc_client: Arc<solana_client::nonblocking::rpc_client::RpcClient>,
+    ) -> Self {
+        Self {
+            config,
+            address,
+            rpc_client,
+            position_manager: None,
+            wallet_manager: None,
+            persistence_path: None,
+            state: std::sync::RwLock::new(WatchState::default()),
+            alerts: broadcast::channel(16).0,
+        }
+    }
+
+    /// Count open position cost as held value when measuring drains
+    pub fn with_position_manager(
+        mut self,
+        position_manager: Arc<crate::position::manager::PositionManager>,
+    ) -> Self {
+        self.position_manager = Some(position_manager);
+        self
+    }
+
+    /// Count recorded transfers out of the hot wallet as held value when
+    /// measuring drains
+    pub fn with_wallet_manager(mut self, wallet_manager: Arc<WalletManager>) -> Self {
+        self.wallet_manager = Some(wallet_manager);
+        self
+    }
+
+    /// Save the report at `path`; today's starting balance is restored from it
+    pub fn with_persistence(mut self, path: PathBuf) -> Self {
+        match load_balance_report(&path) {
+            Ok(Some(report)) if report.day == Some(Utc::now().date_naive()) => {
+                let mut state = self.state.write().unwrap();
+                state.report.day = report.day;
+                state.report.day_start_balance_sol = report.day_start_balance_sol;
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not load balance report: {}", e),
+        }
+        self.persistence_path = Some(path);
+        self
+    }
+
+    /// Alerts as they are raised
+    pub fn subscribe(&self) -> broadcast::Receiver<BalanceAlert> {
+        self.alerts.subscribe()
+    }
+
+    pub fn report(&self) -> BalanceReport {
+        self.state.read().unwrap().report.clone()
+    }
+
+    /// Why new entries are blocked (None = allowed, or not polled yet)
+    pub fn entry_block_reason(&self) -> Option<String> {
+        let state = self.state.read().unwrap();
+        (state.report.updated_at.is_some() && state.report.level == BalanceLevel::Blocked).then(
+            || {
+                format!(
+                    "Hot wallet balance {:.4} SOL below entry floor {} SOL",
+                    state.report.balance_sol, self.config.entry_floor_sol
+                )
+            },
+        )
+    }
+
+    /// Record a balance sample; `held_sol` is value that left the balance
+    /// without being lost (open position cost, extractions). Returns the
+    /// alerts it raised.
+    pub fn record(
+        &self,
+        balance_sol: f64,
+        held_sol: f64,
+        now: chrono::DateTime<Utc>,
+    ) -> Vec<BalanceAlert> {
+        let mut alerts = Vec::new();
+        let mut state = self.state.write().unwrap();
+
+        let level = self.config.level_for(balance_sol);
+        let previous = state
+            .report
+            .updated_at
+            .map_or(BalanceLevel::Ok, |_| state.report.level);
+        if level != previous {
+            alerts.push(BalanceAlert::LevelChanged {
+                from: previous,
+                to: level,
+                balance_sol,
+            });
+        }
+
+        let today = now.date_naive();
+        if state.report.day != Some(today) {
+            state.report.day = Some(today);
+            state.report.day_start_balance_sol = balance_sol;
+        }
+
+        let cutoff = now - chrono::Duration::hours(1);
+        state.samples.push_back((now, balance_sol + held_sol));
+        while state.samples.front().is_some_and(|(at, _)| *at < cutoff) {
+            state.samples.pop_front();
+        }
+        let peak = state
+            .samples
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(f64::MIN, f64::max);
+        let lost = (peak - (balance_sol + held_sol)).max(0.0);
+        let limit = self.config.max_drain_sol_per_hour;
+        if limit > 0.0 {
+            if lost > limit && !state.drain_alerted {
+                state.drain_alerted = true;
+                alerts.push(BalanceAlert::RapidDrain {
+                    lost_last_hour_sol: lost,
+                    balance_sol,
+                });
+            } else if lost < limit / 2.0 {
+                state.drain_alerted = false;
+            }
+        }
+
+        state.report.updated_at = Some(now);
+        state.report.balance_sol = balance_sol;
+        state.report.level = level;
+        state.report.lost_last_hour_sol = lost;
+        drop(state);
+
+        for alert in &alerts {
+            match alert {
+                BalanceAlert::RapidDrain { .. } => error!("{}", alert),
+                BalanceAlert::LevelChanged { to, .. } if *to != BalanceLevel::Ok => {
+                    warn!("{}", alert)
+                }
+                BalanceAlert::LevelChanged { .. } => info!("{}", alert),
+            }
+            let _ = self.alerts.send(alert.clone());
+        }
+        alerts
+    }
+
+    /// Poll the balance once
+    pub async fn poll(&self) -> Result<()> {
+        let lamports = self.rpc_client.get_balance(&self.address).await?;
+        let now = Utc::now();
+        let mut held_sol = 0.0;
+        if let Some(position_manager) = &self.position_manager {
+            held_sol += position_manager.total_position_value().await;
+        }
+        if let Some(wallet_manager) = &self.wallet_manager {
+            // Transfers recorded since the previous sample
+            let previous = self.state.read().unwrap().report.updated_at;
+            let sent = match previous {
+                Some(since) => wallet_manager.hot_wallet_outflow_sol(since, now).await,
+                None => 0.0,
+            };
+            let mut state = self.state.write().unwrap();
+            state.transferred_sol += sent;
+            held_sol += state.transferred_sol;
+        }
+        self.record(lamports as f64 / 1e9, held_sol, now);
+        self.save();
+        Ok(())
+    }
+
+    /// Poll every `poll_interval_secs` until `shutdown` fires
+    pub fn spawn(self: Arc<Self>, mut shutdown: broadcast::Receiver<()>) {
+        info!(
+            "Balance watcher started for {} (entry floor {} SOL, warn below {} SOL)",
+            self.address, self.config.entry_floor_sol, self.config.warn_below_sol
+        );
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                self.config.poll_interval_secs,
+            ));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = self.poll().await {
+                            warn!("Balance poll failed: {}", e);
+                        }
+                    }
+                    _ = shutdown.recv() => break,
+                }
+            }
+        });
+    }
+
+    /// Save the report (failures are logged and never block trading)
+    fn save(&self) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&self.report())
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            warn!("Failed to save balance report to {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watcher() -> BalanceWatcher {
+        BalanceWatcher::new(
+            BalanceWatchConfig::default(),
+            Pubkey::new_unique(),
+            Arc::new(solana_client::nonblocking::rpc_client::RpcClient::new(
+                "http://127.0.0.1:1".to_string(),
+            )),
+        )
+    }
+
+    #[test]
+    fn test_balance_levels() {
+        let watcher = watcher();
+        let now = Utc::now();
+        // Nothing is blocked before the first poll
+        assert!(watcher.entry_block_reason().is_none());
+
+        assert!(watcher.record(1.0, 0.0, now).is_empty());
+        let alerts = watcher.record(0.05, 0.95, now);
+        assert_eq!(
+            alerts,
+            vec![BalanceAlert::LevelChanged {
+                from: BalanceLevel::Ok,
+                to: BalanceLevel::Low,
+                balance_sol: 0.05
+            }]
+        );
+        assert!(watcher.entry_block_reason().is_none());
+        watcher.record(0.01, 0.99, now);
+        assert!(watcher.entry_block_reason().is_some());
+        assert!((watcher.report().net_change_today_sol() + 0.99).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rapid_drain() {
+        let watcher = watcher();
+        let start = Utc::now();
+        watcher.record(5.0, 0.0, start);
+        // Buying moves SOL into positions, not a loss
+        assert!(watcher
+            .record(3.0, 2.0, start + chrono::Duration::minutes(5))
+            .is_empty());
+        let alerts = watcher.record(0.5, 2.0, start + chrono::Duration::minutes(10));
+        assert!(matches!(
+            alerts.as_slice(),
+            [BalanceAlert::RapidDrain { lost_last_hour_sol, .. }] if (*lost_last_hour_sol - 2.5).abs() < 1e-9
+        ));
+        // Raised once until it recovers
+        assert!(watcher
+            .record(0.4, 2.0, start + chrono::Duration::minutes(11))
+            .is_empty());
+        // The peak leaves the window after an hour
+        watcher.record(0.4, 2.0, start + chrono::Duration::minutes(75));
+        assert_eq!(watcher.report().lost_last_hour_sol, 0.0);
+    }
+}
@@ -1,10 +1,13 @@
 //! Wallet management module
 //!
 //! Provides hot wallet + vault architecture with:
-//! - Credential management (wallets.json registry)
+//! - Credential management (wallets.json registry, encrypted keypairs)
 //! - Safety enforcement (limits, locks, bounds)
 //! - Profit extraction (rule-based + AI-assisted)
+//! - AI proposals (proposals.json, approved via `snipe wallet proposals`)
 //! - Transfer execution
+//! - Token sweep (stray tokens to the vault, dust burned, rent reclaimed)
+//! - Transaction signing (local keypair, remote signing service, subprocess)
 //!
 //! # Architecture
 //!
@@ -27,17 +30,24 @@
 pub mod advisor;
 pub mod credentials;
 pub mod extractor;
+pub mod keystore;
 pub mod manager;
 pub mod multi_wallet;
+pub mod proposals;
 pub mod safety;
+pub mod signer;
+pub mod sweep;
 pub mod transfer;
 pub mod types;
 
 pub use credentials::CredentialManager;
 pub use manager::WalletManager;
 pub use multi_wallet::{MultiWalletManager, SelectionStrategy, TradingWallet};
+pub use proposals::ProposalStore;
 pub use safety::{SafetyEnforcer, SafetyViolation};
+pub use signer::{SharedSigner, TxSigner};
 pub use types::{
     AiProposal, DailyExtractionStats, InitiatedBy, ProposalStatus, ProposedAction, TransferHistory,
-    TransferReason, TransferRecord, WalletEntry, WalletRegistry, WalletStatus, WalletType,
+    TransferReason, TransferRecord, TransferStatus, WalletEntry, WalletRegistry, WalletStatus,
+    WalletType,
 };
@@ -12,6 +12,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tracing::{info, warn};
 
+use super::credentials::CredentialManager;
+
 /// A trading wallet with its keypair and metadata
 #[derive(Debug)]
 pub struct TradingWallet {
@@ -94,16 +96,11 @@ impl MultiWalletManager {
         })
     }
 
-    /// Load a wallet from a keypair file
+    /// Load a wallet from a keypair file (plain or encrypted)
     fn load_wallet(path: &str, index: usize) -> Result<TradingWallet> {
-        let keypair_data = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read keypair file: {}", path))?;
-        let secret_key: Vec<u8> = serde_json::from_str(&keypair_data)
-            .with_context(|| format!("Failed to parse keypair JSON: {}", path))?;
-        let keypair = Keypair::from_bytes(&secret_key)
-            .with_context(|| format!("Invalid keypair bytes: {}", path))?;
-
         let name = format!("wallet-{}", index + 1);
+        let keypair = CredentialManager::read_keypair(std::path::Path::new(path), &name)
+            .with_context(|| format!("Failed to load keypair file: {}", path))?;
 
         Ok(TradingWallet {
             keypair,
@@ -0,0 +1,264 @@
+//! AI proposal store
+//!
+//! Proposals from the `AiAdvisor` are kept in proposals.json (next to
+//! wallets.json) with their status, expiry and outcome. The running bot owns
+//! the file; `snipe wallet proposals --approve/--reject` goes through the
+//! control channel and only edits the file directly when no bot is running.
+//! The advisor's buy-size multiplier (set by approved sizing proposals) is
+//! kept in the same file so it survives a restart.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use crate::error::{Error, Result};
+
+use super::types::{AiProposal, ProposalStatus};
+
+/// Closed proposals kept for the audit trail
+const MAX_CLOSED_PROPOSALS: usize = 200;
+
+/// proposals.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProposalFile {
+    #[serde(default)]
+    proposals: Vec<AiProposal>,
+    #[serde(default = "default_size_multiplier")]
+    buy_size_multiplier: f64,
+}
+
+fn default_size_multiplier() -> f64 {
+    1.0
+}
+
+/// Proposals with their status, optionally persisted
+#[derive(Debug)]
+pub struct ProposalStore {
+    path: Option<PathBuf>,
+    proposals: Vec<AiProposal>,
+    buy_size_multiplier: f64,
+}
+
+impl Default for ProposalStore {
+    fn default() -> Self {
+        Self {
+            path: None,
+            proposals: Vec::new(),
+            buy_size_multiplier: default_size_multiplier(),
+        }
+    }
+}
+
+impl ProposalStore {
+    /// Load the store from `path` (a missing file is an empty store)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<ProposalFile>(&content).map_err(|e| {
+                Error::PositionPersistence(format!("Failed to parse proposals: {}", e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => ProposalFile {
+                proposals: Vec::new(),
+                buy_size_multiplier: default_size_multiplier(),
+            },
+            Err(e) => {
+                return Err(Error::PositionPersistence(format!(
+                    "Failed to read proposals: {}",
+                    e
+                )))
+            }
+        };
+        debug!("Loaded {} AI proposals", file.proposals.len());
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            proposals: file.proposals,
+            buy_size_multiplier: file.buy_size_multiplier,
+        })
+    }
+
+    /// Write the store back to its file (no-op without a path)
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = ProposalFile {
+            proposals: self.proposals.clone(),
+            buy_size_multiplier: self.buy_size_multiplier,
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| {
+            Error::PositionPersistence(format!("Failed to serialize proposals: {}", e))
+        })?;
+        std::fs::write(path, json)
+            .map_err(|e| Error::PositionPersistence(format!("Failed to write proposals: {}", e)))
+    }
+
+    /// All proposals, oldest first
+    pub fn all(&self) -> &[AiProposal] {
+        &self.proposals
+    }
+
+    pub fn get(&self, id: &str) -> Option<&AiProposal> {
+        self.proposals.iter().find(|p| p.id == id)
+    }
+
+    /// Proposals with the given status
+    pub fn with_status(&self, status: ProposalStatus) -> Vec<AiProposal> {
+        self.proposals
+            .iter()
+            .filter(|p| p.status == status)
+            .cloned()
+            .collect()
+    }
+
+    /// Check if a proposal for the same action is still open
+    pub fn has_open(&self, proposal: &AiProposal) -> bool {
+        self.proposals.iter().any(|p| {
+            p.action == proposal.action
+                && matches!(p.status, ProposalStatus::Pending | ProposalStatus::Approved)
+        })
+    }
+
+    /// Record a new proposal, dropping the oldest closed ones past the cap
+    pub fn add(&mut self, proposal: AiProposal) {
+        self.proposals.push(proposal);
+        let closed = self
+            .proposals
+            .iter()
+            .filter(|p| !matches!(p.status, ProposalStatus::Pending | ProposalStatus::Approved))
+            .count();
+        let mut excess = closed.saturating_sub(MAX_CLOSED_PROPOSALS);
+        self.proposals.retain(|p| {
+            let open = matches!(p.status, ProposalStatus::Pending | ProposalStatus::Approved);
+            if open || excess == 0 {
+                return true;
+            }
+            excess -= 1;
+            false
+        });
+    }
+
+    /// Approve a pending proposal
+    pub fn approve(&mut self, id: &str, now: DateTime<Utc>) -> Result<AiProposal> {
+        self.decide(id, ProposalStatus::Approved, now)
+    }
+
+    /// Reject a pending proposal
+    pub fn reject(&mut self, id: &str, now: DateTime<Utc>) -> Result<AiProposal> {
+        self.decide(id, ProposalStatus::Rejected, now)
+    }
+
+    fn decide(
+        &mut self,
+        id: &str,
+        status: ProposalStatus,
+        now: DateTime<Utc>,
+    ) -> Result<AiProposal> {
+        self.expire(now);
+        let proposal = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| Error::Config(format!("Proposal not found: {}", id)))?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(Error::Config(format!(
+                "Proposal {} is {}, not pending",
+                id, proposal.status
+            )));
+        }
+        proposal.status = status;
+        proposal.status_updated_at = Some(now);
+        info!("{} proposal {}: {}", status, id, proposal.action);
+        Ok(proposal.clone())
+    }
+
+    /// Close a proposal after execution (or a failed attempt)
+    pub fn set_outcome(&mut self, id: &str, status: ProposalStatus, outcome: String) {
+        if let Some(proposal) = self.proposals.iter_mut().find(|p| p.id == id) {
+            proposal.status = status;
+            proposal.status_updated_at = Some(Utc::now());
+            proposal.outcome = Some(outcome);
+        }
+    }
+
+    /// Expire open proposals past their expiry; returns how many
+    pub fn expire(&mut self, now: DateTime<Utc>) -> usize {
+        let mut expired = 0;
+        for proposal in self.proposals.iter_mut().filter(|p| p.is_expired(now)) {
+            proposal.status = ProposalStatus::Expired;
+            proposal.status_updated_at = Some(now);
+            expired += 1;
+        }
+        expired
+    }
+
+    /// Multiplier applied to buy sizes (1.0 = as configured)
+    pub fn buy_size_multiplier(&self) -> f64 {
+        self.buy_size_multiplier
+    }
+
+    pub fn set_buy_size_multiplier(&mut self, multiplier: f64) {
+        self.buy_size_multiplier = multiplier;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::types::ProposedAction;
+
+    fn proposal(id: &str, expires_in_secs: i64) -> AiProposal {
+        let now = Utc::now();
+        AiProposal {
+            id: id.to_string(),
+            action: ProposedAction::ExtractToVault,
+            reasoning: String::new(),
+            confidence: 0.9,
+            amount_sol: Some(1.0),
+            created_at: now,
+            status: ProposalStatus::Pending,
+            status_updated_at: None,
+            expires_at: Some(now + chrono::Duration::seconds(expires_in_secs)),
+            outcome: None,
+        }
+    }
+
+    #[test]
+    fn test_approve_reject_expire() {
+        let now = Utc::now();
+        let mut store = ProposalStore::default();
+        store.add(proposal("a", 60));
+        store.add(proposal("b", 60));
+        store.add(proposal("old", -1));
+
+        assert!(store.approve("a", now).is_ok());
+        // Only pending proposals can be decided
+        assert!(store.reject("a", now).is_err());
+        assert!(store.reject("b", now).is_ok());
+        assert!(store.approve("old", now).is_err());
+        assert_eq!(store.get("old").unwrap().status, ProposalStatus::Expired);
+        assert!(store.approve("missing", now).is_err());
+
+        assert_eq!(store.with_status(ProposalStatus::Approved).len(), 1);
+        store.set_outcome("a", ProposalStatus::Executed, "sig".to_string());
+        assert_eq!(store.get("a").unwrap().outcome.as_deref(), Some("sig"));
+        assert!(!store.has_open(&proposal("c", 60)));
+    }
+
+    #[test]
+    fn test_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proposals.json");
+
+        let mut store = ProposalStore::load(&path).unwrap();
+        assert!(store.all().is_empty());
+        assert_eq!(store.buy_size_multiplier(), 1.0);
+        store.add(proposal("a", 60));
+        store.set_buy_size_multiplier(0.5);
+        store.save().unwrap();
+
+        let store = ProposalStore::load(&path).unwrap();
+        assert_eq!(store.all().len(), 1);
+        assert_eq!(store.buy_size_multiplier(), 0.5);
+    }
+}
@@ -2,11 +2,27 @@
 //!
 //! Enforces hard limits on all transfers and AI actions.
 //! These limits cannot be overridden by AI or automatic systems.
-
+//!
+//! Wallets with a `daily_spend_limit_sol` in wallets.json are also capped
+//! per day: every buy and transfer debits spend_ledger.json, keyed by
+//! wallet address, independently of the other limits.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
+use crate::error::Error;
+use crate::strategy::portfolio_risk::trading_day_start;
+
 use super::types::{AiProposal, DailyExtractionStats, ProposedAction, WalletType};
 
 /// Safety configuration
@@ -27,11 +43,26 @@ pub struct WalletSafetyConfig {
     /// Emergency threshold - pause trading if hot wallet drops below
     pub emergency_threshold_sol: f64,
 
-    /// Lock vault address (prevent changes)
+    /// Lock vault address: hot wallet transfers go only to the configured vault
     pub vault_address_locked: bool,
 
+    /// With the vault address locked, still allow user transfers to
+    /// external addresses
+    pub allow_external_transfers: bool,
+
     /// Maximum AI can auto-execute without user approval
     pub ai_max_auto_transfer_sol: f64,
+
+    /// Transfers above this are staged and need a second confirmation
+    /// with a one-time code (0 = off)
+    pub dual_confirm_above_sol: f64,
+
+    /// How long a staged transfer waits for its confirmation
+    pub dual_confirm_window_secs: u64,
+
+    /// Spend caps reset at midnight in this UTC offset (hours), with the
+    /// daily loss limit
+    pub day_reset_utc_offset_hours: i32,
 }
 
 impl Default for WalletSafetyConfig {
@@ -43,9 +74,67 @@ impl Default for WalletSafetyConfig {
             confirm_above_sol: 1.0,
             emergency_threshold_sol: 0.05,
             vault_address_locked: true,
+            allow_external_transfers: false,
             ai_max_auto_transfer_sol: 0.5,
+            dual_confirm_above_sol: 0.0,
+            dual_confirm_window_secs: 900,
+            day_reset_utc_offset_hours: 0,
+        }
+    }
+}
+
+impl WalletSafetyConfig {
+    /// Check if a transfer must be staged for dual confirmation
+    pub fn requires_dual_confirmation(&self, amount_sol: f64) -> bool {
+        self.dual_confirm_above_sol > 0.0 && amount_sol > self.dual_confirm_above_sol
+    }
+
+    /// Largest extraction the limits allow right now (automatic
+    /// extractions stay at the dual confirmation threshold)
+    pub fn bounded_extraction(
+        &self,
+        amount: f64,
+        hot_balance: f64,
+        remaining_allowance: f64,
+    ) -> f64 {
+        amount
+            .min(remaining_allowance)
+            .min(self.max_single_transfer_sol)
+            .min(if self.dual_confirm_above_sol > 0.0 {
+                self.dual_confirm_above_sol
+            } else {
+                f64::INFINITY
+            })
+            .min(hot_balance - self.min_hot_balance_sol)
+            .max(0.0)
+    }
+}
+
+/// Confirmation code characters (no 0/O, 1/I/L or U)
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTVWXYZ23456789";
+
+/// New one-time code for a staged transfer, `XXXX-XXXX-XXXX`
+pub fn confirmation_code() -> String {
+    let mut rng = rand::thread_rng();
+    let mut code = String::with_capacity(14);
+    for i in 0..12 {
+        if i > 0 && i % 4 == 0 {
+            code.push('-');
         }
+        code.push(CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())] as char);
     }
+    code
+}
+
+/// Stored form of a confirmation code: base64 SHA-256, ignoring case,
+/// dashes and spaces
+pub fn confirmation_code_hash(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' '))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    BASE64.encode(Sha256::digest(normalized.as_bytes()))
 }
 
 /// Safety violation types
@@ -84,6 +173,17 @@ pub enum SafetyViolation {
 
     /// Destination wallet cannot receive
     InvalidDestinationWallet { wallet_type: String },
+
+    /// Spend ledger can't be read or written (spends are refused)
+    SpendLedgerUnavailable { reason: String },
+
+    /// Wallet's daily spend cap reached
+    WalletSpendCapExceeded {
+        wallet: String,
+        spent: f64,
+        requested: f64,
+        cap: f64,
+    },
 }
 
 impl std::fmt::Display for SafetyViolation {
@@ -115,7 +215,10 @@ impl std::fmt::Display for SafetyViolation {
                 )
             }
             SafetyViolation::VaultAddressLocked => {
-                write!(f, "Vault address is locked and cannot be changed")
+                write!(
+                    f,
+                    "Vault address is locked - transfers may only go to the configured vault"
+                )
             }
             SafetyViolation::VaultWithdrawalBlocked => {
                 write!(f, "Cannot withdraw from vault - vault is receive-only")
@@ -139,6 +242,21 @@ impl std::fmt::Display for SafetyViolation {
             SafetyViolation::InvalidDestinationWallet { wallet_type } => {
                 write!(f, "Cannot transfer to {} wallet", wallet_type)
             }
+            SafetyViolation::SpendLedgerUnavailable { reason } => {
+                write!(f, "Spend ledger unavailable, spend refused: {}", reason)
+            }
+            SafetyViolation::WalletSpendCapExceeded {
+                wallet,
+                spent,
+                requested,
+                cap,
+            } => {
+                write!(
+                    f,
+                    "Daily spend cap of {} exceeded: {} SOL spent today + {} SOL requested > {} SOL cap",
+                    wallet, spent, requested, cap
+                )
+            }
         }
     }
 }
@@ -171,6 +289,10 @@ pub struct PendingTransfer {
 
     /// Is this an AI-initiated transfer?
     pub is_ai_initiated: bool,
+
+    /// The user explicitly unlocked a transfer out of the vault
+    /// (`--unlock-vault`; never honored for AI transfers)
+    pub vault_unlocked: bool,
 }
 
 /// Emergency action to take
@@ -183,12 +305,91 @@ pub enum EmergencyAction {
     PauseTradingDailyLoss,
 }
 
+/// Daily spend cap of one wallet (`daily_spend_limit_sol` in wallets.json)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendCap {
+    /// Registry name
+    pub wallet: String,
+    pub daily_limit_sol: f64,
+}
+
+/// SOL spent per wallet address today (spend_ledger.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpendLedger {
+    /// Start of the day the totals belong to
+    pub day_start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub spent_sol: HashMap<String, f64>,
+}
+
+impl SpendLedger {
+    /// Load the ledger at `path` (a missing file is an empty ledger)
+    pub fn load(path: &Path) -> crate::error::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data).map_err(|e| {
+                Error::PositionPersistence(format!("Failed to parse spend ledger: {}", e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::PositionPersistence(format!(
+                "Failed to read spend ledger: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Replace the ledger at `path` (written to a temp file, then renamed)
+    pub fn save(&self, path: &Path) -> crate::error::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            Error::PositionPersistence(format!("Failed to serialize spend ledger: {}", e))
+        })?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| Error::PositionPersistence(format!("Failed to write spend ledger: {}", e)))
+    }
+
+    /// Exclusive lock on `<ledger>.lock`, released when the file is dropped;
+    /// held around load-debit-save so debits of other processes serialize
+    fn lock_file(path: &Path) -> crate::error::Result<std::fs::File> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path.with_extension("lock"))
+            .and_then(|file| file.lock().map(|_| file))
+            .map_err(|e| Error::PositionPersistence(format!("Failed to lock spend ledger: {}", e)))
+    }
+
+    /// Clear the totals once a new day has started
+    fn roll_over(&mut self, day_start: DateTime<Utc>) {
+        if !matches!(self.day_start, Some(start) if start >= day_start) {
+            self.day_start = Some(day_start);
+            self.spent_sol.clear();
+        }
+    }
+
+    pub fn spent(&self, address: &Pubkey) -> f64 {
+        self.spent_sol
+            .get(&address.to_string())
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
+
+/// Serializes ledger debits across the enforcers of this process (the bot
+/// runs one for buys and one inside the wallet manager)
+static SPEND_LEDGER_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 /// Safety enforcer - validates all wallet operations
 pub struct SafetyEnforcer {
     config: WalletSafetyConfig,
     daily_stats: Arc<RwLock<DailyExtractionStats>>,
     emergency_lock: Arc<RwLock<bool>>,
     configured_vault_address: Option<solana_sdk::pubkey::Pubkey>,
+    spend_caps: std::sync::RwLock<HashMap<Pubkey, SpendCap>>,
+    spend_ledger: std::sync::Mutex<SpendLedger>,
+    /// Where the ledger is kept (None = memory only)
+    spend_ledger_path: Option<PathBuf>,
 }
 
 impl SafetyEnforcer {
@@ -199,7 +400,108 @@ impl SafetyEnforcer {
             daily_stats: Arc::new(RwLock::new(DailyExtractionStats::new_today())),
             emergency_lock: Arc::new(RwLock::new(false)),
             configured_vault_address: None,
+            spend_caps: std::sync::RwLock::new(HashMap::new()),
+            spend_ledger: std::sync::Mutex::new(SpendLedger::default()),
+            spend_ledger_path: None,
+        }
+    }
+
+    /// Keep the spend ledger at `path`
+    pub fn set_spend_ledger_path(&mut self, path: PathBuf) {
+        self.spend_ledger_path = Some(path);
+    }
+
+    /// Cap a wallet's daily spend
+    pub fn set_spend_cap(&self, address: Pubkey, cap: SpendCap) {
+        self.spend_caps.write().unwrap().insert(address, cap);
+    }
+
+    /// Replace all spend caps
+    pub fn set_spend_caps(&self, caps: HashMap<Pubkey, SpendCap>) {
+        *self.spend_caps.write().unwrap() = caps;
+    }
+
+    pub fn spend_cap(&self, address: &Pubkey) -> Option<SpendCap> {
+        self.spend_caps.read().unwrap().get(address).cloned()
+    }
+
+    /// Debit `amount_sol` from a wallet's daily allowance
+    ///
+    /// Rejected if it would take the wallet past its cap, or if the ledger
+    /// file can't be read, parsed or saved. The file is locked and re-read
+    /// first, so spends by other processes (`snipe wallet transfer` next to
+    /// a running bot) count too. Debits are not refunded when the buy or
+    /// transfer then fails.
+    pub fn debit_spend(&self, address: &Pubkey, amount_sol: f64) -> Result<(), SafetyViolation> {
+        self.debit_spend_at(address, amount_sol, Utc::now())
+    }
+
+    fn debit_spend_at(
+        &self,
+        address: &Pubkey,
+        amount_sol: f64,
+        now: DateTime<Utc>,
+    ) -> Result<(), SafetyViolation> {
+        let _guard = SPEND_LEDGER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let ledger_error = |e: Error| SafetyViolation::SpendLedgerUnavailable {
+            reason: e.to_string(),
+        };
+        let _file_lock = match &self.spend_ledger_path {
+            Some(path) => Some(SpendLedger::lock_file(path).map_err(ledger_error)?),
+            None => None,
+        };
+        let mut ledger = self.spend_ledger.lock().unwrap();
+        if let Some(path) = &self.spend_ledger_path {
+            *ledger = SpendLedger::load(path).map_err(ledger_error)?;
+        }
+        ledger.roll_over(trading_day_start(
+            now,
+            self.config.day_reset_utc_offset_hours,
+        ));
+
+        let spent = ledger.spent(address);
+        if let Some(cap) = self.spend_cap(address) {
+            if spent + amount_sol > cap.daily_limit_sol {
+                return Err(SafetyViolation::WalletSpendCapExceeded {
+                    wallet: cap.wallet,
+                    spent,
+                    requested: amount_sol,
+                    cap: cap.daily_limit_sol,
+                });
+            }
+        }
+
+        let mut debited = ledger.clone();
+        debited
+            .spent_sol
+            .insert(address.to_string(), spent + amount_sol);
+        if let Some(path) = &self.spend_ledger_path {
+            debited.save(path).map_err(ledger_error)?;
+        }
+        *ledger = debited;
+        debug!(
+            "Debited {} SOL from {} (spent today: {} SOL)",
+            amount_sol,
+            address,
+            spent + amount_sol
+        );
+        Ok(())
+    }
+
+    /// SOL a wallet has spent today
+    pub fn spent_today(&self, address: &Pubkey) -> f64 {
+        let _guard = SPEND_LEDGER_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ledger = self.spend_ledger.lock().unwrap();
+        if let Some(path) = &self.spend_ledger_path {
+            if let Ok(stored) = SpendLedger::load(path) {
+                *ledger = stored;
+            }
         }
+        ledger.roll_over(trading_day_start(
+            Utc::now(),
+            self.config.day_reset_utc_offset_hours,
+        ));
+        ledger.spent(address)
     }
 
     /// Set the configured vault address (for lock validation)
@@ -230,8 +532,10 @@ impl SafetyEnforcer {
             });
         }
 
-        // Block vault withdrawals
-        if transfer.from_type == WalletType::Vault {
+        // Block vault withdrawals unless the user unlocked this one
+        if transfer.from_type == WalletType::Vault
+            && (transfer.is_ai_initiated || !transfer.vault_unlocked)
+        {
             return Err(SafetyViolation::VaultWithdrawalBlocked);
         }
 
@@ -266,12 +570,15 @@ impl SafetyEnforcer {
             });
         }
 
-        // Check vault address lock
-        if self.config.vault_address_locked {
-            if let Some(configured) = self.configured_vault_address {
-                if transfer.to_address != configured {
-                    return Err(SafetyViolation::VaultAddressLocked);
-                }
+        // Check vault address lock: hot wallet funds go to the configured
+        // vault, or to external addresses only when separately allowed
+        if self.config.vault_address_locked && transfer.from_type == WalletType::Hot {
+            let to_vault = self.configured_vault_address == Some(transfer.to_address);
+            let external_allowed = transfer.to_type == WalletType::External
+                && self.config.allow_external_transfers
+                && !transfer.is_ai_initiated;
+            if !to_vault && !external_allowed {
+                return Err(SafetyViolation::VaultAddressLocked);
             }
         }
 
@@ -339,6 +646,11 @@ impl SafetyEnforcer {
         amount_sol > self.config.confirm_above_sol
     }
 
+    /// Check if a transfer must be staged and confirmed a second time
+    pub fn requires_dual_confirmation(&self, amount_sol: f64) -> bool {
+        self.config.requires_dual_confirmation(amount_sol)
+    }
+
     /// Check for emergency conditions
     pub fn check_emergency(&self, hot_balance: f64) -> Option<EmergencyAction> {
         if hot_balance < self.config.emergency_threshold_sol {
@@ -389,6 +701,14 @@ impl SafetyEnforcer {
         stats.clone()
     }
 
+    /// Restore today's totals (from the transfer history) after a restart
+    pub async fn restore_daily_stats(&self, restored: DailyExtractionStats) {
+        let mut stats = self.daily_stats.write().await;
+        if restored.is_today() {
+            *stats = restored;
+        }
+    }
+
     /// Get remaining daily extraction allowance
     pub async fn remaining_daily_allowance(&self) -> f64 {
         let stats = self.daily_stats().await;
@@ -409,7 +729,11 @@ mod tests {
             confirm_above_sol: 1.0,
             emergency_threshold_sol: 0.05,
             vault_address_locked: false, // Disable for testing
+            allow_external_transfers: false,
             ai_max_auto_transfer_sol: 0.5,
+            dual_confirm_above_sol: 0.0,
+            dual_confirm_window_secs: 900,
+            day_reset_utc_offset_hours: 0,
         }
     }
 
@@ -426,6 +750,7 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 0.1, // Would leave only 0.05
             is_ai_initiated: false,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
@@ -448,6 +773,7 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 10.0, // Exceeds max 5.0
             is_ai_initiated: false,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
@@ -470,6 +796,7 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 1.0,
             is_ai_initiated: false,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
@@ -477,6 +804,21 @@ mod tests {
             result,
             Err(SafetyViolation::VaultWithdrawalBlocked)
         ));
+
+        // Explicit user unlock lets it through, never for AI
+        let unlocked = PendingTransfer {
+            vault_unlocked: true,
+            ..transfer
+        };
+        assert!(enforcer.validate_transfer(&unlocked).await.is_ok());
+        let ai = PendingTransfer {
+            is_ai_initiated: true,
+            ..unlocked
+        };
+        assert!(matches!(
+            enforcer.validate_transfer(&ai).await,
+            Err(SafetyViolation::VaultWithdrawalBlocked)
+        ));
     }
 
     #[tokio::test]
@@ -492,6 +834,7 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 1.0, // Exceeds AI max 0.5
             is_ai_initiated: true,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
@@ -514,12 +857,57 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 1.0,
             is_ai_initiated: false,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_vault_address_locked() {
+        let vault = Pubkey::new_unique();
+        let locked = WalletSafetyConfig {
+            vault_address_locked: true,
+            ..test_config()
+        };
+        let mut enforcer = SafetyEnforcer::new(locked.clone());
+        enforcer.set_vault_address(vault);
+
+        let external = PendingTransfer {
+            from_wallet: "hot".to_string(),
+            from_type: WalletType::Hot,
+            from_balance: 10.0,
+            to_wallet: "exchange".to_string(),
+            to_type: WalletType::External,
+            to_address: Pubkey::new_unique(),
+            amount_sol: 1.0,
+            is_ai_initiated: false,
+            vault_unlocked: false,
+        };
+        assert!(matches!(
+            enforcer.validate_transfer(&external).await,
+            Err(SafetyViolation::VaultAddressLocked)
+        ));
+
+        // The configured vault is always allowed
+        let to_vault = PendingTransfer {
+            to_wallet: "vault".to_string(),
+            to_type: WalletType::Vault,
+            to_address: vault,
+            ..external.clone()
+        };
+        assert!(enforcer.validate_transfer(&to_vault).await.is_ok());
+
+        // External destinations need the separate opt-in
+        let mut enforcer = SafetyEnforcer::new(WalletSafetyConfig {
+            allow_external_transfers: true,
+            ..locked
+        });
+        enforcer.set_vault_address(vault);
+        assert!(enforcer.validate_transfer(&external).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_daily_limit() {
         let enforcer = SafetyEnforcer::new(test_config());
@@ -536,6 +924,7 @@ mod tests {
             to_address: Pubkey::default(),
             amount_sol: 2.0, // Would exceed 10 SOL daily limit
             is_ai_initiated: false,
+            vault_unlocked: false,
         };
 
         let result = enforcer.validate_transfer(&transfer).await;
@@ -544,4 +933,88 @@ mod tests {
             Err(SafetyViolation::DailyLimitExceeded { .. })
         ));
     }
+
+    #[test]
+    fn test_confirmation_code() {
+        let code = confirmation_code();
+        assert_eq!(code.len(), 14);
+        assert_ne!(code, confirmation_code());
+        // Typed back loosely, it still matches
+        let typed = code.replace('-', " ").to_lowercase();
+        assert_eq!(
+            confirmation_code_hash(&typed),
+            confirmation_code_hash(&code)
+        );
+        assert_ne!(
+            confirmation_code_hash("AAAA-AAAA-AAAA"),
+            confirmation_code_hash(&code)
+        );
+    }
+
+    #[test]
+    fn test_bounded_extraction() {
+        let config = test_config();
+        // Limited by the daily allowance
+        assert_eq!(config.bounded_extraction(2.0, 10.0, 1.5), 1.5);
+        // Limited by the single transfer cap
+        assert_eq!(config.bounded_extraction(8.0, 20.0, 10.0), 5.0);
+        // Never below the minimum hot balance
+        assert!((config.bounded_extraction(1.0, 0.5, 10.0) - 0.4).abs() < 1e-9);
+        assert_eq!(config.bounded_extraction(1.0, 0.05, 10.0), 0.0);
+
+        // Automatic extractions stay out of dual confirmation
+        let config = WalletSafetyConfig {
+            dual_confirm_above_sol: 2.0,
+            ..config
+        };
+        assert!(config.requires_dual_confirmation(2.5));
+        assert!(!config.requires_dual_confirmation(2.0));
+        assert_eq!(config.bounded_extraction(4.0, 20.0, 10.0), 2.0);
+    }
+
+    #[test]
+    fn test_wallet_spend_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spend_ledger.json");
+        let wallet = Pubkey::new_unique();
+        let uncapped = Pubkey::new_unique();
+        let now = Utc::now();
+        let cap = || SpendCap {
+            wallet: "hot-trading".to_string(),
+            daily_limit_sol: 1.0,
+        };
+
+        let mut enforcer = SafetyEnforcer::new(test_config());
+        enforcer.set_spend_ledger_path(path.clone());
+        enforcer.set_spend_cap(wallet, cap());
+        assert!(enforcer.debit_spend_at(&wallet, 0.6, now).is_ok());
+        assert_eq!(
+            enforcer.debit_spend_at(&wallet, 0.5, now),
+            Err(SafetyViolation::WalletSpendCapExceeded {
+                wallet: "hot-trading".to_string(),
+                spent: 0.6,
+                requested: 0.5,
+                cap: 1.0,
+            })
+        );
+        assert!(enforcer.debit_spend_at(&uncapped, 50.0, now).is_ok());
+
+        // Another enforcer (or process) on the same ledger sees the spend
+        let mut other = SafetyEnforcer::new(test_config());
+        other.set_spend_ledger_path(path);
+        other.set_spend_cap(wallet, cap());
+        assert!(other.debit_spend_at(&wallet, 0.5, now).is_err());
+        assert!(other.debit_spend_at(&wallet, 0.4, now).is_ok());
+
+        // The allowance comes back the next day
+        let tomorrow = now + chrono::Duration::days(1);
+        assert!(enforcer.debit_spend_at(&wallet, 1.0, tomorrow).is_ok());
+
+        // An unreadable ledger refuses the spend instead of forgetting it
+        std::fs::write(dir.path().join("spend_ledger.json"), "{not json").unwrap();
+        assert!(matches!(
+            enforcer.debit_spend_at(&uncapped, 0.1, tomorrow),
+            Err(SafetyViolation::SpendLedgerUnavailable { .. })
+        ));
+    }
 }
@@ -0,0 +1,487 @@
+//! Transaction signers
+//!
+//! Everything that signs for the hot wallet goes through [`TxSigner`], so the
+//! key doesn't have to live on the sniping box (`[wallet.signer]`):
+//! - `local`: the hot wallet keypair, loaded through `CredentialManager`
+//! - `remote`: a signing service over HTTPS. The bot POSTs the serialized
+//!   message to `{url}/sign` and gets the signature back; requests carry an
+//!   HMAC-SHA256 header and/or a client certificate (mutual TLS)
+//! - `subprocess`: a long-running sign-only process reading one base64
+//!   message per line on stdin and answering with one base58 signature (or
+//!   `ERR <reason>`) per line on stdout
+//!
+//! Remote and subprocess signatures are verified against the configured
+//! pubkey before they are used. Every signature is timed and logged;
+//! signatures slower than `latency_budget_ms` are logged as warnings.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use solana_sdk::{
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    transaction::{Transaction, VersionedTransaction},
+};
+use std::process::Stdio;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::config::SignerConfig;
+use crate::error::{Error, Result};
+
+/// Backend names (`wallet.signer.backend`)
+pub const LOCAL: &str = "local";
+pub const REMOTE: &str = "remote";
+pub const SUBPROCESS: &str = "subprocess";
+
+/// Remote signer request header: unix time in milliseconds
+pub const TIMESTAMP_HEADER: &str = "X-Signer-Timestamp";
+
+/// Remote signer request header: base64 HMAC-SHA256 of `<timestamp>.<body>`
+pub const AUTH_HEADER: &str = "X-Signer-Auth";
+
+/// Signs transaction messages for one address
+#[async_trait]
+pub trait TxSigner: Send + Sync {
+    fn pubkey(&self) -> Pubkey;
+
+    /// Backend name for logs
+    fn backend(&self) -> &'static str;
+
+    /// Signatures slower than this are logged as warnings
+    fn latency_budget(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Sign a serialized message (untimed; callers use `sign_message`)
+    async fn sign_raw(&self, message: &[u8]) -> Result<Signature>;
+
+    /// Sign a serialized message and log how long it took
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        let started = Instant::now();
+        let result = self.sign_raw(message).await;
+        let elapsed = started.elapsed();
+        let ms = elapsed.as_secs_f64() * 1000.0;
+        match &result {
+            Err(e) => warn!("{} signer failed after {:.1}ms: {}", self.backend(), ms, e),
+            Ok(_) if self.latency_budget().is_some_and(|budget| elapsed > budget) => warn!(
+                "{} signature took {:.1}ms (budget {}ms)",
+                self.backend(),
+                ms,
+                self.latency_budget().unwrap_or_default().as_millis()
+            ),
+            Ok(_) if self.backend() == LOCAL => debug!("local signature took {:.3}ms", ms),
+            Ok(_) => info!("{} signature took {:.1}ms", self.backend(), ms),
+        }
+        result
+    }
+}
+
+/// Signer shared between tasks
+pub type SharedSigner = Arc<dyn TxSigner>;
+
+#[async_trait]
+impl TxSigner for Keypair {
+    fn pubkey(&self) -> Pubkey {
+        solana_sdk::signer::Signer::pubkey(self)
+    }
+
+    fn backend(&self) -> &'static str {
+        LOCAL
+    }
+
+    async fn sign_raw(&self, message: &[u8]) -> Result<Signature> {
+        Ok(solana_sdk::signer::Signer::sign_message(self, message))
+    }
+}
+
+/// Build the remote or subprocess signer from `[wallet.signer]` (the local
+/// backend is the hot wallet keypair itself)
+pub fn from_config(config: &SignerConfig) -> Result<SharedSigner> {
+    match config.backend.as_str() {
+        REMOTE => Ok(Arc::new(RemoteSigner::new(config)?)),
+        SUBPROCESS => Ok(Arc::new(SubprocessSigner::new(config)?)),
+        other => Err(Error::Config(format!(
+            "Signer backend '{}' needs no external signer",
+            other
+        ))),
+    }
+}
+
+/// Set the blockhash and sign a transaction whose only signer is `signer`
+pub async fn sign_transaction(
+    signer: &dyn TxSigner,
+    tx: &mut Transaction,
+    blockhash: Hash,
+) -> Result<()> {
+    if tx.message.header.num_required_signatures != 1
+        || tx.message.account_keys.first() != Some(&signer.pubkey())
+    {
+        return Err(Error::TransactionBuild(format!(
+            "{} is not the only signer of the transaction",
+            signer.pubkey()
+        )));
+    }
+    tx.message.recent_blockhash = blockhash;
+    let signature = signer.sign_message(&tx.message_data()).await?;
+    tx.signatures = vec![signature];
+    Ok(())
+}
+
+/// Sign a versioned transaction as its fee payer
+pub async fn sign_versioned(signer: &dyn TxSigner, tx: &mut VersionedTransaction) -> Result<()> {
+    if tx.message.static_account_keys().first() != Some(&signer.pubkey()) {
+        return Err(Error::TransactionBuild(format!(
+            "{} is not the fee payer of the transaction",
+            signer.pubkey()
+        )));
+    }
+    let signature = signer.sign_message(&tx.message.serialize()).await?;
+    match tx.signatures.first_mut() {
+        Some(slot) => *slot = signature,
+        None => tx.signatures.push(signature),
+    }
+    Ok(())
+}
+
+fn signing_error(backend: &str, reason: impl Into<String>) -> Error {
+    Error::Signing {
+        backend: backend.to_string(),
+        reason: reason.into(),
+    }
+}
+
+/// Parse a base58 signature and check it against `pubkey` and `message`
+fn verify_signature(
+    backend: &str,
+    pubkey: &Pubkey,
+    message: &[u8],
+    encoded: &str,
+) -> Result<Signature> {
+    let encoded = encoded.trim();
+    let signature = Signature::from_str(encoded)
+        .map_err(|e| signing_error(backend, format!("bad signature '{}': {}", encoded, e)))?;
+    if !signature.verify(pubkey.as_ref(), message) {
+        return Err(signing_error(
+            backend,
+            format!("signature does not verify for {}", pubkey),
+        ));
+    }
+    Ok(signature)
+}
+
+fn configured_pubkey(config: &SignerConfig) -> Result<Pubkey> {
+    let pubkey = config.pubkey.as_deref().unwrap_or_default();
+    Pubkey::from_str(pubkey)
+        .map_err(|_| Error::Config(format!("Invalid wallet.signer.pubkey: '{}'", pubkey)))
+}
+
+fn latency_budget(config: &SignerConfig) -> Option<Duration> {
+    (config.latency_budget_ms > 0).then(|| Duration::from_millis(config.latency_budget_ms))
+}
+
+/// POST {url}/sign body
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    /// Address the message must be signed with
+    pubkey: String,
+    /// Serialized transaction message, base64
+    message: String,
+}
+
+/// POST {url}/sign reply
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    /// base58
+    signature: String,
+}
+
+/// base64 HMAC-SHA256 over `<timestamp>.<body>`
+fn auth_tag(secret: &[u8], timestamp: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+/// Signing service over HTTPS
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    sign_url: String,
+    pubkey: Pubkey,
+    hmac_secret: Option<Vec<u8>>,
+    latency_budget: Option<Duration>,
+}
+
+impl RemoteSigner {
+    pub fn new(config: &SignerConfig) -> Result<Self> {
+        let url = config
+            .url
+            .as_deref()
+            .ok_or_else(|| Error::Config("wallet.signer.url is not set".to_string()))?;
+        let hmac_secret = match &config.hmac_secret_env {
+            Some(var) => Some(
+                std::env::var(var)
+                    .map_err(|_| Error::MissingEnvVar(var.clone()))?
+                    .into_bytes(),
+            ),
+            None => None,
+        };
+
+        // Keep connections open so signatures don't pay for a TLS handshake
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .tcp_nodelay(true)
+            .pool_idle_timeout(None);
+        if let (Some(cert), Some(key)) = (&config.client_cert_path, &config.client_key_path) {
+            let identity = reqwest::Identity::from_pkcs8_pem(&read_pem(cert)?, &read_pem(key)?)
+                .map_err(|e| Error::Config(format!("Invalid signer client certificate: {}", e)))?;
+            builder = builder.identity(identity);
+        }
+        if let Some(ca) = &config.ca_cert_path {
+            let ca = reqwest::Certificate::from_pem(&read_pem(ca)?)
+                .map_err(|e| Error::Config(format!("Invalid signer CA certificate: {}", e)))?;
+            builder = builder.add_root_certificate(ca);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| Error::Config(format!("Failed to build signer client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            sign_url: format!("{}/sign", url.trim_end_matches('/')),
+            pubkey: configured_pubkey(config)?,
+            hmac_secret,
+            latency_budget: latency_budget(config),
+        })
+    }
+}
+
+fn read_pem(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| Error::Config(format!("Failed to read {}: {}", path, e)))
+}
+
+#[async_trait]
+impl TxSigner for RemoteSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn backend(&self) -> &'static str {
+        REMOTE
+    }
+
+    fn latency_budget(&self) -> Option<Duration> {
+        self.latency_budget
+    }
+
+    async fn sign_raw(&self, message: &[u8]) -> Result<Signature> {
+        let body = serde_json::to_vec(&SignRequest {
+            pubkey: self.pubkey.to_string(),
+            message: BASE64.encode(message),
+        })?;
+
+        let mut request = self
+            .client
+            .post(&self.sign_url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.hmac_secret {
+            let timestamp = chrono::Utc::now().timestamp_millis().to_string();
+            request = request
+                .header(AUTH_HEADER, auth_tag(secret, &timestamp, &body))
+                .header(TIMESTAMP_HEADER, timestamp);
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| signing_error(REMOTE, format!("request failed: {}", e)))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(signing_error(REMOTE, format!("{}: {}", status, text)));
+        }
+        let reply: SignResponse = response
+            .json()
+            .await
+            .map_err(|e| signing_error(REMOTE, format!("bad reply: {}", e)))?;
+        verify_signature(REMOTE, &self.pubkey, message, &reply.signature)
+    }
+}
+
+/// Running sign-only process
+struct SignerProcess {
+    /// Killed when dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl SignerProcess {
+    fn spawn(command: &[String]) -> Result<Self> {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| Error::Config("wallet.signer.command is empty".to_string()))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                signing_error(SUBPROCESS, format!("failed to start {}: {}", program, e))
+            })?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        info!("Started signer process {} (pid {:?})", program, child.id());
+        Ok(Self {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    async fn request(&mut self, message: &[u8]) -> std::io::Result<String> {
+        let mut line = BASE64.encode(message);
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+        let mut reply = String::new();
+        if self.stdout.read_line(&mut reply).await? == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "signer process exited",
+            ));
+        }
+        Ok(reply)
+    }
+}
+
+/// Parse one reply line from the signer process
+fn parse_reply(pubkey: &Pubkey, message: &[u8], reply: &str) -> Result<Signature> {
+    if let Some(reason) = reply.trim().strip_prefix("ERR") {
+        return Err(signing_error(SUBPROCESS, reason.trim()));
+    }
+    verify_signature(SUBPROCESS, pubkey, message, reply)
+}
+
+/// Sign-only child process, started with the signer and restarted after an
+/// I/O error or timeout (a late reply would answer the next request)
+pub struct SubprocessSigner {
+    command: Vec<String>,
+    pubkey: Pubkey,
+    timeout: Duration,
+    latency_budget: Option<Duration>,
+    process: Mutex<Option<SignerProcess>>,
+}
+
+impl SubprocessSigner {
+    pub fn new(config: &SignerConfig) -> Result<Self> {
+        Ok(Self {
+            command: config.command.clone(),
+            pubkey: configured_pubkey(config)?,
+            timeout: Duration::from_millis(config.timeout_ms),
+            latency_budget: latency_budget(config),
+            process: Mutex::new(Some(SignerProcess::spawn(&config.command)?)),
+        })
+    }
+}
+
+#[async_trait]
+impl TxSigner for SubprocessSigner {
+    fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    fn backend(&self) -> &'static str {
+        SUBPROCESS
+    }
+
+    fn latency_budget(&self) -> Option<Duration> {
+        self.latency_budget
+    }
+
+    async fn sign_raw(&self, message: &[u8]) -> Result<Signature> {
+        let mut process = self.process.lock().await;
+        if process.is_none() {
+            *process = Some(SignerProcess::spawn(&self.command)?);
+        }
+        let running = process.as_mut().expect("signer process started above");
+        let reply = match tokio::time::timeout(self.timeout, running.request(message)).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(e)) => {
+                *process = None;
+                return Err(signing_error(SUBPROCESS, format!("I/O error: {}", e)));
+            }
+            Err(_) => {
+                *process = None;
+                return Err(signing_error(
+                    SUBPROCESS,
+                    format!("no reply within {}ms", self.timeout.as_millis()),
+                ));
+            }
+        };
+        parse_reply(&self.pubkey, message, &reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::system_instruction;
+
+    #[tokio::test]
+    async fn test_sign_transaction() {
+        let signer: SharedSigner = Arc::new(Keypair::new());
+        let transfer = system_instruction::transfer(&signer.pubkey(), &Pubkey::new_unique(), 1);
+        let mut tx = Transaction::new_with_payer(&[transfer], Some(&signer.pubkey()));
+        sign_transaction(signer.as_ref(), &mut tx, Hash::new_unique())
+            .await
+            .unwrap();
+        assert!(tx.verify().is_ok());
+
+        // Not ours to sign
+        let payer = Pubkey::new_unique();
+        let transfer = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+        let mut tx = Transaction::new_with_payer(&[transfer], Some(&payer));
+        assert!(
+            sign_transaction(signer.as_ref(), &mut tx, Hash::new_unique())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_subprocess_reply_is_verified() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let message = b"message";
+        let signature = solana_sdk::signer::Signer::sign_message(&keypair, message);
+
+        let reply = format!("{}\n", signature);
+        assert_eq!(parse_reply(&pubkey, message, &reply).unwrap(), signature);
+        assert!(parse_reply(&pubkey, b"other message", &reply).is_err());
+        assert!(parse_reply(&Pubkey::new_unique(), message, &reply).is_err());
+        assert!(parse_reply(&pubkey, message, "ERR locked\n").is_err());
+        assert!(parse_reply(&pubkey, message, "garbage\n").is_err());
+    }
+
+    #[test]
+    fn test_auth_tag() {
+        assert_eq!(
+            auth_tag(b"secret", "1700000000000", b"{}"),
+            "g5khbRESh+O7KOJcD08x3/34McaMnuK5bC9nybgdNBs="
+        );
+    }
+}
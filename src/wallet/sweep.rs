@@ -0,0 +1,553 @@
+//! Sweep of stray tokens from the hot wallet
+//!
+//! `snipe wallet sweep` walks every SPL Token and Token-2022 account the hot
+//! wallet owns. Balances worth at least `min_value_usd` (DexScreener price)
+//! go to the vault's associated token account, created if needed; smaller
+//! balances are burned with `--burn-dust`. Emptied accounts are closed so
+//! their rent returns to the hot wallet. Mints of open positions, frozen
+//! accounts and unpriced tokens are left alone.
+//!
+//! Token-2022 accounts are handled with their own program id, and transfers
+//! are valued after the mint's transfer fee. Accounts holding withheld fees
+//! can't be closed, so they are emptied but kept.
+
+use chrono::Utc;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::{
+    instruction::Instruction, pubkey::Pubkey, signature::Signature, transaction::Transaction,
+};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::position::reconcile::{TOKEN_2022_PROGRAM, WSOL_MINT};
+
+use super::signer::{sign_transaction, SharedSigner};
+use super::types::{
+    InitiatedBy, SweepAction, SweepDetail, TransferReason, TransferRecord, TransferStatus,
+};
+
+/// Token-2022 accounts: account type byte after the base state (1 = mint)
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Token-2022 TransferFeeConfig extension type
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+
+/// `to_wallet` of a burn record
+pub const BURN_DESTINATION: &str = "burn";
+
+/// Token-2022 transfer fee in effect for an epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    /// Cap per transfer, raw units
+    pub maximum_fee: u64,
+}
+
+impl TransferFee {
+    /// Fee withheld from a transfer of `amount` (rounded up, capped)
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        let fee = (amount as u128 * self.basis_points as u128).div_ceil(10_000);
+        (fee.min(u64::MAX as u128) as u64).min(self.maximum_fee)
+    }
+}
+
+/// Transfer fee of a Token-2022 mint at `epoch` (None without the extension)
+pub fn parse_transfer_fee(mint_data: &[u8], epoch: u64) -> Option<TransferFee> {
+    if mint_data.get(ACCOUNT_TYPE_OFFSET) != Some(&ACCOUNT_TYPE_MINT) {
+        return None;
+    }
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let Some(header) = mint_data.get(offset..offset + 4) {
+        let kind = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let value = mint_data.get(offset + 4..offset + 4 + len)?;
+        if kind == EXTENSION_TRANSFER_FEE_CONFIG {
+            // Two authorities, withheld amount, then the older and newer fee
+            // (epoch, maximum fee, basis points)
+            let fee_at = |start: usize| -> Option<(u64, TransferFee)> {
+                let bytes = value.get(start..start + 18)?;
+                let epoch = u64::from_le_bytes(bytes[0..8].try_into().ok()?);
+                let fee = TransferFee {
+                    maximum_fee: u64::from_le_bytes(bytes[8..16].try_into().ok()?),
+                    basis_points: u16::from_le_bytes(bytes[16..18].try_into().ok()?),
+                };
+                Some((epoch, fee))
+            };
+            let (_, older) = fee_at(72)?;
+            let (newer_epoch, newer) = fee_at(90)?;
+            return Some(if epoch >= newer_epoch { newer } else { older });
+        }
+        if kind == 0 {
+            break;
+        }
+        offset += 4 + len;
+    }
+    None
+}
+
+/// Token account owned by the hot wallet
+#[derive(Debug, Clone)]
+pub struct SweepAccount {
+    pub address: Pubkey,
+    pub mint: Pubkey,
+    /// SPL Token or Token-2022
+    pub program: Pubkey,
+    pub raw_amount: u64,
+    pub decimals: u8,
+    pub frozen: bool,
+    /// Token-2022 fees withheld in the account; it can't be closed until
+    /// they are harvested
+    pub withheld_fees: u64,
+    /// Lamports returned by closing the account
+    pub rent_lamports: u64,
+    pub transfer_fee: Option<TransferFee>,
+}
+
+impl SweepAccount {
+    fn ui_amount(&self, raw: u64) -> f64 {
+        raw as f64 / 10f64.powi(self.decimals as i32)
+    }
+
+    /// Transfer fee on moving the whole balance, raw units
+    pub fn fee(&self) -> u64 {
+        self.transfer_fee
+            .map_or(0, |fee| fee.fee_for(self.raw_amount))
+    }
+
+    pub fn can_close(&self) -> bool {
+        self.withheld_fees == 0
+    }
+}
+
+/// What the sweep does with one account
+#[derive(Debug, Clone)]
+pub struct SweepItem {
+    pub account: SweepAccount,
+    pub price_usd: Option<f64>,
+    /// None = left alone (see `note`)
+    pub action: Option<SweepAction>,
+    pub note: String,
+}
+
+impl SweepItem {
+    /// Whole tokens in the account
+    pub fn token_amount(&self) -> f64 {
+        self.account.ui_amount(self.account.raw_amount)
+    }
+
+    /// USD value of the balance after the transfer fee
+    pub fn value_usd(&self) -> Option<f64> {
+        let net = self.account.raw_amount - self.account.fee();
+        self.price_usd
+            .map(|price| price * self.account.ui_amount(net))
+    }
+
+    /// Rent the sweep reclaims, SOL
+    pub fn rent_reclaimed_sol(&self) -> f64 {
+        if self.action.is_some() && self.account.can_close() {
+            self.account.rent_lamports as f64 / 1e9
+        } else {
+            0.0
+        }
+    }
+
+    /// Transfer history record of the executed action
+    pub fn record(
+        &self,
+        hot_wallet: &str,
+        vault_wallet: &str,
+        signature: &Signature,
+    ) -> Option<TransferRecord> {
+        let action = self.action?;
+        let (to_wallet, token_amount, transfer_fee) = match action {
+            SweepAction::Transfer => (
+                vault_wallet,
+                self.token_amount(),
+                self.account.ui_amount(self.account.fee()),
+            ),
+            SweepAction::Burn => (BURN_DESTINATION, self.token_amount(), 0.0),
+            SweepAction::Close => (hot_wallet, 0.0, 0.0),
+        };
+        Some(TransferRecord {
+            id: Uuid::new_v4().to_string(),
+            from_wallet: hot_wallet.to_string(),
+            to_wallet: to_wallet.to_string(),
+            amount_sol: 0.0,
+            reason: TransferReason::Sweep,
+            signature: signature.to_string(),
+            timestamp: Utc::now(),
+            initiated_by: InitiatedBy::User,
+            sweep: Some(SweepDetail {
+                action,
+                mint: self.account.mint.to_string(),
+                token_account: self.account.address.to_string(),
+                token_amount,
+                transfer_fee,
+                value_usd: self.value_usd(),
+                rent_reclaimed_sol: self.rent_reclaimed_sol(),
+            }),
+            status: TransferStatus::Completed,
+            dual_confirmation: None,
+        })
+    }
+}
+
+/// Decide what to do with one account
+pub fn plan_item(
+    account: SweepAccount,
+    price_usd: Option<f64>,
+    open_mints: &HashSet<String>,
+    min_value_usd: f64,
+    burn_dust: bool,
+) -> SweepItem {
+    let mut item = SweepItem {
+        account,
+        price_usd,
+        action: None,
+        note: String::new(),
+    };
+    let (action, note) = if open_mints.contains(&item.account.mint.to_string()) {
+        (None, "open position".to_string())
+    } else if item.account.frozen {
+        (None, "frozen".to_string())
+    } else if item.account.raw_amount == 0 {
+        if item.account.can_close() {
+            (Some(SweepAction::Close), "empty".to_string())
+        } else {
+            (None, "empty, withheld transfer fees".to_string())
+        }
+    } else {
+        match item.value_usd() {
+            None => (None, "no price".to_string()),
+            Some(value) if value >= min_value_usd => {
+                let note = match item.account.fee() {
+                    0 => "to vault".to_string(),
+                    fee => format!("to vault, {} transfer fee", item.account.ui_amount(fee)),
+                };
+                (Some(SweepAction::Transfer), note)
+            }
+            Some(_) if burn_dust => (Some(SweepAction::Burn), "dust".to_string()),
+            Some(_) => (
+                None,
+                format!("below ${} (--burn-dust to burn)", min_value_usd),
+            ),
+        }
+    };
+    item.action = action;
+    item.note = note;
+    item
+}
+
+/// spl_token's builders only accept its own program id; Token-2022 shares
+/// these instruction layouts
+fn for_program(
+    instruction: std::result::Result<Instruction, solana_sdk::program_error::ProgramError>,
+    program: &Pubkey,
+) -> Result<Instruction> {
+    let mut instruction =
+        instruction.map_err(|e| Error::TransactionBuild(format!("Token instruction: {}", e)))?;
+    instruction.program_id = *program;
+    Ok(instruction)
+}
+
+/// Enumerates and sweeps the hot wallet's token accounts
+pub struct TokenSweeper {
+    rpc_client: RpcClient,
+    signer: SharedSigner,
+    vault: Pubkey,
+}
+
+impl TokenSweeper {
+    pub fn new(rpc_client: RpcClient, signer: SharedSigner, vault: Pubkey) -> Self {
+        Self {
+            rpc_client,
+            signer,
+            vault,
+        }
+    }
+
+    /// Every SPL Token and Token-2022 account of the hot wallet, empty ones
+    /// included (wrapped SOL is skipped)
+    pub fn accounts(&self) -> Result<Vec<SweepAccount>> {
+        let owner = self.signer.pubkey();
+        let token_2022 = Pubkey::from_str(TOKEN_2022_PROGRAM).expect("valid program id");
+
+        let mut accounts = Vec::new();
+        for program in [spl_token::id(), token_2022] {
+            let found = self
+                .rpc_client
+                .get_token_accounts_by_owner(&owner, TokenAccountsFilter::ProgramId(program))?;
+            for keyed in found {
+                let solana_account_decoder::UiAccountData::Json(parsed) = &keyed.account.data
+                else {
+                    continue;
+                };
+                let Some(info) = parsed.parsed.get("info") else {
+                    continue;
+                };
+                let mint = info
+                    .get("mint")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default();
+                let (Ok(address), Ok(mint_pubkey)) =
+                    (Pubkey::from_str(&keyed.pubkey), Pubkey::from_str(mint))
+                else {
+                    continue;
+                };
+                if mint == WSOL_MINT {
+                    continue;
+                }
+                let token_amount = info.get("tokenAmount");
+                let withheld_fees = info
+                    .get("extensions")
+                    .and_then(|e| e.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter(|e| {
+                        e.get("extension").and_then(|k| k.as_str()) == Some("transferFeeAmount")
+                    })
+                    .filter_map(|e| e.get("state")?.get("withheldAmount")?.as_u64())
+                    .sum();
+
+                accounts.push(SweepAccount {
+                    address,
+                    mint: mint_pubkey,
+                    program,
+                    raw_amount: token_amount
+                        .and_then(|t| t.get("amount"))
+                        .and_then(|a| a.as_str())
+                        .and_then(|a| a.parse::<u64>().ok())
+                        .unwrap_or(0),
+                    decimals: token_amount
+                        .and_then(|t| t.get("decimals"))
+                        .and_then(|d| d.as_u64())
+                        .unwrap_or(6) as u8,
+                    frozen: info.get("state").and_then(|s| s.as_str()) == Some("frozen"),
+                    withheld_fees,
+                    rent_lamports: keyed.account.lamports,
+                    transfer_fee: None,
+                });
+            }
+        }
+
+        self.load_transfer_fees(&mut accounts, &token_2022)?;
+        debug!("Found {} token accounts on {}", accounts.len(), owner);
+        Ok(accounts)
+    }
+
+    /// Fill in the transfer fee of Token-2022 mints with a balance
+    fn load_transfer_fees(&self, accounts: &mut [SweepAccount], token_2022: &Pubkey) -> Result<()> {
+        let mints: HashSet<Pubkey> = accounts
+            .iter()
+            .filter(|a| a.program == *token_2022 && a.raw_amount > 0)
+            .map(|a| a.mint)
+            .collect();
+        if mints.is_empty() {
+            return Ok(());
+        }
+        let epoch = self.rpc_client.get_epoch_info()?.epoch;
+        let mut fees = HashMap::new();
+        for mint in mints {
+            let data = self.rpc_client.get_account_data(&mint)?;
+            if let Some(fee) = parse_transfer_fee(&data, epoch) {
+                fees.insert(mint, fee);
+            }
+        }
+        for account in accounts {
+            account.transfer_fee = fees.get(&account.mint).copied();
+        }
+        Ok(())
+    }
+
+    /// Instructions for one planned action (close included when possible)
+    fn instructions(&self, item: &SweepItem) -> Result<Vec<Instruction>> {
+        let Some(action) = item.action else {
+            return Ok(Vec::new());
+        };
+        let hot = self.signer.pubkey();
+        let account = &item.account;
+        let mut instructions = Vec::new();
+        match action {
+            SweepAction::Transfer => {
+                let destination =
+                    spl_associated_token_account::get_associated_token_address_with_program_id(
+                        &self.vault,
+                        &account.mint,
+                        &account.program,
+                    );
+                instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &hot,
+                        &self.vault,
+                        &account.mint,
+                        &account.program,
+                    ),
+                );
+                instructions.push(for_program(
+                    spl_token::instruction::transfer_checked(
+                        &spl_token::id(),
+                        &account.address,
+                        &account.mint,
+                        &destination,
+                        &hot,
+                        &[],
+                        account.raw_amount,
+                        account.decimals,
+                    ),
+                    &account.program,
+                )?);
+            }
+            SweepAction::Burn => {
+                instructions.push(for_program(
+                    spl_token::instruction::burn_checked(
+                        &spl_token::id(),
+                        &account.address,
+                        &account.mint,
+                        &hot,
+                        &[],
+                        account.raw_amount,
+                        account.decimals,
+                    ),
+                    &account.program,
+                )?);
+            }
+            SweepAction::Close => {}
+        }
+        if account.can_close() {
+            instructions.push(for_program(
+                spl_token::instruction::close_account(
+                    &spl_token::id(),
+                    &account.address,
+                    &hot,
+                    &hot,
+                    &[],
+                ),
+                &account.program,
+            )?);
+        }
+        Ok(instructions)
+    }
+
+    /// Execute one planned action in its own transaction
+    pub async fn execute(&self, item: &SweepItem) -> Result<Signature> {
+        let instructions = self.instructions(item)?;
+        if instructions.is_empty() {
+            return Err(Error::TransactionBuild(format!(
+                "Nothing to do for {}",
+                item.account.address
+            )));
+        }
+        let hot = self.signer.pubkey();
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash()
+            .map_err(|e| Error::TransactionBuild(format!("Failed to get blockhash: {}", e)))?;
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&hot));
+        sign_transaction(self.signer.as_ref(), &mut transaction, blockhash).await?;
+
+        let signature = self
+            .rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .map_err(|e| Error::TransactionSend(format!("Sweep failed: {}", e)))?;
+        info!(
+            "Swept {} ({:?}): {}",
+            item.account.mint, item.action, signature
+        );
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(raw_amount: u64) -> SweepAccount {
+        SweepAccount {
+            address: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            program: spl_token::id(),
+            raw_amount,
+            decimals: 6,
+            frozen: false,
+            withheld_fees: 0,
+            rent_lamports: 2_039_280,
+            transfer_fee: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_item() {
+        let open = HashSet::new();
+        // 10 tokens at $0.5 = $5
+        let item = plan_item(account(10_000_000), Some(0.5), &open, 1.0, false);
+        assert_eq!(item.action, Some(SweepAction::Transfer));
+        assert_eq!(item.value_usd(), Some(5.0));
+
+        let dust = plan_item(account(1_000_000), Some(0.5), &open, 1.0, false);
+        assert_eq!(dust.action, None);
+        let dust = plan_item(account(1_000_000), Some(0.5), &open, 1.0, true);
+        assert_eq!(dust.action, Some(SweepAction::Burn));
+
+        assert_eq!(
+            plan_item(account(1_000_000), None, &open, 1.0, true).action,
+            None
+        );
+
+        let empty = plan_item(account(0), None, &open, 1.0, false);
+        assert_eq!(empty.action, Some(SweepAction::Close));
+        assert!((empty.rent_reclaimed_sol() - 0.00203928).abs() < 1e-9);
+        let mut withheld = account(0);
+        withheld.withheld_fees = 5;
+        assert_eq!(plan_item(withheld, None, &open, 1.0, false).action, None);
+
+        let held = account(10_000_000);
+        let open = HashSet::from([held.mint.to_string()]);
+        assert_eq!(plan_item(held, Some(0.5), &open, 1.0, true).action, None);
+    }
+
+    #[test]
+    fn test_transfer_fee() {
+        // Value is taken after the fee: 10 tokens, 50% fee -> $2.5
+        let mut taxed = account(10_000_000);
+        taxed.transfer_fee = Some(TransferFee {
+            basis_points: 5_000,
+            maximum_fee: u64::MAX,
+        });
+        let item = plan_item(taxed, Some(0.5), &HashSet::new(), 3.0, false);
+        assert_eq!(item.value_usd(), Some(2.5));
+        assert_eq!(item.action, None);
+
+        let fee = TransferFee {
+            basis_points: 100,
+            maximum_fee: 5,
+        };
+        assert_eq!(fee.fee_for(101), 2); // rounded up
+        assert_eq!(fee.fee_for(1_000_000), 5); // capped
+
+        // Mint base state + account type + TransferFeeConfig TLV
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        data.push(ACCOUNT_TYPE_MINT);
+        data.extend_from_slice(&EXTENSION_TRANSFER_FEE_CONFIG.to_le_bytes());
+        data.extend_from_slice(&108u16.to_le_bytes());
+        data.extend_from_slice(&[0u8; 72]);
+        for (epoch, maximum_fee, basis_points) in [(0u64, 10u64, 50u16), (500, 20, 100)] {
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&maximum_fee.to_le_bytes());
+            data.extend_from_slice(&basis_points.to_le_bytes());
+        }
+        assert_eq!(
+            parse_transfer_fee(&data, 499),
+            Some(TransferFee {
+                basis_points: 50,
+                maximum_fee: 10
+            })
+        );
+        assert_eq!(parse_transfer_fee(&data, 500).unwrap().basis_points, 100);
+        assert_eq!(parse_transfer_fee(&data[..ACCOUNT_TYPE_OFFSET], 0), None);
+    }
+}
@@ -4,52 +4,118 @@
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
-    pubkey::Pubkey,
-    signature::{Keypair, Signature},
-    signer::Signer,
-    system_instruction,
-    transaction::Transaction,
+    pubkey::Pubkey, signature::Signature, system_instruction, transaction::Transaction,
 };
 use tracing::{debug, info};
 
 use crate::error::{Error, Result};
 
+use super::safety::confirmation_code_hash;
+use super::signer::{sign_transaction, TxSigner};
+use super::types::{TransferRecord, TransferStatus};
+
 /// Transfer executor for SOL transfers
 pub struct TransferExecutor {
     rpc_client: RpcClient,
+    /// Larger transfers (lamports) are only signed as confirmed staged
+    /// transfers
+    dual_confirm_above: Option<u64>,
 }
 
 impl TransferExecutor {
     /// Create a new transfer executor
     pub fn new(rpc_client: RpcClient) -> Self {
-        Self { rpc_client }
+        Self {
+            rpc_client,
+            dual_confirm_above: None,
+        }
+    }
+
+    /// Require dual confirmation above `amount_sol` (0 = off)
+    pub fn set_dual_confirm_above_sol(&mut self, amount_sol: f64) {
+        self.dual_confirm_above = (amount_sol > 0.0).then(|| sol_to_lamports(amount_sol));
     }
 
     /// Execute a SOL transfer
     ///
     /// # Arguments
-    /// * `from_keypair` - Keypair of the source wallet
+    /// * `from` - Signer of the source wallet (local keypair or remote signer)
     /// * `to_address` - Destination address
     /// * `amount_lamports` - Amount in lamports (1 SOL = 1_000_000_000 lamports)
     ///
     /// # Returns
     /// Transaction signature on success
-    pub fn transfer(
+    pub async fn transfer(
         &self,
-        from_keypair: &Keypair,
+        from: &dyn TxSigner,
+        to_address: &Pubkey,
+        amount_lamports: u64,
+    ) -> Result<Signature> {
+        if self
+            .dual_confirm_above
+            .is_some_and(|limit| amount_lamports > limit)
+        {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Transfer of {} SOL needs dual confirmation",
+                lamports_to_sol(amount_lamports)
+            )));
+        }
+        self.send(from, to_address, amount_lamports).await
+    }
+
+    /// Execute a staged transfer with its one-time confirmation code
+    ///
+    /// Refused unless the record is still pending, unexpired and the code
+    /// matches.
+    pub async fn transfer_confirmed(
+        &self,
+        from: &dyn TxSigner,
+        staged: &TransferRecord,
+        code: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Signature> {
+        let confirmation = staged
+            .dual_confirmation
+            .as_ref()
+            .filter(|_| staged.status == TransferStatus::Pending)
+            .ok_or_else(|| {
+                Error::SafetyLimitExceeded(format!("Transfer {} is not pending", staged.id))
+            })?;
+        if now > confirmation.expires_at {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Transfer {} expired at {}",
+                staged.id, confirmation.expires_at
+            )));
+        }
+        if confirmation_code_hash(code) != confirmation.code_hash {
+            return Err(Error::SafetyLimitExceeded(format!(
+                "Confirmation code does not match transfer {}",
+                staged.id
+            )));
+        }
+        let to_address: Pubkey = confirmation
+            .to_address
+            .parse()
+            .map_err(|e| Error::Config(format!("Invalid staged destination: {}", e)))?;
+        self.send(from, &to_address, sol_to_lamports(staged.amount_sol))
+            .await
+    }
+
+    async fn send(
+        &self,
+        from: &dyn TxSigner,
         to_address: &Pubkey,
         amount_lamports: u64,
     ) -> Result<Signature> {
         debug!(
             "Executing transfer: {} lamports from {} to {}",
             amount_lamports,
-            from_keypair.pubkey(),
+            from.pubkey(),
             to_address
         );
 
         // Create transfer instruction
-        let instruction =
-            system_instruction::transfer(&from_keypair.pubkey(), to_address, amount_lamports);
+        let instruction = system_instruction::transfer(&from.pubkey(), to_address, amount_lamports);
 
         // Get recent blockhash
         let blockhash = self
@@ -58,12 +124,8 @@ impl TransferExecutor {
             .map_err(|e| Error::TransactionBuild(format!("Failed to get blockhash: {}", e)))?;
 
         // Build and sign transaction
-        let transaction = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&from_keypair.pubkey()),
-            &[from_keypair],
-            blockhash,
-        );
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&from.pubkey()));
+        sign_transaction(from, &mut transaction, blockhash).await?;
 
         // Send and confirm transaction
         let signature = self
@@ -80,14 +142,14 @@ impl TransferExecutor {
     }
 
     /// Execute a SOL transfer with amount in SOL
-    pub fn transfer_sol(
+    pub async fn transfer_sol(
         &self,
-        from_keypair: &Keypair,
+        from: &dyn TxSigner,
         to_address: &Pubkey,
         amount_sol: f64,
     ) -> Result<Signature> {
         let amount_lamports = sol_to_lamports(amount_sol);
-        self.transfer(from_keypair, to_address, amount_lamports)
+        self.transfer(from, to_address, amount_lamports).await
     }
 
     /// Get balance of an address in lamports
@@ -103,22 +165,20 @@ impl TransferExecutor {
         Ok(lamports_to_sol(lamports))
     }
 
-    /// Simulate a transfer (dry run)
+    /// Simulate a transfer (dry run; unsigned, so no signer round trip)
     pub fn simulate_transfer(
         &self,
-        from_keypair: &Keypair,
+        from: &Pubkey,
         to_address: &Pubkey,
         amount_lamports: u64,
     ) -> Result<()> {
         debug!(
             "Simulating transfer: {} lamports from {} to {}",
-            amount_lamports,
-            from_keypair.pubkey(),
-            to_address
+            amount_lamports, from, to_address
         );
 
         // Check balance
-        let balance = self.get_balance(&from_keypair.pubkey())?;
+        let balance = self.get_balance(from)?;
         if balance < amount_lamports {
             return Err(Error::TransactionBuild(format!(
                 "Insufficient balance: {} lamports < {} lamports",
@@ -127,20 +187,15 @@ impl TransferExecutor {
         }
 
         // Estimate fee
-        let instruction =
-            system_instruction::transfer(&from_keypair.pubkey(), to_address, amount_lamports);
+        let instruction = system_instruction::transfer(from, to_address, amount_lamports);
 
         let blockhash = self
             .rpc_client
             .get_latest_blockhash()
             .map_err(|e| Error::TransactionBuild(format!("Failed to get blockhash: {}", e)))?;
 
-        let transaction = Transaction::new_signed_with_payer(
-            &[instruction],
-            Some(&from_keypair.pubkey()),
-            &[from_keypair],
-            blockhash,
-        );
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(from));
+        transaction.message.recent_blockhash = blockhash;
 
         // Simulate
         let result = self
@@ -184,4 +239,56 @@ mod tests {
         assert_eq!(lamports_to_sol(500_000_000), 0.5);
         assert_eq!(lamports_to_sol(1_000_000), 0.001);
     }
+
+    #[tokio::test]
+    async fn test_dual_confirmation_refusals() {
+        use super::super::types::{DualConfirmation, InitiatedBy, TransferReason};
+        use chrono::{Duration, Utc};
+        use solana_sdk::signature::Keypair;
+
+        // Every refusal happens before the RPC is touched
+        let mut executor = TransferExecutor::new(RpcClient::new("http://127.0.0.1:1".to_string()));
+        executor.set_dual_confirm_above_sol(2.0);
+        let from = Keypair::new();
+        let to = Pubkey::new_unique();
+        assert!(matches!(
+            executor.transfer_sol(&from, &to, 3.0).await,
+            Err(Error::SafetyLimitExceeded(_))
+        ));
+
+        let now = Utc::now();
+        let mut staged = TransferRecord {
+            id: "t1".to_string(),
+            from_wallet: "hot".to_string(),
+            to_wallet: "vault".to_string(),
+            amount_sol: 3.0,
+            reason: TransferReason::ManualTransfer,
+            signature: String::new(),
+            timestamp: now,
+            initiated_by: InitiatedBy::User,
+            sweep: None,
+            status: TransferStatus::Pending,
+            dual_confirmation: Some(DualConfirmation {
+                code_hash: confirmation_code_hash("ABCD-EFGH-JKMN"),
+                to_address: to.to_string(),
+                vault_unlocked: false,
+                staged_by: "telegram:1001".to_string(),
+                expires_at: now + Duration::minutes(15),
+                confirmed_by: None,
+            }),
+        };
+        let wrong_code = executor
+            .transfer_confirmed(&from, &staged, "ABCD-EFGH-JKMP", now)
+            .await;
+        assert!(matches!(wrong_code, Err(Error::SafetyLimitExceeded(_))));
+        let expired = executor
+            .transfer_confirmed(&from, &staged, "ABCD-EFGH-JKMN", now + Duration::hours(1))
+            .await;
+        assert!(matches!(expired, Err(Error::SafetyLimitExceeded(_))));
+        staged.status = TransferStatus::Cancelled;
+        let cancelled = executor
+            .transfer_confirmed(&from, &staged, "ABCD-EFGH-JKMN", now)
+            .await;
+        assert!(matches!(cancelled, Err(Error::SafetyLimitExceeded(_))));
+    }
 }
@@ -33,6 +33,15 @@ pub struct WalletEntry {
     /// User notes about this wallet
     #[serde(default)]
     pub notes: String,
+
+    /// Most SOL this wallet may spend (buys and transfers) per day
+    /// (None = no cap)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_spend_limit_sol: Option<f64>,
+
+    /// SHA-256 of the keypair file, checked on load
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keypair_sha256: Option<String>,
 }
 
 /// Type of wallet
@@ -130,6 +139,66 @@ pub struct TransferRecord {
 
     /// Who/what initiated this transfer
     pub initiated_by: InitiatedBy,
+
+    /// Token side of a `Sweep` record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sweep: Option<SweepDetail>,
+
+    /// Pending until a staged transfer is confirmed (no signature yet)
+    #[serde(default)]
+    pub status: TransferStatus,
+
+    /// Second confirmation of a staged transfer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dual_confirmation: Option<DualConfirmation>,
+}
+
+/// State of a transfer record
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    /// Sent and confirmed on chain
+    #[default]
+    Completed,
+
+    /// Staged, waiting for its dual confirmation
+    Pending,
+
+    /// Staged but never confirmed (expired)
+    Cancelled,
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferStatus::Completed => write!(f, "completed"),
+            TransferStatus::Pending => write!(f, "pending"),
+            TransferStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// One-time code confirmation of a staged transfer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DualConfirmation {
+    /// SHA-256 of the one-time code (the code itself is never stored)
+    pub code_hash: String,
+
+    /// Destination address pinned at staging
+    pub to_address: String,
+
+    /// Transfer out of the vault unlocked by the user
+    #[serde(default)]
+    pub vault_unlocked: bool,
+
+    /// Notifier identity (`telegram:<user id>`) of the operator who staged it
+    pub staged_by: String,
+
+    pub expires_at: DateTime<Utc>,
+
+    /// Notifier identity of the approver who confirmed it
+    #[serde(default)]
+    pub confirmed_by: Option<String>,
 }
 
 /// Reason for a transfer
@@ -150,6 +219,30 @@ pub enum TransferReason {
 
     /// Funding the hot wallet
     Funding,
+
+    /// Stray token transfer, burn or account close (`snipe wallet sweep`)
+    Sweep,
+}
+
+impl TransferReason {
+    /// Reason recorded for a manual transfer between wallets of these types
+    pub fn for_manual(from: WalletType, to: WalletType) -> Self {
+        match (from, to) {
+            (WalletType::Hot, WalletType::Hot) => TransferReason::Rebalance,
+            (_, WalletType::Hot) => TransferReason::Funding,
+            _ => TransferReason::ManualTransfer,
+        }
+    }
+
+    /// SOL leaving the trading side, counted against the daily extraction cap
+    pub fn is_extraction(&self) -> bool {
+        matches!(
+            self,
+            TransferReason::ProfitExtraction
+                | TransferReason::EmergencyWithdraw
+                | TransferReason::ManualTransfer
+        )
+    }
 }
 
 impl std::fmt::Display for TransferReason {
@@ -160,10 +253,56 @@ impl std::fmt::Display for TransferReason {
             TransferReason::EmergencyWithdraw => write!(f, "Emergency Withdraw"),
             TransferReason::ManualTransfer => write!(f, "Manual Transfer"),
             TransferReason::Funding => write!(f, "Funding"),
+            TransferReason::Sweep => write!(f, "Sweep"),
+        }
+    }
+}
+
+/// What a sweep did with one token account
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepAction {
+    /// Tokens moved to the vault's associated token account
+    Transfer,
+
+    /// Dust burned
+    Burn,
+
+    /// Empty account closed (rent back to the hot wallet)
+    Close,
+}
+
+impl std::fmt::Display for SweepAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepAction::Transfer => write!(f, "transfer"),
+            SweepAction::Burn => write!(f, "burn"),
+            SweepAction::Close => write!(f, "close"),
         }
     }
 }
 
+/// Token details of a sweep record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepDetail {
+    pub action: SweepAction,
+    pub mint: String,
+    pub token_account: String,
+
+    /// Whole tokens moved or burned (0 for a close)
+    pub token_amount: f64,
+
+    /// Token-2022 transfer fee withheld from a transfer, whole tokens
+    #[serde(default)]
+    pub transfer_fee: f64,
+
+    /// DexScreener value at sweep time
+    pub value_usd: Option<f64>,
+
+    /// Rent returned by closing the account
+    pub rent_reclaimed_sol: f64,
+}
+
 /// Who initiated a transfer
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -205,6 +344,60 @@ pub struct TransferHistory {
     pub transfers: Vec<TransferRecord>,
 }
 
+impl TransferHistory {
+    /// Extraction totals for the UTC day of `now`, rebuilt from the records
+    /// (only extractions count: not funding, rebalances or token sweeps,
+    /// nor unsent staged transfers)
+    pub fn daily_stats(&self, now: DateTime<Utc>) -> DailyExtractionStats {
+        let date = now.format("%Y-%m-%d").to_string();
+        let today: Vec<&TransferRecord> = self
+            .transfers
+            .iter()
+            .filter(|t| t.reason.is_extraction())
+            .filter(|t| t.status == TransferStatus::Completed)
+            .filter(|t| t.timestamp.format("%Y-%m-%d").to_string() == date)
+            .collect();
+        DailyExtractionStats {
+            date,
+            total_extracted_sol: today.iter().map(|t| t.amount_sol).sum(),
+            extraction_count: today.len() as u32,
+            last_extraction: today.iter().map(|t| t.timestamp).max(),
+        }
+    }
+
+    /// SOL that left `wallet` through sent transfers timestamped after
+    /// `since` and up to `until`, less what it received (token sweeps move
+    /// no SOL)
+    pub fn net_outflow_sol(&self, wallet: &str, since: DateTime<Utc>, until: DateTime<Utc>) -> f64 {
+        self.transfers
+            .iter()
+            .filter(|t| t.reason != TransferReason::Sweep)
+            .filter(|t| t.status == TransferStatus::Completed)
+            .filter(|t| t.timestamp > since && t.timestamp <= until)
+            .map(|t| {
+                if t.from_wallet == wallet {
+                    t.amount_sol
+                } else if t.to_wallet == wallet {
+                    -t.amount_sol
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+
+    /// Most recent transfer made by an automatic rule (any rule if None)
+    pub fn last_auto(&self, rule: Option<&str>) -> Option<&TransferRecord> {
+        self.transfers
+            .iter()
+            .filter(|t| match &t.initiated_by {
+                InitiatedBy::AutoRule { rule: r } => rule.is_none_or(|rule| rule == r),
+                _ => false,
+            })
+            .max_by_key(|t| t.timestamp)
+    }
+}
+
 /// AI-generated proposal for action
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiProposal {
@@ -231,6 +424,24 @@ pub struct AiProposal {
 
     /// When status last changed
     pub status_updated_at: Option<DateTime<Utc>>,
+
+    /// Still pending after this time = expired
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// What execution did (transfer signature or error)
+    #[serde(default)]
+    pub outcome: Option<String>,
+}
+
+impl AiProposal {
+    /// Check if an open (pending or approved) proposal ran past its expiry
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        matches!(
+            self.status,
+            ProposalStatus::Pending | ProposalStatus::Approved
+        ) && self.expires_at.is_some_and(|at| now >= at)
+    }
 }
 
 /// Action proposed by AI
@@ -287,6 +498,12 @@ pub enum ProposalStatus {
 
     /// Expired (too old)
     Expired,
+
+    /// Approved and carried out by the running bot
+    Executed,
+
+    /// Approved but execution failed (see outcome)
+    Failed,
 }
 
 impl std::fmt::Display for ProposalStatus {
@@ -297,6 +514,8 @@ impl std::fmt::Display for ProposalStatus {
             ProposalStatus::Approved => write!(f, "Approved"),
             ProposalStatus::Rejected => write!(f, "Rejected"),
             ProposalStatus::Expired => write!(f, "Expired"),
+            ProposalStatus::Executed => write!(f, "Executed"),
+            ProposalStatus::Failed => write!(f, "Failed"),
         }
     }
 }
@@ -388,6 +607,8 @@ mod tests {
             address: "AUTO_DERIVED".to_string(),
             created_at: Utc::now(),
             notes: "Test".to_string(),
+            daily_spend_limit_sol: None,
+            keypair_sha256: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -407,4 +628,62 @@ mod tests {
         assert_eq!(stats.total_extracted_sol, 0.0);
         assert_eq!(stats.extraction_count, 0);
     }
+
+    #[test]
+    fn test_manual_transfer_reason() {
+        use WalletType::*;
+        assert_eq!(
+            TransferReason::for_manual(Hot, Hot),
+            TransferReason::Rebalance
+        );
+        assert_eq!(
+            TransferReason::for_manual(Vault, Hot),
+            TransferReason::Funding
+        );
+        assert_eq!(
+            TransferReason::for_manual(Hot, Vault),
+            TransferReason::ManualTransfer
+        );
+    }
+
+    #[test]
+    fn test_daily_stats_counts_extractions_only() {
+        use TransferReason::*;
+        use TransferStatus::{Completed, Pending};
+        let now = Utc::now();
+        let record = |reason, amount_sol, status| TransferRecord {
+            id: format!("{:?}", reason),
+            from_wallet: "hot".to_string(),
+            to_wallet: "vault".to_string(),
+            amount_sol,
+            reason,
+            signature: "sig".to_string(),
+            timestamp: now,
+            initiated_by: InitiatedBy::User,
+            sweep: None,
+            status,
+            dual_confirmation: None,
+        };
+        let history = TransferHistory {
+            transfers: vec![
+                record(ProfitExtraction, 1.0, Completed),
+                record(ManualTransfer, 0.5, Completed),
+                record(EmergencyWithdraw, 0.25, Completed),
+                record(Funding, 2.0, Completed),
+                record(Rebalance, 3.0, Completed),
+                record(Sweep, 4.0, Completed),
+                record(ProfitExtraction, 5.0, Pending),
+            ],
+        };
+
+        let stats = history.daily_stats(now);
+        assert_eq!(stats.total_extracted_sol, 1.75);
+        assert_eq!(stats.extraction_count, 3);
+
+        // Every sent SOL transfer leaves the hot wallet, extraction or not
+        let before = now - chrono::Duration::minutes(1);
+        assert_eq!(history.net_outflow_sol("hot", before, now), 6.75);
+        assert_eq!(history.net_outflow_sol("vault", before, now), -6.75);
+        assert_eq!(history.net_outflow_sol("hot", now, now), 0.0);
+    }
 }